@@ -0,0 +1,10 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How server requests (approvals, user input, dynamic tool calls) for a thread
+ * are routed when several connections are attached to it. Every eligible
+ * connection receives the request and the first response wins.
+ */
+export type ApprovalRouting = "all" | "ownTurns" | "none";
@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type BookmarkAddedEvent = { 
+/**
+ * 1-based turn number, counting user messages.
+ */
+turn: number, 
+/**
+ * First line of the bookmarked cell, identifying it within the turn.
+ */
+excerpt: string, note?: string, };
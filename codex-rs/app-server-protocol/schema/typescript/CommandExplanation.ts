@@ -0,0 +1,18 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Model-written explanation of a command, attached to its approval request
+ * when `[approval_explanations]` is enabled.
+ */
+export type CommandExplanation = { 
+/**
+ * One plain-English paragraph on what the command does.
+ */
+summary: string, 
+/**
+ * Operations that delete, overwrite, or otherwise cannot be undone, e.g.
+ * "deletes the build/ directory". Empty when there are none.
+ */
+destructive_operations: Array<string>, };
@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type CommitCompletedEvent = { success: boolean, 
+/**
+ * SHA of the new commit, when one was created.
+ */
+commit_sha: string | null, 
+/**
+ * Human-readable outcome, e.g. the commit subject or why nothing was committed.
+ */
+message: string | null, };
@@ -0,0 +1,18 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadId } from "./ThreadId";
+
+export type ConversationForkedEvent = { 
+/**
+ * Id of the newly created thread.
+ */
+thread_id: ThreadId, 
+/**
+ * Rollout file of the new thread, when session persistence is enabled.
+ */
+rollout_path: string | null, 
+/**
+ * The user message the fork was cut before (0-based).
+ */
+nth_user_message: number, };
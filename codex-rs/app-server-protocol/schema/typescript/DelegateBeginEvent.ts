@@ -0,0 +1,25 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type DelegateBeginEvent = { 
+/**
+ * Identifier for the delegate tool call.
+ */
+call_id: string, 
+/**
+ * Zero-based index of the task within the tool call.
+ */
+task_index: number, 
+/**
+ * Prompt the sub-session was started with.
+ */
+prompt: string, 
+/**
+ * Model the sub-session runs on.
+ */
+model: string, 
+/**
+ * Configured agent role the sub-session runs as, if any.
+ */
+role: string | null, };
@@ -0,0 +1,34 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AgentStatus } from "./AgentStatus";
+
+export type DelegateEndEvent = { 
+/**
+ * Identifier for the delegate tool call.
+ */
+call_id: string, 
+/**
+ * Zero-based index of the task within the tool call.
+ */
+task_index: number, 
+/**
+ * Prompt the sub-session was started with.
+ */
+prompt: string, 
+/**
+ * Model the sub-session ran on.
+ */
+model: string, 
+/**
+ * Configured agent role the sub-session ran as, if any.
+ */
+role: string | null, 
+/**
+ * Final status of the sub-session. `Completed` carries its last message.
+ */
+status: AgentStatus, 
+/**
+ * Tokens used by the sub-session.
+ */
+total_tokens: bigint, };
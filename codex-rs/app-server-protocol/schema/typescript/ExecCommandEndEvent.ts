@@ -4,6 +4,7 @@
 import type { ExecCommandSource } from "./ExecCommandSource";
 import type { ExecCommandStatus } from "./ExecCommandStatus";
 import type { ParsedCommand } from "./ParsedCommand";
+import type { TestFailure } from "./TestFailure";
 
 export type ExecCommandEndEvent = { 
 /**
@@ -61,4 +62,9 @@ formatted_output: string,
 /**
  * Completion status for this command execution.
  */
-status: ExecCommandStatus, };
+status: ExecCommandStatus, 
+/**
+ * Failing tests found in the output of a failed `cargo test`, `pytest`,
+ * `jest` or `go test` run.
+ */
+test_failures?: Array<TestFailure>, };
@@ -0,0 +1,16 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * SHA-256 digests of a file's content around a patch, as lowercase hex.
+ */
+export type FileContentHashes = { 
+/**
+ * Digest before the patch; absent when the file did not exist.
+ */
+before?: string, 
+/**
+ * Digest after the patch; absent when the patch deletes the file.
+ */
+after?: string, };
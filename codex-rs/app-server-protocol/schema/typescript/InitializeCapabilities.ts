@@ -1,6 +1,7 @@
 // GENERATED CODE! DO NOT MODIFY BY HAND!
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ApprovalRouting } from "./ApprovalRouting";
 
 /**
  * Client-declared capabilities negotiated during initialize.
@@ -14,4 +15,9 @@ experimentalApi: boolean,
  * Exact notification method names that should be suppressed for this
  * connection (for example `codex/event/session_configured`).
  */
-optOutNotificationMethods?: Array<string> | null, };
+optOutNotificationMethods?: Array<string> | null, 
+/**
+ * Which approval and other server requests on shared threads this
+ * connection receives. Defaults to `all`.
+ */
+approvalRouting?: ApprovalRouting | null, };
@@ -2,6 +2,7 @@
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { FileChange } from "./FileChange";
+import type { FileContentHashes } from "./FileContentHashes";
 
 export type PatchApplyBeginEvent = { 
 /**
@@ -20,4 +21,9 @@ auto_approved: boolean,
 /**
  * The changes to be applied.
  */
-changes: { [key in string]?: FileChange }, };
+changes: { [key in string]?: FileChange }, 
+/**
+ * SHA-256 digests of each changed file before and after the patch,
+ * keyed like `changes`.
+ */
+content_hashes?: { [key in string]?: FileContentHashes }, };
@@ -3,4 +3,8 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { StepStatus } from "./StepStatus";
 
-export type PlanItemArg = { step: string, status: StepStatus, };
+export type PlanItemArg = { step: string, status: StepStatus, 
+/**
+ * Assigned by Codex; a step keeps its id across updates while its text is unchanged.
+ */
+id: string | null, };
@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type PullRequestCompletedEvent = { success: boolean, 
+/**
+ * Web URL of the new pull/merge request.
+ */
+url: string | null, 
+/**
+ * Human-readable outcome, e.g. the request title or why it could not be opened.
+ */
+message: string | null, };
@@ -0,0 +1,25 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ReadPrefetchStatsEvent = { 
+/**
+ * `read_file` calls served from the prefetch cache.
+ */
+hits: bigint, 
+/**
+ * `read_file` calls that had to read the file from disk.
+ */
+misses: bigint, 
+/**
+ * Files read into the cache in the background.
+ */
+prefetched_files: bigint, 
+/**
+ * Files in the cache at the end of the turn.
+ */
+cached_files: bigint, 
+/**
+ * Bytes held by the cache at the end of the turn.
+ */
+cached_bytes: bigint, };
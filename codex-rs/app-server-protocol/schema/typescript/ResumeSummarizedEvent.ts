@@ -0,0 +1,17 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ResumeSummarizedEvent = { 
+/**
+ * Summary the model sees in place of the older turns.
+ */
+summary: string, 
+/**
+ * Number of user turns folded into the summary.
+ */
+summarized_turns: number, 
+/**
+ * Number of most recent user turns kept verbatim.
+ */
+kept_turns: number, };
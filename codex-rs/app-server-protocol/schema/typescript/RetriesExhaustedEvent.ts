@@ -0,0 +1,27 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Sent when a model request still fails after all of its stream retries and
+ * `retry_budget.on_exhausted` is `ask`. The turn waits for an
+ * `Op::RetryExhaustedDecision` with this `turn_id`.
+ */
+export type RetriesExhaustedEvent = { turn_id: string, 
+/**
+ * The error from the last attempt.
+ */
+message: string, 
+/**
+ * Retries made after the first attempt failed.
+ */
+retries: bigint, 
+/**
+ * Model offered by `switch_model`, from `retry_budget.fallback_model`.
+ */
+fallback_model: string | null, 
+/**
+ * Describes what `drop_largest_context_item` would remove; `None` when
+ * there is nothing to drop.
+ */
+largest_context_item: string | null, };
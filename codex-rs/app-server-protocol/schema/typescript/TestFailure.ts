@@ -0,0 +1,24 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A failing test reported by a test runner.
+ */
+export type TestFailure = { 
+/**
+ * Test name as the runner prints it.
+ */
+name: string, 
+/**
+ * File the failure was reported in, when the runner names one.
+ */
+file?: string, 
+/**
+ * Line in `file` the failure was reported at.
+ */
+line?: number, 
+/**
+ * First line of the failure message; empty when the runner gave none.
+ */
+message: string, };
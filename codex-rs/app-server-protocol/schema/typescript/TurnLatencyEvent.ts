@@ -0,0 +1,47 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Stage durations are summed across calls, so parallel tool calls (and tools
+ * running while the model streams) can add up to more than `total_ms`.
+ */
+export type TurnLatencyEvent = { turn_id: string, 
+/**
+ * From the turn being submitted to it finishing.
+ */
+total_ms: bigint, 
+/**
+ * From the turn being submitted to its task starting.
+ */
+queue_wait_ms: bigint, 
+/**
+ * From sending each model request to its first streamed event.
+ */
+model_ttfb_ms: bigint, 
+/**
+ * From each response's first streamed event to its last.
+ */
+model_stream_ms: bigint, model_requests: number, 
+/**
+ * Running tool calls, including the sandbox, sidecar and approval time
+ * below.
+ */
+tool_dispatch_ms: bigint, tool_calls: number, 
+/**
+ * The part of `tool_dispatch_ms` spent while a model response was still
+ * streaming, for tool calls started before it finished.
+ */
+tool_stream_overlap_ms: bigint, 
+/**
+ * Preparing sandboxed attempts before the tool runs.
+ */
+sandbox_setup_ms: bigint, 
+/**
+ * Requests to the `py_repl`, `js_repl` and browser sidecar processes.
+ */
+sidecar_round_trip_ms: bigint, sidecar_calls: number, 
+/**
+ * Waiting for the user to answer approval prompts.
+ */
+approval_wait_ms: bigint, approvals: number, };
@@ -0,0 +1,15 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Sent before a model request when the active profile has used up one of
+ * its `[profiles.<name>.quota]` limits. The turn waits for an
+ * `Op::UsageQuotaOverride` with this `turn_id`.
+ */
+export type UsageQuotaExceededEvent = { turn_id: string, profile: string, 
+/**
+ * The limits that were reached, e.g. "2,150,000 of 2,000,000 daily
+ * tokens".
+ */
+message: string, };
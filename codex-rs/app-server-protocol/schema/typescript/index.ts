@@ -17,12 +17,14 @@ export type { AgentStatus } from "./AgentStatus";
 export type { ApplyPatchApprovalParams } from "./ApplyPatchApprovalParams";
 export type { ApplyPatchApprovalRequestEvent } from "./ApplyPatchApprovalRequestEvent";
 export type { ApplyPatchApprovalResponse } from "./ApplyPatchApprovalResponse";
+export type { ApprovalRouting } from "./ApprovalRouting";
 export type { ArchiveConversationParams } from "./ArchiveConversationParams";
 export type { ArchiveConversationResponse } from "./ArchiveConversationResponse";
 export type { AskForApproval } from "./AskForApproval";
 export type { AuthMode } from "./AuthMode";
 export type { AuthStatusChangeNotification } from "./AuthStatusChangeNotification";
 export type { BackgroundEventEvent } from "./BackgroundEventEvent";
+export type { BookmarkAddedEvent } from "./BookmarkAddedEvent";
 export type { ByteRange } from "./ByteRange";
 export type { CallToolResult } from "./CallToolResult";
 export type { CancelLoginChatGptParams } from "./CancelLoginChatGptParams";
@@ -43,13 +45,18 @@ export type { CollabWaitingBeginEvent } from "./CollabWaitingBeginEvent";
 export type { CollabWaitingEndEvent } from "./CollabWaitingEndEvent";
 export type { CollaborationMode } from "./CollaborationMode";
 export type { CollaborationModeMask } from "./CollaborationModeMask";
+export type { CommandExplanation } from "./CommandExplanation";
+export type { CommitCompletedEvent } from "./CommitCompletedEvent";
 export type { ContentItem } from "./ContentItem";
 export type { ContextCompactedEvent } from "./ContextCompactedEvent";
 export type { ContextCompactionItem } from "./ContextCompactionItem";
+export type { ConversationForkedEvent } from "./ConversationForkedEvent";
 export type { ConversationGitInfo } from "./ConversationGitInfo";
 export type { ConversationSummary } from "./ConversationSummary";
 export type { CreditsSnapshot } from "./CreditsSnapshot";
 export type { CustomPrompt } from "./CustomPrompt";
+export type { DelegateBeginEvent } from "./DelegateBeginEvent";
+export type { DelegateEndEvent } from "./DelegateEndEvent";
 export type { DeprecationNoticeEvent } from "./DeprecationNoticeEvent";
 export type { DynamicToolCallRequest } from "./DynamicToolCallRequest";
 export type { ElicitationRequestEvent } from "./ElicitationRequestEvent";
@@ -69,6 +76,7 @@ export type { ExecOutputStream } from "./ExecOutputStream";
 export type { ExecPolicyAmendment } from "./ExecPolicyAmendment";
 export type { ExitedReviewModeEvent } from "./ExitedReviewModeEvent";
 export type { FileChange } from "./FileChange";
+export type { FileContentHashes } from "./FileContentHashes";
 export type { ForcedLoginMethod } from "./ForcedLoginMethod";
 export type { ForkConversationParams } from "./ForkConversationParams";
 export type { ForkConversationResponse } from "./ForkConversationResponse";
@@ -140,10 +148,13 @@ export type { PlanItem } from "./PlanItem";
 export type { PlanItemArg } from "./PlanItemArg";
 export type { PlanType } from "./PlanType";
 export type { Profile } from "./Profile";
+export type { PullRequestCompletedEvent } from "./PullRequestCompletedEvent";
+export type { QueuedUserMessagesUpdatedEvent } from "./QueuedUserMessagesUpdatedEvent";
 export type { RateLimitSnapshot } from "./RateLimitSnapshot";
 export type { RateLimitWindow } from "./RateLimitWindow";
 export type { RawResponseItemEvent } from "./RawResponseItemEvent";
 export type { ReadOnlyAccess } from "./ReadOnlyAccess";
+export type { ReadPrefetchStatsEvent } from "./ReadPrefetchStatsEvent";
 export type { ReasoningContentDeltaEvent } from "./ReasoningContentDeltaEvent";
 export type { ReasoningEffort } from "./ReasoningEffort";
 export type { ReasoningItem } from "./ReasoningItem";
@@ -164,6 +175,8 @@ export type { ResourceTemplate } from "./ResourceTemplate";
 export type { ResponseItem } from "./ResponseItem";
 export type { ResumeConversationParams } from "./ResumeConversationParams";
 export type { ResumeConversationResponse } from "./ResumeConversationResponse";
+export type { ResumeSummarizedEvent } from "./ResumeSummarizedEvent";
+export type { RetriesExhaustedEvent } from "./RetriesExhaustedEvent";
 export type { ReviewCodeLocation } from "./ReviewCodeLocation";
 export type { ReviewDecision } from "./ReviewDecision";
 export type { ReviewFinding } from "./ReviewFinding";
@@ -198,6 +211,7 @@ export type { StepStatus } from "./StepStatus";
 export type { StreamErrorEvent } from "./StreamErrorEvent";
 export type { SubAgentSource } from "./SubAgentSource";
 export type { TerminalInteractionEvent } from "./TerminalInteractionEvent";
+export type { TestFailure } from "./TestFailure";
 export type { TextElement } from "./TextElement";
 export type { ThreadId } from "./ThreadId";
 export type { ThreadNameUpdatedEvent } from "./ThreadNameUpdatedEvent";
@@ -211,11 +225,13 @@ export type { TurnAbortReason } from "./TurnAbortReason";
 export type { TurnAbortedEvent } from "./TurnAbortedEvent";
 export type { TurnCompleteEvent } from "./TurnCompleteEvent";
 export type { TurnDiffEvent } from "./TurnDiffEvent";
+export type { TurnLatencyEvent } from "./TurnLatencyEvent";
 export type { TurnItem } from "./TurnItem";
 export type { TurnStartedEvent } from "./TurnStartedEvent";
 export type { UndoCompletedEvent } from "./UndoCompletedEvent";
 export type { UndoStartedEvent } from "./UndoStartedEvent";
 export type { UpdatePlanArgs } from "./UpdatePlanArgs";
+export type { UsageQuotaExceededEvent } from "./UsageQuotaExceededEvent";
 export type { UserInfoResponse } from "./UserInfoResponse";
 export type { UserInput } from "./UserInput";
 export type { UserMessageEvent } from "./UserMessageEvent";
@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type CommandExecutionExplanation = { 
+/**
+ * One plain-English paragraph on what the command does.
+ */
+summary: string, 
+/**
+ * Operations that delete, overwrite, or otherwise cannot be undone.
+ */
+destructiveOperations: Array<string>, };
@@ -2,6 +2,7 @@
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { CommandAction } from "./CommandAction";
+import type { CommandExecutionExplanation } from "./CommandExecutionExplanation";
 import type { ExecPolicyAmendment } from "./ExecPolicyAmendment";
 
 export type CommandExecutionRequestApprovalParams = { threadId: string, turnId: string, itemId: string, 
@@ -24,4 +25,8 @@ commandActions?: Array<CommandAction> | null,
 /**
  * Optional proposed execpolicy amendment to allow similar commands without prompting.
  */
-proposedExecpolicyAmendment?: ExecPolicyAmendment | null, };
+proposedExecpolicyAmendment?: ExecPolicyAmendment | null, 
+/**
+ * Model-written explanation of the command, when approval explanations are enabled.
+ */
+explanation?: CommandExecutionExplanation | null, };
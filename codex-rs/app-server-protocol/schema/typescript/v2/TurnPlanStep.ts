@@ -3,4 +3,8 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { TurnPlanStepStatus } from "./TurnPlanStepStatus";
 
-export type TurnPlanStep = { step: string, status: TurnPlanStepStatus, };
+export type TurnPlanStep = { step: string, status: TurnPlanStepStatus, 
+/**
+ * Stable across plan updates while the step's text is unchanged.
+ */
+id: string | null, };
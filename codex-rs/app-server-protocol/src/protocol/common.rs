@@ -887,6 +887,7 @@ mod tests {
                         "codex/event/session_configured".to_string(),
                         "item/agentMessage/delta".to_string(),
                     ]),
+                    approval_routing: None,
                 }),
             },
         };
@@ -952,6 +953,7 @@ mod tests {
                             "codex/event/session_configured".to_string(),
                             "item/agentMessage/delta".to_string(),
                         ]),
+                        approval_routing: None,
                     }),
                 },
             }
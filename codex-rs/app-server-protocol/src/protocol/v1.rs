@@ -56,6 +56,27 @@ pub struct InitializeCapabilities {
     /// connection (for example `codex/event/session_configured`).
     #[ts(optional = nullable)]
     pub opt_out_notification_methods: Option<Vec<String>>,
+    /// Which approval and other server requests on shared threads this
+    /// connection receives. Defaults to `all`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional = nullable)]
+    pub approval_routing: Option<ApprovalRouting>,
+}
+
+/// How server requests (approvals, user input, dynamic tool calls) for a thread
+/// are routed when several connections are attached to it. Every eligible
+/// connection receives the request and the first response wins.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub enum ApprovalRouting {
+    /// Receive requests for every turn.
+    #[default]
+    All,
+    /// Receive requests only for turns this connection started, or whose
+    /// starting connection is no longer attached.
+    OwnTurns,
+    /// Never receive requests; observe only.
+    None,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -4,6 +4,7 @@ use std::path::PathBuf;
 use crate::protocol::common::AuthMode;
 use codex_experimental_api_macros::ExperimentalApi;
 use codex_protocol::account::PlanType;
+use codex_protocol::approvals::CommandExplanation as CoreCommandExplanation;
 use codex_protocol::approvals::ExecPolicyAmendment as CoreExecPolicyAmendment;
 use codex_protocol::config_types::CollaborationMode;
 use codex_protocol::config_types::CollaborationModeMask;
@@ -2887,6 +2888,8 @@ pub struct TurnPlanUpdatedNotification {
 pub struct TurnPlanStep {
     pub step: String,
     pub status: TurnPlanStepStatus,
+    /// Stable across plan updates while the step's text is unchanged.
+    pub id: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
@@ -2903,6 +2906,7 @@ impl From<CorePlanItemArg> for TurnPlanStep {
         Self {
             step: value.step,
             status: value.status.into(),
+            id: value.id,
         }
     }
 }
@@ -3097,6 +3101,29 @@ pub struct CommandExecutionRequestApprovalParams {
     /// Optional proposed execpolicy amendment to allow similar commands without prompting.
     #[ts(optional = nullable)]
     pub proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
+    /// Model-written explanation of the command, when approval explanations are enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional = nullable)]
+    pub explanation: Option<CommandExecutionExplanation>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct CommandExecutionExplanation {
+    /// One plain-English paragraph on what the command does.
+    pub summary: String,
+    /// Operations that delete, overwrite, or otherwise cannot be undone.
+    pub destructive_operations: Vec<String>,
+}
+
+impl From<CoreCommandExplanation> for CommandExecutionExplanation {
+    fn from(value: CoreCommandExplanation) -> Self {
+        Self {
+            summary: value.summary,
+            destructive_operations: value.destructive_operations,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -863,6 +863,7 @@ impl CodexClient {
                 capabilities: Some(InitializeCapabilities {
                     experimental_api: true,
                     opt_out_notification_methods: None,
+                    approval_routing: None,
                 }),
             },
         };
@@ -1307,6 +1308,7 @@ impl CodexClient {
             cwd,
             command_actions,
             proposed_execpolicy_amendment,
+            explanation,
         } = params;
 
         println!(
@@ -1329,6 +1331,12 @@ impl CodexClient {
         if let Some(execpolicy_amendment) = proposed_execpolicy_amendment.as_ref() {
             println!("< proposed execpolicy amendment: {execpolicy_amendment:?}");
         }
+        if let Some(explanation) = explanation.as_ref() {
+            println!("< explanation: {}", explanation.summary);
+            for operation in &explanation.destructive_operations {
+                println!("< destructive: {operation}");
+            }
+        }
 
         let response = CommandExecutionRequestApprovalResponse {
             decision: CommandExecutionApprovalDecision::Accept,
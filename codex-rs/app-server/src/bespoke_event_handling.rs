@@ -18,6 +18,7 @@ use codex_app_server_protocol::CollabAgentTool;
 use codex_app_server_protocol::CollabAgentToolCallStatus as V2CollabToolCallStatus;
 use codex_app_server_protocol::CommandAction as V2ParsedCommand;
 use codex_app_server_protocol::CommandExecutionApprovalDecision;
+use codex_app_server_protocol::CommandExecutionExplanation;
 use codex_app_server_protocol::CommandExecutionOutputDeltaNotification;
 use codex_app_server_protocol::CommandExecutionRequestApprovalParams;
 use codex_app_server_protocol::CommandExecutionRequestApprovalResponse;
@@ -206,6 +207,7 @@ pub(crate) async fn apply_bespoke_event_handling(
             reason,
             proposed_execpolicy_amendment,
             parsed_cmd,
+            explanation,
             ..
         }) => match api_version {
             ApiVersion::V1 => {
@@ -246,6 +248,7 @@ pub(crate) async fn apply_bespoke_event_handling(
                     cwd: Some(cwd.clone()),
                     command_actions: Some(command_actions.clone()),
                     proposed_execpolicy_amendment: proposed_execpolicy_amendment_v2,
+                    explanation: explanation.map(CommandExecutionExplanation::from),
                 };
                 let rx = outgoing
                     .send_request(ServerRequestPayload::CommandExecutionRequestApproval(
@@ -1312,6 +1315,8 @@ async fn find_and_remove_turn_summary(
     thread_state: &Arc<Mutex<ThreadState>>,
 ) -> TurnSummary {
     let mut state = thread_state.lock().await;
+    // The turn is over, so another client may start the next one.
+    state.active_turn_owner = None;
     std::mem::take(&mut state.turn_summary)
 }
 
@@ -2172,10 +2177,12 @@ mod tests {
                 PlanItemArg {
                     step: "first".to_string(),
                     status: StepStatus::Pending,
+                    id: None,
                 },
                 PlanItemArg {
                     step: "second".to_string(),
                     status: StepStatus::Completed,
+                    id: None,
                 },
             ],
         };
@@ -20,6 +20,7 @@ use codex_app_server_protocol::AddConversationListenerParams;
 use codex_app_server_protocol::AddConversationSubscriptionResponse;
 use codex_app_server_protocol::AppInfo;
 use codex_app_server_protocol::AppListUpdatedNotification;
+use codex_app_server_protocol::ApprovalRouting;
 use codex_app_server_protocol::AppsListParams;
 use codex_app_server_protocol::AppsListResponse;
 use codex_app_server_protocol::ArchiveConversationParams;
@@ -848,7 +849,7 @@ impl CodexMessageProcessor {
         }
 
         match login_with_api_key(
-            &self.config.codex_home,
+            &self.config.auth_home,
             &params.api_key,
             self.config.cli_auth_credentials_store_mode,
         ) {
@@ -951,7 +952,7 @@ impl CodexMessageProcessor {
         Ok(LoginServerOptions {
             open_browser: false,
             ..LoginServerOptions::new(
-                config.codex_home.clone(),
+                config.auth_home.clone(),
                 CLIENT_ID.to_string(),
                 config.forced_chatgpt_workspace_id.clone(),
                 config.cli_auth_credentials_store_mode,
@@ -1282,7 +1283,7 @@ impl CodexMessageProcessor {
         }
 
         if let Err(err) = login_with_chatgpt_auth_tokens(
-            &self.config.codex_home,
+            &self.config.auth_home,
             &access_token,
             &chatgpt_account_id,
             chatgpt_plan_type.as_deref(),
@@ -2235,6 +2236,8 @@ impl CodexMessageProcessor {
             };
             if !file_name
                 .to_string_lossy()
+                .trim_end_matches(".zst")
+                .trim_end_matches(".enc")
                 .ends_with(required_suffix.as_str())
             {
                 return Err(JSONRPCErrorError {
@@ -2674,6 +2677,15 @@ impl CodexMessageProcessor {
         self.thread_manager.subscribe_thread_created()
     }
 
+    pub(crate) fn set_connection_approval_routing(
+        &mut self,
+        connection_id: ConnectionId,
+        approval_routing: ApprovalRouting,
+    ) {
+        self.thread_state_manager
+            .set_approval_routing(connection_id, approval_routing);
+    }
+
     pub(crate) async fn connection_closed(&mut self, connection_id: ConnectionId) {
         self.thread_state_manager
             .remove_connection(connection_id)
@@ -4568,6 +4580,8 @@ impl CodexMessageProcessor {
         };
         if !file_name
             .to_string_lossy()
+            .trim_end_matches(".zst")
+            .trim_end_matches(".enc")
             .ends_with(required_suffix.as_str())
         {
             return Err(JSONRPCErrorError {
@@ -5177,8 +5191,8 @@ impl CodexMessageProcessor {
         let _ = conversation.submit(Op::Interrupt).await;
     }
 
-    async fn turn_start(&self, request_id: ConnectionRequestId, params: TurnStartParams) {
-        let (_, thread) = match self.load_thread(&params.thread_id).await {
+    async fn turn_start(&mut self, request_id: ConnectionRequestId, params: TurnStartParams) {
+        let (thread_id, thread) = match self.load_thread(&params.thread_id).await {
             Ok(v) => v,
             Err(error) => {
                 self.outgoing.send_error(request_id, error).await;
@@ -5186,6 +5200,29 @@ impl CodexMessageProcessor {
             }
         };
 
+        // Only one attached client drives a turn at a time; the others can steer
+        // it or interrupt it. Holding the lock until the input is submitted keeps
+        // simultaneous `turn/start` calls from both claiming the thread.
+        let thread_state_handle = self.thread_state_manager.thread_state(thread_id);
+        let mut thread_state = thread_state_handle.lock().await;
+        if thread_state
+            .conflicting_turn_owner(request_id.connection_id)
+            .is_some()
+        {
+            drop(thread_state);
+            self.send_invalid_request_error(
+                request_id,
+                format!(
+                    "thread {thread_id} has an active turn started by another client; use turn/steer or turn/interrupt"
+                ),
+            )
+            .await;
+            return;
+        }
+        let previous_owner = thread_state
+            .active_turn_owner
+            .replace(request_id.connection_id);
+
         let collaboration_mode = params
             .collaboration_mode
             .map(|mode| self.normalize_turn_start_collaboration_mode(mode));
@@ -5230,6 +5267,10 @@ impl CodexMessageProcessor {
                 final_output_json_schema: params.output_schema,
             })
             .await;
+        if turn_id.is_err() {
+            thread_state.active_turn_owner = previous_owner;
+        }
+        drop(thread_state);
 
         match turn_id {
             Ok(turn_id) => {
@@ -5737,10 +5778,11 @@ impl CodexMessageProcessor {
                             "conversationId".to_string(),
                             conversation_id.to_string().into(),
                         );
-                        let (subscribed_connection_ids, raw_events_enabled) = {
+                        let (subscribed_connection_ids, approval_connection_ids, raw_events_enabled) = {
                             let thread_state = thread_state.lock().await;
                             (
                                 thread_state.subscribed_connection_ids(),
+                                thread_state.approval_connection_ids(),
                                 thread_state.experimental_raw_events,
                             )
                         };
@@ -5763,7 +5805,8 @@ impl CodexMessageProcessor {
                         let thread_outgoing = ThreadScopedOutgoingMessageSender::new(
                             outgoing_for_task.clone(),
                             subscribed_connection_ids,
-                        );
+                        )
+                        .with_request_connection_ids(approval_connection_ids);
                         apply_bespoke_event_handling(
                             event.clone(),
                             conversation_id,
@@ -6412,7 +6455,10 @@ async fn summary_from_thread_list_item(
 
 fn thread_id_from_rollout_path(path: &Path) -> Option<ThreadId> {
     let file_name = path.file_name()?.to_str()?;
-    let stem = file_name.strip_suffix(".jsonl")?;
+    let stem = file_name
+        .strip_suffix(".jsonl.zst")
+        .or_else(|| file_name.strip_suffix(".jsonl.enc"))
+        .or_else(|| file_name.strip_suffix(".jsonl"))?;
     if stem.len() < 37 {
         return None;
     }
@@ -6967,4 +7013,47 @@ mod tests {
         );
         Ok(())
     }
+
+    #[tokio::test]
+    async fn approval_routing_and_turn_ownership_across_connections() -> Result<()> {
+        let mut manager = ThreadStateManager::new();
+        let thread_id = ThreadId::from_string("ad7f0408-99b8-4f6e-a46f-bd0eec433370")?;
+        let editor = ConnectionId(1);
+        let terminal = ConnectionId(2);
+        let dashboard = ConnectionId(3);
+        manager.set_approval_routing(terminal, ApprovalRouting::OwnTurns);
+        manager.set_approval_routing(dashboard, ApprovalRouting::None);
+        for connection_id in [editor, terminal, dashboard] {
+            manager
+                .ensure_connection_subscribed(thread_id, connection_id, false)
+                .await;
+        }
+        let state = manager.thread_state(thread_id);
+
+        let sorted = |mut ids: Vec<ConnectionId>| {
+            ids.sort_by_key(|id| id.0);
+            ids
+        };
+        {
+            let mut state = state.lock().await;
+            // Nobody owns the turn, so `ownTurns` clients are asked too.
+            assert_eq!(
+                sorted(state.approval_connection_ids()),
+                vec![editor, terminal]
+            );
+            assert_eq!(state.conflicting_turn_owner(terminal), None);
+
+            state.active_turn_owner = Some(editor);
+            assert_eq!(state.approval_connection_ids(), vec![editor]);
+            assert_eq!(state.conflicting_turn_owner(editor), None);
+            assert_eq!(state.conflicting_turn_owner(terminal), Some(editor));
+        }
+
+        // Once the owner leaves, the turn is up for grabs.
+        manager.remove_connection(editor).await;
+        let state = state.lock().await;
+        assert_eq!(state.approval_connection_ids(), vec![terminal]);
+        assert_eq!(state.conflicting_turn_owner(terminal), None);
+        Ok(())
+    }
 }
@@ -0,0 +1,421 @@
+//! `codex serve`: the raw core `Submission`/`Event` protocol over a local
+//! WebSocket, for frontends that want to drive sessions without JSON-RPC.
+//!
+//! Each connection owns one thread. Clients authenticate with the server token
+//! (`Authorization: Bearer <token>` or a `?token=` query parameter), may pass
+//! `?resume=<thread id>` to continue a recorded session, then exchange one JSON
+//! `Submission` or `Event` per text frame. The first event is always
+//! `session_configured`; closing the socket shuts the thread down.
+
+use std::io::Error as IoError;
+use std::io::ErrorKind;
+use std::io::Result as IoResult;
+use std::net::Ipv4Addr;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_core::AuthManager;
+use codex_core::NewThread;
+use codex_core::ThreadManager;
+use codex_core::config::Config;
+use codex_core::config::ConfigOverrides;
+use codex_core::find_thread_path_by_id_str;
+use codex_core::protocol::CodexErrorInfo;
+use codex_core::protocol::ErrorEvent;
+use codex_core::protocol::Event;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::SessionSource;
+use codex_core::protocol::Submission;
+use codex_utils_cli::CliConfigOverrides;
+use futures::SinkExt;
+use futures::StreamExt;
+use owo_colors::OwoColorize;
+use owo_colors::Stream;
+use owo_colors::Style;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+use tokio_tungstenite::accept_hdr_async;
+use tokio_tungstenite::tungstenite::Message as WebSocketMessage;
+use tokio_tungstenite::tungstenite::handshake::server::ErrorResponse;
+use tokio_tungstenite::tungstenite::handshake::server::Request;
+use tokio_tungstenite::tungstenite::handshake::server::Response;
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tokio_tungstenite::tungstenite::http::header::AUTHORIZATION;
+use tracing::error;
+use tracing::info;
+use tracing::warn;
+use tracing_subscriber::EnvFilter;
+use url::form_urlencoded;
+use uuid::Uuid;
+
+/// Default port for `codex serve`.
+pub const DEFAULT_EVENT_STREAM_PORT: u16 = 8765;
+
+/// Token used when `--token` is not given, so it need not appear in `ps`.
+const SERVE_TOKEN_ENV_VAR: &str = "CODEX_SERVE_TOKEN";
+
+#[derive(Debug, Clone)]
+pub struct EventStreamOptions {
+    /// Port to listen on, on the loopback interface. `0` picks a free port.
+    pub port: u16,
+    /// Token clients must present. Falls back to `CODEX_SERVE_TOKEN`, then to
+    /// a random token.
+    pub token: Option<String>,
+}
+
+struct ServerState {
+    config: Config,
+    auth_manager: Arc<AuthManager>,
+    thread_manager: ThreadManager,
+    token: String,
+}
+
+pub async fn run_event_stream_server(
+    codex_linux_sandbox_exe: Option<PathBuf>,
+    cli_config_overrides: CliConfigOverrides,
+    options: EventStreamOptions,
+) -> IoResult<()> {
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_env_filter(EnvFilter::from_default_env())
+        .init();
+
+    let cli_kv_overrides = cli_config_overrides.parse_overrides().map_err(|e| {
+        IoError::new(
+            ErrorKind::InvalidInput,
+            format!("error parsing -c overrides: {e}"),
+        )
+    })?;
+    let config = Config::load_with_cli_overrides_and_harness_overrides(
+        cli_kv_overrides,
+        ConfigOverrides {
+            codex_linux_sandbox_exe,
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(|e| IoError::new(ErrorKind::InvalidData, format!("error loading config: {e}")))?;
+
+    let auth_manager = AuthManager::shared(
+        config.auth_home.clone(),
+        false,
+        config.cli_auth_credentials_store_mode,
+    );
+    // Recorded like other app-server sessions.
+    let thread_manager = ThreadManager::new(
+        config.codex_home.clone(),
+        auth_manager.clone(),
+        SessionSource::Mcp,
+    );
+    let token = options
+        .token
+        .or_else(|| std::env::var(SERVE_TOKEN_ENV_VAR).ok())
+        .filter(|token| !token.is_empty())
+        .unwrap_or_else(|| Uuid::new_v4().simple().to_string());
+    let state = Arc::new(ServerState {
+        config,
+        auth_manager,
+        thread_manager,
+        token,
+    });
+
+    let listener = TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, options.port))).await?;
+    let local_addr = listener.local_addr()?;
+    print_startup_banner(local_addr, &state.token);
+    info!("codex serve listening on ws://{local_addr}");
+
+    loop {
+        match listener.accept().await {
+            Ok((stream, peer_addr)) => {
+                let state = state.clone();
+                tokio::spawn(async move {
+                    run_connection(state, stream, peer_addr).await;
+                });
+            }
+            Err(err) => {
+                error!("failed to accept websocket connection: {err}");
+            }
+        }
+    }
+}
+
+fn colorize(text: &str, style: Style) -> String {
+    text.if_supports_color(Stream::Stderr, |value| value.style(style))
+        .to_string()
+}
+
+#[allow(clippy::print_stderr)]
+fn print_startup_banner(addr: SocketAddr, token: &str) {
+    let title = colorize("codex serve (WebSockets)", Style::new().bold().cyan());
+    let listening_label = colorize("listening on:", Style::new().dimmed());
+    let listen_url = colorize(&format!("ws://{addr}"), Style::new().green());
+    let token_label = colorize("token:", Style::new().dimmed());
+    let note_label = colorize("note:", Style::new().dimmed());
+    eprintln!("{title}");
+    eprintln!("  {listening_label} {listen_url}");
+    eprintln!("  {token_label} {token}");
+    eprintln!(
+        "  {note_label} send `Authorization: Bearer <token>` or connect to ws://{addr}/?token=<token>"
+    );
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ConnectRequest {
+    token: Option<String>,
+    resume: Option<String>,
+}
+
+/// Extract the presented token and the optional `resume` thread id from the
+/// handshake request. The `Authorization` header wins over the query string.
+fn parse_connect_request(request: &Request) -> ConnectRequest {
+    let mut connect = ConnectRequest::default();
+    let query = request.uri().query().unwrap_or_default();
+    for (key, value) in form_urlencoded::parse(query.as_bytes()) {
+        match key.as_ref() {
+            "token" => connect.token = Some(value.into_owned()),
+            "resume" if !value.is_empty() => connect.resume = Some(value.into_owned()),
+            _ => {}
+        }
+    }
+    if let Some(token) = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+    {
+        connect.token = Some(token.trim().to_string());
+    }
+    connect
+}
+
+/// Compares tokens without stopping at the first differing byte, so the
+/// time taken does not reveal how much of the token a client guessed.
+fn token_matches(presented: Option<&str>, expected: &str) -> bool {
+    let Some(presented) = presented else {
+        return false;
+    };
+    presented.len() == expected.len()
+        && presented
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn unauthorized() -> ErrorResponse {
+    let mut response = ErrorResponse::new(Some("invalid or missing token".to_string()));
+    *response.status_mut() = StatusCode::UNAUTHORIZED;
+    response
+}
+
+async fn run_connection(state: Arc<ServerState>, stream: TcpStream, peer_addr: SocketAddr) {
+    let mut resume = None;
+    let callback = |request: &Request, response: Response| {
+        let connect = parse_connect_request(request);
+        if !token_matches(connect.token.as_deref(), &state.token) {
+            return Err(unauthorized());
+        }
+        resume = connect.resume;
+        Ok(response)
+    };
+    let websocket_stream = match accept_hdr_async(stream, callback).await {
+        Ok(stream) => stream,
+        Err(err) => {
+            warn!("rejected websocket connection from {peer_addr}: {err}");
+            return;
+        }
+    };
+    info!("websocket client connected from {peer_addr}");
+    let (mut websocket_writer, mut websocket_reader) = websocket_stream.split();
+
+    let NewThread {
+        thread_id,
+        thread,
+        session_configured,
+    } = match start_thread(&state, resume.as_deref()).await {
+        Ok(new_thread) => new_thread,
+        Err(message) => {
+            let event = error_event(message, None);
+            let _ = send_event(&mut websocket_writer, &event).await;
+            let _ = websocket_writer.close().await;
+            return;
+        }
+    };
+    let configured = Event {
+        id: String::new(),
+        msg: EventMsg::SessionConfigured(session_configured),
+    };
+
+    if send_event(&mut websocket_writer, &configured).await {
+        loop {
+            tokio::select! {
+                event = thread.next_event() => {
+                    let Ok(event) = event else {
+                        break;
+                    };
+                    let shutdown = matches!(event.msg, EventMsg::ShutdownComplete);
+                    if !send_event(&mut websocket_writer, &event).await || shutdown {
+                        break;
+                    }
+                }
+                incoming_message = websocket_reader.next() => {
+                    match incoming_message {
+                        Some(Ok(WebSocketMessage::Text(text))) => {
+                            match serde_json::from_str::<Submission>(&text) {
+                                Ok(submission) => {
+                                    if let Err(err) = thread.submit_with_id(submission).await {
+                                        warn!("failed to submit to thread {thread_id}: {err}");
+                                        break;
+                                    }
+                                }
+                                Err(err) => {
+                                    let event = error_event(
+                                        format!("invalid submission: {err}"),
+                                        Some(CodexErrorInfo::BadRequest),
+                                    );
+                                    if !send_event(&mut websocket_writer, &event).await {
+                                        break;
+                                    }
+                                }
+                            }
+                        }
+                        Some(Ok(WebSocketMessage::Ping(payload))) => {
+                            if websocket_writer.send(WebSocketMessage::Pong(payload)).await.is_err() {
+                                break;
+                            }
+                        }
+                        Some(Ok(WebSocketMessage::Pong(_))) => {}
+                        Some(Ok(WebSocketMessage::Close(_))) | None => break,
+                        Some(Ok(WebSocketMessage::Binary(_))) => {
+                            warn!("dropping unsupported binary websocket message");
+                        }
+                        Some(Ok(WebSocketMessage::Frame(_))) => {}
+                        Some(Err(err)) => {
+                            warn!("websocket receive error: {err}");
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    info!("websocket client {peer_addr} disconnected; shutting down thread {thread_id}");
+    let _ = thread.submit(Op::Shutdown).await;
+    state.thread_manager.remove_thread(&thread_id).await;
+}
+
+async fn start_thread(state: &ServerState, resume: Option<&str>) -> Result<NewThread, String> {
+    let config = state.config.clone();
+    let Some(thread_id) = resume else {
+        return state
+            .thread_manager
+            .start_thread(config)
+            .await
+            .map_err(|err| format!("failed to start thread: {err}"));
+    };
+    let rollout_path = find_thread_path_by_id_str(&config.codex_home, thread_id)
+        .await
+        .map_err(|err| format!("failed to look up thread {thread_id}: {err}"))?
+        .ok_or_else(|| format!("no recorded thread with id {thread_id}"))?;
+    state
+        .thread_manager
+        .resume_thread_from_rollout(config, rollout_path, state.auth_manager.clone())
+        .await
+        .map_err(|err| format!("failed to resume thread {thread_id}: {err}"))
+}
+
+fn error_event(message: String, codex_error_info: Option<CodexErrorInfo>) -> Event {
+    Event {
+        id: String::new(),
+        msg: EventMsg::Error(ErrorEvent {
+            message,
+            codex_error_info,
+        }),
+    }
+}
+
+/// Returns `false` once the client can no longer be written to.
+async fn send_event<S>(writer: &mut S, event: &Event) -> bool
+where
+    S: futures::Sink<WebSocketMessage> + Unpin,
+{
+    let json = match serde_json::to_string(event) {
+        Ok(json) => json,
+        Err(err) => {
+            error!("failed to serialize event: {err}");
+            return true;
+        }
+    };
+    writer
+        .send(WebSocketMessage::Text(json.into()))
+        .await
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn request(uri: &str, authorization: Option<&str>) -> Request {
+        let mut builder = Request::builder().uri(uri);
+        if let Some(authorization) = authorization {
+            builder = builder.header(AUTHORIZATION, authorization);
+        }
+        builder.body(()).expect("request")
+    }
+
+    #[test]
+    fn parse_connect_request_reads_query_parameters() {
+        assert_eq!(
+            parse_connect_request(&request("/?token=secret&resume=abc", None)),
+            ConnectRequest {
+                token: Some("secret".to_string()),
+                resume: Some("abc".to_string()),
+            }
+        );
+        assert_eq!(
+            parse_connect_request(&request("/", None)),
+            ConnectRequest::default()
+        );
+    }
+
+    #[test]
+    fn parse_connect_request_decodes_query_parameters() {
+        assert_eq!(
+            parse_connect_request(&request("/?token=a%2Bb%3Dc&resume=t%2D1", None)),
+            ConnectRequest {
+                token: Some("a+b=c".to_string()),
+                resume: Some("t-1".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn token_matches_only_the_exact_token() {
+        assert_eq!(
+            [
+                token_matches(Some("secret"), "secret"),
+                token_matches(Some("secreT"), "secret"),
+                token_matches(Some("secret2"), "secret"),
+                token_matches(Some(""), "secret"),
+                token_matches(None, "secret"),
+            ],
+            [true, false, false, false, false]
+        );
+    }
+
+    #[test]
+    fn parse_connect_request_prefers_bearer_header() {
+        assert_eq!(
+            parse_connect_request(&request("/?token=stale", Some("Bearer secret"))),
+            ConnectRequest {
+                token: Some("secret".to_string()),
+                resume: None,
+            }
+        );
+    }
+}
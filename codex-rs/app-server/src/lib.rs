@@ -7,6 +7,8 @@ use codex_core::config::ConfigBuilder;
 use codex_core::config_loader::CloudRequirementsLoader;
 use codex_core::config_loader::ConfigLayerStackOrdering;
 use codex_core::config_loader::LoaderOverrides;
+use codex_core::default_client::set_default_client_http_config;
+use codex_core::default_client::set_default_client_offline;
 use codex_utils_cli::CliConfigOverrides;
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -30,6 +32,7 @@ use crate::transport::TransportEvent;
 use crate::transport::route_outgoing_envelope;
 use crate::transport::start_stdio_connection;
 use crate::transport::start_websocket_acceptor;
+use crate::web_dashboard::start_web_acceptor;
 use codex_app_server_protocol::ConfigLayerSource;
 use codex_app_server_protocol::ConfigWarningNotification;
 use codex_app_server_protocol::JSONRPCMessage;
@@ -56,6 +59,7 @@ mod codex_message_processor;
 mod config_api;
 mod dynamic_tools;
 mod error_code;
+mod event_stream;
 mod filters;
 mod fuzzy_file_search;
 mod message_processor;
@@ -63,8 +67,13 @@ mod models;
 mod outgoing_message;
 mod thread_state;
 mod transport;
+mod web_dashboard;
 
+pub use crate::event_stream::DEFAULT_EVENT_STREAM_PORT;
+pub use crate::event_stream::EventStreamOptions;
+pub use crate::event_stream::run_event_stream_server;
 pub use crate::transport::AppServerTransport;
+pub use crate::web_dashboard::DEFAULT_WEB_PORT;
 
 /// Control-plane messages from the processor/transport side to the outbound router task.
 ///
@@ -228,6 +237,7 @@ pub async fn run_main_with_transport(
 
     let mut stdio_handles = Vec::<JoinHandle<()>>::new();
     let mut websocket_accept_handle = None;
+    let shutdown_when_no_connections = matches!(transport, AppServerTransport::Stdio);
     match transport {
         AppServerTransport::Stdio => {
             start_stdio_connection(transport_event_tx.clone(), &mut stdio_handles).await?;
@@ -236,8 +246,14 @@ pub async fn run_main_with_transport(
             websocket_accept_handle =
                 Some(start_websocket_acceptor(bind_address, transport_event_tx.clone()).await?);
         }
+        AppServerTransport::Web {
+            bind_address,
+            token,
+        } => {
+            websocket_accept_handle =
+                Some(start_web_acceptor(bind_address, token, transport_event_tx.clone()).await?);
+        }
     }
-    let shutdown_when_no_connections = matches!(transport, AppServerTransport::Stdio);
 
     // Parse CLI overrides once and derive the base Config eagerly so later
     // components do not need to work with raw TOML values.
@@ -271,16 +287,22 @@ pub async fn run_main_with_transport(
                 }
             }
 
-            let auth_manager = AuthManager::shared(
-                config.codex_home.clone(),
-                false,
-                config.cli_auth_credentials_store_mode,
-            );
-            cloud_requirements_loader(
-                auth_manager,
-                config.chatgpt_base_url,
-                config.codex_home.clone(),
-            )
+            set_default_client_offline(config.offline);
+            set_default_client_http_config(config.http_client.clone());
+            if config.offline {
+                CloudRequirementsLoader::default()
+            } else {
+                let auth_manager = AuthManager::shared(
+                    config.auth_home.clone(),
+                    false,
+                    config.cli_auth_credentials_store_mode,
+                );
+                cloud_requirements_loader(
+                    auth_manager,
+                    config.chatgpt_base_url,
+                    config.codex_home.clone(),
+                )
+            }
         }
         Err(err) => {
             warn!(error = %err, "Failed to preload config for cloud requirements");
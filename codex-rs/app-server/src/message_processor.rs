@@ -13,6 +13,7 @@ use crate::outgoing_message::ConnectionId;
 use crate::outgoing_message::ConnectionRequestId;
 use crate::outgoing_message::OutgoingMessageSender;
 use async_trait::async_trait;
+use codex_app_server_protocol::ApprovalRouting;
 use codex_app_server_protocol::ChatgptAuthTokensRefreshParams;
 use codex_app_server_protocol::ChatgptAuthTokensRefreshReason;
 use codex_app_server_protocol::ChatgptAuthTokensRefreshResponse;
@@ -161,7 +162,7 @@ impl MessageProcessor {
             config_warnings,
         } = args;
         let auth_manager = AuthManager::shared(
-            config.codex_home.clone(),
+            config.auth_home.clone(),
             false,
             config.cli_auth_credentials_store_mode,
         );
@@ -261,19 +262,22 @@ impl MessageProcessor {
                     // shared thread when another connected client did not opt into
                     // experimental API). Proposed direction is instance-global first-write-wins
                     // with initialize-time mismatch rejection.
-                    let (experimental_api_enabled, opt_out_notification_methods) =
+                    let (experimental_api_enabled, opt_out_notification_methods, approval_routing) =
                         match params.capabilities {
                             Some(capabilities) => (
                                 capabilities.experimental_api,
                                 capabilities
                                     .opt_out_notification_methods
                                     .unwrap_or_default(),
+                                capabilities.approval_routing.unwrap_or_default(),
                             ),
-                            None => (false, Vec::new()),
+                            None => (false, Vec::new(), ApprovalRouting::default()),
                         };
                     session.experimental_api_enabled = experimental_api_enabled;
                     session.opted_out_notification_methods =
                         opt_out_notification_methods.into_iter().collect();
+                    self.codex_message_processor
+                        .set_connection_approval_routing(connection_id, approval_routing);
                     let ClientInfo {
                         name,
                         title: _title,
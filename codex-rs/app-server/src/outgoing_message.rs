@@ -55,6 +55,8 @@ pub(crate) struct OutgoingMessageSender {
 pub(crate) struct ThreadScopedOutgoingMessageSender {
     outgoing: Arc<OutgoingMessageSender>,
     connection_ids: Arc<Vec<ConnectionId>>,
+    /// Connections that receive server requests; defaults to `connection_ids`.
+    request_connection_ids: Arc<Vec<ConnectionId>>,
 }
 
 impl ThreadScopedOutgoingMessageSender {
@@ -62,22 +64,34 @@ impl ThreadScopedOutgoingMessageSender {
         outgoing: Arc<OutgoingMessageSender>,
         connection_ids: Vec<ConnectionId>,
     ) -> Self {
+        let connection_ids = Arc::new(connection_ids);
         Self {
             outgoing,
-            connection_ids: Arc::new(connection_ids),
+            request_connection_ids: connection_ids.clone(),
+            connection_ids,
         }
     }
 
+    /// Restrict server requests to the connections whose approval routing
+    /// accepts them. Notifications still reach every subscribed connection.
+    pub(crate) fn with_request_connection_ids(
+        mut self,
+        request_connection_ids: Vec<ConnectionId>,
+    ) -> Self {
+        self.request_connection_ids = Arc::new(request_connection_ids);
+        self
+    }
+
     pub(crate) async fn send_request(
         &self,
         payload: ServerRequestPayload,
     ) -> oneshot::Receiver<ClientRequestResult> {
-        if self.connection_ids.is_empty() {
+        if self.request_connection_ids.is_empty() {
             let (_tx, rx) = oneshot::channel();
             return rx;
         }
         self.outgoing
-            .send_request_to_connections(self.connection_ids.as_slice(), payload)
+            .send_request_to_connections(self.request_connection_ids.as_slice(), payload)
             .await
     }
 
@@ -1,5 +1,6 @@
 use crate::outgoing_message::ConnectionId;
 use crate::outgoing_message::ConnectionRequestId;
+use codex_app_server_protocol::ApprovalRouting;
 use codex_app_server_protocol::TurnError;
 use codex_core::CodexThread;
 use codex_protocol::ThreadId;
@@ -30,8 +31,10 @@ pub(crate) struct ThreadState {
     pub(crate) turn_summary: TurnSummary,
     pub(crate) cancel_tx: Option<oneshot::Sender<()>>,
     pub(crate) experimental_raw_events: bool,
+    /// Connection whose `turn/start` began the running turn, if any.
+    pub(crate) active_turn_owner: Option<ConnectionId>,
     listener_thread: Option<Weak<CodexThread>>,
-    subscribed_connections: HashSet<ConnectionId>,
+    subscribed_connections: HashMap<ConnectionId, ApprovalRouting>,
 }
 
 impl ThreadState {
@@ -60,8 +63,13 @@ impl ThreadState {
         self.listener_thread = None;
     }
 
-    pub(crate) fn add_connection(&mut self, connection_id: ConnectionId) {
-        self.subscribed_connections.insert(connection_id);
+    pub(crate) fn add_connection(
+        &mut self,
+        connection_id: ConnectionId,
+        approval_routing: ApprovalRouting,
+    ) {
+        self.subscribed_connections
+            .insert(connection_id, approval_routing);
     }
 
     pub(crate) fn remove_connection(&mut self, connection_id: ConnectionId) {
@@ -69,7 +77,36 @@ impl ThreadState {
     }
 
     pub(crate) fn subscribed_connection_ids(&self) -> Vec<ConnectionId> {
-        self.subscribed_connections.iter().copied().collect()
+        self.subscribed_connections.keys().copied().collect()
+    }
+
+    /// Subscribed connections whose [`ApprovalRouting`] accepts server requests
+    /// for the running turn.
+    pub(crate) fn approval_connection_ids(&self) -> Vec<ConnectionId> {
+        let owner_attached = self
+            .active_turn_owner
+            .is_some_and(|owner| self.subscribed_connections.contains_key(&owner));
+        self.subscribed_connections
+            .iter()
+            .filter(|(connection_id, routing)| match routing {
+                ApprovalRouting::All => true,
+                ApprovalRouting::OwnTurns => {
+                    !owner_attached || self.active_turn_owner == Some(**connection_id)
+                }
+                ApprovalRouting::None => false,
+            })
+            .map(|(connection_id, _)| *connection_id)
+            .collect()
+    }
+
+    /// Another attached connection that owns the running turn, if any.
+    pub(crate) fn conflicting_turn_owner(
+        &self,
+        connection_id: ConnectionId,
+    ) -> Option<ConnectionId> {
+        self.active_turn_owner.filter(|owner| {
+            *owner != connection_id && self.subscribed_connections.contains_key(owner)
+        })
     }
 
     pub(crate) fn set_experimental_raw_events(&mut self, enabled: bool) {
@@ -88,6 +125,7 @@ pub(crate) struct ThreadStateManager {
     thread_states: HashMap<ThreadId, Arc<Mutex<ThreadState>>>,
     subscription_state_by_id: HashMap<Uuid, SubscriptionState>,
     thread_ids_by_connection: HashMap<ConnectionId, HashSet<ThreadId>>,
+    approval_routing_by_connection: HashMap<ConnectionId, ApprovalRouting>,
 }
 
 impl ThreadStateManager {
@@ -95,6 +133,24 @@ impl ThreadStateManager {
         Self::default()
     }
 
+    /// Record the routing a connection negotiated in `initialize`; applies to
+    /// threads it subscribes to afterwards.
+    pub(crate) fn set_approval_routing(
+        &mut self,
+        connection_id: ConnectionId,
+        approval_routing: ApprovalRouting,
+    ) {
+        self.approval_routing_by_connection
+            .insert(connection_id, approval_routing);
+    }
+
+    fn approval_routing(&self, connection_id: ConnectionId) -> ApprovalRouting {
+        self.approval_routing_by_connection
+            .get(&connection_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
     pub(crate) fn thread_state(&mut self, thread_id: ThreadId) -> Arc<Mutex<ThreadState>> {
         self.thread_states
             .entry(thread_id)
@@ -171,10 +227,11 @@ impl ThreadStateManager {
             .entry(connection_id)
             .or_default()
             .insert(thread_id);
+        let approval_routing = self.approval_routing(connection_id);
         let thread_state = self.thread_state(thread_id);
         {
             let mut thread_state_guard = thread_state.lock().await;
-            thread_state_guard.add_connection(connection_id);
+            thread_state_guard.add_connection(connection_id, approval_routing);
             thread_state_guard.set_experimental_raw_events(experimental_raw_events);
         }
         thread_state
@@ -190,10 +247,11 @@ impl ThreadStateManager {
             .entry(connection_id)
             .or_default()
             .insert(thread_id);
+        let approval_routing = self.approval_routing(connection_id);
         let thread_state = self.thread_state(thread_id);
         {
             let mut thread_state_guard = thread_state.lock().await;
-            thread_state_guard.add_connection(connection_id);
+            thread_state_guard.add_connection(connection_id, approval_routing);
             if experimental_raw_events {
                 thread_state_guard.set_experimental_raw_events(true);
             }
@@ -202,6 +260,7 @@ impl ThreadStateManager {
     }
 
     pub(crate) async fn remove_connection(&mut self, connection_id: ConnectionId) {
+        self.approval_routing_by_connection.remove(&connection_id);
         let Some(thread_ids) = self.thread_ids_by_connection.remove(&connection_id) else {
             return;
         };
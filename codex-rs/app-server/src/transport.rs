@@ -30,6 +30,7 @@ use tokio::net::TcpListener;
 use tokio::net::TcpStream;
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
+use tokio_tungstenite::WebSocketStream;
 use tokio_tungstenite::accept_async;
 use tokio_tungstenite::tungstenite::Message as WebSocketMessage;
 use tracing::debug;
@@ -42,7 +43,7 @@ use tracing::warn;
 /// plenty for an interactive CLI.
 pub(crate) const CHANNEL_CAPACITY: usize = 128;
 
-fn colorize(text: &str, style: Style) -> String {
+pub(crate) fn colorize(text: &str, style: Style) -> String {
     text.if_supports_color(Stream::Stderr, |value| value.style(style))
         .to_string()
 }
@@ -67,15 +68,24 @@ fn print_websocket_startup_banner(addr: SocketAddr) {
 }
 
 #[allow(clippy::print_stderr)]
-fn print_websocket_connection(peer_addr: SocketAddr) {
+pub(crate) fn print_websocket_connection(peer_addr: SocketAddr) {
     let connected_label = colorize("websocket client connected from", Style::new().dimmed());
     eprintln!("{connected_label} {peer_addr}");
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum AppServerTransport {
     Stdio,
-    WebSocket { bind_address: SocketAddr },
+    WebSocket {
+        bind_address: SocketAddr,
+    },
+    /// The `codex web` dashboard plus a token-protected WebSocket on the same port.
+    Web {
+        bind_address: SocketAddr,
+        /// Token the dashboard's WebSocket must present. Falls back to `CODEX_WEB_TOKEN`, then
+        /// to a random token.
+        token: Option<String>,
+    },
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -298,7 +308,15 @@ async fn run_websocket_connection(
             return;
         }
     };
+    run_websocket_session(connection_id, websocket_stream, transport_event_tx).await;
+}
 
+/// Exchanges JSON-RPC messages over an established WebSocket until either side closes it.
+pub(crate) async fn run_websocket_session(
+    connection_id: ConnectionId,
+    websocket_stream: WebSocketStream<TcpStream>,
+    transport_event_tx: mpsc::Sender<TransportEvent>,
+) {
     let (writer_tx, mut writer_rx) = mpsc::channel::<OutgoingMessage>(CHANNEL_CAPACITY);
     let writer_tx_for_reader = writer_tx.clone();
     if transport_event_tx
@@ -0,0 +1,436 @@
+//! `codex web`: a browser dashboard for app-server sessions.
+//!
+//! A single loopback port serves the dashboard's static assets, which are embedded in the binary,
+//! and the app-server JSON-RPC protocol over a WebSocket at `/ws`. The page is plain HTML and
+//! JavaScript with no build step: it lists recorded threads, resumes or starts one, streams its
+//! items live, answers approval requests and shows the turn's diff.
+//!
+//! Any web page can open a WebSocket to localhost, so `/ws` requires the token printed at startup.
+//! Browsers cannot set headers on a WebSocket, so it is passed as `?token=`; the dashboard URL in
+//! the banner carries it and the page forwards it.
+
+use std::io::Error as IoError;
+use std::io::ErrorKind;
+use std::io::Result as IoResult;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+use owo_colors::Style;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::WebSocketStream;
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::protocol::Role;
+use tracing::error;
+use tracing::info;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::outgoing_message::ConnectionId;
+use crate::transport::TransportEvent;
+use crate::transport::colorize;
+use crate::transport::print_websocket_connection;
+use crate::transport::run_websocket_session;
+
+/// Default port for `codex web`.
+pub const DEFAULT_WEB_PORT: u16 = 8766;
+
+/// Token used when `--token` is not given, so it need not appear in `ps`.
+const WEB_TOKEN_ENV_VAR: &str = "CODEX_WEB_TOKEN";
+
+/// Requests are a request line and a few headers; anything larger is not from the dashboard.
+const MAX_REQUEST_HEAD_BYTES: usize = 16 * 1024;
+
+const WEBSOCKET_PATH: &str = "/ws";
+
+/// Embedded assets as `(path, content type, body)`.
+const ASSETS: &[(&str, &str, &str)] = &[
+    (
+        "/",
+        "text/html; charset=utf-8",
+        include_str!("../web/index.html"),
+    ),
+    (
+        "/app.js",
+        "text/javascript; charset=utf-8",
+        include_str!("../web/app.js"),
+    ),
+    (
+        "/app.css",
+        "text/css; charset=utf-8",
+        include_str!("../web/app.css"),
+    ),
+];
+
+pub(crate) async fn start_web_acceptor(
+    bind_address: SocketAddr,
+    token: Option<String>,
+    transport_event_tx: mpsc::Sender<TransportEvent>,
+) -> IoResult<JoinHandle<()>> {
+    let token = token
+        .or_else(|| std::env::var(WEB_TOKEN_ENV_VAR).ok())
+        .filter(|token| !token.is_empty())
+        .unwrap_or_else(|| Uuid::new_v4().simple().to_string());
+    if !token
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || b"-._~".contains(&b))
+    {
+        return Err(IoError::new(
+            ErrorKind::InvalidInput,
+            "the web token may only contain letters, digits, `-`, `.`, `_` and `~`",
+        ));
+    }
+    let listener = TcpListener::bind(bind_address).await?;
+    let local_addr = listener.local_addr()?;
+    print_web_startup_banner(local_addr, &token);
+    info!("codex web listening on http://{local_addr}");
+
+    let token: Arc<str> = token.into();
+    let connection_counter = Arc::new(AtomicU64::new(1));
+    Ok(tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, peer_addr)) => {
+                    let token = token.clone();
+                    let connection_counter = connection_counter.clone();
+                    let transport_event_tx = transport_event_tx.clone();
+                    tokio::spawn(async move {
+                        run_web_connection(
+                            stream,
+                            peer_addr,
+                            &token,
+                            &connection_counter,
+                            transport_event_tx,
+                        )
+                        .await;
+                    });
+                }
+                Err(err) => {
+                    error!("failed to accept web connection: {err}");
+                }
+            }
+        }
+    }))
+}
+
+#[allow(clippy::print_stderr)]
+fn print_web_startup_banner(addr: SocketAddr, token: &str) {
+    let title = colorize("codex web", Style::new().bold().cyan());
+    let open_label = colorize("open:", Style::new().dimmed());
+    let url = colorize(
+        &format!("http://{addr}/?token={token}"),
+        Style::new().green(),
+    );
+    let note_label = colorize("note:", Style::new().dimmed());
+    eprintln!("{title}");
+    eprintln!("  {open_label} {url}");
+    if addr.ip().is_loopback() {
+        eprintln!(
+            "  {note_label} binds localhost only (use SSH port-forwarding to reach a remote run)"
+        );
+    } else {
+        eprintln!(
+            "  {note_label} traffic is unencrypted; put it behind TLS before exposing it beyond localhost"
+        );
+    }
+}
+
+/// Serves one HTTP request, or upgrades it to the app-server WebSocket.
+async fn run_web_connection(
+    mut stream: TcpStream,
+    peer_addr: SocketAddr,
+    token: &str,
+    connection_counter: &AtomicU64,
+    transport_event_tx: mpsc::Sender<TransportEvent>,
+) {
+    let request = match read_request_head(&mut stream).await {
+        Ok(Some(request)) => request,
+        Ok(None) => {
+            let _ = write_status(&mut stream, "400 Bad Request").await;
+            return;
+        }
+        Err(err) => {
+            warn!("failed to read web request from {peer_addr}: {err}");
+            return;
+        }
+    };
+
+    let result = match route(&request, token) {
+        Route::Asset {
+            content_type,
+            body,
+            head_only,
+        } => write_response(&mut stream, "200 OK", content_type, body, head_only).await,
+        Route::Status(status) => write_status(&mut stream, status).await,
+        Route::Upgrade { key } => {
+            if let Err(err) = write_upgrade_response(&mut stream, &key).await {
+                warn!("failed to complete websocket handshake with {peer_addr}: {err}");
+                return;
+            }
+            print_websocket_connection(peer_addr);
+            let connection_id = ConnectionId(connection_counter.fetch_add(1, Ordering::Relaxed));
+            let websocket_stream =
+                WebSocketStream::from_raw_socket(stream, Role::Server, None).await;
+            run_websocket_session(connection_id, websocket_stream, transport_event_tx).await;
+            return;
+        }
+    };
+    if let Err(err) = result {
+        warn!("failed to write web response to {peer_addr}: {err}");
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct RequestHead {
+    method: String,
+    target: String,
+    headers: Vec<(String, String)>,
+}
+
+impl RequestHead {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn path(&self) -> &str {
+        self.target
+            .split_once('?')
+            .map_or(self.target.as_str(), |(path, _)| path)
+    }
+
+    fn query_param(&self, name: &str) -> Option<&str> {
+        let (_, query) = self.target.split_once('?')?;
+        query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+    }
+}
+
+/// Reads up to the blank line that ends the request head. Returns `None` for anything that is
+/// not a well-formed HTTP/1.1 request head.
+async fn read_request_head(stream: &mut TcpStream) -> IoResult<Option<RequestHead>> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 1024];
+    loop {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if let Some(end) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            return Ok(parse_request_head(&buffer[..end]));
+        }
+        if buffer.len() > MAX_REQUEST_HEAD_BYTES {
+            return Ok(None);
+        }
+    }
+}
+
+fn parse_request_head(head: &[u8]) -> Option<RequestHead> {
+    let head = std::str::from_utf8(head).ok()?;
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split(' ');
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?.to_string();
+    if !request_line.next()?.starts_with("HTTP/1.") || !target.starts_with('/') {
+        return None;
+    }
+    let headers = lines
+        .map(|line| {
+            let (name, value) = line.split_once(':')?;
+            Some((name.trim().to_string(), value.trim().to_string()))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(RequestHead {
+        method,
+        target,
+        headers,
+    })
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Route {
+    Asset {
+        content_type: &'static str,
+        body: &'static str,
+        head_only: bool,
+    },
+    Upgrade {
+        key: String,
+    },
+    Status(&'static str),
+}
+
+fn route(request: &RequestHead, token: &str) -> Route {
+    let path = request.path();
+    if path == WEBSOCKET_PATH {
+        let is_upgrade = request
+            .header("upgrade")
+            .is_some_and(|value| value.eq_ignore_ascii_case("websocket"));
+        let key = request.header("sec-websocket-key");
+        return match (is_upgrade, key) {
+            (true, Some(_)) if request.query_param("token") != Some(token) => {
+                Route::Status("401 Unauthorized")
+            }
+            (true, Some(key)) if request.method == "GET" => Route::Upgrade {
+                key: key.to_string(),
+            },
+            _ => Route::Status("400 Bad Request"),
+        };
+    }
+
+    let head_only = match request.method.as_str() {
+        "GET" => false,
+        "HEAD" => true,
+        _ => return Route::Status("405 Method Not Allowed"),
+    };
+    let path = if path == "/index.html" { "/" } else { path };
+    match ASSETS.iter().find(|(asset_path, _, _)| *asset_path == path) {
+        Some((_, content_type, body)) => Route::Asset {
+            content_type,
+            body,
+            head_only,
+        },
+        None => Route::Status("404 Not Found"),
+    }
+}
+
+/// Writes a complete response and closes the connection. `HEAD` responses omit the body.
+async fn write_response(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &str,
+    head_only: bool,
+) -> IoResult<()> {
+    let length = body.len();
+    let body = if head_only { "" } else { body };
+    let response = format!(
+        "HTTP/1.1 {status}\r\n\
+         Content-Type: {content_type}\r\n\
+         Content-Length: {length}\r\n\
+         Cache-Control: no-store\r\n\
+         Referrer-Policy: no-referrer\r\n\
+         X-Content-Type-Options: nosniff\r\n\
+         Content-Security-Policy: default-src 'self'; connect-src 'self' ws: wss:; frame-ancestors 'none'\r\n\
+         Connection: close\r\n\
+         \r\n\
+         {body}"
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Plain-text responses for errors carry their status line as the body.
+async fn write_status(stream: &mut TcpStream, status: &str) -> IoResult<()> {
+    write_response(stream, status, "text/plain; charset=utf-8", status, false).await
+}
+
+async fn write_upgrade_response(stream: &mut TcpStream, key: &str) -> IoResult<()> {
+    let accept = derive_accept_key(key.as_bytes());
+    let response = format!(
+        "HTTP/1.1 101 Switching Protocols\r\n\
+         Upgrade: websocket\r\n\
+         Connection: Upgrade\r\n\
+         Sec-WebSocket-Accept: {accept}\r\n\
+         \r\n"
+    );
+    stream.write_all(response.as_bytes()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn request(method: &str, target: &str, headers: &[(&str, &str)]) -> RequestHead {
+        RequestHead {
+            method: method.to_string(),
+            target: target.to_string(),
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn parses_request_heads() {
+        let head = b"GET /ws?token=abc HTTP/1.1\r\nHost: localhost\r\nUpgrade:  websocket ";
+        assert_eq!(
+            parse_request_head(head),
+            Some(request(
+                "GET",
+                "/ws?token=abc",
+                &[("Host", "localhost"), ("Upgrade", "websocket")]
+            ))
+        );
+        assert_eq!(
+            parse_request_head(b"GET http://example.com/ HTTP/1.1"),
+            None
+        );
+        assert_eq!(parse_request_head(b"GET / SSH-2.0"), None);
+        assert_eq!(parse_request_head(b"GET / HTTP/1.1\r\nno colon"), None);
+    }
+
+    #[test]
+    fn routes_assets_by_path_and_method() {
+        assert!(matches!(
+            route(&request("GET", "/?token=abc", &[]), "abc"),
+            Route::Asset {
+                content_type: "text/html; charset=utf-8",
+                head_only: false,
+                ..
+            }
+        ));
+        assert!(matches!(
+            route(&request("HEAD", "/app.js", &[]), "abc"),
+            Route::Asset {
+                head_only: true,
+                ..
+            }
+        ));
+        assert_eq!(
+            route(&request("GET", "/../Cargo.toml", &[]), "abc"),
+            Route::Status("404 Not Found")
+        );
+        assert_eq!(
+            route(&request("POST", "/", &[]), "abc"),
+            Route::Status("405 Method Not Allowed")
+        );
+    }
+
+    #[test]
+    fn websocket_upgrades_require_the_token() {
+        let headers = [("Upgrade", "WebSocket"), ("Sec-WebSocket-Key", "key")];
+        assert_eq!(
+            route(&request("GET", "/ws?token=abc", &headers), "abc"),
+            Route::Upgrade {
+                key: "key".to_string()
+            }
+        );
+        assert_eq!(
+            route(&request("GET", "/ws?token=abd", &headers), "abc"),
+            Route::Status("401 Unauthorized")
+        );
+        assert_eq!(
+            route(&request("GET", "/ws", &headers), "abc"),
+            Route::Status("401 Unauthorized")
+        );
+        assert_eq!(
+            route(&request("GET", "/ws?token=abc", &[]), "abc"),
+            Route::Status("400 Bad Request")
+        );
+    }
+}
@@ -570,6 +570,7 @@ fn append_rollout_turn_context(path: &Path, timestamp: &str, model: &str) -> std
             collaboration_mode: None,
             effort: None,
             summary: ReasoningSummary::Auto,
+            verbosity: None,
             user_instructions: None,
             developer_instructions: None,
             final_output_json_schema: None,
@@ -1,14 +1,19 @@
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::bail;
+use app_test_support::create_final_assistant_message_sse_response;
 use app_test_support::create_mock_responses_server_sequence_unchecked;
+use app_test_support::create_shell_command_sse_response;
+use codex_app_server_protocol::ApprovalRouting;
 use codex_app_server_protocol::ClientInfo;
+use codex_app_server_protocol::InitializeCapabilities;
 use codex_app_server_protocol::InitializeParams;
 use codex_app_server_protocol::JSONRPCError;
 use codex_app_server_protocol::JSONRPCMessage;
 use codex_app_server_protocol::JSONRPCRequest;
 use codex_app_server_protocol::JSONRPCResponse;
 use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadStartResponse;
 use futures::SinkExt;
 use futures::StreamExt;
 use serde_json::json;
@@ -78,6 +83,110 @@ async fn websocket_transport_routes_per_connection_handshake_and_responses() ->
     Ok(())
 }
 
+#[tokio::test]
+async fn websocket_clients_share_a_thread_with_approval_routing_and_turn_ownership() -> Result<()> {
+    let responses = vec![
+        create_final_assistant_message_sse_response("seeded")?,
+        create_shell_command_sse_response(
+            vec![
+                "python3".to_string(),
+                "-c".to_string(),
+                "print(42)".to_string(),
+            ],
+            None,
+            Some(5000),
+            "call-shared",
+        )?,
+        create_final_assistant_message_sse_response("done")?,
+    ];
+    let server = create_mock_responses_server_sequence_unchecked(responses).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), "untrusted")?;
+
+    let bind_addr = reserve_local_addr()?;
+    let mut process = spawn_websocket_server(codex_home.path(), bind_addr).await?;
+
+    let mut editor = connect_websocket(bind_addr).await?;
+    let mut dashboard = connect_websocket(bind_addr).await?;
+    send_initialize_request(&mut editor, 1, "ws_editor").await?;
+    read_response_for_id(&mut editor, 1).await?;
+    send_initialize_request_with_capabilities(
+        &mut dashboard,
+        1,
+        "ws_dashboard",
+        InitializeCapabilities {
+            approval_routing: Some(ApprovalRouting::None),
+            ..Default::default()
+        },
+    )
+    .await?;
+    read_response_for_id(&mut dashboard, 1).await?;
+
+    send_request(
+        &mut editor,
+        "thread/start",
+        2,
+        Some(json!({ "model": "mock-model" })),
+    )
+    .await?;
+    let ThreadStartResponse { thread, .. } =
+        serde_json::from_value(read_response_for_id(&mut editor, 2).await?.result)?;
+    send_turn_start_request(&mut editor, 3, &thread.id, "seed history").await?;
+    read_response_for_id(&mut editor, 3).await?;
+    read_notification(&mut editor, "turn/completed").await?;
+
+    send_request(
+        &mut dashboard,
+        "thread/resume",
+        2,
+        Some(json!({ "threadId": thread.id })),
+    )
+    .await?;
+    read_response_for_id(&mut dashboard, 2).await?;
+
+    send_turn_start_request(&mut editor, 4, &thread.id, "run python").await?;
+    read_response_for_id(&mut editor, 4).await?;
+    let approval = read_request(&mut editor, "item/commandExecution/requestApproval").await?;
+
+    // The dashboard observes the turn but neither gets the approval nor may
+    // start a competing turn.
+    send_turn_start_request(&mut dashboard, 3, &thread.id, "my turn").await?;
+    let target_id = RequestId::Integer(3);
+    let conflict = loop {
+        match read_jsonrpc_message(&mut dashboard).await? {
+            JSONRPCMessage::Request(request) => {
+                bail!("observer received server request {}", request.method)
+            }
+            JSONRPCMessage::Error(err) if err.id == target_id => break err,
+            _ => {}
+        }
+    };
+    assert!(
+        conflict
+            .error
+            .message
+            .contains("has an active turn started by another client"),
+        "unexpected error: {}",
+        conflict.error.message
+    );
+
+    send_jsonrpc(
+        &mut editor,
+        JSONRPCMessage::Response(JSONRPCResponse {
+            id: approval.id,
+            result: json!({ "decision": "decline" }),
+        }),
+    )
+    .await?;
+    read_notification(&mut editor, "turn/completed").await?;
+
+    process
+        .kill()
+        .await
+        .context("failed to stop websocket app-server process")?;
+    Ok(())
+}
+
 async fn spawn_websocket_server(codex_home: &Path, bind_addr: SocketAddr) -> Result<Child> {
     let program = codex_utils_cargo_bin::cargo_bin("codex-app-server")
         .context("should find app-server binary")?;
@@ -130,13 +239,31 @@ async fn connect_websocket(bind_addr: SocketAddr) -> Result<WsClient> {
 }
 
 async fn send_initialize_request(stream: &mut WsClient, id: i64, client_name: &str) -> Result<()> {
+    send_initialize_params(stream, id, client_name, None).await
+}
+
+async fn send_initialize_request_with_capabilities(
+    stream: &mut WsClient,
+    id: i64,
+    client_name: &str,
+    capabilities: InitializeCapabilities,
+) -> Result<()> {
+    send_initialize_params(stream, id, client_name, Some(capabilities)).await
+}
+
+async fn send_initialize_params(
+    stream: &mut WsClient,
+    id: i64,
+    client_name: &str,
+    capabilities: Option<InitializeCapabilities>,
+) -> Result<()> {
     let params = InitializeParams {
         client_info: ClientInfo {
             name: client_name.to_string(),
             title: Some("WebSocket Test Client".to_string()),
             version: "0.1.0".to_string(),
         },
-        capabilities: None,
+        capabilities,
     };
     send_request(
         stream,
@@ -157,6 +284,24 @@ async fn send_config_read_request(stream: &mut WsClient, id: i64) -> Result<()>
     .await
 }
 
+async fn send_turn_start_request(
+    stream: &mut WsClient,
+    id: i64,
+    thread_id: &str,
+    text: &str,
+) -> Result<()> {
+    send_request(
+        stream,
+        "turn/start",
+        id,
+        Some(json!({
+            "threadId": thread_id,
+            "input": [{ "type": "text", "text": text }],
+        })),
+    )
+    .await
+}
+
 async fn send_request(
     stream: &mut WsClient,
     method: &str,
@@ -203,6 +348,26 @@ async fn read_error_for_id(stream: &mut WsClient, id: i64) -> Result<JSONRPCErro
     }
 }
 
+async fn read_notification(stream: &mut WsClient, method: &str) -> Result<()> {
+    loop {
+        if let JSONRPCMessage::Notification(notification) = read_jsonrpc_message(stream).await?
+            && notification.method == method
+        {
+            return Ok(());
+        }
+    }
+}
+
+async fn read_request(stream: &mut WsClient, method: &str) -> Result<JSONRPCRequest> {
+    loop {
+        if let JSONRPCMessage::Request(request) = read_jsonrpc_message(stream).await?
+            && request.method == method
+        {
+            return Ok(request);
+        }
+    }
+}
+
 async fn read_jsonrpc_message(stream: &mut WsClient) -> Result<JSONRPCMessage> {
     loop {
         let frame = timeout(DEFAULT_READ_TIMEOUT, stream.next())
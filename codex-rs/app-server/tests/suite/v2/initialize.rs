@@ -134,6 +134,7 @@ async fn initialize_opt_out_notification_methods_filters_notifications() -> Resu
                     "thread/started".to_string(),
                     "codex/event/session_configured".to_string(),
                 ]),
+                approval_routing: None,
             }),
         ),
     )
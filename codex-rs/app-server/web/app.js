@@ -0,0 +1,538 @@
+// Codex web dashboard: a thin client for the app-server JSON-RPC protocol over a WebSocket.
+//
+// The server only accepts the socket with the token it printed at startup. The dashboard URL
+// carries it as `?token=`; it is moved to session storage and dropped from the address bar so
+// it does not end up in bookmarks or screenshots.
+"use strict";
+
+const TOKEN_KEY = "codex-web-token";
+const RECONNECT_DELAY_MS = 2000;
+
+const params = new URLSearchParams(location.search);
+if (params.has("token")) {
+  sessionStorage.setItem(TOKEN_KEY, params.get("token"));
+  history.replaceState(null, "", location.pathname);
+}
+const token = sessionStorage.getItem(TOKEN_KEY) ?? "";
+
+const $ = (id) => document.getElementById(id);
+
+const state = {
+  socket: null,
+  nextRequestId: 1,
+  // Request id -> { resolve, reject } for requests awaiting a response.
+  pending: new Map(),
+  threadId: null,
+  turnId: null,
+  // Item id -> { item, element } for the selected thread.
+  items: new Map(),
+  // Server request id -> approval card.
+  approvals: new Map(),
+};
+
+function connect() {
+  const scheme = location.protocol === "https:" ? "wss:" : "ws:";
+  const url = `${scheme}//${location.host}/ws?token=${encodeURIComponent(token)}`;
+  const socket = new WebSocket(url);
+  state.socket = socket;
+  setConnection("connecting…");
+
+  socket.addEventListener("open", async () => {
+    try {
+      await request("initialize", {
+        clientInfo: { name: "codex_web", title: "Codex Web", version: "0.0.0" },
+      });
+      notify("initialized");
+      setConnection("connected");
+      setControlsEnabled(true);
+      await loadThreads();
+      if (state.threadId) {
+        await openThread(state.threadId);
+      }
+    } catch (err) {
+      showError(err);
+    }
+  });
+  socket.addEventListener("message", (event) => {
+    let message;
+    try {
+      message = JSON.parse(event.data);
+    } catch (err) {
+      console.error("invalid message from server", err);
+      return;
+    }
+    handleMessage(message);
+  });
+  socket.addEventListener("close", () => {
+    for (const { reject } of state.pending.values()) {
+      reject(new Error("disconnected"));
+    }
+    state.pending.clear();
+    for (const card of state.approvals.values()) {
+      card.remove();
+    }
+    state.approvals.clear();
+    setControlsEnabled(false);
+    setConnection(
+      token
+        ? "disconnected, retrying…"
+        : "disconnected: open the URL printed by `codex web`, including its token",
+    );
+    setTimeout(connect, RECONNECT_DELAY_MS);
+  });
+}
+
+function send(message) {
+  state.socket?.send(JSON.stringify(message));
+}
+
+function request(method, params) {
+  const id = state.nextRequestId++;
+  return new Promise((resolve, reject) => {
+    state.pending.set(id, { resolve, reject });
+    send({ id, method, params });
+  });
+}
+
+function notify(method, params) {
+  send(params === undefined ? { method } : { method, params });
+}
+
+function respond(id, result) {
+  send({ id, result });
+}
+
+function handleMessage(message) {
+  const isRequest = "method" in message && "id" in message;
+  if (isRequest) {
+    handleServerRequest(message);
+  } else if ("method" in message) {
+    handleNotification(message.method, message.params ?? {});
+  } else if (state.pending.has(message.id)) {
+    const { resolve, reject } = state.pending.get(message.id);
+    state.pending.delete(message.id);
+    if ("error" in message) {
+      reject(new Error(message.error.message));
+    } else {
+      resolve(message.result);
+    }
+  }
+}
+
+// Sessions
+
+async function loadThreads() {
+  const result = await request("thread/list", { limit: 50, sortKey: "updated_at" });
+  const list = $("threads");
+  list.replaceChildren();
+  for (const thread of result.data) {
+    list.append(threadEntry(thread));
+  }
+  markSelectedThread();
+}
+
+function threadEntry(thread) {
+  const entry = document.createElement("li");
+  entry.dataset.threadId = thread.id;
+  const preview = document.createElement("span");
+  preview.className = "preview";
+  preview.textContent = thread.preview || "(no messages yet)";
+  const meta = document.createElement("span");
+  meta.className = "meta";
+  meta.textContent = `${formatTime(thread.updatedAt)} · ${thread.cwd}`;
+  meta.title = thread.cwd;
+  entry.append(preview, meta);
+  entry.addEventListener("click", () => openThread(thread.id).catch(showError));
+  return entry;
+}
+
+function addThread(thread) {
+  const list = $("threads");
+  list.querySelector(`li[data-thread-id="${CSS.escape(thread.id)}"]`)?.remove();
+  list.prepend(threadEntry(thread));
+  markSelectedThread();
+}
+
+function markSelectedThread() {
+  for (const entry of $("threads").children) {
+    entry.classList.toggle("selected", entry.dataset.threadId === state.threadId);
+  }
+}
+
+async function openThread(threadId) {
+  const result = await request("thread/resume", { threadId });
+  showThread(result.thread);
+  for (const turn of result.thread.turns) {
+    for (const item of turn.items) {
+      upsertItem(item);
+    }
+    if (turn.status === "inProgress") {
+      state.turnId = turn.id;
+    }
+  }
+  setTurnStatus(state.turnId ? "working…" : "");
+}
+
+async function newThread() {
+  const result = await request("thread/start", {});
+  showThread(result.thread);
+  addThread(result.thread);
+  $("prompt").focus();
+}
+
+function showThread(thread) {
+  state.threadId = thread.id;
+  state.turnId = null;
+  state.items.clear();
+  $("transcript").replaceChildren();
+  $("thread-title").textContent = thread.preview || "New session";
+  $("thread-cwd").textContent = thread.cwd;
+  renderDiff("");
+  markSelectedThread();
+  updateComposer();
+}
+
+function formatTime(seconds) {
+  return new Date(seconds * 1000).toLocaleString();
+}
+
+// Turns
+
+async function sendPrompt() {
+  const prompt = $("prompt");
+  const text = prompt.value.trim();
+  if (!text || !state.threadId) {
+    return;
+  }
+  prompt.value = "";
+  try {
+    const result = await request("turn/start", {
+      threadId: state.threadId,
+      input: [{ type: "text", text }],
+    });
+    state.turnId = result.turn.id;
+    setTurnStatus("working…");
+  } catch (err) {
+    prompt.value = text;
+    showError(err);
+  }
+}
+
+async function interruptTurn() {
+  if (state.threadId && state.turnId) {
+    await request("turn/interrupt", { threadId: state.threadId, turnId: state.turnId });
+  }
+}
+
+function handleNotification(method, params) {
+  if (method === "thread/started") {
+    addThread(params.thread);
+    return;
+  }
+  if (params.threadId !== state.threadId) {
+    return;
+  }
+  switch (method) {
+    case "turn/started":
+      state.turnId = params.turn.id;
+      setTurnStatus("working…");
+      renderDiff("");
+      break;
+    case "turn/completed":
+      state.turnId = null;
+      setTurnStatus(params.turn.status === "completed" ? "" : params.turn.status);
+      if (params.turn.error) {
+        showError(new Error(params.turn.error.message));
+      }
+      break;
+    case "turn/diff/updated":
+      renderDiff(params.diff);
+      break;
+    case "item/started":
+    case "item/completed":
+      upsertItem(params.item);
+      break;
+    case "item/agentMessage/delta":
+    case "item/plan/delta":
+    case "item/reasoning/summaryTextDelta":
+      appendToItem(params.itemId, ".body", params.delta);
+      break;
+    case "item/commandExecution/outputDelta":
+      appendToItem(params.itemId, "pre", params.delta);
+      break;
+    case "error":
+      if (!params.willRetry) {
+        showError(new Error(params.error.message));
+      }
+      break;
+  }
+}
+
+// Transcript
+
+function upsertItem(item) {
+  const element = renderItem(item);
+  const existing = state.items.get(item.id);
+  if (existing) {
+    existing.element.replaceWith(element);
+  } else {
+    const transcript = $("transcript");
+    const atBottom =
+      transcript.scrollHeight - transcript.scrollTop - transcript.clientHeight < 40;
+    transcript.append(element);
+    if (atBottom) {
+      transcript.scrollTop = transcript.scrollHeight;
+    }
+  }
+  state.items.set(item.id, { item, element });
+}
+
+function appendToItem(itemId, selector, delta) {
+  const element = state.items.get(itemId)?.element.querySelector(selector);
+  if (element) {
+    element.textContent += delta;
+  }
+}
+
+function renderItem(item) {
+  const element = document.createElement("div");
+  element.className = `item ${item.type}`;
+  const label = document.createElement("span");
+  label.className = "label";
+  const body = document.createElement("div");
+  body.className = "body";
+  element.append(label, body);
+
+  switch (item.type) {
+    case "userMessage":
+      label.textContent = "You";
+      body.textContent = item.content
+        .filter((input) => input.type === "text")
+        .map((input) => input.text)
+        .join("\n");
+      break;
+    case "agentMessage":
+      label.textContent = "Codex";
+      body.textContent = item.text;
+      break;
+    case "plan":
+      label.textContent = "Plan";
+      body.textContent = item.text;
+      break;
+    case "reasoning":
+      label.textContent = "Thinking";
+      body.textContent = item.summary.join("\n");
+      break;
+    case "commandExecution": {
+      label.textContent = `${statusLabel(item.status)} · exit ${item.exitCode ?? "–"}`;
+      body.textContent = `$ ${item.command}`;
+      const output = document.createElement("pre");
+      output.textContent = item.aggregatedOutput ?? "";
+      element.append(output);
+      element.classList.add(item.status);
+      break;
+    }
+    case "fileChange": {
+      const count = item.changes.length;
+      label.textContent = `${statusLabel(item.status)} · ${count} ${count === 1 ? "file" : "files"}`;
+      body.textContent = item.changes.map((change) => change.path).join("\n");
+      element.append(diffView(item.changes.map(changeDiff).join("\n")));
+      element.classList.add(item.status);
+      break;
+    }
+    case "mcpToolCall":
+      label.textContent = `${statusLabel(item.status)} · tool`;
+      body.textContent = `${item.server}.${item.tool}`;
+      if (item.error) {
+        body.textContent += `\n${item.error.message}`;
+      }
+      break;
+    case "webSearch":
+      label.textContent = "Web search";
+      body.textContent = item.query;
+      break;
+    case "contextCompaction":
+      label.textContent = "Context compacted";
+      break;
+    default:
+      label.textContent = item.type;
+  }
+  return element;
+}
+
+function statusLabel(status) {
+  return { inProgress: "running", completed: "done" }[status] ?? status;
+}
+
+function changeDiff(change) {
+  if (change.diff.startsWith("diff ") || change.diff.startsWith("---")) {
+    return change.diff;
+  }
+  return `--- ${change.path}\n+++ ${change.path}\n${change.diff}`;
+}
+
+// Diff review
+
+function renderDiff(diff) {
+  $("diff-summary").textContent = diff ? diffSummary(diff) : "No changes in this turn";
+  $("diff").replaceWith(Object.assign(diffView(diff), { id: "diff" }));
+}
+
+function diffSummary(diff) {
+  let files = 0;
+  let added = 0;
+  let removed = 0;
+  for (const line of diff.split("\n")) {
+    if (line.startsWith("diff --git")) {
+      files++;
+    } else if (line.startsWith("+") && !line.startsWith("+++")) {
+      added++;
+    } else if (line.startsWith("-") && !line.startsWith("---")) {
+      removed++;
+    }
+  }
+  return `${files} ${files === 1 ? "file" : "files"} · +${added} −${removed}`;
+}
+
+function diffView(diff) {
+  const view = document.createElement("pre");
+  view.className = "diff";
+  for (const line of diff.split("\n")) {
+    const row = document.createElement("span");
+    if (/^(diff |index |\+\+\+|---)/.test(line)) {
+      row.className = "file";
+    } else if (line.startsWith("@@")) {
+      row.className = "hunk";
+    } else if (line.startsWith("+")) {
+      row.className = "add";
+    } else if (line.startsWith("-")) {
+      row.className = "del";
+    }
+    row.textContent = `${line}\n`;
+    view.append(row);
+  }
+  return view;
+}
+
+// Approvals
+
+const APPROVAL_DECISIONS = [
+  ["accept", "Approve", true],
+  ["acceptForSession", "Approve for session", false],
+  ["decline", "Decline", false],
+  ["cancel", "Decline and stop", false],
+];
+
+function handleServerRequest(message) {
+  const { id, method, params } = message;
+  switch (method) {
+    case "item/commandExecution/requestApproval": {
+      const command = document.createElement("pre");
+      command.textContent = `$ ${params.command ?? "(command unavailable)"}`;
+      const details = [params.reason, params.cwd && `in ${params.cwd}`].filter(Boolean);
+      showApproval(id, params, "Run this command?", details, command);
+      break;
+    }
+    case "item/fileChange/requestApproval": {
+      const item = state.items.get(params.itemId)?.item;
+      const changes = item?.type === "fileChange" ? item.changes : [];
+      const diff = diffView(changes.map(changeDiff).join("\n"));
+      const details = [params.reason, params.grantRoot && `grant write access to ${params.grantRoot}`];
+      showApproval(id, params, "Apply these changes?", details.filter(Boolean), diff);
+      break;
+    }
+    default:
+      send({
+        id,
+        error: { code: -32601, message: `codex web does not support ${method}` },
+      });
+  }
+}
+
+function showApproval(id, params, title, details, content) {
+  const card = document.createElement("div");
+  card.className = "approval";
+  const heading = document.createElement("strong");
+  heading.textContent = title;
+  card.append(heading);
+  if (params.threadId !== state.threadId) {
+    const other = document.createElement("button");
+    other.type = "button";
+    other.textContent = "Open session";
+    other.addEventListener("click", () => openThread(params.threadId).catch(showError));
+    card.append(" (another session) ", other);
+  }
+  for (const detail of details) {
+    const line = document.createElement("div");
+    line.className = "muted";
+    line.textContent = detail;
+    card.append(line);
+  }
+  card.append(content);
+
+  const actions = document.createElement("div");
+  actions.className = "actions";
+  for (const [decision, text, primary] of APPROVAL_DECISIONS) {
+    const button = document.createElement("button");
+    button.type = "button";
+    button.textContent = text;
+    button.classList.toggle("primary", primary);
+    button.addEventListener("click", () => {
+      respond(id, { decision });
+      card.remove();
+      state.approvals.delete(id);
+    });
+    actions.append(button);
+  }
+  card.append(actions);
+  state.approvals.set(id, card);
+  $("approvals").append(card);
+}
+
+// Chrome
+
+function setConnection(text) {
+  $("connection").textContent = text;
+}
+
+function setTurnStatus(text) {
+  $("turn-status").textContent = text;
+  updateComposer();
+}
+
+function setControlsEnabled(enabled) {
+  $("new-thread").disabled = !enabled;
+  $("refresh-threads").disabled = !enabled;
+  updateComposer();
+}
+
+function updateComposer() {
+  const connected = state.socket?.readyState === WebSocket.OPEN;
+  const ready = connected && state.threadId !== null;
+  $("prompt").disabled = !ready;
+  $("send").disabled = !ready || state.turnId !== null;
+  $("interrupt").disabled = !ready || state.turnId === null;
+}
+
+function showError(err) {
+  const element = document.createElement("div");
+  element.className = "item error";
+  element.textContent = err.message ?? String(err);
+  $("transcript").append(element);
+}
+
+$("new-thread").addEventListener("click", () => newThread().catch(showError));
+$("refresh-threads").addEventListener("click", () => loadThreads().catch(showError));
+$("interrupt").addEventListener("click", () => interruptTurn().catch(showError));
+$("composer").addEventListener("submit", (event) => {
+  event.preventDefault();
+  sendPrompt();
+});
+$("prompt").addEventListener("keydown", (event) => {
+  if (event.key === "Enter" && (event.ctrlKey || event.metaKey)) {
+    event.preventDefault();
+    sendPrompt();
+  }
+});
+
+connect();
@@ -219,6 +219,27 @@ impl Turn {
     fn error_summary(&self) -> Option<String> {
         self.error.as_ref().and_then(TurnError::summary)
     }
+
+    fn worklog_lines(&self) -> Vec<String> {
+        let Some(log) = &self.worklog else {
+            return Vec::new();
+        };
+        log.messages
+            .iter()
+            .flat_map(|message| {
+                let role = message
+                    .author
+                    .as_ref()
+                    .and_then(|a| a.role.as_deref())
+                    .unwrap_or("system")
+                    .to_string();
+                message
+                    .text_values()
+                    .into_iter()
+                    .map(move |text| format!("{role}: {text}"))
+            })
+            .collect()
+    }
 }
 
 impl WorklogMessage {
@@ -266,6 +287,10 @@ pub trait CodeTaskDetailsResponseExt {
     fn user_text_prompt(&self) -> Option<String>;
     /// Extract an assistant error message (if the turn failed and provided one).
     fn assistant_error_message(&self) -> Option<String>;
+    /// Extract every worklog entry of the assistant turn as `role: text` lines.
+    fn assistant_worklog_lines(&self) -> Vec<String>;
+    /// The raw status of the current assistant turn, when reported.
+    fn assistant_turn_status(&self) -> Option<&str>;
 }
 
 impl CodeTaskDetailsResponseExt for CodeTaskDetailsResponse {
@@ -302,6 +327,19 @@ impl CodeTaskDetailsResponseExt for CodeTaskDetailsResponse {
             .as_ref()
             .and_then(Turn::error_summary)
     }
+
+    fn assistant_worklog_lines(&self) -> Vec<String> {
+        self.current_assistant_turn
+            .as_ref()
+            .map(Turn::worklog_lines)
+            .unwrap_or_default()
+    }
+
+    fn assistant_turn_status(&self) -> Option<&str> {
+        self.current_assistant_turn
+            .as_ref()
+            .and_then(|turn| turn.turn_status.as_deref())
+    }
 }
 
 fn deserialize_vec<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
@@ -327,6 +365,7 @@ mod tests {
         let json = match name {
             "diff" => include_str!("../tests/fixtures/task_details_with_diff.json"),
             "error" => include_str!("../tests/fixtures/task_details_with_error.json"),
+            "worklog" => include_str!("../tests/fixtures/task_details_in_progress.json"),
             other => panic!("unknown fixture {other}"),
         };
         serde_json::from_str(json).expect("fixture should deserialize")
@@ -373,4 +412,18 @@ Second line"
             .expect("error should be present");
         assert_eq!(msg, "APPLY_FAILED: Patch could not be applied");
     }
+
+    #[test]
+    fn assistant_worklog_lines_include_every_role() {
+        let details = fixture("worklog");
+        assert_eq!(
+            details.assistant_worklog_lines(),
+            vec![
+                "assistant: Reading the repository layout".to_string(),
+                "tool: cargo test -p codex-core".to_string(),
+                "assistant: Tests pass; preparing the diff".to_string(),
+            ]
+        );
+        assert_eq!(details.assistant_turn_status(), Some("in_progress"));
+    }
 }
@@ -90,7 +90,7 @@ pub async fn list_cached_all_connectors(config: &Config) -> Option<Vec<AppInfo>>
         return Some(Vec::new());
     }
 
-    if init_chatgpt_token_from_auth(&config.codex_home, config.cli_auth_credentials_store_mode)
+    if init_chatgpt_token_from_auth(&config.auth_home, config.cli_auth_credentials_store_mode)
         .await
         .is_err()
     {
@@ -108,8 +108,7 @@ pub async fn list_all_connectors_with_options(
     if !config.features.enabled(Feature::Apps) {
         return Ok(Vec::new());
     }
-    init_chatgpt_token_from_auth(&config.codex_home, config.cli_auth_credentials_store_mode)
-        .await?;
+    init_chatgpt_token_from_auth(&config.auth_home, config.cli_auth_credentials_store_mode).await?;
 
     let token_data =
         get_chatgpt_token_data().ok_or_else(|| anyhow::anyhow!("ChatGPT token not available"))?;
@@ -0,0 +1,318 @@
+//! Shell completion scripts with dynamic values.
+//!
+//! `codex completion <shell>` prints clap's static script. For bash, zsh, and
+//! fish it also appends a wrapper that first asks `codex __complete` for
+//! values that only exist at runtime: profile names, model ids, session ids,
+//! and MCP server names. `__complete` receives the words before the cursor and
+//! exits non-zero when it has nothing to offer, so the wrapper falls back to
+//! the static completions.
+
+use std::collections::HashSet;
+use std::io::Write;
+
+use clap::Parser;
+use clap_complete::Shell;
+use clap_complete::generate;
+use codex_core::AuthManager;
+use codex_core::INTERACTIVE_SESSION_SOURCES;
+use codex_core::RolloutRecorder;
+use codex_core::ThreadSortKey;
+use codex_core::config::Config;
+use codex_core::config::find_codex_home;
+use codex_core::config::load_global_mcp_servers;
+use codex_core::find_thread_names_by_ids;
+use codex_core::models_manager::manager::ModelsManager;
+use codex_core::models_manager::manager::RefreshStrategy;
+use codex_utils_cli::CliConfigOverrides;
+
+/// Number of recent sessions offered when completing a session id.
+const MAX_SESSION_CANDIDATES: usize = 20;
+
+/// Longest description shown next to a candidate.
+const MAX_DESCRIPTION_CHARS: usize = 60;
+
+/// Flags whose next word is a value rather than a positional argument.
+const VALUE_FLAGS: &[&str] = &[
+    "-a",
+    "--add-dir",
+    "--ask-for-approval",
+    "-C",
+    "-c",
+    "--cd",
+    "--color",
+    "--config",
+    "--disable",
+    "--enable",
+    "--fork-at",
+    "-i",
+    "--image",
+    "--local-provider",
+    "-m",
+    "--model",
+    "-o",
+    "--orchestration",
+    "--output-last-message",
+    "--output-schema",
+    "-p",
+    "--profile",
+    "-s",
+    "--sandbox",
+    "--scope",
+];
+
+/// Subcommands of `codex mcp` that take a server name.
+const MCP_SERVER_SUBCOMMANDS: &[&str] = &["get", "remove", "login", "logout"];
+
+const BASH_DYNAMIC: &str = r#"
+_codex_dynamic() {
+    local cur="${COMP_WORDS[COMP_CWORD]}" values
+    if values="$(codex __complete -- "${COMP_WORDS[@]:1:COMP_CWORD-1}" 2>/dev/null)"; then
+        local IFS=$'\n'
+        COMPREPLY=($(compgen -W "$(printf '%s\n' "$values" | cut -f1)" -- "$cur"))
+        return 0
+    fi
+    _codex "$@"
+}
+complete -F _codex_dynamic -o bashdefault -o default codex
+"#;
+
+const ZSH_DYNAMIC: &str = r#"
+_codex_dynamic() {
+    local output
+    local -a lines values descriptions
+    if output="$(codex __complete -- "${(@)words[2,CURRENT-1]}" 2>/dev/null)"; then
+        [[ -n "$output" ]] || return 1
+        lines=("${(@f)output}")
+        values=("${(@)lines%%$'\t'*}")
+        descriptions=("${(@)lines//$'\t'/  -- }")
+        compadd -l -d descriptions -a values
+        return
+    fi
+    _codex "$@"
+}
+compdef _codex_dynamic codex
+"#;
+
+const FISH_DYNAMIC: &str = r#"
+function __codex_dynamic_complete
+    set -l tokens (commandline -opc)
+    codex __complete -- $tokens[2..-1] 2>/dev/null
+end
+complete -c codex -f -n '__codex_dynamic_complete >/dev/null' -a '(__codex_dynamic_complete)'
+"#;
+
+#[derive(Debug, Parser)]
+pub(crate) struct CompleteArgs {
+    /// Words on the command line before the one being completed, without `codex`.
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    words: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompletionKind {
+    Profiles,
+    Models,
+    Sessions,
+    McpServers,
+}
+
+/// Writes the completion script for `shell`, including the dynamic wrapper
+/// where the shell supports it.
+pub(crate) fn write_completion_script(
+    shell: Shell,
+    cmd: &mut clap::Command,
+    out: &mut impl Write,
+) -> std::io::Result<()> {
+    generate(shell, cmd, "codex", out);
+    let dynamic = match shell {
+        Shell::Bash => BASH_DYNAMIC,
+        Shell::Zsh => ZSH_DYNAMIC,
+        Shell::Fish => FISH_DYNAMIC,
+        _ => return Ok(()),
+    };
+    out.write_all(dynamic.as_bytes())
+}
+
+/// Prints `value<TAB>description` lines for the word after `args.words`.
+/// Returns `false` when the word has no dynamic values.
+pub(crate) async fn run_complete(
+    args: CompleteArgs,
+    root_config_overrides: CliConfigOverrides,
+) -> anyhow::Result<bool> {
+    let Some(kind) = completion_kind(&args.words) else {
+        return Ok(false);
+    };
+    let candidates = match kind {
+        CompletionKind::McpServers => {
+            let codex_home = find_codex_home()?;
+            load_global_mcp_servers(&codex_home)
+                .await?
+                .into_keys()
+                .map(|name| (name, String::new()))
+                .collect()
+        }
+        CompletionKind::Profiles | CompletionKind::Models | CompletionKind::Sessions => {
+            let cli_kv_overrides = root_config_overrides
+                .parse_overrides()
+                .map_err(anyhow::Error::msg)?;
+            let config = Config::load_with_cli_overrides(cli_kv_overrides).await?;
+            match kind {
+                CompletionKind::Profiles => profile_candidates(&config),
+                CompletionKind::Models => model_candidates(&config).await,
+                _ => session_candidates(&config).await?,
+            }
+        }
+    };
+    let mut stdout = std::io::stdout().lock();
+    for (value, description) in candidates {
+        if description.is_empty() {
+            writeln!(stdout, "{value}")?;
+        } else {
+            writeln!(stdout, "{value}\t{}", one_line(&description))?;
+        }
+    }
+    Ok(true)
+}
+
+/// Decides which values complete the word after `words`.
+fn completion_kind(words: &[String]) -> Option<CompletionKind> {
+    match words.last().map(String::as_str) {
+        Some("-p" | "--profile") => return Some(CompletionKind::Profiles),
+        Some("-m" | "--model") => return Some(CompletionKind::Models),
+        Some(flag) if VALUE_FLAGS.contains(&flag) => return None,
+        _ => {}
+    }
+
+    let mut positionals = Vec::new();
+    let mut iter = words.iter();
+    while let Some(word) = iter.next() {
+        if VALUE_FLAGS.contains(&word.as_str()) {
+            iter.next();
+        } else if !word.starts_with('-') {
+            positionals.push(word.as_str());
+        }
+    }
+    match positionals.as_slice() {
+        ["resume" | "fork"] | ["exec" | "e", "resume"] => Some(CompletionKind::Sessions),
+        ["mcp", subcommand] if MCP_SERVER_SUBCOMMANDS.contains(subcommand) => {
+            Some(CompletionKind::McpServers)
+        }
+        _ => None,
+    }
+}
+
+fn profile_candidates(config: &Config) -> Vec<(String, String)> {
+    let effective = config.config_layer_stack.effective_config();
+    let Some(profiles) = effective.get("profiles").and_then(toml::Value::as_table) else {
+        return Vec::new();
+    };
+    let mut candidates: Vec<_> = profiles
+        .iter()
+        .map(|(name, profile)| {
+            let model = profile
+                .get("model")
+                .and_then(toml::Value::as_str)
+                .unwrap_or_default();
+            (name.clone(), model.to_string())
+        })
+        .collect();
+    candidates.sort();
+    candidates
+}
+
+async fn model_candidates(config: &Config) -> Vec<(String, String)> {
+    let auth_manager = AuthManager::shared(
+        config.auth_home.clone(),
+        true,
+        config.cli_auth_credentials_store_mode,
+    );
+    // Completion must stay fast, so only built-in and cached models are offered.
+    ModelsManager::new(config.codex_home.clone(), auth_manager)
+        .list_models(config, RefreshStrategy::Offline)
+        .await
+        .into_iter()
+        .map(|preset| (preset.model, preset.description))
+        .collect()
+}
+
+async fn session_candidates(config: &Config) -> anyhow::Result<Vec<(String, String)>> {
+    let page = RolloutRecorder::list_threads(
+        config,
+        MAX_SESSION_CANDIDATES,
+        None,
+        ThreadSortKey::UpdatedAt,
+        INTERACTIVE_SESSION_SOURCES,
+        None,
+        &config.model_provider_id,
+    )
+    .await?;
+    let thread_ids: HashSet<_> = page
+        .items
+        .iter()
+        .filter_map(|item| item.thread_id)
+        .collect();
+    let names = find_thread_names_by_ids(&config.codex_home, &thread_ids).await?;
+    Ok(page
+        .items
+        .into_iter()
+        .filter_map(|item| {
+            let thread_id = item.thread_id?;
+            let description = names
+                .get(&thread_id)
+                .cloned()
+                .or(item.first_user_message)
+                .unwrap_or_default();
+            Some((thread_id.to_string(), description))
+        })
+        .collect())
+}
+
+fn one_line(description: &str) -> String {
+    let line = description.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.chars().count() <= MAX_DESCRIPTION_CHARS {
+        return line;
+    }
+    let truncated: String = line.chars().take(MAX_DESCRIPTION_CHARS - 1).collect();
+    format!("{truncated}…")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn kind_for(line: &str) -> Option<CompletionKind> {
+        let words: Vec<String> = line.split_whitespace().map(str::to_string).collect();
+        completion_kind(&words)
+    }
+
+    #[test]
+    fn completes_flag_values() {
+        assert_eq!(kind_for("-p"), Some(CompletionKind::Profiles));
+        assert_eq!(kind_for("exec --profile"), Some(CompletionKind::Profiles));
+        assert_eq!(kind_for("-c model=o3 -m"), Some(CompletionKind::Models));
+        assert_eq!(kind_for("--sandbox"), None);
+    }
+
+    #[test]
+    fn completes_positional_arguments() {
+        assert_eq!(kind_for("resume"), Some(CompletionKind::Sessions));
+        assert_eq!(
+            kind_for("-p work resume --all"),
+            Some(CompletionKind::Sessions)
+        );
+        assert_eq!(kind_for("exec resume"), Some(CompletionKind::Sessions));
+        assert_eq!(kind_for("fork"), Some(CompletionKind::Sessions));
+        assert_eq!(kind_for("resume 0199a213"), None);
+        assert_eq!(kind_for("mcp remove"), Some(CompletionKind::McpServers));
+        assert_eq!(kind_for("mcp add"), None);
+        assert_eq!(kind_for(""), None);
+    }
+
+    #[test]
+    fn descriptions_fit_on_one_line() {
+        assert_eq!(one_line("fix the\nflaky  test"), "fix the flaky test");
+        let long = "word ".repeat(20);
+        assert_eq!(one_line(&long).chars().count(), MAX_DESCRIPTION_CHARS);
+    }
+}
@@ -9,6 +9,8 @@ use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
 use codex_core::exec_env::create_env;
 use codex_core::landlock::spawn_command_under_linux_sandbox;
+use codex_core::sandbox_explain::command_for_line;
+use codex_core::sandbox_explain::explain_command;
 #[cfg(target_os = "macos")]
 use codex_core::seatbelt::spawn_command_under_seatbelt;
 use codex_core::spawn::StdioPolicy;
@@ -16,6 +18,7 @@ use codex_protocol::config_types::SandboxMode;
 use codex_utils_cli::CliConfigOverrides;
 
 use crate::LandlockCommand;
+use crate::SandboxExplainCommand;
 use crate::SeatbeltCommand;
 use crate::WindowsCommand;
 use crate::exit_status::handle_exit_status;
@@ -93,6 +96,35 @@ pub async fn run_command_under_windows(
     .await
 }
 
+/// Reports what the configured sandbox and approval policy would do with a
+/// command, without running it.
+pub async fn explain_command_under_sandbox(command: SandboxExplainCommand) -> anyhow::Result<()> {
+    let SandboxExplainCommand {
+        json,
+        config_overrides,
+        command,
+    } = command;
+    let config = Config::load_with_cli_overrides_and_harness_overrides(
+        config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?,
+        ConfigOverrides::default(),
+    )
+    .await?;
+
+    let command = match command.as_slice() {
+        [line] if line.contains(char::is_whitespace) => command_for_line(line),
+        _ => command,
+    };
+    let explanation = explain_command(&config, &command).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&explanation)?);
+    } else {
+        println!("{explanation}");
+    }
+    Ok(())
+}
+
 enum SandboxType {
     #[cfg(target_os = "macos")]
     Seatbelt,
@@ -120,6 +152,8 @@ async fn run_command_under_sandbox(
         },
     )
     .await?;
+    codex_core::seatbelt_profile::set_profile_additions(config.macos_seatbelt.clone());
+    codex_core::sandbox_devices::set_allowed_devices(config.sandbox_allowed_devices.clone());
 
     // In practice, this should be `std::env::current_dir()` because this CLI
     // does not support `--cwd`, but let's use the config value for consistency.
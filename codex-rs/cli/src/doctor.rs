@@ -0,0 +1,462 @@
+//! `codex doctor`: checks the local environment and says how to fix problems.
+//!
+//! Each check produces one [`DoctorCheck`]. The report prints as a list for
+//! people or, with `--json`, as a single JSON object for scripts and bug
+//! reports. The command exits non-zero when any check fails.
+
+use std::io::IsTerminal;
+use std::process::Stdio;
+use std::time::Duration;
+
+use clap::Parser;
+use codex_core::AuthManager;
+use codex_core::auth::AuthMode;
+use codex_core::config::Config;
+use codex_core::mcp::check_mcp_startup;
+use codex_core::shell::default_user_shell;
+use codex_core::terminal;
+use codex_utils_cli::CliConfigOverrides;
+use owo_colors::OwoColorize;
+use serde::Serialize;
+use supports_color::Stream;
+use tokio::process::Command;
+
+/// How long a spawned probe (sandbox, shell) may take before it counts as hung.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Printed by the shell probe; the check passes when it comes back intact.
+const SHELL_HANDSHAKE: &str = "codex-doctor-handshake";
+
+#[derive(Debug, Parser)]
+pub(crate) struct DoctorCommand {
+    /// Print the report as JSON.
+    #[arg(long, default_value_t = false)]
+    json: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+    Skip,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct DoctorCheck {
+    name: String,
+    status: CheckStatus,
+    summary: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fix: Option<String>,
+}
+
+impl DoctorCheck {
+    fn new(name: &str, status: CheckStatus, summary: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            summary: summary.into(),
+            fix: None,
+        }
+    }
+
+    fn with_fix(mut self, fix: impl Into<String>) -> Self {
+        self.fix = Some(fix.into());
+        self
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct DoctorReport {
+    version: &'static str,
+    platform: String,
+    checks: Vec<DoctorCheck>,
+}
+
+impl DoctorReport {
+    fn has_failures(&self) -> bool {
+        self.checks
+            .iter()
+            .any(|check| check.status == CheckStatus::Fail)
+    }
+}
+
+/// Runs every check and prints the report. Returns whether all checks passed
+/// or only warned.
+pub(crate) async fn run_doctor(
+    cmd: DoctorCommand,
+    root_config_overrides: CliConfigOverrides,
+) -> anyhow::Result<bool> {
+    let mut checks = Vec::new();
+    let config = match load_config(root_config_overrides).await {
+        Ok(config) => {
+            checks.push(DoctorCheck::new(
+                "config",
+                CheckStatus::Ok,
+                format!("loaded {}", config.codex_home.join("config.toml").display()),
+            ));
+            Some(config)
+        }
+        Err(err) => {
+            checks.push(
+                DoctorCheck::new("config", CheckStatus::Fail, err.to_string())
+                    .with_fix("Fix the reported setting in config.toml, or pass a corrected value with `-c key=value`."),
+            );
+            None
+        }
+    };
+
+    checks.push(check_sandbox(config.as_ref()).await);
+    checks.push(check_shell().await);
+    match &config {
+        Some(config) => {
+            checks.push(check_auth(config).await);
+            checks.extend(check_mcp_servers(config).await);
+        }
+        None => {
+            for name in ["auth", "mcp"] {
+                checks.push(DoctorCheck::new(
+                    name,
+                    CheckStatus::Skip,
+                    "needs a valid config",
+                ));
+            }
+        }
+    }
+    checks.push(check_terminal());
+
+    let report = DoctorReport {
+        version: env!("CARGO_PKG_VERSION"),
+        platform: format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS),
+        checks,
+    };
+    if cmd.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+    Ok(!report.has_failures())
+}
+
+async fn load_config(root_config_overrides: CliConfigOverrides) -> anyhow::Result<Config> {
+    let cli_kv_overrides = root_config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    Ok(Config::load_with_cli_overrides(cli_kv_overrides).await?)
+}
+
+/// Runs a no-op command under the platform sandbox through `codex sandbox`.
+async fn check_sandbox(config: Option<&Config>) -> DoctorCheck {
+    let (sandbox, probe): (&str, &[&str]) = if cfg!(target_os = "macos") {
+        ("macos", &["true"])
+    } else if cfg!(target_os = "linux") {
+        ("linux", &["true"])
+    } else if cfg!(windows) {
+        if config.is_some_and(|config| config.permissions.windows_sandbox_mode.is_none()) {
+            return DoctorCheck::new(
+                "sandbox",
+                CheckStatus::Warn,
+                "the Windows sandbox is not enabled, so commands run unsandboxed",
+            )
+            .with_fix("Enable it with `[windows] sandbox = \"elevated\"` in config.toml.");
+        }
+        ("windows", &["cmd", "/C", "exit 0"])
+    } else {
+        return DoctorCheck::new(
+            "sandbox",
+            CheckStatus::Warn,
+            "no sandbox is available on this platform",
+        );
+    };
+
+    let label = match sandbox {
+        "macos" => "Seatbelt",
+        "linux" => "Landlock/seccomp",
+        _ => "restricted token",
+    };
+    let rerun = format!("codex sandbox {sandbox} -- {}", probe.join(" "));
+    match run_probe(
+        std::env::current_exe().ok(),
+        &["sandbox", sandbox, "--"],
+        probe,
+    )
+    .await
+    {
+        Ok(output) if output.status.success() => DoctorCheck::new(
+            "sandbox",
+            CheckStatus::Ok,
+            format!("{label} sandbox ran a test command"),
+        ),
+        Ok(output) => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let fix = if sandbox == "linux" {
+                format!(
+                    "Run `{rerun}` to see the full error. Landlock needs Linux 5.13 or newer with Landlock enabled; inside containers, allow the landlock and seccomp syscalls."
+                )
+            } else {
+                format!("Run `{rerun}` to see the full error.")
+            };
+            DoctorCheck::new(
+                "sandbox",
+                CheckStatus::Fail,
+                format!(
+                    "{label} sandbox could not run a test command: {}",
+                    last_line(&stderr).unwrap_or("no error output")
+                ),
+            )
+            .with_fix(fix)
+        }
+        Err(err) => DoctorCheck::new("sandbox", CheckStatus::Fail, err)
+            .with_fix(format!("Run `{rerun}` to see the full error.")),
+    }
+}
+
+/// Starts the user's login shell and checks that a trivial command round-trips.
+async fn check_shell() -> DoctorCheck {
+    let shell = default_user_shell();
+    let args = shell.derive_exec_args(&format!("echo {SHELL_HANDSHAKE}"), true);
+    let Some((shell_path, rest)) = args.split_first() else {
+        return DoctorCheck::new("shell", CheckStatus::Fail, "no shell found");
+    };
+    let rest: Vec<&str> = rest.iter().map(String::as_str).collect();
+    let fix = format!(
+        "Check your shell startup files: `{shell_path} -lc true` should exit promptly without errors."
+    );
+    match run_probe(Some(shell_path.into()), &rest, &[]).await {
+        Ok(output)
+            if output.status.success()
+                && String::from_utf8_lossy(&output.stdout).contains(SHELL_HANDSHAKE) =>
+        {
+            DoctorCheck::new(
+                "shell",
+                CheckStatus::Ok,
+                format!("{} login shell at {shell_path} runs commands", shell.name()),
+            )
+        }
+        Ok(output) => DoctorCheck::new(
+            "shell",
+            CheckStatus::Fail,
+            format!(
+                "{shell_path} did not complete the handshake ({})",
+                last_line(&String::from_utf8_lossy(&output.stderr)).unwrap_or("no error output")
+            ),
+        )
+        .with_fix(fix),
+        Err(err) => DoctorCheck::new("shell", CheckStatus::Fail, err).with_fix(fix),
+    }
+}
+
+async fn check_auth(config: &Config) -> DoctorCheck {
+    let provider = &config.model_provider;
+    if !provider.requires_openai_auth {
+        return match provider.api_key() {
+            Ok(Some(_)) => DoctorCheck::new(
+                "auth",
+                CheckStatus::Ok,
+                format!(
+                    "{} API key found in ${}",
+                    provider.name,
+                    provider.env_key.as_deref().unwrap_or_default()
+                ),
+            ),
+            Ok(None) => DoctorCheck::new(
+                "auth",
+                CheckStatus::Ok,
+                format!("{} does not need an API key", provider.name),
+            ),
+            Err(err) => DoctorCheck::new("auth", CheckStatus::Fail, err.to_string()).with_fix(
+                provider.env_key_instructions.clone().unwrap_or_else(|| {
+                    "Set the provider's API key environment variable.".to_string()
+                }),
+            ),
+        };
+    }
+
+    let auth_manager = AuthManager::shared(
+        config.auth_home.clone(),
+        true,
+        config.cli_auth_credentials_store_mode,
+    );
+    let Some(auth) = auth_manager.auth().await else {
+        return DoctorCheck::new("auth", CheckStatus::Fail, "not logged in")
+            .with_fix("Run `codex login`, or set OPENAI_API_KEY.");
+    };
+    if let Err(err) = auth.get_token() {
+        return DoctorCheck::new(
+            "auth",
+            CheckStatus::Fail,
+            format!("stored credentials are unreadable: {err}"),
+        )
+        .with_fix("Run `codex logout` and then `codex login`.");
+    }
+    let summary = match auth.auth_mode() {
+        AuthMode::ApiKey => "logged in with an API key".to_string(),
+        AuthMode::Chatgpt => match auth.get_account_email() {
+            Some(email) => format!("logged in with ChatGPT as {email}"),
+            None => "logged in with ChatGPT".to_string(),
+        },
+    };
+    DoctorCheck::new("auth", CheckStatus::Ok, summary)
+}
+
+/// Starts each enabled MCP server once. One check per server.
+async fn check_mcp_servers(config: &Config) -> Vec<DoctorCheck> {
+    let startup = check_mcp_startup(config).await;
+    if startup.ready.is_empty() && startup.failed.is_empty() && startup.cancelled.is_empty() {
+        return vec![DoctorCheck::new(
+            "mcp",
+            CheckStatus::Skip,
+            "no MCP servers enabled",
+        )];
+    }
+    let mut checks: Vec<DoctorCheck> = startup
+        .ready
+        .iter()
+        .map(|server| DoctorCheck::new(&format!("mcp:{server}"), CheckStatus::Ok, "started"))
+        .chain(startup.failed.iter().map(|failure| {
+            DoctorCheck::new(
+                &format!("mcp:{}", failure.server),
+                CheckStatus::Fail,
+                failure.error.clone(),
+            )
+            .with_fix(format!(
+                "Run `codex mcp get {}` to review its settings, or disable it with `enabled = false`.",
+                failure.server
+            ))
+        }))
+        .chain(startup.cancelled.iter().map(|server| {
+            DoctorCheck::new(&format!("mcp:{server}"), CheckStatus::Warn, "startup was cancelled")
+        }))
+        .collect();
+    checks.sort_by(|a, b| a.name.cmp(&b.name));
+    checks
+}
+
+fn check_terminal() -> DoctorCheck {
+    let terminal = terminal::user_agent();
+    let term = std::env::var("TERM").unwrap_or_default();
+    let colors = match supports_color::on(Stream::Stdout) {
+        Some(level) if level.has_16m => "truecolor",
+        Some(level) if level.has_256 => "256 colors",
+        Some(_) => "16 colors",
+        None => "no color",
+    };
+    let summary = format!("{terminal} (TERM={term}), {colors}");
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        DoctorCheck::new(
+            "terminal",
+            CheckStatus::Warn,
+            format!("{summary}; not a TTY"),
+        )
+        .with_fix("The interactive TUI needs a terminal; use `codex exec` in scripts and pipes.")
+    } else if term == "dumb" {
+        DoctorCheck::new("terminal", CheckStatus::Warn, summary)
+            .with_fix("TERM=dumb disables cursor control; set TERM to your terminal's type, e.g. xterm-256color.")
+    } else {
+        DoctorCheck::new("terminal", CheckStatus::Ok, summary)
+    }
+}
+
+/// Runs `program args probe` with no stdin, failing if it does not exit in time.
+async fn run_probe(
+    program: Option<std::path::PathBuf>,
+    args: &[&str],
+    probe: &[&str],
+) -> Result<std::process::Output, String> {
+    let program = program.ok_or_else(|| "cannot locate the codex executable".to_string())?;
+    let output = Command::new(&program)
+        .args(args)
+        .args(probe)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    match tokio::time::timeout(PROBE_TIMEOUT, output).await {
+        Ok(Ok(output)) => Ok(output),
+        Ok(Err(err)) => Err(format!("failed to start {}: {err}", program.display())),
+        Err(_) => Err(format!(
+            "{} did not exit within {}s",
+            program.display(),
+            PROBE_TIMEOUT.as_secs()
+        )),
+    }
+}
+
+fn last_line(text: &str) -> Option<&str> {
+    text.lines().map(str::trim).rfind(|line| !line.is_empty())
+}
+
+fn print_report(report: &DoctorReport) {
+    let color = supports_color::on(Stream::Stdout).is_some();
+    println!("Codex {} on {}", report.version, report.platform);
+    let width = report
+        .checks
+        .iter()
+        .map(|check| check.name.len())
+        .max()
+        .unwrap_or_default();
+    for check in &report.checks {
+        let marker = match check.status {
+            CheckStatus::Ok => "ok",
+            CheckStatus::Warn => "warn",
+            CheckStatus::Fail => "FAIL",
+            CheckStatus::Skip => "skip",
+        };
+        let marker = format!("{marker:>4}");
+        let marker = if !color {
+            marker
+        } else {
+            match check.status {
+                CheckStatus::Ok => marker.green().to_string(),
+                CheckStatus::Warn => marker.yellow().to_string(),
+                CheckStatus::Fail => marker.red().bold().to_string(),
+                CheckStatus::Skip => marker.dimmed().to_string(),
+            }
+        };
+        println!("{marker}  {:<width$}  {}", check.name, check.summary);
+        if let Some(fix) = &check.fix {
+            println!("      {:<width$}  -> {fix}", "");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn report_serializes_with_fixes_only_when_present() {
+        let report = DoctorReport {
+            version: "1.2.3",
+            platform: "x86_64-linux".to_string(),
+            checks: vec![
+                DoctorCheck::new("shell", CheckStatus::Ok, "zsh login shell runs commands"),
+                DoctorCheck::new("auth", CheckStatus::Fail, "not logged in")
+                    .with_fix("Run `codex login`."),
+            ],
+        };
+        assert!(report.has_failures());
+        assert_eq!(
+            serde_json::to_value(&report).expect("serialize report"),
+            serde_json::json!({
+                "version": "1.2.3",
+                "platform": "x86_64-linux",
+                "checks": [
+                    {"name": "shell", "status": "ok", "summary": "zsh login shell runs commands"},
+                    {"name": "auth", "status": "fail", "summary": "not logged in", "fix": "Run `codex login`."},
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn last_line_skips_trailing_blank_lines() {
+        assert_eq!(
+            last_line("warning\nerror: denied\n\n"),
+            Some("error: denied")
+        );
+        assert_eq!(last_line("  \n"), None);
+    }
+}
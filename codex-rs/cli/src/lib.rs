@@ -50,3 +50,17 @@ pub struct WindowsCommand {
     #[arg(trailing_var_arg = true)]
     pub command: Vec<String>,
 }
+
+#[derive(Debug, Parser)]
+pub struct SandboxExplainCommand {
+    /// Print the report as JSON.
+    #[arg(long = "json", default_value_t = false)]
+    pub json: bool,
+
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Command to explain. A single argument is treated as a shell command line.
+    #[arg(trailing_var_arg = true, required = true)]
+    pub command: Vec<String>,
+}
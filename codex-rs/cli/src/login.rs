@@ -1,13 +1,21 @@
 use codex_core::CodexAuth;
+use codex_core::ProviderOidcConfig;
 use codex_core::auth::AuthCredentialsStoreMode;
 use codex_core::auth::AuthMode;
 use codex_core::auth::CLIENT_ID;
+use codex_core::auth::credential_home;
+use codex_core::auth::delete_oidc_tokens;
+use codex_core::auth::list_credentials;
 use codex_core::auth::login_with_api_key;
 use codex_core::auth::logout;
+use codex_core::auth::validate_credential_name;
 use codex_core::config::Config;
+use codex_core::default_client::set_default_client_http_config;
+use codex_core::default_client::set_default_client_offline;
 use codex_login::ServerOptions;
 use codex_login::run_device_code_login;
 use codex_login::run_login_server;
+use codex_login::run_oidc_device_code_login;
 use codex_protocol::config_types::ForcedLoginMethod;
 use codex_utils_cli::CliConfigOverrides;
 use std::io::IsTerminal;
@@ -44,8 +52,12 @@ pub async fn login_with_chatgpt(
     server.block_until_done().await
 }
 
-pub async fn run_login_with_chatgpt(cli_config_overrides: CliConfigOverrides) -> ! {
-    let config = load_config_or_exit(cli_config_overrides).await;
+pub async fn run_login_with_chatgpt(
+    cli_config_overrides: CliConfigOverrides,
+    credential: Option<String>,
+) -> ! {
+    let config = load_config_or_exit(cli_config_overrides, credential).await;
+    create_credential_home_or_exit(&config);
 
     if matches!(config.forced_login_method, Some(ForcedLoginMethod::Api)) {
         eprintln!("{CHATGPT_LOGIN_DISABLED_MESSAGE}");
@@ -55,7 +67,7 @@ pub async fn run_login_with_chatgpt(cli_config_overrides: CliConfigOverrides) ->
     let forced_chatgpt_workspace_id = config.forced_chatgpt_workspace_id.clone();
 
     match login_with_chatgpt(
-        config.codex_home,
+        config.auth_home,
         forced_chatgpt_workspace_id,
         config.cli_auth_credentials_store_mode,
     )
@@ -74,9 +86,11 @@ pub async fn run_login_with_chatgpt(cli_config_overrides: CliConfigOverrides) ->
 
 pub async fn run_login_with_api_key(
     cli_config_overrides: CliConfigOverrides,
+    credential: Option<String>,
     api_key: String,
 ) -> ! {
-    let config = load_config_or_exit(cli_config_overrides).await;
+    let config = load_config_or_exit(cli_config_overrides, credential).await;
+    create_credential_home_or_exit(&config);
 
     if matches!(config.forced_login_method, Some(ForcedLoginMethod::Chatgpt)) {
         eprintln!("{API_KEY_LOGIN_DISABLED_MESSAGE}");
@@ -84,7 +98,7 @@ pub async fn run_login_with_api_key(
     }
 
     match login_with_api_key(
-        &config.codex_home,
+        &config.auth_home,
         &api_key,
         config.cli_auth_credentials_store_mode,
     ) {
@@ -129,17 +143,19 @@ pub fn read_api_key_from_stdin() -> String {
 /// Login using the OAuth device code flow.
 pub async fn run_login_with_device_code(
     cli_config_overrides: CliConfigOverrides,
+    credential: Option<String>,
     issuer_base_url: Option<String>,
     client_id: Option<String>,
 ) -> ! {
-    let config = load_config_or_exit(cli_config_overrides).await;
+    let config = load_config_or_exit(cli_config_overrides, credential).await;
+    create_credential_home_or_exit(&config);
     if matches!(config.forced_login_method, Some(ForcedLoginMethod::Api)) {
         eprintln!("{CHATGPT_LOGIN_DISABLED_MESSAGE}");
         std::process::exit(1);
     }
     let forced_chatgpt_workspace_id = config.forced_chatgpt_workspace_id.clone();
     let mut opts = ServerOptions::new(
-        config.codex_home,
+        config.auth_home,
         client_id.unwrap_or(CLIENT_ID.to_string()),
         forced_chatgpt_workspace_id,
         config.cli_auth_credentials_store_mode,
@@ -165,10 +181,12 @@ pub async fn run_login_with_device_code(
 /// falls back to starting the local browser login server.
 pub async fn run_login_with_device_code_fallback_to_browser(
     cli_config_overrides: CliConfigOverrides,
+    credential: Option<String>,
     issuer_base_url: Option<String>,
     client_id: Option<String>,
 ) -> ! {
-    let config = load_config_or_exit(cli_config_overrides).await;
+    let config = load_config_or_exit(cli_config_overrides, credential).await;
+    create_credential_home_or_exit(&config);
     if matches!(config.forced_login_method, Some(ForcedLoginMethod::Api)) {
         eprintln!("{CHATGPT_LOGIN_DISABLED_MESSAGE}");
         std::process::exit(1);
@@ -176,7 +194,7 @@ pub async fn run_login_with_device_code_fallback_to_browser(
 
     let forced_chatgpt_workspace_id = config.forced_chatgpt_workspace_id.clone();
     let mut opts = ServerOptions::new(
-        config.codex_home,
+        config.auth_home,
         client_id.unwrap_or(CLIENT_ID.to_string()),
         forced_chatgpt_workspace_id,
         config.cli_auth_credentials_store_mode,
@@ -221,14 +239,50 @@ pub async fn run_login_with_device_code_fallback_to_browser(
     }
 }
 
-pub async fn run_login_status(cli_config_overrides: CliConfigOverrides) -> ! {
-    let config = load_config_or_exit(cli_config_overrides).await;
+/// Login to a model provider through its OIDC issuer's device authorization
+/// flow.
+pub async fn run_login_with_provider(
+    cli_config_overrides: CliConfigOverrides,
+    credential: Option<String>,
+    provider_id: String,
+) -> ! {
+    let config = load_config_or_exit(cli_config_overrides, credential).await;
+    create_credential_home_or_exit(&config);
+    let (provider_name, oidc) = provider_oidc_or_exit(&config, &provider_id);
+    match run_oidc_device_code_login(
+        &config.auth_home,
+        config.cli_auth_credentials_store_mode,
+        &provider_name,
+        &oidc,
+    )
+    .await
+    {
+        Ok(()) => {
+            eprintln!("{LOGIN_SUCCESS_MESSAGE} to {provider_name}");
+            std::process::exit(0);
+        }
+        Err(e) => {
+            eprintln!("Error logging in to {provider_name}: {e}");
+            std::process::exit(1);
+        }
+    }
+}
 
-    match CodexAuth::from_auth_storage(&config.codex_home, config.cli_auth_credentials_store_mode) {
+pub async fn run_login_status(
+    cli_config_overrides: CliConfigOverrides,
+    credential: Option<String>,
+) -> ! {
+    let config = load_config_or_exit(cli_config_overrides, credential).await;
+    let label = credential_label(&config);
+
+    match CodexAuth::from_auth_storage(&config.auth_home, config.cli_auth_credentials_store_mode) {
         Ok(Some(auth)) => match auth.auth_mode() {
             AuthMode::ApiKey => match auth.get_token() {
                 Ok(api_key) => {
-                    eprintln!("Logged in using an API key - {}", safe_format_key(&api_key));
+                    eprintln!(
+                        "Logged in{label} using an API key - {}",
+                        safe_format_key(&api_key)
+                    );
                     std::process::exit(0);
                 }
                 Err(e) => {
@@ -237,12 +291,12 @@ pub async fn run_login_status(cli_config_overrides: CliConfigOverrides) -> ! {
                 }
             },
             AuthMode::Chatgpt => {
-                eprintln!("Logged in using ChatGPT");
+                eprintln!("Logged in{label} using ChatGPT");
                 std::process::exit(0);
             }
         },
         Ok(None) => {
-            eprintln!("Not logged in");
+            eprintln!("Not logged in{label}");
             std::process::exit(1);
         }
         Err(e) => {
@@ -252,16 +306,77 @@ pub async fn run_login_status(cli_config_overrides: CliConfigOverrides) -> ! {
     }
 }
 
-pub async fn run_logout(cli_config_overrides: CliConfigOverrides) -> ! {
-    let config = load_config_or_exit(cli_config_overrides).await;
+pub async fn run_logout(cli_config_overrides: CliConfigOverrides, credential: Option<String>) -> ! {
+    let config = load_config_or_exit(cli_config_overrides, credential).await;
+    let label = credential_label(&config);
+
+    let result = logout(&config.auth_home, config.cli_auth_credentials_store_mode);
+    if config.credential.is_some() {
+        // Drops the credential from `codex login list` once nothing is left
+        // in its directory.
+        let _ = std::fs::remove_dir(&config.auth_home);
+    }
+    match result {
+        Ok(true) => {
+            eprintln!("Successfully logged out{label}");
+            std::process::exit(0);
+        }
+        Ok(false) => {
+            eprintln!("Not logged in{label}");
+            std::process::exit(0);
+        }
+        Err(e) => {
+            eprintln!("Error logging out: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Lists the named credentials stored under `CODEX_HOME`, marking the one the
+/// active profile selects.
+pub async fn run_login_list(cli_config_overrides: CliConfigOverrides) -> ! {
+    let config = load_config_or_exit(cli_config_overrides, None).await;
+    let names = match list_credentials(&config.codex_home) {
+        Ok(names) => names,
+        Err(e) => {
+            eprintln!("Error listing credentials: {e}");
+            std::process::exit(1);
+        }
+    };
+    if names.is_empty() {
+        eprintln!("No named credentials. Create one with `codex login --name <NAME>`.");
+        std::process::exit(0);
+    }
+    for name in names {
+        let marker = if config.credential.as_deref() == Some(name.as_str()) {
+            "*"
+        } else {
+            " "
+        };
+        println!("{marker} {name}");
+    }
+    std::process::exit(0);
+}
 
-    match logout(&config.codex_home, config.cli_auth_credentials_store_mode) {
+/// Removes the stored OIDC sign-in of one provider.
+pub async fn run_logout_provider(
+    cli_config_overrides: CliConfigOverrides,
+    credential: Option<String>,
+    provider_id: String,
+) -> ! {
+    let config = load_config_or_exit(cli_config_overrides, credential).await;
+    let (provider_name, oidc) = provider_oidc_or_exit(&config, &provider_id);
+    match delete_oidc_tokens(
+        &config.auth_home,
+        config.cli_auth_credentials_store_mode,
+        &oidc,
+    ) {
         Ok(true) => {
-            eprintln!("Successfully logged out");
+            eprintln!("Successfully logged out of {provider_name}");
             std::process::exit(0);
         }
         Ok(false) => {
-            eprintln!("Not logged in");
+            eprintln!("Not logged in to {provider_name}");
             std::process::exit(0);
         }
         Err(e) => {
@@ -271,7 +386,26 @@ pub async fn run_logout(cli_config_overrides: CliConfigOverrides) -> ! {
     }
 }
 
-async fn load_config_or_exit(cli_config_overrides: CliConfigOverrides) -> Config {
+fn provider_oidc_or_exit(config: &Config, provider_id: &str) -> (String, ProviderOidcConfig) {
+    let Some(provider) = config.model_providers.get(provider_id) else {
+        eprintln!("Unknown model provider `{provider_id}`.");
+        std::process::exit(1);
+    };
+    let Some(oidc) = provider.oidc.clone() else {
+        eprintln!(
+            "Model provider `{provider_id}` has no `oidc` issuer configured; it does not use `codex login`."
+        );
+        std::process::exit(1);
+    };
+    (provider.name.clone(), oidc)
+}
+
+/// Loads the config, switching it to the credential named on the command line
+/// when one is given.
+async fn load_config_or_exit(
+    cli_config_overrides: CliConfigOverrides,
+    credential: Option<String>,
+) -> Config {
     let cli_overrides = match cli_config_overrides.parse_overrides() {
         Ok(v) => v,
         Err(e) => {
@@ -280,15 +414,50 @@ async fn load_config_or_exit(cli_config_overrides: CliConfigOverrides) -> Config
         }
     };
 
-    match Config::load_with_cli_overrides(cli_overrides).await {
+    let mut config = match Config::load_with_cli_overrides(cli_overrides).await {
         Ok(config) => config,
         Err(e) => {
             eprintln!("Error loading configuration: {e}");
             std::process::exit(1);
         }
+    };
+    set_default_client_offline(config.offline);
+    set_default_client_http_config(config.http_client.clone());
+    if let Some(name) = credential {
+        if let Err(e) = validate_credential_name(&name) {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+        config.auth_home = credential_home(&config.codex_home, Some(&name));
+        config.credential = Some(name);
+    }
+    config
+}
+
+/// Creates the directory of a named credential before logging in to it, so
+/// `codex login list` finds it even when the keyring holds the secret.
+fn create_credential_home_or_exit(config: &Config) {
+    if config.credential.is_some()
+        && let Err(e) = std::fs::create_dir_all(&config.auth_home)
+    {
+        eprintln!(
+            "Error creating credential directory {}: {e}",
+            config.auth_home.display()
+        );
+        std::process::exit(1);
     }
 }
 
+/// Suffix naming the credential a message is about, empty for the default
+/// login.
+fn credential_label(config: &Config) -> String {
+    config
+        .credential
+        .as_deref()
+        .map(|name| format!(" (credential `{name}`)"))
+        .unwrap_or_default()
+}
+
 fn safe_format_key(key: &str) -> String {
     if key.len() <= 13 {
         return "***".to_string();
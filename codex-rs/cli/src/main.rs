@@ -2,29 +2,35 @@ use clap::Args;
 use clap::CommandFactory;
 use clap::Parser;
 use clap_complete::Shell;
-use clap_complete::generate;
 use codex_arg0::arg0_dispatch_or_else;
 use codex_chatgpt::apply_command::ApplyCommand;
 use codex_chatgpt::apply_command::run_apply_command;
 use codex_cli::LandlockCommand;
+use codex_cli::SandboxExplainCommand;
 use codex_cli::SeatbeltCommand;
 use codex_cli::WindowsCommand;
 use codex_cli::login::read_api_key_from_stdin;
+use codex_cli::login::run_login_list;
 use codex_cli::login::run_login_status;
 use codex_cli::login::run_login_with_api_key;
 use codex_cli::login::run_login_with_chatgpt;
 use codex_cli::login::run_login_with_device_code;
+use codex_cli::login::run_login_with_provider;
 use codex_cli::login::run_logout;
+use codex_cli::login::run_logout_provider;
 use codex_cloud_tasks::Cli as CloudTasksCli;
 use codex_exec::Cli as ExecCli;
 use codex_exec::Command as ExecCommand;
+use codex_exec::ReplayArgs;
 use codex_exec::ReviewArgs;
+use codex_exec::SessionExportArgs;
 use codex_execpolicy::ExecPolicyCheckCommand;
 use codex_responses_api_proxy::Args as ResponsesApiProxyArgs;
 use codex_tui::AppExitInfo;
 use codex_tui::Cli as TuiCli;
 use codex_tui::ExitReason;
 use codex_tui::update_action::UpdateAction;
+use codex_tui::update_action::get_package_manager_update_action;
 use codex_utils_cli::CliConfigOverrides;
 use owo_colors::OwoColorize;
 use std::io::IsTerminal;
@@ -33,20 +39,26 @@ use supports_color::Stream;
 
 #[cfg(target_os = "macos")]
 mod app_cmd;
+mod completion;
 #[cfg(target_os = "macos")]
 mod desktop_app;
+mod doctor;
 mod mcp_cmd;
+mod policy_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
+use crate::completion::CompleteArgs;
 use crate::mcp_cmd::McpCli;
 
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
 use codex_core::config::edit::ConfigEditsBuilder;
 use codex_core::config::find_codex_home;
+use codex_core::config::types::UpdateChannel;
 use codex_core::features::Stage;
 use codex_core::features::is_known_feature_key;
+use codex_core::self_update;
 use codex_core::terminal::TerminalName;
 
 /// Codex CLI
@@ -102,6 +114,12 @@ enum Subcommand {
     /// [experimental] Run the app server or related tooling.
     AppServer(AppServerCommand),
 
+    /// [experimental] Stream the Codex protocol over a local WebSocket for external frontends.
+    Serve(ServeCommand),
+
+    /// [experimental] Serve a browser dashboard for sessions, approvals and diffs.
+    Web(WebCommand),
+
     /// Launch the Codex desktop app (downloads the macOS installer if missing).
     #[cfg(target_os = "macos")]
     App(app_cmd::AppCommand),
@@ -109,6 +127,16 @@ enum Subcommand {
     /// Generate shell completion scripts.
     Completion(CompletionCommand),
 
+    /// Internal: print dynamic completion values for the shell completion scripts.
+    #[clap(hide = true, name = "__complete")]
+    Complete(CompleteArgs),
+
+    /// Update Codex to the latest release on a channel.
+    Upgrade(UpgradeCommand),
+
+    /// Diagnose the local environment: sandbox, shell, auth, MCP servers, terminal, and config.
+    Doctor(doctor::DoctorCommand),
+
     /// Run commands within a Codex-provided sandbox.
     Sandbox(SandboxArgs),
 
@@ -119,6 +147,9 @@ enum Subcommand {
     #[clap(hide = true)]
     Execpolicy(ExecpolicyCommand),
 
+    /// Develop approval rules: show how the rules files decide a command.
+    Policy(policy_cmd::PolicyCommand),
+
     /// Apply the latest diff produced by Codex agent as a `git apply` to your local working tree.
     #[clap(visible_alias = "a")]
     Apply(ApplyCommand),
@@ -143,6 +174,18 @@ enum Subcommand {
 
     /// Inspect feature flags.
     Features(FeaturesCli),
+
+    /// Inspect the hash-chained audit log.
+    Audit(AuditCommand),
+
+    /// Manage recorded session files.
+    Sessions(SessionsCommand),
+
+    /// Search the contents of past sessions.
+    History(HistoryCommand),
+
+    /// Re-render a recorded session from its rollout file, without calling the model or running commands.
+    Replay(ReplayArgs),
 }
 
 #[derive(Debug, Parser)]
@@ -152,6 +195,32 @@ struct CompletionCommand {
     shell: Shell,
 }
 
+#[derive(Debug, Parser)]
+struct UpgradeCommand {
+    /// Release channel to install from. Defaults to `updates.channel` in config.toml.
+    #[arg(long, value_enum)]
+    channel: Option<UpgradeChannelArg>,
+
+    /// Install the release even when it is not newer than the running version.
+    #[arg(long)]
+    allow_downgrade: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum UpgradeChannelArg {
+    Stable,
+    Nightly,
+}
+
+impl From<UpgradeChannelArg> for UpdateChannel {
+    fn from(channel: UpgradeChannelArg) -> Self {
+        match channel {
+            UpgradeChannelArg::Stable => UpdateChannel::Stable,
+            UpgradeChannelArg::Nightly => UpdateChannel::Nightly,
+        }
+    }
+}
+
 #[derive(Debug, Parser)]
 struct DebugCommand {
     #[command(subcommand)]
@@ -197,6 +266,11 @@ struct ResumeCommand {
     #[arg(long = "all", default_value_t = false)]
     all: bool,
 
+    /// Instead of resuming, fork the session into a new one that keeps only
+    /// the first N user turns. The original session is left untouched.
+    #[arg(long = "fork-at", value_name = "N")]
+    fork_at: Option<usize>,
+
     #[clap(flatten)]
     config_overrides: TuiCli,
 }
@@ -238,6 +312,9 @@ enum SandboxCommand {
 
     /// Run a command under Windows restricted token (Windows only).
     Windows(WindowsCommand),
+
+    /// Show what the current sandbox and approval policy would do with a command, without running it.
+    Explain(SandboxExplainCommand),
 }
 
 #[derive(Debug, Parser)]
@@ -284,6 +361,16 @@ struct LoginCommand {
     #[arg(long = "experimental_client-id", value_name = "CLIENT_ID", hide = true)]
     client_id: Option<String>,
 
+    /// Sign in to a model provider configured with an `oidc` issuer, using
+    /// the device authorization flow.
+    #[arg(long = "provider", value_name = "PROVIDER_ID")]
+    provider: Option<String>,
+
+    /// Store this login as a named credential that profiles can select with
+    /// `credential = "<NAME>"`, instead of replacing the default login.
+    #[arg(long = "name", value_name = "NAME", global = true)]
+    name: Option<String>,
+
     #[command(subcommand)]
     action: Option<LoginSubcommand>,
 }
@@ -292,12 +379,51 @@ struct LoginCommand {
 enum LoginSubcommand {
     /// Show login status.
     Status,
+
+    /// List named credentials.
+    List,
 }
 
 #[derive(Debug, Parser)]
 struct LogoutCommand {
     #[clap(skip)]
     config_overrides: CliConfigOverrides,
+
+    /// Remove the stored sign-in for this OIDC provider only.
+    #[arg(long = "provider", value_name = "PROVIDER_ID")]
+    provider: Option<String>,
+
+    /// Log out of this named credential instead of the default login.
+    #[arg(long = "name", value_name = "NAME")]
+    name: Option<String>,
+}
+
+#[derive(Debug, Parser)]
+struct ServeCommand {
+    /// Port to listen on (localhost only). Use 0 to pick a free port.
+    #[arg(long = "port", default_value_t = codex_app_server::DEFAULT_EVENT_STREAM_PORT)]
+    port: u16,
+
+    /// Token clients must present. Defaults to `$CODEX_SERVE_TOKEN`, or a
+    /// random token printed at startup.
+    #[arg(long = "token", value_name = "TOKEN")]
+    token: Option<String>,
+}
+
+#[derive(Debug, Parser)]
+struct WebCommand {
+    /// Port to listen on. Use 0 to pick a free port.
+    #[arg(long = "port", default_value_t = codex_app_server::DEFAULT_WEB_PORT)]
+    port: u16,
+
+    /// Address to bind. Keep the default and use SSH port-forwarding to reach a remote run.
+    #[arg(long = "host", value_name = "IP", default_value = "127.0.0.1")]
+    host: std::net::IpAddr,
+
+    /// Token the dashboard must present. Defaults to `$CODEX_WEB_TOKEN`, or a
+    /// random token printed at startup.
+    #[arg(long = "token", value_name = "TOKEN")]
+    token: Option<String>,
 }
 
 #[derive(Debug, Parser)]
@@ -434,7 +560,12 @@ fn run_update_action(action: UpdateAction) -> anyhow::Result<()> {
     let cmd_str = action.command_str();
     println!("Updating Codex via `{cmd_str}`...");
 
-    let status = {
+    let status = if action == UpdateAction::SelfUpgrade {
+        // Upgrade this binary rather than whichever `codex` comes first on PATH.
+        std::process::Command::new(std::env::current_exe()?)
+            .arg("upgrade")
+            .status()?
+    } else {
         #[cfg(windows)]
         {
             // On Windows, run via cmd.exe so .CMD/.BAT are correctly resolved (PATHEXT semantics).
@@ -462,6 +593,62 @@ fn run_update_action(action: UpdateAction) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Install the latest release on the requested channel.
+///
+/// With `updates.endpoint` configured, the release comes from that server and
+/// replaces this binary once its signature checks out. Otherwise stable
+/// updates go through the package manager that installed Codex.
+async fn run_upgrade(
+    cmd: UpgradeCommand,
+    root_config_overrides: CliConfigOverrides,
+) -> anyhow::Result<()> {
+    let cli_kv_overrides = root_config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(cli_kv_overrides).await?;
+    let channel = cmd
+        .channel
+        .map(UpdateChannel::from)
+        .unwrap_or(config.updates.channel);
+
+    let Some(server) = &config.updates.release_server else {
+        if channel == UpdateChannel::Nightly {
+            anyhow::bail!("the nightly channel requires `updates.endpoint` in config.toml");
+        }
+        let Some(action) = get_package_manager_update_action() else {
+            anyhow::bail!(
+                "Codex was not installed with npm, bun, or Homebrew; download the latest release from https://github.com/openai/codex/releases"
+            );
+        };
+        return run_update_action(action);
+    };
+
+    let current_version = env!("CARGO_PKG_VERSION");
+    let manifest = self_update::fetch_manifest(server, channel).await?;
+    if manifest.version == current_version {
+        println!("Codex {current_version} is the latest {channel} release.");
+        return Ok(());
+    }
+    // The signature binds the version, so a server cannot relabel an old build.
+    if !cmd.allow_downgrade && !self_update::is_newer_release(&manifest.version, current_version) {
+        anyhow::bail!(
+            "the {channel} channel offers Codex {}, which is not newer than {current_version}; pass --allow-downgrade to install it anyway",
+            manifest.version
+        );
+    }
+    println!(
+        "Downloading Codex {} ({channel}) from {}...",
+        manifest.version, server.endpoint
+    );
+    let binary = self_update::download_release(server, channel, &manifest).await?;
+    self_update::install_binary(&binary, &std::env::current_exe()?)?;
+    println!(
+        "Updated Codex {current_version} -> {}. Please restart Codex.",
+        manifest.version
+    );
+    Ok(())
+}
+
 fn run_execpolicycheck(cmd: ExecPolicyCheckCommand) -> anyhow::Result<()> {
     cmd.run()
 }
@@ -525,6 +712,67 @@ enum FeaturesSubcommand {
     Disable(FeatureSetArgs),
 }
 
+#[derive(Debug, Parser)]
+struct AuditCommand {
+    #[command(subcommand)]
+    sub: AuditSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum AuditSubcommand {
+    /// Check that no audit record was edited, inserted, removed, or reordered.
+    Verify(AuditVerifyArgs),
+}
+
+#[derive(Debug, Parser)]
+struct AuditVerifyArgs {
+    /// Audit log to check. Defaults to the configured `[audit_log].path`, or
+    /// `~/.codex/audit.jsonl`.
+    #[arg(value_name = "PATH")]
+    path: Option<PathBuf>,
+}
+
+#[derive(Debug, Parser)]
+struct SessionsCommand {
+    #[command(subcommand)]
+    sub: SessionsSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum SessionsSubcommand {
+    /// Compress existing plain-JSONL rollout files with zstd.
+    Compress,
+
+    /// Encrypt existing rollout files with the data key in the OS keyring.
+    Encrypt,
+
+    /// Export a session as a Markdown or HTML report.
+    Export(SessionExportArgs),
+}
+
+#[derive(Debug, Parser)]
+struct HistoryCommand {
+    #[command(subcommand)]
+    sub: HistorySubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum HistorySubcommand {
+    /// Find sessions whose messages, commands, or edited file paths match a query.
+    Search(HistorySearchArgs),
+}
+
+#[derive(Debug, Parser)]
+struct HistorySearchArgs {
+    /// Words to search for. Every word must match, as a prefix.
+    #[arg(value_name = "QUERY", required = true, num_args = 1..)]
+    query: Vec<String>,
+
+    /// Maximum number of matches to print.
+    #[arg(long, default_value_t = 20)]
+    limit: usize,
+}
+
 #[derive(Debug, Parser)]
 struct FeatureSetArgs {
     /// Feature key to update (for example: unified_exec).
@@ -589,6 +837,30 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
         Some(Subcommand::McpServer) => {
             codex_mcp_server::run_main(codex_linux_sandbox_exe, root_config_overrides).await?;
         }
+        Some(Subcommand::Serve(serve_cli)) => {
+            codex_app_server::run_event_stream_server(
+                codex_linux_sandbox_exe,
+                root_config_overrides,
+                codex_app_server::EventStreamOptions {
+                    port: serve_cli.port,
+                    token: serve_cli.token,
+                },
+            )
+            .await?;
+        }
+        Some(Subcommand::Web(web_cli)) => {
+            codex_app_server::run_main_with_transport(
+                codex_linux_sandbox_exe,
+                root_config_overrides,
+                codex_core::config_loader::LoaderOverrides::default(),
+                false,
+                codex_app_server::AppServerTransport::Web {
+                    bind_address: std::net::SocketAddr::new(web_cli.host, web_cli.port),
+                    token: web_cli.token,
+                },
+            )
+            .await?;
+        }
         Some(Subcommand::Mcp(mut mcp_cli)) => {
             // Propagate any root-level config overrides (e.g. `-c key=value`).
             prepend_config_flags(&mut mcp_cli.config_overrides, root_config_overrides.clone());
@@ -632,6 +904,7 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             session_id,
             last,
             all,
+            fork_at,
             config_overrides,
         })) => {
             interactive = finalize_resume_interactive(
@@ -640,6 +913,7 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 session_id,
                 last,
                 all,
+                fork_at,
                 config_overrides,
             );
             let exit_info = run_interactive_tui(interactive, codex_linux_sandbox_exe).await?;
@@ -667,14 +941,22 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 &mut login_cli.config_overrides,
                 root_config_overrides.clone(),
             );
+            let name = login_cli.name;
             match login_cli.action {
                 Some(LoginSubcommand::Status) => {
-                    run_login_status(login_cli.config_overrides).await;
+                    run_login_status(login_cli.config_overrides, name).await;
+                }
+                Some(LoginSubcommand::List) => {
+                    run_login_list(login_cli.config_overrides).await;
                 }
                 None => {
-                    if login_cli.use_device_code {
+                    if let Some(provider_id) = login_cli.provider {
+                        run_login_with_provider(login_cli.config_overrides, name, provider_id)
+                            .await;
+                    } else if login_cli.use_device_code {
                         run_login_with_device_code(
                             login_cli.config_overrides,
+                            name,
                             login_cli.issuer_base_url,
                             login_cli.client_id,
                         )
@@ -686,9 +968,9 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                         std::process::exit(1);
                     } else if login_cli.with_api_key {
                         let api_key = read_api_key_from_stdin();
-                        run_login_with_api_key(login_cli.config_overrides, api_key).await;
+                        run_login_with_api_key(login_cli.config_overrides, name, api_key).await;
                     } else {
-                        run_login_with_chatgpt(login_cli.config_overrides).await;
+                        run_login_with_chatgpt(login_cli.config_overrides, name).await;
                     }
                 }
             }
@@ -698,10 +980,29 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 &mut logout_cli.config_overrides,
                 root_config_overrides.clone(),
             );
-            run_logout(logout_cli.config_overrides).await;
+            match logout_cli.provider {
+                Some(provider_id) => {
+                    run_logout_provider(logout_cli.config_overrides, logout_cli.name, provider_id)
+                        .await;
+                }
+                None => run_logout(logout_cli.config_overrides, logout_cli.name).await,
+            }
         }
         Some(Subcommand::Completion(completion_cli)) => {
-            print_completion(completion_cli);
+            print_completion(completion_cli)?;
+        }
+        Some(Subcommand::Complete(complete_args)) => {
+            if !completion::run_complete(complete_args, root_config_overrides).await? {
+                std::process::exit(1);
+            }
+        }
+        Some(Subcommand::Upgrade(upgrade_cmd)) => {
+            run_upgrade(upgrade_cmd, root_config_overrides).await?;
+        }
+        Some(Subcommand::Doctor(doctor_cmd)) => {
+            if !doctor::run_doctor(doctor_cmd, root_config_overrides).await? {
+                std::process::exit(1);
+            }
         }
         Some(Subcommand::Cloud(mut cloud_cli)) => {
             prepend_config_flags(
@@ -744,6 +1045,13 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 )
                 .await?;
             }
+            SandboxCommand::Explain(mut explain_cli) => {
+                prepend_config_flags(
+                    &mut explain_cli.config_overrides,
+                    root_config_overrides.clone(),
+                );
+                codex_cli::debug_sandbox::explain_command_under_sandbox(explain_cli).await?;
+            }
         },
         Some(Subcommand::Debug(DebugCommand { subcommand })) => match subcommand {
             DebugSubcommand::AppServer(cmd) => {
@@ -753,6 +1061,11 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
         Some(Subcommand::Execpolicy(ExecpolicyCommand { sub })) => match sub {
             ExecpolicySubcommand::Check(cmd) => run_execpolicycheck(cmd)?,
         },
+        Some(Subcommand::Policy(policy_cmd)) => {
+            if !policy_cmd::run_policy_command(policy_cmd, root_config_overrides).await? {
+                std::process::exit(1);
+            }
+        }
         Some(Subcommand::Apply(mut apply_cli)) => {
             prepend_config_flags(
                 &mut apply_cli.config_overrides,
@@ -818,8 +1131,104 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 disable_feature_in_config(&interactive, &feature).await?;
             }
         },
+        Some(Subcommand::Replay(mut replay_args)) => {
+            prepend_config_flags(
+                &mut replay_args.config_overrides,
+                root_config_overrides.clone(),
+            );
+            codex_exec::run_replay(replay_args).await?;
+        }
+        Some(Subcommand::Audit(AuditCommand { sub })) => match sub {
+            AuditSubcommand::Verify(args) => {
+                run_audit_verify(args, root_config_overrides).await?;
+            }
+        },
+        Some(Subcommand::Sessions(SessionsCommand { sub })) => match sub {
+            SessionsSubcommand::Compress => run_sessions_compress().await?,
+            SessionsSubcommand::Encrypt => run_sessions_encrypt().await?,
+            SessionsSubcommand::Export(args) => codex_exec::run_session_export(args).await?,
+        },
+        Some(Subcommand::History(HistoryCommand { sub })) => match sub {
+            HistorySubcommand::Search(args) => run_history_search(args).await?,
+        },
+    }
+
+    Ok(())
+}
+
+async fn run_audit_verify(
+    args: AuditVerifyArgs,
+    root_config_overrides: CliConfigOverrides,
+) -> anyhow::Result<()> {
+    let path = match args.path {
+        Some(path) => path,
+        None => {
+            let cli_kv_overrides = root_config_overrides
+                .parse_overrides()
+                .map_err(anyhow::Error::msg)?;
+            let config = Config::load_with_cli_overrides(cli_kv_overrides).await?;
+            config.audit_log_path.unwrap_or_else(|| {
+                config
+                    .codex_home
+                    .join(codex_core::audit_log::AUDIT_LOG_FILENAME)
+            })
+        }
+    };
+    let verification = codex_core::audit_log::verify(&path)?;
+    match verification.head_hash {
+        Some(head_hash) => println!(
+            "{}: {} records verified, head hash {head_hash}",
+            path.display(),
+            verification.records
+        ),
+        None => println!("{}: empty audit log", path.display()),
+    }
+    Ok(())
+}
+
+async fn run_sessions_compress() -> anyhow::Result<()> {
+    let codex_home = find_codex_home()?;
+    let summary = codex_core::compress_rollouts(&codex_home).await?;
+    for path in &summary.skipped_live {
+        println!("skipped {} (session is running)", path.display());
     }
+    println!(
+        "compressed {} rollout files: {} -> {} bytes",
+        summary.compressed, summary.bytes_before, summary.bytes_after
+    );
+    Ok(())
+}
 
+async fn run_sessions_encrypt() -> anyhow::Result<()> {
+    let codex_home = find_codex_home()?;
+    let summary = codex_core::encrypt_rollouts(&codex_home).await?;
+    for path in &summary.skipped_live {
+        println!("skipped {} (session is running)", path.display());
+    }
+    println!("encrypted {} rollout files", summary.encrypted);
+    Ok(())
+}
+
+async fn run_history_search(args: HistorySearchArgs) -> anyhow::Result<()> {
+    let codex_home = find_codex_home()?;
+    codex_core::refresh_history_index(&codex_home).await?;
+    let query = args.query.join(" ");
+    let hits = codex_core::search_history(&codex_home, &query, args.limit).await?;
+    if hits.is_empty() {
+        println!("No sessions match \"{query}\".");
+        return Ok(());
+    }
+    for hit in &hits {
+        println!(
+            "{}  turn {}  {}  {}",
+            hit.thread_id,
+            hit.turn,
+            hit.kind.as_str(),
+            hit.snippet.replace('\n', " ")
+        );
+    }
+    println!();
+    println!("Resume a session with `codex resume <SESSION_ID>`.");
     Ok(())
 }
 
@@ -931,8 +1340,24 @@ fn finalize_resume_interactive(
     session_id: Option<String>,
     last: bool,
     show_all: bool,
+    fork_at: Option<usize>,
     resume_cli: TuiCli,
 ) -> TuiCli {
+    // `--fork-at` selects the session the same way resume does, but forks it
+    // at the requested turn instead of continuing it in place.
+    if let Some(nth_user_message) = fork_at {
+        let mut interactive = finalize_fork_interactive(
+            interactive,
+            root_config_overrides,
+            session_id,
+            last,
+            show_all,
+            resume_cli,
+        );
+        interactive.fork_at = Some(nth_user_message);
+        return interactive;
+    }
+
     // Start with the parsed interactive CLI so resume shares the same
     // configuration surface area as `codex` without additional flags.
     let resume_session_id = session_id;
@@ -989,6 +1414,15 @@ fn merge_interactive_cli_flags(interactive: &mut TuiCli, subcommand_cli: TuiCli)
     if let Some(profile) = subcommand_cli.config_profile {
         interactive.config_profile = Some(profile);
     }
+    if let Some(orchestration) = subcommand_cli.orchestration {
+        interactive.orchestration = Some(orchestration);
+    }
+    if subcommand_cli.offline {
+        interactive.offline = true;
+    }
+    if let Some(wire_log) = subcommand_cli.wire_log {
+        interactive.wire_log = Some(wire_log);
+    }
     if let Some(sandbox) = subcommand_cli.sandbox_mode {
         interactive.sandbox_mode = Some(sandbox);
     }
@@ -1001,18 +1435,27 @@ fn merge_interactive_cli_flags(interactive: &mut TuiCli, subcommand_cli: TuiCli)
     if subcommand_cli.dangerously_bypass_approvals_and_sandbox {
         interactive.dangerously_bypass_approvals_and_sandbox = true;
     }
+    if subcommand_cli.read_only {
+        interactive.read_only = true;
+    }
     if let Some(cwd) = subcommand_cli.cwd {
         interactive.cwd = Some(cwd);
     }
     if subcommand_cli.web_search {
         interactive.web_search = true;
     }
+    if subcommand_cli.screen_reader {
+        interactive.screen_reader = true;
+    }
     if !subcommand_cli.images.is_empty() {
         interactive.images = subcommand_cli.images;
     }
     if !subcommand_cli.add_dir.is_empty() {
         interactive.add_dir.extend(subcommand_cli.add_dir);
     }
+    if !subcommand_cli.scope.is_empty() {
+        interactive.scope = subcommand_cli.scope;
+    }
     if let Some(prompt) = subcommand_cli.prompt {
         // Normalize CRLF/CR to LF so CLI-provided text can't leak `\r` into TUI state.
         interactive.prompt = Some(prompt.replace("\r\n", "\n").replace('\r', "\n"));
@@ -1024,10 +1467,9 @@ fn merge_interactive_cli_flags(interactive: &mut TuiCli, subcommand_cli: TuiCli)
         .extend(subcommand_cli.config_overrides.raw_overrides);
 }
 
-fn print_completion(cmd: CompletionCommand) {
+fn print_completion(cmd: CompletionCommand) -> std::io::Result<()> {
     let mut app = MultitoolCli::command();
-    let name = "codex";
-    generate(cmd.shell, &mut app, name, &mut std::io::stdout());
+    completion::write_completion_script(cmd.shell, &mut app, &mut std::io::stdout())
 }
 
 #[cfg(test)]
@@ -1051,6 +1493,7 @@ mod tests {
             session_id,
             last,
             all,
+            fork_at,
             config_overrides: resume_cli,
         }) = subcommand.expect("resume present")
         else {
@@ -1063,6 +1506,7 @@ mod tests {
             session_id,
             last,
             all,
+            fork_at,
             resume_cli,
         )
     }
@@ -1301,6 +1745,17 @@ mod tests {
         assert_eq!(interactive.resume_session_id, None);
     }
 
+    #[test]
+    fn resume_fork_at_forks_selected_session() {
+        let interactive =
+            finalize_resume_from_args(["codex", "resume", "1234", "--fork-at", "2"].as_ref());
+        assert!(!interactive.resume_picker);
+        assert_eq!(interactive.resume_session_id, None);
+        assert!(!interactive.fork_picker);
+        assert_eq!(interactive.fork_session_id.as_deref(), Some("1234"));
+        assert_eq!(interactive.fork_at, Some(2));
+    }
+
     #[test]
     fn fork_picker_logic_none_and_not_last() {
         let interactive = finalize_fork_from_args(["codex", "fork"].as_ref());
@@ -1375,6 +1830,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn web_defaults_to_localhost() {
+        let cli = MultitoolCli::try_parse_from(["codex", "web", "--port", "0"])
+            .expect("parse should succeed");
+        let Some(Subcommand::Web(web)) = cli.subcommand else {
+            unreachable!()
+        };
+        assert_eq!(
+            std::net::SocketAddr::new(web.host, web.port),
+            "127.0.0.1:0".parse().expect("valid socket address")
+        );
+        assert_eq!(web.token, None);
+    }
+
     #[test]
     fn app_server_listen_invalid_url_fails_to_parse() {
         let parse_result =
@@ -1408,6 +1877,187 @@ mod tests {
         assert_eq!(feature, "shell_tool");
     }
 
+    #[test]
+    fn replay_parses_rollout_and_format() {
+        let cli = MultitoolCli::try_parse_from(["codex", "replay", "--json", "/tmp/rollout.jsonl"])
+            .expect("parse should succeed");
+        let Some(Subcommand::Replay(args)) = cli.subcommand else {
+            panic!("expected replay subcommand");
+        };
+        assert_eq!(args.rollout, PathBuf::from("/tmp/rollout.jsonl"));
+        assert!(args.json);
+        assert!(!args.raw);
+
+        assert!(
+            MultitoolCli::try_parse_from(["codex", "replay", "--json", "--raw", "/tmp/r.jsonl"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn sandbox_explain_parses_trailing_command() {
+        let cli = MultitoolCli::try_parse_from([
+            "codex", "sandbox", "explain", "--json", "git", "push", "--force",
+        ])
+        .expect("parse should succeed");
+        let Some(Subcommand::Sandbox(SandboxArgs {
+            cmd: SandboxCommand::Explain(explain),
+        })) = cli.subcommand
+        else {
+            panic!("expected sandbox explain subcommand");
+        };
+        assert!(explain.json);
+        assert_eq!(explain.command, vec!["git", "push", "--force"]);
+    }
+
+    #[test]
+    fn audit_verify_parses_optional_path() {
+        let cli = MultitoolCli::try_parse_from(["codex", "audit", "verify", "/tmp/audit.jsonl"])
+            .expect("parse should succeed");
+        let Some(Subcommand::Audit(AuditCommand {
+            sub: AuditSubcommand::Verify(AuditVerifyArgs { path }),
+        })) = cli.subcommand
+        else {
+            panic!("expected audit verify subcommand");
+        };
+        assert_eq!(path, Some(PathBuf::from("/tmp/audit.jsonl")));
+    }
+
+    #[test]
+    fn login_and_logout_accept_provider() {
+        let cli = MultitoolCli::try_parse_from(["codex", "login", "--provider", "corp"])
+            .expect("parse should succeed");
+        let Some(Subcommand::Login(login)) = cli.subcommand else {
+            panic!("expected login subcommand");
+        };
+        assert_eq!(login.provider.as_deref(), Some("corp"));
+
+        let cli = MultitoolCli::try_parse_from(["codex", "logout", "--provider", "corp"])
+            .expect("parse should succeed");
+        let Some(Subcommand::Logout(logout)) = cli.subcommand else {
+            panic!("expected logout subcommand");
+        };
+        assert_eq!(logout.provider.as_deref(), Some("corp"));
+    }
+
+    #[test]
+    fn login_name_applies_to_login_status_and_logout() {
+        let cli = MultitoolCli::try_parse_from(["codex", "login", "--name", "work"])
+            .expect("parse should succeed");
+        let Some(Subcommand::Login(login)) = cli.subcommand else {
+            panic!("expected login subcommand");
+        };
+        assert_eq!(login.name.as_deref(), Some("work"));
+        assert!(login.action.is_none());
+
+        let cli = MultitoolCli::try_parse_from(["codex", "login", "status", "--name", "work"])
+            .expect("parse should succeed");
+        let Some(Subcommand::Login(login)) = cli.subcommand else {
+            panic!("expected login subcommand");
+        };
+        assert_eq!(login.name.as_deref(), Some("work"));
+        assert!(matches!(login.action, Some(LoginSubcommand::Status)));
+
+        let cli = MultitoolCli::try_parse_from(["codex", "logout", "--name", "work"])
+            .expect("parse should succeed");
+        let Some(Subcommand::Logout(logout)) = cli.subcommand else {
+            panic!("expected logout subcommand");
+        };
+        assert_eq!(logout.name.as_deref(), Some("work"));
+    }
+
+    #[test]
+    fn sessions_compress_parses() {
+        let cli = MultitoolCli::try_parse_from(["codex", "sessions", "compress"])
+            .expect("parse should succeed");
+        assert!(matches!(
+            cli.subcommand,
+            Some(Subcommand::Sessions(SessionsCommand {
+                sub: SessionsSubcommand::Compress,
+            }))
+        ));
+    }
+
+    #[test]
+    fn sessions_encrypt_parses() {
+        let cli = MultitoolCli::try_parse_from(["codex", "sessions", "encrypt"])
+            .expect("parse should succeed");
+        assert!(matches!(
+            cli.subcommand,
+            Some(Subcommand::Sessions(SessionsCommand {
+                sub: SessionsSubcommand::Encrypt,
+            }))
+        ));
+    }
+
+    #[test]
+    fn upgrade_parses_channel() {
+        let cli = MultitoolCli::try_parse_from(["codex", "upgrade", "--channel", "nightly"])
+            .expect("parse should succeed");
+        let Some(Subcommand::Upgrade(UpgradeCommand {
+            channel,
+            allow_downgrade,
+        })) = cli.subcommand
+        else {
+            panic!("expected upgrade subcommand");
+        };
+        assert_eq!(
+            (channel, allow_downgrade),
+            (Some(UpgradeChannelArg::Nightly), false)
+        );
+
+        assert!(MultitoolCli::try_parse_from(["codex", "upgrade", "--channel", "beta"]).is_err());
+    }
+
+    #[test]
+    fn doctor_parses_json_flag() {
+        let cli = MultitoolCli::try_parse_from(["codex", "doctor", "--json"])
+            .expect("parse should succeed");
+        assert_matches!(cli.subcommand, Some(Subcommand::Doctor(_)));
+    }
+
+    #[test]
+    fn sessions_export_parses_format_and_output() {
+        let cli = MultitoolCli::try_parse_from([
+            "codex",
+            "sessions",
+            "export",
+            "my-thread",
+            "--format",
+            "html",
+            "-o",
+            "/tmp/report.html",
+        ])
+        .expect("parse should succeed");
+        let Some(Subcommand::Sessions(SessionsCommand {
+            sub: SessionsSubcommand::Export(args),
+        })) = cli.subcommand
+        else {
+            panic!("expected sessions export subcommand");
+        };
+        assert_eq!(args.session, "my-thread");
+        assert_eq!(args.format, codex_exec::ExportFormat::Html);
+        assert_eq!(args.output, Some(PathBuf::from("/tmp/report.html")));
+        assert_eq!(args.input_price, None);
+    }
+
+    #[test]
+    fn history_search_joins_query_words() {
+        let cli = MultitoolCli::try_parse_from([
+            "codex", "history", "search", "--limit", "5", "flaky", "test",
+        ])
+        .expect("parse should succeed");
+        let Some(Subcommand::History(HistoryCommand {
+            sub: HistorySubcommand::Search(HistorySearchArgs { query, limit }),
+        })) = cli.subcommand
+        else {
+            panic!("expected history search subcommand");
+        };
+        assert_eq!(query, vec!["flaky".to_string(), "test".to_string()]);
+        assert_eq!(limit, 5);
+        assert!(MultitoolCli::try_parse_from(["codex", "history", "search"]).is_err());
+    }
+
     #[test]
     fn feature_toggles_known_features_generate_overrides() {
         let toggles = FeatureToggles {
@@ -0,0 +1,242 @@
+//! `codex policy test`: shows how the rules files decide a command, for
+//! developing `prefix_rule` and `command_rule` policies. Nothing is executed.
+//!
+//! By default the command is checked against the rules a session in the
+//! current directory would load, with the facts (working directory, network
+//! access, risk) the current config implies. `--rules`, `--cwd`, `--network`
+//! and `--risk` override them, and `--expect` makes the command usable as a
+//! check in a policy repository's CI.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use codex_core::command_facts;
+use codex_core::config::Config;
+use codex_core::config::ConfigOverrides;
+use codex_core::evaluate_command;
+use codex_core::format_exec_policy_error_with_source;
+use codex_core::load_exec_policy;
+use codex_core::sandbox_explain::command_for_line;
+use codex_execpolicy::CommandFacts;
+use codex_execpolicy::Decision;
+use codex_execpolicy::Evaluation;
+use codex_execpolicy::RuleMatch;
+use codex_execpolicy::execpolicycheck::load_policies;
+use codex_protocol::models::SandboxPermissions;
+use codex_utils_cli::CliConfigOverrides;
+use serde::Serialize;
+use shlex::try_join as shlex_try_join;
+
+#[derive(Debug, Parser)]
+pub(crate) struct PolicyCommand {
+    #[command(subcommand)]
+    sub: PolicySubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum PolicySubcommand {
+    /// Evaluate a command against the rules and print the decision.
+    Test(PolicyTestCommand),
+}
+
+#[derive(Debug, Parser)]
+struct PolicyTestCommand {
+    /// Rules files to evaluate instead of the ones the config loads (repeatable).
+    #[arg(short = 'r', long = "rules", value_name = "PATH")]
+    rules: Vec<PathBuf>,
+
+    /// Directory the command runs in. Defaults to the current directory.
+    #[arg(long, value_name = "DIR")]
+    cwd: Option<PathBuf>,
+
+    /// Whether the command has network access. Defaults to what the sandbox policy allows.
+    #[arg(long, value_name = "BOOL")]
+    network: Option<bool>,
+
+    /// Risk score to use instead of the computed one: 0 known safe, 1 unknown, 2 possibly dangerous.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(0..=2))]
+    risk: Option<u8>,
+
+    /// Exit with status 1 unless the decision is this one (allow, prompt or forbidden).
+    #[arg(long, value_name = "DECISION", value_parser = parse_decision)]
+    expect: Option<Decision>,
+
+    /// Print the result as JSON.
+    #[arg(long, default_value_t = false)]
+    json: bool,
+
+    /// Command to evaluate. A single argument is treated as a shell command line.
+    #[arg(
+        value_name = "COMMAND",
+        required = true,
+        trailing_var_arg = true,
+        allow_hyphen_values = true
+    )]
+    command: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct PolicyTestOutput<'a> {
+    facts: &'a CommandFacts,
+    #[serde(flatten)]
+    evaluation: &'a Evaluation,
+}
+
+fn parse_decision(raw: &str) -> Result<Decision, String> {
+    Decision::parse(raw).map_err(|err| err.to_string())
+}
+
+/// Runs `codex policy`. Returns false when `--expect` did not hold.
+pub(crate) async fn run_policy_command(
+    cmd: PolicyCommand,
+    root_config_overrides: CliConfigOverrides,
+) -> anyhow::Result<bool> {
+    match cmd.sub {
+        PolicySubcommand::Test(cmd) => run_policy_test(cmd, root_config_overrides).await,
+    }
+}
+
+async fn run_policy_test(
+    cmd: PolicyTestCommand,
+    root_config_overrides: CliConfigOverrides,
+) -> anyhow::Result<bool> {
+    let PolicyTestCommand {
+        rules,
+        cwd,
+        network,
+        risk,
+        expect,
+        json,
+        command,
+    } = cmd;
+    let config = Config::load_with_cli_overrides_and_harness_overrides(
+        root_config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?,
+        ConfigOverrides {
+            cwd,
+            ..Default::default()
+        },
+    )
+    .await?;
+    let policy = if rules.is_empty() {
+        load_exec_policy(&config.config_layer_stack)
+            .await
+            .map_err(|err| anyhow::anyhow!(format_exec_policy_error_with_source(&err)))?
+    } else {
+        load_policies(&rules)?
+    };
+
+    let command = match command.as_slice() {
+        [line] if line.contains(char::is_whitespace) => command_for_line(line),
+        _ => command,
+    };
+    let sandbox_policy = config.permissions.sandbox_policy.get();
+    let mut facts = command_facts(
+        &command,
+        Some(&config.cwd),
+        sandbox_policy,
+        SandboxPermissions::UseDefault,
+    );
+    if let Some(network) = network {
+        facts.network = Some(network);
+    }
+    if let Some(risk) = risk {
+        facts.risk = risk;
+    }
+    let evaluation = evaluate_command(
+        &policy,
+        &facts,
+        config.permissions.approval_policy.value(),
+        sandbox_policy,
+        SandboxPermissions::UseDefault,
+    );
+
+    if json {
+        let output = PolicyTestOutput {
+            facts: &facts,
+            evaluation: &evaluation,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        print_report(&facts, &evaluation);
+    }
+
+    match expect {
+        Some(expected) if expected != evaluation.decision => {
+            eprintln!(
+                "expected {}, got {}",
+                decision_name(expected),
+                decision_name(evaluation.decision)
+            );
+            Ok(false)
+        }
+        _ => Ok(true),
+    }
+}
+
+fn print_report(facts: &CommandFacts, evaluation: &Evaluation) {
+    println!("Command: {}", render_command(&facts.argv));
+    if facts.subcommands.len() > 1 {
+        let subcommands: Vec<String> = facts
+            .subcommands
+            .iter()
+            .map(|command| render_command(command))
+            .collect();
+        println!("Subcommands: {}", subcommands.join("; "));
+    }
+    if let Some(cwd) = &facts.cwd {
+        println!("Working directory: {}", cwd.display());
+    }
+    println!("Risk: {}", facts.risk);
+    match facts.network {
+        Some(true) => println!("Network: yes"),
+        Some(false) => println!("Network: no"),
+        None => println!("Network: unknown"),
+    }
+    println!("Matched rules:");
+    for rule_match in &evaluation.matched_rules {
+        println!("  {}", describe_match(rule_match));
+    }
+    println!("Decision: {}", decision_name(evaluation.decision));
+}
+
+fn describe_match(rule_match: &RuleMatch) -> String {
+    let (rule, decision, justification) = match rule_match {
+        RuleMatch::PrefixRuleMatch {
+            matched_prefix,
+            decision,
+            justification,
+        } => (
+            format!("prefix_rule `{}`", render_command(matched_prefix)),
+            *decision,
+            justification.as_deref(),
+        ),
+        RuleMatch::CommandRuleMatch {
+            rule,
+            decision,
+            justification,
+        } => (format!("`{rule}`"), *decision, justification.as_deref()),
+        RuleMatch::HeuristicsRuleMatch { command, decision } => (
+            format!("no rule for `{}`, default", render_command(command)),
+            *decision,
+            None,
+        ),
+    };
+    match justification {
+        Some(justification) => format!("{rule}: {} ({justification})", decision_name(decision)),
+        None => format!("{rule}: {}", decision_name(decision)),
+    }
+}
+
+fn decision_name(decision: Decision) -> &'static str {
+    match decision {
+        Decision::Allow => "allow",
+        Decision::Prompt => "prompt",
+        Decision::Forbidden => "forbidden",
+    }
+}
+
+fn render_command(command: &[String]) -> String {
+    shlex_try_join(command.iter().map(String::as_str)).unwrap_or_else(|_| command.join(" "))
+}
@@ -0,0 +1,70 @@
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex")?);
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+#[test]
+fn completion_scripts_include_dynamic_wrapper() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    for (shell, wrapper) in [
+        ("bash", "complete -F _codex_dynamic"),
+        ("zsh", "compdef _codex_dynamic codex"),
+        ("fish", "function __codex_dynamic_complete"),
+    ] {
+        codex_command(codex_home.path())?
+            .args(["completion", shell])
+            .assert()
+            .success()
+            .stdout(contains(wrapper));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn complete_lists_profiles_and_mcp_servers() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        r#"
+[profiles.work]
+model = "gpt-5.2-codex"
+
+[profiles.fast]
+
+[mcp_servers.docs]
+command = "docs-server"
+"#,
+    )?;
+
+    let output = codex_command(codex_home.path())?
+        .args(["__complete", "--", "exec", "-p"])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "fast\nwork\tgpt-5.2-codex\n"
+    );
+
+    let output = codex_command(codex_home.path())?
+        .args(["__complete", "--", "mcp", "remove"])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout)?, "docs\n");
+
+    codex_command(codex_home.path())?
+        .args(["__complete", "--", "mcp", "add"])
+        .assert()
+        .failure();
+
+    Ok(())
+}
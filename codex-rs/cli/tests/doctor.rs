@@ -0,0 +1,39 @@
+use std::path::Path;
+
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex")?);
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+#[test]
+fn doctor_json_reports_invalid_config() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        "model_reasoning_effort = \"extreme\"\n",
+    )?;
+
+    let output = codex_command(codex_home.path())?
+        .args(["doctor", "--json"])
+        .output()?;
+    assert!(!output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let checks = report["checks"].as_array().expect("checks array");
+    let status_of = |name: &str| {
+        checks
+            .iter()
+            .find(|check| check["name"] == name)
+            .map(|check| check["status"].clone())
+    };
+    assert_eq!(status_of("config"), Some(serde_json::json!("fail")));
+    assert_eq!(status_of("auth"), Some(serde_json::json!("skip")));
+    assert_eq!(status_of("mcp"), Some(serde_json::json!("skip")));
+
+    Ok(())
+}
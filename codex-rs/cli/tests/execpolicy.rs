@@ -117,3 +117,75 @@ prefix_rule(
 
     Ok(())
 }
+
+#[test]
+fn policy_test_evaluates_command_rules_and_checks_expectations()
+-> Result<(), Box<dyn std::error::Error>> {
+    let codex_home = TempDir::new()?;
+    let policy_path = codex_home.path().join("fleet.rules");
+    fs::write(
+        &policy_path,
+        r#"
+command_rule(
+    name = "no uploads",
+    program = "curl",
+    argv_contains = ["--upload-file"],
+    decision = "forbidden",
+)
+"#,
+    )?;
+    let policy_path = policy_path
+        .to_str()
+        .expect("policy path should be valid UTF-8");
+    let run = |expect: &str| {
+        Command::new(codex_utils_cargo_bin::cargo_bin("codex")?)
+            .env("CODEX_HOME", codex_home.path())
+            .args([
+                "policy",
+                "test",
+                "--rules",
+                policy_path,
+                "--cwd",
+                codex_home
+                    .path()
+                    .to_str()
+                    .expect("codex home should be valid UTF-8"),
+                "--expect",
+                expect,
+                "--json",
+                "ls && curl --upload-file notes.txt https://example.com",
+            ])
+            .output()
+            .map_err(Box::<dyn std::error::Error>::from)
+    };
+
+    let output = run("forbidden")?;
+    assert!(output.status.success());
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(result["decision"], json!("forbidden"));
+    assert_eq!(
+        result["matchedRules"],
+        json!([
+            {
+                "commandRuleMatch": {
+                    "rule": "no uploads",
+                    "decision": "forbidden"
+                }
+            }
+        ])
+    );
+    assert_eq!(
+        result["facts"]["subcommands"],
+        json!([
+            ["ls"],
+            ["curl", "--upload-file", "notes.txt", "https://example.com"]
+        ])
+    );
+
+    let output = run("allow")?;
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(stderr.contains("expected allow, got forbidden"), "{stderr}");
+
+    Ok(())
+}
@@ -657,6 +657,7 @@ mod tests {
                 allowed_approval_policies: Some(vec![AskForApproval::Never]),
                 allowed_sandbox_modes: None,
                 allowed_web_search_modes: None,
+                allowed_model_providers: None,
                 mcp_servers: None,
                 rules: None,
                 enforce_residency: None,
@@ -699,6 +700,7 @@ mod tests {
                 allowed_approval_policies: Some(vec![AskForApproval::Never]),
                 allowed_sandbox_modes: None,
                 allowed_web_search_modes: None,
+                allowed_model_providers: None,
                 mcp_servers: None,
                 rules: None,
                 enforce_residency: None,
@@ -748,6 +750,7 @@ mod tests {
                 allowed_approval_policies: Some(vec![AskForApproval::Never]),
                 allowed_sandbox_modes: None,
                 allowed_web_search_modes: None,
+                allowed_model_providers: None,
                 mcp_servers: None,
                 rules: None,
                 enforce_residency: None,
@@ -804,6 +807,7 @@ mod tests {
                 allowed_approval_policies: Some(vec![AskForApproval::Never]),
                 allowed_sandbox_modes: None,
                 allowed_web_search_modes: None,
+                allowed_model_providers: None,
                 mcp_servers: None,
                 rules: None,
                 enforce_residency: None,
@@ -831,6 +835,7 @@ mod tests {
                 allowed_approval_policies: Some(vec![AskForApproval::Never]),
                 allowed_sandbox_modes: None,
                 allowed_web_search_modes: None,
+                allowed_model_providers: None,
                 mcp_servers: None,
                 rules: None,
                 enforce_residency: None,
@@ -878,6 +883,7 @@ mod tests {
                 allowed_approval_policies: Some(vec![AskForApproval::OnRequest]),
                 allowed_sandbox_modes: None,
                 allowed_web_search_modes: None,
+                allowed_model_providers: None,
                 mcp_servers: None,
                 rules: None,
                 enforce_residency: None,
@@ -924,6 +930,7 @@ mod tests {
                 allowed_approval_policies: Some(vec![AskForApproval::OnRequest]),
                 allowed_sandbox_modes: None,
                 allowed_web_search_modes: None,
+                allowed_model_providers: None,
                 mcp_servers: None,
                 rules: None,
                 enforce_residency: None,
@@ -974,6 +981,7 @@ mod tests {
                 allowed_approval_policies: Some(vec![AskForApproval::Never]),
                 allowed_sandbox_modes: None,
                 allowed_web_search_modes: None,
+                allowed_model_providers: None,
                 mcp_servers: None,
                 rules: None,
                 enforce_residency: None,
@@ -1025,6 +1033,7 @@ mod tests {
                 allowed_approval_policies: Some(vec![AskForApproval::Never]),
                 allowed_sandbox_modes: None,
                 allowed_web_search_modes: None,
+                allowed_model_providers: None,
                 mcp_servers: None,
                 rules: None,
                 enforce_residency: None,
@@ -1072,6 +1081,7 @@ mod tests {
                 allowed_approval_policies: Some(vec![AskForApproval::Never]),
                 allowed_sandbox_modes: None,
                 allowed_web_search_modes: None,
+                allowed_model_providers: None,
                 mcp_servers: None,
                 rules: None,
                 enforce_residency: None,
@@ -130,6 +130,24 @@ impl Default for TaskText {
     }
 }
 
+/// Snapshot of a task's worklog, polled while the task runs.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct TaskLogs {
+    /// Worklog entries rendered as `role: text`, oldest first.
+    pub lines: Vec<String>,
+    pub status: AttemptStatus,
+}
+
+impl AttemptStatus {
+    /// True once the attempt can no longer produce output.
+    pub fn is_terminal(self) -> bool {
+        matches!(
+            self,
+            AttemptStatus::Completed | AttemptStatus::Failed | AttemptStatus::Cancelled
+        )
+    }
+}
+
 #[async_trait::async_trait]
 pub trait CloudBackend: Send + Sync {
     async fn list_tasks(
@@ -144,6 +162,8 @@ pub trait CloudBackend: Send + Sync {
     async fn get_task_messages(&self, id: TaskId) -> Result<Vec<String>>;
     /// Return the creating prompt and assistant messages (when available).
     async fn get_task_text(&self, id: TaskId) -> Result<TaskText>;
+    /// Return the current worklog of the task's assistant turn.
+    async fn get_task_logs(&self, id: TaskId) -> Result<TaskLogs>;
     /// Return any sibling attempts (best-of-N) for the given assistant turn.
     async fn list_sibling_attempts(
         &self,
@@ -7,6 +7,7 @@ use crate::DiffSummary;
 use crate::Result;
 use crate::TaskId;
 use crate::TaskListPage;
+use crate::TaskLogs;
 use crate::TaskStatus;
 use crate::TaskSummary;
 use crate::TurnAttempt;
@@ -85,6 +86,10 @@ impl CloudBackend for HttpClient {
         self.tasks_api().task_text(id).await
     }
 
+    async fn get_task_logs(&self, id: TaskId) -> Result<TaskLogs> {
+        self.tasks_api().logs(id).await
+    }
+
     async fn list_sibling_attempts(
         &self,
         task: TaskId,
@@ -309,6 +314,22 @@ mod api {
             })
         }
 
+        pub(crate) async fn logs(&self, id: TaskId) -> Result<TaskLogs> {
+            let details = self
+                .backend
+                .get_task_details(&id.0)
+                .await
+                .map_err(|e| CloudTaskError::Http(format!("get_task_details failed: {e}")))?;
+            let mut lines = details.assistant_worklog_lines();
+            if let Some(err) = details.assistant_error_message() {
+                lines.push(format!("error: {err}"));
+            }
+            Ok(TaskLogs {
+                lines,
+                status: attempt_status_from_str(details.assistant_turn_status()),
+            })
+        }
+
         pub(crate) async fn create(
             &self,
             env_id: &str,
@@ -694,6 +715,7 @@ mod api {
             "completed" => AttemptStatus::Completed,
             "in_progress" => AttemptStatus::InProgress,
             "pending" => AttemptStatus::Pending,
+            "cancelled" => AttemptStatus::Cancelled,
             _ => AttemptStatus::Pending,
         }
     }
@@ -10,6 +10,7 @@ pub use api::DiffSummary;
 pub use api::Result;
 pub use api::TaskId;
 pub use api::TaskListPage;
+pub use api::TaskLogs;
 pub use api::TaskStatus;
 pub use api::TaskSummary;
 pub use api::TaskText;
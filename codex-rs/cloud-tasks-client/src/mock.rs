@@ -5,6 +5,7 @@ use crate::CloudTaskError;
 use crate::DiffSummary;
 use crate::Result;
 use crate::TaskId;
+use crate::TaskLogs;
 use crate::TaskStatus;
 use crate::TaskSummary;
 use crate::TurnAttempt;
@@ -98,6 +99,21 @@ impl CloudBackend for MockClient {
         })
     }
 
+    async fn get_task_logs(&self, id: TaskId) -> Result<TaskLogs> {
+        let status = match self.get_task_summary(id).await?.status {
+            TaskStatus::Pending => AttemptStatus::InProgress,
+            TaskStatus::Error => AttemptStatus::Failed,
+            TaskStatus::Ready | TaskStatus::Applied => AttemptStatus::Completed,
+        };
+        Ok(TaskLogs {
+            lines: vec![
+                "assistant: Mock worklog: reading the repository".to_string(),
+                "tool: git status".to_string(),
+            ],
+            status,
+        })
+    }
+
     async fn apply_task(&self, id: TaskId, _diff_override: Option<String>) -> Result<ApplyOutcome> {
         Ok(ApplyOutcome {
             applied: true,
@@ -39,7 +39,20 @@ pub struct ApplyModalState {
     pub diff_override: Option<String>,
 }
 
+/// Worklog of the selected task, streamed into the list view while the task runs.
+#[derive(Clone, Debug)]
+pub struct TaskLogsView {
+    pub task_id: TaskId,
+    pub title: String,
+    pub lines: Vec<String>,
+    pub status: AttemptStatus,
+    pub error: Option<String>,
+    /// Matches `AppEvent::TaskLogsLoaded::generation` so polls for a previous selection are dropped.
+    pub generation: u64,
+}
+
 use crate::scrollable_diff::ScrollableDiff;
+use codex_cloud_tasks_client::AttemptStatus;
 use codex_cloud_tasks_client::CloudBackend;
 use codex_cloud_tasks_client::TaskId;
 use codex_cloud_tasks_client::TaskSummary;
@@ -72,6 +85,9 @@ pub struct App {
     pub list_generation: u64,
     pub in_flight: std::collections::HashSet<String>,
     // Background enrichment caches were planned; currently unused.
+    // Live worklog panel
+    pub task_logs: Option<TaskLogsView>,
+    pub logs_generation: u64,
 }
 
 impl App {
@@ -98,7 +114,35 @@ impl App {
             apply_inflight: false,
             list_generation: 0,
             in_flight: std::collections::HashSet::new(),
+            task_logs: None,
+            logs_generation: 0,
+        }
+    }
+
+    /// Point the worklog panel at `task`, returning the generation the caller should poll with.
+    pub fn open_task_logs(&mut self, task: &TaskSummary) -> u64 {
+        self.logs_generation = self.logs_generation.saturating_add(1);
+        self.task_logs = Some(TaskLogsView {
+            task_id: task.id.clone(),
+            title: task.title.clone(),
+            lines: Vec::new(),
+            status: AttemptStatus::Unknown,
+            error: None,
+            generation: self.logs_generation,
+        });
+        self.logs_generation
+    }
+
+    /// When the worklog panel is open, retarget it to the current selection.
+    /// Returns the task id and generation to poll, if a new poll is needed.
+    pub fn follow_selection_with_logs(&mut self) -> Option<(TaskId, u64)> {
+        let current = self.task_logs.as_ref()?.task_id.clone();
+        let task = self.tasks.get(self.selected)?.clone();
+        if task.id == current {
+            return None;
         }
+        let generation = self.open_task_logs(&task);
+        Some((task.id, generation))
     }
 
     pub fn next(&mut self) {
@@ -347,6 +391,18 @@ pub enum AppEvent {
         id: TaskId,
         result: std::result::Result<codex_cloud_tasks_client::ApplyOutcome, String>,
     },
+    /// One poll of the worklog panel finished
+    TaskLogsLoaded {
+        id: TaskId,
+        generation: u64,
+        result: std::result::Result<codex_cloud_tasks_client::TaskLogs, String>,
+    },
+    /// The original prompt was fetched so the task can be retried with edits
+    RetryPromptLoaded {
+        id: TaskId,
+        env_id: Option<String>,
+        result: std::result::Result<Option<String>, String>,
+    },
 }
 
 // Convenience aliases; currently unused.
@@ -445,6 +501,13 @@ mod tests {
             })
         }
 
+        async fn get_task_logs(
+            &self,
+            _id: TaskId,
+        ) -> codex_cloud_tasks_client::Result<codex_cloud_tasks_client::TaskLogs> {
+            Ok(codex_cloud_tasks_client::TaskLogs::default())
+        }
+
         async fn list_sibling_attempts(
             &self,
             _task: TaskId,
@@ -509,4 +572,38 @@ mod tests {
         assert_eq!(b.len(), 3);
         assert_eq!(b[2].title, "B-3");
     }
+
+    fn summary(id: &str) -> TaskSummary {
+        TaskSummary {
+            id: TaskId(id.to_string()),
+            title: format!("title {id}"),
+            status: codex_cloud_tasks_client::TaskStatus::Pending,
+            updated_at: Utc::now(),
+            environment_id: None,
+            environment_label: None,
+            summary: codex_cloud_tasks_client::DiffSummary::default(),
+            is_review: false,
+            attempt_total: Some(1),
+        }
+    }
+
+    #[test]
+    fn logs_panel_follows_selection_with_new_generation() {
+        let mut app = App::new();
+        app.tasks = vec![summary("T-1"), summary("T-2")];
+        assert_eq!(app.follow_selection_with_logs(), None);
+
+        let first = app.open_task_logs(&app.tasks[0].clone());
+        assert_eq!(app.follow_selection_with_logs(), None);
+
+        app.next();
+        let (id, second) = app
+            .follow_selection_with_logs()
+            .expect("panel should retarget");
+        assert_eq!(id, TaskId("T-2".to_string()));
+        assert!(second > first);
+        let view = app.task_logs.as_ref().expect("panel open");
+        assert_eq!(view.generation, second);
+        assert_eq!(view.title, "title T-2");
+    }
 }
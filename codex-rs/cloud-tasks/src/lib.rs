@@ -724,6 +724,34 @@ fn spawn_apply(
     true
 }
 
+/// How often the worklog panel polls a running task.
+const LOGS_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+fn spawn_logs_poll(
+    backend: &Arc<dyn codex_cloud_tasks_client::CloudBackend>,
+    tx: &UnboundedSender<app::AppEvent>,
+    task_id: codex_cloud_tasks_client::TaskId,
+    generation: u64,
+    delay: Duration,
+) {
+    let backend = backend.clone();
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+        let result =
+            codex_cloud_tasks_client::CloudBackend::get_task_logs(&*backend, task_id.clone())
+                .await
+                .map_err(|e| format!("{e}"));
+        let _ = tx.send(app::AppEvent::TaskLogsLoaded {
+            id: task_id,
+            generation,
+            result,
+        });
+    });
+}
+
 // logging helper lives in util module
 
 // (no standalone patch summarizer needed – UI displays raw diffs)
@@ -1276,6 +1304,53 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                             app.details_inflight = false;
                             needs_redraw = true;
                         }
+                        app::AppEvent::TaskLogsLoaded { id, generation, result } => {
+                            // Drop polls for a previous selection or a closed panel.
+                            let Some(view) = app.task_logs.as_mut() else { continue; };
+                            if view.task_id != id || view.generation != generation { continue; }
+                            let was_running = matches!(view.status, codex_cloud_tasks_client::AttemptStatus::Pending | codex_cloud_tasks_client::AttemptStatus::InProgress);
+                            match result {
+                                Ok(logs) => {
+                                    view.lines = logs.lines;
+                                    view.status = logs.status;
+                                    view.error = None;
+                                }
+                                Err(e) => {
+                                    append_error_log(format!("get_task_logs failed for {}: {e}", id.0));
+                                    view.error = Some(e);
+                                }
+                            }
+                            if !view.status.is_terminal() {
+                                spawn_logs_poll(&backend, &tx, id, generation, LOGS_POLL_INTERVAL);
+                            } else if was_running {
+                                // The task just finished; refresh so its row reflects the new status.
+                                let backend = Arc::clone(&backend);
+                                let tx = tx.clone();
+                                let env_sel = app.env_filter.clone();
+                                tokio::spawn(async move {
+                                    let res = app::load_tasks(&*backend, env_sel.as_deref()).await;
+                                    let _ = tx.send(app::AppEvent::TasksLoaded { env: env_sel, result: res });
+                                });
+                            }
+                            needs_redraw = true;
+                        }
+                        app::AppEvent::RetryPromptLoaded { id, env_id, result } => {
+                            match result {
+                                Ok(Some(prompt)) => {
+                                    app.diff_overlay = None;
+                                    app.new_task = Some(crate::new_task::NewTaskPage::retry(env_id, app.best_of_n, prompt));
+                                    app.status = "Retry: edit the prompt, Enter to submit; Esc to cancel".to_string();
+                                }
+                                Ok(None) => {
+                                    app.status = format!("No prompt found for task {}", id.0);
+                                }
+                                Err(e) => {
+                                    append_error_log(format!("retry: get_task_text failed for {}: {e}", id.0));
+                                    app.status = format!("Failed to load prompt: {e}");
+                                }
+                            }
+                            needs_redraw = true;
+                        }
                         app::AppEvent::ApplyFinished { id, result } => {
                             // Only update if the modal still corresponds to this id.
                             if let Some(m) = &app.apply_modal {
@@ -1784,12 +1859,44 @@ pub async fn run_main(cli: Cli, _codex_linux_sandbox_exe: Option<PathBuf>) -> an
                                 }
                                 KeyCode::Down | KeyCode::Char('j') => {
                                     app.next();
+                                    if let Some((id, generation)) = app.follow_selection_with_logs() {
+                                        spawn_logs_poll(&backend, &tx, id, generation, Duration::ZERO);
+                                    }
                                     needs_redraw = true;
                                 }
                                 KeyCode::Up | KeyCode::Char('k') => {
                                     app.prev();
+                                    if let Some((id, generation)) = app.follow_selection_with_logs() {
+                                        spawn_logs_poll(&backend, &tx, id, generation, Duration::ZERO);
+                                    }
                                     needs_redraw = true;
                                 }
+                                KeyCode::Char('l') => {
+                                    if app.task_logs.take().is_some() {
+                                        app.status = "Closed logs".to_string();
+                                    } else if let Some(task) = app.tasks.get(app.selected).cloned() {
+                                        let generation = app.open_task_logs(&task);
+                                        spawn_logs_poll(&backend, &tx, task.id, generation, Duration::ZERO);
+                                        app.status = format!("Streaming logs for '{}'", task.title);
+                                    }
+                                    needs_redraw = true;
+                                }
+                                KeyCode::Char('e') => {
+                                    if let Some(task) = app.tasks.get(app.selected).cloned() {
+                                        app.status = format!("Loading prompt for '{}'…", task.title);
+                                        let env_id = task.environment_id.clone().or_else(|| app.env_filter.clone());
+                                        let backend = Arc::clone(&backend);
+                                        let tx = tx.clone();
+                                        tokio::spawn(async move {
+                                            let result = codex_cloud_tasks_client::CloudBackend::get_task_text(&*backend, task.id.clone())
+                                                .await
+                                                .map(|text| text.prompt)
+                                                .map_err(|e| format!("{e}"));
+                                            let _ = tx.send(app::AppEvent::RetryPromptLoaded { id: task.id, env_id, result });
+                                        });
+                                        needs_redraw = true;
+                                    }
+                                }
                                 // Ensure 'r' does not refresh tasks when the env modal is open.
                                 KeyCode::Char('r') | KeyCode::Char('R') => {
                                     if app.env_modal.is_some() { break 0; }
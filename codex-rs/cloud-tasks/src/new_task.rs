@@ -25,6 +25,13 @@ impl NewTaskPage {
         }
     }
 
+    /// Open the composer prefilled with a previous prompt so it can be edited and resubmitted.
+    pub fn retry(env_id: Option<String>, best_of_n: usize, prompt: String) -> Self {
+        let mut page = Self::new(env_id, best_of_n);
+        page.composer.set_text(prompt);
+        page
+    }
+
     // Additional helpers can be added as usage evolves.
 }
 
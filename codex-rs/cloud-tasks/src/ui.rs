@@ -174,6 +174,17 @@ pub fn draw_new_task_page(frame: &mut Frame, area: Rect, app: &mut App) {
 }
 
 fn draw_list(frame: &mut Frame, area: Rect, app: &mut App) {
+    // Reserve the lower part of the list area for the worklog panel when streaming.
+    let area = if app.task_logs.is_some() {
+        let split = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(6), Constraint::Percentage(40)])
+            .split(area);
+        draw_task_logs(frame, split[1], app);
+        split[0]
+    } else {
+        area
+    };
     let items: Vec<ListItem> = app.tasks.iter().map(|t| render_task_item(app, t)).collect();
 
     // Selection reflects the actual task index (no artificial spacer item).
@@ -233,6 +244,41 @@ fn draw_list(frame: &mut Frame, area: Rect, app: &mut App) {
     }
 }
 
+fn draw_task_logs(frame: &mut Frame, area: Rect, app: &App) {
+    let Some(view) = app.task_logs.as_ref() else {
+        return;
+    };
+    let mut title_spans: Vec<ratatui::text::Span> = vec!["Logs".into(), " • ".dim()];
+    title_spans.push(view.title.clone().dim());
+    if let Some(status) = attempt_status_span(view.status) {
+        title_spans.push(" • ".dim());
+        title_spans.push(status);
+    }
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Line::from(title_spans));
+    let inner = block.inner(area);
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let mut lines: Vec<Line> = view
+        .lines
+        .iter()
+        .flat_map(|entry| entry.lines())
+        .map(|line| Line::from(line.to_string()))
+        .collect();
+    if lines.is_empty() {
+        lines.push(Line::from("<waiting for output>".dim()));
+    }
+    if let Some(err) = view.error.as_ref() {
+        lines.push(Line::from(format!("Failed to load logs: {err}").red()));
+    }
+    // Keep the newest entries visible.
+    let skip = lines.len().saturating_sub(inner.height as usize);
+    let visible: Vec<Line> = lines.into_iter().skip(skip).collect();
+    frame.render_widget(Paragraph::new(visible), inner);
+}
+
 fn draw_footer(frame: &mut Frame, area: Rect, app: &mut App) {
     let mut help = vec![
         "↑/↓".dim(),
@@ -260,6 +306,14 @@ fn draw_footer(frame: &mut Frame, area: Rect, app: &mut App) {
     } else {
         help.push("a".dim());
         help.push(": Apply  ".dim());
+        help.push("l".dim());
+        help.push(if app.task_logs.is_some() {
+            ": Hide logs  ".dim()
+        } else {
+            ": Logs  ".dim()
+        });
+        help.push("e".dim());
+        help.push(": Edit & retry  ".dim());
     }
     help.push("o : Set Env  ".dim());
     if app.new_task.is_some() {
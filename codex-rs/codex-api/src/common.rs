@@ -4,6 +4,7 @@ use codex_protocol::config_types::Verbosity as VerbosityConfig;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
 use codex_protocol::protocol::RateLimitSnapshot;
+use codex_protocol::protocol::RequestBudgetSnapshot;
 use codex_protocol::protocol::TokenUsage;
 use futures::Stream;
 use serde::Deserialize;
@@ -79,6 +80,7 @@ pub enum ResponseEvent {
         summary_index: i64,
     },
     RateLimits(RateLimitSnapshot),
+    RequestBudget(RequestBudgetSnapshot),
     ModelsEtag(String),
 }
 
@@ -0,0 +1,244 @@
+//! Translation between Codex's request/event model and a provider's wire
+//! format.
+//!
+//! Codex builds every model request as a [`ResponsesApiRequest`] and consumes
+//! [`ResponseEvent`]s, both shaped after the Responses API. A [`WireDialect`]
+//! maps them onto what a provider actually speaks, so [`crate::ResponsesClient`]
+//! can stream from any of them. Supporting another provider API (for example
+//! Anthropic Messages or Gemini) means adding a dialect; nothing above the
+//! client changes.
+
+use crate::common::ResponseStream;
+use crate::common::ResponsesApiRequest;
+use crate::error::ApiError;
+use crate::provider::Provider;
+use crate::requests::anthropic::build_anthropic_request;
+use crate::requests::chat::build_chat_request;
+use crate::requests::chat::custom_tool_names;
+use crate::requests::responses::attach_item_ids;
+use crate::sse::anthropic::spawn_anthropic_stream;
+use crate::sse::chat::spawn_chat_stream;
+use crate::sse::spawn_response_stream;
+use crate::telemetry::SseTelemetry;
+use codex_client::Request;
+use codex_client::StreamResponse;
+use http::HeaderValue;
+use http::header::AUTHORIZATION;
+use serde_json::Value;
+use std::sync::Arc;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Per-request settings for [`WireDialect::decode_stream`].
+pub struct StreamDecodeOptions {
+    pub idle_timeout: Duration,
+    pub telemetry: Option<Arc<dyn SseTelemetry>>,
+    /// Receives the `x-codex-turn-state` header, for dialects that support
+    /// sticky routing.
+    pub turn_state: Option<Arc<OnceLock<String>>>,
+}
+
+pub trait WireDialect: Send + Sync {
+    /// Streaming endpoint, relative to the provider's base URL.
+    fn path(&self) -> &'static str;
+
+    /// Encodes `request` as the JSON body sent to [`WireDialect::path`].
+    fn encode_request(
+        &self,
+        request: &ResponsesApiRequest,
+        provider: &Provider,
+    ) -> Result<Value, ApiError>;
+
+    /// Decodes the streamed response to `request` into [`ResponseEvent`]s.
+    ///
+    /// [`ResponseEvent`]: crate::common::ResponseEvent
+    fn decode_stream(
+        &self,
+        request: &ResponsesApiRequest,
+        response: StreamResponse,
+        options: StreamDecodeOptions,
+    ) -> ResponseStream;
+
+    /// Adjusts the outgoing request once provider and auth headers are set,
+    /// for dialects that authenticate or version requests differently.
+    fn prepare_request(&self, _request: &mut Request) {}
+}
+
+/// The Responses API (`/responses`), which needs no translation.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ResponsesDialect;
+
+impl WireDialect for ResponsesDialect {
+    fn path(&self) -> &'static str {
+        "responses"
+    }
+
+    fn encode_request(
+        &self,
+        request: &ResponsesApiRequest,
+        provider: &Provider,
+    ) -> Result<Value, ApiError> {
+        let mut body = serde_json::to_value(request)
+            .map_err(|e| ApiError::Stream(format!("failed to encode responses request: {e}")))?;
+        if request.store && provider.is_azure_responses_endpoint() {
+            attach_item_ids(&mut body, &request.input);
+        }
+        Ok(body)
+    }
+
+    fn decode_stream(
+        &self,
+        _request: &ResponsesApiRequest,
+        response: StreamResponse,
+        options: StreamDecodeOptions,
+    ) -> ResponseStream {
+        spawn_response_stream(
+            response,
+            options.idle_timeout,
+            options.telemetry,
+            options.turn_state,
+        )
+    }
+}
+
+/// Chat Completions (`/chat/completions`), as served by most
+/// OpenAI-compatible servers. See [`crate::requests::chat`] and
+/// [`crate::sse::chat`] for how items and tools are mapped.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChatCompletionsDialect;
+
+impl WireDialect for ChatCompletionsDialect {
+    fn path(&self) -> &'static str {
+        "chat/completions"
+    }
+
+    fn encode_request(
+        &self,
+        request: &ResponsesApiRequest,
+        _provider: &Provider,
+    ) -> Result<Value, ApiError> {
+        build_chat_request(request)
+    }
+
+    fn decode_stream(
+        &self,
+        request: &ResponsesApiRequest,
+        response: StreamResponse,
+        options: StreamDecodeOptions,
+    ) -> ResponseStream {
+        spawn_chat_stream(
+            response,
+            custom_tool_names(request),
+            options.idle_timeout,
+            options.telemetry,
+        )
+    }
+}
+
+/// Anthropic's Messages API (`/messages`). See [`crate::requests::anthropic`]
+/// and [`crate::sse::anthropic`] for how items, tools, and thinking blocks are
+/// mapped.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AnthropicMessagesDialect;
+
+/// Messages API version sent unless the provider configures its own.
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+impl WireDialect for AnthropicMessagesDialect {
+    fn path(&self) -> &'static str {
+        "messages"
+    }
+
+    fn encode_request(
+        &self,
+        request: &ResponsesApiRequest,
+        _provider: &Provider,
+    ) -> Result<Value, ApiError> {
+        build_anthropic_request(request)
+    }
+
+    fn decode_stream(
+        &self,
+        request: &ResponsesApiRequest,
+        response: StreamResponse,
+        options: StreamDecodeOptions,
+    ) -> ResponseStream {
+        spawn_anthropic_stream(
+            response,
+            custom_tool_names(request),
+            options.idle_timeout,
+            options.telemetry,
+        )
+    }
+
+    /// Sends the API key as `x-api-key` rather than a bearer token, unless the
+    /// provider already sets `x-api-key` itself (as gateways that expect a
+    /// bearer token alongside it do).
+    fn prepare_request(&self, request: &mut Request) {
+        let headers = &mut request.headers;
+        if !headers.contains_key("x-api-key")
+            && let Some(api_key) = headers
+                .get(AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer "))
+                .and_then(|api_key| HeaderValue::from_str(api_key).ok())
+        {
+            headers.remove(AUTHORIZATION);
+            headers.insert("x-api-key", api_key);
+        }
+        if !headers.contains_key("anthropic-version") {
+            headers.insert(
+                "anthropic-version",
+                HeaderValue::from_static(ANTHROPIC_VERSION),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::Method;
+    use pretty_assertions::assert_eq;
+
+    fn request_with(headers: &[(&'static str, &'static str)]) -> Request {
+        let mut request =
+            Request::new(Method::POST, "https://api.anthropic.com/v1/messages".into());
+        for (name, value) in headers {
+            request
+                .headers
+                .insert(*name, HeaderValue::from_static(value));
+        }
+        request
+    }
+
+    #[test]
+    fn anthropic_sends_api_key_and_version_headers() {
+        let mut request = request_with(&[("authorization", "Bearer sk-ant")]);
+        AnthropicMessagesDialect.prepare_request(&mut request);
+        assert_eq!(request.headers.get(AUTHORIZATION), None);
+        assert_eq!(
+            request.headers.get("x-api-key"),
+            Some(&HeaderValue::from_static("sk-ant"))
+        );
+        assert_eq!(
+            request.headers.get("anthropic-version"),
+            Some(&HeaderValue::from_static(ANTHROPIC_VERSION))
+        );
+
+        let mut request = request_with(&[
+            ("authorization", "Bearer gateway-token"),
+            ("x-api-key", "sk-ant"),
+            ("anthropic-version", "2099-01-01"),
+        ]);
+        AnthropicMessagesDialect.prepare_request(&mut request);
+        assert_eq!(
+            request.headers.get(AUTHORIZATION),
+            Some(&HeaderValue::from_static("Bearer gateway-token"))
+        );
+        assert_eq!(
+            request.headers.get("anthropic-version"),
+            Some(&HeaderValue::from_static("2099-01-01"))
+        );
+    }
+}
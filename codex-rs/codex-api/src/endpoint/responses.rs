@@ -1,6 +1,9 @@
 use crate::auth::AuthProvider;
 use crate::common::ResponseStream;
 use crate::common::ResponsesApiRequest;
+use crate::dialect::ResponsesDialect;
+use crate::dialect::StreamDecodeOptions;
+use crate::dialect::WireDialect;
 use crate::endpoint::session::EndpointSession;
 use crate::error::ApiError;
 use crate::provider::Provider;
@@ -8,12 +11,12 @@ use crate::requests::headers::build_conversation_headers;
 use crate::requests::headers::insert_header;
 use crate::requests::headers::subagent_header;
 use crate::requests::responses::Compression;
-use crate::requests::responses::attach_item_ids;
 use crate::sse::spawn_response_stream;
 use crate::telemetry::SseTelemetry;
 use codex_client::HttpTransport;
 use codex_client::RequestCompression;
 use codex_client::RequestTelemetry;
+use codex_client::StreamResponse;
 use codex_protocol::protocol::SessionSource;
 use http::HeaderMap;
 use http::HeaderValue;
@@ -25,6 +28,7 @@ use std::sync::OnceLock;
 pub struct ResponsesClient<T: HttpTransport, A: AuthProvider> {
     session: EndpointSession<T, A>,
     sse_telemetry: Option<Arc<dyn SseTelemetry>>,
+    dialect: Arc<dyn WireDialect>,
 }
 
 #[derive(Default)]
@@ -41,9 +45,16 @@ impl<T: HttpTransport, A: AuthProvider> ResponsesClient<T, A> {
         Self {
             session: EndpointSession::new(transport, provider, auth),
             sse_telemetry: None,
+            dialect: Arc::new(ResponsesDialect),
         }
     }
 
+    /// Speaks `dialect` in [`Self::stream_request`] instead of the Responses
+    /// API.
+    pub fn with_dialect(self, dialect: Arc<dyn WireDialect>) -> Self {
+        Self { dialect, ..self }
+    }
+
     pub fn with_telemetry(
         self,
         request: Option<Arc<dyn RequestTelemetry>>,
@@ -52,6 +63,7 @@ impl<T: HttpTransport, A: AuthProvider> ResponsesClient<T, A> {
         Self {
             session: self.session.with_request_telemetry(request),
             sse_telemetry: sse,
+            dialect: self.dialect,
         }
     }
 
@@ -68,11 +80,9 @@ impl<T: HttpTransport, A: AuthProvider> ResponsesClient<T, A> {
             turn_state,
         } = options;
 
-        let mut body = serde_json::to_value(&request)
-            .map_err(|e| ApiError::Stream(format!("failed to encode responses request: {e}")))?;
-        if request.store && self.session.provider().is_azure_responses_endpoint() {
-            attach_item_ids(&mut body, &request.input);
-        }
+        let body = self
+            .dialect
+            .encode_request(&request, self.session.provider())?;
 
         let mut headers = extra_headers;
         headers.extend(build_conversation_headers(conversation_id));
@@ -80,13 +90,21 @@ impl<T: HttpTransport, A: AuthProvider> ResponsesClient<T, A> {
             insert_header(&mut headers, "x-openai-subagent", &subagent);
         }
 
-        self.stream(body, headers, compression, turn_state).await
-    }
-
-    fn path() -> &'static str {
-        "responses"
+        let stream_response = self
+            .send(self.dialect.as_ref(), body, headers, compression)
+            .await?;
+        Ok(self.dialect.decode_stream(
+            &request,
+            stream_response,
+            StreamDecodeOptions {
+                idle_timeout: self.session.provider().stream_idle_timeout,
+                telemetry: self.sse_telemetry.clone(),
+                turn_state,
+            },
+        ))
     }
 
+    /// Streams a raw Responses API request body, bypassing the dialect.
     pub async fn stream(
         &self,
         body: Value,
@@ -94,16 +112,34 @@ impl<T: HttpTransport, A: AuthProvider> ResponsesClient<T, A> {
         compression: Compression,
         turn_state: Option<Arc<OnceLock<String>>>,
     ) -> Result<ResponseStream, ApiError> {
+        let stream_response = self
+            .send(&ResponsesDialect, body, extra_headers, compression)
+            .await?;
+
+        Ok(spawn_response_stream(
+            stream_response,
+            self.session.provider().stream_idle_timeout,
+            self.sse_telemetry.clone(),
+            turn_state,
+        ))
+    }
+
+    async fn send(
+        &self,
+        dialect: &dyn WireDialect,
+        body: Value,
+        extra_headers: HeaderMap,
+        compression: Compression,
+    ) -> Result<StreamResponse, ApiError> {
         let request_compression = match compression {
             Compression::None => RequestCompression::None,
             Compression::Zstd => RequestCompression::Zstd,
         };
 
-        let stream_response = self
-            .session
+        self.session
             .stream_with(
                 Method::POST,
-                Self::path(),
+                dialect.path(),
                 extra_headers,
                 Some(body),
                 |req| {
@@ -112,15 +148,9 @@ impl<T: HttpTransport, A: AuthProvider> ResponsesClient<T, A> {
                         HeaderValue::from_static("text/event-stream"),
                     );
                     req.compression = request_compression;
+                    dialect.prepare_request(req);
                 },
             )
-            .await?;
-
-        Ok(spawn_response_stream(
-            stream_response,
-            self.session.provider().stream_idle_timeout,
-            self.sse_telemetry.clone(),
-            turn_state,
-        ))
+            .await
     }
 }
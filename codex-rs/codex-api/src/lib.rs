@@ -1,5 +1,6 @@
 pub mod auth;
 pub mod common;
+pub mod dialect;
 pub mod endpoint;
 pub mod error;
 pub mod provider;
@@ -25,6 +26,11 @@ pub use crate::common::ResponseEvent;
 pub use crate::common::ResponseStream;
 pub use crate::common::ResponsesApiRequest;
 pub use crate::common::create_text_param_for_request;
+pub use crate::dialect::AnthropicMessagesDialect;
+pub use crate::dialect::ChatCompletionsDialect;
+pub use crate::dialect::ResponsesDialect;
+pub use crate::dialect::StreamDecodeOptions;
+pub use crate::dialect::WireDialect;
 pub use crate::endpoint::aggregate::AggregateStreamExt;
 pub use crate::endpoint::compact::CompactClient;
 pub use crate::endpoint::memories::MemoriesClient;
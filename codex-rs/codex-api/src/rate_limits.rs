@@ -2,10 +2,13 @@ use codex_protocol::account::PlanType;
 use codex_protocol::protocol::CreditsSnapshot;
 use codex_protocol::protocol::RateLimitSnapshot;
 use codex_protocol::protocol::RateLimitWindow;
+use codex_protocol::protocol::RequestBudgetSnapshot;
+use codex_protocol::protocol::RequestBudgetWindow;
 use http::HeaderMap;
 use serde::Deserialize;
 use std::collections::BTreeSet;
 use std::fmt::Display;
+use std::time::Duration;
 
 #[derive(Debug)]
 pub struct RateLimitError {
@@ -176,6 +179,82 @@ pub fn parse_promo_message(headers: &HeaderMap) -> Option<String> {
         .map(std::string::ToString::to_string)
 }
 
+/// Parses the `x-ratelimit-{limit,remaining,reset}-{requests,tokens}` headers
+/// that OpenAI-compatible providers send on every response.
+pub fn parse_request_budget(headers: &HeaderMap) -> Option<RequestBudgetSnapshot> {
+    let snapshot = RequestBudgetSnapshot {
+        requests: parse_request_budget_window(headers, "requests"),
+        tokens: parse_request_budget_window(headers, "tokens"),
+    };
+    (snapshot.requests.is_some() || snapshot.tokens.is_some()).then_some(snapshot)
+}
+
+/// Parses `retry-after-ms` or `retry-after` (delay in seconds; HTTP dates are
+/// not supported).
+pub fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    if let Some(millis) = parse_header_str(headers, "retry-after-ms")
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .filter(|millis| millis.is_finite() && *millis >= 0.0)
+    {
+        return Some(Duration::from_secs_f64(millis / 1000.0));
+    }
+    parse_header_str(headers, "retry-after")
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+        .map(Duration::from_secs_f64)
+}
+
+fn parse_request_budget_window(headers: &HeaderMap, unit: &str) -> Option<RequestBudgetWindow> {
+    let limit = parse_header_str(headers, &format!("x-ratelimit-limit-{unit}"))?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    let remaining = parse_header_str(headers, &format!("x-ratelimit-remaining-{unit}"))?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    let resets_in = parse_header_str(headers, &format!("x-ratelimit-reset-{unit}"))
+        .and_then(parse_reset_duration)
+        .unwrap_or_default();
+    Some(RequestBudgetWindow {
+        limit,
+        remaining: remaining.min(limit),
+        resets_in_ms: u64::try_from(resets_in.as_millis()).unwrap_or(u64::MAX),
+    })
+}
+
+/// Parses Go-style durations such as `20ms`, `1s`, `6m0s`, or `1h2m3.5s`. A
+/// bare number is taken as seconds.
+fn parse_reset_duration(raw: &str) -> Option<Duration> {
+    let raw = raw.trim();
+    if let Ok(seconds) = raw.parse::<f64>() {
+        return (seconds.is_finite() && seconds >= 0.0).then(|| Duration::from_secs_f64(seconds));
+    }
+
+    let mut total = 0.0;
+    let mut rest = raw;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let value = rest[..number_len].parse::<f64>().ok()?;
+        rest = &rest[number_len..];
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+        let seconds_per_unit = match &rest[..unit_len] {
+            "h" => 3600.0,
+            "m" => 60.0,
+            "s" => 1.0,
+            "ms" => 0.001,
+            _ => return None,
+        };
+        rest = &rest[unit_len..];
+        total += value * seconds_per_unit;
+    }
+    (total.is_finite() && !raw.is_empty()).then(|| Duration::from_secs_f64(total))
+}
+
 fn parse_rate_limit_window(
     headers: &HeaderMap,
     used_percent_header: &str,
@@ -351,6 +430,96 @@ mod tests {
         assert_eq!(updates[1].limit_name, None);
     }
 
+    #[test]
+    fn parse_request_budget_reads_requests_and_tokens() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-ratelimit-limit-requests",
+            HeaderValue::from_static("500"),
+        );
+        headers.insert(
+            "x-ratelimit-remaining-requests",
+            HeaderValue::from_static("499"),
+        );
+        headers.insert(
+            "x-ratelimit-reset-requests",
+            HeaderValue::from_static("120ms"),
+        );
+        headers.insert(
+            "x-ratelimit-limit-tokens",
+            HeaderValue::from_static("30000"),
+        );
+        headers.insert(
+            "x-ratelimit-remaining-tokens",
+            HeaderValue::from_static("1200"),
+        );
+        headers.insert(
+            "x-ratelimit-reset-tokens",
+            HeaderValue::from_static("1m4.5s"),
+        );
+
+        let budget = parse_request_budget(&headers).expect("budget");
+        assert_eq!(
+            budget,
+            RequestBudgetSnapshot {
+                requests: Some(RequestBudgetWindow {
+                    limit: 500,
+                    remaining: 499,
+                    resets_in_ms: 120,
+                }),
+                tokens: Some(RequestBudgetWindow {
+                    limit: 30000,
+                    remaining: 1200,
+                    resets_in_ms: 64_500,
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_request_budget_requires_limit_and_remaining() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-ratelimit-limit-tokens",
+            HeaderValue::from_static("30000"),
+        );
+        headers.insert("x-ratelimit-reset-tokens", HeaderValue::from_static("6m0s"));
+
+        assert_eq!(parse_request_budget(&headers), None);
+    }
+
+    #[test]
+    fn parse_reset_duration_accepts_go_durations() {
+        assert_eq!(parse_reset_duration("6m0s"), Some(Duration::from_secs(360)));
+        assert_eq!(parse_reset_duration("1h"), Some(Duration::from_secs(3600)));
+        assert_eq!(
+            parse_reset_duration("2.5"),
+            Some(Duration::from_millis(2500))
+        );
+        assert_eq!(parse_reset_duration("7d"), None);
+        assert_eq!(parse_reset_duration(""), None);
+    }
+
+    #[test]
+    fn parse_retry_after_prefers_milliseconds() {
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", HeaderValue::from_static("3"));
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(3)));
+
+        headers.insert("retry-after-ms", HeaderValue::from_static("1500"));
+        assert_eq!(
+            parse_retry_after(&headers),
+            Some(Duration::from_millis(1500))
+        );
+
+        let mut dated = HeaderMap::new();
+        dated.insert(
+            "retry-after",
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(parse_retry_after(&dated), None);
+    }
+
     #[test]
     fn parse_all_rate_limits_includes_default_codex_snapshot() {
         let headers = HeaderMap::new();
@@ -0,0 +1,439 @@
+//! Encodes a [`ResponsesApiRequest`] as an Anthropic Messages request.
+//!
+//! Codex keeps its conversation as Responses API items; this module turns
+//! them into content blocks:
+//!
+//! - Instructions and the developer messages that open the conversation form
+//!   `system`. Later developer messages are sent as user text, since Messages
+//!   only has one system prompt.
+//! - Function, custom, and local shell calls become `tool_use` blocks keyed
+//!   by the item's `call_id`, and their outputs become `tool_result` blocks.
+//!   Consecutive blocks of one role share a message, as the API requires.
+//! - Custom (freeform) tools are offered with a single `input` string, as for
+//!   Chat Completions.
+//! - Reasoning items that came from Anthropic carry the block's signature in
+//!   `encrypted_content` and are replayed as `thinking` (or
+//!   `redacted_thinking`) blocks. Reasoning without one cannot be replayed.
+//! - Web search calls, ghost snapshots, and compaction items have no Messages
+//!   form and are left out, as are tools other than functions and custom
+//!   tools.
+
+use crate::common::ResponsesApiRequest;
+use crate::error::ApiError;
+use crate::requests::chat::CUSTOM_TOOL_INPUT_ARG;
+use crate::requests::chat::LOCAL_SHELL_TOOL_NAME;
+use crate::requests::chat::custom_tool_parameters;
+use crate::requests::chat::reasoning_text;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::openai_models::ReasoningEffort;
+use serde_json::Map;
+use serde_json::Value;
+use serde_json::json;
+
+/// `max_tokens` is required by the Messages API. This fits the output limit
+/// of every current Claude model and leaves room for the largest thinking
+/// budget below.
+const MAX_TOKENS: u64 = 32_000;
+
+pub(crate) fn build_anthropic_request(request: &ResponsesApiRequest) -> Result<Value, ApiError> {
+    let mut builder = MessagesBuilder::default();
+    if !request.instructions.is_empty() {
+        builder.system.push(text_block(&request.instructions));
+    }
+    if let Some(format) = request.text.as_ref().and_then(|text| text.format.as_ref()) {
+        let schema = serde_json::to_string(&format.schema)
+            .map_err(|err| ApiError::Stream(format!("failed to encode output schema: {err}")))?;
+        builder.system.push(text_block(&format!(
+            "Respond with a single JSON value matching this JSON schema, and nothing else:\n{schema}"
+        )));
+    }
+    for item in &request.input {
+        builder.push_item(item);
+    }
+    let MessagesBuilder {
+        mut system,
+        mut messages,
+    } = builder;
+
+    // Cache everything up to the system prompt and up to the latest message,
+    // so each turn only pays full price for what it added.
+    mark_cache_breakpoint(system.last_mut());
+    mark_cache_breakpoint(
+        messages
+            .last_mut()
+            .and_then(|message| message.get_mut("content"))
+            .and_then(Value::as_array_mut)
+            .and_then(|content| content.last_mut()),
+    );
+
+    let mut body = Map::new();
+    body.insert("model".to_string(), Value::String(request.model.clone()));
+    body.insert("max_tokens".to_string(), json!(MAX_TOKENS));
+    if !system.is_empty() {
+        body.insert("system".to_string(), Value::Array(system));
+    }
+    body.insert("messages".to_string(), Value::Array(messages));
+    body.insert("stream".to_string(), Value::Bool(true));
+
+    let tools: Vec<Value> = request.tools.iter().filter_map(anthropic_tool).collect();
+    if !tools.is_empty() {
+        body.insert("tools".to_string(), Value::Array(tools));
+        body.insert(
+            "tool_choice".to_string(),
+            tool_choice(&request.tool_choice, request.parallel_tool_calls),
+        );
+    }
+
+    if let Some(budget_tokens) = request
+        .reasoning
+        .as_ref()
+        .and_then(|reasoning| reasoning.effort)
+        .and_then(thinking_budget)
+    {
+        body.insert(
+            "thinking".to_string(),
+            json!({ "type": "enabled", "budget_tokens": budget_tokens }),
+        );
+    }
+
+    Ok(Value::Object(body))
+}
+
+/// Extended thinking budget for `effort`, or `None` to leave thinking off.
+fn thinking_budget(effort: ReasoningEffort) -> Option<u64> {
+    match effort {
+        ReasoningEffort::None | ReasoningEffort::Minimal => None,
+        ReasoningEffort::Low => Some(4_000),
+        ReasoningEffort::Medium => Some(10_000),
+        ReasoningEffort::High => Some(20_000),
+        ReasoningEffort::XHigh => Some(30_000),
+    }
+}
+
+fn tool_choice(tool_choice: &str, parallel_tool_calls: bool) -> Value {
+    let disable_parallel_tool_use = !parallel_tool_calls;
+    match tool_choice {
+        "none" => json!({ "type": "none" }),
+        "required" => json!({
+            "type": "any",
+            "disable_parallel_tool_use": disable_parallel_tool_use,
+        }),
+        _ => json!({
+            "type": "auto",
+            "disable_parallel_tool_use": disable_parallel_tool_use,
+        }),
+    }
+}
+
+fn anthropic_tool(tool: &Value) -> Option<Value> {
+    let name = tool.get("name")?.clone();
+    let description = tool.get("description").cloned().unwrap_or(Value::Null);
+    let input_schema = match tool.get("type").and_then(Value::as_str)? {
+        "function" => tool
+            .get("parameters")
+            .cloned()
+            .unwrap_or_else(|| json!({ "type": "object", "properties": {} })),
+        "custom" => custom_tool_parameters(tool),
+        _ => return None,
+    };
+    Some(json!({
+        "name": name,
+        "description": description,
+        "input_schema": input_schema,
+    }))
+}
+
+fn text_block(text: &str) -> Value {
+    json!({ "type": "text", "text": text })
+}
+
+fn mark_cache_breakpoint(block: Option<&mut Value>) {
+    if let Some(Value::Object(block)) = block {
+        block.insert("cache_control".to_string(), json!({ "type": "ephemeral" }));
+    }
+}
+
+/// An `image` block for a data URL or a plain URL.
+fn image_block(image_url: &str) -> Value {
+    let data = image_url
+        .strip_prefix("data:")
+        .and_then(|rest| rest.split_once(";base64,"));
+    match data {
+        Some((media_type, data)) => json!({
+            "type": "image",
+            "source": { "type": "base64", "media_type": media_type, "data": data },
+        }),
+        None => json!({
+            "type": "image",
+            "source": { "type": "url", "url": image_url },
+        }),
+    }
+}
+
+#[derive(Default)]
+struct MessagesBuilder {
+    system: Vec<Value>,
+    messages: Vec<Value>,
+}
+
+impl MessagesBuilder {
+    /// Appends `block` to the last message when it has `role`, otherwise
+    /// starts a new message.
+    fn push(&mut self, role: &str, block: Value) {
+        if let Some(message) = self.messages.last_mut()
+            && message.get("role").and_then(Value::as_str) == Some(role)
+            && let Some(Value::Array(content)) = message.get_mut("content")
+        {
+            content.push(block);
+            return;
+        }
+        self.messages
+            .push(json!({ "role": role, "content": [block] }));
+    }
+
+    fn push_tool_use(&mut self, call_id: &str, name: &str, input: Value) {
+        self.push(
+            "assistant",
+            json!({ "type": "tool_use", "id": call_id, "name": name, "input": input }),
+        );
+    }
+
+    fn push_tool_result(&mut self, call_id: &str, content: Value, is_error: bool) {
+        let mut block = Map::new();
+        block.insert("type".to_string(), Value::String("tool_result".to_string()));
+        block.insert(
+            "tool_use_id".to_string(),
+            Value::String(call_id.to_string()),
+        );
+        block.insert("content".to_string(), content);
+        if is_error {
+            block.insert("is_error".to_string(), Value::Bool(true));
+        }
+        self.push("user", Value::Object(block));
+    }
+
+    fn push_item(&mut self, item: &ResponseItem) {
+        match item {
+            ResponseItem::Message { role, content, .. } => {
+                let role = match role.as_str() {
+                    "assistant" => "assistant",
+                    "developer" | "system" if self.messages.is_empty() => {
+                        self.system.extend(content_blocks(content));
+                        return;
+                    }
+                    _ => "user",
+                };
+                for block in content_blocks(content) {
+                    self.push(role, block);
+                }
+            }
+            ResponseItem::Reasoning {
+                summary,
+                content,
+                encrypted_content: Some(signature),
+                ..
+            } => {
+                let text = reasoning_text(summary, content.as_deref());
+                let block = if text.is_empty() {
+                    json!({ "type": "redacted_thinking", "data": signature })
+                } else {
+                    json!({ "type": "thinking", "thinking": text, "signature": signature })
+                };
+                self.push("assistant", block);
+            }
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => {
+                let input = serde_json::from_str::<Value>(arguments)
+                    .ok()
+                    .filter(Value::is_object)
+                    .unwrap_or_else(|| json!({}));
+                self.push_tool_use(call_id, name, input);
+            }
+            ResponseItem::CustomToolCall {
+                call_id,
+                name,
+                input,
+                ..
+            } => {
+                self.push_tool_use(call_id, name, json!({ CUSTOM_TOOL_INPUT_ARG: input }));
+            }
+            ResponseItem::LocalShellCall {
+                id,
+                call_id,
+                action,
+                ..
+            } => {
+                let Some(call_id) = call_id.as_ref().or(id.as_ref()) else {
+                    return;
+                };
+                let input = serde_json::to_value(action).unwrap_or_else(|_| json!({}));
+                self.push_tool_use(call_id, LOCAL_SHELL_TOOL_NAME, input);
+            }
+            ResponseItem::FunctionCallOutput { call_id, output } => {
+                let content = match &output.body {
+                    FunctionCallOutputBody::Text(text) => Value::String(text.clone()),
+                    FunctionCallOutputBody::ContentItems(items) => Value::Array(
+                        items
+                            .iter()
+                            .map(|item| match item {
+                                FunctionCallOutputContentItem::InputText { text } => {
+                                    text_block(text)
+                                }
+                                FunctionCallOutputContentItem::InputImage { image_url } => {
+                                    image_block(image_url)
+                                }
+                            })
+                            .collect(),
+                    ),
+                };
+                self.push_tool_result(call_id, content, output.success == Some(false));
+            }
+            ResponseItem::CustomToolCallOutput { call_id, output } => {
+                self.push_tool_result(call_id, Value::String(output.clone()), false);
+            }
+            ResponseItem::Reasoning { .. }
+            | ResponseItem::WebSearchCall { .. }
+            | ResponseItem::GhostSnapshot { .. }
+            | ResponseItem::Compaction { .. }
+            | ResponseItem::Other => {}
+        }
+    }
+}
+
+/// Text and image blocks for a message, without the empty text blocks the
+/// API rejects.
+fn content_blocks(content: &[ContentItem]) -> Vec<Value> {
+    content
+        .iter()
+        .filter_map(|item| match item {
+            ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                (!text.is_empty()).then(|| text_block(text))
+            }
+            ContentItem::InputImage { image_url } => Some(image_block(image_url)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Reasoning;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use codex_protocol::models::ReasoningItemContent;
+    use pretty_assertions::assert_eq;
+
+    fn message(role: &str, text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: role.into(),
+            content: vec![if role == "assistant" {
+                ContentItem::OutputText { text: text.into() }
+            } else {
+                ContentItem::InputText { text: text.into() }
+            }],
+            end_turn: None,
+            phase: None,
+        }
+    }
+
+    #[test]
+    fn maps_items_and_tools_onto_content_blocks() {
+        let request = ResponsesApiRequest {
+            model: "claude-test".into(),
+            instructions: "Be brief.".into(),
+            input: vec![
+                message("developer", "sandbox: read-only"),
+                message("user", "fix the bug"),
+                ResponseItem::Reasoning {
+                    id: "rs_1".into(),
+                    summary: Vec::new(),
+                    content: Some(vec![ReasoningItemContent::ReasoningText {
+                        text: "look at main.rs".into(),
+                    }]),
+                    encrypted_content: Some("sig".into()),
+                },
+                ResponseItem::FunctionCall {
+                    id: None,
+                    name: "shell".into(),
+                    arguments: r#"{"command":["cat","main.rs"]}"#.into(),
+                    call_id: "toolu_1".into(),
+                },
+                ResponseItem::CustomToolCall {
+                    id: None,
+                    status: None,
+                    call_id: "toolu_2".into(),
+                    name: "apply_patch".into(),
+                    input: "*** Begin Patch".into(),
+                },
+                ResponseItem::FunctionCallOutput {
+                    call_id: "toolu_1".into(),
+                    output: FunctionCallOutputPayload::from_text("fn main() {}".into()),
+                },
+                ResponseItem::CustomToolCallOutput {
+                    call_id: "toolu_2".into(),
+                    output: "Done!".into(),
+                },
+                message("developer", "model changed"),
+                message("assistant", "Fixed."),
+            ],
+            tools: vec![
+                json!({"type": "function", "name": "shell", "description": "Run a command", "strict": false, "parameters": {"type": "object"}}),
+                json!({"type": "custom", "name": "apply_patch", "description": "Edit files"}),
+                json!({"type": "web_search"}),
+            ],
+            tool_choice: "auto".into(),
+            parallel_tool_calls: false,
+            reasoning: Some(Reasoning {
+                effort: Some(ReasoningEffort::Medium),
+                summary: None,
+            }),
+            store: false,
+            stream: true,
+            include: Vec::new(),
+            prompt_cache_key: None,
+            text: None,
+        };
+
+        let body = build_anthropic_request(&request).expect("anthropic request");
+
+        let expected = json!({
+            "model": "claude-test",
+            "max_tokens": 32_000,
+            "system": [
+                {"type": "text", "text": "Be brief."},
+                {"type": "text", "text": "sandbox: read-only", "cache_control": {"type": "ephemeral"}},
+            ],
+            "messages": [
+                {"role": "user", "content": [{"type": "text", "text": "fix the bug"}]},
+                {"role": "assistant", "content": [
+                    {"type": "thinking", "thinking": "look at main.rs", "signature": "sig"},
+                    {"type": "tool_use", "id": "toolu_1", "name": "shell", "input": {"command": ["cat", "main.rs"]}},
+                    {"type": "tool_use", "id": "toolu_2", "name": "apply_patch", "input": {"input": "*** Begin Patch"}},
+                ]},
+                {"role": "user", "content": [
+                    {"type": "tool_result", "tool_use_id": "toolu_1", "content": "fn main() {}"},
+                    {"type": "tool_result", "tool_use_id": "toolu_2", "content": "Done!"},
+                    {"type": "text", "text": "model changed"},
+                ]},
+                {"role": "assistant", "content": [
+                    {"type": "text", "text": "Fixed.", "cache_control": {"type": "ephemeral"}},
+                ]},
+            ],
+            "stream": true,
+            "tools": [
+                {"name": "shell", "description": "Run a command", "input_schema": {"type": "object"}},
+                {"name": "apply_patch", "description": "Edit files", "input_schema": custom_tool_parameters(&request.tools[1])},
+            ],
+            "tool_choice": {"type": "auto", "disable_parallel_tool_use": true},
+            "thinking": {"type": "enabled", "budget_tokens": 10_000},
+        });
+        assert_eq!(body, expected);
+    }
+}
@@ -0,0 +1,550 @@
+//! Encodes a [`ResponsesApiRequest`] as a Chat Completions request.
+//!
+//! Codex keeps its conversation as Responses API items; this module flattens
+//! them into `messages`:
+//!
+//! - Developer and system messages become `system` messages, since not every
+//!   Chat Completions server knows the `developer` role.
+//! - Function, custom, and local shell calls become `tool_calls` on an
+//!   assistant message, keyed by the item's `call_id`, and their outputs
+//!   become `tool` messages with the same `tool_call_id`.
+//! - Custom (freeform) tools are offered as functions that take a single
+//!   `input` string. [`crate::sse::chat`] maps calls to them back.
+//! - Reasoning text is replayed as `reasoning_content` on the assistant
+//!   message that follows it. Encrypted reasoning cannot be replayed.
+//! - Web search calls, ghost snapshots, and compaction items have no Chat
+//!   Completions form and are left out, as are tools other than functions and
+//!   custom tools.
+
+use crate::common::ResponsesApiRequest;
+use crate::error::ApiError;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ReasoningItemReasoningSummary;
+use codex_protocol::models::ResponseItem;
+use serde_json::Map;
+use serde_json::Value;
+use serde_json::json;
+
+/// Name of the argument that carries a custom tool's freeform input.
+pub(crate) const CUSTOM_TOOL_INPUT_ARG: &str = "input";
+
+/// Name under which local shell calls from history are replayed.
+pub(crate) const LOCAL_SHELL_TOOL_NAME: &str = "local_shell";
+
+pub(crate) fn build_chat_request(request: &ResponsesApiRequest) -> Result<Value, ApiError> {
+    let mut body = Map::new();
+    body.insert("model".to_string(), Value::String(request.model.clone()));
+    body.insert(
+        "messages".to_string(),
+        Value::Array(build_messages(&request.instructions, &request.input)),
+    );
+    body.insert("stream".to_string(), Value::Bool(true));
+    body.insert(
+        "stream_options".to_string(),
+        json!({ "include_usage": true }),
+    );
+
+    let tools: Vec<Value> = request.tools.iter().filter_map(chat_tool).collect();
+    if !tools.is_empty() {
+        body.insert("tools".to_string(), Value::Array(tools));
+        body.insert(
+            "tool_choice".to_string(),
+            Value::String(request.tool_choice.clone()),
+        );
+        body.insert(
+            "parallel_tool_calls".to_string(),
+            Value::Bool(request.parallel_tool_calls),
+        );
+    }
+
+    if let Some(effort) = request
+        .reasoning
+        .as_ref()
+        .and_then(|reasoning| reasoning.effort)
+    {
+        let effort = serde_json::to_value(effort)
+            .map_err(|err| ApiError::Stream(format!("failed to encode reasoning effort: {err}")))?;
+        body.insert("reasoning_effort".to_string(), effort);
+    }
+
+    if let Some(format) = request.text.as_ref().and_then(|text| text.format.as_ref()) {
+        body.insert(
+            "response_format".to_string(),
+            json!({
+                "type": "json_schema",
+                "json_schema": {
+                    "name": format.name,
+                    "schema": format.schema,
+                    "strict": format.strict,
+                },
+            }),
+        );
+    }
+
+    Ok(Value::Object(body))
+}
+
+/// Names of the custom tools in `request`, whose calls carry freeform input.
+pub(crate) fn custom_tool_names(request: &ResponsesApiRequest) -> Vec<String> {
+    request
+        .tools
+        .iter()
+        .filter(|tool| tool.get("type").and_then(Value::as_str) == Some("custom"))
+        .filter_map(|tool| tool.get("name").and_then(Value::as_str))
+        .map(str::to_string)
+        .collect()
+}
+
+fn chat_tool(tool: &Value) -> Option<Value> {
+    let name = tool.get("name")?.clone();
+    let description = tool.get("description").cloned().unwrap_or(Value::Null);
+    match tool.get("type").and_then(Value::as_str)? {
+        "function" => {
+            let mut function = Map::new();
+            function.insert("name".to_string(), name);
+            function.insert("description".to_string(), description);
+            if let Some(parameters) = tool.get("parameters") {
+                function.insert("parameters".to_string(), parameters.clone());
+            }
+            if let Some(strict) = tool.get("strict") {
+                function.insert("strict".to_string(), strict.clone());
+            }
+            Some(json!({ "type": "function", "function": function }))
+        }
+        "custom" => Some(json!({
+            "type": "function",
+            "function": {
+                "name": name,
+                "description": description,
+                "parameters": custom_tool_parameters(tool),
+            },
+        })),
+        _ => None,
+    }
+}
+
+/// JSON schema of the single string argument a custom tool is offered with.
+pub(crate) fn custom_tool_parameters(tool: &Value) -> Value {
+    let input_description = tool
+        .get("format")
+        .and_then(|format| format.get("definition"))
+        .and_then(Value::as_str)
+        .map_or_else(
+            || "Freeform input for the tool.".to_string(),
+            |definition| format!("Input matching this grammar:\n{definition}"),
+        );
+    json!({
+        "type": "object",
+        "properties": {
+            CUSTOM_TOOL_INPUT_ARG: {
+                "type": "string",
+                "description": input_description,
+            },
+        },
+        "required": [CUSTOM_TOOL_INPUT_ARG],
+        "additionalProperties": false,
+    })
+}
+
+fn build_messages(instructions: &str, input: &[ResponseItem]) -> Vec<Value> {
+    let mut messages = Vec::new();
+    if !instructions.is_empty() {
+        messages.push(json!({ "role": "system", "content": instructions }));
+    }
+
+    // Reasoning waiting for the assistant message it belongs to.
+    let mut pending_reasoning = String::new();
+    // Whether the last message is an assistant message that later tool calls
+    // can join.
+    let mut assistant_open = false;
+
+    for item in input {
+        match item {
+            ResponseItem::Message { role, content, .. } if role == "assistant" => {
+                let text: String = content
+                    .iter()
+                    .filter_map(|item| match item {
+                        ContentItem::OutputText { text } => Some(text.as_str()),
+                        ContentItem::InputText { .. } | ContentItem::InputImage { .. } => None,
+                    })
+                    .collect();
+                let mut message = Map::new();
+                message.insert("role".to_string(), Value::String("assistant".to_string()));
+                message.insert("content".to_string(), Value::String(text));
+                take_reasoning(&mut message, &mut pending_reasoning);
+                messages.push(Value::Object(message));
+                assistant_open = true;
+            }
+            ResponseItem::Message { role, content, .. } => {
+                let role = match role.as_str() {
+                    "developer" | "system" => "system",
+                    _ => "user",
+                };
+                messages.push(json!({ "role": role, "content": user_content(content) }));
+                assistant_open = false;
+            }
+            ResponseItem::Reasoning {
+                summary, content, ..
+            } => {
+                let text = reasoning_text(summary, content.as_deref());
+                if !text.is_empty() {
+                    if !pending_reasoning.is_empty() {
+                        pending_reasoning.push('\n');
+                    }
+                    pending_reasoning.push_str(&text);
+                }
+            }
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => {
+                push_tool_call(
+                    &mut messages,
+                    &mut assistant_open,
+                    &mut pending_reasoning,
+                    call_id,
+                    name,
+                    arguments.clone(),
+                );
+            }
+            ResponseItem::CustomToolCall {
+                call_id,
+                name,
+                input,
+                ..
+            } => {
+                let arguments = json!({ CUSTOM_TOOL_INPUT_ARG: input }).to_string();
+                push_tool_call(
+                    &mut messages,
+                    &mut assistant_open,
+                    &mut pending_reasoning,
+                    call_id,
+                    name,
+                    arguments,
+                );
+            }
+            ResponseItem::LocalShellCall {
+                id,
+                call_id,
+                action,
+                ..
+            } => {
+                let Some(call_id) = call_id.as_ref().or(id.as_ref()) else {
+                    continue;
+                };
+                let arguments = serde_json::to_string(action).unwrap_or_default();
+                push_tool_call(
+                    &mut messages,
+                    &mut assistant_open,
+                    &mut pending_reasoning,
+                    call_id,
+                    LOCAL_SHELL_TOOL_NAME,
+                    arguments,
+                );
+            }
+            ResponseItem::FunctionCallOutput { call_id, output } => {
+                let content = output.body.to_text().unwrap_or_default();
+                messages.push(json!({
+                    "role": "tool",
+                    "tool_call_id": call_id,
+                    "content": content,
+                }));
+                assistant_open = false;
+            }
+            ResponseItem::CustomToolCallOutput { call_id, output } => {
+                messages.push(json!({
+                    "role": "tool",
+                    "tool_call_id": call_id,
+                    "content": output,
+                }));
+                assistant_open = false;
+            }
+            ResponseItem::WebSearchCall { .. }
+            | ResponseItem::GhostSnapshot { .. }
+            | ResponseItem::Compaction { .. }
+            | ResponseItem::Other => {}
+        }
+    }
+
+    messages
+}
+
+/// Plain text when `content` has no images, which every server accepts;
+/// content parts otherwise.
+fn user_content(content: &[ContentItem]) -> Value {
+    let has_images = content
+        .iter()
+        .any(|item| matches!(item, ContentItem::InputImage { .. }));
+    if !has_images {
+        let text: Vec<&str> = content
+            .iter()
+            .filter_map(|item| match item {
+                ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                    Some(text.as_str())
+                }
+                ContentItem::InputImage { .. } => None,
+            })
+            .collect();
+        return Value::String(text.join("\n"));
+    }
+    Value::Array(
+        content
+            .iter()
+            .map(|item| match item {
+                ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                    json!({ "type": "text", "text": text })
+                }
+                ContentItem::InputImage { image_url } => {
+                    json!({ "type": "image_url", "image_url": { "url": image_url } })
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Raw reasoning text when the provider sent it, otherwise the summary.
+pub(crate) fn reasoning_text(
+    summary: &[ReasoningItemReasoningSummary],
+    content: Option<&[ReasoningItemContent]>,
+) -> String {
+    let content: Vec<&str> = content
+        .unwrap_or_default()
+        .iter()
+        .map(|item| match item {
+            ReasoningItemContent::ReasoningText { text } | ReasoningItemContent::Text { text } => {
+                text.as_str()
+            }
+        })
+        .collect();
+    if !content.is_empty() {
+        return content.concat();
+    }
+    summary
+        .iter()
+        .map(|item| match item {
+            ReasoningItemReasoningSummary::SummaryText { text } => text.as_str(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn take_reasoning(message: &mut Map<String, Value>, pending_reasoning: &mut String) {
+    if !pending_reasoning.is_empty() {
+        message.insert(
+            "reasoning_content".to_string(),
+            Value::String(std::mem::take(pending_reasoning)),
+        );
+    }
+}
+
+fn push_tool_call(
+    messages: &mut Vec<Value>,
+    assistant_open: &mut bool,
+    pending_reasoning: &mut String,
+    call_id: &str,
+    name: &str,
+    arguments: String,
+) {
+    let tool_call = json!({
+        "id": call_id,
+        "type": "function",
+        "function": { "name": name, "arguments": arguments },
+    });
+
+    if *assistant_open && let Some(Value::Object(message)) = messages.last_mut() {
+        take_reasoning(message, pending_reasoning);
+        match message.get_mut("tool_calls") {
+            Some(Value::Array(tool_calls)) => tool_calls.push(tool_call),
+            _ => {
+                message.insert("tool_calls".to_string(), Value::Array(vec![tool_call]));
+            }
+        }
+        return;
+    }
+
+    let mut message = Map::new();
+    message.insert("role".to_string(), Value::String("assistant".to_string()));
+    message.insert("content".to_string(), Value::Null);
+    message.insert("tool_calls".to_string(), Value::Array(vec![tool_call]));
+    take_reasoning(&mut message, pending_reasoning);
+    messages.push(Value::Object(message));
+    *assistant_open = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Reasoning;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use codex_protocol::openai_models::ReasoningEffort;
+    use pretty_assertions::assert_eq;
+
+    fn request(input: Vec<ResponseItem>, tools: Vec<Value>) -> ResponsesApiRequest {
+        ResponsesApiRequest {
+            model: "gpt-test".into(),
+            instructions: "Be brief.".into(),
+            input,
+            tools,
+            tool_choice: "auto".into(),
+            parallel_tool_calls: true,
+            reasoning: Some(Reasoning {
+                effort: Some(ReasoningEffort::High),
+                summary: None,
+            }),
+            store: false,
+            stream: true,
+            include: Vec::new(),
+            prompt_cache_key: None,
+            text: None,
+        }
+    }
+
+    #[test]
+    fn maps_items_and_tools_onto_messages() {
+        let input = vec![
+            ResponseItem::Message {
+                id: None,
+                role: "developer".into(),
+                content: vec![ContentItem::InputText {
+                    text: "sandbox: read-only".into(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+            ResponseItem::Message {
+                id: None,
+                role: "user".into(),
+                content: vec![ContentItem::InputText {
+                    text: "fix the bug".into(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+            ResponseItem::Reasoning {
+                id: "rs_1".into(),
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText {
+                    text: "look at main.rs".into(),
+                }]),
+                encrypted_content: None,
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".into(),
+                arguments: r#"{"command":["cat","main.rs"]}"#.into(),
+                call_id: "call_1".into(),
+            },
+            ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "call_2".into(),
+                name: "apply_patch".into(),
+                input: "*** Begin Patch".into(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "call_1".into(),
+                output: FunctionCallOutputPayload::from_text("fn main() {}".into()),
+            },
+            ResponseItem::CustomToolCallOutput {
+                call_id: "call_2".into(),
+                output: "Done".into(),
+            },
+            ResponseItem::Message {
+                id: None,
+                role: "assistant".into(),
+                content: vec![ContentItem::OutputText {
+                    text: "Fixed.".into(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+        ];
+        let tools = vec![
+            json!({
+                "type": "function",
+                "name": "shell",
+                "description": "Runs a command.",
+                "strict": false,
+                "parameters": {"type": "object", "properties": {}},
+            }),
+            json!({
+                "type": "custom",
+                "name": "apply_patch",
+                "description": "Applies a patch.",
+                "format": {"type": "grammar", "syntax": "lark", "definition": "start: patch"},
+            }),
+            json!({"type": "web_search"}),
+        ];
+
+        let body = build_chat_request(&request(input, tools)).expect("chat request");
+
+        assert_eq!(
+            body,
+            json!({
+                "model": "gpt-test",
+                "messages": [
+                    {"role": "system", "content": "Be brief."},
+                    {"role": "system", "content": "sandbox: read-only"},
+                    {"role": "user", "content": "fix the bug"},
+                    {
+                        "role": "assistant",
+                        "content": null,
+                        "reasoning_content": "look at main.rs",
+                        "tool_calls": [
+                            {
+                                "id": "call_1",
+                                "type": "function",
+                                "function": {"name": "shell", "arguments": r#"{"command":["cat","main.rs"]}"#},
+                            },
+                            {
+                                "id": "call_2",
+                                "type": "function",
+                                "function": {"name": "apply_patch", "arguments": r#"{"input":"*** Begin Patch"}"#},
+                            },
+                        ],
+                    },
+                    {"role": "tool", "tool_call_id": "call_1", "content": "fn main() {}"},
+                    {"role": "tool", "tool_call_id": "call_2", "content": "Done"},
+                    {"role": "assistant", "content": "Fixed."},
+                ],
+                "stream": true,
+                "stream_options": {"include_usage": true},
+                "tools": [
+                    {
+                        "type": "function",
+                        "function": {
+                            "name": "shell",
+                            "description": "Runs a command.",
+                            "parameters": {"type": "object", "properties": {}},
+                            "strict": false,
+                        },
+                    },
+                    {
+                        "type": "function",
+                        "function": {
+                            "name": "apply_patch",
+                            "description": "Applies a patch.",
+                            "parameters": {
+                                "type": "object",
+                                "properties": {
+                                    "input": {
+                                        "type": "string",
+                                        "description": "Input matching this grammar:\nstart: patch",
+                                    },
+                                },
+                                "required": ["input"],
+                                "additionalProperties": false,
+                            },
+                        },
+                    },
+                ],
+                "tool_choice": "auto",
+                "parallel_tool_calls": true,
+                "reasoning_effort": "high",
+            })
+        );
+    }
+}
@@ -1,2 +1,4 @@
+pub(crate) mod anthropic;
+pub(crate) mod chat;
 pub(crate) mod headers;
 pub mod responses;
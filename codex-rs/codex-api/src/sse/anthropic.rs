@@ -0,0 +1,639 @@
+//! Decodes a streamed Anthropic Messages response into [`ResponseEvent`]s.
+//!
+//! Every content block becomes the item the Responses API would have
+//! produced: `text` blocks are assistant messages, `thinking` and
+//! `redacted_thinking` blocks are reasoning items, and `tool_use` blocks are
+//! function or custom tool calls. A block's item is finished on its
+//! `content_block_stop`, and the response on `message_stop`.
+//!
+//! A thinking block's `signature` (or a redacted block's `data`) is kept as
+//! the reasoning item's `encrypted_content` so [`crate::requests::anthropic`]
+//! can hand the block back unchanged, as the API requires during tool use.
+
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::rate_limits::parse_all_rate_limits;
+use crate::sse::chat::custom_tool_input;
+use crate::telemetry::SseTelemetry;
+use codex_client::ByteStream;
+use codex_client::StreamResponse;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::StreamExt;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::trace;
+
+pub(crate) fn spawn_anthropic_stream(
+    stream_response: StreamResponse,
+    custom_tools: Vec<String>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+) -> ResponseStream {
+    let rate_limit_snapshots = parse_all_rate_limits(&stream_response.headers);
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(async move {
+        for snapshot in rate_limit_snapshots {
+            let _ = tx_event.send(Ok(ResponseEvent::RateLimits(snapshot))).await;
+        }
+        process_anthropic_sse(
+            stream_response.bytes,
+            custom_tools,
+            tx_event,
+            idle_timeout,
+            telemetry,
+        )
+        .await;
+    });
+    ResponseStream { rx_event }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum MessagesEvent {
+    MessageStart {
+        message: MessageStart,
+    },
+    ContentBlockStart {
+        index: usize,
+        content_block: ContentBlockStart,
+    },
+    ContentBlockDelta {
+        index: usize,
+        delta: ContentBlockDelta,
+    },
+    ContentBlockStop {
+        index: usize,
+    },
+    MessageDelta {
+        #[serde(default)]
+        delta: MessageDelta,
+        #[serde(default)]
+        usage: Option<MessagesUsage>,
+    },
+    MessageStop,
+    Ping,
+    Error {
+        error: MessagesError,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageStart {
+    id: String,
+    #[serde(default)]
+    usage: Option<MessagesUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ContentBlockStart {
+    Text,
+    Thinking,
+    RedactedThinking {
+        data: String,
+    },
+    ToolUse {
+        id: String,
+        name: String,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ContentBlockDelta {
+    TextDelta {
+        text: String,
+    },
+    ThinkingDelta {
+        thinking: String,
+    },
+    SignatureDelta {
+        signature: String,
+    },
+    InputJsonDelta {
+        partial_json: String,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct MessageDelta {
+    #[serde(default)]
+    stop_reason: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct MessagesUsage {
+    #[serde(default)]
+    input_tokens: Option<i64>,
+    #[serde(default)]
+    cache_creation_input_tokens: Option<i64>,
+    #[serde(default)]
+    cache_read_input_tokens: Option<i64>,
+    #[serde(default)]
+    output_tokens: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessagesError {
+    #[serde(rename = "type", default)]
+    kind: String,
+    #[serde(default)]
+    message: String,
+}
+
+impl From<MessagesError> for ApiError {
+    fn from(error: MessagesError) -> Self {
+        match error.kind.as_str() {
+            "overloaded_error" => ApiError::ServerOverloaded,
+            "rate_limit_error" | "api_error" => ApiError::Retryable {
+                message: error.message,
+                delay: None,
+            },
+            "invalid_request_error" if error.message.contains("prompt is too long") => {
+                ApiError::ContextWindowExceeded
+            }
+            "invalid_request_error" => ApiError::InvalidRequest {
+                message: error.message,
+            },
+            _ => ApiError::Stream(error.message),
+        }
+    }
+}
+
+/// A content block whose deltas are still arriving.
+#[derive(Debug)]
+enum PartialBlock {
+    Text(String),
+    Thinking {
+        text: String,
+        signature: String,
+    },
+    RedactedThinking(String),
+    ToolUse {
+        id: String,
+        name: String,
+        input: String,
+    },
+}
+
+/// Items being assembled from the events of one message.
+#[derive(Debug, Default)]
+struct MessagesResponseState {
+    response_id: String,
+    blocks: BTreeMap<usize, PartialBlock>,
+    stop_reason: Option<String>,
+    input_tokens: i64,
+    cache_creation_input_tokens: i64,
+    cached_input_tokens: i64,
+    output_tokens: i64,
+    completed: bool,
+}
+
+impl MessagesResponseState {
+    fn message_item(&self, index: usize, text: Option<&str>) -> ResponseItem {
+        ResponseItem::Message {
+            id: Some(format!("msg_{}_{index}", self.response_id)),
+            role: "assistant".to_string(),
+            content: text
+                .map(|text| {
+                    vec![ContentItem::OutputText {
+                        text: text.to_string(),
+                    }]
+                })
+                .unwrap_or_default(),
+            end_turn: None,
+            phase: None,
+        }
+    }
+
+    fn reasoning_item(
+        &self,
+        index: usize,
+        text: Option<&str>,
+        encrypted_content: Option<String>,
+    ) -> ResponseItem {
+        ResponseItem::Reasoning {
+            id: format!("rs_{}_{index}", self.response_id),
+            summary: Vec::new(),
+            content: Some(
+                text.map(|text| {
+                    vec![ReasoningItemContent::ReasoningText {
+                        text: text.to_string(),
+                    }]
+                })
+                .unwrap_or_default(),
+            ),
+            encrypted_content,
+        }
+    }
+
+    fn record_usage(&mut self, usage: MessagesUsage) {
+        // Later events repeat the counts they carry, so keep the latest of each.
+        if let Some(input_tokens) = usage.input_tokens {
+            self.input_tokens = input_tokens;
+        }
+        if let Some(cache_creation) = usage.cache_creation_input_tokens {
+            self.cache_creation_input_tokens = cache_creation;
+        }
+        if let Some(cache_read) = usage.cache_read_input_tokens {
+            self.cached_input_tokens = cache_read;
+        }
+        if let Some(output_tokens) = usage.output_tokens {
+            self.output_tokens = output_tokens;
+        }
+    }
+
+    fn token_usage(&self) -> TokenUsage {
+        // Anthropic counts cached input separately from `input_tokens`.
+        let input_tokens =
+            self.input_tokens + self.cache_creation_input_tokens + self.cached_input_tokens;
+        TokenUsage {
+            input_tokens,
+            cached_input_tokens: self.cached_input_tokens,
+            output_tokens: self.output_tokens,
+            reasoning_output_tokens: 0,
+            total_tokens: input_tokens + self.output_tokens,
+        }
+    }
+
+    fn apply(
+        &mut self,
+        event: MessagesEvent,
+        custom_tools: &[String],
+    ) -> Result<Vec<ResponseEvent>, ApiError> {
+        let mut events = Vec::new();
+        match event {
+            MessagesEvent::MessageStart { message } => {
+                self.response_id = message.id;
+                if let Some(usage) = message.usage {
+                    self.record_usage(usage);
+                }
+                events.push(ResponseEvent::Created);
+            }
+            MessagesEvent::ContentBlockStart {
+                index,
+                content_block,
+            } => {
+                let block = match content_block {
+                    ContentBlockStart::Text => {
+                        events.push(ResponseEvent::OutputItemAdded(
+                            self.message_item(index, None),
+                        ));
+                        PartialBlock::Text(String::new())
+                    }
+                    ContentBlockStart::Thinking => {
+                        events.push(ResponseEvent::OutputItemAdded(
+                            self.reasoning_item(index, None, None),
+                        ));
+                        PartialBlock::Thinking {
+                            text: String::new(),
+                            signature: String::new(),
+                        }
+                    }
+                    ContentBlockStart::RedactedThinking { data } => {
+                        PartialBlock::RedactedThinking(data)
+                    }
+                    ContentBlockStart::ToolUse { id, name } => PartialBlock::ToolUse {
+                        id,
+                        name,
+                        input: String::new(),
+                    },
+                    ContentBlockStart::Unknown => return Ok(events),
+                };
+                self.blocks.insert(index, block);
+            }
+            MessagesEvent::ContentBlockDelta { index, delta } => {
+                match (self.blocks.get_mut(&index), delta) {
+                    (
+                        Some(PartialBlock::Text(text)),
+                        ContentBlockDelta::TextDelta { text: delta },
+                    ) => {
+                        text.push_str(&delta);
+                        events.push(ResponseEvent::OutputTextDelta(delta));
+                    }
+                    (
+                        Some(PartialBlock::Thinking { text, .. }),
+                        ContentBlockDelta::ThinkingDelta { thinking },
+                    ) => {
+                        text.push_str(&thinking);
+                        events.push(ResponseEvent::ReasoningContentDelta {
+                            delta: thinking,
+                            content_index: 0,
+                        });
+                    }
+                    (
+                        Some(PartialBlock::Thinking { signature, .. }),
+                        ContentBlockDelta::SignatureDelta { signature: delta },
+                    ) => signature.push_str(&delta),
+                    (
+                        Some(PartialBlock::ToolUse { input, .. }),
+                        ContentBlockDelta::InputJsonDelta { partial_json },
+                    ) => input.push_str(&partial_json),
+                    _ => {}
+                }
+            }
+            MessagesEvent::ContentBlockStop { index } => {
+                if let Some(block) = self.blocks.remove(&index) {
+                    events.push(ResponseEvent::OutputItemDone(self.finish_block(
+                        index,
+                        block,
+                        custom_tools,
+                    )));
+                }
+            }
+            MessagesEvent::MessageDelta { delta, usage } => {
+                if let Some(stop_reason) = delta.stop_reason {
+                    self.stop_reason = Some(stop_reason);
+                }
+                if let Some(usage) = usage {
+                    self.record_usage(usage);
+                }
+            }
+            MessagesEvent::MessageStop => {
+                if self.stop_reason.as_deref() == Some("max_tokens") {
+                    return Err(ApiError::Stream(
+                        "Incomplete response returned, reason: max_tokens".to_string(),
+                    ));
+                }
+                self.completed = true;
+                events.push(ResponseEvent::Completed {
+                    response_id: self.response_id.clone(),
+                    token_usage: Some(self.token_usage()),
+                    can_append: false,
+                });
+            }
+            MessagesEvent::Error { error } => return Err(error.into()),
+            MessagesEvent::Ping | MessagesEvent::Unknown => {}
+        }
+        Ok(events)
+    }
+
+    fn finish_block(
+        &self,
+        index: usize,
+        block: PartialBlock,
+        custom_tools: &[String],
+    ) -> ResponseItem {
+        match block {
+            PartialBlock::Text(text) => self.message_item(index, Some(&text)),
+            PartialBlock::Thinking { text, signature } => {
+                self.reasoning_item(index, Some(&text), Some(signature))
+            }
+            PartialBlock::RedactedThinking(data) => self.reasoning_item(index, None, Some(data)),
+            PartialBlock::ToolUse { id, name, input } => {
+                if custom_tools.contains(&name) {
+                    ResponseItem::CustomToolCall {
+                        id: None,
+                        status: None,
+                        call_id: id,
+                        input: custom_tool_input(&input),
+                        name,
+                    }
+                } else {
+                    ResponseItem::FunctionCall {
+                        id: None,
+                        name,
+                        arguments: if input.is_empty() {
+                            "{}".to_string()
+                        } else {
+                            input
+                        },
+                        call_id: id,
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub(crate) async fn process_anthropic_sse(
+    stream: ByteStream,
+    custom_tools: Vec<String>,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+) {
+    let mut stream = stream.eventsource();
+    let mut state = MessagesResponseState::default();
+
+    while !state.completed {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                debug!("SSE Error: {e:#}");
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream(
+                        "stream closed before message_stop".into(),
+                    )))
+                    .await;
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", &sse.data);
+        let event: MessagesEvent = match serde_json::from_str(&sse.data) {
+            Ok(event) => event,
+            Err(e) => {
+                debug!("Failed to parse SSE event: {e}, data: {}", &sse.data);
+                continue;
+            }
+        };
+        match state.apply(event, &custom_tools) {
+            Ok(events) => {
+                for event in events {
+                    if tx_event.send(Ok(event)).await.is_err() {
+                        return;
+                    }
+                }
+            }
+            Err(error) => {
+                let _ = tx_event.send(Err(error)).await;
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_client::TransportError;
+    use futures::TryStreamExt;
+    use pretty_assertions::assert_eq;
+    use serde_json::Value;
+    use serde_json::json;
+    use tokio_util::io::ReaderStream;
+
+    async fn run_anthropic_sse(
+        events: Vec<Value>,
+        custom_tools: Vec<String>,
+    ) -> Vec<Result<ResponseEvent, ApiError>> {
+        let mut body = String::new();
+        for event in events {
+            let kind = event["type"].as_str().unwrap_or_default().to_string();
+            body.push_str(&format!("event: {kind}\ndata: {event}\n\n"));
+        }
+        let stream = ReaderStream::new(std::io::Cursor::new(body))
+            .map_err(|err| TransportError::Network(err.to_string()));
+        let (tx, mut rx) = mpsc::channel(32);
+        process_anthropic_sse(
+            Box::pin(stream),
+            custom_tools,
+            tx,
+            Duration::from_secs(5),
+            None,
+        )
+        .await;
+        let mut events = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            events.push(event);
+        }
+        events
+    }
+
+    #[tokio::test]
+    async fn assembles_thinking_text_and_tool_use() {
+        let events = run_anthropic_sse(
+            vec![
+                json!({"type": "message_start", "message": {"id": "m1", "usage": {"input_tokens": 10, "cache_read_input_tokens": 90, "output_tokens": 1}}}),
+                json!({"type": "content_block_start", "index": 0, "content_block": {"type": "thinking", "thinking": ""}}),
+                json!({"type": "content_block_delta", "index": 0, "delta": {"type": "thinking_delta", "thinking": "Think"}}),
+                json!({"type": "content_block_delta", "index": 0, "delta": {"type": "signature_delta", "signature": "sig"}}),
+                json!({"type": "content_block_stop", "index": 0}),
+                json!({"type": "content_block_start", "index": 1, "content_block": {"type": "text", "text": ""}}),
+                json!({"type": "content_block_delta", "index": 1, "delta": {"type": "text_delta", "text": "Hi"}}),
+                json!({"type": "content_block_stop", "index": 1}),
+                json!({"type": "ping"}),
+                json!({"type": "content_block_start", "index": 2, "content_block": {"type": "tool_use", "id": "toolu_1", "name": "shell", "input": {}}}),
+                json!({"type": "content_block_delta", "index": 2, "delta": {"type": "input_json_delta", "partial_json": "{\"command\":"}}),
+                json!({"type": "content_block_delta", "index": 2, "delta": {"type": "input_json_delta", "partial_json": "[]}"}}),
+                json!({"type": "content_block_stop", "index": 2}),
+                json!({"type": "content_block_start", "index": 3, "content_block": {"type": "tool_use", "id": "toolu_2", "name": "apply_patch", "input": {}}}),
+                json!({"type": "content_block_delta", "index": 3, "delta": {"type": "input_json_delta", "partial_json": "{\"input\":\"*** Begin Patch\"}"}}),
+                json!({"type": "content_block_stop", "index": 3}),
+                json!({"type": "message_delta", "delta": {"stop_reason": "tool_use"}, "usage": {"output_tokens": 20}}),
+                json!({"type": "message_stop"}),
+            ],
+            vec!["apply_patch".to_string()],
+        )
+        .await;
+
+        let events: Vec<String> = events
+            .into_iter()
+            .map(|event| format!("{:?}", event.expect("anthropic event")))
+            .collect();
+        let expected: Vec<String> = [
+            ResponseEvent::Created,
+            ResponseEvent::OutputItemAdded(ResponseItem::Reasoning {
+                id: "rs_m1_0".into(),
+                summary: Vec::new(),
+                content: Some(Vec::new()),
+                encrypted_content: None,
+            }),
+            ResponseEvent::ReasoningContentDelta {
+                delta: "Think".into(),
+                content_index: 0,
+            },
+            ResponseEvent::OutputItemDone(ResponseItem::Reasoning {
+                id: "rs_m1_0".into(),
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText {
+                    text: "Think".into(),
+                }]),
+                encrypted_content: Some("sig".into()),
+            }),
+            ResponseEvent::OutputItemAdded(ResponseItem::Message {
+                id: Some("msg_m1_1".into()),
+                role: "assistant".into(),
+                content: Vec::new(),
+                end_turn: None,
+                phase: None,
+            }),
+            ResponseEvent::OutputTextDelta("Hi".into()),
+            ResponseEvent::OutputItemDone(ResponseItem::Message {
+                id: Some("msg_m1_1".into()),
+                role: "assistant".into(),
+                content: vec![ContentItem::OutputText { text: "Hi".into() }],
+                end_turn: None,
+                phase: None,
+            }),
+            ResponseEvent::OutputItemDone(ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".into(),
+                arguments: "{\"command\":[]}".into(),
+                call_id: "toolu_1".into(),
+            }),
+            ResponseEvent::OutputItemDone(ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "toolu_2".into(),
+                name: "apply_patch".into(),
+                input: "*** Begin Patch".into(),
+            }),
+            ResponseEvent::Completed {
+                response_id: "m1".into(),
+                token_usage: Some(TokenUsage {
+                    input_tokens: 100,
+                    cached_input_tokens: 90,
+                    output_tokens: 20,
+                    reasoning_output_tokens: 0,
+                    total_tokens: 120,
+                }),
+                can_append: false,
+            },
+        ]
+        .iter()
+        .map(|event| format!("{event:?}"))
+        .collect();
+        assert_eq!(events, expected);
+    }
+
+    #[tokio::test]
+    async fn maps_error_events() {
+        let events = run_anthropic_sse(
+            vec![
+                json!({"type": "message_start", "message": {"id": "m1"}}),
+                json!({"type": "error", "error": {"type": "overloaded_error", "message": "Overloaded"}}),
+            ],
+            Vec::new(),
+        )
+        .await;
+
+        assert!(matches!(
+            events.as_slice(),
+            [Ok(ResponseEvent::Created), Err(ApiError::ServerOverloaded)]
+        ));
+    }
+}
@@ -0,0 +1,684 @@
+//! Decodes a streamed Chat Completions response into [`ResponseEvent`]s.
+//!
+//! Chat Completions streams deltas of a single choice. They are replayed as
+//! the items the Responses API would have produced: a reasoning item for
+//! `reasoning_content` (or `reasoning`) deltas, an assistant message for
+//! `content` deltas, and one function or custom tool call per `tool_calls`
+//! entry, all finished when the stream reports `[DONE]`.
+//!
+//! Servers stream tool calls one after another, so a call whose arguments are
+//! already a complete JSON object is finished as soon as the next call
+//! starts, letting it run while the rest of the response streams. The open
+//! message is finished first, so items keep the order they were streamed in.
+//!
+//! A tool call's `id` becomes the item's `call_id`. Servers that leave the id
+//! out get a generated one, so the tool output can still be matched to it on
+//! the next request.
+
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::rate_limits::parse_all_rate_limits;
+use crate::requests::chat::CUSTOM_TOOL_INPUT_ARG;
+use crate::telemetry::SseTelemetry;
+use codex_client::ByteStream;
+use codex_client::StreamResponse;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::trace;
+
+pub(crate) fn spawn_chat_stream(
+    stream_response: StreamResponse,
+    custom_tools: Vec<String>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+) -> ResponseStream {
+    let rate_limit_snapshots = parse_all_rate_limits(&stream_response.headers);
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(async move {
+        for snapshot in rate_limit_snapshots {
+            let _ = tx_event.send(Ok(ResponseEvent::RateLimits(snapshot))).await;
+        }
+        process_chat_sse(
+            stream_response.bytes,
+            custom_tools,
+            tx_event,
+            idle_timeout,
+            telemetry,
+        )
+        .await;
+    });
+    ResponseStream { rx_event }
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChunk {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    choices: Vec<ChatChoice>,
+    #[serde(default)]
+    usage: Option<ChatUsage>,
+    #[serde(default)]
+    error: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    #[serde(default)]
+    delta: Option<ChatDelta>,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ChatDelta {
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    reasoning_content: Option<String>,
+    #[serde(default)]
+    reasoning: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<ChatToolCallDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatToolCallDelta {
+    #[serde(default)]
+    index: Option<usize>,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    function: Option<ChatFunctionDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatFunctionDelta {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    arguments: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatUsage {
+    #[serde(default)]
+    prompt_tokens: i64,
+    #[serde(default)]
+    completion_tokens: i64,
+    #[serde(default)]
+    total_tokens: i64,
+    #[serde(default)]
+    prompt_tokens_details: Option<ChatPromptTokensDetails>,
+    #[serde(default)]
+    completion_tokens_details: Option<ChatCompletionTokensDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatPromptTokensDetails {
+    #[serde(default)]
+    cached_tokens: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionTokensDetails {
+    #[serde(default)]
+    reasoning_tokens: i64,
+}
+
+impl From<ChatUsage> for TokenUsage {
+    fn from(usage: ChatUsage) -> Self {
+        TokenUsage {
+            input_tokens: usage.prompt_tokens,
+            cached_input_tokens: usage
+                .prompt_tokens_details
+                .map_or(0, |details| details.cached_tokens),
+            output_tokens: usage.completion_tokens,
+            reasoning_output_tokens: usage
+                .completion_tokens_details
+                .map_or(0, |details| details.reasoning_tokens),
+            total_tokens: usage.total_tokens,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct PartialToolCall {
+    id: Option<String>,
+    name: String,
+    arguments: String,
+}
+
+/// Items being assembled from the deltas of one response.
+#[derive(Debug, Default)]
+struct ChatResponseState {
+    response_id: String,
+    created: bool,
+    reasoning: Option<String>,
+    reasoning_done: bool,
+    message: Option<String>,
+    /// Messages started so far; text after a finished message starts another.
+    messages: usize,
+    tool_calls: BTreeMap<usize, PartialToolCall>,
+    finish_reason: Option<String>,
+    usage: Option<TokenUsage>,
+}
+
+impl ChatResponseState {
+    fn reasoning_item(&self, text: Option<&str>) -> ResponseItem {
+        ResponseItem::Reasoning {
+            id: format!("rs_{}", self.response_id),
+            summary: Vec::new(),
+            content: Some(
+                text.map(|text| {
+                    vec![ReasoningItemContent::ReasoningText {
+                        text: text.to_string(),
+                    }]
+                })
+                .unwrap_or_default(),
+            ),
+            encrypted_content: None,
+        }
+    }
+
+    fn message_item(&self, text: Option<&str>) -> ResponseItem {
+        let id = match self.messages {
+            0 | 1 => format!("msg_{}", self.response_id),
+            n => format!("msg_{}_{n}", self.response_id),
+        };
+        ResponseItem::Message {
+            id: Some(id),
+            role: "assistant".to_string(),
+            content: text
+                .map(|text| {
+                    vec![ContentItem::OutputText {
+                        text: text.to_string(),
+                    }]
+                })
+                .unwrap_or_default(),
+            end_turn: None,
+            phase: None,
+        }
+    }
+
+    fn tool_call_item(
+        &self,
+        index: usize,
+        tool_call: PartialToolCall,
+        custom_tools: &[String],
+    ) -> ResponseItem {
+        let call_id = tool_call
+            .id
+            .unwrap_or_else(|| format!("call_{}_{index}", self.response_id));
+        if custom_tools.contains(&tool_call.name) {
+            ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id,
+                input: custom_tool_input(&tool_call.arguments),
+                name: tool_call.name,
+            }
+        } else {
+            ResponseItem::FunctionCall {
+                id: None,
+                name: tool_call.name,
+                arguments: if tool_call.arguments.is_empty() {
+                    "{}".to_string()
+                } else {
+                    tool_call.arguments
+                },
+                call_id,
+            }
+        }
+    }
+
+    /// Events for one chunk, in the order the Responses API would send them.
+    fn apply(&mut self, chunk: ChatChunk, custom_tools: &[String]) -> Vec<ResponseEvent> {
+        let mut events = Vec::new();
+        if let Some(id) = chunk.id
+            && self.response_id.is_empty()
+        {
+            self.response_id = id;
+        }
+        if !self.created {
+            self.created = true;
+            events.push(ResponseEvent::Created);
+        }
+        if let Some(usage) = chunk.usage {
+            self.usage = Some(usage.into());
+        }
+
+        let Some(choice) = chunk.choices.into_iter().next() else {
+            return events;
+        };
+        if let Some(finish_reason) = choice.finish_reason {
+            self.finish_reason = Some(finish_reason);
+        }
+        let delta = choice.delta.unwrap_or_default();
+
+        if let Some(reasoning) = delta
+            .reasoning_content
+            .or(delta.reasoning)
+            .filter(|text| !text.is_empty())
+            && !self.reasoning_done
+        {
+            if self.reasoning.is_none() {
+                events.push(ResponseEvent::OutputItemAdded(self.reasoning_item(None)));
+            }
+            self.reasoning
+                .get_or_insert_with(String::new)
+                .push_str(&reasoning);
+            events.push(ResponseEvent::ReasoningContentDelta {
+                delta: reasoning,
+                content_index: 0,
+            });
+        }
+
+        let content = delta.content.filter(|text| !text.is_empty());
+        if content.is_some() || !delta.tool_calls.is_empty() {
+            // Reasoning comes first; close it once the answer starts.
+            events.extend(self.finish_reasoning());
+        }
+        if let Some(content) = content {
+            if self.message.is_none() {
+                self.messages += 1;
+                events.push(ResponseEvent::OutputItemAdded(self.message_item(None)));
+            }
+            self.message
+                .get_or_insert_with(String::new)
+                .push_str(&content);
+            events.push(ResponseEvent::OutputTextDelta(content));
+        }
+
+        for tool_call in delta.tool_calls {
+            let index = tool_call.index.unwrap_or(self.tool_calls.len());
+            events.extend(self.finish_tool_calls_before(index, custom_tools));
+            let partial = self.tool_calls.entry(index).or_default();
+            if let Some(id) = tool_call.id.filter(|id| !id.is_empty()) {
+                partial.id = Some(id);
+            }
+            if let Some(function) = tool_call.function {
+                if let Some(name) = function.name {
+                    partial.name.push_str(&name);
+                }
+                if let Some(arguments) = function.arguments {
+                    partial.arguments.push_str(&arguments);
+                }
+            }
+        }
+
+        events
+    }
+
+    fn finish_reasoning(&mut self) -> Option<ResponseEvent> {
+        if self.reasoning_done {
+            return None;
+        }
+        let text = self.reasoning.take()?;
+        self.reasoning_done = true;
+        Some(ResponseEvent::OutputItemDone(
+            self.reasoning_item(Some(&text)),
+        ))
+    }
+
+    fn finish_message(&mut self) -> Option<ResponseEvent> {
+        let text = self.message.take()?;
+        Some(ResponseEvent::OutputItemDone(
+            self.message_item(Some(&text)),
+        ))
+    }
+
+    /// Finishes the tool calls before `index` whose arguments are complete,
+    /// now that a later call is streaming. Calls with incomplete arguments
+    /// are left to [`Self::finish`], in case the server interleaves calls.
+    fn finish_tool_calls_before(
+        &mut self,
+        index: usize,
+        custom_tools: &[String],
+    ) -> Vec<ResponseEvent> {
+        let done: Vec<usize> = self
+            .tool_calls
+            .range(..index)
+            .filter(|(_, tool_call)| {
+                serde_json::from_str::<Value>(&tool_call.arguments).is_ok_and(|v| v.is_object())
+            })
+            .map(|(index, _)| *index)
+            .collect();
+        if done.is_empty() {
+            return Vec::new();
+        }
+        let mut events: Vec<ResponseEvent> = self.finish_message().into_iter().collect();
+        for index in done {
+            if let Some(tool_call) = self.tool_calls.remove(&index) {
+                events.push(ResponseEvent::OutputItemDone(self.tool_call_item(
+                    index,
+                    tool_call,
+                    custom_tools,
+                )));
+            }
+        }
+        events
+    }
+
+    /// Completes every open item and the response.
+    fn finish(mut self, custom_tools: &[String]) -> Vec<ResponseEvent> {
+        let mut events: Vec<ResponseEvent> = self.finish_reasoning().into_iter().collect();
+        events.extend(self.finish_message());
+        for (index, tool_call) in std::mem::take(&mut self.tool_calls) {
+            let item = self.tool_call_item(index, tool_call, custom_tools);
+            events.push(ResponseEvent::OutputItemDone(item));
+        }
+        events.push(ResponseEvent::Completed {
+            response_id: self.response_id,
+            token_usage: self.usage,
+            can_append: false,
+        });
+        events
+    }
+}
+
+/// The freeform input of a custom tool call, or the raw arguments when the
+/// model did not wrap it as asked.
+pub(crate) fn custom_tool_input(arguments: &str) -> String {
+    serde_json::from_str::<Value>(arguments)
+        .ok()
+        .and_then(|value| {
+            value
+                .get(CUSTOM_TOOL_INPUT_ARG)
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| arguments.to_string())
+}
+
+fn error_message(error: &Value) -> String {
+    error
+        .get("message")
+        .and_then(Value::as_str)
+        .map_or_else(|| error.to_string(), str::to_string)
+}
+
+pub(crate) async fn process_chat_sse(
+    stream: ByteStream,
+    custom_tools: Vec<String>,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+) {
+    let mut stream = stream.eventsource();
+    let mut state = ChatResponseState::default();
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                debug!("SSE Error: {e:#}");
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) => {
+                // Some servers end the stream after the finish reason
+                // without sending `[DONE]`.
+                if state.finish_reason.is_some() {
+                    break;
+                }
+                let _ = tx_event
+                    .send(Err(ApiError::Stream(
+                        "stream closed before the chat completion finished".into(),
+                    )))
+                    .await;
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", &sse.data);
+        if sse.data.trim() == "[DONE]" {
+            break;
+        }
+
+        let chunk: ChatChunk = match serde_json::from_str(&sse.data) {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                debug!("Failed to parse SSE event: {e}, data: {}", &sse.data);
+                continue;
+            }
+        };
+        if let Some(error) = chunk.error.as_ref() {
+            let _ = tx_event
+                .send(Err(ApiError::Stream(error_message(error))))
+                .await;
+            return;
+        }
+        for event in state.apply(chunk, &custom_tools) {
+            if tx_event.send(Ok(event)).await.is_err() {
+                return;
+            }
+        }
+    }
+
+    for event in state.finish(&custom_tools) {
+        if tx_event.send(Ok(event)).await.is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_client::TransportError;
+    use futures::TryStreamExt;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tokio_util::io::ReaderStream;
+
+    async fn run_chat_sse(chunks: Vec<Value>, custom_tools: Vec<String>) -> Vec<ResponseEvent> {
+        let mut body = String::new();
+        for chunk in chunks {
+            body.push_str(&format!("data: {chunk}\n\n"));
+        }
+        body.push_str("data: [DONE]\n\n");
+        let stream = ReaderStream::new(std::io::Cursor::new(body))
+            .map_err(|err| TransportError::Network(err.to_string()));
+        let (tx, mut rx) = mpsc::channel(16);
+        process_chat_sse(
+            Box::pin(stream),
+            custom_tools,
+            tx,
+            Duration::from_secs(5),
+            None,
+        )
+        .await;
+        let mut events = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            events.push(event.expect("chat event"));
+        }
+        events
+    }
+
+    #[tokio::test]
+    async fn assembles_reasoning_text_and_tool_calls() {
+        let events = run_chat_sse(
+            vec![
+                json!({"id": "c1", "choices": [{"delta": {"role": "assistant", "reasoning_content": "Think"}}]}),
+                json!({"id": "c1", "choices": [{"delta": {"content": "Hi"}}]}),
+                json!({"id": "c1", "choices": [{"delta": {"tool_calls": [
+                    {"index": 0, "id": "call_a", "function": {"name": "shell", "arguments": "{\"comm"}},
+                    {"index": 1, "function": {"name": "apply_patch", "arguments": ""}},
+                ]}}]}),
+                json!({"id": "c1", "choices": [{"delta": {"tool_calls": [
+                    {"index": 0, "function": {"arguments": "and\":[]}"}},
+                    {"index": 1, "function": {"arguments": "{\"input\":\"*** Begin Patch\"}"}},
+                ]}, "finish_reason": "tool_calls"}]}),
+                json!({"id": "c1", "choices": [], "usage": {"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15}}),
+            ],
+            vec!["apply_patch".to_string()],
+        )
+        .await;
+
+        let events: Vec<String> = events.iter().map(|event| format!("{event:?}")).collect();
+        let expected: Vec<String> = [
+            ResponseEvent::Created,
+            ResponseEvent::OutputItemAdded(ResponseItem::Reasoning {
+                id: "rs_c1".into(),
+                summary: Vec::new(),
+                content: Some(Vec::new()),
+                encrypted_content: None,
+            }),
+            ResponseEvent::ReasoningContentDelta {
+                delta: "Think".into(),
+                content_index: 0,
+            },
+            ResponseEvent::OutputItemDone(ResponseItem::Reasoning {
+                id: "rs_c1".into(),
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText {
+                    text: "Think".into(),
+                }]),
+                encrypted_content: None,
+            }),
+            ResponseEvent::OutputItemAdded(ResponseItem::Message {
+                id: Some("msg_c1".into()),
+                role: "assistant".into(),
+                content: Vec::new(),
+                end_turn: None,
+                phase: None,
+            }),
+            ResponseEvent::OutputTextDelta("Hi".into()),
+            ResponseEvent::OutputItemDone(ResponseItem::Message {
+                id: Some("msg_c1".into()),
+                role: "assistant".into(),
+                content: vec![ContentItem::OutputText { text: "Hi".into() }],
+                end_turn: None,
+                phase: None,
+            }),
+            ResponseEvent::OutputItemDone(ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".into(),
+                arguments: "{\"command\":[]}".into(),
+                call_id: "call_a".into(),
+            }),
+            ResponseEvent::OutputItemDone(ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "call_c1_1".into(),
+                name: "apply_patch".into(),
+                input: "*** Begin Patch".into(),
+            }),
+            ResponseEvent::Completed {
+                response_id: "c1".into(),
+                token_usage: Some(TokenUsage {
+                    input_tokens: 10,
+                    cached_input_tokens: 0,
+                    output_tokens: 5,
+                    reasoning_output_tokens: 0,
+                    total_tokens: 15,
+                }),
+                can_append: false,
+            },
+        ]
+        .iter()
+        .map(|event| format!("{event:?}"))
+        .collect();
+        assert_eq!(events, expected);
+    }
+
+    #[test]
+    fn finishes_a_tool_call_once_the_next_one_starts() {
+        let chunk = |value: Value| serde_json::from_value::<ChatChunk>(value).expect("chunk");
+        let mut state = ChatResponseState::default();
+        state.apply(
+            chunk(json!({"id": "c1", "choices": [{"delta": {"content": "Listing"}}]})),
+            &[],
+        );
+        state.apply(
+            chunk(json!({"id": "c1", "choices": [{"delta": {"tool_calls": [
+                {"index": 0, "id": "call_a", "function": {"name": "shell", "arguments": "{\"command\":[\"ls\"]}"}},
+            ]}}]})),
+            &[],
+        );
+
+        let events = state.apply(
+            chunk(json!({"id": "c1", "choices": [{"delta": {"tool_calls": [
+                {"index": 1, "id": "call_b", "function": {"name": "shell", "arguments": "{\"comm"}},
+            ]}}]})),
+            &[],
+        );
+        let events: Vec<String> = events.iter().map(|event| format!("{event:?}")).collect();
+        let expected: Vec<String> = [
+            ResponseEvent::OutputItemDone(ResponseItem::Message {
+                id: Some("msg_c1".into()),
+                role: "assistant".into(),
+                content: vec![ContentItem::OutputText {
+                    text: "Listing".into(),
+                }],
+                end_turn: None,
+                phase: None,
+            }),
+            ResponseEvent::OutputItemDone(ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".into(),
+                arguments: "{\"command\":[\"ls\"]}".into(),
+                call_id: "call_a".into(),
+            }),
+        ]
+        .iter()
+        .map(|event| format!("{event:?}"))
+        .collect();
+        assert_eq!(events, expected);
+
+        // Only the second call is left for the end of the stream.
+        let events = state.finish(&[]);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            &events[0],
+            ResponseEvent::OutputItemDone(ResponseItem::FunctionCall { call_id, .. }) if call_id == "call_b"
+        ));
+    }
+
+    #[tokio::test]
+    async fn error_chunk_fails_the_stream() {
+        let body = "data: {\"error\":{\"message\":\"model not found\"}}\n\n".to_string();
+        let stream = ReaderStream::new(std::io::Cursor::new(body))
+            .map_err(|err| TransportError::Network(err.to_string()));
+        let (tx, mut rx) = mpsc::channel(16);
+        process_chat_sse(
+            Box::pin(stream),
+            Vec::new(),
+            tx,
+            Duration::from_secs(5),
+            None,
+        )
+        .await;
+
+        let Some(Err(ApiError::Stream(message))) = rx.recv().await else {
+            panic!("expected a stream error");
+        };
+        assert_eq!(message, "model not found");
+    }
+}
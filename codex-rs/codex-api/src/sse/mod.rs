@@ -1,3 +1,5 @@
+pub(crate) mod anthropic;
+pub(crate) mod chat;
 pub mod responses;
 
 pub use responses::process_sse;
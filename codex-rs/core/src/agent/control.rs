@@ -2,11 +2,13 @@ use crate::agent::AgentStatus;
 use crate::agent::guards::Guards;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
+use crate::thread_manager::NewThread;
 use crate::thread_manager::ThreadManagerState;
 use codex_protocol::ThreadId;
 use codex_protocol::protocol::Op;
 use codex_protocol::protocol::SessionSource;
 use codex_protocol::user_input::UserInput;
+use futures::future::BoxFuture;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Weak;
@@ -93,6 +95,36 @@ impl AgentControl {
         Ok(resumed_thread.thread_id)
     }
 
+    /// Fork a thread from its recorded rollout into a new, independent thread that keeps the
+    /// history before the `nth_user_message`-th user message (0-based).
+    ///
+    /// The future is boxed because spawning the fork re-enters `Codex::spawn`, whose submission
+    /// loop is what drives this call; a named future type keeps that cycle out of the `Send`
+    /// analysis.
+    pub(crate) fn fork_thread(
+        &self,
+        config: crate::config::Config,
+        rollout_path: PathBuf,
+        nth_user_message: usize,
+    ) -> BoxFuture<'static, CodexResult<NewThread>> {
+        let control = self.clone();
+        Box::pin(async move {
+            let state = control.upgrade()?;
+            // The fork is a sibling of the forked thread rather than a sub-agent, so it gets its
+            // own spawn guards instead of sharing this session's.
+            let new_thread = state
+                .fork_thread_from_rollout(
+                    config,
+                    rollout_path,
+                    nth_user_message,
+                    AgentControl::new(control.manager.clone()),
+                )
+                .await?;
+            state.notify_thread_created(new_thread.thread_id);
+            Ok(new_thread)
+        })
+    }
+
     /// Send rich user input items to an existing agent thread.
     pub(crate) async fn send_input(
         &self,
@@ -171,8 +203,14 @@ mod tests {
     use crate::config::ConfigBuilder;
     use assert_matches::assert_matches;
     use codex_protocol::config_types::ModeKind;
+    use codex_protocol::models::ContentItem;
+    use codex_protocol::models::ResponseItem;
     use codex_protocol::protocol::ErrorEvent;
     use codex_protocol::protocol::EventMsg;
+    use codex_protocol::protocol::RolloutItem;
+    use codex_protocol::protocol::RolloutLine;
+    use codex_protocol::protocol::SessionMeta;
+    use codex_protocol::protocol::SessionMetaLine;
     use codex_protocol::protocol::TurnAbortReason;
     use codex_protocol::protocol::TurnAbortedEvent;
     use codex_protocol::protocol::TurnCompleteEvent;
@@ -205,6 +243,54 @@ mod tests {
         }]
     }
 
+    /// Writes a rollout with one user message per entry in `prompts`.
+    fn write_rollout(codex_home: &std::path::Path, prompts: &[&str]) -> PathBuf {
+        let timestamp = "2025-01-01T00:00:00.000Z";
+        let thread_id = ThreadId::new();
+        let path = codex_home.join(format!("rollout-{thread_id}.jsonl"));
+        let mut items = vec![RolloutItem::SessionMeta(SessionMetaLine {
+            meta: SessionMeta {
+                id: thread_id,
+                forked_from_id: None,
+                timestamp: timestamp.to_string(),
+                cwd: PathBuf::from("."),
+                originator: "test_originator".to_string(),
+                cli_version: "test_version".to_string(),
+                source: SessionSource::Cli,
+                model_provider: None,
+                base_instructions: None,
+                dynamic_tools: None,
+            },
+            git: None,
+        })];
+        items.extend(prompts.iter().map(|prompt| {
+            RolloutItem::ResponseItem(ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: prompt.to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            })
+        }));
+        let contents: String = items
+            .into_iter()
+            .map(|item| {
+                let line = RolloutLine {
+                    timestamp: timestamp.to_string(),
+                    item,
+                };
+                format!(
+                    "{}\n",
+                    serde_json::to_string(&line).expect("serialize rollout line")
+                )
+            })
+            .collect();
+        std::fs::write(&path, contents).expect("write rollout");
+        path
+    }
+
     struct AgentControlHarness {
         _home: TempDir,
         config: Config,
@@ -345,6 +431,57 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn fork_thread_errors_when_manager_dropped() {
+        let control = AgentControl::default();
+        let (_home, config) = test_config().await;
+        let Err(err) = control
+            .fork_thread(config, PathBuf::from("/tmp/missing-rollout.jsonl"), 0)
+            .await
+        else {
+            panic!("fork_thread should fail without a manager");
+        };
+        assert_eq!(
+            err.to_string(),
+            "unsupported operation: thread manager dropped"
+        );
+    }
+
+    #[tokio::test]
+    async fn fork_thread_creates_new_thread_before_selected_turn() {
+        let harness = AgentControlHarness::new().await;
+        let rollout_path = write_rollout(&harness.config.codex_home, &["first", "second"]);
+
+        let forked = harness
+            .control
+            .fork_thread(harness.config.clone(), rollout_path, 1)
+            .await
+            .expect("fork should succeed");
+
+        assert!(harness.manager.get_thread(forked.thread_id).await.is_ok());
+        let _ = harness
+            .control
+            .shutdown_agent(forked.thread_id)
+            .await
+            .expect("shutdown forked thread");
+    }
+
+    #[tokio::test]
+    async fn fork_thread_rejects_out_of_range_turn() {
+        let harness = AgentControlHarness::new().await;
+        let rollout_path = write_rollout(&harness.config.codex_home, &["first", "second"]);
+
+        let Err(err) = harness
+            .control
+            .fork_thread(harness.config.clone(), rollout_path, 2)
+            .await
+        else {
+            panic!("fork past the last user message should fail");
+        };
+
+        assert_matches!(err, CodexErr::InvalidRequest(_));
+    }
+
     #[tokio::test]
     async fn send_input_errors_when_thread_missing() {
         let harness = AgentControlHarness::new().await;
@@ -5,9 +5,13 @@ use codex_api::TransportError;
 use codex_api::error::ApiError;
 use codex_api::rate_limits::parse_promo_message;
 use codex_api::rate_limits::parse_rate_limit_for_limit;
+use codex_api::rate_limits::parse_request_budget;
+use codex_api::rate_limits::parse_retry_after;
 use http::HeaderMap;
 use serde::Deserialize;
+use std::time::Duration;
 
+use crate::auth::AuthManager;
 use crate::auth::CodexAuth;
 use crate::error::CodexErr;
 use crate::error::RetryLimitReachedError;
@@ -87,6 +91,13 @@ pub(crate) fn map_api_error(err: ApiError) -> CodexErr {
                         }
                     }
 
+                    if let Some(delay) = headers.as_ref().and_then(rate_limit_retry_delay) {
+                        return CodexErr::Stream(
+                            format!("rate limit exceeded; retrying in {delay:?}"),
+                            Some(delay),
+                        );
+                    }
+
                     CodexErr::RetryLimit(RetryLimitReachedError {
                         status,
                         request_id: extract_request_tracking_id(headers.as_ref()),
@@ -148,6 +159,45 @@ mod tests {
         assert!(matches!(err, CodexErr::ServerOverloaded));
     }
 
+    #[test]
+    fn map_api_error_retries_plain_429_after_reset() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-ratelimit-limit-tokens",
+            http::HeaderValue::from_static("30000"),
+        );
+        headers.insert(
+            "x-ratelimit-remaining-tokens",
+            http::HeaderValue::from_static("0"),
+        );
+        headers.insert(
+            "x-ratelimit-reset-tokens",
+            http::HeaderValue::from_static("2.5s"),
+        );
+        let err = map_api_error(ApiError::Transport(TransportError::Http {
+            status: http::StatusCode::TOO_MANY_REQUESTS,
+            url: Some("http://example.com/v1/responses".to_string()),
+            headers: Some(headers.clone()),
+            body: Some(r#"{"error":{"type":"tokens","message":"slow down"}}"#.to_string()),
+        }));
+        let CodexErr::Stream(_, delay) = err else {
+            panic!("expected CodexErr::Stream, got {err:?}");
+        };
+        assert_eq!(delay, Some(Duration::from_millis(2500)));
+
+        headers.insert("retry-after", http::HeaderValue::from_static("7"));
+        let err = map_api_error(ApiError::Transport(TransportError::Http {
+            status: http::StatusCode::TOO_MANY_REQUESTS,
+            url: None,
+            headers: Some(headers),
+            body: None,
+        }));
+        let CodexErr::Stream(_, delay) = err else {
+            panic!("expected CodexErr::Stream, got {err:?}");
+        };
+        assert_eq!(delay, Some(Duration::from_secs(7)));
+    }
+
     #[test]
     fn map_api_error_maps_usage_limit_limit_name_header() {
         let mut headers = HeaderMap::new();
@@ -219,6 +269,21 @@ mod tests {
     }
 }
 
+/// How long a plain 429 (not a usage limit) asks us to wait: `retry-after` when
+/// present, otherwise until the exhausted `x-ratelimit-*` window resets.
+fn rate_limit_retry_delay(headers: &HeaderMap) -> Option<Duration> {
+    if let Some(delay) = parse_retry_after(headers) {
+        return Some(delay);
+    }
+    let budget = parse_request_budget(headers)?;
+    [budget.requests, budget.tokens]
+        .into_iter()
+        .flatten()
+        .filter(|window| window.remaining == 0 && window.resets_in_ms > 0)
+        .map(|window| Duration::from_millis(window.resets_in_ms))
+        .max()
+}
+
 fn extract_request_tracking_id(headers: Option<&HeaderMap>) -> Option<String> {
     extract_request_id(headers).or_else(|| extract_header(headers, CF_RAY_HEADER))
 }
@@ -236,6 +301,22 @@ fn extract_header(headers: Option<&HeaderMap>, name: &str) -> Option<String> {
     })
 }
 
+/// Like [`auth_provider_from_auth`], but signs in with the stored OIDC tokens
+/// of a provider configured with an `oidc` issuer.
+pub(crate) async fn auth_provider_for_provider(
+    auth_manager: Option<&AuthManager>,
+    auth: Option<CodexAuth>,
+    provider: &ModelProviderInfo,
+) -> crate::error::Result<CoreAuthProvider> {
+    if let (Some(oidc), Some(auth_manager)) = (provider.oidc.as_ref(), auth_manager) {
+        return Ok(CoreAuthProvider {
+            token: Some(auth_manager.oidc_access_token(oidc).await?),
+            account_id: None,
+        });
+    }
+    auth_provider_from_auth(auth, provider)
+}
+
 pub(crate) fn auth_provider_from_auth(
     auth: Option<CodexAuth>,
     provider: &ModelProviderInfo,
@@ -1,16 +1,23 @@
 use crate::codex::TurnContext;
 use crate::function_tool::FunctionCallError;
 use crate::protocol::FileChange;
+use crate::protocol::FileContentHashes;
 use crate::safety::SafetyCheck;
 use crate::safety::assess_patch_safety;
 use crate::tools::sandboxing::ExecApprovalRequirement;
 use codex_apply_patch::ApplyPatchAction;
 use codex_apply_patch::ApplyPatchFileChange;
+use sha2::Digest;
+use sha2::Sha256;
 use std::collections::HashMap;
+use std::path::Path;
 use std::path::PathBuf;
 
 pub const CODEX_APPLY_PATCH_ARG1: &str = "--codex-run-as-apply-patch";
 
+/// Context lines around each hunk of a saved patch proposal.
+const PROPOSAL_CONTEXT_LINES: usize = 3;
+
 pub(crate) enum InternalApplyPatchInvocation {
     /// The `apply_patch` call was handled programmatically, without any sort
     /// of sandbox, because the user explicitly approved it. This is the
@@ -37,11 +44,23 @@ pub(crate) async fn apply_patch(
     turn_context: &TurnContext,
     action: ApplyPatchAction,
 ) -> InternalApplyPatchInvocation {
+    // Proposals are saved for review instead of touching the workspace, so
+    // there is nothing to approve.
+    if turn_context.config.patch_proposal_dir.is_some() {
+        return InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
+            action,
+            auto_approved: true,
+            exec_approval_requirement: ExecApprovalRequirement::Skip {
+                bypass_sandbox: false,
+                proposed_execpolicy_amendment: None,
+            },
+        });
+    }
     match assess_patch_safety(
         &action,
         turn_context.approval_policy,
         &turn_context.sandbox_policy,
-        &turn_context.cwd,
+        turn_context.sandbox_cwd(),
         turn_context.windows_sandbox_level,
     ) {
         SafetyCheck::AutoApprove {
@@ -101,6 +120,104 @@ pub(crate) fn convert_apply_patch_to_protocol(
     result
 }
 
+/// SHA-256 digests of each file in `action` before and after it is applied.
+pub(crate) fn patch_content_hashes(
+    action: &ApplyPatchAction,
+) -> HashMap<PathBuf, FileContentHashes> {
+    let read_hash = |path: &Path| std::fs::read(path).ok().map(|content| sha256_hex(&content));
+    action
+        .changes()
+        .iter()
+        .map(|(path, change)| {
+            let hashes = match change {
+                ApplyPatchFileChange::Add { content } => FileContentHashes {
+                    before: read_hash(path),
+                    after: Some(sha256_hex(content.as_bytes())),
+                },
+                ApplyPatchFileChange::Delete { content } => FileContentHashes {
+                    before: Some(sha256_hex(content.as_bytes())),
+                    after: None,
+                },
+                ApplyPatchFileChange::Update { new_content, .. } => FileContentHashes {
+                    before: read_hash(path),
+                    after: Some(sha256_hex(new_content.as_bytes())),
+                },
+            };
+            (path.clone(), hashes)
+        })
+        .collect()
+}
+
+fn sha256_hex(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+/// Saves `action` in `dir` as the next numbered `.patch` file, a unified diff
+/// that `git apply` accepts, instead of applying it. Returns the file's path.
+pub(crate) async fn save_patch_proposal(
+    dir: &Path,
+    call_id: &str,
+    action: &ApplyPatchAction,
+) -> std::io::Result<PathBuf> {
+    tokio::fs::create_dir_all(dir).await?;
+    let mut saved = 0;
+    let mut entries = tokio::fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        if entry.path().extension().is_some_and(|ext| ext == "patch") {
+            saved += 1;
+        }
+    }
+    let path = dir.join(format!("{:04}-{call_id}.patch", saved + 1));
+    tokio::fs::write(&path, proposal_diff(action)).await?;
+    Ok(path)
+}
+
+fn proposal_diff(action: &ApplyPatchAction) -> String {
+    let display = |path: &Path| {
+        path.strip_prefix(&action.cwd)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .into_owned()
+    };
+    let mut changes: Vec<_> = action.changes().iter().collect();
+    changes.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let mut diff = String::new();
+    for (path, change) in changes {
+        let (old, new, old_name, new_name) = match change {
+            ApplyPatchFileChange::Add { content } => (
+                String::new(),
+                content.as_str(),
+                "/dev/null".to_string(),
+                format!("b/{}", display(path)),
+            ),
+            ApplyPatchFileChange::Delete { content } => (
+                content.clone(),
+                "",
+                format!("a/{}", display(path)),
+                "/dev/null".to_string(),
+            ),
+            ApplyPatchFileChange::Update {
+                move_path,
+                new_content,
+                ..
+            } => (
+                std::fs::read_to_string(path).unwrap_or_default(),
+                new_content.as_str(),
+                format!("a/{}", display(path)),
+                format!("b/{}", display(move_path.as_deref().unwrap_or(path))),
+            ),
+        };
+        diff.push_str(
+            &similar::TextDiff::from_lines(old.as_str(), new)
+                .unified_diff()
+                .context_radius(PROPOSAL_CONTEXT_LINES)
+                .header(&old_name, &new_name)
+                .to_string(),
+        );
+    }
+    diff
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -124,4 +241,38 @@ mod tests {
             })
         );
     }
+
+    #[tokio::test]
+    async fn proposals_are_saved_as_numbered_unified_diffs() {
+        let tmp = tempdir().expect("tmp");
+        let path = tmp.path().join("a.txt");
+        let action = ApplyPatchAction::new_add_for_test(&path, "hello\n".to_string());
+        let dir = tmp.path().join("proposals");
+
+        let first = save_patch_proposal(&dir, "call-1", &action)
+            .await
+            .expect("save first proposal");
+        let second = save_patch_proposal(&dir, "call-2", &action)
+            .await
+            .expect("save second proposal");
+
+        assert_eq!(first, dir.join("0001-call-1.patch"));
+        assert_eq!(second, dir.join("0002-call-2.patch"));
+        let diff = std::fs::read_to_string(&first).expect("read proposal");
+        assert!(
+            diff.starts_with("--- /dev/null\n+++ b/a.txt\n@@ "),
+            "{diff}"
+        );
+        assert!(diff.ends_with("\n+hello\n"), "{diff}");
+        assert!(!path.exists());
+        assert_eq!(
+            patch_content_hashes(&action).get(&path),
+            Some(&FileContentHashes {
+                before: None,
+                after: Some(
+                    "5891b5b522d5df086d0ff0b110fbd9d21bb4fc7163af34d08286a2e846f6be03".to_string()
+                ),
+            })
+        );
+    }
 }
@@ -0,0 +1,439 @@
+//! Append-only, hash-chained *audit log* of what a session was allowed to do.
+//!
+//! Unlike rollouts, which capture the conversation of a single thread, the
+//! audit log is one file shared by every session (`~/.codex/audit.jsonl` unless
+//! `[audit_log].path` says otherwise) and records only security-relevant facts:
+//! approval decisions, sandbox violations and escalations, and executed
+//! commands. Each line is a JSON object:
+//!
+//! ````text
+//! {"seq":1,"ts":"2026-01-01T00:00:00.000Z","thread_id":"<uuid>","user":"alice",
+//!  "prev_hash":"<hex>","type":"command_executed",...,"hash":"<hex>"}
+//! ````
+//!
+//! `hash` is the SHA-256 of the line without its trailing `,"hash":"…"` member,
+//! and `prev_hash` is the `hash` of the previous line (all zeros for the first
+//! one), so editing, inserting, removing, or reordering records breaks the
+//! chain. Truncating the newest records cannot be detected from the file alone;
+//! ship the head hash reported by `codex audit verify` elsewhere if that matters.
+//!
+//! Writers hold an exclusive advisory lock while reading the last record and
+//! appending the next one, so concurrent Codex processes extend a single chain.
+
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use chrono::SecondsFormat;
+use chrono::Utc;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::ExecCommandEndEvent;
+use codex_protocol::protocol::ExecCommandSource;
+use codex_protocol::protocol::ReviewDecision;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+use tracing::warn;
+
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+
+/// Filename of the audit log inside `~/.codex` when no path is configured.
+pub const AUDIT_LOG_FILENAME: &str = "audit.jsonl";
+
+/// `prev_hash` of the first record.
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+const HASH_MEMBER_PREFIX: &str = ",\"hash\":\"";
+
+/// How far back to look for the start of the last record before doubling.
+const TAIL_CHUNK_BYTES: u64 = 64 * 1024;
+
+/// Who settled an approval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ApprovalDecider {
+    /// The user answered a prompt.
+    User,
+    /// Approval policy, sandbox policy, or execpolicy rules decided without asking.
+    Policy,
+    /// An earlier "approve for session" answer was reused.
+    Session,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum AuditEvent {
+    ApprovalDecision {
+        call_id: String,
+        /// Tool name, `network` for proxy host approvals, or `mcp:<server>/<tool>`.
+        tool: String,
+        decision: String,
+        decided_by: ApprovalDecider,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
+    /// The sandbox denied a command, whether or not it is retried.
+    SandboxViolation {
+        call_id: String,
+        tool: String,
+        sandbox: String,
+        /// Host the command tried to reach, when the network proxy blocked it.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        network_host: Option<String>,
+    },
+    /// A command denied by the sandbox is retried without it.
+    SandboxEscalation {
+        call_id: String,
+        tool: String,
+        from_sandbox: String,
+        reason: String,
+    },
+    CommandExecuted {
+        call_id: String,
+        turn_id: String,
+        command: Vec<String>,
+        cwd: PathBuf,
+        source: ExecCommandSource,
+        exit_code: i32,
+        duration_ms: u64,
+    },
+}
+
+impl AuditEvent {
+    pub(crate) fn approval(
+        call_id: &str,
+        tool: &str,
+        decision: &ReviewDecision,
+        decided_by: ApprovalDecider,
+        reason: Option<String>,
+    ) -> Self {
+        Self::ApprovalDecision {
+            call_id: call_id.to_string(),
+            tool: tool.to_string(),
+            decision: decision.to_opaque_string().to_string(),
+            decided_by,
+            reason,
+        }
+    }
+
+    pub(crate) fn command_executed(event: &ExecCommandEndEvent) -> Self {
+        Self::CommandExecuted {
+            call_id: event.call_id.clone(),
+            turn_id: event.turn_id.clone(),
+            command: event.command.clone(),
+            cwd: event.cwd.clone(),
+            source: event.source,
+            exit_code: event.exit_code,
+            duration_ms: u64::try_from(event.duration.as_millis()).unwrap_or(u64::MAX),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct AuditRecord<'a> {
+    seq: u64,
+    ts: String,
+    thread_id: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user: Option<&'a str>,
+    prev_hash: &'a str,
+    #[serde(flatten)]
+    event: &'a AuditEvent,
+}
+
+/// The members of a record that take part in the chain.
+#[derive(Deserialize)]
+struct ChainLink {
+    seq: u64,
+    prev_hash: String,
+}
+
+/// Writer for one session.
+#[derive(Debug, Clone)]
+pub(crate) struct AuditLog {
+    path: PathBuf,
+    thread_id: String,
+    user: Option<String>,
+}
+
+impl AuditLog {
+    pub(crate) fn new(path: PathBuf, thread_id: ThreadId) -> Self {
+        let user = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .ok()
+            .filter(|user| !user.is_empty());
+        Self {
+            path,
+            thread_id: thread_id.to_string(),
+            user,
+        }
+    }
+
+    /// Append `event`. Failures are logged rather than returned so that an
+    /// unwritable log never blocks the turn.
+    pub(crate) async fn record(&self, event: AuditEvent) {
+        let log = self.clone();
+        let result = tokio::task::spawn_blocking(move || log.append(&event)).await;
+        match result {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => warn!("failed to append to {}: {err}", self.path.display()),
+            Err(err) => warn!("audit log writer panicked: {err}"),
+        }
+    }
+
+    fn append(&self, event: &AuditEvent) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut options = OpenOptions::new();
+        options.read(true).append(true).create(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        let mut file = options.open(&self.path)?;
+
+        file.lock()?;
+        let (last_seq, prev_hash) = last_link(&mut file)?;
+        let record = AuditRecord {
+            seq: last_seq + 1,
+            ts: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            thread_id: &self.thread_id,
+            user: self.user.as_deref(),
+            prev_hash: &prev_hash,
+            event,
+        };
+        let body = serde_json::to_string(&record).map_err(io::Error::other)?;
+        let line = seal(&body)?;
+        // Appending one buffer keeps the line whole even if the lock is ignored.
+        file.write_all(line.as_bytes())?;
+        file.flush()
+    }
+}
+
+/// Add the `hash` member to a serialized record and terminate the line.
+fn seal(body: &str) -> io::Result<String> {
+    let Some(open) = body.strip_suffix('}') else {
+        return Err(io::Error::other("audit record is not a JSON object"));
+    };
+    let hash = hash_hex(body);
+    Ok(format!("{open}{HASH_MEMBER_PREFIX}{hash}\"}}\n"))
+}
+
+/// Split a line into the hashed body and the stored hash.
+fn unseal(line: &str) -> Option<(String, &str)> {
+    let start = line.rfind(HASH_MEMBER_PREFIX)?;
+    let hash = line[start + HASH_MEMBER_PREFIX.len()..].strip_suffix("\"}")?;
+    if hash.len() != GENESIS_HASH.len() || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some((format!("{}}}", &line[..start]), hash))
+}
+
+fn hash_hex(body: &str) -> String {
+    format!("{:x}", Sha256::digest(body.as_bytes()))
+}
+
+/// Sequence number and hash of the newest record, read while holding the lock.
+fn last_link(file: &mut File) -> io::Result<(u64, String)> {
+    let len = file.metadata()?.len();
+    if len == 0 {
+        return Ok((0, GENESIS_HASH.to_string()));
+    }
+
+    let mut chunk = TAIL_CHUNK_BYTES;
+    let tail = loop {
+        let start = len.saturating_sub(chunk);
+        file.seek(SeekFrom::Start(start))?;
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf)?;
+        let trimmed = buf.strip_suffix(b"\n").unwrap_or(&buf);
+        if let Some(pos) = trimmed.iter().rposition(|b| *b == b'\n') {
+            break trimmed[pos + 1..].to_vec();
+        }
+        if start == 0 {
+            break trimmed.to_vec();
+        }
+        chunk = chunk.saturating_mul(2);
+    };
+
+    let tail = String::from_utf8(tail).map_err(io::Error::other)?;
+    let Some((body, hash)) = unseal(&tail) else {
+        return Err(io::Error::other(
+            "last record is not sealed; run `codex audit verify`",
+        ));
+    };
+    let link: ChainLink = serde_json::from_str(&body).map_err(io::Error::other)?;
+    Ok((link.seq, hash.to_string()))
+}
+
+/// Outcome of a successful [`verify`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditVerification {
+    pub records: u64,
+    /// Hash of the newest record, or `None` for an empty log.
+    pub head_hash: Option<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AuditVerifyError {
+    #[error("failed to read {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("line {line}: {reason}")]
+    Broken { line: usize, reason: String },
+}
+
+/// Check every record's hash and its link to the previous record.
+pub fn verify(path: &Path) -> Result<AuditVerification, AuditVerifyError> {
+    let io_err = |source| AuditVerifyError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    let reader = BufReader::new(File::open(path).map_err(io_err)?);
+
+    let mut records = 0u64;
+    let mut prev_hash = GENESIS_HASH.to_string();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(io_err)?;
+        let broken = |reason: String| AuditVerifyError::Broken {
+            line: index + 1,
+            reason,
+        };
+        let Some((body, hash)) = unseal(&line) else {
+            return Err(broken("record has no hash".to_string()));
+        };
+        if hash_hex(&body) != hash {
+            return Err(broken(
+                "hash does not match the record contents".to_string(),
+            ));
+        }
+        let link: ChainLink = serde_json::from_str(&body)
+            .map_err(|err| broken(format!("record is not valid JSON: {err}")))?;
+        if link.prev_hash != prev_hash {
+            return Err(broken(
+                "prev_hash does not match the previous record".to_string(),
+            ));
+        }
+        if link.seq != records + 1 {
+            return Err(broken(format!(
+                "expected seq {}, found {}",
+                records + 1,
+                link.seq
+            )));
+        }
+        records = link.seq;
+        prev_hash = hash.to_string();
+    }
+
+    Ok(AuditVerification {
+        records,
+        head_hash: (records > 0).then_some(prev_hash),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn command(call_id: &str) -> AuditEvent {
+        AuditEvent::CommandExecuted {
+            call_id: call_id.to_string(),
+            turn_id: "turn-1".to_string(),
+            command: vec!["echo".to_string(), "hi".to_string()],
+            cwd: PathBuf::from("/tmp"),
+            source: ExecCommandSource::Agent,
+            exit_code: 0,
+            duration_ms: 5,
+        }
+    }
+
+    async fn write_log(dir: &TempDir) -> PathBuf {
+        let path = dir.path().join(AUDIT_LOG_FILENAME);
+        let first = AuditLog::new(path.clone(), ThreadId::new());
+        let second = AuditLog::new(path.clone(), ThreadId::new());
+        first
+            .record(AuditEvent::approval(
+                "call-1",
+                "shell",
+                &ReviewDecision::Approved,
+                ApprovalDecider::User,
+                None,
+            ))
+            .await;
+        second
+            .record(AuditEvent::SandboxEscalation {
+                call_id: "call-1".to_string(),
+                tool: "shell".to_string(),
+                from_sandbox: "seccomp".to_string(),
+                reason: "command failed; retry without sandbox?".to_string(),
+            })
+            .await;
+        first.record(command("call-1")).await;
+        path
+    }
+
+    #[tokio::test]
+    async fn sessions_extend_one_chain() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = write_log(&dir).await;
+
+        let verification = verify(&path).expect("valid log");
+        assert_eq!(verification.records, 3);
+
+        let lines: Vec<serde_json::Value> = std::fs::read_to_string(&path)
+            .expect("read log")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("json"))
+            .collect();
+        assert_eq!(lines[0]["prev_hash"], GENESIS_HASH);
+        assert_eq!(lines[0]["type"], "approval_decision");
+        assert_eq!(lines[0]["decided_by"], "user");
+        assert_eq!(lines[1]["prev_hash"], lines[0]["hash"]);
+        assert_eq!(lines[1]["type"], "sandbox_escalation");
+        assert_eq!(lines[2]["prev_hash"], lines[1]["hash"]);
+        assert_eq!(lines[2]["command"], serde_json::json!(["echo", "hi"]));
+        assert_eq!(verification.head_hash.as_deref(), lines[2]["hash"].as_str());
+    }
+
+    #[tokio::test]
+    async fn verify_detects_edits_and_removals() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = write_log(&dir).await;
+        let original = std::fs::read_to_string(&path).expect("read log");
+
+        std::fs::write(&path, original.replacen("\"approved\"", "\"denied\"", 1))
+            .expect("write edited log");
+        let err = verify(&path).expect_err("edited record");
+        assert_eq!(
+            err.to_string(),
+            "line 1: hash does not match the record contents"
+        );
+
+        let without_middle: String = original
+            .lines()
+            .enumerate()
+            .filter(|(index, _)| *index != 1)
+            .map(|(_, line)| format!("{line}\n"))
+            .collect();
+        std::fs::write(&path, without_middle).expect("write truncated log");
+        let err = verify(&path).expect_err("removed record");
+        assert_eq!(
+            err.to_string(),
+            "line 2: prev_hash does not match the previous record"
+        );
+    }
+}
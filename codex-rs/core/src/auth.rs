@@ -1,3 +1,4 @@
+mod oidc;
 mod storage;
 
 use async_trait::async_trait;
@@ -19,6 +20,14 @@ use codex_app_server_protocol::AuthMode as ApiAuthMode;
 use codex_otel::TelemetryAuthMode;
 use codex_protocol::config_types::ForcedLoginMethod;
 
+pub use crate::auth::oidc::DEVICE_CODE_GRANT_TYPE;
+pub use crate::auth::oidc::OidcEndpoints;
+pub use crate::auth::oidc::OidcTokenResponse;
+pub use crate::auth::oidc::OidcTokens;
+pub use crate::auth::oidc::delete_oidc_tokens;
+pub use crate::auth::oidc::discover_oidc_endpoints;
+pub use crate::auth::oidc::load_oidc_tokens;
+pub use crate::auth::oidc::save_oidc_tokens;
 pub use crate::auth::storage::AuthCredentialsStoreMode;
 pub use crate::auth::storage::AuthDotJson;
 use crate::auth::storage::AuthStorageBackend;
@@ -26,6 +35,7 @@ use crate::auth::storage::create_auth_storage;
 use crate::config::Config;
 use crate::error::RefreshTokenFailedError;
 use crate::error::RefreshTokenFailedReason;
+use crate::model_provider_info::ProviderOidcConfig;
 use crate::token_data::KnownPlan as InternalKnownPlan;
 use crate::token_data::PlanType as InternalPlanType;
 use crate::token_data::TokenData;
@@ -371,6 +381,65 @@ pub fn read_codex_api_key_from_env() -> Option<String> {
         .filter(|value| !value.is_empty())
 }
 
+/// Directory under `CODEX_HOME` holding named credentials, one subdirectory
+/// per name.
+pub const CREDENTIALS_SUBDIR: &str = "credentials";
+
+/// Directory whose auth store holds the credential called `name`, or
+/// `codex_home` itself for the default, unnamed login. Each named credential
+/// is stored exactly like the default one, just rooted in its own directory.
+pub fn credential_home(codex_home: &Path, name: Option<&str>) -> PathBuf {
+    match name {
+        Some(name) => codex_home.join(CREDENTIALS_SUBDIR).join(name),
+        None => codex_home.to_path_buf(),
+    }
+}
+
+/// Credential names become directory names, so only letters, digits, `-`,
+/// `_` and `.` are allowed.
+pub fn validate_credential_name(name: &str) -> std::io::Result<()> {
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "invalid credential name `{name}`: use letters, digits, `-`, `_` or `.`, not starting with `.`"
+            ),
+        ))
+    }
+}
+
+/// Names of the credentials that have a directory under `CODEX_HOME`, sorted.
+/// Credentials kept only in the keyring still get a directory when they are
+/// created, so they are listed too.
+pub fn list_credentials(codex_home: &Path) -> std::io::Result<Vec<String>> {
+    let entries = match std::fs::read_dir(codex_home.join(CREDENTIALS_SUBDIR)) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut names = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        if let Some(name) = entry.file_name().to_str()
+            && validate_credential_name(name).is_ok()
+        {
+            names.push(name.to_string());
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
 /// Delete the auth.json file inside `codex_home` if it exists. Returns `Ok(true)`
 /// if a file was removed, `Ok(false)` if no auth file was present.
 pub fn logout(
@@ -440,7 +509,7 @@ pub fn load_auth_dot_json(
 
 pub fn enforce_login_restrictions(config: &Config) -> std::io::Result<()> {
     let Some(auth) = load_auth(
-        &config.codex_home,
+        &config.auth_home,
         true,
         config.cli_auth_credentials_store_mode,
     )?
@@ -464,7 +533,7 @@ pub fn enforce_login_restrictions(config: &Config) -> std::io::Result<()> {
 
         if let Some(message) = method_violation {
             return logout_with_message(
-                &config.codex_home,
+                &config.auth_home,
                 message,
                 config.cli_auth_credentials_store_mode,
             );
@@ -480,7 +549,7 @@ pub fn enforce_login_restrictions(config: &Config) -> std::io::Result<()> {
             Ok(data) => data,
             Err(err) => {
                 return logout_with_message(
-                    &config.codex_home,
+                    &config.auth_home,
                     format!(
                         "Failed to load ChatGPT credentials while enforcing workspace restrictions: {err}. Logging out."
                     ),
@@ -501,7 +570,7 @@ pub fn enforce_login_restrictions(config: &Config) -> std::io::Result<()> {
                 ),
             };
             return logout_with_message(
-                &config.codex_home,
+                &config.auth_home,
                 message,
                 config.cli_auth_credentials_store_mode,
             );
@@ -1031,6 +1100,15 @@ impl AuthManager {
         self.auth_cached()
     }
 
+    /// Access token for a provider that signs in through an OIDC issuer,
+    /// refreshed first if it is about to expire.
+    pub(crate) async fn oidc_access_token(
+        &self,
+        oidc: &ProviderOidcConfig,
+    ) -> Result<String, crate::error::CodexErr> {
+        oidc::oidc_access_token(&self.codex_home, self.auth_credentials_store_mode, oidc).await
+    }
+
     /// Force a reload of the auth information from auth.json. Returns
     /// whether the auth value changed.
     pub fn reload(&self) -> bool {
@@ -1663,6 +1741,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn named_credentials_are_stored_apart_from_the_default_login() {
+        let codex_home = tempdir().unwrap();
+        login_with_api_key(
+            codex_home.path(),
+            "sk-default",
+            AuthCredentialsStoreMode::File,
+        )
+        .expect("default login");
+        for (name, key) in [("work", "sk-work"), ("azure", "sk-azure")] {
+            let home = credential_home(codex_home.path(), Some(name));
+            login_with_api_key(&home, key, AuthCredentialsStoreMode::File).expect("named login");
+        }
+        std::fs::write(codex_home.path().join(CREDENTIALS_SUBDIR).join("notes"), "")
+            .expect("stray file");
+
+        assert_eq!(
+            list_credentials(codex_home.path()).expect("list"),
+            vec!["azure".to_string(), "work".to_string()]
+        );
+        let work = load_auth_dot_json(
+            &credential_home(codex_home.path(), Some("work")),
+            AuthCredentialsStoreMode::File,
+        )
+        .expect("load")
+        .expect("work credential");
+        assert_eq!(work.openai_api_key.as_deref(), Some("sk-work"));
+        let default = load_auth_dot_json(codex_home.path(), AuthCredentialsStoreMode::File)
+            .expect("load")
+            .expect("default credential");
+        assert_eq!(default.openai_api_key.as_deref(), Some("sk-default"));
+
+        assert!(validate_credential_name("client-a_2.0").is_ok());
+        for invalid in ["", ".hidden", "../work", "a/b", "a b"] {
+            assert!(validate_credential_name(invalid).is_err(), "{invalid:?}");
+        }
+    }
+
     #[test]
     fn plan_type_maps_known_plan() {
         let codex_home = tempdir().unwrap();
@@ -0,0 +1,492 @@
+//! Tokens for model providers that sign in through an OIDC issuer (Okta,
+//! Azure AD, ...) rather than with an API key or a ChatGPT login.
+//!
+//! `codex login --provider <id>` runs the device authorization grant and
+//! stores the resulting tokens here, keyed by issuer and client ID, using the
+//! same `cli_auth_credentials_store` choice as `auth.json`. Requests to the
+//! provider then use the access token, refreshing it shortly before it
+//! expires.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+use std::sync::Mutex;
+
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::Utc;
+use codex_keyring_store::DefaultKeyringStore;
+use codex_keyring_store::KeyringStore;
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::warn;
+
+use super::storage::AuthCredentialsStoreMode;
+use super::storage::compute_store_key;
+use crate::default_client::build_reqwest_client;
+use crate::encryption::DataKey;
+use crate::encryption::open_document;
+use crate::encryption::seal_document;
+use crate::error::CodexErr;
+use crate::error::RefreshTokenFailedError;
+use crate::error::RefreshTokenFailedReason;
+use crate::model_provider_info::ProviderOidcConfig;
+use crate::util::try_parse_error_message;
+
+/// `grant_type` for polling the token endpoint during device authorization
+/// (RFC 8628).
+pub const DEVICE_CODE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+const PROVIDER_AUTH_FILE: &str = "provider_auth.json";
+const ENCRYPTED_PROVIDER_AUTH_FILE: &str = "provider_auth.json.enc";
+const KEYRING_SERVICE: &str = "Codex Provider Auth";
+
+// Access tokens this close to expiry are refreshed before use.
+const REFRESH_MARGIN_SECONDS: i64 = 60;
+
+/// Stored tokens for one issuer and client ID.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct OidcTokens {
+    pub access_token: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id_token: Option<String>,
+    /// When the access token expires, if the issuer said.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl OidcTokens {
+    fn needs_refresh(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at - Duration::seconds(REFRESH_MARGIN_SECONDS) <= now)
+    }
+}
+
+/// Successful token endpoint response.
+#[derive(Deserialize, Debug)]
+pub struct OidcTokenResponse {
+    pub access_token: String,
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+    #[serde(default)]
+    pub id_token: Option<String>,
+    #[serde(default)]
+    pub expires_in: Option<i64>,
+}
+
+impl OidcTokenResponse {
+    /// Tokens to store. Issuers that do not rotate refresh tokens omit them
+    /// from refresh responses, so `previous_refresh_token` is kept then.
+    pub fn into_tokens(self, previous_refresh_token: Option<String>) -> OidcTokens {
+        OidcTokens {
+            access_token: self.access_token,
+            refresh_token: self.refresh_token.or(previous_refresh_token),
+            id_token: self.id_token,
+            expires_at: self
+                .expires_in
+                .map(|seconds| Utc::now() + Duration::seconds(seconds)),
+        }
+    }
+}
+
+/// Endpoints from the issuer's discovery document.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct OidcEndpoints {
+    #[serde(default)]
+    pub device_authorization_endpoint: Option<String>,
+    pub token_endpoint: String,
+}
+
+/// Reads `<issuer>/.well-known/openid-configuration`.
+pub async fn discover_oidc_endpoints(
+    client: &reqwest::Client,
+    issuer: &str,
+) -> io::Result<OidcEndpoints> {
+    let url = format!(
+        "{}/.well-known/openid-configuration",
+        issuer.trim_end_matches('/')
+    );
+    let response = client.get(&url).send().await.map_err(io::Error::other)?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(io::Error::other(format!(
+            "OIDC discovery at {url} failed with status {status}"
+        )));
+    }
+    response.json().await.map_err(io::Error::other)
+}
+
+/// The tokens stored for `oidc`, if any.
+pub fn load_oidc_tokens(
+    codex_home: &Path,
+    mode: AuthCredentialsStoreMode,
+    oidc: &ProviderOidcConfig,
+) -> io::Result<Option<OidcTokens>> {
+    Ok(load_document(codex_home, mode)?.remove(&token_key(oidc)))
+}
+
+/// Stores `tokens` for `oidc`, keeping the tokens of other issuers.
+pub fn save_oidc_tokens(
+    codex_home: &Path,
+    mode: AuthCredentialsStoreMode,
+    oidc: &ProviderOidcConfig,
+    tokens: OidcTokens,
+) -> io::Result<()> {
+    let mut document = load_document(codex_home, mode)?;
+    document.insert(token_key(oidc), tokens);
+    save_document(codex_home, mode, &document)
+}
+
+/// Removes the tokens stored for `oidc`. Returns whether there were any.
+pub fn delete_oidc_tokens(
+    codex_home: &Path,
+    mode: AuthCredentialsStoreMode,
+    oidc: &ProviderOidcConfig,
+) -> io::Result<bool> {
+    let mut document = load_document(codex_home, mode)?;
+    if document.remove(&token_key(oidc)).is_none() {
+        return Ok(false);
+    }
+    save_document(codex_home, mode, &document)?;
+    Ok(true)
+}
+
+/// A current access token for `oidc`, refreshing the stored tokens first
+/// when they are about to expire.
+pub(crate) async fn oidc_access_token(
+    codex_home: &Path,
+    mode: AuthCredentialsStoreMode,
+    oidc: &ProviderOidcConfig,
+) -> Result<String, CodexErr> {
+    let Some(tokens) = load_oidc_tokens(codex_home, mode, oidc)? else {
+        return Err(CodexErr::RefreshTokenFailed(RefreshTokenFailedError::new(
+            RefreshTokenFailedReason::Other,
+            format!(
+                "Not signed in to {}. Run `codex login --provider <provider id>`.",
+                oidc.issuer
+            ),
+        )));
+    };
+    if !tokens.needs_refresh(Utc::now()) {
+        return Ok(tokens.access_token);
+    }
+    let Some(refresh_token) = tokens.refresh_token.clone() else {
+        return Err(expired(oidc));
+    };
+    let refreshed = refresh_oidc_tokens(&build_reqwest_client(), oidc, refresh_token).await?;
+    let access_token = refreshed.access_token.clone();
+    save_oidc_tokens(codex_home, mode, oidc, refreshed)?;
+    Ok(access_token)
+}
+
+async fn refresh_oidc_tokens(
+    client: &reqwest::Client,
+    oidc: &ProviderOidcConfig,
+    refresh_token: String,
+) -> Result<OidcTokens, CodexErr> {
+    let endpoints = discover_oidc_endpoints(client, &oidc.issuer).await?;
+    let scope = oidc.scope();
+    let response = client
+        .post(&endpoints.token_endpoint)
+        .form(&[
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token.as_str()),
+            ("client_id", oidc.client_id.as_str()),
+            ("scope", scope.as_str()),
+        ])
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    let status = response.status();
+    if status.is_success() {
+        let response: OidcTokenResponse = response.json().await.map_err(io::Error::other)?;
+        return Ok(response.into_tokens(Some(refresh_token)));
+    }
+    let body = response.text().await.unwrap_or_default();
+    tracing::error!("Failed to refresh OIDC token: {status}: {body}");
+    if status.is_client_error() {
+        // `invalid_grant`: the refresh token expired or was revoked.
+        return Err(expired(oidc));
+    }
+    let message = try_parse_error_message(&body);
+    Err(io::Error::other(format!("Failed to refresh OIDC token: {status}: {message}")).into())
+}
+
+fn expired(oidc: &ProviderOidcConfig) -> CodexErr {
+    CodexErr::RefreshTokenFailed(RefreshTokenFailedError::new(
+        RefreshTokenFailedReason::Expired,
+        format!(
+            "Your sign-in to {} has expired. Run `codex login --provider <provider id>` again.",
+            oidc.issuer
+        ),
+    ))
+}
+
+fn token_key(oidc: &ProviderOidcConfig) -> String {
+    format!("{}|{}", oidc.issuer.trim_end_matches('/'), oidc.client_id)
+}
+
+// ===== Storage =====
+
+type ProviderAuthDocument = BTreeMap<String, OidcTokens>;
+
+// In-memory tokens for `AuthCredentialsStoreMode::Ephemeral`, by store key.
+static EPHEMERAL_PROVIDER_AUTH: Lazy<Mutex<HashMap<String, ProviderAuthDocument>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn load_document(
+    codex_home: &Path,
+    mode: AuthCredentialsStoreMode,
+) -> io::Result<ProviderAuthDocument> {
+    match mode {
+        AuthCredentialsStoreMode::File => load_file(&codex_home.join(PROVIDER_AUTH_FILE)),
+        AuthCredentialsStoreMode::Encrypted => load_encrypted(codex_home),
+        AuthCredentialsStoreMode::Keyring => load_keyring(codex_home),
+        AuthCredentialsStoreMode::Auto => match load_keyring(codex_home) {
+            Ok(document) if !document.is_empty() => Ok(document),
+            Ok(_) => load_file(&codex_home.join(PROVIDER_AUTH_FILE)),
+            Err(err) => {
+                warn!("failed to load provider auth from keyring, falling back to file: {err}");
+                load_file(&codex_home.join(PROVIDER_AUTH_FILE))
+            }
+        },
+        AuthCredentialsStoreMode::Ephemeral => {
+            let key = compute_store_key(codex_home)?;
+            Ok(ephemeral_store()?.get(&key).cloned().unwrap_or_default())
+        }
+    }
+}
+
+fn save_document(
+    codex_home: &Path,
+    mode: AuthCredentialsStoreMode,
+    document: &ProviderAuthDocument,
+) -> io::Result<()> {
+    match mode {
+        AuthCredentialsStoreMode::File => write_private_file(
+            &codex_home.join(PROVIDER_AUTH_FILE),
+            serde_json::to_string_pretty(document)?.as_bytes(),
+        ),
+        AuthCredentialsStoreMode::Encrypted => {
+            let key = DataKey::load_or_create(codex_home, &DefaultKeyringStore)?;
+            let sealed = seal_document(&key, &serde_json::to_vec(document)?)?;
+            write_private_file(
+                &codex_home.join(ENCRYPTED_PROVIDER_AUTH_FILE),
+                sealed.as_bytes(),
+            )?;
+            remove_if_exists(&codex_home.join(PROVIDER_AUTH_FILE))?;
+            Ok(())
+        }
+        AuthCredentialsStoreMode::Keyring => save_keyring(codex_home, document),
+        AuthCredentialsStoreMode::Auto => match save_keyring(codex_home, document) {
+            Ok(()) => Ok(()),
+            Err(err) => {
+                warn!("failed to save provider auth to keyring, falling back to file: {err}");
+                write_private_file(
+                    &codex_home.join(PROVIDER_AUTH_FILE),
+                    serde_json::to_string_pretty(document)?.as_bytes(),
+                )
+            }
+        },
+        AuthCredentialsStoreMode::Ephemeral => {
+            let key = compute_store_key(codex_home)?;
+            ephemeral_store()?.insert(key, document.clone());
+            Ok(())
+        }
+    }
+}
+
+fn load_file(path: &Path) -> io::Result<ProviderAuthDocument> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => Ok(serde_json::from_str(&contents)?),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(ProviderAuthDocument::new()),
+        Err(err) => Err(err),
+    }
+}
+
+fn load_encrypted(codex_home: &Path) -> io::Result<ProviderAuthDocument> {
+    match std::fs::read_to_string(codex_home.join(ENCRYPTED_PROVIDER_AUTH_FILE)) {
+        Ok(sealed) => {
+            let plaintext = open_document(&sealed, &DefaultKeyringStore)?;
+            Ok(serde_json::from_slice(&plaintext)?)
+        }
+        // Tokens saved under another store mode are migrated on the next save.
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            load_file(&codex_home.join(PROVIDER_AUTH_FILE))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+fn load_keyring(codex_home: &Path) -> io::Result<ProviderAuthDocument> {
+    let key = compute_store_key(codex_home)?;
+    match DefaultKeyringStore.load(KEYRING_SERVICE, &key) {
+        Ok(Some(serialized)) => Ok(serde_json::from_str(&serialized)?),
+        Ok(None) => Ok(ProviderAuthDocument::new()),
+        Err(err) => Err(io::Error::other(format!(
+            "failed to load provider auth from keyring: {}",
+            err.message()
+        ))),
+    }
+}
+
+fn save_keyring(codex_home: &Path, document: &ProviderAuthDocument) -> io::Result<()> {
+    let key = compute_store_key(codex_home)?;
+    let serialized = serde_json::to_string(document)?;
+    DefaultKeyringStore
+        .save(KEYRING_SERVICE, &key, &serialized)
+        .map_err(|err| {
+            io::Error::other(format!(
+                "failed to write provider auth to keyring: {}",
+                err.message()
+            ))
+        })?;
+    if let Err(err) = remove_if_exists(&codex_home.join(PROVIDER_AUTH_FILE)) {
+        warn!("failed to remove provider auth fallback file: {err}");
+    }
+    Ok(())
+}
+
+fn ephemeral_store()
+-> io::Result<std::sync::MutexGuard<'static, HashMap<String, ProviderAuthDocument>>> {
+    EPHEMERAL_PROVIDER_AUTH
+        .lock()
+        .map_err(|_| io::Error::other("failed to lock ephemeral provider auth storage"))
+}
+
+fn write_private_file(path: &Path, contents: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut options = OpenOptions::new();
+    options.truncate(true).write(true).create(true);
+    #[cfg(unix)]
+    {
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    file.write_all(contents)?;
+    file.flush()
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::body_string_contains;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+
+    fn oidc(issuer: &str) -> ProviderOidcConfig {
+        ProviderOidcConfig {
+            issuer: issuer.to_string(),
+            client_id: "codex-cli".to_string(),
+            scopes: None,
+            audience: None,
+        }
+    }
+
+    #[test]
+    fn tokens_for_each_issuer_are_stored_side_by_side() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let okta = oidc("https://example.okta.com/oauth2/default");
+        let azure = oidc("https://login.microsoftonline.com/tenant/v2.0/");
+        let tokens = |access: &str| OidcTokens {
+            access_token: access.to_string(),
+            refresh_token: Some(format!("{access}-refresh")),
+            id_token: None,
+            expires_at: None,
+        };
+        let mode = AuthCredentialsStoreMode::File;
+        save_oidc_tokens(codex_home.path(), mode, &okta, tokens("okta")).expect("save");
+        save_oidc_tokens(codex_home.path(), mode, &azure, tokens("azure")).expect("save");
+
+        assert_eq!(
+            load_oidc_tokens(codex_home.path(), mode, &okta).expect("load"),
+            Some(tokens("okta"))
+        );
+        assert!(delete_oidc_tokens(codex_home.path(), mode, &okta).expect("delete"));
+        assert_eq!(
+            load_oidc_tokens(codex_home.path(), mode, &okta).expect("load"),
+            None
+        );
+        assert_eq!(
+            load_oidc_tokens(codex_home.path(), mode, &azure).expect("load"),
+            Some(tokens("azure"))
+        );
+    }
+
+    #[tokio::test]
+    async fn expiring_access_token_is_refreshed_and_stored() {
+        let server = MockServer::start().await;
+        let issuer = server.uri();
+        Mock::given(method("GET"))
+            .and(path("/.well-known/openid-configuration"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "issuer": issuer,
+                "token_endpoint": format!("{issuer}/token"),
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .and(body_string_contains("grant_type=refresh_token"))
+            .and(body_string_contains("refresh_token=old-refresh"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "new-access",
+                "expires_in": 3600,
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let codex_home = TempDir::new().expect("tempdir");
+        let config = oidc(&issuer);
+        let mode = AuthCredentialsStoreMode::File;
+        save_oidc_tokens(
+            codex_home.path(),
+            mode,
+            &config,
+            OidcTokens {
+                access_token: "old-access".to_string(),
+                refresh_token: Some("old-refresh".to_string()),
+                id_token: None,
+                expires_at: Some(Utc::now() + Duration::seconds(10)),
+            },
+        )
+        .expect("save");
+
+        let token = oidc_access_token(codex_home.path(), mode, &config)
+            .await
+            .expect("token");
+
+        assert_eq!(token, "new-access");
+        let stored = load_oidc_tokens(codex_home.path(), mode, &config)
+            .expect("load")
+            .expect("tokens");
+        assert_eq!(stored.access_token, "new-access");
+        // The issuer did not rotate the refresh token, so the old one is kept.
+        assert_eq!(stored.refresh_token.as_deref(), Some("old-refresh"));
+        assert!(!stored.needs_refresh(Utc::now()));
+    }
+}
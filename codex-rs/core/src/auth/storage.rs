@@ -19,6 +19,9 @@ use std::sync::Arc;
 use std::sync::Mutex;
 use tracing::warn;
 
+use crate::encryption::DataKey;
+use crate::encryption::open_document;
+use crate::encryption::seal_document;
 use crate::token_data::TokenData;
 use codex_app_server_protocol::AuthMode;
 use codex_keyring_store::DefaultKeyringStore;
@@ -38,6 +41,9 @@ pub enum AuthCredentialsStoreMode {
     Auto,
     /// Store credentials in memory only for the current process.
     Ephemeral,
+    /// Persist credentials in CODEX_HOME/auth.json.enc, encrypted with a key
+    /// kept in the keyring. An existing auth.json is migrated on first load.
+    Encrypted,
 }
 
 /// Expected structure for $CODEX_HOME/auth.json.
@@ -60,6 +66,10 @@ pub(super) fn get_auth_file(codex_home: &Path) -> PathBuf {
     codex_home.join("auth.json")
 }
 
+fn get_encrypted_auth_file(codex_home: &Path) -> PathBuf {
+    codex_home.join("auth.json.enc")
+}
+
 pub(super) fn delete_file_if_exists(codex_home: &Path) -> std::io::Result<bool> {
     let auth_file = get_auth_file(codex_home);
     match std::fs::remove_file(&auth_file) {
@@ -132,10 +142,79 @@ impl AuthStorageBackend for FileAuthStorage {
     }
 }
 
+/// `auth.json` sealed with the `CODEX_HOME` data key (see
+/// [`crate::encryption`]). Only the key lives in the keyring, so this works
+/// where the keyring cannot hold the whole credential blob.
+#[derive(Clone, Debug)]
+struct EncryptedFileAuthStorage {
+    codex_home: PathBuf,
+    keyring_store: Arc<dyn KeyringStore>,
+    plaintext_storage: FileAuthStorage,
+}
+
+impl EncryptedFileAuthStorage {
+    fn new(codex_home: PathBuf, keyring_store: Arc<dyn KeyringStore>) -> Self {
+        Self {
+            plaintext_storage: FileAuthStorage::new(codex_home.clone()),
+            codex_home,
+            keyring_store,
+        }
+    }
+}
+
+impl AuthStorageBackend for EncryptedFileAuthStorage {
+    fn load(&self) -> std::io::Result<Option<AuthDotJson>> {
+        let sealed = match std::fs::read_to_string(get_encrypted_auth_file(&self.codex_home)) {
+            Ok(sealed) => sealed,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                // Migrate a plaintext auth.json left from another store mode.
+                let Some(auth) = self.plaintext_storage.load()? else {
+                    return Ok(None);
+                };
+                self.save(&auth)?;
+                return Ok(Some(auth));
+            }
+            Err(err) => return Err(err),
+        };
+        let plaintext = open_document(&sealed, self.keyring_store.as_ref())?;
+        Ok(Some(serde_json::from_slice(&plaintext)?))
+    }
+
+    fn save(&self, auth: &AuthDotJson) -> std::io::Result<()> {
+        let key = DataKey::load_or_create(&self.codex_home, self.keyring_store.as_ref())?;
+        let sealed = seal_document(&key, &serde_json::to_vec(auth)?)?;
+        std::fs::create_dir_all(&self.codex_home)?;
+        let mut options = OpenOptions::new();
+        options.truncate(true).write(true).create(true);
+        #[cfg(unix)]
+        {
+            options.mode(0o600);
+        }
+        let mut file = options.open(get_encrypted_auth_file(&self.codex_home))?;
+        file.write_all(sealed.as_bytes())?;
+        file.flush()?;
+        if let Err(err) = delete_file_if_exists(&self.codex_home) {
+            warn!("failed to remove plaintext auth.json: {err}");
+        }
+        Ok(())
+    }
+
+    fn delete(&self) -> std::io::Result<bool> {
+        let encrypted_removed =
+            match std::fs::remove_file(get_encrypted_auth_file(&self.codex_home)) {
+                Ok(()) => true,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => false,
+                Err(err) => return Err(err),
+            };
+        let file_removed = delete_file_if_exists(&self.codex_home)?;
+        Ok(encrypted_removed || file_removed)
+    }
+}
+
 const KEYRING_SERVICE: &str = "Codex Auth";
 
 // turns codex_home path into a stable, short key string
-fn compute_store_key(codex_home: &Path) -> std::io::Result<String> {
+pub(super) fn compute_store_key(codex_home: &Path) -> std::io::Result<String> {
     let canonical = codex_home
         .canonicalize()
         .unwrap_or_else(|_| codex_home.to_path_buf());
@@ -328,6 +407,9 @@ fn create_auth_storage_with_keyring_store(
         }
         AuthCredentialsStoreMode::Auto => Arc::new(AutoAuthStorage::new(codex_home, keyring_store)),
         AuthCredentialsStoreMode::Ephemeral => Arc::new(EphemeralAuthStorage::new(codex_home)),
+        AuthCredentialsStoreMode::Encrypted => {
+            Arc::new(EncryptedFileAuthStorage::new(codex_home, keyring_store))
+        }
     }
 }
 
@@ -432,6 +514,35 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn encrypted_storage_migrates_plaintext_auth_file() -> anyhow::Result<()> {
+        let dir = tempdir()?;
+        let auth_dot_json = AuthDotJson {
+            auth_mode: Some(AuthMode::ApiKey),
+            openai_api_key: Some("sk-plaintext".to_string()),
+            tokens: None,
+            last_refresh: None,
+        };
+        FileAuthStorage::new(dir.path().to_path_buf()).save(&auth_dot_json)?;
+        let mock_keyring = Arc::new(MockKeyringStore::default());
+        let storage = create_auth_storage_with_keyring_store(
+            dir.path().to_path_buf(),
+            AuthCredentialsStoreMode::Encrypted,
+            mock_keyring,
+        );
+
+        assert_eq!(Some(auth_dot_json.clone()), storage.load()?);
+        assert!(!get_auth_file(dir.path()).exists());
+        let sealed = std::fs::read_to_string(get_encrypted_auth_file(dir.path()))?;
+        assert!(!sealed.contains("sk-plaintext"));
+        assert_eq!(Some(auth_dot_json), storage.load()?);
+
+        assert!(storage.delete()?);
+        assert!(!get_encrypted_auth_file(dir.path()).exists());
+        assert_eq!(None, storage.load()?);
+        Ok(())
+    }
+
     fn seed_keyring_and_fallback_auth_file_for_delete<F>(
         mock_keyring: &MockKeyringStore,
         codex_home: &Path,
@@ -28,11 +28,12 @@
 
 use std::sync::Arc;
 use std::sync::OnceLock;
+use std::sync::RwLock;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 
 use crate::api_bridge::CoreAuthProvider;
-use crate::api_bridge::auth_provider_from_auth;
+use crate::api_bridge::auth_provider_for_provider;
 use crate::api_bridge::map_api_error;
 use crate::auth::UnauthorizedRecovery;
 use codex_api::CompactClient as ApiCompactClient;
@@ -90,13 +91,17 @@ use crate::auth::RefreshTokenError;
 use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
 use crate::client_common::ResponseStream;
-use crate::default_client::build_reqwest_client;
+use crate::config::types::ResponseCacheConfig;
+use crate::default_client::build_reqwest_client_for_provider;
 use crate::error::CodexErr;
 use crate::error::Result;
 use crate::flags::CODEX_RS_SSE_FIXTURE;
 use crate::model_provider_info::ModelProviderInfo;
 use crate::model_provider_info::WireApi;
+use crate::response_cache::ResponseCache;
 use crate::tools::spec::create_tools_json_for_responses_api;
+use crate::wire_log::WireLog;
+use crate::wire_log::WireLogTransport;
 
 pub const OPENAI_BETA_HEADER: &str = "OpenAI-Beta";
 pub const OPENAI_BETA_RESPONSES_WEBSOCKETS: &str = "responses_websockets=2026-02-04";
@@ -115,7 +120,8 @@ struct ModelClientState {
     conversation_id: ThreadId,
     provider: ModelProviderInfo,
     session_source: SessionSource,
-    model_verbosity: Option<VerbosityConfig>,
+    /// Starts from config and can be changed mid-session by `Op::OverrideTurnSettings`.
+    model_verbosity: RwLock<Option<VerbosityConfig>>,
     enable_responses_websockets: bool,
     enable_responses_websockets_v2: bool,
     enable_request_compression: bool,
@@ -149,6 +155,8 @@ struct CurrentClientSetup {
 #[derive(Debug, Clone)]
 pub struct ModelClient {
     state: Arc<ModelClientState>,
+    response_cache: Option<Arc<ResponseCache>>,
+    wire_log: Option<Arc<WireLog>>,
 }
 
 /// A turn-scoped streaming session created from a [`ModelClient`].
@@ -218,7 +226,7 @@ impl ModelClient {
                 conversation_id,
                 provider,
                 session_source,
-                model_verbosity,
+                model_verbosity: RwLock::new(model_verbosity),
                 enable_responses_websockets,
                 enable_responses_websockets_v2,
                 enable_request_compression,
@@ -226,9 +234,35 @@ impl ModelClient {
                 beta_features_header,
                 disable_websockets: AtomicBool::new(false),
             }),
+            response_cache: None,
+            wire_log: None,
         }
     }
 
+    /// Replays and records model responses through `response_cache`, when set.
+    pub fn with_response_cache(mut self, response_cache: Option<ResponseCacheConfig>) -> Self {
+        self.response_cache = response_cache.map(|config| Arc::new(ResponseCache::new(config)));
+        self
+    }
+
+    /// Records HTTP model API traffic in `wire_log` while it is enabled.
+    pub(crate) fn with_wire_log(mut self, wire_log: Arc<WireLog>) -> Self {
+        self.wire_log = Some(wire_log);
+        self
+    }
+
+    pub(crate) fn wire_log(&self) -> Option<&Arc<WireLog>> {
+        self.wire_log.as_ref()
+    }
+
+    /// HTTP transport for model API calls to this session's provider.
+    fn http_transport(&self) -> WireLogTransport<ReqwestTransport> {
+        WireLogTransport::new(
+            ReqwestTransport::new(build_reqwest_client_for_provider(&self.state.provider)),
+            self.wire_log.clone(),
+        )
+    }
+
     /// Creates a fresh turn-scoped streaming session.
     ///
     /// This constructor does not perform network I/O itself; the session opens a websocket lazily
@@ -260,7 +294,7 @@ impl ModelClient {
             return Ok(Vec::new());
         }
         let client_setup = self.current_client_setup().await?;
-        let transport = ReqwestTransport::new(build_reqwest_client());
+        let transport = self.http_transport();
         let request_telemetry = Self::build_request_telemetry(otel_manager);
         let client =
             ApiCompactClient::new(transport, client_setup.api_provider, client_setup.api_auth)
@@ -298,7 +332,7 @@ impl ModelClient {
         }
 
         let client_setup = self.current_client_setup().await?;
-        let transport = ReqwestTransport::new(build_reqwest_client());
+        let transport = self.http_transport();
         let request_telemetry = Self::build_request_telemetry(otel_manager);
         let client =
             ApiMemoriesClient::new(transport, client_setup.api_provider, client_setup.api_auth)
@@ -350,7 +384,8 @@ impl ModelClient {
     /// This combines provider capability and feature gating; both must be true for websocket paths
     /// to be eligible.
     pub fn responses_websocket_enabled(&self, model_info: &ModelInfo) -> bool {
-        self.state.provider.supports_websockets
+        self.state.provider.wire_api == WireApi::Responses
+            && self.state.provider.supports_websockets
             && (self.state.enable_responses_websockets || model_info.prefer_websockets)
     }
 
@@ -358,6 +393,24 @@ impl ModelClient {
         self.state.enable_responses_websockets_v2
     }
 
+    /// Verbosity requested for subsequent turns, when the model supports it.
+    pub(crate) fn model_verbosity(&self) -> Option<VerbosityConfig> {
+        *self
+            .state
+            .model_verbosity
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Changes the verbosity requested by subsequent turns of this session.
+    pub(crate) fn set_model_verbosity(&self, model_verbosity: Option<VerbosityConfig>) {
+        *self
+            .state
+            .model_verbosity
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = model_verbosity;
+    }
+
     /// Returns whether websocket transport has been permanently disabled for this session.
     ///
     /// Once set by fallback activation, subsequent turns must stay on HTTP transport.
@@ -378,7 +431,12 @@ impl ModelClient {
             .state
             .provider
             .to_api_provider(auth.as_ref().map(CodexAuth::auth_mode))?;
-        let api_auth = auth_provider_from_auth(auth.clone(), &self.state.provider)?;
+        let api_auth = auth_provider_for_provider(
+            self.state.auth_manager.as_deref(),
+            auth.clone(),
+            &self.state.provider,
+        )
+        .await?;
         Ok(CurrentClientSetup {
             auth,
             api_provider,
@@ -486,13 +544,11 @@ impl ModelClientSession {
         } else {
             Vec::new()
         };
+        let model_verbosity = self.client.model_verbosity();
         let verbosity = if model_info.support_verbosity {
-            self.client
-                .state
-                .model_verbosity
-                .or(model_info.default_verbosity)
+            model_verbosity.or(model_info.default_verbosity)
         } else {
-            if self.client.state.model_verbosity.is_some() {
+            if model_verbosity.is_some() {
                 warn!(
                     "model_verbosity is set but ignored as the model does not support verbosity: {}",
                     model_info.slug
@@ -503,7 +559,12 @@ impl ModelClientSession {
         let text = create_text_param_for_request(verbosity, &prompt.output_schema);
         let prompt_cache_key = Some(self.client.state.conversation_id.to_string());
         let request = ResponsesApiRequest {
-            model: model_info.slug.clone(),
+            model: self
+                .client
+                .state
+                .provider
+                .request_model(&model_info.slug)
+                .to_string(),
             instructions: instructions.clone(),
             input,
             tools,
@@ -712,7 +773,7 @@ impl ModelClientSession {
         }
     }
 
-    /// Streams a turn via the OpenAI Responses API.
+    /// Streams a turn over HTTP, in the provider's wire dialect.
     ///
     /// Handles SSE fixtures, reasoning summaries, verbosity, and the
     /// `text` controls used for output schemas.
@@ -743,7 +804,7 @@ impl ModelClientSession {
             .map(super::auth::AuthManager::unauthorized_recovery);
         loop {
             let client_setup = self.client.current_client_setup().await?;
-            let transport = ReqwestTransport::new(build_reqwest_client());
+            let transport = self.client.http_transport();
             let (request_telemetry, sse_telemetry) = Self::build_streaming_telemetry(otel_manager);
             let compression = self.responses_request_compression(client_setup.auth.as_ref());
             let options = self.build_responses_options(turn_metadata_header, compression);
@@ -760,7 +821,8 @@ impl ModelClientSession {
                 client_setup.api_provider,
                 client_setup.api_auth,
             )
-            .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
+            .with_telemetry(Some(request_telemetry), Some(sse_telemetry))
+            .with_dialect(self.client.state.provider.wire_api.dialect());
             let stream_result = client.stream_request(request, options).await;
 
             match stream_result {
@@ -879,7 +941,8 @@ impl ModelClientSession {
     /// The caller is responsible for passing per-turn settings explicitly (model selection,
     /// reasoning settings, telemetry context, and turn metadata). This method will prefer the
     /// Responses WebSocket transport when enabled and healthy, and will fall back to the HTTP
-    /// Responses API transport otherwise.
+    /// Responses API transport otherwise. With a response cache configured, a request that is
+    /// already cached is replayed without contacting the provider.
     pub async fn stream(
         &mut self,
         prompt: &Prompt,
@@ -888,6 +951,53 @@ impl ModelClientSession {
         effort: Option<ReasoningEffortConfig>,
         summary: ReasoningSummaryConfig,
         turn_metadata_header: Option<&str>,
+    ) -> Result<ResponseStream> {
+        let Some(response_cache) = self.client.response_cache.clone() else {
+            return self
+                .stream_from_provider(
+                    prompt,
+                    model_info,
+                    otel_manager,
+                    effort,
+                    summary,
+                    turn_metadata_header,
+                )
+                .await;
+        };
+
+        let api_provider = self.client.state.provider.to_api_provider(None)?;
+        let request =
+            self.build_responses_request(&api_provider, prompt, model_info, effort, summary)?;
+        let entry_path = response_cache.entry_path(&request)?;
+        if let Some(stream) = response_cache.replay(&entry_path).await? {
+            // The provider never saw the replayed response, so the next request
+            // must not be sent as an incremental append on top of it.
+            self.websocket_last_request = None;
+            self.websocket_last_response_rx = None;
+            return Ok(stream);
+        }
+        let stream = self
+            .stream_from_provider(
+                prompt,
+                model_info,
+                otel_manager,
+                effort,
+                summary,
+                turn_metadata_header,
+            )
+            .await?;
+        Ok(response_cache.record(entry_path, request.model, stream))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn stream_from_provider(
+        &mut self,
+        prompt: &Prompt,
+        model_info: &ModelInfo,
+        otel_manager: &OtelManager,
+        effort: Option<ReasoningEffortConfig>,
+        summary: ReasoningSummaryConfig,
+        turn_metadata_header: Option<&str>,
     ) -> Result<ResponseStream> {
         let wire_api = self.client.state.provider.wire_api;
         match wire_api {
@@ -924,6 +1034,17 @@ impl ModelClientSession {
                 )
                 .await
             }
+            WireApi::Chat | WireApi::Anthropic => {
+                self.stream_responses_api(
+                    prompt,
+                    model_info,
+                    otel_manager,
+                    effort,
+                    summary,
+                    turn_metadata_header,
+                )
+                .await
+            }
         }
     }
 
@@ -5,6 +5,8 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicU64;
+use std::time::Duration;
+use std::time::Instant;
 
 use crate::AuthManager;
 use crate::CodexAuth;
@@ -17,17 +19,26 @@ use crate::analytics_client::AnalyticsEventsClient;
 use crate::analytics_client::AppInvocation;
 use crate::analytics_client::build_track_events_context;
 use crate::apps::render_apps_section;
+use crate::audit_log::ApprovalDecider;
+use crate::audit_log::AuditEvent;
+use crate::audit_log::AuditLog;
+use crate::command_explanation;
 use crate::compact;
 use crate::compact::run_inline_auto_compact_task;
+use crate::compact::run_resume_summary_task;
 use crate::compact::should_use_remote_compact_task;
 use crate::compact_remote::run_inline_remote_auto_compact_task;
+use crate::completion_gate::run_completion_gate;
 use crate::connectors;
 use crate::exec_policy::ExecPolicyManager;
 use crate::features::FEATURES;
 use crate::features::Feature;
 use crate::features::Features;
 use crate::features::maybe_push_unstable_features_warning;
+use crate::model_routing;
+use crate::model_routing::RouteDecision;
 use crate::models_manager::manager::ModelsManager;
+use crate::output_schema;
 use crate::parse_command::parse_command;
 use crate::parse_turn_item;
 use crate::rollout::session_index;
@@ -37,10 +48,14 @@ use crate::stream_events_utils::handle_output_item_done;
 use crate::stream_events_utils::last_assistant_message_from_item;
 use crate::terminal;
 use crate::truncate::TruncationPolicy;
+use crate::turn_journal::TurnJournal;
+use crate::turn_latency::LatencyStage;
+use crate::turn_latency::TurnLatency;
 use crate::turn_metadata::TurnMetadataState;
 use crate::util::error_or_panic;
 use async_channel::Receiver;
 use async_channel::Sender;
+use codex_file_search::IgnoreRules;
 use codex_hooks::HookEvent;
 use codex_hooks::HookEventAfterAgent;
 use codex_hooks::HookPayload;
@@ -90,6 +105,7 @@ use rmcp::model::RequestId;
 use serde_json;
 use serde_json::Value;
 use tokio::sync::Mutex;
+use tokio::sync::OnceCell;
 use tokio::sync::RwLock;
 use tokio::sync::oneshot;
 use tokio::sync::watch;
@@ -111,6 +127,7 @@ use crate::client::ModelClient;
 use crate::client::ModelClientSession;
 use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
+use crate::client_common::ResponseStream;
 use crate::codex_thread::ThreadConfigSnapshot;
 use crate::compact::collect_user_messages;
 use crate::config::CONFIG_TOML_FILE;
@@ -124,6 +141,8 @@ use crate::config::types::McpServerConfig;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::context_manager::ContextManager;
 use crate::context_manager::TotalTokenUsageBreakdown;
+use crate::context_packs::ContextPackCache;
+use crate::context_packs::session_start_context_packs;
 use crate::environment_context::EnvironmentContext;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
@@ -136,6 +155,7 @@ pub enum SteerInputError {
     ExpectedTurnMismatch { expected: String, actual: String },
     EmptyInput,
 }
+use crate::disk_quota::DiskQuota;
 use crate::exec_policy::ExecPolicyUpdateError;
 use crate::feedback_tags;
 use crate::file_watcher::FileWatcher;
@@ -151,12 +171,17 @@ use crate::mcp_connection_manager::McpConnectionManager;
 use crate::mcp_connection_manager::filter_codex_apps_mcp_tools_only;
 use crate::mcp_connection_manager::filter_mcp_tools_by_name;
 use crate::mcp_connection_manager::filter_non_codex_apps_mcp_tools_only;
+use crate::mcp_connection_manager::filter_read_only_mcp_tools;
 use crate::memories;
 use crate::mentions::build_connector_slug_counts;
 use crate::mentions::build_skill_name_counts;
 use crate::mentions::collect_explicit_app_ids;
 use crate::mentions::collect_tool_mentions_from_messages;
+use crate::patch_backups::PATCH_BACKUPS_DIR;
+use crate::patch_backups::PatchBackups;
+use crate::project_commands::project_commands_item;
 use crate::project_doc::get_user_instructions;
+use crate::project_memory::project_memory_item;
 use crate::proposed_plan_parser::ProposedPlanParser;
 use crate::proposed_plan_parser::ProposedPlanSegment;
 use crate::proposed_plan_parser::extract_proposed_plan_text;
@@ -171,13 +196,19 @@ use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecApprovalRequestEvent;
 use crate::protocol::McpServerRefreshConfig;
+use crate::protocol::ModelRoutedEvent;
+use crate::protocol::ModelTier;
 use crate::protocol::NetworkApprovalContext;
 use crate::protocol::Op;
 use crate::protocol::PlanDeltaEvent;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::ReasoningContentDeltaEvent;
 use crate::protocol::ReasoningRawContentDeltaEvent;
+use crate::protocol::RequestBudgetEvent;
+use crate::protocol::RequestBudgetSnapshot;
 use crate::protocol::RequestUserInputEvent;
+use crate::protocol::RetriesExhaustedEvent;
+use crate::protocol::RetryExhaustedDecision;
 use crate::protocol::ReviewDecision;
 use crate::protocol::SandboxPolicy;
 use crate::protocol::SessionConfiguredEvent;
@@ -193,12 +224,25 @@ use crate::protocol::TokenCountEvent;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
 use crate::protocol::TurnDiffEvent;
+use crate::protocol::UsageQuotaExceededEvent;
 use crate::protocol::WarningEvent;
+use crate::read_prefetch::ReadPrefetchCache;
+use crate::repo_brief::RepoBrief;
+use crate::request_pacer;
+use crate::retry_budget::Recovery;
+use crate::retry_budget::RetryBudget;
 use crate::rollout::RolloutRecorder;
 use crate::rollout::RolloutRecorderParams;
 use crate::rollout::map_session_init_error;
 use crate::rollout::metadata;
 use crate::rollout::policy::EventPersistenceMode;
+use crate::run_limits;
+use crate::run_limits::RunBudget;
+use crate::scope::ScopeContext;
+use crate::scope::display_scope;
+use crate::scope::resolve_scope;
+use crate::scope::scoped_sandbox_policy;
+use crate::security_events::SecurityEventForwarder;
 use crate::shell;
 use crate::shell_snapshot::ShellSnapshot;
 use crate::skills::SkillError;
@@ -223,32 +267,41 @@ use crate::tasks::ReviewTask;
 use crate::tasks::SessionTask;
 use crate::tasks::SessionTaskContext;
 use crate::tools::ToolRouter;
+use crate::tools::browser::BrowserHandle;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::handlers::SEARCH_TOOL_BM25_TOOL_NAME;
+use crate::tools::handlers::resume_plan_instructions;
 use crate::tools::js_repl::JsReplHandle;
 use crate::tools::network_approval::NetworkApprovalService;
 use crate::tools::network_approval::build_blocked_request_observer;
 use crate::tools::network_approval::build_network_policy_decider;
+use crate::tools::output_summary::FullOutputs;
 use crate::tools::parallel::ToolCallRuntime;
+use crate::tools::py_repl::PyReplHandle;
 use crate::tools::sandboxing::ApprovalStore;
 use crate::tools::spec::ToolsConfig;
 use crate::tools::spec::ToolsConfigParams;
 use crate::turn_diff_tracker::TurnDiffTracker;
 use crate::unified_exec::UnifiedExecProcessManager;
+use crate::usage_quota;
+use crate::usage_quota::UsageQuota;
 use crate::util::backoff;
 use crate::windows_sandbox::WindowsSandboxLevelExt;
+use crate::wire_log::WireLog;
 use codex_async_utils::OrCancelExt;
 use codex_otel::OtelManager;
 use codex_otel::TelemetryAuthMode;
 use codex_protocol::config_types::CollaborationMode;
 use codex_protocol::config_types::Personality;
 use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
+use codex_protocol::config_types::Verbosity;
 use codex_protocol::config_types::WindowsSandboxLevel;
 use codex_protocol::models::ContentItem;
 use codex_protocol::models::DeveloperInstructions;
 use codex_protocol::models::ResponseInputItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
+use codex_protocol::plan_tool::PlanItemArg;
 use codex_protocol::protocol::CodexErrorInfo;
 use codex_protocol::protocol::InitialHistory;
 use codex_protocol::user_input::UserInput;
@@ -278,6 +331,15 @@ pub struct CodexSpawnOk {
 
 pub(crate) const INITIAL_SUBMIT_ID: &str = "";
 pub(crate) const SUBMISSION_CHANNEL_CAPACITY: usize = 64;
+/// File in `CODEX_HOME` whose presence stops every session, as a kill switch
+/// for unattended runs.
+pub const STOP_FILE_NAME: &str = "STOP";
+const STOP_FILE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Appended to assistant text that was cut off by a user interrupt.
+const INTERRUPTED_AGENT_MESSAGE_MARKER: &str =
+    "[Interrupted by the user; the response above is incomplete.]";
+/// Appended to assistant text that was cut off by a stalled response stream.
+const STALLED_AGENT_MESSAGE_MARKER: &str = "[The response stream stalled here; the response above is incomplete. Continue from where it stopped.]";
 
 impl Codex {
     /// Spawn a new [`Codex`] and initialize the session.
@@ -391,6 +453,7 @@ impl Codex {
             provider: config.model_provider.clone(),
             collaboration_mode,
             model_reasoning_summary: config.model_reasoning_summary,
+            model_verbosity: config.model_verbosity,
             developer_instructions: config.developer_instructions.clone(),
             user_instructions,
             personality: config.personality,
@@ -400,6 +463,7 @@ impl Codex {
             sandbox_policy: config.permissions.sandbox_policy.clone(),
             windows_sandbox_level: WindowsSandboxLevel::from_config(&config),
             cwd: config.cwd.clone(),
+            scope: config.scope.clone(),
             codex_home: config.codex_home.clone(),
             thread_name: None,
             original_config_do_not_use: Arc::clone(&config),
@@ -536,11 +600,16 @@ pub(crate) struct TurnContext {
     pub(crate) provider: ModelProviderInfo,
     pub(crate) reasoning_effort: Option<ReasoningEffortConfig>,
     pub(crate) reasoning_summary: ReasoningSummaryConfig,
+    pub(crate) model_verbosity: Option<Verbosity>,
     pub(crate) session_source: SessionSource,
     /// The session's current working directory. All relative paths provided by
     /// the model as well as sandbox policies are resolved against this path
     /// instead of `std::env::current_dir()`.
     pub(crate) cwd: PathBuf,
+    /// Sub-directories of `cwd` the session is narrowed to; empty when the
+    /// whole working directory is in scope. `sandbox_policy` is already
+    /// scoped, and sandboxes must be given [`TurnContext::sandbox_cwd`].
+    pub(crate) scope: Vec<AbsolutePathBuf>,
     pub(crate) developer_instructions: Option<String>,
     pub(crate) compact_prompt: Option<String>,
     pub(crate) user_instructions: Option<String>,
@@ -561,6 +630,8 @@ pub(crate) struct TurnContext {
     pub(crate) js_repl: Arc<JsReplHandle>,
     pub(crate) dynamic_tools: Vec<DynamicToolSpec>,
     pub(crate) turn_metadata_state: Arc<TurnMetadataState>,
+    /// Time spent in each stage of the turn, reported when it finishes.
+    pub(crate) latency: Arc<TurnLatency>,
 }
 impl TurnContext {
     pub(crate) fn model_context_window(&self) -> Option<i64> {
@@ -601,11 +672,15 @@ impl TurnContext {
             self.collaboration_mode
                 .with_updates(Some(model.clone()), Some(reasoning_effort), None);
         let features = self.features.clone();
-        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+        let mut tools_config = ToolsConfig::new(&ToolsConfigParams {
             model_info: &model_info,
             features: &features,
             web_search_mode: self.tools_config.web_search_mode,
         });
+        tools_config.apply_builtin_tools(config.builtin_tools);
+        tools_config.web_search_provider = self.tools_config.web_search_provider;
+        tools_config.allowed_tools = self.tools_config.allowed_tools.clone();
+        tools_config.delegate_roles = self.tools_config.delegate_roles.clone();
 
         Self {
             sub_id: self.sub_id.clone(),
@@ -619,8 +694,10 @@ impl TurnContext {
             provider: self.provider.clone(),
             reasoning_effort,
             reasoning_summary: self.reasoning_summary,
+            model_verbosity: self.model_verbosity,
             session_source: self.session_source.clone(),
             cwd: self.cwd.clone(),
+            scope: self.scope.clone(),
             developer_instructions: self.developer_instructions.clone(),
             compact_prompt: self.compact_prompt.clone(),
             user_instructions: self.user_instructions.clone(),
@@ -641,9 +718,15 @@ impl TurnContext {
             js_repl: Arc::clone(&self.js_repl),
             dynamic_tools: self.dynamic_tools.clone(),
             turn_metadata_state: self.turn_metadata_state.clone(),
+            latency: Arc::clone(&self.latency),
         }
     }
 
+    /// Working directory to hand to the sandbox; see [`crate::scope::sandbox_cwd`].
+    pub(crate) fn sandbox_cwd(&self) -> &Path {
+        crate::scope::sandbox_cwd(&self.cwd, &self.scope)
+    }
+
     pub(crate) fn resolve_path(&self, path: Option<String>) -> PathBuf {
         path.as_ref()
             .map(PathBuf::from)
@@ -671,6 +754,7 @@ impl TurnContext {
             collaboration_mode: Some(collaboration_mode),
             effort: self.reasoning_effort,
             summary: self.reasoning_summary,
+            verbosity: self.model_verbosity,
             user_instructions: self.user_instructions.clone(),
             developer_instructions: self.developer_instructions.clone(),
             final_output_json_schema: self.final_output_json_schema.clone(),
@@ -699,6 +783,8 @@ pub(crate) struct SessionConfiguration {
 
     collaboration_mode: CollaborationMode,
     model_reasoning_summary: ReasoningSummaryConfig,
+    /// Output verbosity for models that support it; `None` uses the model default.
+    model_verbosity: Option<Verbosity>,
 
     /// Developer instructions that supplement the base instructions.
     developer_instructions: Option<String>,
@@ -729,6 +815,9 @@ pub(crate) struct SessionConfiguration {
     /// `ConfigureSession` operation so that the business-logic layer can
     /// operate deterministically.
     cwd: PathBuf,
+    /// Sub-directories of `cwd` the session is narrowed to (`--scope`,
+    /// `Op::SetScope`).
+    scope: Vec<AbsolutePathBuf>,
     /// Directory containing all Codex state for this session.
     codex_home: PathBuf,
     /// Optional user-facing name for the thread, updated during the session.
@@ -768,6 +857,9 @@ impl SessionConfiguration {
         if let Some(summary) = updates.reasoning_summary {
             next_configuration.model_reasoning_summary = summary;
         }
+        if let Some(model_verbosity) = updates.model_verbosity {
+            next_configuration.model_verbosity = model_verbosity;
+        }
         if let Some(personality) = updates.personality {
             next_configuration.personality = Some(personality);
         }
@@ -781,6 +873,10 @@ impl SessionConfiguration {
             next_configuration.windows_sandbox_level = windows_sandbox_level;
         }
         if let Some(cwd) = updates.cwd.clone() {
+            // Scope directories outside the new working directory drop out.
+            next_configuration
+                .scope
+                .retain(|dir| dir.as_path().starts_with(&cwd));
             next_configuration.cwd = cwd;
         }
         Ok(next_configuration)
@@ -795,6 +891,7 @@ pub(crate) struct SessionSettingsUpdate {
     pub(crate) windows_sandbox_level: Option<WindowsSandboxLevel>,
     pub(crate) collaboration_mode: Option<CollaborationMode>,
     pub(crate) reasoning_summary: Option<ReasoningSummaryConfig>,
+    pub(crate) model_verbosity: Option<Option<Verbosity>>,
     pub(crate) final_output_json_schema: Option<Option<Value>>,
     pub(crate) personality: Option<Personality>,
 }
@@ -862,6 +959,7 @@ impl Session {
         per_turn_config.model_reasoning_effort =
             session_configuration.collaboration_mode.reasoning_effort();
         per_turn_config.model_reasoning_summary = session_configuration.model_reasoning_summary;
+        per_turn_config.model_verbosity = session_configuration.model_verbosity;
         per_turn_config.personality = session_configuration.personality;
         let resolved_web_search_mode = resolve_web_search_mode_for_turn(
             &per_turn_config.web_search_mode,
@@ -935,11 +1033,19 @@ impl Session {
         let otel_manager_for_context = otel_manager;
         let per_turn_config = Arc::new(per_turn_config);
 
-        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+        let mut tools_config = ToolsConfig::new(&ToolsConfigParams {
             model_info: &model_info,
             features: &per_turn_config.features,
             web_search_mode: Some(per_turn_config.web_search_mode.value()),
         });
+        tools_config.apply_builtin_tools(per_turn_config.builtin_tools);
+        tools_config.web_search_provider = per_turn_config.web_search_provider.is_some();
+        tools_config.allowed_tools = per_turn_config
+            .agent_role
+            .as_ref()
+            .and_then(|role| role.tools.clone());
+        tools_config.delegate_roles =
+            crate::tools::handlers::delegate::delegate_roles(&per_turn_config);
 
         let cwd = session_configuration.cwd.clone();
         let turn_metadata_state = Arc::new(TurnMetadataState::new(
@@ -960,15 +1066,20 @@ impl Session {
             provider: provider_for_context,
             reasoning_effort,
             reasoning_summary,
+            model_verbosity: session_configuration.model_verbosity,
             session_source,
             cwd,
+            scope: session_configuration.scope.clone(),
             developer_instructions: session_configuration.developer_instructions.clone(),
             compact_prompt: session_configuration.compact_prompt.clone(),
             user_instructions: session_configuration.user_instructions.clone(),
             collaboration_mode: session_configuration.collaboration_mode.clone(),
             personality: session_configuration.personality,
             approval_policy: session_configuration.approval_policy.value(),
-            sandbox_policy: session_configuration.sandbox_policy.get().clone(),
+            sandbox_policy: scoped_sandbox_policy(
+                session_configuration.sandbox_policy.get(),
+                &session_configuration.scope,
+            ),
             network,
             windows_sandbox_level: session_configuration.windows_sandbox_level,
             shell_environment_policy: per_turn_config.permissions.shell_environment_policy.clone(),
@@ -982,6 +1093,7 @@ impl Session {
             js_repl,
             dynamic_tools: session_configuration.dynamic_tools.clone(),
             turn_metadata_state,
+            latency: Arc::new(TurnLatency::new()),
         }
     }
 
@@ -1012,6 +1124,14 @@ impl Session {
             ));
         }
 
+        // Command output is decoded and Seatbelt profiles are built deep
+        // inside the exec paths, which do not see the config, so these
+        // settings are process-wide.
+        crate::text_encoding::set_output_encoding_override(config.exec_output_encoding);
+        crate::output_escapes::set_output_escapes(config.exec_output_escapes);
+        crate::seatbelt_profile::set_profile_additions(config.macos_seatbelt.clone());
+        crate::sandbox_devices::set_allowed_devices(config.sandbox_allowed_devices.clone());
+
         let forked_from_id = initial_history.forked_from_id();
 
         let (conversation_id, rollout_params) = match &initial_history {
@@ -1146,6 +1266,22 @@ impl Session {
                 }),
             });
         }
+        let wire_log = Arc::new(WireLog::new(config.wire_log.clone().unwrap_or_else(|| {
+            config.log_dir.join(format!("wire-{conversation_id}.jsonl"))
+        })));
+        if config.wire_log.is_some()
+            && let Err(err) = wire_log.set_enabled(true)
+        {
+            post_session_configured_events.push(Event {
+                id: "".to_owned(),
+                msg: EventMsg::Warning(WarningEvent {
+                    message: format!(
+                        "Wire logging is off: failed to open {}: {err}",
+                        wire_log.path().display()
+                    ),
+                }),
+            });
+        }
 
         let auth = auth.as_ref();
         let auth_mode = auth.map(CodexAuth::auth_mode).map(TelemetryAuthMode::from);
@@ -1284,6 +1420,45 @@ impl Session {
             network_proxy,
             network_approval: Arc::clone(&network_approval),
             state_db: state_db_ctx.clone(),
+            py_repl: PyReplHandle::default(),
+            browser: BrowserHandle::default(),
+            session_diff_tracker: Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new())),
+            full_outputs: Mutex::new(FullOutputs::default()),
+            project_commands: OnceCell::new(),
+            context_packs: ContextPackCache::default(),
+            read_prefetch: ReadPrefetchCache::default(),
+            patch_backups: Mutex::new(PatchBackups::new(
+                config
+                    .codex_home
+                    .join(PATCH_BACKUPS_DIR)
+                    .join(conversation_id.to_string()),
+            )),
+            disk_quota: DiskQuota::new(
+                config.session_disk_quota_mb,
+                session_configuration.sandbox_policy.get(),
+                &session_configuration.cwd,
+            ),
+            usage_quota: config
+                .usage_quota
+                .clone()
+                .map(|quota| UsageQuota::new(&config.codex_home, quota)),
+            audit_log: config
+                .audit_log_path
+                .clone()
+                .map(|path| AuditLog::new(path, conversation_id)),
+            security_events: config
+                .security_events
+                .clone()
+                .map(|config| SecurityEventForwarder::new(config, conversation_id)),
+            turn_journal: rollout_path.clone().and_then(|rollout_path| {
+                TurnJournal::open(
+                    &config.codex_home,
+                    conversation_id,
+                    session_configuration.session_source.clone(),
+                    rollout_path,
+                    session_configuration.cwd.clone(),
+                )
+            }),
             model_client: ModelClient::new(
                 Some(Arc::clone(&auth_manager)),
                 conversation_id,
@@ -1296,7 +1471,9 @@ impl Session {
                 config.features.enabled(Feature::EnableRequestCompression),
                 config.features.enabled(Feature::RuntimeMetrics),
                 Self::build_model_client_beta_features_header(config.as_ref()),
-            ),
+            )
+            .with_response_cache(config.response_cache.clone())
+            .with_wire_log(wire_log),
         };
         let js_repl = Arc::new(JsReplHandle::with_node_path(
             config.js_repl_node_path.clone(),
@@ -1359,13 +1536,21 @@ impl Session {
 
         // Start the watcher after SessionConfigured so it cannot emit earlier events.
         sess.start_file_watcher_listener();
+        sess.start_stop_file_watch(&config.codex_home);
 
         // Construct sandbox_state before initialize() so it can be sent to each
         // MCP server immediately after it becomes ready (avoiding blocking).
         let sandbox_state = SandboxState {
-            sandbox_policy: session_configuration.sandbox_policy.get().clone(),
+            sandbox_policy: scoped_sandbox_policy(
+                session_configuration.sandbox_policy.get(),
+                &session_configuration.scope,
+            ),
             codex_linux_sandbox_exe: config.codex_linux_sandbox_exe.clone(),
-            sandbox_cwd: session_configuration.cwd.clone(),
+            sandbox_cwd: crate::scope::sandbox_cwd(
+                &session_configuration.cwd,
+                &session_configuration.scope,
+            )
+            .to_path_buf(),
             use_linux_sandbox_bwrap: config.features.enabled(Feature::UseLinuxSandboxBwrap),
         };
         let mut required_mcp_servers: Vec<String> = mcp_servers
@@ -1387,6 +1572,7 @@ impl Session {
                 tx_event.clone(),
                 cancel_token,
                 sandbox_state,
+                config.features.enabled(Feature::LazyMcpStartup),
             )
             .await;
         if !required_mcp_servers.is_empty() {
@@ -1552,6 +1738,7 @@ impl Session {
                 {
                     let mut state = self.state.lock().await;
                     state.initial_context_seeded = false;
+                    state.resume_summary_pending = turn_context.config.resume_summary.is_some();
                 }
                 self.set_previous_model(previous_model).await;
 
@@ -1581,6 +1768,9 @@ impl Session {
                     self.record_into_history(&reconstructed_history, &turn_context)
                         .await;
                 }
+                self.restore_plan_from_rollout(&turn_context, &rollout_items)
+                    .await;
+                self.recover_interrupted_turn(&turn_context).await;
 
                 // Seed usage info from the recorded rollout so UIs can show token counts
                 // immediately on resume/fork.
@@ -1611,6 +1801,8 @@ impl Session {
                     self.record_into_history(&reconstructed_history, &turn_context)
                         .await;
                 }
+                self.restore_plan_from_rollout(&turn_context, &rollout_items)
+                    .await;
 
                 // Seed usage info from the recorded rollout so UIs can show token counts
                 // immediately on resume/fork.
@@ -1645,6 +1837,62 @@ impl Session {
         }
     }
 
+    /// Restores the `update_plan` checklist from the rollout and, while steps remain,
+    /// reminds the model of them so the continued session picks up where it left off.
+    async fn restore_plan_from_rollout(
+        &self,
+        turn_context: &TurnContext,
+        rollout_items: &[RolloutItem],
+    ) {
+        let plan = {
+            let mut state = self.state.lock().await;
+            state.restore_plan(rollout_items.iter().filter_map(|item| match item {
+                RolloutItem::EventMsg(EventMsg::PlanUpdate(update)) => Some(update),
+                _ => None,
+            }));
+            state.plan().to_vec()
+        };
+        if let Some(instructions) = resume_plan_instructions(&plan) {
+            let item: ResponseItem = DeveloperInstructions::new(instructions).into();
+            self.record_into_history(std::slice::from_ref(&item), turn_context)
+                .await;
+        }
+    }
+
+    /// When the previous process died mid-turn, tells the model (and the user) what was
+    /// cut off, using the state the turn journal captured.
+    async fn recover_interrupted_turn(&self, turn_context: &TurnContext) {
+        let Some(turn_journal) = &self.services.turn_journal else {
+            return;
+        };
+        let Some(interrupted) = turn_journal.take_recovered().await else {
+            return;
+        };
+        let note = ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: interrupted.recovery_note(),
+            }],
+            end_turn: None,
+            phase: None,
+        };
+        self.record_into_history(std::slice::from_ref(&note), turn_context)
+            .await;
+        self.persist_rollout_items(&[RolloutItem::ResponseItem(note)])
+            .await;
+        let pending = interrupted.pending_tool_calls.len();
+        let message = if pending == 0 {
+            "The previous session exited unexpectedly during a turn. Codex has been told the turn did not finish.".to_string()
+        } else {
+            format!(
+                "The previous session exited unexpectedly during a turn with {pending} tool call(s) still running. Codex has been told to verify their effects before retrying."
+            )
+        };
+        self.send_event(turn_context, EventMsg::Warning(WarningEvent { message }))
+            .await;
+    }
+
     fn last_rollout_model_name(rollout_items: &[RolloutItem]) -> Option<&str> {
         rollout_items.iter().rev().find_map(|it| {
             if let RolloutItem::TurnContext(ctx) = it {
@@ -1824,12 +2072,24 @@ impl Session {
         sandbox_policy_changed: bool,
     ) -> Arc<TurnContext> {
         let per_turn_config = Self::build_per_turn_config(&session_configuration);
+        // Verbosity lives on the session-scoped client; keep it in step with
+        // `Op::OverrideTurnSettings`.
+        self.services
+            .model_client
+            .set_model_verbosity(session_configuration.model_verbosity);
 
         if sandbox_policy_changed {
             let sandbox_state = SandboxState {
-                sandbox_policy: per_turn_config.permissions.sandbox_policy.get().clone(),
+                sandbox_policy: scoped_sandbox_policy(
+                    per_turn_config.permissions.sandbox_policy.get(),
+                    &session_configuration.scope,
+                ),
                 codex_linux_sandbox_exe: per_turn_config.codex_linux_sandbox_exe.clone(),
-                sandbox_cwd: per_turn_config.cwd.clone(),
+                sandbox_cwd: crate::scope::sandbox_cwd(
+                    &per_turn_config.cwd,
+                    &session_configuration.scope,
+                )
+                .to_path_buf(),
                 use_linux_sandbox_bwrap: per_turn_config
                     .features
                     .enabled(Feature::UseLinuxSandboxBwrap),
@@ -1854,6 +2114,12 @@ impl Session {
                 &per_turn_config,
             )
             .await;
+        // Follow model switches so the context meter and compaction triggers
+        // count with the new model's vocabulary.
+        self.state
+            .lock()
+            .await
+            .set_tokenizer_for_model(&model_info.slug);
         let mut turn_context: TurnContext = Self::make_turn_context(
             Some(Arc::clone(&self.services.auth_manager)),
             &self.services.otel_manager,
@@ -1892,6 +2158,61 @@ impl Session {
         }
     }
 
+    /// Moves a new turn to `model_routing.fast_model` when the router, or a
+    /// pending `Op::RouteNextTurn`, picks the fast tier, and reports the
+    /// decision. Turns are left alone when routing is not configured.
+    async fn route_turn_model(
+        &self,
+        turn_context: Arc<TurnContext>,
+        items: &[UserInput],
+    ) -> Arc<TurnContext> {
+        let Some(routing) = turn_context.config.model_routing.clone() else {
+            return turn_context;
+        };
+        // Sub-agents run on the model their role or parent picked.
+        if matches!(turn_context.session_source, SessionSource::SubAgent(_)) {
+            return turn_context;
+        }
+        let requested_tier = self.state.lock().await.next_turn_model_tier.take();
+        let overridden = requested_tier.is_some();
+        let decision = match requested_tier {
+            Some(tier) => RouteDecision {
+                tier,
+                reason: "requested for this turn".to_string(),
+            },
+            None => model_routing::route_turn(&routing, items),
+        };
+
+        let turn_context = if decision.tier == ModelTier::Fast
+            && turn_context.collaboration_mode.model() != routing.fast_model
+        {
+            let mut fast_context = turn_context
+                .with_model(routing.fast_model.clone(), &self.services.models_manager)
+                .await;
+            if let Some(effort) = routing.fast_reasoning_effort {
+                fast_context.reasoning_effort = Some(effort);
+                fast_context.collaboration_mode =
+                    fast_context
+                        .collaboration_mode
+                        .with_updates(None, Some(Some(effort)), None);
+            }
+            Arc::new(fast_context)
+        } else {
+            turn_context
+        };
+        self.send_event(
+            &turn_context,
+            EventMsg::ModelRouted(ModelRoutedEvent {
+                model: turn_context.collaboration_mode.model().to_string(),
+                tier: decision.tier,
+                reason: decision.reason,
+                overridden,
+            }),
+        )
+        .await;
+        turn_context
+    }
+
     pub(crate) async fn new_default_turn(&self) -> Arc<TurnContext> {
         self.new_default_turn_with_sub_id(self.next_internal_sub_id())
             .await
@@ -1952,6 +2273,73 @@ impl Session {
         state.session_configuration.original_config_do_not_use = Arc::new(config);
     }
 
+    /// Replaces the session's scope (`Op::SetScope`) and returns a message
+    /// describing the new one. Later turns pick it up.
+    async fn set_scope(&self, paths: &[PathBuf]) -> std::io::Result<String> {
+        let session_configuration = {
+            let mut state = self.state.lock().await;
+            let scope = resolve_scope(&state.session_configuration.cwd, paths)?;
+            state.session_configuration.scope = scope;
+            state.session_configuration.clone()
+        };
+
+        let sandbox_state = SandboxState {
+            sandbox_policy: scoped_sandbox_policy(
+                session_configuration.sandbox_policy.get(),
+                &session_configuration.scope,
+            ),
+            codex_linux_sandbox_exe: session_configuration
+                .original_config_do_not_use
+                .codex_linux_sandbox_exe
+                .clone(),
+            sandbox_cwd: crate::scope::sandbox_cwd(
+                &session_configuration.cwd,
+                &session_configuration.scope,
+            )
+            .to_path_buf(),
+            use_linux_sandbox_bwrap: self.features.enabled(Feature::UseLinuxSandboxBwrap),
+        };
+        if let Err(e) = self
+            .services
+            .mcp_connection_manager
+            .read()
+            .await
+            .notify_sandbox_state_change(&sandbox_state)
+            .await
+        {
+            warn!("Failed to notify sandbox state change to MCP servers: {e:#}");
+        }
+
+        Ok(if session_configuration.scope.is_empty() {
+            "Scope cleared".to_string()
+        } else {
+            format!(
+                "Scope: {}",
+                display_scope(&session_configuration.cwd, &session_configuration.scope)
+            )
+        })
+    }
+
+    /// Describes whether `path` is excluded by the `.gitignore` and
+    /// `.codexignore` rules of the working directory (`Op::ExplainIgnore`).
+    async fn explain_ignore(&self, path: &Path) -> String {
+        let cwd = {
+            let state = self.state.lock().await;
+            state.session_configuration.cwd.clone()
+        };
+        let path = cwd.join(path);
+        let Ok(relative) = path.strip_prefix(&cwd) else {
+            return format!(
+                "`{}` is outside the working directory; ignore rules only apply inside it",
+                path.display()
+            );
+        };
+        match IgnoreRules::new(&cwd, true).explain(&path) {
+            Some(reason) => format!("`{}` is excluded: {reason}", relative.display()),
+            None => format!("`{}` is not excluded", relative.display()),
+        }
+    }
+
     pub(crate) async fn new_default_turn_with_sub_id(&self, sub_id: String) -> Arc<TurnContext> {
         let session_configuration = {
             let state = self.state.lock().await;
@@ -2004,12 +2392,24 @@ impl Session {
                 next.approval_policy,
                 self.services.exec_policy.current().as_ref(),
                 self.features.enabled(Feature::RequestRule),
-                &next.cwd,
+                next.sandbox_cwd(),
             )
             .into(),
         )
     }
 
+    fn build_scope_update_item(
+        &self,
+        previous: Option<&Arc<TurnContext>>,
+        next: &TurnContext,
+    ) -> Option<ResponseItem> {
+        let prev = previous?;
+        if prev.scope == next.scope {
+            return None;
+        }
+        Some(ScopeContext::collect(&next.config, &next.cwd, &next.scope).into())
+    }
+
     fn build_personality_update_item(
         &self,
         previous: Option<&Arc<TurnContext>>,
@@ -2110,6 +2510,9 @@ impl Session {
         {
             update_items.push(permissions_item);
         }
+        if let Some(scope_item) = self.build_scope_update_item(previous_context, current_context) {
+            update_items.push(scope_item);
+        }
         if let Some(collaboration_mode_item) =
             self.build_collaboration_mode_update_item(previous_context, current_context)
         {
@@ -2154,6 +2557,13 @@ impl Session {
         if let Some(status) = agent_status_from_event(&event.msg) {
             self.agent_status.send_replace(status);
         }
+        if let EventMsg::ExecCommandEnd(end) = &event.msg {
+            self.record_audit_event(AuditEvent::command_executed(end))
+                .await;
+        }
+        if let Some(turn_journal) = &self.services.turn_journal {
+            turn_journal.observe(&event.msg).await;
+        }
         // Persist the event into rollout (recorder filters as needed)
         let rollout_items = vec![RolloutItem::EventMsg(event.msg.clone())];
         self.persist_rollout_items(&rollout_items).await;
@@ -2162,6 +2572,12 @@ impl Session {
         }
     }
 
+    /// Append to the audit log when `[audit_log]` is enabled, and forward the
+    /// event when `[security_events]` is configured.
+    pub(crate) async fn record_audit_event(&self, event: AuditEvent) {
+        self.services.record_audit_event(event).await;
+    }
+
     /// Persist the event to the rollout file, flush it, and only then deliver it to clients.
     ///
     /// Most events can be delivered immediately after queueing the rollout write, but some
@@ -2283,18 +2699,33 @@ impl Session {
     ///
     /// The request is keyed by `call_id` so matching responses are delivered
     /// to the correct in-flight turn. If the task is aborted, this returns the
-    /// default `ReviewDecision` (`Denied`).
+    /// default `ReviewDecision` (`Denied`). The decision is recorded in the
+    /// audit log under `tool`.
     #[allow(clippy::too_many_arguments)]
     pub async fn request_command_approval(
         &self,
         turn_context: &TurnContext,
         call_id: String,
+        tool: &str,
         command: Vec<String>,
         cwd: PathBuf,
         reason: Option<String>,
         network_approval_context: Option<NetworkApprovalContext>,
         proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
     ) -> ReviewDecision {
+        // Read-only sessions never ask; anything needing approval is denied.
+        if turn_context.config.read_only {
+            let decision = ReviewDecision::Denied;
+            self.record_audit_event(AuditEvent::approval(
+                &call_id,
+                tool,
+                &decision,
+                ApprovalDecider::Policy,
+                reason,
+            ))
+            .await;
+            return decision;
+        }
         // Add the tx_approve callback to the map before sending the request.
         let (tx_approve, rx_approve) = oneshot::channel();
         let approval_id = call_id.clone();
@@ -2313,20 +2744,38 @@ impl Session {
         }
 
         let parsed_cmd = parse_command(&command);
+        // Network approvals are about the host, not the command.
+        let explanation = if network_approval_context.is_none() {
+            command_explanation::explain_command(self, turn_context, &command, &cwd).await
+        } else {
+            None
+        };
         let event = EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
             call_id,
             turn_id: turn_context.sub_id.clone(),
             command,
             cwd,
-            reason,
+            reason: reason.clone(),
             network_approval_context,
             proposed_execpolicy_amendment,
             parsed_cmd,
+            explanation,
         });
         self.send_event(turn_context, event).await;
-        rx_approve.await.unwrap_or_default()
+        let decision = rx_approve.await.unwrap_or_default();
+        self.record_audit_event(AuditEvent::approval(
+            &approval_id,
+            tool,
+            &decision,
+            ApprovalDecider::User,
+            reason,
+        ))
+        .await;
+        decision
     }
 
+    /// Emit a patch approval request event and await the user's decision,
+    /// recording it in the audit log as for [`Self::request_command_approval`].
     pub async fn request_patch_approval(
         &self,
         turn_context: &TurnContext,
@@ -2334,7 +2783,19 @@ impl Session {
         changes: HashMap<PathBuf, FileChange>,
         reason: Option<String>,
         grant_root: Option<PathBuf>,
-    ) -> oneshot::Receiver<ReviewDecision> {
+    ) -> ReviewDecision {
+        if turn_context.config.read_only {
+            let decision = ReviewDecision::Denied;
+            self.record_audit_event(AuditEvent::approval(
+                &call_id,
+                "apply_patch",
+                &decision,
+                ApprovalDecider::Policy,
+                reason,
+            ))
+            .await;
+            return decision;
+        }
         // Add the tx_approve callback to the map before sending the request.
         let (tx_approve, rx_approve) = oneshot::channel();
         let approval_id = call_id.clone();
@@ -2356,11 +2817,20 @@ impl Session {
             call_id,
             turn_id: turn_context.sub_id.clone(),
             changes,
-            reason,
+            reason: reason.clone(),
             grant_root,
         });
         self.send_event(turn_context, event).await;
-        rx_approve
+        let decision = rx_approve.await.unwrap_or_default();
+        self.record_audit_event(AuditEvent::approval(
+            &approval_id,
+            "apply_patch",
+            &decision,
+            ApprovalDecider::User,
+            reason,
+        ))
+        .await;
+        decision
     }
 
     pub async fn request_user_input(
@@ -2420,6 +2890,104 @@ impl Session {
         }
     }
 
+    /// Emits `event` and waits for the client's decision; `None` when the turn
+    /// ends before one arrives.
+    pub(crate) async fn request_retry_decision(
+        &self,
+        turn_context: &TurnContext,
+        event: RetriesExhaustedEvent,
+    ) -> Option<RetryExhaustedDecision> {
+        let sub_id = turn_context.sub_id.clone();
+        let (tx_decision, rx_decision) = oneshot::channel();
+        let prev_entry = {
+            let mut active = self.active_turn.lock().await;
+            match active.as_mut() {
+                Some(at) => {
+                    let mut ts = at.turn_state.lock().await;
+                    ts.insert_pending_retry_decision(sub_id.clone(), tx_decision)
+                }
+                None => None,
+            }
+        };
+        if prev_entry.is_some() {
+            warn!("Overwriting existing pending retry decision for sub_id: {sub_id}");
+        }
+
+        self.send_event(turn_context, EventMsg::RetriesExhausted(event))
+            .await;
+        rx_decision.await.ok()
+    }
+
+    pub async fn notify_retry_decision(&self, sub_id: &str, decision: RetryExhaustedDecision) {
+        let entry = {
+            let mut active = self.active_turn.lock().await;
+            match active.as_mut() {
+                Some(at) => {
+                    let mut ts = at.turn_state.lock().await;
+                    ts.remove_pending_retry_decision(sub_id)
+                }
+                None => None,
+            }
+        };
+        match entry {
+            Some(tx_decision) => {
+                tx_decision.send(decision).ok();
+            }
+            None => {
+                warn!("No pending retry decision found for sub_id: {sub_id}");
+            }
+        }
+    }
+
+    /// Emits `event` and waits for the client's answer; `None` when the turn
+    /// ends before one arrives.
+    pub(crate) async fn request_usage_quota_override(
+        &self,
+        turn_context: &TurnContext,
+        event: UsageQuotaExceededEvent,
+    ) -> Option<bool> {
+        let sub_id = turn_context.sub_id.clone();
+        let (tx_approve, rx_approve) = oneshot::channel();
+        let prev_entry = {
+            let mut active = self.active_turn.lock().await;
+            match active.as_mut() {
+                Some(at) => {
+                    let mut ts = at.turn_state.lock().await;
+                    ts.insert_pending_quota_override(sub_id.clone(), tx_approve)
+                }
+                None => None,
+            }
+        };
+        if prev_entry.is_some() {
+            warn!("Overwriting existing pending quota override for sub_id: {sub_id}");
+        }
+
+        self.send_event(turn_context, EventMsg::UsageQuotaExceeded(event))
+            .await;
+        rx_approve.await.ok()
+    }
+
+    pub async fn notify_usage_quota_override(&self, sub_id: &str, approve: bool) {
+        let entry = {
+            let mut active = self.active_turn.lock().await;
+            match active.as_mut() {
+                Some(at) => {
+                    let mut ts = at.turn_state.lock().await;
+                    ts.remove_pending_quota_override(sub_id)
+                }
+                None => None,
+            }
+        };
+        match entry {
+            Some(tx_approve) => {
+                tx_approve.send(approve).ok();
+            }
+            None => {
+                warn!("No pending quota override found for sub_id: {sub_id}");
+            }
+        }
+    }
+
     pub async fn notify_dynamic_tool_response(&self, call_id: &str, response: DynamicToolResponse) {
         let entry = {
             let mut active = self.active_turn.lock().await;
@@ -2565,6 +3133,16 @@ impl Session {
         state.replace_history(items);
     }
 
+    /// Replaces the largest tool output in the history with a short note;
+    /// false when there is none.
+    pub(crate) async fn drop_largest_tool_output(&self) -> bool {
+        let mut state = self.state.lock().await;
+        match state.history.largest_tool_output() {
+            Some((index, _)) => state.history.drop_tool_output(index),
+            None => false,
+        }
+    }
+
     pub(crate) async fn seed_initial_context_if_needed(&self, turn_context: &TurnContext) {
         {
             let mut state = self.state.lock().await;
@@ -2624,13 +3202,23 @@ impl Session {
                 turn_context.approval_policy,
                 self.services.exec_policy.current().as_ref(),
                 self.features.enabled(Feature::RequestRule),
-                &turn_context.cwd,
+                turn_context.sandbox_cwd(),
             )
             .into(),
         );
         if let Some(developer_instructions) = turn_context.developer_instructions.as_deref() {
             items.push(DeveloperInstructions::new(developer_instructions.to_string()).into());
         }
+        // Add developer instructions for the agent role this session runs as.
+        if let Some(role) = turn_context.config.agent_role.as_ref() {
+            let mut role_instructions =
+                format!("You are acting as the `{}` agent role.", role.name);
+            if let Some(instructions) = role.instructions.as_deref() {
+                role_instructions.push_str("\n\n");
+                role_instructions.push_str(instructions);
+            }
+            items.push(DeveloperInstructions::new(role_instructions).into());
+        }
         // Add developer instructions for memories.
         if let Some(memory_prompt) =
             build_memory_tool_developer_instructions(&turn_context.config.codex_home).await
@@ -2682,6 +3270,28 @@ impl Session {
             turn_context,
             shell.as_ref(),
         )));
+        if !turn_context.scope.is_empty() {
+            items.push(
+                ScopeContext::collect(&turn_context.config, &turn_context.cwd, &turn_context.scope)
+                    .into(),
+            );
+        }
+        if turn_context.features.enabled(Feature::RepoBrief)
+            && let Some(brief) = RepoBrief::collect(&turn_context.cwd, &turn_context.scope).await
+        {
+            items.push(brief.into());
+        }
+        if turn_context.features.enabled(Feature::ProjectCommands)
+            && let Some(item) = project_commands_item(self, turn_context).await
+        {
+            items.push(item);
+        }
+        if turn_context.features.enabled(Feature::ProjectMemory)
+            && let Some(item) = project_memory_item(&turn_context.cwd).await
+        {
+            items.push(item);
+        }
+        items.extend(session_start_context_packs(self, turn_context).await);
         items
     }
 
@@ -2702,6 +3312,11 @@ impl Session {
         state.clone_history()
     }
 
+    pub(crate) async fn thread_name(&self) -> Option<String> {
+        let state = self.state.lock().await;
+        state.session_configuration.thread_name.clone()
+    }
+
     pub(crate) async fn update_token_usage_info(
         &self,
         turn_context: &TurnContext,
@@ -2714,6 +3329,23 @@ impl Session {
                     .update_token_info_from_usage(token_usage, turn_context.model_context_window());
             }
         }
+        if let Some(token_usage) = token_usage {
+            if let Some(usage_quota) = &self.services.usage_quota {
+                usage_quota.record(token_usage).await;
+            }
+            for (kind, tokens) in [
+                ("input", token_usage.input_tokens),
+                ("cached_input", token_usage.cached_input_tokens),
+                ("output", token_usage.output_tokens),
+                ("reasoning_output", token_usage.reasoning_output_tokens),
+            ] {
+                if tokens > 0 {
+                    turn_context
+                        .otel_manager
+                        .counter("codex.tokens", tokens, &[("type", kind)]);
+                }
+            }
+        }
         self.send_token_count_event(turn_context).await;
     }
 
@@ -2762,6 +3394,25 @@ impl Session {
         self.send_token_count_event(turn_context).await;
     }
 
+    /// Feed the provider-reported request budget to the shared pacer and
+    /// forward it to clients.
+    pub(crate) async fn update_request_budget(
+        &self,
+        turn_context: &TurnContext,
+        budget: RequestBudgetSnapshot,
+    ) {
+        request_pacer::pacer_for(&turn_context.provider, &turn_context.model_info.slug)
+            .observe(&budget);
+        self.send_event(
+            turn_context,
+            EventMsg::RequestBudget(RequestBudgetEvent {
+                budget,
+                delay_ms: None,
+            }),
+        )
+        .await;
+    }
+
     pub(crate) async fn mcp_dependency_prompted(&self) -> HashSet<String> {
         let state = self.state.lock().await;
         state.mcp_dependency_prompted()
@@ -2775,6 +3426,16 @@ impl Session {
         state.record_mcp_dependency_prompted(names);
     }
 
+    pub(crate) async fn record_read_file_region(&self, path: PathBuf, digest: u64) -> bool {
+        let mut state = self.state.lock().await;
+        state.record_read_file_region(path, digest)
+    }
+
+    pub(crate) async fn apply_plan_update(&self, plan: &mut [PlanItemArg]) {
+        let mut state = self.state.lock().await;
+        state.apply_plan_update(plan);
+    }
+
     pub async fn dependency_env(&self) -> HashMap<String, String> {
         let state = self.state.lock().await;
         state.dependency_env()
@@ -3044,6 +3705,74 @@ impl Session {
         }
     }
 
+    /// Stops all execution for `Op::EmergencyStop` or the `STOP` file: aborts
+    /// the turn and its model requests, kills background terminals and MCP
+    /// server processes, and refuses new tasks for the rest of the session.
+    pub async fn emergency_stop(self: &Arc<Self>, reason: String) {
+        {
+            let mut state = self.state.lock().await;
+            if state.emergency_stop.is_some() {
+                return;
+            }
+            state.emergency_stop = Some(reason.clone());
+        }
+        warn!("emergency stop: {reason}");
+        // Interrupting also terminates the background terminals.
+        self.abort_all_tasks(TurnAbortReason::Interrupted).await;
+        self.cancel_mcp_startup().await;
+        // Aborting a REPL cell leaves the kernel running it; kill the kernels
+        // and the browser so nothing the model started keeps executing.
+        if let Some(manager) = self.services.py_repl.initialized() {
+            manager.reset().await;
+        }
+        if let Some(manager) = self.js_repl.initialized()
+            && let Err(err) = manager.reset().await
+        {
+            warn!("failed to reset js_repl on emergency stop: {err}");
+        }
+        self.services.browser.shutdown().await;
+        // Dropping the clients kills their server processes, including the
+        // exec server and the commands it is running.
+        drop(std::mem::take(
+            &mut *self.services.mcp_connection_manager.write().await,
+        ));
+        self.flush_rollout().await;
+        let event = Event {
+            id: self.next_internal_sub_id(),
+            msg: EventMsg::Error(ErrorEvent {
+                message: emergency_stop_message(&reason),
+                codex_error_info: Some(CodexErrorInfo::Other),
+            }),
+        };
+        self.send_event_raw(event).await;
+    }
+
+    pub(crate) async fn emergency_stop_reason(&self) -> Option<String> {
+        self.state.lock().await.emergency_stop.clone()
+    }
+
+    /// Polls for `$CODEX_HOME/STOP` and triggers an emergency stop when it
+    /// appears. Polling avoids a watcher on `CODEX_HOME`, which every session
+    /// writes to constantly.
+    fn start_stop_file_watch(self: &Arc<Self>, codex_home: &Path) {
+        let stop_file = codex_home.join(STOP_FILE_NAME);
+        let weak_sess = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(STOP_FILE_POLL_INTERVAL);
+            loop {
+                interval.tick().await;
+                let Some(sess) = weak_sess.upgrade() else {
+                    break;
+                };
+                if tokio::fs::try_exists(&stop_file).await.unwrap_or(false) {
+                    sess.emergency_stop(format!("{} exists", stop_file.display()))
+                        .await;
+                    break;
+                }
+            }
+        });
+    }
+
     pub(crate) fn hooks(&self) -> &Hooks {
         &self.services.hooks
     }
@@ -3070,7 +3799,7 @@ impl Session {
         let sandbox_state = SandboxState {
             sandbox_policy: turn_context.sandbox_policy.clone(),
             codex_linux_sandbox_exe: turn_context.codex_linux_sandbox_exe.clone(),
-            sandbox_cwd: turn_context.cwd.clone(),
+            sandbox_cwd: turn_context.sandbox_cwd().to_path_buf(),
             use_linux_sandbox_bwrap: turn_context.features.enabled(Feature::UseLinuxSandboxBwrap),
         };
         let cancel_token = self.reset_mcp_startup_cancellation_token().await;
@@ -3084,6 +3813,7 @@ impl Session {
                 self.get_tx_event(),
                 cancel_token,
                 sandbox_state,
+                self.features.enabled(Feature::LazyMcpStartup),
             )
             .await;
 
@@ -3177,6 +3907,9 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::CleanBackgroundTerminals => {
                 handlers::clean_background_terminals(&sess).await;
             }
+            Op::EmergencyStop => {
+                handlers::emergency_stop(&sess).await;
+            }
             Op::OverrideTurnContext {
                 cwd,
                 approval_policy,
@@ -3214,6 +3947,32 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                 )
                 .await;
             }
+            Op::OverrideTurnSettings {
+                model,
+                effort,
+                verbosity,
+            } => {
+                let collaboration_mode = {
+                    let state = sess.state.lock().await;
+                    state
+                        .session_configuration
+                        .collaboration_mode
+                        .with_updates(model, effort, None)
+                };
+                handlers::override_turn_context(
+                    &sess,
+                    sub.id.clone(),
+                    SessionSettingsUpdate {
+                        collaboration_mode: Some(collaboration_mode),
+                        model_verbosity: verbosity,
+                        ..Default::default()
+                    },
+                )
+                .await;
+            }
+            Op::RouteNextTurn { tier } => {
+                handlers::route_next_turn(&sess, tier).await;
+            }
             Op::UserInput { .. } | Op::UserTurn { .. } => {
                 handlers::user_input_or_turn(&sess, sub.id.clone(), sub.op, &mut previous_context)
                     .await;
@@ -3231,6 +3990,12 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::UserInputAnswer { id, response } => {
                 handlers::request_user_input_response(&sess, id, response).await;
             }
+            Op::RetryExhaustedDecision { id, decision } => {
+                handlers::retry_exhausted_decision(&sess, id, decision).await;
+            }
+            Op::UsageQuotaOverride { id, approve } => {
+                handlers::usage_quota_override(&sess, id, approve).await;
+            }
             Op::DynamicToolResponse { id, response } => {
                 handlers::dynamic_tool_response(&sess, id, response).await;
             }
@@ -3250,6 +4015,18 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::ReloadUserConfig => {
                 handlers::reload_user_config(&sess).await;
             }
+            Op::SetWireLog { enabled } => {
+                handlers::set_wire_log(&sess, sub.id.clone(), enabled).await;
+            }
+            Op::SetScope { paths } => {
+                handlers::set_scope(&sess, sub.id.clone(), paths).await;
+            }
+            Op::ExplainIgnore { path } => {
+                handlers::explain_ignore(&sess, sub.id.clone(), path).await;
+            }
+            Op::AddContextPack { name } => {
+                handlers::add_context_pack(&sess, sub.id.clone(), name).await;
+            }
             Op::ListCustomPrompts => {
                 handlers::list_custom_prompts(&sess, sub.id.clone()).await;
             }
@@ -3275,6 +4052,15 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::Undo => {
                 handlers::undo(&sess, sub.id.clone()).await;
             }
+            Op::UndoFileChange { paths } => {
+                handlers::undo_file_change(&sess, sub.id.clone(), paths).await;
+            }
+            Op::CommitChanges { message } => {
+                handlers::commit_changes(&sess, sub.id.clone(), message).await;
+            }
+            Op::CreatePullRequest { title } => {
+                handlers::create_pull_request(&sess, sub.id.clone(), title).await;
+            }
             Op::Compact => {
                 handlers::compact(&sess, sub.id.clone()).await;
             }
@@ -3287,9 +4073,22 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::ThreadRollback { num_turns } => {
                 handlers::thread_rollback(&sess, sub.id.clone(), num_turns).await;
             }
+            Op::ForkConversation { nth_user_message } => {
+                handlers::fork_conversation(&sess, sub.id.clone(), nth_user_message).await;
+            }
             Op::SetThreadName { name } => {
                 handlers::set_thread_name(&sess, sub.id.clone(), name).await;
             }
+            Op::SetQueuedUserMessages { messages } => {
+                handlers::set_queued_user_messages(&sess, sub.id.clone(), messages).await;
+            }
+            Op::AddBookmark {
+                turn,
+                excerpt,
+                note,
+            } => {
+                handlers::add_bookmark(&sess, sub.id.clone(), turn, excerpt, note).await;
+            }
             Op::RunUserShellCommand { command } => {
                 handlers::run_user_shell_command(
                     &sess,
@@ -3320,6 +4119,12 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
     debug!("Agent loop exited");
 }
 
+pub(crate) fn emergency_stop_message(reason: &str) -> String {
+    format!(
+        "Emergency stop ({reason}): all execution was stopped. Resume this session to continue."
+    )
+}
+
 /// Operation handlers
 mod handlers {
     use crate::codex::Session;
@@ -3329,19 +4134,28 @@ mod handlers {
 
     use crate::codex::spawn_review_thread;
     use crate::config::Config;
+    use crate::config::types::TurnRouting;
+    use crate::context_packs::context_pack_item;
 
     use crate::mcp::auth::compute_auth_statuses;
     use crate::mcp::collect_mcp_snapshot_from_manager;
     use crate::mcp::effective_mcp_servers;
     use crate::review_prompts::resolve_review_request;
     use crate::rollout::session_index;
+    use crate::tasks::CommitTask;
     use crate::tasks::CompactTask;
+    use crate::tasks::PipelineTask;
+    use crate::tasks::PullRequestTask;
+    use crate::tasks::UndoFileChangeTask;
     use crate::tasks::UndoTask;
     use crate::tasks::UserShellCommandMode;
     use crate::tasks::UserShellCommandTask;
     use crate::tasks::execute_user_shell_command;
     use codex_protocol::custom_prompts::CustomPrompt;
+    use codex_protocol::protocol::BackgroundEventEvent;
+    use codex_protocol::protocol::BookmarkAddedEvent;
     use codex_protocol::protocol::CodexErrorInfo;
+    use codex_protocol::protocol::ConversationForkedEvent;
     use codex_protocol::protocol::ErrorEvent;
     use codex_protocol::protocol::Event;
     use codex_protocol::protocol::EventMsg;
@@ -3349,9 +4163,12 @@ mod handlers {
     use codex_protocol::protocol::ListRemoteSkillsResponseEvent;
     use codex_protocol::protocol::ListSkillsResponseEvent;
     use codex_protocol::protocol::McpServerRefreshConfig;
+    use codex_protocol::protocol::ModelTier;
     use codex_protocol::protocol::Op;
+    use codex_protocol::protocol::QueuedUserMessagesUpdatedEvent;
     use codex_protocol::protocol::RemoteSkillDownloadedEvent;
     use codex_protocol::protocol::RemoteSkillSummary;
+    use codex_protocol::protocol::RetryExhaustedDecision;
     use codex_protocol::protocol::ReviewDecision;
     use codex_protocol::protocol::ReviewRequest;
     use codex_protocol::protocol::SkillsListEntry;
@@ -3383,6 +4200,11 @@ mod handlers {
         sess.close_unified_exec_processes().await;
     }
 
+    pub async fn emergency_stop(sess: &Arc<Session>) {
+        sess.emergency_stop("requested by the client".to_string())
+            .await;
+    }
+
     pub async fn override_turn_context(
         sess: &Session,
         sub_id: String,
@@ -3400,6 +4222,10 @@ mod handlers {
         }
     }
 
+    pub async fn route_next_turn(sess: &Session, tier: ModelTier) {
+        sess.state.lock().await.next_turn_model_tier = Some(tier);
+    }
+
     pub async fn user_input_or_turn(
         sess: &Arc<Session>,
         sub_id: String,
@@ -3466,6 +4292,7 @@ mod handlers {
 
         // Attempt to inject input into current task.
         if let Err(SteerInputError::NoActiveTurn(items)) = sess.steer_input(items, None).await {
+            let current_context = sess.route_turn_model(current_context, &items).await;
             sess.seed_initial_context_if_needed(&current_context).await;
             let previous_model = sess.previous_model().await;
             let update_items = sess.build_settings_update_items(
@@ -3480,9 +4307,23 @@ mod handlers {
 
             sess.refresh_mcp_servers_if_requested(&current_context)
                 .await;
-            let regular_task = sess.take_startup_regular_task().await.unwrap_or_default();
-            sess.spawn_task(Arc::clone(&current_context), items, regular_task)
+            let pipeline = current_context
+                .config
+                .orchestration
+                .clone()
+                .filter(|orchestration| orchestration.routing == TurnRouting::Pipeline);
+            if let Some(orchestration) = pipeline {
+                sess.spawn_task(
+                    Arc::clone(&current_context),
+                    items,
+                    PipelineTask::new(orchestration),
+                )
                 .await;
+            } else {
+                let regular_task = sess.take_startup_regular_task().await.unwrap_or_default();
+                sess.spawn_task(Arc::clone(&current_context), items, regular_task)
+                    .await;
+            }
             *previous_context = Some(current_context);
         }
     }
@@ -3616,6 +4457,18 @@ mod handlers {
         sess.notify_user_input_response(&id, response).await;
     }
 
+    pub async fn retry_exhausted_decision(
+        sess: &Arc<Session>,
+        id: String,
+        decision: RetryExhaustedDecision,
+    ) {
+        sess.notify_retry_decision(&id, decision).await;
+    }
+
+    pub async fn usage_quota_override(sess: &Arc<Session>, id: String, approve: bool) {
+        sess.notify_usage_quota_override(&id, approve).await;
+    }
+
     pub async fn dynamic_tool_response(
         sess: &Arc<Session>,
         id: String,
@@ -3680,6 +4533,65 @@ mod handlers {
         sess.reload_user_config_layer().await;
     }
 
+    pub async fn set_wire_log(sess: &Session, sub_id: String, enabled: bool) {
+        let Some(wire_log) = sess.services.model_client.wire_log() else {
+            return;
+        };
+        let msg = match wire_log.set_enabled(enabled) {
+            Ok(()) if enabled => EventMsg::BackgroundEvent(BackgroundEventEvent {
+                message: format!("Wire log enabled: {}", wire_log.path().display()),
+            }),
+            Ok(()) => EventMsg::BackgroundEvent(BackgroundEventEvent {
+                message: "Wire log disabled".to_string(),
+            }),
+            Err(err) => EventMsg::Error(ErrorEvent {
+                message: format!(
+                    "failed to open wire log {}: {err}",
+                    wire_log.path().display()
+                ),
+                codex_error_info: Some(CodexErrorInfo::Other),
+            }),
+        };
+        sess.send_event_raw(Event { id: sub_id, msg }).await;
+    }
+
+    pub async fn set_scope(sess: &Session, sub_id: String, paths: Vec<PathBuf>) {
+        let msg = match sess.set_scope(&paths).await {
+            Ok(message) => EventMsg::BackgroundEvent(BackgroundEventEvent { message }),
+            Err(err) => EventMsg::Error(ErrorEvent {
+                message: format!("failed to set scope: {err}"),
+                codex_error_info: Some(CodexErrorInfo::BadRequest),
+            }),
+        };
+        sess.send_event_raw(Event { id: sub_id, msg }).await;
+    }
+
+    pub async fn explain_ignore(sess: &Session, sub_id: String, path: PathBuf) {
+        let message = sess.explain_ignore(&path).await;
+        sess.send_event_raw(Event {
+            id: sub_id,
+            msg: EventMsg::BackgroundEvent(BackgroundEventEvent { message }),
+        })
+        .await;
+    }
+
+    pub async fn add_context_pack(sess: &Session, sub_id: String, name: String) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id.clone()).await;
+        let msg = match context_pack_item(sess, &turn_context, &name).await {
+            Ok(item) => {
+                sess.record_conversation_items(&turn_context, &[item]).await;
+                EventMsg::BackgroundEvent(BackgroundEventEvent {
+                    message: format!("Added context pack `{name}`"),
+                })
+            }
+            Err(err) => EventMsg::Error(ErrorEvent {
+                message: format!("failed to add context pack: {err}"),
+                codex_error_info: Some(CodexErrorInfo::BadRequest),
+            }),
+        };
+        sess.send_event_raw(Event { id: sub_id, msg }).await;
+    }
+
     pub async fn list_mcp_tools(sess: &Session, config: &Arc<Config>, sub_id: String) {
         let mcp_connection_manager = sess.services.mcp_connection_manager.read().await;
         let auth = sess.services.auth_manager.auth().await;
@@ -3820,10 +4732,57 @@ mod handlers {
 
     pub async fn undo(sess: &Arc<Session>, sub_id: String) {
         let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        if reject_in_read_only(sess, &turn_context, "Undoing a turn").await {
+            return;
+        }
         sess.spawn_task(turn_context, Vec::new(), UndoTask::new())
             .await;
     }
 
+    /// Read-only sessions never ask for approval, so operations that write
+    /// to the workspace are refused up front instead of spawning their task.
+    async fn reject_in_read_only(sess: &Session, turn_context: &TurnContext, action: &str) -> bool {
+        if !turn_context.config.read_only {
+            return false;
+        }
+        sess.send_event_raw(Event {
+            id: turn_context.sub_id.clone(),
+            msg: EventMsg::Error(ErrorEvent {
+                message: format!("{action} is not allowed in a read-only session"),
+                codex_error_info: Some(CodexErrorInfo::BadRequest),
+            }),
+        })
+        .await;
+        true
+    }
+
+    pub async fn undo_file_change(sess: &Arc<Session>, sub_id: String, paths: Vec<PathBuf>) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        if reject_in_read_only(sess, &turn_context, "Undoing file changes").await {
+            return;
+        }
+        sess.spawn_task(turn_context, Vec::new(), UndoFileChangeTask::new(paths))
+            .await;
+    }
+
+    pub async fn commit_changes(sess: &Arc<Session>, sub_id: String, message: Option<String>) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        if reject_in_read_only(sess, &turn_context, "Committing changes").await {
+            return;
+        }
+        sess.spawn_task(turn_context, Vec::new(), CommitTask::new(message))
+            .await;
+    }
+
+    pub async fn create_pull_request(sess: &Arc<Session>, sub_id: String, title: Option<String>) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        if reject_in_read_only(sess, &turn_context, "Creating a pull request").await {
+            return;
+        }
+        sess.spawn_task(turn_context, Vec::new(), PullRequestTask::new(title))
+            .await;
+    }
+
     pub async fn compact(sess: &Arc<Session>, sub_id: String) {
         let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
 
@@ -3939,9 +4898,78 @@ mod handlers {
         sess.replace_history(history.raw_items().to_vec()).await;
         sess.recompute_token_usage(turn_context.as_ref()).await;
 
-        sess.send_event_raw_flushed(Event {
+        sess.send_event_raw_flushed(Event {
+            id: turn_context.sub_id.clone(),
+            msg: EventMsg::ThreadRolledBack(ThreadRolledBackEvent { num_turns }),
+        })
+        .await;
+    }
+
+    /// Forks the recorded rollout into a new thread cut before the `nth_user_message`-th user
+    /// message, leaving this thread untouched, and emits `ConversationForked` on success.
+    pub async fn fork_conversation(sess: &Arc<Session>, sub_id: String, nth_user_message: u32) {
+        let has_active_turn = { sess.active_turn.lock().await.is_some() };
+        if has_active_turn {
+            sess.send_event_raw(Event {
+                id: sub_id,
+                msg: EventMsg::Error(ErrorEvent {
+                    message: "Cannot fork while a turn is in progress.".to_string(),
+                    codex_error_info: Some(CodexErrorInfo::BadRequest),
+                }),
+            })
+            .await;
+            return;
+        }
+
+        let rollout_path = {
+            let rollout = sess.services.rollout.lock().await;
+            rollout
+                .as_ref()
+                .map(|recorder| recorder.rollout_path().to_path_buf())
+        };
+        let Some(rollout_path) = rollout_path else {
+            sess.send_event_raw(Event {
+                id: sub_id,
+                msg: EventMsg::Error(ErrorEvent {
+                    message: "Session persistence is disabled; cannot fork thread.".to_string(),
+                    codex_error_info: Some(CodexErrorInfo::Other),
+                }),
+            })
+            .await;
+            return;
+        };
+        sess.ensure_rollout_materialized().await;
+        sess.flush_rollout().await;
+
+        // Carry over the session's current settings (model, cwd, scope) rather than the ones it was
+        // started with.
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        let mut config = (*turn_context.config).clone();
+        config.model = Some(turn_context.model_info.slug.clone());
+        config.model_reasoning_effort = turn_context.reasoning_effort;
+        config.cwd = turn_context.cwd.clone();
+        config.scope = turn_context.scope.clone();
+
+        let nth = usize::try_from(nth_user_message).unwrap_or(usize::MAX);
+        let msg = match sess
+            .services
+            .agent_control
+            .fork_thread(config, rollout_path, nth)
+            .await
+        {
+            Ok(forked) => EventMsg::ConversationForked(ConversationForkedEvent {
+                thread_id: forked.thread_id,
+                rollout_path: forked.session_configured.rollout_path,
+                nth_user_message,
+            }),
+            Err(err) => EventMsg::Error(ErrorEvent {
+                message: format!("Failed to fork thread: {err}"),
+                codex_error_info: Some(CodexErrorInfo::BadRequest),
+            }),
+        };
+        sess.send_event_raw(Event {
             id: turn_context.sub_id.clone(),
-            msg: EventMsg::ThreadRolledBack(ThreadRolledBackEvent { num_turns }),
+            msg,
         })
         .await;
     }
@@ -4012,12 +5040,50 @@ mod handlers {
         .await;
     }
 
+    /// Records the client's pending-message queue in the rollout; the latest
+    /// `QueuedUserMessagesUpdated` event is what a resumed client restores.
+    pub async fn set_queued_user_messages(
+        sess: &Arc<Session>,
+        sub_id: String,
+        messages: Vec<String>,
+    ) {
+        sess.send_event_raw(Event {
+            id: sub_id,
+            msg: EventMsg::QueuedUserMessagesUpdated(QueuedUserMessagesUpdatedEvent { messages }),
+        })
+        .await;
+    }
+
+    /// Records a bookmark in the rollout, where clients find it again on resume
+    /// and `codex sessions export` includes it.
+    pub async fn add_bookmark(
+        sess: &Arc<Session>,
+        sub_id: String,
+        turn: u32,
+        excerpt: String,
+        note: Option<String>,
+    ) {
+        let note = note
+            .map(|note| note.trim().to_string())
+            .filter(|note| !note.is_empty());
+        sess.send_event_raw(Event {
+            id: sub_id,
+            msg: EventMsg::BookmarkAdded(BookmarkAddedEvent {
+                turn,
+                excerpt,
+                note,
+            }),
+        })
+        .await;
+    }
+
     pub async fn shutdown(sess: &Arc<Session>, sub_id: String) -> bool {
         sess.abort_all_tasks(TurnAbortReason::Interrupted).await;
         sess.services
             .unified_exec_manager
             .terminate_all_processes()
             .await;
+        sess.services.patch_backups.lock().await.clear();
         info!("Shutting down Codex instance");
         let history = sess.clone_history().await;
         let turn_count = history
@@ -4117,11 +5183,12 @@ async fn spawn_review_thread(
         .disable(crate::features::Feature::WebSearchRequest)
         .disable(crate::features::Feature::WebSearchCached);
     let review_web_search_mode = WebSearchMode::Disabled;
-    let tools_config = ToolsConfig::new(&ToolsConfigParams {
+    let mut tools_config = ToolsConfig::new(&ToolsConfigParams {
         model_info: &review_model_info,
         features: &review_features,
         web_search_mode: Some(review_web_search_mode),
     });
+    tools_config.apply_builtin_tools(config.builtin_tools);
 
     let review_prompt = resolved.prompt.clone();
     let provider = parent_turn_context.provider.clone();
@@ -4174,6 +5241,7 @@ async fn spawn_review_thread(
         provider: provider_for_context,
         reasoning_effort,
         reasoning_summary,
+        model_verbosity: parent_turn_context.model_verbosity,
         session_source,
         tools_config,
         features: parent_turn_context.features.clone(),
@@ -4189,6 +5257,7 @@ async fn spawn_review_thread(
         windows_sandbox_level: parent_turn_context.windows_sandbox_level,
         shell_environment_policy: parent_turn_context.shell_environment_policy.clone(),
         cwd: parent_turn_context.cwd.clone(),
+        scope: parent_turn_context.scope.clone(),
         final_output_json_schema: None,
         codex_linux_sandbox_exe: parent_turn_context.codex_linux_sandbox_exe.clone(),
         tool_call_gate: Arc::new(ReadinessFlag::new()),
@@ -4196,6 +5265,7 @@ async fn spawn_review_thread(
         dynamic_tools: parent_turn_context.dynamic_tools.clone(),
         truncation_policy: model_info.truncation_policy.into(),
         turn_metadata_state,
+        latency: Arc::new(TurnLatency::new()),
     };
 
     // Seed the child task with the review prompt as the initial user message.
@@ -4436,11 +5506,18 @@ pub(crate) async fn run_turn(
     // Although from the perspective of codex.rs, TurnDiffTracker has the lifecycle of a Task which contains
     // many turns, from the perspective of the user, it is a single turn.
     let turn_diff_tracker = Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new()));
+    // A model fallback replaces the context for the rest of the turn.
+    let mut turn_context = turn_context;
+    let mut retry_budget = RetryBudget::default();
 
     // `ModelClientSession` is turn-scoped and caches WebSocket + sticky routing state, so we reuse
     // one instance across retries within this turn.
     let mut client_session =
         prewarmed_client_session.unwrap_or_else(|| sess.services.model_client.new_session());
+    let mut output_schema_repairs = 0;
+    let mut completion_gate_retries = 0;
+    let mut run_budget = RunBudget::new(turn_context.config.run_limits);
+    let mut summarizing = false;
 
     loop {
         // Note that pending_input would be something like a message the user
@@ -4473,6 +5550,25 @@ pub(crate) async fn run_turn(
             }
         }
 
+        if !summarizing && let Some(limit) = run_budget.exceeded() {
+            sess.send_event(
+                &turn_context,
+                EventMsg::Warning(WarningEvent {
+                    message: format!(
+                        "This run has reached {limit}; asking the model to summarize and stop."
+                    ),
+                }),
+            )
+            .await;
+            sess.record_conversation_items(&turn_context, &[run_limits::summary_request(&limit)])
+                .await;
+            summarizing = true;
+        }
+
+        if !usage_quota::enforce(&sess, &turn_context).await {
+            break;
+        }
+
         // Construct the input that we will send to the model.
         let sampling_request_input: Vec<ResponseItem> = {
             sess.clone_history()
@@ -4498,6 +5594,7 @@ pub(crate) async fn run_turn(
             sampling_request_input,
             &explicitly_enabled_connectors,
             skills_outcome.as_ref(),
+            !summarizing,
             cancellation_token.child_token(),
         )
         .await
@@ -4507,6 +5604,9 @@ pub(crate) async fn run_turn(
                     needs_follow_up,
                     last_agent_message: sampling_request_last_agent_message,
                 } = sampling_request_output;
+                run_budget.record_turn();
+                // The summary is the last thing a run that hit its limits does.
+                let needs_follow_up = needs_follow_up && !summarizing;
                 let total_usage_tokens = sess.get_total_token_usage().await;
                 let token_limit_reached = total_usage_tokens >= auto_compact_limit;
 
@@ -4532,6 +5632,74 @@ pub(crate) async fn run_turn(
                 }
 
                 if !needs_follow_up {
+                    if let Some(schema) = turn_context.final_output_json_schema.as_ref()
+                        && let Err(errors) = output_schema::validate_final_message(
+                            schema,
+                            sampling_request_last_agent_message
+                                .as_deref()
+                                .unwrap_or_default(),
+                        )
+                    {
+                        if output_schema_repairs < output_schema::MAX_OUTPUT_SCHEMA_REPAIRS {
+                            output_schema_repairs += 1;
+                            warn!(
+                                turn_id = %turn_context.sub_id,
+                                attempt = output_schema_repairs,
+                                "final message does not match the output schema; requesting a repair"
+                            );
+                            sess.send_event(
+                                &turn_context,
+                                EventMsg::Warning(WarningEvent {
+                                    message: format!(
+                                        "The final message does not match the output schema; asking the model to fix it (attempt {output_schema_repairs} of {}).",
+                                        output_schema::MAX_OUTPUT_SCHEMA_REPAIRS
+                                    ),
+                                }),
+                            )
+                            .await;
+                            sess.record_conversation_items(
+                                &turn_context,
+                                &[output_schema::repair_request(&errors)],
+                            )
+                            .await;
+                            continue;
+                        }
+                        let event = EventMsg::Error(ErrorEvent {
+                            message: format!(
+                                "The final message does not match the output schema:\n{}",
+                                output_schema::describe_errors(&errors)
+                            ),
+                            codex_error_info: Some(CodexErrorInfo::Other),
+                        });
+                        sess.send_event(&turn_context, event).await;
+                    }
+                    if !summarizing
+                        && !turn_context.config.completion_gate.commands.is_empty()
+                        && turn_changed_files(&turn_diff_tracker).await
+                        && let Some(failure) =
+                            run_completion_gate(&sess, &turn_context, &cancellation_token).await
+                    {
+                        let max_retries = turn_context.config.completion_gate.max_retries;
+                        if completion_gate_retries < max_retries {
+                            completion_gate_retries += 1;
+                            sess.record_conversation_items(
+                                &turn_context,
+                                &[failure.follow_up(max_retries - completion_gate_retries)],
+                            )
+                            .await;
+                            continue;
+                        }
+                        sess.send_event(
+                            &turn_context,
+                            EventMsg::Warning(WarningEvent {
+                                message: format!(
+                                    "The completion check `{}` still fails after {max_retries} attempts to fix it.",
+                                    failure.command
+                                ),
+                            }),
+                        )
+                        .await;
+                    }
                     last_agent_message = sampling_request_last_agent_message;
                     sess.hooks()
                         .dispatch(HookPayload {
@@ -4574,6 +5742,14 @@ pub(crate) async fn run_turn(
             }
             Err(e) => {
                 info!("Turn error: {e:#}");
+                // Retryable errors only get here once their retries are used up.
+                if e.is_retryable()
+                    && let Recovery::Retry(next) =
+                        retry_budget.recover(&sess, &turn_context, &e).await
+                {
+                    turn_context = next;
+                    continue;
+                }
                 let event = EventMsg::Error(e.to_error_event(None));
                 sess.send_event(&turn_context, event).await;
                 // let the user continue the conversation
@@ -4585,10 +5761,29 @@ pub(crate) async fn run_turn(
     last_agent_message
 }
 
+/// Whether the files touched by patches in this turn differ from where they
+/// started.
+async fn turn_changed_files(turn_diff_tracker: &SharedTurnDiffTracker) -> bool {
+    matches!(
+        turn_diff_tracker.lock().await.get_unified_diff(),
+        Ok(Some(_))
+    )
+}
+
 async fn run_pre_sampling_compact(
     sess: &Arc<Session>,
     turn_context: &Arc<TurnContext>,
 ) -> CodexResult<()> {
+    let resume_summary_pending =
+        std::mem::take(&mut sess.state.lock().await.resume_summary_pending);
+    if resume_summary_pending && let Some(config) = turn_context.config.resume_summary {
+        // Without a summary the turn still works from the full history.
+        match run_resume_summary_task(Arc::clone(sess), Arc::clone(turn_context), config).await {
+            Err(CodexErr::Interrupted) => return Err(CodexErr::Interrupted),
+            Err(err) => warn!("resume summary failed; keeping the full history: {err}"),
+            Ok(()) => {}
+        }
+    }
     let total_usage_tokens_before_compaction = sess.get_total_token_usage().await;
     maybe_run_previous_model_inline_compact(
         sess,
@@ -4829,6 +6024,7 @@ async fn run_sampling_request(
     input: Vec<ResponseItem>,
     explicitly_enabled_connectors: &HashSet<String>,
     skills_outcome: Option<&SkillLoadOutcome>,
+    allow_tools: bool,
     cancellation_token: CancellationToken,
 ) -> CodexResult<SamplingRequestResult> {
     let router = built_tools(
@@ -4843,11 +6039,14 @@ async fn run_sampling_request(
 
     let model_supports_parallel = turn_context.model_info.supports_parallel_tool_calls;
 
-    let tools =
-        crate::tools::spec::filter_tools_for_model(router.specs(), &turn_context.tools_config);
+    let tools = if allow_tools {
+        crate::tools::spec::filter_tools_for_model(router.specs(), &turn_context.tools_config)
+    } else {
+        Vec::new()
+    };
     let base_instructions = sess.get_base_instructions().await;
 
-    let prompt = Prompt {
+    let mut prompt = Prompt {
         input,
         tools,
         parallel_tool_calls: model_supports_parallel,
@@ -4858,6 +6057,7 @@ async fn run_sampling_request(
 
     let mut retries = 0;
     loop {
+        pace_sampling_request(&sess, &turn_context, &cancellation_token).await?;
         let err = match try_run_sampling_request(
             Arc::clone(&router),
             Arc::clone(&sess),
@@ -4910,8 +6110,12 @@ async fn run_sampling_request(
         if retries < max_retries {
             retries += 1;
             let delay = match &err {
-                CodexErr::Stream(_, requested_delay) => {
-                    requested_delay.unwrap_or_else(|| backoff(retries))
+                CodexErr::Stream(_, Some(requested_delay)) => {
+                    // The provider asked us to back off: hold every session
+                    // sharing this budget, not just this one.
+                    request_pacer::pacer_for(&turn_context.provider, &turn_context.model_info.slug)
+                        .pause(*requested_delay);
+                    *requested_delay
                 }
                 _ => backoff(retries),
             };
@@ -4919,6 +6123,23 @@ async fn run_sampling_request(
                 "stream disconnected - retrying sampling request ({retries}/{max_retries} in {delay:?})...",
             );
 
+            if let CodexErr::StreamStalled(_) = &err {
+                // Continue from what the stalled attempt produced: its completed items, tool
+                // outputs, and partial message are already in history.
+                prompt.input = sess
+                    .clone_history()
+                    .await
+                    .for_prompt(&turn_context.model_info.input_modalities);
+                sess.notify_stream_error(
+                    &turn_context,
+                    format!("Model stream stalled; continuing... {retries}/{max_retries}"),
+                    err,
+                )
+                .await;
+                tokio::time::sleep(delay).await;
+                continue;
+            }
+
             // In release builds, hide the first websocket retry notification to reduce noisy
             // transient reconnect messages. In debug builds, keep full visibility for diagnosis.
             let report_error = retries > 1
@@ -4946,6 +6167,41 @@ async fn run_sampling_request(
     }
 }
 
+/// Wait until the provider-reported request and token budget has room for the
+/// next request, telling clients when that means holding it back.
+async fn pace_sampling_request(
+    sess: &Session,
+    turn_context: &TurnContext,
+    cancellation_token: &CancellationToken,
+) -> CodexResult<()> {
+    let pacer = request_pacer::pacer_for(&turn_context.provider, &turn_context.model_info.slug);
+    let estimated_tokens = sess
+        .get_estimated_token_count(turn_context)
+        .await
+        .and_then(|tokens| u64::try_from(tokens).ok())
+        .unwrap_or_default();
+    let delay = pacer.reserve(estimated_tokens);
+    if delay.is_zero() {
+        return Ok(());
+    }
+
+    if let Some(budget) = pacer.snapshot() {
+        sess.send_event(
+            turn_context,
+            EventMsg::RequestBudget(RequestBudgetEvent {
+                budget,
+                delay_ms: Some(u64::try_from(delay.as_millis()).unwrap_or(u64::MAX)),
+            }),
+        )
+        .await;
+    }
+    debug!("pacing sampling request by {delay:?} to stay within the provider rate limit");
+    tokio::time::sleep(delay)
+        .or_cancel(cancellation_token)
+        .await?;
+    Ok(())
+}
+
 async fn built_tools(
     sess: &Session,
     turn_context: &TurnContext,
@@ -4962,6 +6218,11 @@ async fn built_tools(
         .list_all_tools()
         .or_cancel(cancellation_token)
         .await?;
+    // Read-only sessions never ask for approval, so only offer MCP tools
+    // their server declares read-only.
+    if turn_context.config.read_only {
+        mcp_tools = filter_read_only_mcp_tools(&mcp_tools);
+    }
 
     let mut effective_explicitly_enabled_connectors = explicitly_enabled_connectors.clone();
     effective_explicitly_enabled_connectors.extend(sess.get_connector_selection().await);
@@ -5419,6 +6680,54 @@ async fn drain_in_flight(
     Ok(())
 }
 
+/// Next event from the model stream. Returns `None` when `stall_timeout` passes without one.
+async fn next_response_event(
+    stream: &mut ResponseStream,
+    stall_timeout: Option<Duration>,
+) -> Option<Option<CodexResult<ResponseEvent>>> {
+    match stall_timeout {
+        Some(stall_timeout) => tokio::time::timeout(stall_timeout, stream.next())
+            .await
+            .ok(),
+        None => Some(stream.next().await),
+    }
+}
+
+/// Keeps the part of an assistant message that streamed before the user interrupted the
+/// turn (or the stream stalled), followed by `marker`, so later requests can refer to it.
+async fn record_interrupted_agent_message(
+    sess: &Session,
+    turn_context: &TurnContext,
+    active_item: Option<TurnItem>,
+    streamed_text: &str,
+    marker: &str,
+) {
+    let Some(TurnItem::AgentMessage(item)) = active_item else {
+        return;
+    };
+    if streamed_text.trim().is_empty() {
+        return;
+    }
+    let text = format!("{streamed_text}\n\n{marker}");
+    let message = ResponseItem::Message {
+        id: None,
+        role: "assistant".to_string(),
+        content: vec![ContentItem::OutputText { text: text.clone() }],
+        end_turn: None,
+        phase: item.phase.clone(),
+    };
+    sess.record_conversation_items(turn_context, &[message])
+        .await;
+    sess.emit_turn_item_completed(
+        turn_context,
+        TurnItem::AgentMessage(codex_protocol::items::AgentMessageItem {
+            content: vec![codex_protocol::items::AgentMessageContent::Text { text }],
+            ..item
+        }),
+    )
+    .await;
+}
+
 #[allow(clippy::too_many_arguments)]
 #[instrument(level = "trace",
     skip_all,
@@ -5451,6 +6760,7 @@ async fn try_run_sampling_request(
     );
 
     sess.persist_rollout_items(&[rollout_item]).await;
+    let request_started_at = Instant::now();
     let mut stream = client_session
         .stream(
             prompt,
@@ -5475,9 +6785,20 @@ async fn try_run_sampling_request(
     let mut needs_follow_up = false;
     let mut last_agent_message: Option<String> = None;
     let mut active_item: Option<TurnItem> = None;
+    // Assistant text streamed for `active_item` so far, kept in case the turn is interrupted.
+    let mut streamed_text = String::new();
     let mut should_emit_turn_diff = false;
     let plan_mode = turn_context.collaboration_mode.mode == ModeKind::Plan;
     let mut plan_mode_state = plan_mode.then(|| PlanModeStreamState::new(&turn_context.sub_id));
+    // The stall watchdog is armed once deltas start flowing for an item and disarmed when the
+    // item is done, so long silent reasoning before the first delta is not a stall.
+    let stall_timeout = turn_context.provider.stream_stall_timeout();
+    let mut streaming_deltas = false;
+    let mut handled_at = Instant::now();
+    let mut first_event_at: Option<Instant> = None;
+    // Open while the response streams, so tool calls dispatched before it completes are
+    // reported as overlapping it.
+    let mut model_stream_window = None;
     let receiving_span = trace_span!("receiving_stream");
     let outcome: CodexResult<SamplingRequestResult> = loop {
         let handle_responses = trace_span!(
@@ -5488,14 +6809,69 @@ async fn try_run_sampling_request(
             from = field::Empty,
         );
 
-        let event = match stream
-            .next()
+        // Only time spent waiting on the stream counts towards a stall; time spent handling
+        // the previous event is local backpressure, not a provider problem.
+        if let Some(stall_timeout) = stall_timeout
+            && handled_at.elapsed() >= stall_timeout
+        {
+            debug!(
+                "handling a model stream event took {:?}; not counting it as a stall",
+                handled_at.elapsed()
+            );
+        }
+        let watchdog = if streaming_deltas {
+            stall_timeout
+        } else {
+            None
+        };
+        let event = match next_response_event(&mut stream, watchdog)
             .instrument(trace_span!(parent: &handle_responses, "receiving"))
             .or_cancel(&cancellation_token)
             .await
         {
-            Ok(event) => event,
-            Err(codex_async_utils::CancelErr::Cancelled) => break Err(CodexErr::TurnAborted),
+            Ok(Some(event)) => event,
+            Ok(None) => {
+                let stall_timeout = watchdog.unwrap_or_default();
+                warn!("model stream stalled: no delta for {stall_timeout:?}");
+                record_interrupted_agent_message(
+                    &sess,
+                    &turn_context,
+                    active_item.take(),
+                    &streamed_text,
+                    STALLED_AGENT_MESSAGE_MARKER,
+                )
+                .await;
+                break Err(CodexErr::StreamStalled(stall_timeout));
+            }
+            Err(codex_async_utils::CancelErr::Cancelled) => {
+                record_interrupted_agent_message(
+                    &sess,
+                    &turn_context,
+                    active_item.take(),
+                    &streamed_text,
+                    INTERRUPTED_AGENT_MESSAGE_MARKER,
+                )
+                .await;
+                break Err(CodexErr::TurnAborted);
+            }
+        };
+        handled_at = Instant::now();
+        if first_event_at.is_none() {
+            first_event_at = Some(handled_at);
+            turn_context.latency.record(
+                LatencyStage::ModelTtfb,
+                handled_at.duration_since(request_started_at),
+            );
+            model_stream_window = Some(turn_context.latency.model_stream(handled_at));
+        }
+        streaming_deltas = match &event {
+            Some(Ok(
+                ResponseEvent::OutputTextDelta(_)
+                | ResponseEvent::ReasoningSummaryDelta { .. }
+                | ResponseEvent::ReasoningContentDelta { .. },
+            )) => true,
+            Some(Ok(ResponseEvent::OutputItemDone(_) | ResponseEvent::Completed { .. })) => false,
+            _ => streaming_deltas,
         };
 
         let event = match event {
@@ -5516,6 +6892,7 @@ async fn try_run_sampling_request(
             ResponseEvent::Created => {}
             ResponseEvent::OutputItemDone(item) => {
                 let previously_active_item = active_item.take();
+                streamed_text.clear();
                 if let Some(state) = plan_mode_state.as_mut() {
                     if let Some(previous) = previously_active_item.as_ref() {
                         let item_id = previous.id();
@@ -5573,6 +6950,7 @@ async fn try_run_sampling_request(
                     } else {
                         sess.emit_turn_item_started(&turn_context, &turn_item).await;
                     }
+                    streamed_text.clear();
                     active_item = Some(turn_item);
                 }
             }
@@ -5584,6 +6962,9 @@ async fn try_run_sampling_request(
                 // token usage is available to avoid duplicate TokenCount events.
                 sess.update_rate_limits(&turn_context, snapshot).await;
             }
+            ResponseEvent::RequestBudget(budget) => {
+                sess.update_request_budget(&turn_context, budget).await;
+            }
             ResponseEvent::ModelsEtag(etag) => {
                 // Update internal state with latest models etag
                 let config = sess.get_config().await;
@@ -5625,6 +7006,9 @@ async fn try_run_sampling_request(
                             .parse(&delta);
                         handle_plan_segments(&sess, &turn_context, state, &item_id, segments).await;
                     } else {
+                        if matches!(active, TurnItem::AgentMessage(_)) {
+                            streamed_text.push_str(&delta);
+                        }
                         let event = AgentMessageContentDeltaEvent {
                             thread_id: sess.conversation_id.to_string(),
                             turn_id: turn_context.sub_id.clone(),
@@ -5688,6 +7072,12 @@ async fn try_run_sampling_request(
             }
         }
     };
+    drop(model_stream_window);
+    if let Some(first_event_at) = first_event_at {
+        turn_context
+            .latency
+            .record_since(LatencyStage::ModelStream, first_event_at);
+    }
 
     drain_in_flight(&mut in_flight, sess.clone(), turn_context.clone()).await?;
 
@@ -6378,6 +7768,7 @@ mod tests {
             collaboration_mode: Some(turn_context.collaboration_mode.clone()),
             effort: turn_context.reasoning_effort,
             summary: turn_context.reasoning_summary,
+            verbosity: turn_context.model_verbosity,
             user_instructions: None,
             developer_instructions: None,
             final_output_json_schema: None,
@@ -6398,6 +7789,44 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn record_initial_history_resumed_reinjects_outstanding_plan() {
+        let (session, _turn_context) = make_session_and_context().await;
+        let plan = vec![
+            PlanItemArg {
+                step: "Read the code".to_string(),
+                status: codex_protocol::plan_tool::StepStatus::Completed,
+                id: Some("step-1".to_string()),
+            },
+            PlanItemArg {
+                step: "Write the fix".to_string(),
+                status: codex_protocol::plan_tool::StepStatus::InProgress,
+                id: Some("step-2".to_string()),
+            },
+        ];
+        let rollout_items = vec![RolloutItem::EventMsg(EventMsg::PlanUpdate(
+            codex_protocol::plan_tool::UpdatePlanArgs {
+                explanation: None,
+                plan: plan.clone(),
+            },
+        ))];
+
+        session
+            .record_initial_history(InitialHistory::Resumed(ResumedHistory {
+                conversation_id: ThreadId::default(),
+                history: rollout_items,
+                rollout_path: PathBuf::from("/tmp/resume.jsonl"),
+            }))
+            .await;
+
+        let expected: ResponseItem =
+            DeveloperInstructions::new(resume_plan_instructions(&plan).expect("unfinished plan"))
+                .into();
+        let state = session.state.lock().await;
+        assert_eq!(state.plan(), plan.as_slice());
+        assert_eq!(state.clone_history().raw_items(), &[expected]);
+    }
+
     #[tokio::test]
     async fn resumed_history_seeds_initial_context_on_first_turn_only() {
         let (session, turn_context) = make_session_and_context().await;
@@ -6597,6 +8026,7 @@ mod tests {
             collaboration_mode: Some(turn_context.collaboration_mode.clone()),
             effort: turn_context.reasoning_effort,
             summary: turn_context.reasoning_summary,
+            verbosity: turn_context.model_verbosity,
             user_instructions: None,
             developer_instructions: None,
             final_output_json_schema: None,
@@ -6682,6 +8112,104 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn approval_requests_record_their_decision() {
+        let (mut sess, mut tc) = make_session_and_context().await;
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let path = dir.path().join("audit.jsonl");
+        sess.services.audit_log = Some(AuditLog::new(path.clone(), sess.conversation_id));
+        let mut config = (*tc.config).clone();
+        config.read_only = true;
+        tc.config = Arc::new(config);
+
+        let decision = sess
+            .request_command_approval(
+                &tc,
+                "call-1".to_string(),
+                "commit",
+                vec!["git".to_string(), "commit".to_string()],
+                tc.cwd.clone(),
+                Some("Commit 1 session file(s)".to_string()),
+                None,
+                None,
+            )
+            .await;
+        assert_eq!(decision, ReviewDecision::Denied);
+        let decision = sess
+            .request_patch_approval(&tc, "call-2".to_string(), HashMap::new(), None, None)
+            .await;
+        assert_eq!(decision, ReviewDecision::Denied);
+
+        let records: Vec<serde_json::Value> = std::fs::read_to_string(&path)
+            .expect("read audit log")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("json"))
+            .collect();
+        let summary: Vec<_> = records
+            .iter()
+            .map(|record| {
+                (
+                    record["call_id"].as_str(),
+                    record["tool"].as_str(),
+                    record["decision"].as_str(),
+                    record["decided_by"].as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    Some("call-1"),
+                    Some("commit"),
+                    Some("denied"),
+                    Some("policy")
+                ),
+                (
+                    Some("call-2"),
+                    Some("apply_patch"),
+                    Some("denied"),
+                    Some("policy")
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn read_only_sessions_reject_workspace_writes() {
+        let (sess, _tc, rx) = make_session_and_context_with_rx().await;
+        {
+            let mut state = sess.state.lock().await;
+            let mut config = (*state.session_configuration.original_config_do_not_use).clone();
+            config.read_only = true;
+            state.session_configuration.original_config_do_not_use = Arc::new(config);
+        }
+
+        handlers::undo(&sess, "undo".to_string()).await;
+        handlers::undo_file_change(&sess, "undo-file".to_string(), Vec::new()).await;
+        handlers::commit_changes(&sess, "commit".to_string(), None).await;
+        handlers::create_pull_request(&sess, "pr".to_string(), None).await;
+
+        for sub_id in ["undo", "undo-file", "commit", "pr"] {
+            let event = tokio::time::timeout(std::time::Duration::from_secs(2), rx.recv())
+                .await
+                .expect("timeout waiting for event")
+                .expect("event");
+            assert_eq!(event.id, sub_id);
+            match event.msg {
+                EventMsg::Error(err) => {
+                    assert!(
+                        err.message
+                            .ends_with("is not allowed in a read-only session")
+                    );
+                    assert_eq!(err.codex_error_info, Some(CodexErrorInfo::BadRequest));
+                }
+                other => panic!("unexpected event: {other:?}"),
+            }
+        }
+        assert!(sess.active_turn.lock().await.is_none());
+    }
+
     #[tokio::test]
     async fn thread_rollback_clears_history_when_num_turns_exceeds_existing_turns() {
         let (sess, tc, rx) = make_session_and_context_with_rx().await;
@@ -6773,6 +8301,7 @@ mod tests {
             provider: config.model_provider.clone(),
             collaboration_mode,
             model_reasoning_summary: config.model_reasoning_summary,
+            model_verbosity: config.model_verbosity,
             developer_instructions: config.developer_instructions.clone(),
             user_instructions: config.user_instructions.clone(),
             personality: config.personality,
@@ -6785,6 +8314,7 @@ mod tests {
             sandbox_policy: config.permissions.sandbox_policy.clone(),
             windows_sandbox_level: WindowsSandboxLevel::from_config(&config),
             cwd: config.cwd.clone(),
+            scope: config.scope.clone(),
             codex_home: config.codex_home.clone(),
             thread_name: None,
             original_config_do_not_use: Arc::clone(&config),
@@ -6864,6 +8394,7 @@ mod tests {
             provider: config.model_provider.clone(),
             collaboration_mode,
             model_reasoning_summary: config.model_reasoning_summary,
+            model_verbosity: config.model_verbosity,
             developer_instructions: config.developer_instructions.clone(),
             user_instructions: config.user_instructions.clone(),
             personality: config.personality,
@@ -6876,6 +8407,7 @@ mod tests {
             sandbox_policy: config.permissions.sandbox_policy.clone(),
             windows_sandbox_level: WindowsSandboxLevel::from_config(&config),
             cwd: config.cwd.clone(),
+            scope: config.scope.clone(),
             codex_home: config.codex_home.clone(),
             thread_name: None,
             original_config_do_not_use: Arc::clone(&config),
@@ -7174,6 +8706,7 @@ mod tests {
             provider: config.model_provider.clone(),
             collaboration_mode,
             model_reasoning_summary: config.model_reasoning_summary,
+            model_verbosity: config.model_verbosity,
             developer_instructions: config.developer_instructions.clone(),
             user_instructions: config.user_instructions.clone(),
             personality: config.personality,
@@ -7186,6 +8719,7 @@ mod tests {
             sandbox_policy: config.permissions.sandbox_policy.clone(),
             windows_sandbox_level: WindowsSandboxLevel::from_config(&config),
             cwd: config.cwd.clone(),
+            scope: config.scope.clone(),
             codex_home: config.codex_home.clone(),
             thread_name: None,
             original_config_do_not_use: Arc::clone(&config),
@@ -7227,6 +8761,7 @@ mod tests {
             provider: config.model_provider.clone(),
             collaboration_mode,
             model_reasoning_summary: config.model_reasoning_summary,
+            model_verbosity: config.model_verbosity,
             developer_instructions: config.developer_instructions.clone(),
             user_instructions: config.user_instructions.clone(),
             personality: config.personality,
@@ -7239,6 +8774,7 @@ mod tests {
             sandbox_policy: config.permissions.sandbox_policy.clone(),
             windows_sandbox_level: WindowsSandboxLevel::from_config(&config),
             cwd: config.cwd.clone(),
+            scope: config.scope.clone(),
             codex_home: config.codex_home.clone(),
             thread_name: None,
             original_config_do_not_use: Arc::clone(&config),
@@ -7290,6 +8826,24 @@ mod tests {
             network_proxy: None,
             network_approval: Arc::clone(&network_approval),
             state_db: None,
+            py_repl: PyReplHandle::default(),
+            browser: BrowserHandle::default(),
+            session_diff_tracker: Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new())),
+            full_outputs: Mutex::new(FullOutputs::default()),
+            project_commands: OnceCell::new(),
+            context_packs: ContextPackCache::default(),
+            read_prefetch: ReadPrefetchCache::default(),
+            patch_backups: Mutex::new(PatchBackups::new(
+                config
+                    .codex_home
+                    .join(PATCH_BACKUPS_DIR)
+                    .join(conversation_id.to_string()),
+            )),
+            disk_quota: DiskQuota::disabled(),
+            usage_quota: None,
+            audit_log: None,
+            security_events: None,
+            turn_journal: None,
             model_client: ModelClient::new(
                 Some(auth_manager.clone()),
                 conversation_id,
@@ -7375,6 +8929,7 @@ mod tests {
             provider: config.model_provider.clone(),
             collaboration_mode,
             model_reasoning_summary: config.model_reasoning_summary,
+            model_verbosity: config.model_verbosity,
             developer_instructions: config.developer_instructions.clone(),
             user_instructions: config.user_instructions.clone(),
             personality: config.personality,
@@ -7387,6 +8942,7 @@ mod tests {
             sandbox_policy: config.permissions.sandbox_policy.clone(),
             windows_sandbox_level: WindowsSandboxLevel::from_config(&config),
             cwd: config.cwd.clone(),
+            scope: config.scope.clone(),
             codex_home: config.codex_home.clone(),
             thread_name: None,
             original_config_do_not_use: Arc::clone(&config),
@@ -7438,6 +8994,24 @@ mod tests {
             network_proxy: None,
             network_approval: Arc::clone(&network_approval),
             state_db: None,
+            py_repl: PyReplHandle::default(),
+            browser: BrowserHandle::default(),
+            session_diff_tracker: Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new())),
+            full_outputs: Mutex::new(FullOutputs::default()),
+            project_commands: OnceCell::new(),
+            context_packs: ContextPackCache::default(),
+            read_prefetch: ReadPrefetchCache::default(),
+            patch_backups: Mutex::new(PatchBackups::new(
+                config
+                    .codex_home
+                    .join(PATCH_BACKUPS_DIR)
+                    .join(conversation_id.to_string()),
+            )),
+            disk_quota: DiskQuota::disabled(),
+            usage_quota: None,
+            audit_log: None,
+            security_events: None,
+            turn_journal: None,
             model_client: ModelClient::new(
                 Some(Arc::clone(&auth_manager)),
                 conversation_id,
@@ -7641,6 +9215,42 @@ mod tests {
         assert!(environment_update.contains("<denied>blocked.example.com</denied>"));
     }
 
+    #[tokio::test]
+    async fn build_settings_update_items_emits_scope_item_for_scope_changes() {
+        let (session, previous_context) = make_session_and_context().await;
+        let previous_context = Arc::new(previous_context);
+        let mut current_context = previous_context
+            .with_model(
+                previous_context.model_info.slug.clone(),
+                &session.services.models_manager,
+            )
+            .await;
+        let scope_dir = AbsolutePathBuf::from_absolute_path(current_context.cwd.join("packages"))
+            .expect("absolute scope dir");
+        current_context.scope = vec![scope_dir];
+
+        let update_items =
+            session.build_settings_update_items(Some(&previous_context), None, &current_context);
+
+        let scope_update = update_items
+            .iter()
+            .find_map(|item| match item {
+                ResponseItem::Message { role, content, .. } if role == "user" => {
+                    let [ContentItem::InputText { text }] = content.as_slice() else {
+                        return None;
+                    };
+                    text.starts_with("<workspace_scope>").then_some(text)
+                }
+                _ => None,
+            })
+            .expect("scope update item should be emitted");
+        assert!(scope_update.contains("<dir>packages</dir>"));
+        assert_eq!(
+            current_context.sandbox_cwd(),
+            current_context.cwd.join("packages")
+        );
+    }
+
     #[derive(Clone, Copy)]
     struct NeverEndingTask {
         kind: TaskKind,
@@ -7704,6 +9314,55 @@ mod tests {
         assert!(rx.try_recv().is_err());
     }
 
+    #[tokio::test]
+    async fn emergency_stop_aborts_the_turn_and_refuses_new_tasks() {
+        let (sess, tc, rx) = make_session_and_context_with_rx().await;
+        let input = vec![UserInput::Text {
+            text: "hello".to_string(),
+            text_elements: Vec::new(),
+        }];
+        sess.spawn_task(
+            Arc::clone(&tc),
+            input.clone(),
+            NeverEndingTask {
+                kind: TaskKind::Regular,
+                listen_to_cancellation_token: false,
+            },
+        )
+        .await;
+
+        sess.emergency_stop("test".to_string()).await;
+
+        async fn next_msg(rx: &async_channel::Receiver<Event>) -> EventMsg {
+            tokio::time::timeout(std::time::Duration::from_secs(2), rx.recv())
+                .await
+                .expect("timeout waiting for event")
+                .expect("event")
+                .msg
+        }
+        assert!(matches!(next_msg(&rx).await, EventMsg::TurnAborted(_)));
+        match next_msg(&rx).await {
+            EventMsg::Error(err) => assert_eq!(err.message, emergency_stop_message("test")),
+            other => panic!("unexpected event: {other:?}"),
+        }
+
+        sess.spawn_task(
+            Arc::clone(&tc),
+            input,
+            NeverEndingTask {
+                kind: TaskKind::Regular,
+                listen_to_cancellation_token: false,
+            },
+        )
+        .await;
+        assert!(matches!(next_msg(&rx).await, EventMsg::Error(_)));
+        match next_msg(&rx).await {
+            EventMsg::TurnAborted(e) => assert_eq!(TurnAbortReason::Interrupted, e.reason),
+            other => panic!("unexpected event: {other:?}"),
+        }
+        assert!(sess.active_turn.lock().await.is_none());
+    }
+
     #[tokio::test]
     async fn abort_gracefully_emits_turn_aborted_only() {
         let (sess, tc, rx) = make_session_and_context_with_rx().await;
@@ -0,0 +1,222 @@
+//! Model-written explanations for command approval prompts
+//! (`[approval_explanations]` in config.toml).
+//!
+//! Before Codex asks the user to approve a command that is not known to be
+//! safe and is either possibly destructive or more than one plain program
+//! invocation, it asks a model for a one-paragraph explanation and a list of
+//! the operations that cannot be undone. The result rides along on the
+//! `ExecApprovalRequest` for clients to render. When the call fails or takes
+//! longer than `timeout_ms`, the prompt is shown without an explanation.
+
+use std::path::Path;
+
+use codex_api::ResponseEvent;
+use codex_protocol::approvals::CommandExplanation;
+use codex_protocol::models::BaseInstructions;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::Value;
+use serde_json::json;
+use tracing::warn;
+
+use crate::Prompt;
+use crate::bash::extract_bash_command;
+use crate::bash::parse_shell_lc_plain_commands;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::is_dangerous_command::command_might_be_dangerous;
+use crate::is_safe_command::is_known_safe_command;
+use crate::parse_command::shlex_join;
+
+const INSTRUCTIONS: &str = "\
+You explain shell commands to someone deciding whether to let a coding agent run them. \
+Write `summary` as one short plain-English paragraph saying what the command does and what \
+it changes, without restating it token by token. List in `destructive_operations` each \
+operation that deletes, overwrites, or otherwise cannot easily be undone, such as removing \
+files, force-pushing, or dropping data, in a few words each; leave it empty when there are \
+none. Do not judge whether the command should be approved.";
+
+#[derive(Deserialize)]
+struct ExplanationOutput {
+    summary: String,
+    destructive_operations: Vec<String>,
+}
+
+/// Explains `command` when explanations are enabled and the command is
+/// complex or risky enough to need one.
+pub(crate) async fn explain_command(
+    sess: &Session,
+    turn_context: &TurnContext,
+    command: &[String],
+    cwd: &Path,
+) -> Option<CommandExplanation> {
+    let config = turn_context.config.approval_explanations.as_ref()?;
+    if !needs_explanation(command) {
+        return None;
+    }
+    let model = config
+        .model
+        .clone()
+        .or_else(|| {
+            turn_context
+                .config
+                .model_routing
+                .as_ref()
+                .map(|routing| routing.fast_model.clone())
+        })
+        .unwrap_or_else(|| turn_context.model_info.slug.clone());
+    match tokio::time::timeout(
+        config.timeout,
+        request_explanation(sess, turn_context, &model, command, cwd),
+    )
+    .await
+    {
+        Ok(Ok(explanation)) => Some(explanation),
+        Ok(Err(err)) => {
+            warn!("failed to explain command for approval: {err}");
+            None
+        }
+        Err(_) => {
+            warn!(
+                "explaining command for approval took longer than {:?}",
+                config.timeout
+            );
+            None
+        }
+    }
+}
+
+/// Commands worth explaining: anything not known to be safe that might be
+/// destructive, or a shell script that is more than one plain command.
+fn needs_explanation(command: &[String]) -> bool {
+    if is_known_safe_command(command) {
+        return false;
+    }
+    if command_might_be_dangerous(command) {
+        return true;
+    }
+    if extract_bash_command(command).is_none() {
+        return false;
+    }
+    parse_shell_lc_plain_commands(command).is_none_or(|commands| commands.len() > 1)
+}
+
+async fn request_explanation(
+    sess: &Session,
+    turn_context: &TurnContext,
+    model: &str,
+    command: &[String],
+    cwd: &Path,
+) -> anyhow::Result<CommandExplanation> {
+    let model_info = sess
+        .services
+        .models_manager
+        .get_model_info(model, &turn_context.config)
+        .await;
+    let text = format!(
+        "Working directory: {}\nCommand: {}",
+        cwd.display(),
+        shlex_join(command)
+    );
+    let prompt = Prompt {
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText { text }],
+            end_turn: None,
+            phase: None,
+        }],
+        tools: Vec::new(),
+        parallel_tool_calls: false,
+        base_instructions: BaseInstructions {
+            text: INSTRUCTIONS.to_string(),
+        },
+        personality: None,
+        output_schema: Some(output_schema()),
+    };
+
+    let mut client_session = sess.services.model_client.new_session();
+    let mut stream = client_session
+        .stream(
+            &prompt,
+            &model_info,
+            &turn_context.otel_manager,
+            model_info.default_reasoning_level,
+            turn_context.reasoning_summary,
+            turn_context
+                .turn_metadata_state
+                .current_header_value()
+                .as_deref(),
+        )
+        .await?;
+
+    let mut result = String::new();
+    while let Some(event) = stream.next().await.transpose()? {
+        match event {
+            ResponseEvent::OutputTextDelta(delta) => result.push_str(&delta),
+            ResponseEvent::OutputItemDone(item) => {
+                if result.is_empty()
+                    && let ResponseItem::Message { content, .. } = item
+                    && let Some(text) = crate::compact::content_items_to_text(&content)
+                {
+                    result.push_str(&text);
+                }
+            }
+            ResponseEvent::Completed { .. } => break,
+            _ => {}
+        }
+    }
+
+    let output: ExplanationOutput = serde_json::from_str(&result)?;
+    Ok(CommandExplanation {
+        summary: output.summary.trim().to_string(),
+        destructive_operations: output
+            .destructive_operations
+            .into_iter()
+            .map(|operation| operation.trim().to_string())
+            .filter(|operation| !operation.is_empty())
+            .collect(),
+    })
+}
+
+fn output_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "summary": { "type": "string" },
+            "destructive_operations": {
+                "type": "array",
+                "items": { "type": "string" }
+            }
+        },
+        "required": ["summary", "destructive_operations"],
+        "additionalProperties": false
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shell(script: &str) -> Vec<String> {
+        vec!["bash".to_string(), "-lc".to_string(), script.to_string()]
+    }
+
+    #[test]
+    fn explains_risky_or_compound_commands_only() {
+        assert!(!needs_explanation(&shell("ls -la")));
+        assert!(!needs_explanation(&[
+            "cargo".to_string(),
+            "build".to_string()
+        ]));
+        assert!(!needs_explanation(&shell("cargo build")));
+
+        assert!(needs_explanation(&shell("rm -rf target")));
+        assert!(needs_explanation(&shell("cargo build && ./deploy.sh")));
+        assert!(needs_explanation(&shell(
+            "for f in *.log; do gzip \"$f\"; done"
+        )));
+    }
+}
@@ -2,16 +2,21 @@ use std::sync::Arc;
 
 use crate::ModelProviderInfo;
 use crate::Prompt;
+use crate::WireApi;
 use crate::client::ModelClientSession;
 use crate::client_common::ResponseEvent;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::codex::get_last_assistant_message_from_turn;
+use crate::config::types::ResumeSummaryConfig;
 use crate::context_manager::ContextManager;
+use crate::context_manager::estimate_item_token_count;
+use crate::context_manager::is_user_turn_boundary;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 use crate::protocol::CompactedItem;
 use crate::protocol::EventMsg;
+use crate::protocol::ResumeSummarizedEvent;
 use crate::protocol::TurnStartedEvent;
 use crate::protocol::WarningEvent;
 use crate::truncate::TruncationPolicy;
@@ -25,6 +30,7 @@ use codex_protocol::models::ResponseInputItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::RolloutItem;
 use codex_protocol::user_input::UserInput;
+use codex_utils_tokenizer::Tokenizer;
 use futures::prelude::*;
 use tracing::error;
 
@@ -33,7 +39,7 @@ pub const SUMMARY_PREFIX: &str = include_str!("../templates/compact/summary_pref
 const COMPACT_USER_MESSAGE_MAX_TOKENS: usize = 20_000;
 
 pub(crate) fn should_use_remote_compact_task(provider: &ModelProviderInfo) -> bool {
-    provider.is_openai()
+    provider.is_openai() && provider.wire_api == WireApi::Responses
 }
 
 pub(crate) fn extract_trailing_model_switch_update_for_compaction_request(
@@ -110,14 +116,6 @@ async fn run_compact_task_inner(
         turn_context.truncation_policy,
     );
 
-    let mut truncated_count = 0usize;
-
-    let max_retries = turn_context.provider.stream_max_retries();
-    let mut retries = 0;
-    let mut client_session = sess.services.model_client.new_session();
-    // Reuse one client session so turn-scoped state (sticky routing, websocket append tracking)
-    // survives retries within this compact turn.
-
     // TODO: If we need to guarantee the persisted mode always matches the prompt used for this
     // turn, capture it in TurnContext at creation time. Using SessionConfiguration here avoids
     // duplicating model settings on TurnContext, but an Op after turn start could update the
@@ -127,6 +125,174 @@ async fn run_compact_task_inner(
         RolloutItem::TurnContext(turn_context.to_turn_context_item(collaboration_mode));
     sess.persist_rollout_items(&[rollout_item]).await;
 
+    stream_until_completed(&sess, &turn_context, history).await?;
+
+    let history_snapshot = sess.clone_history().await;
+    let history_items = history_snapshot.raw_items();
+    let summary_suffix = get_last_assistant_message_from_turn(history_items).unwrap_or_default();
+    let summary_text = format!("{SUMMARY_PREFIX}\n{summary_suffix}");
+    let user_messages = collect_user_messages(history_items);
+
+    let initial_context = sess.build_initial_context(turn_context.as_ref()).await;
+    let mut new_history = build_compacted_history(initial_context, &user_messages, &summary_text);
+    // Reattach the stripped model-switch update only after successful compaction so the model
+    // still sees the switch instructions on the next real sampling request.
+    if let Some(model_switch_item) = stripped_model_switch_item {
+        new_history.push(model_switch_item);
+    }
+    let ghost_snapshots: Vec<ResponseItem> = history_items
+        .iter()
+        .filter(|item| matches!(item, ResponseItem::GhostSnapshot { .. }))
+        .cloned()
+        .collect();
+    new_history.extend(ghost_snapshots);
+    sess.replace_history(new_history).await;
+    sess.recompute_token_usage(&turn_context).await;
+
+    let rollout_item = RolloutItem::Compacted(CompactedItem {
+        message: summary_text.clone(),
+        replacement_history: None,
+    });
+    sess.persist_rollout_items(&[rollout_item]).await;
+
+    sess.emit_turn_item_completed(&turn_context, compaction_item)
+        .await;
+    let warning = EventMsg::Warning(WarningEvent {
+        message: "Heads up: Long threads and multiple compactions can cause the model to be less accurate. Start a new thread when possible to keep threads small and targeted.".to_string(),
+    });
+    sess.send_event(&turn_context, warning).await;
+    Ok(())
+}
+
+/// Replaces the turns before the last `keep_turns` of a resumed session with a
+/// summary (`[resume_summary]`), so the first request after resuming does not
+/// replay the whole thread. The new history is persisted as a compaction with
+/// its replacement history: resuming again reuses the summary instead of
+/// generating another one.
+pub(crate) async fn run_resume_summary_task(
+    sess: Arc<Session>,
+    turn_context: Arc<TurnContext>,
+    config: ResumeSummaryConfig,
+) -> CodexResult<()> {
+    let history = sess.clone_history().await;
+    let items = history.raw_items().to_vec();
+    let Some(split) = resume_summary_split(&items, &config, history.tokenizer()) else {
+        return Ok(());
+    };
+    let (older, recent) = items.split_at(split);
+    let summarized_turns = older.iter().filter(|item| is_turn_start(item)).count();
+
+    let prompt_item: ResponseInputItem = ResponseInputItem::from(vec![UserInput::Text {
+        text: turn_context.compact_prompt().to_string(),
+        text_elements: Vec::new(),
+    }]);
+    let mut request = ContextManager::new();
+    request.record_items(older, turn_context.truncation_policy);
+    request.record_items(&[prompt_item.into()], turn_context.truncation_policy);
+    if let Err(err) = stream_until_completed(&sess, &turn_context, request).await {
+        sess.replace_history(items).await;
+        return Err(err);
+    }
+
+    // The response was appended to the session history; only look at it, not
+    // at the assistant messages of the kept turns.
+    let summary = {
+        let history = sess.clone_history().await;
+        history
+            .raw_items()
+            .get(items.len()..)
+            .and_then(get_last_assistant_message_from_turn)
+            .unwrap_or_default()
+    };
+    if summary.trim().is_empty() {
+        sess.replace_history(items).await;
+        return Ok(());
+    }
+    let summary_text = format!("{SUMMARY_PREFIX}\n{summary}");
+
+    let mut new_history: Vec<ResponseItem> = older
+        .iter()
+        .filter(|item| matches!(item, ResponseItem::GhostSnapshot { .. }))
+        .cloned()
+        .collect();
+    new_history.push(ResponseItem::Message {
+        id: None,
+        role: "user".to_string(),
+        content: vec![ContentItem::InputText {
+            text: summary_text.clone(),
+        }],
+        end_turn: None,
+        phase: None,
+    });
+    new_history.extend_from_slice(recent);
+    sess.replace_history(new_history.clone()).await;
+    sess.recompute_token_usage(&turn_context).await;
+
+    let rollout_item = RolloutItem::Compacted(CompactedItem {
+        message: summary_text,
+        replacement_history: Some(new_history),
+    });
+    sess.persist_rollout_items(&[rollout_item]).await;
+
+    let event = EventMsg::ResumeSummarized(ResumeSummarizedEvent {
+        summary,
+        summarized_turns: u32::try_from(summarized_turns).unwrap_or(u32::MAX),
+        kept_turns: u32::try_from(config.keep_turns).unwrap_or(u32::MAX),
+    });
+    sess.send_event(&turn_context, event).await;
+    Ok(())
+}
+
+/// Index of the first item kept verbatim: the start of the `keep_turns`-th
+/// most recent user turn. `None` when there are no older turns to summarize
+/// or they hold fewer than `min_tokens` tokens. An earlier summary does not
+/// count as a turn.
+fn resume_summary_split(
+    items: &[ResponseItem],
+    config: &ResumeSummaryConfig,
+    tokenizer: Option<&Tokenizer>,
+) -> Option<usize> {
+    let turn_starts: Vec<usize> = items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| is_turn_start(item))
+        .map(|(index, _)| index)
+        .collect();
+    if turn_starts.len() <= config.keep_turns {
+        return None;
+    }
+    let split = turn_starts[turn_starts.len() - config.keep_turns];
+    let older_tokens = items[..split]
+        .iter()
+        .map(|item| estimate_item_token_count(item, tokenizer))
+        .fold(0i64, i64::saturating_add);
+    (older_tokens >= config.min_tokens).then_some(split)
+}
+
+fn is_turn_start(item: &ResponseItem) -> bool {
+    is_user_turn_boundary(item)
+        && !matches!(
+            crate::event_mapping::parse_turn_item(item),
+            Some(TurnItem::UserMessage(user)) if is_summary_message(&user.message())
+        )
+}
+
+/// Streams `history` to the model until the response completes, dropping
+/// the oldest items while the request does not fit the context window. The
+/// response items are recorded into the session history.
+async fn stream_until_completed(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    mut history: ContextManager,
+) -> CodexResult<()> {
+    let mut truncated_count = 0usize;
+
+    let max_retries = turn_context.provider.stream_max_retries();
+    let mut retries = 0;
+    let mut client_session = sess.services.model_client.new_session();
+    // Reuse one client session so turn-scoped state (sticky routing, websocket append tracking)
+    // survives retries within this compact turn.
+
     loop {
         // Clone is required because of the loop
         let turn_input = history
@@ -141,7 +307,7 @@ async fn run_compact_task_inner(
         };
         let turn_metadata_header = turn_context.turn_metadata_state.current_header_value();
         let attempt_result = drain_to_completed(
-            &sess,
+            sess,
             turn_context.as_ref(),
             &mut client_session,
             turn_metadata_header.as_deref(),
@@ -160,7 +326,7 @@ async fn run_compact_task_inner(
                     )
                     .await;
                 }
-                break;
+                return Ok(());
             }
             Err(CodexErr::Interrupted) => {
                 return Err(CodexErr::Interrupted);
@@ -178,7 +344,7 @@ async fn run_compact_task_inner(
                 }
                 sess.set_total_tokens_full(turn_context.as_ref()).await;
                 let event = EventMsg::Error(e.to_error_event(None));
-                sess.send_event(&turn_context, event).await;
+                sess.send_event(turn_context, event).await;
                 return Err(e);
             }
             Err(e) => {
@@ -195,48 +361,12 @@ async fn run_compact_task_inner(
                     continue;
                 } else {
                     let event = EventMsg::Error(e.to_error_event(None));
-                    sess.send_event(&turn_context, event).await;
+                    sess.send_event(turn_context, event).await;
                     return Err(e);
                 }
             }
         }
     }
-
-    let history_snapshot = sess.clone_history().await;
-    let history_items = history_snapshot.raw_items();
-    let summary_suffix = get_last_assistant_message_from_turn(history_items).unwrap_or_default();
-    let summary_text = format!("{SUMMARY_PREFIX}\n{summary_suffix}");
-    let user_messages = collect_user_messages(history_items);
-
-    let initial_context = sess.build_initial_context(turn_context.as_ref()).await;
-    let mut new_history = build_compacted_history(initial_context, &user_messages, &summary_text);
-    // Reattach the stripped model-switch update only after successful compaction so the model
-    // still sees the switch instructions on the next real sampling request.
-    if let Some(model_switch_item) = stripped_model_switch_item {
-        new_history.push(model_switch_item);
-    }
-    let ghost_snapshots: Vec<ResponseItem> = history_items
-        .iter()
-        .filter(|item| matches!(item, ResponseItem::GhostSnapshot { .. }))
-        .cloned()
-        .collect();
-    new_history.extend(ghost_snapshots);
-    sess.replace_history(new_history).await;
-    sess.recompute_token_usage(&turn_context).await;
-
-    let rollout_item = RolloutItem::Compacted(CompactedItem {
-        message: summary_text.clone(),
-        replacement_history: None,
-    });
-    sess.persist_rollout_items(&[rollout_item]).await;
-
-    sess.emit_turn_item_completed(&turn_context, compaction_item)
-        .await;
-    let warning = EventMsg::Warning(WarningEvent {
-        message: "Heads up: Long threads and multiple compactions can cause the model to be less accurate. Start a new thread when possible to keep threads small and targeted.".to_string(),
-    });
-    sess.send_event(&turn_context, warning).await;
-    Ok(())
 }
 
 pub fn content_items_to_text(content: &[ContentItem]) -> Option<String> {
@@ -431,6 +561,9 @@ async fn drain_to_completed(
             Ok(ResponseEvent::RateLimits(snapshot)) => {
                 sess.update_rate_limits(turn_context, snapshot).await;
             }
+            Ok(ResponseEvent::RequestBudget(budget)) => {
+                sess.update_request_budget(turn_context, budget).await;
+            }
             Ok(ResponseEvent::Completed { token_usage, .. }) => {
                 sess.update_token_usage_info(turn_context, token_usage.as_ref())
                     .await;
@@ -579,6 +712,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn resume_summary_split_keeps_recent_turns_and_skips_earlier_summary() {
+        let message = |role: &str, text: &str| ResponseItem::Message {
+            id: None,
+            role: role.to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        };
+        let items = vec![
+            message("user", &format!("{SUMMARY_PREFIX}\nearlier work")),
+            message("user", "first"),
+            message("assistant", &"long answer ".repeat(100)),
+            message("user", "second"),
+            message("assistant", "ok"),
+            message("user", "third"),
+            message("assistant", "ok"),
+        ];
+        let config = ResumeSummaryConfig {
+            keep_turns: 2,
+            min_tokens: 100,
+        };
+
+        assert_eq!(resume_summary_split(&items, &config, None), Some(3));
+        assert_eq!(
+            resume_summary_split(
+                &items,
+                &ResumeSummaryConfig {
+                    keep_turns: 3,
+                    ..config
+                },
+                None
+            ),
+            None
+        );
+        assert_eq!(
+            resume_summary_split(
+                &items,
+                &ResumeSummaryConfig {
+                    min_tokens: 10_000,
+                    ..config
+                },
+                None
+            ),
+            None
+        );
+    }
+
     #[test]
     fn collect_user_messages_extracts_user_text_only() {
         let items = vec![
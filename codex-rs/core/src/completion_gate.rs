@@ -0,0 +1,185 @@
+//! Checks that must pass before a turn that changed files is done, from
+//! `[completion_gate]` in config.toml.
+//!
+//! When the model ends a turn that edited files, the configured commands run
+//! in order under the turn's sandbox. The first failure is sent back to the
+//! model as a follow-up, up to `max_retries` times; after that the turn ends
+//! with a warning that the gate is still failing.
+
+use std::time::Duration;
+
+use codex_async_utils::OrCancelExt;
+use codex_protocol::models::DeveloperInstructions;
+use codex_protocol::models::ResponseItem;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::error::CodexErr;
+use crate::error::SandboxErr;
+use crate::exec::ExecExpiration;
+use crate::exec::ExecParams;
+use crate::exec::ExecToolCallOutput;
+use crate::exec::StdoutStream;
+use crate::exec::StreamOutput;
+use crate::exec::process_exec_tool_call;
+use crate::exec_env::create_env;
+use crate::features::Feature;
+use crate::parse_command::parse_command;
+use crate::protocol::EventMsg;
+use crate::protocol::ExecCommandBeginEvent;
+use crate::protocol::ExecCommandEndEvent;
+use crate::protocol::ExecCommandSource;
+use crate::protocol::ExecCommandStatus;
+use crate::sandboxing::SandboxPermissions;
+use crate::test_failures::exec_test_failures;
+use crate::tools::format_exec_output_str;
+
+/// A gate command that did not pass.
+pub(crate) struct GateFailure {
+    pub(crate) command: String,
+    output: String,
+}
+
+impl GateFailure {
+    /// Developer message asking the model to fix the failure.
+    pub(crate) fn follow_up(&self, retries_left: u32) -> ResponseItem {
+        DeveloperInstructions::new(format!(
+            "You are not done yet: the completion check `{}` failed.\n\n{}\n\nFix the problem, then finish again. The checks will run again when you do ({retries_left} more attempts before the turn ends anyway).",
+            self.command, self.output
+        ))
+        .into()
+    }
+}
+
+/// Runs the gate commands in order and returns the first failure, or `None`
+/// when they all pass or the turn was interrupted.
+pub(crate) async fn run_completion_gate(
+    sess: &Session,
+    turn_context: &TurnContext,
+    cancellation_token: &CancellationToken,
+) -> Option<GateFailure> {
+    let gate = &turn_context.config.completion_gate;
+    for command in &gate.commands {
+        let output = run_gate_command(
+            sess,
+            turn_context,
+            command,
+            gate.timeout,
+            cancellation_token,
+        )
+        .await?;
+        if output.exit_code != 0 {
+            return Some(GateFailure {
+                command: command.clone(),
+                output: format_exec_output_str(&output, turn_context.truncation_policy),
+            });
+        }
+    }
+    None
+}
+
+async fn run_gate_command(
+    sess: &Session,
+    turn_context: &TurnContext,
+    command: &str,
+    timeout: Duration,
+    cancellation_token: &CancellationToken,
+) -> Option<ExecToolCallOutput> {
+    let display_command = sess.user_shell().derive_exec_args(command, true);
+    let parsed_cmd = parse_command(&display_command);
+    let call_id = Uuid::new_v4().to_string();
+    let cwd = turn_context.cwd.clone();
+    sess.send_event(
+        turn_context,
+        EventMsg::ExecCommandBegin(ExecCommandBeginEvent {
+            call_id: call_id.clone(),
+            process_id: None,
+            turn_id: turn_context.sub_id.clone(),
+            command: display_command.clone(),
+            cwd: cwd.clone(),
+            parsed_cmd: parsed_cmd.clone(),
+            source: ExecCommandSource::UserShell,
+            interaction_input: None,
+        }),
+    )
+    .await;
+
+    let params = ExecParams {
+        command: display_command.clone(),
+        cwd: cwd.clone(),
+        expiration: ExecExpiration::Timeout(timeout),
+        env: create_env(
+            &turn_context.shell_environment_policy,
+            Some(sess.conversation_id),
+        ),
+        network: turn_context.network.clone(),
+        network_attempt_id: None,
+        sandbox_permissions: SandboxPermissions::UseDefault,
+        windows_sandbox_level: turn_context.windows_sandbox_level,
+        justification: None,
+        arg0: None,
+    };
+    let stdout_stream = Some(StdoutStream {
+        sub_id: turn_context.sub_id.clone(),
+        call_id: call_id.clone(),
+        tx_event: sess.get_tx_event(),
+    });
+    let result = process_exec_tool_call(
+        params,
+        &turn_context.sandbox_policy,
+        turn_context.sandbox_cwd(),
+        &turn_context.codex_linux_sandbox_exe,
+        turn_context.features.enabled(Feature::UseLinuxSandboxBwrap),
+        stdout_stream,
+    )
+    .or_cancel(cancellation_token)
+    .await;
+    let output = match result {
+        Err(_) => return None,
+        Ok(Ok(output)) => output,
+        Ok(Err(CodexErr::Sandbox(
+            SandboxErr::Timeout { output } | SandboxErr::Denied { output, .. },
+        ))) => *output,
+        Ok(Err(err)) => {
+            let message = format!("execution error: {err}");
+            ExecToolCallOutput {
+                exit_code: -1,
+                stdout: StreamOutput::new(String::new()),
+                stderr: StreamOutput::new(message.clone()),
+                aggregated_output: StreamOutput::new(message),
+                duration: Duration::ZERO,
+                timed_out: false,
+            }
+        }
+    };
+
+    sess.send_event(
+        turn_context,
+        EventMsg::ExecCommandEnd(ExecCommandEndEvent {
+            call_id,
+            process_id: None,
+            turn_id: turn_context.sub_id.clone(),
+            command: display_command,
+            cwd,
+            parsed_cmd,
+            source: ExecCommandSource::UserShell,
+            interaction_input: None,
+            stdout: output.stdout.text.clone(),
+            stderr: output.stderr.text.clone(),
+            aggregated_output: output.aggregated_output.text.clone(),
+            exit_code: output.exit_code,
+            duration: output.duration,
+            formatted_output: format_exec_output_str(&output, turn_context.truncation_policy),
+            status: if output.exit_code == 0 {
+                ExecCommandStatus::Completed
+            } else {
+                ExecCommandStatus::Failed
+            },
+            test_failures: exec_test_failures(&output),
+        }),
+    )
+    .await;
+    Some(output)
+}
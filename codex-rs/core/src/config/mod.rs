@@ -1,26 +1,64 @@
 use crate::auth::AuthCredentialsStoreMode;
+use crate::auth::credential_home;
+use crate::auth::validate_credential_name;
 use crate::config::edit::ConfigEdit;
 use crate::config::edit::ConfigEditsBuilder;
+use crate::config::types::AgentRoleConfig;
+use crate::config::types::AgentRoleToml;
+use crate::config::types::ApprovalExplanationsConfig;
+use crate::config::types::ApprovalExplanationsToml;
+use crate::config::types::ApprovalsBridgeConfig;
+use crate::config::types::ApprovalsBridgeToml;
 use crate::config::types::AppsConfigToml;
+use crate::config::types::CommitConfig;
+use crate::config::types::CommitToml;
+use crate::config::types::CompletionGateConfig;
+use crate::config::types::CompletionGateToml;
+use crate::config::types::ContextPackConfig;
+use crate::config::types::ContextPackToml;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
+use crate::config::types::ExecOutputEscapes;
+use crate::config::types::ForgeHostToml;
 use crate::config::types::History;
+use crate::config::types::HttpClientConfig;
+use crate::config::types::MacOsSeatbeltToml;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerDisabledReason;
 use crate::config::types::McpServerTransportConfig;
 use crate::config::types::MemoriesConfig;
 use crate::config::types::MemoriesToml;
+use crate::config::types::ModelRoutingConfig;
+use crate::config::types::ModelRoutingToml;
 use crate::config::types::Notice;
 use crate::config::types::NotificationMethod;
 use crate::config::types::Notifications;
+use crate::config::types::OrchestrationConfig;
+use crate::config::types::OrchestrationToml;
 use crate::config::types::OtelConfig;
 use crate::config::types::OtelConfigToml;
 use crate::config::types::OtelExporterKind;
+use crate::config::types::ReleaseServer;
+use crate::config::types::ResponseCacheConfig;
+use crate::config::types::ResponseCacheMode;
+use crate::config::types::ResumeSummaryConfig;
+use crate::config::types::ResumeSummaryToml;
+use crate::config::types::RetryBudgetConfig;
+use crate::config::types::RetryBudgetToml;
+use crate::config::types::RolloutCompression;
 use crate::config::types::SandboxWorkspaceWrite;
+use crate::config::types::SessionStorage;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
 use crate::config::types::SkillsConfig;
 use crate::config::types::Tui;
+use crate::config::types::TurnRouting;
+use crate::config::types::UpdateChannel;
+use crate::config::types::UpdatesConfig;
+use crate::config::types::UpdatesToml;
 use crate::config::types::UriBasedFileOpener;
+use crate::config::types::UsageQuotaConfig;
+use crate::config::types::WebSearchProviderConfig;
+use crate::config::types::WebSearchProviderToml;
 use crate::config::types::WindowsSandboxModeToml;
 use crate::config::types::WindowsToml;
 use crate::config_loader::CloudRequirementsLoader;
@@ -32,7 +70,10 @@ use crate::config_loader::McpServerIdentity;
 use crate::config_loader::McpServerRequirement;
 use crate::config_loader::ResidencyRequirement;
 use crate::config_loader::Sourced;
+use crate::config_loader::default_project_root_markers;
+use crate::config_loader::find_project_root_blocking;
 use crate::config_loader::load_config_layers_state;
+use crate::default_client::validate_http_client_config;
 use crate::features::Feature;
 use crate::features::FeatureOverrides;
 use crate::features::Features;
@@ -49,10 +90,16 @@ use crate::project_doc::LOCAL_PROJECT_DOC_FILENAME;
 use crate::protocol::AskForApproval;
 use crate::protocol::ReadOnlyAccess;
 use crate::protocol::SandboxPolicy;
+use crate::rollout::SESSIONS_SUBDIR;
+use crate::run_limits::RunLimits;
+use crate::scope::resolve_scope;
 #[cfg(target_os = "macos")]
 use crate::seatbelt_permissions::MacOsSeatbeltProfileExtensions;
+use crate::seatbelt_profile::SeatbeltProfileAdditions;
 use crate::windows_sandbox::WindowsSandboxLevelExt;
 use crate::windows_sandbox::resolve_windows_sandbox_mode;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use codex_app_server_protocol::Tools;
 use codex_app_server_protocol::UserSavedConfig;
 use codex_protocol::config_types::AltScreenMode;
@@ -69,6 +116,7 @@ use codex_protocol::openai_models::ReasoningEffort;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use codex_utils_absolute_path::AbsolutePathBufGuard;
+use encoding_rs::Encoding;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
@@ -78,6 +126,7 @@ use std::collections::HashMap;
 use std::io::ErrorKind;
 use std::path::Path;
 use std::path::PathBuf;
+use std::time::Duration;
 #[cfg(test)]
 use tempfile::tempdir;
 #[cfg(not(target_os = "macos"))]
@@ -89,7 +138,9 @@ use toml_edit::DocumentMut;
 
 pub mod edit;
 mod network_proxy_spec;
+pub(crate) mod offline;
 pub mod profile;
+mod read_only;
 pub mod schema;
 pub mod service;
 pub mod types;
@@ -99,6 +150,7 @@ pub use codex_config::ConstraintResult;
 
 pub use network_proxy_spec::NetworkProxySpec;
 pub use network_proxy_spec::StartedNetworkProxy;
+pub use offline::is_loopback_url;
 pub use service::ConfigService;
 pub use service::ConfigServiceError;
 
@@ -109,6 +161,8 @@ pub use codex_git::GhostSnapshotConfig;
 /// the context window.
 pub(crate) const PROJECT_DOC_MAX_BYTES: usize = 32 * 1024; // 32 KiB
 pub(crate) const DEFAULT_AGENT_MAX_THREADS: Option<usize> = Some(6);
+const DEFAULT_MAX_FAST_INPUT_CHARS: usize = 300;
+const DEFAULT_APPROVAL_EXPLANATION_TIMEOUT_MS: u64 = 10_000;
 
 pub const CONFIG_TOML_FILE: &str = "config.toml";
 
@@ -255,17 +309,41 @@ pub struct Config {
     /// Ordered list of status line item identifiers for the TUI.
     pub tui_status_line: Option<Vec<String>>,
 
+    /// Status line items to keep longest on narrow terminals, most important
+    /// first.
+    pub tui_status_line_priority: Option<Vec<String>>,
+
+    /// Render the TUI for screen readers (`tui.screen_reader` or `--screen-reader`).
+    pub tui_screen_reader: bool,
+
+    /// Capture the mouse in the TUI's full-screen views (`tui.mouse`).
+    pub tui_mouse: bool,
+
+    /// Hyperlink URLs and file references in the TUI transcript (`tui.hyperlinks`).
+    pub tui_hyperlinks: bool,
+
     /// The directory that should be treated as the current working directory
     /// for the session. All relative paths inside the business-logic layer are
     /// resolved against this path.
     pub cwd: PathBuf,
 
+    /// Sub-directories of `cwd` the session is narrowed to (`--scope`). Empty
+    /// means the whole working directory is in scope.
+    pub scope: Vec<AbsolutePathBuf>,
+
     /// Preferred store for CLI auth credentials.
     /// file (default): Use a file in the Codex home directory.
     /// keyring: Use an OS-specific keyring service.
     /// auto: Use the OS-specific keyring service if available, otherwise use a file.
     pub cli_auth_credentials_store_mode: AuthCredentialsStoreMode,
 
+    /// Named credential selected by the active profile or `credential`, if any.
+    pub credential: Option<String>,
+
+    /// Directory whose auth store holds the login in use: `codex_home`, or
+    /// `codex_home/credentials/<name>` when a named credential is selected.
+    pub auth_home: PathBuf,
+
     /// Definition for MCP servers that Codex can reach out to for tool calls.
     pub mcp_servers: Constrained<HashMap<String, McpServerConfig>>,
 
@@ -301,6 +379,44 @@ pub struct Config {
     /// Memories subsystem settings.
     pub memories: MemoriesConfig,
 
+    /// Search backend for the provider-backed `web_search` tool. When set and
+    /// the web search mode is `live`, it replaces the hosted web search tool.
+    pub web_search_provider: Option<WebSearchProviderConfig>,
+
+    /// Settings for commits created via `Op::CommitChanges`.
+    pub commit: CommitConfig,
+
+    /// Self-hosted forges pull requests can be opened on, keyed by lowercase hostname.
+    pub forge_hosts: HashMap<String, ForgeHostToml>,
+
+    /// Checks run when the model finishes a turn that changed files.
+    pub completion_gate: CompletionGateConfig,
+
+    /// Context packs from `[context_packs]`, sorted by name.
+    pub context_packs: Vec<ContextPackConfig>,
+
+    /// Summarization of older turns on resume; `None` replays the whole
+    /// history.
+    pub resume_summary: Option<ResumeSummaryConfig>,
+
+    /// Chat channel that answers approval requests of
+    /// `codex exec --approvals-bridge`; `None` without `[approvals_bridge]`.
+    pub approvals_bridge: Option<ApprovalsBridgeConfig>,
+
+    /// What a turn does when model requests keep failing after their retries.
+    pub retry_budget: RetryBudgetConfig,
+
+    /// Daily and weekly limits of the active profile from
+    /// `[profiles.<name>.quota]`; `None` without a profile or limits.
+    pub usage_quota: Option<UsageQuotaConfig>,
+
+    /// Orchestration selected via `orchestration` or `--orchestration`.
+    pub orchestration: Option<OrchestrationConfig>,
+
+    /// Agent role this session runs as. Its instructions are added to the
+    /// initial context and its tool allow-list limits the available tools.
+    pub agent_role: Option<AgentRoleConfig>,
+
     /// Directory containing all Codex state (defaults to `~/.codex` but can be
     /// overridden by the `CODEX_HOME` environment variable).
     pub codex_home: PathBuf,
@@ -311,6 +427,31 @@ pub struct Config {
     /// Settings that govern if and what will be written to `~/.codex/history.jsonl`.
     pub history: History,
 
+    /// Audit log file every session appends to, or `None` when auditing is off.
+    pub audit_log_path: Option<PathBuf>,
+
+    /// Where approvals, denials, sandbox violations and escalations are
+    /// forwarded, or `None` when forwarding is off.
+    pub security_events: Option<crate::config::types::SecurityEventsConfig>,
+
+    /// Model response cache, or `None` when responses are not cached.
+    pub response_cache: Option<ResponseCacheConfig>,
+
+    /// How new rollout files are stored on disk.
+    pub rollout_compression: RolloutCompression,
+
+    /// Whether new rollout files are encrypted with the keyring-held data key.
+    pub encrypt_rollouts: bool,
+
+    /// Project-local sessions directory (`<project root>/.codex/sessions`)
+    /// new rollouts are written to, or `None` to write them under
+    /// `~/.codex/sessions`.
+    pub project_sessions_dir: Option<PathBuf>,
+
+    /// Whether the project sessions directory is left visible to git instead
+    /// of being ignored with its own `.gitignore`.
+    pub track_project_sessions: bool,
+
     /// When true, session is not persisted on disk. Default to `false`
     pub ephemeral: bool,
 
@@ -343,6 +484,13 @@ pub struct Config {
     /// Optional verbosity control for GPT-5 models (Responses API `text.verbosity`).
     pub model_verbosity: Option<Verbosity>,
 
+    /// Runs trivial turns on a cheaper model, or `None` when every turn uses `model`.
+    pub model_routing: Option<ModelRoutingConfig>,
+
+    /// Explains complex or risky commands on their approval prompts, or
+    /// `None` when prompts show only the command.
+    pub approval_explanations: Option<ApprovalExplanationsConfig>,
+
     /// Base URL for requests to ChatGPT (as opposed to the OpenAI API).
     pub chatgpt_base_url: String,
 
@@ -390,6 +538,57 @@ pub struct Config {
     /// Defaults to `true`.
     pub check_for_update_on_startup: bool,
 
+    /// Release channel and optional self-hosted release server for updates.
+    pub updates: UpdatesConfig,
+
+    /// When `true`, the Codex process only talks to services on this machine.
+    pub offline: bool,
+
+    /// Proxy and TLS settings for Codex's own HTTP requests.
+    pub http_client: HttpClientConfig,
+
+    /// Encoding that command output is decoded from when it is not UTF-8.
+    /// Unset means the encoding is detected.
+    pub exec_output_encoding: Option<&'static Encoding>,
+
+    /// How terminal control sequences in command output are handled.
+    pub exec_output_escapes: ExecOutputEscapes,
+
+    /// Rules from `[macos_seatbelt]` appended to the builtin Seatbelt profile.
+    pub macos_seatbelt: SeatbeltProfileAdditions,
+
+    /// Device nodes from `sandbox_workspace_write.allowed_devices` that
+    /// workspace-write commands may open on Linux.
+    pub sandbox_allowed_devices: Vec<String>,
+
+    /// MiB that files inside the writable roots may grow by during a session
+    /// before the next tool call needs approval. `None` disables the quota.
+    pub session_disk_quota_mb: Option<u64>,
+
+    /// When `true`, the detected project commands run without an approval
+    /// prompt in trusted projects.
+    pub auto_approve_project_commands: bool,
+
+    /// File that model API traffic is recorded in from the start of the
+    /// session (`--wire-log`). Unset means wire logging starts off.
+    pub wire_log: Option<PathBuf>,
+
+    /// Limits after which a turn stops working and summarizes
+    /// (`codex exec --max-runtime` and `--max-turns`).
+    pub run_limits: RunLimits,
+
+    /// Directory that apply_patch writes proposed patches to instead of
+    /// applying them (`codex exec --patch-mode propose-only`).
+    pub patch_proposal_dir: Option<PathBuf>,
+
+    /// Built-in tools switched on or off by `[tools]` and the active profile.
+    pub builtin_tools: BuiltinTools,
+
+    /// When `true`, the session cannot change anything: the sandbox stays
+    /// read-only, approvals are denied, and tools that edit files or run code
+    /// outside the sandbox are left out (`--read-only`).
+    pub read_only: bool,
+
     /// When true, disables burst-paste detection for typed input entirely.
     /// All characters are inserted as they are received, and no buffering
     /// or placeholder replacement will occur for fast keypress bursts.
@@ -929,6 +1128,10 @@ pub struct ConfigToml {
     #[serde(default)]
     pub cli_auth_credentials_store: Option<AuthCredentialsStoreMode>,
 
+    /// Named credential to use instead of the default login, as created with
+    /// `codex login --name <name>`. A profile's `credential` takes precedence.
+    pub credential: Option<String>,
+
     /// Definition for MCP servers that Codex can reach out to for tool calls.
     #[serde(default)]
     // Uses the raw MCP input shape (custom deserialization) rather than `McpServerConfig`.
@@ -974,6 +1177,33 @@ pub struct ConfigToml {
     #[serde(default)]
     pub history: Option<History>,
 
+    /// Hash-chained audit log of approvals, sandbox escalations, and executed commands.
+    pub audit_log: Option<crate::config::types::AuditLogConfigToml>,
+
+    /// Forwarding of security-relevant events to syslog or Splunk.
+    pub security_events: Option<crate::config::types::SecurityEventsToml>,
+
+    /// Cache of model responses keyed on the model and the full request, for
+    /// reproducible `codex exec` runs.
+    pub response_cache: Option<crate::config::types::ResponseCacheConfigToml>,
+
+    /// Storage format for new rollout files under `~/.codex/sessions`.
+    /// Defaults to `none` (plain JSONL).
+    pub rollout_compression: Option<RolloutCompression>,
+
+    /// Encrypt new rollout files (AES-256-GCM, key kept in the OS keyring).
+    /// Takes precedence over `rollout_compression`. Defaults to `false`.
+    pub encrypt_rollouts: Option<bool>,
+
+    /// Where new rollout files are written: `home` (`~/.codex/sessions`) or
+    /// `project` (`.codex/sessions` under the project root). Defaults to `home`.
+    pub session_storage: Option<SessionStorage>,
+
+    /// With `session_storage = "project"`, keep project sessions visible to
+    /// git so they can be committed. Defaults to `false`, which writes a
+    /// `.gitignore` into `.codex/sessions`.
+    pub track_project_sessions: Option<bool>,
+
     /// Directory where Codex writes log files, for example `codex-tui.log`.
     /// Defaults to `$CODEX_HOME/log`.
     pub log_dir: Option<AbsolutePathBuf>,
@@ -998,6 +1228,13 @@ pub struct ConfigToml {
     /// Optional verbosity control for GPT-5 models (Responses API `text.verbosity`).
     pub model_verbosity: Option<Verbosity>,
 
+    /// Run trivial turns (short questions, file reads) on a cheaper model.
+    pub model_routing: Option<ModelRoutingToml>,
+
+    /// Model-written explanations of complex or risky commands on approval
+    /// prompts.
+    pub approval_explanations: Option<ApprovalExplanationsToml>,
+
     /// Override to force-enable reasoning summaries for the configured model.
     pub model_supports_reasoning_summaries: Option<bool>,
 
@@ -1012,6 +1249,48 @@ pub struct ConfigToml {
     /// Controls the web search tool mode: disabled, cached, or live.
     pub web_search: Option<WebSearchMode>,
 
+    /// Search backend (Bing, Brave, or SearXNG) for the `web_search` tool.
+    pub web_search_provider: Option<WebSearchProviderToml>,
+
+    /// Settings for commits created from session changes (co-author trailers).
+    pub commit: Option<CommitToml>,
+
+    /// GitHub Enterprise and self-hosted GitLab hosts pull requests can be
+    /// opened on, with the token to use for each.
+    #[serde(default)]
+    pub forge_hosts: HashMap<String, ForgeHostToml>,
+
+    /// Build, test or lint commands that must pass before a turn that changed
+    /// files is done.
+    pub completion_gate: Option<CompletionGateToml>,
+
+    /// Named context providers: a command or file globs whose output is added
+    /// to the conversation at session start or with `/context <name>`.
+    #[serde(default)]
+    pub context_packs: BTreeMap<String, ContextPackToml>,
+
+    /// Replace older turns with a summary when resuming a long session.
+    pub resume_summary: Option<ResumeSummaryToml>,
+
+    /// Post approval requests of `codex exec --approvals-bridge` to a Slack or
+    /// Matrix webhook and take decisions from signed links.
+    pub approvals_bridge: Option<ApprovalsBridgeToml>,
+
+    /// Ask, retry, switch model, drop context or abort when a model request
+    /// still fails after its stream retries.
+    pub retry_budget: Option<RetryBudgetToml>,
+
+    /// Orchestration to use from the `orchestrations` map.
+    pub orchestration: Option<String>,
+
+    /// Named agent roles that orchestrations are built from.
+    #[serde(default)]
+    pub agent_roles: HashMap<String, AgentRoleToml>,
+
+    /// Named orchestrations: an ordered set of agent roles plus a turn-routing policy.
+    #[serde(default)]
+    pub orchestrations: HashMap<String, OrchestrationToml>,
+
     /// Nested tools section for feature toggles
     pub tools: Option<ToolsToml>,
 
@@ -1047,6 +1326,38 @@ pub struct ConfigToml {
     /// Defaults to `true`.
     pub check_for_update_on_startup: Option<bool>,
 
+    /// Release channel and self-hosted release server for updates.
+    pub updates: Option<UpdatesToml>,
+
+    /// When `true`, the Codex process only talks to services on this machine:
+    /// the model provider must be on localhost, only stdio MCP servers start,
+    /// and update checks, telemetry, web search and apps are turned off.
+    pub offline: Option<bool>,
+
+    /// Proxy, extra CA certificates and mTLS client certificate for Codex's
+    /// own HTTP requests (model calls, login, update checks). Providers can
+    /// override it with their own `http_client` table.
+    pub http_client: Option<HttpClientConfig>,
+
+    /// Encoding to decode command output from when it is not valid UTF-8, as
+    /// a WHATWG label such as `shift_jis` or `latin1`. When unset, the encoding
+    /// is detected from the output.
+    pub exec_output_encoding: Option<String>,
+
+    /// How terminal control sequences (progress bars, cursor movement, colors)
+    /// in command output are handled before it is stored or sent to the model.
+    /// Defaults to `render`.
+    pub exec_output_escapes: Option<ExecOutputEscapes>,
+
+    /// MiB that files inside the writable roots may grow by during a session
+    /// before Codex pauses for approval. Unset means no quota.
+    pub session_disk_quota_mb: Option<u64>,
+
+    /// When `true`, the build, test, lint and format commands detected with
+    /// the `project_commands` feature run without an approval prompt in
+    /// trusted projects. They still run in the sandbox.
+    pub auto_approve_project_commands: Option<bool>,
+
     /// When true, disables burst-paste detection for typed input entirely.
     /// All characters are inserted as they are received, and no buffering
     /// or placeholder replacement will occur for fast keypress bursts.
@@ -1071,6 +1382,11 @@ pub struct ConfigToml {
     #[serde(default)]
     pub windows: Option<WindowsToml>,
 
+    /// Additions to the builtin macOS Seatbelt profile: extra readable
+    /// paths, mach services and raw profile rules.
+    #[serde(default)]
+    pub macos_seatbelt: Option<MacOsSeatbeltToml>,
+
     /// Tracks whether the Windows onboarding screen has been acknowledged.
     pub windows_wsl_setup_acknowledged: Option<bool>,
 
@@ -1139,6 +1455,64 @@ pub struct ToolsToml {
     /// Enable the `view_image` tool that lets the agent attach local images.
     #[serde(default)]
     pub view_image: Option<bool>,
+
+    /// Enable the shell tools that run commands. Off means the agent cannot
+    /// execute anything.
+    #[serde(default)]
+    pub shell: Option<bool>,
+
+    /// Enable the `apply_patch` tool that edits files.
+    #[serde(default)]
+    pub apply_patch: Option<bool>,
+
+    /// Enable the `update_plan` tool.
+    #[serde(default)]
+    pub plan: Option<bool>,
+}
+
+/// Built-in tools offered to the model, from `[tools]` and the active
+/// profile's `[profiles.<name>.tools]`. Every tool is on unless switched off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuiltinTools {
+    pub shell: bool,
+    pub apply_patch: bool,
+    pub plan: bool,
+    pub web_search: bool,
+    pub view_image: bool,
+}
+
+impl Default for BuiltinTools {
+    fn default() -> Self {
+        Self {
+            shell: true,
+            apply_patch: true,
+            plan: true,
+            web_search: true,
+            view_image: true,
+        }
+    }
+}
+
+impl BuiltinTools {
+    /// Resolves each tool from the profile's `tools` table, then the flat
+    /// `tools_*` profile keys, then `[tools]`.
+    fn resolve(base: Option<&ToolsToml>, profile: &ConfigProfile) -> Self {
+        let profile_tools = profile.tools.as_ref();
+        let pick = |get: fn(&ToolsToml) -> Option<bool>, profile_flat: Option<bool>| {
+            profile_tools
+                .and_then(get)
+                .or(profile_flat)
+                .or(base.and_then(get))
+                != Some(false)
+        };
+        Self {
+            shell: pick(|tools| tools.shell, None),
+            apply_patch: pick(|tools| tools.apply_patch, None),
+            plan: pick(|tools| tools.plan, None),
+            web_search: pick(|tools| tools.web_search, profile.tools_web_search),
+            view_image: pick(|tools| tools.view_image, profile.tools_view_image),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
@@ -1217,6 +1591,7 @@ impl ConfigToml {
                     network_access,
                     exclude_tmpdir_env_var,
                     exclude_slash_tmp,
+                    allowed_devices: _,
                 }) => SandboxPolicy::WorkspaceWrite {
                     writable_roots: writable_roots.clone(),
                     read_only_access: ReadOnlyAccess::FullAccess,
@@ -1297,6 +1672,20 @@ impl ConfigToml {
             None => Ok(ConfigProfile::default()),
         }
     }
+
+    /// Auth directory of the credential selected for `override_profile` (or
+    /// the default profile), for callers that need auth before the full
+    /// [`Config`] is loaded. Falls back to `codex_home` when the selection is
+    /// invalid; loading the full config reports the error.
+    pub fn auth_home(&self, codex_home: &Path, override_profile: Option<String>) -> PathBuf {
+        let credential = self
+            .get_config_profile(override_profile)
+            .ok()
+            .and_then(|profile| profile.credential)
+            .or_else(|| self.credential.clone())
+            .filter(|name| validate_credential_name(name).is_ok());
+        credential_home(codex_home, credential.as_deref())
+    }
 }
 
 /// Optional overrides for user configuration (e.g., from CLI flags).
@@ -1321,6 +1710,24 @@ pub struct ConfigOverrides {
     pub ephemeral: Option<bool>,
     /// Additional directories that should be treated as writable roots for this session.
     pub additional_writable_roots: Vec<PathBuf>,
+    /// Sub-directories of the working directory to narrow the session to.
+    pub scope: Vec<PathBuf>,
+    /// Orchestration to use instead of the one named in config.toml.
+    pub orchestration: Option<String>,
+    /// Directory for the model response cache. Setting this or
+    /// `response_cache_mode` enables the cache.
+    pub response_cache_dir: Option<PathBuf>,
+    pub response_cache_mode: Option<ResponseCacheMode>,
+    /// Forces offline mode on (`--offline`).
+    pub offline: Option<bool>,
+    /// Records model API traffic in this file (`--wire-log`).
+    pub wire_log: Option<PathBuf>,
+    /// Limits after which a turn stops working and summarizes.
+    pub run_limits: Option<RunLimits>,
+    /// Saves patches in this directory instead of applying them.
+    pub patch_proposal_dir: Option<PathBuf>,
+    /// Forces read-only mode on (`--read-only`).
+    pub read_only: Option<bool>,
 }
 
 /// Resolves the OSS provider from CLI override, profile config, or global config.
@@ -1351,6 +1758,85 @@ pub fn resolve_oss_provider(
     }
 }
 
+/// Looks up the selected orchestration and resolves the roles it names.
+fn resolve_orchestration(
+    name: Option<String>,
+    agent_roles: &HashMap<String, AgentRoleToml>,
+    orchestrations: &HashMap<String, OrchestrationToml>,
+) -> std::io::Result<Option<OrchestrationConfig>> {
+    let Some(name) = name else {
+        return Ok(None);
+    };
+    let orchestration = orchestrations.get(&name).ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("orchestration `{name}` not found"),
+        )
+    })?;
+    if orchestration.roles.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("orchestration `{name}` must list at least one role"),
+        ));
+    }
+    let roles = orchestration
+        .roles
+        .iter()
+        .map(|role| {
+            agent_roles
+                .get(role)
+                .map(|toml| AgentRoleConfig::from_toml(role, toml.clone()))
+                .ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        format!("orchestration `{name}` uses unknown agent role `{role}`"),
+                    )
+                })
+        })
+        .collect::<std::io::Result<Vec<_>>>()?;
+    Ok(Some(OrchestrationConfig {
+        name,
+        routing: orchestration.routing.unwrap_or_default(),
+        roles,
+    }))
+}
+
+/// Checks that a self-hosted release server comes with a usable public key.
+fn resolve_updates(updates: UpdatesToml) -> std::io::Result<UpdatesConfig> {
+    let invalid =
+        |message: &str| std::io::Error::new(std::io::ErrorKind::InvalidInput, message.to_string());
+    let channel = updates.channel.unwrap_or_default();
+    let release_server = match (updates.endpoint, updates.public_key) {
+        (None, None) if channel == UpdateChannel::Nightly => {
+            return Err(invalid(
+                "the nightly update channel requires updates.endpoint",
+            ));
+        }
+        (None, None) => None,
+        (None, Some(_)) => return Err(invalid("updates.public_key requires updates.endpoint")),
+        (Some(_), None) => {
+            return Err(invalid(
+                "updates.endpoint requires updates.public_key to verify downloaded binaries",
+            ));
+        }
+        (Some(endpoint), Some(public_key)) => {
+            let public_key = BASE64_STANDARD
+                .decode(public_key.trim())
+                .ok()
+                .filter(|key| key.len() == 32)
+                .ok_or_else(|| invalid("updates.public_key must be a base64 Ed25519 public key"))?;
+            Some(ReleaseServer {
+                endpoint,
+                public_key,
+            })
+        }
+    };
+    Ok(UpdatesConfig {
+        channel,
+        release_server,
+    })
+}
+
 /// Resolve the web search mode from explicit config and feature flags.
 fn resolve_web_search_mode(
     config_toml: &ConfigToml,
@@ -1447,6 +1933,15 @@ impl Config {
             tools_web_search_request: override_tools_web_search_request,
             ephemeral,
             additional_writable_roots,
+            scope,
+            orchestration: orchestration_override,
+            response_cache_dir: response_cache_dir_override,
+            response_cache_mode: response_cache_mode_override,
+            offline: offline_override,
+            wire_log: wire_log_override,
+            run_limits,
+            patch_proposal_dir,
+            read_only: read_only_override,
         } = overrides;
 
         let active_profile_name = config_profile_key
@@ -1472,7 +1967,26 @@ impl Config {
             web_search_request: override_tools_web_search_request,
         };
 
-        let features = Features::from_config(&cfg, &config_profile, feature_overrides);
+        let mut features = Features::from_config(&cfg, &config_profile, feature_overrides);
+        let mut builtin_tools = BuiltinTools::resolve(cfg.tools.as_ref(), &config_profile);
+        if let Some(enabled) = override_tools_web_search_request {
+            builtin_tools.web_search = enabled;
+        }
+        let orchestration = resolve_orchestration(
+            orchestration_override.or_else(|| cfg.orchestration.clone()),
+            &cfg.agent_roles,
+            &cfg.orchestrations,
+        )?;
+        // Under `lead` routing, the other roles are reached through `delegate`.
+        let lead_role = orchestration
+            .as_ref()
+            .filter(|orchestration| orchestration.routing == TurnRouting::Lead)
+            .and_then(|orchestration| {
+                if orchestration.roles.len() > 1 {
+                    features.enable(Feature::Delegate);
+                }
+                orchestration.roles.first().cloned()
+            });
         let windows_sandbox_mode = resolve_windows_sandbox_mode(&cfg, &config_profile);
         let resolved_cwd = {
             use std::env;
@@ -1496,6 +2010,7 @@ impl Config {
             .into_iter()
             .map(|path| AbsolutePathBuf::resolve_path_against_base(path, &resolved_cwd))
             .collect::<Result<Vec<_>, _>>()?;
+        let scope = resolve_scope(&resolved_cwd, &scope)?;
         let active_project = cfg
             .get_active_project(&resolved_cwd)
             .unwrap_or(ProjectConfig { trust_level: None });
@@ -1559,10 +2074,28 @@ impl Config {
             model_providers.entry(key).or_insert(provider);
         }
 
-        let model_provider_id = model_provider
+        let mut model_provider_id = model_provider
             .or(config_profile.model_provider)
             .or(cfg.model_provider)
             .unwrap_or_else(|| "openai".to_string());
+        if let Some(Sourced {
+            value: allowed_providers,
+            source,
+        }) = requirements.allowed_model_providers.as_ref()
+            && !allowed_providers.contains(&model_provider_id)
+            && let Some(required_provider) = allowed_providers.first()
+        {
+            tracing::warn!(
+                configured = %model_provider_id,
+                required = %required_provider,
+                requirement_source = %source,
+                "configured model provider is disallowed by requirements; falling back to required value"
+            );
+            startup_warnings.push(format!(
+                "Configured value for `model_provider` is disallowed by requirements; falling back to required value `{required_provider}`. Details: `{model_provider_id}` is not in allowed_model_providers {allowed_providers:?} set by {source}"
+            ));
+            model_provider_id = required_provider.clone();
+        }
         let model_provider = model_providers
             .get(&model_provider_id)
             .ok_or_else(|| {
@@ -1578,6 +2111,62 @@ impl Config {
         let shell_environment_policy = cfg.shell_environment_policy.into();
 
         let history = cfg.history.unwrap_or_default();
+        let audit_log = cfg.audit_log.unwrap_or_default();
+        let audit_log_path = audit_log.enabled.unwrap_or(false).then(|| {
+            audit_log
+                .path
+                .map(AbsolutePathBuf::into_path_buf)
+                .unwrap_or_else(|| codex_home.join(crate::audit_log::AUDIT_LOG_FILENAME))
+        });
+        let security_events = crate::config::types::SecurityEventsConfig::from_toml(
+            cfg.security_events.unwrap_or_default(),
+        )
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+        let project_sessions_dir = match cfg.session_storage.unwrap_or_default() {
+            SessionStorage::Home => None,
+            SessionStorage::Project => {
+                let project_root_markers = cfg
+                    .project_root_markers
+                    .clone()
+                    .unwrap_or_else(default_project_root_markers);
+                let project_root = find_project_root_blocking(&resolved_cwd, &project_root_markers);
+                Some(project_root.join(".codex").join(SESSIONS_SUBDIR))
+            }
+        };
+        let model_routing = cfg.model_routing.clone().and_then(|routing| {
+            Some(ModelRoutingConfig {
+                fast_model: routing.fast_model?,
+                fast_reasoning_effort: routing.fast_reasoning_effort,
+                max_fast_input_chars: routing
+                    .max_fast_input_chars
+                    .unwrap_or(DEFAULT_MAX_FAST_INPUT_CHARS),
+            })
+        });
+        let approval_explanations = cfg
+            .approval_explanations
+            .clone()
+            .filter(|explanations| explanations.enabled.unwrap_or(false))
+            .map(|explanations| ApprovalExplanationsConfig {
+                model: explanations.model,
+                timeout: Duration::from_millis(
+                    explanations
+                        .timeout_ms
+                        .unwrap_or(DEFAULT_APPROVAL_EXPLANATION_TIMEOUT_MS),
+                ),
+            });
+        let response_cache_toml = cfg.response_cache.unwrap_or_default();
+        let response_cache_enabled = response_cache_toml.enabled.unwrap_or(false)
+            || response_cache_dir_override.is_some()
+            || response_cache_mode_override.is_some();
+        let response_cache = response_cache_enabled.then(|| ResponseCacheConfig {
+            dir: response_cache_dir_override
+                .or_else(|| response_cache_toml.dir.map(AbsolutePathBuf::into_path_buf))
+                .unwrap_or_else(|| codex_home.join(crate::response_cache::RESPONSE_CACHE_DIR)),
+            mode: response_cache_mode_override
+                .or(response_cache_toml.mode)
+                .unwrap_or_default(),
+            salt: response_cache_toml.salt,
+        });
 
         let agent_max_threads = cfg
             .agents
@@ -1631,7 +2220,20 @@ impl Config {
 
         let forced_login_method = cfg.forced_login_method;
 
-        let model = model.or(config_profile.model).or(cfg.model);
+        let credential = config_profile
+            .credential
+            .clone()
+            .or_else(|| cfg.credential.clone());
+        if let Some(name) = credential.as_deref() {
+            validate_credential_name(name)?;
+        }
+        let auth_home = credential_home(&codex_home, credential.as_deref());
+
+        let lead_reasoning_effort = lead_role.as_ref().and_then(|role| role.reasoning_effort);
+        let model = model
+            .or_else(|| lead_role.as_ref().and_then(|role| role.model.clone()))
+            .or(config_profile.model)
+            .or(cfg.model);
 
         let compact_prompt = compact_prompt.or(cfg.compact_prompt).and_then(|value| {
             let trimmed = value.trim();
@@ -1678,6 +2280,87 @@ impl Config {
         let review_model = override_review_model.or(cfg.review_model);
 
         let check_for_update_on_startup = cfg.check_for_update_on_startup.unwrap_or(true);
+        let updates = resolve_updates(cfg.updates.clone().unwrap_or_default())?;
+        let context_packs = cfg
+            .context_packs
+            .clone()
+            .into_iter()
+            .map(|(name, toml)| ContextPackConfig::from_toml(&name, toml))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|message| std::io::Error::new(std::io::ErrorKind::InvalidInput, message))?;
+        let approvals_bridge = cfg
+            .approvals_bridge
+            .map(ApprovalsBridgeConfig::try_from)
+            .transpose()
+            .map_err(|message| std::io::Error::new(std::io::ErrorKind::InvalidInput, message))?;
+        let retry_budget = cfg
+            .retry_budget
+            .map(RetryBudgetConfig::try_from)
+            .transpose()
+            .map_err(|message| std::io::Error::new(std::io::ErrorKind::InvalidInput, message))?
+            .unwrap_or_default();
+        let usage_quota = match (active_profile_name.as_deref(), config_profile.quota.clone()) {
+            (Some(profile), Some(quota)) => {
+                UsageQuotaConfig::from_toml(profile, quota).map_err(|message| {
+                    std::io::Error::new(std::io::ErrorKind::InvalidInput, message)
+                })?
+            }
+            _ => None,
+        };
+        let offline = offline_override.or(cfg.offline).unwrap_or(false);
+        let http_client = cfg.http_client.clone().unwrap_or_default();
+        let provider_http_client = model_provider
+            .http_client
+            .as_ref()
+            .map_or_else(|| http_client.clone(), |config| config.or(&http_client));
+        for http_config in [&http_client, &provider_http_client] {
+            validate_http_client_config(http_config).map_err(|err| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("invalid http_client settings: {err}"),
+                )
+            })?;
+        }
+
+        let exec_output_encoding = cfg
+            .exec_output_encoding
+            .as_deref()
+            .map(|label| {
+                Encoding::for_label(label.trim().as_bytes()).ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        format!("unknown exec_output_encoding `{label}`"),
+                    )
+                })
+            })
+            .transpose()?;
+
+        let macos_seatbelt = cfg
+            .macos_seatbelt
+            .as_ref()
+            .map(SeatbeltProfileAdditions::from_toml)
+            .transpose()
+            .map_err(|err| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("invalid [macos_seatbelt]: {err}"),
+                )
+            })?
+            .unwrap_or_default();
+
+        let sandbox_allowed_devices = cfg
+            .sandbox_workspace_write
+            .as_ref()
+            .map(|sandbox| sandbox.allowed_devices.clone())
+            .unwrap_or_default();
+        for pattern in &sandbox_allowed_devices {
+            crate::sandbox_devices::validate_device_pattern(pattern).map_err(|err| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("invalid sandbox_workspace_write.allowed_devices: {err}"),
+                )
+            })?;
+        }
 
         let log_dir = cfg
             .log_dir
@@ -1695,6 +2378,7 @@ impl Config {
             approval_policy: mut constrained_approval_policy,
             sandbox_policy: mut constrained_sandbox_policy,
             web_search_mode: mut constrained_web_search_mode,
+            allowed_model_providers: _,
             mcp_servers,
             exec_policy: _,
             enforce_residency,
@@ -1737,7 +2421,7 @@ impl Config {
             None => None,
         };
 
-        let config = Self {
+        let mut config = Self {
             model,
             review_model,
             model_context_window: cfg.model_context_window,
@@ -1745,6 +2429,7 @@ impl Config {
             model_provider_id,
             model_provider,
             cwd: resolved_cwd,
+            scope,
             startup_warnings,
             permissions: Permissions {
                 approval_policy: constrained_approval_policy.value,
@@ -1765,6 +2450,8 @@ impl Config {
             // The config.toml omits "_mode" because it's a config file. However, "_mode"
             // is important in code to differentiate the mode from the store implementation.
             cli_auth_credentials_store_mode: cfg.cli_auth_credentials_store.unwrap_or_default(),
+            credential,
+            auth_home,
             mcp_servers,
             // The config.toml omits "_mode" because it's a config file. However, "_mode"
             // is important in code to differentiate the mode from the store implementation.
@@ -1788,10 +2475,32 @@ impl Config {
             tool_output_token_limit: cfg.tool_output_token_limit,
             agent_max_threads,
             memories: cfg.memories.unwrap_or_default().into(),
+            web_search_provider: cfg.web_search_provider.map(Into::into),
+            commit: cfg.commit.map(Into::into).unwrap_or_default(),
+            forge_hosts: cfg
+                .forge_hosts
+                .into_iter()
+                .map(|(host, forge)| (host.to_ascii_lowercase(), forge))
+                .collect(),
+            completion_gate: cfg.completion_gate.map(Into::into).unwrap_or_default(),
+            context_packs,
+            resume_summary: cfg.resume_summary.map(Into::into),
+            approvals_bridge,
+            retry_budget,
+            usage_quota,
+            orchestration,
+            agent_role: lead_role,
             codex_home,
             log_dir,
             config_layer_stack,
             history,
+            audit_log_path,
+            security_events,
+            response_cache,
+            rollout_compression: cfg.rollout_compression.unwrap_or_default(),
+            encrypt_rollouts: cfg.encrypt_rollouts.unwrap_or(false),
+            project_sessions_dir,
+            track_project_sessions: cfg.track_project_sessions.unwrap_or(false),
             ephemeral: ephemeral.unwrap_or_default(),
             file_opener: cfg.file_opener.unwrap_or(UriBasedFileOpener::VsCode),
             codex_linux_sandbox_exe,
@@ -1802,8 +2511,8 @@ impl Config {
                 .show_raw_agent_reasoning
                 .or(show_raw_agent_reasoning)
                 .unwrap_or(false),
-            model_reasoning_effort: config_profile
-                .model_reasoning_effort
+            model_reasoning_effort: lead_reasoning_effort
+                .or(config_profile.model_reasoning_effort)
                 .or(cfg.model_reasoning_effort),
             model_reasoning_summary: config_profile
                 .model_reasoning_summary
@@ -1811,6 +2520,8 @@ impl Config {
                 .unwrap_or_default(),
             model_supports_reasoning_summaries: cfg.model_supports_reasoning_summaries,
             model_verbosity: config_profile.model_verbosity.or(cfg.model_verbosity),
+            model_routing,
+            approval_explanations,
             chatgpt_base_url: config_profile
                 .chatgpt_base_url
                 .or(cfg.chatgpt_base_url)
@@ -1830,6 +2541,20 @@ impl Config {
             windows_wsl_setup_acknowledged: cfg.windows_wsl_setup_acknowledged.unwrap_or(false),
             notices: cfg.notice.unwrap_or_default(),
             check_for_update_on_startup,
+            updates,
+            offline,
+            http_client,
+            exec_output_encoding,
+            exec_output_escapes: cfg.exec_output_escapes.unwrap_or_default(),
+            session_disk_quota_mb: cfg.session_disk_quota_mb.filter(|quota| *quota > 0),
+            macos_seatbelt,
+            sandbox_allowed_devices,
+            auto_approve_project_commands: cfg.auto_approve_project_commands.unwrap_or(false),
+            wire_log: wire_log_override,
+            run_limits: run_limits.unwrap_or_default(),
+            patch_proposal_dir,
+            builtin_tools,
+            read_only: read_only_override.unwrap_or(false),
             disable_paste_burst: cfg.disable_paste_burst.unwrap_or(false),
             analytics_enabled: config_profile
                 .analytics
@@ -1851,7 +2576,11 @@ impl Config {
                 .as_ref()
                 .map(|t| t.notification_method)
                 .unwrap_or_default(),
-            animations: cfg.tui.as_ref().map(|t| t.animations).unwrap_or(true),
+            animations: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.animations && !t.screen_reader)
+                .unwrap_or(true),
             show_tooltips: cfg.tui.as_ref().map(|t| t.show_tooltips).unwrap_or(true),
             experimental_mode: cfg.tui.as_ref().and_then(|t| t.experimental_mode),
             tui_alternate_screen: cfg
@@ -1860,6 +2589,13 @@ impl Config {
                 .map(|t| t.alternate_screen)
                 .unwrap_or_default(),
             tui_status_line: cfg.tui.as_ref().and_then(|t| t.status_line.clone()),
+            tui_status_line_priority: cfg
+                .tui
+                .as_ref()
+                .and_then(|t| t.status_line_priority.clone()),
+            tui_screen_reader: cfg.tui.as_ref().is_some_and(|t| t.screen_reader),
+            tui_mouse: cfg.tui.as_ref().is_some_and(|t| t.mouse),
+            tui_hyperlinks: cfg.tui.as_ref().is_none_or(|t| t.hyperlinks),
             otel: {
                 let t: OtelConfigToml = cfg.otel.unwrap_or_default();
                 let log_user_prompt = t.log_user_prompt.unwrap_or(false);
@@ -1875,9 +2611,32 @@ impl Config {
                     exporter,
                     trace_exporter,
                     metrics_exporter,
+                    prometheus_listen_addr: t.prometheus_listen_addr,
                 }
             },
         };
+        if config
+            .agent_role
+            .as_ref()
+            .is_some_and(|role| role.read_only)
+        {
+            config
+                .permissions
+                .sandbox_policy
+                .set(SandboxPolicy::new_read_only_policy())
+                .map_err(|err| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        format!("agent role requires a read-only sandbox: {err}"),
+                    )
+                })?;
+        }
+        if config.offline {
+            offline::enforce_offline(&mut config)?;
+        }
+        if config.read_only {
+            read_only::enforce_read_only(&mut config)?;
+        }
         Ok(config)
     }
 
@@ -2006,6 +2765,9 @@ mod tests {
     use crate::config::edit::ConfigEdit;
     use crate::config::edit::ConfigEditsBuilder;
     use crate::config::edit::apply_blocking;
+    use crate::config::types::ApprovalsBridgeFormat;
+    use crate::config::types::ContextPackInject;
+    use crate::config::types::ContextPackSource;
     use crate::config::types::FeedbackConfigToml;
     use crate::config::types::HistoryPersistence;
     use crate::config::types::McpServerTransportConfig;
@@ -2013,6 +2775,10 @@ mod tests {
     use crate::config::types::MemoriesToml;
     use crate::config::types::NotificationMethod;
     use crate::config::types::Notifications;
+    use crate::config::types::OtelHttpProtocol;
+    use crate::config::types::RetryExhaustedPolicy;
+    use crate::config::types::TokenPrices;
+    use crate::config::types::WebSearchProviderKind;
     use crate::config_loader::RequirementSource;
     use crate::features::Feature;
 
@@ -2123,18 +2889,437 @@ phase_2_model = "gpt-5"
             ConfigOverrides::default(),
             tempdir().expect("tempdir").path().to_path_buf(),
         )
-        .expect("load config from memories settings");
-        assert_eq!(
-            config.memories,
-            MemoriesConfig {
-                max_raw_memories_for_global: 512,
-                max_rollout_age_days: 42,
-                max_rollouts_per_startup: 9,
-                min_rollout_idle_hours: 24,
-                phase_1_model: Some("gpt-5-mini".to_string()),
-                phase_2_model: Some("gpt-5".to_string()),
-            }
-        );
+        .expect("load config from memories settings");
+        assert_eq!(
+            config.memories,
+            MemoriesConfig {
+                max_raw_memories_for_global: 512,
+                max_rollout_age_days: 42,
+                max_rollouts_per_startup: 9,
+                min_rollout_idle_hours: 24,
+                phase_1_model: Some("gpt-5-mini".to_string()),
+                phase_2_model: Some("gpt-5".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn web_search_provider_applies_defaults() {
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[web_search_provider]
+kind = "searxng"
+base_url = "https://search.example.com/"
+max_results = 50
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            tempdir().expect("tempdir").path().to_path_buf(),
+        )
+        .expect("load config from web search provider settings");
+        assert_eq!(
+            config.web_search_provider,
+            Some(WebSearchProviderConfig {
+                kind: WebSearchProviderKind::Searxng,
+                base_url: "https://search.example.com".to_string(),
+                api_key_env: None,
+                max_results: 20,
+                fetch_results: 3,
+                max_bytes: 32 * 1024,
+            })
+        );
+    }
+
+    #[test]
+    fn commit_co_authors_are_trimmed() {
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[commit]
+co_authors = [" Codex <codex@openai.com> ", ""]
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            tempdir().expect("tempdir").path().to_path_buf(),
+        )
+        .expect("load config from commit settings");
+        assert_eq!(
+            config.commit,
+            CommitConfig {
+                co_authors: vec!["Codex <codex@openai.com>".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn completion_gate_defaults_apply() {
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[completion_gate]
+commands = ["cargo build", " ", "cargo test"]
+timeout_sec = 0
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            tempdir().expect("tempdir").path().to_path_buf(),
+        )
+        .expect("load config from completion gate settings");
+        assert_eq!(
+            config.completion_gate,
+            CompletionGateConfig {
+                commands: vec!["cargo build".to_string(), "cargo test".to_string()],
+                max_retries: 2,
+                timeout: Duration::from_secs(600),
+            }
+        );
+    }
+
+    #[test]
+    fn context_packs_resolve_sources_and_defaults() {
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[context_packs.kube]
+command = "kubectl config current-context"
+inject = "session_start"
+max_tokens = 200
+
+[context_packs.runbooks]
+files = ["docs/runbooks/*.md"]
+cache_ttl_sec = 0
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            tempdir().expect("tempdir").path().to_path_buf(),
+        )
+        .expect("load config with context packs");
+        assert_eq!(
+            config.context_packs,
+            vec![
+                ContextPackConfig {
+                    name: "kube".to_string(),
+                    source: ContextPackSource::Command(
+                        "kubectl config current-context".to_string()
+                    ),
+                    inject: ContextPackInject::SessionStart,
+                    max_tokens: 200,
+                    cache_ttl: Duration::from_secs(300),
+                    timeout: Duration::from_secs(30),
+                },
+                ContextPackConfig {
+                    name: "runbooks".to_string(),
+                    source: ContextPackSource::Files(vec!["docs/runbooks/*.md".to_string()]),
+                    inject: ContextPackInject::OnDemand,
+                    max_tokens: 2_000,
+                    cache_ttl: Duration::ZERO,
+                    timeout: Duration::from_secs(30),
+                },
+            ]
+        );
+
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[context_packs.both]
+command = "date"
+files = ["README.md"]
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            tempdir().expect("tempdir").path().to_path_buf(),
+        )
+        .expect_err("a pack with two sources is rejected");
+        assert_eq!(
+            err.to_string(),
+            "context pack `both` sets both `command` and `files`; use one"
+        );
+    }
+
+    #[test]
+    fn resume_summary_defaults_apply() {
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[resume_summary]
+keep_turns = 0
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            tempdir().expect("tempdir").path().to_path_buf(),
+        )
+        .expect("load config with resume summary settings");
+        assert_eq!(
+            config.resume_summary,
+            Some(ResumeSummaryConfig {
+                keep_turns: 3,
+                min_tokens: 30_000,
+            })
+        );
+    }
+
+    #[test]
+    fn approvals_bridge_defaults_apply() {
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[approvals_bridge]
+webhook_url = "https://hooks.slack.com/services/T/B/X"
+public_url = "https://codex.example.com/bridge/"
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            tempdir().expect("tempdir").path().to_path_buf(),
+        )
+        .expect("load config with approvals bridge settings");
+        assert_eq!(
+            config.approvals_bridge,
+            Some(ApprovalsBridgeConfig {
+                webhook_url: "https://hooks.slack.com/services/T/B/X".to_string(),
+                format: ApprovalsBridgeFormat::Slack,
+                listen_addr: "127.0.0.1:8788".parse().expect("socket address"),
+                public_url: Some("https://codex.example.com/bridge".to_string()),
+                secret_env: "CODEX_APPROVALS_BRIDGE_SECRET".to_string(),
+                timeout: Duration::from_secs(1800),
+            })
+        );
+
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[approvals_bridge]
+format = "matrix"
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            tempdir().expect("tempdir").path().to_path_buf(),
+        )
+        .expect_err("webhook_url is required");
+        assert_eq!(err.to_string(), "approvals_bridge.webhook_url is required");
+    }
+
+    #[test]
+    fn retry_budget_requires_fallback_model_to_switch() {
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[retry_budget]
+on_exhausted = "switch_model"
+fallback_model = "gpt-5.1-codex-mini"
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            tempdir().expect("tempdir").path().to_path_buf(),
+        )
+        .expect("load config with retry budget settings");
+        assert_eq!(
+            config.retry_budget,
+            RetryBudgetConfig {
+                on_exhausted: RetryExhaustedPolicy::SwitchModel,
+                fallback_model: Some("gpt-5.1-codex-mini".to_string()),
+                max_recoveries: 2,
+            }
+        );
+
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[retry_budget]
+on_exhausted = "switch_model"
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            tempdir().expect("tempdir").path().to_path_buf(),
+        )
+        .expect_err("switching needs a fallback model");
+        assert_eq!(
+            err.to_string(),
+            "retry_budget.on_exhausted = \"switch_model\" needs retry_budget.fallback_model"
+        );
+    }
+
+    #[test]
+    fn usage_quota_applies_to_the_active_profile() {
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+profile = "team"
+
+[profiles.team.quota]
+daily_tokens = 2000000
+weekly_usd = 80.0
+input_usd_per_million = 1.25
+output_usd_per_million = 10.0
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            tempdir().expect("tempdir").path().to_path_buf(),
+        )
+        .expect("load config with a usage quota");
+        assert_eq!(
+            config.usage_quota,
+            Some(UsageQuotaConfig {
+                profile: "team".to_string(),
+                daily_tokens: Some(2_000_000),
+                weekly_tokens: None,
+                daily_usd: None,
+                weekly_usd: Some(80.0),
+                prices: Some(TokenPrices {
+                    input: 1.25,
+                    cached_input: 1.25,
+                    output: 10.0,
+                }),
+            })
+        );
+
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+profile = "team"
+
+[profiles.team.quota]
+daily_usd = 20.0
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            tempdir().expect("tempdir").path().to_path_buf(),
+        )
+        .expect_err("dollar limits need prices");
+        assert_eq!(
+            err.to_string(),
+            "dollar limits in profiles.team.quota need input_usd_per_million and output_usd_per_million"
+        );
+    }
+
+    #[test]
+    fn lead_orchestration_applies_first_role() {
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+orchestration = "build"
+
+[agent_roles.planner]
+model = "gpt-5.1-codex-mini"
+model_reasoning_effort = "high"
+instructions = " Plan before changing anything. "
+tools = ["read_file", "grep_files"]
+read_only = true
+
+[agent_roles.implementer]
+description = "Writes the code"
+
+[orchestrations.build]
+roles = ["planner", "implementer"]
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            tempdir().expect("tempdir").path().to_path_buf(),
+        )
+        .expect("load config with orchestration");
+        let orchestration = config.orchestration.as_ref().expect("orchestration");
+        assert_eq!(orchestration.name, "build");
+        assert_eq!(orchestration.routing, TurnRouting::Lead);
+        assert_eq!(
+            config.agent_role,
+            Some(AgentRoleConfig {
+                name: "planner".to_string(),
+                description: None,
+                model: Some("gpt-5.1-codex-mini".to_string()),
+                reasoning_effort: Some(ReasoningEffort::High),
+                instructions: Some("Plan before changing anything.".to_string()),
+                tools: Some(vec!["read_file".to_string(), "grep_files".to_string()]),
+                read_only: true,
+                max_tokens: None,
+            })
+        );
+        assert_eq!(config.model.as_deref(), Some("gpt-5.1-codex-mini"));
+        assert_eq!(config.model_reasoning_effort, Some(ReasoningEffort::High));
+        assert_eq!(
+            config.permissions.sandbox_policy.get(),
+            &SandboxPolicy::new_read_only_policy()
+        );
+        assert!(config.features.enabled(Feature::Delegate));
+    }
+
+    #[test]
+    fn orchestration_override_selects_pipeline_and_rejects_unknown_roles() {
+        let toml = r#"
+[agent_roles.reviewer]
+
+[orchestrations.review]
+roles = ["reviewer"]
+routing = "pipeline"
+
+[orchestrations.broken]
+roles = ["reviewer", "tester"]
+"#;
+        let config = Config::load_from_base_config_with_overrides(
+            toml::from_str::<ConfigToml>(toml).expect("TOML deserialization should succeed"),
+            ConfigOverrides {
+                orchestration: Some("review".to_string()),
+                ..Default::default()
+            },
+            tempdir().expect("tempdir").path().to_path_buf(),
+        )
+        .expect("load config with orchestration override");
+        let orchestration = config.orchestration.expect("orchestration");
+        assert_eq!(orchestration.routing, TurnRouting::Pipeline);
+        assert_eq!(config.agent_role, None);
+
+        for (name, message) in [
+            (
+                "broken",
+                "orchestration `broken` uses unknown agent role `tester`",
+            ),
+            ("missing", "orchestration `missing` not found"),
+        ] {
+            let err = Config::load_from_base_config_with_overrides(
+                toml::from_str::<ConfigToml>(toml).expect("TOML deserialization should succeed"),
+                ConfigOverrides {
+                    orchestration: Some(name.to_string()),
+                    ..Default::default()
+                },
+                tempdir().expect("tempdir").path().to_path_buf(),
+            )
+            .expect_err("invalid orchestration should fail to load");
+            assert_eq!(err.to_string(), message);
+        }
     }
 
     #[test]
@@ -2157,6 +3342,10 @@ phase_2_model = "gpt-5"
                 experimental_mode: None,
                 alternate_screen: AltScreenMode::Auto,
                 status_line: None,
+                status_line_priority: None,
+                screen_reader: false,
+                mouse: false,
+                hyperlinks: true,
             }
         );
     }
@@ -2543,6 +3732,272 @@ trust_level = "trusted"
         Ok(())
     }
 
+    #[test]
+    fn profile_credential_overrides_top_level_credential() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = ConfigToml {
+            credential: Some("personal".to_string()),
+            profiles: HashMap::from([(
+                "client".to_string(),
+                ConfigProfile {
+                    credential: Some("client-azure".to_string()),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg.clone(),
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+        assert_eq!(config.credential.as_deref(), Some("personal"));
+        assert_eq!(
+            config.auth_home,
+            codex_home.path().join("credentials").join("personal")
+        );
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg.clone(),
+            ConfigOverrides {
+                config_profile: Some("client".to_string()),
+                ..Default::default()
+            },
+            codex_home.path().to_path_buf(),
+        )?;
+        assert_eq!(config.credential.as_deref(), Some("client-azure"));
+        assert_eq!(
+            config.auth_home,
+            cfg.auth_home(codex_home.path(), Some("client".to_string()))
+        );
+
+        let config = Config::load_from_base_config_with_overrides(
+            ConfigToml::default(),
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+        assert_eq!(config.credential, None);
+        assert_eq!(config.auth_home, codex_home.path());
+
+        Ok(())
+    }
+
+    #[test]
+    fn updates_endpoint_requires_a_valid_public_key() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let load = |public_key: Option<&str>| {
+            Config::load_from_base_config_with_overrides(
+                ConfigToml {
+                    updates: Some(UpdatesToml {
+                        channel: Some(UpdateChannel::Nightly),
+                        endpoint: Some("https://releases.example.com/codex".to_string()),
+                        public_key: public_key.map(str::to_string),
+                    }),
+                    ..Default::default()
+                },
+                ConfigOverrides::default(),
+                codex_home.path().to_path_buf(),
+            )
+        };
+
+        let err = load(None).expect_err("endpoint without a key is rejected");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        let err = load(Some("c2hvcnQ=")).expect_err("short key is rejected");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+        let config = load(Some(&BASE64_STANDARD.encode([7u8; 32])))?;
+        assert_eq!(
+            config.updates,
+            UpdatesConfig {
+                channel: UpdateChannel::Nightly,
+                release_server: Some(ReleaseServer {
+                    endpoint: "https://releases.example.com/codex".to_string(),
+                    public_key: vec![7u8; 32],
+                }),
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn exec_output_encoding_accepts_whatwg_labels() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let load = |label: &str| {
+            Config::load_from_base_config_with_overrides(
+                ConfigToml {
+                    exec_output_encoding: Some(label.to_string()),
+                    ..Default::default()
+                },
+                ConfigOverrides::default(),
+                codex_home.path().to_path_buf(),
+            )
+        };
+
+        assert_eq!(
+            load("Shift_JIS")?.exec_output_encoding,
+            Some(encoding_rs::SHIFT_JIS)
+        );
+        assert_eq!(
+            load("latin1")?.exec_output_encoding,
+            Some(encoding_rs::WINDOWS_1252)
+        );
+        let err = load("klingon").expect_err("unknown label");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        Ok(())
+    }
+
+    #[test]
+    fn macos_seatbelt_rules_are_validated_on_load() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let load = |toml: &str| {
+            Config::load_from_base_config_with_overrides(
+                toml::from_str::<ConfigToml>(toml).expect("parse config"),
+                ConfigOverrides::default(),
+                codex_home.path().to_path_buf(),
+            )
+        };
+
+        let config = load(
+            r#"
+[macos_seatbelt]
+mach_services = ["com.apple.CoreSimulator.CoreSimulatorService"]
+extra_rules = "(allow file-read-metadata (literal \"/opt/homebrew\"))"
+"#,
+        )?;
+        assert!(!config.macos_seatbelt.is_empty());
+
+        let err = load(
+            r#"
+[macos_seatbelt]
+extra_rules = "(version 1) (allow default)"
+"#,
+        )
+        .expect_err("non-rule form");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        Ok(())
+    }
+
+    #[test]
+    fn sandbox_allowed_devices_are_validated_on_load() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let load = |toml: &str| {
+            Config::load_from_base_config_with_overrides(
+                toml::from_str::<ConfigToml>(toml).expect("parse config"),
+                ConfigOverrides::default(),
+                codex_home.path().to_path_buf(),
+            )
+        };
+
+        let config = load(
+            r#"
+sandbox_mode = "workspace-write"
+
+[sandbox_workspace_write]
+allowed_devices = ["/dev/nvidia*", "/dev/dri"]
+"#,
+        )?;
+        assert_eq!(
+            config.sandbox_allowed_devices,
+            vec!["/dev/nvidia*".to_string(), "/dev/dri".to_string()]
+        );
+
+        let err = load(
+            r#"
+[sandbox_workspace_write]
+allowed_devices = ["/etc/shadow"]
+"#,
+        )
+        .expect_err("path outside /dev");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        Ok(())
+    }
+
+    #[test]
+    fn offline_mode_requires_local_provider_and_disables_network_features() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = ConfigToml {
+            offline: Some(true),
+            ..Default::default()
+        };
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("the default provider is not local");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+        let cfg = ConfigToml {
+            model_provider: Some(OLLAMA_OSS_PROVIDER_ID.to_string()),
+            mcp_servers: HashMap::from([
+                ("local".to_string(), stdio_mcp("local-server")),
+                ("remote".to_string(), http_mcp("https://mcp.example.com")),
+            ]),
+            web_search: Some(WebSearchMode::Live),
+            otel: Some(OtelConfigToml {
+                exporter: Some(OtelExporterKind::OtlpHttp {
+                    endpoint: "http://localhost:4318".to_string(),
+                    headers: HashMap::new(),
+                    protocol: OtelHttpProtocol::Json,
+                    tls: None,
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides {
+                offline: Some(true),
+                ..Default::default()
+            },
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(config.offline, true);
+        assert_eq!(
+            config.mcp_servers.keys().cloned().collect::<Vec<_>>(),
+            vec!["local".to_string()]
+        );
+        assert_eq!(config.web_search_mode.value(), WebSearchMode::Disabled);
+        assert_eq!(config.check_for_update_on_startup, false);
+        assert_eq!(config.analytics_enabled, Some(false));
+        assert_eq!(config.feedback_enabled, false);
+        assert_eq!(config.features.enabled(Feature::Apps), false);
+        assert_eq!(config.otel.metrics_exporter, OtelExporterKind::None);
+        assert!(matches!(
+            config.otel.exporter,
+            OtelExporterKind::OtlpHttp { .. }
+        ));
+        assert!(
+            config
+                .startup_warnings
+                .iter()
+                .any(|warning| warning.contains("remote"))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_credential_name_is_rejected() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let cfg = ConfigToml {
+            credential: Some("../work".to_string()),
+            ..Default::default()
+        };
+
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("path-like names must be rejected");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
     #[test]
     fn web_search_mode_defaults_to_none_if_unset() {
         let cfg = ConfigToml::default();
@@ -2663,6 +4118,91 @@ trust_level = "trusted"
         Ok(())
     }
 
+    #[test]
+    fn profile_tools_override_base_tools() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+profile = "explain"
+
+[tools]
+plan = false
+view_image = false
+
+[profiles.explain.tools]
+shell = false
+apply_patch = false
+view_image = true
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.builtin_tools,
+            BuiltinTools {
+                shell: false,
+                apply_patch: false,
+                plan: false,
+                web_search: true,
+                view_image: true,
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_only_mode_pins_sandbox_and_approvals() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = ConfigToml {
+            sandbox_mode: Some(SandboxMode::DangerFullAccess),
+            approval_policy: Some(AskForApproval::OnRequest),
+            ..Default::default()
+        };
+
+        let mut config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides {
+                read_only: Some(true),
+                ..Default::default()
+            },
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.permissions.sandbox_policy.get(),
+            &SandboxPolicy::new_read_only_policy()
+        );
+        assert_eq!(
+            config.permissions.approval_policy.value(),
+            AskForApproval::Never
+        );
+        assert!(!config.builtin_tools.apply_patch);
+        assert!(!config.features.enabled(Feature::JsRepl));
+        assert!(
+            config
+                .permissions
+                .sandbox_policy
+                .set(SandboxPolicy::DangerFullAccess)
+                .is_err()
+        );
+        assert!(
+            config
+                .permissions
+                .approval_policy
+                .set(AskForApproval::OnRequest)
+                .is_err()
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn project_profile_overrides_user_profile() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -4035,8 +5575,14 @@ model_verbosity = "high"
             request_max_retries: Some(4),
             stream_max_retries: Some(10),
             stream_idle_timeout_ms: Some(300_000),
+            stream_stall_timeout_ms: None,
             requires_openai_auth: false,
             supports_websockets: false,
+            oidc: None,
+            http_client: None,
+            organization: None,
+            project: None,
+            azure: None,
         };
         let model_provider_map = {
             let mut model_provider_map = built_in_model_providers();
@@ -4106,7 +5652,10 @@ model_verbosity = "high"
                 user_instructions: None,
                 notify: None,
                 cwd: fixture.cwd(),
+                scope: Vec::new(),
                 cli_auth_credentials_store_mode: Default::default(),
+                credential: None,
+                auth_home: fixture.codex_home(),
                 mcp_servers: Constrained::allow_any(HashMap::new()),
                 mcp_oauth_credentials_store_mode: Default::default(),
                 mcp_oauth_callback_port: None,
@@ -4116,11 +5665,29 @@ model_verbosity = "high"
                 tool_output_token_limit: None,
                 agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
                 memories: MemoriesConfig::default(),
+                web_search_provider: None,
+                commit: CommitConfig::default(),
+                forge_hosts: HashMap::new(),
+                completion_gate: CompletionGateConfig::default(),
+                context_packs: Vec::new(),
+                resume_summary: None,
+                approvals_bridge: None,
+                retry_budget: RetryBudgetConfig::default(),
+                usage_quota: None,
+                orchestration: None,
+                agent_role: None,
                 codex_home: fixture.codex_home(),
                 log_dir: fixture.codex_home().join("log"),
                 config_layer_stack: Default::default(),
                 startup_warnings: Vec::new(),
                 history: History::default(),
+                audit_log_path: None,
+                security_events: None,
+                response_cache: None,
+                rollout_compression: RolloutCompression::None,
+                encrypt_rollouts: false,
+                project_sessions_dir: None,
+                track_project_sessions: false,
                 ephemeral: false,
                 file_opener: UriBasedFileOpener::VsCode,
                 codex_linux_sandbox_exe: None,
@@ -4131,6 +5698,8 @@ model_verbosity = "high"
                 model_reasoning_summary: ReasoningSummary::Detailed,
                 model_supports_reasoning_summaries: None,
                 model_verbosity: None,
+                model_routing: None,
+                approval_explanations: None,
                 personality: Some(Personality::Pragmatic),
                 chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
                 base_instructions: None,
@@ -4149,6 +5718,20 @@ model_verbosity = "high"
                 windows_wsl_setup_acknowledged: false,
                 notices: Default::default(),
                 check_for_update_on_startup: true,
+                updates: UpdatesConfig::default(),
+                offline: false,
+                http_client: HttpClientConfig::default(),
+                exec_output_encoding: None,
+                exec_output_escapes: ExecOutputEscapes::Render,
+                macos_seatbelt: SeatbeltProfileAdditions::default(),
+                sandbox_allowed_devices: Vec::new(),
+                session_disk_quota_mb: None,
+                auto_approve_project_commands: false,
+                wire_log: None,
+                run_limits: RunLimits::default(),
+                patch_proposal_dir: None,
+                builtin_tools: BuiltinTools::default(),
+                read_only: false,
                 disable_paste_burst: false,
                 tui_notifications: Default::default(),
                 tui_notification_method: Default::default(),
@@ -4159,6 +5742,10 @@ model_verbosity = "high"
                 feedback_enabled: true,
                 tui_alternate_screen: AltScreenMode::Auto,
                 tui_status_line: None,
+                tui_status_line_priority: None,
+                tui_screen_reader: false,
+                tui_mouse: false,
+                tui_hyperlinks: true,
                 otel: OtelConfig::default(),
             },
             o3_profile_config
@@ -4217,7 +5804,10 @@ model_verbosity = "high"
             user_instructions: None,
             notify: None,
             cwd: fixture.cwd(),
+            scope: Vec::new(),
             cli_auth_credentials_store_mode: Default::default(),
+            credential: None,
+            auth_home: fixture.codex_home(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
             mcp_oauth_credentials_store_mode: Default::default(),
             mcp_oauth_callback_port: None,
@@ -4227,11 +5817,29 @@ model_verbosity = "high"
             tool_output_token_limit: None,
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
             memories: MemoriesConfig::default(),
+            web_search_provider: None,
+            commit: CommitConfig::default(),
+            forge_hosts: HashMap::new(),
+            completion_gate: CompletionGateConfig::default(),
+            context_packs: Vec::new(),
+            resume_summary: None,
+            approvals_bridge: None,
+            retry_budget: RetryBudgetConfig::default(),
+            usage_quota: None,
+            orchestration: None,
+            agent_role: None,
             codex_home: fixture.codex_home(),
             log_dir: fixture.codex_home().join("log"),
             config_layer_stack: Default::default(),
             startup_warnings: Vec::new(),
             history: History::default(),
+            audit_log_path: None,
+            security_events: None,
+            response_cache: None,
+            rollout_compression: RolloutCompression::None,
+            encrypt_rollouts: false,
+            project_sessions_dir: None,
+            track_project_sessions: false,
             ephemeral: false,
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
@@ -4242,6 +5850,8 @@ model_verbosity = "high"
             model_reasoning_summary: ReasoningSummary::default(),
             model_supports_reasoning_summaries: None,
             model_verbosity: None,
+            model_routing: None,
+            approval_explanations: None,
             personality: Some(Personality::Pragmatic),
             chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
             base_instructions: None,
@@ -4260,6 +5870,20 @@ model_verbosity = "high"
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
+            updates: UpdatesConfig::default(),
+            offline: false,
+            http_client: HttpClientConfig::default(),
+            exec_output_encoding: None,
+            exec_output_escapes: ExecOutputEscapes::Render,
+            macos_seatbelt: SeatbeltProfileAdditions::default(),
+            sandbox_allowed_devices: Vec::new(),
+            session_disk_quota_mb: None,
+            auto_approve_project_commands: false,
+            wire_log: None,
+            run_limits: RunLimits::default(),
+            patch_proposal_dir: None,
+            builtin_tools: BuiltinTools::default(),
+            read_only: false,
             disable_paste_burst: false,
             tui_notifications: Default::default(),
             tui_notification_method: Default::default(),
@@ -4270,6 +5894,10 @@ model_verbosity = "high"
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
             tui_status_line: None,
+            tui_status_line_priority: None,
+            tui_screen_reader: false,
+            tui_mouse: false,
+            tui_hyperlinks: true,
             otel: OtelConfig::default(),
         };
 
@@ -4326,7 +5954,10 @@ model_verbosity = "high"
             user_instructions: None,
             notify: None,
             cwd: fixture.cwd(),
+            scope: Vec::new(),
             cli_auth_credentials_store_mode: Default::default(),
+            credential: None,
+            auth_home: fixture.codex_home(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
             mcp_oauth_credentials_store_mode: Default::default(),
             mcp_oauth_callback_port: None,
@@ -4336,11 +5967,29 @@ model_verbosity = "high"
             tool_output_token_limit: None,
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
             memories: MemoriesConfig::default(),
+            web_search_provider: None,
+            commit: CommitConfig::default(),
+            forge_hosts: HashMap::new(),
+            completion_gate: CompletionGateConfig::default(),
+            context_packs: Vec::new(),
+            resume_summary: None,
+            approvals_bridge: None,
+            retry_budget: RetryBudgetConfig::default(),
+            usage_quota: None,
+            orchestration: None,
+            agent_role: None,
             codex_home: fixture.codex_home(),
             log_dir: fixture.codex_home().join("log"),
             config_layer_stack: Default::default(),
             startup_warnings: Vec::new(),
             history: History::default(),
+            audit_log_path: None,
+            security_events: None,
+            response_cache: None,
+            rollout_compression: RolloutCompression::None,
+            encrypt_rollouts: false,
+            project_sessions_dir: None,
+            track_project_sessions: false,
             ephemeral: false,
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
@@ -4351,6 +6000,8 @@ model_verbosity = "high"
             model_reasoning_summary: ReasoningSummary::default(),
             model_supports_reasoning_summaries: None,
             model_verbosity: None,
+            model_routing: None,
+            approval_explanations: None,
             personality: Some(Personality::Pragmatic),
             chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
             base_instructions: None,
@@ -4369,6 +6020,20 @@ model_verbosity = "high"
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
+            updates: UpdatesConfig::default(),
+            offline: false,
+            http_client: HttpClientConfig::default(),
+            exec_output_encoding: None,
+            exec_output_escapes: ExecOutputEscapes::Render,
+            macos_seatbelt: SeatbeltProfileAdditions::default(),
+            sandbox_allowed_devices: Vec::new(),
+            session_disk_quota_mb: None,
+            auto_approve_project_commands: false,
+            wire_log: None,
+            run_limits: RunLimits::default(),
+            patch_proposal_dir: None,
+            builtin_tools: BuiltinTools::default(),
+            read_only: false,
             disable_paste_burst: false,
             tui_notifications: Default::default(),
             tui_notification_method: Default::default(),
@@ -4379,6 +6044,10 @@ model_verbosity = "high"
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
             tui_status_line: None,
+            tui_status_line_priority: None,
+            tui_screen_reader: false,
+            tui_mouse: false,
+            tui_hyperlinks: true,
             otel: OtelConfig::default(),
         };
 
@@ -4421,7 +6090,10 @@ model_verbosity = "high"
             user_instructions: None,
             notify: None,
             cwd: fixture.cwd(),
+            scope: Vec::new(),
             cli_auth_credentials_store_mode: Default::default(),
+            credential: None,
+            auth_home: fixture.codex_home(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
             mcp_oauth_credentials_store_mode: Default::default(),
             mcp_oauth_callback_port: None,
@@ -4431,11 +6103,29 @@ model_verbosity = "high"
             tool_output_token_limit: None,
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
             memories: MemoriesConfig::default(),
+            web_search_provider: None,
+            commit: CommitConfig::default(),
+            forge_hosts: HashMap::new(),
+            completion_gate: CompletionGateConfig::default(),
+            context_packs: Vec::new(),
+            resume_summary: None,
+            approvals_bridge: None,
+            retry_budget: RetryBudgetConfig::default(),
+            usage_quota: None,
+            orchestration: None,
+            agent_role: None,
             codex_home: fixture.codex_home(),
             log_dir: fixture.codex_home().join("log"),
             config_layer_stack: Default::default(),
             startup_warnings: Vec::new(),
             history: History::default(),
+            audit_log_path: None,
+            security_events: None,
+            response_cache: None,
+            rollout_compression: RolloutCompression::None,
+            encrypt_rollouts: false,
+            project_sessions_dir: None,
+            track_project_sessions: false,
             ephemeral: false,
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
@@ -4446,6 +6136,8 @@ model_verbosity = "high"
             model_reasoning_summary: ReasoningSummary::Detailed,
             model_supports_reasoning_summaries: None,
             model_verbosity: Some(Verbosity::High),
+            model_routing: None,
+            approval_explanations: None,
             personality: Some(Personality::Pragmatic),
             chatgpt_base_url: "https://chatgpt.com/backend-api/".to_string(),
             base_instructions: None,
@@ -4464,6 +6156,20 @@ model_verbosity = "high"
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
+            updates: UpdatesConfig::default(),
+            offline: false,
+            http_client: HttpClientConfig::default(),
+            exec_output_encoding: None,
+            exec_output_escapes: ExecOutputEscapes::Render,
+            macos_seatbelt: SeatbeltProfileAdditions::default(),
+            sandbox_allowed_devices: Vec::new(),
+            session_disk_quota_mb: None,
+            auto_approve_project_commands: false,
+            wire_log: None,
+            run_limits: RunLimits::default(),
+            patch_proposal_dir: None,
+            builtin_tools: BuiltinTools::default(),
+            read_only: false,
             disable_paste_burst: false,
             tui_notifications: Default::default(),
             tui_notification_method: Default::default(),
@@ -4474,6 +6180,10 @@ model_verbosity = "high"
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
             tui_status_line: None,
+            tui_status_line_priority: None,
+            tui_screen_reader: false,
+            tui_mouse: false,
+            tui_hyperlinks: true,
             otel: OtelConfig::default(),
         };
 
@@ -4511,6 +6221,7 @@ model_verbosity = "high"
             allowed_web_search_modes: Some(vec![
                 crate::config_loader::WebSearchModeRequirement::Cached,
             ]),
+            allowed_model_providers: None,
             mcp_servers: None,
             rules: None,
             enforce_residency: None,
@@ -5060,6 +6771,7 @@ mcp_oauth_callback_port = 5678
                 crate::config_loader::SandboxModeRequirement::ReadOnly,
             ]),
             allowed_web_search_modes: None,
+            allowed_model_providers: None,
             mcp_servers: None,
             rules: None,
             enforce_residency: None,
@@ -5178,6 +6890,39 @@ trust_level = "untrusted"
         );
         Ok(())
     }
+
+    #[tokio::test]
+    async fn model_provider_falls_back_when_disallowed_by_requirements() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        std::fs::write(
+            codex_home.path().join(CONFIG_TOML_FILE),
+            r#"model_provider = "ollama"
+"#,
+        )?;
+
+        let config = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .fallback_cwd(Some(codex_home.path().to_path_buf()))
+            .cloud_requirements(CloudRequirementsLoader::new(async {
+                Some(crate::config_loader::ConfigRequirementsToml {
+                    allowed_model_providers: Some(vec!["openai".to_string()]),
+                    ..Default::default()
+                })
+            }))
+            .build()
+            .await?;
+
+        assert_eq!(config.model_provider_id, "openai");
+        assert!(
+            config
+                .startup_warnings
+                .iter()
+                .any(|warning| warning.contains("`model_provider`")),
+            "{:?}",
+            config.startup_warnings
+        );
+        Ok(())
+    }
 }
 
 #[cfg(test)]
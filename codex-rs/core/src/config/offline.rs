@@ -0,0 +1,147 @@
+//! Offline mode (`offline = true` / `--offline`): the Codex process itself
+//! talks only to services on this machine.
+//!
+//! [`enforce_offline`] turns off every feature that reaches out on its own
+//! and rejects configurations that cannot work offline. The default HTTP
+//! client refuses non-loopback hosts as a backstop (see
+//! [`crate::default_client::set_default_client_offline`]).
+
+use std::io;
+use std::net::IpAddr;
+
+use codex_protocol::config_types::WebSearchMode;
+
+use crate::config::Config;
+use crate::config::types::McpServerTransportConfig;
+use crate::config::types::OtelExporterKind;
+use crate::features::Feature;
+
+/// True when `url` points at this machine: `localhost` (or a subdomain of
+/// it) or a loopback IP address.
+pub fn is_loopback_url(url: &url::Url) -> bool {
+    match url.host() {
+        Some(url::Host::Domain(domain)) => {
+            let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+            domain == "localhost" || domain.ends_with(".localhost")
+        }
+        Some(url::Host::Ipv4(ip)) => IpAddr::V4(ip).is_loopback(),
+        Some(url::Host::Ipv6(ip)) => IpAddr::V6(ip).is_loopback(),
+        None => false,
+    }
+}
+
+fn is_loopback_endpoint(endpoint: &str) -> bool {
+    url::Url::parse(endpoint).is_ok_and(|url| is_loopback_url(&url))
+}
+
+/// Applies offline mode to a loaded config. Fails when the model provider is
+/// not on this machine, since no session could run.
+pub(super) fn enforce_offline(config: &mut Config) -> io::Result<()> {
+    let provider_is_local = config
+        .model_provider
+        .base_url
+        .as_deref()
+        .is_some_and(is_loopback_endpoint);
+    if !provider_is_local {
+        let base_url = config
+            .model_provider
+            .base_url
+            .as_deref()
+            .unwrap_or("its default endpoint");
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "offline mode requires a model provider on localhost, but `{}` uses {base_url}. Use `--oss` or a provider whose base_url is a loopback address.",
+                config.model_provider_id
+            ),
+        ));
+    }
+
+    let mut dropped_servers: Vec<String> = config
+        .mcp_servers
+        .get()
+        .iter()
+        .filter(|(_, server)| !matches!(server.transport, McpServerTransportConfig::Stdio { .. }))
+        .map(|(name, _)| name.clone())
+        .collect();
+    if !dropped_servers.is_empty() {
+        let mut servers = config.mcp_servers.get().clone();
+        servers.retain(|name, _| !dropped_servers.contains(name));
+        config.mcp_servers.set(servers).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("offline mode cannot disable HTTP MCP servers: {err}"),
+            )
+        })?;
+        dropped_servers.sort();
+        config.startup_warnings.push(format!(
+            "Offline mode: skipping MCP servers that are not stdio: {}",
+            dropped_servers.join(", ")
+        ));
+    }
+
+    config
+        .web_search_mode
+        .set(WebSearchMode::Disabled)
+        .map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("offline mode cannot disable web search: {err}"),
+            )
+        })?;
+    config.web_search_provider = None;
+    config.features.disable(Feature::Apps);
+    // Chromium opens its own connections, which the default client's backstop
+    // does not see.
+    config.features.disable(Feature::Browser);
+
+    config.check_for_update_on_startup = false;
+    config.analytics_enabled = Some(false);
+    config.feedback_enabled = false;
+    for exporter in [
+        &mut config.otel.exporter,
+        &mut config.otel.trace_exporter,
+        &mut config.otel.metrics_exporter,
+    ] {
+        let keep = match exporter {
+            OtelExporterKind::None => true,
+            OtelExporterKind::Statsig => false,
+            OtelExporterKind::OtlpHttp { endpoint, .. }
+            | OtelExporterKind::OtlpGrpc { endpoint, .. } => is_loopback_endpoint(endpoint),
+        };
+        if !keep {
+            *exporter = OtelExporterKind::None;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn loopback_urls_are_recognized() {
+        for url in [
+            "http://localhost:11434/v1",
+            "http://LOCALHOST./v1",
+            "http://api.localhost/v1",
+            "http://127.0.0.1:1234",
+            "http://127.8.9.10",
+            "http://[::1]:8080/v1",
+        ] {
+            assert_eq!(is_loopback_endpoint(url), true, "{url}");
+        }
+        for url in [
+            "https://api.openai.com/v1",
+            "http://localhost.example.com",
+            "http://10.0.0.5:11434",
+            "http://[::2]",
+            "not a url",
+        ] {
+            assert_eq!(is_loopback_endpoint(url), false, "{url}");
+        }
+    }
+}
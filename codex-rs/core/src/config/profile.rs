@@ -28,6 +28,8 @@ pub struct ConfigProfile {
     pub model_verbosity: Option<Verbosity>,
     pub personality: Option<Personality>,
     pub chatgpt_base_url: Option<String>,
+    /// Named credential to use while this profile is active.
+    pub credential: Option<String>,
     /// Optional path to a file containing model instructions.
     pub model_instructions_file: Option<AbsolutePathBuf>,
     pub js_repl_node_path: Option<AbsolutePathBuf>,
@@ -40,6 +42,9 @@ pub struct ConfigProfile {
     pub experimental_use_freeform_apply_patch: Option<bool>,
     pub tools_web_search: Option<bool>,
     pub tools_view_image: Option<bool>,
+    /// Built-in tools switched on or off for this profile, overriding
+    /// `[tools]`.
+    pub tools: Option<crate::config::ToolsToml>,
     pub web_search: Option<WebSearchMode>,
     pub analytics: Option<crate::config::types::AnalyticsConfigToml>,
     #[serde(default)]
@@ -50,6 +55,9 @@ pub struct ConfigProfile {
     #[schemars(schema_with = "crate::config::schema::features_schema")]
     pub features: Option<crate::features::FeaturesToml>,
     pub oss_provider: Option<String>,
+    /// Daily and weekly usage limits, counted across every session that uses
+    /// this profile.
+    pub quota: Option<crate::config::types::UsageQuotaToml>,
 }
 
 impl From<ConfigProfile> for codex_app_server_protocol::Profile {
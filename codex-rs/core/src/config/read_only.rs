@@ -0,0 +1,58 @@
+//! Read-only mode (`--read-only`): a session that can look at a checkout but
+//! not change it.
+//!
+//! [`enforce_read_only`] pins the sandbox to read-only and the approval policy
+//! to `never` for the life of the session, so neither the model nor a later
+//! `/approvals` change can widen them. It also leaves out every tool that
+//! edits files or runs code outside the shell sandbox.
+//! MCP tools are filtered per turn instead, since only their annotations say
+//! whether they write (see `filter_read_only_mcp_tools`).
+
+use std::io;
+
+use crate::config::Config;
+use crate::config::Constrained;
+use crate::features::Feature;
+use crate::protocol::AskForApproval;
+use crate::protocol::SandboxPolicy;
+
+/// Tools that write or run code without going through the shell sandbox.
+const MUTATING_FEATURES: [Feature; 5] = [
+    Feature::JsRepl,
+    Feature::PyRepl,
+    Feature::Browser,
+    Feature::GitTool,
+    Feature::RequestRule,
+];
+
+/// Applies read-only mode to a loaded config. Fails when requirements do not
+/// allow a read-only sandbox or the `never` approval policy.
+pub(super) fn enforce_read_only(config: &mut Config) -> io::Result<()> {
+    let sandbox_policy = SandboxPolicy::new_read_only_policy();
+    config
+        .permissions
+        .sandbox_policy
+        .can_set(&sandbox_policy)
+        .map_err(|err| read_only_error("a read-only sandbox", err))?;
+    config
+        .permissions
+        .approval_policy
+        .can_set(&AskForApproval::Never)
+        .map_err(|err| read_only_error("the `never` approval policy", err))?;
+    config.permissions.sandbox_policy = Constrained::allow_only(sandbox_policy);
+    config.permissions.approval_policy = Constrained::allow_only(AskForApproval::Never);
+    config.did_user_set_custom_approval_policy_or_sandbox_mode = true;
+
+    config.builtin_tools.apply_patch = false;
+    for feature in MUTATING_FEATURES {
+        config.features.disable(feature);
+    }
+    Ok(())
+}
+
+fn read_only_error(what: &str, err: impl std::fmt::Display) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("read-only mode requires {what}: {err}"),
+    )
+}
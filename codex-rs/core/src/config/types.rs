@@ -8,10 +8,12 @@ pub use codex_protocol::config_types::AltScreenMode;
 pub use codex_protocol::config_types::ModeKind;
 pub use codex_protocol::config_types::Personality;
 pub use codex_protocol::config_types::WebSearchMode;
+use codex_protocol::openai_models::ReasoningEffort;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::fmt;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::time::Duration;
 use wildmatch::WildMatchPattern;
@@ -41,6 +43,24 @@ pub struct WindowsToml {
     pub sandbox: Option<WindowsSandboxModeToml>,
 }
 
+/// Additions to the builtin macOS Seatbelt profile, for tools that need
+/// system paths or services the stock profile does not grant.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct MacOsSeatbeltToml {
+    /// Extra paths (and everything under them) sandboxed commands may read.
+    #[serde(default)]
+    pub readable_paths: Vec<AbsolutePathBuf>,
+    /// Mach services sandboxed commands may look up, by global name, e.g.
+    /// `com.apple.CoreSimulator.CoreSimulatorService`.
+    #[serde(default)]
+    pub mach_services: Vec<String>,
+    /// Raw profile rules appended to the builtin profile. Each top-level form
+    /// must be an `(allow ...)` or `(deny ...)` rule.
+    #[serde(default)]
+    pub extra_rules: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum McpServerDisabledReason {
     Unknown,
@@ -301,6 +321,10 @@ pub enum UriBasedFileOpener {
     #[serde(rename = "cursor")]
     Cursor,
 
+    /// JetBrains IDEs, through the `idea://` scheme of the JetBrains Toolbox.
+    #[serde(rename = "idea")]
+    Idea,
+
     /// Option to disable the URI-based file opener.
     #[serde(rename = "none")]
     None,
@@ -313,6 +337,7 @@ impl UriBasedFileOpener {
             UriBasedFileOpener::VsCodeInsiders => Some("vscode-insiders"),
             UriBasedFileOpener::Windsurf => Some("windsurf"),
             UriBasedFileOpener::Cursor => Some("cursor"),
+            UriBasedFileOpener::Idea => Some("idea"),
             UriBasedFileOpener::None => None,
         }
     }
@@ -340,6 +365,294 @@ pub enum HistoryPersistence {
     None,
 }
 
+/// Settings for the hash-chained audit log of approvals, sandbox escalations,
+/// and executed commands.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct AuditLogConfigToml {
+    /// When `true`, every session appends to the audit log. Defaults to `false`.
+    pub enabled: Option<bool>,
+
+    /// Log file to append to. Defaults to `~/.codex/audit.jsonl`.
+    pub path: Option<AbsolutePathBuf>,
+}
+
+/// Where to forward security-relevant events (`[security_events]`).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SecurityEventsToml {
+    /// Syslog receiver: `udp://host:port`, `tcp://host:port`, or
+    /// `unix:///dev/log`.
+    pub syslog: Option<String>,
+
+    /// Splunk HTTP Event Collector endpoint, e.g.
+    /// `https://splunk.example.com:8088/services/collector/event`.
+    pub splunk_hec_url: Option<String>,
+
+    /// Environment variable holding the HEC token. Defaults to
+    /// `SPLUNK_HEC_TOKEN`.
+    pub splunk_hec_token_env: Option<String>,
+
+    /// Splunk index for the events. Defaults to the token's default index.
+    pub splunk_index: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyslogTarget {
+    Udp(String),
+    Tcp(String),
+    Unix(PathBuf),
+}
+
+impl std::str::FromStr for SyslogTarget {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "security_events.syslog must be udp://host:port, tcp://host:port or unix:///path (got `{raw}`)"
+            )
+        };
+        let (scheme, rest) = raw.split_once("://").ok_or_else(invalid)?;
+        let host_port = || {
+            rest.rsplit_once(':')
+                .is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok())
+        };
+        match scheme {
+            "udp" if host_port() => Ok(Self::Udp(rest.to_string())),
+            "tcp" if host_port() => Ok(Self::Tcp(rest.to_string())),
+            "unix" if rest.starts_with('/') => Ok(Self::Unix(PathBuf::from(rest))),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplunkHecConfig {
+    pub url: String,
+    pub token_env: String,
+    pub index: Option<String>,
+}
+
+/// Resolved `[security_events]`; `None` in [`crate::config::Config`] when no
+/// destination is configured.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityEventsConfig {
+    pub syslog: Option<SyslogTarget>,
+    pub splunk_hec: Option<SplunkHecConfig>,
+}
+
+impl SecurityEventsConfig {
+    pub fn from_toml(toml: SecurityEventsToml) -> Result<Option<Self>, String> {
+        let syslog = toml.syslog.as_deref().map(str::parse).transpose()?;
+        let splunk_hec = match toml.splunk_hec_url {
+            Some(url) if !(url.starts_with("https://") || url.starts_with("http://")) => {
+                return Err(format!(
+                    "security_events.splunk_hec_url must be an http(s) URL (got `{url}`)"
+                ));
+            }
+            Some(url) => Some(SplunkHecConfig {
+                url,
+                token_env: toml
+                    .splunk_hec_token_env
+                    .unwrap_or_else(|| "SPLUNK_HEC_TOKEN".to_string()),
+                index: toml.splunk_index,
+            }),
+            None => None,
+        };
+        if syslog.is_none() && splunk_hec.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(Self { syslog, splunk_hec }))
+    }
+}
+
+/// Settings for model-written explanations on command approval prompts.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ApprovalExplanationsToml {
+    /// When `true`, complex or risky commands are explained before their
+    /// approval prompt is shown. Defaults to `false`.
+    pub enabled: Option<bool>,
+
+    /// Model that writes the explanations. Defaults to
+    /// `model_routing.fast_model` when set, otherwise the session's model.
+    pub model: Option<String>,
+
+    /// How long to wait for an explanation before showing the prompt without
+    /// one, in milliseconds. Defaults to 10000.
+    pub timeout_ms: Option<u64>,
+}
+
+/// Resolved approval explanation settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApprovalExplanationsConfig {
+    pub model: Option<String>,
+    pub timeout: Duration,
+}
+
+/// Settings for running trivial turns on a cheaper model.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ModelRoutingToml {
+    /// Model for turns that look trivial, such as short questions and file
+    /// reads. Routing is off when unset.
+    pub fast_model: Option<String>,
+
+    /// Reasoning effort for turns on `fast_model`. Defaults to that model's
+    /// default effort.
+    pub fast_reasoning_effort: Option<ReasoningEffort>,
+
+    /// Longest user message, in characters, that can run on `fast_model`.
+    /// Defaults to 300.
+    pub max_fast_input_chars: Option<usize>,
+}
+
+/// Resolved model routing settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelRoutingConfig {
+    pub fast_model: String,
+    pub fast_reasoning_effort: Option<ReasoningEffort>,
+    pub max_fast_input_chars: usize,
+}
+
+/// Settings for where `codex upgrade` and the startup update check look for
+/// new releases.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct UpdatesToml {
+    /// Release channel to follow. Defaults to `stable`.
+    pub channel: Option<UpdateChannel>,
+
+    /// Base URL of a self-hosted release server. Manifests are read from
+    /// `<endpoint>/<channel>/latest.json`. Requires `public_key`.
+    pub endpoint: Option<String>,
+
+    /// Base64-encoded Ed25519 public key that must have signed every binary
+    /// downloaded from `endpoint`.
+    pub public_key: Option<String>,
+}
+
+/// Release channel followed by updates.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    /// Published releases.
+    #[default]
+    Stable,
+    /// Builds from the main branch. Only available from a self-hosted `endpoint`.
+    Nightly,
+}
+
+impl UpdateChannel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UpdateChannel::Stable => "stable",
+            UpdateChannel::Nightly => "nightly",
+        }
+    }
+}
+
+impl fmt::Display for UpdateChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Resolved update settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UpdatesConfig {
+    pub channel: UpdateChannel,
+    /// Self-hosted release server, or `None` to use the public releases.
+    pub release_server: Option<ReleaseServer>,
+}
+
+/// A self-hosted release server and the key its binaries are signed with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseServer {
+    pub endpoint: String,
+    /// Raw 32-byte Ed25519 public key.
+    pub public_key: Vec<u8>,
+}
+
+/// Settings for caching model responses keyed on the model and a hash of the
+/// full request, so identical runs replay without API calls.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ResponseCacheConfigToml {
+    /// When `true`, model responses are read from and written to the cache.
+    /// Defaults to `false`.
+    pub enabled: Option<bool>,
+
+    /// Directory holding cached responses. Defaults to `~/.codex/response-cache`.
+    pub dir: Option<AbsolutePathBuf>,
+
+    /// How the cache is used. Defaults to `read-write`.
+    pub mode: Option<ResponseCacheMode>,
+
+    /// Mixed into every cache key. Change it to invalidate all existing entries.
+    pub salt: Option<String>,
+}
+
+/// How the response cache is used.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResponseCacheMode {
+    /// Replay cached responses and record the ones that are missing.
+    #[default]
+    ReadWrite,
+    /// Ignore cached responses, call the model, and overwrite the entries.
+    Refresh,
+    /// Only replay cached responses; a request that is not cached fails.
+    ReplayOnly,
+}
+
+/// Resolved response cache settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseCacheConfig {
+    pub dir: PathBuf,
+    pub mode: ResponseCacheMode,
+    pub salt: Option<String>,
+}
+
+/// How terminal control sequences in command output are handled before the
+/// output is stored or sent to the model.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExecOutputEscapes {
+    /// Keep the output exactly as the command wrote it.
+    Raw,
+    /// Remove escape sequences, and put each carriage-return redraw on its own line.
+    Strip,
+    /// Remove escape sequences, and keep only the last redraw of each line.
+    KeepLastLine,
+    /// Replay cursor movement and erases, and keep the text a terminal would show.
+    #[default]
+    Render,
+}
+
+/// Storage format for rollout files.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RolloutCompression {
+    /// Plain JSONL (`rollout-*.jsonl`).
+    #[default]
+    None,
+    /// zstd-compressed JSONL (`rollout-*.jsonl.zst`).
+    Zstd,
+}
+
+/// Where new rollout files are written.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionStorage {
+    /// `~/.codex/sessions`, shared by every project on the machine.
+    #[default]
+    Home,
+    /// `.codex/sessions` under the project root of the session's cwd.
+    Project,
+}
+
 // ===== Analytics configuration =====
 
 /// Analytics settings loaded from config.toml. Fields are optional so we can apply defaults.
@@ -425,6 +738,703 @@ impl From<MemoriesToml> for MemoriesConfig {
     }
 }
 
+// ===== Web search provider configuration =====
+
+pub const DEFAULT_WEB_SEARCH_MAX_RESULTS: usize = 5;
+pub const DEFAULT_WEB_SEARCH_FETCH_RESULTS: usize = 3;
+pub const DEFAULT_WEB_SEARCH_MAX_BYTES: usize = 32 * 1024;
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum WebSearchProviderKind {
+    /// Bing Web Search API.
+    Bing,
+    /// Brave Search API.
+    Brave,
+    /// A self-hosted SearXNG instance with the JSON output format enabled.
+    Searxng,
+}
+
+/// Search backend for the `web_search` function tool, loaded from config.toml.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct WebSearchProviderToml {
+    /// Which search API to query.
+    pub kind: WebSearchProviderKind,
+    /// Override the API base URL. Required for SearXNG.
+    pub base_url: Option<String>,
+    /// Environment variable holding the API key (Bing and Brave).
+    pub api_key_env: Option<String>,
+    /// Maximum number of ranked results to return.
+    pub max_results: Option<usize>,
+    /// Number of top results whose pages are fetched and extracted.
+    pub fetch_results: Option<usize>,
+    /// Byte budget for the whole tool output of a single call.
+    pub max_bytes: Option<usize>,
+}
+
+/// Effective web search provider settings after defaults are applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebSearchProviderConfig {
+    pub kind: WebSearchProviderKind,
+    pub base_url: String,
+    pub api_key_env: Option<String>,
+    pub max_results: usize,
+    pub fetch_results: usize,
+    pub max_bytes: usize,
+}
+
+impl From<WebSearchProviderToml> for WebSearchProviderConfig {
+    fn from(toml: WebSearchProviderToml) -> Self {
+        let (default_base_url, default_api_key_env) = match toml.kind {
+            WebSearchProviderKind::Bing => ("https://api.bing.microsoft.com", Some("BING_API_KEY")),
+            WebSearchProviderKind::Brave => {
+                ("https://api.search.brave.com", Some("BRAVE_SEARCH_API_KEY"))
+            }
+            WebSearchProviderKind::Searxng => ("http://localhost:8080", None),
+        };
+        Self {
+            kind: toml.kind,
+            base_url: toml
+                .base_url
+                .unwrap_or_else(|| default_base_url.to_string())
+                .trim_end_matches('/')
+                .to_string(),
+            api_key_env: toml
+                .api_key_env
+                .or_else(|| default_api_key_env.map(str::to_string)),
+            max_results: toml
+                .max_results
+                .unwrap_or(DEFAULT_WEB_SEARCH_MAX_RESULTS)
+                .clamp(1, 20),
+            fetch_results: toml
+                .fetch_results
+                .unwrap_or(DEFAULT_WEB_SEARCH_FETCH_RESULTS)
+                .min(10),
+            max_bytes: toml.max_bytes.unwrap_or(DEFAULT_WEB_SEARCH_MAX_BYTES),
+        }
+    }
+}
+
+// ===== Agent roles and orchestrations =====
+
+/// A named agent role, loaded from `[agent_roles.<name>]` in config.toml.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct AgentRoleToml {
+    /// Short summary of what the role is for, shown to roles that can delegate to it.
+    pub description: Option<String>,
+    /// Model the role runs. Defaults to the session model.
+    pub model: Option<String>,
+    /// Reasoning effort the role runs with.
+    pub model_reasoning_effort: Option<ReasoningEffort>,
+    /// Developer instructions added to every turn the role runs.
+    pub instructions: Option<String>,
+    /// Names of the tools the role may call. All tools are available when unset.
+    pub tools: Option<Vec<String>>,
+    /// Force a read-only sandbox for the role.
+    pub read_only: Option<bool>,
+    /// Token budget for one pipeline stage run by this role.
+    pub max_tokens: Option<i64>,
+}
+
+/// How user turns are routed across the roles of an orchestration.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum TurnRouting {
+    /// The first role handles every turn and can delegate tasks to the other roles.
+    #[default]
+    Lead,
+    /// Every turn runs through each role in order; the last role's answer ends the turn.
+    Pipeline,
+}
+
+/// A named set of roles plus a routing policy, loaded from `[orchestrations.<name>]`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct OrchestrationToml {
+    /// Names of the `agent_roles` taking part, in order.
+    pub roles: Vec<String>,
+    /// How user turns are routed across `roles`. Defaults to `lead`.
+    pub routing: Option<TurnRouting>,
+}
+
+/// Effective settings for one agent role.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentRoleConfig {
+    pub name: String,
+    pub description: Option<String>,
+    pub model: Option<String>,
+    pub reasoning_effort: Option<ReasoningEffort>,
+    pub instructions: Option<String>,
+    pub tools: Option<Vec<String>>,
+    pub read_only: bool,
+    pub max_tokens: Option<i64>,
+}
+
+impl AgentRoleConfig {
+    pub fn from_toml(name: &str, toml: AgentRoleToml) -> Self {
+        Self {
+            name: name.to_string(),
+            description: toml.description,
+            model: toml.model,
+            reasoning_effort: toml.model_reasoning_effort,
+            instructions: toml
+                .instructions
+                .map(|instructions| instructions.trim().to_string())
+                .filter(|instructions| !instructions.is_empty()),
+            tools: toml.tools,
+            read_only: toml.read_only.unwrap_or(false),
+            max_tokens: toml.max_tokens,
+        }
+    }
+}
+
+/// The orchestration selected for this session, with its roles resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrchestrationConfig {
+    pub name: String,
+    pub routing: TurnRouting,
+    /// Never empty; the first role leads under [`TurnRouting::Lead`].
+    pub roles: Vec<AgentRoleConfig>,
+}
+
+// ===== Commit authoring configuration =====
+
+/// Settings for commits created through `Op::CommitChanges`, loaded from config.toml.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct CommitToml {
+    /// Identities appended as `Co-authored-by:` trailers, e.g. `"Codex <codex@openai.com>"`.
+    pub co_authors: Option<Vec<String>>,
+}
+
+/// Effective commit authoring settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommitConfig {
+    pub co_authors: Vec<String>,
+}
+
+impl From<CommitToml> for CommitConfig {
+    fn from(toml: CommitToml) -> Self {
+        Self {
+            co_authors: toml
+                .co_authors
+                .unwrap_or_default()
+                .into_iter()
+                .map(|co_author| co_author.trim().to_string())
+                .filter(|co_author| !co_author.is_empty())
+                .collect(),
+        }
+    }
+}
+
+// ===== Forge host configuration =====
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ForgeHostKind {
+    /// GitHub Enterprise Server, with its API at `https://<host>/api/v3`.
+    Github,
+    /// Self-hosted GitLab, with its API at `https://<host>/api/v4`.
+    Gitlab,
+}
+
+/// A self-hosted forge pull requests can be opened on, loaded from
+/// `[forge_hosts."<host>"]` in config.toml.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ForgeHostToml {
+    pub kind: ForgeHostKind,
+    /// Name of the Codex secret, or environment variable, holding the API token for this host.
+    pub token: String,
+}
+
+// ===== Completion gate configuration =====
+
+const DEFAULT_COMPLETION_GATE_MAX_RETRIES: u32 = 2;
+const DEFAULT_COMPLETION_GATE_TIMEOUT_SEC: u64 = 600;
+
+/// Checks that run when the model finishes a turn that changed files, loaded
+/// from `[completion_gate]` in config.toml.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct CompletionGateToml {
+    /// Command lines run in order through the user's shell, e.g. `"cargo test"`.
+    pub commands: Option<Vec<String>>,
+    /// How many times failures are sent back to the model before the turn
+    /// ends anyway. Defaults to 2.
+    pub max_retries: Option<u32>,
+    /// Timeout for each command, in seconds. Defaults to 600.
+    pub timeout_sec: Option<u64>,
+}
+
+/// Effective completion gate settings. The gate is off when `commands` is
+/// empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletionGateConfig {
+    pub commands: Vec<String>,
+    pub max_retries: u32,
+    pub timeout: Duration,
+}
+
+impl Default for CompletionGateConfig {
+    fn default() -> Self {
+        Self {
+            commands: Vec::new(),
+            max_retries: DEFAULT_COMPLETION_GATE_MAX_RETRIES,
+            timeout: Duration::from_secs(DEFAULT_COMPLETION_GATE_TIMEOUT_SEC),
+        }
+    }
+}
+
+impl From<CompletionGateToml> for CompletionGateConfig {
+    fn from(toml: CompletionGateToml) -> Self {
+        Self {
+            commands: toml
+                .commands
+                .unwrap_or_default()
+                .into_iter()
+                .map(|command| command.trim().to_string())
+                .filter(|command| !command.is_empty())
+                .collect(),
+            max_retries: toml
+                .max_retries
+                .unwrap_or(DEFAULT_COMPLETION_GATE_MAX_RETRIES),
+            timeout: Duration::from_secs(
+                toml.timeout_sec
+                    .filter(|timeout| *timeout > 0)
+                    .unwrap_or(DEFAULT_COMPLETION_GATE_TIMEOUT_SEC),
+            ),
+        }
+    }
+}
+
+// ===== Context pack configuration =====
+
+const DEFAULT_CONTEXT_PACK_MAX_TOKENS: usize = 2_000;
+const DEFAULT_CONTEXT_PACK_CACHE_TTL_SEC: u64 = 300;
+const DEFAULT_CONTEXT_PACK_TIMEOUT_SEC: u64 = 30;
+
+/// When a context pack is added to the conversation.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ContextPackInject {
+    /// Part of the initial context of every session.
+    SessionStart,
+    /// Only when requested with `/context <name>`.
+    #[default]
+    OnDemand,
+}
+
+/// A context provider from `[context_packs.<name>]` in config.toml. Exactly
+/// one of `command` and `files` must be set.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ContextPackToml {
+    /// Command line run through the user's shell in the working directory,
+    /// e.g. `"kubectl config current-context"`. Its output is the pack.
+    pub command: Option<String>,
+    /// Glob patterns, relative to the working directory, of files whose
+    /// contents are the pack, e.g. `["docs/runbooks/*.md"]`.
+    pub files: Option<Vec<String>>,
+    /// When the pack is added. Defaults to `on_demand`.
+    pub inject: Option<ContextPackInject>,
+    /// Approximate token budget; longer content is truncated. Defaults to 2000.
+    pub max_tokens: Option<usize>,
+    /// How long gathered content is reused, in seconds. Defaults to 300; `0`
+    /// gathers it again every time.
+    pub cache_ttl_sec: Option<u64>,
+    /// Timeout for `command`, in seconds. Defaults to 30.
+    pub timeout_sec: Option<u64>,
+}
+
+/// Where a context pack's content comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContextPackSource {
+    Command(String),
+    Files(Vec<String>),
+}
+
+/// Effective settings of one context pack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextPackConfig {
+    pub name: String,
+    pub source: ContextPackSource,
+    pub inject: ContextPackInject,
+    pub max_tokens: usize,
+    pub cache_ttl: Duration,
+    pub timeout: Duration,
+}
+
+impl ContextPackConfig {
+    pub fn from_toml(name: &str, toml: ContextPackToml) -> Result<Self, String> {
+        let command = toml
+            .command
+            .map(|command| command.trim().to_string())
+            .filter(|command| !command.is_empty());
+        let files: Vec<String> = toml
+            .files
+            .unwrap_or_default()
+            .into_iter()
+            .map(|pattern| pattern.trim().to_string())
+            .filter(|pattern| !pattern.is_empty())
+            .collect();
+        let source = match (command, files.is_empty()) {
+            (Some(command), true) => ContextPackSource::Command(command),
+            (None, false) => ContextPackSource::Files(files),
+            (Some(_), false) => {
+                return Err(format!(
+                    "context pack `{name}` sets both `command` and `files`; use one"
+                ));
+            }
+            (None, true) => {
+                return Err(format!(
+                    "context pack `{name}` needs a `command` or `files`"
+                ));
+            }
+        };
+        Ok(Self {
+            name: name.to_string(),
+            source,
+            inject: toml.inject.unwrap_or_default(),
+            max_tokens: toml
+                .max_tokens
+                .filter(|max_tokens| *max_tokens > 0)
+                .unwrap_or(DEFAULT_CONTEXT_PACK_MAX_TOKENS),
+            cache_ttl: Duration::from_secs(
+                toml.cache_ttl_sec
+                    .unwrap_or(DEFAULT_CONTEXT_PACK_CACHE_TTL_SEC),
+            ),
+            timeout: Duration::from_secs(
+                toml.timeout_sec
+                    .filter(|timeout| *timeout > 0)
+                    .unwrap_or(DEFAULT_CONTEXT_PACK_TIMEOUT_SEC),
+            ),
+        })
+    }
+}
+
+// ===== Resume summary configuration =====
+
+const DEFAULT_RESUME_SUMMARY_KEEP_TURNS: usize = 3;
+const DEFAULT_RESUME_SUMMARY_MIN_TOKENS: i64 = 30_000;
+
+/// Summarization of older turns when resuming a session, loaded from
+/// `[resume_summary]` in config.toml.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ResumeSummaryToml {
+    /// Most recent user turns sent verbatim; older ones are replaced by a
+    /// summary. Defaults to 3.
+    pub keep_turns: Option<usize>,
+    /// Only summarize when the older turns hold at least this many tokens.
+    /// Defaults to 30000.
+    pub min_tokens: Option<i64>,
+}
+
+/// Effective resume summary settings. Resuming replays the whole history
+/// unless `[resume_summary]` is present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResumeSummaryConfig {
+    pub keep_turns: usize,
+    pub min_tokens: i64,
+}
+
+impl From<ResumeSummaryToml> for ResumeSummaryConfig {
+    fn from(toml: ResumeSummaryToml) -> Self {
+        Self {
+            keep_turns: toml
+                .keep_turns
+                .filter(|keep_turns| *keep_turns > 0)
+                .unwrap_or(DEFAULT_RESUME_SUMMARY_KEEP_TURNS),
+            min_tokens: toml.min_tokens.unwrap_or(DEFAULT_RESUME_SUMMARY_MIN_TOKENS),
+        }
+    }
+}
+
+// ===== Retry budget configuration =====
+
+const DEFAULT_RETRY_BUDGET_MAX_RECOVERIES: u32 = 2;
+
+/// What a turn does when a model request still fails after all of its stream
+/// retries.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryExhaustedPolicy {
+    /// Let the user choose in the TUI. Other clients, such as `codex exec`,
+    /// abort.
+    #[default]
+    Ask,
+    /// Send the same request again.
+    RetryNow,
+    /// Continue the turn with `fallback_model`.
+    SwitchModel,
+    /// Replace the largest tool output in the history with a short note and
+    /// send the request again.
+    DropLargestContextItem,
+    /// End the turn with the error.
+    Abort,
+}
+
+/// Recovery from model requests that keep failing, loaded from
+/// `[retry_budget]` in config.toml.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct RetryBudgetToml {
+    /// What to do once the stream retries are used up. Defaults to `ask`.
+    pub on_exhausted: Option<RetryExhaustedPolicy>,
+    /// Model to continue with after `switch_model`. Required when
+    /// `on_exhausted` is `switch_model`; otherwise only offered in the TUI.
+    pub fallback_model: Option<String>,
+    /// How many times one turn recovers before the next failure ends it.
+    /// Defaults to 2.
+    pub max_recoveries: Option<u32>,
+}
+
+/// Effective retry budget settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryBudgetConfig {
+    pub on_exhausted: RetryExhaustedPolicy,
+    pub fallback_model: Option<String>,
+    pub max_recoveries: u32,
+}
+
+impl Default for RetryBudgetConfig {
+    fn default() -> Self {
+        Self {
+            on_exhausted: RetryExhaustedPolicy::Ask,
+            fallback_model: None,
+            max_recoveries: DEFAULT_RETRY_BUDGET_MAX_RECOVERIES,
+        }
+    }
+}
+
+impl TryFrom<RetryBudgetToml> for RetryBudgetConfig {
+    type Error = String;
+
+    fn try_from(toml: RetryBudgetToml) -> Result<Self, Self::Error> {
+        let on_exhausted = toml.on_exhausted.unwrap_or_default();
+        let fallback_model = toml
+            .fallback_model
+            .map(|model| model.trim().to_string())
+            .filter(|model| !model.is_empty());
+        if on_exhausted == RetryExhaustedPolicy::SwitchModel && fallback_model.is_none() {
+            return Err(
+                "retry_budget.on_exhausted = \"switch_model\" needs retry_budget.fallback_model"
+                    .to_string(),
+            );
+        }
+        Ok(Self {
+            on_exhausted,
+            fallback_model,
+            max_recoveries: toml
+                .max_recoveries
+                .unwrap_or(DEFAULT_RETRY_BUDGET_MAX_RECOVERIES),
+        })
+    }
+}
+
+// ===== Approvals bridge configuration =====
+
+const DEFAULT_APPROVALS_BRIDGE_LISTEN: SocketAddr =
+    SocketAddr::new(std::net::IpAddr::V4(std::net::Ipv4Addr::LOCALHOST), 8788);
+const DEFAULT_APPROVALS_BRIDGE_SECRET_ENV: &str = "CODEX_APPROVALS_BRIDGE_SECRET";
+const DEFAULT_APPROVALS_BRIDGE_TIMEOUT_SECS: u64 = 30 * 60;
+
+/// Payload shape of the approvals bridge webhook.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalsBridgeFormat {
+    /// Slack incoming webhook: Block Kit message with one button per decision.
+    #[default]
+    Slack,
+    /// Matrix hookshot generic webhook: `text` and `html` bodies with links.
+    Matrix,
+}
+
+/// Approval requests of `codex exec --approvals-bridge`, posted to a chat
+/// channel, loaded from `[approvals_bridge]` in config.toml.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ApprovalsBridgeToml {
+    /// Incoming webhook that approval requests are posted to.
+    pub webhook_url: Option<String>,
+    /// Payload shape the webhook expects. Defaults to `slack`.
+    pub format: Option<ApprovalsBridgeFormat>,
+    /// Address of the listener that receives decisions. Defaults to
+    /// `127.0.0.1:8788`.
+    pub listen_addr: Option<SocketAddr>,
+    /// Base URL of that listener as reachable from the chat client, for
+    /// example behind a reverse proxy or tunnel. Defaults to
+    /// `http://<listen_addr>`.
+    pub public_url: Option<String>,
+    /// Environment variable holding the key that signs decision links.
+    /// Defaults to `CODEX_APPROVALS_BRIDGE_SECRET`; without it each run
+    /// uses a random key.
+    pub secret_env: Option<String>,
+    /// Seconds to wait for a decision before declining. Defaults to 1800.
+    pub timeout_secs: Option<u64>,
+}
+
+/// Effective approvals bridge settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApprovalsBridgeConfig {
+    pub webhook_url: String,
+    pub format: ApprovalsBridgeFormat,
+    pub listen_addr: SocketAddr,
+    pub public_url: Option<String>,
+    pub secret_env: String,
+    pub timeout: Duration,
+}
+
+impl TryFrom<ApprovalsBridgeToml> for ApprovalsBridgeConfig {
+    type Error = String;
+
+    fn try_from(toml: ApprovalsBridgeToml) -> Result<Self, Self::Error> {
+        let webhook_url = toml
+            .webhook_url
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty())
+            .ok_or_else(|| "approvals_bridge.webhook_url is required".to_string())?;
+        let public_url = toml
+            .public_url
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty());
+        if let Some(url) = &public_url
+            && !url.starts_with("https://")
+            && !url.starts_with("http://")
+        {
+            return Err(format!(
+                "approvals_bridge.public_url must start with http:// or https:// (got `{url}`)"
+            ));
+        }
+        Ok(Self {
+            webhook_url,
+            format: toml.format.unwrap_or_default(),
+            listen_addr: toml.listen_addr.unwrap_or(DEFAULT_APPROVALS_BRIDGE_LISTEN),
+            public_url,
+            secret_env: toml
+                .secret_env
+                .unwrap_or_else(|| DEFAULT_APPROVALS_BRIDGE_SECRET_ENV.to_string()),
+            timeout: Duration::from_secs(
+                toml.timeout_secs
+                    .filter(|secs| *secs > 0)
+                    .unwrap_or(DEFAULT_APPROVALS_BRIDGE_TIMEOUT_SECS),
+            ),
+        })
+    }
+}
+
+// ===== Usage quota configuration =====
+
+/// Daily and weekly usage limits of a profile, loaded from
+/// `[profiles.<name>.quota]` in config.toml. Days and weeks are the last 24
+/// hours and the last 7 days.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct UsageQuotaToml {
+    /// Tokens, input and output, the profile may use per day.
+    pub daily_tokens: Option<u64>,
+    /// Tokens, input and output, the profile may use per week.
+    pub weekly_tokens: Option<u64>,
+    /// Dollars the profile may spend per day. Needs `input_usd_per_million`
+    /// and `output_usd_per_million`.
+    pub daily_usd: Option<f64>,
+    /// Dollars the profile may spend per week. Needs `input_usd_per_million`
+    /// and `output_usd_per_million`.
+    pub weekly_usd: Option<f64>,
+    /// Price of a million uncached input tokens, in dollars.
+    pub input_usd_per_million: Option<f64>,
+    /// Price of a million cached input tokens, in dollars. Defaults to
+    /// `input_usd_per_million`.
+    pub cached_input_usd_per_million: Option<f64>,
+    /// Price of a million output tokens, reasoning included, in dollars.
+    pub output_usd_per_million: Option<f64>,
+}
+
+/// Dollars per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TokenPrices {
+    pub input: f64,
+    pub cached_input: f64,
+    pub output: f64,
+}
+
+impl TokenPrices {
+    /// Dollars for the given token counts. `cached_input_tokens` is the part
+    /// of `input_tokens` that was served from the prompt cache.
+    pub fn usd(&self, input_tokens: i64, cached_input_tokens: i64, output_tokens: i64) -> f64 {
+        let input_tokens = input_tokens.max(0);
+        let cached = cached_input_tokens.clamp(0, input_tokens);
+        let uncached = input_tokens - cached;
+        (uncached as f64 * self.input
+            + cached as f64 * self.cached_input
+            + output_tokens.max(0) as f64 * self.output)
+            / 1_000_000.0
+    }
+}
+
+/// Effective quota of the active profile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageQuotaConfig {
+    /// Profile whose usage counts against the quota.
+    pub profile: String,
+    pub daily_tokens: Option<u64>,
+    pub weekly_tokens: Option<u64>,
+    pub daily_usd: Option<f64>,
+    pub weekly_usd: Option<f64>,
+    /// Set whenever a dollar limit is.
+    pub prices: Option<TokenPrices>,
+}
+
+impl UsageQuotaConfig {
+    /// Resolves the quota of `profile`; `None` when it sets no limit. Zero
+    /// limits count as unset.
+    pub fn from_toml(profile: &str, toml: UsageQuotaToml) -> Result<Option<Self>, String> {
+        let amount = |key: &str, value: Option<f64>| match value {
+            Some(value) if !value.is_finite() || value < 0.0 => Err(format!(
+                "profiles.{profile}.quota.{key} must be a positive number"
+            )),
+            value => Ok(value.filter(|value| *value > 0.0)),
+        };
+        let daily_usd = amount("daily_usd", toml.daily_usd)?;
+        let weekly_usd = amount("weekly_usd", toml.weekly_usd)?;
+        let input = amount("input_usd_per_million", toml.input_usd_per_million)?;
+        let cached_input = amount(
+            "cached_input_usd_per_million",
+            toml.cached_input_usd_per_million,
+        )?;
+        let output = amount("output_usd_per_million", toml.output_usd_per_million)?;
+        let prices = match (input, output) {
+            (Some(input), Some(output)) => Some(TokenPrices {
+                input,
+                cached_input: cached_input.unwrap_or(input),
+                output,
+            }),
+            _ => None,
+        };
+        if (daily_usd.is_some() || weekly_usd.is_some()) && prices.is_none() {
+            return Err(format!(
+                "dollar limits in profiles.{profile}.quota need input_usd_per_million and output_usd_per_million"
+            ));
+        }
+        let quota = Self {
+            profile: profile.to_string(),
+            daily_tokens: toml.daily_tokens.filter(|tokens| *tokens > 0),
+            weekly_tokens: toml.weekly_tokens.filter(|tokens| *tokens > 0),
+            daily_usd,
+            weekly_usd,
+            prices,
+        };
+        let unlimited = quota.daily_tokens.is_none()
+            && quota.weekly_tokens.is_none()
+            && quota.daily_usd.is_none()
+            && quota.weekly_usd.is_none();
+        Ok((!unlimited).then_some(quota))
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum AppDisabledReason {
@@ -483,6 +1493,50 @@ pub struct OtelTlsConfig {
     pub client_private_key: Option<AbsolutePathBuf>,
 }
 
+/// Proxy and TLS settings for Codex's own HTTP requests. The top-level
+/// `[http_client]` table applies to every request; a provider's
+/// `http_client` table overrides it field by field for that provider.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HttpClientConfig {
+    /// Proxy URL: `http://`, `https://`, `socks5://` or `socks5h://`,
+    /// optionally with `user:password@`. Replaces the `HTTPS_PROXY`/`ALL_PROXY`
+    /// environment variables when set.
+    pub proxy: Option<String>,
+    /// Hosts that bypass the proxy, in `NO_PROXY` syntax (`example.com`,
+    /// `.corp.example`, `10.0.0.0/8`).
+    pub no_proxy: Option<Vec<String>>,
+    /// PEM bundle of extra root certificates to trust, such as the root of a
+    /// TLS-inspecting corporate proxy. The system roots stay trusted.
+    pub ca_certificate: Option<AbsolutePathBuf>,
+    /// PEM client certificate for mTLS. Requires `client_private_key`.
+    pub client_certificate: Option<AbsolutePathBuf>,
+    /// PEM private key for `client_certificate`.
+    pub client_private_key: Option<AbsolutePathBuf>,
+}
+
+impl HttpClientConfig {
+    /// `self` with unset fields taken from `base`.
+    pub fn or(&self, base: &HttpClientConfig) -> HttpClientConfig {
+        HttpClientConfig {
+            proxy: self.proxy.clone().or_else(|| base.proxy.clone()),
+            no_proxy: self.no_proxy.clone().or_else(|| base.no_proxy.clone()),
+            ca_certificate: self
+                .ca_certificate
+                .clone()
+                .or_else(|| base.ca_certificate.clone()),
+            client_certificate: self
+                .client_certificate
+                .clone()
+                .or_else(|| base.client_certificate.clone()),
+            client_private_key: self
+                .client_private_key
+                .clone()
+                .or_else(|| base.client_private_key.clone()),
+        }
+    }
+}
+
 /// Which OTEL exporter to use.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
 #[schemars(deny_unknown_fields)]
@@ -525,6 +1579,10 @@ pub struct OtelConfigToml {
 
     /// Optional metrics exporter
     pub metrics_exporter: Option<OtelExporterKind>,
+
+    /// Serve Prometheus text metrics on `GET /metrics` at this address (for
+    /// example `127.0.0.1:9464`). Independent of `metrics_exporter`.
+    pub prometheus_listen_addr: Option<SocketAddr>,
 }
 
 /// Effective OTEL settings after defaults are applied.
@@ -535,6 +1593,7 @@ pub struct OtelConfig {
     pub exporter: OtelExporterKind,
     pub trace_exporter: OtelExporterKind,
     pub metrics_exporter: OtelExporterKind,
+    pub prometheus_listen_addr: Option<SocketAddr>,
 }
 
 impl Default for OtelConfig {
@@ -545,6 +1604,7 @@ impl Default for OtelConfig {
             exporter: OtelExporterKind::None,
             trace_exporter: OtelExporterKind::None,
             metrics_exporter: OtelExporterKind::Statsig,
+            prometheus_listen_addr: None,
         }
     }
 }
@@ -626,6 +1686,37 @@ pub struct Tui {
     /// When set, the TUI renders the selected items as the status line.
     #[serde(default)]
     pub status_line: Option<Vec<String>>,
+
+    /// Status line items to keep longest when the line does not fit, most
+    /// important first.
+    ///
+    /// When the terminal is too narrow, items are dropped from the status
+    /// line until the rest fit: items missing from this list first, then the
+    /// listed ones from the end of the list. Unset keeps the built-in order.
+    #[serde(default)]
+    pub status_line_priority: Option<Vec<String>>,
+
+    /// Render for screen readers: no spinners or animations, no box-drawing
+    /// characters, state changes announced as plain lines, and words next to
+    /// indicators that are otherwise only shown by color.
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub screen_reader: bool,
+
+    /// Capture the mouse in full-screen views such as the transcript
+    /// (`Ctrl+T`): wheel scrolling, click to select a cell or open a
+    /// `path:line` reference, and drag to copy. The main view leaves the
+    /// mouse to the terminal so its scrollback and selection keep working.
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub mouse: bool,
+
+    /// Make URLs and references to existing files in the transcript
+    /// clickable in terminals that support OSC 8 hyperlinks. Files open
+    /// through the top-level `file_opener` scheme.
+    /// Defaults to `true`.
+    #[serde(default = "default_true")]
+    pub hyperlinks: bool,
 }
 
 const fn default_true() -> bool {
@@ -683,6 +1774,10 @@ pub struct SandboxWorkspaceWrite {
     pub exclude_tmpdir_env_var: bool,
     #[serde(default)]
     pub exclude_slash_tmp: bool,
+    /// Device nodes that commands may open for reading and writing, such as
+    /// `/dev/nvidia*` or `/dev/dri`. Only honored by the Linux sandbox.
+    #[serde(default)]
+    pub allowed_devices: Vec<String>,
 }
 
 impl From<SandboxWorkspaceWrite> for codex_app_server_protocol::SandboxSettings {
@@ -761,6 +761,25 @@ async fn find_project_root(
     Ok(cwd.clone())
 }
 
+/// Blocking variant of [`find_project_root`] for callers outside an async
+/// context.
+pub(crate) fn find_project_root_blocking(cwd: &Path, project_root_markers: &[String]) -> PathBuf {
+    if project_root_markers.is_empty() {
+        return cwd.to_path_buf();
+    }
+
+    for ancestor in cwd.ancestors() {
+        for marker in project_root_markers {
+            let marker_path = ancestor.join(marker);
+            if marker_path.exists() {
+                return ancestor.to_path_buf();
+            }
+        }
+    }
+
+    cwd.to_path_buf()
+}
+
 /// Return the appropriate list of layers (each with
 /// [ConfigLayerSource::Project] as the source) between `cwd` and
 /// `project_root`, inclusive. The list is ordered in _increasing_ precdence,
@@ -874,11 +893,13 @@ async fn load_project_layers(
 ///
 /// If present, re-interpret `managed_config.toml` as a `requirements.toml`
 /// where each specified field is treated as a constraint allowing only that
-/// value.
+/// value. The same goes for `model_provider`, so a fleet can pin the provider
+/// users talk to.
 #[derive(Deserialize, Debug, Clone, Default, PartialEq)]
 struct LegacyManagedConfigToml {
     approval_policy: Option<AskForApproval>,
     sandbox_mode: Option<SandboxMode>,
+    model_provider: Option<String>,
 }
 
 impl From<LegacyManagedConfigToml> for ConfigRequirementsToml {
@@ -888,6 +909,7 @@ impl From<LegacyManagedConfigToml> for ConfigRequirementsToml {
         let LegacyManagedConfigToml {
             approval_policy,
             sandbox_mode,
+            model_provider,
         } = legacy;
         if let Some(approval_policy) = approval_policy {
             config_requirements_toml.allowed_approval_policies = Some(vec![approval_policy]);
@@ -902,6 +924,9 @@ impl From<LegacyManagedConfigToml> for ConfigRequirementsToml {
             }
             config_requirements_toml.allowed_sandbox_modes = Some(allowed_modes);
         }
+        if let Some(model_provider) = model_provider {
+            config_requirements_toml.allowed_model_providers = Some(vec![model_provider]);
+        }
         config_requirements_toml
     }
 }
@@ -956,6 +981,7 @@ foo = "xyzzy"
         let legacy = LegacyManagedConfigToml {
             approval_policy: None,
             sandbox_mode: Some(SandboxMode::WorkspaceWrite),
+            model_provider: None,
         };
 
         let requirements = ConfigRequirementsToml::from(legacy);
@@ -580,6 +580,7 @@ allowed_approval_policies = ["on-request"]
                 allowed_approval_policies: Some(vec![AskForApproval::Never]),
                 allowed_sandbox_modes: None,
                 allowed_web_search_modes: None,
+                allowed_model_providers: None,
                 mcp_servers: None,
                 rules: None,
                 enforce_residency: None,
@@ -628,6 +629,7 @@ allowed_approval_policies = ["on-request"]
             allowed_approval_policies: Some(vec![AskForApproval::Never]),
             allowed_sandbox_modes: None,
             allowed_web_search_modes: None,
+            allowed_model_providers: None,
             mcp_servers: None,
             rules: None,
             enforce_residency: None,
@@ -665,6 +667,7 @@ async fn load_config_layers_includes_cloud_requirements() -> anyhow::Result<()>
         allowed_approval_policies: Some(vec![AskForApproval::Never]),
         allowed_sandbox_modes: None,
         allowed_web_search_modes: None,
+        allowed_model_providers: None,
         mcp_servers: None,
         rules: None,
         enforce_residency: None,
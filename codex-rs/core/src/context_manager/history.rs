@@ -18,7 +18,18 @@ use codex_protocol::models::ResponseItem;
 use codex_protocol::openai_models::InputModality;
 use codex_protocol::protocol::TokenUsage;
 use codex_protocol::protocol::TokenUsageInfo;
+use codex_utils_tokenizer::EncodingKind;
+use codex_utils_tokenizer::Tokenizer;
 use std::ops::Deref;
+use std::sync::OnceLock;
+use tracing::warn;
+
+/// Tokens the API adds around each item for its role or type.
+const ITEM_OVERHEAD_TOKENS: i64 = 4;
+
+/// Stands in for a tool output removed with [`ContextManager::drop_tool_output`].
+const DROPPED_TOOL_OUTPUT: &str =
+    "[This output was removed from the conversation to make the request smaller.]";
 
 /// Transcript of thread history
 #[derive(Debug, Clone, Default)]
@@ -26,6 +37,8 @@ pub(crate) struct ContextManager {
     /// The oldest items are at the beginning of the vector.
     items: Vec<ResponseItem>,
     token_info: Option<TokenUsageInfo>,
+    /// The vocabulary of the session's model; `o200k_base` until one is set.
+    tokenizer: Option<Tokenizer>,
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -41,9 +54,31 @@ impl ContextManager {
         Self {
             items: Vec::new(),
             token_info: TokenUsageInfo::new_or_append(&None, &None, None),
+            tokenizer: None,
+        }
+    }
+
+    /// Counts tokens with `model`'s vocabulary from now on.
+    pub(crate) fn set_tokenizer_for_model(&mut self, model: &str) {
+        if self
+            .tokenizer
+            .as_ref()
+            .is_some_and(|tokenizer| tokenizer.encoding() == EncodingKind::for_model(model))
+        {
+            return;
+        }
+        match Tokenizer::for_model(model) {
+            Ok(tokenizer) => self.tokenizer = Some(tokenizer),
+            Err(err) => warn!("{err}; estimating tokens for {model} with the default vocabulary"),
         }
     }
 
+    /// The tokenizer counts are made with, or `None` when no vocabulary could
+    /// be loaded and counts fall back to byte-based estimates.
+    pub(crate) fn tokenizer(&self) -> Option<&Tokenizer> {
+        self.tokenizer.as_ref().or_else(default_tokenizer)
+    }
+
     pub(crate) fn token_info(&self) -> Option<TokenUsageInfo> {
         self.token_info.clone()
     }
@@ -95,8 +130,9 @@ impl ContextManager {
         &self.items
     }
 
-    // Estimate token usage using byte-based heuristics from the truncation helpers.
-    // This is a coarse lower bound, not a tokenizer-accurate count.
+    // Estimate token usage by tokenizing the base instructions and history with
+    // the model's vocabulary. Encrypted reasoning and images cannot be
+    // tokenized locally and are estimated from their size.
     pub(crate) fn estimate_token_count(&self, turn_context: &TurnContext) -> Option<i64> {
         let model_info = &turn_context.model_info;
         let personality = turn_context.personality.or(turn_context.config.personality);
@@ -110,13 +146,12 @@ impl ContextManager {
         &self,
         base_instructions: &BaseInstructions,
     ) -> Option<i64> {
-        let base_tokens =
-            i64::try_from(approx_token_count(&base_instructions.text)).unwrap_or(i64::MAX);
+        let base_tokens = count_text_tokens(&base_instructions.text, self.tokenizer());
 
         let items_tokens = self
             .items
             .iter()
-            .map(estimate_item_token_count)
+            .map(|item| estimate_item_token_count(item, self.tokenizer()))
             .fold(0i64, i64::saturating_add);
 
         Some(base_tokens.saturating_add(items_tokens))
@@ -179,6 +214,40 @@ impl ContextManager {
         }
     }
 
+    /// Index and estimated tokens of the tool output holding the most tokens,
+    /// leaving out outputs that were already dropped.
+    pub(crate) fn largest_tool_output(&self) -> Option<(usize, i64)> {
+        self.items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| match item {
+                ResponseItem::FunctionCallOutput { output, .. } => {
+                    output.text_content() != Some(DROPPED_TOOL_OUTPUT)
+                }
+                ResponseItem::CustomToolCallOutput { output, .. } => output != DROPPED_TOOL_OUTPUT,
+                _ => false,
+            })
+            .map(|(index, item)| (index, estimate_item_token_count(item, self.tokenizer())))
+            .max_by_key(|(_, tokens)| *tokens)
+    }
+
+    /// Replaces the tool output at `index` with a short note. The call it
+    /// answers stays, so the history remains valid. Returns false when `index`
+    /// is not a tool output.
+    pub(crate) fn drop_tool_output(&mut self, index: usize) -> bool {
+        match self.items.get_mut(index) {
+            Some(ResponseItem::FunctionCallOutput { output, .. }) => {
+                *output = FunctionCallOutputPayload::from_text(DROPPED_TOOL_OUTPUT.to_string());
+                true
+            }
+            Some(ResponseItem::CustomToolCallOutput { output, .. }) => {
+                *output = DROPPED_TOOL_OUTPUT.to_string();
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Drop the last `num_turns` user turns from this history.
     ///
     /// "User turns" are identified as `ResponseItem::Message` entries whose role is `"user"`.
@@ -244,7 +313,7 @@ impl ContextManager {
                     }
                 )
             })
-            .map(estimate_item_token_count)
+            .map(|item| estimate_item_token_count(item, self.tokenizer()))
             .fold(0i64, i64::saturating_add)
     }
 
@@ -270,7 +339,7 @@ impl ContextManager {
         let items_after_last_model_generated_tokens = self
             .items_after_last_model_generated_item()
             .iter()
-            .map(estimate_item_token_count)
+            .map(|item| estimate_item_token_count(item, self.tokenizer()))
             .fold(0i64, i64::saturating_add);
         if server_reasoning_included {
             last_tokens.saturating_add(items_after_last_model_generated_tokens)
@@ -299,7 +368,7 @@ impl ContextManager {
             estimated_tokens_of_items_added_since_last_successful_api_response:
                 items_after_last_model_generated
                     .iter()
-                    .map(estimate_item_token_count)
+                    .map(|item| estimate_item_token_count(item, self.tokenizer()))
                     .fold(0i64, i64::saturating_add),
             estimated_bytes_of_items_added_since_last_successful_api_response:
                 items_after_last_model_generated
@@ -395,9 +464,95 @@ fn estimate_reasoning_length(encoded_len: usize) -> usize {
         .saturating_sub(650)
 }
 
-fn estimate_item_token_count(item: &ResponseItem) -> i64 {
-    let model_visible_bytes = estimate_response_item_model_visible_bytes(item);
-    approx_tokens_from_byte_count_i64(model_visible_bytes)
+/// `o200k_base`, shared by every history whose model has not set a vocabulary.
+fn default_tokenizer() -> Option<&'static Tokenizer> {
+    static DEFAULT: OnceLock<Option<Tokenizer>> = OnceLock::new();
+    DEFAULT
+        .get_or_init(|| {
+            Tokenizer::new(EncodingKind::O200kBase)
+                .inspect_err(|err| warn!("{err}; estimating tokens from bytes"))
+                .ok()
+        })
+        .as_ref()
+}
+
+fn count_text_tokens(text: &str, tokenizer: Option<&Tokenizer>) -> i64 {
+    let tokens = match tokenizer {
+        Some(tokenizer) => tokenizer.count(text),
+        None => approx_token_count(text),
+    };
+    i64::try_from(tokens).unwrap_or(i64::MAX)
+}
+
+fn count_image_tokens(image_url: &str) -> i64 {
+    approx_tokens_from_byte_count_i64(i64::try_from(image_url.len()).unwrap_or(i64::MAX))
+}
+
+/// Tokens the model sees for `item`. Text is tokenized; encrypted reasoning,
+/// compaction summaries and images are estimated from their size, as is
+/// everything when `tokenizer` is `None`.
+pub(crate) fn estimate_item_token_count(item: &ResponseItem, tokenizer: Option<&Tokenizer>) -> i64 {
+    let Some(tokenizer) = tokenizer else {
+        return approx_tokens_from_byte_count_i64(estimate_response_item_model_visible_bytes(item));
+    };
+    let count = |text: &str| count_text_tokens(text, Some(tokenizer));
+    let content_tokens = match item {
+        ResponseItem::GhostSnapshot { .. } => return 0,
+        ResponseItem::Reasoning {
+            encrypted_content: Some(_),
+            ..
+        }
+        | ResponseItem::Compaction { .. } => {
+            return approx_tokens_from_byte_count_i64(estimate_response_item_model_visible_bytes(
+                item,
+            ));
+        }
+        ResponseItem::Message { content, .. } => content
+            .iter()
+            .map(|content| match content {
+                ContentItem::InputText { text } | ContentItem::OutputText { text } => count(text),
+                ContentItem::InputImage { image_url } => count_image_tokens(image_url),
+            })
+            .fold(0i64, i64::saturating_add),
+        ResponseItem::FunctionCall {
+            name,
+            arguments,
+            call_id,
+            ..
+        } => count(name)
+            .saturating_add(count(arguments))
+            .saturating_add(count(call_id)),
+        ResponseItem::FunctionCallOutput { call_id, output } => {
+            let output_tokens = match &output.body {
+                FunctionCallOutputBody::Text(text) => count(text),
+                FunctionCallOutputBody::ContentItems(items) => items
+                    .iter()
+                    .map(|item| match item {
+                        FunctionCallOutputContentItem::InputText { text } => count(text),
+                        FunctionCallOutputContentItem::InputImage { image_url } => {
+                            count_image_tokens(image_url)
+                        }
+                    })
+                    .fold(0i64, i64::saturating_add),
+            };
+            output_tokens.saturating_add(count(call_id))
+        }
+        ResponseItem::CustomToolCall {
+            call_id,
+            name,
+            input,
+            ..
+        } => count(name)
+            .saturating_add(count(input))
+            .saturating_add(count(call_id)),
+        ResponseItem::CustomToolCallOutput { call_id, output } => {
+            count(output).saturating_add(count(call_id))
+        }
+        item => serde_json::to_string(item)
+            .map(|serialized| count(&serialized))
+            .unwrap_or_default(),
+    };
+    content_tokens.saturating_add(ITEM_OVERHEAD_TOKENS)
 }
 
 pub(crate) fn estimate_response_item_model_visible_bytes(item: &ResponseItem) -> i64 {
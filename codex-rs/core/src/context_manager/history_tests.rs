@@ -99,10 +99,6 @@ fn truncate_exec_output(content: &str) -> String {
     truncate::truncate_text(content, TruncationPolicy::Tokens(EXEC_FORMAT_MAX_TOKENS))
 }
 
-fn approx_token_count_for_text(text: &str) -> i64 {
-    i64::try_from(text.len().saturating_add(3) / 4).unwrap_or(i64::MAX)
-}
-
 #[test]
 fn filters_non_api_messages() {
     let mut h = ContextManager::default();
@@ -190,15 +186,18 @@ fn items_after_last_model_generated_tokens_include_user_and_tool_output() {
         user_msg("new user message"),
         custom_tool_call_output("call-tail", "new tool output"),
     ]);
-    let expected_tokens = estimate_item_token_count(&user_msg("new user message")).saturating_add(
-        estimate_item_token_count(&custom_tool_call_output("call-tail", "new tool output")),
-    );
+    let expected_tokens =
+        estimate_item_token_count(&user_msg("new user message"), history.tokenizer())
+            .saturating_add(estimate_item_token_count(
+                &custom_tool_call_output("call-tail", "new tool output"),
+                history.tokenizer(),
+            ));
 
     assert_eq!(
         history
             .items_after_last_model_generated_item()
             .iter()
-            .map(estimate_item_token_count)
+            .map(|item| estimate_item_token_count(item, history.tokenizer()))
             .fold(0i64, i64::saturating_add),
         expected_tokens
     );
@@ -212,7 +211,7 @@ fn items_after_last_model_generated_tokens_are_zero_without_model_generated_item
         history
             .items_after_last_model_generated_item()
             .iter()
-            .map(estimate_item_token_count)
+            .map(|item| estimate_item_token_count(item, history.tokenizer()))
             .fold(0i64, i64::saturating_add),
         0
     );
@@ -237,8 +236,8 @@ fn total_token_usage_includes_all_items_after_last_model_generated_item() {
 
     assert_eq!(
         history.get_total_token_usage(true),
-        100 + estimate_item_token_count(&added_user)
-            + estimate_item_token_count(&added_tool_output)
+        100 + estimate_item_token_count(&added_user, history.tokenizer())
+            + estimate_item_token_count(&added_tool_output, history.tokenizer())
     );
 }
 
@@ -378,11 +377,52 @@ fn estimate_token_count_with_base_instructions_uses_provided_text() {
         .estimate_token_count_with_base_instructions(&long_base)
         .expect("token estimate");
 
-    let expected_delta = approx_token_count_for_text(&long_base.text)
-        - approx_token_count_for_text(&short_base.text);
+    let tokenizer = history.tokenizer().expect("default vocabulary loads");
+    let expected_delta = i64::try_from(tokenizer.count(&long_base.text)).unwrap_or(i64::MAX)
+        - i64::try_from(tokenizer.count(&short_base.text)).unwrap_or(i64::MAX);
     assert_eq!(long_estimate - short_estimate, expected_delta);
 }
 
+#[test]
+fn counts_item_tokens_with_the_model_vocabulary() {
+    let mut history = ContextManager::new();
+    let code = "fn main() {\n    println!(\"héllo, wörld\");\n}\n";
+    let message = user_msg(code);
+    let call = ResponseItem::FunctionCall {
+        id: None,
+        name: "shell".to_string(),
+        arguments: r#"{"command":["cargo","test"]}"#.to_string(),
+        call_id: "call-1".to_string(),
+    };
+
+    for (model, encoding) in [
+        ("gpt-5.1-codex", EncodingKind::O200kBase),
+        ("gpt-4", EncodingKind::Cl100kBase),
+    ] {
+        history.set_tokenizer_for_model(model);
+        let tokenizer = Tokenizer::new(encoding).expect("vocabulary loads");
+        assert_eq!(history.tokenizer().map(Tokenizer::encoding), Some(encoding));
+        let count = |text: &str| i64::try_from(tokenizer.count(text)).unwrap_or(i64::MAX);
+
+        assert_eq!(
+            estimate_item_token_count(&message, history.tokenizer()),
+            count(code) + ITEM_OVERHEAD_TOKENS
+        );
+        assert_eq!(
+            estimate_item_token_count(&call, history.tokenizer()),
+            count("shell")
+                + count(r#"{"command":["cargo","test"]}"#)
+                + count("call-1")
+                + ITEM_OVERHEAD_TOKENS
+        );
+    }
+
+    assert_eq!(
+        estimate_item_token_count(&message, None),
+        approx_tokens_from_byte_count_i64(estimate_response_item_model_visible_bytes(&message))
+    );
+}
+
 #[test]
 fn remove_first_item_removes_matching_output_for_function_call() {
     let items = vec![
@@ -498,6 +538,29 @@ fn replace_last_turn_images_does_not_touch_user_images() {
     assert_eq!(history.raw_items(), items);
 }
 
+#[test]
+fn drop_tool_output_moves_on_to_the_next_largest() {
+    let mut history = create_history_with_items(vec![
+        user_input_text_msg("hi"),
+        custom_tool_call_output("call-1", "short"),
+        custom_tool_call_output("call-2", &"long output ".repeat(200)),
+    ]);
+
+    let (index, _) = history.largest_tool_output().expect("tool outputs");
+    assert_eq!(index, 2);
+    assert!(history.drop_tool_output(index));
+    assert_eq!(
+        history.raw_items()[2],
+        custom_tool_call_output("call-2", DROPPED_TOOL_OUTPUT)
+    );
+
+    let (index, _) = history.largest_tool_output().expect("tool outputs");
+    assert_eq!(index, 1);
+    assert!(history.drop_tool_output(index));
+    assert_eq!(history.largest_tool_output(), None);
+    assert!(!history.drop_tool_output(0));
+}
+
 #[test]
 fn remove_first_item_handles_local_shell_pair() {
     let items = vec![
@@ -0,0 +1,360 @@
+//! User-defined context providers from `[context_packs]` in config.toml.
+//!
+//! A pack is either a shell command (`kubectl config current-context`) or a
+//! set of file globs (`docs/runbooks/*.md`). Packs with
+//! `inject = "session_start"` are part of the initial context; the others are
+//! added with `/context <name>` (`Op::AddContextPack`). Content is truncated
+//! to the pack's token budget and reused until its `cache_ttl_sec` runs out,
+//! so compaction and repeated requests do not run the command again.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use codex_file_search::IgnoreRules;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use tokio::process::Command;
+use tokio::time::timeout;
+use tracing::warn;
+use wildmatch::WildMatchPattern;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::types::ContextPackConfig;
+use crate::config::types::ContextPackInject;
+use crate::config::types::ContextPackSource;
+use crate::exec_env::create_env;
+use crate::session_prefix::CONTEXT_PACK_CLOSE_TAG;
+use crate::session_prefix::CONTEXT_PACK_OPEN_TAG;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::truncate_text;
+
+/// Most files a `files` pack reads, so a broad glob cannot walk a whole tree
+/// into the context.
+const MAX_PACK_FILES: usize = 50;
+
+/// Gathered pack content, keyed by pack name.
+#[derive(Default)]
+pub(crate) struct ContextPackCache {
+    entries: Mutex<HashMap<String, (Instant, String)>>,
+}
+
+impl ContextPackCache {
+    fn get(&self, pack: &ContextPackConfig) -> Option<String> {
+        let entries = self.entries.lock().ok()?;
+        let (gathered, content) = entries.get(&pack.name)?;
+        (gathered.elapsed() < pack.cache_ttl).then(|| content.clone())
+    }
+
+    fn insert(&self, pack: &ContextPackConfig, content: &str) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(pack.name.clone(), (Instant::now(), content.to_string()));
+        }
+    }
+}
+
+/// Messages for the packs injected at session start. Packs that fail are
+/// left out.
+pub(crate) async fn session_start_context_packs(
+    sess: &Session,
+    turn: &TurnContext,
+) -> Vec<ResponseItem> {
+    let mut items = Vec::new();
+    for pack in &turn.config.context_packs {
+        if pack.inject != ContextPackInject::SessionStart {
+            continue;
+        }
+        match context_pack_message(sess, turn, pack).await {
+            Ok(item) => items.push(item),
+            Err(err) => warn!("context pack `{}` skipped: {err}", pack.name),
+        }
+    }
+    items
+}
+
+/// The message for the pack called `name`, for `Op::AddContextPack`.
+pub(crate) async fn context_pack_item(
+    sess: &Session,
+    turn: &TurnContext,
+    name: &str,
+) -> Result<ResponseItem, String> {
+    let Some(pack) = turn
+        .config
+        .context_packs
+        .iter()
+        .find(|pack| pack.name == name)
+    else {
+        let names: Vec<&str> = turn
+            .config
+            .context_packs
+            .iter()
+            .map(|pack| pack.name.as_str())
+            .collect();
+        return Err(if names.is_empty() {
+            format!("unknown context pack `{name}`; none are configured in [context_packs]")
+        } else {
+            format!(
+                "unknown context pack `{name}`; available: {}",
+                names.join(", ")
+            )
+        });
+    };
+    context_pack_message(sess, turn, pack).await
+}
+
+async fn context_pack_message(
+    sess: &Session,
+    turn: &TurnContext,
+    pack: &ContextPackConfig,
+) -> Result<ResponseItem, String> {
+    let content = match sess.services.context_packs.get(pack) {
+        Some(content) => content,
+        None => {
+            let content = gather(sess, turn, pack).await?;
+            let content = truncate_text(content.trim(), TruncationPolicy::Tokens(pack.max_tokens));
+            sess.services.context_packs.insert(pack, &content);
+            content
+        }
+    };
+    Ok(ResponseItem::Message {
+        id: None,
+        role: "user".to_string(),
+        content: vec![ContentItem::InputText {
+            text: serialize_pack(pack, &content),
+        }],
+        end_turn: None,
+        phase: None,
+    })
+}
+
+/// Wraps pack content for the model:
+///
+/// ```xml
+/// <context_pack name="kube" source="kubectl config current-context">
+/// prod-eu
+/// </context_pack>
+/// ```
+fn serialize_pack(pack: &ContextPackConfig, content: &str) -> String {
+    let source = match &pack.source {
+        ContextPackSource::Command(command) => command.clone(),
+        ContextPackSource::Files(patterns) => patterns.join(" "),
+    };
+    format!(
+        "{CONTEXT_PACK_OPEN_TAG} name=\"{}\" source=\"{}\">\n{content}\n{CONTEXT_PACK_CLOSE_TAG}",
+        pack.name,
+        source.replace('"', "&quot;"),
+    )
+}
+
+async fn gather(
+    sess: &Session,
+    turn: &TurnContext,
+    pack: &ContextPackConfig,
+) -> Result<String, String> {
+    match &pack.source {
+        ContextPackSource::Command(command) => run_pack_command(sess, turn, pack, command).await,
+        ContextPackSource::Files(patterns) => {
+            let cwd = turn.cwd.clone();
+            let patterns = patterns.clone();
+            tokio::task::spawn_blocking(move || read_pack_files(&cwd, &patterns))
+                .await
+                .map_err(|err| format!("failed to read files: {err}"))?
+        }
+    }
+}
+
+/// Runs the pack's command through the user's shell in the working directory.
+/// Like notification hooks, it runs outside the sandbox: it comes from the
+/// user's own config and typically needs credentials or the network.
+async fn run_pack_command(
+    sess: &Session,
+    turn: &TurnContext,
+    pack: &ContextPackConfig,
+    command: &str,
+) -> Result<String, String> {
+    let args = sess.user_shell().derive_exec_args(command, true);
+    let Some((program, args)) = args.split_first() else {
+        return Err("empty command".to_string());
+    };
+    let mut child = Command::new(program);
+    child
+        .args(args)
+        .current_dir(&turn.cwd)
+        .env_clear()
+        .envs(create_env(
+            &turn.shell_environment_policy,
+            Some(sess.conversation_id),
+        ))
+        .kill_on_drop(true);
+    let output = match timeout(pack.timeout, child.output()).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(err)) => return Err(format!("failed to run `{command}`: {err}")),
+        Err(_) => {
+            return Err(format!(
+                "`{command}` timed out after {}s",
+                pack.timeout.as_secs()
+            ));
+        }
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "`{command}` failed ({}): {}",
+            output.status,
+            stderr.trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Reads the files under `cwd` matched by `patterns`, each under a header
+/// with its path. Files excluded by `.gitignore` or `.codexignore` are
+/// skipped.
+fn read_pack_files(cwd: &Path, patterns: &[String]) -> Result<String, String> {
+    let mut rules = IgnoreRules::new(cwd, true);
+    let mut paths = Vec::new();
+    for pattern in patterns {
+        expand_pattern(cwd, pattern, &mut rules, &mut paths);
+    }
+    paths.sort();
+    paths.dedup();
+    if paths.is_empty() {
+        return Err(format!("no files match {}", patterns.join(" ")));
+    }
+    let mut sections = Vec::new();
+    for path in paths.iter().take(MAX_PACK_FILES) {
+        let Ok(contents) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        let relative = path.strip_prefix(cwd).unwrap_or(path);
+        sections.push(format!(
+            "=== {} ===\n{}",
+            relative.display(),
+            contents.trim_end()
+        ));
+    }
+    if paths.len() > MAX_PACK_FILES {
+        sections.push(format!(
+            "({} more files not shown)",
+            paths.len() - MAX_PACK_FILES
+        ));
+    }
+    Ok(sections.join("\n\n"))
+}
+
+/// Adds the files matching `pattern` to `paths`. `*` and `?` match across
+/// directories, so `docs/*.md` also finds `docs/runbooks/db.md`. Only the
+/// directory before the first wildcard is walked.
+fn expand_pattern(cwd: &Path, pattern: &str, rules: &mut IgnoreRules, paths: &mut Vec<PathBuf>) {
+    let literal: Vec<&str> = pattern
+        .split('/')
+        .take_while(|component| !component.contains(['*', '?']))
+        .collect();
+    if literal.len() == pattern.split('/').count() {
+        let path = cwd.join(pattern);
+        if path.is_file() {
+            paths.push(path);
+        }
+        return;
+    }
+    let matcher = WildMatchPattern::<'*', '?'>::new(pattern);
+    let root = cwd.join(literal.join("/"));
+    let mut pending = vec![root];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if entry.file_name() == ".git" || rules.is_ignored(&path) {
+                continue;
+            }
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                pending.push(path);
+            } else if file_type.is_file()
+                && let Ok(relative) = path.strip_prefix(cwd)
+                && matcher.matches(&relative.to_string_lossy().replace('\\', "/"))
+            {
+                paths.push(path);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn pack(name: &str, source: ContextPackSource) -> ContextPackConfig {
+        ContextPackConfig {
+            name: name.to_string(),
+            source,
+            inject: ContextPackInject::OnDemand,
+            max_tokens: 100,
+            cache_ttl: Duration::from_secs(60),
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    #[test]
+    fn reads_matching_files_and_skips_ignored_ones() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("docs/runbooks/old")).expect("mkdir");
+        std::fs::write(root.join("docs/runbooks/db.md"), "restart the db\n").expect("write");
+        std::fs::write(root.join("docs/runbooks/old/cache.md"), "flush it").expect("write");
+        std::fs::write(root.join("docs/runbooks/notes.txt"), "not markdown").expect("write");
+        std::fs::write(root.join("docs/runbooks/draft.md"), "wip").expect("write");
+        std::fs::write(root.join(".codexignore"), "draft.md\n").expect("write");
+        std::fs::write(root.join("README.md"), "hello").expect("write");
+
+        let content = read_pack_files(
+            root,
+            &["docs/runbooks/*.md".to_string(), "README.md".to_string()],
+        )
+        .expect("files match");
+        assert_eq!(
+            content,
+            "=== README.md ===\nhello\n\n=== docs/runbooks/db.md ===\nrestart the db\n\n=== docs/runbooks/old/cache.md ===\nflush it"
+        );
+        assert_eq!(
+            read_pack_files(root, &["missing/*.md".to_string()]),
+            Err("no files match missing/*.md".to_string())
+        );
+    }
+
+    #[test]
+    fn serializes_pack_with_its_source() {
+        let kube = pack(
+            "kube",
+            ContextPackSource::Command("kubectl config current-context".to_string()),
+        );
+        assert_eq!(
+            serialize_pack(&kube, "prod-eu"),
+            "<context_pack name=\"kube\" source=\"kubectl config current-context\">\nprod-eu\n</context_pack>"
+        );
+    }
+
+    #[test]
+    fn cache_expires_after_ttl() {
+        let cache = ContextPackCache::default();
+        let mut runbooks = pack(
+            "runbooks",
+            ContextPackSource::Files(vec!["docs/*.md".to_string()]),
+        );
+        assert_eq!(cache.get(&runbooks), None);
+        cache.insert(&runbooks, "cached");
+        assert_eq!(cache.get(&runbooks), Some("cached".to_string()));
+
+        runbooks.cache_ttl = Duration::ZERO;
+        assert_eq!(cache.get(&runbooks), None);
+    }
+}
@@ -1,4 +1,7 @@
+use crate::config::offline::is_loopback_url;
+use crate::config::types::HttpClientConfig;
 use crate::config_loader::ResidencyRequirement;
+use crate::model_provider_info::ModelProviderInfo;
 use crate::spawn::CODEX_SANDBOX_ENV_VAR;
 use codex_client::CodexHttpClient;
 pub use codex_client::CodexRequestBuilder;
@@ -7,6 +10,8 @@ use reqwest::header::HeaderValue;
 use std::sync::LazyLock;
 use std::sync::Mutex;
 use std::sync::RwLock;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 
 /// Set this to add a suffix to the User-Agent string.
 ///
@@ -36,6 +41,13 @@ pub struct Originator {
 static ORIGINATOR: LazyLock<RwLock<Option<Originator>>> = LazyLock::new(|| RwLock::new(None));
 static REQUIREMENTS_RESIDENCY: LazyLock<RwLock<Option<ResidencyRequirement>>> =
     LazyLock::new(|| RwLock::new(None));
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+static HTTP_CLIENT_CONFIG: LazyLock<RwLock<HttpClientConfig>> =
+    LazyLock::new(|| RwLock::new(HttpClientConfig::default()));
+
+/// Closed local port that requests to other hosts are sent to in offline
+/// mode, so they fail without leaving the machine.
+const OFFLINE_BLOCKED_PROXY: &str = "http://127.0.0.1:9";
 
 #[derive(Debug)]
 pub enum SetOriginatorError {
@@ -87,6 +99,33 @@ pub fn set_default_client_residency_requirement(enforce_residency: Option<Reside
     *guard = enforce_residency;
 }
 
+/// Turns offline mode on for every client built afterwards: requests to
+/// hosts other than this machine fail instead of being sent.
+pub fn set_default_client_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+pub fn is_default_client_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+/// Sets the proxy and TLS settings (`[http_client]`) for every client built
+/// afterwards.
+pub fn set_default_client_http_config(config: HttpClientConfig) {
+    let Ok(mut guard) = HTTP_CLIENT_CONFIG.write() else {
+        tracing::warn!("Failed to acquire http client config lock");
+        return;
+    };
+    *guard = config;
+}
+
+fn default_http_client_config() -> HttpClientConfig {
+    HTTP_CLIENT_CONFIG
+        .read()
+        .map(|guard| guard.clone())
+        .unwrap_or_default()
+}
+
 pub fn originator() -> Originator {
     if let Ok(guard) = ORIGINATOR.read()
         && let Some(originator) = guard.as_ref()
@@ -179,6 +218,21 @@ pub fn create_client() -> CodexHttpClient {
 }
 
 pub fn build_reqwest_client() -> reqwest::Client {
+    build_reqwest_client_with(&default_http_client_config())
+}
+
+/// Like [`build_reqwest_client`], with the provider's `http_client` settings
+/// layered over the defaults.
+pub fn build_reqwest_client_for_provider(provider: &ModelProviderInfo) -> reqwest::Client {
+    let defaults = default_http_client_config();
+    let http_config = match provider.http_client.as_ref() {
+        Some(http_client) => http_client.or(&defaults),
+        None => defaults,
+    };
+    build_reqwest_client_with(&http_config)
+}
+
+fn build_reqwest_client_with(http_config: &HttpClientConfig) -> reqwest::Client {
     let ua = get_codex_user_agent();
 
     let mut builder = reqwest::Client::builder()
@@ -188,10 +242,130 @@ pub fn build_reqwest_client() -> reqwest::Client {
     if is_sandboxed() {
         builder = builder.no_proxy();
     }
+    if is_default_client_offline() {
+        // A custom proxy also turns off the system proxy, so loopback
+        // requests go straight to the local service.
+        builder = builder.proxy(reqwest::Proxy::custom(|url| {
+            if is_loopback_url(url) {
+                None
+            } else {
+                tracing::warn!(
+                    "offline mode: blocked request to {}",
+                    url.host_str().unwrap_or_default()
+                );
+                Some(OFFLINE_BLOCKED_PROXY)
+            }
+        }));
+    }
+    match HttpClientSettings::load(http_config) {
+        Ok(settings) => builder = settings.apply(builder),
+        Err(err) => tracing::error!("ignoring invalid http_client settings: {err}"),
+    }
 
     builder.build().unwrap_or_else(|_| reqwest::Client::new())
 }
 
+/// Checks that the proxy URL parses and the certificate files load, so a bad
+/// `http_client` table is reported when the config loads rather than on the
+/// first request.
+pub fn validate_http_client_config(config: &HttpClientConfig) -> std::io::Result<()> {
+    HttpClientSettings::load(config).map(|_| ())
+}
+
+/// [`HttpClientConfig`] with the proxy parsed and the PEM files read.
+struct HttpClientSettings {
+    proxy: Option<reqwest::Proxy>,
+    root_certificates: Vec<reqwest::Certificate>,
+    identity: Option<reqwest::Identity>,
+}
+
+impl HttpClientSettings {
+    fn load(config: &HttpClientConfig) -> std::io::Result<Self> {
+        let invalid =
+            |message: String| std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+
+        let proxy = match config.proxy.as_deref() {
+            Some(url) => {
+                let proxy = reqwest::Proxy::all(url)
+                    .map_err(|err| invalid(format!("invalid proxy `{url}`: {err}")))?;
+                let no_proxy = config
+                    .no_proxy
+                    .as_ref()
+                    .and_then(|hosts| reqwest::NoProxy::from_string(&hosts.join(",")));
+                Some(proxy.no_proxy(no_proxy))
+            }
+            None => None,
+        };
+
+        let root_certificates = match config.ca_certificate.as_ref() {
+            Some(path) => {
+                let pem = read_pem(path.as_path())?;
+                reqwest::Certificate::from_pem_bundle(&pem).map_err(|err| {
+                    invalid(format!(
+                        "failed to parse CA certificates in {}: {err}",
+                        path.display()
+                    ))
+                })?
+            }
+            None => Vec::new(),
+        };
+
+        let identity = match (&config.client_certificate, &config.client_private_key) {
+            (Some(cert_path), Some(key_path)) => {
+                let mut pem = read_pem(cert_path.as_path())?;
+                pem.extend_from_slice(&read_pem(key_path.as_path())?);
+                Some(reqwest::Identity::from_pem(&pem).map_err(|err| {
+                    invalid(format!(
+                        "failed to parse client identity from {} and {}: {err}",
+                        cert_path.display(),
+                        key_path.display()
+                    ))
+                })?)
+            }
+            (None, None) => None,
+            _ => {
+                return Err(invalid(
+                    "client_certificate and client_private_key must both be set for mTLS"
+                        .to_string(),
+                ));
+            }
+        };
+
+        Ok(Self {
+            proxy,
+            root_certificates,
+            identity,
+        })
+    }
+
+    fn apply(self, mut builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        // Offline mode already routes every request; a configured proxy would
+        // send loopback traffic off the machine.
+        if let Some(proxy) = self.proxy
+            && !is_default_client_offline()
+        {
+            builder = builder.proxy(proxy);
+        }
+        for certificate in self.root_certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+        if let Some(identity) = self.identity {
+            // PEM identities are only supported by the rustls backend.
+            builder = builder.use_rustls_tls().identity(identity);
+        }
+        builder
+    }
+}
+
+fn read_pem(path: &std::path::Path) -> std::io::Result<Vec<u8>> {
+    std::fs::read(path).map_err(|err| {
+        std::io::Error::new(
+            err.kind(),
+            format!("failed to read {}: {err}", path.display()),
+        )
+    })
+}
+
 pub fn default_headers() -> HeaderMap {
     let mut headers = HeaderMap::new();
     headers.insert("originator", originator().header_value);
@@ -214,6 +388,7 @@ fn is_sandboxed() -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use codex_utils_absolute_path::AbsolutePathBuf;
     use core_test_support::skip_if_no_network;
     use pretty_assertions::assert_eq;
 
@@ -234,6 +409,74 @@ mod tests {
         assert_eq!(is_first_party_originator("Other"), false);
     }
 
+    #[test]
+    fn validate_http_client_config_reports_bad_settings() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let cert = AbsolutePathBuf::from_absolute_path(tmp.path().join("client.pem"))
+            .expect("absolute path");
+
+        for (config, ok) in [
+            (HttpClientConfig::default(), true),
+            (
+                HttpClientConfig {
+                    proxy: Some("socks5h://127.0.0.1:1080".to_string()),
+                    no_proxy: Some(vec!["localhost".to_string(), ".corp".to_string()]),
+                    ..Default::default()
+                },
+                true,
+            ),
+            (
+                HttpClientConfig {
+                    proxy: Some("not a proxy".to_string()),
+                    ..Default::default()
+                },
+                false,
+            ),
+            (
+                HttpClientConfig {
+                    client_certificate: Some(cert.clone()),
+                    ..Default::default()
+                },
+                false,
+            ),
+            (
+                HttpClientConfig {
+                    ca_certificate: Some(cert),
+                    ..Default::default()
+                },
+                false,
+            ),
+        ] {
+            assert_eq!(
+                validate_http_client_config(&config).is_ok(),
+                ok,
+                "{config:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn provider_http_client_falls_back_to_defaults_per_field() {
+        let defaults = HttpClientConfig {
+            proxy: Some("http://proxy.corp:3128".to_string()),
+            no_proxy: Some(vec!["localhost".to_string()]),
+            ..Default::default()
+        };
+        let provider = HttpClientConfig {
+            proxy: Some("http://llm-proxy.corp:8080".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            provider.or(&defaults),
+            HttpClientConfig {
+                proxy: Some("http://llm-proxy.corp:8080".to_string()),
+                no_proxy: Some(vec!["localhost".to_string()]),
+                ..Default::default()
+            }
+        );
+    }
+
     #[tokio::test]
     async fn test_create_client_sets_default_headers() {
         skip_if_no_network!();
@@ -0,0 +1,296 @@
+//! Per-session disk quota from `session_disk_quota_mb`.
+//!
+//! A watcher on the session's writable roots, plus the paths touched by
+//! apply_patch, feed the net growth of the files under those roots. Once the
+//! growth passes the quota, the tool orchestrator asks for approval before
+//! the next tool call, so a looping agent cannot fill the disk unattended.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use notify::Event;
+use notify::EventKind;
+use notify::RecommendedWatcher;
+use notify::RecursiveMode;
+use notify::Watcher;
+use notify::event::ModifyKind;
+use tracing::warn;
+
+use crate::protocol::SandboxPolicy;
+
+const MIB: u64 = 1024 * 1024;
+
+pub(crate) struct DiskQuota {
+    usage: Arc<Mutex<DiskUsage>>,
+    /// Kept alive for as long as the session; dropping it stops the events.
+    _watcher: Option<RecommendedWatcher>,
+}
+
+impl DiskQuota {
+    pub(crate) fn disabled() -> Self {
+        Self {
+            usage: Arc::new(Mutex::new(DiskUsage::new(None))),
+            _watcher: None,
+        }
+    }
+
+    /// Starts tracking the writable roots of `sandbox_policy` against a
+    /// quota of `quota_mb` MiB. Without a quota nothing is watched. When the
+    /// policy can write anywhere, only `cwd` is watched.
+    pub(crate) fn new(quota_mb: Option<u64>, sandbox_policy: &SandboxPolicy, cwd: &Path) -> Self {
+        let Some(quota_mb) = quota_mb else {
+            return Self::disabled();
+        };
+        let roots: Vec<PathBuf> = if sandbox_policy.has_full_disk_write_access() {
+            vec![cwd.to_path_buf()]
+        } else {
+            sandbox_policy
+                .get_writable_roots_with_cwd(cwd)
+                .into_iter()
+                .map(|writable_root| writable_root.root.to_path_buf())
+                .collect()
+        };
+        let usage = Arc::new(Mutex::new(DiskUsage::new(Some(
+            quota_mb.saturating_mul(MIB),
+        ))));
+        let watcher_usage = Arc::clone(&usage);
+        let watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+            if let Ok(event) = res
+                && let Ok(mut usage) = watcher_usage.lock()
+            {
+                usage.on_event(&event);
+            }
+        });
+        let mut watcher = match watcher {
+            Ok(watcher) => watcher,
+            Err(err) => {
+                warn!("disk quota: failed to start file watcher: {err}");
+                return Self {
+                    usage,
+                    _watcher: None,
+                };
+            }
+        };
+        for root in &roots {
+            if let Err(err) = watcher.watch(root, RecursiveMode::Recursive) {
+                warn!("disk quota: not watching {}: {err}", root.display());
+            }
+        }
+        Self {
+            usage,
+            _watcher: Some(watcher),
+        }
+    }
+
+    /// Records the current size of `paths` before apply_patch writes them.
+    pub(crate) fn before_write<'a>(&self, paths: impl IntoIterator<Item = &'a Path>) {
+        if let Ok(mut usage) = self.usage.lock() {
+            for path in paths {
+                usage.remember(path);
+            }
+        }
+    }
+
+    /// Counts the growth of `paths` after apply_patch wrote them.
+    pub(crate) fn after_write<'a>(&self, paths: impl IntoIterator<Item = &'a Path>) {
+        if let Ok(mut usage) = self.usage.lock() {
+            for path in paths {
+                usage.observe(path);
+            }
+        }
+    }
+
+    /// Returns the approval reason when the session is over its quota.
+    pub(crate) fn exceeded_reason(&self) -> Option<String> {
+        let usage = self.usage.lock().ok()?;
+        let limit = usage.limit?;
+        (usage.grown > limit).then(|| {
+            format!(
+                "This session has written {} MiB inside its writable roots, over its {} MiB disk quota. Approving allows another {} MiB.",
+                usage.grown / MIB,
+                limit / MIB,
+                usage.quota.unwrap_or_default() / MIB,
+            )
+        })
+    }
+
+    /// Grants another full quota on top of the current usage.
+    pub(crate) fn extend(&self) {
+        if let Ok(mut usage) = self.usage.lock() {
+            usage.extend();
+        }
+    }
+}
+
+/// Net growth of the files seen so far, in bytes.
+struct DiskUsage {
+    quota: Option<u64>,
+    limit: Option<u64>,
+    grown: u64,
+    sizes: HashMap<PathBuf, u64>,
+}
+
+impl DiskUsage {
+    fn new(quota: Option<u64>) -> Self {
+        Self {
+            quota,
+            limit: quota,
+            grown: 0,
+            sizes: HashMap::new(),
+        }
+    }
+
+    fn on_event(&mut self, event: &Event) {
+        if let EventKind::Modify(ModifyKind::Name(_)) = event.kind {
+            self.on_rename(&event.paths);
+            return;
+        }
+        for path in &event.paths {
+            match event.kind {
+                // New files start empty, so all of their content counts.
+                EventKind::Create(_) => {
+                    self.sizes.entry(path.clone()).or_insert(0);
+                    self.observe(path);
+                }
+                EventKind::Modify(_) => self.observe(path),
+                EventKind::Remove(_) => self.forget(path),
+                _ => {}
+            }
+        }
+    }
+
+    /// Renames move bytes rather than write them, so they never count.
+    fn on_rename(&mut self, paths: &[PathBuf]) {
+        if let [from, to] = paths {
+            if let Some(size) = self.sizes.remove(from) {
+                self.sizes.insert(to.clone(), size);
+            }
+            return;
+        }
+        // Some platforms report each side of a rename separately.
+        for path in paths {
+            if file_size(path).is_some() {
+                self.remember(path);
+            } else {
+                self.sizes.remove(path);
+            }
+        }
+    }
+
+    /// Starts tracking `path` at its current size, if it is not yet tracked.
+    fn remember(&mut self, path: &Path) {
+        if !self.sizes.contains_key(path) {
+            self.sizes
+                .insert(path.to_path_buf(), file_size(path).unwrap_or(0));
+        }
+    }
+
+    fn observe(&mut self, path: &Path) {
+        let Some(size) = file_size(path) else {
+            // Renamed away or deleted: modify events also report the old name.
+            self.forget(path);
+            return;
+        };
+        // A file seen for the first time through a modify event was already
+        // there; only later growth is ours.
+        let previous = self.sizes.insert(path.to_path_buf(), size).unwrap_or(size);
+        self.apply_delta(previous, size);
+    }
+
+    fn forget(&mut self, path: &Path) {
+        if let Some(previous) = self.sizes.remove(path) {
+            self.apply_delta(previous, 0);
+        }
+    }
+
+    fn apply_delta(&mut self, previous: u64, current: u64) {
+        self.grown = if current >= previous {
+            self.grown.saturating_add(current - previous)
+        } else {
+            self.grown.saturating_sub(previous - current)
+        };
+    }
+
+    fn extend(&mut self) {
+        if let Some(quota) = self.quota {
+            self.limit = Some(self.grown.saturating_add(quota));
+        }
+    }
+}
+
+fn file_size(path: &Path) -> Option<u64> {
+    let metadata = std::fs::symlink_metadata(path).ok()?;
+    metadata.is_file().then(|| metadata.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::CreateKind;
+    use notify::event::RemoveKind;
+    use notify::event::RenameMode;
+    use pretty_assertions::assert_eq;
+
+    fn event(kind: EventKind, path: &Path) -> Event {
+        Event::new(kind).add_path(path.to_path_buf())
+    }
+
+    #[test]
+    fn counts_net_growth_of_new_and_existing_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let existing = dir.path().join("existing.log");
+        let created = dir.path().join("created.bin");
+        std::fs::write(&existing, vec![0; 100]).expect("write existing");
+        let mut usage = DiskUsage::new(Some(1000));
+
+        // Appending to a file that was there before counts only the append.
+        usage.remember(&existing);
+        std::fs::write(&existing, vec![0; 300]).expect("grow existing");
+        usage.on_event(&event(EventKind::Modify(ModifyKind::Any), &existing));
+        assert_eq!(usage.grown, 200);
+
+        std::fs::write(&created, vec![0; 500]).expect("write created");
+        usage.on_event(&event(EventKind::Create(CreateKind::File), &created));
+        usage.on_event(&event(EventKind::Modify(ModifyKind::Any), &created));
+        assert_eq!(usage.grown, 700);
+
+        let renamed = dir.path().join("renamed.bin");
+        std::fs::rename(&created, &renamed).expect("rename created");
+        usage.on_event(
+            &Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Both)))
+                .add_path(created.clone())
+                .add_path(renamed.clone()),
+        );
+        assert_eq!(usage.grown, 700);
+
+        std::fs::remove_file(&renamed).expect("remove renamed");
+        usage.on_event(&event(EventKind::Remove(RemoveKind::File), &renamed));
+        assert_eq!(usage.grown, 200);
+    }
+
+    #[test]
+    fn extend_grants_another_quota_on_top_of_usage() {
+        let quota = DiskQuota::disabled();
+        assert_eq!(quota.exceeded_reason(), None);
+
+        let quota = DiskQuota {
+            usage: Arc::new(Mutex::new(DiskUsage::new(Some(2 * MIB)))),
+            _watcher: None,
+        };
+        quota.usage.lock().expect("lock").grown = 3 * MIB;
+        assert_eq!(
+            quota.exceeded_reason(),
+            Some(
+                "This session has written 3 MiB inside its writable roots, over its 2 MiB disk quota. Approving allows another 2 MiB."
+                    .to_string()
+            )
+        );
+
+        quota.extend();
+        assert_eq!(quota.exceeded_reason(), None);
+        assert_eq!(quota.usage.lock().expect("lock").limit, Some(5 * MIB));
+    }
+}
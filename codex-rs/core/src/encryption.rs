@@ -0,0 +1,405 @@
+//! Encryption at rest for files Codex writes under `CODEX_HOME` (rollouts and
+//! `auth.json`).
+//!
+//! Data is sealed with AES-256-GCM under a key generated on first use and kept
+//! in the OS keyring, one key per `CODEX_HOME`. An encrypted file starts with a
+//! plaintext header line naming the algorithm and the keyring entry of its
+//! key, so a reader never has to guess which `CODEX_HOME` the file came from,
+//! plus a random id for the file. Every following line is one record: base64
+//! of `nonce || ciphertext || tag`. Each record is authenticated together with
+//! the file id and its position, so records cannot be reordered, dropped from
+//! the middle, or spliced in from another file. Records are still sealed one
+//! at a time, which lets rollouts be appended line by line and keeps
+//! everything before a torn final line readable.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use codex_keyring_store::KeyringStore;
+use once_cell::sync::Lazy;
+use ring::aead::AES_256_GCM;
+use ring::aead::Aad;
+use ring::aead::LessSafeKey;
+use ring::aead::NONCE_LEN;
+use ring::aead::Nonce;
+use ring::aead::UnboundKey;
+use ring::rand::SecureRandom;
+use ring::rand::SystemRandom;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+
+const KEYRING_SERVICE: &str = "Codex Data Key";
+const ALGORITHM: &str = "aes-256-gcm";
+const KEY_LEN: usize = 32;
+const FILE_ID_LEN: usize = 16;
+
+// Keys already loaded from the keyring, by key id, so a session does not go
+// back to the keyring (and possibly prompt) for every file it opens.
+static LOADED_KEYS: Lazy<Mutex<HashMap<String, Arc<DataKey>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+struct EncryptionHeader {
+    encryption: String,
+    key_id: String,
+    file_id: String,
+}
+
+/// A data key and the keyring account it is stored under.
+#[derive(Debug)]
+pub(crate) struct DataKey {
+    id: String,
+    key: LessSafeKey,
+}
+
+impl DataKey {
+    /// The key for `codex_home`, generating and storing one in the keyring on
+    /// first use.
+    pub(crate) fn load_or_create(
+        codex_home: &Path,
+        keyring_store: &dyn KeyringStore,
+    ) -> io::Result<Arc<DataKey>> {
+        let id = key_id(codex_home);
+        if let Some(key) = cached_key(&id)? {
+            return Ok(key);
+        }
+        let encoded = match load_encoded_key(&id, keyring_store)? {
+            Some(encoded) => encoded,
+            None => {
+                let mut bytes = [0u8; KEY_LEN];
+                SystemRandom::new()
+                    .fill(&mut bytes)
+                    .map_err(|_| io::Error::other("failed to generate data key"))?;
+                let encoded = BASE64_STANDARD.encode(bytes);
+                keyring_store
+                    .save(KEYRING_SERVICE, &id, &encoded)
+                    .map_err(|err| {
+                        io::Error::other(format!(
+                            "failed to store data key in keyring: {}",
+                            err.message()
+                        ))
+                    })?;
+                encoded
+            }
+        };
+        cache_key(DataKey::from_encoded(id, &encoded)?)
+    }
+
+    /// The key a file was sealed with, from the header of that file.
+    fn for_header(
+        header: &EncryptionHeader,
+        keyring_store: &dyn KeyringStore,
+    ) -> io::Result<Arc<DataKey>> {
+        if header.encryption != ALGORITHM {
+            return Err(invalid_data(format!(
+                "unsupported encryption `{}`",
+                header.encryption
+            )));
+        }
+        if let Some(key) = cached_key(&header.key_id)? {
+            return Ok(key);
+        }
+        let Some(encoded) = load_encoded_key(&header.key_id, keyring_store)? else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("data key {} is not in the keyring", header.key_id),
+            ));
+        };
+        cache_key(DataKey::from_encoded(header.key_id.clone(), &encoded)?)
+    }
+
+    fn from_encoded(id: String, encoded: &str) -> io::Result<Self> {
+        let bytes = BASE64_STANDARD
+            .decode(encoded.trim())
+            .map_err(|err| invalid_data(format!("invalid data key in keyring: {err}")))?;
+        let key = UnboundKey::new(&AES_256_GCM, &bytes)
+            .map_err(|_| invalid_data("invalid data key length in keyring".to_string()))?;
+        Ok(Self {
+            id,
+            key: LessSafeKey::new(key),
+        })
+    }
+
+    /// Seals `plaintext` into one record line, without the newline.
+    fn seal(&self, plaintext: &[u8], aad: &[u8]) -> io::Result<String> {
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| io::Error::other("failed to generate nonce"))?;
+        let mut in_out = plaintext.to_vec();
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(aad),
+                &mut in_out,
+            )
+            .map_err(|_| io::Error::other("failed to encrypt record"))?;
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&in_out);
+        Ok(BASE64_STANDARD.encode(sealed))
+    }
+
+    /// Opens a record line written by [`DataKey::seal`].
+    fn open(&self, record: &str, aad: &[u8]) -> io::Result<Vec<u8>> {
+        let sealed = BASE64_STANDARD
+            .decode(record.trim_end())
+            .map_err(|err| invalid_data(format!("invalid encrypted record: {err}")))?;
+        if sealed.len() < NONCE_LEN {
+            return Err(invalid_data("truncated encrypted record".to_string()));
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce)
+            .map_err(|_| invalid_data("invalid nonce".to_string()))?;
+        let mut in_out = ciphertext.to_vec();
+        let plaintext = self
+            .key
+            .open_in_place(nonce, Aad::from(aad), &mut in_out)
+            .map_err(|_| invalid_data("encrypted record failed authentication".to_string()))?;
+        Ok(plaintext.to_vec())
+    }
+}
+
+/// Seals and opens the records of one encrypted file, in order.
+#[derive(Debug)]
+pub(crate) struct RecordCipher {
+    key: Arc<DataKey>,
+    file_id: String,
+    next_index: u64,
+}
+
+impl RecordCipher {
+    /// Starts a new file sealed with `key`.
+    pub(crate) fn create(key: Arc<DataKey>) -> io::Result<Self> {
+        let mut file_id = [0u8; FILE_ID_LEN];
+        SystemRandom::new()
+            .fill(&mut file_id)
+            .map_err(|_| io::Error::other("failed to generate file id"))?;
+        Ok(Self {
+            key,
+            file_id: BASE64_STANDARD.encode(file_id),
+            next_index: 0,
+        })
+    }
+
+    /// Reads the header line of an existing file, positioned at its first
+    /// record.
+    pub(crate) fn for_header(
+        header_line: &str,
+        keyring_store: &dyn KeyringStore,
+    ) -> io::Result<Self> {
+        let header: EncryptionHeader = serde_json::from_str(header_line.trim_end())
+            .map_err(|err| invalid_data(format!("invalid encryption header: {err}")))?;
+        Ok(Self {
+            key: DataKey::for_header(&header, keyring_store)?,
+            file_id: header.file_id,
+            next_index: 0,
+        })
+    }
+
+    /// The first line of the file, newline included.
+    pub(crate) fn header_line(&self) -> String {
+        let header = EncryptionHeader {
+            encryption: ALGORITHM.to_string(),
+            key_id: self.key.id.clone(),
+            file_id: self.file_id.clone(),
+        };
+        // Serializing strings cannot fail.
+        let mut line = serde_json::to_string(&header).unwrap_or_default();
+        line.push('\n');
+        line
+    }
+
+    /// Moves past `records` existing records, to append after them.
+    pub(crate) fn skip_records(&mut self, records: u64) {
+        self.next_index += records;
+    }
+
+    /// Seals the next record of the file, without the newline.
+    pub(crate) fn seal_next(&mut self, plaintext: &[u8]) -> io::Result<String> {
+        let record = self.key.seal(plaintext, &self.next_aad())?;
+        self.next_index += 1;
+        Ok(record)
+    }
+
+    /// Opens the next record of the file.
+    pub(crate) fn open_next(&mut self, record: &str) -> io::Result<Vec<u8>> {
+        let aad = self.next_aad();
+        self.next_index += 1;
+        self.key.open(record, &aad)
+    }
+
+    /// `file id || record index`.
+    fn next_aad(&self) -> Vec<u8> {
+        let mut aad = self.file_id.as_bytes().to_vec();
+        aad.extend_from_slice(&self.next_index.to_be_bytes());
+        aad
+    }
+}
+
+/// Encrypts a whole document (header line plus one record).
+pub(crate) fn seal_document(key: &Arc<DataKey>, plaintext: &[u8]) -> io::Result<String> {
+    let mut cipher = RecordCipher::create(Arc::clone(key))?;
+    Ok(format!(
+        "{}{}\n",
+        cipher.header_line(),
+        cipher.seal_next(plaintext)?
+    ))
+}
+
+/// Decrypts a document written by [`seal_document`].
+pub(crate) fn open_document(text: &str, keyring_store: &dyn KeyringStore) -> io::Result<Vec<u8>> {
+    let mut lines = text.lines();
+    let header = lines
+        .next()
+        .ok_or_else(|| invalid_data("empty encrypted file".to_string()))?;
+    let mut cipher = RecordCipher::for_header(header, keyring_store)?;
+    let record = lines
+        .next()
+        .ok_or_else(|| invalid_data("encrypted file has no record".to_string()))?;
+    cipher.open_next(record)
+}
+
+/// The keyring account holding the data key for `codex_home`.
+fn key_id(codex_home: &Path) -> String {
+    let canonical = codex_home
+        .canonicalize()
+        .unwrap_or_else(|_| codex_home.to_path_buf());
+    let mut hasher = Sha256::new();
+    hasher.update(canonical.to_string_lossy().as_bytes());
+    let hex = format!("{:x}", hasher.finalize());
+    let truncated = hex.get(..16).unwrap_or(&hex);
+    format!("data|{truncated}")
+}
+
+fn load_encoded_key(id: &str, keyring_store: &dyn KeyringStore) -> io::Result<Option<String>> {
+    keyring_store.load(KEYRING_SERVICE, id).map_err(|err| {
+        io::Error::other(format!(
+            "failed to load data key from keyring: {}",
+            err.message()
+        ))
+    })
+}
+
+fn cached_key(id: &str) -> io::Result<Option<Arc<DataKey>>> {
+    let keys = LOADED_KEYS
+        .lock()
+        .map_err(|_| io::Error::other("data key cache poisoned"))?;
+    Ok(keys.get(id).cloned())
+}
+
+fn cache_key(key: DataKey) -> io::Result<Arc<DataKey>> {
+    let key = Arc::new(key);
+    LOADED_KEYS
+        .lock()
+        .map_err(|_| io::Error::other("data key cache poisoned"))?
+        .insert(key.id.clone(), Arc::clone(&key));
+    Ok(key)
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_keyring_store::tests::MockKeyringStore;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn documents_round_trip_through_the_keyring_key() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let keyring = MockKeyringStore::default();
+        let key = DataKey::load_or_create(codex_home.path(), &keyring).expect("key");
+        assert!(keyring.saved_value(&key_id(codex_home.path())).is_some());
+
+        let sealed = seal_document(&key, b"{\"OPENAI_API_KEY\":\"sk-test\"}").expect("seal");
+        assert!(!sealed.contains("sk-test"));
+        assert_eq!(
+            open_document(&sealed, &keyring).expect("open"),
+            b"{\"OPENAI_API_KEY\":\"sk-test\"}".to_vec()
+        );
+
+        // Tampering with the record fails authentication.
+        let mut tampered = sealed.into_bytes();
+        let inside_record = tampered.len() - 10;
+        tampered[inside_record] = if tampered[inside_record] == b'A' {
+            b'B'
+        } else {
+            b'A'
+        };
+        let tampered = String::from_utf8(tampered).expect("utf8");
+        assert_eq!(
+            open_document(&tampered, &keyring)
+                .expect_err("tampered record")
+                .kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn existing_keyring_key_is_reused() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let keyring = MockKeyringStore::default();
+        let key = DataKey::load_or_create(codex_home.path(), &keyring).expect("key");
+        let mut writer = RecordCipher::create(key).expect("cipher");
+        let record = writer.seal_next(b"line").expect("seal");
+
+        // A fresh process finds the key in the keyring rather than the cache.
+        LOADED_KEYS
+            .lock()
+            .expect("lock")
+            .remove(&key_id(codex_home.path()));
+        let mut reader = RecordCipher::for_header(&writer.header_line(), &keyring).expect("key");
+        assert_eq!(reader.open_next(&record).expect("open"), b"line".to_vec());
+    }
+
+    #[test]
+    fn headers_without_a_file_id_are_rejected() {
+        let keyring = MockKeyringStore::default();
+        let header = format!(r#"{{"encryption":"{ALGORITHM}","key_id":"data|0"}}"#);
+        assert_eq!(
+            RecordCipher::for_header(&header, &keyring)
+                .expect_err("missing file id")
+                .kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn records_are_bound_to_their_file_and_position() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let keyring = MockKeyringStore::default();
+        let key = DataKey::load_or_create(codex_home.path(), &keyring).expect("key");
+        let mut writer = RecordCipher::create(Arc::clone(&key)).expect("cipher");
+        let first = writer.seal_next(b"first").expect("seal");
+        let second = writer.seal_next(b"second").expect("seal");
+        let mut other_file = RecordCipher::create(key).expect("cipher");
+        let foreign = other_file.seal_next(b"foreign").expect("seal");
+
+        let open = |records: &[&str]| {
+            let mut reader =
+                RecordCipher::for_header(&writer.header_line(), &keyring).expect("cipher");
+            records
+                .iter()
+                .map(|record| reader.open_next(record).is_ok())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            (
+                open(&[&first, &second]),
+                open(&[&second, &first]),
+                open(&[&foreign]),
+            ),
+            (vec![true, true], vec![false, false], vec![false])
+        );
+    }
+}
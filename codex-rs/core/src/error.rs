@@ -74,6 +74,12 @@ pub enum CodexErr {
     #[error("stream disconnected before completion: {0}")]
     Stream(String, Option<Duration>),
 
+    /// The response stream stopped producing deltas mid-message for longer than the provider's
+    /// stall timeout while Codex was waiting on it. Retried like [`CodexErr::Stream`], continuing
+    /// from the output received so far.
+    #[error("stream stalled: no output from the model for {}s", .0.as_secs())]
+    StreamStalled(Duration),
+
     #[error(
         "Codex ran out of room in the model's context window. Start a new thread or clear earlier history before retrying."
     )]
@@ -215,6 +221,7 @@ impl CodexErr {
             | CodexErr::UsageLimitReached(_)
             | CodexErr::ServerOverloaded => false,
             CodexErr::Stream(..)
+            | CodexErr::StreamStalled(_)
             | CodexErr::Timeout
             | CodexErr::UnexpectedStatus(_)
             | CodexErr::ResponseStreamFailed(_)
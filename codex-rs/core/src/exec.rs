@@ -11,16 +11,20 @@ use std::time::Instant;
 
 use async_channel::Sender;
 use tokio::io::AsyncRead;
-use tokio::io::AsyncReadExt;
-use tokio::io::BufReader;
 use tokio::process::Child;
 use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
+use tracing::field;
+use tracing::trace_span;
 use uuid::Uuid;
 
 use crate::error::CodexErr;
 use crate::error::Result;
 use crate::error::SandboxErr;
+use crate::exec_output::CapturedOutput;
+use crate::exec_output::OutputCapture;
 use crate::get_platform_sandbox;
+use crate::output_escapes::normalize_output;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecCommandOutputDeltaEvent;
@@ -48,12 +52,13 @@ const EXEC_TIMEOUT_EXIT_CODE: i32 = 124; // conventional timeout exit code
 
 // I/O buffer sizing
 const READ_CHUNK_SIZE: usize = 8192; // bytes per read
-const AGGREGATE_BUFFER_INITIAL_CAPACITY: usize = 8 * 1024; // 8 KiB
 
 /// Hard cap on bytes retained from exec stdout/stderr/aggregated output.
 ///
 /// This mirrors unified exec's output cap so a single runaway command cannot
-/// OOM the process by dumping huge amounts of data to stdout/stderr.
+/// OOM the process by dumping huge amounts of data to stdout/stderr. Each
+/// stream keeps its beginning and its end; what falls in between is spilled
+/// to a temp file (see [`crate::exec_output`]).
 const EXEC_OUTPUT_MAX_BYTES: usize = 1024 * 1024; // 1 MiB
 
 /// Limit the number of ExecCommandOutputDelta events emitted per exec call.
@@ -269,10 +274,43 @@ pub(crate) async fn execute_exec_env(
         arg0,
     };
 
+    let exec_span = trace_span!(
+        "exec_command",
+        sandbox = sandbox.as_metric_tag(),
+        exit_code = field::Empty,
+        timed_out = field::Empty,
+    );
     let start = Instant::now();
-    let raw_output_result = exec(params, sandbox, sandbox_policy, stdout_stream).await;
+    let raw_output_result = exec(params, sandbox, sandbox_policy, stdout_stream)
+        .instrument(exec_span.clone())
+        .await;
     let duration = start.elapsed();
-    finalize_exec_result(raw_output_result, sandbox, duration)
+    let result = finalize_exec_result(raw_output_result, sandbox, duration);
+    let output = match &result {
+        Ok(output) => Some(output),
+        Err(CodexErr::Sandbox(SandboxErr::Denied { output, .. }))
+        | Err(CodexErr::Sandbox(SandboxErr::Timeout { output })) => Some(output.as_ref()),
+        Err(_) => None,
+    };
+    if let Some(output) = output {
+        exec_span.record("exit_code", output.exit_code);
+        exec_span.record("timed_out", output.timed_out);
+    }
+    if let Some(metrics) = codex_otel::metrics::global() {
+        let outcome = match &result {
+            Ok(output) if output.exit_code == 0 => "success",
+            Ok(_) => "failure",
+            Err(CodexErr::Sandbox(SandboxErr::Timeout { .. })) => "timeout",
+            Err(CodexErr::Sandbox(SandboxErr::Denied { .. })) => "denied",
+            Err(_) => "error",
+        };
+        let _ = metrics.record_duration(
+            "codex.exec.duration_ms",
+            duration,
+            &[("sandbox", sandbox.as_metric_tag()), ("outcome", outcome)],
+        );
+    }
+    result
 }
 
 #[cfg(target_os = "windows")]
@@ -453,6 +491,7 @@ async fn exec_windows_sandbox(
         stderr,
         aggregated_output,
         timed_out: capture.timed_out,
+        omitted_output_notes: Vec::new(),
     })
 }
 
@@ -484,7 +523,11 @@ fn finalize_exec_result(
 
             let stdout = raw_output.stdout.from_utf8_lossy();
             let stderr = raw_output.stderr.from_utf8_lossy();
-            let aggregated_output = raw_output.aggregated_output.from_utf8_lossy();
+            let mut aggregated_output = raw_output.aggregated_output.from_utf8_lossy();
+            for note in &raw_output.omitted_output_notes {
+                aggregated_output.text.push('\n');
+                aggregated_output.text.push_str(note);
+            }
             let exec_output = ExecToolCallOutput {
                 exit_code,
                 stdout,
@@ -610,6 +653,8 @@ struct RawExecToolCallOutput {
     pub stderr: StreamOutput<Vec<u8>>,
     pub aggregated_output: StreamOutput<Vec<u8>>,
     pub timed_out: bool,
+    /// Where stdout or stderr output that did not fit was saved, for the model.
+    pub omitted_output_notes: Vec<String>,
 }
 
 impl StreamOutput<String> {
@@ -622,22 +667,23 @@ impl StreamOutput<String> {
 }
 
 impl StreamOutput<Vec<u8>> {
+    /// Decodes the output and resolves its terminal control sequences.
     pub fn from_utf8_lossy(&self) -> StreamOutput<String> {
         StreamOutput {
-            text: bytes_to_string_smart(&self.text),
+            text: normalize_output(bytes_to_string_smart(&self.text)),
             truncated_after_lines: self.truncated_after_lines,
         }
     }
 }
 
-#[inline]
-fn append_capped(dst: &mut Vec<u8>, src: &[u8], max_bytes: usize) {
-    if dst.len() >= max_bytes {
-        return;
-    }
-    let remaining = max_bytes.saturating_sub(dst.len());
-    let take = remaining.min(src.len());
-    dst.extend_from_slice(&src[..take]);
+/// Appends `take` bytes of `src` to `dst`, split between its beginning and
+/// its end, so neither is lost when the stream has to be cut.
+fn append_head_and_tail(dst: &mut Vec<u8>, src: &[u8], take: usize) {
+    let take = take.min(src.len());
+    let head = take / 2;
+    let tail = take.saturating_sub(head);
+    dst.extend_from_slice(&src[..head]);
+    dst.extend_from_slice(&src[src.len().saturating_sub(tail)..]);
 }
 
 fn aggregate_output(
@@ -664,8 +710,8 @@ fn aggregate_output(
     let remaining = max_bytes.saturating_sub(want_stdout + stderr_take);
     let stdout_take = want_stdout + remaining.min(stdout.text.len().saturating_sub(want_stdout));
 
-    aggregated.extend_from_slice(&stdout.text[..stdout_take]);
-    aggregated.extend_from_slice(&stderr.text[..stderr_take]);
+    append_head_and_tail(&mut aggregated, &stdout.text, stdout_take);
+    append_head_and_tail(&mut aggregated, &stderr.text, stderr_take);
 
     StreamOutput {
         text: aggregated,
@@ -775,16 +821,8 @@ async fn consume_truncated_output(
         ))
     })?;
 
-    let stdout_handle = tokio::spawn(read_capped(
-        BufReader::new(stdout_reader),
-        stdout_stream.clone(),
-        false,
-    ));
-    let stderr_handle = tokio::spawn(read_capped(
-        BufReader::new(stderr_reader),
-        stdout_stream.clone(),
-        true,
-    ));
+    let stdout_handle = tokio::spawn(read_capped(stdout_reader, stdout_stream.clone(), false));
+    let stderr_handle = tokio::spawn(read_capped(stderr_reader, stdout_stream.clone(), true));
 
     let (exit_status, timed_out) = tokio::select! {
         status_result = child.wait() => {
@@ -817,9 +855,9 @@ async fn consume_truncated_output(
     use tokio::task::JoinHandle;
 
     async fn await_with_timeout(
-        handle: &mut JoinHandle<std::io::Result<StreamOutput<Vec<u8>>>>,
+        handle: &mut JoinHandle<std::io::Result<CapturedOutput>>,
         timeout: Duration,
-    ) -> std::io::Result<StreamOutput<Vec<u8>>> {
+    ) -> std::io::Result<CapturedOutput> {
         match tokio::time::timeout(timeout, &mut *handle).await {
             Ok(join_res) => match join_res {
                 Ok(io_res) => io_res,
@@ -828,9 +866,10 @@ async fn consume_truncated_output(
             Err(_elapsed) => {
                 // Timeout: abort the task to avoid hanging on open pipes.
                 handle.abort();
-                Ok(StreamOutput {
-                    text: Vec::new(),
-                    truncated_after_lines: None,
+                Ok(CapturedOutput {
+                    bytes: Vec::new(),
+                    omitted_bytes: 0,
+                    spill: None,
                 })
             }
         }
@@ -849,6 +888,18 @@ async fn consume_truncated_output(
         Duration::from_millis(IO_DRAIN_TIMEOUT_MS),
     )
     .await?;
+    let omitted_output_notes = [("stdout", &stdout), ("stderr", &stderr)]
+        .into_iter()
+        .filter_map(|(name, output)| output.omission_note(name))
+        .collect();
+    let stdout = StreamOutput {
+        text: stdout.bytes,
+        truncated_after_lines: None,
+    };
+    let stderr = StreamOutput {
+        text: stderr.bytes,
+        truncated_after_lines: None,
+    };
     let aggregated_output = aggregate_output(&stdout, &stderr);
 
     Ok(RawExecToolCallOutput {
@@ -857,6 +908,7 @@ async fn consume_truncated_output(
         stderr,
         aggregated_output,
         timed_out,
+        omitted_output_notes,
     })
 }
 
@@ -864,21 +916,20 @@ async fn read_capped<R: AsyncRead + Unpin + Send + 'static>(
     mut reader: R,
     stream: Option<StdoutStream>,
     is_stderr: bool,
-) -> io::Result<StreamOutput<Vec<u8>>> {
-    let mut buf = Vec::with_capacity(AGGREGATE_BUFFER_INITIAL_CAPACITY.min(EXEC_OUTPUT_MAX_BYTES));
-    let mut tmp = [0u8; READ_CHUNK_SIZE];
+) -> io::Result<CapturedOutput> {
+    let mut capture = OutputCapture::new(EXEC_OUTPUT_MAX_BYTES);
     let mut emitted_deltas: usize = 0;
 
     loop {
-        let n = reader.read(&mut tmp).await?;
-        if n == 0 {
+        let chunk = capture.read_chunk(&mut reader, READ_CHUNK_SIZE).await?;
+        if chunk.is_empty() {
             break;
         }
 
         if let Some(stream) = &stream
             && emitted_deltas < MAX_EXEC_OUTPUT_DELTAS_PER_CALL
         {
-            let chunk = tmp[..n].to_vec();
+            let chunk = chunk.to_vec();
             let msg = EventMsg::ExecCommandOutputDelta(ExecCommandOutputDeltaEvent {
                 call_id: stream.call_id.clone(),
                 stream: if is_stderr {
@@ -896,15 +947,10 @@ async fn read_capped<R: AsyncRead + Unpin + Send + 'static>(
             let _ = stream.tx_event.send(event).await;
             emitted_deltas += 1;
         }
-
-        append_capped(&mut buf, &tmp[..n], EXEC_OUTPUT_MAX_BYTES);
         // Continue reading to EOF to avoid back-pressure
     }
 
-    Ok(StreamOutput {
-        text: buf,
-        truncated_after_lines: None,
-    })
+    Ok(capture.finish().await)
 }
 
 #[cfg(unix)]
@@ -1023,7 +1069,11 @@ mod tests {
         });
 
         let out = read_capped(reader, None, false).await.expect("read");
-        assert_eq!(out.text.len(), EXEC_OUTPUT_MAX_BYTES);
+        assert_eq!(out.bytes.len(), EXEC_OUTPUT_MAX_BYTES);
+        assert_eq!(out.omitted_bytes, 128 * 1024);
+        if let Some(spill) = out.spill {
+            std::fs::remove_file(spill.path).expect("remove spill file");
+        }
     }
 
     #[test]
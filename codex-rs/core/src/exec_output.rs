@@ -0,0 +1,249 @@
+//! Bounded capture of a command's stdout or stderr.
+//!
+//! [`OutputCapture`] keeps the first half of its budget as a head and the
+//! rest as a fixed-size ring holding the most recent output, and reads from
+//! the pipe straight into whichever of the two is being filled, so verbose
+//! commands never grow a buffer past the budget. Once the ring is about to
+//! overwrite output, everything so far is written to a spill file in the
+//! temp directory and the rest of the stream is appended to it, so the full
+//! output can still be read after the call.
+
+use std::io;
+use std::path::PathBuf;
+
+use tokio::io::AsyncRead;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+
+/// Upper bound on a spill file, so a runaway command cannot fill the disk.
+const SPILL_MAX_BYTES: u64 = 256 * 1024 * 1024; // 256 MiB
+
+/// Where output that did not fit the capture was saved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OutputSpill {
+    pub(crate) path: PathBuf,
+    /// False when the stream outgrew [`SPILL_MAX_BYTES`] and the file only
+    /// holds its beginning.
+    pub(crate) complete: bool,
+}
+
+#[derive(Debug)]
+pub(crate) struct CapturedOutput {
+    /// The head followed by the tail; `omitted_bytes` were dropped in between.
+    pub(crate) bytes: Vec<u8>,
+    pub(crate) omitted_bytes: u64,
+    pub(crate) spill: Option<OutputSpill>,
+}
+
+impl CapturedOutput {
+    /// Tells the model how much of stream `name` was left out, and where to
+    /// find it; `None` when nothing was.
+    pub(crate) fn omission_note(&self, name: &str) -> Option<String> {
+        if self.omitted_bytes == 0 {
+            return None;
+        }
+        let omitted = self.omitted_bytes;
+        Some(match &self.spill {
+            Some(spill) if spill.complete => format!(
+                "[{omitted} bytes in the middle of {name} were omitted; the full {name} is in {}]",
+                spill.path.display()
+            ),
+            Some(spill) => format!(
+                "[{omitted} bytes in the middle of {name} were omitted; the first {} MiB of {name} are in {}]",
+                SPILL_MAX_BYTES / (1024 * 1024),
+                spill.path.display()
+            ),
+            None => format!("[{omitted} bytes in the middle of {name} were omitted]"),
+        })
+    }
+}
+
+pub(crate) struct OutputCapture {
+    head: Vec<u8>,
+    head_budget: usize,
+    /// Ring of the most recent output, allocated once the head is full.
+    tail: Box<[u8]>,
+    tail_budget: usize,
+    /// Next position written in `tail`; also its oldest byte once it is full.
+    tail_pos: usize,
+    tail_len: usize,
+    total_bytes: u64,
+    spill: Option<Spill>,
+    spill_failed: bool,
+}
+
+struct Spill {
+    file: tokio::fs::File,
+    path: PathBuf,
+    bytes: u64,
+}
+
+impl OutputCapture {
+    pub(crate) fn new(max_bytes: usize) -> Self {
+        let head_budget = max_bytes / 2;
+        Self {
+            head: Vec::new(),
+            head_budget,
+            tail: Box::default(),
+            // At least one byte, so every read has somewhere to go.
+            tail_budget: max_bytes.saturating_sub(head_budget).max(1),
+            tail_pos: 0,
+            tail_len: 0,
+            total_bytes: 0,
+            spill: None,
+            spill_failed: false,
+        }
+    }
+
+    /// Reads at most `max_chunk` bytes from `reader` into the capture and
+    /// returns them; empty at end of file.
+    pub(crate) async fn read_chunk<R: AsyncRead + Unpin>(
+        &mut self,
+        reader: &mut R,
+        max_chunk: usize,
+    ) -> io::Result<&[u8]> {
+        if self.head.len() < self.head_budget {
+            let start = self.head.len();
+            let end = start.saturating_add(max_chunk).min(self.head_budget);
+            self.head.resize(end, 0);
+            let n = reader.read(&mut self.head[start..]).await?;
+            self.head.truncate(start + n);
+            self.total_bytes = self.total_bytes.saturating_add(n as u64);
+            return Ok(&self.head[start..start + n]);
+        }
+
+        if self.tail.is_empty() {
+            self.tail = vec![0; self.tail_budget].into_boxed_slice();
+        }
+        if self.tail_len == self.tail_budget && self.spill.is_none() && !self.spill_failed {
+            self.start_spill().await;
+        }
+        let start = self.tail_pos;
+        let end = start.saturating_add(max_chunk).min(self.tail_budget);
+        let n = reader.read(&mut self.tail[start..end]).await?;
+        self.total_bytes = self.total_bytes.saturating_add(n as u64);
+        self.tail_pos = (start + n) % self.tail_budget;
+        self.tail_len = self.tail_len.saturating_add(n).min(self.tail_budget);
+        if let Some(spill) = &mut self.spill {
+            spill.append(&self.tail[start..start + n]).await;
+        }
+        Ok(&self.tail[start..start + n])
+    }
+
+    pub(crate) async fn finish(mut self) -> CapturedOutput {
+        let retained = self.head.len().saturating_add(self.tail_len);
+        let mut bytes = std::mem::take(&mut self.head);
+        bytes.reserve_exact(self.tail_len);
+        if self.tail_len == self.tail_budget {
+            bytes.extend_from_slice(&self.tail[self.tail_pos..]);
+        }
+        bytes.extend_from_slice(&self.tail[..self.tail_pos.min(self.tail_len)]);
+        let spill = match self.spill {
+            Some(mut spill) => {
+                let _ = spill.file.flush().await;
+                Some(OutputSpill {
+                    complete: spill.bytes == self.total_bytes,
+                    path: spill.path,
+                })
+            }
+            None => None,
+        };
+        CapturedOutput {
+            bytes,
+            omitted_bytes: self.total_bytes.saturating_sub(retained as u64),
+            spill,
+        }
+    }
+
+    /// Saves the output so far, which is all still retained because the ring
+    /// has not wrapped yet, to a new spill file.
+    async fn start_spill(&mut self) {
+        let created = tempfile::Builder::new()
+            .prefix("codex-exec-output-")
+            .suffix(".log")
+            .tempfile()
+            .and_then(|file| file.keep().map_err(|err| err.error));
+        let (file, path) = match created {
+            Ok(created) => created,
+            Err(err) => {
+                tracing::warn!("failed to create a spill file for exec output: {err}");
+                self.spill_failed = true;
+                return;
+            }
+        };
+        let mut spill = Spill {
+            file: tokio::fs::File::from_std(file),
+            path,
+            bytes: 0,
+        };
+        spill.append(&self.head).await;
+        spill.append(&self.tail[..self.tail_len]).await;
+        self.spill = Some(spill);
+    }
+}
+
+impl Spill {
+    async fn append(&mut self, bytes: &[u8]) {
+        let remaining = SPILL_MAX_BYTES.saturating_sub(self.bytes);
+        let take = usize::try_from(remaining)
+            .unwrap_or(usize::MAX)
+            .min(bytes.len());
+        if take == 0 {
+            return;
+        }
+        match self.file.write_all(&bytes[..take]).await {
+            Ok(()) => self.bytes = self.bytes.saturating_add(take as u64),
+            Err(err) => {
+                tracing::warn!(
+                    "failed to write exec output to {}: {err}",
+                    self.path.display()
+                );
+                // Stop writing; the file is reported as incomplete.
+                self.bytes = SPILL_MAX_BYTES;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    async fn capture(input: &[u8], max_bytes: usize, max_chunk: usize) -> CapturedOutput {
+        let mut reader = input;
+        let mut capture = OutputCapture::new(max_bytes);
+        while !capture
+            .read_chunk(&mut reader, max_chunk)
+            .await
+            .expect("read")
+            .is_empty()
+        {}
+        capture.finish().await
+    }
+
+    #[tokio::test]
+    async fn keeps_everything_within_budget() {
+        let output = capture(b"hello world", 32, 4).await;
+
+        assert_eq!(output.bytes, b"hello world");
+        assert_eq!(output.omitted_bytes, 0);
+        assert_eq!(output.spill, None);
+    }
+
+    #[tokio::test]
+    async fn keeps_head_and_tail_and_spills_the_rest() {
+        let input: Vec<u8> = (0..100u8).collect();
+        let output = capture(&input, 20, 3).await;
+
+        let mut expected = input[..10].to_vec();
+        expected.extend_from_slice(&input[90..]);
+        assert_eq!(output.bytes, expected);
+        assert_eq!(output.omitted_bytes, 80);
+
+        let spill = output.spill.expect("output over budget is spilled");
+        assert!(spill.complete);
+        assert_eq!(std::fs::read(&spill.path).expect("read spill"), input);
+        std::fs::remove_file(&spill.path).expect("remove spill");
+    }
+}
@@ -10,6 +10,7 @@ use crate::config_loader::ConfigLayerStackOrdering;
 use crate::is_dangerous_command::command_might_be_dangerous;
 use crate::is_safe_command::is_known_safe_command;
 use codex_execpolicy::AmendError;
+use codex_execpolicy::CommandFacts;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Error as ExecPolicyRuleError;
 use codex_execpolicy::Evaluation;
@@ -86,7 +87,7 @@ static BANNED_PREFIX_SUGGESTIONS: &[&[&str]] = &[
 
 fn is_policy_match(rule_match: &RuleMatch) -> bool {
     match rule_match {
-        RuleMatch::PrefixRuleMatch { .. } => true,
+        RuleMatch::PrefixRuleMatch { .. } | RuleMatch::CommandRuleMatch { .. } => true,
         RuleMatch::HeuristicsRuleMatch { .. } => false,
     }
 }
@@ -133,6 +134,7 @@ pub(crate) struct ExecPolicyManager {
 
 pub(crate) struct ExecApprovalRequest<'a> {
     pub(crate) command: &'a [String],
+    pub(crate) cwd: Option<&'a Path>,
     pub(crate) approval_policy: AskForApproval,
     pub(crate) sandbox_policy: &'a SandboxPolicy,
     pub(crate) sandbox_permissions: SandboxPermissions,
@@ -164,6 +166,7 @@ impl ExecPolicyManager {
     ) -> ExecApprovalRequirement {
         let ExecApprovalRequest {
             command,
+            cwd,
             approval_policy,
             sandbox_policy,
             sandbox_permissions,
@@ -175,15 +178,14 @@ impl ExecPolicyManager {
         // allow/prompt/forbidden rules still apply, but avoid auto-derived
         // amendments when only the heredoc fallback parser matched.
         let auto_amendment_allowed = !used_heredoc_fallback;
-        let exec_policy_fallback = |cmd: &[String]| {
-            render_decision_for_unmatched_command(
-                approval_policy,
-                sandbox_policy,
-                cmd,
-                sandbox_permissions,
-            )
-        };
-        let evaluation = exec_policy.check_multiple(commands.iter(), &exec_policy_fallback);
+        let facts = facts_for_commands(command, commands, cwd, sandbox_policy, sandbox_permissions);
+        let evaluation = evaluate_command(
+            &exec_policy,
+            &facts,
+            approval_policy,
+            sandbox_policy,
+            sandbox_permissions,
+        );
 
         let requested_amendment =
             derive_requested_execpolicy_amendment(prefix_rule.as_ref(), &evaluation.matched_rules);
@@ -213,9 +215,16 @@ impl ExecPolicyManager {
                 }
             }
             Decision::Allow => ExecApprovalRequirement::Skip {
-                // Bypass sandbox if execpolicy allows the command
+                // Bypass sandbox if a prefix rule allows the command;
+                // commands allowed by a `command_rule` stay sandboxed.
                 bypass_sandbox: evaluation.matched_rules.iter().any(|rule_match| {
-                    is_policy_match(rule_match) && rule_match.decision() == Decision::Allow
+                    matches!(
+                        rule_match,
+                        RuleMatch::PrefixRuleMatch {
+                            decision: Decision::Allow,
+                            ..
+                        }
+                    )
                 }),
                 proposed_execpolicy_amendment: if auto_amendment_allowed {
                     try_derive_execpolicy_amendment_for_allow_rules(&evaluation.matched_rules)
@@ -397,7 +406,71 @@ pub async fn load_exec_policy(config_stack: &ConfigLayerStack) -> Result<Policy,
         }
     }
 
-    Ok(Policy::new(combined_rules))
+    Ok(Policy::new(combined_rules).with_command_rules(policy.command_rules().to_vec()))
+}
+
+/// The facts `command_rule`s are evaluated against for `command` running in
+/// `cwd` under `sandbox_policy`.
+pub fn command_facts(
+    command: &[String],
+    cwd: Option<&Path>,
+    sandbox_policy: &SandboxPolicy,
+    sandbox_permissions: SandboxPermissions,
+) -> CommandFacts {
+    let (commands, _) = commands_for_exec_policy(command);
+    facts_for_commands(command, commands, cwd, sandbox_policy, sandbox_permissions)
+}
+
+fn facts_for_commands(
+    command: &[String],
+    commands: Vec<Vec<String>>,
+    cwd: Option<&Path>,
+    sandbox_policy: &SandboxPolicy,
+    sandbox_permissions: SandboxPermissions,
+) -> CommandFacts {
+    CommandFacts {
+        argv: command.to_vec(),
+        // Resolve symlinks and `..` so `cwd_under` sees where the command
+        // actually runs.
+        cwd: cwd.map(|cwd| dunce::canonicalize(cwd).unwrap_or_else(|_| cwd.to_path_buf())),
+        risk: risk_score(command),
+        network: Some(
+            sandbox_policy.has_full_network_access()
+                || sandbox_permissions.requires_escalated_permissions(),
+        ),
+        subcommands: commands,
+    }
+}
+
+/// Decides the command described by `facts` from the rules in `exec_policy`,
+/// falling back to the approval and sandbox heuristics for the subcommands no
+/// rule covers.
+pub fn evaluate_command(
+    exec_policy: &Policy,
+    facts: &CommandFacts,
+    approval_policy: AskForApproval,
+    sandbox_policy: &SandboxPolicy,
+    sandbox_permissions: SandboxPermissions,
+) -> Evaluation {
+    let exec_policy_fallback = |cmd: &[String]| {
+        render_decision_for_unmatched_command(
+            approval_policy,
+            sandbox_policy,
+            cmd,
+            sandbox_permissions,
+        )
+    };
+    exec_policy.check_with_facts(facts.subcommands.iter(), facts, &exec_policy_fallback)
+}
+
+fn risk_score(command: &[String]) -> u8 {
+    if is_known_safe_command(command) {
+        0
+    } else if command_might_be_dangerous(command) {
+        2
+    } else {
+        1
+    }
 }
 
 /// If a command is not matched by any execpolicy rule, derive a [`Decision`].
@@ -464,6 +537,44 @@ pub fn render_decision_for_unmatched_command(
     }
 }
 
+/// Decides how the exec server handles one intercepted `execve(2)`. Commands
+/// no rule matches are prompted when they look dangerous and run otherwise.
+/// When a rule (rather than the heuristic) drove the decision, the command
+/// runs outside the sandbox, which the returned permissions signal.
+pub fn evaluate_intercepted_exec(
+    policy: &Policy,
+    command: &[String],
+) -> (Decision, SandboxPermissions) {
+    // The exec server knows neither the working directory nor whether the
+    // program has network access, so only forbidden and prompt rules that
+    // test them apply here.
+    let facts = CommandFacts {
+        argv: command.to_vec(),
+        cwd: None,
+        risk: risk_score(command),
+        network: None,
+        subcommands: vec![command.to_vec()],
+    };
+    let evaluation = policy.check_with_facts([command], &facts, &|cmd| {
+        if command_might_be_dangerous(cmd) {
+            Decision::Prompt
+        } else {
+            Decision::Allow
+        }
+    });
+
+    let decision_driven_by_policy = evaluation.matched_rules.iter().any(|rule_match| {
+        matches!(rule_match, RuleMatch::PrefixRuleMatch { .. })
+            && rule_match.decision() == evaluation.decision
+    });
+    let sandbox_permissions = if decision_driven_by_policy {
+        SandboxPermissions::RequireEscalated
+    } else {
+        SandboxPermissions::UseDefault
+    };
+    (evaluation.decision, sandbox_permissions)
+}
+
 fn default_policy_path(codex_home: &Path) -> PathBuf {
     codex_home.join(RULES_DIR_NAME).join(DEFAULT_POLICY_FILE)
 }
@@ -566,6 +677,13 @@ fn derive_requested_execpolicy_amendment(
 fn derive_prompt_reason(command_args: &[String], evaluation: &Evaluation) -> Option<String> {
     let command = render_shlex_command(command_args);
 
+    if let Some((rule, justification)) = command_rule_match(evaluation, Decision::Prompt) {
+        return Some(match justification {
+            Some(justification) => format!("`{command}` requires approval: {justification}"),
+            None => format!("`{command}` requires approval by policy rule `{rule}`"),
+        });
+    }
+
     let most_specific_prompt = evaluation
         .matched_rules
         .iter()
@@ -601,6 +719,13 @@ fn render_shlex_command(args: &[String]) -> String {
 fn derive_forbidden_reason(command_args: &[String], evaluation: &Evaluation) -> String {
     let command = render_shlex_command(command_args);
 
+    if let Some((rule, justification)) = command_rule_match(evaluation, Decision::Forbidden) {
+        return match justification {
+            Some(justification) => format!("`{command}` rejected: {justification}"),
+            None => format!("`{command}` rejected: blocked by policy rule `{rule}`"),
+        };
+    }
+
     let most_specific_forbidden = evaluation
         .matched_rules
         .iter()
@@ -627,6 +752,21 @@ fn derive_forbidden_reason(command_args: &[String], evaluation: &Evaluation) ->
     }
 }
 
+/// The first `command_rule` that decided `decision`, with its justification.
+fn command_rule_match(evaluation: &Evaluation, decision: Decision) -> Option<(&str, Option<&str>)> {
+    evaluation
+        .matched_rules
+        .iter()
+        .find_map(|rule_match| match rule_match {
+            RuleMatch::CommandRuleMatch {
+                rule,
+                decision: matched,
+                justification,
+            } if *matched == decision => Some((rule.as_str(), justification.as_deref())),
+            _ => None,
+        })
+}
+
 async fn collect_policy_files(dir: impl AsRef<Path>) -> Result<Vec<PathBuf>, ExecPolicyError> {
     let dir = dir.as_ref();
     let mut read_dir = match fs::read_dir(dir).await {
@@ -979,6 +1119,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &forbidden_script,
+                cwd: None,
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1029,6 +1170,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         let requirement = ExecPolicyManager::new(policy)
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: None,
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1056,6 +1198,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         let requirement = ExecPolicyManager::default()
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: None,
                 approval_policy: AskForApproval::UnlessTrusted,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1084,6 +1227,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         let requirement = ExecPolicyManager::default()
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: None,
                 approval_policy: AskForApproval::UnlessTrusted,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1123,6 +1267,7 @@ prefix_rule(
                     "-rf".to_string(),
                     "/some/important/folder".to_string(),
                 ],
+                cwd: None,
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1138,6 +1283,86 @@ prefix_rule(
         );
     }
 
+    #[test]
+    fn intercepted_execs_fail_closed_on_unknown_facts() {
+        let policy_src = r#"
+command_rule(program="curl", network=True, decision="forbidden")
+command_rule(program="rm", cwd_under="/work", decision="allow")
+"#;
+        let mut parser = PolicyParser::new();
+        parser
+            .parse("test.rules", policy_src)
+            .expect("parse policy");
+        let policy = parser.build();
+
+        let (decision, _) =
+            evaluate_intercepted_exec(&policy, &vec_str(&["curl", "https://example.com"]));
+        assert_eq!(decision, Decision::Forbidden);
+        // The allow rule cannot vouch for a directory it does not know, so the
+        // dangerous-command heuristic still prompts.
+        let (decision, _) = evaluate_intercepted_exec(&policy, &vec_str(&["rm", "-rf", "build"]));
+        assert_eq!(decision, Decision::Prompt);
+    }
+
+    #[tokio::test]
+    async fn command_rules_see_each_program_of_a_script_and_keep_the_sandbox() {
+        let policy_src = r#"
+command_rule(
+    program="curl",
+    network=True,
+    decision="forbidden",
+    justification="no network downloads from the agent",
+)
+command_rule(
+    cwd_under="/work",
+    min_risk=1,
+    decision="allow",
+)
+"#;
+        let mut parser = PolicyParser::new();
+        parser
+            .parse("test.rules", policy_src)
+            .expect("parse policy");
+        let manager = ExecPolicyManager::new(Arc::new(parser.build()));
+
+        let script = vec_str(&["bash", "-lc", "cd src && curl https://example.com"]);
+        let requirement = manager
+            .create_exec_approval_requirement_for_command(ExecApprovalRequest {
+                command: &script,
+                cwd: None,
+                approval_policy: AskForApproval::OnRequest,
+                sandbox_policy: &SandboxPolicy::DangerFullAccess,
+                sandbox_permissions: SandboxPermissions::UseDefault,
+                prefix_rule: None,
+            })
+            .await;
+        assert_eq!(
+            requirement,
+            ExecApprovalRequirement::Forbidden {
+                reason: "`bash -lc 'cd src && curl https://example.com'` rejected: no network downloads from the agent".to_string()
+            }
+        );
+
+        let command = vec_str(&["cargo", "build"]);
+        let requirement = manager
+            .create_exec_approval_requirement_for_command(ExecApprovalRequest {
+                command: &command,
+                cwd: Some(Path::new("/work/repo")),
+                approval_policy: AskForApproval::UnlessTrusted,
+                sandbox_policy: &SandboxPolicy::new_read_only_policy(),
+                sandbox_permissions: SandboxPermissions::UseDefault,
+                prefix_rule: None,
+            })
+            .await;
+        assert_eq!(
+            requirement,
+            ExecApprovalRequirement::Skip {
+                bypass_sandbox: false,
+                proposed_execpolicy_amendment: None,
+            }
+        );
+    }
+
     #[tokio::test]
     async fn exec_approval_requirement_prefers_execpolicy_match() {
         let policy_src = r#"prefix_rule(pattern=["rm"], decision="prompt")"#;
@@ -1152,6 +1377,7 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: None,
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1182,6 +1408,7 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: None,
                 approval_policy: AskForApproval::Never,
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1205,6 +1432,7 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: None,
                 approval_policy: AskForApproval::UnlessTrusted,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1229,6 +1457,7 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: None,
                 approval_policy: AskForApproval::UnlessTrusted,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1257,6 +1486,7 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: None,
                 approval_policy: AskForApproval::UnlessTrusted,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1287,6 +1517,7 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: None,
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::RequireEscalated,
@@ -1324,6 +1555,7 @@ prefix_rule(
             ExecPolicyManager::new(policy)
                 .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                     command: &command,
+                    cwd: None,
                     approval_policy: AskForApproval::UnlessTrusted,
                     sandbox_policy: &SandboxPolicy::DangerFullAccess,
                     sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1398,6 +1630,7 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: None,
                 approval_policy: AskForApproval::UnlessTrusted,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1428,6 +1661,7 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: None,
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1455,6 +1689,7 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: None,
                 approval_policy: AskForApproval::UnlessTrusted,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1493,6 +1728,7 @@ prefix_rule(
             ExecPolicyManager::new(policy)
                 .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                     command: &command,
+                    cwd: None,
                     approval_policy: AskForApproval::UnlessTrusted,
                     sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                     sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1516,6 +1752,7 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: None,
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1546,6 +1783,7 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: None,
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1665,6 +1903,7 @@ prefix_rule(
         let requirement = manager
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &command,
+                cwd: None,
                 approval_policy: AskForApproval::OnRequest,
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
@@ -1731,6 +1970,7 @@ prefix_rule(
             policy
                 .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                     command: &sneaky_command,
+                    cwd: None,
                     approval_policy: AskForApproval::OnRequest,
                     sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                     sandbox_permissions: permissions,
@@ -1754,6 +1994,7 @@ prefix_rule(
             policy
                 .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                     command: &dangerous_command,
+                    cwd: None,
                     approval_policy: AskForApproval::OnRequest,
                     sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                     sandbox_permissions: permissions,
@@ -1773,6 +2014,7 @@ prefix_rule(
             policy
                 .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                     command: &dangerous_command,
+                    cwd: None,
                     approval_policy: AskForApproval::Never,
                     sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                     sandbox_permissions: permissions,
@@ -82,6 +82,32 @@ pub enum Feature {
     JsRepl,
     /// Only expose js_repl tools directly to the model.
     JsReplToolsOnly,
+    /// Enable Python REPL tools backed by a persistent interpreter.
+    PyRepl,
+    /// Enable the headless browser tool driven over the Chrome DevTools Protocol.
+    Browser,
+    /// Inject a compact git repository brief into the initial context.
+    RepoBrief,
+    /// Expose the structured `git` tool.
+    GitTool,
+    /// Summarize exec output that is over the truncation budget instead of
+    /// cutting its middle, and expose `fetch_output` to read the rest.
+    OutputSummaries,
+    /// Expose the `search_content` tool, which returns ranked snippets of the
+    /// files containing a query.
+    ContentSearch,
+    /// Detect the project's build, test, lint and format commands, cache them
+    /// in `.codex/project.toml`, and list them in the initial context.
+    ProjectCommands,
+    /// Expose the `memory` tool over `.codex/memory/` and load the most
+    /// relevant entries into the initial context.
+    ProjectMemory,
+    /// Read the files `read_file` is likely to be asked for next into a
+    /// cache in the background.
+    ReadPrefetch,
+    /// Start MCP servers that are not `required` when their tools are first
+    /// needed instead of when the session starts.
+    LazyMcpStartup,
     /// Use the single unified PTY-backed exec tool.
     UnifiedExec,
     /// Include the freeform apply_patch tool.
@@ -119,6 +145,8 @@ pub enum Feature {
     EnableRequestCompression,
     /// Enable collab tools.
     Collab,
+    /// Expose the `delegate` tool for bounded, parallel sub-sessions.
+    Delegate,
     /// Enable apps.
     Apps,
     /// Route apps MCP calls through the configured gateway.
@@ -320,7 +348,11 @@ impl Features {
                 .experimental_use_freeform_apply_patch,
 
             experimental_use_unified_exec_tool: config_profile.experimental_use_unified_exec_tool,
-            tools_web_search: config_profile.tools_web_search,
+            tools_web_search: config_profile
+                .tools
+                .as_ref()
+                .and_then(|tools| tools.web_search)
+                .or(config_profile.tools_web_search),
         };
         profile_legacy.apply(&mut features);
         if let Some(profile_features) = config_profile.features.as_ref() {
@@ -446,6 +478,66 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::UnderDevelopment,
         default_enabled: false,
     },
+    FeatureSpec {
+        id: Feature::PyRepl,
+        key: "py_repl",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::Browser,
+        key: "browser",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::RepoBrief,
+        key: "repo_brief",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::GitTool,
+        key: "git_tool",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::OutputSummaries,
+        key: "output_summaries",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::ContentSearch,
+        key: "content_search",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::ProjectCommands,
+        key: "project_commands",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::ProjectMemory,
+        key: "project_memory",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::ReadPrefetch,
+        key: "read_prefetch",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::LazyMcpStartup,
+        key: "lazy_mcp_startup",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
     FeatureSpec {
         id: Feature::WebSearchRequest,
         key: "web_search_request",
@@ -560,6 +652,12 @@ pub const FEATURES: &[FeatureSpec] = &[
         },
         default_enabled: false,
     },
+    FeatureSpec {
+        id: Feature::Delegate,
+        key: "delegate",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
     FeatureSpec {
         id: Feature::Apps,
         key: "apps",
@@ -1,5 +1,6 @@
 //! Watches skill roots for changes and broadcasts coarse-grained
 //! `FileWatcherEvent`s that higher-level components react to on the next turn.
+//! Changes to paths excluded by `.gitignore` or `.codexignore` are dropped.
 
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -10,6 +11,7 @@ use std::sync::Mutex;
 use std::sync::RwLock;
 use std::time::Duration;
 
+use codex_file_search::IgnoreRules;
 use notify::Event;
 use notify::EventKind;
 use notify::RecommendedWatcher;
@@ -255,8 +257,15 @@ fn classify_event(event: &Event, state: &RwLock<WatchState>) -> Vec<PathBuf> {
         }
     };
 
+    let mut rules: HashMap<&PathBuf, IgnoreRules> = HashMap::new();
     for path in &event.paths {
-        if is_skills_path(path, &skills_roots) {
+        let Some(root) = skills_root_for(path, &skills_roots) else {
+            continue;
+        };
+        let rules = rules
+            .entry(root)
+            .or_insert_with(|| IgnoreRules::new(root, true));
+        if !rules.is_ignored(path) {
             skills_paths.push(path.clone());
         }
     }
@@ -264,8 +273,8 @@ fn classify_event(event: &Event, state: &RwLock<WatchState>) -> Vec<PathBuf> {
     skills_paths
 }
 
-fn is_skills_path(path: &Path, roots: &HashSet<PathBuf>) -> bool {
-    roots.iter().any(|root| path.starts_with(root))
+fn skills_root_for<'a>(path: &Path, roots: &'a HashSet<PathBuf>) -> Option<&'a PathBuf> {
+    roots.iter().find(|root| path.starts_with(root))
 }
 
 #[cfg(test)]
@@ -368,6 +377,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn classify_event_drops_ignored_paths() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let root = temp.path().to_path_buf();
+        std::fs::write(root.join(".codexignore"), "drafts/\n").expect("write");
+        let state = RwLock::new(WatchState {
+            skills_roots: HashSet::from([root.clone()]),
+        });
+        let event = notify_event(
+            EventKind::Modify(ModifyKind::Any),
+            vec![root.join("demo/SKILL.md"), root.join("drafts/SKILL.md")],
+        );
+
+        let classified = classify_event(&event, &state);
+        assert_eq!(classified, vec![root.join("demo/SKILL.md")]);
+    }
+
     #[test]
     fn classify_event_ignores_non_mutating_event_kinds() {
         let root = path("/tmp/skills");
@@ -0,0 +1,311 @@
+//! Minimal GitHub/GitLab client used to open a pull (merge) request for the
+//! session branch.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use codex_secrets::SecretName;
+use codex_secrets::SecretScope;
+use codex_secrets::SecretsBackendKind;
+use codex_secrets::SecretsManager;
+use reqwest::header::ACCEPT;
+use reqwest::header::AUTHORIZATION;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::config::types::ForgeHostKind;
+use crate::config::types::ForgeHostToml;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ForgeKind {
+    GitHub,
+    GitLab,
+}
+
+impl ForgeKind {
+    pub(crate) fn request_noun(self) -> &'static str {
+        match self {
+            ForgeKind::GitHub => "pull request",
+            ForgeKind::GitLab => "merge request",
+        }
+    }
+}
+
+/// A repository hosted on a supported forge, derived from a git remote URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ForgeRepo {
+    pub(crate) kind: ForgeKind,
+    pub(crate) host: String,
+    /// `owner/repo` on GitHub; `group/subgroup/project` on GitLab.
+    pub(crate) path: String,
+    /// Name of the secret (and fallback environment variable) holding the API token.
+    token_name: String,
+}
+
+impl ForgeRepo {
+    /// Parses `git@host:owner/repo.git`, `ssh://git@host/owner/repo` and
+    /// `https://host/owner/repo` remotes. Only `github.com`, `gitlab.com` and
+    /// the hosts listed in `forge_hosts` are supported, so a token is never
+    /// sent to a host the user did not name.
+    pub(crate) fn from_remote_url(
+        url: &str,
+        forge_hosts: &HashMap<String, ForgeHostToml>,
+    ) -> Option<Self> {
+        let url = url.trim();
+        let (host, path) = if let Some((_, rest)) = url.split_once("://") {
+            let (authority, path) = rest.split_once('/')?;
+            let host = authority.rsplit_once('@').map_or(authority, |(_, h)| h);
+            (host.split_once(':').map_or(host, |(h, _)| h), path)
+        } else {
+            let (authority, path) = url.split_once(':')?;
+            (
+                authority.rsplit_once('@').map_or(authority, |(_, h)| h),
+                path,
+            )
+        };
+        let path = path.trim_matches('/');
+        let path = path.strip_suffix(".git").unwrap_or(path);
+        if host.is_empty() || path.split('/').filter(|part| !part.is_empty()).count() < 2 {
+            return None;
+        }
+
+        let host = host.to_ascii_lowercase();
+        let (kind, token_name) = match (forge_hosts.get(&host), host.as_str()) {
+            (Some(forge), _) => {
+                let kind = match forge.kind {
+                    ForgeHostKind::Github => ForgeKind::GitHub,
+                    ForgeHostKind::Gitlab => ForgeKind::GitLab,
+                };
+                (kind, forge.token.clone())
+            }
+            (None, "github.com") => (ForgeKind::GitHub, "GITHUB_TOKEN".to_string()),
+            (None, "gitlab.com") => (ForgeKind::GitLab, "GITLAB_TOKEN".to_string()),
+            (None, _) => return None,
+        };
+        Some(Self {
+            kind,
+            host,
+            path: path.to_string(),
+            token_name,
+        })
+    }
+
+    fn api_base(&self) -> String {
+        match self.kind {
+            ForgeKind::GitHub if self.host == "github.com" => "https://api.github.com".to_string(),
+            ForgeKind::GitHub => format!("https://{}/api/v3", self.host),
+            ForgeKind::GitLab => format!("https://{}/api/v4", self.host),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PullRequestDraft {
+    pub(crate) title: String,
+    pub(crate) body: String,
+    pub(crate) head: String,
+    pub(crate) base: String,
+}
+
+/// Reads the forge token from the Codex secrets store (global scope), falling
+/// back to the environment variable of the same name.
+pub(crate) async fn load_token(codex_home: &Path, repo: &ForgeRepo) -> Option<String> {
+    let codex_home = codex_home.to_path_buf();
+    let name = repo.token_name.as_str();
+    let stored = tokio::task::spawn_blocking({
+        let name = name.to_string();
+        move || {
+            let name = SecretName::new(&name).ok()?;
+            SecretsManager::new(codex_home, SecretsBackendKind::Local)
+                .get(&SecretScope::Global, &name)
+                .ok()
+                .flatten()
+        }
+    })
+    .await
+    .ok()
+    .flatten();
+    stored
+        .or_else(|| std::env::var(name).ok())
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+}
+
+#[derive(Deserialize)]
+struct GitHubPullResponse {
+    html_url: String,
+}
+
+#[derive(Deserialize)]
+struct GitLabMergeResponse {
+    web_url: String,
+}
+
+/// Opens the pull/merge request and returns its web URL.
+pub(crate) async fn create_pull_request(
+    repo: &ForgeRepo,
+    token: &str,
+    draft: &PullRequestDraft,
+) -> Result<String, String> {
+    let client = crate::default_client::build_reqwest_client();
+    let request = match repo.kind {
+        ForgeKind::GitHub => client
+            .post(format!("{}/repos/{}/pulls", repo.api_base(), repo.path))
+            .header(AUTHORIZATION, format!("Bearer {token}"))
+            .header(ACCEPT, "application/vnd.github+json")
+            .json(&json!({
+                "title": draft.title,
+                "body": draft.body,
+                "head": draft.head,
+                "base": draft.base,
+            })),
+        ForgeKind::GitLab => {
+            let project: String =
+                url::form_urlencoded::byte_serialize(repo.path.as_bytes()).collect();
+            client
+                .post(format!(
+                    "{}/projects/{project}/merge_requests",
+                    repo.api_base()
+                ))
+                .header("PRIVATE-TOKEN", token)
+                .json(&json!({
+                    "title": draft.title,
+                    "description": draft.body,
+                    "source_branch": draft.head,
+                    "target_branch": draft.base,
+                }))
+        }
+    };
+
+    let response = request
+        .send()
+        .await
+        .map_err(|err| format!("request to {} failed: {err}", repo.host))?;
+    let status = response.status();
+    let text = response
+        .text()
+        .await
+        .map_err(|err| format!("failed to read response from {}: {err}", repo.host))?;
+    if !status.is_success() {
+        return Err(format!(
+            "{} rejected the {} ({status}): {}",
+            repo.host,
+            repo.kind.request_noun(),
+            text.trim()
+        ));
+    }
+
+    let url = match repo.kind {
+        ForgeKind::GitHub => {
+            serde_json::from_str::<GitHubPullResponse>(&text).map(|response| response.html_url)
+        }
+        ForgeKind::GitLab => {
+            serde_json::from_str::<GitLabMergeResponse>(&text).map(|response| response.web_url)
+        }
+    };
+    url.map_err(|err| format!("unexpected response from {}: {err}", repo.host))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn repo(kind: ForgeKind, host: &str, path: &str, token_name: &str) -> Option<ForgeRepo> {
+        Some(ForgeRepo {
+            kind,
+            host: host.to_string(),
+            path: path.to_string(),
+            token_name: token_name.to_string(),
+        })
+    }
+
+    fn enterprise_hosts() -> HashMap<String, ForgeHostToml> {
+        HashMap::from([
+            (
+                "github.example.com".to_string(),
+                ForgeHostToml {
+                    kind: ForgeHostKind::Github,
+                    token: "GHE_TOKEN".to_string(),
+                },
+            ),
+            (
+                "code.example.com".to_string(),
+                ForgeHostToml {
+                    kind: ForgeHostKind::Gitlab,
+                    token: "CODE_TOKEN".to_string(),
+                },
+            ),
+        ])
+    }
+
+    #[test]
+    fn parses_common_remote_url_shapes() {
+        let hosts = enterprise_hosts();
+        assert_eq!(
+            ForgeRepo::from_remote_url("git@github.com:openai/codex.git", &hosts),
+            repo(
+                ForgeKind::GitHub,
+                "github.com",
+                "openai/codex",
+                "GITHUB_TOKEN"
+            )
+        );
+        assert_eq!(
+            ForgeRepo::from_remote_url("https://github.com/openai/codex", &hosts),
+            repo(
+                ForgeKind::GitHub,
+                "github.com",
+                "openai/codex",
+                "GITHUB_TOKEN"
+            )
+        );
+        assert_eq!(
+            ForgeRepo::from_remote_url(
+                "ssh://git@code.example.com:2222/group/sub/project.git",
+                &hosts
+            ),
+            repo(
+                ForgeKind::GitLab,
+                "code.example.com",
+                "group/sub/project",
+                "CODE_TOKEN"
+            )
+        );
+        assert_eq!(
+            ForgeRepo::from_remote_url("https://token@gitlab.com/group/project/", &hosts),
+            repo(
+                ForgeKind::GitLab,
+                "gitlab.com",
+                "group/project",
+                "GITLAB_TOKEN"
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_hosts_and_incomplete_paths() {
+        let hosts = enterprise_hosts();
+        for url in [
+            "git@bitbucket.org:team/repo.git",
+            "https://github.attacker.com/team/repo",
+            "https://mygitlab.evil.io/team/repo",
+            "git@gitlab.example.com:team/repo.git",
+            "https://github.com/openai",
+            "/srv/git/repo.git",
+        ] {
+            assert_eq!(ForgeRepo::from_remote_url(url, &hosts), None, "{url}");
+        }
+    }
+
+    #[test]
+    fn api_base_handles_enterprise_hosts() {
+        let hosts = enterprise_hosts();
+        let enterprise = ForgeRepo::from_remote_url("git@github.example.com:team/repo", &hosts)
+            .expect("configured host");
+        assert_eq!(enterprise.api_base(), "https://github.example.com/api/v3");
+        let gitlab = ForgeRepo::from_remote_url("https://gitlab.com/group/project", &hosts)
+            .expect("gitlab.com");
+        assert_eq!(gitlab.api_base(), "https://gitlab.com/api/v4");
+    }
+}
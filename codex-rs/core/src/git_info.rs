@@ -276,6 +276,49 @@ async fn run_git_command_with_timeout(args: &[&str], cwd: &Path) -> Option<std::
     }
 }
 
+/// Passed to every git command Codex runs outside the sandbox on the user's
+/// behalf, so nothing the workspace controls runs with it: hooks (a
+/// repository can point `core.hooksPath` at a writable directory such as
+/// `.husky`), the fsmonitor daemon, and external diff programs.
+const HARDENING_ARGS: &[&str] = &[
+    "-c",
+    "core.hooksPath=/dev/null",
+    "-c",
+    "core.fsmonitor=false",
+    "-c",
+    "diff.external=",
+];
+
+/// Runs git outside the sandbox with [`HARDENING_ARGS`], returning stdout or
+/// a message describing the failure. Callers that commit or push should also
+/// pass `--no-verify`.
+pub(crate) async fn run_hardened_git<S: AsRef<str>>(
+    cwd: &Path,
+    args: &[S],
+    timeout_after: TokioDuration,
+) -> Result<String, String> {
+    let mut command = Command::new("git");
+    command
+        .env("GIT_OPTIONAL_LOCKS", "0")
+        .args(HARDENING_ARGS)
+        .args(args.iter().map(AsRef::as_ref))
+        .current_dir(cwd)
+        .kill_on_drop(true);
+    let output = timeout(timeout_after, command.output())
+        .await
+        .map_err(|_| "git timed out".to_string())?
+        .map_err(|err| format!("failed to run git: {err}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "git exited with {}: {}",
+            output.status,
+            stderr.trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
 async fn get_git_remotes(cwd: &Path) -> Option<Vec<String>> {
     let output = run_git_command_with_timeout(&["remote"], cwd).await?;
     if !output.status.success() {
@@ -663,6 +706,27 @@ pub async fn local_git_branches(cwd: &Path) -> Vec<String> {
     branches
 }
 
+/// Returns the paths with uncommitted changes (including untracked files), as
+/// reported by `git status --porcelain`. Renames are reported by their new path.
+pub async fn dirty_files(cwd: &Path) -> Option<Vec<String>> {
+    let output = run_git_command_with_timeout(&["status", "--porcelain"], cwd).await?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    Some(
+        text.lines()
+            .filter_map(|line| line.get(3..))
+            .map(|path| {
+                path.rsplit_once(" -> ")
+                    .map_or(path, |(_, new_path)| new_path)
+                    .to_string()
+            })
+            .collect(),
+    )
+}
+
 /// Returns the current checked out branch name.
 pub async fn current_branch_name(cwd: &Path) -> Option<String> {
     let out = run_git_command_with_timeout(&["branch", "--show-current"], cwd).await?;
@@ -742,6 +806,28 @@ mod tests {
         repo_path
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn hardened_git_skips_repository_hooks() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let repo_path = create_test_git_repo(&temp_dir).await;
+        let hook = repo_path.join(".git/hooks/pre-commit");
+        fs::write(&hook, "#!/bin/sh\ntouch hook-ran\nexit 1\n").expect("write hook");
+        fs::set_permissions(&hook, fs::Permissions::from_mode(0o755)).expect("chmod hook");
+        fs::write(repo_path.join("test.txt"), "changed").expect("write file");
+
+        run_hardened_git(
+            &repo_path,
+            &["commit", "-am", "Change"],
+            GIT_COMMAND_TIMEOUT,
+        )
+        .await
+        .expect("commit without hooks");
+        assert!(!repo_path.join("hook-ran").exists());
+    }
+
     #[tokio::test]
     async fn test_recent_commits_non_git_directory_returns_empty() {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
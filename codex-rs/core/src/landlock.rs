@@ -92,6 +92,12 @@ pub(crate) fn create_linux_sandbox_command_args(
     if allow_network_for_proxy {
         linux_cmd.push("--allow-network-for-proxy".to_string());
     }
+    if matches!(sandbox_policy, SandboxPolicy::WorkspaceWrite { .. }) {
+        for device in crate::sandbox_devices::allowed_devices() {
+            linux_cmd.push("--allow-device".to_string());
+            linux_cmd.push(device);
+        }
+    }
 
     // Separator so that command arguments starting with `-` are not parsed as
     // options of the helper itself.
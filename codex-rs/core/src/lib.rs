@@ -9,6 +9,7 @@ mod analytics_client;
 pub mod api_bridge;
 mod apply_patch;
 mod apps;
+pub mod audit_log;
 pub mod auth;
 mod client;
 mod client_common;
@@ -16,25 +17,32 @@ pub mod codex;
 pub use codex::SteerInputError;
 mod codex_thread;
 mod compact_remote;
+mod completion_gate;
 pub use codex_thread::CodexThread;
 pub use codex_thread::ThreadConfigSnapshot;
 mod agent;
 mod codex_delegate;
 mod command_canonicalization;
+mod command_explanation;
 pub mod config;
 pub mod config_loader;
 pub mod connectors;
 mod context_manager;
+mod context_packs;
 pub mod custom_prompts;
+mod disk_quota;
+mod encryption;
 pub mod env;
 mod environment_context;
 pub mod error;
 pub mod exec;
 pub mod exec_env;
+mod exec_output;
 mod exec_policy;
 pub mod features;
 mod file_watcher;
 mod flags;
+mod forge;
 pub mod git_info;
 pub mod instructions;
 pub mod landlock;
@@ -45,23 +53,35 @@ mod network_policy_decision;
 pub mod network_proxy_loader;
 pub use mcp_connection_manager::MCP_SANDBOX_STATE_CAPABILITY;
 pub use mcp_connection_manager::MCP_SANDBOX_STATE_METHOD;
+pub use mcp_connection_manager::MCP_SHELL_SELF_TEST_METHOD;
 pub use mcp_connection_manager::SandboxState;
+pub use mcp_connection_manager::ShellSelfTestReport;
 mod mcp_tool_call;
 mod memories;
 mod mentions;
 mod message_history;
 mod model_provider_info;
+mod model_routing;
+pub mod output_escapes;
+mod output_schema;
+mod patch_backups;
 pub mod path_utils;
 pub mod personality_migration;
+mod project_commands;
+mod project_memory;
 mod proposed_plan_parser;
+mod read_prefetch;
+pub mod sandbox_devices;
+pub mod sandbox_explain;
 mod sandbox_tags;
 pub mod sandboxing;
 mod session_prefix;
 mod shell_detect;
 mod stream_events_utils;
 mod tagged_block_parser;
+mod test_failures;
 pub mod test_support;
-mod text_encoding;
+pub mod text_encoding;
 pub mod token_data;
 mod truncate;
 mod unified_exec;
@@ -72,16 +92,25 @@ pub use model_provider_info::DEFAULT_OLLAMA_PORT;
 pub use model_provider_info::LMSTUDIO_OSS_PROVIDER_ID;
 pub use model_provider_info::ModelProviderInfo;
 pub use model_provider_info::OLLAMA_OSS_PROVIDER_ID;
+pub use model_provider_info::ProviderOidcConfig;
 pub use model_provider_info::WireApi;
 pub use model_provider_info::built_in_model_providers;
 pub use model_provider_info::create_oss_provider_with_base_url;
 mod event_mapping;
+mod repo_brief;
+mod request_pacer;
+mod response_cache;
+mod retry_budget;
 pub mod review_format;
 pub mod review_prompts;
+pub mod run_limits;
+mod scope;
 mod seatbelt_permissions;
+mod security_events;
 mod thread_manager;
 pub mod web_search;
 pub mod windows_sandbox_read_grants;
+mod wire_log;
 pub use codex_protocol::protocol::InitialHistory;
 pub use thread_manager::NewThread;
 pub use thread_manager::ThreadManager;
@@ -99,6 +128,8 @@ pub mod project_doc;
 mod rollout;
 pub(crate) mod safety;
 pub mod seatbelt;
+pub mod seatbelt_profile;
+pub mod self_update;
 pub mod shell;
 pub mod shell_snapshot;
 pub mod skills;
@@ -107,13 +138,20 @@ pub mod state_db;
 pub mod terminal;
 mod tools;
 pub mod turn_diff_tracker;
+pub mod turn_journal;
+mod turn_latency;
 mod turn_metadata;
 pub use rollout::ARCHIVED_SESSIONS_SUBDIR;
+pub use rollout::HistoryIndexRefresh;
 pub use rollout::INTERACTIVE_SESSION_SOURCES;
+pub use rollout::RolloutCompressionSummary;
+pub use rollout::RolloutEncryptionSummary;
 pub use rollout::RolloutRecorder;
 pub use rollout::RolloutRecorderParams;
 pub use rollout::SESSIONS_SUBDIR;
 pub use rollout::SessionMeta;
+pub use rollout::compress_rollouts;
+pub use rollout::encrypt_rollouts;
 pub use rollout::find_archived_thread_path_by_id_str;
 #[deprecated(note = "use find_thread_path_by_id_str")]
 pub use rollout::find_conversation_path_by_id_str;
@@ -128,11 +166,15 @@ pub use rollout::list::parse_cursor;
 pub use rollout::list::read_head_for_summary;
 pub use rollout::list::read_session_meta_line;
 pub use rollout::policy::EventPersistenceMode;
+pub use rollout::read_rollout_text;
+pub use rollout::refresh_history_index;
 pub use rollout::rollout_date_parts;
+pub use rollout::search_history;
 pub use rollout::session_index::find_thread_names_by_ids;
 mod function_tool;
 mod state;
 mod tasks;
+mod usage_quota;
 mod user_shell_command;
 pub mod util;
 pub use codex_shell_command::bash;
@@ -145,6 +187,9 @@ pub use apply_patch::CODEX_APPLY_PATCH_ARG1;
 pub use client::X_CODEX_TURN_METADATA_HEADER;
 pub use exec_policy::ExecPolicyError;
 pub use exec_policy::check_execpolicy_for_warnings;
+pub use exec_policy::command_facts;
+pub use exec_policy::evaluate_command;
+pub use exec_policy::evaluate_intercepted_exec;
 pub use exec_policy::format_exec_policy_error_with_source;
 pub use exec_policy::load_exec_policy;
 pub use file_watcher::FileWatcherEvent;
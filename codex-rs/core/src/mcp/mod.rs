@@ -11,7 +11,9 @@ use async_channel::unbounded;
 use codex_protocol::mcp::Resource;
 use codex_protocol::mcp::ResourceTemplate;
 use codex_protocol::mcp::Tool;
+use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::McpListToolsResponseEvent;
+use codex_protocol::protocol::McpStartupCompleteEvent;
 use codex_protocol::protocol::SandboxPolicy;
 use serde_json::Value;
 use tokio_util::sync::CancellationToken;
@@ -173,7 +175,7 @@ pub(crate) fn effective_mcp_servers(
 
 pub async fn collect_mcp_snapshot(config: &Config) -> McpListToolsResponseEvent {
     let auth_manager = AuthManager::shared(
-        config.codex_home.clone(),
+        config.auth_home.clone(),
         false,
         config.cli_auth_credentials_store_mode,
     );
@@ -212,6 +214,7 @@ pub async fn collect_mcp_snapshot(config: &Config) -> McpListToolsResponseEvent
             tx_event,
             cancel_token.clone(),
             sandbox_state,
+            false,
         )
         .await;
 
@@ -223,6 +226,55 @@ pub async fn collect_mcp_snapshot(config: &Config) -> McpListToolsResponseEvent
     snapshot
 }
 
+/// Starts every enabled MCP server once and reports which came up, without
+/// keeping them running.
+pub async fn check_mcp_startup(config: &Config) -> McpStartupCompleteEvent {
+    let auth_manager = AuthManager::shared(
+        config.auth_home.clone(),
+        false,
+        config.cli_auth_credentials_store_mode,
+    );
+    let auth = auth_manager.auth().await;
+    let mcp_servers = effective_mcp_servers(config, auth.as_ref());
+    if mcp_servers.is_empty() {
+        return McpStartupCompleteEvent::default();
+    }
+
+    let auth_status_entries =
+        compute_auth_statuses(mcp_servers.iter(), config.mcp_oauth_credentials_store_mode).await;
+
+    let mut mcp_connection_manager = McpConnectionManager::default();
+    let (tx_event, rx_event) = unbounded();
+    let cancel_token = CancellationToken::new();
+    let sandbox_state = SandboxState {
+        sandbox_policy: SandboxPolicy::new_read_only_policy(),
+        codex_linux_sandbox_exe: config.codex_linux_sandbox_exe.clone(),
+        sandbox_cwd: env::current_dir().unwrap_or_else(|_| PathBuf::from("/")),
+        use_linux_sandbox_bwrap: config.features.enabled(Feature::UseLinuxSandboxBwrap),
+    };
+    mcp_connection_manager
+        .initialize(
+            &mcp_servers,
+            config.mcp_oauth_credentials_store_mode,
+            auth_status_entries,
+            tx_event,
+            cancel_token.clone(),
+            sandbox_state,
+            false,
+        )
+        .await;
+
+    let mut summary = McpStartupCompleteEvent::default();
+    while let Ok(event) = rx_event.recv().await {
+        if let EventMsg::McpStartupComplete(complete) = event.msg {
+            summary = complete;
+            break;
+        }
+    }
+    cancel_token.cancel();
+    summary
+}
+
 pub fn split_qualified_tool_name(qualified_name: &str) -> Option<(String, String)> {
     let mut parts = qualified_name.split(MCP_TOOL_NAME_DELIMITER);
     let prefix = parts.next()?;
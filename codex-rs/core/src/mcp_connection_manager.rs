@@ -50,6 +50,7 @@ use rmcp::model::Implementation;
 use rmcp::model::InitializeRequestParams;
 use rmcp::model::ListResourceTemplatesResult;
 use rmcp::model::ListResourcesResult;
+use rmcp::model::Meta;
 use rmcp::model::PaginatedRequestParams;
 use rmcp::model::ProtocolVersion;
 use rmcp::model::ReadResourceRequestParams;
@@ -65,6 +66,7 @@ use sha1::Digest;
 use sha1::Sha1;
 use tokio::sync::Mutex;
 use tokio::sync::oneshot;
+use tokio::sync::watch;
 use tokio::task::JoinSet;
 use tokio_util::sync::CancellationToken;
 use tracing::instrument;
@@ -335,7 +337,7 @@ pub const MCP_SANDBOX_STATE_CAPABILITY: &str = "codex/sandbox-state";
 /// When used, the `params` field of the notification is [`SandboxState`].
 pub const MCP_SANDBOX_STATE_METHOD: &str = "codex/sandbox-state/update";
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SandboxState {
     pub sandbox_policy: SandboxPolicy,
@@ -345,14 +347,68 @@ pub struct SandboxState {
     pub use_linux_sandbox_bwrap: bool,
 }
 
+/// Custom MCP request asking a shell server to check its exec interception end
+/// to end: start the patched shell, run a probe command, and confirm the
+/// probe's `execve(2)` reached the server. Takes no params and returns a
+/// [`ShellSelfTestReport`]. Servers advertise support with an experimental
+/// capability of the same name.
+pub const MCP_SHELL_SELF_TEST_METHOD: &str = "zsh/selfTest";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShellSelfTestReport {
+    /// The patched shell the server runs commands with.
+    pub shell_path: PathBuf,
+    /// `$ZSH_VERSION` or `$BASH_VERSION` as printed by the shell.
+    pub shell_version: Option<String>,
+    /// Whether the exec wrapper executable exists.
+    pub exec_wrapper_found: bool,
+    /// Whether the shell started and ran the probe command.
+    pub shell_started: bool,
+    /// Whether the probe's `execve(2)` was routed through the exec wrapper.
+    pub exec_intercepted: bool,
+    /// Details of the first failed step.
+    pub error: Option<String>,
+}
+
+impl ShellSelfTestReport {
+    /// Describes the first failed check, or `None` when every check passed.
+    pub fn failure_message(&self) -> Option<String> {
+        let shell = self.shell_path.display();
+        let message = if !self.exec_wrapper_found {
+            "the exec wrapper is missing".to_string()
+        } else if !self.shell_started {
+            format!("{shell} could not run a probe command")
+        } else if !self.exec_intercepted {
+            format!("{shell} ran commands without the exec wrapper; is it the patched build?")
+        } else {
+            return None;
+        };
+        Some(match &self.error {
+            Some(error) => format!("{message} ({error})"),
+            None => message,
+        })
+    }
+}
+
 /// A thin wrapper around a set of running [`RmcpClient`] instances.
 #[derive(Default)]
 pub(crate) struct McpConnectionManager {
     clients: HashMap<String, AsyncManagedClient>,
     elicitation_requests: ElicitationRequestManager,
+    /// Servers that [`Self::initialize`] left for [`Self::start_lazy_servers`].
+    lazy_servers: HashSet<String>,
+    /// Set to `true` to start `lazy_servers`; `None` when there are none.
+    lazy_start: Option<watch::Sender<bool>>,
+    /// Latest sandbox state, sent to each server once it is ready.
+    sandbox_state: Option<watch::Sender<SandboxState>>,
 }
 
 impl McpConnectionManager {
+    /// Starts the enabled servers in parallel, each bounded by its startup
+    /// timeout. With `lazy_startup`, servers that are not `required` are only
+    /// reported as lazy and start once their tools are first needed.
+    #[allow(clippy::too_many_arguments)]
     pub async fn initialize(
         &mut self,
         mcp_servers: &HashMap<String, McpServerConfig>,
@@ -361,21 +417,32 @@ impl McpConnectionManager {
         tx_event: Sender<Event>,
         cancel_token: CancellationToken,
         initial_sandbox_state: SandboxState,
+        lazy_startup: bool,
     ) {
         if cancel_token.is_cancelled() {
             return;
         }
         let mut clients = HashMap::new();
         let mut join_set = JoinSet::new();
+        let mut lazy_join_set = JoinSet::new();
+        let mut lazy_servers = HashSet::new();
+        let (lazy_start, _) = watch::channel(false);
+        let (sandbox_state, _) = watch::channel(initial_sandbox_state);
         let elicitation_requests = ElicitationRequestManager::default();
         let mcp_servers = mcp_servers.clone();
         for (server_name, cfg) in mcp_servers.into_iter().filter(|(_, cfg)| cfg.enabled) {
             let cancel_token = cancel_token.child_token();
+            // The session cannot start without its required servers.
+            let lazy = lazy_startup && !cfg.required;
             let _ = emit_update(
                 &tx_event,
                 McpStartupUpdateEvent {
                     server: server_name.clone(),
-                    status: McpStartupStatus::Starting,
+                    status: if lazy {
+                        McpStartupStatus::Lazy
+                    } else {
+                        McpStartupStatus::Starting
+                    },
                 },
             )
             .await;
@@ -388,76 +455,75 @@ impl McpConnectionManager {
                 elicitation_requests.clone(),
             );
             clients.insert(server_name.clone(), async_managed_client.clone());
+            let startup = report_startup(
+                server_name.clone(),
+                async_managed_client,
+                cancel_token.clone(),
+                tx_event.clone(),
+                auth_entries.get(&server_name).cloned(),
+                sandbox_state.subscribe(),
+            );
+            if !lazy {
+                join_set.spawn(startup);
+                continue;
+            }
+            lazy_servers.insert(server_name.clone());
+            let mut start = lazy_start.subscribe();
             let tx_event = tx_event.clone();
-            let auth_entry = auth_entries.get(&server_name).cloned();
-            let sandbox_state = initial_sandbox_state.clone();
-            join_set.spawn(async move {
-                let outcome = async_managed_client.client().await;
-                if cancel_token.is_cancelled() {
-                    return (server_name, Err(StartupOutcomeError::Cancelled));
+            lazy_join_set.spawn(async move {
+                // Gives up without reporting anything when startup is cancelled
+                // or the manager is dropped before the server was needed.
+                let started = async { start.wait_for(|start| *start).await.is_ok() }
+                    .or_cancel(&cancel_token)
+                    .await;
+                if started != Ok(true) {
+                    return None;
                 }
-                let status = match &outcome {
-                    Ok(_) => {
-                        // Send sandbox state notification immediately after Ready
-                        if let Err(e) = async_managed_client
-                            .notify_sandbox_state_change(&sandbox_state)
-                            .await
-                        {
-                            warn!(
-                                "Failed to notify sandbox state to MCP server {server_name}: {e:#}",
-                            );
-                        }
-                        McpStartupStatus::Ready
-                    }
-                    Err(error) => {
-                        let error_str = mcp_init_error_display(
-                            server_name.as_str(),
-                            auth_entry.as_ref(),
-                            error,
-                        );
-                        McpStartupStatus::Failed { error: error_str }
-                    }
-                };
-
                 let _ = emit_update(
                     &tx_event,
                     McpStartupUpdateEvent {
-                        server: server_name.clone(),
-                        status,
+                        server: server_name,
+                        status: McpStartupStatus::Starting,
                     },
                 )
                 .await;
-
-                (server_name, outcome)
+                Some(startup.await)
             });
         }
         self.clients = clients;
         self.elicitation_requests = elicitation_requests.clone();
+        self.lazy_start = (!lazy_servers.is_empty()).then_some(lazy_start);
+        self.lazy_servers = lazy_servers;
+        self.sandbox_state = Some(sandbox_state);
+        let eager_tx_event = tx_event.clone();
         tokio::spawn(async move {
-            let outcomes = join_set.join_all().await;
-            let mut summary = McpStartupCompleteEvent::default();
-            for (server_name, outcome) in outcomes {
-                match outcome {
-                    Ok(_) => summary.ready.push(server_name),
-                    Err(StartupOutcomeError::Cancelled) => summary.cancelled.push(server_name),
-                    Err(StartupOutcomeError::Failed { error }) => {
-                        summary.failed.push(McpStartupFailure {
-                            server: server_name,
-                            error,
-                        })
-                    }
-                }
-            }
-            let _ = tx_event
-                .send(Event {
-                    id: INITIAL_SUBMIT_ID.to_owned(),
-                    msg: EventMsg::McpStartupComplete(summary),
-                })
-                .await;
+            emit_startup_complete(join_set.join_all().await, &eager_tx_event).await;
         });
+        if !lazy_join_set.is_empty() {
+            tokio::spawn(async move {
+                let outcomes: Vec<_> = lazy_join_set
+                    .join_all()
+                    .await
+                    .into_iter()
+                    .flatten()
+                    .collect();
+                if !outcomes.is_empty() {
+                    emit_startup_complete(outcomes, &tx_event).await;
+                }
+            });
+        }
+    }
+
+    /// Starts the servers that [`Self::initialize`] left lazy, unless they
+    /// were started already.
+    pub(crate) fn start_lazy_servers(&self) {
+        if let Some(lazy_start) = &self.lazy_start {
+            lazy_start.send_replace(true);
+        }
     }
 
     async fn client_by_name(&self, name: &str) -> Result<ManagedClient> {
+        self.start_lazy_servers();
         self.clients
             .get(name)
             .ok_or_else(|| anyhow!("unknown MCP server '{name}'"))?
@@ -478,6 +544,7 @@ impl McpConnectionManager {
     }
 
     pub(crate) async fn wait_for_server_ready(&self, server_name: &str, timeout: Duration) -> bool {
+        self.start_lazy_servers();
         let Some(async_managed_client) = self.clients.get(server_name) else {
             return false;
         };
@@ -517,6 +584,7 @@ impl McpConnectionManager {
     /// fully-qualified name for the tool.
     #[instrument(level = "trace", skip_all)]
     pub async fn list_all_tools(&self) -> HashMap<String, ToolInfo> {
+        self.start_lazy_servers();
         let mut tools = HashMap::new();
         for (server_name, managed_client) in &self.clients {
             let client = managed_client.client().await.ok();
@@ -550,6 +618,7 @@ impl McpConnectionManager {
     /// On success, the refreshed tools replace the cache contents. On failure,
     /// the existing cache remains unchanged.
     pub async fn hard_refresh_codex_apps_tools_cache(&self) -> Result<()> {
+        self.start_lazy_servers();
         let managed_client = self
             .clients
             .get(CODEX_APPS_MCP_SERVER_NAME)
@@ -575,6 +644,7 @@ impl McpConnectionManager {
     /// Returns a single map that contains all resources. Each key is the
     /// server name and the value is a vector of resources.
     pub async fn list_all_resources(&self) -> HashMap<String, Vec<Resource>> {
+        self.start_lazy_servers();
         let mut join_set = JoinSet::new();
 
         let clients_snapshot = &self.clients;
@@ -641,6 +711,7 @@ impl McpConnectionManager {
     /// Returns a single map that contains all resource templates. Each key is the
     /// server name and the value is a vector of resource templates.
     pub async fn list_all_resource_templates(&self) -> HashMap<String, Vec<ResourceTemplate>> {
+        self.start_lazy_servers();
         let mut join_set = JoinSet::new();
 
         let clients_snapshot = &self.clients;
@@ -724,7 +795,12 @@ impl McpConnectionManager {
 
         let result: rmcp::model::CallToolResult = client
             .client
-            .call_tool(tool.to_string(), arguments, client.tool_timeout)
+            .call_tool(
+                tool.to_string(),
+                arguments,
+                trace_context_meta(),
+                client.tool_timeout,
+            )
             .await
             .with_context(|| format!("tool call failed for `{server}/{tool}`"))?;
 
@@ -802,9 +878,20 @@ impl McpConnectionManager {
     }
 
     pub async fn notify_sandbox_state_change(&self, sandbox_state: &SandboxState) -> Result<()> {
+        if let Some(latest) = &self.sandbox_state {
+            latest.send_replace(sandbox_state.clone());
+        }
+        let lazy_started = self
+            .lazy_start
+            .as_ref()
+            .is_none_or(|lazy_start| *lazy_start.borrow());
         let mut join_set = JoinSet::new();
 
-        for async_managed_client in self.clients.values() {
+        for (server_name, async_managed_client) in &self.clients {
+            // Servers that have not started get the latest state once ready.
+            if !lazy_started && self.lazy_servers.contains(server_name) {
+                continue;
+            }
             let sandbox_state = sandbox_state.clone();
             let async_managed_client = async_managed_client.clone();
             join_set.spawn(async move {
@@ -830,6 +917,74 @@ impl McpConnectionManager {
     }
 }
 
+/// Waits for a server to start and reports whether it became ready, sending
+/// it the latest sandbox state if it did.
+async fn report_startup(
+    server_name: String,
+    async_managed_client: AsyncManagedClient,
+    cancel_token: CancellationToken,
+    tx_event: Sender<Event>,
+    auth_entry: Option<McpAuthStatusEntry>,
+    sandbox_state: watch::Receiver<SandboxState>,
+) -> (String, Result<ManagedClient, StartupOutcomeError>) {
+    let outcome = async_managed_client.client().await;
+    if cancel_token.is_cancelled() {
+        return (server_name, Err(StartupOutcomeError::Cancelled));
+    }
+    let status = match &outcome {
+        Ok(_) => {
+            // Send sandbox state notification immediately after Ready
+            let sandbox_state = sandbox_state.borrow().clone();
+            if let Err(e) = async_managed_client
+                .notify_sandbox_state_change(&sandbox_state)
+                .await
+            {
+                warn!("Failed to notify sandbox state to MCP server {server_name}: {e:#}");
+            }
+            McpStartupStatus::Ready
+        }
+        Err(error) => {
+            let error_str =
+                mcp_init_error_display(server_name.as_str(), auth_entry.as_ref(), error);
+            McpStartupStatus::Failed { error: error_str }
+        }
+    };
+
+    let _ = emit_update(
+        &tx_event,
+        McpStartupUpdateEvent {
+            server: server_name.clone(),
+            status,
+        },
+    )
+    .await;
+
+    (server_name, outcome)
+}
+
+async fn emit_startup_complete(
+    outcomes: Vec<(String, Result<ManagedClient, StartupOutcomeError>)>,
+    tx_event: &Sender<Event>,
+) {
+    let mut summary = McpStartupCompleteEvent::default();
+    for (server_name, outcome) in outcomes {
+        match outcome {
+            Ok(_) => summary.ready.push(server_name),
+            Err(StartupOutcomeError::Cancelled) => summary.cancelled.push(server_name),
+            Err(StartupOutcomeError::Failed { error }) => summary.failed.push(McpStartupFailure {
+                server: server_name,
+                error,
+            }),
+        }
+    }
+    let _ = tx_event
+        .send(Event {
+            id: INITIAL_SUBMIT_ID.to_owned(),
+            msg: EventMsg::McpStartupComplete(summary),
+        })
+        .await;
+}
+
 async fn emit_update(
     tx_event: &Sender<Event>,
     update: McpStartupUpdateEvent,
@@ -877,6 +1032,17 @@ impl ToolFilter {
     }
 }
 
+/// Request `_meta` carrying the W3C trace context of the current tool call span,
+/// so servers that export traces (such as the exec sidecar) can join the trace.
+fn trace_context_meta() -> Option<Meta> {
+    let carrier = codex_otel::otel_provider::current_span_trace_context()?;
+    let mut meta = Meta::new();
+    for (key, value) in carrier {
+        meta.0.insert(key, serde_json::Value::String(value));
+    }
+    Some(meta)
+}
+
 fn filter_tools(tools: Vec<ToolInfo>, filter: ToolFilter) -> Vec<ToolInfo> {
     tools
         .into_iter()
@@ -918,6 +1084,24 @@ pub(crate) fn filter_non_codex_apps_mcp_tools_only(
         .collect()
 }
 
+/// Tools a read-only session may offer: only those whose server declares
+/// `readOnlyHint: true`. Tools without annotations are assumed to write.
+pub(crate) fn filter_read_only_mcp_tools(
+    mcp_tools: &HashMap<String, ToolInfo>,
+) -> HashMap<String, ToolInfo> {
+    mcp_tools
+        .iter()
+        .filter(|(_, tool)| is_read_only_mcp_tool(&tool.tool))
+        .map(|(name, tool)| (name.clone(), tool.clone()))
+        .collect()
+}
+
+pub(crate) fn is_read_only_mcp_tool(tool: &Tool) -> bool {
+    tool.annotations
+        .as_ref()
+        .is_some_and(|annotations| annotations.read_only_hint == Some(true))
+}
+
 pub(crate) fn filter_mcp_tools_by_name(
     mcp_tools: &HashMap<String, ToolInfo>,
     selected_tools: &[String],
@@ -1056,6 +1240,15 @@ async fn start_server_task(
         .as_ref()
         .and_then(|exp| exp.get(MCP_SANDBOX_STATE_CAPABILITY))
         .is_some();
+    let server_supports_shell_self_test = initialize_result
+        .capabilities
+        .experimental
+        .as_ref()
+        .and_then(|exp| exp.get(MCP_SHELL_SELF_TEST_METHOD))
+        .is_some();
+    if server_supports_shell_self_test {
+        run_shell_self_test(&client, startup_timeout).await?;
+    }
 
     let managed = ManagedClient {
         client: Arc::clone(&client),
@@ -1068,6 +1261,30 @@ async fn start_server_task(
     Ok(managed)
 }
 
+/// Fails startup when a shell server reports broken exec interception, so the
+/// problem surfaces when the session starts rather than on the first command.
+async fn run_shell_self_test(
+    client: &RmcpClient,
+    timeout: Option<Duration>,
+) -> Result<(), StartupOutcomeError> {
+    let request = client.send_custom_request(MCP_SHELL_SELF_TEST_METHOD, None);
+    let response = match timeout {
+        Some(duration) => tokio::time::timeout(duration, request)
+            .await
+            .map_err(|_| anyhow!("shell self-test timed out after {duration:?}"))?,
+        None => request.await,
+    }?;
+    let report: ShellSelfTestReport = serde_json::to_value(response)
+        .and_then(serde_json::from_value)
+        .context("invalid shell self-test report")?;
+    match report.failure_message() {
+        Some(error) => Err(StartupOutcomeError::Failed {
+            error: format!("shell self-test failed: {error}"),
+        }),
+        None => Ok(()),
+    }
+}
+
 async fn make_rmcp_client(
     server_name: &str,
     transport: McpServerTransportConfig,
@@ -1306,6 +1523,7 @@ mod tests {
     use super::*;
     use codex_protocol::protocol::McpAuthStatus;
     use rmcp::model::JsonObject;
+    use rmcp::model::ToolAnnotations;
     use std::collections::HashSet;
     use std::sync::Arc;
 
@@ -1329,6 +1547,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn read_only_filter_keeps_only_tools_declared_read_only() {
+        let annotations = |read_only| ToolAnnotations {
+            destructive_hint: None,
+            idempotent_hint: None,
+            open_world_hint: None,
+            read_only_hint: Some(read_only),
+            title: None,
+        };
+        let mut read_only = create_test_tool("docs", "search");
+        read_only.tool.annotations = Some(annotations(true));
+        let mut writes = create_test_tool("docs", "update");
+        writes.tool.annotations = Some(annotations(false));
+        let unannotated = create_test_tool("docs", "sync");
+        let tools = HashMap::from([
+            ("mcp__docs__search".to_string(), read_only),
+            ("mcp__docs__update".to_string(), writes),
+            ("mcp__docs__sync".to_string(), unannotated),
+        ]);
+
+        let filtered = filter_read_only_mcp_tools(&tools);
+        assert_eq!(
+            filtered.keys().collect::<Vec<_>>(),
+            vec!["mcp__docs__search"]
+        );
+    }
+
     fn with_clean_codex_apps_tools_cache<T>(f: impl FnOnce() -> T) -> T {
         let previous_cache = {
             let mut cache_guard = CODEX_APPS_TOOLS_CACHE
@@ -1627,4 +1872,118 @@ mod tests {
             display
         );
     }
+
+    #[test]
+    fn shell_self_test_report_names_first_failed_check() {
+        let mut report = ShellSelfTestReport {
+            shell_path: PathBuf::from("/opt/codex/zsh"),
+            shell_version: Some("5.9".to_string()),
+            exec_wrapper_found: true,
+            shell_started: true,
+            exec_intercepted: true,
+            error: None,
+        };
+        assert_eq!(None, report.failure_message());
+
+        report.exec_intercepted = false;
+        assert_eq!(
+            Some(
+                "/opt/codex/zsh ran commands without the exec wrapper; is it the patched build?"
+                    .to_string()
+            ),
+            report.failure_message()
+        );
+
+        report.shell_started = false;
+        report.error = Some("probe exited with 127".to_string());
+        assert_eq!(
+            Some(
+                "/opt/codex/zsh could not run a probe command (probe exited with 127)".to_string()
+            ),
+            report.failure_message()
+        );
+    }
+
+    async fn next_event(rx_event: &async_channel::Receiver<Event>) -> EventMsg {
+        rx_event.recv().await.expect("event").msg
+    }
+
+    #[tokio::test]
+    async fn lazy_servers_start_when_first_needed() {
+        let config = McpServerConfig {
+            transport: McpServerTransportConfig::Stdio {
+                command: "codex-test-missing-mcp-server".to_string(),
+                args: Vec::new(),
+                env: None,
+                env_vars: Vec::new(),
+                cwd: None,
+            },
+            enabled: true,
+            required: false,
+            disabled_reason: None,
+            startup_timeout_sec: Some(Duration::from_secs(5)),
+            tool_timeout_sec: None,
+            enabled_tools: None,
+            disabled_tools: None,
+            scopes: None,
+        };
+        let (tx_event, rx_event) = async_channel::unbounded();
+        let mut manager = McpConnectionManager::default();
+        manager
+            .initialize(
+                &HashMap::from([("docs".to_string(), config)]),
+                OAuthCredentialsStoreMode::File,
+                HashMap::new(),
+                tx_event,
+                CancellationToken::new(),
+                SandboxState {
+                    sandbox_policy: SandboxPolicy::new_read_only_policy(),
+                    codex_linux_sandbox_exe: None,
+                    sandbox_cwd: std::env::temp_dir(),
+                    use_linux_sandbox_bwrap: false,
+                },
+                true,
+            )
+            .await;
+
+        assert!(matches!(
+            next_event(&rx_event).await,
+            EventMsg::McpStartupUpdate(McpStartupUpdateEvent {
+                status: McpStartupStatus::Lazy,
+                ..
+            })
+        ));
+        // Nothing else started, so the startup summary is empty.
+        match next_event(&rx_event).await {
+            EventMsg::McpStartupComplete(summary) => {
+                assert!(summary.ready.is_empty() && summary.failed.is_empty());
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+        assert!(rx_event.is_empty());
+
+        manager.start_lazy_servers();
+
+        assert!(matches!(
+            next_event(&rx_event).await,
+            EventMsg::McpStartupUpdate(McpStartupUpdateEvent {
+                status: McpStartupStatus::Starting,
+                ..
+            })
+        ));
+        assert!(matches!(
+            next_event(&rx_event).await,
+            EventMsg::McpStartupUpdate(McpStartupUpdateEvent {
+                status: McpStartupStatus::Failed { .. },
+                ..
+            })
+        ));
+        match next_event(&rx_event).await {
+            EventMsg::McpStartupComplete(summary) => {
+                let failed: Vec<_> = summary.failed.iter().map(|f| f.server.as_str()).collect();
+                assert_eq!(failed, vec!["docs"]);
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
 }
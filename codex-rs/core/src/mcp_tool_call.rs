@@ -5,9 +5,12 @@ use tracing::error;
 
 use crate::analytics_client::AppInvocation;
 use crate::analytics_client::build_track_events_context;
+use crate::audit_log::ApprovalDecider;
+use crate::audit_log::AuditEvent;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::mcp::CODEX_APPS_MCP_SERVER_NAME;
+use crate::mcp_connection_manager::is_read_only_mcp_tool;
 use crate::protocol::EventMsg;
 use crate::protocol::McpInvocation;
 use crate::protocol::McpToolCallBeginEvent;
@@ -64,6 +67,15 @@ pub(crate) async fn handle_mcp_tool_call(
         arguments: arguments_value.clone(),
     };
 
+    if turn_context.config.read_only && !is_read_only_tool(sess.as_ref(), &server, &tool_name).await
+    {
+        let message = "read-only session: this MCP tool is not declared read-only".to_string();
+        let result =
+            notify_mcp_tool_call_skip(sess.as_ref(), turn_context, &call_id, invocation, message)
+                .await;
+        return ResponseInputItem::McpToolCallOutput { call_id, result };
+    }
+
     if let Some(decision) =
         maybe_request_mcp_tool_approval(sess.as_ref(), turn_context, &call_id, &server, &tool_name)
             .await
@@ -315,7 +327,17 @@ async fn maybe_request_mcp_tool_approval(
     if let Some(key) = approval_key.as_ref()
         && mcp_tool_approval_is_remembered(sess, key).await
     {
-        return Some(McpToolApprovalDecision::Accept);
+        let decision = McpToolApprovalDecision::Accept;
+        audit_mcp_tool_approval(
+            sess,
+            call_id,
+            server,
+            tool_name,
+            &decision,
+            ApprovalDecider::Session,
+        )
+        .await;
+        return Some(decision);
     }
 
     let question_id = format!("{MCP_TOOL_APPROVAL_QUESTION_ID_PREFIX}_{call_id}");
@@ -334,6 +356,15 @@ async fn maybe_request_mcp_tool_approval(
         .request_user_input(turn_context, call_id.to_string(), args)
         .await;
     let decision = parse_mcp_tool_approval_response(response, &question_id);
+    audit_mcp_tool_approval(
+        sess,
+        call_id,
+        server,
+        tool_name,
+        &decision,
+        ApprovalDecider::User,
+    )
+    .await;
     if matches!(decision, McpToolApprovalDecision::AcceptAndRemember)
         && let Some(key) = approval_key
     {
@@ -342,6 +373,30 @@ async fn maybe_request_mcp_tool_approval(
     Some(decision)
 }
 
+async fn audit_mcp_tool_approval(
+    sess: &Session,
+    call_id: &str,
+    server: &str,
+    tool_name: &str,
+    decision: &McpToolApprovalDecision,
+    decided_by: ApprovalDecider,
+) {
+    let decision = match decision {
+        McpToolApprovalDecision::Accept => ReviewDecision::Approved,
+        McpToolApprovalDecision::AcceptAndRemember => ReviewDecision::ApprovedForSession,
+        McpToolApprovalDecision::Decline => ReviewDecision::Denied,
+        McpToolApprovalDecision::Cancel => ReviewDecision::Abort,
+    };
+    sess.record_audit_event(AuditEvent::approval(
+        call_id,
+        &format!("mcp:{server}/{tool_name}"),
+        &decision,
+        decided_by,
+        None,
+    ))
+    .await;
+}
+
 fn is_full_access_mode(turn_context: &TurnContext) -> bool {
     matches!(turn_context.approval_policy, AskForApproval::Never)
         && matches!(
@@ -350,6 +405,21 @@ fn is_full_access_mode(turn_context: &TurnContext) -> bool {
         )
 }
 
+async fn is_read_only_tool(sess: &Session, server: &str, tool_name: &str) -> bool {
+    sess.services
+        .mcp_connection_manager
+        .read()
+        .await
+        .list_all_tools()
+        .await
+        .into_values()
+        .any(|tool_info| {
+            tool_info.server_name == server
+                && tool_info.tool_name == tool_name
+                && is_read_only_mcp_tool(&tool_info.tool)
+        })
+}
+
 async fn lookup_mcp_tool_metadata(
     sess: &Session,
     server: &str,
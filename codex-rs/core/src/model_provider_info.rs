@@ -6,8 +6,13 @@
 //!      key. These override or extend the defaults at runtime.
 
 use crate::auth::AuthMode;
+use crate::config::types::HttpClientConfig;
 use crate::error::EnvVarError;
+use codex_api::AnthropicMessagesDialect;
+use codex_api::ChatCompletionsDialect;
 use codex_api::Provider as ApiProvider;
+use codex_api::ResponsesDialect;
+use codex_api::WireDialect;
 use codex_api::provider::RetryConfig as ApiRetryConfig;
 use http::HeaderMap;
 use http::header::HeaderName;
@@ -16,9 +21,11 @@ use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 
 const DEFAULT_STREAM_IDLE_TIMEOUT_MS: u64 = 300_000;
+const DEFAULT_STREAM_STALL_TIMEOUT_MS: u64 = 60_000;
 const DEFAULT_STREAM_MAX_RETRIES: u64 = 5;
 const DEFAULT_REQUEST_MAX_RETRIES: u64 = 4;
 /// Hard cap for user-configured `stream_max_retries`.
@@ -27,7 +34,8 @@ const MAX_STREAM_MAX_RETRIES: u64 = 100;
 const MAX_REQUEST_MAX_RETRIES: u64 = 100;
 
 const OPENAI_PROVIDER_NAME: &str = "OpenAI";
-const CHAT_WIRE_API_REMOVED_ERROR: &str = "`wire_api = \"chat\"` is no longer supported.\nHow to fix: set `wire_api = \"responses\"` in your provider config.\nMore info: https://github.com/openai/codex/discussions/7782";
+const ANTHROPIC_PROVIDER_NAME: &str = "Anthropic";
+const ANTHROPIC_PROVIDER_ID: &str = "anthropic";
 pub(crate) const LEGACY_OLLAMA_CHAT_PROVIDER_ID: &str = "ollama-chat";
 pub(crate) const OLLAMA_CHAT_PROVIDER_REMOVED_ERROR: &str = "`ollama-chat` is no longer supported.\nHow to fix: replace `ollama-chat` with `ollama` in `model_provider`, `oss_provider`, or `--local-provider`.\nMore info: https://github.com/openai/codex/discussions/7782";
 
@@ -38,6 +46,23 @@ pub enum WireApi {
     /// The Responses API exposed by OpenAI at `/v1/responses`.
     #[default]
     Responses,
+    /// Chat Completions at `/v1/chat/completions`, for OpenAI-compatible
+    /// servers without the Responses API. Requests and responses are
+    /// translated to and from Responses items.
+    Chat,
+    /// Anthropic's Messages API at `/v1/messages`, translated the same way.
+    Anthropic,
+}
+
+impl WireApi {
+    /// Translation between Codex's items and this wire protocol.
+    pub(crate) fn dialect(self) -> Arc<dyn WireDialect> {
+        match self {
+            WireApi::Responses => Arc::new(ResponsesDialect),
+            WireApi::Chat => Arc::new(ChatCompletionsDialect),
+            WireApi::Anthropic => Arc::new(AnthropicMessagesDialect),
+        }
+    }
 }
 
 impl<'de> Deserialize<'de> for WireApi {
@@ -48,8 +73,12 @@ impl<'de> Deserialize<'de> for WireApi {
         let value = String::deserialize(deserializer)?;
         match value.as_str() {
             "responses" => Ok(Self::Responses),
-            "chat" => Err(serde::de::Error::custom(CHAT_WIRE_API_REMOVED_ERROR)),
-            _ => Err(serde::de::Error::unknown_variant(&value, &["responses"])),
+            "chat" => Ok(Self::Chat),
+            "anthropic" => Ok(Self::Anthropic),
+            _ => Err(serde::de::Error::unknown_variant(
+                &value,
+                &["responses", "chat", "anthropic"],
+            )),
         }
     }
 }
@@ -101,6 +130,10 @@ pub struct ModelProviderInfo {
     /// the connection as lost.
     pub stream_idle_timeout_ms: Option<u64>,
 
+    /// Time (in milliseconds) without a new delta, once a message has started streaming, before
+    /// the response is treated as stalled and re-requested. `0` disables stall detection.
+    pub stream_stall_timeout_ms: Option<u64>,
+
     /// Does this provider require an OpenAI API Key or ChatGPT login token? If true,
     /// user is presented with login screen on first run, and login preference and token/key
     /// are stored in auth.json. If false (which is the default), login screen is skipped,
@@ -111,6 +144,72 @@ pub struct ModelProviderInfo {
     /// Whether this provider supports the Responses API WebSocket transport.
     #[serde(default)]
     pub supports_websockets: bool,
+
+    /// OIDC issuer that signs the tokens this provider (typically an
+    /// enterprise gateway) accepts. Sign in with `codex login --provider <id>`;
+    /// the access token is sent as the bearer token and refreshed as it nears
+    /// expiry. Takes precedence over `env_key` and `experimental_bearer_token`.
+    pub oidc: Option<ProviderOidcConfig>,
+
+    /// Proxy and TLS settings for requests to this provider, overriding the
+    /// top-level `[http_client]` field by field.
+    pub http_client: Option<HttpClientConfig>,
+
+    /// OpenAI organization ID, sent as the `OpenAI-Organization` header.
+    /// Needed when an API key belongs to more than one organization.
+    pub organization: Option<String>,
+
+    /// OpenAI project ID, sent as the `OpenAI-Project` header.
+    pub project: Option<String>,
+
+    /// Azure OpenAI deployment and API version, so they do not have to be
+    /// spelled out in `base_url` and `query_params`.
+    pub azure: Option<ProviderAzureConfig>,
+}
+
+/// OIDC device authorization settings for a provider.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ProviderOidcConfig {
+    /// Issuer URL, for example `https://example.okta.com/oauth2/default` or
+    /// `https://login.microsoftonline.com/<tenant>/v2.0`. Endpoints are read
+    /// from its `/.well-known/openid-configuration`.
+    pub issuer: String,
+
+    /// Client ID of the application registered with the issuer. The
+    /// application must allow the device authorization grant.
+    pub client_id: String,
+
+    /// Scopes to request. Defaults to `openid profile offline_access`.
+    pub scopes: Option<Vec<String>>,
+
+    /// `audience` parameter, required by some issuers to mint an access token
+    /// for the gateway rather than for the issuer itself.
+    pub audience: Option<String>,
+}
+
+/// Azure OpenAI settings for a provider.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ProviderAzureConfig {
+    /// Deployment that serves the requests. With the Responses API it is sent
+    /// as the model name; with Chat Completions requests go to
+    /// `<base_url>/deployments/<deployment>`.
+    pub deployment: Option<String>,
+
+    /// `api-version` query parameter, for example `2025-04-01-preview`. Leave
+    /// it unset for the versionless `/openai/v1` API.
+    pub api_version: Option<String>,
+}
+
+impl ProviderOidcConfig {
+    /// Space-separated scope string to request.
+    pub fn scope(&self) -> String {
+        match &self.scopes {
+            Some(scopes) => scopes.join(" "),
+            None => "openid profile offline_access".to_string(),
+        }
+    }
 }
 
 impl ModelProviderInfo {
@@ -138,6 +237,19 @@ impl ModelProviderInfo {
             }
         }
 
+        // Set last so they win over the environment fallbacks above.
+        for (name, value) in [
+            ("OpenAI-Organization", &self.organization),
+            ("OpenAI-Project", &self.project),
+        ] {
+            if let Some(value) = value
+                && !value.trim().is_empty()
+                && let Ok(value) = HeaderValue::try_from(value.trim())
+            {
+                headers.insert(name, value);
+            }
+        }
+
         Ok(headers)
     }
 
@@ -150,10 +262,26 @@ impl ModelProviderInfo {
         } else {
             "https://api.openai.com/v1"
         };
-        let base_url = self
+        let mut base_url = self
             .base_url
             .clone()
             .unwrap_or_else(|| default_base_url.to_string());
+        let mut query_params = self.query_params.clone();
+        if let Some(azure) = &self.azure {
+            if let Some(api_version) = &azure.api_version {
+                query_params
+                    .get_or_insert_with(HashMap::new)
+                    .insert("api-version".to_string(), api_version.clone());
+            }
+            if self.wire_api == WireApi::Chat
+                && let Some(deployment) = &azure.deployment
+            {
+                base_url = format!(
+                    "{}/deployments/{deployment}",
+                    base_url.trim_end_matches('/')
+                );
+            }
+        }
 
         let headers = self.build_header_map()?;
         let retry = ApiRetryConfig {
@@ -167,13 +295,22 @@ impl ModelProviderInfo {
         Ok(ApiProvider {
             name: self.name.clone(),
             base_url,
-            query_params: self.query_params.clone(),
+            query_params,
             headers,
             retry,
             stream_idle_timeout: self.stream_idle_timeout(),
         })
     }
 
+    /// Model name to send in request bodies: the Azure deployment when one is
+    /// configured, `slug` otherwise.
+    pub(crate) fn request_model<'a>(&'a self, slug: &'a str) -> &'a str {
+        self.azure
+            .as_ref()
+            .and_then(|azure| azure.deployment.as_deref())
+            .unwrap_or(slug)
+    }
+
     /// If `env_key` is Some, returns the API key for this provider if present
     /// (and non-empty) in the environment. If `env_key` is required but
     /// cannot be found, returns an error.
@@ -215,6 +352,17 @@ impl ModelProviderInfo {
             .map(Duration::from_millis)
             .unwrap_or(Duration::from_millis(DEFAULT_STREAM_IDLE_TIMEOUT_MS))
     }
+
+    /// Effective stall timeout for streaming responses, or `None` when disabled.
+    pub fn stream_stall_timeout(&self) -> Option<Duration> {
+        match self
+            .stream_stall_timeout_ms
+            .unwrap_or(DEFAULT_STREAM_STALL_TIMEOUT_MS)
+        {
+            0 => None,
+            ms => Some(Duration::from_millis(ms)),
+        }
+    }
     pub fn create_openai_provider() -> ModelProviderInfo {
         ModelProviderInfo {
             name: OPENAI_PROVIDER_NAME.into(),
@@ -251,8 +399,41 @@ impl ModelProviderInfo {
             request_max_retries: None,
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
+            stream_stall_timeout_ms: None,
             requires_openai_auth: true,
             supports_websockets: true,
+            oidc: None,
+            http_client: None,
+            organization: None,
+            project: None,
+            azure: None,
+        }
+    }
+
+    pub fn create_anthropic_provider() -> ModelProviderInfo {
+        ModelProviderInfo {
+            name: ANTHROPIC_PROVIDER_NAME.into(),
+            base_url: Some("https://api.anthropic.com/v1".into()),
+            env_key: Some("ANTHROPIC_API_KEY".to_string()),
+            env_key_instructions: Some(
+                "Create an API key at https://console.anthropic.com/settings/keys and export it as ANTHROPIC_API_KEY.".to_string(),
+            ),
+            experimental_bearer_token: None,
+            wire_api: WireApi::Anthropic,
+            query_params: None,
+            http_headers: None,
+            env_http_headers: None,
+            request_max_retries: None,
+            stream_max_retries: None,
+            stream_idle_timeout_ms: None,
+            stream_stall_timeout_ms: None,
+            requires_openai_auth: false,
+            supports_websockets: false,
+            oidc: None,
+            http_client: None,
+            organization: None,
+            project: None,
+            azure: None,
         }
     }
 
@@ -272,11 +453,13 @@ pub fn built_in_model_providers() -> HashMap<String, ModelProviderInfo> {
     use ModelProviderInfo as P;
 
     // We do not want to be in the business of adjucating which third-party
-    // providers are bundled with Codex CLI, so we only include the OpenAI and
-    // open source ("oss") providers by default. Users are encouraged to add to
-    // `model_providers` in config.toml to add their own providers.
+    // providers are bundled with Codex CLI, so we only include the OpenAI,
+    // Anthropic (which has its own wire API), and open source ("oss")
+    // providers by default. Users are encouraged to add to `model_providers`
+    // in config.toml to add their own providers.
     [
         ("openai", P::create_openai_provider()),
+        (ANTHROPIC_PROVIDER_ID, P::create_anthropic_provider()),
         (
             OLLAMA_OSS_PROVIDER_ID,
             create_oss_provider(DEFAULT_OLLAMA_PORT, WireApi::Responses),
@@ -324,8 +507,14 @@ pub fn create_oss_provider_with_base_url(base_url: &str, wire_api: WireApi) -> M
         request_max_retries: None,
         stream_max_retries: None,
         stream_idle_timeout_ms: None,
+        stream_stall_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        oidc: None,
+        http_client: None,
+        organization: None,
+        project: None,
+        azure: None,
     }
 }
 
@@ -353,8 +542,14 @@ base_url = "http://localhost:11434/v1"
             request_max_retries: None,
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
+            stream_stall_timeout_ms: None,
             requires_openai_auth: false,
             supports_websockets: false,
+            oidc: None,
+            http_client: None,
+            organization: None,
+            project: None,
+            azure: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -384,8 +579,14 @@ query_params = { api-version = "2025-04-01-preview" }
             request_max_retries: None,
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
+            stream_stall_timeout_ms: None,
             requires_openai_auth: false,
             supports_websockets: false,
+            oidc: None,
+            http_client: None,
+            organization: None,
+            project: None,
+            azure: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -418,8 +619,14 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             request_max_retries: None,
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
+            stream_stall_timeout_ms: None,
             requires_openai_auth: false,
             supports_websockets: false,
+            oidc: None,
+            http_client: None,
+            organization: None,
+            project: None,
+            azure: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -427,7 +634,54 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
     }
 
     #[test]
-    fn test_deserialize_chat_wire_api_shows_helpful_error() {
+    fn organization_project_and_azure_settings_shape_requests() {
+        let provider_toml = r#"
+name = "Azure"
+base_url = "https://xxxxx.openai.azure.com/openai/"
+wire_api = "chat"
+organization = "org-123"
+project = "proj_456"
+azure = { deployment = "gpt-4o-prod", api_version = "2024-10-21" }
+        "#;
+
+        let provider: ModelProviderInfo = toml::from_str(provider_toml).unwrap();
+        let api_provider = provider.to_api_provider(None).unwrap();
+        assert_eq!(
+            api_provider.url_for_path("chat/completions"),
+            "https://xxxxx.openai.azure.com/openai/deployments/gpt-4o-prod/chat/completions?api-version=2024-10-21"
+        );
+        assert_eq!(
+            api_provider
+                .headers
+                .get("OpenAI-Organization")
+                .and_then(|value| value.to_str().ok()),
+            Some("org-123")
+        );
+        assert_eq!(
+            api_provider
+                .headers
+                .get("OpenAI-Project")
+                .and_then(|value| value.to_str().ok()),
+            Some("proj_456")
+        );
+        assert_eq!(provider.request_model("gpt-4o"), "gpt-4o-prod");
+
+        let provider: ModelProviderInfo = toml::from_str(
+            "name = \"Azure\"\nbase_url = \"https://xxxxx.openai.azure.com/openai\"\nazure = { deployment = \"prod\" }",
+        )
+        .unwrap();
+        assert_eq!(
+            provider
+                .to_api_provider(None)
+                .unwrap()
+                .url_for_path("responses"),
+            "https://xxxxx.openai.azure.com/openai/responses"
+        );
+        assert_eq!(provider.request_model("gpt-5.1-codex"), "prod");
+    }
+
+    #[test]
+    fn test_deserialize_chat_wire_api() {
         let provider_toml = r#"
 name = "OpenAI using Chat Completions"
 base_url = "https://api.openai.com/v1"
@@ -435,7 +689,21 @@ env_key = "OPENAI_API_KEY"
 wire_api = "chat"
         "#;
 
-        let err = toml::from_str::<ModelProviderInfo>(provider_toml).unwrap_err();
-        assert!(err.to_string().contains(CHAT_WIRE_API_REMOVED_ERROR));
+        let provider: ModelProviderInfo = toml::from_str(provider_toml).unwrap();
+        assert_eq!(provider.wire_api, WireApi::Chat);
+        assert_eq!(provider.wire_api.dialect().path(), "chat/completions");
+    }
+
+    #[test]
+    fn test_built_in_anthropic_provider() {
+        let provider = built_in_model_providers()["anthropic"].clone();
+        assert_eq!(provider.wire_api, WireApi::Anthropic);
+        assert_eq!(provider.wire_api.dialect().path(), "messages");
+        assert_eq!(provider.env_key.as_deref(), Some("ANTHROPIC_API_KEY"));
+        assert!(!provider.requires_openai_auth);
+
+        let provider: ModelProviderInfo =
+            toml::from_str("name = \"Gateway\"\nwire_api = \"anthropic\"").unwrap();
+        assert_eq!(provider.wire_api, WireApi::Anthropic);
     }
 }
@@ -0,0 +1,186 @@
+//! Picks the model tier for a turn when `model_routing` is configured.
+//!
+//! The router only looks at the user's message: short questions and read-only
+//! requests run on `model_routing.fast_model`, while anything that asks for
+//! changes, carries attachments, or is long stays on the session's model. When
+//! the message gives no clear signal the turn stays on the session's model, so
+//! a misroute costs money rather than quality.
+
+use codex_protocol::protocol::ModelTier;
+use codex_protocol::user_input::UserInput;
+
+use crate::config::types::ModelRoutingConfig;
+
+/// Words that mark a request for changes. Matched as whole words, ignoring case.
+const EDIT_WORDS: &[&str] = &[
+    "add",
+    "build",
+    "change",
+    "commit",
+    "convert",
+    "create",
+    "delete",
+    "edit",
+    "fix",
+    "implement",
+    "install",
+    "migrate",
+    "modify",
+    "move",
+    "patch",
+    "refactor",
+    "remove",
+    "rename",
+    "replace",
+    "rewrite",
+    "run",
+    "update",
+    "upgrade",
+    "write",
+];
+
+/// Leading words of questions and read-only requests.
+const READ_WORDS: &[&str] = &[
+    "are",
+    "can",
+    "cat",
+    "describe",
+    "does",
+    "explain",
+    "find",
+    "how",
+    "is",
+    "list",
+    "open",
+    "print",
+    "read",
+    "show",
+    "summarize",
+    "what",
+    "when",
+    "where",
+    "which",
+    "who",
+    "why",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RouteDecision {
+    pub(crate) tier: ModelTier,
+    pub(crate) reason: String,
+}
+
+impl RouteDecision {
+    fn new(tier: ModelTier, reason: &str) -> Self {
+        Self {
+            tier,
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// Picks the tier for a turn that starts with `items`.
+pub(crate) fn route_turn(config: &ModelRoutingConfig, items: &[UserInput]) -> RouteDecision {
+    let mut text = String::new();
+    for item in items {
+        match item {
+            UserInput::Text { text: part, .. } => {
+                text.push_str(part);
+                text.push('\n');
+            }
+            UserInput::Image { .. }
+            | UserInput::LocalImage { .. }
+            | UserInput::Skill { .. }
+            | UserInput::Mention { .. } => {
+                return RouteDecision::new(ModelTier::Flagship, "the message has attachments");
+            }
+        }
+    }
+    let text = text.trim();
+
+    if text.chars().count() > config.max_fast_input_chars {
+        return RouteDecision::new(ModelTier::Flagship, "long request");
+    }
+    if text.contains("```") {
+        return RouteDecision::new(ModelTier::Flagship, "the message includes code");
+    }
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    if words.iter().any(|word| EDIT_WORDS.contains(&word.as_str())) {
+        return RouteDecision::new(ModelTier::Flagship, "asks for changes");
+    }
+    let starts_with_read_word = words
+        .first()
+        .is_some_and(|word| READ_WORDS.contains(&word.as_str()));
+    if text.ends_with('?') || starts_with_read_word {
+        return RouteDecision::new(ModelTier::Fast, "short question or read-only request");
+    }
+    RouteDecision::new(ModelTier::Flagship, "no read-only signal")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn config() -> ModelRoutingConfig {
+        ModelRoutingConfig {
+            fast_model: "fast".to_string(),
+            fast_reasoning_effort: None,
+            max_fast_input_chars: 80,
+        }
+    }
+
+    fn tier_for(text: &str) -> ModelTier {
+        let items = vec![UserInput::Text {
+            text: text.to_string(),
+            text_elements: Vec::new(),
+        }];
+        route_turn(&config(), &items).tier
+    }
+
+    #[test]
+    fn routes_questions_and_reads_to_the_fast_model() {
+        assert_eq!(tier_for("What does the retry loop do?"), ModelTier::Fast);
+        assert_eq!(tier_for("show me src/main.rs"), ModelTier::Fast);
+        assert_eq!(tier_for("Where is the config loaded"), ModelTier::Fast);
+    }
+
+    #[test]
+    fn keeps_edits_and_unclear_requests_on_the_flagship() {
+        assert_eq!(
+            tier_for("Can you fix the failing test?"),
+            ModelTier::Flagship
+        );
+        assert_eq!(tier_for("rename foo to bar"), ModelTier::Flagship);
+        assert_eq!(tier_for("yes, go ahead"), ModelTier::Flagship);
+        assert_eq!(
+            tier_for("what is wrong here?\n```\nfn main() {}\n```"),
+            ModelTier::Flagship
+        );
+        assert_eq!(
+            tier_for(&format!("why {}?", "a".repeat(80))),
+            ModelTier::Flagship
+        );
+    }
+
+    #[test]
+    fn keeps_attachments_on_the_flagship() {
+        let items = vec![
+            UserInput::Text {
+                text: "what is this?".to_string(),
+                text_elements: Vec::new(),
+            },
+            UserInput::LocalImage {
+                path: "screenshot.png".into(),
+            },
+        ];
+        assert_eq!(
+            route_turn(&config(), &items),
+            RouteDecision::new(ModelTier::Flagship, "the message has attachments")
+        );
+    }
+}
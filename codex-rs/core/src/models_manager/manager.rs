@@ -1,10 +1,10 @@
 use super::cache::ModelsCacheManager;
-use crate::api_bridge::auth_provider_from_auth;
+use crate::api_bridge::auth_provider_for_provider;
 use crate::api_bridge::map_api_error;
 use crate::auth::AuthManager;
 use crate::auth::AuthMode;
 use crate::config::Config;
-use crate::default_client::build_reqwest_client;
+use crate::default_client::build_reqwest_client_for_provider;
 use crate::error::CodexErr;
 use crate::error::Result as CoreResult;
 use crate::features::Feature;
@@ -240,8 +240,13 @@ impl ModelsManager {
         let auth = self.auth_manager.auth().await;
         let auth_mode = self.auth_manager.auth_mode();
         let api_provider = self.provider.to_api_provider(auth_mode)?;
-        let api_auth = auth_provider_from_auth(auth.clone(), &self.provider)?;
-        let transport = ReqwestTransport::new(build_reqwest_client());
+        let api_auth = auth_provider_for_provider(
+            Some(self.auth_manager.as_ref()),
+            auth.clone(),
+            &self.provider,
+        )
+        .await?;
+        let transport = ReqwestTransport::new(build_reqwest_client_for_provider(&self.provider));
         let client = ModelsClient::new(transport, api_provider, api_auth);
 
         let client_version = crate::models_manager::client_version_to_whole();
@@ -468,8 +473,14 @@ mod tests {
             request_max_retries: Some(0),
             stream_max_retries: Some(0),
             stream_idle_timeout_ms: Some(5_000),
+            stream_stall_timeout_ms: None,
             requires_openai_auth: false,
             supports_websockets: false,
+            oidc: None,
+            http_client: None,
+            organization: None,
+            project: None,
+            azure: None,
         }
     }
 
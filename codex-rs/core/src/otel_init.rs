@@ -89,6 +89,7 @@ pub fn build_provider(
         trace_exporter,
         metrics_exporter,
         runtime_metrics,
+        prometheus_listen_addr: config.otel.prometheus_listen_addr,
     })
 }
 
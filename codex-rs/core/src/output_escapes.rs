@@ -0,0 +1,412 @@
+//! Resolves terminal control sequences in captured command output.
+//!
+//! Progress bars redraw themselves with carriage returns and cursor movement,
+//! so the raw output of a long build holds every frame even though a terminal
+//! only ever showed the last one. Before output is stored or sent to the model
+//! it goes through [`normalize_output`], which handles it according to the
+//! `exec_output_escapes` setting.
+
+use std::borrow::Cow;
+use std::sync::RwLock;
+
+use crate::config::types::ExecOutputEscapes;
+
+/// How far past the end of a line [`render`] lets the cursor move, so stray
+/// sequences cannot make it allocate enormous lines.
+const MAX_COLUMNS_PAST_END: usize = 1_000;
+
+static OUTPUT_ESCAPES: RwLock<ExecOutputEscapes> = RwLock::new(ExecOutputEscapes::Render);
+
+/// Sets how [`normalize_output`] handles control sequences from now on.
+pub fn set_output_escapes(mode: ExecOutputEscapes) {
+    let Ok(mut guard) = OUTPUT_ESCAPES.write() else {
+        tracing::warn!("Failed to acquire output escapes lock");
+        return;
+    };
+    *guard = mode;
+}
+
+/// Applies the configured [`ExecOutputEscapes`] mode to `text`. Output
+/// without control characters is returned as is.
+pub fn normalize_output(text: String) -> String {
+    let mode = OUTPUT_ESCAPES.read().map(|mode| *mode).unwrap_or_default();
+    let normalized = match normalize_output_with(&text, mode) {
+        Cow::Owned(normalized) => Some(normalized),
+        Cow::Borrowed(_) => None,
+    };
+    normalized.unwrap_or(text)
+}
+
+fn normalize_output_with(text: &str, mode: ExecOutputEscapes) -> Cow<'_, str> {
+    if mode == ExecOutputEscapes::Raw
+        || !text
+            .chars()
+            .any(|c| c.is_control() && c != '\n' && c != '\t')
+    {
+        return Cow::Borrowed(text);
+    }
+    let tokens = Tokens::new(text);
+    Cow::Owned(match mode {
+        ExecOutputEscapes::Raw => text.to_string(),
+        ExecOutputEscapes::Strip => strip(tokens),
+        ExecOutputEscapes::KeepLastLine => keep_last_line(tokens),
+        ExecOutputEscapes::Render => render(tokens),
+    })
+}
+
+/// Drops escape sequences, and puts each carriage-return redraw on its own line.
+fn strip(tokens: Tokens<'_>) -> String {
+    let mut out = String::new();
+    let mut pending_cr = false;
+    for token in tokens {
+        match token {
+            Token::Char('\r') => pending_cr = true,
+            Token::Char(c) => {
+                if pending_cr && c != '\n' {
+                    out.push('\n');
+                }
+                pending_cr = false;
+                out.push(c);
+            }
+            Token::Backspace | Token::Csi { .. } => {}
+        }
+    }
+    out
+}
+
+/// Drops escape sequences, and keeps only the last frame of each line: the
+/// text written after its last carriage return, or before it if nothing was.
+fn keep_last_line(tokens: Tokens<'_>) -> String {
+    let mut out = String::new();
+    let mut frame = String::new();
+    let mut previous_frame = String::new();
+    for token in tokens {
+        match token {
+            Token::Char('\r') => {
+                if !frame.is_empty() {
+                    previous_frame = std::mem::take(&mut frame);
+                }
+            }
+            Token::Char('\n') => {
+                out.push_str(last_frame(&frame, &previous_frame));
+                out.push('\n');
+                frame.clear();
+                previous_frame.clear();
+            }
+            Token::Char(c) => frame.push(c),
+            Token::Backspace => {
+                frame.pop();
+            }
+            Token::Csi { .. } => {}
+        }
+    }
+    out.push_str(last_frame(&frame, &previous_frame));
+    out
+}
+
+fn last_frame<'a>(frame: &'a str, previous_frame: &'a str) -> &'a str {
+    if frame.is_empty() {
+        previous_frame
+    } else {
+        frame
+    }
+}
+
+/// Replays the output on a terminal of unbounded size and returns what it
+/// would show. Cursor movement, carriage returns, backspaces and erase
+/// sequences are honored; everything else (colors, titles, ...) is dropped.
+fn render(tokens: Tokens<'_>) -> String {
+    let mut screen = Screen::default();
+    for token in tokens {
+        match token {
+            Token::Char('\r') => screen.col = 0,
+            Token::Char('\n') => screen.move_to_row(screen.row + 1, 0),
+            Token::Char(c) => screen.put(c),
+            Token::Backspace => screen.col = screen.col.saturating_sub(1),
+            Token::Csi { params, action } => screen.apply_csi(params, action),
+        }
+    }
+    screen.into_text()
+}
+
+#[derive(Default)]
+struct Screen {
+    lines: Vec<Vec<char>>,
+    row: usize,
+    col: usize,
+}
+
+impl Screen {
+    fn line(&mut self) -> &mut Vec<char> {
+        if self.lines.len() <= self.row {
+            self.lines.resize_with(self.row + 1, Vec::new);
+        }
+        &mut self.lines[self.row]
+    }
+
+    fn put(&mut self, c: char) {
+        let col = self.col;
+        let line = self.line();
+        if col < line.len() {
+            line[col] = c;
+        } else {
+            line.resize(col, ' ');
+            line.push(c);
+        }
+        self.col += 1;
+    }
+
+    fn last_row(&self) -> usize {
+        self.lines.len().saturating_sub(1).max(self.row)
+    }
+
+    fn move_to_col(&mut self, col: usize) {
+        let end = self.line().len();
+        self.col = col.min(end + MAX_COLUMNS_PAST_END);
+    }
+
+    fn move_to_row(&mut self, row: usize, col: usize) {
+        self.row = row;
+        self.col = col;
+        self.line();
+    }
+
+    fn apply_csi(&mut self, params: &str, action: char) {
+        let first = params
+            .split(';')
+            .next()
+            .and_then(|param| param.parse::<usize>().ok());
+        let count = first.unwrap_or(1).max(1);
+        match action {
+            'A' => self.row = self.row.saturating_sub(count),
+            // Like a terminal, moving down stops at the last line instead of
+            // adding new ones.
+            'B' => self.row = self.row.saturating_add(count).min(self.last_row()),
+            'C' => self.move_to_col(self.col.saturating_add(count)),
+            'D' => self.col = self.col.saturating_sub(count),
+            'E' => self.move_to_row(self.row.saturating_add(count).min(self.last_row()), 0),
+            'F' => self.move_to_row(self.row.saturating_sub(count), 0),
+            'G' => self.move_to_col(count - 1),
+            'K' => {
+                let col = self.col;
+                let line = self.line();
+                match first.unwrap_or(0) {
+                    0 => line.truncate(col),
+                    1 => line.iter_mut().take(col + 1).for_each(|c| *c = ' '),
+                    2 => line.clear(),
+                    _ => {}
+                }
+            }
+            // Only "erase below" is honored: clearing the whole screen would
+            // throw away output that stays in a real terminal's scrollback.
+            'J' if first.unwrap_or(0) == 0 => {
+                let col = self.col;
+                self.line().truncate(col);
+                self.lines.truncate(self.row + 1);
+            }
+            _ => {}
+        }
+    }
+
+    fn into_text(self) -> String {
+        let lines: Vec<String> = self
+            .lines
+            .into_iter()
+            .map(|line| line.into_iter().collect::<String>().trim_end().to_string())
+            .collect();
+        lines.join("\n")
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Token<'a> {
+    /// A printable character, `\r`, `\n` or `\t`.
+    Char(char),
+    Backspace,
+    /// A control sequence (`ESC [ params action`).
+    Csi {
+        params: &'a str,
+        action: char,
+    },
+}
+
+/// Splits output into characters and control sequences. OSC sequences (such
+/// as window titles), other escape sequences and stray control characters are
+/// skipped.
+struct Tokens<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Tokens<'a> {
+    fn new(text: &'a str) -> Self {
+        Self { text, pos: 0 }
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.text[self.pos..].chars().next()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    /// Skips to the end of an OSC or other string sequence, which ends with
+    /// BEL or `ESC \`.
+    fn skip_string(&mut self) {
+        while let Some(c) = self.next_char() {
+            match c {
+                '\x07' => return,
+                '\x1b' if self.text[self.pos..].starts_with('\\') => {
+                    self.pos += 1;
+                    return;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn csi(&mut self) -> Option<Token<'a>> {
+        let start = self.pos;
+        while let Some(c) = self.next_char() {
+            match c {
+                '\x30'..='\x3f' | '\x20'..='\x2f' => {}
+                '\x40'..='\x7e' => {
+                    return Some(Token::Csi {
+                        params: &self.text[start..self.pos - 1],
+                        action: c,
+                    });
+                }
+                // Malformed; drop what was read.
+                _ => return None,
+            }
+        }
+        None
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        loop {
+            let c = self.next_char()?;
+            match c {
+                '\x1b' => match self.next_char() {
+                    Some('[') => {
+                        if let Some(token) = self.csi() {
+                            return Some(token);
+                        }
+                    }
+                    Some(']' | 'P' | 'X' | '^' | '_') => self.skip_string(),
+                    // `ESC ( B` and friends: intermediates, then a final byte.
+                    Some('\x20'..='\x2f') => {
+                        while let Some(c) = self.next_char() {
+                            if !('\x20'..='\x2f').contains(&c) {
+                                break;
+                            }
+                        }
+                    }
+                    _ => {}
+                },
+                '\x08' => return Some(Token::Backspace),
+                '\r' | '\n' | '\t' => return Some(Token::Char(c)),
+                c if c.is_control() => {}
+                c => return Some(Token::Char(c)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const PROGRESS: &str = "Downloading 10%\rDownloading 50%\rDownloading 100%\ndone\n";
+
+    #[test]
+    fn plain_output_is_borrowed() {
+        let text = "line 1\n\tline 2\n";
+        for mode in [
+            ExecOutputEscapes::Raw,
+            ExecOutputEscapes::Strip,
+            ExecOutputEscapes::KeepLastLine,
+            ExecOutputEscapes::Render,
+        ] {
+            assert!(matches!(
+                normalize_output_with(text, mode),
+                Cow::Borrowed("line 1\n\tline 2\n")
+            ));
+        }
+    }
+
+    #[test]
+    fn strip_keeps_every_frame() {
+        assert_eq!(
+            normalize_output_with(
+                "\x1b[32mok\x1b[0m\r\nDownloading 10%\rDownloading 100%\n",
+                ExecOutputEscapes::Strip
+            ),
+            "ok\nDownloading 10%\nDownloading 100%\n"
+        );
+    }
+
+    #[test]
+    fn keep_last_line_keeps_the_final_frame() {
+        assert_eq!(
+            normalize_output_with(PROGRESS, ExecOutputEscapes::KeepLastLine),
+            "Downloading 100%\ndone\n"
+        );
+        // Frames ending in `\r` keep the last frame that was written.
+        assert_eq!(
+            normalize_output_with("50%\r100%\r\nok", ExecOutputEscapes::KeepLastLine),
+            "100%\nok"
+        );
+        assert_eq!(
+            normalize_output_with("downloading...\rdone", ExecOutputEscapes::KeepLastLine),
+            "done"
+        );
+    }
+
+    #[test]
+    fn render_overwrites_like_a_terminal() {
+        assert_eq!(
+            normalize_output_with(PROGRESS, ExecOutputEscapes::Render),
+            "Downloading 100%\ndone\n"
+        );
+        // `\r` without an erase leaves the tail of the longer frame.
+        assert_eq!(
+            normalize_output_with("downloading...\rdone", ExecOutputEscapes::Render),
+            "doneloading..."
+        );
+        assert_eq!(
+            normalize_output_with("downloading...\r\x1b[Kdone", ExecOutputEscapes::Render),
+            "done"
+        );
+    }
+
+    #[test]
+    fn render_follows_cursor_movement() {
+        // Two progress bars redrawn in place, as cargo and docker do.
+        let output = concat!(
+            "a: 0%\nb: 0%\n",
+            "\x1b[2A\x1b[2Ka: 50%\n\x1b[2Kb: 10%\n",
+            "\x1b[2A\x1b[2Ka: 100%\n\x1b[2Kb: 100%\n",
+            "\x1b]0;title\x07\x1b[1;31merror\x1b[0m: failed\n",
+        );
+        assert_eq!(
+            normalize_output_with(output, ExecOutputEscapes::Render),
+            "a: 100%\nb: 100%\nerror: failed\n"
+        );
+    }
+
+    #[test]
+    fn render_handles_backspace_spinners() {
+        assert_eq!(
+            normalize_output_with(
+                "working |\x08/\x08-\x08\\\x08done\n",
+                ExecOutputEscapes::Render
+            ),
+            "working done\n"
+        );
+    }
+}
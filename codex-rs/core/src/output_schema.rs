@@ -0,0 +1,345 @@
+//! Local validation of final messages against a turn's output schema.
+//!
+//! Providers with structured outputs already constrain the final message, but
+//! others (and models that ignore the constraint) can still answer in prose or
+//! with JSON of the wrong shape. The turn checks the final message here and
+//! asks the model to repair it a few times before reporting an error.
+//!
+//! The validator covers the JSON Schema subset accepted by structured outputs:
+//! `type`, `enum`, `const`, `properties`, `required`, `additionalProperties`,
+//! `items`, `anyOf`/`oneOf`/`allOf`, local `$ref`s, and the usual length,
+//! size, range, and `pattern` bounds. Other keywords are ignored.
+
+use codex_protocol::models::DeveloperInstructions;
+use codex_protocol::models::ResponseItem;
+use regex_lite::Regex;
+use serde_json::Value;
+
+/// How many times a turn asks the model to fix a final message that does not
+/// match the output schema.
+pub(crate) const MAX_OUTPUT_SCHEMA_REPAIRS: usize = 2;
+
+/// Errors beyond this many are summarized so the repair prompt stays short.
+const MAX_REPORTED_ERRORS: usize = 10;
+
+/// Checks that `message` is a single JSON value matching `schema`, returning
+/// the problems found otherwise.
+pub(crate) fn validate_final_message(schema: &Value, message: &str) -> Result<(), Vec<String>> {
+    let value: Value = serde_json::from_str(message.trim()).map_err(|err| {
+        vec![format!(
+            "the final message is not a single JSON value ({err})"
+        )]
+    })?;
+    let mut errors = Vec::new();
+    Validator { root: schema }.validate(schema, &value, "$", &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Human-readable list of validation errors.
+pub(crate) fn describe_errors(errors: &[String]) -> String {
+    let mut lines: Vec<String> = errors
+        .iter()
+        .take(MAX_REPORTED_ERRORS)
+        .map(|error| format!("- {error}"))
+        .collect();
+    if errors.len() > MAX_REPORTED_ERRORS {
+        lines.push(format!(
+            "- ... and {} more",
+            errors.len() - MAX_REPORTED_ERRORS
+        ));
+    }
+    lines.join("\n")
+}
+
+/// Developer message asking the model to answer again within the schema.
+pub(crate) fn repair_request(errors: &[String]) -> ResponseItem {
+    DeveloperInstructions::new(format!(
+        "Your final message does not match the required output JSON schema:\n{}\n\nReply again with only a JSON value that matches the schema: no prose and no code fences.",
+        describe_errors(errors)
+    ))
+    .into()
+}
+
+struct Validator<'a> {
+    root: &'a Value,
+}
+
+impl<'a> Validator<'a> {
+    fn validate(&self, schema: &'a Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+        let Some(schema) = schema.as_object() else {
+            // `true`, `{}`, and anything unexpected accept every value.
+            if schema == &Value::Bool(false) {
+                errors.push(format!("{path}: no value is allowed here"));
+            }
+            return;
+        };
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            match self.resolve(reference) {
+                Some(target) => self.validate(target, value, path, errors),
+                None => errors.push(format!("{path}: cannot resolve `$ref` {reference}")),
+            }
+        }
+
+        if let Some(expected) = schema.get("type")
+            && !type_matches(expected, value)
+        {
+            errors.push(format!(
+                "{path}: expected {}, found {}",
+                describe_type(expected),
+                type_name(value)
+            ));
+            return;
+        }
+
+        if let Some(options) = schema.get("enum").and_then(Value::as_array)
+            && !options.contains(value)
+        {
+            errors.push(format!(
+                "{path}: {value} is not one of {}",
+                Value::Array(options.clone())
+            ));
+        }
+        if let Some(expected) = schema.get("const")
+            && expected != value
+        {
+            errors.push(format!("{path}: expected {expected}, found {value}"));
+        }
+
+        if let Some(branches) = schema.get("allOf").and_then(Value::as_array) {
+            for branch in branches {
+                self.validate(branch, value, path, errors);
+            }
+        }
+        for keyword in ["anyOf", "oneOf"] {
+            if let Some(branches) = schema.get(keyword).and_then(Value::as_array)
+                && !branches.iter().any(|branch| self.is_valid(branch, value))
+            {
+                errors.push(format!(
+                    "{path}: does not match any of the `{keyword}` alternatives"
+                ));
+            }
+        }
+
+        match value {
+            Value::Object(object) => {
+                let properties = schema.get("properties").and_then(Value::as_object);
+                if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                    for name in required.iter().filter_map(Value::as_str) {
+                        if !object.contains_key(name) {
+                            errors.push(format!("{path}: missing required property `{name}`"));
+                        }
+                    }
+                }
+                for (name, property) in object {
+                    let property_path = format!("{path}.{name}");
+                    match properties.and_then(|properties| properties.get(name)) {
+                        Some(property_schema) => {
+                            self.validate(property_schema, property, &property_path, errors);
+                        }
+                        None => match schema.get("additionalProperties") {
+                            Some(Value::Bool(false)) => {
+                                errors.push(format!("{path}: unexpected property `{name}`"));
+                            }
+                            Some(additional) => {
+                                self.validate(additional, property, &property_path, errors);
+                            }
+                            None => {}
+                        },
+                    }
+                }
+            }
+            Value::Array(items) => {
+                check_bound(schema, "minItems", items.len(), path, "items", errors);
+                check_bound(schema, "maxItems", items.len(), path, "items", errors);
+                if let Some(item_schema) = schema.get("items") {
+                    for (index, item) in items.iter().enumerate() {
+                        self.validate(item_schema, item, &format!("{path}[{index}]"), errors);
+                    }
+                }
+            }
+            Value::String(text) => {
+                let length = text.chars().count();
+                check_bound(schema, "minLength", length, path, "characters", errors);
+                check_bound(schema, "maxLength", length, path, "characters", errors);
+                if let Some(pattern) = schema.get("pattern").and_then(Value::as_str)
+                    && let Ok(regex) = Regex::new(pattern)
+                    && !regex.is_match(text)
+                {
+                    errors.push(format!("{path}: does not match the pattern `{pattern}`"));
+                }
+            }
+            Value::Number(number) => {
+                if let Some(number) = number.as_f64() {
+                    check_range(schema, number, path, errors);
+                }
+            }
+            Value::Bool(_) | Value::Null => {}
+        }
+    }
+
+    fn is_valid(&self, schema: &'a Value, value: &Value) -> bool {
+        let mut errors = Vec::new();
+        self.validate(schema, value, "$", &mut errors);
+        errors.is_empty()
+    }
+
+    /// Resolves a local reference such as `#/$defs/step`.
+    fn resolve(&self, reference: &str) -> Option<&'a Value> {
+        let pointer = reference.strip_prefix('#')?;
+        self.root.pointer(pointer)
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(number) if number.is_i64() || number.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn type_matches(expected: &Value, value: &Value) -> bool {
+    let matches = |name: &str| match (name, type_name(value)) {
+        ("number", "integer") => true,
+        ("integer", "number") => value.as_f64().is_some_and(|number| number.fract() == 0.0),
+        (expected, actual) => expected == actual,
+    };
+    match expected {
+        Value::String(name) => matches(name),
+        Value::Array(names) => names.iter().filter_map(Value::as_str).any(matches),
+        _ => true,
+    }
+}
+
+fn describe_type(expected: &Value) -> String {
+    match expected {
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" or "),
+        Value::String(name) => name.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn check_bound(
+    schema: &serde_json::Map<String, Value>,
+    keyword: &str,
+    actual: usize,
+    path: &str,
+    unit: &str,
+    errors: &mut Vec<String>,
+) {
+    let Some(bound) = schema.get(keyword).and_then(Value::as_u64) else {
+        return;
+    };
+    let actual = actual as u64;
+    let violated = if keyword.starts_with("min") {
+        actual < bound
+    } else {
+        actual > bound
+    };
+    if violated {
+        errors.push(format!(
+            "{path}: has {actual} {unit}, `{keyword}` is {bound}"
+        ));
+    }
+}
+
+fn check_range(
+    schema: &serde_json::Map<String, Value>,
+    number: f64,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    let bound = |keyword: &str| schema.get(keyword).and_then(Value::as_f64);
+    let checks = [
+        ("minimum", bound("minimum").is_some_and(|min| number < min)),
+        ("maximum", bound("maximum").is_some_and(|max| number > max)),
+        (
+            "exclusiveMinimum",
+            bound("exclusiveMinimum").is_some_and(|min| number <= min),
+        ),
+        (
+            "exclusiveMaximum",
+            bound("exclusiveMaximum").is_some_and(|max| number >= max),
+        ),
+    ];
+    for (keyword, violated) in checks {
+        if violated {
+            errors.push(format!("{path}: {number} is outside `{keyword}`"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "status": {"type": "string", "enum": ["ok", "failed"]},
+                "steps": {"type": "array", "items": {"$ref": "#/$defs/step"}, "minItems": 1},
+                "note": {"type": ["string", "null"]},
+            },
+            "required": ["status", "steps"],
+            "additionalProperties": false,
+            "$defs": {
+                "step": {
+                    "type": "object",
+                    "properties": {"name": {"type": "string"}, "seconds": {"type": "number", "minimum": 0}},
+                    "required": ["name", "seconds"],
+                    "additionalProperties": false,
+                },
+            },
+        })
+    }
+
+    #[test]
+    fn accepts_matching_messages() {
+        let message = r#"
+            {"status": "ok", "steps": [{"name": "build", "seconds": 12}], "note": null}
+        "#;
+        assert_eq!(validate_final_message(&schema(), message), Ok(()));
+    }
+
+    #[test]
+    fn reports_every_mismatch_with_its_path() {
+        let message = r#"{"status": "done", "steps": [{"name": 3, "seconds": -1}], "extra": true}"#;
+        let mut errors =
+            validate_final_message(&schema(), message).expect_err("message does not match");
+        // Property order depends on serde_json's `preserve_order` feature.
+        errors.sort();
+        assert_eq!(
+            errors,
+            vec![
+                r#"$.status: "done" is not one of ["ok","failed"]"#.to_string(),
+                "$.steps[0].name: expected string, found integer".to_string(),
+                "$.steps[0].seconds: -1 is outside `minimum`".to_string(),
+                "$: unexpected property `extra`".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_prose_around_the_json() {
+        let errors = validate_final_message(&schema(), "Here you go: {\"status\": \"ok\"}")
+            .expect_err("prose is not JSON");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("the final message is not a single JSON value"));
+    }
+}
@@ -0,0 +1,326 @@
+//! Content-addressed backups of the file versions `apply_patch` replaces during
+//! a session, so a whole patch or individual files can be restored regardless
+//! of git state.
+//!
+//! Every version is stored once under its SHA-256 in a per-thread directory of
+//! `CODEX_HOME`. The list of patches and the versions they replaced lives in
+//! memory and is dropped, together with the objects, when the session ends.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use sha2::Digest;
+use sha2::Sha256;
+use tracing::warn;
+
+use crate::protocol::FileChange;
+
+/// Subdirectory of `CODEX_HOME` holding one object directory per thread.
+pub(crate) const PATCH_BACKUPS_DIR: &str = "patch_backups";
+
+/// State of a path right before a patch touched it.
+#[derive(Debug, Clone)]
+struct FileBackup {
+    path: PathBuf,
+    /// Object id of the previous content, or `None` if the file did not exist.
+    before: Option<String>,
+}
+
+#[derive(Debug)]
+struct PatchRecord {
+    files: Vec<FileBackup>,
+}
+
+pub(crate) struct PatchBackups {
+    objects_dir: PathBuf,
+    /// Backups taken for patches that have not finished yet, keyed by call id.
+    pending: HashMap<String, PatchRecord>,
+    /// Finished patches that changed at least one file, oldest first.
+    patches: Vec<PatchRecord>,
+}
+
+impl PatchBackups {
+    pub(crate) fn new(objects_dir: PathBuf) -> Self {
+        Self {
+            objects_dir,
+            pending: HashMap::new(),
+            patches: Vec::new(),
+        }
+    }
+
+    /// Back up every path `changes` is about to replace, including move destinations.
+    pub(crate) fn on_patch_begin(&mut self, call_id: &str, changes: &HashMap<PathBuf, FileChange>) {
+        let mut paths: Vec<PathBuf> = Vec::with_capacity(changes.len());
+        for (path, change) in changes {
+            paths.push(path.clone());
+            if let FileChange::Update {
+                move_path: Some(dest),
+                ..
+            } = change
+            {
+                paths.push(dest.clone());
+            }
+        }
+        paths.sort();
+        paths.dedup();
+
+        let files = paths
+            .into_iter()
+            .filter_map(|path| match self.store(&path) {
+                Ok(before) => Some(FileBackup { path, before }),
+                Err(err) => {
+                    warn!("failed to back up {}: {err:#}", path.display());
+                    None
+                }
+            })
+            .collect();
+        self.pending
+            .insert(call_id.to_string(), PatchRecord { files });
+    }
+
+    /// Keep the backups of the paths the patch actually changed. Patches that were
+    /// declined or failed before touching anything leave no record.
+    pub(crate) fn on_patch_end(&mut self, call_id: &str) {
+        let Some(mut record) = self.pending.remove(call_id) else {
+            return;
+        };
+        record
+            .files
+            .retain(|file| object_id_for_path(&file.path).ok() != Some(file.before.clone()));
+        if !record.files.is_empty() {
+            self.patches.push(record);
+        }
+    }
+
+    /// Restore every file changed by the most recent patch that still has backups.
+    /// Returns the restored paths.
+    pub(crate) fn undo_last_patch(&mut self) -> Result<Vec<PathBuf>> {
+        let Some(record) = self.patches.last() else {
+            bail!("No apply_patch changes to undo.");
+        };
+        for file in &record.files {
+            self.restore(file)?;
+        }
+        let restored = self
+            .patches
+            .pop()
+            .map(|record| record.files.into_iter().map(|file| file.path).collect())
+            .unwrap_or_default();
+        Ok(restored)
+    }
+
+    /// Restore each of `paths` to its version before the most recent patch that
+    /// changed it. Nothing is restored unless every path has a backup.
+    pub(crate) fn undo_files(&mut self, paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+        let mut targets: Vec<(usize, usize)> = Vec::with_capacity(paths.len());
+        for path in paths {
+            let Some(target) = self
+                .patches
+                .iter()
+                .enumerate()
+                .rev()
+                .find_map(|(idx, record)| {
+                    record
+                        .files
+                        .iter()
+                        .position(|file| &file.path == path)
+                        .map(|file_idx| (idx, file_idx))
+                })
+            else {
+                bail!("No apply_patch backup for {}.", path.display());
+            };
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+
+        let mut restored = Vec::with_capacity(targets.len());
+        for (idx, file_idx) in &targets {
+            let file = &self.patches[*idx].files[*file_idx];
+            self.restore(file)?;
+            restored.push(file.path.clone());
+        }
+
+        // Drop the restored entries, highest indices first so earlier ones stay valid.
+        targets.sort_unstable_by(|a, b| b.cmp(a));
+        for (idx, file_idx) in targets {
+            self.patches[idx].files.remove(file_idx);
+        }
+        self.patches.retain(|record| !record.files.is_empty());
+        Ok(restored)
+    }
+
+    /// Delete the stored objects and forget every patch.
+    pub(crate) fn clear(&mut self) {
+        self.pending.clear();
+        self.patches.clear();
+        if let Err(err) = fs::remove_dir_all(&self.objects_dir)
+            && err.kind() != io::ErrorKind::NotFound
+        {
+            warn!(
+                "failed to remove patch backups at {}: {err}",
+                self.objects_dir.display()
+            );
+        }
+    }
+
+    fn store(&self, path: &Path) -> Result<Option<String>> {
+        let content = match fs::read(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err).with_context(|| format!("reading {}", path.display())),
+        };
+        let id = object_id(&content);
+        let object_path = self.objects_dir.join(&id);
+        if !object_path.exists() {
+            fs::create_dir_all(&self.objects_dir)
+                .with_context(|| format!("creating {}", self.objects_dir.display()))?;
+            fs::write(&object_path, &content)
+                .with_context(|| format!("writing {}", object_path.display()))?;
+        }
+        Ok(Some(id))
+    }
+
+    fn restore(&self, file: &FileBackup) -> Result<()> {
+        match &file.before {
+            Some(id) => {
+                let object_path = self.objects_dir.join(id);
+                let content = fs::read(&object_path)
+                    .with_context(|| format!("reading backup of {}", file.path.display()))?;
+                if let Some(parent) = file.path.parent() {
+                    fs::create_dir_all(parent)
+                        .with_context(|| format!("creating {}", parent.display()))?;
+                }
+                fs::write(&file.path, content)
+                    .with_context(|| format!("restoring {}", file.path.display()))?;
+            }
+            None => match fs::remove_file(&file.path) {
+                Ok(()) => {}
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => {
+                    return Err(err).with_context(|| format!("removing {}", file.path.display()));
+                }
+            },
+        }
+        Ok(())
+    }
+}
+
+fn object_id(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+fn object_id_for_path(path: &Path) -> io::Result<Option<String>> {
+    match fs::read(path) {
+        Ok(content) => Ok(Some(object_id(&content))),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    fn update(move_path: Option<PathBuf>) -> FileChange {
+        FileChange::Update {
+            unified_diff: String::new(),
+            move_path,
+        }
+    }
+
+    #[test]
+    fn undo_last_patch_restores_updates_moves_and_additions() {
+        let tmp = tempdir().expect("tmp");
+        let mut backups = PatchBackups::new(tmp.path().join("objects"));
+        let edited = tmp.path().join("edited.txt");
+        let moved = tmp.path().join("moved.txt");
+        let dest = tmp.path().join("dest.txt");
+        let added = tmp.path().join("added.txt");
+        fs::write(&edited, "original\n").expect("write edited");
+        fs::write(&moved, "moved\n").expect("write moved");
+
+        let changes = HashMap::from([
+            (edited.clone(), update(None)),
+            (moved.clone(), update(Some(dest.clone()))),
+            (
+                added.clone(),
+                FileChange::Add {
+                    content: "new\n".to_string(),
+                },
+            ),
+        ]);
+        backups.on_patch_begin("call-1", &changes);
+        fs::write(&edited, "changed\n").expect("edit");
+        fs::rename(&moved, &dest).expect("move");
+        fs::write(&added, "new\n").expect("add");
+        backups.on_patch_end("call-1");
+
+        let mut restored = backups.undo_last_patch().expect("undo");
+        restored.sort();
+        let mut expected = vec![edited.clone(), moved.clone(), dest.clone(), added.clone()];
+        expected.sort();
+        assert_eq!(restored, expected);
+        assert_eq!(fs::read_to_string(&edited).expect("read"), "original\n");
+        assert_eq!(fs::read_to_string(&moved).expect("read"), "moved\n");
+        assert!(!dest.exists());
+        assert!(!added.exists());
+        assert!(backups.undo_last_patch().is_err());
+    }
+
+    #[test]
+    fn undo_files_steps_back_one_patch_at_a_time() {
+        let tmp = tempdir().expect("tmp");
+        let mut backups = PatchBackups::new(tmp.path().join("objects"));
+        let path = tmp.path().join("notes.txt");
+        let other = tmp.path().join("other.txt");
+        fs::write(&path, "v1\n").expect("write");
+        fs::write(&other, "other v1\n").expect("write");
+
+        let changes = HashMap::from([(path.clone(), update(None)), (other.clone(), update(None))]);
+        backups.on_patch_begin("call-1", &changes);
+        fs::write(&path, "v2\n").expect("write");
+        fs::write(&other, "other v2\n").expect("write");
+        backups.on_patch_end("call-1");
+
+        let changes = HashMap::from([(path.clone(), update(None))]);
+        backups.on_patch_begin("call-2", &changes);
+        fs::write(&path, "v3\n").expect("write");
+        backups.on_patch_end("call-2");
+
+        // A declined patch leaves nothing to undo.
+        backups.on_patch_begin("call-3", &changes);
+        backups.on_patch_end("call-3");
+
+        assert_eq!(
+            backups
+                .undo_files(std::slice::from_ref(&path))
+                .expect("undo"),
+            vec![path.clone()]
+        );
+        assert_eq!(fs::read_to_string(&path).expect("read"), "v2\n");
+        assert_eq!(
+            backups
+                .undo_files(std::slice::from_ref(&path))
+                .expect("undo"),
+            vec![path.clone()]
+        );
+        assert_eq!(fs::read_to_string(&path).expect("read"), "v1\n");
+        assert_eq!(fs::read_to_string(&other).expect("read"), "other v2\n");
+
+        assert!(backups.undo_files(std::slice::from_ref(&path)).is_err());
+        assert_eq!(
+            backups.undo_last_patch().expect("undo"),
+            vec![other.clone()]
+        );
+        assert_eq!(fs::read_to_string(&other).expect("read"), "other v1\n");
+    }
+}
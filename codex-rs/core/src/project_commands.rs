@@ -0,0 +1,417 @@
+//! Build, test, lint and format commands of the project containing `cwd`.
+//!
+//! With the `project_commands` feature, the commands are detected from the
+//! project's build files (Cargo, npm/pnpm, Maven, Bazel, Make) the first time
+//! a session needs them and cached in `.codex/project.toml` at the project
+//! root, where they can be edited. The model sees them in the initial context
+//! instead of guessing, and with `auto_approve_project_commands` set in a
+//! trusted project, running one of them exactly skips the approval prompt.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::command_canonicalization::canonicalize_command_for_approval;
+use crate::features::Feature;
+use crate::git_info::get_git_repo_root;
+use crate::session_prefix::PROJECT_COMMANDS_CLOSE_TAG;
+use crate::session_prefix::PROJECT_COMMANDS_OPEN_TAG;
+use crate::tools::sandboxing::ExecApprovalRequirement;
+
+const PROJECT_FILE_DIR: &str = ".codex";
+const PROJECT_FILE_NAME: &str = "project.toml";
+
+const PROJECT_FILE_HEADER: &str = "# Commands Codex detected for this project. Edit them to fix or add\n# commands, or delete this file to detect them again.\n\n";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ProjectCommands {
+    /// Command line by name (`build`, `test`, `lint`, `format`, ...).
+    #[serde(default)]
+    commands: BTreeMap<String, String>,
+}
+
+impl ProjectCommands {
+    /// Reads `.codex/project.toml` at the root of the project containing
+    /// `cwd`. When the file does not exist, detects the commands and writes it.
+    pub(crate) async fn load(cwd: &Path) -> Self {
+        let root = get_git_repo_root(cwd).unwrap_or_else(|| cwd.to_path_buf());
+        let path = root.join(PROJECT_FILE_DIR).join(PROJECT_FILE_NAME);
+        match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => {
+                return toml::from_str(&contents).unwrap_or_else(|err| {
+                    warn!("ignoring invalid {}: {err}", path.display());
+                    Self::default()
+                });
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => {
+                warn!("failed to read {}: {err}", path.display());
+                return Self::default();
+            }
+        }
+
+        let detected = Self {
+            commands: detect_commands(&BuildFiles::read(&root).await),
+        };
+        if !detected.commands.is_empty()
+            && let Err(err) = detected.write(&path).await
+        {
+            warn!("failed to write {}: {err}", path.display());
+        }
+        detected
+    }
+
+    async fn write(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        let body = toml::to_string(self).map_err(std::io::Error::other)?;
+        tokio::fs::write(path, format!("{PROJECT_FILE_HEADER}{body}")).await
+    }
+
+    /// Whether `command` runs one of the project commands, and nothing else.
+    pub(crate) fn contains(&self, command: &[String]) -> bool {
+        let command = canonicalize_command_for_approval(command);
+        self.commands
+            .values()
+            .any(|line| shlex::split(line).is_some_and(|argv| argv == command))
+    }
+
+    /// Serializes the commands for the initial context:
+    ///
+    /// ```xml
+    /// <project_commands>
+    ///   <command name="build">cargo build</command>
+    ///   <command name="test">cargo test</command>
+    /// </project_commands>
+    /// ```
+    ///
+    /// Returns `None` when no commands are known.
+    pub(crate) fn serialize_to_xml(&self, auto_approved: bool) -> Option<String> {
+        if self.commands.is_empty() {
+            return None;
+        }
+        let mut lines = vec![PROJECT_COMMANDS_OPEN_TAG.to_string()];
+        lines.push(if auto_approved {
+            "  <note>Verified commands for this project. Prefer them over guessing; they run without an approval prompt when run exactly as written.</note>".to_string()
+        } else {
+            "  <note>Verified commands for this project. Prefer them over guessing.</note>"
+                .to_string()
+        });
+        for (name, line) in &self.commands {
+            lines.push(format!("  <command name=\"{name}\">{line}</command>"));
+        }
+        lines.push(PROJECT_COMMANDS_CLOSE_TAG.to_string());
+        Some(lines.join("\n"))
+    }
+}
+
+/// The session's project commands, loaded for `cwd` on first use.
+pub(crate) async fn session_project_commands<'a>(
+    session: &'a Session,
+    cwd: &Path,
+) -> &'a ProjectCommands {
+    session
+        .services
+        .project_commands
+        .get_or_init(|| ProjectCommands::load(cwd))
+        .await
+}
+
+/// Whether the user opted in to running project commands without approval
+/// and trusts this project.
+pub(crate) fn project_commands_auto_approved(turn: &TurnContext) -> bool {
+    turn.config.auto_approve_project_commands && turn.config.active_project.is_trusted()
+}
+
+/// The initial-context message listing the project commands, if any.
+pub(crate) async fn project_commands_item(
+    session: &Session,
+    turn: &TurnContext,
+) -> Option<ResponseItem> {
+    let text = session_project_commands(session, &turn.cwd)
+        .await
+        .serialize_to_xml(project_commands_auto_approved(turn))?;
+    Some(ResponseItem::Message {
+        id: None,
+        role: "user".to_string(),
+        content: vec![ContentItem::InputText { text }],
+        end_turn: None,
+        phase: None,
+    })
+}
+
+/// Lets an exact project command skip its approval prompt when
+/// [`project_commands_auto_approved`]. Commands that a rule forbids stay
+/// forbidden, and auto-approved commands still run in the sandbox.
+pub(crate) async fn approve_project_command(
+    session: &Session,
+    turn: &TurnContext,
+    command: &[String],
+    requirement: ExecApprovalRequirement,
+) -> ExecApprovalRequirement {
+    if !matches!(requirement, ExecApprovalRequirement::NeedsApproval { .. })
+        || !turn.features.enabled(Feature::ProjectCommands)
+        || !project_commands_auto_approved(turn)
+        || !session_project_commands(session, &turn.cwd)
+            .await
+            .contains(command)
+    {
+        return requirement;
+    }
+    ExecApprovalRequirement::Skip {
+        bypass_sandbox: false,
+        proposed_execpolicy_amendment: None,
+    }
+}
+
+/// Build files found at the project root.
+#[derive(Debug, Default)]
+struct BuildFiles {
+    cargo: bool,
+    package_json: Option<String>,
+    pnpm: bool,
+    maven: bool,
+    bazel: bool,
+    makefile: Option<String>,
+}
+
+impl BuildFiles {
+    async fn read(root: &Path) -> Self {
+        let exists = |name: &str| {
+            let path = root.join(name);
+            async move { tokio::fs::try_exists(path).await.unwrap_or(false) }
+        };
+        let read = |name: &str| {
+            let path = root.join(name);
+            async move { tokio::fs::read_to_string(path).await.ok() }
+        };
+        Self {
+            cargo: exists("Cargo.toml").await,
+            package_json: read("package.json").await,
+            pnpm: exists("pnpm-lock.yaml").await || exists("pnpm-workspace.yaml").await,
+            maven: exists("pom.xml").await,
+            bazel: exists("MODULE.bazel").await
+                || exists("WORKSPACE").await
+                || exists("WORKSPACE.bazel").await,
+            makefile: read("Makefile").await,
+        }
+    }
+}
+
+/// Commands for each build system found, in the order of `BuildFiles`. The
+/// first build system to provide a command name wins.
+fn detect_commands(files: &BuildFiles) -> BTreeMap<String, String> {
+    let mut commands = BTreeMap::new();
+    let mut add = |name: &str, line: String| {
+        commands.entry(name.to_string()).or_insert(line);
+    };
+
+    if files.cargo {
+        add("build", "cargo build".to_string());
+        add("test", "cargo test".to_string());
+        add("lint", "cargo clippy --all-targets".to_string());
+        add("format", "cargo fmt".to_string());
+    }
+
+    if let Some(package_json) = &files.package_json {
+        let runner = if files.pnpm { "pnpm" } else { "npm" };
+        let scripts = package_scripts(package_json);
+        for (name, candidates) in [
+            ("build", &["build"][..]),
+            ("test", &["test"]),
+            ("lint", &["lint"]),
+            ("format", &["format", "fmt"]),
+        ] {
+            if let Some(script) = candidates
+                .iter()
+                .find(|script| scripts.iter().any(|known| known == *script))
+            {
+                add(name, format!("{runner} run {script}"));
+            }
+        }
+    }
+
+    if files.maven {
+        add("build", "mvn compile".to_string());
+        add("test", "mvn test".to_string());
+    }
+
+    if files.bazel {
+        add("build", "bazel build //...".to_string());
+        add("test", "bazel test //...".to_string());
+    }
+
+    if let Some(makefile) = &files.makefile {
+        let targets = make_targets(makefile);
+        for (name, candidates) in [
+            ("build", &["build", "all"][..]),
+            ("test", &["test", "check"]),
+            ("lint", &["lint"]),
+            ("format", &["format", "fmt"]),
+        ] {
+            if let Some(target) = candidates.iter().find(|target| targets.contains(target)) {
+                add(name, format!("make {target}"));
+            }
+        }
+    }
+
+    commands
+}
+
+/// Names of the `scripts` in a `package.json`.
+fn package_scripts(package_json: &str) -> Vec<String> {
+    serde_json::from_str::<serde_json::Value>(package_json)
+        .ok()
+        .and_then(|package| {
+            package
+                .get("scripts")?
+                .as_object()
+                .map(|scripts| scripts.keys().cloned().collect())
+        })
+        .unwrap_or_default()
+}
+
+/// Explicit targets of a Makefile: `name:` rules that are not variable
+/// assignments, special targets or pattern rules.
+fn make_targets(makefile: &str) -> Vec<&str> {
+    makefile
+        .lines()
+        .filter(|line| !line.starts_with(['\t', ' ', '#', '.']))
+        .filter_map(|line| {
+            let (targets, rest) = line.split_once(':')?;
+            if rest.starts_with('=') || targets.contains(['=', '$', '%']) {
+                return None;
+            }
+            Some(targets.split_whitespace())
+        })
+        .flatten()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn commands(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, line)| (name.to_string(), line.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn detects_cargo_commands_before_make() {
+        let files = BuildFiles {
+            cargo: true,
+            makefile: Some("test:\n\tcargo nextest run\nrelease:\n\tcargo build -r\n".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            detect_commands(&files),
+            commands(&[
+                ("build", "cargo build"),
+                ("format", "cargo fmt"),
+                ("lint", "cargo clippy --all-targets"),
+                ("test", "cargo test"),
+            ])
+        );
+    }
+
+    #[test]
+    fn detects_package_scripts_with_pnpm() {
+        let files = BuildFiles {
+            package_json: Some(
+                r#"{"scripts": {"build": "tsc", "test": "vitest", "fmt": "prettier -w ."}}"#
+                    .to_string(),
+            ),
+            pnpm: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            detect_commands(&files),
+            commands(&[
+                ("build", "pnpm run build"),
+                ("format", "pnpm run fmt"),
+                ("test", "pnpm run test"),
+            ])
+        );
+    }
+
+    #[test]
+    fn detects_make_targets() {
+        let makefile = "\
+CC := gcc
+.PHONY: all check lint
+all: main.o
+\t$(CC) -o app main.o
+%.o: %.c
+\t$(CC) -c $<
+check lint:
+\t./run-checks
+";
+        assert_eq!(make_targets(makefile), vec!["all", "check", "lint"]);
+        assert_eq!(
+            detect_commands(&BuildFiles {
+                makefile: Some(makefile.to_string()),
+                ..Default::default()
+            }),
+            commands(&[
+                ("build", "make all"),
+                ("lint", "make lint"),
+                ("test", "make check")
+            ])
+        );
+    }
+
+    #[test]
+    fn matches_exact_commands_through_shell_wrappers() {
+        let project = ProjectCommands {
+            commands: commands(&[("test", "cargo test --workspace")]),
+        };
+        let shell = |script: &str| {
+            vec![
+                "/bin/bash".to_string(),
+                "-lc".to_string(),
+                script.to_string(),
+            ]
+        };
+
+        assert!(project.contains(&shell("cargo  test --workspace")));
+        assert!(!project.contains(&shell("cargo test --workspace && rm -rf target")));
+        assert!(!project.contains(&shell("cargo test")));
+    }
+
+    #[tokio::test]
+    async fn load_writes_and_then_reads_the_project_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        tokio::fs::write(dir.path().join("pom.xml"), "<project />")
+            .await
+            .expect("write pom.xml");
+
+        let detected = ProjectCommands::load(dir.path()).await;
+        assert_eq!(
+            detected.commands,
+            commands(&[("build", "mvn compile"), ("test", "mvn test")])
+        );
+
+        let path = dir.path().join(PROJECT_FILE_DIR).join(PROJECT_FILE_NAME);
+        tokio::fs::write(&path, "[commands]\ntest = \"mvn -q verify\"\n")
+            .await
+            .expect("edit project.toml");
+        assert_eq!(
+            ProjectCommands::load(dir.path()).await.commands,
+            commands(&[("test", "mvn -q verify")])
+        );
+    }
+}
@@ -12,12 +12,19 @@
 //!     current working directory (inclusive) and concatenate their contents in
 //!     that order.
 //! 3.  We do **not** walk past the Git root.
+//!
+//! A session scoped to sub-directories of the working directory (`--scope`)
+//! also reads the files between the working directory and each scope
+//! directory; see [`discover_scope_doc_paths`].
 
 use crate::config::Config;
 use crate::features::Feature;
 use crate::skills::SkillMetadata;
 use crate::skills::render_skills_section;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use dunce::canonicalize as normalize_path;
+use std::collections::HashSet;
+use std::path::Path;
 use std::path::PathBuf;
 use tokio::io::AsyncReadExt;
 use tracing::error;
@@ -227,19 +234,42 @@ pub fn discover_project_doc_paths(config: &Config) -> std::io::Result<Vec<PathBu
     let mut found: Vec<PathBuf> = Vec::new();
     let candidate_filenames = candidate_filenames(config);
     for d in search_dirs {
-        for name in &candidate_filenames {
-            let candidate = d.join(name);
-            match std::fs::symlink_metadata(&candidate) {
-                Ok(md) => {
-                    let ft = md.file_type();
-                    // Allow regular files and symlinks; opening will later fail for dangling links.
-                    if ft.is_file() || ft.is_symlink() {
-                        found.push(candidate);
-                        break;
-                    }
-                }
-                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
-                Err(e) => return Err(e),
+        if let Some(doc) = find_project_doc(&d, &candidate_filenames)? {
+            found.push(doc);
+        }
+    }
+
+    Ok(found)
+}
+
+/// Discover the AGENTS.md files of a scoped session: those in the directories
+/// below `cwd` on the way down to each scope directory (inclusive), in scope
+/// order. The files from the repository root to `cwd` are already covered by
+/// `discover_project_doc_paths` and are not repeated.
+pub(crate) fn discover_scope_doc_paths(
+    config: &Config,
+    cwd: &Path,
+    scope: &[AbsolutePathBuf],
+) -> std::io::Result<Vec<PathBuf>> {
+    if config.project_doc_max_bytes == 0 {
+        return Ok(Vec::new());
+    }
+
+    let candidate_filenames = candidate_filenames(config);
+    let mut visited: HashSet<PathBuf> = HashSet::new();
+    let mut found: Vec<PathBuf> = Vec::new();
+    for dir in scope {
+        let Ok(relative) = dir.as_path().strip_prefix(cwd) else {
+            continue;
+        };
+        let mut d = cwd.to_path_buf();
+        for component in relative.components() {
+            d.push(component);
+            if !visited.insert(d.clone()) {
+                continue;
+            }
+            if let Some(doc) = find_project_doc(&d, &candidate_filenames)? {
+                found.push(doc);
             }
         }
     }
@@ -247,6 +277,25 @@ pub fn discover_project_doc_paths(config: &Config) -> std::io::Result<Vec<PathBu
     Ok(found)
 }
 
+/// Returns the first of `candidate_filenames` present in `dir`.
+fn find_project_doc(dir: &Path, candidate_filenames: &[&str]) -> std::io::Result<Option<PathBuf>> {
+    for name in candidate_filenames {
+        let candidate = dir.join(name);
+        match std::fs::symlink_metadata(&candidate) {
+            Ok(md) => {
+                let ft = md.file_type();
+                // Allow regular files and symlinks; opening will later fail for dangling links.
+                if ft.is_file() || ft.is_symlink() {
+                    return Ok(Some(candidate));
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(None)
+}
+
 fn candidate_filenames<'a>(config: &'a Config) -> Vec<&'a str> {
     let mut names: Vec<&'a str> =
         Vec::with_capacity(2 + config.project_doc_fallback_filenames.len());
@@ -496,6 +545,30 @@ mod tests {
         assert_eq!(res, "root doc\n\ncrate doc");
     }
 
+    /// A scoped session finds the docs between cwd and each scope directory,
+    /// visiting shared parents once and skipping cwd itself.
+    #[tokio::test]
+    async fn discovers_docs_down_to_scope_dirs() {
+        let repo = tempfile::tempdir().expect("tempdir");
+        fs::write(repo.path().join("AGENTS.md"), "root doc").unwrap();
+        let packages = repo.path().join("packages");
+        for dir in ["api", "web"] {
+            fs::create_dir_all(packages.join(dir)).unwrap();
+        }
+        fs::write(packages.join("AGENTS.md"), "packages doc").unwrap();
+        fs::write(packages.join("web/AGENTS.md"), "web doc").unwrap();
+
+        let cfg = make_config(&repo, 4096, None).await;
+        let scope = ["packages/web", "packages/api"]
+            .map(|dir| AbsolutePathBuf::resolve_path_against_base(dir, repo.path()).unwrap());
+
+        let paths = discover_scope_doc_paths(&cfg, repo.path(), &scope).unwrap();
+        assert_eq!(
+            paths,
+            vec![packages.join("AGENTS.md"), packages.join("web/AGENTS.md")]
+        );
+    }
+
     /// AGENTS.override.md is preferred over AGENTS.md when both are present.
     #[tokio::test]
     async fn agents_local_md_preferred() {
@@ -0,0 +1,340 @@
+//! Durable project memory in `.codex/memory/` (the `project_memory` feature).
+//!
+//! Each entry is a markdown file named after the entry, holding one project
+//! fact such as a build quirk, a decision or a naming convention. The model
+//! reads and saves entries with the `memory` tool; every save shows up in the
+//! transcript as a file change. New sessions start with the most relevant
+//! entries in their initial context: `high` importance first, then the most
+//! recently updated, within a token budget. `low` entries are only read on
+//! request.
+//!
+//! An entry may start with front matter that sets its importance:
+//!
+//! ```markdown
+//! ---
+//! importance: high
+//! ---
+//! Integration tests need `DATABASE_URL`; run `just db` first.
+//! ```
+
+use std::cmp::Reverse;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::git_info::get_git_repo_root;
+use crate::session_prefix::PROJECT_MEMORY_CLOSE_TAG;
+use crate::session_prefix::PROJECT_MEMORY_OPEN_TAG;
+use crate::truncate::approx_token_count;
+
+const MEMORY_DIR: &str = ".codex/memory";
+const MAX_NAME_LEN: usize = 64;
+/// Token budget for the entries loaded into a new session.
+const CONTEXT_TOKEN_BUDGET: usize = 2_000;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Importance {
+    High,
+    #[default]
+    Normal,
+    Low,
+}
+
+impl Importance {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Importance::High => "high",
+            Importance::Normal => "normal",
+            Importance::Low => "low",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MemoryEntry {
+    pub(crate) name: String,
+    pub(crate) importance: Importance,
+    pub(crate) content: String,
+    pub(crate) modified: SystemTime,
+}
+
+impl MemoryEntry {
+    /// File contents for the entry; `normal` entries have no front matter.
+    pub(crate) fn serialize(importance: Importance, content: &str) -> String {
+        let content = content.trim();
+        match importance {
+            Importance::Normal => format!("{content}\n"),
+            importance => format!("---\nimportance: {}\n---\n{content}\n", importance.as_str()),
+        }
+    }
+
+    fn parse(name: String, text: &str, modified: SystemTime) -> Self {
+        let mut importance = Importance::Normal;
+        let mut content = text;
+        if let Some(rest) = text.strip_prefix("---\n")
+            && let Some((front_matter, body)) = rest.split_once("\n---\n")
+        {
+            for line in front_matter.lines() {
+                if let Some(value) = line.strip_prefix("importance:") {
+                    importance = match value.trim() {
+                        "high" => Importance::High,
+                        "low" => Importance::Low,
+                        _ => Importance::Normal,
+                    };
+                }
+            }
+            content = body;
+        }
+        Self {
+            name,
+            importance,
+            content: content.trim().to_string(),
+            modified,
+        }
+    }
+}
+
+/// The memory store of the project containing a working directory.
+pub(crate) struct ProjectMemory {
+    dir: PathBuf,
+}
+
+impl ProjectMemory {
+    /// The store at the root of the git repository containing `cwd`, or in
+    /// `cwd` outside a repository.
+    pub(crate) fn for_cwd(cwd: &Path) -> Self {
+        let root = get_git_repo_root(cwd).unwrap_or_else(|| cwd.to_path_buf());
+        Self {
+            dir: root.join(MEMORY_DIR),
+        }
+    }
+
+    /// The file for entry `name`. Names are limited to letters, digits, `-`,
+    /// `_` and `.` so an entry cannot point outside the store.
+    pub(crate) fn path_for(&self, name: &str) -> Result<PathBuf, String> {
+        let valid = !name.is_empty()
+            && name.len() <= MAX_NAME_LEN
+            && !name.starts_with('.')
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if !valid {
+            return Err(format!(
+                "invalid memory name `{name}`: use up to {MAX_NAME_LEN} letters, digits, `-`, `_` or `.`, such as `build-quirks`"
+            ));
+        }
+        Ok(self.dir.join(format!("{name}.md")))
+    }
+
+    pub(crate) async fn read(&self, name: &str) -> Result<Option<MemoryEntry>, String> {
+        let path = self.path_for(name)?;
+        let text = match tokio::fs::read_to_string(&path).await {
+            Ok(text) => text,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(format!("failed to read {}: {err}", path.display())),
+        };
+        let modified = modified_time(&path).await;
+        Ok(Some(MemoryEntry::parse(name.to_string(), &text, modified)))
+    }
+
+    /// Every entry in the store, by name.
+    pub(crate) async fn entries(&self) -> Vec<MemoryEntry> {
+        let mut entries = Vec::new();
+        let Ok(mut dir) = tokio::fs::read_dir(&self.dir).await else {
+            return entries;
+        };
+        while let Ok(Some(entry)) = dir.next_entry().await {
+            let path = entry.path();
+            let Some(name) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(".md"))
+            else {
+                continue;
+            };
+            if self.path_for(name).is_err() {
+                continue;
+            }
+            if let Ok(text) = tokio::fs::read_to_string(&path).await {
+                let modified = modified_time(&path).await;
+                entries.push(MemoryEntry::parse(name.to_string(), &text, modified));
+            }
+        }
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        entries
+    }
+}
+
+async fn modified_time(path: &Path) -> SystemTime {
+    tokio::fs::metadata(path)
+        .await
+        .and_then(|metadata| metadata.modified())
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+/// The initial-context message with the project's most relevant entries, or
+/// `None` when the store is empty.
+pub(crate) async fn project_memory_item(cwd: &Path) -> Option<ResponseItem> {
+    let entries = ProjectMemory::for_cwd(cwd).entries().await;
+    let text = serialize_for_context(entries, CONTEXT_TOKEN_BUDGET)?;
+    Some(ResponseItem::Message {
+        id: None,
+        role: "user".to_string(),
+        content: vec![ContentItem::InputText { text }],
+        end_turn: None,
+        phase: None,
+    })
+}
+
+/// Serializes the entries that fit in `budget` tokens, by importance and
+/// then recency, and names the ones left out:
+///
+/// ```xml
+/// <project_memory>
+///   <note>...</note>
+///   <entry name="build-quirks">Integration tests need `DATABASE_URL`.</entry>
+///   <not_loaded>naming, release-process</not_loaded>
+/// </project_memory>
+/// ```
+fn serialize_for_context(mut entries: Vec<MemoryEntry>, budget: usize) -> Option<String> {
+    if entries.is_empty() {
+        return None;
+    }
+    entries.sort_by_key(|entry| (entry.importance, Reverse(entry.modified)));
+    let mut lines = vec![
+        PROJECT_MEMORY_OPEN_TAG.to_string(),
+        "  <note>Facts about this project saved in earlier sessions (.codex/memory/). Trust them unless the code says otherwise. Use the memory tool to read the entries not loaded here, and to save new facts worth remembering.</note>".to_string(),
+    ];
+    let mut used = 0;
+    let mut not_loaded = Vec::new();
+    for entry in entries {
+        let line = format!("  <entry name=\"{}\">{}</entry>", entry.name, entry.content);
+        let tokens = approx_token_count(&line);
+        if entry.importance == Importance::Low || used + tokens > budget {
+            not_loaded.push(entry.name);
+            continue;
+        }
+        used += tokens;
+        lines.push(line);
+    }
+    if !not_loaded.is_empty() {
+        not_loaded.sort();
+        lines.push(format!(
+            "  <not_loaded>{}</not_loaded>",
+            not_loaded.join(", ")
+        ));
+    }
+    lines.push(PROJECT_MEMORY_CLOSE_TAG.to_string());
+    Some(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn entry(name: &str, importance: Importance, content: &str, age_secs: u64) -> MemoryEntry {
+        MemoryEntry {
+            name: name.to_string(),
+            importance,
+            content: content.to_string(),
+            modified: SystemTime::UNIX_EPOCH + Duration::from_secs(1_000 - age_secs),
+        }
+    }
+
+    #[test]
+    fn front_matter_round_trips() {
+        let text = MemoryEntry::serialize(Importance::High, " Run `just db` first.\n");
+        assert_eq!(text, "---\nimportance: high\n---\nRun `just db` first.\n");
+        let parsed = MemoryEntry::parse("db".to_string(), &text, SystemTime::UNIX_EPOCH);
+        assert_eq!(parsed.importance, Importance::High);
+        assert_eq!(parsed.content, "Run `just db` first.");
+
+        let text = MemoryEntry::serialize(Importance::Normal, "Plain fact.");
+        assert_eq!(text, "Plain fact.\n");
+        let parsed = MemoryEntry::parse("fact".to_string(), &text, SystemTime::UNIX_EPOCH);
+        assert_eq!(parsed.importance, Importance::Normal);
+    }
+
+    #[test]
+    fn rejects_names_that_leave_the_store() {
+        let memory = ProjectMemory {
+            dir: PathBuf::from("/repo/.codex/memory"),
+        };
+        assert_eq!(
+            memory.path_for("build-quirks"),
+            Ok(PathBuf::from("/repo/.codex/memory/build-quirks.md"))
+        );
+        assert!(memory.path_for("../secrets").is_err());
+        assert!(memory.path_for(".hidden").is_err());
+        assert!(memory.path_for("").is_err());
+    }
+
+    #[test]
+    fn loads_important_then_recent_entries_within_budget() {
+        let entries = vec![
+            entry("old", Importance::Normal, "An old fact.", 500),
+            entry("archived", Importance::Low, "Rarely needed.", 0),
+            entry("recent", Importance::Normal, "A recent fact.", 10),
+            entry("build", Importance::High, "Run `just db` first.", 900),
+        ];
+        let budget = approx_token_count("  <entry name=\"build\">Run `just db` first.</entry>")
+            + approx_token_count("  <entry name=\"recent\">A recent fact.</entry>");
+        let text = serialize_for_context(entries, budget).expect("entries");
+        let body: Vec<&str> = text
+            .lines()
+            .filter(|line| !line.contains("<note>"))
+            .collect();
+        assert_eq!(
+            body,
+            vec![
+                "<project_memory>",
+                "  <entry name=\"build\">Run `just db` first.</entry>",
+                "  <entry name=\"recent\">A recent fact.</entry>",
+                "  <not_loaded>archived, old</not_loaded>",
+                "</project_memory>",
+            ]
+        );
+        assert_eq!(serialize_for_context(Vec::new(), budget), None);
+    }
+
+    #[tokio::test]
+    async fn lists_entries_in_the_store() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let memory = ProjectMemory::for_cwd(dir.path());
+        let path = memory.path_for("naming").expect("valid name");
+        tokio::fs::create_dir_all(path.parent().expect("parent"))
+            .await
+            .expect("mkdir");
+        tokio::fs::write(&path, "Handlers end in `Handler`.\n")
+            .await
+            .expect("write");
+        tokio::fs::write(path.with_file_name("notes.txt"), "not an entry")
+            .await
+            .expect("write");
+
+        let names: Vec<String> = memory
+            .entries()
+            .await
+            .into_iter()
+            .map(|entry| entry.name)
+            .collect();
+        assert_eq!(names, vec!["naming".to_string()]);
+        assert_eq!(
+            memory
+                .read("naming")
+                .await
+                .expect("read")
+                .map(|entry| entry.content),
+            Some("Handlers end in `Handler`.".to_string())
+        );
+        assert_eq!(memory.read("missing").await, Ok(None));
+    }
+}
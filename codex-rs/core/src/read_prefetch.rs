@@ -0,0 +1,392 @@
+//! Speculative prefetch for `read_file` (the `read_prefetch` feature).
+//!
+//! After the model reads a file, the files it is likely to ask for next are
+//! read in the background into a small in-memory cache: the local modules
+//! the file imports first, then its siblings with the same extension. A
+//! cached copy is only served while the file's size and modification time
+//! still match, so edits made since the prefetch are never hidden.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use codex_protocol::protocol::ReadPrefetchStatsEvent;
+
+/// Most files prefetched after a single read.
+const MAX_FILES_PER_READ: usize = 8;
+/// Larger files are left to be read on demand.
+const MAX_FILE_BYTES: u64 = 256 * 1024;
+/// Bytes the cache holds before evicting the oldest entries.
+const MAX_CACHE_BYTES: usize = 8 * 1024 * 1024;
+/// Leading bytes checked for NUL when skipping binary files.
+const BINARY_SNIFF_LEN: usize = 8 * 1024;
+
+const SCRIPT_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "mjs", "cjs"];
+const C_EXTENSIONS: &[&str] = &["c", "h", "cc", "cpp", "hh", "hpp"];
+
+struct CachedFile {
+    len: u64,
+    modified: Option<SystemTime>,
+    contents: Arc<[u8]>,
+}
+
+#[derive(Default)]
+struct CacheState {
+    files: HashMap<PathBuf, CachedFile>,
+    /// Cached paths, oldest first.
+    order: VecDeque<PathBuf>,
+    bytes: usize,
+    /// Counters since the last `take_stats`.
+    hits: u64,
+    misses: u64,
+    prefetched_files: u64,
+}
+
+impl CacheState {
+    fn insert(&mut self, path: PathBuf, file: CachedFile) {
+        self.remove(&path);
+        self.bytes += file.contents.len();
+        self.order.push_back(path.clone());
+        self.files.insert(path, file);
+        while self.bytes > MAX_CACHE_BYTES {
+            let Some(oldest) = self.order.front().cloned() else {
+                break;
+            };
+            self.remove(&oldest);
+        }
+    }
+
+    fn remove(&mut self, path: &Path) {
+        if let Some(file) = self.files.remove(path) {
+            self.bytes -= file.contents.len();
+            self.order.retain(|cached| cached != path);
+        }
+    }
+}
+
+/// Session-wide cache of prefetched file contents. Clones share the cache.
+#[derive(Clone, Default)]
+pub(crate) struct ReadPrefetchCache {
+    state: Arc<Mutex<CacheState>>,
+}
+
+impl ReadPrefetchCache {
+    /// The cached contents of `path`, if they still match the file on disk.
+    /// Counts a hit or a miss either way.
+    pub(crate) async fn get(&self, path: &Path) -> Option<Arc<[u8]>> {
+        let metadata = tokio::fs::metadata(path).await.ok();
+        let mut state = self.state.lock().ok()?;
+        let fresh = match (state.files.get(path), &metadata) {
+            (Some(cached), Some(metadata)) => (cached.len == metadata.len()
+                && cached.modified == metadata.modified().ok())
+            .then(|| Arc::clone(&cached.contents)),
+            _ => None,
+        };
+        if fresh.is_some() {
+            state.hits += 1;
+        } else {
+            state.remove(path);
+            state.misses += 1;
+        }
+        fresh
+    }
+
+    /// Prefetches the files related to `path` on a background task.
+    pub(crate) fn spawn_prefetch(&self, path: PathBuf) {
+        let cache = self.clone();
+        tokio::spawn(async move { cache.prefetch_related(&path).await });
+    }
+
+    /// Cache activity since the last call, or `None` when there was none.
+    pub(crate) fn take_stats(&self) -> Option<ReadPrefetchStatsEvent> {
+        let mut state = self.state.lock().ok()?;
+        if state.hits == 0 && state.misses == 0 && state.prefetched_files == 0 {
+            return None;
+        }
+        let stats = ReadPrefetchStatsEvent {
+            hits: state.hits,
+            misses: state.misses,
+            prefetched_files: state.prefetched_files,
+            cached_files: u64::try_from(state.files.len()).unwrap_or(u64::MAX),
+            cached_bytes: u64::try_from(state.bytes).unwrap_or(u64::MAX),
+        };
+        state.hits = 0;
+        state.misses = 0;
+        state.prefetched_files = 0;
+        Some(stats)
+    }
+
+    async fn prefetch_related(&self, path: &Path) {
+        let Some(dir) = path.parent() else {
+            return;
+        };
+        let Some((_, contents)) = read_text_file(path).await else {
+            return;
+        };
+        let text = String::from_utf8_lossy(&contents);
+        let mut candidates = import_candidates(path, &text);
+        candidates.extend(sibling_files(path, dir).await);
+
+        let mut prefetched = 0;
+        for candidate in candidates {
+            if prefetched == MAX_FILES_PER_READ {
+                break;
+            }
+            if candidate == path || self.contains(&candidate) {
+                continue;
+            }
+            let Some((metadata, contents)) = read_text_file(&candidate).await else {
+                continue;
+            };
+            let file = CachedFile {
+                len: metadata.len(),
+                modified: metadata.modified().ok(),
+                contents: contents.into(),
+            };
+            if let Ok(mut state) = self.state.lock() {
+                state.insert(candidate, file);
+                state.prefetched_files += 1;
+            }
+            prefetched += 1;
+        }
+    }
+
+    fn contains(&self, path: &Path) -> bool {
+        self.state
+            .lock()
+            .is_ok_and(|state| state.files.contains_key(path))
+    }
+}
+
+/// The contents of `path` when it is a regular text file small enough to
+/// prefetch.
+async fn read_text_file(path: &Path) -> Option<(std::fs::Metadata, Vec<u8>)> {
+    let metadata = tokio::fs::metadata(path).await.ok()?;
+    if !metadata.is_file() || metadata.len() > MAX_FILE_BYTES {
+        return None;
+    }
+    let contents = tokio::fs::read(path).await.ok()?;
+    let head = &contents[..contents.len().min(BINARY_SNIFF_LEN)];
+    (!head.contains(&0)).then_some((metadata, contents))
+}
+
+/// Files in `dir` with the same extension as `path`, by name.
+async fn sibling_files(path: &Path, dir: &Path) -> Vec<PathBuf> {
+    let Some(extension) = path.extension() else {
+        return Vec::new();
+    };
+    let mut siblings = Vec::new();
+    let Ok(mut entries) = tokio::fs::read_dir(dir).await else {
+        return siblings;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let sibling = entry.path();
+        if sibling.extension() == Some(extension) && sibling != path {
+            siblings.push(sibling);
+        }
+    }
+    siblings.sort();
+    siblings
+}
+
+/// Paths the local imports in `text` may resolve to, in the order they
+/// appear. Each import can yield several candidates (`foo.rs` and
+/// `foo/mod.rs`); the ones that do not exist are skipped when prefetching.
+fn import_candidates(path: &Path, text: &str) -> Vec<PathBuf> {
+    let Some(dir) = path.parent() else {
+        return Vec::new();
+    };
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default();
+    let mut candidates = Vec::new();
+    for line in text.lines().map(str::trim) {
+        match extension {
+            "rs" => {
+                if let Some(name) = rust_mod_declaration(line) {
+                    // `mod foo;` in `lib.rs`, `main.rs` or `mod.rs` lives next
+                    // to it; anywhere else it lives in a directory named
+                    // after the file.
+                    let stem = path.file_stem().and_then(|stem| stem.to_str());
+                    let base = match stem {
+                        Some("lib" | "main" | "mod") | None => dir.to_path_buf(),
+                        Some(stem) => dir.join(stem),
+                    };
+                    candidates.push(base.join(format!("{name}.rs")));
+                    candidates.push(base.join(name).join("mod.rs"));
+                }
+            }
+            "py" => {
+                for module in python_relative_imports(line) {
+                    candidates.push(dir.join(format!("{module}.py")));
+                    candidates.push(dir.join(module).join("__init__.py"));
+                }
+            }
+            extension if SCRIPT_EXTENSIONS.contains(&extension) => {
+                if !(line.starts_with("import")
+                    || line.contains("from ")
+                    || line.contains("require("))
+                {
+                    continue;
+                }
+                for specifier in quoted_strings(line) {
+                    if !(specifier.starts_with("./") || specifier.starts_with("../")) {
+                        continue;
+                    }
+                    let base = dir.join(specifier);
+                    candidates.push(base.clone());
+                    for extension in SCRIPT_EXTENSIONS {
+                        candidates.push(base.with_extension(extension));
+                        candidates.push(base.join(format!("index.{extension}")));
+                    }
+                }
+            }
+            extension if C_EXTENSIONS.contains(&extension) => {
+                if let Some(rest) = line.strip_prefix("#include")
+                    && let Some(header) = quoted_strings(rest).into_iter().next()
+                {
+                    candidates.push(dir.join(header));
+                }
+            }
+            _ => return candidates,
+        }
+    }
+    candidates
+}
+
+/// `foo` for `mod foo;`, `pub mod foo;` or `pub(crate) mod foo;`.
+fn rust_mod_declaration(line: &str) -> Option<&str> {
+    let rest = line
+        .strip_prefix("pub(crate) ")
+        .or_else(|| line.strip_prefix("pub(super) "))
+        .or_else(|| line.strip_prefix("pub "))
+        .unwrap_or(line);
+    let name = rest.strip_prefix("mod ")?.strip_suffix(';')?.trim();
+    name.chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        .then_some(name)
+}
+
+/// Modules imported relative to the current package: `foo` and `bar` for
+/// `from .foo import x` and `from . import bar`.
+fn python_relative_imports(line: &str) -> Vec<&str> {
+    let Some(rest) = line.strip_prefix("from .") else {
+        return Vec::new();
+    };
+    match rest.split_once(" import ") {
+        Some(("", names)) => names
+            .split(',')
+            .filter_map(|name| {
+                name.trim()
+                    .trim_matches(['(', ')'])
+                    .split_whitespace()
+                    .next()
+            })
+            .collect(),
+        Some((module, _)) if !module.starts_with('.') => {
+            module.split('.').next().into_iter().collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn quoted_strings(line: &str) -> Vec<&str> {
+    let mut strings = Vec::new();
+    let mut rest = line;
+    while let Some(start) = rest.find(['"', '\'']) {
+        let quote = rest[start..].chars().next().unwrap_or('"');
+        let after = &rest[start + 1..];
+        let Some(end) = after.find(quote) else {
+            break;
+        };
+        strings.push(&after[..end]);
+        rest = &after[end + 1..];
+    }
+    strings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn finds_local_imports() {
+        let rust = "mod config;\npub(crate) mod tools;\nuse std::fmt;\n";
+        assert_eq!(
+            import_candidates(Path::new("/repo/src/lib.rs"), rust),
+            vec![
+                PathBuf::from("/repo/src/config.rs"),
+                PathBuf::from("/repo/src/config/mod.rs"),
+                PathBuf::from("/repo/src/tools.rs"),
+                PathBuf::from("/repo/src/tools/mod.rs"),
+            ]
+        );
+        assert_eq!(
+            import_candidates(Path::new("/repo/src/tools.rs"), "mod spec;"),
+            vec![
+                PathBuf::from("/repo/src/tools/spec.rs"),
+                PathBuf::from("/repo/src/tools/spec/mod.rs"),
+            ]
+        );
+
+        let python = "import os\nfrom .models import User\nfrom . import views, urls\n";
+        assert_eq!(
+            import_candidates(Path::new("/repo/app/admin.py"), python),
+            vec![
+                PathBuf::from("/repo/app/models.py"),
+                PathBuf::from("/repo/app/models/__init__.py"),
+                PathBuf::from("/repo/app/views.py"),
+                PathBuf::from("/repo/app/views/__init__.py"),
+                PathBuf::from("/repo/app/urls.py"),
+                PathBuf::from("/repo/app/urls/__init__.py"),
+            ]
+        );
+
+        let script = "import React from 'react';\nimport { api } from \"../lib/api\";\n";
+        let candidates = import_candidates(Path::new("/repo/web/src/App.tsx"), script);
+        assert!(candidates.contains(&PathBuf::from("/repo/web/src/../lib/api.ts")));
+        assert!(!candidates.iter().any(|path| path.ends_with("react")));
+    }
+
+    #[tokio::test]
+    async fn serves_prefetched_files_until_they_change() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let lib = dir.path().join("lib.rs");
+        let config = dir.path().join("config.rs");
+        let tools = dir.path().join("tools.rs");
+        std::fs::write(&lib, "mod config;\n").expect("write");
+        std::fs::write(&config, "pub struct Config;\n").expect("write");
+        std::fs::write(&tools, "pub fn tools() {}\n").expect("write");
+        std::fs::write(dir.path().join("notes.md"), "not rust").expect("write");
+
+        let cache = ReadPrefetchCache::default();
+        cache.prefetch_related(&lib).await;
+        assert_eq!(
+            cache.get(&config).await.as_deref(),
+            Some(&b"pub struct Config;\n"[..])
+        );
+        assert!(cache.get(&tools).await.is_some());
+        assert!(cache.get(&dir.path().join("notes.md")).await.is_none());
+
+        std::fs::write(&config, "pub struct Config { changed: bool }\n").expect("write");
+        assert_eq!(cache.get(&config).await, None);
+
+        assert_eq!(
+            cache.take_stats(),
+            Some(ReadPrefetchStatsEvent {
+                hits: 2,
+                misses: 2,
+                prefetched_files: 2,
+                cached_files: 1,
+                cached_bytes: 18,
+            })
+        );
+        assert_eq!(cache.take_stats(), None);
+    }
+}
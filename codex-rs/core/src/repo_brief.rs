@@ -0,0 +1,367 @@
+//! Compact repository brief injected into the initial context.
+//!
+//! Saves the model a round of exploratory shell calls at the start of every
+//! session by summarizing the branch, working tree state, recent history,
+//! top-level layout, and build system of the repository containing `cwd`.
+//! Paths excluded by `.gitignore` or `.codexignore` are left out.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_file_search::IgnoreRules;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_utils_absolute_path::AbsolutePathBuf;
+
+use crate::git_info::current_branch_name;
+use crate::git_info::dirty_files;
+use crate::git_info::get_git_repo_root;
+use crate::git_info::recent_commits;
+use crate::scope::in_scope;
+use crate::session_prefix::REPO_CONTEXT_CLOSE_TAG;
+use crate::session_prefix::REPO_CONTEXT_OPEN_TAG;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::truncate_text;
+
+const MAX_DIRTY_FILES: usize = 20;
+const MAX_RECENT_COMMITS: usize = 8;
+const MAX_LAYOUT_ENTRIES: usize = 40;
+/// Upper bound on the serialized brief so very wide repositories cannot
+/// crowd out the rest of the initial context.
+const MAX_BRIEF_BYTES: usize = 4 * 1024;
+
+/// Marker files mapped to the build system they indicate, in display order.
+const BUILD_SYSTEM_MARKERS: &[(&str, &str)] = &[
+    ("Cargo.toml", "cargo"),
+    ("package.json", "npm"),
+    ("pnpm-workspace.yaml", "pnpm"),
+    ("pyproject.toml", "python (pyproject)"),
+    ("setup.py", "python (setuptools)"),
+    ("go.mod", "go"),
+    ("pom.xml", "maven"),
+    ("build.gradle", "gradle"),
+    ("build.gradle.kts", "gradle"),
+    ("CMakeLists.txt", "cmake"),
+    ("MODULE.bazel", "bazel"),
+    ("WORKSPACE", "bazel"),
+    ("Makefile", "make"),
+    ("justfile", "just"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RepoBrief {
+    root: PathBuf,
+    branch: Option<String>,
+    dirty_files: Vec<String>,
+    recent_commits: Vec<String>,
+    layout: Vec<String>,
+    build_systems: Vec<&'static str>,
+}
+
+impl RepoBrief {
+    /// Gathers the brief for the git repository containing `cwd`, or `None`
+    /// when `cwd` is not inside a repository. In a scoped session, the dirty
+    /// files and layout only cover the `scope` directories.
+    pub(crate) async fn collect(cwd: &Path, scope: &[AbsolutePathBuf]) -> Option<Self> {
+        let root = get_git_repo_root(cwd)?;
+        let (branch, dirty_files, commits) = tokio::join!(
+            current_branch_name(&root),
+            dirty_files(&root),
+            recent_commits(&root, MAX_RECENT_COMMITS),
+        );
+        let mut rules = IgnoreRules::new(&root, true);
+        let (layout, build_systems) = if scope.is_empty() {
+            read_layout(&root, &mut rules).await
+        } else {
+            read_scoped_layout(&root, scope, &mut rules).await
+        };
+        Some(Self {
+            dirty_files: dirty_files
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|file| {
+                    let path = root.join(file);
+                    in_scope(scope, &path) && !rules.is_ignored(&path)
+                })
+                .collect(),
+            root,
+            branch,
+            recent_commits: commits.into_iter().map(|commit| commit.subject).collect(),
+            layout,
+            build_systems,
+        })
+    }
+
+    /// Serializes the brief to XML, in the same shape as
+    /// `<environment_context>`. Repository-controlled text (paths, branch
+    /// names, commit subjects) is escaped so it cannot close or forge tags:
+    ///
+    /// ```xml
+    /// <repo_context>
+    ///   <root>...</root>
+    ///   <branch>...</branch>
+    ///   <build_systems>cargo, make</build_systems>
+    ///   <dirty_files count="2">
+    ///     <file>...</file>
+    ///   </dirty_files>
+    ///   <recent_commits>
+    ///     <commit>...</commit>
+    ///   </recent_commits>
+    ///   <layout>
+    ///     <entry>...</entry>
+    ///   </layout>
+    /// </repo_context>
+    /// ```
+    pub(crate) fn serialize_to_xml(self) -> String {
+        let mut lines = vec![REPO_CONTEXT_OPEN_TAG.to_string()];
+        lines.push(format!(
+            "  <root>{}</root>",
+            escape_xml(&self.root.to_string_lossy())
+        ));
+        if let Some(branch) = self.branch {
+            lines.push(format!("  <branch>{}</branch>", escape_xml(&branch)));
+        }
+        if !self.build_systems.is_empty() {
+            lines.push(format!(
+                "  <build_systems>{}</build_systems>",
+                self.build_systems.join(", ")
+            ));
+        }
+
+        let dirty_count = self.dirty_files.len();
+        if dirty_count == 0 {
+            lines.push("  <dirty_files count=\"0\" />".to_string());
+        } else {
+            lines.push(format!("  <dirty_files count=\"{dirty_count}\">"));
+            for file in self.dirty_files.iter().take(MAX_DIRTY_FILES) {
+                lines.push(format!("    <file>{}</file>", escape_xml(file)));
+            }
+            if dirty_count > MAX_DIRTY_FILES {
+                lines.push(format!(
+                    "    <more>{}</more>",
+                    dirty_count - MAX_DIRTY_FILES
+                ));
+            }
+            lines.push("  </dirty_files>".to_string());
+        }
+
+        if !self.recent_commits.is_empty() {
+            lines.push("  <recent_commits>".to_string());
+            for subject in self.recent_commits {
+                lines.push(format!("    <commit>{}</commit>", escape_xml(&subject)));
+            }
+            lines.push("  </recent_commits>".to_string());
+        }
+
+        if !self.layout.is_empty() {
+            lines.push("  <layout>".to_string());
+            let layout_count = self.layout.len();
+            for entry in self.layout.iter().take(MAX_LAYOUT_ENTRIES) {
+                lines.push(format!("    <entry>{}</entry>", escape_xml(entry)));
+            }
+            if layout_count > MAX_LAYOUT_ENTRIES {
+                lines.push(format!(
+                    "    <more>{}</more>",
+                    layout_count - MAX_LAYOUT_ENTRIES
+                ));
+            }
+            lines.push("  </layout>".to_string());
+        }
+
+        let body = truncate_text(&lines.join("\n"), TruncationPolicy::Bytes(MAX_BRIEF_BYTES));
+        format!("{body}\n{REPO_CONTEXT_CLOSE_TAG}")
+    }
+}
+
+impl From<RepoBrief> for ResponseItem {
+    fn from(brief: RepoBrief) -> Self {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: brief.serialize_to_xml(),
+            }],
+            end_turn: None,
+            phase: None,
+        }
+    }
+}
+
+/// Lists the top-level entries of `root` (directories get a trailing `/`) that
+/// `rules` does not exclude, and detects build systems from well-known marker
+/// files.
+async fn read_layout(root: &Path, rules: &mut IgnoreRules) -> (Vec<String>, Vec<&'static str>) {
+    let mut names = Vec::new();
+    if let Ok(mut entries) = tokio::fs::read_dir(root).await {
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name == ".git" || rules.is_ignored(&entry.path()) {
+                continue;
+            }
+            let is_dir = entry
+                .file_type()
+                .await
+                .is_ok_and(|file_type| file_type.is_dir());
+            names.push((name, is_dir));
+        }
+    }
+    names.sort();
+
+    let build_systems = detect_build_systems(names.iter().map(|(name, _)| name.as_str()));
+    let layout = names
+        .into_iter()
+        .map(|(name, is_dir)| if is_dir { format!("{name}/") } else { name })
+        .collect();
+    (layout, build_systems)
+}
+
+/// Lists the entries of each scope directory, prefixed with its path from
+/// `root`, and detects build systems in the scope directories.
+async fn read_scoped_layout(
+    root: &Path,
+    scope: &[AbsolutePathBuf],
+    rules: &mut IgnoreRules,
+) -> (Vec<String>, Vec<&'static str>) {
+    let mut layout = Vec::new();
+    let mut build_systems: Vec<&'static str> = Vec::new();
+    for dir in scope {
+        let prefix = dir
+            .as_path()
+            .strip_prefix(root)
+            .unwrap_or(dir.as_path())
+            .to_string_lossy()
+            .into_owned();
+        let (entries, detected) = read_layout(dir.as_path(), rules).await;
+        layout.extend(entries.into_iter().map(|entry| format!("{prefix}/{entry}")));
+        for build_system in detected {
+            if !build_systems.contains(&build_system) {
+                build_systems.push(build_system);
+            }
+        }
+    }
+    (layout, build_systems)
+}
+
+/// Escapes the characters that would otherwise let `text` end the element it
+/// is embedded in or open a new one.
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn detect_build_systems<'a>(names: impl Iterator<Item = &'a str> + Clone) -> Vec<&'static str> {
+    let mut detected: Vec<&'static str> = Vec::new();
+    for (marker, build_system) in BUILD_SYSTEM_MARKERS {
+        if names.clone().any(|name| name == *marker) && !detected.contains(build_system) {
+            detected.push(build_system);
+        }
+    }
+    detected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn serializes_brief_with_caps() {
+        let brief = RepoBrief {
+            root: PathBuf::from("/repo"),
+            branch: Some("main".to_string()),
+            dirty_files: (0..MAX_DIRTY_FILES + 2)
+                .map(|index| format!("src/file{index}.rs"))
+                .collect(),
+            recent_commits: vec!["Fix parser".to_string()],
+            layout: vec!["Cargo.toml".to_string(), "src/".to_string()],
+            build_systems: vec!["cargo"],
+        };
+
+        let xml = brief.serialize_to_xml();
+        let mut expected = vec![
+            "<repo_context>".to_string(),
+            "  <root>/repo</root>".to_string(),
+            "  <branch>main</branch>".to_string(),
+            "  <build_systems>cargo</build_systems>".to_string(),
+            format!("  <dirty_files count=\"{}\">", MAX_DIRTY_FILES + 2),
+        ];
+        expected.extend(
+            (0..MAX_DIRTY_FILES).map(|index| format!("    <file>src/file{index}.rs</file>")),
+        );
+        expected.extend(
+            [
+                "    <more>2</more>",
+                "  </dirty_files>",
+                "  <recent_commits>",
+                "    <commit>Fix parser</commit>",
+                "  </recent_commits>",
+                "  <layout>",
+                "    <entry>Cargo.toml</entry>",
+                "    <entry>src/</entry>",
+                "  </layout>",
+                "</repo_context>",
+            ]
+            .map(str::to_string),
+        );
+        assert_eq!(xml, expected.join("\n"));
+    }
+
+    #[test]
+    fn escapes_repository_controlled_text() {
+        let brief = RepoBrief {
+            root: PathBuf::from("/repo"),
+            branch: Some("feat/<x>&y".to_string()),
+            dirty_files: vec!["</repo_context>.md".to_string()],
+            recent_commits: vec!["Use Vec<u8> & friends".to_string()],
+            layout: vec!["<system>/".to_string()],
+            build_systems: Vec::new(),
+        };
+
+        let xml = brief.serialize_to_xml();
+        let expected = [
+            "<repo_context>",
+            "  <root>/repo</root>",
+            "  <branch>feat/&lt;x&gt;&amp;y</branch>",
+            "  <dirty_files count=\"1\">",
+            "    <file>&lt;/repo_context&gt;.md</file>",
+            "  </dirty_files>",
+            "  <recent_commits>",
+            "    <commit>Use Vec&lt;u8&gt; &amp; friends</commit>",
+            "  </recent_commits>",
+            "  <layout>",
+            "    <entry>&lt;system&gt;/</entry>",
+            "  </layout>",
+            "</repo_context>",
+        ];
+        assert_eq!(xml, expected.join("\n"));
+    }
+
+    #[test]
+    fn detects_build_systems_once_in_marker_order() {
+        let names = ["Makefile", "build.gradle", "Cargo.toml", "build.gradle.kts"];
+        assert_eq!(
+            detect_build_systems(names.into_iter()),
+            vec!["cargo", "gradle", "make"]
+        );
+    }
+
+    #[tokio::test]
+    async fn layout_skips_ignored_entries() {
+        let root = tempfile::tempdir().expect("tempdir");
+        for dir in [".git", "src", "target", "transcripts"] {
+            std::fs::create_dir(root.path().join(dir)).expect("mkdir");
+        }
+        std::fs::write(root.path().join(".gitignore"), "target/\n").expect("write");
+        std::fs::write(root.path().join(".codexignore"), "transcripts/\n").expect("write");
+        std::fs::write(root.path().join("Cargo.toml"), "").expect("write");
+
+        let mut rules = IgnoreRules::new(root.path(), true);
+        let (layout, build_systems) = read_layout(root.path(), &mut rules).await;
+        assert_eq!(
+            layout,
+            vec![".codexignore", ".gitignore", "Cargo.toml", "src/"]
+        );
+        assert_eq!(build_systems, vec!["cargo"]);
+    }
+}
@@ -0,0 +1,257 @@
+//! Client-side pacing of model requests against the provider's short-window
+//! rate limits.
+//!
+//! Every response carries `x-ratelimit-*` headers describing how many requests
+//! and tokens are left in the current window. Those seed one token bucket per
+//! limit. Before a request is sent its cost is reserved from the buckets; a
+//! reservation that overdraws a bucket waits until it has refilled, and later
+//! reservations queue behind it. Pacers are shared per provider endpoint and
+//! model by every session in the process, so parallel sub-agents draw from one
+//! budget instead of each discovering the limit through a 429.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::LazyLock;
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_protocol::protocol::RequestBudgetSnapshot;
+use codex_protocol::protocol::RequestBudgetWindow;
+
+use crate::model_provider_info::ModelProviderInfo;
+
+/// Refill period assumed when a response does not say how long the window
+/// takes to reset (provider limits are per minute).
+const DEFAULT_WINDOW: Duration = Duration::from_secs(60);
+
+static PACERS: LazyLock<StdMutex<HashMap<String, Arc<RequestPacer>>>> =
+    LazyLock::new(|| StdMutex::new(HashMap::new()));
+
+/// Returns the pacer shared by every session talking to `model` on `provider`.
+pub(crate) fn pacer_for(provider: &ModelProviderInfo, model: &str) -> Arc<RequestPacer> {
+    let key = format!(
+        "{}\n{}\n{model}",
+        provider.name,
+        provider.base_url.as_deref().unwrap_or_default()
+    );
+    let mut pacers = PACERS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    Arc::clone(pacers.entry(key).or_default())
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct RequestPacer {
+    state: StdMutex<PacerState>,
+}
+
+#[derive(Debug, Default)]
+struct PacerState {
+    requests: Option<TokenBucket>,
+    tokens: Option<TokenBucket>,
+    /// Set from `retry-after` style hints; nothing is sent before it.
+    blocked_until: Option<Instant>,
+}
+
+impl RequestPacer {
+    /// Replace the modelled budget with what the provider just reported.
+    pub(crate) fn observe(&self, budget: &RequestBudgetSnapshot) {
+        self.observe_at(budget, Instant::now());
+    }
+
+    /// Hold every request for `delay`, e.g. after the provider asked to retry
+    /// later.
+    pub(crate) fn pause(&self, delay: Duration) {
+        let until = Instant::now() + delay;
+        let mut state = self.lock();
+        state.blocked_until = Some(
+            state
+                .blocked_until
+                .map_or(until, |current| current.max(until)),
+        );
+    }
+
+    /// Reserve one request estimated at `estimated_tokens` and return how long
+    /// to wait before sending it. Zero until a budget has been observed.
+    pub(crate) fn reserve(&self, estimated_tokens: u64) -> Duration {
+        self.reserve_at(estimated_tokens, Instant::now())
+    }
+
+    /// The modelled budget right now, if the provider has reported one.
+    pub(crate) fn snapshot(&self) -> Option<RequestBudgetSnapshot> {
+        let now = Instant::now();
+        let mut state = self.lock();
+        let requests = state.requests.as_mut().map(|bucket| bucket.window(now));
+        let tokens = state.tokens.as_mut().map(|bucket| bucket.window(now));
+        (requests.is_some() || tokens.is_some())
+            .then_some(RequestBudgetSnapshot { requests, tokens })
+    }
+
+    fn observe_at(&self, budget: &RequestBudgetSnapshot, now: Instant) {
+        let mut state = self.lock();
+        if let Some(window) = budget.requests.as_ref() {
+            state.requests = Some(TokenBucket::from_window(window, now));
+        }
+        if let Some(window) = budget.tokens.as_ref() {
+            state.tokens = Some(TokenBucket::from_window(window, now));
+        }
+    }
+
+    fn reserve_at(&self, estimated_tokens: u64, now: Instant) -> Duration {
+        let mut state = self.lock();
+        let blocked = state
+            .blocked_until
+            .map(|until| until.saturating_duration_since(now))
+            .unwrap_or_default();
+        let requests = state
+            .requests
+            .as_mut()
+            .map(|bucket| bucket.reserve(1.0, now))
+            .unwrap_or_default();
+        let tokens = state
+            .tokens
+            .as_mut()
+            .map(|bucket| bucket.reserve(estimated_tokens as f64, now))
+            .unwrap_or_default();
+        blocked.max(requests).max(tokens)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, PacerState> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// A bucket that refills continuously at the rate implied by the last report.
+/// Its level goes negative while reservations are queued.
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    level: f64,
+    refill_per_sec: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    fn from_window(window: &RequestBudgetWindow, now: Instant) -> Self {
+        let capacity = window.limit as f64;
+        let level = window.remaining.min(window.limit) as f64;
+        let missing = capacity - level;
+        let refill_per_sec = if missing > 0.0 && window.resets_in_ms > 0 {
+            missing / Duration::from_millis(window.resets_in_ms).as_secs_f64()
+        } else {
+            capacity / DEFAULT_WINDOW.as_secs_f64()
+        };
+        Self {
+            capacity,
+            level,
+            refill_per_sec,
+            updated_at: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.level = (self.level + elapsed * self.refill_per_sec).min(self.capacity);
+        self.updated_at = now;
+    }
+
+    /// Take `cost` (capped at the capacity so one large request cannot wait
+    /// forever) and return how long until the bucket is no longer overdrawn.
+    fn reserve(&mut self, cost: f64, now: Instant) -> Duration {
+        self.refill(now);
+        self.level -= cost.min(self.capacity);
+        if self.level >= 0.0 || self.refill_per_sec <= 0.0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f64(-self.level / self.refill_per_sec)
+    }
+
+    fn window(&mut self, now: Instant) -> RequestBudgetWindow {
+        self.refill(now);
+        let remaining = self.level.max(0.0);
+        let resets_in = if self.refill_per_sec > 0.0 {
+            (self.capacity - self.level) / self.refill_per_sec
+        } else {
+            0.0
+        };
+        RequestBudgetWindow {
+            limit: self.capacity as u64,
+            remaining: remaining as u64,
+            resets_in_ms: (resets_in * 1000.0).round() as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn budget(requests: (u64, u64, u64), tokens: (u64, u64, u64)) -> RequestBudgetSnapshot {
+        let window = |(limit, remaining, resets_in_ms)| RequestBudgetWindow {
+            limit,
+            remaining,
+            resets_in_ms,
+        };
+        RequestBudgetSnapshot {
+            requests: Some(window(requests)),
+            tokens: Some(window(tokens)),
+        }
+    }
+
+    #[test]
+    fn unobserved_pacer_never_waits() {
+        let pacer = RequestPacer::default();
+        assert_eq!(pacer.reserve_at(1_000_000, Instant::now()), Duration::ZERO);
+        assert_eq!(pacer.snapshot(), None);
+    }
+
+    #[test]
+    fn exhausted_requests_queue_behind_each_other() {
+        let pacer = RequestPacer::default();
+        let now = Instant::now();
+        // 60 RPM, one request left, the window is full again in 59s: one
+        // request per second.
+        pacer.observe_at(&budget((60, 1, 59_000), (100_000, 100_000, 0)), now);
+
+        assert_eq!(pacer.reserve_at(10, now), Duration::ZERO);
+        assert_eq!(pacer.reserve_at(10, now), Duration::from_secs(1));
+        assert_eq!(pacer.reserve_at(10, now), Duration::from_secs(2));
+        assert_eq!(
+            pacer.reserve_at(10, now + Duration::from_secs(2)),
+            Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    fn token_budget_paces_large_requests() {
+        let pacer = RequestPacer::default();
+        let now = Instant::now();
+        // 6k tokens missing, refilled over 6s: 1k tokens per second.
+        pacer.observe_at(&budget((500, 499, 120), (30_000, 24_000, 6_000)), now);
+
+        assert_eq!(pacer.reserve_at(20_000, now), Duration::ZERO);
+        assert_eq!(pacer.reserve_at(6_000, now), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn pause_blocks_until_the_hint_expires() {
+        let pacer = RequestPacer::default();
+        pacer.pause(Duration::from_secs(30));
+        let wait = pacer.reserve(0);
+        assert!(wait > Duration::from_secs(29), "wait was {wait:?}");
+    }
+
+    #[test]
+    fn sessions_on_the_same_model_share_a_pacer() {
+        let provider = crate::model_provider_info::ModelProviderInfo::create_openai_provider();
+        let first = pacer_for(&provider, "pacer-test-model");
+        let second = pacer_for(&provider, "pacer-test-model");
+        let other = pacer_for(&provider, "pacer-test-other-model");
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(!Arc::ptr_eq(&first, &other));
+    }
+}
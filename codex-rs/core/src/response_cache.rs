@@ -0,0 +1,540 @@
+//! Opt-in cache of model responses, so re-running an identical `codex exec`
+//! job replays the recorded conversation instead of calling the model.
+//!
+//! Entries live at `<dir>/<model>/<key>.json`, where `key` is the SHA-256 of
+//! the configured salt and the full Responses API request. The per-session
+//! `prompt_cache_key` is left out of the hash, and tool output details that
+//! differ between otherwise identical runs (wall time, unified exec chunk ids)
+//! are normalized first. Each entry holds the events of one completed
+//! response; responses that fail or disconnect are never written.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use codex_api::ResponsesApiRequest;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use futures::StreamExt;
+use regex_lite::Regex;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use sha2::Digest;
+use sha2::Sha256;
+use tokio::sync::mpsc;
+use tracing::debug;
+use tracing::warn;
+
+use crate::client_common::ResponseEvent;
+use crate::client_common::ResponseStream;
+use crate::config::types::ResponseCacheConfig;
+use crate::config::types::ResponseCacheMode;
+use crate::error::CodexErr;
+use crate::error::Result;
+
+/// Directory inside `~/.codex` used when no cache directory is configured.
+pub(crate) const RESPONSE_CACHE_DIR: &str = "response-cache";
+
+static VOLATILE_PATTERNS: LazyLock<Vec<(Regex, &'static str)>> = LazyLock::new(|| {
+    [
+        (r"Wall time: [0-9.]+ seconds", "Wall time: 0 seconds"),
+        (
+            r#""duration_seconds":\s*[0-9.eE+-]+"#,
+            r#""duration_seconds":0"#,
+        ),
+        (r"Chunk ID: [0-9A-Za-z]+", "Chunk ID: 0"),
+    ]
+    .into_iter()
+    .map(|(pattern, replacement)| {
+        (
+            Regex::new(pattern).expect("static regex should compile"),
+            replacement,
+        )
+    })
+    .collect()
+});
+
+#[derive(Debug)]
+pub(crate) struct ResponseCache {
+    config: ResponseCacheConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedResponse {
+    model: String,
+    events: Vec<CachedEvent>,
+}
+
+/// The replayable subset of [`ResponseEvent`]. Rate limit and budget updates
+/// describe the provider at recording time and are dropped.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum CachedEvent {
+    Created,
+    OutputItemAdded {
+        item: ResponseItem,
+    },
+    OutputItemDone {
+        item: ResponseItem,
+    },
+    ServerReasoningIncluded {
+        included: bool,
+    },
+    OutputTextDelta {
+        delta: String,
+    },
+    ReasoningSummaryDelta {
+        delta: String,
+        summary_index: i64,
+    },
+    ReasoningContentDelta {
+        delta: String,
+        content_index: i64,
+    },
+    ReasoningSummaryPartAdded {
+        summary_index: i64,
+    },
+    Completed {
+        response_id: String,
+        token_usage: Option<TokenUsage>,
+    },
+}
+
+impl CachedEvent {
+    fn from_event(event: &ResponseEvent) -> Option<Self> {
+        let cached = match event {
+            ResponseEvent::Created => Self::Created,
+            ResponseEvent::OutputItemAdded(item) => Self::OutputItemAdded { item: item.clone() },
+            ResponseEvent::OutputItemDone(item) => Self::OutputItemDone { item: item.clone() },
+            ResponseEvent::ServerReasoningIncluded(included) => Self::ServerReasoningIncluded {
+                included: *included,
+            },
+            ResponseEvent::OutputTextDelta(delta) => Self::OutputTextDelta {
+                delta: delta.clone(),
+            },
+            ResponseEvent::ReasoningSummaryDelta {
+                delta,
+                summary_index,
+            } => Self::ReasoningSummaryDelta {
+                delta: delta.clone(),
+                summary_index: *summary_index,
+            },
+            ResponseEvent::ReasoningContentDelta {
+                delta,
+                content_index,
+            } => Self::ReasoningContentDelta {
+                delta: delta.clone(),
+                content_index: *content_index,
+            },
+            ResponseEvent::ReasoningSummaryPartAdded { summary_index } => {
+                Self::ReasoningSummaryPartAdded {
+                    summary_index: *summary_index,
+                }
+            }
+            ResponseEvent::Completed {
+                response_id,
+                token_usage,
+                ..
+            } => Self::Completed {
+                response_id: response_id.clone(),
+                token_usage: token_usage.clone(),
+            },
+            ResponseEvent::RateLimits(_)
+            | ResponseEvent::RequestBudget(_)
+            | ResponseEvent::ModelsEtag(_) => return None,
+        };
+        Some(cached)
+    }
+
+    fn into_event(self) -> ResponseEvent {
+        match self {
+            Self::Created => ResponseEvent::Created,
+            Self::OutputItemAdded { item } => ResponseEvent::OutputItemAdded(item),
+            Self::OutputItemDone { item } => ResponseEvent::OutputItemDone(item),
+            Self::ServerReasoningIncluded { included } => {
+                ResponseEvent::ServerReasoningIncluded(included)
+            }
+            Self::OutputTextDelta { delta } => ResponseEvent::OutputTextDelta(delta),
+            Self::ReasoningSummaryDelta {
+                delta,
+                summary_index,
+            } => ResponseEvent::ReasoningSummaryDelta {
+                delta,
+                summary_index,
+            },
+            Self::ReasoningContentDelta {
+                delta,
+                content_index,
+            } => ResponseEvent::ReasoningContentDelta {
+                delta,
+                content_index,
+            },
+            Self::ReasoningSummaryPartAdded { summary_index } => {
+                ResponseEvent::ReasoningSummaryPartAdded { summary_index }
+            }
+            Self::Completed {
+                response_id,
+                token_usage,
+            } => ResponseEvent::Completed {
+                response_id,
+                token_usage,
+                // Nothing server-side backs a replayed response.
+                can_append: false,
+            },
+        }
+    }
+}
+
+impl ResponseCache {
+    pub(crate) fn new(config: ResponseCacheConfig) -> Self {
+        Self { config }
+    }
+
+    /// Path of the entry for `request`, whether or not it exists yet.
+    pub(crate) fn entry_path(&self, request: &ResponsesApiRequest) -> Result<PathBuf> {
+        let key = request_key(request, self.config.salt.as_deref())?;
+        Ok(self
+            .config
+            .dir
+            .join(model_dir_name(&request.model))
+            .join(format!("{key}.json")))
+    }
+
+    /// Replays the entry at `path`. Returns `None` when the request should go
+    /// to the model instead, and an error on a miss in `replay-only` mode.
+    pub(crate) async fn replay(&self, path: &Path) -> Result<Option<ResponseStream>> {
+        if self.config.mode == ResponseCacheMode::Refresh {
+            return Ok(None);
+        }
+        let cached = match tokio::fs::read(path).await {
+            Ok(bytes) => match serde_json::from_slice::<CachedResponse>(&bytes) {
+                Ok(cached) => Some(cached),
+                Err(err) => {
+                    warn!(
+                        "ignoring unreadable response cache entry {}: {err}",
+                        path.display()
+                    );
+                    None
+                }
+            },
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => return Err(err.into()),
+        };
+        let Some(cached) = cached else {
+            if self.config.mode == ResponseCacheMode::ReplayOnly {
+                return Err(CodexErr::Fatal(format!(
+                    "no cached model response at {} and the response cache is replay-only",
+                    path.display()
+                )));
+            }
+            return Ok(None);
+        };
+
+        debug!("replaying cached model response from {}", path.display());
+        let (tx_event, rx_event) = mpsc::channel(cached.events.len().max(1));
+        for event in cached.events {
+            // The channel was sized to hold every event.
+            let _ = tx_event.try_send(Ok(event.into_event()));
+        }
+        Ok(Some(ResponseStream { rx_event }))
+    }
+
+    /// Forwards `stream` unchanged and writes it to `path` once the response
+    /// completes. The entry is written before `Completed` is forwarded so a
+    /// process that exits at the end of the turn still leaves it on disk.
+    pub(crate) fn record(
+        &self,
+        path: PathBuf,
+        model: String,
+        stream: ResponseStream,
+    ) -> ResponseStream {
+        if self.config.mode == ResponseCacheMode::ReplayOnly {
+            return stream;
+        }
+        let (tx_event, rx_event) = mpsc::channel(1600);
+        tokio::spawn(async move {
+            let mut stream = stream;
+            let mut events = Vec::new();
+            while let Some(event) = stream.next().await {
+                if let Ok(event) = &event
+                    && let Some(cached) = CachedEvent::from_event(event)
+                {
+                    let completed = matches!(cached, CachedEvent::Completed { .. });
+                    events.push(cached);
+                    if completed {
+                        let response = CachedResponse {
+                            model: model.clone(),
+                            events: std::mem::take(&mut events),
+                        };
+                        if let Err(err) = write_entry(&path, &response).await {
+                            warn!(
+                                "failed to write response cache entry {}: {err}",
+                                path.display()
+                            );
+                        }
+                    }
+                }
+                if tx_event.send(event).await.is_err() {
+                    return;
+                }
+            }
+        });
+        ResponseStream { rx_event }
+    }
+}
+
+fn request_key(request: &ResponsesApiRequest, salt: Option<&str>) -> Result<String> {
+    let mut request = request.clone();
+    request.prompt_cache_key = None;
+    let mut value = serde_json::to_value(&request)?;
+    normalize_volatile_strings(&mut value);
+
+    let mut hasher = Sha256::new();
+    if let Some(salt) = salt {
+        hasher.update(salt.as_bytes());
+    }
+    hasher.update(b"\n");
+    hasher.update(serde_json::to_vec(&value)?);
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn normalize_volatile_strings(value: &mut Value) {
+    match value {
+        Value::String(text) => {
+            for (pattern, replacement) in VOLATILE_PATTERNS.iter() {
+                if pattern.is_match(text) {
+                    *text = pattern.replace_all(text, *replacement).into_owned();
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(normalize_volatile_strings),
+        Value::Object(map) => map.values_mut().for_each(normalize_volatile_strings),
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+}
+
+fn model_dir_name(model: &str) -> String {
+    model
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+async fn write_entry(path: &Path, response: &CachedResponse) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let tmp = path.with_extension(format!("json.tmp-{}", std::process::id()));
+    tokio::fs::write(&tmp, serde_json::to_vec(response)?).await?;
+    tokio::fs::rename(&tmp, path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::ContentItem;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn request(input: Vec<ResponseItem>, prompt_cache_key: &str) -> ResponsesApiRequest {
+        ResponsesApiRequest {
+            model: "gpt-test/mini".to_string(),
+            instructions: "be brief".to_string(),
+            input,
+            tools: Vec::new(),
+            tool_choice: "auto".to_string(),
+            parallel_tool_calls: false,
+            reasoning: None,
+            store: false,
+            stream: true,
+            include: Vec::new(),
+            prompt_cache_key: Some(prompt_cache_key.to_string()),
+            text: None,
+        }
+    }
+
+    fn message(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "assistant".to_string(),
+            content: vec![ContentItem::OutputText {
+                text: text.to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        }
+    }
+
+    fn tool_output(text: &str) -> ResponseItem {
+        ResponseItem::FunctionCallOutput {
+            call_id: "call-1".to_string(),
+            output: FunctionCallOutputPayload::from_text(text.to_string()),
+        }
+    }
+
+    fn cache(dir: &TempDir, mode: ResponseCacheMode) -> ResponseCache {
+        ResponseCache::new(ResponseCacheConfig {
+            dir: dir.path().to_path_buf(),
+            mode,
+            salt: None,
+        })
+    }
+
+    fn stream_of(events: Vec<ResponseEvent>) -> ResponseStream {
+        let (tx_event, rx_event) = mpsc::channel(events.len());
+        for event in events {
+            tx_event.try_send(Ok(event)).expect("send");
+        }
+        ResponseStream { rx_event }
+    }
+
+    async fn collect_items(stream: ResponseStream) -> Vec<ResponseItem> {
+        stream
+            .filter_map(|event| async move {
+                match event {
+                    Ok(ResponseEvent::OutputItemDone(item)) => Some(item),
+                    _ => None,
+                }
+            })
+            .collect()
+            .await
+    }
+
+    #[test]
+    fn key_ignores_prompt_cache_key_and_wall_time() {
+        let first = request(
+            vec![tool_output(
+                "Exit code: 0\nWall time: 0.1234 seconds\nOutput:\nok",
+            )],
+            "thread-a",
+        );
+        let second = request(
+            vec![tool_output(
+                "Exit code: 0\nWall time: 2.5 seconds\nOutput:\nok",
+            )],
+            "thread-b",
+        );
+        let different = request(
+            vec![tool_output(
+                "Exit code: 1\nWall time: 0.1 seconds\nOutput:\nfail",
+            )],
+            "thread-a",
+        );
+
+        assert_eq!(
+            request_key(&first, None).expect("key"),
+            request_key(&second, None).expect("key")
+        );
+        assert_ne!(
+            request_key(&first, None).expect("key"),
+            request_key(&different, None).expect("key")
+        );
+        assert_ne!(
+            request_key(&first, None).expect("key"),
+            request_key(&first, Some("v2")).expect("key")
+        );
+    }
+
+    #[tokio::test]
+    async fn recorded_response_replays_without_rate_limit_events() {
+        let dir = TempDir::new().expect("tempdir");
+        let cache = cache(&dir, ResponseCacheMode::ReadWrite);
+        let request = request(vec![message("hi")], "thread");
+        let path = cache.entry_path(&request).expect("path");
+        assert!(path.starts_with(dir.path().join("gpt-test_mini")));
+        assert!(cache.replay(&path).await.expect("replay").is_none());
+
+        let recorded = cache.record(
+            path.clone(),
+            request.model.clone(),
+            stream_of(vec![
+                ResponseEvent::Created,
+                ResponseEvent::OutputItemDone(message("hello")),
+                ResponseEvent::ModelsEtag("etag".to_string()),
+                ResponseEvent::Completed {
+                    response_id: "resp-1".to_string(),
+                    token_usage: None,
+                    can_append: true,
+                },
+            ]),
+        );
+        assert_eq!(collect_items(recorded).await, vec![message("hello")]);
+
+        let replayed = cache
+            .replay(&path)
+            .await
+            .expect("replay")
+            .expect("cache hit");
+        let events: Vec<_> = replayed.collect().await;
+        let events: Vec<_> = events
+            .into_iter()
+            .map(|event| CachedEvent::from_event(&event.expect("event")).expect("cacheable"))
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                CachedEvent::Created,
+                CachedEvent::OutputItemDone {
+                    item: message("hello")
+                },
+                CachedEvent::Completed {
+                    response_id: "resp-1".to_string(),
+                    token_usage: None,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn incomplete_response_is_not_recorded() {
+        let dir = TempDir::new().expect("tempdir");
+        let cache = cache(&dir, ResponseCacheMode::ReadWrite);
+        let request = request(vec![message("hi")], "thread");
+        let path = cache.entry_path(&request).expect("path");
+
+        let recorded = cache.record(
+            path.clone(),
+            request.model.clone(),
+            stream_of(vec![ResponseEvent::OutputItemDone(message("partial"))]),
+        );
+        assert_eq!(collect_items(recorded).await, vec![message("partial")]);
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn refresh_skips_hits_and_replay_only_fails_on_miss() {
+        let dir = TempDir::new().expect("tempdir");
+        let request = request(vec![message("hi")], "thread");
+        let path = cache(&dir, ResponseCacheMode::ReadWrite)
+            .entry_path(&request)
+            .expect("path");
+        write_entry(
+            &path,
+            &CachedResponse {
+                model: request.model.clone(),
+                events: vec![CachedEvent::Created],
+            },
+        )
+        .await
+        .expect("write");
+
+        let refresh = cache(&dir, ResponseCacheMode::Refresh);
+        assert!(refresh.replay(&path).await.expect("replay").is_none());
+
+        let replay_only = cache(&dir, ResponseCacheMode::ReplayOnly);
+        assert!(replay_only.replay(&path).await.expect("replay").is_some());
+        let missing = dir.path().join("missing.json");
+        assert!(matches!(
+            replay_only.replay(&missing).await,
+            Err(CodexErr::Fatal(_))
+        ));
+    }
+}
@@ -0,0 +1,179 @@
+//! Recovery from model requests that still fail after their stream retries
+//! (`[retry_budget]` in config.toml).
+//!
+//! Without a recovery the turn ends with the error. With `on_exhausted =
+//! "ask"` the TUI shows a `RetriesExhausted` prompt and the turn waits for
+//! the user's choice; every other client, `codex exec` included, aborts
+//! unless a different policy is configured, which is how scripted runs opt
+//! into retrying, switching to `fallback_model` or dropping context. A turn
+//! recovers at most `max_recoveries` times; the next failure ends it.
+
+use std::sync::Arc;
+
+use codex_protocol::models::ResponseItem;
+use codex_protocol::num_format::format_with_separators;
+use codex_protocol::protocol::SessionSource;
+use tracing::info;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::types::RetryExhaustedPolicy;
+use crate::context_manager::ContextManager;
+use crate::error::CodexErr;
+use crate::protocol::EventMsg;
+use crate::protocol::RetriesExhaustedEvent;
+use crate::protocol::RetryExhaustedDecision;
+use crate::protocol::WarningEvent;
+
+/// How a turn continues after its request ran out of retries.
+pub(crate) enum Recovery {
+    /// Send the request again, with this context for the rest of the turn.
+    Retry(Arc<TurnContext>),
+    /// End the turn with the error.
+    Abort,
+}
+
+/// Recoveries made so far in one turn.
+#[derive(Default)]
+pub(crate) struct RetryBudget {
+    recoveries: u32,
+}
+
+impl RetryBudget {
+    pub(crate) async fn recover(
+        &mut self,
+        sess: &Arc<Session>,
+        turn_context: &Arc<TurnContext>,
+        err: &CodexErr,
+    ) -> Recovery {
+        let config = &turn_context.config.retry_budget;
+        if self.recoveries >= config.max_recoveries {
+            return Recovery::Abort;
+        }
+        let decision = match config.on_exhausted {
+            RetryExhaustedPolicy::Ask if turn_context.session_source == SessionSource::Cli => {
+                let event = RetriesExhaustedEvent {
+                    turn_id: turn_context.sub_id.clone(),
+                    message: err.to_string(),
+                    retries: turn_context.provider.stream_max_retries(),
+                    fallback_model: config
+                        .fallback_model
+                        .clone()
+                        .filter(|model| *model != turn_context.model_info.slug),
+                    largest_context_item: describe_largest_tool_output(&sess.clone_history().await),
+                };
+                sess.request_retry_decision(turn_context, event)
+                    .await
+                    .unwrap_or(RetryExhaustedDecision::Abort)
+            }
+            RetryExhaustedPolicy::Ask | RetryExhaustedPolicy::Abort => {
+                RetryExhaustedDecision::Abort
+            }
+            RetryExhaustedPolicy::RetryNow => RetryExhaustedDecision::RetryNow,
+            RetryExhaustedPolicy::SwitchModel => match config.fallback_model.clone() {
+                Some(model) => RetryExhaustedDecision::SwitchModel { model },
+                None => RetryExhaustedDecision::Abort,
+            },
+            RetryExhaustedPolicy::DropLargestContextItem => {
+                RetryExhaustedDecision::DropLargestContextItem
+            }
+        };
+        info!(turn_id = %turn_context.sub_id, ?decision, "model request ran out of retries");
+
+        let (recovery, message) = match decision {
+            RetryExhaustedDecision::Abort => return Recovery::Abort,
+            RetryExhaustedDecision::RetryNow => (
+                Arc::clone(turn_context),
+                "Retrying the request.".to_string(),
+            ),
+            RetryExhaustedDecision::SwitchModel { model } => {
+                let next = turn_context
+                    .with_model(model.clone(), &sess.services.models_manager)
+                    .await;
+                (
+                    Arc::new(next),
+                    format!("Continuing this turn with {model}."),
+                )
+            }
+            RetryExhaustedDecision::DropLargestContextItem => {
+                let Some(description) = describe_largest_tool_output(&sess.clone_history().await)
+                else {
+                    return Recovery::Abort;
+                };
+                if !sess.drop_largest_tool_output().await {
+                    return Recovery::Abort;
+                }
+                (
+                    Arc::clone(turn_context),
+                    format!("Removed the {description} from the conversation; retrying."),
+                )
+            }
+        };
+        self.recoveries += 1;
+        sess.send_event(turn_context, EventMsg::Warning(WarningEvent { message }))
+            .await;
+        Recovery::Retry(recovery)
+    }
+}
+
+/// Names the tool output `drop_largest_context_item` would replace, e.g.
+/// "output of `shell` (~12,000 tokens)".
+fn describe_largest_tool_output(history: &ContextManager) -> Option<String> {
+    let (index, tokens) = history.largest_tool_output()?;
+    let items = history.raw_items();
+    let call_id = match items.get(index)? {
+        ResponseItem::FunctionCallOutput { call_id, .. }
+        | ResponseItem::CustomToolCallOutput { call_id, .. } => call_id,
+        _ => return None,
+    };
+    let tool = items.iter().find_map(|item| match item {
+        ResponseItem::FunctionCall {
+            name, call_id: id, ..
+        }
+        | ResponseItem::CustomToolCall {
+            name, call_id: id, ..
+        } if id == call_id => Some(name.as_str()),
+        ResponseItem::LocalShellCall {
+            call_id: Some(id), ..
+        } if id == call_id => Some("shell"),
+        _ => None,
+    });
+    let tokens = format_with_separators(tokens);
+    Some(match tool {
+        Some(tool) => format!("output of `{tool}` (~{tokens} tokens)"),
+        None => format!("tool output (~{tokens} tokens)"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::truncate::TruncationPolicy;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn describes_the_largest_tool_output_by_its_call() {
+        let items = [
+            ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "call-1".to_string(),
+                name: "apply_patch".to_string(),
+                input: "patch".to_string(),
+            },
+            ResponseItem::CustomToolCallOutput {
+                call_id: "call-1".to_string(),
+                output: "done ".repeat(400),
+            },
+        ];
+        let mut history = ContextManager::new();
+        history.record_items(items.iter(), TruncationPolicy::Tokens(10_000));
+
+        let description = describe_largest_tool_output(&history).expect("a tool output");
+        assert!(
+            description.starts_with("output of `apply_patch` (~"),
+            "{description}"
+        );
+        assert_eq!(describe_largest_tool_output(&ContextManager::new()), None);
+    }
+}
@@ -0,0 +1,445 @@
+//! zstd-compressed rollouts (`rollout-*.jsonl.zst`).
+//!
+//! A compressed rollout holds the same JSONL as a plain one. The writer keeps
+//! one zstd frame open for the life of the recorder and flushes it after every
+//! line, so everything written so far can be decoded even if the process dies
+//! before the frame is finished. Readers treat a truncated final frame as the
+//! end of the file. Resuming appends a new frame; concatenated frames decode as
+//! one stream.
+//!
+//! Every reader in this module accepts plain, compressed, and encrypted
+//! (`super::encryption`) rollouts, chosen by file name.
+
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_keyring_store::DefaultKeyringStore;
+use codex_protocol::ThreadId;
+use tokio::io::AsyncBufReadExt;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use super::ARCHIVED_SESSIONS_SUBDIR;
+use super::SESSIONS_SUBDIR;
+use super::encryption;
+use super::encryption::ENCRYPTED_ROLLOUT_FILE_SUFFIX;
+use super::list::parse_timestamp_uuid_from_filename;
+use crate::encryption::RecordCipher;
+use crate::state_db;
+use crate::turn_journal;
+
+pub(crate) const ROLLOUT_FILE_PREFIX: &str = "rollout-";
+pub(crate) const ROLLOUT_FILE_SUFFIX: &str = ".jsonl";
+pub(crate) const COMPRESSED_ROLLOUT_FILE_SUFFIX: &str = ".jsonl.zst";
+
+/// Compression level for new frames; zstd's default trades well for text.
+const ZSTD_LEVEL: i32 = 3;
+
+/// Lines buffered between the decoding thread and an async reader.
+const DECODED_LINE_BUFFER: usize = 64;
+
+/// Returns the name without its `.jsonl`, `.jsonl.zst`, or `.jsonl.enc`
+/// suffix, if it is a rollout file name.
+pub(crate) fn strip_rollout_suffix(name: &str) -> Option<&str> {
+    if !name.starts_with(ROLLOUT_FILE_PREFIX) {
+        return None;
+    }
+    name.strip_suffix(COMPRESSED_ROLLOUT_FILE_SUFFIX)
+        .or_else(|| name.strip_suffix(ENCRYPTED_ROLLOUT_FILE_SUFFIX))
+        .or_else(|| name.strip_suffix(ROLLOUT_FILE_SUFFIX))
+}
+
+pub(crate) fn is_rollout_file_name(name: &str) -> bool {
+    strip_rollout_suffix(name).is_some()
+}
+
+/// True when `path` names a zstd-compressed rollout.
+pub fn is_compressed_rollout(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.ends_with(COMPRESSED_ROLLOUT_FILE_SUFFIX))
+}
+
+/// Reads the whole rollout at `path` as JSONL text, decompressing or
+/// decrypting if needed.
+pub async fn read_rollout_text(path: &Path) -> io::Result<String> {
+    if encryption::is_encrypted_rollout(path) {
+        let text = tokio::fs::read_to_string(path).await?;
+        return encryption::decrypt_rollout_text(&text, path);
+    }
+    if !is_compressed_rollout(path) {
+        return tokio::fs::read_to_string(path).await;
+    }
+    let path = path.to_path_buf();
+    let bytes = tokio::task::spawn_blocking(move || {
+        let file = std::fs::File::open(&path)?;
+        decode_lenient(file, &path)
+    })
+    .await
+    .map_err(io::Error::other)??;
+    String::from_utf8(bytes).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Decodes `reader`, keeping everything before a truncated or corrupt tail.
+fn decode_lenient(reader: impl Read, path: &Path) -> io::Result<Vec<u8>> {
+    let mut decoder = zstd::stream::read::Decoder::new(reader)?;
+    let mut decoded = Vec::new();
+    let mut chunk = [0u8; 64 * 1024];
+    loop {
+        match decoder.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => decoded.extend_from_slice(&chunk[..n]),
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => {
+                if decoded.is_empty() {
+                    return Err(err);
+                }
+                warn!(
+                    "ignoring unreadable tail of compressed rollout {}: {err}",
+                    path.display()
+                );
+                break;
+            }
+        }
+    }
+    // A truncated frame can end mid-line; drop the partial line.
+    if !decoded.ends_with(b"\n")
+        && let Some(end) = decoded.iter().rposition(|byte| *byte == b'\n')
+    {
+        decoded.truncate(end + 1);
+    }
+    Ok(decoded)
+}
+
+/// Reads a rollout line by line without loading all of it, for callers that
+/// only need its head.
+pub(crate) enum RolloutLineReader {
+    Plain(tokio::io::Lines<tokio::io::BufReader<tokio::fs::File>>),
+    Compressed(mpsc::Receiver<io::Result<String>>),
+    Encrypted {
+        records: tokio::io::Lines<tokio::io::BufReader<tokio::fs::File>>,
+        cipher: RecordCipher,
+        path: PathBuf,
+    },
+}
+
+impl RolloutLineReader {
+    pub(crate) async fn open(path: &Path) -> io::Result<Self> {
+        let file = tokio::fs::File::open(path).await?;
+        if encryption::is_encrypted_rollout(path) {
+            let mut records = tokio::io::BufReader::new(file).lines();
+            let header = records.next_line().await?.unwrap_or_default();
+            let cipher = RecordCipher::for_header(&header, &DefaultKeyringStore)?;
+            return Ok(Self::Encrypted {
+                records,
+                cipher,
+                path: path.to_path_buf(),
+            });
+        }
+        if !is_compressed_rollout(path) {
+            return Ok(Self::Plain(tokio::io::BufReader::new(file).lines()));
+        }
+        let file = file.into_std().await;
+        let path = path.to_path_buf();
+        let (tx, rx) = mpsc::channel(DECODED_LINE_BUFFER);
+        tokio::task::spawn_blocking(move || {
+            let decoder = match zstd::stream::read::Decoder::new(file) {
+                Ok(decoder) => decoder,
+                Err(err) => {
+                    let _ = tx.blocking_send(Err(err));
+                    return;
+                }
+            };
+            let mut read_any = false;
+            for line in BufReader::new(decoder).lines() {
+                match line {
+                    Ok(line) => {
+                        read_any = true;
+                        // The reader was dropped: stop decoding.
+                        if tx.blocking_send(Ok(line)).is_err() {
+                            return;
+                        }
+                    }
+                    Err(err) if read_any => {
+                        warn!(
+                            "ignoring unreadable tail of compressed rollout {}: {err}",
+                            path.display()
+                        );
+                        return;
+                    }
+                    Err(err) => {
+                        let _ = tx.blocking_send(Err(err));
+                        return;
+                    }
+                }
+            }
+        });
+        Ok(Self::Compressed(rx))
+    }
+
+    pub(crate) async fn next_line(&mut self) -> io::Result<Option<String>> {
+        match self {
+            Self::Plain(lines) => lines.next_line().await,
+            Self::Compressed(rx) => rx.recv().await.transpose(),
+            Self::Encrypted {
+                records,
+                cipher,
+                path,
+            } => {
+                let Some(record) = records.next_line().await? else {
+                    return Ok(None);
+                };
+                match encryption::open_record(cipher, &record) {
+                    Ok(line) => Ok(Some(line)),
+                    Err(err) => {
+                        let is_last = records.next_line().await?.is_none();
+                        encryption::unreadable_record(path, err, is_last)?;
+                        Ok(None)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Streams JSONL lines into zstd frames for the rollout writer.
+pub(crate) struct ZstdLineEncoder {
+    encoder: Option<zstd::stream::write::Encoder<'static, Vec<u8>>>,
+}
+
+impl ZstdLineEncoder {
+    pub(crate) fn new() -> Self {
+        Self { encoder: None }
+    }
+
+    /// Compresses `line` and returns the bytes to append to the file. A frame
+    /// is started on demand.
+    pub(crate) fn encode_line(&mut self, line: &[u8]) -> io::Result<Vec<u8>> {
+        let encoder = match self.encoder.as_mut() {
+            Some(encoder) => encoder,
+            None => self
+                .encoder
+                .insert(zstd::stream::write::Encoder::new(Vec::new(), ZSTD_LEVEL)?),
+        };
+        encoder.write_all(line)?;
+        encoder.flush()?;
+        Ok(std::mem::take(encoder.get_mut()))
+    }
+
+    /// Ends the open frame, if any, and returns its trailing bytes.
+    pub(crate) fn finish_frame(&mut self) -> io::Result<Vec<u8>> {
+        match self.encoder.take() {
+            Some(encoder) => encoder.finish(),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+/// Rewrites a compressed rollout whose last frame was never finished (the
+/// writer crashed) so new frames can be appended to it.
+pub(crate) async fn repair_compressed_rollout(path: &Path) -> io::Result<()> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let compressed = std::fs::read(&path)?;
+        if compressed.is_empty() || zstd::stream::decode_all(compressed.as_slice()).is_ok() {
+            return Ok(());
+        }
+        warn!("repairing truncated compressed rollout {}", path.display());
+        let decoded = decode_lenient(compressed.as_slice(), &path)?;
+        let recompressed = zstd::stream::encode_all(decoded.as_slice(), ZSTD_LEVEL)?;
+        write_atomically(&path, &recompressed)
+    })
+    .await
+    .map_err(io::Error::other)?
+}
+
+pub(crate) fn write_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut tmp_path = path.as_os_str().to_os_string();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+    std::fs::write(&tmp_path, contents)?;
+    std::fs::rename(&tmp_path, path)
+}
+
+/// Result of [`compress_rollouts`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RolloutCompressionSummary {
+    pub compressed: usize,
+    /// Rollouts of sessions that are still running, left as they are.
+    pub skipped_live: Vec<PathBuf>,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+}
+
+/// Compresses every plain `.jsonl` rollout under `sessions/` and
+/// `archived_sessions/` into a `.jsonl.zst` next to it and removes the
+/// original. Rollouts of sessions that are still running are skipped.
+pub async fn compress_rollouts(codex_home: &Path) -> io::Result<RolloutCompressionSummary> {
+    let state_db_ctx = state_db::open_if_present(codex_home, "").await;
+    let mut summary = RolloutCompressionSummary::default();
+    for (subdir, archived) in [(SESSIONS_SUBDIR, false), (ARCHIVED_SESSIONS_SUBDIR, true)] {
+        let root = codex_home.join(subdir);
+        let paths = tokio::task::spawn_blocking(move || {
+            rollout_paths_with_suffixes(&root, &[ROLLOUT_FILE_SUFFIX])
+        })
+        .await
+        .map_err(io::Error::other)??;
+        for path in paths {
+            let thread_id = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(parse_timestamp_uuid_from_filename)
+                .and_then(|(_, uuid)| ThreadId::from_string(&uuid.to_string()).ok());
+            if let Some(thread_id) = thread_id
+                && turn_journal::is_thread_live(codex_home, thread_id)
+            {
+                summary.skipped_live.push(path);
+                continue;
+            }
+            let compressed_path = compressed_path_for(&path);
+            let (before, after) = {
+                let path = path.clone();
+                let compressed_path = compressed_path.clone();
+                tokio::task::spawn_blocking(move || compress_file(&path, &compressed_path))
+                    .await
+                    .map_err(io::Error::other)??
+            };
+            summary.compressed += 1;
+            summary.bytes_before += before;
+            summary.bytes_after += after;
+            state_db::read_repair_rollout_path(
+                state_db_ctx.as_deref(),
+                thread_id,
+                Some(archived),
+                &compressed_path,
+            )
+            .await;
+        }
+    }
+    Ok(summary)
+}
+
+fn compressed_path_for(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".zst");
+    PathBuf::from(name)
+}
+
+/// Compresses `path` into `compressed_path` and removes `path`. Returns the
+/// sizes before and after.
+fn compress_file(path: &Path, compressed_path: &Path) -> io::Result<(u64, u64)> {
+    let plain = std::fs::read(path)?;
+    let compressed = zstd::stream::encode_all(plain.as_slice(), ZSTD_LEVEL)?;
+    write_atomically(compressed_path, &compressed)?;
+    std::fs::remove_file(path)?;
+    Ok((plain.len() as u64, compressed.len() as u64))
+}
+
+/// Rollout files under `root` whose names end in one of `suffixes`.
+pub(crate) fn rollout_paths_with_suffixes(
+    root: &Path,
+    suffixes: &[&str],
+) -> io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err),
+        };
+        for entry in entries {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                stack.push(entry.path());
+            } else if file_type.is_file()
+                && entry.file_name().to_str().is_some_and(|name| {
+                    name.starts_with(ROLLOUT_FILE_PREFIX)
+                        && suffixes.iter().any(|suffix| name.ends_with(suffix))
+                })
+            {
+                paths.push(entry.path());
+            }
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    const LINES: [&str; 3] = [
+        "{\"type\":\"session_meta\"}\n",
+        "{\"type\":\"response_item\"}\n",
+        "{\"type\":\"event_msg\"}\n",
+    ];
+
+    #[tokio::test]
+    async fn unfinished_frame_is_readable_and_repaired_before_appending() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join("rollout-2026-01-01T00-00-00-x.jsonl.zst");
+
+        // A writer that died before finishing its frame.
+        let mut encoder = ZstdLineEncoder::new();
+        let mut bytes = encoder.encode_line(LINES[0].as_bytes()).expect("encode");
+        bytes.extend(encoder.encode_line(LINES[1].as_bytes()).expect("encode"));
+        std::fs::write(&path, &bytes).expect("write");
+
+        assert_eq!(
+            read_rollout_text(&path).await.expect("read"),
+            format!("{}{}", LINES[0], LINES[1])
+        );
+
+        repair_compressed_rollout(&path).await.expect("repair");
+        let mut resumed = ZstdLineEncoder::new();
+        let mut appended = resumed.encode_line(LINES[2].as_bytes()).expect("encode");
+        appended.extend(resumed.finish_frame().expect("finish"));
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .expect("open");
+        file.write_all(&appended).expect("append");
+
+        let mut reader = RolloutLineReader::open(&path).await.expect("open reader");
+        let mut lines = Vec::new();
+        while let Some(line) = reader.next_line().await.expect("next line") {
+            lines.push(format!("{line}\n"));
+        }
+        assert_eq!(lines, LINES.to_vec());
+    }
+
+    #[tokio::test]
+    async fn compress_rollouts_replaces_plain_files() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let day_dir = codex_home.path().join(SESSIONS_SUBDIR).join("2026/01/01");
+        std::fs::create_dir_all(&day_dir).expect("mkdir");
+        let plain = day_dir.join(format!(
+            "rollout-2026-01-01T00-00-00-{}.jsonl",
+            ThreadId::new()
+        ));
+        std::fs::write(&plain, LINES.concat()).expect("write");
+
+        let summary = compress_rollouts(codex_home.path())
+            .await
+            .expect("compress");
+
+        assert_eq!(summary.compressed, 1);
+        assert_eq!(summary.skipped_live, Vec::<PathBuf>::new());
+        assert!(!plain.exists());
+        let compressed = compressed_path_for(&plain);
+        assert_eq!(
+            read_rollout_text(&compressed).await.expect("read"),
+            LINES.concat()
+        );
+    }
+}
@@ -0,0 +1,329 @@
+//! Encrypted rollouts (`rollout-*.jsonl.enc`).
+//!
+//! An encrypted rollout is the encryption header line followed by one sealed
+//! record per JSONL line (see [`crate::encryption`]). Appending a line never
+//! touches earlier ones, so resuming works as it does for plain rollouts, and
+//! a final record cut short by a crash is skipped on read like a torn plain
+//! line. Any other record that fails authentication makes the read fail.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_keyring_store::DefaultKeyringStore;
+use codex_protocol::ThreadId;
+use tracing::warn;
+
+use super::ARCHIVED_SESSIONS_SUBDIR;
+use super::SESSIONS_SUBDIR;
+use super::compression;
+use super::list::parse_timestamp_uuid_from_filename;
+use crate::encryption::DataKey;
+use crate::encryption::RecordCipher;
+use crate::state_db;
+use crate::turn_journal;
+
+pub(crate) const ENCRYPTED_ROLLOUT_FILE_SUFFIX: &str = ".jsonl.enc";
+
+/// True when `path` names an encrypted rollout.
+pub fn is_encrypted_rollout(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.ends_with(ENCRYPTED_ROLLOUT_FILE_SUFFIX))
+}
+
+/// The cipher of an existing encrypted rollout, positioned after its last
+/// record for appending. Repair the file first so a torn record is not
+/// counted.
+pub(crate) async fn rollout_cipher(path: &Path) -> io::Result<RecordCipher> {
+    let text = tokio::fs::read_to_string(path).await?;
+    let mut lines = text.lines();
+    let header = lines.next().unwrap_or_default();
+    let mut cipher = RecordCipher::for_header(header, &DefaultKeyringStore)?;
+    cipher.skip_records(lines.count() as u64);
+    Ok(cipher)
+}
+
+/// Decrypts a whole encrypted rollout into JSONL text. Only a final record
+/// that does not authenticate (a torn line looks like that) is skipped.
+pub(crate) fn decrypt_rollout_text(text: &str, path: &Path) -> io::Result<String> {
+    let mut lines = text.lines().peekable();
+    let Some(header) = lines.next() else {
+        return Ok(String::new());
+    };
+    let mut cipher = RecordCipher::for_header(header, &DefaultKeyringStore)?;
+    let mut decrypted = String::with_capacity(text.len());
+    while let Some(record) = lines.next() {
+        match open_record(&mut cipher, record) {
+            Ok(line) => {
+                decrypted.push_str(&line);
+                decrypted.push('\n');
+            }
+            Err(err) => {
+                let is_last = lines.peek().is_none();
+                return unreadable_record(path, err, is_last).map(|()| decrypted);
+            }
+        }
+    }
+    Ok(decrypted)
+}
+
+/// Handles a record that failed to open: a torn final record is skipped with a
+/// warning, anything else is an error.
+pub(crate) fn unreadable_record(path: &Path, err: io::Error, is_last: bool) -> io::Result<()> {
+    if !is_last {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("encrypted rollout {} is corrupted: {err}", path.display()),
+        ));
+    }
+    warn!(
+        "skipping unreadable final record in encrypted rollout {}: {err}",
+        path.display()
+    );
+    Ok(())
+}
+
+/// Cuts a record torn by a crash off the end of an encrypted rollout, so the
+/// next appended record starts on its own line.
+pub(crate) async fn repair_encrypted_rollout(path: &Path) -> io::Result<()> {
+    let contents = tokio::fs::read(path).await?;
+    if contents.is_empty() || contents.ends_with(b"\n") {
+        return Ok(());
+    }
+    warn!("repairing truncated encrypted rollout {}", path.display());
+    let keep = contents
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map_or(0, |end| end + 1);
+    let file = tokio::fs::OpenOptions::new().write(true).open(path).await?;
+    file.set_len(keep as u64).await
+}
+
+/// Decrypts the next record back into its JSONL line (without the newline).
+pub(crate) fn open_record(cipher: &mut RecordCipher, record: &str) -> io::Result<String> {
+    String::from_utf8(cipher.open_next(record)?)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Result of [`encrypt_rollouts`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RolloutEncryptionSummary {
+    pub encrypted: usize,
+    /// Rollouts of sessions that are still running, left as they are.
+    pub skipped_live: Vec<PathBuf>,
+}
+
+/// Encrypts every plain or compressed rollout under `sessions/` and
+/// `archived_sessions/` into a `.jsonl.enc` next to it and removes the
+/// original. Rollouts of sessions that are still running are skipped.
+pub async fn encrypt_rollouts(codex_home: &Path) -> io::Result<RolloutEncryptionSummary> {
+    let key = DataKey::load_or_create(codex_home, &DefaultKeyringStore)?;
+    let state_db_ctx = state_db::open_if_present(codex_home, "").await;
+    let mut summary = RolloutEncryptionSummary::default();
+    for (subdir, archived) in [(SESSIONS_SUBDIR, false), (ARCHIVED_SESSIONS_SUBDIR, true)] {
+        let root = codex_home.join(subdir);
+        let paths = tokio::task::spawn_blocking(move || {
+            compression::rollout_paths_with_suffixes(
+                &root,
+                &[
+                    compression::ROLLOUT_FILE_SUFFIX,
+                    compression::COMPRESSED_ROLLOUT_FILE_SUFFIX,
+                ],
+            )
+        })
+        .await
+        .map_err(io::Error::other)??;
+        for path in paths {
+            let thread_id = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(parse_timestamp_uuid_from_filename)
+                .and_then(|(_, uuid)| ThreadId::from_string(&uuid.to_string()).ok());
+            if let Some(thread_id) = thread_id
+                && turn_journal::is_thread_live(codex_home, thread_id)
+            {
+                summary.skipped_live.push(path);
+                continue;
+            }
+            let encrypted_path = encrypted_path_for(&path);
+            let text = compression::read_rollout_text(&path).await?;
+            let mut cipher = RecordCipher::create(Arc::clone(&key))?;
+            let mut sealed = cipher.header_line();
+            for line in text.lines() {
+                sealed.push_str(&cipher.seal_next(line.as_bytes())?);
+                sealed.push('\n');
+            }
+            {
+                let encrypted_path = encrypted_path.clone();
+                tokio::task::spawn_blocking(move || {
+                    compression::write_atomically(&encrypted_path, sealed.as_bytes())
+                })
+                .await
+                .map_err(io::Error::other)??;
+            }
+            tokio::fs::remove_file(&path).await?;
+            summary.encrypted += 1;
+            state_db::read_repair_rollout_path(
+                state_db_ctx.as_deref(),
+                thread_id,
+                Some(archived),
+                &encrypted_path,
+            )
+            .await;
+        }
+    }
+    Ok(summary)
+}
+
+fn encrypted_path_for(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(compression::strip_rollout_suffix)
+        .map(|stem| format!("{stem}{ENCRYPTED_ROLLOUT_FILE_SUFFIX}"))
+        .unwrap_or_default();
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rollout::compression::RolloutLineReader;
+    use codex_keyring_store::tests::MockKeyringStore;
+    use pretty_assertions::assert_eq;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    const LINES: [&str; 3] = [
+        "{\"type\":\"session_meta\"}\n",
+        "{\"type\":\"response_item\"}\n",
+        "{\"type\":\"event_msg\",\"payload\":\"sk-secret\"}\n",
+    ];
+
+    #[tokio::test]
+    async fn encrypt_rollouts_replaces_plain_and_compressed_files() {
+        let codex_home = TempDir::new().expect("tempdir");
+        // Loads the key into the process cache so nothing reaches the real
+        // keyring.
+        DataKey::load_or_create(codex_home.path(), &MockKeyringStore::default()).expect("key");
+        let day_dir = codex_home.path().join(SESSIONS_SUBDIR).join("2026/01/01");
+        std::fs::create_dir_all(&day_dir).expect("mkdir");
+        let plain = day_dir.join(format!(
+            "rollout-2026-01-01T00-00-00-{}.jsonl",
+            ThreadId::new()
+        ));
+        std::fs::write(&plain, LINES.concat()).expect("write");
+        let compressed = day_dir.join(format!(
+            "rollout-2026-01-01T00-00-01-{}.jsonl.zst",
+            ThreadId::new()
+        ));
+        std::fs::write(
+            &compressed,
+            zstd::stream::encode_all(LINES.concat().as_bytes(), 3).expect("compress"),
+        )
+        .expect("write");
+
+        let summary = encrypt_rollouts(codex_home.path()).await.expect("encrypt");
+
+        assert_eq!(summary.encrypted, 2);
+        assert!(!plain.exists());
+        assert!(!compressed.exists());
+        for path in [encrypted_path_for(&plain), encrypted_path_for(&compressed)] {
+            let on_disk = std::fs::read_to_string(&path).expect("read");
+            assert!(!on_disk.contains("sk-secret"));
+            assert_eq!(
+                compression::read_rollout_text(&path)
+                    .await
+                    .expect("decrypt"),
+                LINES.concat()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn torn_final_record_is_skipped_and_repaired_before_appending() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let key =
+            DataKey::load_or_create(codex_home.path(), &MockKeyringStore::default()).expect("key");
+        let path = codex_home
+            .path()
+            .join("rollout-2026-01-01T00-00-00-x.jsonl.enc");
+        let mut cipher = RecordCipher::create(key).expect("cipher");
+        let first = cipher
+            .seal_next(LINES[0].trim_end().as_bytes())
+            .expect("seal");
+        let torn = cipher
+            .seal_next(LINES[1].trim_end().as_bytes())
+            .expect("seal");
+        std::fs::write(
+            &path,
+            format!(
+                "{}{first}\n{}",
+                cipher.header_line(),
+                &torn[..torn.len() / 2]
+            ),
+        )
+        .expect("write");
+
+        assert_eq!(
+            compression::read_rollout_text(&path).await.expect("read"),
+            LINES[0]
+        );
+
+        // A resumed writer repairs the file, finds the key from the header,
+        // and appends records.
+        repair_encrypted_rollout(&path).await.expect("repair");
+        let mut resumed = rollout_cipher(&path).await.expect("cipher");
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .expect("open");
+        writeln!(
+            file,
+            "{}",
+            resumed
+                .seal_next(LINES[2].trim_end().as_bytes())
+                .expect("seal")
+        )
+        .expect("append");
+        let mut reader = RolloutLineReader::open(&path).await.expect("open reader");
+        let mut lines = Vec::new();
+        while let Some(line) = reader.next_line().await.expect("next line") {
+            lines.push(format!("{line}\n"));
+        }
+        assert_eq!(lines, vec![LINES[0].to_string(), LINES[2].to_string()]);
+    }
+
+    #[tokio::test]
+    async fn reordered_records_fail_the_read() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let key =
+            DataKey::load_or_create(codex_home.path(), &MockKeyringStore::default()).expect("key");
+        let path = codex_home
+            .path()
+            .join("rollout-2026-01-01T00-00-00-x.jsonl.enc");
+        let mut cipher = RecordCipher::create(key).expect("cipher");
+        let records = LINES.map(|line| cipher.seal_next(line.trim_end().as_bytes()).expect("seal"));
+        std::fs::write(
+            &path,
+            format!(
+                "{}{}\n{}\n{}\n",
+                cipher.header_line(),
+                records[1],
+                records[0],
+                records[2]
+            ),
+        )
+        .expect("write");
+
+        assert_eq!(
+            compression::read_rollout_text(&path)
+                .await
+                .expect_err("reordered records")
+                .kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+}
@@ -0,0 +1,359 @@
+//! Keeps the full-text history index (`codex_state::HistoryIndex`) in sync with
+//! the rollouts on disk and searches it.
+//!
+//! Indexed text: user and agent messages, shell commands, and the paths of
+//! files touched by `apply_patch`. Each entry carries the 1-based user turn it
+//! belongs to. Encrypted rollouts are not indexed.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::ThreadId;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::RolloutLine;
+use codex_state::HistoryEntry;
+use codex_state::HistoryEntryKind;
+use codex_state::HistoryIndex;
+use codex_state::HistorySearchHit;
+use codex_state::IndexedRollout;
+use serde_json::Value;
+use tracing::warn;
+
+use super::ARCHIVED_SESSIONS_SUBDIR;
+use super::SESSIONS_SUBDIR;
+use super::compression::RolloutLineReader;
+use super::encryption::is_encrypted_rollout;
+use super::list::parse_timestamp_uuid_from_filename;
+use super::metadata::collect_rollout_paths;
+
+const PATCH_PATH_MARKERS: [&str; 4] = [
+    "*** Add File: ",
+    "*** Update File: ",
+    "*** Delete File: ",
+    "*** Move to: ",
+];
+
+/// Outcome of [`refresh_history_index`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryIndexRefresh {
+    /// Rollouts with new lines since the last refresh.
+    pub updated: usize,
+    /// Threads dropped because their rollout no longer exists or is now
+    /// encrypted.
+    pub removed: usize,
+}
+
+/// Indexes whatever was appended to rollouts under `codex_home` since the
+/// last refresh. Unchanged rollouts are skipped by size alone.
+pub async fn refresh_history_index(codex_home: &Path) -> anyhow::Result<HistoryIndexRefresh> {
+    let index = HistoryIndex::open(codex_home).await?;
+    let mut refresh = HistoryIndexRefresh::default();
+    let mut live = HashSet::new();
+    for subdir in [SESSIONS_SUBDIR, ARCHIVED_SESSIONS_SUBDIR] {
+        let root = codex_home.join(subdir);
+        if !tokio::fs::try_exists(&root).await.unwrap_or(false) {
+            continue;
+        }
+        for path in collect_rollout_paths(&root).await? {
+            // The index is plaintext; keep encrypted sessions out of it (and
+            // drop them if they were indexed before being encrypted).
+            if is_encrypted_rollout(&path) {
+                continue;
+            }
+            let Some(thread_id) = thread_id_from_path(&path) else {
+                continue;
+            };
+            live.insert(thread_id);
+            match index_rollout(&index, thread_id, &path).await {
+                Ok(true) => refresh.updated += 1,
+                Ok(false) => {}
+                Err(err) => warn!("failed to index rollout {}: {err}", path.display()),
+            }
+        }
+    }
+    refresh.removed = index.retain_threads(&live).await?;
+    Ok(refresh)
+}
+
+/// Searches the history index; call [`refresh_history_index`] first to pick
+/// up recent sessions.
+pub async fn search_history(
+    codex_home: &Path,
+    query: &str,
+    limit: usize,
+) -> anyhow::Result<Vec<HistorySearchHit>> {
+    HistoryIndex::open(codex_home)
+        .await?
+        .search(query, limit)
+        .await
+}
+
+fn thread_id_from_path(path: &Path) -> Option<ThreadId> {
+    let name = path.file_name()?.to_str()?;
+    let (_, uuid) = parse_timestamp_uuid_from_filename(name)?;
+    ThreadId::from_string(&uuid.to_string()).ok()
+}
+
+/// Returns whether anything was (re)indexed.
+async fn index_rollout(
+    index: &HistoryIndex,
+    thread_id: ThreadId,
+    path: &Path,
+) -> anyhow::Result<bool> {
+    let size = tokio::fs::metadata(path).await?.len();
+    let previous = index.indexed_rollout(thread_id).await?;
+    // A moved (archived, compressed) or truncated rollout is indexed again from
+    // the start.
+    let resume_from =
+        previous.filter(|previous| previous.rollout_path == path && previous.size <= size);
+    if resume_from
+        .as_ref()
+        .is_some_and(|previous| previous.size == size)
+    {
+        return Ok(false);
+    }
+    let reset = resume_from.is_none();
+    let (skip_lines, mut turns) = resume_from
+        .map(|previous| (previous.lines, previous.turns))
+        .unwrap_or_default();
+
+    let mut reader = RolloutLineReader::open(path).await?;
+    let mut lines = 0u64;
+    let mut pending_line = None;
+    let mut entries = Vec::new();
+    while let Some(line) = reader.next_line().await? {
+        // Only count a line once the next one shows it was complete; the last
+        // line may still be being written.
+        if let Some(complete) = pending_line.replace(line) {
+            lines += 1;
+            if lines > skip_lines {
+                extract_entries(&complete, &mut turns, &mut entries);
+            }
+        }
+    }
+    if let Some(last) = pending_line
+        && serde_json::from_str::<RolloutLine>(&last).is_ok()
+    {
+        lines += 1;
+        if lines > skip_lines {
+            extract_entries(&last, &mut turns, &mut entries);
+        }
+    }
+
+    let progress = IndexedRollout {
+        rollout_path: PathBuf::from(path),
+        size,
+        lines,
+        turns,
+    };
+    index.append(thread_id, &progress, &entries, reset).await?;
+    Ok(true)
+}
+
+fn extract_entries(line: &str, turns: &mut u32, entries: &mut Vec<HistoryEntry>) {
+    let Ok(rollout_line) = serde_json::from_str::<RolloutLine>(line) else {
+        return;
+    };
+    let turn = *turns;
+    let mut push = |kind: HistoryEntryKind, text: String| {
+        if !text.trim().is_empty() {
+            entries.push(HistoryEntry { turn, kind, text });
+        }
+    };
+    match rollout_line.item {
+        RolloutItem::EventMsg(EventMsg::UserMessage(event)) => {
+            *turns += 1;
+            entries.push(HistoryEntry {
+                turn: *turns,
+                kind: HistoryEntryKind::UserMessage,
+                text: event.message,
+            });
+        }
+        RolloutItem::EventMsg(EventMsg::AgentMessage(event)) => {
+            push(HistoryEntryKind::AgentMessage, event.message);
+        }
+        RolloutItem::ResponseItem(ResponseItem::LocalShellCall {
+            action: LocalShellAction::Exec(action),
+            ..
+        }) => push(HistoryEntryKind::Command, action.command.join(" ")),
+        RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+            name, arguments, ..
+        }) => {
+            let Ok(arguments) = serde_json::from_str::<Value>(&arguments) else {
+                return;
+            };
+            if name == "apply_patch" {
+                if let Some(input) = arguments.get("input").and_then(Value::as_str) {
+                    for path in patch_paths(input) {
+                        push(HistoryEntryKind::FilePath, path);
+                    }
+                }
+            } else if let Some(command) = command_from_arguments(&arguments) {
+                push(HistoryEntryKind::Command, command);
+            }
+        }
+        RolloutItem::ResponseItem(ResponseItem::CustomToolCall { name, input, .. })
+            if name == "apply_patch" =>
+        {
+            for path in patch_paths(&input) {
+                push(HistoryEntryKind::FilePath, path);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The command of a shell-like tool call: `command` as an argv array or a
+/// string, or the unified exec `cmd`.
+fn command_from_arguments(arguments: &Value) -> Option<String> {
+    match arguments.get("command").or_else(|| arguments.get("cmd"))? {
+        Value::String(command) => Some(command.clone()),
+        Value::Array(argv) => Some(
+            argv.iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join(" "),
+        ),
+        _ => None,
+    }
+}
+
+fn patch_paths(patch: &str) -> Vec<String> {
+    patch
+        .lines()
+        .filter_map(|line| {
+            PATCH_PATH_MARKERS
+                .iter()
+                .find_map(|marker| line.strip_prefix(marker))
+        })
+        .map(|path| path.trim().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn line(item: Value) -> String {
+        let mut line = json!({ "timestamp": "2026-01-01T00:00:00.000Z" });
+        line.as_object_mut()
+            .expect("object")
+            .extend(item.as_object().expect("object").clone());
+        format!("{line}\n")
+    }
+
+    fn user_message(text: &str) -> String {
+        line(json!({
+            "type": "event_msg",
+            "payload": { "type": "user_message", "message": text },
+        }))
+    }
+
+    fn shell_call(command: &str) -> String {
+        line(json!({
+            "type": "response_item",
+            "payload": {
+                "type": "function_call",
+                "name": "shell_command",
+                "arguments": json!({ "command": command }).to_string(),
+                "call_id": "call-1",
+            },
+        }))
+    }
+
+    fn patch_call(path: &str) -> String {
+        line(json!({
+            "type": "response_item",
+            "payload": {
+                "type": "custom_tool_call",
+                "name": "apply_patch",
+                "input": format!("*** Begin Patch\n*** Update File: {path}\n@@\n-a\n+b\n*** End Patch"),
+                "call_id": "call-2",
+            },
+        }))
+    }
+
+    #[tokio::test]
+    async fn refresh_indexes_only_appended_lines_with_turn_offsets() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let day_dir = codex_home.path().join(SESSIONS_SUBDIR).join("2026/01/01");
+        std::fs::create_dir_all(&day_dir).expect("mkdir");
+        let thread_id = ThreadId::new();
+        let path = day_dir.join(format!("rollout-2026-01-01T00-00-00-{thread_id}.jsonl"));
+        std::fs::write(
+            &path,
+            format!(
+                "{}{}",
+                user_message("why is the parser slow"),
+                shell_call("cargo bench -p parser")
+            ),
+        )
+        .expect("write");
+
+        let refresh = refresh_history_index(codex_home.path())
+            .await
+            .expect("refresh");
+        assert_eq!(
+            refresh,
+            HistoryIndexRefresh {
+                updated: 1,
+                removed: 0
+            }
+        );
+
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .expect("open");
+        std::io::Write::write_all(
+            &mut file,
+            format!(
+                "{}{}",
+                user_message("now fix the lexer"),
+                patch_call("src/lexer.rs")
+            )
+            .as_bytes(),
+        )
+        .expect("append");
+        refresh_history_index(codex_home.path())
+            .await
+            .expect("refresh");
+
+        let turns_for = |hits: Vec<HistorySearchHit>| {
+            hits.into_iter()
+                .map(|hit| (hit.thread_id, hit.turn, hit.kind))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            turns_for(
+                search_history(codex_home.path(), "bench", 10)
+                    .await
+                    .expect("search")
+            ),
+            vec![(thread_id, 1, HistoryEntryKind::Command)]
+        );
+        assert_eq!(
+            turns_for(
+                search_history(codex_home.path(), "lexer.rs", 10)
+                    .await
+                    .expect("search")
+            ),
+            vec![(thread_id, 2, HistoryEntryKind::FilePath)]
+        );
+        // The first refresh's lines were not indexed twice.
+        assert_eq!(
+            search_history(codex_home.path(), "parser slow", 10)
+                .await
+                .expect("search")
+                .len(),
+            1
+        );
+    }
+}
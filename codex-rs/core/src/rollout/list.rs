@@ -15,6 +15,11 @@ use uuid::Uuid;
 
 use super::ARCHIVED_SESSIONS_SUBDIR;
 use super::SESSIONS_SUBDIR;
+use super::compression::RolloutLineReader;
+use super::compression::is_rollout_file_name;
+use super::compression::strip_rollout_suffix;
+use super::summary_index::RolloutSummary;
+use super::summary_index::SummaryCache;
 use crate::protocol::EventMsg;
 use crate::state_db;
 use codex_file_search as file_search;
@@ -24,6 +29,7 @@ use codex_protocol::protocol::RolloutLine;
 use codex_protocol::protocol::SessionMetaLine;
 use codex_protocol::protocol::SessionSource;
 use codex_protocol::protocol::USER_MESSAGE_BEGIN;
+use futures::future::join_all;
 
 /// Returned page of thread (thread) summaries.
 #[derive(Debug, Default, PartialEq)]
@@ -67,6 +73,8 @@ pub struct ThreadItem {
     /// RFC3339 timestamp string for the most recent update (from file mtime).
     /// updated_at is truncated to second precision to match created_at.
     pub updated_at: Option<String>,
+    /// Total tokens used by the thread, when recorded.
+    pub tokens_used: Option<i64>,
 }
 
 #[allow(dead_code)]
@@ -90,7 +98,24 @@ struct HeadTailSummary {
     model_provider: Option<String>,
     cli_version: Option<String>,
     created_at: Option<String>,
-    updated_at: Option<String>,
+}
+
+impl From<HeadTailSummary> for RolloutSummary {
+    fn from(head: HeadTailSummary) -> Self {
+        Self {
+            thread_id: head.thread_id,
+            first_user_message: head.first_user_message,
+            cwd: head.cwd,
+            git_branch: head.git_branch,
+            git_sha: head.git_sha,
+            git_origin_url: head.git_origin_url,
+            source: head.source,
+            model_provider: head.model_provider,
+            cli_version: head.cli_version,
+            created_at: head.created_at,
+            tokens_used: None,
+        }
+    }
 }
 
 /// Hard cap to bound worst‑case work per request.
@@ -194,6 +219,7 @@ struct FilesByCreatedAtVisitor<'a> {
     more_matches_available: bool,
     allowed_sources: &'a [SessionSource],
     provider_matcher: Option<&'a ProviderMatcher<'a>>,
+    summaries: &'a mut SummaryCache,
 }
 
 #[async_trait]
@@ -220,14 +246,20 @@ impl<'a> RolloutFileVisitor for FilesByCreatedAtVisitor<'a> {
             .await
             .unwrap_or(None)
             .and_then(format_rfc3339);
+        self.summaries.load_dirs([path.as_path()]).await;
+        let Some((summary, from_head)) = read_summary(&path, self.summaries).await else {
+            return ControlFlow::Continue(());
+        };
+        if from_head {
+            self.summaries.insert_miss(&path, summary.clone());
+        }
         if let Some(item) = build_thread_item(
             path,
+            summary,
             self.allowed_sources,
             self.provider_matcher,
             updated_at,
-        )
-        .await
-        {
+        ) {
             self.items.push(item);
         }
         ControlFlow::Continue(())
@@ -440,6 +472,7 @@ async fn traverse_directories_for_paths_created(
     let mut items: Vec<ThreadItem> = Vec::with_capacity(page_size);
     let mut scanned_files = 0usize;
     let mut more_matches_available = false;
+    let mut summaries = SummaryCache::default();
     let mut visitor = FilesByCreatedAtVisitor {
         items: &mut items,
         page_size,
@@ -447,9 +480,11 @@ async fn traverse_directories_for_paths_created(
         more_matches_available,
         allowed_sources,
         provider_matcher,
+        summaries: &mut summaries,
     };
     walk_rollout_files(&root, &mut scanned_files, &mut visitor).await?;
     more_matches_available = visitor.more_matches_available;
+    summaries.save_misses().await;
 
     let reached_scan_cap = scanned_files >= MAX_SCAN_FILES;
     if reached_scan_cap && !items.is_empty() {
@@ -487,7 +522,6 @@ async fn traverse_directories_for_paths_updated(
     let mut items: Vec<ThreadItem> = Vec::with_capacity(page_size);
     let mut scanned_files = 0usize;
     let mut anchor_state = AnchorState::new(anchor);
-    let mut more_matches_available = false;
 
     let candidates = collect_files_by_updated_at(&root, &mut scanned_files).await?;
     let mut candidates = candidates;
@@ -496,28 +530,21 @@ async fn traverse_directories_for_paths_updated(
         (Reverse(ts), Reverse(candidate.id))
     });
 
-    for candidate in candidates.into_iter() {
-        let ts = candidate.updated_at.unwrap_or(OffsetDateTime::UNIX_EPOCH);
-        if anchor_state.should_skip(ts, candidate.id) {
-            continue;
-        }
-        if items.len() == page_size {
-            more_matches_available = true;
-            break;
-        }
-
-        let updated_at_fallback = candidate.updated_at.and_then(format_rfc3339);
-        if let Some(item) = build_thread_item(
-            candidate.path,
-            allowed_sources,
-            provider_matcher,
-            updated_at_fallback,
-        )
-        .await
-        {
-            items.push(item);
-        }
-    }
+    let candidates = candidates
+        .into_iter()
+        .filter(|candidate| {
+            let ts = candidate.updated_at.unwrap_or(OffsetDateTime::UNIX_EPOCH);
+            !anchor_state.should_skip(ts, candidate.id)
+        })
+        .map(|candidate| (candidate.path, candidate.updated_at));
+    let mut more_matches_available = fill_page(
+        &mut items,
+        page_size,
+        candidates,
+        allowed_sources,
+        provider_matcher,
+    )
+    .await;
 
     let reached_scan_cap = scanned_files >= MAX_SCAN_FILES;
     if reached_scan_cap && !items.is_empty() {
@@ -547,27 +574,20 @@ async fn traverse_flat_paths_created(
     let mut items: Vec<ThreadItem> = Vec::with_capacity(page_size);
     let mut scanned_files = 0usize;
     let mut anchor_state = AnchorState::new(anchor);
-    let mut more_matches_available = false;
 
     let files = collect_flat_rollout_files(&root, &mut scanned_files).await?;
-    for (ts, id, path) in files.into_iter() {
-        if anchor_state.should_skip(ts, id) {
-            continue;
-        }
-        if items.len() == page_size {
-            more_matches_available = true;
-            break;
-        }
-        let updated_at = file_modified_time(&path)
-            .await
-            .unwrap_or(None)
-            .and_then(format_rfc3339);
-        if let Some(item) =
-            build_thread_item(path, allowed_sources, provider_matcher, updated_at).await
-        {
-            items.push(item);
-        }
-    }
+    let candidates = files
+        .into_iter()
+        .filter(|(ts, id, _)| !anchor_state.should_skip(*ts, *id))
+        .map(|(_, _, path)| (path, None));
+    let mut more_matches_available = fill_page(
+        &mut items,
+        page_size,
+        candidates,
+        allowed_sources,
+        provider_matcher,
+    )
+    .await;
 
     let reached_scan_cap = scanned_files >= MAX_SCAN_FILES;
     if reached_scan_cap && !items.is_empty() {
@@ -597,7 +617,6 @@ async fn traverse_flat_paths_updated(
     let mut items: Vec<ThreadItem> = Vec::with_capacity(page_size);
     let mut scanned_files = 0usize;
     let mut anchor_state = AnchorState::new(anchor);
-    let mut more_matches_available = false;
 
     let candidates = collect_flat_files_by_updated_at(&root, &mut scanned_files).await?;
     let mut candidates = candidates;
@@ -606,28 +625,21 @@ async fn traverse_flat_paths_updated(
         (Reverse(ts), Reverse(candidate.id))
     });
 
-    for candidate in candidates.into_iter() {
-        let ts = candidate.updated_at.unwrap_or(OffsetDateTime::UNIX_EPOCH);
-        if anchor_state.should_skip(ts, candidate.id) {
-            continue;
-        }
-        if items.len() == page_size {
-            more_matches_available = true;
-            break;
-        }
-
-        let updated_at_fallback = candidate.updated_at.and_then(format_rfc3339);
-        if let Some(item) = build_thread_item(
-            candidate.path,
-            allowed_sources,
-            provider_matcher,
-            updated_at_fallback,
-        )
-        .await
-        {
-            items.push(item);
-        }
-    }
+    let candidates = candidates
+        .into_iter()
+        .filter(|candidate| {
+            let ts = candidate.updated_at.unwrap_or(OffsetDateTime::UNIX_EPOCH);
+            !anchor_state.should_skip(ts, candidate.id)
+        })
+        .map(|candidate| (candidate.path, candidate.updated_at));
+    let mut more_matches_available = fill_page(
+        &mut items,
+        page_size,
+        candidates,
+        allowed_sources,
+        provider_matcher,
+    )
+    .await;
 
     let reached_scan_cap = scanned_files >= MAX_SCAN_FILES;
     if reached_scan_cap && !items.is_empty() {
@@ -669,29 +681,127 @@ pub fn parse_cursor(token: &str) -> Option<Cursor> {
 }
 
 fn build_next_cursor(items: &[ThreadItem], sort_key: ThreadSortKey) -> Option<Cursor> {
-    let last = items.last()?;
-    let file_name = last.path.file_name()?.to_string_lossy();
+    let (ts, id) = thread_sort_position(items.last()?, sort_key)?;
+    Some(Cursor::new(ts, id))
+}
+
+fn thread_sort_position(
+    item: &ThreadItem,
+    sort_key: ThreadSortKey,
+) -> Option<(OffsetDateTime, Uuid)> {
+    let file_name = item.path.file_name()?.to_string_lossy();
     let (created_ts, id) = parse_timestamp_uuid_from_filename(&file_name)?;
     let ts = match sort_key {
         ThreadSortKey::CreatedAt => created_ts,
         ThreadSortKey::UpdatedAt => {
-            let updated_at = last.updated_at.as_deref()?;
+            let updated_at = item.updated_at.as_deref()?;
             OffsetDateTime::parse(updated_at, &Rfc3339).ok()?
         }
     };
-    Some(Cursor::new(ts, id))
+    Some((ts, id))
 }
 
-async fn build_thread_item(
-    path: PathBuf,
+/// Merges pages listed from two session roots with the same cursor into one
+/// page of at most `page_size` items, newest first.
+pub(crate) fn merge_thread_pages(
+    first: ThreadsPage,
+    second: ThreadsPage,
+    page_size: usize,
+    sort_key: ThreadSortKey,
+) -> ThreadsPage {
+    let more = first.next_cursor.is_some() || second.next_cursor.is_some();
+    let mut items = first.items;
+    items.extend(second.items);
+    items.sort_by_key(|item| Reverse(thread_sort_position(item, sort_key)));
+    let truncated = items.len() > page_size;
+    items.truncate(page_size);
+    let next_cursor = if more || truncated {
+        build_next_cursor(&items, sort_key)
+    } else {
+        None
+    };
+    ThreadsPage {
+        items,
+        next_cursor,
+        num_scanned_files: first.num_scanned_files + second.num_scanned_files,
+        reached_scan_cap: first.reached_scan_cap || second.reached_scan_cap,
+    }
+}
+
+/// Appends items for `candidates`, in order, until `items` holds `page_size`.
+///
+/// Summaries are read for up to the number of missing items at a time, all
+/// concurrently; candidates without a known mtime are stat'ed. Returns true
+/// when candidates were left over once the page filled up.
+async fn fill_page(
+    items: &mut Vec<ThreadItem>,
+    page_size: usize,
+    candidates: impl Iterator<Item = (PathBuf, Option<OffsetDateTime>)>,
     allowed_sources: &[SessionSource],
     provider_matcher: Option<&ProviderMatcher<'_>>,
-    updated_at: Option<String>,
-) -> Option<ThreadItem> {
+) -> bool {
+    let mut candidates = candidates.peekable();
+    let mut summaries = SummaryCache::default();
+    while items.len() < page_size {
+        let batch: Vec<_> = candidates.by_ref().take(page_size - items.len()).collect();
+        if batch.is_empty() {
+            break;
+        }
+        summaries
+            .load_dirs(batch.iter().map(|(path, _)| path.as_path()))
+            .await;
+        let cache = &summaries;
+        let read = join_all(batch.iter().map(|(path, updated_at)| async move {
+            let updated_at = match updated_at {
+                Some(updated_at) => Some(*updated_at),
+                None => file_modified_time(path).await.unwrap_or(None),
+            };
+            (read_summary(path, cache).await, updated_at)
+        }))
+        .await;
+        for ((path, _), (summary, updated_at)) in batch.into_iter().zip(read) {
+            let Some((summary, from_head)) = summary else {
+                continue;
+            };
+            if from_head {
+                summaries.insert_miss(&path, summary.clone());
+            }
+            if let Some(item) = build_thread_item(
+                path,
+                summary,
+                allowed_sources,
+                provider_matcher,
+                updated_at.and_then(format_rfc3339),
+            ) {
+                items.push(item);
+            }
+        }
+    }
+    summaries.save_misses().await;
+    candidates.peek().is_some()
+}
+
+/// Returns the summary of the rollout at `path` from the index, or else read
+/// from the head of the file, flagged `true`. `None` unless the rollout has
+/// session meta and a user message.
+async fn read_summary(path: &Path, summaries: &SummaryCache) -> Option<(RolloutSummary, bool)> {
+    if let Some(summary) = summaries.get(path) {
+        return Some((summary.clone(), false));
+    }
     // Read head and detect message events; stop once meta + user are found.
-    let summary = read_head_summary(&path, HEAD_RECORD_LIMIT)
+    let head = read_head_summary(path, HEAD_RECORD_LIMIT)
         .await
         .unwrap_or_default();
+    (head.saw_session_meta && head.saw_user_event).then(|| (RolloutSummary::from(head), true))
+}
+
+fn build_thread_item(
+    path: PathBuf,
+    summary: RolloutSummary,
+    allowed_sources: &[SessionSource],
+    provider_matcher: Option<&ProviderMatcher<'_>>,
+    updated_at: Option<String>,
+) -> Option<ThreadItem> {
     if !allowed_sources.is_empty()
         && !summary
             .source
@@ -705,41 +815,35 @@ async fn build_thread_item(
     {
         return None;
     }
-    // Apply filters: must have session meta and at least one user message event
-    if summary.saw_session_meta && summary.saw_user_event {
-        let HeadTailSummary {
-            thread_id,
-            first_user_message,
-            cwd,
-            git_branch,
-            git_sha,
-            git_origin_url,
-            source,
-            model_provider,
-            cli_version,
-            created_at,
-            updated_at: mut summary_updated_at,
-            ..
-        } = summary;
-        if summary_updated_at.is_none() {
-            summary_updated_at = updated_at.or_else(|| created_at.clone());
-        }
-        return Some(ThreadItem {
-            path,
-            thread_id,
-            first_user_message,
-            cwd,
-            git_branch,
-            git_sha,
-            git_origin_url,
-            source,
-            model_provider,
-            cli_version,
-            created_at,
-            updated_at: summary_updated_at,
-        });
-    }
-    None
+    let RolloutSummary {
+        thread_id,
+        first_user_message,
+        cwd,
+        git_branch,
+        git_sha,
+        git_origin_url,
+        source,
+        model_provider,
+        cli_version,
+        created_at,
+        tokens_used,
+    } = summary;
+    let updated_at = updated_at.or_else(|| created_at.clone());
+    Some(ThreadItem {
+        path,
+        thread_id,
+        first_user_message,
+        cwd,
+        git_branch,
+        git_sha,
+        git_origin_url,
+        source,
+        model_provider,
+        cli_version,
+        created_at,
+        updated_at,
+        tokens_used,
+    })
 }
 
 /// Collects immediate subdirectories of `parent`, parses their (string) names with `parse`,
@@ -811,7 +915,7 @@ async fn collect_flat_rollout_files(
         let Some(name_str) = file_name.to_str() else {
             continue;
         };
-        if !name_str.starts_with("rollout-") || !name_str.ends_with(".jsonl") {
+        if !is_rollout_file_name(name_str) {
             continue;
         }
         let Some((ts, id)) = parse_timestamp_uuid_from_filename(name_str) else {
@@ -831,7 +935,7 @@ async fn collect_rollout_day_files(
     day_path: &Path,
 ) -> io::Result<Vec<(OffsetDateTime, Uuid, PathBuf)>> {
     let mut day_files = collect_files(day_path, |name_str, path| {
-        if !name_str.starts_with("rollout-") || !name_str.ends_with(".jsonl") {
+        if !is_rollout_file_name(name_str) {
             return None;
         }
 
@@ -844,8 +948,8 @@ async fn collect_rollout_day_files(
 }
 
 pub(crate) fn parse_timestamp_uuid_from_filename(name: &str) -> Option<(OffsetDateTime, Uuid)> {
-    // Expected: rollout-YYYY-MM-DDThh-mm-ss-<uuid>.jsonl
-    let core = name.strip_prefix("rollout-")?.strip_suffix(".jsonl")?;
+    // Expected: rollout-YYYY-MM-DDThh-mm-ss-<uuid>.jsonl (or .jsonl.zst, .jsonl.enc)
+    let core = strip_rollout_suffix(name)?.strip_prefix("rollout-")?;
 
     // Scan from the right for a '-' such that the suffix parses as a UUID.
     let (sep_idx, uuid) = core
@@ -901,7 +1005,7 @@ async fn collect_flat_files_by_updated_at(
         let Some(name_str) = file_name.to_str() else {
             continue;
         };
-        if !name_str.starts_with("rollout-") || !name_str.ends_with(".jsonl") {
+        if !is_rollout_file_name(name_str) {
             continue;
         }
         let Some((_ts, id)) = parse_timestamp_uuid_from_filename(name_str) else {
@@ -989,11 +1093,7 @@ impl<'a> ProviderMatcher<'a> {
 }
 
 async fn read_head_summary(path: &Path, head_limit: usize) -> io::Result<HeadTailSummary> {
-    use tokio::io::AsyncBufReadExt;
-
-    let file = tokio::fs::File::open(path).await?;
-    let reader = tokio::io::BufReader::new(file);
-    let mut lines = reader.lines();
+    let mut lines = RolloutLineReader::open(path).await?;
     let mut summary = HeadTailSummary::default();
     let mut lines_scanned = 0usize;
 
@@ -1073,11 +1173,7 @@ async fn read_head_summary(path: &Path, head_limit: usize) -> io::Result<HeadTai
 /// Read up to `HEAD_RECORD_LIMIT` records from the start of the rollout file at `path`.
 /// This should be enough to produce a summary including the session meta line.
 pub async fn read_head_for_summary(path: &Path) -> io::Result<Vec<serde_json::Value>> {
-    use tokio::io::AsyncBufReadExt;
-
-    let file = tokio::fs::File::open(path).await?;
-    let reader = tokio::io::BufReader::new(file);
-    let mut lines = reader.lines();
+    let mut lines = RolloutLineReader::open(path).await?;
     let mut head = Vec::new();
 
     while head.len() < HEAD_RECORD_LIMIT {
@@ -1110,7 +1206,7 @@ pub async fn read_head_for_summary(path: &Path) -> io::Result<Vec<serde_json::Va
     Ok(head)
 }
 
-fn strip_user_message_prefix(text: &str) -> &str {
+pub(super) fn strip_user_message_prefix(text: &str) -> &str {
     match text.find(USER_MESSAGE_BEGIN) {
         Some(idx) => text[idx + USER_MESSAGE_BEGIN.len()..].trim(),
         None => text.trim(),
@@ -1246,6 +1342,29 @@ pub async fn find_archived_thread_path_by_id_str(
     find_thread_path_by_id_str_in_subdir(codex_home, ARCHIVED_SESSIONS_SUBDIR, id_str).await
 }
 
+/// Locate a thread rollout file by its UUID string under a sessions root
+/// outside `CODEX_HOME`, such as a project's `.codex/sessions`.
+pub(crate) async fn find_thread_path_by_id_str_in_root(
+    root: &Path,
+    id_str: &str,
+) -> io::Result<Option<PathBuf>> {
+    if Uuid::parse_str(id_str).is_err() || !root.exists() {
+        return Ok(None);
+    }
+    // This is safe because we know the values are valid.
+    #[allow(clippy::unwrap_used)]
+    let limit = NonZero::new(1).unwrap();
+    let options = file_search::FileSearchOptions {
+        limit,
+        compute_indices: false,
+        respect_gitignore: false,
+        ..Default::default()
+    };
+    let results = file_search::run(id_str, vec![root.to_path_buf()], options, None)
+        .map_err(|e| io::Error::other(format!("file search failed: {e}")))?;
+    Ok(results.matches.into_iter().next().map(|m| m.full_path()))
+}
+
 /// Extract the `YYYY/MM/DD` directory components from a rollout filename.
 pub fn rollout_date_parts(file_name: &OsStr) -> Option<(String, String, String)> {
     let name = file_name.to_string_lossy();
@@ -1,5 +1,6 @@
 use crate::config::Config;
 use crate::rollout;
+use crate::rollout::compression::is_rollout_file_name;
 use crate::rollout::list::parse_timestamp_uuid_from_filename;
 use crate::rollout::recorder::RolloutRecorder;
 use chrono::DateTime;
@@ -27,8 +28,6 @@ use std::path::PathBuf;
 use tracing::info;
 use tracing::warn;
 
-const ROLLOUT_PREFIX: &str = "rollout-";
-const ROLLOUT_SUFFIX: &str = ".jsonl";
 const BACKFILL_BATCH_SIZE: usize = 200;
 #[cfg(not(test))]
 const BACKFILL_LEASE_SECONDS: i64 = 900;
@@ -75,7 +74,7 @@ pub(crate) fn builder_from_items(
     }
 
     let file_name = rollout_path.file_name()?.to_str()?;
-    if !file_name.starts_with(ROLLOUT_PREFIX) || !file_name.ends_with(ROLLOUT_SUFFIX) {
+    if !is_rollout_file_name(file_name) {
         return None;
     }
     let (created_ts, uuid) = parse_timestamp_uuid_from_filename(file_name)?;
@@ -419,7 +418,7 @@ fn parse_timestamp_to_utc(ts: &str) -> Option<DateTime<Utc>> {
     None
 }
 
-async fn collect_rollout_paths(root: &Path) -> std::io::Result<Vec<PathBuf>> {
+pub(crate) async fn collect_rollout_paths(root: &Path) -> std::io::Result<Vec<PathBuf>> {
     let mut stack = vec![root.to_path_buf()];
     let mut paths = Vec::new();
     while let Some(dir) = stack.pop() {
@@ -463,7 +462,7 @@ async fn collect_rollout_paths(root: &Path) -> std::io::Result<Vec<PathBuf>> {
             let Some(name) = file_name.to_str() else {
                 continue;
             };
-            if name.starts_with(ROLLOUT_PREFIX) && name.ends_with(ROLLOUT_SUFFIX) {
+            if is_rollout_file_name(name) {
                 paths.push(path);
             }
         }
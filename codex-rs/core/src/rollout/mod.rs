@@ -7,16 +7,28 @@ pub const ARCHIVED_SESSIONS_SUBDIR: &str = "archived_sessions";
 pub const INTERACTIVE_SESSION_SOURCES: &[SessionSource] =
     &[SessionSource::Cli, SessionSource::VSCode];
 
+pub(crate) mod compression;
+pub(crate) mod encryption;
 pub(crate) mod error;
+pub(crate) mod history_search;
 pub mod list;
 pub(crate) mod metadata;
 pub(crate) mod policy;
 pub mod recorder;
 pub(crate) mod session_index;
+pub(crate) mod summary_index;
 pub(crate) mod truncation;
 
 pub use codex_protocol::protocol::SessionMeta;
+pub use compression::RolloutCompressionSummary;
+pub use compression::compress_rollouts;
+pub use compression::read_rollout_text;
+pub use encryption::RolloutEncryptionSummary;
+pub use encryption::encrypt_rollouts;
 pub(crate) use error::map_session_init_error;
+pub use history_search::HistoryIndexRefresh;
+pub use history_search::refresh_history_index;
+pub use history_search::search_history;
 pub use list::find_archived_thread_path_by_id_str;
 pub use list::find_thread_path_by_id_str;
 #[deprecated(note = "use find_thread_path_by_id_str")]
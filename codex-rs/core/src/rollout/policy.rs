@@ -1,5 +1,6 @@
 use crate::protocol::EventMsg;
 use crate::protocol::RolloutItem;
+use crate::tools::output_summary::is_summary;
 use codex_protocol::models::ResponseItem;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -92,11 +93,18 @@ fn event_msg_persistence_mode(ev: &EventMsg) -> Option<EventPersistenceMode> {
         | EventMsg::AgentReasoning(_)
         | EventMsg::AgentReasoningRawContent(_)
         | EventMsg::TokenCount(_)
+        | EventMsg::ModelRouted(_)
         | EventMsg::ContextCompacted(_)
+        | EventMsg::ResumeSummarized(_)
         | EventMsg::EnteredReviewMode(_)
         | EventMsg::ExitedReviewMode(_)
         | EventMsg::ThreadRolledBack(_)
         | EventMsg::UndoCompleted(_)
+        | EventMsg::CommitCompleted(_)
+        | EventMsg::PullRequestCompleted(_)
+        | EventMsg::PlanUpdate(_)
+        | EventMsg::QueuedUserMessagesUpdated(_)
+        | EventMsg::BookmarkAdded(_)
         | EventMsg::TurnAborted(_)
         | EventMsg::TurnStarted(_)
         | EventMsg::TurnComplete(_) => Some(EventPersistenceMode::Limited),
@@ -110,6 +118,11 @@ fn event_msg_persistence_mode(ev: &EventMsg) -> Option<EventPersistenceMode> {
                 None
             }
         }
+        // The model only saw a summary of this output, so keep the full text
+        // in every rollout.
+        EventMsg::ExecCommandEnd(event) if is_summary(&event.formatted_output) => {
+            Some(EventPersistenceMode::Limited)
+        }
         EventMsg::Error(_)
         | EventMsg::WebSearchEnd(_)
         | EventMsg::ExecCommandEnd(_)
@@ -120,7 +133,8 @@ fn event_msg_persistence_mode(ev: &EventMsg) -> Option<EventPersistenceMode> {
         | EventMsg::CollabAgentInteractionEnd(_)
         | EventMsg::CollabWaitingEnd(_)
         | EventMsg::CollabCloseEnd(_)
-        | EventMsg::CollabResumeEnd(_) => Some(EventPersistenceMode::Extended),
+        | EventMsg::CollabResumeEnd(_)
+        | EventMsg::DelegateEnd(_) => Some(EventPersistenceMode::Extended),
         EventMsg::Warning(_)
         | EventMsg::AgentMessageDelta(_)
         | EventMsg::AgentReasoningDelta(_)
@@ -129,6 +143,7 @@ fn event_msg_persistence_mode(ev: &EventMsg) -> Option<EventPersistenceMode> {
         | EventMsg::RawResponseItem(_)
         | EventMsg::SessionConfigured(_)
         | EventMsg::ThreadNameUpdated(_)
+        | EventMsg::ConversationForked(_)
         | EventMsg::McpToolCallBegin(_)
         | EventMsg::WebSearchBegin(_)
         | EventMsg::ExecCommandBegin(_)
@@ -141,6 +156,11 @@ fn event_msg_persistence_mode(ev: &EventMsg) -> Option<EventPersistenceMode> {
         | EventMsg::ApplyPatchApprovalRequest(_)
         | EventMsg::BackgroundEvent(_)
         | EventMsg::StreamError(_)
+        | EventMsg::RetriesExhausted(_)
+        | EventMsg::UsageQuotaExceeded(_)
+        | EventMsg::RequestBudget(_)
+        | EventMsg::ReadPrefetchStats(_)
+        | EventMsg::TurnLatency(_)
         | EventMsg::PatchApplyBegin(_)
         | EventMsg::TurnDiff(_)
         | EventMsg::GetHistoryEntryResponse(_)
@@ -152,7 +172,6 @@ fn event_msg_persistence_mode(ev: &EventMsg) -> Option<EventPersistenceMode> {
         | EventMsg::ListSkillsResponse(_)
         | EventMsg::ListRemoteSkillsResponse(_)
         | EventMsg::RemoteSkillDownloaded(_)
-        | EventMsg::PlanUpdate(_)
         | EventMsg::ShutdownComplete
         | EventMsg::DeprecationNotice(_)
         | EventMsg::ItemStarted(_)
@@ -165,6 +184,7 @@ fn event_msg_persistence_mode(ev: &EventMsg) -> Option<EventPersistenceMode> {
         | EventMsg::CollabAgentInteractionBegin(_)
         | EventMsg::CollabWaitingBegin(_)
         | EventMsg::CollabCloseBegin(_)
-        | EventMsg::CollabResumeBegin(_) => None,
+        | EventMsg::CollabResumeBegin(_)
+        | EventMsg::DelegateBegin(_) => None,
     }
 }
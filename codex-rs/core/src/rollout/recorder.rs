@@ -7,6 +7,7 @@ use std::path::Path;
 use std::path::PathBuf;
 
 use chrono::SecondsFormat;
+use codex_keyring_store::DefaultKeyringStore;
 use codex_protocol::ThreadId;
 use codex_protocol::dynamic_tools::DynamicToolSpec;
 use codex_protocol::models::BaseInstructions;
@@ -25,21 +26,31 @@ use tracing::warn;
 
 use super::ARCHIVED_SESSIONS_SUBDIR;
 use super::SESSIONS_SUBDIR;
+use super::compression;
+use super::compression::ZstdLineEncoder;
+use super::encryption;
 use super::list::Cursor;
 use super::list::ThreadItem;
 use super::list::ThreadListConfig;
 use super::list::ThreadListLayout;
 use super::list::ThreadSortKey;
 use super::list::ThreadsPage;
+use super::list::find_thread_path_by_id_str;
+use super::list::find_thread_path_by_id_str_in_root;
 use super::list::get_threads;
 use super::list::get_threads_in_root;
+use super::list::merge_thread_pages;
 use super::list::parse_cursor;
 use super::list::parse_timestamp_uuid_from_filename;
 use super::metadata;
 use super::policy::EventPersistenceMode;
 use super::policy::is_persisted_response_item;
+use super::summary_index::SummaryRecorder;
 use crate::config::Config;
+use crate::config::types::RolloutCompression;
 use crate::default_client::originator;
+use crate::encryption::DataKey;
+use crate::encryption::RecordCipher;
 use crate::git_info::collect_git_info;
 use crate::path_utils;
 use crate::state_db;
@@ -236,8 +247,8 @@ impl RolloutRecorder {
             )
             .await?
         } else {
-            get_threads(
-                codex_home,
+            get_session_threads(
+                config,
                 fs_page_size,
                 cursor,
                 sort_key,
@@ -329,8 +340,8 @@ impl RolloutRecorder {
 
         let mut cursor = cursor.cloned();
         loop {
-            let page = get_threads(
-                codex_home,
+            let page = get_session_threads(
+                config,
                 page_size,
                 cursor.as_ref(),
                 sort_key,
@@ -349,6 +360,21 @@ impl RolloutRecorder {
         }
     }
 
+    /// Locate a thread rollout by its UUID string under `~/.codex/sessions`
+    /// and, with project session storage, the project's `.codex/sessions`.
+    pub async fn find_thread_path(
+        config: &Config,
+        id_str: &str,
+    ) -> std::io::Result<Option<PathBuf>> {
+        if let Some(path) = find_thread_path_by_id_str(&config.codex_home, id_str).await? {
+            return Ok(Some(path));
+        }
+        match config.project_sessions_dir.as_deref() {
+            Some(root) => find_thread_path_by_id_str_in_root(root, id_str).await,
+            None => Ok(None),
+        }
+    }
+
     /// Attempt to create a new [`RolloutRecorder`].
     ///
     /// For newly created sessions, this precomputes path/metadata and defers
@@ -412,20 +438,41 @@ impl RolloutRecorder {
                 RolloutRecorderParams::Resume {
                     path,
                     event_persistence_mode,
-                } => (
-                    Some(
-                        tokio::fs::OpenOptions::new()
-                            .append(true)
-                            .open(&path)
-                            .await?,
-                    ),
-                    None,
-                    path,
-                    None,
-                    event_persistence_mode,
-                ),
+                } => {
+                    if compression::is_compressed_rollout(&path) {
+                        compression::repair_compressed_rollout(&path).await?;
+                    }
+                    if encryption::is_encrypted_rollout(&path) {
+                        encryption::repair_encrypted_rollout(&path).await?;
+                    }
+                    (
+                        Some(
+                            tokio::fs::OpenOptions::new()
+                                .append(true)
+                                .open(&path)
+                                .await?,
+                        ),
+                        None,
+                        path,
+                        None,
+                        event_persistence_mode,
+                    )
+                }
             };
 
+        // New encrypted rollouts use this CODEX_HOME's key; resumed ones keep
+        // the key and file id named in their header.
+        let cipher = if !encryption::is_encrypted_rollout(&rollout_path) {
+            None
+        } else if file.is_some() {
+            Some(encryption::rollout_cipher(&rollout_path).await?)
+        } else {
+            Some(RecordCipher::create(DataKey::load_or_create(
+                &config.codex_home,
+                &DefaultKeyringStore,
+            )?)?)
+        };
+
         // Clone the cwd for the spawned task to collect git info asynchronously
         let cwd = config.cwd.clone();
 
@@ -439,6 +486,7 @@ impl RolloutRecorder {
         // driver instead of blocking the runtime.
         tokio::task::spawn(rollout_writer(
             file,
+            cipher,
             deferred_log_file_info,
             rx,
             meta,
@@ -515,7 +563,7 @@ impl RolloutRecorder {
         path: &Path,
     ) -> std::io::Result<(Vec<RolloutItem>, Option<ThreadId>, usize)> {
         trace!("Resuming rollout from {path:?}");
-        let text = tokio::fs::read_to_string(path).await?;
+        let text = compression::read_rollout_text(path).await?;
         if text.trim().is_empty() {
             return Err(IoError::other("empty session file"));
         }
@@ -609,6 +657,52 @@ impl RolloutRecorder {
     }
 }
 
+/// Lists `~/.codex/sessions`, merged with the project's `.codex/sessions`
+/// when sessions are stored in the project.
+#[allow(clippy::too_many_arguments)]
+async fn get_session_threads(
+    config: &Config,
+    page_size: usize,
+    cursor: Option<&Cursor>,
+    sort_key: ThreadSortKey,
+    allowed_sources: &[SessionSource],
+    model_providers: Option<&[String]>,
+    default_provider: &str,
+) -> std::io::Result<ThreadsPage> {
+    let home_page = get_threads(
+        &config.codex_home,
+        page_size,
+        cursor,
+        sort_key,
+        allowed_sources,
+        model_providers,
+        default_provider,
+    )
+    .await?;
+    let Some(project_sessions_dir) = config.project_sessions_dir.clone() else {
+        return Ok(home_page);
+    };
+    let project_page = get_threads_in_root(
+        project_sessions_dir,
+        page_size,
+        cursor,
+        sort_key,
+        ThreadListConfig {
+            allowed_sources,
+            model_providers,
+            default_provider,
+            layout: ThreadListLayout::NestedByDate,
+        },
+    )
+    .await?;
+    Ok(merge_thread_pages(
+        home_page,
+        project_page,
+        page_size,
+        sort_key,
+    ))
+}
+
 fn truncate_fs_page(
     mut page: ThreadsPage,
     page_size: usize,
@@ -634,6 +728,10 @@ struct LogFileInfo {
     /// Full path to the rollout file.
     path: PathBuf,
 
+    /// Project sessions directory that gets a `.gitignore` when the file is
+    /// created.
+    gitignore_dir: Option<PathBuf>,
+
     /// Session ID (also embedded in filename).
     conversation_id: ThreadId,
 
@@ -645,11 +743,13 @@ fn precompute_log_file_info(
     config: &Config,
     conversation_id: ThreadId,
 ) -> std::io::Result<LogFileInfo> {
-    // Resolve ~/.codex/sessions/YYYY/MM/DD path.
+    // Resolve ~/.codex/sessions/YYYY/MM/DD (or <project>/.codex/sessions/...) path.
     let timestamp = OffsetDateTime::now_local()
         .map_err(|e| IoError::other(format!("failed to get local time: {e}")))?;
-    let mut dir = config.codex_home.clone();
-    dir.push(SESSIONS_SUBDIR);
+    let mut dir = config
+        .project_sessions_dir
+        .clone()
+        .unwrap_or_else(|| config.codex_home.join(SESSIONS_SUBDIR));
     dir.push(timestamp.year().to_string());
     dir.push(format!("{:02}", u8::from(timestamp.month())));
     dir.push(format!("{:02}", timestamp.day()));
@@ -662,12 +762,26 @@ fn precompute_log_file_info(
         .format(format)
         .map_err(|e| IoError::other(format!("failed to format timestamp: {e}")))?;
 
-    let filename = format!("rollout-{date_str}-{conversation_id}.jsonl");
+    let suffix = if config.encrypt_rollouts {
+        encryption::ENCRYPTED_ROLLOUT_FILE_SUFFIX
+    } else {
+        match config.rollout_compression {
+            RolloutCompression::None => compression::ROLLOUT_FILE_SUFFIX,
+            RolloutCompression::Zstd => compression::COMPRESSED_ROLLOUT_FILE_SUFFIX,
+        }
+    };
+    let filename = format!("rollout-{date_str}-{conversation_id}{suffix}");
 
     let path = dir.join(filename);
 
+    let gitignore_dir = config
+        .project_sessions_dir
+        .clone()
+        .filter(|_| !config.track_project_sessions);
+
     Ok(LogFileInfo {
         path,
+        gitignore_dir,
         conversation_id,
         timestamp,
     })
@@ -687,9 +801,23 @@ fn open_log_file(path: &Path) -> std::io::Result<File> {
         .open(path)
 }
 
+/// Keeps a project's sessions out of git unless the directory already has a
+/// `.gitignore` (for example one the team edited to commit them).
+fn ensure_sessions_gitignore(dir: &Path) -> std::io::Result<()> {
+    let path = dir.join(".gitignore");
+    if path.exists() {
+        return Ok(());
+    }
+    fs::write(
+        path,
+        "# Codex session history. Set `track_project_sessions = true` to commit it.\n*\n",
+    )
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn rollout_writer(
     file: Option<tokio::fs::File>,
+    mut cipher: Option<RecordCipher>,
     mut deferred_log_file_info: Option<LogFileInfo>,
     mut rx: mpsc::Receiver<RolloutCmd>,
     mut meta: Option<SessionMeta>,
@@ -699,8 +827,9 @@ async fn rollout_writer(
     mut state_builder: Option<ThreadMetadataBuilder>,
     default_provider: String,
 ) -> std::io::Result<()> {
-    let mut writer = file.map(|file| JsonlWriter { file });
+    let mut writer = file.map(|file| JsonlWriter::new(file, &rollout_path, cipher.take()));
     let mut buffered_items = Vec::<RolloutItem>::new();
+    let mut summary = SummaryRecorder::load(&rollout_path).await;
     if let Some(builder) = state_builder.as_mut() {
         builder.rollout_path = rollout_path.clone();
     }
@@ -717,6 +846,7 @@ async fn rollout_writer(
             &rollout_path,
             state_db_ctx.as_deref(),
             &mut state_builder,
+            &mut summary,
             default_provider.as_str(),
         )
         .await?;
@@ -745,9 +875,11 @@ async fn rollout_writer(
                     &rollout_path,
                     state_db_ctx.as_deref(),
                     &mut state_builder,
+                    &mut summary,
                     default_provider.as_str(),
                 )
                 .await?;
+                record_summary(&mut summary, false).await;
             }
             RolloutCmd::Persist { ack } => {
                 if writer.is_none() {
@@ -758,9 +890,15 @@ async fn rollout_writer(
                             ));
                         };
                         let file = open_log_file(log_file_info.path.as_path())?;
-                        writer = Some(JsonlWriter {
-                            file: tokio::fs::File::from_std(file),
-                        });
+                        if let Some(dir) = log_file_info.gitignore_dir.as_deref() {
+                            ensure_sessions_gitignore(dir)?;
+                        }
+                        let new_writer = writer.insert(JsonlWriter::new(
+                            tokio::fs::File::from_std(file),
+                            &rollout_path,
+                            cipher.take(),
+                        ));
+                        new_writer.write_encryption_header().await?;
 
                         if let Some(session_meta) = meta.take() {
                             write_session_meta(
@@ -770,6 +908,7 @@ async fn rollout_writer(
                                 &rollout_path,
                                 state_db_ctx.as_deref(),
                                 &mut state_builder,
+                                &mut summary,
                                 default_provider.as_str(),
                             )
                             .await?;
@@ -782,6 +921,7 @@ async fn rollout_writer(
                                 &rollout_path,
                                 state_db_ctx.as_deref(),
                                 &mut state_builder,
+                                &mut summary,
                                 default_provider.as_str(),
                             )
                             .await?;
@@ -796,6 +936,7 @@ async fn rollout_writer(
                         let _ = ack.send(());
                         return Err(err);
                     }
+                    record_summary(&mut summary, false).await;
                 }
                 let _ = ack.send(());
             }
@@ -810,14 +951,32 @@ async fn rollout_writer(
                 let _ = ack.send(());
             }
             RolloutCmd::Shutdown { ack } => {
+                if let Some(writer) = writer.as_mut()
+                    && let Err(e) = writer.finish().await
+                {
+                    let _ = ack.send(());
+                    return Err(e);
+                }
+                record_summary(&mut summary, true).await;
                 let _ = ack.send(());
             }
         }
     }
 
+    if let Some(writer) = writer.as_mut() {
+        writer.finish().await?;
+    }
+    record_summary(&mut summary, true).await;
     Ok(())
 }
 
+async fn record_summary(summary: &mut Option<SummaryRecorder>, finished: bool) {
+    if let Some(summary) = summary.as_mut() {
+        summary.record(finished).await;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn write_session_meta(
     mut writer: Option<&mut JsonlWriter>,
     session_meta: SessionMeta,
@@ -825,6 +984,7 @@ async fn write_session_meta(
     rollout_path: &Path,
     state_db_ctx: Option<&StateRuntime>,
     state_builder: &mut Option<ThreadMetadataBuilder>,
+    summary: &mut Option<SummaryRecorder>,
     default_provider: &str,
 ) -> std::io::Result<()> {
     let git_info = collect_git_info(cwd).await;
@@ -839,6 +999,9 @@ async fn write_session_meta(
     let rollout_item = RolloutItem::SessionMeta(session_meta_line);
     if let Some(writer) = writer.as_mut() {
         writer.write_rollout_item(&rollout_item).await?;
+        if let Some(summary) = summary.as_mut() {
+            summary.observe(&rollout_item);
+        }
     }
     state_db::reconcile_rollout(
         state_db_ctx,
@@ -858,11 +1021,15 @@ async fn write_and_reconcile_items(
     rollout_path: &Path,
     state_db_ctx: Option<&StateRuntime>,
     state_builder: &mut Option<ThreadMetadataBuilder>,
+    summary: &mut Option<SummaryRecorder>,
     default_provider: &str,
 ) -> std::io::Result<()> {
     if let Some(writer) = writer.as_mut() {
         for item in items {
             writer.write_rollout_item(item).await?;
+            if let Some(summary) = summary.as_mut() {
+                summary.observe(item);
+            }
         }
     }
     if let Some(builder) = state_builder.as_mut() {
@@ -882,6 +1049,10 @@ async fn write_and_reconcile_items(
 
 struct JsonlWriter {
     file: tokio::fs::File,
+    /// Set when the rollout is stored zstd-compressed.
+    encoder: Option<ZstdLineEncoder>,
+    /// Set when the rollout is stored encrypted.
+    cipher: Option<RecordCipher>,
 }
 
 #[derive(serde::Serialize)]
@@ -892,6 +1063,23 @@ struct RolloutLineRef<'a> {
 }
 
 impl JsonlWriter {
+    fn new(file: tokio::fs::File, path: &Path, cipher: Option<RecordCipher>) -> Self {
+        let encoder = compression::is_compressed_rollout(path).then(ZstdLineEncoder::new);
+        Self {
+            file,
+            encoder,
+            cipher,
+        }
+    }
+
+    /// Starts a new encrypted rollout with the header naming its key.
+    async fn write_encryption_header(&mut self) -> std::io::Result<()> {
+        if let Some(cipher) = self.cipher.as_ref() {
+            self.file.write_all(cipher.header_line().as_bytes()).await?;
+        }
+        Ok(())
+    }
+
     async fn write_rollout_item(&mut self, rollout_item: &RolloutItem) -> std::io::Result<()> {
         let timestamp_format: &[FormatItem] = format_description!(
             "[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:3]Z"
@@ -908,11 +1096,30 @@ impl JsonlWriter {
     }
     async fn write_line(&mut self, item: &impl serde::Serialize) -> std::io::Result<()> {
         let mut json = serde_json::to_string(item)?;
+        if let Some(cipher) = self.cipher.as_mut() {
+            json = cipher.seal_next(json.as_bytes())?;
+        }
         json.push('\n');
-        self.file.write_all(json.as_bytes()).await?;
+        match self.encoder.as_mut() {
+            Some(encoder) => {
+                let bytes = encoder.encode_line(json.as_bytes())?;
+                self.file.write_all(&bytes).await?;
+            }
+            None => self.file.write_all(json.as_bytes()).await?,
+        }
         self.file.flush().await?;
         Ok(())
     }
+
+    /// Ends the open zstd frame so the file is a complete stream; later
+    /// writes start a new frame.
+    async fn finish(&mut self) -> std::io::Result<()> {
+        if let Some(encoder) = self.encoder.as_mut() {
+            let bytes = encoder.finish_frame()?;
+            self.file.write_all(&bytes).await?;
+        }
+        self.file.flush().await
+    }
 }
 
 impl From<codex_state::ThreadsPage> for ThreadsPage {
@@ -936,6 +1143,7 @@ impl From<codex_state::ThreadsPage> for ThreadsPage {
                 cli_version: Some(item.cli_version),
                 created_at: Some(item.created_at.to_rfc3339_opts(SecondsFormat::Secs, true)),
                 updated_at: Some(item.updated_at.to_rfc3339_opts(SecondsFormat::Secs, true)),
+                tokens_used: Some(item.tokens_used),
             })
             .collect();
         Self {
@@ -1169,6 +1377,69 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn project_session_storage_writes_and_discovers_project_rollouts() -> std::io::Result<()>
+    {
+        let home = TempDir::new().expect("temp dir");
+        let project = TempDir::new().expect("temp dir");
+        let mut config = ConfigBuilder::default()
+            .codex_home(home.path().to_path_buf())
+            .build()
+            .await?;
+        config.features.disable(Feature::Sqlite);
+        let project_sessions_dir = project.path().join(".codex").join(SESSIONS_SUBDIR);
+        config.project_sessions_dir = Some(project_sessions_dir.clone());
+
+        let home_rollout =
+            write_session_file(home.path(), "2025-01-03T12-00-00", Uuid::from_u128(9020))?;
+        let project_uuid = Uuid::from_u128(9021);
+        let project_rollout = write_session_file(
+            &project.path().join(".codex"),
+            "2025-01-02T12-00-00",
+            project_uuid,
+        )?;
+
+        let default_provider = config.model_provider_id.clone();
+        let page = RolloutRecorder::list_threads(
+            &config,
+            10,
+            None,
+            ThreadSortKey::CreatedAt,
+            &[],
+            None,
+            default_provider.as_str(),
+        )
+        .await?;
+        let paths: Vec<PathBuf> = page.items.into_iter().map(|item| item.path).collect();
+        assert_eq!(paths, vec![home_rollout, project_rollout.clone()]);
+        assert_eq!(
+            RolloutRecorder::find_thread_path(&config, &project_uuid.to_string()).await?,
+            Some(project_rollout)
+        );
+
+        let recorder = RolloutRecorder::new(
+            &config,
+            RolloutRecorderParams::new(
+                ThreadId::new(),
+                None,
+                SessionSource::Exec,
+                BaseInstructions::default(),
+                Vec::new(),
+                EventPersistenceMode::Limited,
+            ),
+            None,
+            None,
+        )
+        .await?;
+        recorder.persist().await?;
+        assert!(recorder.rollout_path().starts_with(&project_sessions_dir));
+        assert!(recorder.rollout_path().exists());
+        let gitignore = std::fs::read_to_string(project_sessions_dir.join(".gitignore"))?;
+        assert!(gitignore.lines().any(|line| line == "*"));
+        recorder.shutdown().await?;
+        Ok(())
+    }
+
     #[tokio::test]
     async fn list_threads_db_enabled_drops_missing_rollout_paths() -> std::io::Result<()> {
         let home = TempDir::new().expect("temp dir");
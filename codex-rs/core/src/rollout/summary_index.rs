@@ -0,0 +1,360 @@
+//! Index of rollout summaries, so listing threads does not re-read every rollout.
+//!
+//! Listing needs the session meta and first user message from the head of
+//! each rollout, and reading them means opening, and possibly decompressing,
+//! every listed file. Each directory of rollouts keeps a `summaries.jsonl`
+//! next to them instead: the rollout writer appends a rollout's summary, and
+//! later its token total, as soon as the summary is complete, and listing
+//! appends the summaries it had to read from rollouts written before the
+//! index existed. The file is append-only; the last entry for a file wins.
+//!
+//! Summaries of encrypted rollouts are never indexed, since the index is
+//! plain text.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::SessionMetaLine;
+use codex_protocol::protocol::SessionSource;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncWriteExt;
+use tracing::warn;
+
+use super::encryption::is_encrypted_rollout;
+use super::list::strip_user_message_prefix;
+
+pub(crate) const SUMMARY_INDEX_FILE: &str = "summaries.jsonl";
+
+/// What listing shows for a rollout, as recorded in the index.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct RolloutSummary {
+    pub(crate) thread_id: Option<ThreadId>,
+    pub(crate) first_user_message: Option<String>,
+    pub(crate) cwd: Option<PathBuf>,
+    pub(crate) git_branch: Option<String>,
+    pub(crate) git_sha: Option<String>,
+    pub(crate) git_origin_url: Option<String>,
+    pub(crate) source: Option<SessionSource>,
+    pub(crate) model_provider: Option<String>,
+    pub(crate) cli_version: Option<String>,
+    pub(crate) created_at: Option<String>,
+    /// Total tokens used by the thread, as of the last time it was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) tokens_used: Option<i64>,
+}
+
+impl RolloutSummary {
+    fn apply_session_meta(&mut self, session_meta_line: &SessionMetaLine) {
+        let meta = &session_meta_line.meta;
+        let git = session_meta_line.git.as_ref();
+        self.thread_id = Some(meta.id);
+        self.cwd = Some(meta.cwd.clone());
+        self.git_branch = git.and_then(|git| git.branch.clone());
+        self.git_sha = git.and_then(|git| git.commit_hash.clone());
+        self.git_origin_url = git.and_then(|git| git.repository_url.clone());
+        self.source = Some(meta.source.clone());
+        self.model_provider = meta.model_provider.clone();
+        self.cli_version = Some(meta.cli_version.clone());
+        self.created_at = Some(meta.timestamp.clone());
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SummaryIndexEntry {
+    /// Name of the rollout file, in the directory holding the index.
+    file_name: String,
+    #[serde(flatten)]
+    summary: RolloutSummary,
+}
+
+/// Loads the summaries indexed in `dir`, by rollout file name. A missing or
+/// unreadable index is empty.
+async fn load_directory_summaries(dir: &Path) -> HashMap<String, RolloutSummary> {
+    let mut summaries = HashMap::new();
+    let Ok(file) = tokio::fs::File::open(dir.join(SUMMARY_INDEX_FILE)).await else {
+        return summaries;
+    };
+    let mut lines = tokio::io::BufReader::new(file).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if let Ok(entry) = serde_json::from_str::<SummaryIndexEntry>(trimmed) {
+            summaries.insert(entry.file_name, entry.summary);
+        }
+    }
+    summaries
+}
+
+async fn append_directory_summaries(dir: &Path, entries: &[SummaryIndexEntry]) -> io::Result<()> {
+    let mut lines = String::new();
+    for entry in entries {
+        lines.push_str(&serde_json::to_string(entry).map_err(io::Error::other)?);
+        lines.push('\n');
+    }
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(SUMMARY_INDEX_FILE))
+        .await?;
+    file.write_all(lines.as_bytes()).await?;
+    file.flush().await
+}
+
+fn split_rollout_path(path: &Path) -> Option<(&Path, &str)> {
+    Some((path.parent()?, path.file_name()?.to_str()?))
+}
+
+/// Indexed summaries loaded while listing, plus the summaries that had to be
+/// read from rollout heads and are added to the index afterwards.
+#[derive(Default)]
+pub(crate) struct SummaryCache {
+    dirs: HashMap<PathBuf, HashMap<String, RolloutSummary>>,
+    misses: HashMap<PathBuf, Vec<SummaryIndexEntry>>,
+}
+
+impl SummaryCache {
+    /// Loads the index of each directory holding one of `paths`, unless it
+    /// was loaded already.
+    pub(crate) async fn load_dirs<'a>(&mut self, paths: impl IntoIterator<Item = &'a Path>) {
+        for path in paths {
+            let Some((dir, _)) = split_rollout_path(path) else {
+                continue;
+            };
+            if !self.dirs.contains_key(dir) {
+                let summaries = load_directory_summaries(dir).await;
+                self.dirs.insert(dir.to_path_buf(), summaries);
+            }
+        }
+    }
+
+    pub(crate) fn get(&self, path: &Path) -> Option<&RolloutSummary> {
+        let (dir, file_name) = split_rollout_path(path)?;
+        self.dirs.get(dir)?.get(file_name)
+    }
+
+    /// Remembers a summary read from the head of the rollout at `path`.
+    pub(crate) fn insert_miss(&mut self, path: &Path, summary: RolloutSummary) {
+        if is_encrypted_rollout(path) {
+            return;
+        }
+        let Some((dir, file_name)) = split_rollout_path(path) else {
+            return;
+        };
+        self.misses
+            .entry(dir.to_path_buf())
+            .or_default()
+            .push(SummaryIndexEntry {
+                file_name: file_name.to_string(),
+                summary,
+            });
+    }
+
+    /// Adds the summaries read from rollout heads to their directories'
+    /// indexes, so the next listing finds them there.
+    pub(crate) async fn save_misses(self) {
+        for (dir, entries) in self.misses {
+            if let Err(err) = append_directory_summaries(&dir, &entries).await {
+                warn!(
+                    "failed to update rollout summaries in {}: {err}",
+                    dir.display()
+                );
+            }
+        }
+    }
+}
+
+/// Keeps the indexed summary of the rollout being written up to date.
+pub(crate) struct SummaryRecorder {
+    dir: PathBuf,
+    file_name: String,
+    summary: RolloutSummary,
+    saw_session_meta: bool,
+    saw_user_event: bool,
+    /// The listed fields changed since the summary was last appended.
+    summary_changed: bool,
+    /// The token total changed since the summary was last appended.
+    tokens_changed: bool,
+}
+
+impl SummaryRecorder {
+    /// Starts from the indexed summary of the rollout at `rollout_path`, if
+    /// any, as when resuming. `None` for encrypted rollouts.
+    pub(crate) async fn load(rollout_path: &Path) -> Option<Self> {
+        if is_encrypted_rollout(rollout_path) {
+            return None;
+        }
+        let (dir, file_name) = split_rollout_path(rollout_path)?;
+        let indexed = load_directory_summaries(dir).await.remove(file_name);
+        Some(Self {
+            dir: dir.to_path_buf(),
+            file_name: file_name.to_string(),
+            saw_session_meta: indexed.is_some(),
+            saw_user_event: indexed.is_some(),
+            summary: indexed.unwrap_or_default(),
+            summary_changed: false,
+            tokens_changed: false,
+        })
+    }
+
+    /// Updates the summary with an item written to the rollout.
+    pub(crate) fn observe(&mut self, item: &RolloutItem) {
+        match item {
+            RolloutItem::SessionMeta(session_meta_line) if !self.saw_session_meta => {
+                self.summary.apply_session_meta(session_meta_line);
+                self.saw_session_meta = true;
+                self.summary_changed = true;
+            }
+            RolloutItem::EventMsg(EventMsg::UserMessage(user)) if !self.saw_user_event => {
+                let message = strip_user_message_prefix(user.message.as_str());
+                if !message.is_empty() {
+                    self.summary.first_user_message = Some(message.to_string());
+                }
+                self.saw_user_event = true;
+                self.summary_changed = true;
+            }
+            RolloutItem::EventMsg(EventMsg::TokenCount(event)) => {
+                let Some(info) = event.info.as_ref() else {
+                    return;
+                };
+                let tokens_used = Some(info.total_token_usage.total_tokens.max(0));
+                if self.summary.tokens_used != tokens_used {
+                    self.summary.tokens_used = tokens_used;
+                    self.tokens_changed = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Appends the summary to the index once it is complete and has changed.
+    /// Token totals change every turn, so on their own they are only
+    /// appended when the rollout is `finished`.
+    pub(crate) async fn record(&mut self, finished: bool) {
+        if !self.saw_session_meta || !self.saw_user_event {
+            return;
+        }
+        if !self.summary_changed && !(finished && self.tokens_changed) {
+            return;
+        }
+        let entry = SummaryIndexEntry {
+            file_name: self.file_name.clone(),
+            summary: self.summary.clone(),
+        };
+        match append_directory_summaries(&self.dir, std::slice::from_ref(&entry)).await {
+            Ok(()) => {
+                self.summary_changed = false;
+                self.tokens_changed = false;
+            }
+            Err(err) => warn!(
+                "failed to record the summary of {}: {err}",
+                self.dir.join(&self.file_name).display()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::SessionMeta;
+    use codex_protocol::protocol::TokenCountEvent;
+    use codex_protocol::protocol::TokenUsage;
+    use codex_protocol::protocol::TokenUsageInfo;
+    use codex_protocol::protocol::UserMessageEvent;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn session_meta_item() -> RolloutItem {
+        RolloutItem::SessionMeta(SessionMetaLine {
+            meta: SessionMeta {
+                id: ThreadId::new(),
+                timestamp: "2025-06-01T08:00:00.000Z".to_string(),
+                cwd: PathBuf::from("/repo"),
+                cli_version: "1.2.3".to_string(),
+                source: SessionSource::Cli,
+                model_provider: Some("openai".to_string()),
+                ..Default::default()
+            },
+            git: None,
+        })
+    }
+
+    fn user_message_item(message: &str) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+            message: message.to_string(),
+            images: None,
+            text_elements: Vec::new(),
+            local_images: Vec::new(),
+        }))
+    }
+
+    fn token_count_item(total_tokens: i64) -> RolloutItem {
+        let usage = TokenUsage {
+            total_tokens,
+            ..Default::default()
+        };
+        RolloutItem::EventMsg(EventMsg::TokenCount(TokenCountEvent {
+            info: Some(TokenUsageInfo {
+                total_token_usage: usage.clone(),
+                last_token_usage: usage,
+                model_context_window: None,
+            }),
+            rate_limits: None,
+        }))
+    }
+
+    #[tokio::test]
+    async fn recorder_appends_summary_once_complete_and_tokens_when_finished() {
+        let temp = TempDir::new().expect("tempdir");
+        let path = temp.path().join("rollout-2025-06-01T08-00-00-x.jsonl");
+        let mut recorder = SummaryRecorder::load(&path).await.expect("plain rollout");
+
+        recorder.observe(&session_meta_item());
+        recorder.record(false).await;
+        let mut cache = SummaryCache::default();
+        cache.load_dirs([path.as_path()]).await;
+        assert_eq!(cache.get(&path), None);
+
+        recorder.observe(&user_message_item("fix the build"));
+        recorder.observe(&token_count_item(120));
+        recorder.record(false).await;
+        recorder.observe(&token_count_item(480));
+        recorder.record(false).await;
+        let mut cache = SummaryCache::default();
+        cache.load_dirs([path.as_path()]).await;
+        let summary = cache.get(&path).expect("indexed summary");
+        assert_eq!(summary.first_user_message.as_deref(), Some("fix the build"));
+        assert_eq!(summary.cwd, Some(PathBuf::from("/repo")));
+        assert_eq!(summary.tokens_used, Some(120));
+
+        recorder.record(true).await;
+        let mut resumed = SummaryRecorder::load(&path).await.expect("plain rollout");
+        assert_eq!(resumed.summary.tokens_used, Some(480));
+        resumed.observe(&user_message_item("and the tests"));
+        assert_eq!(
+            resumed.summary.first_user_message.as_deref(),
+            Some("fix the build")
+        );
+    }
+
+    #[tokio::test]
+    async fn encrypted_rollouts_are_not_indexed() {
+        let temp = TempDir::new().expect("tempdir");
+        let path = temp.path().join("rollout-2025-06-01T08-00-00-x.jsonl.enc");
+        assert!(SummaryRecorder::load(&path).await.is_none());
+
+        let mut cache = SummaryCache::default();
+        cache.insert_miss(&path, RolloutSummary::default());
+        cache.save_misses().await;
+        assert!(!temp.path().join(SUMMARY_INDEX_FILE).exists());
+    }
+}
@@ -25,6 +25,7 @@ use crate::rollout::list::ThreadsPage;
 use crate::rollout::list::get_threads;
 use crate::rollout::list::read_head_for_summary;
 use crate::rollout::rollout_date_parts;
+use crate::rollout::summary_index::SUMMARY_INDEX_FILE;
 use anyhow::Result;
 use codex_protocol::ThreadId;
 use codex_protocol::models::ContentItem;
@@ -564,6 +565,7 @@ async fn test_list_conversations_latest_first() {
                 cli_version: Some("test_version".to_string()),
                 created_at: Some("2025-01-03T12-00-00".into()),
                 updated_at: updated_times.first().cloned().flatten(),
+                tokens_used: None,
             },
             ThreadItem {
                 path: p2,
@@ -578,6 +580,7 @@ async fn test_list_conversations_latest_first() {
                 cli_version: Some("test_version".to_string()),
                 created_at: Some("2025-01-02T12-00-00".into()),
                 updated_at: updated_times.get(1).cloned().flatten(),
+                tokens_used: None,
             },
             ThreadItem {
                 path: p3,
@@ -592,6 +595,7 @@ async fn test_list_conversations_latest_first() {
                 cli_version: Some("test_version".to_string()),
                 created_at: Some("2025-01-01T12-00-00".into()),
                 updated_at: updated_times.get(2).cloned().flatten(),
+                tokens_used: None,
             },
         ],
         next_cursor: None,
@@ -699,6 +703,7 @@ async fn test_pagination_cursor() {
                 cli_version: Some("test_version".to_string()),
                 created_at: Some("2025-03-05T09-00-00".into()),
                 updated_at: updated_page1.first().cloned().flatten(),
+                tokens_used: None,
             },
             ThreadItem {
                 path: p4,
@@ -713,6 +718,7 @@ async fn test_pagination_cursor() {
                 cli_version: Some("test_version".to_string()),
                 created_at: Some("2025-03-04T09-00-00".into()),
                 updated_at: updated_page1.get(1).cloned().flatten(),
+                tokens_used: None,
             },
         ],
         next_cursor: Some(expected_cursor1.clone()),
@@ -763,6 +769,7 @@ async fn test_pagination_cursor() {
                 cli_version: Some("test_version".to_string()),
                 created_at: Some("2025-03-03T09-00-00".into()),
                 updated_at: updated_page2.first().cloned().flatten(),
+                tokens_used: None,
             },
             ThreadItem {
                 path: p2,
@@ -777,6 +784,7 @@ async fn test_pagination_cursor() {
                 cli_version: Some("test_version".to_string()),
                 created_at: Some("2025-03-02T09-00-00".into()),
                 updated_at: updated_page2.get(1).cloned().flatten(),
+                tokens_used: None,
             },
         ],
         next_cursor: Some(expected_cursor2.clone()),
@@ -818,6 +826,7 @@ async fn test_pagination_cursor() {
             cli_version: Some("test_version".to_string()),
             created_at: Some("2025-03-01T09-00-00".into()),
             updated_at: updated_page3.first().cloned().flatten(),
+            tokens_used: None,
         }],
         next_cursor: None,
         num_scanned_files: 5, // scanned 05, 04 (anchor), 03, 02 (anchor), 01
@@ -852,6 +861,45 @@ async fn test_list_threads_scans_past_head_for_user_event() {
     assert_eq!(page.items[0].thread_id, Some(thread_id_from_uuid(uuid)));
 }
 
+#[tokio::test]
+async fn test_listing_reuses_indexed_summaries() -> Result<()> {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path();
+
+    let uuid = Uuid::from_u128(7);
+    let ts = "2025-03-04T09-00-00";
+    write_session_file(home, ts, uuid, 1, Some(SessionSource::Cli))?;
+    let list = || {
+        get_threads(
+            home,
+            10,
+            None,
+            ThreadSortKey::UpdatedAt,
+            NO_SOURCE_FILTER,
+            None,
+            TEST_PROVIDER,
+        )
+    };
+
+    let first = list().await?;
+    let day_dir = home.join("sessions").join("2025").join("03").join("04");
+    assert!(day_dir.join(SUMMARY_INDEX_FILE).exists());
+    let first_item = first.items.first().expect("listed thread");
+    assert!(first_item.first_user_message.is_some());
+
+    // Listing again answers from the index instead of the rollout head.
+    fs::write(day_dir.join(format!("rollout-{ts}-{uuid}.jsonl")), "")?;
+    let second = list().await?;
+    let second_item = second.items.first().expect("indexed thread");
+    assert_eq!(second_item.thread_id, first_item.thread_id);
+    assert_eq!(
+        second_item.first_user_message,
+        first_item.first_user_message
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_get_thread_contents() {
     let temp = TempDir::new().unwrap();
@@ -898,6 +946,7 @@ async fn test_get_thread_contents() {
             cli_version: Some("test_version".to_string()),
             created_at: Some(ts.into()),
             updated_at: page.items[0].updated_at.clone(),
+            tokens_used: None,
         }],
         next_cursor: None,
         num_scanned_files: 1,
@@ -1207,6 +1256,7 @@ async fn test_stable_ordering_same_second_pagination() {
                 cli_version: Some("test_version".to_string()),
                 created_at: Some(ts.to_string()),
                 updated_at: updated_page1.first().cloned().flatten(),
+                tokens_used: None,
             },
             ThreadItem {
                 path: p2,
@@ -1221,6 +1271,7 @@ async fn test_stable_ordering_same_second_pagination() {
                 cli_version: Some("test_version".to_string()),
                 created_at: Some(ts.to_string()),
                 updated_at: updated_page1.get(1).cloned().flatten(),
+                tokens_used: None,
             },
         ],
         next_cursor: Some(expected_cursor1.clone()),
@@ -1262,6 +1313,7 @@ async fn test_stable_ordering_same_second_pagination() {
             cli_version: Some("test_version".to_string()),
             created_at: Some(ts.to_string()),
             updated_at: updated_page2.first().cloned().flatten(),
+            tokens_used: None,
         }],
         next_cursor: None,
         num_scanned_files: 3, // scanned u3, u2 (anchor), u1
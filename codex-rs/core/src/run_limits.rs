@@ -0,0 +1,120 @@
+//! Time and step limits for autonomous runs (`codex exec --max-runtime` and
+//! `--max-turns`).
+//!
+//! The limits are checked between model requests, so a command or patch that
+//! is already running always finishes. Once a limit is reached the model gets
+//! one last request, without tools, asking it to summarize where the work
+//! stands and how to resume it. The turn then ends with that summary as its
+//! final message.
+
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_protocol::models::DeveloperInstructions;
+use codex_protocol::models::ResponseItem;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RunLimits {
+    /// Model requests a turn may make before it has to wrap up.
+    pub max_turns: Option<u32>,
+    /// Wall-clock time a turn may run before it has to wrap up.
+    pub max_runtime: Option<Duration>,
+}
+
+/// Progress of one turn against its [`RunLimits`].
+pub(crate) struct RunBudget {
+    limits: RunLimits,
+    started: Instant,
+    turns: u32,
+}
+
+impl RunBudget {
+    pub(crate) fn new(limits: RunLimits) -> Self {
+        Self {
+            limits,
+            started: Instant::now(),
+            turns: 0,
+        }
+    }
+
+    /// Counts a completed model request.
+    pub(crate) fn record_turn(&mut self) {
+        self.turns = self.turns.saturating_add(1);
+    }
+
+    /// Describes the limit that has been reached, if any.
+    pub(crate) fn exceeded(&self) -> Option<String> {
+        if let Some(max_turns) = self.limits.max_turns
+            && self.turns >= max_turns
+        {
+            return Some(format!("the limit of {max_turns} turns"));
+        }
+        if let Some(max_runtime) = self.limits.max_runtime
+            && self.started.elapsed() >= max_runtime
+        {
+            return Some(format!(
+                "the runtime limit of {}",
+                format_duration(max_runtime)
+            ));
+        }
+        None
+    }
+}
+
+/// Developer message asking the model for the final summary of a run that
+/// reached `limit`.
+pub(crate) fn summary_request(limit: &str) -> ResponseItem {
+    DeveloperInstructions::new(format!(
+        "This run has reached {limit} and must stop now. Tools are no longer available. Reply with a final summary for whoever picks this work up:\n\
+         - what you changed and the current state of the work, including anything left half-done\n\
+         - what remains to be done\n\
+         - how to resume, such as the next commands to run or files to look at"
+    ))
+    .into()
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs > 0 && secs % 3600 == 0 {
+        format!("{}h", secs / 3600)
+    } else if secs > 0 && secs % 60 == 0 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn reports_the_limit_that_was_reached() {
+        let mut budget = RunBudget::new(RunLimits {
+            max_turns: Some(2),
+            max_runtime: Some(Duration::from_secs(3600)),
+        });
+        budget.record_turn();
+        assert_eq!(budget.exceeded(), None);
+        budget.record_turn();
+        assert_eq!(budget.exceeded(), Some("the limit of 2 turns".to_string()));
+
+        let budget = RunBudget::new(RunLimits {
+            max_turns: None,
+            max_runtime: Some(Duration::ZERO),
+        });
+        assert_eq!(
+            budget.exceeded(),
+            Some("the runtime limit of 0s".to_string())
+        );
+        assert_eq!(RunBudget::new(RunLimits::default()).exceeded(), None);
+    }
+
+    #[test]
+    fn formats_durations_in_their_largest_whole_unit() {
+        assert_eq!(format_duration(Duration::from_secs(7200)), "2h");
+        assert_eq!(format_duration(Duration::from_secs(1800)), "30m");
+        assert_eq!(format_duration(Duration::from_secs(90)), "90s");
+    }
+}
@@ -0,0 +1,77 @@
+//! Device nodes that workspace-write commands may use, from
+//! `sandbox_workspace_write.allowed_devices`. They are validated when config
+//! loads and forwarded to `codex-linux-sandbox`, which keeps every other
+//! device except `/dev/null` blocked.
+
+use std::sync::RwLock;
+
+static ALLOWED_DEVICES: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// Checks that `pattern` names device nodes under `/dev`. A `*` wildcard is
+/// allowed in the last path component only, as in `/dev/nvidia*`.
+pub fn validate_device_pattern(pattern: &str) -> Result<(), String> {
+    let Some(rest) = pattern.strip_prefix("/dev/") else {
+        return Err(format!("device `{pattern}` must be a path under /dev/"));
+    };
+    let components: Vec<&str> = rest.split('/').collect();
+    if components
+        .iter()
+        .any(|component| component.is_empty() || matches!(*component, "." | ".."))
+    {
+        return Err(format!(
+            "device `{pattern}` must not contain empty, `.` or `..` components"
+        ));
+    }
+    let (last, parents) = components.split_last().unwrap_or((&"", &[]));
+    if parents.iter().any(|component| component.contains('*')) {
+        return Err(format!(
+            "device `{pattern}` may only use `*` in its last component"
+        ));
+    }
+    if last.chars().all(|c| c == '*') {
+        return Err(format!(
+            "device `{pattern}` must name a device, not every entry in a directory"
+        ));
+    }
+    Ok(())
+}
+
+/// Lets commands sandboxed with a workspace-write policy open `devices`
+/// from now on.
+pub fn set_allowed_devices(devices: Vec<String>) {
+    let Ok(mut guard) = ALLOWED_DEVICES.write() else {
+        tracing::warn!("Failed to acquire sandbox allowed devices lock");
+        return;
+    };
+    *guard = devices;
+}
+
+pub(crate) fn allowed_devices() -> Vec<String> {
+    ALLOWED_DEVICES
+        .read()
+        .map(|devices| devices.clone())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_device_paths_and_trailing_wildcards() {
+        for pattern in ["/dev/nvidia*", "/dev/dri", "/dev/dri/renderD*", "/dev/kfd"] {
+            assert_eq!(validate_device_pattern(pattern), Ok(()), "{pattern}");
+        }
+        for pattern in [
+            "nvidia0",
+            "/etc/passwd",
+            "/dev/",
+            "/dev/*",
+            "/dev/../etc",
+            "/dev/*/card0",
+            "/dev//nvidia0",
+        ] {
+            assert!(validate_device_pattern(pattern).is_err(), "{pattern}");
+        }
+    }
+}
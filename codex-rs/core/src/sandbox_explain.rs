@@ -0,0 +1,466 @@
+//! Dry run of the sandbox and approval checks for a command line, behind
+//! `codex sandbox explain` and `/sandbox-explain`. Nothing is executed: the
+//! report says which sandbox would apply, what it lets the command write and
+//! reach, whether the command would need approval, and how an exec server
+//! (`codex-exec-mcp-server`) would treat each program the command starts.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_execpolicy::Decision;
+use codex_execpolicy::Policy;
+use codex_protocol::config_types::WindowsSandboxLevel;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use serde::Serialize;
+use shlex::try_join as shlex_try_join;
+
+use crate::bash::extract_bash_command;
+use crate::bash::parse_shell_lc_plain_commands;
+use crate::config::Config;
+use crate::config::types::McpServerConfig;
+use crate::config::types::McpServerTransportConfig;
+use crate::exec_policy::ExecApprovalRequest;
+use crate::exec_policy::ExecPolicyError;
+use crate::exec_policy::ExecPolicyManager;
+use crate::exec_policy::evaluate_intercepted_exec;
+use crate::exec_policy::load_exec_policy;
+use crate::safety::get_platform_sandbox;
+use crate::sandboxing::SandboxPermissions;
+use crate::scope::sandbox_cwd;
+use crate::scope::scoped_sandbox_policy;
+use crate::tools::sandboxing::ExecApprovalRequirement;
+use crate::windows_sandbox::WindowsSandboxLevelExt;
+
+/// Executables that serve the intercepting `shell` tool over MCP.
+const EXEC_SERVER_EXECUTABLES: [&str; 2] = ["codex-exec-mcp-server", "codex-shell-tool-mcp"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SandboxExplanation {
+    pub command: Vec<String>,
+    /// `danger-full-access`, `read-only`, `workspace-write` or `external-sandbox`.
+    pub sandbox_policy: String,
+    /// Platform sandbox the command would run under; `None` when unsandboxed.
+    pub sandbox: Option<String>,
+    /// Directory the sandbox treats as the working directory.
+    pub cwd: PathBuf,
+    pub cwd_writable: bool,
+    pub full_disk_write_access: bool,
+    pub network_access: bool,
+    pub writable_roots: Vec<ExplainedWritableRoot>,
+    /// What happens when the model runs the command as is.
+    pub approval: ApprovalOutcome,
+    /// What happens when the model asks to run it outside the sandbox.
+    pub escalated_approval: ApprovalOutcome,
+    /// Enabled MCP servers that intercept `execve(2)` of the commands they run.
+    pub exec_servers: Vec<String>,
+    /// How those servers would treat each program the command starts. Empty
+    /// when no exec server is configured.
+    pub intercepted_commands: Vec<InterceptedCommand>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExplainedWritableRoot {
+    pub root: PathBuf,
+    pub read_only_subpaths: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum ApprovalOutcome {
+    RunsInSandbox,
+    RunsOutsideSandbox,
+    NeedsApproval { reason: Option<String> },
+    Forbidden { reason: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InterceptedCommand {
+    pub command: Vec<String>,
+    pub action: InterceptedAction,
+}
+
+/// What an exec server does with one intercepted `execve(2)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum InterceptedAction {
+    /// Runs inside the sandbox.
+    Run,
+    /// Runs outside the sandbox.
+    Escalate,
+    /// Asks the user first; `escalate` tells where it runs once approved.
+    Prompt { escalate: bool },
+    /// Refused.
+    Deny,
+}
+
+/// Turns a command line typed as one string into the argv the shell tool
+/// would run.
+pub fn command_for_line(line: &str) -> Vec<String> {
+    vec!["bash".to_string(), "-lc".to_string(), line.to_string()]
+}
+
+/// Reports how `command` would be sandboxed and approved under `config`,
+/// with the same rules files a session loads.
+pub async fn explain_command(
+    config: &Config,
+    command: &[String],
+) -> Result<SandboxExplanation, ExecPolicyError> {
+    let policy = load_exec_policy(&config.config_layer_stack).await?;
+    Ok(explain_with_policy(config, policy, command).await)
+}
+
+async fn explain_with_policy(
+    config: &Config,
+    policy: Policy,
+    command: &[String],
+) -> SandboxExplanation {
+    let sandbox_policy =
+        scoped_sandbox_policy(config.permissions.sandbox_policy.get(), &config.scope);
+    let cwd = sandbox_cwd(&config.cwd, &config.scope);
+    let sandbox = match &sandbox_policy {
+        SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => None,
+        SandboxPolicy::ReadOnly { .. } | SandboxPolicy::WorkspaceWrite { .. } => {
+            get_platform_sandbox(
+                WindowsSandboxLevel::from_config(config) != WindowsSandboxLevel::Disabled,
+            )
+        }
+    };
+
+    let writable_roots = sandbox_policy.get_writable_roots_with_cwd(cwd);
+    let cwd_writable = sandbox_policy.has_full_disk_write_access()
+        || writable_roots.iter().any(|root| root.is_path_writable(cwd));
+
+    let exec_servers = exec_server_names(config.mcp_servers.get());
+    let intercepted_commands = if exec_servers.is_empty() {
+        Vec::new()
+    } else {
+        intercepted_commands(&policy, command)
+    };
+
+    let manager = ExecPolicyManager::new(Arc::new(policy));
+    let request = |sandbox_permissions| ExecApprovalRequest {
+        command,
+        cwd: Some(cwd),
+        approval_policy: config.permissions.approval_policy.value(),
+        sandbox_policy: &sandbox_policy,
+        sandbox_permissions,
+        prefix_rule: None,
+    };
+    let approval = approval_outcome(
+        manager
+            .create_exec_approval_requirement_for_command(request(SandboxPermissions::UseDefault))
+            .await,
+        sandbox.is_none(),
+    );
+    let escalated_approval = approval_outcome(
+        manager
+            .create_exec_approval_requirement_for_command(request(
+                SandboxPermissions::RequireEscalated,
+            ))
+            .await,
+        true,
+    );
+
+    SandboxExplanation {
+        command: command.to_vec(),
+        sandbox_policy: policy_name(&sandbox_policy).to_string(),
+        sandbox: sandbox.map(|sandbox| sandbox.as_metric_tag().to_string()),
+        cwd: cwd.to_path_buf(),
+        cwd_writable,
+        full_disk_write_access: sandbox_policy.has_full_disk_write_access(),
+        network_access: sandbox_policy.has_full_network_access(),
+        writable_roots: writable_roots
+            .into_iter()
+            .map(|root| ExplainedWritableRoot {
+                root: root.root.to_path_buf(),
+                read_only_subpaths: root
+                    .read_only_subpaths
+                    .iter()
+                    .map(AbsolutePathBuf::to_path_buf)
+                    .collect(),
+            })
+            .collect(),
+        approval,
+        escalated_approval,
+        exec_servers,
+        intercepted_commands,
+    }
+}
+
+fn approval_outcome(requirement: ExecApprovalRequirement, unsandboxed: bool) -> ApprovalOutcome {
+    match requirement {
+        ExecApprovalRequirement::Skip { bypass_sandbox, .. } => {
+            if bypass_sandbox || unsandboxed {
+                ApprovalOutcome::RunsOutsideSandbox
+            } else {
+                ApprovalOutcome::RunsInSandbox
+            }
+        }
+        ExecApprovalRequirement::NeedsApproval { reason, .. } => {
+            ApprovalOutcome::NeedsApproval { reason }
+        }
+        ExecApprovalRequirement::Forbidden { reason } => ApprovalOutcome::Forbidden { reason },
+    }
+}
+
+fn policy_name(policy: &SandboxPolicy) -> &'static str {
+    match policy {
+        SandboxPolicy::DangerFullAccess => "danger-full-access",
+        SandboxPolicy::ReadOnly { .. } => "read-only",
+        SandboxPolicy::WorkspaceWrite { .. } => "workspace-write",
+        SandboxPolicy::ExternalSandbox { .. } => "external-sandbox",
+    }
+}
+
+/// Names of the enabled stdio servers that launch an exec server, sorted.
+fn exec_server_names(servers: &HashMap<String, McpServerConfig>) -> Vec<String> {
+    let mut names: Vec<String> = servers
+        .iter()
+        .filter(|(_, server)| server.enabled)
+        .filter(|(_, server)| match &server.transport {
+            McpServerTransportConfig::Stdio { command, args, .. } => {
+                std::iter::once(command).chain(args).any(|arg| {
+                    EXEC_SERVER_EXECUTABLES
+                        .iter()
+                        .any(|executable| arg.contains(executable))
+                })
+            }
+            McpServerTransportConfig::StreamableHttp { .. } => false,
+        })
+        .map(|(name, _)| name.clone())
+        .collect();
+    names.sort();
+    names
+}
+
+/// Splits a `bash -lc` script into the programs it starts and decides each
+/// one the way the exec server does. Other commands are a single program.
+/// Scripts with constructs other than plain words and `&&`, `||`, `;` and
+/// `|` cannot be split and yield no entries.
+fn intercepted_commands(policy: &Policy, command: &[String]) -> Vec<InterceptedCommand> {
+    let commands = match parse_shell_lc_plain_commands(command) {
+        Some(commands) => commands,
+        None if extract_bash_command(command).is_some() => Vec::new(),
+        None => vec![command.to_vec()],
+    };
+    commands
+        .into_iter()
+        .map(|mut command| {
+            // The exec server sees the resolved executable and, by default,
+            // matches rules against its file name.
+            if let Some(program) = command.first_mut()
+                && let Some(name) = Path::new(program.as_str()).file_name()
+            {
+                *program = name.to_string_lossy().into_owned();
+            }
+            let action = match evaluate_intercepted_exec(policy, &command) {
+                (Decision::Allow, permissions) if permissions.requires_escalated_permissions() => {
+                    InterceptedAction::Escalate
+                }
+                (Decision::Allow, _) => InterceptedAction::Run,
+                (Decision::Prompt, permissions) => InterceptedAction::Prompt {
+                    escalate: permissions.requires_escalated_permissions(),
+                },
+                (Decision::Forbidden, _) => InterceptedAction::Deny,
+            };
+            InterceptedCommand { command, action }
+        })
+        .collect()
+}
+
+fn render_command(command: &[String]) -> String {
+    shlex_try_join(command.iter().map(String::as_str)).unwrap_or_else(|_| command.join(" "))
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value { "yes" } else { "no" }
+}
+
+impl fmt::Display for ApprovalOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApprovalOutcome::RunsInSandbox => write!(f, "runs in the sandbox without approval"),
+            ApprovalOutcome::RunsOutsideSandbox => {
+                write!(f, "runs outside the sandbox without approval")
+            }
+            ApprovalOutcome::NeedsApproval { reason: None } => write!(f, "needs approval"),
+            ApprovalOutcome::NeedsApproval {
+                reason: Some(reason),
+            } => write!(f, "needs approval ({reason})"),
+            ApprovalOutcome::Forbidden { reason } => write!(f, "blocked ({reason})"),
+        }
+    }
+}
+
+impl fmt::Display for InterceptedAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InterceptedAction::Run => write!(f, "runs in the sandbox"),
+            InterceptedAction::Escalate => write!(f, "runs outside the sandbox"),
+            InterceptedAction::Prompt { escalate: false } => {
+                write!(f, "asks first, then runs in the sandbox")
+            }
+            InterceptedAction::Prompt { escalate: true } => {
+                write!(f, "asks first, then runs outside the sandbox")
+            }
+            InterceptedAction::Deny => write!(f, "denied"),
+        }
+    }
+}
+
+impl fmt::Display for SandboxExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Command: {}", render_command(&self.command))?;
+        match &self.sandbox {
+            Some(sandbox) => writeln!(f, "Sandbox: {} ({sandbox})", self.sandbox_policy)?,
+            None => writeln!(f, "Sandbox: {} (not sandboxed)", self.sandbox_policy)?,
+        }
+        writeln!(
+            f,
+            "Working directory: {} (writable: {})",
+            self.cwd.display(),
+            yes_no(self.cwd_writable)
+        )?;
+        if self.full_disk_write_access {
+            writeln!(f, "Writable: everywhere")?;
+        } else if self.writable_roots.is_empty() {
+            writeln!(f, "Writable: nothing")?;
+        } else {
+            writeln!(f, "Writable:")?;
+            for root in &self.writable_roots {
+                write!(f, "  {}", root.root.display())?;
+                if !root.read_only_subpaths.is_empty() {
+                    let read_only: Vec<String> = root
+                        .read_only_subpaths
+                        .iter()
+                        .map(|path| path.display().to_string())
+                        .collect();
+                    write!(f, " (except {})", read_only.join(", "))?;
+                }
+                writeln!(f)?;
+            }
+        }
+        writeln!(f, "Network: {}", yes_no(self.network_access))?;
+        writeln!(f, "As requested: {}", self.approval)?;
+        writeln!(f, "With escalation: {}", self.escalated_approval)?;
+
+        if self.exec_servers.is_empty() {
+            return write!(
+                f,
+                "Exec server: none configured, subcommands are not intercepted"
+            );
+        }
+        writeln!(f, "Exec server: {}", self.exec_servers.join(", "))?;
+        if self.intercepted_commands.is_empty() {
+            return write!(
+                f,
+                "  the script is too complex to split; each program is checked when it starts"
+            );
+        }
+        let lines: Vec<String> = self
+            .intercepted_commands
+            .iter()
+            .map(|intercepted| {
+                format!(
+                    "  {}: {}",
+                    render_command(&intercepted.command),
+                    intercepted.action
+                )
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_config;
+    use codex_protocol::protocol::AskForApproval;
+    use pretty_assertions::assert_eq;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[tokio::test]
+    async fn explains_workspace_write_policy() {
+        let mut config = test_config();
+        config
+            .permissions
+            .sandbox_policy
+            .set(SandboxPolicy::new_workspace_write_policy())
+            .expect("set sandbox policy");
+        config
+            .permissions
+            .approval_policy
+            .set(AskForApproval::OnRequest)
+            .expect("set approval policy");
+
+        let explanation =
+            explain_with_policy(&config, Policy::empty(), &strings(&["cargo", "build"])).await;
+
+        assert_eq!(explanation.sandbox_policy, "workspace-write");
+        assert!(explanation.cwd_writable);
+        assert!(!explanation.full_disk_write_access);
+        assert!(!explanation.network_access);
+        assert!(
+            explanation
+                .writable_roots
+                .iter()
+                .any(|root| root.root == config.cwd)
+        );
+        assert_eq!(
+            explanation.escalated_approval,
+            ApprovalOutcome::NeedsApproval { reason: None }
+        );
+        assert!(explanation.exec_servers.is_empty());
+        assert!(explanation.intercepted_commands.is_empty());
+    }
+
+    #[test]
+    fn intercepted_commands_follow_rules_and_heuristics() {
+        let mut policy = Policy::empty();
+        policy
+            .add_prefix_rule(&strings(&["git", "push"]), Decision::Prompt)
+            .expect("add prompt rule");
+        policy
+            .add_prefix_rule(&strings(&["cargo"]), Decision::Allow)
+            .expect("add allow rule");
+
+        let commands = intercepted_commands(
+            &policy,
+            &command_for_line("/usr/bin/git push && cargo test; ls && rm -rf /"),
+        );
+
+        assert_eq!(
+            commands,
+            vec![
+                InterceptedCommand {
+                    command: strings(&["git", "push"]),
+                    action: InterceptedAction::Prompt { escalate: true },
+                },
+                InterceptedCommand {
+                    command: strings(&["cargo", "test"]),
+                    action: InterceptedAction::Escalate,
+                },
+                InterceptedCommand {
+                    command: strings(&["ls"]),
+                    action: InterceptedAction::Run,
+                },
+                InterceptedCommand {
+                    command: strings(&["rm", "-rf", "/"]),
+                    action: InterceptedAction::Prompt { escalate: false },
+                },
+            ]
+        );
+        assert_eq!(
+            intercepted_commands(&policy, &command_for_line("echo $(date)")),
+            Vec::new()
+        );
+    }
+}
@@ -0,0 +1,290 @@
+//! Monorepo scoping: narrows a session to some sub-directories of its working
+//! directory (`--scope`, or `Op::SetScope` mid-session).
+//!
+//! In a scoped session:
+//!
+//! - workspace-write sandboxes make the scope directories writable instead of
+//!   the working directory,
+//! - `grep_files` searches the scope directories when no path is given,
+//! - the AGENTS.md files between the working directory and each scope
+//!   directory are read, and
+//! - the model is told about the scope in a `<workspace_scope>` message, and
+//!   the repository brief only covers the scope directories.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use tracing::warn;
+
+use crate::config::Config;
+use crate::project_doc::discover_scope_doc_paths;
+use crate::session_prefix::WORKSPACE_SCOPE_CLOSE_TAG;
+use crate::session_prefix::WORKSPACE_SCOPE_OPEN_TAG;
+
+/// Resolves `paths` against `cwd` and checks that each one is an existing
+/// directory inside `cwd`. Duplicates are dropped.
+pub(crate) fn resolve_scope(
+    cwd: &Path,
+    paths: &[PathBuf],
+) -> std::io::Result<Vec<AbsolutePathBuf>> {
+    let mut scope: Vec<AbsolutePathBuf> = Vec::with_capacity(paths.len());
+    for path in paths {
+        let dir = AbsolutePathBuf::resolve_path_against_base(path, cwd)?;
+        if !dir.as_path().starts_with(cwd) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "scope `{}` is outside the working directory {}",
+                    path.display(),
+                    cwd.display()
+                ),
+            ));
+        }
+        if !dir.as_path().is_dir() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("scope `{}` is not a directory", path.display()),
+            ));
+        }
+        if !scope.contains(&dir) {
+            scope.push(dir);
+        }
+    }
+    Ok(scope)
+}
+
+/// Returns `policy` with the scope directories after the first added to its
+/// writable roots; the first takes the place of the working directory through
+/// [`sandbox_cwd`]. Only workspace-write policies change.
+pub(crate) fn scoped_sandbox_policy(
+    policy: &SandboxPolicy,
+    scope: &[AbsolutePathBuf],
+) -> SandboxPolicy {
+    let mut policy = policy.clone();
+    if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = &mut policy {
+        for dir in scope.iter().skip(1) {
+            if !writable_roots.contains(dir) {
+                writable_roots.push(dir.clone());
+            }
+        }
+    }
+    policy
+}
+
+/// Directory the sandbox treats as the working directory. Sandboxes always
+/// make it writable, so a scoped session passes its first scope directory
+/// instead of `cwd`.
+pub(crate) fn sandbox_cwd<'a>(cwd: &'a Path, scope: &'a [AbsolutePathBuf]) -> &'a Path {
+    scope.first().map_or(cwd, AbsolutePathBuf::as_path)
+}
+
+/// Returns true when `path` is inside one of the scope directories, or when
+/// there is no scope.
+pub(crate) fn in_scope(scope: &[AbsolutePathBuf], path: &Path) -> bool {
+    scope.is_empty() || scope.iter().any(|dir| path.starts_with(dir))
+}
+
+/// Lists the scope directories relative to `cwd`, for messages.
+pub(crate) fn display_scope(cwd: &Path, scope: &[AbsolutePathBuf]) -> String {
+    scope
+        .iter()
+        .map(|dir| relative_display(cwd, dir.as_path()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn relative_display(cwd: &Path, path: &Path) -> String {
+    path.strip_prefix(cwd)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ScopeContext {
+    dirs: Vec<String>,
+    docs: Vec<ScopeDoc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ScopeDoc {
+    path: String,
+    text: String,
+}
+
+impl ScopeContext {
+    /// Gathers the scope directories and their AGENTS.md files. The files
+    /// share the `project_doc_max_bytes` budget; an empty scope yields a
+    /// context that tells the model the scope was cleared.
+    pub(crate) fn collect(config: &Config, cwd: &Path, scope: &[AbsolutePathBuf]) -> Self {
+        let dirs = scope
+            .iter()
+            .map(|dir| relative_display(cwd, dir.as_path()))
+            .collect();
+        let paths = discover_scope_doc_paths(config, cwd, scope).unwrap_or_else(|err| {
+            warn!("error trying to find scope docs: {err:#}");
+            Vec::new()
+        });
+
+        let mut remaining = config.project_doc_max_bytes;
+        let mut docs = Vec::new();
+        for path in paths {
+            if remaining == 0 {
+                break;
+            }
+            let Ok(mut data) = std::fs::read(&path) else {
+                continue;
+            };
+            data.truncate(remaining);
+            let text = String::from_utf8_lossy(&data).trim().to_string();
+            if text.is_empty() {
+                continue;
+            }
+            remaining = remaining.saturating_sub(data.len());
+            docs.push(ScopeDoc {
+                path: relative_display(cwd, &path),
+                text,
+            });
+        }
+
+        Self { dirs, docs }
+    }
+
+    /// Serializes the scope to XML:
+    ///
+    /// ```xml
+    /// <workspace_scope>
+    ///   <note>...</note>
+    ///   <dir>packages/api</dir>
+    ///   <instructions path="packages/api/AGENTS.md">
+    ///   ...
+    ///   </instructions>
+    /// </workspace_scope>
+    /// ```
+    pub(crate) fn serialize_to_xml(self) -> String {
+        let mut lines = vec![WORKSPACE_SCOPE_OPEN_TAG.to_string()];
+        if self.dirs.is_empty() {
+            lines.push(
+                "  <note>The scope was cleared: the whole working directory is in scope again.</note>"
+                    .to_string(),
+            );
+        } else {
+            lines.push(
+                "  <note>This session is scoped to the directories below, relative to the working directory. Keep searches, reads and edits inside them unless the user asks otherwise. The instructions of each scope directory apply to the files in it.</note>"
+                    .to_string(),
+            );
+            for dir in self.dirs {
+                lines.push(format!("  <dir>{dir}</dir>"));
+            }
+            for doc in self.docs {
+                lines.push(format!("  <instructions path=\"{}\">", doc.path));
+                lines.push(doc.text);
+                lines.push("  </instructions>".to_string());
+            }
+        }
+        lines.push(WORKSPACE_SCOPE_CLOSE_TAG.to_string());
+        lines.join("\n")
+    }
+}
+
+impl From<ScopeContext> for ResponseItem {
+    fn from(context: ScopeContext) -> Self {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: context.serialize_to_xml(),
+            }],
+            end_turn: None,
+            phase: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn absolute(path: &Path) -> AbsolutePathBuf {
+        AbsolutePathBuf::from_absolute_path(path).expect("absolute path")
+    }
+
+    #[test]
+    fn resolves_scope_inside_cwd_only() {
+        let cwd = TempDir::new().expect("tempdir");
+        std::fs::create_dir_all(cwd.path().join("packages/api")).expect("mkdir");
+
+        let scope = resolve_scope(
+            cwd.path(),
+            &[
+                PathBuf::from("packages/api"),
+                PathBuf::from("packages/./api"),
+            ],
+        )
+        .expect("scope");
+        assert_eq!(scope, vec![absolute(&cwd.path().join("packages/api"))]);
+
+        let outside =
+            resolve_scope(cwd.path(), &[PathBuf::from("../elsewhere")]).expect_err("outside cwd");
+        assert_eq!(outside.kind(), std::io::ErrorKind::InvalidInput);
+        let missing =
+            resolve_scope(cwd.path(), &[PathBuf::from("packages/web")]).expect_err("missing");
+        assert_eq!(missing.kind(), std::io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn scope_replaces_cwd_as_writable_root() {
+        let cwd = Path::new("/repo");
+        let scope = vec![
+            absolute(Path::new("/repo/packages/api")),
+            absolute(Path::new("/repo/packages/web")),
+        ];
+        let policy = scoped_sandbox_policy(&SandboxPolicy::new_workspace_write_policy(), &scope);
+        let sandbox_cwd = sandbox_cwd(cwd, &scope);
+        assert_eq!(sandbox_cwd, Path::new("/repo/packages/api"));
+
+        let roots = policy.get_writable_roots_with_cwd(sandbox_cwd);
+        assert!(roots.iter().all(|root| root.root.as_path() != cwd));
+        assert!(roots.iter().any(|root| root.root == scope[1]));
+        assert_eq!(
+            scoped_sandbox_policy(&SandboxPolicy::DangerFullAccess, &scope),
+            SandboxPolicy::DangerFullAccess
+        );
+    }
+
+    #[test]
+    fn serializes_scope_with_instructions() {
+        let context = ScopeContext {
+            dirs: vec!["packages/api".to_string()],
+            docs: vec![ScopeDoc {
+                path: "packages/api/AGENTS.md".to_string(),
+                text: "Run `pnpm test` here.".to_string(),
+            }],
+        };
+        let xml = context.serialize_to_xml();
+        let lines: Vec<&str> = xml.lines().collect();
+        assert_eq!(lines.first(), Some(&"<workspace_scope>"));
+        assert_eq!(
+            lines[2..],
+            [
+                "  <dir>packages/api</dir>",
+                "  <instructions path=\"packages/api/AGENTS.md\">",
+                "Run `pnpm test` here.",
+                "  </instructions>",
+                "</workspace_scope>",
+            ]
+        );
+
+        let cleared = ScopeContext {
+            dirs: Vec::new(),
+            docs: Vec::new(),
+        };
+        assert_eq!(cleared.serialize_to_xml().lines().count(), 3);
+    }
+}
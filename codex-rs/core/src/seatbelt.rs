@@ -16,6 +16,7 @@ use url::Url;
 use crate::protocol::SandboxPolicy;
 use crate::seatbelt_permissions::MacOsSeatbeltProfileExtensions;
 use crate::seatbelt_permissions::build_seatbelt_extensions;
+use crate::seatbelt_profile::profile_additions;
 use crate::spawn::CODEX_SANDBOX_ENV_VAR;
 use crate::spawn::SpawnChildRequest;
 use crate::spawn::StdioPolicy;
@@ -303,7 +304,9 @@ pub(crate) fn create_seatbelt_command_args_with_extensions(
         build_seatbelt_extensions,
     );
 
-    let full_policy = if seatbelt_extensions.policy.is_empty() {
+    let (additions_policy, additions_dir_params) = profile_additions().policy();
+
+    let mut full_policy = if seatbelt_extensions.policy.is_empty() {
         format!(
             "{MACOS_SEATBELT_BASE_POLICY}\n{file_read_policy}\n{file_write_policy}\n{network_policy}"
         )
@@ -313,12 +316,17 @@ pub(crate) fn create_seatbelt_command_args_with_extensions(
             seatbelt_extensions.policy
         )
     };
+    if !additions_policy.is_empty() {
+        full_policy.push('\n');
+        full_policy.push_str(&additions_policy);
+    }
 
     let dir_params = [
         file_read_dir_params,
         file_write_dir_params,
         macos_dir_params(),
         seatbelt_extensions.dir_params,
+        additions_dir_params,
     ]
     .concat();
 
@@ -0,0 +1,265 @@
+//! User additions to the macOS Seatbelt profile, from the `[macos_seatbelt]`
+//! config table. They are validated when config loads and appended to the
+//! builtin profile of every command Codex runs under Seatbelt.
+
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+use codex_utils_absolute_path::AbsolutePathBuf;
+
+use crate::config::types::MacOsSeatbeltToml;
+
+static PROFILE_ADDITIONS: RwLock<Option<SeatbeltProfileAdditions>> = RwLock::new(None);
+
+/// Rule heads accepted at the top level of `extra_rules`. Anything else, such
+/// as `version` or `import`, could replace the builtin profile rather than
+/// extend it.
+const ALLOWED_RULE_HEADS: [&str; 2] = ["allow", "deny"];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SeatbeltProfileAdditions {
+    readable_paths: Vec<AbsolutePathBuf>,
+    mach_services: Vec<String>,
+    extra_rules: Option<String>,
+}
+
+impl SeatbeltProfileAdditions {
+    /// Validates a `[macos_seatbelt]` table.
+    pub fn from_toml(toml: &MacOsSeatbeltToml) -> Result<Self, String> {
+        for service in &toml.mach_services {
+            validate_mach_service(service)?;
+        }
+        let extra_rules = toml
+            .extra_rules
+            .as_deref()
+            .map(str::trim)
+            .filter(|rules| !rules.is_empty());
+        if let Some(rules) = extra_rules {
+            validate_rules(rules)?;
+        }
+        Ok(Self {
+            readable_paths: toml.readable_paths.clone(),
+            mach_services: toml.mach_services.clone(),
+            extra_rules: extra_rules.map(str::to_string),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.readable_paths.is_empty()
+            && self.mach_services.is_empty()
+            && self.extra_rules.is_none()
+    }
+
+    /// Renders the additions as profile text plus the `-D` parameters it
+    /// refers to.
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    pub(crate) fn policy(&self) -> (String, Vec<(String, PathBuf)>) {
+        if self.is_empty() {
+            return (String::new(), Vec::new());
+        }
+        let mut clauses = vec!["; additions from [macos_seatbelt]".to_string()];
+        let mut params = Vec::new();
+
+        if !self.readable_paths.is_empty() {
+            let mut subpaths = Vec::new();
+            for (index, path) in self.readable_paths.iter().enumerate() {
+                // Canonicalize to avoid mismatches like /var vs /private/var on macOS.
+                let canonical = path
+                    .as_path()
+                    .canonicalize()
+                    .unwrap_or_else(|_| path.to_path_buf());
+                let param = format!("EXTRA_READABLE_ROOT_{index}");
+                subpaths.push(format!("(subpath (param \"{param}\"))"));
+                params.push((param, canonical));
+            }
+            clauses.push(format!("(allow file-read*\n{}\n)", subpaths.join(" ")));
+        }
+
+        if !self.mach_services.is_empty() {
+            let names = self
+                .mach_services
+                .iter()
+                .map(|service| format!("    (global-name \"{service}\")"))
+                .collect::<Vec<String>>()
+                .join("\n");
+            clauses.push(format!("(allow mach-lookup\n{names}\n)"));
+        }
+
+        if let Some(rules) = &self.extra_rules {
+            clauses.push(rules.clone());
+        }
+        (clauses.join("\n"), params)
+    }
+}
+
+/// Applies `additions` to every Seatbelt profile built from now on.
+pub fn set_profile_additions(additions: SeatbeltProfileAdditions) {
+    let Ok(mut guard) = PROFILE_ADDITIONS.write() else {
+        tracing::warn!("Failed to acquire seatbelt profile additions lock");
+        return;
+    };
+    *guard = Some(additions);
+}
+
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(crate) fn profile_additions() -> SeatbeltProfileAdditions {
+    PROFILE_ADDITIONS
+        .read()
+        .ok()
+        .and_then(|additions| additions.clone())
+        .unwrap_or_default()
+}
+
+fn validate_mach_service(service: &str) -> Result<(), String> {
+    let valid = !service.is_empty()
+        && service
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "mach service `{service}` must be a global name made of letters, digits, `.`, `-` and `_`"
+        ))
+    }
+}
+
+/// Checks that `rules` is a sequence of balanced `(allow ...)` and
+/// `(deny ...)` forms. Strings (`"..."`, `#"..."`) and `;` comments are
+/// skipped so that parentheses inside them do not count.
+fn validate_rules(rules: &str) -> Result<(), String> {
+    let mut depth = 0usize;
+    let mut chars = rules.char_indices().peekable();
+    while let Some((offset, c)) = chars.next() {
+        match c {
+            ';' => {
+                for (_, c) in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '"' => skip_string(&mut chars, offset)?,
+            '#' if chars.peek().is_some_and(|(_, next)| *next == '"') => {
+                chars.next();
+                skip_string(&mut chars, offset)?;
+            }
+            '(' => {
+                if depth == 0 {
+                    let head: String = rules[offset + 1..]
+                        .trim_start()
+                        .chars()
+                        .take_while(|c| !c.is_whitespace() && *c != '(' && *c != ')')
+                        .collect();
+                    if !ALLOWED_RULE_HEADS.contains(&head.as_str()) {
+                        return Err(format!(
+                            "extra_rules may only contain (allow ...) and (deny ...) rules, found `({head}`"
+                        ));
+                    }
+                }
+                depth += 1;
+            }
+            ')' => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| format!("extra_rules has an unmatched `)` at byte {offset}"))?;
+            }
+            c if depth == 0 && !c.is_whitespace() => {
+                return Err(format!(
+                    "extra_rules has text outside a rule at byte {offset}"
+                ));
+            }
+            _ => {}
+        }
+    }
+    if depth == 0 {
+        Ok(())
+    } else {
+        Err("extra_rules has an unclosed `(`".to_string())
+    }
+}
+
+fn skip_string(
+    chars: &mut std::iter::Peekable<std::str::CharIndices<'_>>,
+    start: usize,
+) -> Result<(), String> {
+    while let Some((_, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '"' => return Ok(()),
+            _ => {}
+        }
+    }
+    Err(format!(
+        "extra_rules has an unterminated string at byte {start}"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn additions(
+        mach_services: &[&str],
+        extra_rules: &str,
+    ) -> Result<SeatbeltProfileAdditions, String> {
+        SeatbeltProfileAdditions::from_toml(&MacOsSeatbeltToml {
+            readable_paths: vec![
+                AbsolutePathBuf::from_absolute_path("/nonexistent/Xcode.app")
+                    .expect("absolute path"),
+            ],
+            mach_services: mach_services.iter().map(ToString::to_string).collect(),
+            extra_rules: Some(extra_rules.to_string()),
+        })
+    }
+
+    #[test]
+    fn renders_paths_services_and_rules() {
+        let additions = additions(
+            &["com.apple.CoreSimulator.CoreSimulatorService"],
+            "; brew needs this\n(allow file-read-metadata (literal \"/opt/homebrew\"))",
+        )
+        .expect("valid additions");
+
+        let (policy, params) = additions.policy();
+
+        assert_eq!(
+            policy,
+            "; additions from [macos_seatbelt]\n\
+             (allow file-read*\n(subpath (param \"EXTRA_READABLE_ROOT_0\"))\n)\n\
+             (allow mach-lookup\n    (global-name \"com.apple.CoreSimulator.CoreSimulatorService\")\n)\n\
+             ; brew needs this\n(allow file-read-metadata (literal \"/opt/homebrew\"))"
+        );
+        assert_eq!(
+            params,
+            vec![(
+                "EXTRA_READABLE_ROOT_0".to_string(),
+                PathBuf::from("/nonexistent/Xcode.app")
+            )]
+        );
+        assert_eq!(
+            SeatbeltProfileAdditions::default().policy(),
+            (String::new(), Vec::new())
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_rules_and_services() {
+        assert!(
+            additions(
+                &[],
+                "(allow process-exec (regex #\"^/usr/bin/(xcrun|xcodebuild)$\"))"
+            )
+            .is_ok()
+        );
+        assert!(additions(&["com.apple.foo\")) (allow default"], "").is_err());
+        assert!(additions(&[], "(version 1)").is_err());
+        assert!(additions(&[], "(allow default").is_err());
+        assert!(additions(&[], "(allow default))").is_err());
+        assert!(additions(&[], "allow default").is_err());
+        assert!(additions(&[], "(allow file-read* (literal \"/tmp)").is_err());
+    }
+}
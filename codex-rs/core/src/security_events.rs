@@ -0,0 +1,351 @@
+//! Forwarding of security-relevant events to syslog or a Splunk HTTP Event
+//! Collector (`[security_events]` in config.toml), so security teams can watch
+//! every Codex install from one place.
+//!
+//! The forwarded events are the audit log's approval decisions, sandbox
+//! violations, and sandbox escalations, whether or not `[audit_log]` is on.
+//! Denials are always forwarded; approvals only when a person gave them, not
+//! when policy let a command run without asking. Each event is one JSON object:
+//!
+//! ````text
+//! {"schema":"codex.security_event.v1","ts":"2026-01-01T00:00:00.000Z",
+//!  "host":"build-7","user":"alice","thread_id":"<uuid>",
+//!  "type":"approval_decision","call_id":"call_1","tool":"shell",
+//!  "decision":"denied","decided_by":"user"}
+//! ````
+//!
+//! Fields are only ever added to a schema version; anything else gets a new
+//! `schema` value. Syslog messages follow RFC 5424 with the JSON object as the
+//! message, facility `authpriv`, and severity `warning` for denials, violations
+//! and escalations (`notice` otherwise); over TCP they are octet-counted
+//! (RFC 6587). Splunk receives the object as the `event` of an HEC request.
+//!
+//! Delivery is best effort. One background task per session sends events in
+//! order and logs failures, so a slow or unreachable collector never holds up
+//! a turn.
+
+use std::io;
+
+use chrono::SecondsFormat;
+use chrono::Utc;
+use codex_protocol::ThreadId;
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+use tokio::net::UdpSocket;
+use tokio::sync::mpsc;
+use tracing::debug;
+use tracing::warn;
+
+use crate::audit_log::ApprovalDecider;
+use crate::audit_log::AuditEvent;
+use crate::config::types::SecurityEventsConfig;
+use crate::config::types::SplunkHecConfig;
+use crate::config::types::SyslogTarget;
+use crate::default_client::build_reqwest_client;
+
+const SCHEMA: &str = "codex.security_event.v1";
+
+/// RFC 5424 facility for security/authorization messages.
+const FACILITY_AUTHPRIV: u8 = 10;
+const SEVERITY_WARNING: u8 = 4;
+const SEVERITY_NOTICE: u8 = 5;
+
+const SPLUNK_SOURCETYPE: &str = "codex:security_event";
+
+#[derive(Serialize)]
+struct SecurityEventRecord<'a> {
+    schema: &'static str,
+    ts: &'a str,
+    host: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user: Option<&'a str>,
+    thread_id: &'a str,
+    #[serde(flatten)]
+    event: &'a AuditEvent,
+}
+
+#[derive(Debug)]
+struct Outgoing {
+    ts: String,
+    severity: u8,
+    json: String,
+}
+
+/// Forwarder for one session.
+#[derive(Debug, Clone)]
+pub(crate) struct SecurityEventForwarder {
+    tx: mpsc::UnboundedSender<Outgoing>,
+    host: String,
+    user: Option<String>,
+    thread_id: String,
+}
+
+impl SecurityEventForwarder {
+    pub(crate) fn new(config: SecurityEventsConfig, thread_id: ThreadId) -> Self {
+        let host = hostname();
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(deliver(config, host.clone(), rx));
+        let user = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .ok()
+            .filter(|user| !user.is_empty());
+        Self {
+            tx,
+            host,
+            user,
+            thread_id: thread_id.to_string(),
+        }
+    }
+
+    /// Queue `event` if it is one the forwarder sends.
+    pub(crate) fn forward(&self, event: &AuditEvent) {
+        let Some(severity) = severity(event) else {
+            return;
+        };
+        let ts = Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true);
+        let record = SecurityEventRecord {
+            schema: SCHEMA,
+            ts: &ts,
+            host: &self.host,
+            user: self.user.as_deref(),
+            thread_id: &self.thread_id,
+            event,
+        };
+        let json = match serde_json::to_string(&record) {
+            Ok(json) => json,
+            Err(err) => {
+                warn!("failed to serialize security event: {err}");
+                return;
+            }
+        };
+        if self.tx.send(Outgoing { ts, severity, json }).is_err() {
+            debug!("security event forwarder stopped; dropping event");
+        }
+    }
+}
+
+/// Syslog severity of a forwarded event, or `None` for events that are not
+/// forwarded.
+fn severity(event: &AuditEvent) -> Option<u8> {
+    match event {
+        AuditEvent::ApprovalDecision {
+            decision,
+            decided_by,
+            ..
+        } => {
+            if matches!(decision.as_str(), "denied" | "abort") {
+                Some(SEVERITY_WARNING)
+            } else if *decided_by == ApprovalDecider::Policy {
+                None
+            } else {
+                Some(SEVERITY_NOTICE)
+            }
+        }
+        AuditEvent::SandboxViolation { .. } | AuditEvent::SandboxEscalation { .. } => {
+            Some(SEVERITY_WARNING)
+        }
+        AuditEvent::CommandExecuted { .. } => None,
+    }
+}
+
+async fn deliver(
+    config: SecurityEventsConfig,
+    host: String,
+    mut rx: mpsc::UnboundedReceiver<Outgoing>,
+) {
+    let SecurityEventsConfig { syslog, splunk_hec } = config;
+    let mut syslog = syslog.map(|target| SyslogSink { target, tcp: None });
+    let client = splunk_hec.as_ref().map(|_| build_reqwest_client());
+    while let Some(event) = rx.recv().await {
+        if let Some(sink) = syslog.as_mut() {
+            let message = syslog_message(&host, &event);
+            if let Err(err) = sink.send(&message).await {
+                warn!("failed to forward security event to syslog: {err}");
+            }
+        }
+        if let (Some(hec), Some(client)) = (&splunk_hec, &client)
+            && let Err(err) = send_to_splunk(client, hec, &host, &event).await
+        {
+            warn!("failed to forward security event to Splunk: {err}");
+        }
+    }
+}
+
+/// RFC 5424 message with the event as its body.
+fn syslog_message(host: &str, event: &Outgoing) -> String {
+    let priority = FACILITY_AUTHPRIV * 8 + event.severity;
+    let pid = std::process::id();
+    format!(
+        "<{priority}>1 {} {host} codex {pid} security - {}",
+        event.ts, event.json
+    )
+}
+
+struct SyslogSink {
+    target: SyslogTarget,
+    /// Open connection for a TCP target, reopened after a failed write.
+    tcp: Option<TcpStream>,
+}
+
+impl SyslogSink {
+    async fn send(&mut self, message: &str) -> io::Result<()> {
+        match &self.target {
+            SyslogTarget::Udp(address) => {
+                let Some(remote) = tokio::net::lookup_host(address.as_str()).await?.next() else {
+                    return Err(io::Error::other(format!("{address} did not resolve")));
+                };
+                let local = if remote.is_ipv4() {
+                    "0.0.0.0:0"
+                } else {
+                    "[::]:0"
+                };
+                let socket = UdpSocket::bind(local).await?;
+                socket.send_to(message.as_bytes(), remote).await?;
+                Ok(())
+            }
+            SyslogTarget::Tcp(address) => {
+                let frame = format!("{} {message}", message.len());
+                if let Some(stream) = self.tcp.as_mut()
+                    && stream.write_all(frame.as_bytes()).await.is_ok()
+                {
+                    return Ok(());
+                }
+                self.tcp = None;
+                let mut stream = TcpStream::connect(address.as_str()).await?;
+                stream.write_all(frame.as_bytes()).await?;
+                self.tcp = Some(stream);
+                Ok(())
+            }
+            #[cfg(unix)]
+            SyslogTarget::Unix(path) => {
+                let socket = tokio::net::UnixDatagram::unbound()?;
+                socket.send_to(message.as_bytes(), path).await?;
+                Ok(())
+            }
+            #[cfg(not(unix))]
+            SyslogTarget::Unix(path) => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!(
+                    "cannot send to {}: unix sockets are unsupported",
+                    path.display()
+                ),
+            )),
+        }
+    }
+}
+
+async fn send_to_splunk(
+    client: &reqwest::Client,
+    hec: &SplunkHecConfig,
+    host: &str,
+    event: &Outgoing,
+) -> anyhow::Result<()> {
+    let token = std::env::var(&hec.token_env)
+        .map_err(|_| anyhow::anyhow!("{} is not set", hec.token_env))?;
+    let body = splunk_body(hec, host, event)?;
+    client
+        .post(&hec.url)
+        .header(reqwest::header::AUTHORIZATION, format!("Splunk {token}"))
+        .json(&body)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+fn splunk_body(
+    hec: &SplunkHecConfig,
+    host: &str,
+    event: &Outgoing,
+) -> serde_json::Result<serde_json::Value> {
+    let mut body = serde_json::json!({
+        "event": serde_json::from_str::<serde_json::Value>(&event.json)?,
+        "host": host,
+        "source": "codex",
+        "sourcetype": SPLUNK_SOURCETYPE,
+    });
+    if let Some(index) = &hec.index {
+        body["index"] = serde_json::Value::String(index.clone());
+    }
+    Ok(body)
+}
+
+fn hostname() -> String {
+    std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|host| host.trim().to_string())
+        .filter(|host| !host.is_empty() && !host.contains(char::is_whitespace))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tokio::net::UdpSocket;
+
+    fn denial() -> AuditEvent {
+        AuditEvent::ApprovalDecision {
+            call_id: "call_1".to_string(),
+            tool: "shell".to_string(),
+            decision: "denied".to_string(),
+            decided_by: ApprovalDecider::User,
+            reason: None,
+        }
+    }
+
+    #[test]
+    fn forwards_denials_and_people_but_not_policy_approvals() {
+        let policy_approval = AuditEvent::ApprovalDecision {
+            call_id: "call_2".to_string(),
+            tool: "shell".to_string(),
+            decision: "approved".to_string(),
+            decided_by: ApprovalDecider::Policy,
+            reason: None,
+        };
+        let violation = AuditEvent::SandboxViolation {
+            call_id: "call_3".to_string(),
+            tool: "shell".to_string(),
+            sandbox: "seatbelt".to_string(),
+            network_host: Some("example.com".to_string()),
+        };
+
+        assert_eq!(severity(&denial()), Some(SEVERITY_WARNING));
+        assert_eq!(severity(&policy_approval), None);
+        assert_eq!(severity(&violation), Some(SEVERITY_WARNING));
+    }
+
+    #[tokio::test]
+    async fn sends_rfc5424_messages_over_udp() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").await.expect("bind receiver");
+        let address = receiver.local_addr().expect("receiver address");
+        let forwarder = SecurityEventForwarder::new(
+            SecurityEventsConfig {
+                syslog: Some(SyslogTarget::Udp(address.to_string())),
+                splunk_hec: None,
+            },
+            ThreadId::new(),
+        );
+
+        forwarder.forward(&denial());
+
+        let mut buf = vec![0; 4096];
+        let len = tokio::time::timeout(std::time::Duration::from_secs(5), receiver.recv(&mut buf))
+            .await
+            .expect("message before timeout")
+            .expect("receive message");
+        let message = String::from_utf8_lossy(&buf[..len]).to_string();
+        assert!(message.starts_with("<84>1 "), "{message}");
+        let json = message
+            .split_once(" security - ")
+            .map(|(_, json)| json)
+            .expect("message body");
+        let record: serde_json::Value = serde_json::from_str(json).expect("json body");
+        assert_eq!(record["schema"], SCHEMA);
+        assert_eq!(record["type"], "approval_decision");
+        assert_eq!(record["decision"], "denied");
+    }
+}
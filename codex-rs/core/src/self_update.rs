@@ -0,0 +1,396 @@
+//! Updates from a self-hosted release server configured under `[updates]`.
+//!
+//! The server publishes one manifest per channel at
+//! `<endpoint>/<channel>/latest.json`:
+//!
+//! ```json
+//! {
+//!   "version": "0.99.0",
+//!   "artifacts": {
+//!     "x86_64-linux": { "url": "codex-x86_64-linux", "sha256": "…", "signature": "…" }
+//!   }
+//! }
+//! ```
+//!
+//! Artifacts are keyed by `<arch>-<os>` and hold the `codex` executable itself.
+//! Relative URLs resolve against the manifest. `signature` is a base64 Ed25519
+//! signature over the release tuple (see [`signed_message`]), so a server
+//! cannot pass off an old build, or a build from another channel or platform,
+//! under a different version. A download replaces the running binary only
+//! after both the checksum and the signature match.
+
+use std::collections::HashMap;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use ring::signature::ED25519;
+use ring::signature::UnparsedPublicKey;
+use serde::Deserialize;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::config::types::ReleaseServer;
+use crate::config::types::UpdateChannel;
+use crate::default_client::create_client;
+
+const MANIFEST_FILENAME: &str = "latest.json";
+
+/// Latest release on one channel of a self-hosted release server.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseManifest {
+    pub version: String,
+    pub artifacts: HashMap<String, ReleaseArtifact>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseArtifact {
+    pub url: String,
+    /// Hex-encoded SHA-256 of the executable.
+    pub sha256: String,
+    /// Base64-encoded Ed25519 signature over [`signed_message`].
+    pub signature: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SelfUpdateError {
+    #[error("invalid release URL {url}: {reason}")]
+    InvalidUrl { url: String, reason: String },
+    #[error("request to the release server failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("the {channel} channel has no build for {platform}")]
+    NoArtifact {
+        channel: UpdateChannel,
+        platform: String,
+    },
+    #[error("downloaded binary does not match the manifest checksum")]
+    ChecksumMismatch,
+    #[error("release is not signed by updates.public_key")]
+    BadSignature,
+    #[error("failed to install {}: {source}", path.display())]
+    Install {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// Key of this build's artifact in a [`ReleaseManifest`], e.g. `aarch64-macos`.
+pub fn current_platform() -> String {
+    format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS)
+}
+
+fn manifest_url(endpoint: &str, channel: UpdateChannel) -> String {
+    format!(
+        "{}/{channel}/{MANIFEST_FILENAME}",
+        endpoint.trim_end_matches('/')
+    )
+}
+
+fn artifact_url(manifest_url: &str, artifact: &ReleaseArtifact) -> Result<String, SelfUpdateError> {
+    reqwest::Url::parse(manifest_url)
+        .and_then(|base| base.join(&artifact.url))
+        .map(String::from)
+        .map_err(|err| SelfUpdateError::InvalidUrl {
+            url: artifact.url.clone(),
+            reason: err.to_string(),
+        })
+}
+
+/// Fetches the latest release published on `channel`.
+pub async fn fetch_manifest(
+    server: &ReleaseServer,
+    channel: UpdateChannel,
+) -> Result<ReleaseManifest, SelfUpdateError> {
+    let manifest = create_client()
+        .get(manifest_url(&server.endpoint, channel))
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<ReleaseManifest>()
+        .await?;
+    Ok(manifest)
+}
+
+/// Downloads this platform's executable from `manifest` and verifies it
+/// against the server's public key.
+pub async fn download_release(
+    server: &ReleaseServer,
+    channel: UpdateChannel,
+    manifest: &ReleaseManifest,
+) -> Result<Vec<u8>, SelfUpdateError> {
+    let platform = current_platform();
+    let Some(artifact) = manifest.artifacts.get(&platform) else {
+        return Err(SelfUpdateError::NoArtifact { channel, platform });
+    };
+    let url = artifact_url(&manifest_url(&server.endpoint, channel), artifact)?;
+    let bytes = create_client()
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+    let message = signed_message(&manifest.version, channel, &platform, &artifact.sha256);
+    verify_artifact(&bytes, artifact, &message, &server.public_key)?;
+    Ok(bytes.to_vec())
+}
+
+/// The bytes a release signature covers: one line each for a fixed prefix,
+/// the version, the channel, the platform, and the lowercase hex SHA-256 of
+/// the executable, e.g. `codex-release\n0.99.0\nstable\nx86_64-linux\n<hex>\n`.
+pub fn signed_message(
+    version: &str,
+    channel: UpdateChannel,
+    platform: &str,
+    sha256: &str,
+) -> Vec<u8> {
+    format!(
+        "codex-release\n{}\n{channel}\n{platform}\n{}\n",
+        version.trim(),
+        sha256.trim().to_ascii_lowercase()
+    )
+    .into_bytes()
+}
+
+/// True when `offered` is a later version than `current`. Versions that are
+/// not semver never count as later.
+pub fn is_newer_release(offered: &str, current: &str) -> bool {
+    match (
+        semver::Version::parse(offered.trim()),
+        semver::Version::parse(current.trim()),
+    ) {
+        (Ok(offered), Ok(current)) => offered > current,
+        _ => false,
+    }
+}
+
+fn verify_artifact(
+    bytes: &[u8],
+    artifact: &ReleaseArtifact,
+    message: &[u8],
+    public_key: &[u8],
+) -> Result<(), SelfUpdateError> {
+    let checksum = format!("{:x}", Sha256::digest(bytes));
+    if !checksum.eq_ignore_ascii_case(artifact.sha256.trim()) {
+        return Err(SelfUpdateError::ChecksumMismatch);
+    }
+    let signature = BASE64_STANDARD
+        .decode(artifact.signature.trim())
+        .map_err(|_| SelfUpdateError::BadSignature)?;
+    UnparsedPublicKey::new(&ED25519, public_key)
+        .verify(message, &signature)
+        .map_err(|_| SelfUpdateError::BadSignature)
+}
+
+/// Replaces the executable at `target` with `bytes`.
+///
+/// The new binary is written next to `target` and renamed over it, so a
+/// failed install leaves the old binary in place.
+pub fn install_binary(bytes: &[u8], target: &Path) -> Result<(), SelfUpdateError> {
+    let install_err = |source| SelfUpdateError::Install {
+        path: target.to_path_buf(),
+        source,
+    };
+    let dir = target.parent().unwrap_or_else(|| Path::new("."));
+    let mut staged = tempfile::NamedTempFile::new_in(dir).map_err(install_err)?;
+    staged.write_all(bytes).map_err(install_err)?;
+    staged.as_file().sync_all().map_err(install_err)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        staged
+            .as_file()
+            .set_permissions(std::fs::Permissions::from_mode(0o755))
+            .map_err(install_err)?;
+    }
+    #[cfg(windows)]
+    {
+        // Windows cannot replace a running executable, but it can rename it.
+        let previous = target.with_extension("old.exe");
+        replace_renaming_previous(target, &previous, || {
+            staged.persist(target).map(drop).map_err(|err| err.error)
+        })
+        .map_err(install_err)
+    }
+    #[cfg(not(windows))]
+    {
+        staged
+            .persist(target)
+            .map_err(|err| install_err(err.error))?;
+        Ok(())
+    }
+}
+
+/// Moves `target` aside to `previous` and runs `persist` to put the new
+/// binary in its place. If `persist` fails, `previous` is moved back so the
+/// old binary stays installed.
+#[cfg(any(windows, test))]
+fn replace_renaming_previous(
+    target: &Path,
+    previous: &Path,
+    persist: impl FnOnce() -> io::Result<()>,
+) -> io::Result<()> {
+    let _ = std::fs::remove_file(previous);
+    let moved = target.exists();
+    if moved {
+        std::fs::rename(target, previous)?;
+    }
+    if let Err(err) = persist() {
+        if moved {
+            std::fs::rename(previous, target)?;
+        }
+        return Err(err);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use ring::rand::SystemRandom;
+    use ring::signature::Ed25519KeyPair;
+    use ring::signature::KeyPair;
+
+    fn signed_artifact(bytes: &[u8], version: &str) -> (ReleaseArtifact, Vec<u8>) {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).expect("generate key");
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).expect("parse key");
+        let sha256 = format!("{:x}", Sha256::digest(bytes));
+        let message = signed_message(version, UpdateChannel::Stable, "x86_64-linux", &sha256);
+        let artifact = ReleaseArtifact {
+            url: "codex".to_string(),
+            sha256,
+            signature: BASE64_STANDARD.encode(key_pair.sign(&message)),
+        };
+        (artifact, key_pair.public_key().as_ref().to_vec())
+    }
+
+    #[test]
+    fn verifies_checksum_and_signature() {
+        let binary = b"#!/bin/sh\necho codex\n";
+        let (artifact, public_key) = signed_artifact(binary, "0.99.0");
+        let message = |version: &str, channel: UpdateChannel| {
+            signed_message(version, channel, "x86_64-linux", &artifact.sha256)
+        };
+        verify_artifact(
+            binary,
+            &artifact,
+            &message("0.99.0", UpdateChannel::Stable),
+            &public_key,
+        )
+        .expect("valid artifact");
+
+        let tampered = b"#!/bin/sh\necho pwned\n";
+        assert!(matches!(
+            verify_artifact(
+                tampered,
+                &artifact,
+                &message("0.99.0", UpdateChannel::Stable),
+                &public_key
+            ),
+            Err(SelfUpdateError::ChecksumMismatch)
+        ));
+
+        // A signed build cannot be offered under another version or channel.
+        for (version, channel) in [
+            ("1.0.0", UpdateChannel::Stable),
+            ("0.99.0", UpdateChannel::Nightly),
+        ] {
+            assert!(matches!(
+                verify_artifact(binary, &artifact, &message(version, channel), &public_key),
+                Err(SelfUpdateError::BadSignature)
+            ));
+        }
+
+        let (_, other_key) = signed_artifact(binary, "0.99.0");
+        assert!(matches!(
+            verify_artifact(
+                binary,
+                &artifact,
+                &message("0.99.0", UpdateChannel::Stable),
+                &other_key
+            ),
+            Err(SelfUpdateError::BadSignature)
+        ));
+    }
+
+    #[test]
+    fn only_later_semver_versions_are_newer() {
+        assert_eq!(
+            [
+                is_newer_release("0.100.0", "0.99.0"),
+                is_newer_release("0.99.0", "0.99.0"),
+                is_newer_release("0.98.5", "0.99.0"),
+                is_newer_release("0.99.0", "0.99.0-alpha.1"),
+                is_newer_release("nightly-20261015", "0.99.0"),
+            ],
+            [true, false, false, true, false]
+        );
+    }
+
+    #[test]
+    fn resolves_artifact_urls_against_the_manifest() {
+        let manifest = manifest_url(
+            "https://releases.example.com/codex/",
+            UpdateChannel::Nightly,
+        );
+        assert_eq!(
+            manifest,
+            "https://releases.example.com/codex/nightly/latest.json"
+        );
+        let artifact = |url: &str| ReleaseArtifact {
+            url: url.to_string(),
+            sha256: String::new(),
+            signature: String::new(),
+        };
+        assert_eq!(
+            artifact_url(&manifest, &artifact("codex-x86_64-linux")).expect("relative url"),
+            "https://releases.example.com/codex/nightly/codex-x86_64-linux"
+        );
+        assert_eq!(
+            artifact_url(&manifest, &artifact("https://cdn.example.com/codex")).expect("absolute"),
+            "https://cdn.example.com/codex"
+        );
+    }
+
+    #[test]
+    fn installs_over_the_existing_binary() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let target = dir.path().join("codex");
+        std::fs::write(&target, b"old").expect("write old binary");
+
+        install_binary(b"new", &target).expect("install");
+
+        assert_eq!(std::fs::read(&target).expect("read binary"), b"new");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&target)
+                .expect("metadata")
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o755);
+        }
+    }
+
+    #[test]
+    fn failed_replace_restores_the_previous_binary() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let target = dir.path().join("codex.exe");
+        let previous = dir.path().join("codex.old.exe");
+        std::fs::write(&target, b"old").expect("write old binary");
+
+        let err = replace_renaming_previous(&target, &previous, || {
+            Err(io::Error::other("persist failed"))
+        })
+        .expect_err("persist fails");
+
+        assert_eq!(err.to_string(), "persist failed");
+        assert_eq!(std::fs::read(&target).expect("read binary"), b"old");
+        assert!(!previous.exists());
+    }
+}
@@ -1,15 +1,36 @@
 /// Helpers for identifying model-visible "session prefix" messages.
 ///
 /// A session prefix is a user-role message that carries configuration or state needed by
-/// follow-up turns (e.g. `<environment_context>`, `<turn_aborted>`). These items are persisted in
-/// history so the model can see them, but they are not user intent and must not create user-turn
-/// boundaries.
+/// follow-up turns (e.g. `<environment_context>`, `<repo_context>`, `<project_commands>`,
+/// `<project_memory>`, `<context_pack>`, `<workspace_scope>`, `<turn_aborted>`,
+/// `<turn_interrupted>`). These items are persisted in history so the model can see them, but
+/// they are not user intent and must not create user-turn boundaries.
 pub(crate) const ENVIRONMENT_CONTEXT_OPEN_TAG: &str = "<environment_context>";
+pub(crate) const REPO_CONTEXT_OPEN_TAG: &str = "<repo_context>";
+pub(crate) const REPO_CONTEXT_CLOSE_TAG: &str = "</repo_context>";
+pub(crate) const PROJECT_COMMANDS_OPEN_TAG: &str = "<project_commands>";
+pub(crate) const PROJECT_COMMANDS_CLOSE_TAG: &str = "</project_commands>";
+pub(crate) const PROJECT_MEMORY_OPEN_TAG: &str = "<project_memory>";
+pub(crate) const PROJECT_MEMORY_CLOSE_TAG: &str = "</project_memory>";
+/// Open tag without its closing `>`: packs carry `name` and `source` attributes.
+pub(crate) const CONTEXT_PACK_OPEN_TAG: &str = "<context_pack";
+pub(crate) const CONTEXT_PACK_CLOSE_TAG: &str = "</context_pack>";
+pub(crate) const WORKSPACE_SCOPE_OPEN_TAG: &str = "<workspace_scope>";
+pub(crate) const WORKSPACE_SCOPE_CLOSE_TAG: &str = "</workspace_scope>";
 pub(crate) const TURN_ABORTED_OPEN_TAG: &str = "<turn_aborted>";
+pub(crate) const TURN_INTERRUPTED_OPEN_TAG: &str = "<turn_interrupted>";
+pub(crate) const TURN_INTERRUPTED_CLOSE_TAG: &str = "</turn_interrupted>";
 
 /// Returns true if `text` starts with a session prefix marker (case-insensitive).
 pub(crate) fn is_session_prefix(text: &str) -> bool {
     let trimmed = text.trim_start();
     let lowered = trimmed.to_ascii_lowercase();
-    lowered.starts_with(ENVIRONMENT_CONTEXT_OPEN_TAG) || lowered.starts_with(TURN_ABORTED_OPEN_TAG)
+    lowered.starts_with(ENVIRONMENT_CONTEXT_OPEN_TAG)
+        || lowered.starts_with(REPO_CONTEXT_OPEN_TAG)
+        || lowered.starts_with(PROJECT_COMMANDS_OPEN_TAG)
+        || lowered.starts_with(PROJECT_MEMORY_OPEN_TAG)
+        || lowered.starts_with(CONTEXT_PACK_OPEN_TAG)
+        || lowered.starts_with(WORKSPACE_SCOPE_OPEN_TAG)
+        || lowered.starts_with(TURN_ABORTED_OPEN_TAG)
+        || lowered.starts_with(TURN_INTERRUPTED_OPEN_TAG)
 }
@@ -3,6 +3,7 @@ use crate::config::Permissions;
 use crate::config_loader::ConfigLayerStack;
 use crate::config_loader::ConfigLayerStackOrdering;
 use crate::config_loader::default_project_root_markers;
+use crate::config_loader::find_project_root_blocking;
 use crate::config_loader::merge_toml_values;
 use crate::config_loader::project_root_markers_from_config;
 use crate::skills::model::SkillDependencies;
@@ -269,7 +270,7 @@ fn dedupe_skill_roots_by_path(roots: &mut Vec<SkillRoot>) {
 
 fn repo_agents_skill_roots(config_layer_stack: &ConfigLayerStack, cwd: &Path) -> Vec<SkillRoot> {
     let project_root_markers = project_root_markers_from_stack(config_layer_stack);
-    let project_root = find_project_root(cwd, &project_root_markers);
+    let project_root = find_project_root_blocking(cwd, &project_root_markers);
     let dirs = dirs_between_project_root_and_cwd(cwd, &project_root);
     let mut roots = Vec::new();
     for dir in dirs {
@@ -305,23 +306,6 @@ fn project_root_markers_from_stack(config_layer_stack: &ConfigLayerStack) -> Vec
     }
 }
 
-fn find_project_root(cwd: &Path, project_root_markers: &[String]) -> PathBuf {
-    if project_root_markers.is_empty() {
-        return cwd.to_path_buf();
-    }
-
-    for ancestor in cwd.ancestors() {
-        for marker in project_root_markers {
-            let marker_path = ancestor.join(marker);
-            if marker_path.exists() {
-                return ancestor.to_path_buf();
-            }
-        }
-    }
-
-    cwd.to_path_buf()
-}
-
 fn dirs_between_project_root_and_cwd(cwd: &Path, project_root: &Path) -> Vec<PathBuf> {
     let mut dirs = cwd
         .ancestors()
@@ -4,20 +4,35 @@ use crate::AuthManager;
 use crate::RolloutRecorder;
 use crate::agent::AgentControl;
 use crate::analytics_client::AnalyticsEventsClient;
+use crate::audit_log::AuditEvent;
+use crate::audit_log::AuditLog;
 use crate::client::ModelClient;
 use crate::config::StartedNetworkProxy;
+use crate::context_packs::ContextPackCache;
+use crate::disk_quota::DiskQuota;
 use crate::exec_policy::ExecPolicyManager;
 use crate::file_watcher::FileWatcher;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::models_manager::manager::ModelsManager;
+use crate::patch_backups::PatchBackups;
+use crate::project_commands::ProjectCommands;
+use crate::read_prefetch::ReadPrefetchCache;
+use crate::security_events::SecurityEventForwarder;
 use crate::skills::SkillsManager;
 use crate::state_db::StateDbHandle;
+use crate::tools::browser::BrowserHandle;
+use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::network_approval::NetworkApprovalService;
+use crate::tools::output_summary::FullOutputs;
+use crate::tools::py_repl::PyReplHandle;
 use crate::tools::sandboxing::ApprovalStore;
+use crate::turn_journal::TurnJournal;
 use crate::unified_exec::UnifiedExecProcessManager;
+use crate::usage_quota::UsageQuota;
 use codex_hooks::Hooks;
 use codex_otel::OtelManager;
 use tokio::sync::Mutex;
+use tokio::sync::OnceCell;
 use tokio::sync::RwLock;
 use tokio::sync::watch;
 use tokio_util::sync::CancellationToken;
@@ -43,6 +58,43 @@ pub(crate) struct SessionServices {
     pub(crate) network_proxy: Option<StartedNetworkProxy>,
     pub(crate) network_approval: Arc<NetworkApprovalService>,
     pub(crate) state_db: Option<StateDbHandle>,
+    pub(crate) py_repl: PyReplHandle,
+    pub(crate) browser: BrowserHandle,
+    /// Accumulates every apply_patch change made since the last session commit.
+    pub(crate) session_diff_tracker: SharedTurnDiffTracker,
+    /// Backups of the file versions replaced by apply_patch, for `Op::UndoFileChange`.
+    pub(crate) patch_backups: Mutex<PatchBackups>,
+    /// Full output of exec calls that were summarized for the model, for `fetch_output`.
+    pub(crate) full_outputs: Mutex<FullOutputs>,
+    /// Commands from `.codex/project.toml`, loaded on first use.
+    pub(crate) project_commands: OnceCell<ProjectCommands>,
+    /// Content gathered for `[context_packs]`, reused until each pack's TTL runs out.
+    pub(crate) context_packs: ContextPackCache,
+    /// Files prefetched for `read_file` under the `read_prefetch` feature.
+    pub(crate) read_prefetch: ReadPrefetchCache,
+    /// Shared hash-chained audit log, when `[audit_log]` is enabled.
+    pub(crate) audit_log: Option<AuditLog>,
+    /// Forwarder for `[security_events]`, when configured.
+    pub(crate) security_events: Option<SecurityEventForwarder>,
+    /// Growth of the writable roots against `session_disk_quota_mb`.
+    pub(crate) disk_quota: DiskQuota,
+    /// Daily and weekly limits of the active profile, when it has any.
+    pub(crate) usage_quota: Option<UsageQuota>,
+    /// Crash-recovery journal of the turn in flight; `None` for ephemeral sessions.
+    pub(crate) turn_journal: Option<TurnJournal>,
     /// Session-scoped model client shared across turns.
     pub(crate) model_client: ModelClient,
 }
+
+impl SessionServices {
+    /// Forwards `event` to the security event sink and appends it to the
+    /// audit log, whichever of the two are configured.
+    pub(crate) async fn record_audit_event(&self, event: AuditEvent) {
+        if let Some(forwarder) = &self.security_events {
+            forwarder.forward(&event);
+        }
+        if let Some(audit_log) = &self.audit_log {
+            audit_log.record(event).await;
+        }
+    }
+}
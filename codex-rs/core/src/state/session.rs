@@ -1,17 +1,27 @@
 //! Session-wide mutable state.
 
 use codex_protocol::models::ResponseItem;
+use codex_protocol::plan_tool::PlanItemArg;
+use codex_protocol::plan_tool::UpdatePlanArgs;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::path::PathBuf;
 
 use crate::codex::SessionConfiguration;
 use crate::context_manager::ContextManager;
+use crate::protocol::ModelTier;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
 use crate::tasks::RegularTask;
 use crate::truncate::TruncationPolicy;
 
+const PLAN_STEP_ID_PREFIX: &str = "step-";
+
+fn plan_step_id(n: u64) -> String {
+    format!("{PLAN_STEP_ID_PREFIX}{n}")
+}
+
 /// Persistent, session-scoped state previously stored directly on `Session`.
 pub(crate) struct SessionState {
     pub(crate) session_configuration: SessionConfiguration,
@@ -25,18 +35,35 @@ pub(crate) struct SessionState {
     /// TODO(owen): This is a temporary solution to avoid updating a thread's updated_at
     /// timestamp when resuming a session. Remove this once SQLite is in place.
     pub(crate) initial_context_seeded: bool,
+    /// Set on resume when `[resume_summary]` is configured; the first turn
+    /// then summarizes the older turns before sampling.
+    pub(crate) resume_summary_pending: bool,
     /// Previous model seen by the session, used for model-switch handling on task start.
     previous_model: Option<String>,
+    /// Tier requested by `Op::RouteNextTurn`, consumed by the next turn.
+    pub(crate) next_turn_model_tier: Option<ModelTier>,
     /// Startup regular task pre-created during session initialization.
     pub(crate) startup_regular_task: Option<RegularTask>,
     pub(crate) active_mcp_tool_selection: Option<Vec<String>>,
     pub(crate) active_connector_selection: HashSet<String>,
+    /// Regions already returned by `read_file`, keyed by path and a digest of
+    /// the returned text. Cleared whenever history is replaced (e.g. after
+    /// compaction) since the model may no longer see the earlier output.
+    read_file_regions: HashSet<(PathBuf, u64)>,
+    /// Latest `update_plan` checklist, with stable step ids.
+    plan: Vec<PlanItemArg>,
+    /// Numeric suffix of the most recently assigned plan step id.
+    last_plan_step_id: u64,
+    /// Why the session was stopped by `Op::EmergencyStop` or the `STOP`
+    /// file; once set, no new tasks start.
+    pub(crate) emergency_stop: Option<String>,
 }
 
 impl SessionState {
     /// Create a new session state mirroring previous `State::default()` semantics.
     pub(crate) fn new(session_configuration: SessionConfiguration) -> Self {
-        let history = ContextManager::new();
+        let mut history = ContextManager::new();
+        history.set_tokenizer_for_model(session_configuration.collaboration_mode.model());
         Self {
             session_configuration,
             history,
@@ -45,10 +72,16 @@ impl SessionState {
             dependency_env: HashMap::new(),
             mcp_dependency_prompted: HashSet::new(),
             initial_context_seeded: false,
+            resume_summary_pending: false,
             previous_model: None,
+            next_turn_model_tier: None,
             startup_regular_task: None,
             active_mcp_tool_selection: None,
             active_connector_selection: HashSet::new(),
+            read_file_regions: HashSet::new(),
+            plan: Vec::new(),
+            last_plan_step_id: 0,
+            emergency_stop: None,
         }
     }
 
@@ -74,6 +107,12 @@ impl SessionState {
 
     pub(crate) fn replace_history(&mut self, items: Vec<ResponseItem>) {
         self.history.replace(items);
+        self.read_file_regions.clear();
+    }
+
+    /// Counts history tokens with `model`'s vocabulary.
+    pub(crate) fn set_tokenizer_for_model(&mut self, model: &str) {
+        self.history.set_tokenizer_for_model(model);
     }
 
     pub(crate) fn set_token_info(&mut self, info: Option<TokenUsageInfo>) {
@@ -144,6 +183,57 @@ impl SessionState {
         self.dependency_env.clone()
     }
 
+    /// Records a `read_file` result; returns `false` when the same region was
+    /// already returned earlier in the session.
+    pub(crate) fn record_read_file_region(&mut self, path: PathBuf, digest: u64) -> bool {
+        self.read_file_regions.insert((path, digest))
+    }
+
+    /// Assigns step ids to a new plan: a step whose text matches a step of the
+    /// current plan keeps that step's id, any other step gets a fresh one.
+    pub(crate) fn apply_plan_update(&mut self, plan: &mut [PlanItemArg]) {
+        let mut unmatched: Vec<(String, String)> = self
+            .plan
+            .drain(..)
+            .filter_map(|item| item.id.map(|id| (item.step, id)))
+            .collect();
+        for item in plan.iter_mut() {
+            let matched = unmatched
+                .iter()
+                .position(|(step, _)| step.trim() == item.step.trim());
+            item.id = Some(match matched {
+                Some(index) => unmatched.remove(index).1,
+                None => {
+                    self.last_plan_step_id += 1;
+                    plan_step_id(self.last_plan_step_id)
+                }
+            });
+        }
+        self.plan = plan.to_vec();
+    }
+
+    /// Restores the plan from the `update_plan` events of a resumed rollout, oldest first.
+    pub(crate) fn restore_plan<'a>(
+        &mut self,
+        updates: impl IntoIterator<Item = &'a UpdatePlanArgs>,
+    ) {
+        for update in updates {
+            for id in update.plan.iter().filter_map(|item| item.id.as_deref()) {
+                if let Some(n) = id
+                    .strip_prefix(PLAN_STEP_ID_PREFIX)
+                    .and_then(|n| n.parse().ok())
+                {
+                    self.last_plan_step_id = self.last_plan_step_id.max(n);
+                }
+            }
+            self.plan = update.plan.clone();
+        }
+    }
+
+    pub(crate) fn plan(&self) -> &[PlanItemArg] {
+        &self.plan
+    }
+
     pub(crate) fn set_startup_regular_task(&mut self, task: RegularTask) {
         self.startup_regular_task = Some(task);
     }
@@ -243,6 +333,7 @@ mod tests {
     use super::*;
     use crate::codex::make_session_configuration_for_tests;
     use crate::protocol::RateLimitWindow;
+    use codex_protocol::plan_tool::StepStatus;
     use pretty_assertions::assert_eq;
 
     #[tokio::test]
@@ -498,4 +589,72 @@ mod tests {
             })
         );
     }
+
+    fn plan_items(steps: &[(&str, StepStatus)]) -> Vec<PlanItemArg> {
+        steps
+            .iter()
+            .map(|(step, status)| PlanItemArg {
+                step: step.to_string(),
+                status: status.clone(),
+                id: None,
+            })
+            .collect()
+    }
+
+    fn plan_ids(plan: &[PlanItemArg]) -> Vec<Option<&str>> {
+        plan.iter().map(|item| item.id.as_deref()).collect()
+    }
+
+    #[tokio::test]
+    async fn apply_plan_update_keeps_ids_of_unchanged_steps() {
+        let session_configuration = make_session_configuration_for_tests().await;
+        let mut state = SessionState::new(session_configuration);
+
+        let mut first = plan_items(&[
+            ("Read the code", StepStatus::InProgress),
+            ("Write the fix", StepStatus::Pending),
+        ]);
+        state.apply_plan_update(&mut first);
+        assert_eq!(plan_ids(&first), vec![Some("step-1"), Some("step-2")]);
+
+        let mut second = plan_items(&[
+            ("Read the code", StepStatus::Completed),
+            ("Add a test", StepStatus::InProgress),
+            ("Write the fix", StepStatus::Pending),
+        ]);
+        state.apply_plan_update(&mut second);
+        assert_eq!(
+            plan_ids(&second),
+            vec![Some("step-1"), Some("step-3"), Some("step-2")]
+        );
+        assert_eq!(plan_ids(state.plan()), plan_ids(&second));
+    }
+
+    #[tokio::test]
+    async fn restore_plan_continues_id_sequence() {
+        let session_configuration = make_session_configuration_for_tests().await;
+        let mut state = SessionState::new(session_configuration);
+        let mut dropped = plan_items(&[("Dropped step", StepStatus::Pending)]);
+        dropped[0].id = Some("step-4".to_string());
+        let mut kept = plan_items(&[("Write the fix", StepStatus::InProgress)]);
+        kept[0].id = Some("step-2".to_string());
+
+        state.restore_plan(&[
+            UpdatePlanArgs {
+                explanation: None,
+                plan: dropped,
+            },
+            UpdatePlanArgs {
+                explanation: None,
+                plan: kept,
+            },
+        ]);
+        let mut next = plan_items(&[
+            ("Write the fix", StepStatus::Completed),
+            ("Run the tests", StepStatus::Pending),
+        ]);
+        state.apply_plan_update(&mut next);
+
+        assert_eq!(plan_ids(&next), vec![Some("step-2"), Some("step-5")]);
+    }
 }
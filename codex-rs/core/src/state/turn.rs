@@ -14,6 +14,7 @@ use codex_protocol::request_user_input::RequestUserInputResponse;
 use tokio::sync::oneshot;
 
 use crate::codex::TurnContext;
+use crate::protocol::RetryExhaustedDecision;
 use crate::protocol::ReviewDecision;
 use crate::tasks::SessionTask;
 
@@ -39,6 +40,16 @@ pub(crate) enum TaskKind {
     Compact,
 }
 
+impl TaskKind {
+    pub(crate) fn as_metric_tag(self) -> &'static str {
+        match self {
+            TaskKind::Regular => "regular",
+            TaskKind::Review => "review",
+            TaskKind::Compact => "compact",
+        }
+    }
+}
+
 pub(crate) struct RunningTask {
     pub(crate) done: Arc<Notify>,
     pub(crate) kind: TaskKind,
@@ -72,6 +83,8 @@ pub(crate) struct TurnState {
     pending_approvals: HashMap<String, oneshot::Sender<ReviewDecision>>,
     pending_user_input: HashMap<String, oneshot::Sender<RequestUserInputResponse>>,
     pending_dynamic_tools: HashMap<String, oneshot::Sender<DynamicToolResponse>>,
+    pending_retry_decisions: HashMap<String, oneshot::Sender<RetryExhaustedDecision>>,
+    pending_quota_overrides: HashMap<String, oneshot::Sender<bool>>,
     pending_input: Vec<ResponseInputItem>,
 }
 
@@ -95,6 +108,8 @@ impl TurnState {
         self.pending_approvals.clear();
         self.pending_user_input.clear();
         self.pending_dynamic_tools.clear();
+        self.pending_retry_decisions.clear();
+        self.pending_quota_overrides.clear();
         self.pending_input.clear();
     }
 
@@ -113,6 +128,36 @@ impl TurnState {
         self.pending_user_input.remove(key)
     }
 
+    pub(crate) fn insert_pending_retry_decision(
+        &mut self,
+        key: String,
+        tx: oneshot::Sender<RetryExhaustedDecision>,
+    ) -> Option<oneshot::Sender<RetryExhaustedDecision>> {
+        self.pending_retry_decisions.insert(key, tx)
+    }
+
+    pub(crate) fn remove_pending_retry_decision(
+        &mut self,
+        key: &str,
+    ) -> Option<oneshot::Sender<RetryExhaustedDecision>> {
+        self.pending_retry_decisions.remove(key)
+    }
+
+    pub(crate) fn insert_pending_quota_override(
+        &mut self,
+        key: String,
+        tx: oneshot::Sender<bool>,
+    ) -> Option<oneshot::Sender<bool>> {
+        self.pending_quota_overrides.insert(key, tx)
+    }
+
+    pub(crate) fn remove_pending_quota_override(
+        &mut self,
+        key: &str,
+    ) -> Option<oneshot::Sender<bool>> {
+        self.pending_quota_overrides.remove(key)
+    }
+
     pub(crate) fn insert_pending_dynamic_tool(
         &mut self,
         key: String,
@@ -0,0 +1,490 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::codex::TurnContext;
+use crate::git_info::get_git_repo_root;
+use crate::git_info::run_hardened_git;
+use crate::protocol::CommitCompletedEvent;
+use crate::protocol::EventMsg;
+use crate::protocol::ReviewDecision;
+use crate::state::TaskKind;
+use crate::tasks::SessionTask;
+use crate::tasks::SessionTaskContext;
+use crate::turn_diff_tracker::TurnDiffTracker;
+use async_trait::async_trait;
+use codex_protocol::user_input::UserInput;
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+const GIT_TIMEOUT: Duration = Duration::from_secs(60);
+const CO_AUTHORED_BY: &str = "Co-authored-by:";
+
+/// Commits the files changed during this session (as recorded by the session diff tracker),
+/// leaving unrelated staged or unstaged changes in the worktree untouched.
+pub(crate) struct CommitTask {
+    message: Option<String>,
+}
+
+impl CommitTask {
+    pub(crate) fn new(message: Option<String>) -> Self {
+        Self { message }
+    }
+}
+
+#[async_trait]
+impl SessionTask for CommitTask {
+    fn kind(&self) -> TaskKind {
+        TaskKind::Regular
+    }
+
+    async fn run(
+        self: Arc<Self>,
+        session: Arc<SessionTaskContext>,
+        ctx: Arc<TurnContext>,
+        _input: Vec<UserInput>,
+        cancellation_token: CancellationToken,
+    ) -> Option<String> {
+        let _ = session
+            .session
+            .services
+            .otel_manager
+            .counter("codex.task.commit", 1, &[]);
+        let sess = session.clone_session();
+        let completed = match self.commit(&session, &ctx, &cancellation_token).await {
+            Ok(completed) => completed,
+            Err(message) => CommitCompletedEvent {
+                success: false,
+                commit_sha: None,
+                message: Some(message),
+            },
+        };
+        sess.send_event(ctx.as_ref(), EventMsg::CommitCompleted(completed))
+            .await;
+        None
+    }
+}
+
+impl CommitTask {
+    async fn commit(
+        &self,
+        session: &SessionTaskContext,
+        ctx: &TurnContext,
+        cancellation_token: &CancellationToken,
+    ) -> Result<CommitCompletedEvent, String> {
+        let sess = session.clone_session();
+        let Some(repo_root) = get_git_repo_root(&ctx.cwd) else {
+            return Err("Not inside a git repository.".to_string());
+        };
+
+        let (touched, unified_diff) = {
+            let mut tracker = sess.services.session_diff_tracker.lock().await;
+            let touched = tracker.touched_paths();
+            let unified_diff = tracker.get_unified_diff().unwrap_or_else(|err| {
+                warn!("failed to compute session diff: {err}");
+                None
+            });
+            (touched, unified_diff)
+        };
+        let candidates: Vec<String> = touched
+            .iter()
+            .filter_map(|path| repo_relative(&repo_root, path))
+            .collect();
+        if candidates.is_empty() {
+            return Err("No session changes to commit.".to_string());
+        }
+
+        // Only keep paths git still considers changed; files that were created and removed
+        // again (or edited back to their original content) have nothing to commit.
+        let mut status_args = vec![
+            "status".to_string(),
+            "--porcelain=v1".to_string(),
+            "-z".to_string(),
+            "--untracked-files=all".to_string(),
+            "--".to_string(),
+        ];
+        status_args.extend(candidates);
+        let status = run_git(&repo_root, &status_args).await?;
+        let paths = parse_changed_paths(&status);
+        if paths.is_empty() {
+            return Err("No session changes to commit.".to_string());
+        }
+
+        let stats: Vec<FileStat> = unified_diff
+            .as_deref()
+            .map(parse_file_stats)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|stat| paths.contains(&stat.path))
+            .collect();
+        let message = match self.message.as_deref().map(str::trim) {
+            Some(message) if !message.is_empty() => message.to_string(),
+            _ => generate_commit_message(&stats, &paths),
+        };
+        let message = append_co_author_trailers(&message, &ctx.config.commit.co_authors);
+
+        let files = paths
+            .iter()
+            .map(|path| format!("  {path}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let decision = sess
+            .request_command_approval(
+                ctx,
+                ctx.sub_id.clone(),
+                "commit",
+                vec![
+                    "git".to_string(),
+                    "commit".to_string(),
+                    "-m".to_string(),
+                    message.clone(),
+                ],
+                repo_root.clone(),
+                Some(format!("Commit {} session file(s):\n{files}", paths.len())),
+                None,
+                None,
+            )
+            .await;
+        match decision {
+            ReviewDecision::Approved
+            | ReviewDecision::ApprovedExecpolicyAmendment { .. }
+            | ReviewDecision::ApprovedForSession => {}
+            ReviewDecision::Denied | ReviewDecision::Abort => {
+                return Err("Commit declined.".to_string());
+            }
+        }
+
+        if cancellation_token.is_cancelled() {
+            return Err("Commit cancelled.".to_string());
+        }
+
+        let path_args: Vec<String> = paths.iter().cloned().collect();
+        let mut add_args = vec!["add".to_string(), "-A".to_string(), "--".to_string()];
+        add_args.extend(path_args.iter().cloned());
+        run_git(&repo_root, &add_args).await?;
+
+        // Passing the pathspec commits only these files, even if the user has other changes
+        // staged in the index.
+        let mut commit_args = vec![
+            "commit".to_string(),
+            "--no-verify".to_string(),
+            "-m".to_string(),
+            message.clone(),
+            "--".to_string(),
+        ];
+        commit_args.extend(path_args);
+        run_git(&repo_root, &commit_args).await?;
+
+        let commit_sha = run_git(&repo_root, &["rev-parse", "HEAD"])
+            .await
+            .ok()
+            .map(|stdout| stdout.trim().to_string())
+            .filter(|sha| !sha.is_empty());
+        *sess.services.session_diff_tracker.lock().await = TurnDiffTracker::new();
+
+        let subject = message.lines().next().unwrap_or_default().to_string();
+        Ok(CommitCompletedEvent {
+            success: true,
+            commit_sha,
+            message: Some(subject),
+        })
+    }
+}
+
+fn repo_relative(repo_root: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(repo_root).ok()?;
+    let relative = relative.to_string_lossy().replace('\\', "/");
+    (!relative.is_empty()).then_some(relative)
+}
+
+pub(super) async fn run_git<S: AsRef<str>>(cwd: &Path, args: &[S]) -> Result<String, String> {
+    run_hardened_git(cwd, args, GIT_TIMEOUT).await
+}
+
+/// Parses `git status --porcelain=v1 -z` into the set of changed paths, including the
+/// original path of staged renames and copies.
+fn parse_changed_paths(stdout: &str) -> BTreeSet<String> {
+    let mut paths = BTreeSet::new();
+    let mut records = stdout.split('\0').filter(|record| !record.is_empty());
+    while let Some(record) = records.next() {
+        let Some((status, path)) = record.split_at_checked(3) else {
+            continue;
+        };
+        paths.insert(path.to_string());
+        if (status.starts_with('R') || status.starts_with('C'))
+            && let Some(origin) = records.next()
+        {
+            paths.insert(origin.to_string());
+        }
+    }
+    paths
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FileChangeKind {
+    Added,
+    Deleted,
+    Modified,
+    Renamed { from: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FileStat {
+    path: String,
+    kind: FileChangeKind,
+    added: usize,
+    removed: usize,
+}
+
+/// Summarizes a unified diff produced by [`TurnDiffTracker`] into per-file line counts.
+fn parse_file_stats(unified_diff: &str) -> Vec<FileStat> {
+    let mut stats: Vec<FileStat> = Vec::new();
+    for line in unified_diff.lines() {
+        if let Some(header) = line.strip_prefix("diff --git a/") {
+            let Some((from, to)) = header.rsplit_once(" b/") else {
+                continue;
+            };
+            let kind = if from == to {
+                FileChangeKind::Modified
+            } else {
+                FileChangeKind::Renamed {
+                    from: from.to_string(),
+                }
+            };
+            stats.push(FileStat {
+                path: to.to_string(),
+                kind,
+                added: 0,
+                removed: 0,
+            });
+            continue;
+        }
+        let Some(stat) = stats.last_mut() else {
+            continue;
+        };
+        if line.starts_with("new file mode ") {
+            stat.kind = FileChangeKind::Added;
+        } else if line.starts_with("deleted file mode ") {
+            stat.kind = FileChangeKind::Deleted;
+        } else if line.starts_with("+++ ") || line.starts_with("--- ") {
+            continue;
+        } else if line.starts_with('+') {
+            stat.added += 1;
+        } else if line.starts_with('-') {
+            stat.removed += 1;
+        }
+    }
+    stats
+}
+
+/// Builds a subject line plus a per-file summary. Paths git reports as changed but that are
+/// missing from the diff (e.g. binary files) are listed without line counts.
+fn generate_commit_message(stats: &[FileStat], paths: &BTreeSet<String>) -> String {
+    // A rename shows up as two paths in `git status` but is a single file change.
+    let renames = stats
+        .iter()
+        .filter(|stat| matches!(stat.kind, FileChangeKind::Renamed { .. }))
+        .count();
+    let file_count = paths.len().saturating_sub(renames).max(1);
+    let subject = match stats {
+        [stat] if file_count == 1 => match &stat.kind {
+            FileChangeKind::Added => format!("Add {}", stat.path),
+            FileChangeKind::Deleted => format!("Delete {}", stat.path),
+            FileChangeKind::Modified => format!("Update {}", stat.path),
+            FileChangeKind::Renamed { from } => format!("Rename {from} to {}", stat.path),
+        },
+        _ if file_count == 1 => {
+            let path = paths.iter().next().map(String::as_str).unwrap_or_default();
+            format!("Update {path}")
+        }
+        _ => format!("Update {file_count} files"),
+    };
+
+    if file_count == 1 && stats.len() <= 1 {
+        return subject;
+    }
+
+    let mut body: Vec<String> = stats
+        .iter()
+        .map(|stat| {
+            let label = match &stat.kind {
+                FileChangeKind::Added => format!("add {}", stat.path),
+                FileChangeKind::Deleted => format!("delete {}", stat.path),
+                FileChangeKind::Modified => format!("update {}", stat.path),
+                FileChangeKind::Renamed { from } => format!("rename {from} -> {}", stat.path),
+            };
+            format!("- {label} (+{} -{})", stat.added, stat.removed)
+        })
+        .collect();
+    let described: BTreeSet<&str> = stats
+        .iter()
+        .flat_map(|stat| match &stat.kind {
+            FileChangeKind::Renamed { from } => vec![stat.path.as_str(), from.as_str()],
+            _ => vec![stat.path.as_str()],
+        })
+        .collect();
+    body.extend(
+        paths
+            .iter()
+            .filter(|path| !described.contains(path.as_str()))
+            .map(|path| format!("- update {path}")),
+    );
+    format!("{subject}\n\n{}", body.join("\n"))
+}
+
+/// Appends one `Co-authored-by:` trailer per configured identity, skipping identities the
+/// message already credits.
+fn append_co_author_trailers(message: &str, co_authors: &[String]) -> String {
+    let message = message.trim_end();
+    let existing: BTreeSet<&str> = message
+        .lines()
+        .filter_map(|line| line.strip_prefix(CO_AUTHORED_BY))
+        .map(str::trim)
+        .collect();
+    let trailers: Vec<String> = co_authors
+        .iter()
+        .filter(|co_author| !existing.contains(co_author.as_str()))
+        .map(|co_author| format!("{CO_AUTHORED_BY} {co_author}"))
+        .collect();
+    if trailers.is_empty() {
+        return message.to_string();
+    }
+    let separator = if existing.is_empty() { "\n\n" } else { "\n" };
+    format!("{message}{separator}{}", trailers.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_changed_paths_includes_rename_origin() {
+        let stdout = " M src/lib.rs\0R  new.rs\0old.rs\0?? notes.md\0";
+        assert_eq!(
+            parse_changed_paths(stdout),
+            BTreeSet::from([
+                "new.rs".to_string(),
+                "notes.md".to_string(),
+                "old.rs".to_string(),
+                "src/lib.rs".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_file_stats_counts_lines_per_file() {
+        let diff = "diff --git a/src/lib.rs b/src/lib.rs\n\
+index 1111111..2222222 100644\n\
+--- a/src/lib.rs\n\
++++ b/src/lib.rs\n\
+@@ -1,2 +1,2 @@\n\
+-old\n\
++new\n\
++more\n\
+diff --git a/notes.md b/notes.md\n\
+new file mode 100644\n\
+index 0000000..3333333\n\
+--- /dev/null\n\
++++ b/notes.md\n\
+@@ -0,0 +1 @@\n\
++hello\n\
+diff --git a/a.txt b/b.txt\n\
+index 4444444..5555555\n";
+        assert_eq!(
+            parse_file_stats(diff),
+            vec![
+                FileStat {
+                    path: "src/lib.rs".to_string(),
+                    kind: FileChangeKind::Modified,
+                    added: 2,
+                    removed: 1,
+                },
+                FileStat {
+                    path: "notes.md".to_string(),
+                    kind: FileChangeKind::Added,
+                    added: 1,
+                    removed: 0,
+                },
+                FileStat {
+                    path: "b.txt".to_string(),
+                    kind: FileChangeKind::Renamed {
+                        from: "a.txt".to_string(),
+                    },
+                    added: 0,
+                    removed: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn single_file_message_is_subject_only() {
+        let stats = vec![FileStat {
+            path: "notes.md".to_string(),
+            kind: FileChangeKind::Added,
+            added: 1,
+            removed: 0,
+        }];
+        let paths = BTreeSet::from(["notes.md".to_string()]);
+        assert_eq!(generate_commit_message(&stats, &paths), "Add notes.md");
+    }
+
+    #[test]
+    fn multi_file_message_lists_each_file() {
+        let stats = vec![
+            FileStat {
+                path: "src/lib.rs".to_string(),
+                kind: FileChangeKind::Modified,
+                added: 2,
+                removed: 1,
+            },
+            FileStat {
+                path: "b.txt".to_string(),
+                kind: FileChangeKind::Renamed {
+                    from: "a.txt".to_string(),
+                },
+                added: 0,
+                removed: 0,
+            },
+        ];
+        let paths = BTreeSet::from([
+            "a.txt".to_string(),
+            "b.txt".to_string(),
+            "image.png".to_string(),
+            "src/lib.rs".to_string(),
+        ]);
+        assert_eq!(
+            generate_commit_message(&stats, &paths),
+            "Update 3 files\n\n\
+- update src/lib.rs (+2 -1)\n\
+- rename a.txt -> b.txt (+0 -0)\n\
+- update image.png"
+        );
+    }
+
+    #[test]
+    fn co_author_trailers_are_appended_once() {
+        let co_authors = vec![
+            "Codex <codex@openai.com>".to_string(),
+            "Jane Doe <jane@example.com>".to_string(),
+        ];
+        assert_eq!(
+            append_co_author_trailers("Fix bug\n", &co_authors),
+            "Fix bug\n\n\
+Co-authored-by: Codex <codex@openai.com>\n\
+Co-authored-by: Jane Doe <jane@example.com>"
+        );
+        assert_eq!(
+            append_co_author_trailers(
+                "Fix bug\n\nCo-authored-by: Codex <codex@openai.com>",
+                &co_authors
+            ),
+            "Fix bug\n\n\
+Co-authored-by: Codex <codex@openai.com>\n\
+Co-authored-by: Jane Doe <jane@example.com>"
+        );
+        assert_eq!(append_co_author_trailers("Fix bug", &[]), "Fix bug");
+    }
+}
@@ -1,8 +1,12 @@
+mod commit;
 mod compact;
 mod ghost_snapshot;
+mod pipeline;
+mod pull_request;
 mod regular;
 mod review;
 mod undo;
+mod undo_file_change;
 mod user_shell;
 
 use std::sync::Arc;
@@ -21,7 +25,10 @@ use tracing::warn;
 use crate::AuthManager;
 use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::codex::emergency_stop_message;
 use crate::models_manager::manager::ModelsManager;
+use crate::protocol::CodexErrorInfo;
+use crate::protocol::ErrorEvent;
 use crate::protocol::EventMsg;
 use crate::protocol::TurnAbortReason;
 use crate::protocol::TurnAbortedEvent;
@@ -36,11 +43,15 @@ use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::RolloutItem;
 use codex_protocol::user_input::UserInput;
 
+pub(crate) use commit::CommitTask;
 pub(crate) use compact::CompactTask;
 pub(crate) use ghost_snapshot::GhostSnapshotTask;
+pub(crate) use pipeline::PipelineTask;
+pub(crate) use pull_request::PullRequestTask;
 pub(crate) use regular::RegularTask;
 pub(crate) use review::ReviewTask;
 pub(crate) use undo::UndoTask;
+pub(crate) use undo_file_change::UndoFileChangeTask;
 pub(crate) use user_shell::UserShellCommandMode;
 pub(crate) use user_shell::UserShellCommandTask;
 pub(crate) use user_shell::execute_user_shell_command;
@@ -119,6 +130,25 @@ impl Session {
         input: Vec<UserInput>,
         task: T,
     ) {
+        if let Some(reason) = self.emergency_stop_reason().await {
+            self.send_event(
+                turn_context.as_ref(),
+                EventMsg::Error(ErrorEvent {
+                    message: emergency_stop_message(&reason),
+                    codex_error_info: Some(CodexErrorInfo::Other),
+                }),
+            )
+            .await;
+            self.send_event(
+                turn_context.as_ref(),
+                EventMsg::TurnAborted(TurnAbortedEvent {
+                    turn_id: Some(turn_context.sub_id.clone()),
+                    reason: TurnAbortReason::Interrupted,
+                }),
+            )
+            .await;
+            return;
+        }
         self.abort_all_tasks(TurnAbortReason::Replaced).await;
         self.clear_connector_selection().await;
         self.seed_initial_context_if_needed(turn_context.as_ref())
@@ -139,6 +169,7 @@ impl Session {
             let session_span = Span::current();
             tokio::spawn(
                 async move {
+                    ctx.latency.record_task_started();
                     let ctx_for_finish = Arc::clone(&ctx);
                     let model_slug = ctx_for_finish.model_info.slug.clone();
                     let last_agent_message = task_for_run
@@ -165,6 +196,9 @@ impl Session {
             )
         };
 
+        turn_context
+            .otel_manager
+            .counter("codex.turn", 1, &[("kind", task_kind.as_metric_tag())]);
         let timer = turn_context
             .otel_manager
             .start_timer("codex.turn.e2e_duration_ms", &[])
@@ -222,6 +256,13 @@ impl Session {
             self.record_conversation_items(turn_context.as_ref(), &pending_response_items)
                 .await;
         }
+        if let Some(stats) = self.services.read_prefetch.take_stats() {
+            self.send_event(turn_context.as_ref(), EventMsg::ReadPrefetchStats(stats))
+                .await;
+        }
+        let latency = turn_context.latency.to_event(turn_context.sub_id.clone());
+        self.send_event(turn_context.as_ref(), EventMsg::TurnLatency(latency))
+            .await;
         let event = EventMsg::TurnComplete(TurnCompleteEvent {
             turn_id: turn_context.sub_id.clone(),
             last_agent_message,
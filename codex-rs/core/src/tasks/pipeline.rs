@@ -0,0 +1,233 @@
+//! `pipeline` routing: every user turn runs through each role of the active
+//! orchestration in order. Each stage is a sub-session that starts from the
+//! conversation so far plus the final messages of the earlier stages; the last
+//! stage's final message is the turn's answer.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseInputItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::AgentStatus;
+use codex_protocol::protocol::CodexErrorInfo;
+use codex_protocol::protocol::ErrorEvent;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::InitialHistory;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::TurnStartedEvent;
+use codex_protocol::user_input::UserInput;
+use tokio_util::sync::CancellationToken;
+
+use crate::codex::TurnContext;
+use crate::config::types::OrchestrationConfig;
+use crate::state::TaskKind;
+use crate::tools::handlers::delegate::DEFAULT_DELEGATE_TOKEN_BUDGET;
+use crate::tools::handlers::delegate::DelegateTask;
+use crate::tools::handlers::delegate::build_delegate_config;
+use crate::tools::handlers::delegate::run_task;
+
+use super::SessionTask;
+use super::SessionTaskContext;
+
+pub(crate) struct PipelineTask {
+    orchestration: OrchestrationConfig,
+}
+
+impl PipelineTask {
+    pub(crate) fn new(orchestration: OrchestrationConfig) -> Self {
+        Self { orchestration }
+    }
+}
+
+#[async_trait]
+impl SessionTask for PipelineTask {
+    fn kind(&self) -> TaskKind {
+        TaskKind::Regular
+    }
+
+    async fn run(
+        self: Arc<Self>,
+        session: Arc<SessionTaskContext>,
+        ctx: Arc<TurnContext>,
+        input: Vec<UserInput>,
+        cancellation_token: CancellationToken,
+    ) -> Option<String> {
+        let sess = session.clone_session();
+        sess.send_event(
+            ctx.as_ref(),
+            EventMsg::TurnStarted(TurnStartedEvent {
+                turn_id: ctx.sub_id.clone(),
+                model_context_window: ctx.model_context_window(),
+                collaboration_mode_kind: ctx.collaboration_mode.mode,
+            }),
+        )
+        .await;
+        let response_item: ResponseItem = ResponseInputItem::from(input.clone()).into();
+        sess.record_user_prompt_and_emit_turn_item(ctx.as_ref(), &input, response_item)
+            .await;
+        let history: Vec<RolloutItem> = sess
+            .clone_history()
+            .await
+            .raw_items()
+            .iter()
+            .cloned()
+            .map(RolloutItem::ResponseItem)
+            .collect();
+
+        let roles = &self.orchestration.roles;
+        let mut outputs: Vec<(String, String)> = Vec::with_capacity(roles.len());
+        for (role, index) in roles.iter().zip(0u32..) {
+            let model = role
+                .model
+                .clone()
+                .unwrap_or_else(|| ctx.model_info.slug.clone());
+            let config = match build_delegate_config(
+                ctx.as_ref(),
+                &model,
+                ctx.sandbox_policy.clone(),
+                Some(role),
+            ) {
+                Ok(config) => config,
+                Err(err) => {
+                    report_stage_failure(&session, &ctx, &role.name, err.to_string()).await;
+                    return None;
+                }
+            };
+            let task = DelegateTask {
+                index,
+                prompt: stage_prompt(&self.orchestration, index as usize, &outputs),
+                model,
+                role: Some(role.name.clone()),
+                token_budget: role.max_tokens.unwrap_or(DEFAULT_DELEGATE_TOKEN_BUDGET),
+                config,
+                history: Some(InitialHistory::Forked(history.clone())),
+            };
+            let result = run_task(
+                Arc::clone(&sess),
+                Arc::clone(&ctx),
+                ctx.sub_id.clone(),
+                task,
+                cancellation_token.child_token(),
+            )
+            .await;
+            if cancellation_token.is_cancelled() {
+                return None;
+            }
+            match result.status {
+                AgentStatus::Completed(message) => {
+                    outputs.push((role.name.clone(), message.unwrap_or_default()));
+                }
+                AgentStatus::Errored(reason) => {
+                    report_stage_failure(&session, &ctx, &role.name, reason).await;
+                    return None;
+                }
+                status => {
+                    report_stage_failure(&session, &ctx, &role.name, format!("{status:?}")).await;
+                    return None;
+                }
+            }
+        }
+
+        let (_, answer) = outputs.pop()?;
+        sess.record_response_item_and_emit_turn_item(
+            ctx.as_ref(),
+            ResponseItem::Message {
+                id: None,
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText {
+                    text: answer.clone(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+        )
+        .await;
+        Some(answer)
+    }
+}
+
+async fn report_stage_failure(
+    session: &SessionTaskContext,
+    ctx: &TurnContext,
+    role: &str,
+    reason: String,
+) {
+    session
+        .clone_session()
+        .send_event(
+            ctx,
+            EventMsg::Error(ErrorEvent {
+                message: format!("Pipeline stage `{role}` did not complete: {reason}"),
+                codex_error_info: Some(CodexErrorInfo::Other),
+            }),
+        )
+        .await;
+}
+
+/// Prompt for stage `index`: its place in the pipeline plus every earlier stage's output.
+fn stage_prompt(
+    orchestration: &OrchestrationConfig,
+    index: usize,
+    outputs: &[(String, String)],
+) -> String {
+    let total = orchestration.roles.len();
+    let role = &orchestration.roles[index].name;
+    let mut prompt = format!(
+        "You are stage {} of {total} (`{role}`) in the `{}` pipeline handling the latest user message above.",
+        index + 1,
+        orchestration.name
+    );
+    if index + 1 == total {
+        prompt.push_str(" Your final message is returned to the user as the answer.");
+    } else {
+        prompt.push_str(" Your final message is handed to the next stage.");
+    }
+    for (role, output) in outputs {
+        prompt.push_str(&format!("\n\n## Output from `{role}`\n\n{output}"));
+    }
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::types::AgentRoleConfig;
+    use crate::config::types::TurnRouting;
+    use pretty_assertions::assert_eq;
+
+    fn role(name: &str) -> AgentRoleConfig {
+        AgentRoleConfig {
+            name: name.to_string(),
+            description: None,
+            model: None,
+            reasoning_effort: None,
+            instructions: None,
+            tools: None,
+            read_only: false,
+            max_tokens: None,
+        }
+    }
+
+    #[test]
+    fn stage_prompt_hands_earlier_outputs_forward() {
+        let orchestration = OrchestrationConfig {
+            name: "build".to_string(),
+            routing: TurnRouting::Pipeline,
+            roles: vec![role("planner"), role("implementer")],
+        };
+
+        assert_eq!(
+            stage_prompt(&orchestration, 0, &[]),
+            "You are stage 1 of 2 (`planner`) in the `build` pipeline handling the latest user message above. Your final message is handed to the next stage."
+        );
+        assert_eq!(
+            stage_prompt(
+                &orchestration,
+                1,
+                &[("planner".to_string(), "1. Edit foo.rs".to_string())]
+            ),
+            "You are stage 2 of 2 (`implementer`) in the `build` pipeline handling the latest user message above. Your final message is returned to the user as the answer.\n\n## Output from `planner`\n\n1. Edit foo.rs"
+        );
+    }
+}
@@ -0,0 +1,281 @@
+use std::sync::Arc;
+
+use crate::codex::TurnContext;
+use crate::codex::get_last_assistant_message_from_turn;
+use crate::event_mapping::parse_turn_item;
+use crate::forge::ForgeRepo;
+use crate::forge::PullRequestDraft;
+use crate::forge::create_pull_request;
+use crate::forge::load_token;
+use crate::git_info::current_branch_name;
+use crate::git_info::default_branch_name;
+use crate::git_info::get_git_remote_urls;
+use crate::git_info::get_git_repo_root;
+use crate::protocol::EventMsg;
+use crate::protocol::PullRequestCompletedEvent;
+use crate::protocol::ReviewDecision;
+use crate::state::TaskKind;
+use crate::tasks::SessionTask;
+use crate::tasks::SessionTaskContext;
+use crate::tasks::commit::run_git;
+use async_trait::async_trait;
+use codex_protocol::items::TurnItem;
+use codex_protocol::user_input::UserInput;
+use tokio_util::sync::CancellationToken;
+
+const MAX_TITLE_CHARS: usize = 72;
+const FALLBACK_TITLE: &str = "Changes from Codex session";
+
+/// Pushes the current branch and opens a pull/merge request describing the session.
+pub(crate) struct PullRequestTask {
+    title: Option<String>,
+}
+
+impl PullRequestTask {
+    pub(crate) fn new(title: Option<String>) -> Self {
+        Self { title }
+    }
+}
+
+#[async_trait]
+impl SessionTask for PullRequestTask {
+    fn kind(&self) -> TaskKind {
+        TaskKind::Regular
+    }
+
+    async fn run(
+        self: Arc<Self>,
+        session: Arc<SessionTaskContext>,
+        ctx: Arc<TurnContext>,
+        _input: Vec<UserInput>,
+        cancellation_token: CancellationToken,
+    ) -> Option<String> {
+        let _ = session
+            .session
+            .services
+            .otel_manager
+            .counter("codex.task.pull_request", 1, &[]);
+        let sess = session.clone_session();
+        let completed = match self.open(&session, &ctx, &cancellation_token).await {
+            Ok(completed) => completed,
+            Err(message) => PullRequestCompletedEvent {
+                success: false,
+                url: None,
+                message: Some(message),
+            },
+        };
+        sess.send_event(ctx.as_ref(), EventMsg::PullRequestCompleted(completed))
+            .await;
+        None
+    }
+}
+
+impl PullRequestTask {
+    async fn open(
+        &self,
+        session: &SessionTaskContext,
+        ctx: &TurnContext,
+        cancellation_token: &CancellationToken,
+    ) -> Result<PullRequestCompletedEvent, String> {
+        let sess = session.clone_session();
+        let Some(repo_root) = get_git_repo_root(&ctx.cwd) else {
+            return Err("Not inside a git repository.".to_string());
+        };
+        let Some(head) = current_branch_name(&repo_root).await else {
+            return Err("HEAD is detached; check out a branch first.".to_string());
+        };
+        let Some(base) = default_branch_name(&repo_root).await else {
+            return Err("Could not determine the repository's default branch.".to_string());
+        };
+        if head == base {
+            return Err(format!(
+                "Currently on the default branch `{base}`; create a branch for this session first."
+            ));
+        }
+
+        let remotes = get_git_remote_urls(&repo_root).await.unwrap_or_default();
+        let Some((remote, remote_url)) = remotes
+            .get_key_value("origin")
+            .or_else(|| remotes.iter().next())
+        else {
+            return Err("The repository has no remote to push to.".to_string());
+        };
+        let Some(repo) = ForgeRepo::from_remote_url(remote_url, &ctx.config.forge_hosts) else {
+            return Err(format!(
+                "Remote `{remote}` ({remote_url}) is not on github.com, gitlab.com, or a host listed under `forge_hosts`."
+            ));
+        };
+        let noun = repo.kind.request_noun();
+        let Some(token) = load_token(&ctx.config.codex_home, &repo).await else {
+            return Err(format!(
+                "No API token for {}; store one as a Codex secret or set the environment variable.",
+                repo.host
+            ));
+        };
+
+        let history = sess.clone_history().await;
+        let items = history.raw_items();
+        let first_request = items.iter().find_map(|item| match parse_turn_item(item) {
+            Some(TurnItem::UserMessage(message)) => {
+                message.content.into_iter().find_map(|input| match input {
+                    UserInput::Text { text, .. } => Some(text),
+                    _ => None,
+                })
+            }
+            _ => None,
+        });
+        let title = match self.title.as_deref().map(str::trim) {
+            Some(title) if !title.is_empty() => title.to_string(),
+            _ => derive_title(
+                sess.thread_name().await.as_deref(),
+                first_request.as_deref(),
+            ),
+        };
+        let commits = run_git(
+            &repo_root,
+            &["log", "--format=- %s", &format!("{remote}/{base}..HEAD")],
+        )
+        .await
+        .unwrap_or_default();
+        let body = build_description(
+            get_last_assistant_message_from_turn(items).as_deref(),
+            &commits,
+            &sess.conversation_id.to_string(),
+        );
+
+        let decision = sess
+            .request_command_approval(
+                ctx,
+                ctx.sub_id.clone(),
+                "pull_request",
+                vec![
+                    "git".to_string(),
+                    "push".to_string(),
+                    "--set-upstream".to_string(),
+                    remote.clone(),
+                    head.clone(),
+                ],
+                repo_root.clone(),
+                Some(format!(
+                    "Push `{head}` and open a {noun} into `{base}` on {}/{}: {title}",
+                    repo.host, repo.path
+                )),
+                None,
+                None,
+            )
+            .await;
+        match decision {
+            ReviewDecision::Approved
+            | ReviewDecision::ApprovedExecpolicyAmendment { .. }
+            | ReviewDecision::ApprovedForSession => {}
+            ReviewDecision::Denied | ReviewDecision::Abort => {
+                return Err(format!("Opening the {noun} was declined."));
+            }
+        }
+
+        if cancellation_token.is_cancelled() {
+            return Err(format!("Opening the {noun} was cancelled."));
+        }
+
+        run_git(
+            &repo_root,
+            &[
+                "push",
+                "--no-verify",
+                "--set-upstream",
+                remote.as_str(),
+                head.as_str(),
+            ],
+        )
+        .await?;
+        let url = create_pull_request(
+            &repo,
+            &token,
+            &PullRequestDraft {
+                title: title.clone(),
+                body,
+                head,
+                base,
+            },
+        )
+        .await?;
+
+        Ok(PullRequestCompletedEvent {
+            success: true,
+            url: Some(url),
+            message: Some(title),
+        })
+    }
+}
+
+/// Prefers the thread name, then the first line of the first user request.
+fn derive_title(thread_name: Option<&str>, first_request: Option<&str>) -> String {
+    let candidate = thread_name
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .or_else(|| {
+            first_request
+                .and_then(|request| request.lines().map(str::trim).find(|line| !line.is_empty()))
+        });
+    let Some(candidate) = candidate else {
+        return FALLBACK_TITLE.to_string();
+    };
+    if candidate.chars().count() <= MAX_TITLE_CHARS {
+        return candidate.to_string();
+    }
+    let truncated: String = candidate.chars().take(MAX_TITLE_CHARS - 3).collect();
+    format!("{}...", truncated.trim_end())
+}
+
+/// Uses the agent's final message as the session summary, followed by the branch's commits
+/// and the thread id so reviewers can find the originating rollout.
+fn build_description(summary: Option<&str>, commits: &str, thread_id: &str) -> String {
+    let mut sections = Vec::new();
+    if let Some(summary) = summary.map(str::trim).filter(|summary| !summary.is_empty()) {
+        sections.push(summary.to_string());
+    }
+    let commits = commits.trim();
+    if !commits.is_empty() {
+        sections.push(format!("## Commits\n\n{commits}"));
+    }
+    sections.push(format!("---\nOpened from Codex session `{thread_id}`."));
+    sections.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn title_prefers_thread_name_then_first_request_line() {
+        assert_eq!(
+            derive_title(Some(" Fix login flow "), Some("ignored")),
+            "Fix login flow"
+        );
+        assert_eq!(
+            derive_title(None, Some("\nAdd retries to the uploader\nmore detail")),
+            "Add retries to the uploader"
+        );
+        assert_eq!(derive_title(Some(""), None), FALLBACK_TITLE);
+    }
+
+    #[test]
+    fn long_titles_are_truncated() {
+        let title = derive_title(None, Some(&"word ".repeat(30)));
+        assert_eq!(title.chars().count(), MAX_TITLE_CHARS);
+        assert!(title.ends_with("..."));
+    }
+
+    #[test]
+    fn description_links_the_session() {
+        assert_eq!(
+            build_description(Some("Added retries.\n"), "- Add retries\n", "thread-1"),
+            "Added retries.\n\n## Commits\n\n- Add retries\n\n---\nOpened from Codex session `thread-1`."
+        );
+        assert_eq!(
+            build_description(None, "", "thread-1"),
+            "---\nOpened from Codex session `thread-1`."
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::codex::TurnContext;
+use crate::protocol::EventMsg;
+use crate::protocol::UndoCompletedEvent;
+use crate::protocol::UndoStartedEvent;
+use crate::state::TaskKind;
+use crate::tasks::SessionTask;
+use crate::tasks::SessionTaskContext;
+use async_trait::async_trait;
+use codex_protocol::models::DeveloperInstructions;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::user_input::UserInput;
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+/// Restores files from the session's apply_patch backups: the whole most recent
+/// patch when `paths` is empty, otherwise just the listed files.
+pub(crate) struct UndoFileChangeTask {
+    paths: Vec<PathBuf>,
+}
+
+impl UndoFileChangeTask {
+    pub(crate) fn new(paths: Vec<PathBuf>) -> Self {
+        Self { paths }
+    }
+}
+
+#[async_trait]
+impl SessionTask for UndoFileChangeTask {
+    fn kind(&self) -> TaskKind {
+        TaskKind::Regular
+    }
+
+    async fn run(
+        self: Arc<Self>,
+        session: Arc<SessionTaskContext>,
+        ctx: Arc<TurnContext>,
+        _input: Vec<UserInput>,
+        _cancellation_token: CancellationToken,
+    ) -> Option<String> {
+        let _ =
+            session
+                .session
+                .services
+                .otel_manager
+                .counter("codex.task.undo_file_change", 1, &[]);
+        let sess = session.clone_session();
+        sess.send_event(
+            ctx.as_ref(),
+            EventMsg::UndoStarted(UndoStartedEvent {
+                message: Some("Restoring files...".to_string()),
+            }),
+        )
+        .await;
+
+        let paths: Vec<PathBuf> = self.paths.iter().map(|path| ctx.cwd.join(path)).collect();
+        let result = {
+            let mut backups = sess.services.patch_backups.lock().await;
+            if paths.is_empty() {
+                backups.undo_last_patch()
+            } else {
+                backups.undo_files(&paths)
+            }
+        };
+
+        let completed = match result {
+            Ok(restored) => {
+                let list = restored
+                    .iter()
+                    .map(|path| display_path(&ctx.cwd, path))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                // Tell the model, so it does not assume its earlier edits are still in place.
+                let note: ResponseItem = DeveloperInstructions::new(format!(
+                    "The user restored these files to their content before your earlier apply_patch changes: {list}. Re-read them before editing them again."
+                ))
+                .into();
+                sess.record_conversation_items(ctx.as_ref(), std::slice::from_ref(&note))
+                    .await;
+                UndoCompletedEvent {
+                    success: true,
+                    message: Some(format!("Restored {list}.")),
+                }
+            }
+            Err(err) => {
+                let message = format!("{err:#}");
+                warn!("undo file change failed: {message}");
+                UndoCompletedEvent {
+                    success: false,
+                    message: Some(message),
+                }
+            }
+        };
+
+        sess.send_event(ctx.as_ref(), EventMsg::UndoCompleted(completed))
+            .await;
+        None
+    }
+}
+
+fn display_path(cwd: &Path, path: &Path) -> String {
+    path.strip_prefix(cwd).unwrap_or(path).display().to_string()
+}
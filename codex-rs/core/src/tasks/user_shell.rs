@@ -27,6 +27,7 @@ use crate::protocol::TurnStartedEvent;
 use crate::sandboxing::ExecRequest;
 use crate::sandboxing::SandboxPermissions;
 use crate::state::TaskKind;
+use crate::test_failures::exec_test_failures;
 use crate::tools::format_exec_output_str;
 use crate::tools::runtimes::maybe_wrap_shell_lc_with_snapshot;
 use crate::user_shell_command::user_shell_command_record_item;
@@ -210,6 +211,7 @@ pub(crate) async fn execute_user_shell_command(
                         duration: Duration::ZERO,
                         formatted_output: aborted_message,
                         status: ExecCommandStatus::Failed,
+                        test_failures: Vec::new(),
                     }),
                 )
                 .await;
@@ -241,6 +243,7 @@ pub(crate) async fn execute_user_shell_command(
                         } else {
                             ExecCommandStatus::Failed
                         },
+                        test_failures: exec_test_failures(&output),
                     }),
                 )
                 .await;
@@ -281,6 +284,7 @@ pub(crate) async fn execute_user_shell_command(
                             turn_context.truncation_policy,
                         ),
                         status: ExecCommandStatus::Failed,
+                        test_failures: Vec::new(),
                     }),
                 )
                 .await;
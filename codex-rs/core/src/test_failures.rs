@@ -0,0 +1,480 @@
+//! Failing tests parsed from the output of common test runners.
+//!
+//! When a command exits with an error, its output is scanned for the failure
+//! reports of `cargo test`, `pytest`, `jest` and `go test`. The failures are
+//! attached to the `ExecCommandEnd` event so clients can summarize them, and
+//! listed for the model next to the output, where truncation cannot drop them.
+
+use std::collections::HashMap;
+
+use codex_protocol::protocol::TestFailure;
+use codex_utils_string::take_bytes_at_char_boundary;
+
+use crate::exec::ExecToolCallOutput;
+
+/// Failures past this many are left out; the output still has them.
+const MAX_TEST_FAILURES: usize = 50;
+
+/// Longer failure messages are cut.
+const MAX_MESSAGE_BYTES: usize = 200;
+
+/// Failing tests in the output of a command that exited with an error.
+pub(crate) fn exec_test_failures(output: &ExecToolCallOutput) -> Vec<TestFailure> {
+    if output.exit_code == 0 {
+        return Vec::new();
+    }
+    parse_test_failures(&output.aggregated_output.text)
+}
+
+/// Returns the failing tests reported in `output`, in the order the runners
+/// reported them.
+fn parse_test_failures(output: &str) -> Vec<TestFailure> {
+    let lines: Vec<&str> = output.lines().collect();
+    let mut failures = parse_cargo(&lines);
+    failures.extend(parse_pytest(&lines));
+    failures.extend(parse_jest(&lines));
+    failures.extend(parse_go(&lines));
+    failures.truncate(MAX_TEST_FAILURES);
+    failures
+}
+
+/// Formats `failures` as a compact list for the model.
+pub(crate) fn format_test_failures(failures: &[TestFailure]) -> String {
+    let mut text = format!("Failed tests ({}):", failures.len());
+    for failure in failures {
+        text.push_str("\n- ");
+        text.push_str(&failure.name);
+        match (&failure.file, failure.line) {
+            (Some(file), Some(line)) => text.push_str(&format!(" ({file}:{line})")),
+            (Some(file), None) => text.push_str(&format!(" ({file})")),
+            _ => {}
+        }
+        if !failure.message.is_empty() {
+            text.push_str(": ");
+            text.push_str(&failure.message);
+        }
+    }
+    text
+}
+
+fn new_failure(name: &str, file: Option<&str>, line: Option<u32>, message: &str) -> TestFailure {
+    TestFailure {
+        name: name.to_string(),
+        file: file.map(str::to_string),
+        line,
+        message: clip_message(message),
+    }
+}
+
+fn clip_message(message: &str) -> String {
+    let message = message.trim();
+    if message.len() <= MAX_MESSAGE_BYTES {
+        return message.to_string();
+    }
+    format!(
+        "{}…",
+        take_bytes_at_char_boundary(message, MAX_MESSAGE_BYTES)
+    )
+}
+
+/// Splits `path:line`, as in `math_test.go:12`.
+fn parse_file_line(location: &str) -> Option<(&str, u32)> {
+    let (file, line) = location.rsplit_once(':')?;
+    let line = line.parse().ok()?;
+    (!file.is_empty()).then_some((file, line))
+}
+
+/// Splits `path:line:column`, as in `src/lib.rs:10:9`.
+fn parse_file_line_column(location: &str) -> Option<(&str, u32)> {
+    let (location, column) = location.rsplit_once(':')?;
+    column.parse::<u32>().ok()?;
+    parse_file_line(location)
+}
+
+/// `test tests::name ... FAILED`, with the panic from the test's
+/// `---- tests::name stdout ----` block.
+fn parse_cargo(lines: &[&str]) -> Vec<TestFailure> {
+    let mut failures: Vec<TestFailure> = lines
+        .iter()
+        .filter_map(|line| {
+            let name = line.strip_prefix("test ")?.strip_suffix(" ... FAILED")?;
+            Some(new_failure(name, None, None, ""))
+        })
+        .collect();
+    if failures.is_empty() {
+        return failures;
+    }
+
+    let mut current = None;
+    let mut message_follows = false;
+    for line in lines {
+        if let Some(name) = line
+            .strip_prefix("---- ")
+            .and_then(|rest| rest.strip_suffix(" stdout ----"))
+        {
+            current = failures.iter().position(|failure| failure.name == name);
+            message_follows = false;
+            continue;
+        }
+        let Some(index) = current else {
+            continue;
+        };
+        let failure = &mut failures[index];
+        if message_follows {
+            if !line.trim().is_empty() {
+                failure.message = clip_message(line);
+                current = None;
+            }
+            continue;
+        }
+        let Some((_, panic)) = line.split_once("' panicked at ") else {
+            continue;
+        };
+        // Current toolchains print `panicked at src/lib.rs:10:9:` followed by
+        // the message; older ones print `panicked at 'message', src/lib.rs:10:9`.
+        let (message, location) = match panic.strip_suffix(':') {
+            Some(location) => (None, location),
+            None => match panic
+                .strip_prefix('\'')
+                .and_then(|rest| rest.rsplit_once("', "))
+            {
+                Some((message, location)) => (Some(message), location),
+                None => continue,
+            },
+        };
+        if let Some((file, line)) = parse_file_line_column(location) {
+            failure.file = Some(file.to_string());
+            failure.line = Some(line);
+        }
+        match message {
+            Some(message) => {
+                failure.message = clip_message(message);
+                current = None;
+            }
+            None => message_follows = true,
+        }
+    }
+    failures
+}
+
+/// The short test summary (`FAILED tests/test_x.py::test_add - message`),
+/// with line numbers from the matching `____ test_add ____` sections.
+fn parse_pytest(lines: &[&str]) -> Vec<TestFailure> {
+    let mut locations: HashMap<&str, Vec<(&str, u32)>> = HashMap::new();
+    let mut section = None;
+    let mut summary = Vec::new();
+    for line in lines {
+        if line.starts_with("___") && line.ends_with("___") {
+            section = Some(line.trim_matches('_').trim());
+            continue;
+        }
+        if let Some(rest) = line
+            .strip_prefix("FAILED ")
+            .or_else(|| line.strip_prefix("ERROR "))
+        {
+            let (id, message) = rest.split_once(" - ").unwrap_or((rest, ""));
+            if let Some((file, test)) = id.split_once("::") {
+                summary.push((id, file, test, message));
+            }
+            continue;
+        }
+        if let Some(section) = section
+            && let Some((location, _)) = line.split_once(": ")
+            && let Some(location) = parse_file_line(location)
+            && location.0.ends_with(".py")
+        {
+            locations.entry(section).or_default().push(location);
+        }
+    }
+
+    summary
+        .into_iter()
+        .map(|(id, file, test, message)| {
+            // Sections are titled `TestClass.test_name` for `TestClass::test_name`.
+            let line = locations
+                .get(test.replace("::", ".").as_str())
+                .and_then(|locations| {
+                    locations
+                        .iter()
+                        .rev()
+                        .find(|(path, _)| path.ends_with(file) || file.ends_with(path))
+                        .map(|(_, line)| *line)
+                });
+            new_failure(id, Some(file), line, message)
+        })
+        .collect()
+}
+
+/// `● Suite › test` blocks under `FAIL path/to/file.test.js`. Jest repeats
+/// the blocks in its closing summary; repeats are skipped.
+fn parse_jest(lines: &[&str]) -> Vec<TestFailure> {
+    let mut failures: Vec<TestFailure> = Vec::new();
+    let mut file = None;
+    let mut current = None;
+    let mut message_follows = false;
+    for line in lines {
+        let trimmed = line.trim();
+        if let Some(path) = trimmed.strip_prefix("FAIL ") {
+            file = path.split_whitespace().next();
+            current = None;
+            continue;
+        }
+        if let Some(name) = trimmed.strip_prefix("● ") {
+            let name = name.trim();
+            let seen = failures
+                .iter()
+                .any(|failure| failure.name == name && failure.file.as_deref() == file);
+            current = if seen {
+                None
+            } else {
+                failures.push(new_failure(name, file, None, ""));
+                Some(failures.len() - 1)
+            };
+            message_follows = true;
+            continue;
+        }
+        let Some(index) = current else {
+            continue;
+        };
+        let failure = &mut failures[index];
+        if message_follows {
+            if !trimmed.is_empty() {
+                failure.message = clip_message(trimmed);
+                message_follows = false;
+            }
+            continue;
+        }
+        // `at Object.<anonymous> (src/sum.test.js:4:21)`
+        let Some(frame) = trimmed.strip_prefix("at ") else {
+            continue;
+        };
+        let location = frame
+            .rsplit_once('(')
+            .map_or(frame, |(_, location)| location.trim_end_matches(')'));
+        if let (Some(test_file), Some((path, line))) =
+            (failure.file.as_deref(), parse_file_line_column(location))
+            && path.ends_with(test_file)
+        {
+            failure.line = Some(line);
+            current = None;
+        }
+    }
+    failures
+}
+
+/// `--- FAIL: TestName (0.00s)`, with the first `file.go:12: message` the
+/// test logged. Tests that only failed because a subtest did are left out.
+fn parse_go(lines: &[&str]) -> Vec<TestFailure> {
+    let mut names = Vec::new();
+    let mut logs: HashMap<&str, (&str, u32, &str)> = HashMap::new();
+    let mut running = None;
+    for line in lines {
+        let trimmed = line.trim_start();
+        if let Some(rest) = trimmed.strip_prefix("=== ") {
+            // `=== RUN   TestName`, `=== CONT  TestName`, ...
+            running = rest.split_whitespace().nth(1);
+            continue;
+        }
+        if let Some(rest) = trimmed.strip_prefix("--- FAIL: ") {
+            let name = rest.split(" (").next().unwrap_or(rest).trim();
+            names.push(name);
+            running = Some(name);
+            continue;
+        }
+        if trimmed.starts_with("--- ") {
+            running = None;
+            continue;
+        }
+        if let Some(name) = running
+            && let Some((location, message)) = trimmed.split_once(": ")
+            && let Some((file, line)) = parse_file_line(location)
+            && file.ends_with(".go")
+        {
+            logs.entry(name).or_insert((file, line, message));
+        }
+    }
+
+    names
+        .iter()
+        .filter(|name| {
+            let prefix = format!("{name}/");
+            !names.iter().any(|other| other.starts_with(&prefix))
+        })
+        .map(|name| match logs.get(name) {
+            Some((file, line, message)) => new_failure(name, Some(*file), Some(*line), message),
+            None => new_failure(name, None, None, ""),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn failure(name: &str, file: Option<&str>, line: Option<u32>, message: &str) -> TestFailure {
+        new_failure(name, file, line, message)
+    }
+
+    #[test]
+    fn parses_cargo_test_failures() {
+        let output = r#"
+running 3 tests
+test parser::tests::nested ... FAILED
+test parser::tests::flat ... ok
+test parser::tests::legacy ... FAILED
+
+failures:
+
+---- parser::tests::nested stdout ----
+
+thread 'parser::tests::nested' panicked at src/parser.rs:120:9:
+assertion `left == right` failed
+  left: 1
+ right: 2
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+---- parser::tests::legacy stdout ----
+thread 'parser::tests::legacy' panicked at 'index out of bounds', src/parser.rs:80:5
+
+failures:
+    parser::tests::legacy
+    parser::tests::nested
+
+test result: FAILED. 1 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out
+"#;
+
+        assert_eq!(
+            parse_test_failures(output),
+            vec![
+                failure(
+                    "parser::tests::nested",
+                    Some("src/parser.rs"),
+                    Some(120),
+                    "assertion `left == right` failed"
+                ),
+                failure(
+                    "parser::tests::legacy",
+                    Some("src/parser.rs"),
+                    Some(80),
+                    "index out of bounds"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_pytest_failures() {
+        let output = r#"
+============================= FAILURES =============================
+_________________________ TestMath.test_add _________________________
+
+self = <test_math.TestMath object at 0x1>
+
+    def test_add(self):
+>       assert add(1, 2) == 4
+E       assert 3 == 4
+
+tests/test_math.py:12: AssertionError
+========================= short test summary info ==========================
+FAILED tests/test_math.py::TestMath::test_add - assert 3 == 4
+ERROR tests/test_io.py::test_read
+===================== 1 failed, 1 error in 0.12s =====================
+"#;
+
+        assert_eq!(
+            parse_test_failures(output),
+            vec![
+                failure(
+                    "tests/test_math.py::TestMath::test_add",
+                    Some("tests/test_math.py"),
+                    Some(12),
+                    "assert 3 == 4"
+                ),
+                failure(
+                    "tests/test_io.py::test_read",
+                    Some("tests/test_io.py"),
+                    None,
+                    ""
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_jest_failures_once() {
+        let block = r#"
+  ● Math › adds numbers
+
+    expect(received).toBe(expected) // Object.is equality
+
+    Expected: 4
+    Received: 5
+
+      3 | test('adds numbers', () => {
+    > 4 |   expect(sum(2, 2)).toBe(4);
+        |                     ^
+
+      at Object.<anonymous> (/repo/src/sum.test.js:4:21)
+"#;
+        let output = format!(
+            " FAIL  src/sum.test.js (1.2 s)\n{block}\nSummary of all failing tests\n FAIL  src/sum.test.js\n{block}"
+        );
+
+        assert_eq!(
+            parse_test_failures(&output),
+            vec![failure(
+                "Math › adds numbers",
+                Some("src/sum.test.js"),
+                Some(4),
+                "expect(received).toBe(expected) // Object.is equality"
+            )]
+        );
+    }
+
+    #[test]
+    fn parses_go_test_failures() {
+        let output = r#"
+=== RUN   TestAdd
+=== RUN   TestAdd/negative
+    math_test.go:21: Add(-1, -2) = -4; want -3
+--- FAIL: TestAdd (0.00s)
+    --- FAIL: TestAdd/negative (0.00s)
+--- FAIL: TestDiv (0.00s)
+    div_test.go:9: Div(1, 0) did not return an error
+FAIL
+FAIL	example.com/calc	0.005s
+"#;
+
+        assert_eq!(
+            parse_test_failures(output),
+            vec![
+                failure(
+                    "TestAdd/negative",
+                    Some("math_test.go"),
+                    Some(21),
+                    "Add(-1, -2) = -4; want -3"
+                ),
+                failure(
+                    "TestDiv",
+                    Some("div_test.go"),
+                    Some(9),
+                    "Div(1, 0) did not return an error"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn formats_failures_for_the_model() {
+        let failures = vec![
+            failure("tests::a", Some("src/lib.rs"), Some(3), "boom"),
+            failure("TestB", None, None, ""),
+        ];
+
+        assert_eq!(
+            format_test_failures(&failures),
+            "Failed tests (2):\n- tests::a (src/lib.rs:3): boom\n- TestB"
+        );
+    }
+}
@@ -4,24 +4,52 @@
 //! through VS Code. Those bytes show up as invalid UTF-8 and used to be replaced with the standard
 //! Unicode replacement character. We now lean on `chardetng` and `encoding_rs` so we can
 //! automatically detect and decode the vast majority of legacy encodings before falling back to
-//! lossy UTF-8 decoding.
+//! lossy UTF-8 decoding. Detection can guess wrong on short output, so `exec_output_encoding`
+//! lets users name the encoding their system uses instead.
+
+use std::sync::RwLock;
 
 use chardetng::EncodingDetector;
 use encoding_rs::Encoding;
 use encoding_rs::IBM866;
 use encoding_rs::WINDOWS_1252;
 
+static OUTPUT_ENCODING_OVERRIDE: RwLock<Option<&'static Encoding>> = RwLock::new(None);
+
+/// Decodes non-UTF-8 output as `encoding` from now on instead of detecting its encoding.
+/// `None` turns detection back on.
+pub fn set_output_encoding_override(encoding: Option<&'static Encoding>) {
+    let Ok(mut guard) = OUTPUT_ENCODING_OVERRIDE.write() else {
+        tracing::warn!("Failed to acquire output encoding lock");
+        return;
+    };
+    *guard = encoding;
+}
+
 /// Attempts to convert arbitrary bytes to UTF-8 with best-effort encoding detection.
 pub fn bytes_to_string_smart(bytes: &[u8]) -> String {
+    let encoding_override = OUTPUT_ENCODING_OVERRIDE
+        .read()
+        .ok()
+        .and_then(|encoding| *encoding);
+    bytes_to_string_with_override(bytes, encoding_override)
+}
+
+fn bytes_to_string_with_override(
+    bytes: &[u8],
+    encoding_override: Option<&'static Encoding>,
+) -> String {
     if bytes.is_empty() {
         return String::new();
     }
 
+    // Valid UTF-8 is kept even with an override: other encodings rarely
+    // produce it by accident, and many tools print UTF-8 regardless of locale.
     if let Ok(utf8_str) = std::str::from_utf8(bytes) {
         return utf8_str.to_owned();
     }
 
-    let encoding = detect_encoding(bytes);
+    let encoding = encoding_override.unwrap_or_else(|| detect_encoding(bytes));
     decode_bytes(bytes, encoding)
 }
 
@@ -143,6 +171,26 @@ mod tests {
     use encoding_rs::WINDOWS_1258;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn test_override_replaces_detection() {
+        // "テスト" in Shift-JIS; too short for detection to be reliable.
+        let shift_jis = b"\x83\x65\x83\x58\x83\x67";
+        assert_eq!(
+            bytes_to_string_with_override(shift_jis, Some(SHIFT_JIS)),
+            "テスト"
+        );
+        // "café" in Latin-1, which WHATWG maps to Windows-1252.
+        assert_eq!(
+            bytes_to_string_with_override(b"caf\xE9", Encoding::for_label(b"latin1")),
+            "café"
+        );
+        // UTF-8 output is left alone.
+        assert_eq!(
+            bytes_to_string_with_override("テスト".as_bytes(), Some(WINDOWS_1252)),
+            "テスト"
+        );
+    }
+
     #[test]
     fn test_utf8_passthrough() {
         // Fast path: when UTF-8 is valid we should avoid copies and return as-is.
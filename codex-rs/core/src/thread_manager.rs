@@ -481,6 +481,35 @@ impl ThreadManagerState {
         .await
     }
 
+    /// Fork the thread recorded at `rollout_path`, keeping the history before the
+    /// `nth_user_message`-th user message (0-based), which must exist.
+    pub(crate) async fn fork_thread_from_rollout(
+        &self,
+        config: Config,
+        rollout_path: PathBuf,
+        nth_user_message: usize,
+        agent_control: AgentControl,
+    ) -> CodexResult<NewThread> {
+        let history = RolloutRecorder::get_rollout_history(&rollout_path).await?;
+        let user_turns =
+            truncation::user_message_positions_in_rollout(&history.get_rollout_items()).len();
+        if nth_user_message >= user_turns {
+            return Err(CodexErr::InvalidRequest(format!(
+                "cannot fork at user message {nth_user_message}: the thread has {user_turns} user messages"
+            )));
+        }
+        let history = truncate_before_nth_user_message(history, nth_user_message);
+        self.spawn_thread(
+            config,
+            history,
+            Arc::clone(&self.auth_manager),
+            agent_control,
+            Vec::new(),
+            false,
+        )
+        .await
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub(crate) async fn spawn_thread_with_source(
         &self,
@@ -0,0 +1,648 @@
+//! Headless Chromium driven over the Chrome DevTools Protocol (CDP).
+//!
+//! The browser is launched lazily on the first `browser` tool call and kept
+//! alive for the rest of the session so cookies and page state survive
+//! between calls. Only a single page target is driven.
+//!
+//! When the sandbox restricts network access, every request the page makes
+//! (navigations, redirects, subresources, scripts) is paused through the CDP
+//! `Fetch` domain and only allowed to hosts the user approved. `Fetch` does
+//! not see WebSocket handshakes or service worker traffic, so Chromium is
+//! also pointed at the managed network proxy, and the browser is not started
+//! without one.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_otel::OtelManager;
+use futures::SinkExt;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use serde_json::json;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::BufReader;
+use tokio::net::TcpStream;
+use tokio::process::Child;
+use tokio::sync::MappedMutexGuard;
+use tokio::sync::Mutex;
+use tokio::sync::MutexGuard;
+use tokio_tungstenite::MaybeTlsStream;
+use tokio_tungstenite::WebSocketStream;
+use tokio_tungstenite::tungstenite::Message;
+use tracing::warn;
+
+const BROWSER_PATH_ENV_VAR: &str = "CODEX_BROWSER_PATH";
+const BROWSER_CANDIDATES: &[&str] = &[
+    "chromium",
+    "chromium-browser",
+    "google-chrome",
+    "google-chrome-stable",
+    "chrome",
+];
+#[cfg(target_os = "macos")]
+const MACOS_CHROME_PATH: &str = "/Applications/Google Chrome.app/Contents/MacOS/Google Chrome";
+const LAUNCH_TIMEOUT: Duration = Duration::from_secs(15);
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+const DEVTOOLS_LISTENING_PREFIX: &str = "DevTools listening on ";
+const REQUEST_PAUSED_EVENT: &str = "Fetch.requestPaused";
+
+/// Network access the browser is launched with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BrowserNetwork {
+    /// The sandbox allows full network access.
+    Full,
+    /// Requests are limited to approved hosts and go through the managed
+    /// network proxy.
+    Restricted { proxy: SocketAddr },
+}
+
+/// Session-scoped browser handle. The Chromium process is started on demand
+/// and restarted if a previous instance died.
+#[derive(Default)]
+pub(crate) struct BrowserHandle {
+    browser: Mutex<Option<Browser>>,
+    /// Hosts the user approved for the rest of the session.
+    approved_hosts: Mutex<HashSet<String>>,
+}
+
+impl fmt::Debug for BrowserHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BrowserHandle").finish_non_exhaustive()
+    }
+}
+
+impl BrowserHandle {
+    /// Returns the live browser, launching one first if none is running, the
+    /// previous instance exited, or it was launched with other network access.
+    pub(crate) async fn browser(
+        &self,
+        otel_manager: &OtelManager,
+        network: BrowserNetwork,
+    ) -> Result<MappedMutexGuard<'_, Browser>, String> {
+        let mut guard = self.browser.lock().await;
+        if guard
+            .as_mut()
+            .is_none_or(|browser| !browser.is_alive() || browser.network != network)
+        {
+            let restart = guard.as_mut().is_some_and(|browser| !browser.is_alive());
+            // Kill the old instance before starting its replacement.
+            guard.take();
+            *guard = Some(Browser::launch(network).await?);
+            if restart {
+                otel_manager.counter("codex.sidecar.restart", 1, &[("sidecar", "browser")]);
+            }
+        }
+        MutexGuard::try_map(guard, Option::as_mut).map_err(|_| "browser unavailable".to_string())
+    }
+
+    pub(crate) async fn is_host_approved(&self, host: &str) -> bool {
+        self.approved_hosts.lock().await.contains(host)
+    }
+
+    pub(crate) async fn approve_host(&self, host: String) {
+        self.approved_hosts.lock().await.insert(host);
+    }
+
+    pub(crate) async fn approved_hosts(&self) -> HashSet<String> {
+        self.approved_hosts.lock().await.clone()
+    }
+
+    /// Kills the browser, if one is running. The next call launches a new one.
+    pub(crate) async fn shutdown(&self) {
+        self.browser.lock().await.take();
+    }
+}
+
+pub(crate) struct Browser {
+    child: Child,
+    _profile_dir: tempfile::TempDir,
+    network: BrowserNetwork,
+    page: CdpConnection,
+}
+
+/// Result of a `Page.navigate` once the load event fired.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PageSummary {
+    pub url: String,
+    pub title: String,
+}
+
+impl Browser {
+    async fn launch(network: BrowserNetwork) -> Result<Self, String> {
+        let executable = resolve_browser().ok_or_else(|| {
+            format!("Chromium not found; install Chromium/Chrome or set {BROWSER_PATH_ENV_VAR}")
+        })?;
+        let profile_dir = tempfile::tempdir()
+            .map_err(|err| format!("failed to create browser profile dir: {err}"))?;
+
+        let mut command = tokio::process::Command::new(&executable);
+        command
+            .args([
+                "--headless=new",
+                "--disable-gpu",
+                "--no-first-run",
+                "--no-default-browser-check",
+                "--remote-debugging-port=0",
+            ])
+            .arg(format!(
+                "--user-data-dir={}",
+                profile_dir.path().to_string_lossy()
+            ));
+        let mut child = command
+            .args(network_args(network))
+            .arg("about:blank")
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| format!("failed to launch {}: {err}", executable.display()))?;
+
+        let stderr = child
+            .stderr
+            .take()
+            .ok_or_else(|| "browser missing stderr".to_string())?;
+        let mut lines = BufReader::new(stderr).lines();
+        let browser_ws_url = tokio::time::timeout(LAUNCH_TIMEOUT, async {
+            while let Ok(Some(line)) = lines.next_line().await {
+                if let Some(url) = parse_devtools_url(&line) {
+                    return Some(url);
+                }
+            }
+            None
+        })
+        .await
+        .ok()
+        .flatten()
+        .ok_or_else(|| "browser did not report a DevTools endpoint".to_string())?;
+        // Keep draining stderr so Chromium never blocks on a full pipe.
+        tokio::spawn(async move { while let Ok(Some(_)) = lines.next_line().await {} });
+
+        let page_ws_url = find_page_target(&browser_ws_url).await?;
+        let mut page = CdpConnection::connect(&page_ws_url).await?;
+        page.call("Page.enable", json!({})).await?;
+        if let BrowserNetwork::Restricted { .. } = network {
+            let intercept = page
+                .call(
+                    "Fetch.enable",
+                    json!({ "patterns": [{ "urlPattern": "*", "requestStage": "Request" }] }),
+                )
+                .await;
+            match intercept {
+                Ok(_) => page.allowed_hosts = Some(HashSet::new()),
+                Err(err) => {
+                    warn!(
+                        "browser request interception unavailable, relying on the network proxy: {err}"
+                    );
+                }
+            }
+        }
+
+        Ok(Self {
+            child,
+            _profile_dir: profile_dir,
+            network,
+            page,
+        })
+    }
+
+    /// Sets the hosts the page may reach. Has no effect unless requests are
+    /// intercepted.
+    pub(crate) fn set_allowed_hosts(&mut self, hosts: HashSet<String>) {
+        if let Some(allowed_hosts) = self.page.allowed_hosts.as_mut() {
+            *allowed_hosts = hosts;
+        }
+    }
+
+    /// Returns the hosts requests were blocked for since the last call, in
+    /// the order they were first blocked.
+    pub(crate) fn take_blocked_hosts(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.page.blocked_hosts)
+    }
+
+    fn is_alive(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(None))
+    }
+
+    pub(crate) async fn navigate(&mut self, url: &str) -> Result<PageSummary, String> {
+        // Drop load events left over from earlier navigations.
+        self.page.buffered_events.clear();
+        let response = self
+            .page
+            .call("Page.navigate", json!({ "url": url }))
+            .await?;
+        if let Some(error) = response.get("errorText").and_then(JsonValue::as_str) {
+            return Err(format!("navigation failed: {error}"));
+        }
+        self.page.wait_for_event("Page.loadEventFired").await?;
+        let title = self.evaluate("document.title").await?;
+        let url = self.evaluate("location.href").await?;
+        Ok(PageSummary {
+            url: json_to_text(url),
+            title: json_to_text(title),
+        })
+    }
+
+    /// Returns the rendered text of `selector` (or the whole body).
+    pub(crate) async fn text(&mut self, selector: Option<&str>) -> Result<String, String> {
+        let expression = match selector {
+            Some(selector) => format!(
+                "(() => {{ const el = document.querySelector({selector}); return el ? el.innerText : null; }})()",
+                selector = json!(selector)
+            ),
+            None => "document.body ? document.body.innerText : ''".to_string(),
+        };
+        match self.evaluate(&expression).await? {
+            JsonValue::Null => Err(format!(
+                "no element matches selector `{}`",
+                selector.unwrap_or_default()
+            )),
+            value => Ok(json_to_text(value)),
+        }
+    }
+
+    /// Captures the viewport as a base64-encoded PNG.
+    pub(crate) async fn screenshot(&mut self) -> Result<String, String> {
+        let response = self
+            .page
+            .call("Page.captureScreenshot", json!({ "format": "png" }))
+            .await?;
+        response
+            .get("data")
+            .and_then(JsonValue::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| "screenshot response missing data".to_string())
+    }
+
+    pub(crate) async fn click(&mut self, selector: &str) -> Result<(), String> {
+        let expression = format!(
+            "(() => {{ const el = document.querySelector({selector}); if (!el) return false; el.click(); return true; }})()",
+            selector = json!(selector)
+        );
+        match self.evaluate(&expression).await? {
+            JsonValue::Bool(true) => Ok(()),
+            _ => Err(format!("no element matches selector `{selector}`")),
+        }
+    }
+
+    pub(crate) async fn type_text(&mut self, selector: &str, text: &str) -> Result<(), String> {
+        let expression = format!(
+            "(() => {{ const el = document.querySelector({selector}); if (!el) return false; el.focus(); el.value = {text}; el.dispatchEvent(new Event('input', {{ bubbles: true }})); el.dispatchEvent(new Event('change', {{ bubbles: true }})); return true; }})()",
+            selector = json!(selector),
+            text = json!(text)
+        );
+        match self.evaluate(&expression).await? {
+            JsonValue::Bool(true) => Ok(()),
+            _ => Err(format!("no element matches selector `{selector}`")),
+        }
+    }
+
+    async fn evaluate(&mut self, expression: &str) -> Result<JsonValue, String> {
+        let response = self
+            .page
+            .call(
+                "Runtime.evaluate",
+                json!({ "expression": expression, "returnByValue": true }),
+            )
+            .await?;
+        if let Some(details) = response.get("exceptionDetails") {
+            let message = details
+                .pointer("/exception/description")
+                .or_else(|| details.get("text"))
+                .map(|value| json_to_text(value.clone()))
+                .unwrap_or_else(|| "script threw an exception".to_string());
+            return Err(message);
+        }
+        Ok(response
+            .pointer("/result/value")
+            .cloned()
+            .unwrap_or(JsonValue::Null))
+    }
+}
+
+struct CdpConnection {
+    ws: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    next_id: u64,
+    /// Events received while waiting for a command response.
+    buffered_events: Vec<String>,
+    /// Hosts paused requests may reach, or `None` when requests are not
+    /// intercepted.
+    allowed_hosts: Option<HashSet<String>>,
+    blocked_hosts: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct CdpMessage {
+    #[serde(default)]
+    id: Option<u64>,
+    #[serde(default)]
+    method: Option<String>,
+    #[serde(default)]
+    params: Option<JsonValue>,
+    #[serde(default)]
+    result: Option<JsonValue>,
+    #[serde(default)]
+    error: Option<CdpError>,
+}
+
+#[derive(Deserialize)]
+struct CdpError {
+    message: String,
+}
+
+impl CdpConnection {
+    async fn connect(url: &str) -> Result<Self, String> {
+        let (ws, _response) = tokio_tungstenite::connect_async(url)
+            .await
+            .map_err(|err| format!("failed to connect to DevTools: {err}"))?;
+        Ok(Self {
+            ws,
+            next_id: 1,
+            buffered_events: Vec::new(),
+            allowed_hosts: None,
+            blocked_hosts: Vec::new(),
+        })
+    }
+
+    /// Sends a command without waiting for its response, which is skipped
+    /// when it arrives.
+    async fn send(&mut self, method: &str, params: JsonValue) -> Result<u64, String> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let request = json!({ "id": id, "method": method, "params": params });
+        self.ws
+            .send(Message::Text(request.to_string().into()))
+            .await
+            .map_err(|err| format!("failed to send {method}: {err}"))?;
+        Ok(id)
+    }
+
+    async fn call(&mut self, method: &str, params: JsonValue) -> Result<JsonValue, String> {
+        let id = self.send(method, params).await?;
+
+        tokio::time::timeout(COMMAND_TIMEOUT, async {
+            loop {
+                let message = self.next_message().await?;
+                match message.id {
+                    Some(response_id) if response_id == id => {
+                        if let Some(error) = message.error {
+                            return Err(format!("{method} failed: {}", error.message));
+                        }
+                        return Ok(message.result.unwrap_or(JsonValue::Null));
+                    }
+                    Some(_) => {}
+                    None => {
+                        if let Some(event) = message.method {
+                            self.buffered_events.push(event);
+                        }
+                    }
+                }
+            }
+        })
+        .await
+        .map_err(|_| format!("{method} timed out"))?
+    }
+
+    async fn wait_for_event(&mut self, event: &str) -> Result<(), String> {
+        if let Some(index) = self.buffered_events.iter().position(|e| e == event) {
+            self.buffered_events.drain(..=index);
+            return Ok(());
+        }
+        tokio::time::timeout(COMMAND_TIMEOUT, async {
+            loop {
+                let message = self.next_message().await?;
+                if message.method.as_deref() == Some(event) {
+                    self.buffered_events.clear();
+                    return Ok(());
+                }
+            }
+        })
+        .await
+        .map_err(|_| format!("timed out waiting for {event}"))?
+    }
+
+    /// Returns the next message, resolving paused requests along the way.
+    async fn next_message(&mut self) -> Result<CdpMessage, String> {
+        loop {
+            match self.ws.next().await {
+                Some(Ok(Message::Text(text))) => {
+                    let Ok(message) = serde_json::from_str::<CdpMessage>(&text) else {
+                        continue;
+                    };
+                    if message.method.as_deref() == Some(REQUEST_PAUSED_EVENT) {
+                        self.resolve_paused_request(message.params.unwrap_or_default())
+                            .await?;
+                        continue;
+                    }
+                    return Ok(message);
+                }
+                Some(Ok(Message::Close(_))) | None => {
+                    return Err("DevTools connection closed".to_string());
+                }
+                Some(Ok(_)) => {}
+                Some(Err(err)) => return Err(format!("DevTools connection error: {err}")),
+            }
+        }
+    }
+
+    async fn resolve_paused_request(&mut self, params: JsonValue) -> Result<(), String> {
+        let Some(request_id) = params.get("requestId").and_then(JsonValue::as_str) else {
+            return Ok(());
+        };
+        let request_id = request_id.to_string();
+        let url = params
+            .pointer("/request/url")
+            .and_then(JsonValue::as_str)
+            .unwrap_or_default();
+        let blocked = self
+            .allowed_hosts
+            .as_ref()
+            .and_then(|allowed_hosts| blocked_host(url, allowed_hosts));
+        match blocked {
+            None => {
+                self.send("Fetch.continueRequest", json!({ "requestId": request_id }))
+                    .await?;
+            }
+            Some(host) => {
+                if !self.blocked_hosts.contains(&host) {
+                    self.blocked_hosts.push(host);
+                }
+                self.send(
+                    "Fetch.failRequest",
+                    json!({ "requestId": request_id, "errorReason": "BlockedByClient" }),
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the host a request to `url` is blocked for, or `None` when the
+/// request may proceed. Requests that never leave the browser pass.
+/// Chromium flags for `network`. Restricted browsers send everything through
+/// the proxy, since `Fetch` interception misses WebSockets and service workers.
+fn network_args(network: BrowserNetwork) -> Vec<String> {
+    match network {
+        BrowserNetwork::Full => Vec::new(),
+        BrowserNetwork::Restricted { proxy } => vec![
+            // Popups open new targets that the page's `Fetch` interception
+            // does not cover.
+            "--block-new-web-contents".to_string(),
+            "--disable-background-networking".to_string(),
+            format!("--proxy-server=http://{proxy}"),
+            // `<-loopback>` sends loopback traffic through the proxy too.
+            "--proxy-bypass-list=<-loopback>".to_string(),
+        ],
+    }
+}
+
+fn blocked_host(url: &str, allowed_hosts: &HashSet<String>) -> Option<String> {
+    let Ok(parsed) = url::Url::parse(url) else {
+        return Some(url.to_string());
+    };
+    if matches!(parsed.scheme(), "data" | "blob" | "about") {
+        return None;
+    }
+    match parsed.host_str() {
+        Some(host) if allowed_hosts.contains(host) => None,
+        Some(host) => Some(host.to_string()),
+        None => Some(url.to_string()),
+    }
+}
+
+fn parse_devtools_url(line: &str) -> Option<String> {
+    line.trim()
+        .strip_prefix(DEVTOOLS_LISTENING_PREFIX)
+        .filter(|url| url.starts_with("ws://"))
+        .map(str::to_string)
+}
+
+/// Resolves the websocket URL of the initial page target via the DevTools
+/// HTTP endpoint that sits next to the browser websocket.
+async fn find_page_target(browser_ws_url: &str) -> Result<String, String> {
+    let parsed = url::Url::parse(browser_ws_url)
+        .map_err(|err| format!("invalid DevTools url `{browser_ws_url}`: {err}"))?;
+    let host = parsed.host_str().unwrap_or("127.0.0.1");
+    let port = parsed
+        .port()
+        .ok_or_else(|| format!("DevTools url `{browser_ws_url}` has no port"))?;
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Target {
+        r#type: String,
+        web_socket_debugger_url: Option<String>,
+    }
+
+    let targets: Vec<Target> = reqwest::get(format!("http://{host}:{port}/json/list"))
+        .await
+        .map_err(|err| format!("failed to list browser targets: {err}"))?
+        .json()
+        .await
+        .map_err(|err| format!("failed to parse browser targets: {err}"))?;
+    targets
+        .into_iter()
+        .find(|target| target.r#type == "page")
+        .and_then(|target| target.web_socket_debugger_url)
+        .ok_or_else(|| "browser has no page target".to_string())
+}
+
+fn json_to_text(value: JsonValue) -> String {
+    match value {
+        JsonValue::String(text) => text,
+        JsonValue::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn resolve_browser() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(BROWSER_PATH_ENV_VAR) {
+        let path = PathBuf::from(path);
+        if path.exists() {
+            return Some(path);
+        }
+    }
+    if let Some(path) = BROWSER_CANDIDATES
+        .iter()
+        .find_map(|name| which::which(name).ok())
+    {
+        return Some(path);
+    }
+    #[cfg(target_os = "macos")]
+    {
+        let path = PathBuf::from(MACOS_CHROME_PATH);
+        if path.exists() {
+            return Some(path);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_devtools_listening_line() {
+        assert_eq!(
+            parse_devtools_url(
+                "DevTools listening on ws://127.0.0.1:40261/devtools/browser/6b1c4f0e\n"
+            ),
+            Some("ws://127.0.0.1:40261/devtools/browser/6b1c4f0e".to_string())
+        );
+        assert_eq!(parse_devtools_url("[0101/000000.000:ERROR] gpu"), None);
+    }
+
+    #[test]
+    fn blocks_requests_to_unapproved_hosts() {
+        let allowed_hosts = HashSet::from(["example.com".to_string()]);
+        assert_eq!(
+            [
+                "https://example.com/app.js",
+                "https://cdn.example.net/lib.js",
+                "data:text/plain,hi",
+                "wss://evil.test/socket",
+            ]
+            .map(|url| blocked_host(url, &allowed_hosts)),
+            [
+                None,
+                Some("cdn.example.net".to_string()),
+                None,
+                Some("evil.test".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn restricted_browsers_use_the_proxy() {
+        let proxy: SocketAddr = "127.0.0.1:3128".parse().expect("socket addr");
+        assert_eq!(network_args(BrowserNetwork::Full), Vec::<String>::new());
+        assert_eq!(
+            network_args(BrowserNetwork::Restricted { proxy }),
+            vec![
+                "--block-new-web-contents".to_string(),
+                "--disable-background-networking".to_string(),
+                "--proxy-server=http://127.0.0.1:3128".to_string(),
+                "--proxy-bypass-list=<-loopback>".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn json_to_text_unwraps_strings() {
+        assert_eq!(
+            (
+                json_to_text(json!("hello")),
+                json_to_text(JsonValue::Null),
+                json_to_text(json!(3))
+            ),
+            ("hello".to_string(), String::new(), "3".to_string())
+        );
+    }
+}
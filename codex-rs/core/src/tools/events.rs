@@ -3,6 +3,8 @@ use crate::codex::TurnContext;
 use crate::error::CodexErr;
 use crate::error::SandboxErr;
 use crate::exec::ExecToolCallOutput;
+use crate::exec::StreamOutput;
+use crate::features::Feature;
 use crate::function_tool::FunctionCallError;
 use crate::parse_command::parse_command;
 use crate::protocol::EventMsg;
@@ -11,11 +13,15 @@ use crate::protocol::ExecCommandEndEvent;
 use crate::protocol::ExecCommandSource;
 use crate::protocol::ExecCommandStatus;
 use crate::protocol::FileChange;
+use crate::protocol::FileContentHashes;
 use crate::protocol::PatchApplyBeginEvent;
 use crate::protocol::PatchApplyEndEvent;
 use crate::protocol::PatchApplyStatus;
+use crate::protocol::TestFailure;
 use crate::protocol::TurnDiffEvent;
+use crate::test_failures::exec_test_failures;
 use crate::tools::context::SharedTurnDiffTracker;
+use crate::tools::output_summary::summarize_output;
 use crate::tools::sandboxing::ToolError;
 use codex_protocol::parse_command::ParsedCommand;
 use std::collections::HashMap;
@@ -23,6 +29,7 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::time::Duration;
 
+use super::build_content_with_timeout;
 use super::format_exec_output_str;
 
 #[derive(Clone, Copy)]
@@ -97,6 +104,7 @@ pub(crate) enum ToolEmitter {
     },
     ApplyPatch {
         changes: HashMap<PathBuf, FileChange>,
+        content_hashes: HashMap<PathBuf, FileContentHashes>,
         auto_approved: bool,
     },
     UnifiedExec {
@@ -125,9 +133,14 @@ impl ToolEmitter {
         }
     }
 
-    pub fn apply_patch(changes: HashMap<PathBuf, FileChange>, auto_approved: bool) -> Self {
+    pub fn apply_patch(
+        changes: HashMap<PathBuf, FileChange>,
+        content_hashes: HashMap<PathBuf, FileContentHashes>,
+        auto_approved: bool,
+    ) -> Self {
         Self::ApplyPatch {
             changes,
+            content_hashes,
             auto_approved,
         }
     }
@@ -171,6 +184,7 @@ impl ToolEmitter {
             (
                 Self::ApplyPatch {
                     changes,
+                    content_hashes,
                     auto_approved,
                 },
                 ToolEventStage::Begin,
@@ -179,6 +193,22 @@ impl ToolEmitter {
                     let mut guard = tracker.lock().await;
                     guard.on_patch_begin(changes);
                 }
+                ctx.session
+                    .services
+                    .session_diff_tracker
+                    .lock()
+                    .await
+                    .on_patch_begin(changes);
+                ctx.session
+                    .services
+                    .patch_backups
+                    .lock()
+                    .await
+                    .on_patch_begin(ctx.call_id, changes);
+                ctx.session
+                    .services
+                    .disk_quota
+                    .before_write(patch_paths(changes));
                 ctx.session
                     .send_event(
                         ctx.turn,
@@ -187,6 +217,7 @@ impl ToolEmitter {
                             turn_id: ctx.turn.sub_id.clone(),
                             auto_approved: *auto_approved,
                             changes: changes.clone(),
+                            content_hashes: content_hashes.clone(),
                         }),
                     )
                     .await;
@@ -283,16 +314,33 @@ impl ToolEmitter {
         self.emit(ctx, ToolEventStage::Begin).await;
     }
 
-    fn format_exec_output_for_model(
+    async fn format_exec_output_for_model(
         &self,
         output: &ExecToolCallOutput,
         ctx: ToolEventCtx<'_>,
     ) -> String {
+        let summarized = summarized_exec_output(output, ctx);
+        if summarized.is_some() {
+            ctx.session
+                .services
+                .full_outputs
+                .lock()
+                .await
+                .insert(ctx.call_id.to_string(), build_content_with_timeout(output));
+        }
+        let test_failures = exec_test_failures(output);
+        let output = summarized.as_ref().unwrap_or(output);
         match self {
-            Self::Shell { freeform: true, .. } => {
-                super::format_exec_output_for_model_freeform(output, ctx.turn.truncation_policy)
-            }
-            _ => super::format_exec_output_for_model_structured(output, ctx.turn.truncation_policy),
+            Self::Shell { freeform: true, .. } => super::format_exec_output_for_model_freeform(
+                output,
+                &test_failures,
+                ctx.turn.truncation_policy,
+            ),
+            _ => super::format_exec_output_for_model_structured(
+                output,
+                &test_failures,
+                ctx.turn.truncation_policy,
+            ),
         }
     }
 
@@ -303,7 +351,7 @@ impl ToolEmitter {
     ) -> Result<String, FunctionCallError> {
         let (event, result) = match out {
             Ok(output) => {
-                let content = self.format_exec_output_for_model(&output, ctx);
+                let content = self.format_exec_output_for_model(&output, ctx).await;
                 let exit_code = output.exit_code;
                 let event = ToolEventStage::Success(output);
                 let result = if exit_code == 0 {
@@ -315,7 +363,7 @@ impl ToolEmitter {
             }
             Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { output })))
             | Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { output, .. }))) => {
-                let response = self.format_exec_output_for_model(&output, ctx);
+                let response = self.format_exec_output_for_model(&output, ctx).await;
                 let event = ToolEventStage::Failure(ToolEventFailure::Output(*output));
                 let result = Err(FunctionCallError::RespondToModel(response));
                 (event, result)
@@ -356,6 +404,28 @@ impl ToolEmitter {
     }
 }
 
+/// With `output_summaries` on, returns a copy of `output` whose body is a
+/// summary if the full output does not fit the turn's truncation budget.
+fn summarized_exec_output(
+    output: &ExecToolCallOutput,
+    ctx: ToolEventCtx<'_>,
+) -> Option<ExecToolCallOutput> {
+    if !ctx.turn.features.enabled(Feature::OutputSummaries) {
+        return None;
+    }
+    let summary = summarize_output(
+        &build_content_with_timeout(output),
+        ctx.call_id,
+        ctx.turn.truncation_policy,
+    )?;
+    Some(ExecToolCallOutput {
+        aggregated_output: StreamOutput::new(summary),
+        // The summary already starts with the timeout notice.
+        timed_out: false,
+        ..output.clone()
+    })
+}
+
 struct ExecCommandInput<'a> {
     command: &'a [String],
     cwd: &'a Path,
@@ -393,6 +463,7 @@ struct ExecCommandResult {
     duration: Duration,
     formatted_output: String,
     status: ExecCommandStatus,
+    test_failures: Vec<TestFailure>,
 }
 
 async fn emit_exec_stage(
@@ -421,12 +492,18 @@ async fn emit_exec_stage(
                 aggregated_output: output.aggregated_output.text.clone(),
                 exit_code: output.exit_code,
                 duration: output.duration,
-                formatted_output: format_exec_output_str(&output, ctx.turn.truncation_policy),
+                formatted_output: format_exec_output_str(
+                    summarized_exec_output(&output, ctx)
+                        .as_ref()
+                        .unwrap_or(&output),
+                    ctx.turn.truncation_policy,
+                ),
                 status: if output.exit_code == 0 {
                     ExecCommandStatus::Completed
                 } else {
                     ExecCommandStatus::Failed
                 },
+                test_failures: exec_test_failures(&output),
             };
             emit_exec_end(ctx, exec_input, exec_result).await;
         }
@@ -440,6 +517,7 @@ async fn emit_exec_stage(
                 duration: Duration::ZERO,
                 formatted_output: text,
                 status: ExecCommandStatus::Failed,
+                test_failures: Vec::new(),
             };
             emit_exec_end(ctx, exec_input, exec_result).await;
         }
@@ -453,6 +531,7 @@ async fn emit_exec_stage(
                 duration: Duration::ZERO,
                 formatted_output: text,
                 status: ExecCommandStatus::Declined,
+                test_failures: Vec::new(),
             };
             emit_exec_end(ctx, exec_input, exec_result).await;
         }
@@ -483,11 +562,26 @@ async fn emit_exec_end(
                 duration: exec_result.duration,
                 formatted_output: exec_result.formatted_output,
                 status: exec_result.status,
+                test_failures: exec_result.test_failures,
             }),
         )
         .await;
 }
 
+/// Every path a patch writes, including the destinations of moves.
+fn patch_paths(changes: &HashMap<PathBuf, FileChange>) -> impl Iterator<Item = &Path> {
+    changes.iter().flat_map(|(path, change)| {
+        let move_path = match change {
+            FileChange::Update {
+                move_path: Some(move_path),
+                ..
+            } => Some(move_path.as_path()),
+            _ => None,
+        };
+        std::iter::once(path.as_path()).chain(move_path)
+    })
+}
+
 async fn emit_patch_end(
     ctx: ToolEventCtx<'_>,
     changes: HashMap<PathBuf, FileChange>,
@@ -496,6 +590,16 @@ async fn emit_patch_end(
     success: bool,
     status: PatchApplyStatus,
 ) {
+    ctx.session
+        .services
+        .patch_backups
+        .lock()
+        .await
+        .on_patch_end(ctx.call_id);
+    ctx.session
+        .services
+        .disk_quota
+        .after_write(patch_paths(&changes));
     ctx.session
         .send_event(
             ctx.turn,
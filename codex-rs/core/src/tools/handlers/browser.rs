@@ -0,0 +1,317 @@
+use async_trait::async_trait;
+use codex_network_proxy::NetworkProxy;
+use codex_protocol::approvals::NetworkApprovalContext;
+use codex_protocol::approvals::NetworkApprovalProtocol;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::openai_models::InputModality;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::ReviewDecision;
+use codex_utils_string::take_bytes_at_char_boundary;
+use serde::Deserialize;
+use std::net::SocketAddr;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::features::Feature;
+use crate::function_tool::FunctionCallError;
+use crate::tools::browser::Browser;
+use crate::tools::browser::BrowserNetwork;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::turn_latency::LatencyStage;
+
+pub struct BrowserHandler;
+
+const DEFAULT_MAX_TEXT_BYTES: usize = 32 * 1024;
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum BrowserArgs {
+    Navigate {
+        url: String,
+    },
+    Text {
+        #[serde(default)]
+        selector: Option<String>,
+        #[serde(default)]
+        max_bytes: Option<usize>,
+    },
+    Screenshot,
+    Click {
+        selector: String,
+    },
+    Type {
+        selector: String,
+        text: String,
+    },
+}
+
+#[async_trait]
+impl ToolHandler for BrowserHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn is_mutating(&self, _invocation: &ToolInvocation) -> bool {
+        true
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            call_id,
+            ..
+        } = invocation;
+
+        if !session.features().enabled(Feature::Browser) {
+            return Err(FunctionCallError::RespondToModel(
+                "browser is disabled by feature flag".to_string(),
+            ));
+        }
+
+        let args: BrowserArgs = match payload {
+            ToolPayload::Function { arguments } => parse_arguments(&arguments)?,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "browser handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        if matches!(args, BrowserArgs::Screenshot)
+            && !turn
+                .model_info
+                .input_modalities
+                .contains(&InputModality::Image)
+        {
+            return Err(FunctionCallError::RespondToModel(
+                "screenshots are unavailable because the current model does not support image inputs"
+                    .to_string(),
+            ));
+        }
+
+        let handle = &session.services.browser;
+        let mut allowed_hosts = handle.approved_hosts().await;
+        if let BrowserArgs::Navigate { url } = &args {
+            let host =
+                ensure_navigation_allowed(session.as_ref(), turn.as_ref(), &call_id, url).await?;
+            allowed_hosts.insert(host);
+        }
+
+        let _round_trip = turn.latency.span(LatencyStage::SidecarRoundTrip);
+        let mut browser = handle
+            .browser(&turn.otel_manager, browser_network(turn.as_ref())?)
+            .await
+            .map_err(FunctionCallError::RespondToModel)?;
+        browser.set_allowed_hosts(allowed_hosts);
+        let result = run_action(&mut browser, args).await;
+        let blocked_note = blocked_hosts_note(&browser.take_blocked_hosts());
+        let body = match result {
+            Ok(FunctionCallOutputBody::Text(text)) => {
+                FunctionCallOutputBody::Text(format!("{text}{blocked_note}"))
+            }
+            Ok(body) => body,
+            Err(err) => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "{err}{blocked_note}"
+                )));
+            }
+        };
+
+        Ok(ToolOutput::Function {
+            body,
+            success: Some(true),
+        })
+    }
+}
+
+async fn run_action(
+    browser: &mut Browser,
+    args: BrowserArgs,
+) -> Result<FunctionCallOutputBody, String> {
+    let body = match args {
+        BrowserArgs::Navigate { url } => {
+            let page = browser.navigate(&url).await?;
+            FunctionCallOutputBody::Text(format!(
+                "Loaded {url}\nTitle: {title}",
+                url = page.url,
+                title = page.title
+            ))
+        }
+        BrowserArgs::Text {
+            selector,
+            max_bytes,
+        } => {
+            let text = browser.text(selector.as_deref()).await?;
+            let max_bytes = max_bytes.unwrap_or(DEFAULT_MAX_TEXT_BYTES);
+            let body = if text.len() > max_bytes {
+                format!(
+                    "{}\n[page text truncated at {max_bytes} bytes]",
+                    take_bytes_at_char_boundary(&text, max_bytes)
+                )
+            } else {
+                text
+            };
+            FunctionCallOutputBody::Text(body)
+        }
+        BrowserArgs::Screenshot => {
+            let data = browser.screenshot().await?;
+            FunctionCallOutputBody::ContentItems(vec![FunctionCallOutputContentItem::InputImage {
+                image_url: format!("data:image/png;base64,{data}"),
+            }])
+        }
+        BrowserArgs::Click { selector } => {
+            browser.click(&selector).await?;
+            FunctionCallOutputBody::Text(format!("Clicked `{selector}`"))
+        }
+        BrowserArgs::Type { selector, text } => {
+            browser.type_text(&selector, &text).await?;
+            FunctionCallOutputBody::Text(format!("Typed into `{selector}`"))
+        }
+    };
+    Ok(body)
+}
+
+/// Page requests that CDP interception cannot see (WebSockets, service
+/// workers) are only contained by the proxy, so a restricted browser needs one.
+fn browser_network(turn: &TurnContext) -> Result<BrowserNetwork, FunctionCallError> {
+    if turn.sandbox_policy.has_full_network_access() {
+        return Ok(BrowserNetwork::Full);
+    }
+    let proxy = turn.network.as_ref().map(NetworkProxy::http_addr);
+    restricted_network(proxy)
+}
+
+fn restricted_network(proxy: Option<SocketAddr>) -> Result<BrowserNetwork, FunctionCallError> {
+    proxy
+        .map(|proxy| BrowserNetwork::Restricted { proxy })
+        .ok_or_else(|| {
+            FunctionCallError::RespondToModel(
+                "the browser is unavailable: network access is restricted and the network proxy is not running".to_string(),
+            )
+        })
+}
+
+fn blocked_hosts_note(blocked_hosts: &[String]) -> String {
+    if blocked_hosts.is_empty() {
+        return String::new();
+    }
+    format!(
+        "\nBlocked requests to hosts that are not approved: {}. Navigate to a url on one of them to request access.",
+        blocked_hosts.join(", ")
+    )
+}
+
+/// Applies the session's network policy to a navigation: full network access
+/// passes through, otherwise each new host requires user approval (and is
+/// refused outright when approvals are disabled). Returns the url's host.
+async fn ensure_navigation_allowed(
+    session: &Session,
+    turn: &TurnContext,
+    call_id: &str,
+    url: &str,
+) -> Result<String, FunctionCallError> {
+    let parsed = url::Url::parse(url)
+        .map_err(|err| FunctionCallError::RespondToModel(format!("invalid url `{url}`: {err}")))?;
+    let protocol = match parsed.scheme() {
+        "http" => NetworkApprovalProtocol::Http,
+        "https" => NetworkApprovalProtocol::Https,
+        scheme => {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "browser only supports http and https urls; got `{scheme}`"
+            )));
+        }
+    };
+    let Some(host) = parsed.host_str().map(str::to_string) else {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "url `{url}` has no host"
+        )));
+    };
+
+    if turn.sandbox_policy.has_full_network_access()
+        || session.services.browser.is_host_approved(&host).await
+    {
+        return Ok(host);
+    }
+    if turn.approval_policy == AskForApproval::Never {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "network access to \"{host}\" is blocked by the sandbox policy"
+        )));
+    }
+
+    let decision = session
+        .request_command_approval(
+            turn,
+            call_id.to_string(),
+            "browser",
+            vec![
+                "browser".to_string(),
+                "navigate".to_string(),
+                url.to_string(),
+            ],
+            turn.cwd.clone(),
+            Some(format!("The browser wants to load \"{host}\".")),
+            Some(NetworkApprovalContext {
+                host: host.clone(),
+                protocol,
+            }),
+            None,
+        )
+        .await;
+    match decision {
+        ReviewDecision::Approved | ReviewDecision::ApprovedExecpolicyAmendment { .. } => Ok(host),
+        ReviewDecision::ApprovedForSession => {
+            session.services.browser.approve_host(host.clone()).await;
+            Ok(host)
+        }
+        ReviewDecision::Denied | ReviewDecision::Abort => Err(FunctionCallError::RespondToModel(
+            "navigation rejected by user".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_tagged_actions() {
+        let navigate: BrowserArgs =
+            serde_json::from_str(r#"{"action":"navigate","url":"https://example.com"}"#)
+                .expect("parse navigate");
+        let text: BrowserArgs = serde_json::from_str(r#"{"action":"text"}"#).expect("parse text");
+        assert_eq!(
+            (navigate, text),
+            (
+                BrowserArgs::Navigate {
+                    url: "https://example.com".to_string()
+                },
+                BrowserArgs::Text {
+                    selector: None,
+                    max_bytes: None
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn restricted_network_requires_the_proxy() {
+        let proxy: SocketAddr = "127.0.0.1:3128".parse().expect("socket addr");
+        assert_eq!(
+            restricted_network(Some(proxy)),
+            Ok(BrowserNetwork::Restricted { proxy })
+        );
+        assert!(matches!(
+            restricted_network(None),
+            Err(FunctionCallError::RespondToModel(_))
+        ));
+    }
+}
@@ -821,6 +821,7 @@ fn build_agent_shared_config(
     config.permissions.shell_environment_policy = turn.shell_environment_policy.clone();
     config.codex_linux_sandbox_exe = turn.codex_linux_sandbox_exe.clone();
     config.cwd = turn.cwd.clone();
+    config.scope = turn.scope.clone();
     config.permissions.approval_policy = Constrained::allow_only(AskForApproval::Never);
     config
         .permissions
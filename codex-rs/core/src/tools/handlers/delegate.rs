@@ -0,0 +1,604 @@
+//! The `delegate` tool runs independent subtasks in bounded sub-sessions.
+//!
+//! Each task gets its own model, sandbox, and token budget. Sub-sessions run in
+//! parallel, route approvals through the parent session, and forward their tool
+//! activity into the parent's event stream. Each task's final message comes
+//! back to the parent model as structured output.
+
+use std::sync::Arc;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::codex_delegate::run_codex_thread_one_shot;
+use crate::config::Config;
+use crate::config::Constrained;
+use crate::config::types::AgentRoleConfig;
+use crate::config::types::TurnRouting;
+use crate::features::Feature;
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::protocol::AgentStatus;
+use codex_protocol::protocol::DelegateBeginEvent;
+use codex_protocol::protocol::DelegateEndEvent;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::InitialHistory;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_protocol::protocol::SubAgentSource;
+use codex_protocol::user_input::UserInput;
+use futures::future::join_all;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio_util::sync::CancellationToken;
+
+pub struct DelegateHandler;
+
+/// Maximum number of tasks a single `delegate` call may run.
+pub(crate) const MAX_DELEGATE_TASKS: usize = 4;
+/// Token budget applied to a task that does not set `max_tokens`.
+pub(crate) const DEFAULT_DELEGATE_TOKEN_BUDGET: i64 = 200_000;
+/// Session source label recorded for delegated sub-sessions.
+const DELEGATE_SOURCE: &str = "delegate";
+
+#[derive(Debug, Deserialize)]
+struct DelegateArgs {
+    tasks: Vec<DelegateTaskArgs>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DelegateTaskArgs {
+    prompt: String,
+    model: Option<String>,
+    sandbox: Option<DelegateSandbox>,
+    max_tokens: Option<i64>,
+    role: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum DelegateSandbox {
+    ReadOnly,
+    WorkspaceWrite,
+}
+
+#[derive(Debug, Serialize)]
+struct DelegateResult {
+    results: Vec<DelegateTaskResult>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct DelegateTaskResult {
+    pub(crate) task_index: u32,
+    pub(crate) status: AgentStatus,
+    pub(crate) total_tokens: i64,
+}
+
+/// A validated task with the configuration its sub-session will run with.
+pub(crate) struct DelegateTask {
+    pub(crate) index: u32,
+    pub(crate) prompt: String,
+    pub(crate) model: String,
+    pub(crate) role: Option<String>,
+    pub(crate) token_budget: i64,
+    pub(crate) config: Config,
+    /// History the sub-session starts from; `None` starts with a fresh context.
+    pub(crate) history: Option<InitialHistory>,
+}
+
+#[async_trait]
+impl ToolHandler for DelegateHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    fn matches_kind(&self, payload: &ToolPayload) -> bool {
+        matches!(payload, ToolPayload::Function { .. })
+    }
+
+    async fn is_mutating(&self, _invocation: &ToolInvocation) -> bool {
+        // Sub-sessions may run commands and edit files.
+        true
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            call_id,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "delegate handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+        let args: DelegateArgs = parse_arguments(&arguments)?;
+        let tasks = prepare_tasks(turn.as_ref(), args.tasks)?;
+
+        // Dropping the handler (e.g. when the turn is interrupted) stops every sub-session.
+        let cancel = CancellationToken::new();
+        let _cancel_on_drop = cancel.clone().drop_guard();
+        let results = join_all(tasks.into_iter().map(|task| {
+            run_task(
+                Arc::clone(&session),
+                Arc::clone(&turn),
+                call_id.clone(),
+                task,
+                cancel.child_token(),
+            )
+        }))
+        .await;
+
+        let success = results
+            .iter()
+            .all(|result| matches!(result.status, AgentStatus::Completed(_)));
+        let content = serde_json::to_string(&DelegateResult { results }).map_err(|err| {
+            FunctionCallError::Fatal(format!("failed to serialize delegate result: {err}"))
+        })?;
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(content),
+            success: Some(success),
+        })
+    }
+}
+
+fn prepare_tasks(
+    turn: &TurnContext,
+    tasks: Vec<DelegateTaskArgs>,
+) -> Result<Vec<DelegateTask>, FunctionCallError> {
+    if tasks.is_empty() {
+        return Err(FunctionCallError::RespondToModel(
+            "Provide at least one task".to_string(),
+        ));
+    }
+    if tasks.len() > MAX_DELEGATE_TASKS {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "At most {MAX_DELEGATE_TASKS} tasks can be delegated at once"
+        )));
+    }
+
+    tasks
+        .into_iter()
+        .zip(0u32..)
+        .map(|(task, index)| {
+            if task.prompt.trim().is_empty() {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "Task {index} has an empty prompt"
+                )));
+            }
+            let role = match task.role.as_deref() {
+                Some(name) => Some(
+                    turn.tools_config
+                        .delegate_roles
+                        .iter()
+                        .find(|role| role.name == name)
+                        .ok_or_else(|| {
+                            FunctionCallError::RespondToModel(format!(
+                                "Task {index} uses unknown agent role `{name}`"
+                            ))
+                        })?,
+                ),
+                None => None,
+            };
+            let token_budget = task
+                .max_tokens
+                .or_else(|| role.and_then(|role| role.max_tokens))
+                .unwrap_or(DEFAULT_DELEGATE_TOKEN_BUDGET);
+            if token_budget <= 0 {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "Task {index} must have a positive max_tokens"
+                )));
+            }
+            let model = task
+                .model
+                .filter(|model| !model.trim().is_empty())
+                .or_else(|| role.and_then(|role| role.model.clone()))
+                .unwrap_or_else(|| turn.model_info.slug.clone());
+            let sandbox_policy = resolve_sandbox(&turn.sandbox_policy, task.sandbox)?;
+            let config = build_delegate_config(turn, &model, sandbox_policy, role)?;
+            Ok(DelegateTask {
+                index,
+                prompt: task.prompt,
+                model,
+                role: role.map(|role| role.name.clone()),
+                token_budget,
+                config,
+                history: None,
+            })
+        })
+        .collect()
+}
+
+/// Sub-sessions may narrow the parent's sandbox but never widen it.
+fn resolve_sandbox(
+    parent: &SandboxPolicy,
+    requested: Option<DelegateSandbox>,
+) -> Result<SandboxPolicy, FunctionCallError> {
+    match requested {
+        None => Ok(parent.clone()),
+        Some(DelegateSandbox::ReadOnly) => Ok(SandboxPolicy::new_read_only_policy()),
+        Some(DelegateSandbox::WorkspaceWrite) => match parent {
+            SandboxPolicy::ReadOnly { .. } => Err(FunctionCallError::RespondToModel(
+                "workspace-write sandbox is not allowed from a read-only session".to_string(),
+            )),
+            SandboxPolicy::WorkspaceWrite { .. } => Ok(parent.clone()),
+            SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => {
+                Ok(SandboxPolicy::new_workspace_write_policy())
+            }
+        },
+    }
+}
+
+/// Roles `delegate` tasks may run as: under `lead` routing, every role of the
+/// orchestration other than the one this session runs as.
+pub(crate) fn delegate_roles(config: &Config) -> Vec<AgentRoleConfig> {
+    let Some(orchestration) = config
+        .orchestration
+        .as_ref()
+        .filter(|orchestration| orchestration.routing == TurnRouting::Lead)
+    else {
+        return Vec::new();
+    };
+    let own_role = config.agent_role.as_ref().map(|role| role.name.as_str());
+    orchestration
+        .roles
+        .iter()
+        .filter(|role| Some(role.name.as_str()) != own_role)
+        .cloned()
+        .collect()
+}
+
+/// Builds a sub-session config from the parent turn, optionally running as a configured role.
+pub(crate) fn build_delegate_config(
+    turn: &TurnContext,
+    model: &str,
+    sandbox_policy: SandboxPolicy,
+    role: Option<&AgentRoleConfig>,
+) -> Result<Config, FunctionCallError> {
+    let mut config = (*turn.config).clone();
+    if model == turn.model_info.slug {
+        config.model_reasoning_effort = turn.reasoning_effort;
+    }
+    config.model = Some(model.to_string());
+    config.model_provider = turn.provider.clone();
+    config.model_reasoning_summary = turn.reasoning_summary;
+    config.developer_instructions = turn.developer_instructions.clone();
+    config.compact_prompt = turn.compact_prompt.clone();
+    config.permissions.shell_environment_policy = turn.shell_environment_policy.clone();
+    config.codex_linux_sandbox_exe = turn.codex_linux_sandbox_exe.clone();
+    config.cwd = turn.cwd.clone();
+    config.scope = turn.scope.clone();
+    // Approval requests are routed to the parent session, so the parent's policy still applies.
+    config.permissions.approval_policy = Constrained::allow_only(turn.approval_policy);
+    config
+        .permissions
+        .sandbox_policy
+        .set(sandbox_policy)
+        .map_err(|err| {
+            FunctionCallError::RespondToModel(format!("sandbox_policy is invalid: {err}"))
+        })?;
+    if let Some(role) = role {
+        if let Some(reasoning_effort) = role.reasoning_effort {
+            config.model_reasoning_effort = Some(reasoning_effort);
+        }
+        if role.read_only {
+            config
+                .permissions
+                .sandbox_policy
+                .set(SandboxPolicy::new_read_only_policy())
+                .map_err(|err| {
+                    FunctionCallError::RespondToModel(format!("sandbox_policy is invalid: {err}"))
+                })?;
+        }
+        config.agent_role = Some(role.clone());
+    }
+    // Sub-sessions are leaves: they cannot delegate or spawn agents of their own.
+    config.features.disable(Feature::Delegate);
+    config.features.disable(Feature::Collab);
+    Ok(config)
+}
+
+/// Runs one task in a sub-session, bracketed by `DelegateBegin`/`DelegateEnd` events.
+pub(crate) async fn run_task(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    call_id: String,
+    task: DelegateTask,
+    cancel: CancellationToken,
+) -> DelegateTaskResult {
+    let DelegateTask {
+        index,
+        prompt,
+        model,
+        role,
+        token_budget,
+        config,
+        history,
+    } = task;
+    session
+        .send_event(
+            &turn,
+            DelegateBeginEvent {
+                call_id: call_id.clone(),
+                task_index: index,
+                prompt: prompt.clone(),
+                model: model.clone(),
+                role: role.clone(),
+            }
+            .into(),
+        )
+        .await;
+
+    let input = vec![UserInput::Text {
+        text: prompt.clone(),
+        text_elements: Vec::new(),
+    }];
+    let (status, total_tokens) = match run_codex_thread_one_shot(
+        config,
+        Arc::clone(&session.services.auth_manager),
+        Arc::clone(&session.services.models_manager),
+        input,
+        Arc::clone(&session),
+        Arc::clone(&turn),
+        cancel.clone(),
+        history,
+        SubAgentSource::Other(DELEGATE_SOURCE.to_string()),
+    )
+    .await
+    {
+        Ok(io) => {
+            let mut last_error = None;
+            let status = loop {
+                let Ok(event) = io.next_event().await else {
+                    break AgentStatus::Errored(last_error.unwrap_or_else(|| {
+                        "sub-session ended before completing its turn".to_string()
+                    }));
+                };
+                match event.msg {
+                    EventMsg::TurnComplete(complete) => {
+                        break AgentStatus::Completed(complete.last_agent_message);
+                    }
+                    EventMsg::TurnAborted(aborted) => {
+                        break AgentStatus::Errored(format!("{:?}", aborted.reason));
+                    }
+                    EventMsg::Error(error) => last_error = Some(error.message),
+                    msg if forwards_to_parent(&msg) => session.send_event(&turn, msg).await,
+                    _ => {}
+                }
+                if io.session.get_total_token_usage().await > token_budget {
+                    break AgentStatus::Errored(format!("token budget of {token_budget} exceeded"));
+                }
+            };
+            let total_tokens = io.session.get_total_token_usage().await;
+            (status, total_tokens)
+        }
+        Err(err) => (
+            AgentStatus::Errored(format!("failed to start sub-session: {err}")),
+            0,
+        ),
+    };
+    // Stops the sub-session if it is still running (e.g. after exceeding its budget).
+    cancel.cancel();
+
+    session
+        .send_event(
+            &turn,
+            DelegateEndEvent {
+                call_id,
+                task_index: index,
+                prompt,
+                model,
+                role,
+                status: status.clone(),
+                total_tokens,
+            }
+            .into(),
+        )
+        .await;
+    DelegateTaskResult {
+        task_index: index,
+        status,
+        total_tokens,
+    }
+}
+
+/// Child tool activity is surfaced to the parent as-is; its messages, reasoning, and turn
+/// lifecycle stay private so they do not read as the parent's own output.
+fn forwards_to_parent(msg: &EventMsg) -> bool {
+    matches!(
+        msg,
+        EventMsg::ExecCommandBegin(_)
+            | EventMsg::ExecCommandOutputDelta(_)
+            | EventMsg::ExecCommandEnd(_)
+            | EventMsg::PatchApplyBegin(_)
+            | EventMsg::PatchApplyEnd(_)
+            | EventMsg::McpToolCallBegin(_)
+            | EventMsg::McpToolCallEnd(_)
+            | EventMsg::WebSearchBegin(_)
+            | EventMsg::WebSearchEnd(_)
+            | EventMsg::Warning(_)
+            | EventMsg::StreamError(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codex::make_session_and_context;
+    use crate::turn_diff_tracker::TurnDiffTracker;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tokio::sync::Mutex;
+
+    fn invocation(session: Session, turn: TurnContext, args: serde_json::Value) -> ToolInvocation {
+        ToolInvocation {
+            session: Arc::new(session),
+            turn: Arc::new(turn),
+            tracker: Arc::new(Mutex::new(TurnDiffTracker::default())),
+            call_id: "call-1".to_string(),
+            tool_name: "delegate".to_string(),
+            payload: ToolPayload::Function {
+                arguments: args.to_string(),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_empty_and_oversized_task_lists() {
+        let (session, turn) = make_session_and_context().await;
+        let Err(err) = DelegateHandler
+            .handle(invocation(session, turn, json!({"tasks": []})))
+            .await
+        else {
+            panic!("empty task list should be rejected");
+        };
+        assert_eq!(
+            err,
+            FunctionCallError::RespondToModel("Provide at least one task".to_string())
+        );
+
+        let (session, turn) = make_session_and_context().await;
+        let tasks: Vec<_> = (0..=MAX_DELEGATE_TASKS)
+            .map(|i| json!({"prompt": format!("task {i}")}))
+            .collect();
+        let Err(err) = DelegateHandler
+            .handle(invocation(session, turn, json!({ "tasks": tasks })))
+            .await
+        else {
+            panic!("too many tasks should be rejected");
+        };
+        assert_eq!(
+            err,
+            FunctionCallError::RespondToModel(format!(
+                "At most {MAX_DELEGATE_TASKS} tasks can be delegated at once"
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn prepare_tasks_applies_overrides_and_disables_nesting() {
+        let (_session, turn) = make_session_and_context().await;
+        let tasks = prepare_tasks(
+            &turn,
+            vec![
+                DelegateTaskArgs {
+                    prompt: "look around".to_string(),
+                    model: Some("gpt-5.1-codex-mini".to_string()),
+                    sandbox: Some(DelegateSandbox::ReadOnly),
+                    max_tokens: Some(1_000),
+                    role: None,
+                },
+                DelegateTaskArgs {
+                    prompt: "do it".to_string(),
+                    model: None,
+                    sandbox: None,
+                    max_tokens: None,
+                    role: None,
+                },
+            ],
+        )
+        .expect("tasks should be valid");
+
+        assert_eq!(tasks[0].index, 0);
+        assert_eq!(tasks[0].model, "gpt-5.1-codex-mini");
+        assert_eq!(tasks[0].token_budget, 1_000);
+        assert_eq!(
+            tasks[0].config.permissions.sandbox_policy.get(),
+            &SandboxPolicy::new_read_only_policy()
+        );
+        assert_eq!(tasks[1].model, turn.model_info.slug);
+        assert_eq!(tasks[1].token_budget, DEFAULT_DELEGATE_TOKEN_BUDGET);
+        assert_eq!(
+            tasks[1].config.permissions.sandbox_policy.get(),
+            &turn.sandbox_policy
+        );
+        for task in &tasks {
+            assert!(!task.config.features.enabled(Feature::Delegate));
+            assert!(!task.config.features.enabled(Feature::Collab));
+        }
+    }
+
+    #[tokio::test]
+    async fn prepare_tasks_runs_configured_roles() {
+        let (_session, mut turn) = make_session_and_context().await;
+        turn.tools_config.delegate_roles = vec![AgentRoleConfig {
+            name: "reviewer".to_string(),
+            description: Some("Reviews changes".to_string()),
+            model: Some("gpt-5.1-codex-mini".to_string()),
+            reasoning_effort: None,
+            instructions: Some("Only report problems.".to_string()),
+            tools: Some(vec!["read_file".to_string()]),
+            read_only: true,
+            max_tokens: Some(5_000),
+        }];
+        let task = |role: &str| DelegateTaskArgs {
+            prompt: "review the diff".to_string(),
+            model: None,
+            sandbox: None,
+            max_tokens: None,
+            role: Some(role.to_string()),
+        };
+
+        let tasks = prepare_tasks(&turn, vec![task("reviewer")]).expect("role should resolve");
+        assert_eq!(tasks[0].role.as_deref(), Some("reviewer"));
+        assert_eq!(tasks[0].model, "gpt-5.1-codex-mini");
+        assert_eq!(tasks[0].token_budget, 5_000);
+        assert_eq!(
+            tasks[0].config.permissions.sandbox_policy.get(),
+            &SandboxPolicy::new_read_only_policy()
+        );
+        assert_eq!(
+            tasks[0].config.agent_role.as_ref(),
+            turn.tools_config.delegate_roles.first()
+        );
+
+        let Err(err) = prepare_tasks(&turn, vec![task("tester")]) else {
+            panic!("unknown roles should be rejected");
+        };
+        assert_eq!(
+            err,
+            FunctionCallError::RespondToModel(
+                "Task 0 uses unknown agent role `tester`".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn sandbox_can_narrow_but_not_widen() {
+        let read_only = SandboxPolicy::new_read_only_policy();
+        let Err(err) = resolve_sandbox(&read_only, Some(DelegateSandbox::WorkspaceWrite)) else {
+            panic!("widening should be rejected");
+        };
+        assert_eq!(
+            err,
+            FunctionCallError::RespondToModel(
+                "workspace-write sandbox is not allowed from a read-only session".to_string()
+            )
+        );
+        assert_eq!(
+            resolve_sandbox(
+                &SandboxPolicy::DangerFullAccess,
+                Some(DelegateSandbox::WorkspaceWrite)
+            ),
+            Ok(SandboxPolicy::new_workspace_write_policy())
+        );
+        assert_eq!(
+            resolve_sandbox(
+                &SandboxPolicy::new_workspace_write_policy(),
+                Some(DelegateSandbox::ReadOnly)
+            ),
+            Ok(read_only)
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::truncate::formatted_truncate_text;
+
+pub struct FetchOutputHandler;
+
+fn default_offset() -> usize {
+    1
+}
+
+fn default_limit() -> usize {
+    200
+}
+
+#[derive(Deserialize)]
+struct FetchOutputArgs {
+    call_id: String,
+    #[serde(default = "default_offset")]
+    offset: usize,
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+#[async_trait]
+impl ToolHandler for FetchOutputHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "fetch_output handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: FetchOutputArgs = parse_arguments(&arguments)?;
+        if args.offset == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "offset must be a 1-indexed line number".to_string(),
+            ));
+        }
+        if args.limit == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "limit must be greater than zero".to_string(),
+            ));
+        }
+
+        let Some(output) = session
+            .services
+            .full_outputs
+            .lock()
+            .await
+            .get(&args.call_id)
+        else {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "no summarized output for call_id `{}`; only summarized outputs from this session can be fetched",
+                args.call_id
+            )));
+        };
+
+        let text = format_lines(&output, args.offset, args.limit)
+            .map_err(FunctionCallError::RespondToModel)?;
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(formatted_truncate_text(
+                &text,
+                turn.truncation_policy,
+            )),
+            success: Some(true),
+        })
+    }
+}
+
+fn format_lines(output: &str, offset: usize, limit: usize) -> Result<String, String> {
+    let total = output.lines().count();
+    if offset > total {
+        return Err(format!(
+            "offset {offset} is past the end of the output ({total} lines)"
+        ));
+    }
+    let end = total.min(offset.saturating_add(limit) - 1);
+    let mut text = format!("Lines {offset}-{end} of {total}:\n");
+    for (index, line) in output.lines().enumerate().skip(offset - 1).take(limit) {
+        text.push_str(&format!("L{}: {line}\n", index + 1));
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn formats_the_requested_range() {
+        assert_eq!(
+            format_lines("a\nb\nc\nd\n", 2, 2),
+            Ok("Lines 2-3 of 4:\nL2: b\nL3: c\n".to_string())
+        );
+        assert_eq!(
+            format_lines("a\nb\n", 2, 100),
+            Ok("Lines 2-2 of 2:\nL2: b\n".to_string())
+        );
+        assert_eq!(
+            format_lines("a\n", 5, 1),
+            Err("offset 5 is past the end of the output (1 lines)".to_string())
+        );
+    }
+}
@@ -0,0 +1,632 @@
+use std::path::Path;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_utils_string::take_bytes_at_char_boundary;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::function_tool::FunctionCallError;
+use crate::git_info::run_hardened_git;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::tools::sandboxing::with_cached_approval;
+
+pub struct GitHandler;
+
+const GIT_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_LOG_COUNT: usize = 20;
+const MAX_LOG_COUNT: usize = 200;
+const MAX_PATCH_BYTES: usize = 64 * 1024;
+const FIELD_SEPARATOR: char = '\u{1f}';
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+enum GitArgs {
+    Status,
+    Diff {
+        #[serde(default)]
+        staged: bool,
+        #[serde(default)]
+        paths: Vec<String>,
+    },
+    Log {
+        #[serde(default)]
+        max_count: Option<usize>,
+        #[serde(default)]
+        path: Option<String>,
+    },
+    Blame {
+        path: String,
+        #[serde(default)]
+        start_line: Option<usize>,
+        #[serde(default)]
+        end_line: Option<usize>,
+    },
+    Stash {
+        stash_action: StashAction,
+        #[serde(default)]
+        message: Option<String>,
+    },
+    Commit {
+        message: String,
+        #[serde(default)]
+        paths: Vec<String>,
+    },
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum StashAction {
+    List,
+    Push,
+    Pop,
+}
+
+impl GitArgs {
+    /// Mutating operations require approval; everything else only reads the
+    /// repository.
+    fn mutation_name(&self) -> Option<&'static str> {
+        match self {
+            GitArgs::Stash {
+                stash_action: StashAction::Push,
+                ..
+            } => Some("stash push"),
+            GitArgs::Stash {
+                stash_action: StashAction::Pop,
+                ..
+            } => Some("stash pop"),
+            GitArgs::Commit { .. } => Some("commit"),
+            GitArgs::Status
+            | GitArgs::Diff { .. }
+            | GitArgs::Log { .. }
+            | GitArgs::Blame { .. }
+            | GitArgs::Stash {
+                stash_action: StashAction::List,
+                ..
+            } => None,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Default, PartialEq, Eq)]
+struct StatusOutput {
+    branch: Option<String>,
+    upstream: Option<String>,
+    ahead: u32,
+    behind: u32,
+    entries: Vec<StatusEntry>,
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+struct StatusEntry {
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    original_path: Option<String>,
+    /// Single-letter porcelain code for the index (staged) side.
+    index: String,
+    /// Single-letter porcelain code for the working tree side.
+    worktree: String,
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+struct DiffFile {
+    path: String,
+    /// `None` for binary files.
+    additions: Option<u64>,
+    deletions: Option<u64>,
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+struct DiffOutput {
+    files: Vec<DiffFile>,
+    patch: String,
+    truncated: bool,
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+struct LogEntry {
+    sha: String,
+    author: String,
+    timestamp: i64,
+    subject: String,
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+struct BlameLine {
+    line: usize,
+    sha: String,
+    author: String,
+    summary: String,
+    content: String,
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+struct StashEntry {
+    name: String,
+    subject: String,
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+struct CommitOutput {
+    sha: String,
+    subject: String,
+}
+
+#[async_trait]
+impl ToolHandler for GitHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn is_mutating(&self, invocation: &ToolInvocation) -> bool {
+        match &invocation.payload {
+            ToolPayload::Function { arguments } => parse_arguments::<GitArgs>(arguments)
+                .map_or(true, |args| args.mutation_name().is_some()),
+            _ => true,
+        }
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            call_id,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "git handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+        let args: GitArgs = parse_arguments(&arguments)?;
+        let cwd = turn.cwd.as_path();
+
+        if let Some(operation) = args.mutation_name() {
+            ensure_mutation_approved(session.as_ref(), turn.as_ref(), &call_id, operation, &args)
+                .await?;
+        }
+
+        let json = match args {
+            GitArgs::Status => {
+                let stdout = run_git(cwd, &["status", "--porcelain=v2", "--branch", "-z"]).await?;
+                to_json(&parse_status(&stdout))
+            }
+            GitArgs::Diff { staged, paths } => {
+                let mut base = vec![
+                    "diff".to_string(),
+                    "--no-color".to_string(),
+                    "--no-ext-diff".to_string(),
+                    "--no-textconv".to_string(),
+                ];
+                if staged {
+                    base.push("--cached".to_string());
+                }
+                let with_paths = |extra: &str| {
+                    let mut args = base.clone();
+                    args.push(extra.to_string());
+                    args.push("--".to_string());
+                    args.extend(paths.iter().cloned());
+                    args
+                };
+                let numstat = run_git(cwd, &with_paths("--numstat")).await?;
+                let patch = run_git(cwd, &with_paths("--patch")).await?;
+                let truncated = patch.len() > MAX_PATCH_BYTES;
+                to_json(&DiffOutput {
+                    files: parse_numstat(&numstat),
+                    patch: take_bytes_at_char_boundary(&patch, MAX_PATCH_BYTES).to_string(),
+                    truncated,
+                })
+            }
+            GitArgs::Log { max_count, path } => {
+                let count = max_count
+                    .unwrap_or(DEFAULT_LOG_COUNT)
+                    .clamp(1, MAX_LOG_COUNT);
+                let mut args = vec![
+                    "log".to_string(),
+                    format!("--max-count={count}"),
+                    "--pretty=format:%H%x1f%an%x1f%ct%x1f%s".to_string(),
+                ];
+                if let Some(path) = path {
+                    args.push("--".to_string());
+                    args.push(path);
+                }
+                let stdout = run_git(cwd, &args).await?;
+                to_json(&parse_log(&stdout))
+            }
+            GitArgs::Blame {
+                path,
+                start_line,
+                end_line,
+            } => {
+                let mut args = vec!["blame".to_string(), "--line-porcelain".to_string()];
+                if let Some(start) = start_line {
+                    let range = match end_line {
+                        Some(end) => format!("{start},{end}"),
+                        None => format!("{start},"),
+                    };
+                    args.push("-L".to_string());
+                    args.push(range);
+                }
+                args.push("--".to_string());
+                args.push(path);
+                let stdout = run_git(cwd, &args).await?;
+                to_json(&parse_blame(&stdout))
+            }
+            GitArgs::Stash {
+                stash_action,
+                message,
+            } => match stash_action {
+                StashAction::List => {
+                    let stdout = run_git(cwd, &["stash", "list", "--format=%gd%x1f%s"]).await?;
+                    to_json(&parse_stash_list(&stdout))
+                }
+                StashAction::Push => {
+                    let mut args = vec!["stash".to_string(), "push".to_string()];
+                    if let Some(message) = message {
+                        args.push("--message".to_string());
+                        args.push(message);
+                    }
+                    let stdout = run_git(cwd, &args).await?;
+                    to_json(&serde_json::json!({ "output": stdout.trim() }))
+                }
+                StashAction::Pop => {
+                    let stdout = run_git(cwd, &["stash", "pop"]).await?;
+                    to_json(&serde_json::json!({ "output": stdout.trim() }))
+                }
+            },
+            GitArgs::Commit { message, paths } => {
+                if !paths.is_empty() {
+                    let mut args = vec!["add".to_string(), "--".to_string()];
+                    args.extend(paths);
+                    run_git(cwd, &args).await?;
+                }
+                run_git(
+                    cwd,
+                    &["commit", "--no-verify", "--message", message.as_str()],
+                )
+                .await?;
+                let stdout = run_git(cwd, &["log", "-1", "--pretty=format:%H%x1f%s"]).await?;
+                let (sha, subject) = stdout.split_once(FIELD_SEPARATOR).unwrap_or((&stdout, ""));
+                to_json(&CommitOutput {
+                    sha: sha.to_string(),
+                    subject: subject.to_string(),
+                })
+            }
+        }?;
+
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(json),
+            success: Some(true),
+        })
+    }
+}
+
+/// Mutating operations run without prompting only when the session already
+/// runs unsandboxed; otherwise they go through the regular command approval
+/// flow (and are refused when approvals are disabled).
+async fn ensure_mutation_approved(
+    session: &Session,
+    turn: &TurnContext,
+    call_id: &str,
+    operation: &'static str,
+    args: &GitArgs,
+) -> Result<(), FunctionCallError> {
+    if matches!(
+        turn.sandbox_policy,
+        SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. }
+    ) {
+        return Ok(());
+    }
+    if turn.approval_policy == AskForApproval::Never {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "git {operation} requires approval, but approvals are disabled"
+        )));
+    }
+
+    let mut command = vec!["git".to_string()];
+    command.extend(operation.split(' ').map(str::to_string));
+    match args {
+        GitArgs::Commit { message, paths } => {
+            command.push("--message".to_string());
+            command.push(message.clone());
+            command.extend(paths.iter().cloned());
+        }
+        GitArgs::Stash {
+            message: Some(message),
+            ..
+        } => {
+            command.push("--message".to_string());
+            command.push(message.clone());
+        }
+        _ => {}
+    }
+    // Like shell commands, a session approval covers this exact command in
+    // this directory only, not every later commit.
+    let keys = vec![(command.clone(), turn.cwd.clone())];
+    let decision = with_cached_approval(&session.services, "git", call_id, keys, || {
+        session.request_command_approval(
+            turn,
+            call_id.to_string(),
+            "git",
+            command,
+            turn.cwd.clone(),
+            None,
+            None,
+            None,
+        )
+    })
+    .await;
+    match decision {
+        ReviewDecision::Approved
+        | ReviewDecision::ApprovedExecpolicyAmendment { .. }
+        | ReviewDecision::ApprovedForSession => Ok(()),
+        ReviewDecision::Denied | ReviewDecision::Abort => Err(FunctionCallError::RespondToModel(
+            format!("git {operation} rejected by user"),
+        )),
+    }
+}
+
+async fn run_git<S: AsRef<str>>(cwd: &Path, args: &[S]) -> Result<String, FunctionCallError> {
+    run_hardened_git(cwd, args, GIT_TIMEOUT)
+        .await
+        .map_err(FunctionCallError::RespondToModel)
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, FunctionCallError> {
+    serde_json::to_string(value).map_err(|err| {
+        FunctionCallError::RespondToModel(format!("failed to serialize git output: {err}"))
+    })
+}
+
+/// Parses `git status --porcelain=v2 --branch -z`.
+fn parse_status(stdout: &str) -> StatusOutput {
+    let mut status = StatusOutput::default();
+    let mut records = stdout.split('\0').filter(|record| !record.is_empty());
+    while let Some(record) = records.next() {
+        if let Some(header) = record.strip_prefix("# ") {
+            let (key, value) = header.split_once(' ').unwrap_or((header, ""));
+            match key {
+                "branch.head" if value != "(detached)" => status.branch = Some(value.to_string()),
+                "branch.upstream" => status.upstream = Some(value.to_string()),
+                "branch.ab" => {
+                    for part in value.split_whitespace() {
+                        if let Some(ahead) = part.strip_prefix('+') {
+                            status.ahead = ahead.parse().unwrap_or_default();
+                        } else if let Some(behind) = part.strip_prefix('-') {
+                            status.behind = behind.parse().unwrap_or_default();
+                        }
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        let fields: Vec<&str> = record.split(' ').collect();
+        let entry = match fields.first().copied() {
+            // 1 XY sub mH mI mW hH hI path
+            Some("1") if fields.len() >= 9 => Some((fields[1], fields[8..].join(" "), None)),
+            // 2 XY sub mH mI mW hH hI Xscore path, followed by the original path record.
+            Some("2") if fields.len() >= 10 => Some((
+                fields[1],
+                fields[9..].join(" "),
+                records.next().map(str::to_string),
+            )),
+            // u XY sub m1 m2 m3 mW h1 h2 h3 path
+            Some("u") if fields.len() >= 11 => Some((fields[1], fields[10..].join(" "), None)),
+            Some("?") => Some(("??", fields[1..].join(" "), None)),
+            _ => None,
+        };
+        if let Some((codes, path, original_path)) = entry {
+            let mut codes = codes.chars();
+            status.entries.push(StatusEntry {
+                path,
+                original_path,
+                index: codes.next().unwrap_or('.').to_string(),
+                worktree: codes.next().unwrap_or('.').to_string(),
+            });
+        }
+    }
+    status
+}
+
+fn parse_numstat(stdout: &str) -> Vec<DiffFile> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '\t');
+            let additions = parts.next()?;
+            let deletions = parts.next()?;
+            let path = parts.next()?;
+            Some(DiffFile {
+                path: path.to_string(),
+                additions: additions.parse().ok(),
+                deletions: deletions.parse().ok(),
+            })
+        })
+        .collect()
+}
+
+fn parse_log(stdout: &str) -> Vec<LogEntry> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(4, FIELD_SEPARATOR);
+            Some(LogEntry {
+                sha: parts.next()?.to_string(),
+                author: parts.next()?.to_string(),
+                timestamp: parts.next()?.parse().unwrap_or_default(),
+                subject: parts.next().unwrap_or_default().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Parses `git blame --line-porcelain`, which repeats the full commit header
+/// before every line.
+fn parse_blame(stdout: &str) -> Vec<BlameLine> {
+    let mut lines = Vec::new();
+    let mut current: Option<BlameLine> = None;
+    for line in stdout.lines() {
+        if let Some(content) = line.strip_prefix('\t') {
+            if let Some(mut blame) = current.take() {
+                blame.content = content.to_string();
+                lines.push(blame);
+            }
+        } else if let Some(author) = line.strip_prefix("author ") {
+            if let Some(blame) = current.as_mut() {
+                blame.author = author.to_string();
+            }
+        } else if let Some(summary) = line.strip_prefix("summary ") {
+            if let Some(blame) = current.as_mut() {
+                blame.summary = summary.to_string();
+            }
+        } else if current.is_none() {
+            // <sha> <original line> <final line> [<group size>]
+            let mut parts = line.split(' ');
+            if let (Some(sha), Some(_), Some(final_line)) =
+                (parts.next(), parts.next(), parts.next())
+                && sha.len() >= 40
+            {
+                current = Some(BlameLine {
+                    line: final_line.parse().unwrap_or_default(),
+                    sha: sha.to_string(),
+                    author: String::new(),
+                    summary: String::new(),
+                    content: String::new(),
+                });
+            }
+        }
+    }
+    lines
+}
+
+fn parse_stash_list(stdout: &str) -> Vec<StashEntry> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let (name, subject) = line.split_once(FIELD_SEPARATOR)?;
+            Some(StashEntry {
+                name: name.to_string(),
+                subject: subject.to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_porcelain_v2_status() {
+        let stdout = [
+            "# branch.oid 1234567890abcdef1234567890abcdef12345678",
+            "# branch.head main",
+            "# branch.upstream origin/main",
+            "# branch.ab +2 -1",
+            "1 .M N... 100644 100644 100644 abc abc src/lib.rs",
+            "2 R. N... 100644 100644 100644 abc abc R100 src/new name.rs",
+            "src/old.rs",
+            "? notes.txt",
+            "",
+        ]
+        .join("\0");
+
+        assert_eq!(
+            parse_status(&stdout),
+            StatusOutput {
+                branch: Some("main".to_string()),
+                upstream: Some("origin/main".to_string()),
+                ahead: 2,
+                behind: 1,
+                entries: vec![
+                    StatusEntry {
+                        path: "src/lib.rs".to_string(),
+                        original_path: None,
+                        index: ".".to_string(),
+                        worktree: "M".to_string(),
+                    },
+                    StatusEntry {
+                        path: "src/new name.rs".to_string(),
+                        original_path: Some("src/old.rs".to_string()),
+                        index: "R".to_string(),
+                        worktree: ".".to_string(),
+                    },
+                    StatusEntry {
+                        path: "notes.txt".to_string(),
+                        original_path: None,
+                        index: "?".to_string(),
+                        worktree: "?".to_string(),
+                    },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn parses_line_porcelain_blame() {
+        let sha = "a".repeat(40);
+        let stdout = format!(
+            "{sha} 1 1 1\nauthor Ada\nauthor-mail <ada@example.com>\nsummary Initial commit\nfilename src/lib.rs\n\tfn main() {{}}\n"
+        );
+        assert_eq!(
+            parse_blame(&stdout),
+            vec![BlameLine {
+                line: 1,
+                sha,
+                author: "Ada".to_string(),
+                summary: "Initial commit".to_string(),
+                content: "fn main() {}".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn classifies_mutating_operations() {
+        let parse = |json: &str| serde_json::from_str::<GitArgs>(json).expect("parse args");
+        assert_eq!(
+            [
+                r#"{"operation":"status"}"#,
+                r#"{"operation":"stash","stash_action":"list"}"#,
+                r#"{"operation":"stash","stash_action":"pop"}"#,
+                r#"{"operation":"commit","message":"m"}"#,
+            ]
+            .map(|json| parse(json).mutation_name()),
+            [None, None, Some("stash pop"), Some("commit")]
+        );
+    }
+
+    #[tokio::test]
+    async fn ignores_repository_hooks_path() {
+        let repo = tempfile::tempdir().expect("tempdir");
+        run_git(repo.path(), &["init", "--quiet"])
+            .await
+            .expect("git init");
+        run_git(repo.path(), &["config", "core.hooksPath", ".husky"])
+            .await
+            .expect("git config");
+
+        let hooks_path = run_git(repo.path(), &["config", "core.hooksPath"])
+            .await
+            .expect("read hooksPath");
+        assert_eq!(hooks_path.trim(), "/dev/null");
+    }
+}
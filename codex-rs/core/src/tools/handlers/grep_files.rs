@@ -1,5 +1,8 @@
+use codex_file_search::IgnoreRules;
 use codex_protocol::models::FunctionCallOutputBody;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use std::path::Path;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use async_trait::async_trait;
@@ -70,9 +73,19 @@ impl ToolHandler for GrepFilesHandler {
         }
 
         let limit = args.limit.min(MAX_LIMIT);
-        let search_path = turn.resolve_path(args.path.clone());
+        // Without a path, a scoped session searches its scope directories.
+        let search_paths: Vec<PathBuf> = if args.path.is_none() && !turn.scope.is_empty() {
+            turn.scope
+                .iter()
+                .map(AbsolutePathBuf::to_path_buf)
+                .collect()
+        } else {
+            vec![turn.resolve_path(args.path.clone())]
+        };
 
-        verify_path_exists(&search_path).await?;
+        for search_path in &search_paths {
+            verify_path_exists(search_path).await?;
+        }
 
         let include = args.include.as_deref().map(str::trim).and_then(|val| {
             if val.is_empty() {
@@ -83,7 +96,7 @@ impl ToolHandler for GrepFilesHandler {
         });
 
         let search_results =
-            run_rg_search(pattern, include.as_deref(), &search_path, limit, &turn.cwd).await?;
+            run_rg_search(pattern, include.as_deref(), &search_paths, limit, &turn.cwd).await?;
 
         if search_results.is_empty() {
             Ok(ToolOutput::Function {
@@ -109,7 +122,7 @@ async fn verify_path_exists(path: &Path) -> Result<(), FunctionCallError> {
 async fn run_rg_search(
     pattern: &str,
     include: Option<&str>,
-    search_path: &Path,
+    search_paths: &[PathBuf],
     limit: usize,
     cwd: &Path,
 ) -> Result<Vec<String>, FunctionCallError> {
@@ -126,7 +139,7 @@ async fn run_rg_search(
         command.arg("--glob").arg(glob);
     }
 
-    command.arg("--").arg(search_path);
+    command.arg("--").args(search_paths);
 
     let output = timeout(COMMAND_TIMEOUT, command.output())
         .await
@@ -139,8 +152,21 @@ async fn run_rg_search(
             ))
         })?;
 
+    // rg already honours the git rules; `.codexignore` files are applied here.
+    let mut rules: Vec<IgnoreRules> = search_paths
+        .iter()
+        .map(|search_path| IgnoreRules::new(cwd.join(search_path), false))
+        .collect();
+    let is_excluded = |line: &str| {
+        let path = cwd.join(line);
+        rules
+            .iter_mut()
+            .find(|rules| path.starts_with(rules.root()))
+            .is_some_and(|rules| rules.is_ignored(&path))
+    };
+
     match output.status.code() {
-        Some(0) => Ok(parse_results(&output.stdout, limit)),
+        Some(0) => Ok(parse_results(&output.stdout, limit, is_excluded)),
         Some(1) => Ok(Vec::new()),
         _ => {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -151,14 +177,18 @@ async fn run_rg_search(
     }
 }
 
-fn parse_results(stdout: &[u8], limit: usize) -> Vec<String> {
+fn parse_results(
+    stdout: &[u8],
+    limit: usize,
+    mut is_excluded: impl FnMut(&str) -> bool,
+) -> Vec<String> {
     let mut results = Vec::new();
     for line in stdout.split(|byte| *byte == b'\n') {
         if line.is_empty() {
             continue;
         }
         if let Ok(text) = std::str::from_utf8(line) {
-            if text.is_empty() {
+            if text.is_empty() || is_excluded(text) {
                 continue;
             }
             results.push(text.to_string());
@@ -179,7 +209,7 @@ mod tests {
     #[test]
     fn parses_basic_results() {
         let stdout = b"/tmp/file_a.rs\n/tmp/file_b.rs\n";
-        let parsed = parse_results(stdout, 10);
+        let parsed = parse_results(stdout, 10, |_| false);
         assert_eq!(
             parsed,
             vec!["/tmp/file_a.rs".to_string(), "/tmp/file_b.rs".to_string()]
@@ -189,7 +219,7 @@ mod tests {
     #[test]
     fn parse_truncates_after_limit() {
         let stdout = b"/tmp/file_a.rs\n/tmp/file_b.rs\n/tmp/file_c.rs\n";
-        let parsed = parse_results(stdout, 2);
+        let parsed = parse_results(stdout, 2, |_| false);
         assert_eq!(
             parsed,
             vec!["/tmp/file_a.rs".to_string(), "/tmp/file_b.rs".to_string()]
@@ -207,7 +237,7 @@ mod tests {
         std::fs::write(dir.join("match_two.txt"), "alpha delta").unwrap();
         std::fs::write(dir.join("other.txt"), "omega").unwrap();
 
-        let results = run_rg_search("alpha", None, dir, 10, dir).await?;
+        let results = run_rg_search("alpha", None, &[dir.to_path_buf()], 10, dir).await?;
         assert_eq!(results.len(), 2);
         assert!(results.iter().any(|path| path.ends_with("match_one.txt")));
         assert!(results.iter().any(|path| path.ends_with("match_two.txt")));
@@ -224,12 +254,30 @@ mod tests {
         std::fs::write(dir.join("match_one.rs"), "alpha beta gamma").unwrap();
         std::fs::write(dir.join("match_two.txt"), "alpha delta").unwrap();
 
-        let results = run_rg_search("alpha", Some("*.rs"), dir, 10, dir).await?;
+        let results = run_rg_search("alpha", Some("*.rs"), &[dir.to_path_buf()], 10, dir).await?;
         assert_eq!(results.len(), 1);
         assert!(results.iter().all(|path| path.ends_with("match_one.rs")));
         Ok(())
     }
 
+    #[tokio::test]
+    async fn run_search_skips_codexignored_files() -> anyhow::Result<()> {
+        if !rg_available() {
+            return Ok(());
+        }
+        let temp = tempdir().expect("create temp dir");
+        let dir = temp.path();
+        std::fs::create_dir(dir.join("transcripts")).unwrap();
+        std::fs::write(dir.join(".codexignore"), "transcripts/\n").unwrap();
+        std::fs::write(dir.join("notes.txt"), "alpha notes").unwrap();
+        std::fs::write(dir.join("transcripts/session.txt"), "alpha log").unwrap();
+
+        let results = run_rg_search("alpha", None, &[dir.to_path_buf()], 10, dir).await?;
+        assert_eq!(results.len(), 1);
+        assert!(results[0].ends_with("notes.txt"));
+        Ok(())
+    }
+
     #[tokio::test]
     async fn run_search_respects_limit() -> anyhow::Result<()> {
         if !rg_available() {
@@ -241,7 +289,7 @@ mod tests {
         std::fs::write(dir.join("two.txt"), "alpha two").unwrap();
         std::fs::write(dir.join("three.txt"), "alpha three").unwrap();
 
-        let results = run_rg_search("alpha", None, dir, 2, dir).await?;
+        let results = run_rg_search("alpha", None, &[dir.to_path_buf()], 2, dir).await?;
         assert_eq!(results.len(), 2);
         Ok(())
     }
@@ -255,7 +303,7 @@ mod tests {
         let dir = temp.path();
         std::fs::write(dir.join("one.txt"), "omega").unwrap();
 
-        let results = run_rg_search("alpha", None, dir, 5, dir).await?;
+        let results = run_rg_search("alpha", None, &[dir.to_path_buf()], 5, dir).await?;
         assert!(results.is_empty());
         Ok(())
     }
@@ -21,6 +21,7 @@ use crate::tools::js_repl::JS_REPL_PRAGMA_PREFIX;
 use crate::tools::js_repl::JsReplArgs;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
+use crate::turn_latency::LatencyStage;
 use codex_protocol::models::FunctionCallOutputBody;
 use codex_protocol::models::FunctionCallOutputContentItem;
 
@@ -134,9 +135,12 @@ impl ToolHandler for JsReplHandler {
         let manager = turn.js_repl.manager().await?;
         let started_at = Instant::now();
         emit_js_repl_exec_begin(session.as_ref(), turn.as_ref(), &call_id).await;
-        let result = manager
-            .execute(Arc::clone(&session), Arc::clone(&turn), tracker, args)
-            .await;
+        let result = {
+            let _round_trip = turn.latency.span(LatencyStage::SidecarRoundTrip);
+            manager
+                .execute(Arc::clone(&session), Arc::clone(&turn), tracker, args)
+                .await
+        };
         let result = match result {
             Ok(result) => result,
             Err(err) => {
@@ -0,0 +1,330 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use serde::Deserialize;
+
+use crate::exec::ExecToolCallOutput;
+use crate::exec::StreamOutput;
+use crate::function_tool::FunctionCallError;
+use crate::project_memory::Importance;
+use crate::project_memory::MemoryEntry;
+use crate::project_memory::ProjectMemory;
+use crate::protocol::FileChange;
+use crate::protocol::SandboxPolicy;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::events::ToolEmitter;
+use crate::tools::events::ToolEventCtx;
+use crate::tools::events::ToolEventFailure;
+use crate::tools::events::ToolEventStage;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub(crate) const MEMORY_TOOL_NAME: &str = "memory";
+
+/// Lines of context around each change in the diff shown for a save.
+const DIFF_CONTEXT_LINES: usize = 1;
+
+pub struct MemoryHandler;
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum MemoryAction {
+    List,
+    Read,
+    Search,
+    Write,
+    Delete,
+}
+
+#[derive(Deserialize)]
+struct MemoryArgs {
+    action: MemoryAction,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    importance: Option<Importance>,
+    #[serde(default)]
+    query: Option<String>,
+}
+
+impl MemoryArgs {
+    fn name(&self) -> Result<&str, FunctionCallError> {
+        self.name.as_deref().ok_or_else(|| {
+            FunctionCallError::RespondToModel("`name` is required for this action".to_string())
+        })
+    }
+}
+
+#[async_trait]
+impl ToolHandler for MemoryHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn is_mutating(&self, invocation: &ToolInvocation) -> bool {
+        match &invocation.payload {
+            ToolPayload::Function { arguments } => parse_arguments::<MemoryArgs>(arguments)
+                .map_or(true, |args| {
+                    matches!(args.action, MemoryAction::Write | MemoryAction::Delete)
+                }),
+            _ => true,
+        }
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            tracker,
+            call_id,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "memory handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+        let args: MemoryArgs = parse_arguments(&arguments)?;
+        let memory = ProjectMemory::for_cwd(&turn.cwd);
+
+        let text = match args.action {
+            MemoryAction::List => list_entries(&memory.entries().await),
+            MemoryAction::Read => {
+                let name = args.name()?;
+                match memory
+                    .read(name)
+                    .await
+                    .map_err(FunctionCallError::RespondToModel)?
+                {
+                    Some(entry) => entry.content,
+                    None => {
+                        return Err(FunctionCallError::RespondToModel(format!(
+                            "no memory named `{name}`"
+                        )));
+                    }
+                }
+            }
+            MemoryAction::Search => {
+                let query = args.query.as_deref().unwrap_or_default();
+                search_entries(&memory.entries().await, query)
+            }
+            MemoryAction::Write | MemoryAction::Delete => {
+                if turn.config.read_only
+                    || matches!(turn.sandbox_policy, SandboxPolicy::ReadOnly { .. })
+                {
+                    return Err(FunctionCallError::RespondToModel(
+                        "memory cannot be changed in a read-only session".to_string(),
+                    ));
+                }
+                let name = args.name()?;
+                let path = memory
+                    .path_for(name)
+                    .map_err(FunctionCallError::RespondToModel)?;
+                let existing = memory
+                    .read(name)
+                    .await
+                    .map_err(FunctionCallError::RespondToModel)?;
+                let old_text = match existing {
+                    Some(_) => tokio::fs::read_to_string(&path).await.unwrap_or_default(),
+                    None => String::new(),
+                };
+                let (change, new_text) = if args.action == MemoryAction::Delete {
+                    if existing.is_none() {
+                        return Err(FunctionCallError::RespondToModel(format!(
+                            "no memory named `{name}`"
+                        )));
+                    }
+                    (FileChange::Delete { content: old_text }, None)
+                } else {
+                    let content = args.content.as_deref().unwrap_or_default();
+                    if content.trim().is_empty() {
+                        return Err(FunctionCallError::RespondToModel(
+                            "`content` is required for `write`".to_string(),
+                        ));
+                    }
+                    // Rewriting an entry keeps its importance unless a new one is given.
+                    let importance = args
+                        .importance
+                        .or(existing.as_ref().map(|entry| entry.importance))
+                        .unwrap_or_default();
+                    let new_text = MemoryEntry::serialize(importance, content);
+                    if new_text == old_text {
+                        return Ok(ToolOutput::Function {
+                            body: FunctionCallOutputBody::Text(format!(
+                                "Memory `{name}` is unchanged."
+                            )),
+                            success: Some(true),
+                        });
+                    }
+                    let change = if existing.is_some() {
+                        FileChange::Update {
+                            unified_diff: similar::TextDiff::from_lines(&old_text, &new_text)
+                                .unified_diff()
+                                .context_radius(DIFF_CONTEXT_LINES)
+                                .to_string(),
+                            move_path: None,
+                        }
+                    } else {
+                        FileChange::Add {
+                            content: new_text.clone(),
+                        }
+                    };
+                    (change, Some(new_text))
+                };
+
+                // Saves are reported like any other file change, so the user
+                // sees exactly what was remembered and can undo it.
+                let emitter = ToolEmitter::apply_patch(
+                    HashMap::from([(path.clone(), change)]),
+                    HashMap::new(),
+                    true,
+                );
+                let event_ctx =
+                    ToolEventCtx::new(session.as_ref(), turn.as_ref(), &call_id, Some(&tracker));
+                emitter.begin(event_ctx).await;
+                let result = match &new_text {
+                    Some(new_text) => write_entry(&path, new_text).await,
+                    None => tokio::fs::remove_file(&path).await,
+                };
+                let (stage, message) = match result {
+                    Ok(()) => {
+                        let message = if new_text.is_some() {
+                            format!("Saved memory `{name}`.")
+                        } else {
+                            format!("Deleted memory `{name}`.")
+                        };
+                        let output = ExecToolCallOutput {
+                            exit_code: 0,
+                            stdout: StreamOutput::new(message.clone()),
+                            stderr: StreamOutput::new(String::new()),
+                            aggregated_output: StreamOutput::new(message.clone()),
+                            duration: Duration::ZERO,
+                            timed_out: false,
+                        };
+                        (ToolEventStage::Success(output), message)
+                    }
+                    Err(err) => {
+                        let message = format!("failed to update memory `{name}`: {err}");
+                        emitter
+                            .emit(
+                                event_ctx,
+                                ToolEventStage::Failure(ToolEventFailure::Message(message.clone())),
+                            )
+                            .await;
+                        return Err(FunctionCallError::RespondToModel(message));
+                    }
+                };
+                emitter.emit(event_ctx, stage).await;
+                message
+            }
+        };
+
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(text),
+            success: Some(true),
+        })
+    }
+}
+
+async fn write_entry(path: &std::path::Path, text: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(path, text).await
+}
+
+fn list_entries(entries: &[MemoryEntry]) -> String {
+    if entries.is_empty() {
+        return "No memories saved for this project yet.".to_string();
+    }
+    entries
+        .iter()
+        .map(|entry| {
+            let summary = entry.content.lines().next().unwrap_or_default();
+            format!(
+                "- {} ({}): {summary}",
+                entry.name,
+                entry.importance.as_str()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Entries whose name or content contains every word of `query`, ignoring
+/// case, with the lines that matched.
+fn search_entries(entries: &[MemoryEntry], query: &str) -> String {
+    let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    let mut results = Vec::new();
+    for entry in entries {
+        let haystack = format!("{}\n{}", entry.name, entry.content).to_lowercase();
+        if !words.iter().all(|word| haystack.contains(word.as_str())) {
+            continue;
+        }
+        let lines: Vec<&str> = entry
+            .content
+            .lines()
+            .filter(|line| {
+                let line = line.to_lowercase();
+                words.iter().any(|word| line.contains(word.as_str()))
+            })
+            .collect();
+        results.push(format!("{}:\n{}", entry.name, lines.join("\n")));
+    }
+    if results.is_empty() {
+        format!("No memories match `{query}`.")
+    } else {
+        results.join("\n\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::SystemTime;
+
+    fn entry(name: &str, content: &str) -> MemoryEntry {
+        MemoryEntry {
+            name: name.to_string(),
+            importance: Importance::Normal,
+            content: content.to_string(),
+            modified: SystemTime::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn search_matches_every_word_and_shows_matching_lines() {
+        let entries = vec![
+            entry(
+                "build-quirks",
+                "Integration tests need DATABASE_URL.\nRun `just db` first.",
+            ),
+            entry("naming", "Handlers end in `Handler`."),
+        ];
+        assert_eq!(
+            search_entries(&entries, "database tests"),
+            "build-quirks:\nIntegration tests need DATABASE_URL."
+        );
+        assert_eq!(
+            search_entries(&entries, "release"),
+            "No memories match `release`."
+        );
+        assert_eq!(
+            list_entries(&entries[1..]),
+            "- naming (normal): Handlers end in `Handler`."
+        );
+    }
+}
@@ -1,37 +1,55 @@
 pub mod apply_patch;
+mod browser;
 pub(crate) mod collab;
+pub(crate) mod delegate;
 mod dynamic;
+mod fetch_output;
+mod git;
 mod grep_files;
 mod js_repl;
 mod list_dir;
 mod mcp;
 mod mcp_resource;
+mod memory;
 mod plan;
+mod py_repl;
 mod read_file;
 mod request_user_input;
+mod search_content;
 mod search_tool_bm25;
 mod shell;
 mod test_sync;
 mod unified_exec;
 mod view_image;
+mod web_search;
 
 pub use plan::PLAN_TOOL;
 use serde::Deserialize;
 
 use crate::function_tool::FunctionCallError;
 pub use apply_patch::ApplyPatchHandler;
+pub use browser::BrowserHandler;
 pub use collab::CollabHandler;
+pub use delegate::DelegateHandler;
 pub use dynamic::DynamicToolHandler;
+pub use fetch_output::FetchOutputHandler;
+pub use git::GitHandler;
 pub use grep_files::GrepFilesHandler;
 pub use js_repl::JsReplHandler;
 pub use js_repl::JsReplResetHandler;
 pub use list_dir::ListDirHandler;
 pub use mcp::McpHandler;
 pub use mcp_resource::McpResourceHandler;
+pub(crate) use memory::MEMORY_TOOL_NAME;
+pub use memory::MemoryHandler;
 pub use plan::PlanHandler;
+pub(crate) use plan::resume_plan_instructions;
+pub use py_repl::PyReplHandler;
+pub use py_repl::PyReplResetHandler;
 pub use read_file::ReadFileHandler;
 pub use request_user_input::RequestUserInputHandler;
 pub(crate) use request_user_input::request_user_input_tool_description;
+pub use search_content::SearchContentHandler;
 pub(crate) use search_tool_bm25::DEFAULT_LIMIT as SEARCH_TOOL_BM25_DEFAULT_LIMIT;
 pub(crate) use search_tool_bm25::SEARCH_TOOL_BM25_TOOL_NAME;
 pub use search_tool_bm25::SearchToolBm25Handler;
@@ -40,6 +58,7 @@ pub use shell::ShellHandler;
 pub use test_sync::TestSyncHandler;
 pub use unified_exec::UnifiedExecHandler;
 pub use view_image::ViewImageHandler;
+pub use web_search::WebSearchHandler;
 
 fn parse_arguments<T>(arguments: &str) -> Result<T, FunctionCallError>
 where
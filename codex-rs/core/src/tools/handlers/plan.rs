@@ -12,6 +12,8 @@ use crate::tools::spec::JsonSchema;
 use async_trait::async_trait;
 use codex_protocol::config_types::ModeKind;
 use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::plan_tool::PlanItemArg;
+use codex_protocol::plan_tool::StepStatus;
 use codex_protocol::plan_tool::UpdatePlanArgs;
 use codex_protocol::protocol::EventMsg;
 use std::collections::BTreeMap;
@@ -109,7 +111,8 @@ pub(crate) async fn handle_update_plan(
             "update_plan is a TODO/checklist tool and is not allowed in Plan mode".to_string(),
         ));
     }
-    let args = parse_update_plan_arguments(&arguments)?;
+    let mut args = parse_update_plan_arguments(&arguments)?;
+    session.apply_plan_update(&mut args.plan).await;
     session
         .send_event(turn_context, EventMsg::PlanUpdate(args))
         .await;
@@ -121,3 +124,58 @@ fn parse_update_plan_arguments(arguments: &str) -> Result<UpdatePlanArgs, Functi
         FunctionCallError::RespondToModel(format!("failed to parse function arguments: {e}"))
     })
 }
+
+/// Developer message re-injected on resume while the restored plan still has
+/// unfinished steps, so the model picks up the checklist instead of re-planning.
+pub(crate) fn resume_plan_instructions(plan: &[PlanItemArg]) -> Option<String> {
+    if plan
+        .iter()
+        .all(|item| matches!(item.status, StepStatus::Completed))
+    {
+        return None;
+    }
+    let mut instructions = "This session was resumed with an unfinished plan. Continue from its outstanding steps instead of planning again, and keep it current with `update_plan`.\n".to_string();
+    for item in plan {
+        let status = match item.status {
+            StepStatus::Pending => "pending",
+            StepStatus::InProgress => "in_progress",
+            StepStatus::Completed => "completed",
+        };
+        instructions.push_str(&format!("\n- [{status}] {}", item.step));
+    }
+    Some(instructions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn item(step: &str, status: StepStatus) -> PlanItemArg {
+        PlanItemArg {
+            step: step.to_string(),
+            status,
+            id: None,
+        }
+    }
+
+    #[test]
+    fn resume_plan_instructions_lists_unfinished_plan() {
+        assert_eq!(resume_plan_instructions(&[]), None);
+        assert_eq!(
+            resume_plan_instructions(&[item("Read the code", StepStatus::Completed)]),
+            None
+        );
+        assert_eq!(
+            resume_plan_instructions(&[
+                item("Read the code", StepStatus::Completed),
+                item("Write the fix", StepStatus::InProgress),
+                item("Run the tests", StepStatus::Pending),
+            ]),
+            Some(
+                "This session was resumed with an unfinished plan. Continue from its outstanding steps instead of planning again, and keep it current with `update_plan`.\n\n- [completed] Read the code\n- [in_progress] Write the fix\n- [pending] Run the tests"
+                    .to_string()
+            )
+        );
+    }
+}
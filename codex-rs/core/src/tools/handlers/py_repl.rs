@@ -0,0 +1,201 @@
+use async_trait::async_trait;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::local_image_content_items_with_label_number;
+use codex_protocol::openai_models::InputModality;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::exec::ExecToolCallOutput;
+use crate::exec::StreamOutput;
+use crate::features::Feature;
+use crate::function_tool::FunctionCallError;
+use crate::protocol::EventMsg;
+use crate::protocol::ExecCommandSource;
+use crate::protocol::ViewImageToolCallEvent;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::events::ToolEmitter;
+use crate::tools::events::ToolEventCtx;
+use crate::tools::events::ToolEventFailure;
+use crate::tools::events::ToolEventStage;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::py_repl::PyReplArgs;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::turn_latency::LatencyStage;
+
+pub struct PyReplHandler;
+pub struct PyReplResetHandler;
+
+fn py_repl_emitter(turn: &TurnContext) -> ToolEmitter {
+    ToolEmitter::shell(
+        vec!["py_repl".to_string()],
+        turn.cwd.clone(),
+        ExecCommandSource::Agent,
+        false,
+    )
+}
+
+async fn emit_py_repl_exec_end(
+    session: &Session,
+    turn: &TurnContext,
+    call_id: &str,
+    output: &str,
+    error: Option<&str>,
+    duration: Duration,
+) {
+    let stdout = output.to_string();
+    let stderr = error.unwrap_or_default().to_string();
+    let aggregated_output = match (stdout.is_empty(), stderr.is_empty()) {
+        (true, _) => stderr.clone(),
+        (false, true) => stdout.clone(),
+        (false, false) => format!("{stdout}\n{stderr}"),
+    };
+    let exec_output = ExecToolCallOutput {
+        exit_code: if error.is_some() { 1 } else { 0 },
+        stdout: StreamOutput::new(stdout),
+        stderr: StreamOutput::new(stderr),
+        aggregated_output: StreamOutput::new(aggregated_output),
+        duration,
+        timed_out: false,
+    };
+    let ctx = ToolEventCtx::new(session, turn, call_id, None);
+    let stage = if error.is_some() {
+        ToolEventStage::Failure(ToolEventFailure::Output(exec_output))
+    } else {
+        ToolEventStage::Success(exec_output)
+    };
+    py_repl_emitter(turn).emit(ctx, stage).await;
+}
+
+#[async_trait]
+impl ToolHandler for PyReplHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            call_id,
+            ..
+        } = invocation;
+
+        if !session.features().enabled(Feature::PyRepl) {
+            return Err(FunctionCallError::RespondToModel(
+                "py_repl is disabled by feature flag".to_string(),
+            ));
+        }
+
+        let args: PyReplArgs = match payload {
+            ToolPayload::Function { arguments } => parse_arguments(&arguments)?,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "py_repl handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let manager = session.services.py_repl.manager().await?;
+        let started_at = Instant::now();
+        let ctx = ToolEventCtx::new(session.as_ref(), turn.as_ref(), &call_id, None);
+        py_repl_emitter(turn.as_ref())
+            .emit(ctx, ToolEventStage::Begin)
+            .await;
+
+        let result = {
+            let _round_trip = turn.latency.span(LatencyStage::SidecarRoundTrip);
+            manager
+                .execute(turn.clone(), session.conversation_id, args)
+                .await
+        };
+        let result = match result {
+            Ok(result) => result,
+            Err(err) => {
+                emit_py_repl_exec_end(
+                    session.as_ref(),
+                    turn.as_ref(),
+                    &call_id,
+                    "",
+                    Some(&err.to_string()),
+                    started_at.elapsed(),
+                )
+                .await;
+                return Err(err);
+            }
+        };
+
+        let text = result.text();
+        emit_py_repl_exec_end(
+            session.as_ref(),
+            turn.as_ref(),
+            &call_id,
+            &text,
+            None,
+            started_at.elapsed(),
+        )
+        .await;
+
+        let mut items = vec![FunctionCallOutputContentItem::InputText { text }];
+        let supports_images = turn
+            .model_info
+            .input_modalities
+            .contains(&InputModality::Image);
+        for path in result.images {
+            if supports_images {
+                items.extend(
+                    local_image_content_items_with_label_number(&path, None)
+                        .into_iter()
+                        .filter_map(|item| match item {
+                            ContentItem::InputImage { image_url } => {
+                                Some(FunctionCallOutputContentItem::InputImage { image_url })
+                            }
+                            ContentItem::InputText { .. } | ContentItem::OutputText { .. } => None,
+                        }),
+                );
+            }
+            session
+                .send_event(
+                    turn.as_ref(),
+                    EventMsg::ViewImageToolCall(ViewImageToolCallEvent {
+                        call_id: call_id.clone(),
+                        path,
+                    }),
+                )
+                .await;
+        }
+
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::ContentItems(items),
+            success: Some(true),
+        })
+    }
+}
+
+#[async_trait]
+impl ToolHandler for PyReplResetHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        if !invocation.session.features().enabled(Feature::PyRepl) {
+            return Err(FunctionCallError::RespondToModel(
+                "py_repl is disabled by feature flag".to_string(),
+            ));
+        }
+        let manager = invocation.session.services.py_repl.manager().await?;
+        manager.reset().await;
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text("py_repl kernel reset".to_string()),
+            success: Some(true),
+        })
+    }
+}
@@ -1,11 +1,21 @@
+use codex_protocol::models::ContentItem;
 use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::local_image_content_items_with_label_number;
+use codex_protocol::openai_models::InputModality;
 use std::collections::VecDeque;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::path::Path;
 use std::path::PathBuf;
 
 use async_trait::async_trait;
 use codex_utils_string::take_bytes_at_char_boundary;
 use serde::Deserialize;
+use tokio::io::AsyncReadExt;
 
+use crate::features::Feature;
 use crate::function_tool::FunctionCallError;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
@@ -18,6 +28,9 @@ pub struct ReadFileHandler;
 
 const MAX_LINE_LENGTH: usize = 500;
 const TAB_WIDTH: usize = 4;
+/// Number of leading bytes inspected when deciding whether a file is binary.
+const BINARY_SNIFF_LEN: usize = 8 * 1024;
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp"];
 
 // TODO(jif) add support for block comments
 const COMMENT_PREFIXES: &[&str] = &["#", "//", "--"];
@@ -39,6 +52,12 @@ struct ReadFileArgs {
     /// Optional indentation configuration used when `mode` is `Indentation`.
     #[serde(default)]
     indentation: Option<IndentationArgs>,
+    /// Maximum number of bytes of formatted output to return; defaults to 64 KiB.
+    #[serde(default = "defaults::max_bytes")]
+    max_bytes: usize,
+    /// Return the region even if an identical read was already served this session.
+    #[serde(default)]
+    refresh: bool,
 }
 
 #[derive(Deserialize, Default)]
@@ -99,7 +118,12 @@ impl ToolHandler for ReadFileHandler {
     }
 
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
-        let ToolInvocation { payload, .. } = invocation;
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            ..
+        } = invocation;
 
         let arguments = match payload {
             ToolPayload::Function { arguments } => arguments,
@@ -118,6 +142,8 @@ impl ToolHandler for ReadFileHandler {
             limit,
             mode,
             indentation,
+            max_bytes,
+            refresh,
         } = args;
 
         if offset == 0 {
@@ -132,6 +158,12 @@ impl ToolHandler for ReadFileHandler {
             ));
         }
 
+        if max_bytes == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "max_bytes must be greater than zero".to_string(),
+            ));
+        }
+
         let path = PathBuf::from(&file_path);
         if !path.is_absolute() {
             return Err(FunctionCallError::RespondToModel(
@@ -139,25 +171,149 @@ impl ToolHandler for ReadFileHandler {
             ));
         }
 
-        let collected = match mode {
-            ReadMode::Slice => slice::read(&path, offset, limit).await?,
-            ReadMode::Indentation => {
+        if is_image_path(&path) {
+            if !turn
+                .model_info
+                .input_modalities
+                .contains(&InputModality::Image)
+            {
+                return Err(FunctionCallError::RespondToModel(
+                    "file is an image and the current model does not support image inputs"
+                        .to_string(),
+                ));
+            }
+            let content_items = local_image_content_items_with_label_number(&path, None)
+                .into_iter()
+                .map(|item| match item {
+                    ContentItem::InputImage { image_url } => {
+                        FunctionCallOutputContentItem::InputImage { image_url }
+                    }
+                    ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                        FunctionCallOutputContentItem::InputText { text }
+                    }
+                })
+                .collect();
+            return Ok(ToolOutput::Function {
+                body: FunctionCallOutputBody::ContentItems(content_items),
+                success: Some(true),
+            });
+        }
+
+        let prefetch = session
+            .features()
+            .enabled(Feature::ReadPrefetch)
+            .then_some(&session.services.read_prefetch);
+        // Prefetched files are known to be text.
+        let cached = match prefetch {
+            Some(prefetch) => prefetch.get(&path).await,
+            None => None,
+        };
+        if cached.is_none() && is_binary_file(&path).await? {
+            return Err(FunctionCallError::RespondToModel(
+                "file appears to be binary; read_file only returns text and image files"
+                    .to_string(),
+            ));
+        }
+
+        let collected = match (mode, &cached) {
+            (ReadMode::Slice, Some(contents)) => {
+                slice::read_from(&contents[..], offset, limit).await?
+            }
+            (ReadMode::Slice, None) => slice::read(&path, offset, limit).await?,
+            (ReadMode::Indentation, Some(contents)) => {
+                let indentation = indentation.unwrap_or_default();
+                indentation::read_block_from(&contents[..], offset, limit, indentation).await?
+            }
+            (ReadMode::Indentation, None) => {
                 let indentation = indentation.unwrap_or_default();
                 indentation::read_block(&path, offset, limit, indentation).await?
             }
         };
+        let output = apply_byte_budget(collected, max_bytes);
+        if let Some(prefetch) = prefetch {
+            prefetch.spawn_prefetch(path.clone());
+        }
+
+        let mut hasher = DefaultHasher::new();
+        output.hash(&mut hasher);
+        let is_new_region = session.record_read_file_region(path, hasher.finish()).await;
+        if !is_new_region && !refresh {
+            return Ok(ToolOutput::Function {
+                body: FunctionCallOutputBody::Text(UNCHANGED_REGION_MESSAGE.to_string()),
+                success: Some(true),
+            });
+        }
+
         Ok(ToolOutput::Function {
-            body: FunctionCallOutputBody::Text(collected.join("\n")),
+            body: FunctionCallOutputBody::Text(output),
             success: Some(true),
         })
     }
 }
 
+const UNCHANGED_REGION_MESSAGE: &str = "This region was already returned by an earlier read_file call and is unchanged; \
+     refer to that output or pass refresh=true to read it again.";
+
+fn is_image_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            IMAGE_EXTENSIONS
+                .iter()
+                .any(|candidate| ext.eq_ignore_ascii_case(candidate))
+        })
+}
+
+async fn is_binary_file(path: &Path) -> Result<bool, FunctionCallError> {
+    let file = tokio::fs::File::open(path)
+        .await
+        .map_err(|err| FunctionCallError::RespondToModel(format!("failed to read file: {err}")))?;
+    let mut head = Vec::with_capacity(BINARY_SNIFF_LEN);
+    file.take(BINARY_SNIFF_LEN as u64)
+        .read_to_end(&mut head)
+        .await
+        .map_err(|err| FunctionCallError::RespondToModel(format!("failed to read file: {err}")))?;
+    Ok(head.contains(&0))
+}
+
+/// Joins the formatted lines, stopping before the line that would exceed
+/// `max_bytes` and telling the model where to resume.
+fn apply_byte_budget(lines: Vec<String>, max_bytes: usize) -> String {
+    let mut output = String::new();
+    for line in &lines {
+        let needed = if output.is_empty() {
+            line.len()
+        } else {
+            line.len() + 1
+        };
+        if output.len() + needed > max_bytes {
+            if output.is_empty() {
+                output.push_str(take_bytes_at_char_boundary(line, max_bytes));
+            }
+            let resume_hint = line
+                .strip_prefix('L')
+                .and_then(|rest| rest.split_once(':'))
+                .map(|(number, _)| format!("; continue from line {number}"))
+                .unwrap_or_default();
+            output.push_str(&format!(
+                "\n[output truncated at {max_bytes} bytes{resume_hint}]"
+            ));
+            return output;
+        }
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        output.push_str(line);
+    }
+    output
+}
+
 mod slice {
     use crate::function_tool::FunctionCallError;
     use crate::tools::handlers::read_file::format_line;
     use std::path::Path;
     use tokio::fs::File;
+    use tokio::io::AsyncBufRead;
     use tokio::io::AsyncBufReadExt;
     use tokio::io::BufReader;
 
@@ -169,8 +325,15 @@ mod slice {
         let file = File::open(path).await.map_err(|err| {
             FunctionCallError::RespondToModel(format!("failed to read file: {err}"))
         })?;
+        read_from(BufReader::new(file), offset, limit).await
+    }
 
-        let mut reader = BufReader::new(file);
+    /// Like `read`, for contents that are already in memory or open.
+    pub async fn read_from<R: AsyncBufRead + Unpin>(
+        mut reader: R,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<String>, FunctionCallError> {
         let mut collected = Vec::new();
         let mut seen = 0usize;
         let mut buffer = Vec::new();
@@ -230,6 +393,7 @@ mod indentation {
     use std::collections::VecDeque;
     use std::path::Path;
     use tokio::fs::File;
+    use tokio::io::AsyncBufRead;
     use tokio::io::AsyncBufReadExt;
     use tokio::io::BufReader;
 
@@ -238,6 +402,19 @@ mod indentation {
         offset: usize,
         limit: usize,
         options: IndentationArgs,
+    ) -> Result<Vec<String>, FunctionCallError> {
+        let file = File::open(path).await.map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to read file: {err}"))
+        })?;
+        read_block_from(BufReader::new(file), offset, limit, options).await
+    }
+
+    /// Like `read_block`, for contents that are already in memory or open.
+    pub async fn read_block_from<R: AsyncBufRead + Unpin>(
+        reader: R,
+        offset: usize,
+        limit: usize,
+        options: IndentationArgs,
     ) -> Result<Vec<String>, FunctionCallError> {
         let anchor_line = options.anchor_line.unwrap_or(offset);
         if anchor_line == 0 {
@@ -253,7 +430,7 @@ mod indentation {
             ));
         }
 
-        let collected = collect_file_lines(path).await?;
+        let collected = collect_lines(reader).await?;
         if collected.is_empty() || anchor_line > collected.len() {
             return Err(FunctionCallError::RespondToModel(
                 "anchor_line exceeds file length".to_string(),
@@ -365,12 +542,9 @@ mod indentation {
             .collect())
     }
 
-    async fn collect_file_lines(path: &Path) -> Result<Vec<LineRecord>, FunctionCallError> {
-        let file = File::open(path).await.map_err(|err| {
-            FunctionCallError::RespondToModel(format!("failed to read file: {err}"))
-        })?;
-
-        let mut reader = BufReader::new(file);
+    async fn collect_lines<R: AsyncBufRead + Unpin>(
+        mut reader: R,
+    ) -> Result<Vec<LineRecord>, FunctionCallError> {
         let mut buffer = Vec::new();
         let mut lines = Vec::new();
         let mut number = 0usize;
@@ -470,6 +644,10 @@ mod defaults {
         2000
     }
 
+    pub fn max_bytes() -> usize {
+        64 * 1024
+    }
+
     pub fn max_levels() -> usize {
         0
     }
@@ -508,6 +686,13 @@ gamma
         Ok(())
     }
 
+    #[tokio::test]
+    async fn reads_requested_range_from_cached_contents() -> anyhow::Result<()> {
+        let lines = super::slice::read_from(&b"alpha\nbeta\ngamma\n"[..], 2, 2).await?;
+        assert_eq!(lines, vec!["L2: beta".to_string(), "L3: gamma".to_string()]);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn errors_when_offset_exceeds_length() -> anyhow::Result<()> {
         let mut temp = NamedTempFile::new()?;
@@ -580,6 +765,54 @@ third
         Ok(())
     }
 
+    #[test]
+    fn byte_budget_stops_at_line_boundary() {
+        let lines = vec![
+            "L1: alpha".to_string(),
+            "L2: beta".to_string(),
+            "L3: gamma".to_string(),
+        ];
+
+        assert_eq!(
+            apply_byte_budget(lines.clone(), 1024),
+            "L1: alpha\nL2: beta\nL3: gamma".to_string()
+        );
+        assert_eq!(
+            apply_byte_budget(lines, 20),
+            "L1: alpha\nL2: beta\n[output truncated at 20 bytes; continue from line 3]".to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn detects_binary_files() -> anyhow::Result<()> {
+        let mut binary = NamedTempFile::new()?;
+        use std::io::Write as _;
+        binary.as_file_mut().write_all(b"\x7fELF\x00\x01\x02")?;
+        let mut text = NamedTempFile::new()?;
+        writeln!(text, "plain text")?;
+
+        assert_eq!(
+            (
+                is_binary_file(binary.path()).await?,
+                is_binary_file(text.path()).await?
+            ),
+            (true, false)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn recognizes_image_extensions() {
+        assert_eq!(
+            (
+                is_image_path(Path::new("/tmp/screenshot.PNG")),
+                is_image_path(Path::new("/tmp/photo.jpeg")),
+                is_image_path(Path::new("/tmp/main.rs")),
+            ),
+            (true, true, false)
+        );
+    }
+
     #[tokio::test]
     async fn indentation_mode_captures_block() -> anyhow::Result<()> {
         let mut temp = NamedTempFile::new()?;
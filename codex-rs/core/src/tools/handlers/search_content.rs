@@ -0,0 +1,201 @@
+use std::num::NonZero;
+use std::path::Path;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use codex_file_search::ContentMatch;
+use codex_file_search::ContentSearchOptions;
+use codex_file_search::search_content;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct SearchContentHandler;
+
+const DEFAULT_LIMIT: usize = 20;
+const MAX_LIMIT: usize = 100;
+const DEFAULT_CONTEXT_LINES: usize = 2;
+const MAX_CONTEXT_LINES: usize = 10;
+
+fn default_limit() -> usize {
+    DEFAULT_LIMIT
+}
+
+fn default_context_lines() -> usize {
+    DEFAULT_CONTEXT_LINES
+}
+
+#[derive(Deserialize)]
+struct SearchContentArgs {
+    query: String,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default = "default_context_lines")]
+    context_lines: usize,
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+#[async_trait]
+impl ToolHandler for SearchContentHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation { payload, turn, .. } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "search_content handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: SearchContentArgs = parse_arguments(&arguments)?;
+        if args.query.is_empty() {
+            return Err(FunctionCallError::RespondToModel(
+                "query must not be empty".to_string(),
+            ));
+        }
+        let Some(limit) = NonZero::new(args.limit.min(MAX_LIMIT)) else {
+            return Err(FunctionCallError::RespondToModel(
+                "limit must be greater than zero".to_string(),
+            ));
+        };
+
+        // Without a path, a scoped session searches its scope directories.
+        let search_paths: Vec<PathBuf> = if args.path.is_none() && !turn.scope.is_empty() {
+            turn.scope
+                .iter()
+                .map(AbsolutePathBuf::to_path_buf)
+                .collect()
+        } else {
+            vec![turn.resolve_path(args.path.clone())]
+        };
+        for search_path in &search_paths {
+            tokio::fs::metadata(search_path).await.map_err(|err| {
+                FunctionCallError::RespondToModel(format!(
+                    "unable to access `{}`: {err}",
+                    search_path.display()
+                ))
+            })?;
+        }
+
+        let options = ContentSearchOptions {
+            limit,
+            context_lines: args.context_lines.min(MAX_CONTEXT_LINES),
+            ..Default::default()
+        };
+        let query = args.query;
+        let results = tokio::task::spawn_blocking(move || {
+            search_content(&query, &search_paths, options, None)
+        })
+        .await
+        .map_err(|err| FunctionCallError::RespondToModel(format!("search failed: {err}")))?
+        .map_err(|err| FunctionCallError::RespondToModel(format!("search failed: {err}")))?;
+
+        if results.matches.is_empty() {
+            return Ok(ToolOutput::Function {
+                body: FunctionCallOutputBody::Text("No matches found.".to_string()),
+                success: Some(false),
+            });
+        }
+
+        let mut output = if results.total_match_count > results.matches.len() {
+            vec![format!(
+                "Showing the {} best of {} matching files.",
+                results.matches.len(),
+                results.total_match_count
+            )]
+        } else {
+            Vec::new()
+        };
+        output.extend(
+            results
+                .matches
+                .iter()
+                .map(|content_match| format_match(content_match, &turn.cwd)),
+        );
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(output.join("\n\n")),
+            success: Some(true),
+        })
+    }
+}
+
+/// Formats one file the way `rg --context` does: `line:text` for matching
+/// lines, `line-text` for context, and `--` between snippets.
+fn format_match(content_match: &ContentMatch, cwd: &Path) -> String {
+    let full_path = content_match.full_path();
+    let path = full_path.strip_prefix(cwd).unwrap_or(&full_path);
+    let mut lines = vec![format!(
+        "{} ({} matching {})",
+        path.display(),
+        content_match.match_count,
+        if content_match.match_count == 1 {
+            "line"
+        } else {
+            "lines"
+        }
+    )];
+    for (index, snippet) in content_match.snippets.iter().enumerate() {
+        if index > 0 {
+            lines.push("--".to_string());
+        }
+        for line in &snippet.lines {
+            let separator = if line.is_match { ':' } else { '-' };
+            lines.push(format!("{}{separator}{}", line.line_number, line.text));
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_file_search::Snippet;
+    use codex_file_search::SnippetLine;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn formats_match_like_rg_context() {
+        let line = |line_number, text: &str, is_match| SnippetLine {
+            line_number,
+            text: text.to_string(),
+            is_match,
+        };
+        let content_match = ContentMatch {
+            score: 25,
+            path: PathBuf::from("src/lib.rs"),
+            root: PathBuf::from("/repo"),
+            match_count: 2,
+            snippets: vec![
+                Snippet {
+                    lines: vec![
+                        line(1, "use std::fs;", false),
+                        line(2, "fn parse() {}", true),
+                    ],
+                },
+                Snippet {
+                    lines: vec![line(9, "    parse();", true)],
+                },
+            ],
+        };
+
+        assert_eq!(
+            format_match(&content_match, Path::new("/repo")),
+            "src/lib.rs (2 matching lines)\n1-use std::fs;\n2:fn parse() {}\n--\n9:    parse();"
+        );
+    }
+}
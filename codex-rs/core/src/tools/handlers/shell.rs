@@ -11,6 +11,7 @@ use crate::exec_env::create_env;
 use crate::exec_policy::ExecApprovalRequest;
 use crate::function_tool::FunctionCallError;
 use crate::is_safe_command::is_known_safe_command;
+use crate::project_commands::approve_project_command;
 use crate::protocol::ExecCommandSource;
 use crate::shell::Shell;
 use crate::tools::context::ToolInvocation;
@@ -303,12 +304,20 @@ impl ShellHandler {
             .exec_policy
             .create_exec_approval_requirement_for_command(ExecApprovalRequest {
                 command: &exec_params.command,
+                cwd: Some(&exec_params.cwd),
                 approval_policy: turn.approval_policy,
                 sandbox_policy: &turn.sandbox_policy,
                 sandbox_permissions: exec_params.sandbox_permissions,
                 prefix_rule,
             })
             .await;
+        let exec_approval_requirement = approve_project_command(
+            session.as_ref(),
+            turn.as_ref(),
+            &exec_params.command,
+            exec_approval_requirement,
+        )
+        .await;
 
         let req = ShellRequest {
             command: exec_params.command.clone(),
@@ -0,0 +1,159 @@
+use async_trait::async_trait;
+use codex_protocol::items::TurnItem;
+use codex_protocol::items::WebSearchItem;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::WebSearchAction;
+use futures::future::join_all;
+use serde::Deserialize;
+
+use crate::config::is_loopback_url;
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::tools::web_search::WebSearchClient;
+use crate::tools::web_search::format_output;
+
+pub struct WebSearchHandler;
+
+#[derive(Deserialize)]
+struct WebSearchArgs {
+    query: String,
+    #[serde(default)]
+    max_results: Option<usize>,
+    #[serde(default)]
+    max_bytes: Option<usize>,
+}
+
+#[async_trait]
+impl ToolHandler for WebSearchHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            call_id,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "web_search handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+        let args: WebSearchArgs = parse_arguments(&arguments)?;
+        let query = args.query.trim().to_string();
+        if query.is_empty() {
+            return Err(FunctionCallError::RespondToModel(
+                "query must not be empty".to_string(),
+            ));
+        }
+
+        let Some(provider) = turn.config.web_search_provider.as_ref() else {
+            return Err(FunctionCallError::RespondToModel(
+                "web_search has no provider configured".to_string(),
+            ));
+        };
+        ensure_provider_reachable(
+            turn.sandbox_policy.has_full_network_access(),
+            &provider.base_url,
+        )?;
+        let max_results = args
+            .max_results
+            .unwrap_or(provider.max_results)
+            .clamp(1, provider.max_results);
+        let max_bytes = args
+            .max_bytes
+            .unwrap_or(provider.max_bytes)
+            .min(provider.max_bytes);
+
+        let item = WebSearchItem {
+            id: call_id.clone(),
+            query: query.clone(),
+            action: WebSearchAction::Search {
+                query: Some(query.clone()),
+                queries: None,
+            },
+        };
+        session
+            .emit_turn_item_started(turn.as_ref(), &TurnItem::WebSearch(item.clone()))
+            .await;
+
+        let client = WebSearchClient::new(provider);
+        let result = client.search(&query, max_results).await;
+        session
+            .emit_turn_item_completed(turn.as_ref(), TurnItem::WebSearch(item))
+            .await;
+        let results = result.map_err(FunctionCallError::RespondToModel)?;
+
+        // Result pages live on arbitrary hosts, so they are only fetched when
+        // the sandbox allows full network access.
+        let fetch_results = if turn.sandbox_policy.has_full_network_access() {
+            provider.fetch_results
+        } else {
+            0
+        };
+        let pages = join_all(results.iter().take(fetch_results).enumerate().map(
+            |(index, result)| {
+                let client = &client;
+                async move { (index, client.fetch(&result.url).await) }
+            },
+        ))
+        .await;
+
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(format_output(&query, &results, &pages, max_bytes)),
+            success: Some(true),
+        })
+    }
+}
+
+/// The search request leaves the machine unless the provider is local, so it
+/// follows the sandbox's network policy like any other outbound request.
+fn ensure_provider_reachable(
+    full_network_access: bool,
+    base_url: &str,
+) -> Result<(), FunctionCallError> {
+    if full_network_access || url::Url::parse(base_url).is_ok_and(|url| is_loopback_url(&url)) {
+        return Ok(());
+    }
+    Err(FunctionCallError::RespondToModel(
+        "web_search is unavailable: network access is restricted by the sandbox policy".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn restricted_network_only_reaches_local_providers() {
+        assert_eq!(
+            ensure_provider_reachable(true, "https://api.search.brave.com").is_ok(),
+            true
+        );
+        assert_eq!(
+            ensure_provider_reachable(false, "http://127.0.0.1:8888").is_ok(),
+            true
+        );
+        assert_eq!(
+            ensure_provider_reachable(false, "http://localhost:8888").is_ok(),
+            true
+        );
+        assert_eq!(
+            ensure_provider_reachable(false, "https://api.search.brave.com").is_ok(),
+            false
+        );
+    }
+}
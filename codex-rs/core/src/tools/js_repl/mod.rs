@@ -6,6 +6,8 @@ use std::os::unix::process::ExitStatusExt;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 
 use codex_protocol::ThreadId;
@@ -79,6 +81,11 @@ impl JsReplHandle {
             .await
             .cloned()
     }
+
+    /// The manager if a kernel has been requested this session; never starts one.
+    pub(crate) fn initialized(&self) -> Option<Arc<JsReplManager>> {
+        self.cell.get().cloned()
+    }
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -266,6 +273,8 @@ pub struct JsReplManager {
     codex_home: PathBuf,
     tmp_dir: tempfile::TempDir,
     kernel: Mutex<Option<KernelState>>,
+    /// Set once the first kernel started, so later starts count as restarts.
+    kernel_started: AtomicBool,
     exec_lock: Arc<tokio::sync::Semaphore>,
     exec_tool_calls: Arc<Mutex<HashMap<String, ExecToolCalls>>>,
 }
@@ -284,6 +293,7 @@ impl JsReplManager {
             codex_home,
             tmp_dir,
             kernel: Mutex::new(None),
+            kernel_started: AtomicBool::new(false),
             exec_lock: Arc::new(tokio::sync::Semaphore::new(1)),
             exec_tool_calls: Arc::new(Mutex::new(HashMap::new())),
         });
@@ -436,6 +446,13 @@ impl JsReplManager {
                     .start_kernel(Arc::clone(&turn), Some(session.conversation_id))
                     .await
                     .map_err(FunctionCallError::RespondToModel)?;
+                if self.kernel_started.swap(true, Ordering::Relaxed) {
+                    turn.otel_manager.counter(
+                        "codex.sidecar.restart",
+                        1,
+                        &[("sidecar", "js_repl")],
+                    );
+                }
                 *kernel = Some(state);
             }
 
@@ -604,7 +621,7 @@ impl JsReplManager {
                 sandbox: sandbox_type,
                 enforce_managed_network: has_managed_network_requirements,
                 network: None,
-                sandbox_policy_cwd: &turn.cwd,
+                sandbox_policy_cwd: turn.sandbox_cwd(),
                 codex_linux_sandbox_exe: turn.codex_linux_sandbox_exe.as_ref(),
                 use_linux_sandbox_bwrap: turn
                     .features
@@ -1,17 +1,23 @@
+pub(crate) mod browser;
 pub mod context;
 pub mod events;
 pub(crate) mod handlers;
 pub mod js_repl;
 pub(crate) mod network_approval;
 pub mod orchestrator;
+pub(crate) mod output_summary;
 pub mod parallel;
+pub(crate) mod py_repl;
 pub mod registry;
 pub mod router;
 pub mod runtimes;
 pub mod sandboxing;
 pub mod spec;
+pub(crate) mod web_search;
 
 use crate::exec::ExecToolCallOutput;
+use crate::protocol::TestFailure;
+use crate::test_failures::format_test_failures;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::formatted_truncate_text;
 use crate::truncate::truncate_text;
@@ -25,9 +31,11 @@ pub(crate) const TELEMETRY_PREVIEW_TRUNCATION_NOTICE: &str =
     "[... telemetry preview truncated ...]";
 
 /// Format the combined exec output for sending back to the model.
-/// Includes exit code and duration metadata; truncates large bodies safely.
+/// Includes exit code and duration metadata and any failing tests parsed from
+/// the output; truncates large bodies safely.
 pub fn format_exec_output_for_model_structured(
     exec_output: &ExecToolCallOutput,
+    test_failures: &[TestFailure],
     truncation_policy: TruncationPolicy,
 ) -> String {
     let ExecToolCallOutput {
@@ -46,6 +54,8 @@ pub fn format_exec_output_for_model_structured(
     struct ExecOutput<'a> {
         output: &'a str,
         metadata: ExecMetadata,
+        #[serde(skip_serializing_if = "<[TestFailure]>::is_empty")]
+        test_failures: &'a [TestFailure],
     }
 
     // round to 1 decimal place
@@ -59,6 +69,7 @@ pub fn format_exec_output_for_model_structured(
             exit_code: *exit_code,
             duration_seconds,
         },
+        test_failures,
     };
 
     #[expect(clippy::expect_used)]
@@ -67,6 +78,7 @@ pub fn format_exec_output_for_model_structured(
 
 pub fn format_exec_output_for_model_freeform(
     exec_output: &ExecToolCallOutput,
+    test_failures: &[TestFailure],
     truncation_policy: TruncationPolicy,
 ) -> String {
     // round to 1 decimal place
@@ -85,6 +97,9 @@ pub fn format_exec_output_for_model_freeform(
     if total_lines != formatted_output.lines().count() {
         sections.push(format!("Total output lines: {total_lines}"));
     }
+    if !test_failures.is_empty() {
+        sections.push(format_test_failures(test_failures));
+    }
 
     sections.push("Output:".to_string());
     sections.push(formatted_output);
@@ -243,16 +243,18 @@ impl NetworkApprovalService {
             return NetworkDecision::deny(REASON_NOT_ALLOWED);
         };
 
+        let reason = format!(
+            "Network access to \"{}\" is blocked by policy.",
+            request.host
+        );
         let approval_decision = session
             .request_command_approval(
                 turn_context.as_ref(),
                 attempt.call_id.clone(),
+                "network",
                 attempt.command.clone(),
                 attempt.cwd.clone(),
-                Some(format!(
-                    "Network access to \"{}\" is blocked by policy.",
-                    request.host
-                )),
+                Some(reason),
                 Some(NetworkApprovalContext {
                     host: request.host.clone(),
                     protocol,
@@ -6,6 +6,8 @@ simple sequence for any ToolRuntime: approval → select sandbox → attempt →
 retry with an escalated sandbox strategy on denial (no re‑approval thanks to
 caching).
 */
+use crate::audit_log::ApprovalDecider;
+use crate::audit_log::AuditEvent;
 use crate::error::CodexErr;
 use crate::error::SandboxErr;
 use crate::exec::ExecToolCallOutput;
@@ -25,9 +27,11 @@ use crate::tools::sandboxing::ToolCtx;
 use crate::tools::sandboxing::ToolError;
 use crate::tools::sandboxing::ToolRuntime;
 use crate::tools::sandboxing::default_exec_approval_requirement;
+use crate::turn_latency::LatencyStage;
 use codex_otel::ToolDecisionSource;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::ReviewDecision;
+use std::time::Instant;
 
 pub(crate) struct ToolOrchestrator {
     sandbox: SandboxManager,
@@ -55,6 +59,7 @@ impl ToolOrchestrator {
     where
         T: ToolRuntime<Rq, Out>,
     {
+        let setup_started_at = Instant::now();
         let network_approval = begin_network_approval(
             tool_ctx.session,
             &tool_ctx.turn.sub_id,
@@ -73,6 +78,10 @@ impl ToolOrchestrator {
                 network_approval.attempt_id().map(ToString::to_string)
             }),
         };
+        tool_ctx
+            .turn
+            .latency
+            .record_since(LatencyStage::SandboxSetup, setup_started_at);
         let run_result = tool.run(req, attempt, &attempt_tool_ctx).await;
 
         let Some(network_approval) = network_approval else {
@@ -116,6 +125,37 @@ impl ToolOrchestrator {
         let otel_user = ToolDecisionSource::User;
         let otel_cfg = ToolDecisionSource::Config;
 
+        // 0) Disk quota: once the session has written more than its quota,
+        // pause for approval before anything else runs.
+        if let Some(reason) = tool_ctx.session.services.disk_quota.exceeded_reason() {
+            if matches!(approval_policy, AskForApproval::Never) {
+                return Err(ToolError::Rejected(reason));
+            }
+            let approval_ctx = ApprovalCtx {
+                session: tool_ctx.session,
+                turn: turn_ctx,
+                call_id: &tool_ctx.call_id,
+                retry_reason: Some(reason.clone()),
+                network_approval_context: None,
+                force_prompt: true,
+            };
+            let decision = {
+                let _wait = turn_ctx.latency.span(LatencyStage::ApprovalWait);
+                tool.start_approval_async(req, approval_ctx).await
+            };
+            otel.tool_decision(otel_tn, otel_ci, &decision, otel_user.clone());
+            match decision {
+                ReviewDecision::Denied | ReviewDecision::Abort => {
+                    return Err(ToolError::Rejected(reason));
+                }
+                ReviewDecision::Approved
+                | ReviewDecision::ApprovedExecpolicyAmendment { .. }
+                | ReviewDecision::ApprovedForSession => {
+                    tool_ctx.session.services.disk_quota.extend();
+                }
+            }
+        }
+
         // 1) Approval
         let mut already_approved = false;
 
@@ -125,8 +165,28 @@ impl ToolOrchestrator {
         match requirement {
             ExecApprovalRequirement::Skip { .. } => {
                 otel.tool_decision(otel_tn, otel_ci, &ReviewDecision::Approved, otel_cfg);
+                tool_ctx
+                    .session
+                    .record_audit_event(AuditEvent::approval(
+                        otel_ci,
+                        otel_tn,
+                        &ReviewDecision::Approved,
+                        ApprovalDecider::Policy,
+                        None,
+                    ))
+                    .await;
             }
             ExecApprovalRequirement::Forbidden { reason } => {
+                tool_ctx
+                    .session
+                    .record_audit_event(AuditEvent::approval(
+                        otel_ci,
+                        otel_tn,
+                        &ReviewDecision::Denied,
+                        ApprovalDecider::Policy,
+                        Some(reason.clone()),
+                    ))
+                    .await;
                 return Err(ToolError::Rejected(reason));
             }
             ExecApprovalRequirement::NeedsApproval { reason, .. } => {
@@ -134,10 +194,14 @@ impl ToolOrchestrator {
                     session: tool_ctx.session,
                     turn: turn_ctx,
                     call_id: &tool_ctx.call_id,
-                    retry_reason: reason,
+                    retry_reason: reason.clone(),
                     network_approval_context: None,
+                    force_prompt: false,
+                };
+                let decision = {
+                    let _wait = turn_ctx.latency.span(LatencyStage::ApprovalWait);
+                    tool.start_approval_async(req, approval_ctx).await
                 };
-                let decision = tool.start_approval_async(req, approval_ctx).await;
 
                 otel.tool_decision(otel_tn, otel_ci, &decision, otel_user.clone());
 
@@ -178,7 +242,7 @@ impl ToolOrchestrator {
             policy: &turn_ctx.sandbox_policy,
             enforce_managed_network: has_managed_network_requirements,
             manager: &self.sandbox,
-            sandbox_cwd: &turn_ctx.cwd,
+            sandbox_cwd: turn_ctx.sandbox_cwd(),
             codex_linux_sandbox_exe: turn_ctx.codex_linux_sandbox_exe.as_ref(),
             use_linux_sandbox_bwrap,
             windows_sandbox_level: turn_ctx.windows_sandbox_level,
@@ -211,6 +275,17 @@ impl ToolOrchestrator {
                 } else {
                     None
                 };
+                tool_ctx
+                    .session
+                    .record_audit_event(AuditEvent::SandboxViolation {
+                        call_id: otel_ci.clone(),
+                        tool: otel_tn.clone(),
+                        sandbox: initial_sandbox.as_metric_tag().to_string(),
+                        network_host: network_approval_context
+                            .as_ref()
+                            .map(|context| context.host.clone()),
+                    })
+                    .await;
                 if network_policy_decision.is_some() && network_approval_context.is_none() {
                     return Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
                         output,
@@ -262,11 +337,15 @@ impl ToolOrchestrator {
                         session: tool_ctx.session,
                         turn: turn_ctx,
                         call_id: &tool_ctx.call_id,
-                        retry_reason: Some(retry_reason),
+                        retry_reason: Some(retry_reason.clone()),
                         network_approval_context: network_approval_context.clone(),
+                        force_prompt: false,
                     };
 
-                    let decision = tool.start_approval_async(req, approval_ctx).await;
+                    let decision = {
+                        let _wait = turn_ctx.latency.span(LatencyStage::ApprovalWait);
+                        tool.start_approval_async(req, approval_ctx).await
+                    };
                     otel.tool_decision(otel_tn, otel_ci, &decision, otel_user);
 
                     match decision {
@@ -279,12 +358,22 @@ impl ToolOrchestrator {
                     }
                 }
 
+                tool_ctx
+                    .session
+                    .record_audit_event(AuditEvent::SandboxEscalation {
+                        call_id: otel_ci.clone(),
+                        tool: otel_tn.clone(),
+                        from_sandbox: initial_sandbox.as_metric_tag().to_string(),
+                        reason: retry_reason,
+                    })
+                    .await;
+
                 let escalated_attempt = SandboxAttempt {
                     sandbox: crate::exec::SandboxType::None,
                     policy: &turn_ctx.sandbox_policy,
                     enforce_managed_network: has_managed_network_requirements,
                     manager: &self.sandbox,
-                    sandbox_cwd: &turn_ctx.cwd,
+                    sandbox_cwd: turn_ctx.sandbox_cwd(),
                     codex_linux_sandbox_exe: None,
                     use_linux_sandbox_bwrap,
                     windows_sandbox_level: turn_ctx.windows_sandbox_level,
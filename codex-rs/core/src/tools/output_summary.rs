@@ -0,0 +1,226 @@
+//! Summaries of exec output that is too long to send to the model whole.
+//!
+//! Truncating the middle of a long build or test log tends to drop the lines
+//! that explain the failure. With the `output_summaries` feature, output over
+//! the turn's truncation budget is replaced by its first and last lines plus
+//! the lines in between that look like errors. The full text is kept in
+//! [`FullOutputs`] for the `fetch_output` tool, and the `ExecCommandEnd` event
+//! of a summarized call is persisted in the rollout even in limited mode.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use codex_utils_string::take_bytes_at_char_boundary;
+
+use crate::truncate::TruncationPolicy;
+
+pub(crate) const FETCH_OUTPUT_TOOL_NAME: &str = "fetch_output";
+
+/// First line of every summary. The rollout policy looks for it.
+pub(crate) const SUMMARY_HEADER: &str = "[output summarized]";
+
+/// Full outputs kept per session for `fetch_output`; the oldest are dropped
+/// first.
+const MAX_STORED_OUTPUT_BYTES: usize = 16 * 1024 * 1024;
+
+/// Longer lines are cut in summaries; `fetch_output` returns them whole.
+const MAX_SUMMARY_LINE_BYTES: usize = 400;
+
+/// Room left in the budget for section headings.
+const SECTION_HEADINGS_BYTES: usize = 256;
+
+/// Lowercase fragments of lines worth keeping from the middle of the output.
+const ERROR_MARKERS: &[&str] = &[
+    "error",
+    "fail",
+    "panic",
+    "exception",
+    "traceback",
+    "fatal",
+    "assert",
+    "denied",
+    "not found",
+    "undefined",
+];
+
+/// Returns a summary of `content` if it does not fit the `policy` budget.
+pub(crate) fn summarize_output(
+    content: &str,
+    call_id: &str,
+    policy: TruncationPolicy,
+) -> Option<String> {
+    let budget = policy.byte_budget();
+    if content.len() <= budget {
+        return None;
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+    let total = lines.len();
+    let mut summary = format!(
+        "{SUMMARY_HEADER} The output has {total} lines ({} bytes). Shown are the first and last lines and the lines in between that look like errors. Call `{FETCH_OUTPUT_TOOL_NAME}` with call_id \"{call_id}\" to read any range of the full output.\n",
+        content.len()
+    );
+    let body_budget = budget.saturating_sub(summary.len() + SECTION_HEADINGS_BYTES);
+    let head_budget = body_budget * 3 / 10;
+    let error_budget = body_budget * 3 / 10;
+    let tail_budget = body_budget - head_budget - error_budget;
+
+    let head_end = take_lines(lines.iter().copied(), head_budget).len();
+    let mut tail = take_lines(lines[head_end..].iter().rev().copied(), tail_budget);
+    tail.reverse();
+    let tail_start = total - tail.len();
+
+    let mut errors = Vec::new();
+    let mut error_bytes = 0;
+    let mut omitted_errors = 0;
+    for (index, line) in lines.iter().enumerate().take(tail_start).skip(head_end) {
+        if !looks_like_error(line) {
+            continue;
+        }
+        let numbered = format!("L{}: {}", index + 1, clip_line(line));
+        if error_bytes + numbered.len() + 1 > error_budget {
+            omitted_errors += 1;
+            continue;
+        }
+        error_bytes += numbered.len() + 1;
+        errors.push(numbered);
+    }
+
+    if head_end > 0 {
+        summary.push_str(&format!("--- lines 1-{head_end} ---\n"));
+        for line in &lines[..head_end] {
+            summary.push_str(&clip_line(line));
+            summary.push('\n');
+        }
+    }
+    if tail_start > head_end {
+        summary.push_str(&format!(
+            "--- {} lines that look like errors in lines {}-{tail_start} ---\n",
+            errors.len() + omitted_errors,
+            head_end + 1,
+        ));
+        for line in &errors {
+            summary.push_str(line);
+            summary.push('\n');
+        }
+        if omitted_errors > 0 {
+            summary.push_str(&format!("... {omitted_errors} more\n"));
+        }
+    }
+    if tail_start < total {
+        summary.push_str(&format!("--- lines {}-{total} ---\n", tail_start + 1));
+        for line in &tail {
+            summary.push_str(&clip_line(line));
+            summary.push('\n');
+        }
+    }
+    Some(summary)
+}
+
+/// Whether `text` was produced by [`summarize_output`].
+pub(crate) fn is_summary(text: &str) -> bool {
+    text.contains(SUMMARY_HEADER)
+}
+
+fn take_lines<'a>(lines: impl Iterator<Item = &'a str>, budget: usize) -> Vec<&'a str> {
+    let mut taken = Vec::new();
+    let mut used = 0;
+    for line in lines {
+        let len = clip_line(line).len() + 1;
+        if used + len > budget {
+            break;
+        }
+        used += len;
+        taken.push(line);
+    }
+    taken
+}
+
+fn clip_line(line: &str) -> std::borrow::Cow<'_, str> {
+    if line.len() <= MAX_SUMMARY_LINE_BYTES {
+        return line.into();
+    }
+    format!(
+        "{} [line cut]",
+        take_bytes_at_char_boundary(line, MAX_SUMMARY_LINE_BYTES)
+    )
+    .into()
+}
+
+fn looks_like_error(line: &str) -> bool {
+    let line = line.to_ascii_lowercase();
+    ERROR_MARKERS.iter().any(|marker| line.contains(marker))
+}
+
+/// Full output of summarized calls, by call id.
+#[derive(Default)]
+pub(crate) struct FullOutputs {
+    outputs: VecDeque<(String, Arc<str>)>,
+    bytes: usize,
+}
+
+impl FullOutputs {
+    pub(crate) fn insert(&mut self, call_id: String, output: String) {
+        self.bytes += output.len();
+        self.outputs.push_back((call_id, output.into()));
+        while self.bytes > MAX_STORED_OUTPUT_BYTES && self.outputs.len() > 1 {
+            if let Some((_, dropped)) = self.outputs.pop_front() {
+                self.bytes -= dropped.len();
+            }
+        }
+    }
+
+    pub(crate) fn get(&self, call_id: &str) -> Option<Arc<str>> {
+        self.outputs
+            .iter()
+            .rev()
+            .find(|(id, _)| id == call_id)
+            .map(|(_, output)| Arc::clone(output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn output_within_budget_is_not_summarized() {
+        assert_eq!(
+            summarize_output("ok\n", "call-1", TruncationPolicy::Bytes(1024)),
+            None
+        );
+    }
+
+    #[test]
+    fn summary_keeps_errors_from_the_middle() {
+        let mut output = String::new();
+        for i in 1..=2000 {
+            if i == 1234 {
+                output.push_str("test parser::nested ... FAILED\n");
+            } else {
+                output.push_str(&format!("test case_{i} ... ok\n"));
+            }
+        }
+
+        let summary = summarize_output(&output, "call-1", TruncationPolicy::Bytes(4096))
+            .expect("output is over budget");
+
+        assert!(is_summary(&summary));
+        assert!(summary.len() <= 4096, "{}", summary.len());
+        assert!(summary.contains("call_id \"call-1\""));
+        assert!(summary.contains("\ntest case_1 ... ok\n"));
+        assert!(summary.contains("L1234: test parser::nested ... FAILED\n"));
+        assert!(summary.ends_with("test case_2000 ... ok\n"));
+    }
+
+    #[test]
+    fn full_outputs_drop_the_oldest_first() {
+        let mut outputs = FullOutputs::default();
+        outputs.insert("old".to_string(), "a".repeat(MAX_STORED_OUTPUT_BYTES));
+        outputs.insert("new".to_string(), "b".to_string());
+
+        assert!(outputs.get("old").is_none());
+        assert_eq!(outputs.get("new").as_deref(), Some("b"));
+    }
+}
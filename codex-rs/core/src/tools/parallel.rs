@@ -17,6 +17,7 @@ use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::context::ToolPayload;
 use crate::tools::router::ToolCall;
 use crate::tools::router::ToolRouter;
+use crate::turn_latency::LatencyStage;
 use codex_protocol::models::FunctionCallOutputBody;
 use codex_protocol::models::FunctionCallOutputPayload;
 use codex_protocol::models::ResponseInputItem;
@@ -83,6 +84,8 @@ impl ToolCallRuntime {
                         } else {
                             Either::Right(lock.write().await)
                         };
+                        let latency = Arc::clone(&turn.latency);
+                        let _dispatch = latency.span(LatencyStage::ToolDispatch);
 
                         router
                             .dispatch_tool_call(
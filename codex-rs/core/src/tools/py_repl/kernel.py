@@ -0,0 +1,128 @@
+# Persistent Python kernel for the `py_repl` tool.
+#
+# The host talks to this process over stdio using newline-delimited JSON:
+#   host -> kernel: {"type": "exec", "id": "...", "code": "..."}
+#   kernel -> host: {"type": "exec_result", "id": "...", "ok": true, ...}
+#
+# User code runs in a single namespace that persists across cells. stdout and
+# stderr are captured per cell so the protocol stream stays clean.
+
+import ast
+import contextlib
+import io
+import json
+import os
+import sys
+import traceback
+import uuid
+
+MAX_REPR_CHARS = 8_000
+DATAFRAME_MAX_ROWS = 20
+DATAFRAME_MAX_COLS = 20
+
+_protocol_out = sys.stdout
+_tmp_dir = os.environ.get("CODEX_PY_TMP_DIR") or os.getcwd()
+_namespace = {"__name__": "__main__"}
+
+
+def _truncate(text):
+    if len(text) <= MAX_REPR_CHARS:
+        return text
+    omitted = len(text) - MAX_REPR_CHARS
+    return f"{text[:MAX_REPR_CHARS]}\n... [{omitted} characters truncated]"
+
+
+def _format_value(value):
+    pandas = sys.modules.get("pandas")
+    if pandas is not None:
+        if isinstance(value, pandas.DataFrame):
+            return _truncate(
+                value.to_string(max_rows=DATAFRAME_MAX_ROWS, max_cols=DATAFRAME_MAX_COLS)
+            )
+        if isinstance(value, pandas.Series):
+            return _truncate(value.to_string(max_rows=DATAFRAME_MAX_ROWS))
+    return _truncate(repr(value))
+
+
+def _collect_figures():
+    pyplot = sys.modules.get("matplotlib.pyplot")
+    if pyplot is None:
+        return []
+    paths = []
+    for number in pyplot.get_fignums():
+        figure = pyplot.figure(number)
+        path = os.path.join(_tmp_dir, f"figure-{uuid.uuid4().hex}.png")
+        figure.savefig(path, format="png", bbox_inches="tight")
+        paths.append(path)
+    pyplot.close("all")
+    return paths
+
+
+def _run_cell(code):
+    tree = ast.parse(code, filename="<py_repl>", mode="exec")
+    last_expr = None
+    if tree.body and isinstance(tree.body[-1], ast.Expr):
+        last_expr = ast.Expression(tree.body.pop().value)
+    exec(compile(tree, "<py_repl>", "exec"), _namespace)
+    if last_expr is None:
+        return None
+    return eval(compile(last_expr, "<py_repl>", "eval"), _namespace)
+
+
+def _format_user_traceback():
+    exc_type, exc, tb = sys.exc_info()
+    # Drop kernel frames so the model only sees frames from its own code.
+    while tb is not None and tb.tb_frame.f_code.co_filename != "<py_repl>":
+        tb = tb.tb_next
+    return "".join(traceback.format_exception(exc_type, exc, tb))
+
+
+def _handle_exec(message):
+    stdout = io.StringIO()
+    stderr = io.StringIO()
+    result = None
+    error = None
+    with contextlib.redirect_stdout(stdout), contextlib.redirect_stderr(stderr):
+        try:
+            value = _run_cell(message["code"])
+            if value is not None:
+                _namespace["_"] = value
+                result = _format_value(value)
+        except BaseException:  # noqa: BLE001 - report everything, including SystemExit
+            error = _truncate(_format_user_traceback())
+    try:
+        images = _collect_figures()
+    except Exception:  # noqa: BLE001
+        images = []
+        stderr.write(traceback.format_exc())
+    return {
+        "type": "exec_result",
+        "id": message["id"],
+        "ok": error is None,
+        "stdout": _truncate(stdout.getvalue()),
+        "stderr": _truncate(stderr.getvalue()),
+        "result": result,
+        "images": images,
+        "error": error,
+    }
+
+
+def main():
+    for line in sys.stdin:
+        line = line.strip()
+        if not line:
+            continue
+        try:
+            message = json.loads(line)
+        except json.JSONDecodeError as err:
+            sys.stderr.write(f"py_repl kernel received invalid json: {err}\n")
+            continue
+        if message.get("type") != "exec":
+            continue
+        response = _handle_exec(message)
+        _protocol_out.write(json.dumps(response) + "\n")
+        _protocol_out.flush()
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,535 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use codex_protocol::ThreadId;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::io::Lines;
+use tokio::process::Child;
+use tokio::process::ChildStdin;
+use tokio::process::ChildStdout;
+use tokio::sync::Mutex;
+use tokio::sync::OnceCell;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::codex::TurnContext;
+use crate::exec::ExecExpiration;
+use crate::exec_env::create_env;
+use crate::function_tool::FunctionCallError;
+use crate::sandboxing::CommandSpec;
+use crate::sandboxing::SandboxManager;
+use crate::sandboxing::SandboxPermissions;
+use crate::tools::sandboxing::SandboxablePreference;
+
+const KERNEL_SOURCE: &str = include_str!("kernel.py");
+const PY_REPL_DEFAULT_TIMEOUT_MS: u64 = 30_000;
+const PY_REPL_PYTHON_PATH_ENV_VAR: &str = "CODEX_PY_REPL_PYTHON_PATH";
+
+/// Per-session py_repl handle; the kernel is started lazily on first use.
+#[derive(Default)]
+pub(crate) struct PyReplHandle {
+    cell: OnceCell<Arc<PyReplManager>>,
+}
+
+impl fmt::Debug for PyReplHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PyReplHandle").finish_non_exhaustive()
+    }
+}
+
+impl PyReplHandle {
+    pub(crate) async fn manager(&self) -> Result<Arc<PyReplManager>, FunctionCallError> {
+        self.cell
+            .get_or_try_init(|| async { PyReplManager::new() })
+            .await
+            .cloned()
+    }
+
+    /// The manager if a kernel has been requested this session; never starts one.
+    pub(crate) fn initialized(&self) -> Option<Arc<PyReplManager>> {
+        self.cell.get().cloned()
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PyReplArgs {
+    pub code: String,
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+/// Captured output of a single py_repl cell.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PyExecResult {
+    pub stdout: String,
+    pub stderr: String,
+    /// `repr` of the cell's trailing expression, if any. DataFrames and other
+    /// large values are truncated by the kernel.
+    pub result: Option<String>,
+    /// PNG files written for matplotlib figures left open by the cell.
+    pub images: Vec<PathBuf>,
+}
+
+impl PyExecResult {
+    /// Text shown to the model and in exec events: stdout, then stderr, then
+    /// the value of the trailing expression.
+    pub fn text(&self) -> String {
+        [
+            self.stdout.trim_end(),
+            self.stderr.trim_end(),
+            self.result.as_deref().unwrap_or_default(),
+        ]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+    }
+}
+
+struct KernelState {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: Lines<BufReader<ChildStdout>>,
+}
+
+pub struct PyReplManager {
+    tmp_dir: tempfile::TempDir,
+    kernel: Mutex<Option<KernelState>>,
+    /// Set once the first kernel started, so later starts count as restarts.
+    kernel_started: AtomicBool,
+}
+
+impl PyReplManager {
+    fn new() -> Result<Arc<Self>, FunctionCallError> {
+        let tmp_dir = tempfile::tempdir().map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to create py_repl temp dir: {err}"))
+        })?;
+        Ok(Arc::new(Self {
+            tmp_dir,
+            kernel: Mutex::new(None),
+            kernel_started: AtomicBool::new(false),
+        }))
+    }
+
+    pub async fn reset(&self) {
+        let state = self.kernel.lock().await.take();
+        if let Some(mut state) = state {
+            Self::kill_kernel(&mut state.child).await;
+        }
+    }
+
+    /// Runs one cell. Cells are serialized: the kernel lock is held until the
+    /// result arrives, the kernel exits, or the timeout fires (which resets
+    /// the kernel and drops all interpreter state).
+    pub async fn execute(
+        &self,
+        turn: Arc<TurnContext>,
+        thread_id: ThreadId,
+        args: PyReplArgs,
+    ) -> Result<PyExecResult, FunctionCallError> {
+        let mut guard = self.kernel.lock().await;
+        if guard.is_none() {
+            let state = self
+                .start_kernel(turn.as_ref(), thread_id)
+                .await
+                .map_err(FunctionCallError::RespondToModel)?;
+            if self.kernel_started.swap(true, Ordering::Relaxed) {
+                turn.otel_manager
+                    .counter("codex.sidecar.restart", 1, &[("sidecar", "py_repl")]);
+            }
+            *guard = Some(state);
+        }
+        let Some(state) = guard.as_mut() else {
+            return Err(FunctionCallError::RespondToModel(
+                "py_repl kernel unavailable".to_string(),
+            ));
+        };
+
+        let id = Uuid::new_v4().to_string();
+        let request = HostToKernel::Exec {
+            id: id.clone(),
+            code: args.code,
+        };
+        if let Err(err) = Self::write_message(&mut state.stdin, &request).await {
+            if let Some(mut state) = guard.take() {
+                Self::kill_kernel(&mut state.child).await;
+            }
+            return Err(err);
+        }
+
+        let timeout_ms = args.timeout_ms.unwrap_or(PY_REPL_DEFAULT_TIMEOUT_MS);
+        let response = tokio::time::timeout(
+            Duration::from_millis(timeout_ms),
+            Self::read_result(&mut state.stdout, &id),
+        )
+        .await;
+        match response {
+            Ok(Ok(KernelToHost::ExecResult {
+                ok,
+                stdout,
+                stderr,
+                result,
+                images,
+                error,
+                ..
+            })) => {
+                if ok {
+                    Ok(PyExecResult {
+                        stdout,
+                        stderr,
+                        result,
+                        images,
+                    })
+                } else {
+                    let output = PyExecResult {
+                        stdout,
+                        stderr,
+                        result: None,
+                        images,
+                    }
+                    .text();
+                    let error = error.unwrap_or_else(|| "py_repl execution failed".to_string());
+                    Err(FunctionCallError::RespondToModel(if output.is_empty() {
+                        error
+                    } else {
+                        format!("{output}\n{error}")
+                    }))
+                }
+            }
+            Ok(Err(message)) => {
+                if let Some(mut state) = guard.take() {
+                    Self::kill_kernel(&mut state.child).await;
+                }
+                Err(FunctionCallError::RespondToModel(message))
+            }
+            Err(_) => {
+                if let Some(mut state) = guard.take() {
+                    Self::kill_kernel(&mut state.child).await;
+                }
+                Err(FunctionCallError::RespondToModel(
+                    "py_repl execution timed out; kernel reset, rerun your request".to_string(),
+                ))
+            }
+        }
+    }
+
+    async fn read_result(
+        stdout: &mut Lines<BufReader<ChildStdout>>,
+        id: &str,
+    ) -> Result<KernelToHost, String> {
+        loop {
+            let line = match stdout.next_line().await {
+                Ok(Some(line)) => line,
+                Ok(None) => return Err("py_repl kernel exited unexpectedly".to_string()),
+                Err(err) => return Err(format!("failed to read from py_repl kernel: {err}")),
+            };
+            match serde_json::from_str::<KernelToHost>(&line) {
+                Ok(message) if message.id() == id => return Ok(message),
+                Ok(message) => {
+                    warn!(
+                        "py_repl kernel sent result for unknown exec {}",
+                        message.id()
+                    );
+                }
+                Err(err) => warn!("py_repl kernel sent invalid json: {err} (line: {line})"),
+            }
+        }
+    }
+
+    async fn start_kernel(
+        &self,
+        turn: &TurnContext,
+        thread_id: ThreadId,
+    ) -> Result<KernelState, String> {
+        let python_path = resolve_python().ok_or_else(|| {
+            format!(
+                "Python runtime not found; install python3 or set {PY_REPL_PYTHON_PATH_ENV_VAR}"
+            )
+        })?;
+
+        let kernel_path = self.tmp_dir.path().join("py_repl_kernel.py");
+        tokio::fs::write(&kernel_path, KERNEL_SOURCE)
+            .await
+            .map_err(|err| format!("failed to write py_repl kernel: {err}"))?;
+
+        let mut env = create_env(&turn.shell_environment_policy, Some(thread_id));
+        env.insert(
+            "CODEX_PY_TMP_DIR".to_string(),
+            self.tmp_dir.path().to_string_lossy().to_string(),
+        );
+        // Render matplotlib figures off-screen; the kernel saves them as PNGs.
+        env.insert("MPLBACKEND".to_string(), "Agg".to_string());
+        env.insert("PYTHONUNBUFFERED".to_string(), "1".to_string());
+
+        let spec = CommandSpec {
+            program: python_path.to_string_lossy().to_string(),
+            args: vec!["-u".to_string(), kernel_path.to_string_lossy().to_string()],
+            cwd: turn.cwd.clone(),
+            env,
+            expiration: ExecExpiration::DefaultTimeout,
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            justification: None,
+        };
+
+        let sandbox = SandboxManager::new();
+        let has_managed_network_requirements = turn
+            .config
+            .config_layer_stack
+            .requirements_toml()
+            .network
+            .is_some();
+        let sandbox_type = sandbox.select_initial(
+            &turn.sandbox_policy,
+            SandboxablePreference::Auto,
+            turn.windows_sandbox_level,
+            has_managed_network_requirements,
+        );
+        let exec_env = sandbox
+            .transform(crate::sandboxing::SandboxTransformRequest {
+                spec,
+                policy: &turn.sandbox_policy,
+                sandbox: sandbox_type,
+                enforce_managed_network: has_managed_network_requirements,
+                network: None,
+                sandbox_policy_cwd: turn.sandbox_cwd(),
+                codex_linux_sandbox_exe: turn.codex_linux_sandbox_exe.as_ref(),
+                use_linux_sandbox_bwrap: turn
+                    .features
+                    .enabled(crate::features::Feature::UseLinuxSandboxBwrap),
+                windows_sandbox_level: turn.windows_sandbox_level,
+            })
+            .map_err(|err| format!("failed to configure sandbox for py_repl: {err}"))?;
+
+        let mut cmd =
+            tokio::process::Command::new(exec_env.command.first().cloned().unwrap_or_default());
+        if exec_env.command.len() > 1 {
+            cmd.args(&exec_env.command[1..]);
+        }
+        #[cfg(unix)]
+        cmd.arg0(
+            exec_env
+                .arg0
+                .clone()
+                .unwrap_or_else(|| exec_env.command.first().cloned().unwrap_or_default()),
+        );
+        cmd.current_dir(&exec_env.cwd);
+        cmd.env_clear();
+        cmd.envs(exec_env.env);
+        cmd.stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::null())
+            .kill_on_drop(true);
+
+        let mut child = cmd
+            .spawn()
+            .map_err(|err| format!("failed to start Python runtime: {err}"))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| "py_repl kernel missing stdout".to_string())?;
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| "py_repl kernel missing stdin".to_string())?;
+
+        Ok(KernelState {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout).lines(),
+        })
+    }
+
+    async fn write_message(
+        stdin: &mut ChildStdin,
+        msg: &HostToKernel,
+    ) -> Result<(), FunctionCallError> {
+        let mut encoded = serde_json::to_string(msg).map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to serialize kernel message: {err}"))
+        })?;
+        encoded.push('\n');
+        stdin.write_all(encoded.as_bytes()).await.map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to write to py_repl kernel: {err}"))
+        })?;
+        stdin.flush().await.map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to flush py_repl kernel: {err}"))
+        })
+    }
+
+    async fn kill_kernel(child: &mut Child) {
+        if let Err(err) = child.start_kill() {
+            warn!("failed to send kill signal to py_repl kernel: {err}");
+            return;
+        }
+        if tokio::time::timeout(Duration::from_secs(2), child.wait())
+            .await
+            .is_err()
+        {
+            warn!("timed out waiting for py_repl kernel to exit after kill");
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum HostToKernel {
+    Exec { id: String, code: String },
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum KernelToHost {
+    ExecResult {
+        id: String,
+        ok: bool,
+        #[serde(default)]
+        stdout: String,
+        #[serde(default)]
+        stderr: String,
+        #[serde(default)]
+        result: Option<String>,
+        #[serde(default)]
+        images: Vec<PathBuf>,
+        #[serde(default)]
+        error: Option<String>,
+    },
+}
+
+impl KernelToHost {
+    fn id(&self) -> &str {
+        match self {
+            Self::ExecResult { id, .. } => id,
+        }
+    }
+}
+
+pub(crate) fn resolve_python() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(PY_REPL_PYTHON_PATH_ENV_VAR) {
+        let path = PathBuf::from(path);
+        if path.exists() {
+            return Some(path);
+        }
+    }
+
+    ["python3", "python"]
+        .into_iter()
+        .find_map(|name| which::which(name).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codex::make_session_and_context;
+    use crate::protocol::SandboxPolicy;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn exec_result_text_joins_non_empty_streams() {
+        let result = PyExecResult {
+            stdout: "hello\n".to_string(),
+            stderr: String::new(),
+            result: Some("42".to_string()),
+            images: Vec::new(),
+        };
+        assert_eq!(result.text(), "hello\n42");
+    }
+
+    #[test]
+    fn kernel_messages_deserialize_with_defaults() {
+        let message: KernelToHost =
+            serde_json::from_str(r#"{"type":"exec_result","id":"1","ok":true}"#)
+                .expect("parse kernel message");
+        let KernelToHost::ExecResult {
+            id,
+            ok,
+            stdout,
+            images,
+            ..
+        } = message;
+        assert_eq!(
+            (id, ok, stdout, images),
+            ("1".to_string(), true, String::new(), Vec::new())
+        );
+    }
+
+    fn can_run_py_repl_runtime_tests() -> bool {
+        std::env::var_os("CODEX_SANDBOX").is_none() && resolve_python().is_some()
+    }
+
+    #[tokio::test]
+    async fn py_repl_persists_state_between_cells() -> anyhow::Result<()> {
+        if !can_run_py_repl_runtime_tests() {
+            return Ok(());
+        }
+
+        let (session, mut turn) = make_session_and_context().await;
+        turn.sandbox_policy = SandboxPolicy::DangerFullAccess;
+        let turn = Arc::new(turn);
+        let manager = PyReplManager::new()?;
+
+        let first = manager
+            .execute(
+                Arc::clone(&turn),
+                session.conversation_id,
+                PyReplArgs {
+                    code: "x = 41\nprint('ready')".to_string(),
+                    timeout_ms: Some(10_000),
+                },
+            )
+            .await?;
+        let second = manager
+            .execute(
+                Arc::clone(&turn),
+                session.conversation_id,
+                PyReplArgs {
+                    code: "x + 1".to_string(),
+                    timeout_ms: Some(10_000),
+                },
+            )
+            .await?;
+
+        assert_eq!(
+            (first.text(), second.text()),
+            ("ready".to_string(), "42".to_string())
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn py_repl_timeout_resets_kernel() -> anyhow::Result<()> {
+        if !can_run_py_repl_runtime_tests() {
+            return Ok(());
+        }
+
+        let (session, mut turn) = make_session_and_context().await;
+        turn.sandbox_policy = SandboxPolicy::DangerFullAccess;
+        let turn = Arc::new(turn);
+        let manager = PyReplManager::new()?;
+
+        let err = manager
+            .execute(
+                Arc::clone(&turn),
+                session.conversation_id,
+                PyReplArgs {
+                    code: "import time\ntime.sleep(5)".to_string(),
+                    timeout_ms: Some(200),
+                },
+            )
+            .await
+            .expect_err("expected timeout");
+        assert_eq!(
+            err.to_string(),
+            "py_repl execution timed out; kernel reset, rerun your request"
+        );
+        assert!(manager.kernel.lock().await.is_none());
+        Ok(())
+    }
+}
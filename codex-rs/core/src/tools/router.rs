@@ -11,6 +11,7 @@ use crate::tools::registry::ConfiguredToolSpec;
 use crate::tools::registry::ToolRegistry;
 use crate::tools::spec::ToolsConfig;
 use crate::tools::spec::build_specs;
+use crate::tools::spec::tool_allowed_for_role;
 use codex_protocol::dynamic_tools::DynamicToolSpec;
 use codex_protocol::models::FunctionCallOutputBody;
 use codex_protocol::models::LocalShellAction;
@@ -171,6 +172,17 @@ impl ToolRouter {
             ));
         }
 
+        if !tool_allowed_for_role(&turn.tools_config, &tool_name) {
+            let err = FunctionCallError::RespondToModel(format!(
+                "tool `{tool_name}` is not available to this agent role"
+            ));
+            return Ok(Self::failure_response(
+                failure_call_id,
+                payload_outputs_custom,
+                err,
+            ));
+        }
+
         let invocation = ToolInvocation {
             session,
             turn,
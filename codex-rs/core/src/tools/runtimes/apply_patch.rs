@@ -5,7 +5,9 @@
 //! `codex --codex-run-as-apply-patch`, and runs under the current
 //! `SandboxAttempt` with a minimal environment.
 use crate::CODEX_APPLY_PATCH_ARG1;
+use crate::apply_patch::save_patch_proposal;
 use crate::exec::ExecToolCallOutput;
+use crate::exec::StreamOutput;
 use crate::sandboxing::CommandSpec;
 use crate::sandboxing::SandboxPermissions;
 use crate::sandboxing::execute_env;
@@ -26,7 +28,9 @@ use codex_protocol::protocol::ReviewDecision;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use futures::future::BoxFuture;
 use std::collections::HashMap;
+use std::path::Path;
 use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Debug)]
 pub struct ApplyPatchRequest {
@@ -67,6 +71,30 @@ impl ApplyPatchRuntime {
         })
     }
 
+    /// Saves the patch under `dir` for review and reports it as a
+    /// successful call, leaving the workspace untouched.
+    async fn propose(
+        req: &ApplyPatchRequest,
+        dir: &Path,
+        ctx: &ToolCtx<'_>,
+    ) -> Result<ExecToolCallOutput, ToolError> {
+        let path = save_patch_proposal(dir, &ctx.call_id, &req.action)
+            .await
+            .map_err(|err| ToolError::Rejected(format!("failed to save patch proposal: {err}")))?;
+        let message = format!(
+            "Saved the patch to {} for review instead of applying it; the files on disk are unchanged.",
+            path.display()
+        );
+        Ok(ExecToolCallOutput {
+            exit_code: 0,
+            stdout: StreamOutput::new(message.clone()),
+            stderr: StreamOutput::new(String::new()),
+            aggregated_output: StreamOutput::new(message),
+            duration: Duration::ZERO,
+            timed_out: false,
+        })
+    }
+
     fn stdout_stream(ctx: &ToolCtx<'_>) -> Option<crate::exec::StdoutStream> {
         Some(crate::exec::StdoutStream {
             sub_id: ctx.turn.sub_id.clone(),
@@ -105,21 +133,20 @@ impl Approvable<ApplyPatchRequest> for ApplyPatchRuntime {
         let changes = req.changes.clone();
         Box::pin(async move {
             if let Some(reason) = retry_reason {
-                let rx_approve = session
+                return session
                     .request_patch_approval(turn, call_id, changes.clone(), Some(reason), None)
                     .await;
-                return rx_approve.await.unwrap_or_default();
             }
 
             with_cached_approval(
                 &session.services,
                 "apply_patch",
+                ctx.call_id,
                 approval_keys,
                 || async move {
-                    let rx_approve = session
+                    session
                         .request_patch_approval(turn, call_id, changes, None, None)
-                        .await;
-                    rx_approve.await.unwrap_or_default()
+                        .await
                 },
             )
             .await
@@ -149,6 +176,9 @@ impl ToolRuntime<ApplyPatchRequest, ExecToolCallOutput> for ApplyPatchRuntime {
         attempt: &SandboxAttempt<'_>,
         ctx: &ToolCtx<'_>,
     ) -> Result<ExecToolCallOutput, ToolError> {
+        if let Some(dir) = &ctx.turn.config.patch_proposal_dir {
+            return Self::propose(req, dir, ctx).await;
+        }
         let spec = Self::build_command_spec(req)?;
         let env = attempt
             .env_for(spec, None)
@@ -92,7 +92,11 @@ impl Approvable<ShellRequest> for ShellRuntime {
         req: &'a ShellRequest,
         ctx: ApprovalCtx<'a>,
     ) -> BoxFuture<'a, ReviewDecision> {
-        let keys = self.approval_keys(req);
+        let keys = if ctx.force_prompt {
+            Vec::new()
+        } else {
+            self.approval_keys(req)
+        };
         let command = req.command.clone();
         let cwd = req.cwd.clone();
         let reason = ctx
@@ -103,21 +107,28 @@ impl Approvable<ShellRequest> for ShellRuntime {
         let turn = ctx.turn;
         let call_id = ctx.call_id.to_string();
         Box::pin(async move {
-            with_cached_approval(&session.services, "shell", keys, move || async move {
-                session
-                    .request_command_approval(
-                        turn,
-                        call_id,
-                        command,
-                        cwd,
-                        reason,
-                        ctx.network_approval_context.clone(),
-                        req.exec_approval_requirement
-                            .proposed_execpolicy_amendment()
-                            .cloned(),
-                    )
-                    .await
-            })
+            with_cached_approval(
+                &session.services,
+                "shell",
+                ctx.call_id,
+                keys,
+                move || async move {
+                    session
+                        .request_command_approval(
+                            turn,
+                            call_id,
+                            "shell",
+                            command,
+                            cwd,
+                            reason,
+                            ctx.network_approval_context.clone(),
+                            req.exec_approval_requirement
+                                .proposed_execpolicy_amendment()
+                                .cloned(),
+                        )
+                        .await
+                },
+            )
             .await
         })
     }
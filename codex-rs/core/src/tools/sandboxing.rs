@@ -4,6 +4,8 @@
 //! `ApprovalCtx`, `Approvable`) together with the sandbox orchestration traits
 //! and helpers (`Sandboxable`, `ToolRuntime`, `SandboxAttempt`, etc.).
 
+use crate::audit_log::ApprovalDecider;
+use crate::audit_log::AuditEvent;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::error::CodexErr;
@@ -22,6 +24,7 @@ use std::collections::HashMap;
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::path::Path;
+use std::time::Instant;
 
 use futures::Future;
 use futures::future::BoxFuture;
@@ -55,13 +58,15 @@ impl ApprovalStore {
 /// Takes a vector of approval keys and returns a ReviewDecision.
 /// There will be one key in most cases, but apply_patch can modify multiple files at once.
 ///
-/// - If all keys are already approved for session, we skip prompting.
+/// - If all keys are already approved for session, we skip prompting and record
+///   the cached decision in the audit log under `call_id`.
 /// - If the user approves for session, we store the decision for each key individually
 ///   so future requests touching any subset can also skip prompting.
 pub(crate) async fn with_cached_approval<K, F, Fut>(
     services: &SessionServices,
     // Name of the tool, used for metrics collection.
     tool_name: &str,
+    call_id: &str,
     keys: Vec<K>,
     fetch: F,
 ) -> ReviewDecision
@@ -82,19 +87,32 @@ where
     };
 
     if already_approved {
-        return ReviewDecision::ApprovedForSession;
+        let decision = ReviewDecision::ApprovedForSession;
+        services
+            .record_audit_event(AuditEvent::approval(
+                call_id,
+                tool_name,
+                &decision,
+                ApprovalDecider::Session,
+                None,
+            ))
+            .await;
+        return decision;
     }
 
+    let started = Instant::now();
     let decision = fetch().await;
 
-    services.otel_manager.counter(
-        "codex.approval.requested",
-        1,
-        &[
-            ("tool", tool_name),
-            ("approved", decision.to_opaque_string()),
-        ],
-    );
+    let tags = [
+        ("tool", tool_name),
+        ("approved", decision.to_opaque_string()),
+    ];
+    services
+        .otel_manager
+        .counter("codex.approval.requested", 1, &tags);
+    services
+        .otel_manager
+        .record_duration("codex.approval.duration_ms", started.elapsed(), &tags);
 
     if matches!(decision, ReviewDecision::ApprovedForSession) {
         let mut store = services.tool_approvals.lock().await;
@@ -113,6 +131,8 @@ pub(crate) struct ApprovalCtx<'a> {
     pub call_id: &'a str,
     pub retry_reason: Option<String>,
     pub network_approval_context: Option<NetworkApprovalContext>,
+    /// Ask the user even when the call was already approved for the session.
+    pub force_prompt: bool,
 }
 
 // Specifies what tool orchestrator should do with a given tool call.
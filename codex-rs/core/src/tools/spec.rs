@@ -3,9 +3,12 @@ use crate::client_common::tools::FreeformTool;
 use crate::client_common::tools::FreeformToolFormat;
 use crate::client_common::tools::ResponsesApiTool;
 use crate::client_common::tools::ToolSpec;
+use crate::config::BuiltinTools;
+use crate::config::types::AgentRoleConfig;
 use crate::features::Feature;
 use crate::features::Features;
 use crate::mcp_connection_manager::ToolInfo;
+use crate::tools::handlers::MEMORY_TOOL_NAME;
 use crate::tools::handlers::PLAN_TOOL;
 use crate::tools::handlers::SEARCH_TOOL_BM25_DEFAULT_LIMIT;
 use crate::tools::handlers::SEARCH_TOOL_BM25_TOOL_NAME;
@@ -14,7 +17,10 @@ use crate::tools::handlers::apply_patch::create_apply_patch_json_tool;
 use crate::tools::handlers::collab::DEFAULT_WAIT_TIMEOUT_MS;
 use crate::tools::handlers::collab::MAX_WAIT_TIMEOUT_MS;
 use crate::tools::handlers::collab::MIN_WAIT_TIMEOUT_MS;
+use crate::tools::handlers::delegate::DEFAULT_DELEGATE_TOKEN_BUDGET;
+use crate::tools::handlers::delegate::MAX_DELEGATE_TASKS;
 use crate::tools::handlers::request_user_input_tool_description;
+use crate::tools::output_summary::FETCH_OUTPUT_TOOL_NAME;
 use crate::tools::registry::ToolRegistryBuilder;
 use codex_protocol::config_types::WebSearchMode;
 use codex_protocol::dynamic_tools::DynamicToolSpec;
@@ -37,10 +43,26 @@ pub(crate) struct ToolsConfig {
     pub shell_type: ConfigShellToolType,
     pub apply_patch_tool_type: Option<ApplyPatchToolType>,
     pub web_search_mode: Option<WebSearchMode>,
+    /// Whether a `web_search_provider` is configured; in live mode it replaces
+    /// the hosted web search tool with the provider-backed function tool.
+    pub web_search_provider: bool,
     pub search_tool: bool,
+    pub plan_tool: bool,
+    pub view_image_tool: bool,
     pub js_repl_enabled: bool,
     pub js_repl_tools_only: bool,
+    pub py_repl_enabled: bool,
+    pub browser_enabled: bool,
+    pub git_tool: bool,
+    pub fetch_output_tool: bool,
+    pub memory_tool: bool,
+    pub search_content_tool: bool,
     pub collab_tools: bool,
+    pub delegate_tool: bool,
+    /// Configured agent roles that `delegate` tasks may run as.
+    pub delegate_roles: Vec<AgentRoleConfig>,
+    /// Tool allow-list of the active agent role; every tool is available when unset.
+    pub allowed_tools: Option<Vec<String>>,
     pub collaboration_modes_tools: bool,
     pub request_rule_enabled: bool,
     pub experimental_supported_tools: Vec<String>,
@@ -63,7 +85,14 @@ impl ToolsConfig {
         let include_js_repl = features.enabled(Feature::JsRepl);
         let include_js_repl_tools_only =
             include_js_repl && features.enabled(Feature::JsReplToolsOnly);
+        let include_py_repl = features.enabled(Feature::PyRepl);
+        let include_browser = features.enabled(Feature::Browser);
+        let include_git_tool = features.enabled(Feature::GitTool);
+        let include_fetch_output_tool = features.enabled(Feature::OutputSummaries);
+        let include_memory_tool = features.enabled(Feature::ProjectMemory);
+        let include_search_content_tool = features.enabled(Feature::ContentSearch);
         let include_collab_tools = features.enabled(Feature::Collab);
+        let include_delegate_tool = features.enabled(Feature::Delegate);
         let include_collaboration_modes_tools = features.enabled(Feature::CollaborationModes);
         let request_rule_enabled = features.enabled(Feature::RequestRule);
         let include_search_tool = features.enabled(Feature::Apps);
@@ -97,28 +126,66 @@ impl ToolsConfig {
             shell_type,
             apply_patch_tool_type,
             web_search_mode: *web_search_mode,
+            web_search_provider: false,
             search_tool: include_search_tool,
+            plan_tool: true,
+            view_image_tool: true,
             js_repl_enabled: include_js_repl,
             js_repl_tools_only: include_js_repl_tools_only,
+            py_repl_enabled: include_py_repl,
+            browser_enabled: include_browser,
+            git_tool: include_git_tool,
+            fetch_output_tool: include_fetch_output_tool,
+            memory_tool: include_memory_tool,
+            search_content_tool: include_search_content_tool,
             collab_tools: include_collab_tools,
+            delegate_tool: include_delegate_tool,
+            delegate_roles: Vec::new(),
+            allowed_tools: None,
             collaboration_modes_tools: include_collaboration_modes_tools,
             request_rule_enabled,
             experimental_supported_tools: model_info.experimental_supported_tools.clone(),
         }
     }
+
+    /// Leaves out the built-in tools that `[tools]` switches off.
+    pub fn apply_builtin_tools(&mut self, tools: BuiltinTools) {
+        if !tools.shell {
+            self.shell_type = ConfigShellToolType::Disabled;
+        }
+        if !tools.apply_patch {
+            self.apply_patch_tool_type = None;
+        }
+        if !tools.web_search {
+            self.web_search_mode = Some(WebSearchMode::Disabled);
+        }
+        self.plan_tool = tools.plan;
+        self.view_image_tool = tools.view_image;
+    }
 }
 
 pub(crate) fn filter_tools_for_model(tools: Vec<ToolSpec>, config: &ToolsConfig) -> Vec<ToolSpec> {
-    if !config.js_repl_tools_only {
+    if !config.js_repl_tools_only && config.allowed_tools.is_none() {
         return tools;
     }
 
     tools
         .into_iter()
-        .filter(|spec| matches!(spec.name(), "js_repl" | "js_repl_reset"))
+        .filter(|spec| {
+            (!config.js_repl_tools_only || matches!(spec.name(), "js_repl" | "js_repl_reset"))
+                && tool_allowed_for_role(config, spec.name())
+        })
         .collect()
 }
 
+/// Whether the active agent role's tool allow-list permits `name`.
+pub(crate) fn tool_allowed_for_role(config: &ToolsConfig, name: &str) -> bool {
+    config
+        .allowed_tools
+        .as_ref()
+        .is_none_or(|allowed| allowed.iter().any(|tool| tool == name))
+}
+
 /// Generic JSON‑Schema subset needed for our tool definitions
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type", rename_all = "lowercase")]
@@ -564,6 +631,86 @@ fn create_spawn_agent_tool() -> ToolSpec {
     })
 }
 
+fn create_delegate_tool(roles: &[AgentRoleConfig]) -> ToolSpec {
+    let mut task_properties = BTreeMap::from([
+        (
+            "prompt".to_string(),
+            JsonSchema::String {
+                description: Some("Self-contained instructions for the sub-session.".to_string()),
+            },
+        ),
+        (
+            "model".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Optional model for the sub-session. Defaults to the current model."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "sandbox".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Optional sandbox: `read-only` or `workspace-write`. Defaults to the current sandbox and can never be wider than it."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "max_tokens".to_string(),
+            JsonSchema::Number {
+                description: Some(format!(
+                    "Optional token budget; the sub-session is stopped once it is exceeded. Defaults to {DEFAULT_DELEGATE_TOKEN_BUDGET}."
+                )),
+            },
+        ),
+    ]);
+    if !roles.is_empty() {
+        let roles = roles
+            .iter()
+            .map(|role| match role.description.as_deref() {
+                Some(description) => format!("`{}` ({description})", role.name),
+                None => format!("`{}`", role.name),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        task_properties.insert(
+            "role".to_string(),
+            JsonSchema::String {
+                description: Some(format!(
+                    "Optional agent role to run the task as: {roles}. The role's model, instructions, and tools apply unless overridden."
+                )),
+            },
+        );
+    }
+    let properties = BTreeMap::from([(
+        "tasks".to_string(),
+        JsonSchema::Array {
+            items: Box::new(JsonSchema::Object {
+                properties: task_properties,
+                required: Some(vec!["prompt".to_string()]),
+                additional_properties: Some(false.into()),
+            }),
+            description: Some(format!(
+                "Independent subtasks to run in parallel (at most {MAX_DELEGATE_TASKS})."
+            )),
+        },
+    )]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "delegate".to_string(),
+        description: "Run independent subtasks in parallel sub-sessions and wait for all of them. Each sub-session starts with a fresh context, so prompts must be self-contained. Returns each task's status, final message, and token usage."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["tasks".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_send_input_tool() -> ToolSpec {
     let properties = BTreeMap::from([
         (
@@ -856,7 +1003,7 @@ fn create_grep_files_tool() -> ToolSpec {
             "path".to_string(),
             JsonSchema::String {
                 description: Some(
-                    "Directory or file path to search. Defaults to the session's working directory."
+                    "Directory or file path to search. Defaults to the session's working directory, or to its scope directories when the session is scoped."
                         .to_string(),
                 ),
             },
@@ -885,6 +1032,60 @@ fn create_grep_files_tool() -> ToolSpec {
     })
 }
 
+fn create_search_content_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "query".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Literal text to search for. Case-insensitive unless it contains an uppercase letter."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "path".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Directory or file path to search. Defaults to the session's working directory, or to its scope directories when the session is scoped."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "context_lines".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Lines of context to show around each matching line (defaults to 2, at most 10)."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "limit".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Maximum number of files to return (defaults to 20, at most 100).".to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "search_content".to_string(),
+        description: "Searches file contents and returns the best matching files, ranked by \
+                      relevance, with numbered snippets of the matching lines and their context. \
+                      Ignored, binary and very large files are skipped."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["query".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_search_tool_bm25_tool(app_tools: &HashMap<String, ToolInfo>) -> ToolSpec {
     let properties = BTreeMap::from([
         (
@@ -1012,12 +1213,30 @@ fn create_read_file_tool() -> ToolSpec {
                 additional_properties: Some(false.into()),
             },
         ),
+        (
+            "max_bytes".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Maximum number of bytes of output to return (defaults to 65536).".to_string(),
+                ),
+            },
+        ),
+        (
+            "refresh".to_string(),
+            JsonSchema::Boolean {
+                description: Some(
+                    "Return the region even if an identical read was already returned earlier in \
+                     the session."
+                        .to_string(),
+                ),
+            },
+        ),
     ]);
 
     ToolSpec::Function(ResponsesApiTool {
         name: "read_file".to_string(),
         description:
-            "Reads a local file with 1-indexed line numbers, supporting slice and indentation-aware block modes."
+            "Reads a local file with 1-indexed line numbers, supporting slice and indentation-aware block modes. Image files are attached as images; binary files are rejected."
                 .to_string(),
         strict: false,
         parameters: JsonSchema::Object {
@@ -1104,6 +1323,300 @@ fn create_js_repl_reset_tool() -> ToolSpec {
     })
 }
 
+fn create_py_repl_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "code".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Python source to run. The value of a trailing expression is returned."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "timeout_ms".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Optional timeout in milliseconds (defaults to 30000). The kernel is reset on timeout."
+                        .to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "py_repl".to_string(),
+        description: "Runs Python in a persistent sandboxed interpreter. Variables, imports, and definitions persist between calls. Returns captured stdout/stderr, the repr of the trailing expression (DataFrames are truncated), and any open matplotlib figures as images."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["code".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_git_tool() -> ToolSpec {
+    let string = |description: &str| JsonSchema::String {
+        description: Some(description.to_string()),
+    };
+    let number = |description: &str| JsonSchema::Number {
+        description: Some(description.to_string()),
+    };
+    let properties = BTreeMap::from([
+        (
+            "operation".to_string(),
+            string("One of `status`, `diff`, `log`, `blame`, `stash`, or `commit`."),
+        ),
+        (
+            "staged".to_string(),
+            JsonSchema::Boolean {
+                description: Some("For `diff`: compare the index to HEAD instead of the working tree to the index.".to_string()),
+            },
+        ),
+        (
+            "paths".to_string(),
+            JsonSchema::Array {
+                items: Box::new(JsonSchema::String { description: None }),
+                description: Some(
+                    "For `diff`: limit to these paths. For `commit`: stage these paths before committing."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "max_count".to_string(),
+            number("For `log`: number of commits to return (defaults to 20)."),
+        ),
+        (
+            "path".to_string(),
+            string("For `log`: only commits touching this path. For `blame`: the file to blame."),
+        ),
+        (
+            "start_line".to_string(),
+            number("For `blame`: first line (1-based) of the range."),
+        ),
+        (
+            "end_line".to_string(),
+            number("For `blame`: last line (inclusive) of the range."),
+        ),
+        (
+            "stash_action".to_string(),
+            string("For `stash`: one of `list`, `push`, or `pop`."),
+        ),
+        (
+            "message".to_string(),
+            string("For `commit`: the commit message. For `stash push`: optional stash message."),
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "git".to_string(),
+        description: "Runs git operations in the current repository and returns JSON. Use this instead of invoking git through the shell. `status`, `diff`, `log`, `blame`, and `stash list` are read-only; `commit`, `stash push`, and `stash pop` modify the repository and may require user approval."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["operation".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_fetch_output_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "call_id".to_string(),
+            JsonSchema::String {
+                description: Some("The call_id named in the summarized output.".to_string()),
+            },
+        ),
+        (
+            "offset".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "The line number to start from; must be 1 or greater (defaults to 1)."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "limit".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "The maximum number of lines to return (defaults to 200).".to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: FETCH_OUTPUT_TOOL_NAME.to_string(),
+        description: "Reads a range of lines from the full output of an earlier command whose output was summarized. Use the line numbers shown in the summary to pick the range.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["call_id".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_memory_tool() -> ToolSpec {
+    let string = |description: &str| JsonSchema::String {
+        description: Some(description.to_string()),
+    };
+    let properties = BTreeMap::from([
+        (
+            "action".to_string(),
+            string("One of `list`, `read`, `search`, `write`, or `delete`."),
+        ),
+        (
+            "name".to_string(),
+            string(
+                "For `read`, `write` and `delete`: the entry name, such as `build-quirks` (letters, digits, `-`, `_`, `.`).",
+            ),
+        ),
+        (
+            "content".to_string(),
+            string(
+                "For `write`: the full new text of the entry, replacing any previous text. Keep it to one fact or closely related facts.",
+            ),
+        ),
+        (
+            "importance".to_string(),
+            string(
+                "For `write`: `high` (always loaded into new sessions), `normal` (loaded when there is room, default), or `low` (only read on request).",
+            ),
+        ),
+        (
+            "query".to_string(),
+            string("For `search`: words that must all appear in the entry."),
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: MEMORY_TOOL_NAME.to_string(),
+        description: "Reads and saves durable facts about this project in .codex/memory/, shared with future sessions. Save build quirks, decisions and conventions you had to discover and that are not obvious from the code; do not save task progress or secrets. Saves are shown to the user as file changes.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["action".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_browser_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "action".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "One of `navigate`, `text`, `screenshot`, `click`, or `type`.".to_string(),
+                ),
+            },
+        ),
+        (
+            "url".to_string(),
+            JsonSchema::String {
+                description: Some("http(s) URL to load (for `navigate`).".to_string()),
+            },
+        ),
+        (
+            "selector".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "CSS selector of the target element (for `click` and `type`; optional for `text`)."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "text".to_string(),
+            JsonSchema::String {
+                description: Some("Text to type into the element (for `type`).".to_string()),
+            },
+        ),
+        (
+            "max_bytes".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Maximum bytes of page text to return (for `text`; defaults to 32768)."
+                        .to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "browser".to_string(),
+        description: "Drives a headless Chromium page that persists for the session. Navigate to a URL, read the rendered text of the page or an element, take a screenshot, click elements, or type into inputs. Navigation is subject to the session's network approval policy."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["action".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_web_search_provider_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "query".to_string(),
+            JsonSchema::String {
+                description: Some("Search query.".to_string()),
+            },
+        ),
+        (
+            "max_results".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Maximum number of ranked results to return (capped by configuration)."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "max_bytes".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Maximum bytes of output to return (capped by configuration).".to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "web_search".to_string(),
+        description: "Searches the web and returns ranked results (title, URL, snippet) followed by the readable text of the top pages."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["query".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_py_repl_reset_tool() -> ToolSpec {
+    ToolSpec::Function(ResponsesApiTool {
+        name: "py_repl_reset".to_string(),
+        description: "Restarts the py_repl interpreter and clears all session state.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties: BTreeMap::new(),
+            required: None,
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_list_mcp_resources_tool() -> ToolSpec {
     let properties = BTreeMap::from([
         (
@@ -1409,23 +1922,32 @@ pub(crate) fn build_specs(
     dynamic_tools: &[DynamicToolSpec],
 ) -> ToolRegistryBuilder {
     use crate::tools::handlers::ApplyPatchHandler;
+    use crate::tools::handlers::BrowserHandler;
     use crate::tools::handlers::CollabHandler;
+    use crate::tools::handlers::DelegateHandler;
     use crate::tools::handlers::DynamicToolHandler;
+    use crate::tools::handlers::FetchOutputHandler;
+    use crate::tools::handlers::GitHandler;
     use crate::tools::handlers::GrepFilesHandler;
     use crate::tools::handlers::JsReplHandler;
     use crate::tools::handlers::JsReplResetHandler;
     use crate::tools::handlers::ListDirHandler;
     use crate::tools::handlers::McpHandler;
     use crate::tools::handlers::McpResourceHandler;
+    use crate::tools::handlers::MemoryHandler;
     use crate::tools::handlers::PlanHandler;
+    use crate::tools::handlers::PyReplHandler;
+    use crate::tools::handlers::PyReplResetHandler;
     use crate::tools::handlers::ReadFileHandler;
     use crate::tools::handlers::RequestUserInputHandler;
+    use crate::tools::handlers::SearchContentHandler;
     use crate::tools::handlers::SearchToolBm25Handler;
     use crate::tools::handlers::ShellCommandHandler;
     use crate::tools::handlers::ShellHandler;
     use crate::tools::handlers::TestSyncHandler;
     use crate::tools::handlers::UnifiedExecHandler;
     use crate::tools::handlers::ViewImageHandler;
+    use crate::tools::handlers::WebSearchHandler;
     use std::sync::Arc;
 
     let mut builder = ToolRegistryBuilder::new();
@@ -1489,8 +2011,10 @@ pub(crate) fn build_specs(
     builder.register_handler("list_mcp_resource_templates", mcp_resource_handler.clone());
     builder.register_handler("read_mcp_resource", mcp_resource_handler);
 
-    builder.push_spec(PLAN_TOOL.clone());
-    builder.register_handler("update_plan", plan_handler);
+    if config.plan_tool {
+        builder.push_spec(PLAN_TOOL.clone());
+        builder.register_handler("update_plan", plan_handler);
+    }
 
     if config.js_repl_enabled {
         builder.push_spec(create_js_repl_tool());
@@ -1499,6 +2023,33 @@ pub(crate) fn build_specs(
         builder.register_handler("js_repl_reset", js_repl_reset_handler);
     }
 
+    if config.py_repl_enabled {
+        builder.push_spec(create_py_repl_tool());
+        builder.push_spec(create_py_repl_reset_tool());
+        builder.register_handler("py_repl", Arc::new(PyReplHandler));
+        builder.register_handler("py_repl_reset", Arc::new(PyReplResetHandler));
+    }
+
+    if config.git_tool {
+        builder.push_spec(create_git_tool());
+        builder.register_handler("git", Arc::new(GitHandler));
+    }
+
+    if config.fetch_output_tool {
+        builder.push_spec(create_fetch_output_tool());
+        builder.register_handler(FETCH_OUTPUT_TOOL_NAME, Arc::new(FetchOutputHandler));
+    }
+
+    if config.memory_tool {
+        builder.push_spec(create_memory_tool());
+        builder.register_handler(MEMORY_TOOL_NAME, Arc::new(MemoryHandler));
+    }
+
+    if config.browser_enabled {
+        builder.push_spec(create_browser_tool());
+        builder.register_handler("browser", Arc::new(BrowserHandler));
+    }
+
     if config.collaboration_modes_tools {
         builder.push_spec(create_request_user_input_tool());
         builder.register_handler("request_user_input", request_user_input_handler);
@@ -1532,6 +2083,11 @@ pub(crate) fn build_specs(
         builder.register_handler("grep_files", grep_files_handler);
     }
 
+    if config.search_content_tool {
+        builder.push_spec_with_parallel_support(create_search_content_tool(), true);
+        builder.register_handler("search_content", Arc::new(SearchContentHandler));
+    }
+
     if config
         .experimental_supported_tools
         .contains(&"read_file".to_string())
@@ -1566,6 +2122,10 @@ pub(crate) fn build_specs(
                 external_web_access: Some(false),
             });
         }
+        Some(WebSearchMode::Live) if config.web_search_provider => {
+            builder.push_spec_with_parallel_support(create_web_search_provider_tool(), true);
+            builder.register_handler("web_search", Arc::new(WebSearchHandler));
+        }
         Some(WebSearchMode::Live) => {
             builder.push_spec(ToolSpec::WebSearch {
                 external_web_access: Some(true),
@@ -1574,8 +2134,10 @@ pub(crate) fn build_specs(
         Some(WebSearchMode::Disabled) | None => {}
     }
 
-    builder.push_spec_with_parallel_support(create_view_image_tool(), true);
-    builder.register_handler("view_image", view_image_handler);
+    if config.view_image_tool {
+        builder.push_spec_with_parallel_support(create_view_image_tool(), true);
+        builder.register_handler("view_image", view_image_handler);
+    }
 
     if config.collab_tools {
         let collab_handler = Arc::new(CollabHandler);
@@ -1591,6 +2153,11 @@ pub(crate) fn build_specs(
         builder.register_handler("close_agent", collab_handler);
     }
 
+    if config.delegate_tool {
+        builder.push_spec(create_delegate_tool(&config.delegate_roles));
+        builder.register_handler("delegate", Arc::new(DelegateHandler));
+    }
+
     if let Some(mcp_tools) = mcp_tools {
         let mut entries: Vec<(String, rmcp::model::Tool)> = mcp_tools.into_iter().collect();
         entries.sort_by(|a, b| a.0.cmp(&b.0));
@@ -1960,6 +2527,125 @@ mod tests {
         assert_contains_tool_names(&tools, &["js_repl", "js_repl_reset"]);
     }
 
+    #[test]
+    fn py_repl_enabled_adds_tools() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::PyRepl);
+
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+        assert_contains_tool_names(&tools, &["py_repl", "py_repl_reset"]);
+    }
+
+    #[test]
+    fn delegate_feature_adds_tool() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::Delegate);
+
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+        assert_contains_tool_names(&tools, &["delegate"]);
+    }
+
+    #[test]
+    fn git_tool_feature_adds_tool() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::GitTool);
+
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+        assert_contains_tool_names(&tools, &["git"]);
+    }
+
+    #[test]
+    fn content_search_feature_adds_search_content_tool() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::ContentSearch);
+
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+        assert_contains_tool_names(&tools, &["search_content"]);
+    }
+
+    #[test]
+    fn output_summaries_feature_adds_fetch_output_tool() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::OutputSummaries);
+
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+        assert_contains_tool_names(&tools, &["fetch_output"]);
+    }
+
+    #[test]
+    fn project_memory_feature_adds_memory_tool() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::ProjectMemory);
+
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+        assert_contains_tool_names(&tools, &["memory"]);
+    }
+
+    #[test]
+    fn browser_enabled_adds_tool() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::Browser);
+
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+        assert_contains_tool_names(&tools, &["browser"]);
+    }
+
     #[test]
     fn js_repl_tools_only_filters_model_tools() {
         let config = test_config();
@@ -2031,6 +2717,70 @@ mod tests {
         assert_contains_tool_specs(&filtered, &["js_repl", "js_repl_reset"]);
     }
 
+    #[test]
+    fn agent_role_allow_list_limits_model_tools() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let features = Features::with_defaults();
+        let mut tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+        tools_config.allowed_tools = Some(vec!["update_plan".to_string()]);
+
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+        let filtered = filter_tools_for_model(
+            tools.iter().map(|tool| tool.spec.clone()).collect(),
+            &tools_config,
+        );
+        assert_eq!(
+            filtered.iter().map(tool_name).collect::<Vec<_>>(),
+            vec!["update_plan"]
+        );
+        assert!(!tool_allowed_for_role(&tools_config, "shell"));
+    }
+
+    #[test]
+    fn disabled_builtin_tools_are_not_offered_or_registered() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let features = Features::with_defaults();
+        let mut tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Live),
+        });
+        tools_config.apply_builtin_tools(BuiltinTools {
+            shell: false,
+            apply_patch: false,
+            plan: false,
+            web_search: false,
+            view_image: false,
+        });
+
+        let (tools, registry) = build_specs(&tools_config, None, None, &[]).build();
+        let names: Vec<&str> = tools.iter().map(|tool| tool_name(&tool.spec)).collect();
+        for disabled in [
+            "shell",
+            "shell_command",
+            "exec_command",
+            "apply_patch",
+            "update_plan",
+            "web_search",
+            "view_image",
+        ] {
+            assert!(!names.contains(&disabled), "{disabled} in {names:?}");
+            assert!(
+                registry.handler(disabled).is_none(),
+                "{disabled} registered"
+            );
+        }
+        assert!(names.contains(&"list_mcp_resources"), "{names:?}");
+    }
+
     fn assert_model_tools(
         model_slug: &str,
         features: &Features,
@@ -2110,6 +2860,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn web_search_provider_replaces_hosted_tool_in_live_mode() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let features = Features::with_defaults();
+
+        let mut tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Live),
+        });
+        tools_config.web_search_provider = true;
+        let (tools, _) = build_specs(&tools_config, None, None, &[]).build();
+
+        let tool = find_tool(&tools, "web_search");
+        assert_eq!(tool.spec, create_web_search_provider_tool());
+    }
+
     #[test]
     fn test_build_specs_gpt5_codex_default() {
         let mut features = Features::with_defaults();
@@ -0,0 +1,477 @@
+//! Provider-backed web search.
+//!
+//! Queries a configured search API (Bing, Brave, or SearXNG), then fetches the
+//! top results and reduces each page to its readable text so the model gets
+//! ranked links plus page content in a single call.
+
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use codex_utils_string::take_bytes_at_char_boundary;
+use regex_lite::Regex;
+use reqwest::header::CONTENT_TYPE;
+use serde::Deserialize;
+
+use crate::config::types::WebSearchProviderConfig;
+use crate::config::types::WebSearchProviderKind;
+use crate::default_client::build_reqwest_client;
+
+const SEARCH_TIMEOUT: Duration = Duration::from_secs(15);
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+/// Upper bound on the bytes downloaded per fetched page, before extraction.
+const MAX_PAGE_DOWNLOAD_BYTES: usize = 2 * 1024 * 1024;
+/// Elements whose contents never contribute readable text.
+const NOISE_ELEMENTS: &[&str] = &[
+    "script", "style", "noscript", "template", "svg", "iframe", "nav", "header", "footer", "aside",
+    "form",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SearchResult {
+    pub(crate) title: String,
+    pub(crate) url: String,
+    pub(crate) snippet: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FetchedPage {
+    pub(crate) url: String,
+    pub(crate) text: String,
+}
+
+pub(crate) struct WebSearchClient<'a> {
+    config: &'a WebSearchProviderConfig,
+    client: reqwest::Client,
+}
+
+impl<'a> WebSearchClient<'a> {
+    pub(crate) fn new(config: &'a WebSearchProviderConfig) -> Self {
+        Self {
+            config,
+            client: build_reqwest_client(),
+        }
+    }
+
+    pub(crate) async fn search(
+        &self,
+        query: &str,
+        max_results: usize,
+    ) -> Result<Vec<SearchResult>, String> {
+        let count = max_results.to_string();
+        let base_url = &self.config.base_url;
+        let request = match self.config.kind {
+            WebSearchProviderKind::Bing => self
+                .client
+                .get(format!("{base_url}/v7.0/search"))
+                .query(&[("q", query), ("count", count.as_str())])
+                .header("Ocp-Apim-Subscription-Key", self.api_key()?),
+            WebSearchProviderKind::Brave => self
+                .client
+                .get(format!("{base_url}/res/v1/web/search"))
+                .query(&[("q", query), ("count", count.as_str())])
+                .header("Accept", "application/json")
+                .header("X-Subscription-Token", self.api_key()?),
+            WebSearchProviderKind::Searxng => self
+                .client
+                .get(format!("{base_url}/search"))
+                .query(&[("q", query), ("format", "json")]),
+        };
+
+        let response = request
+            .timeout(SEARCH_TIMEOUT)
+            .send()
+            .await
+            .map_err(|err| format!("search request failed: {err}"))?;
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|err| format!("failed to read search response: {err}"))?;
+        if !status.is_success() {
+            return Err(format!(
+                "search provider returned {status}: {}",
+                take_bytes_at_char_boundary(&body, 512)
+            ));
+        }
+
+        let mut results = parse_results(self.config.kind, &body)?;
+        results.truncate(max_results);
+        Ok(results)
+    }
+
+    /// Downloads `url` and extracts its readable text. Non-text responses are
+    /// reported as errors so the caller can skip them.
+    pub(crate) async fn fetch(&self, url: &str) -> Result<FetchedPage, String> {
+        let mut response = self
+            .client
+            .get(url)
+            .timeout(FETCH_TIMEOUT)
+            .send()
+            .await
+            .map_err(|err| format!("fetch failed: {err}"))?;
+        if !response.status().is_success() {
+            return Err(format!("fetch returned {}", response.status()));
+        }
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("text/html")
+            .to_ascii_lowercase();
+        let is_html = content_type.contains("html");
+        if !is_html && !content_type.starts_with("text/") {
+            return Err(format!("unsupported content type `{content_type}`"));
+        }
+        let final_url = response.url().to_string();
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|err| format!("fetch failed: {err}"))?
+        {
+            body.extend_from_slice(&chunk);
+            if body.len() >= MAX_PAGE_DOWNLOAD_BYTES {
+                body.truncate(MAX_PAGE_DOWNLOAD_BYTES);
+                break;
+            }
+        }
+        let body = String::from_utf8_lossy(&body);
+        let text = if is_html {
+            extract_readable_text(&body)
+        } else {
+            body.into_owned()
+        };
+        Ok(FetchedPage {
+            url: final_url,
+            text,
+        })
+    }
+
+    fn api_key(&self) -> Result<String, String> {
+        let Some(var) = self.config.api_key_env.as_deref() else {
+            return Err("web_search_provider.api_key_env is not configured".to_string());
+        };
+        std::env::var(var)
+            .ok()
+            .filter(|key| !key.trim().is_empty())
+            .ok_or_else(|| format!("environment variable `{var}` is not set"))
+    }
+}
+
+#[derive(Deserialize)]
+struct BingResponse {
+    #[serde(rename = "webPages")]
+    web_pages: Option<BingWebPages>,
+}
+
+#[derive(Deserialize)]
+struct BingWebPages {
+    #[serde(default)]
+    value: Vec<BingResult>,
+}
+
+#[derive(Deserialize)]
+struct BingResult {
+    name: String,
+    url: String,
+    #[serde(default)]
+    snippet: String,
+}
+
+#[derive(Deserialize)]
+struct BraveResponse {
+    web: Option<BraveWeb>,
+}
+
+#[derive(Deserialize)]
+struct BraveWeb {
+    #[serde(default)]
+    results: Vec<BraveResult>,
+}
+
+#[derive(Deserialize)]
+struct BraveResult {
+    title: String,
+    url: String,
+    #[serde(default)]
+    description: String,
+}
+
+#[derive(Deserialize)]
+struct SearxngResponse {
+    #[serde(default)]
+    results: Vec<SearxngResult>,
+}
+
+#[derive(Deserialize)]
+struct SearxngResult {
+    #[serde(default)]
+    title: String,
+    url: String,
+    #[serde(default)]
+    content: String,
+}
+
+fn parse_results(kind: WebSearchProviderKind, body: &str) -> Result<Vec<SearchResult>, String> {
+    let parse_error = |err: serde_json::Error| format!("failed to parse search response: {err}");
+    let results = match kind {
+        WebSearchProviderKind::Bing => serde_json::from_str::<BingResponse>(body)
+            .map_err(parse_error)?
+            .web_pages
+            .map(|pages| pages.value)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|result| SearchResult {
+                title: result.name,
+                url: result.url,
+                snippet: result.snippet,
+            })
+            .collect(),
+        WebSearchProviderKind::Brave => serde_json::from_str::<BraveResponse>(body)
+            .map_err(parse_error)?
+            .web
+            .map(|web| web.results)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|result| SearchResult {
+                title: result.title,
+                url: result.url,
+                // Brave highlights matches with inline markup.
+                snippet: extract_readable_text(&result.description),
+            })
+            .collect(),
+        WebSearchProviderKind::Searxng => serde_json::from_str::<SearxngResponse>(body)
+            .map_err(parse_error)?
+            .results
+            .into_iter()
+            .map(|result| SearchResult {
+                title: result.title,
+                url: result.url,
+                snippet: result.content,
+            })
+            .collect(),
+    };
+    Ok(results)
+}
+
+/// Renders results and fetched pages into a single text block that fits in
+/// `max_bytes`. The ranked list is emitted first; page content fills whatever
+/// budget remains, in rank order.
+pub(crate) fn format_output(
+    query: &str,
+    results: &[SearchResult],
+    pages: &[(usize, Result<FetchedPage, String>)],
+    max_bytes: usize,
+) -> String {
+    if results.is_empty() {
+        return format!("No results for \"{query}\".");
+    }
+
+    let mut output = format!("Results for \"{query}\":\n");
+    for (index, result) in results.iter().enumerate() {
+        output.push_str(&format!(
+            "\n{rank}. {title}\n   {url}\n",
+            rank = index + 1,
+            title = result.title,
+            url = result.url
+        ));
+        if !result.snippet.is_empty() {
+            output.push_str(&format!("   {}\n", result.snippet));
+        }
+    }
+
+    for (index, page) in pages {
+        if output.len() >= max_bytes {
+            break;
+        }
+        let section = match page {
+            Ok(page) => format!(
+                "\n--- [{rank}] {url} ---\n{text}\n",
+                rank = index + 1,
+                url = page.url,
+                text = page.text
+            ),
+            Err(err) => format!("\n--- [{rank}] unavailable: {err} ---\n", rank = index + 1),
+        };
+        output.push_str(&section);
+    }
+
+    if output.len() > max_bytes {
+        let mut truncated = take_bytes_at_char_boundary(&output, max_bytes).to_string();
+        truncated.push_str(&format!("\n[output truncated at {max_bytes} bytes]"));
+        return truncated;
+    }
+    output
+}
+
+static COMMENT_RE: LazyLock<Regex> = LazyLock::new(|| regex(r"(?s)<!--.*?-->"));
+static NOISE_RES: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    NOISE_ELEMENTS
+        .iter()
+        .map(|tag| regex(&format!(r"(?is)<{tag}\b[^>]*>.*?</{tag}\s*>")))
+        .collect()
+});
+static ARTICLE_RE: LazyLock<Regex> =
+    LazyLock::new(|| regex(r"(?is)<article\b[^>]*>(.*?)</article\s*>"));
+static MAIN_RE: LazyLock<Regex> = LazyLock::new(|| regex(r"(?is)<main\b[^>]*>(.*?)</main\s*>"));
+static BODY_RE: LazyLock<Regex> = LazyLock::new(|| regex(r"(?is)<body\b[^>]*>(.*)</body\s*>"));
+static BLOCK_TAG_RE: LazyLock<Regex> = LazyLock::new(|| {
+    regex(
+        r"(?i)</?(p|div|br|li|ul|ol|h[1-6]|tr|table|section|article|pre|blockquote|dd|dt)\b[^>]*>",
+    )
+});
+static TAG_RE: LazyLock<Regex> = LazyLock::new(|| regex(r"(?s)<[^>]*>"));
+static ENTITY_RE: LazyLock<Regex> =
+    LazyLock::new(|| regex(r"&(#[0-9]+|#[xX][0-9a-fA-F]+|[a-zA-Z]+);"));
+
+#[expect(clippy::expect_used)]
+fn regex(pattern: &str) -> Regex {
+    Regex::new(pattern).expect("static regex should compile")
+}
+
+/// A small readability pass: drops boilerplate elements, prefers the largest
+/// `<article>` (then `<main>`, then `<body>`), and flattens the remaining
+/// markup to text with one block per line.
+pub(crate) fn extract_readable_text(html: &str) -> String {
+    let mut html = COMMENT_RE.replace_all(html, "").into_owned();
+    for noise in NOISE_RES.iter() {
+        html = noise.replace_all(&html, "").into_owned();
+    }
+
+    let content = ARTICLE_RE
+        .captures_iter(&html)
+        .filter_map(|captures| captures.get(1))
+        .map(|m| m.as_str())
+        .max_by_key(|article| article.len())
+        .or_else(|| {
+            MAIN_RE
+                .captures(&html)
+                .and_then(|captures| captures.get(1))
+                .map(|m| m.as_str())
+        })
+        .or_else(|| {
+            BODY_RE
+                .captures(&html)
+                .and_then(|captures| captures.get(1))
+                .map(|m| m.as_str())
+        })
+        .unwrap_or(&html);
+
+    let content = BLOCK_TAG_RE.replace_all(content, "\n");
+    let content = TAG_RE.replace_all(&content, "");
+    let content = ENTITY_RE.replace_all(&content, |captures: &regex_lite::Captures<'_>| {
+        decode_entity(&captures[1]).unwrap_or_else(|| captures[0].to_string())
+    });
+
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !line.is_empty() {
+            lines.push(line);
+        }
+    }
+    lines.join("\n")
+}
+
+fn decode_entity(entity: &str) -> Option<String> {
+    if let Some(code) = entity.strip_prefix('#') {
+        let value = match code.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => code.parse().ok()?,
+        };
+        return char::from_u32(value).map(String::from);
+    }
+    let decoded = match entity {
+        "amp" => "&",
+        "lt" => "<",
+        "gt" => ">",
+        "quot" => "\"",
+        "apos" => "'",
+        "nbsp" => " ",
+        "mdash" => "\u{2014}",
+        "ndash" => "\u{2013}",
+        "hellip" => "\u{2026}",
+        _ => return None,
+    };
+    Some(decoded.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn extracts_article_text_and_drops_boilerplate() {
+        let html = r#"<html><head><title>T</title><style>p{}</style></head>
+<body><nav>Home | About</nav>
+<article><h1>Heading</h1><p>First &amp; <b>bold</b>   paragraph.</p>
+<script>var x = 1;</script><p>Second&#39;s line</p></article>
+<footer>Copyright</footer></body></html>"#;
+        assert_eq!(
+            extract_readable_text(html),
+            "Heading\nFirst & bold paragraph.\nSecond's line"
+        );
+    }
+
+    #[test]
+    fn parses_provider_payloads() {
+        let bing = r#"{"webPages":{"value":[{"name":"A","url":"https://a","snippet":"sa"}]}}"#;
+        let brave = r#"{"web":{"results":[{"title":"B","url":"https://b","description":"<strong>sb</strong>"}]}}"#;
+        let searxng = r#"{"results":[{"title":"C","url":"https://c","content":"sc"}]}"#;
+        let parsed = [
+            (WebSearchProviderKind::Bing, bing),
+            (WebSearchProviderKind::Brave, brave),
+            (WebSearchProviderKind::Searxng, searxng),
+        ]
+        .into_iter()
+        .map(|(kind, body)| parse_results(kind, body).expect("parse results"))
+        .collect::<Vec<_>>();
+        let result = |title: &str, url: &str, snippet: &str| {
+            vec![SearchResult {
+                title: title.to_string(),
+                url: url.to_string(),
+                snippet: snippet.to_string(),
+            }]
+        };
+        assert_eq!(
+            parsed,
+            vec![
+                result("A", "https://a", "sa"),
+                result("B", "https://b", "sb"),
+                result("C", "https://c", "sc"),
+            ]
+        );
+    }
+
+    #[test]
+    fn output_respects_byte_budget() {
+        let results = vec![SearchResult {
+            title: "Title".to_string(),
+            url: "https://example.com".to_string(),
+            snippet: String::new(),
+        }];
+        let pages = vec![(
+            0,
+            Ok(FetchedPage {
+                url: "https://example.com".to_string(),
+                text: "x".repeat(1000),
+            }),
+        )];
+        let output = format_output("q", &results, &pages, 100);
+        assert_eq!(
+            output,
+            format!(
+                "{}\n[output truncated at 100 bytes]",
+                take_bytes_at_char_boundary(
+                    &format!(
+                        "Results for \"q\":\n\n1. Title\n   https://example.com\n\n--- [1] https://example.com ---\n{}\n",
+                        "x".repeat(1000)
+                    ),
+                    100
+                )
+            )
+        );
+    }
+}
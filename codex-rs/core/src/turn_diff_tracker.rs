@@ -219,6 +219,20 @@ impl TurnDiffTracker {
         if s.len() == 40 { Some(s) } else { None }
     }
 
+    /// Every external path touched by the tracked changes: where each file started and where
+    /// it is now, so moves and deletions can be staged alongside edits.
+    pub fn touched_paths(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = self
+            .baseline_file_info
+            .values()
+            .map(|info| info.path.clone())
+            .chain(self.temp_name_to_current_path.values().cloned())
+            .collect();
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+
     /// Recompute the aggregated unified diff by comparing all of the in-memory snapshots that were
     /// collected before the first time they were touched by apply_patch during this turn with
     /// the current repo state.
@@ -647,6 +661,34 @@ index {left_oid}..{right_oid}
         assert_eq!(out, expected);
     }
 
+    #[test]
+    fn touched_paths_include_move_source_and_destination() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("src.txt");
+        let dest = dir.path().join("dst.txt");
+        let added = dir.path().join("added.txt");
+        fs::write(&src, "line\n").unwrap();
+
+        let mut acc = TurnDiffTracker::new();
+        acc.on_patch_begin(&HashMap::from([
+            (
+                src.clone(),
+                FileChange::Update {
+                    unified_diff: "".to_owned(),
+                    move_path: Some(dest.clone()),
+                },
+            ),
+            (
+                added.clone(),
+                FileChange::Add {
+                    content: "new\n".to_owned(),
+                },
+            ),
+        ]));
+
+        assert_eq!(acc.touched_paths(), vec![added, dest, src]);
+    }
+
     #[test]
     fn move_without_1change_yields_no_diff() {
         let dir = tempdir().unwrap();
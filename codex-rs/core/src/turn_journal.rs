@@ -0,0 +1,529 @@
+//! Crash-recovery journal for the turn in flight.
+//!
+//! The rollout only persists completed items, so a session that crashes or is
+//! OOM-killed mid-turn leaves no trace of the tool calls that were running or
+//! of the answer that was being streamed. While a turn runs, [`TurnJournal`]
+//! mirrors that state into `~/.codex/inflight/<thread_id>.json` and deletes the
+//! file once the turn completes or is aborted.
+//!
+//! Each live session also holds an exclusive advisory lock on
+//! `<thread_id>.lock` next to the journal. A journal whose lock can be taken
+//! belongs to a process that is gone: [`find_orphaned_turns`] reports those so
+//! the next launch can offer to resume the session, and resuming it injects a
+//! `<turn_interrupted>` note telling the model what was cut off.
+
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::fs::TryLockError;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use chrono::SecondsFormat;
+use chrono::Utc;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::SessionSource;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::parse_command::shlex_join;
+use crate::session_prefix::TURN_INTERRUPTED_CLOSE_TAG;
+use crate::session_prefix::TURN_INTERRUPTED_OPEN_TAG;
+
+/// Directory inside `~/.codex` that holds the journals of turns in flight.
+pub const INFLIGHT_DIR: &str = "inflight";
+
+/// Streaming deltas rewrite the journal at most this often.
+const PARTIAL_WRITE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Only the start of a long partial answer is kept.
+const MAX_PARTIAL_MESSAGE_BYTES: usize = 8 * 1024;
+
+/// State of a turn that had not finished when it was last journaled.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InFlightTurn {
+    pub thread_id: ThreadId,
+    pub source: SessionSource,
+    pub rollout_path: PathBuf,
+    pub cwd: PathBuf,
+    pub turn_id: String,
+    /// RFC 3339 timestamp of `TurnStarted`.
+    pub started_at: String,
+    /// Tool calls that had begun but not ended, oldest first.
+    pub pending_tool_calls: Vec<PendingToolCall>,
+    /// Assistant text streamed since the last completed message.
+    pub partial_agent_message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingToolCall {
+    pub call_id: String,
+    /// Short human-readable summary, e.g. the command line.
+    pub description: String,
+}
+
+impl InFlightTurn {
+    /// Model-visible note recorded when the session is resumed.
+    pub(crate) fn recovery_note(&self) -> String {
+        let mut note = format!(
+            "{TURN_INTERRUPTED_OPEN_TAG}\nCodex exited unexpectedly during the previous turn (started {}). The turn did not finish and its results may be incomplete.",
+            self.started_at
+        );
+        if !self.pending_tool_calls.is_empty() {
+            note.push_str(
+                "\nThese tool calls were still running; they may have partially executed, so verify current state before retrying:",
+            );
+            for call in &self.pending_tool_calls {
+                note.push_str("\n- ");
+                note.push_str(&call.description);
+            }
+        }
+        if !self.partial_agent_message.is_empty() {
+            note.push_str("\nYour reply was cut off after:\n");
+            note.push_str(&self.partial_agent_message);
+        }
+        note.push('\n');
+        note.push_str(TURN_INTERRUPTED_CLOSE_TAG);
+        note
+    }
+
+    fn observe(&mut self, msg: &EventMsg) {
+        match msg {
+            EventMsg::ExecCommandBegin(begin) => {
+                self.begin_call(&begin.call_id, shlex_join(&begin.command));
+            }
+            EventMsg::McpToolCallBegin(begin) => self.begin_call(
+                &begin.call_id,
+                format!("{}/{}", begin.invocation.server, begin.invocation.tool),
+            ),
+            EventMsg::PatchApplyBegin(begin) => {
+                let mut paths: Vec<String> = begin
+                    .changes
+                    .keys()
+                    .map(|path| path.display().to_string())
+                    .collect();
+                paths.sort();
+                self.begin_call(&begin.call_id, format!("apply_patch {}", paths.join(" ")));
+            }
+            EventMsg::WebSearchBegin(begin) => {
+                self.begin_call(&begin.call_id, "web_search".to_string());
+            }
+            EventMsg::ExecCommandEnd(end) => self.end_call(&end.call_id),
+            EventMsg::McpToolCallEnd(end) => self.end_call(&end.call_id),
+            EventMsg::PatchApplyEnd(end) => self.end_call(&end.call_id),
+            EventMsg::WebSearchEnd(end) => self.end_call(&end.call_id),
+            EventMsg::AgentMessageDelta(delta) => {
+                let remaining =
+                    MAX_PARTIAL_MESSAGE_BYTES.saturating_sub(self.partial_agent_message.len());
+                let mut end = remaining.min(delta.delta.len());
+                while !delta.delta.is_char_boundary(end) {
+                    end -= 1;
+                }
+                self.partial_agent_message.push_str(&delta.delta[..end]);
+            }
+            EventMsg::AgentMessage(_) => self.partial_agent_message.clear(),
+            _ => {}
+        }
+    }
+
+    fn begin_call(&mut self, call_id: &str, description: String) {
+        if self
+            .pending_tool_calls
+            .iter()
+            .all(|call| call.call_id != call_id)
+        {
+            self.pending_tool_calls.push(PendingToolCall {
+                call_id: call_id.to_string(),
+                description,
+            });
+        }
+    }
+
+    fn end_call(&mut self, call_id: &str) {
+        self.pending_tool_calls
+            .retain(|call| call.call_id != call_id);
+    }
+}
+
+struct JournalState {
+    turn: Option<InFlightTurn>,
+    last_write: Option<Instant>,
+}
+
+/// Journal of the current session's turn in flight.
+pub(crate) struct TurnJournal {
+    path: PathBuf,
+    lock_path: PathBuf,
+    thread_id: ThreadId,
+    source: SessionSource,
+    rollout_path: PathBuf,
+    cwd: PathBuf,
+    /// Held for the life of the session; closing it releases the lock.
+    _lock: File,
+    state: Mutex<JournalState>,
+    /// Journal left behind by a previous process for this thread.
+    recovered: std::sync::Mutex<Option<InFlightTurn>>,
+}
+
+impl TurnJournal {
+    /// Takes ownership of the journal for `thread_id`. Returns `None` (after
+    /// logging why) when the journal cannot be used, e.g. because another
+    /// live process already owns this thread.
+    pub(crate) fn open(
+        codex_home: &Path,
+        thread_id: ThreadId,
+        source: SessionSource,
+        rollout_path: PathBuf,
+        cwd: PathBuf,
+    ) -> Option<Self> {
+        let dir = codex_home.join(INFLIGHT_DIR);
+        let (path, lock_path) = journal_paths(&dir, thread_id);
+        let lock = match open_lock(&dir, &lock_path) {
+            Ok(lock) => lock,
+            Err(err) => {
+                warn!("turn journal disabled for {thread_id}: {err}");
+                return None;
+            }
+        };
+        let recovered = read_journal(&path);
+        Some(Self {
+            path,
+            lock_path,
+            thread_id,
+            source,
+            rollout_path,
+            cwd,
+            _lock: lock,
+            state: Mutex::new(JournalState {
+                turn: None,
+                last_write: None,
+            }),
+            recovered: std::sync::Mutex::new(recovered),
+        })
+    }
+
+    /// The turn a previous process was running when it died, if any. Returns
+    /// it only once and deletes its journal.
+    pub(crate) async fn take_recovered(&self) -> Option<InFlightTurn> {
+        let recovered = self
+            .recovered
+            .lock()
+            .ok()
+            .and_then(|mut recovered| recovered.take())?;
+        let state = self.state.lock().await;
+        if state.turn.is_none() {
+            self.remove().await;
+        }
+        Some(recovered)
+    }
+
+    /// Updates the journal for an event the session is about to emit.
+    pub(crate) async fn observe(&self, msg: &EventMsg) {
+        let mut state = self.state.lock().await;
+        match msg {
+            EventMsg::TurnStarted(started) => {
+                state.turn = Some(InFlightTurn {
+                    thread_id: self.thread_id,
+                    source: self.source.clone(),
+                    rollout_path: self.rollout_path.clone(),
+                    cwd: self.cwd.clone(),
+                    turn_id: started.turn_id.clone(),
+                    started_at: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+                    pending_tool_calls: Vec::new(),
+                    partial_agent_message: String::new(),
+                });
+            }
+            EventMsg::TurnComplete(_) | EventMsg::TurnAborted(_) | EventMsg::ShutdownComplete => {
+                if state.turn.take().is_some() {
+                    self.remove().await;
+                }
+                return;
+            }
+            _ => {
+                let Some(turn) = state.turn.as_mut() else {
+                    return;
+                };
+                let before = turn.clone();
+                turn.observe(msg);
+                if *turn == before {
+                    return;
+                }
+                let only_partial_changed = turn.pending_tool_calls == before.pending_tool_calls;
+                if only_partial_changed
+                    && state
+                        .last_write
+                        .is_some_and(|last| last.elapsed() < PARTIAL_WRITE_INTERVAL)
+                {
+                    return;
+                }
+            }
+        }
+        if let Some(turn) = state.turn.clone() {
+            self.write(&turn).await;
+            state.last_write = Some(Instant::now());
+        }
+    }
+
+    async fn write(&self, turn: &InFlightTurn) {
+        let contents = match serde_json::to_vec(turn) {
+            Ok(contents) => contents,
+            Err(err) => {
+                warn!("failed to serialize turn journal: {err}");
+                return;
+            }
+        };
+        let tmp_path = self.path.with_extension("json.tmp");
+        let result = async {
+            tokio::fs::write(&tmp_path, contents).await?;
+            tokio::fs::rename(&tmp_path, &self.path).await
+        }
+        .await;
+        if let Err(err) = result {
+            warn!(
+                "failed to write turn journal {}: {err}",
+                self.path.display()
+            );
+        }
+    }
+
+    async fn remove(&self) {
+        if let Err(err) = tokio::fs::remove_file(&self.path).await
+            && err.kind() != io::ErrorKind::NotFound
+        {
+            warn!(
+                "failed to remove turn journal {}: {err}",
+                self.path.display()
+            );
+        }
+    }
+}
+
+impl Drop for TurnJournal {
+    fn drop(&mut self) {
+        // A journal still on disk has no lock file any more, which
+        // `find_orphaned_turns` treats the same as an unlocked one.
+        let _ = std::fs::remove_file(&self.lock_path);
+    }
+}
+
+/// Journals of turns whose process is no longer running, newest first.
+pub fn find_orphaned_turns(codex_home: &Path) -> Vec<InFlightTurn> {
+    let dir = codex_home.join(INFLIGHT_DIR);
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+    let mut orphans: Vec<InFlightTurn> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| {
+            let turn = read_journal(&path)?;
+            let (_, lock_path) = journal_paths(&dir, turn.thread_id);
+            is_unlocked(&lock_path).then_some(turn)
+        })
+        .collect();
+    orphans.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+    orphans
+}
+
+/// Deletes the journal of an orphaned turn the user chose not to resume.
+pub fn discard_orphaned_turn(codex_home: &Path, thread_id: ThreadId) -> io::Result<()> {
+    let dir = codex_home.join(INFLIGHT_DIR);
+    let (path, lock_path) = journal_paths(&dir, thread_id);
+    if !is_unlocked(&lock_path) {
+        return Ok(());
+    }
+    for path in [path, lock_path] {
+        match std::fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(())
+}
+
+/// True when a running Codex process has the thread open.
+pub fn is_thread_live(codex_home: &Path, thread_id: ThreadId) -> bool {
+    let (_, lock_path) = journal_paths(&codex_home.join(INFLIGHT_DIR), thread_id);
+    !is_unlocked(&lock_path)
+}
+
+fn journal_paths(dir: &Path, thread_id: ThreadId) -> (PathBuf, PathBuf) {
+    (
+        dir.join(format!("{thread_id}.json")),
+        dir.join(format!("{thread_id}.lock")),
+    )
+}
+
+fn open_lock(dir: &Path, lock_path: &Path) -> io::Result<File> {
+    std::fs::create_dir_all(dir)?;
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(lock_path)?;
+    match file.try_lock() {
+        Ok(()) => Ok(file),
+        Err(TryLockError::WouldBlock) => Err(io::Error::new(
+            io::ErrorKind::WouldBlock,
+            "another Codex process owns this thread",
+        )),
+        Err(TryLockError::Error(err)) => Err(err),
+    }
+}
+
+/// True when no live process holds `lock_path`.
+fn is_unlocked(lock_path: &Path) -> bool {
+    let file = match File::open(lock_path) {
+        Ok(file) => file,
+        Err(err) => return err.kind() == io::ErrorKind::NotFound,
+    };
+    // The probe lock is released when `file` is dropped.
+    file.try_lock().is_ok()
+}
+
+fn read_journal(path: &Path) -> Option<InFlightTurn> {
+    let contents = std::fs::read(path).ok()?;
+    match serde_json::from_slice(&contents) {
+        Ok(turn) => Some(turn),
+        Err(err) => {
+            warn!("ignoring unreadable turn journal {}: {err}", path.display());
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::AgentMessageDeltaEvent;
+    use codex_protocol::protocol::AgentMessageEvent;
+    use codex_protocol::protocol::ExecCommandBeginEvent;
+    use codex_protocol::protocol::ExecCommandSource;
+    use codex_protocol::protocol::TurnCompleteEvent;
+    use codex_protocol::protocol::TurnStartedEvent;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn open_journal(codex_home: &Path, thread_id: ThreadId) -> TurnJournal {
+        TurnJournal::open(
+            codex_home,
+            thread_id,
+            SessionSource::Cli,
+            PathBuf::from("/sessions/rollout.jsonl"),
+            PathBuf::from("/repo"),
+        )
+        .expect("open journal")
+    }
+
+    fn turn_started() -> EventMsg {
+        EventMsg::TurnStarted(TurnStartedEvent {
+            turn_id: "turn-1".to_string(),
+            model_context_window: None,
+            collaboration_mode_kind: Default::default(),
+        })
+    }
+
+    fn exec_begin(call_id: &str) -> EventMsg {
+        EventMsg::ExecCommandBegin(ExecCommandBeginEvent {
+            call_id: call_id.to_string(),
+            process_id: None,
+            turn_id: "turn-1".to_string(),
+            command: vec!["cargo".to_string(), "test".to_string()],
+            cwd: PathBuf::from("/repo"),
+            parsed_cmd: Vec::new(),
+            source: ExecCommandSource::Agent,
+            interaction_input: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn interrupted_turn_is_orphaned_once_the_session_is_gone() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let thread_id = ThreadId::new();
+        let journal = open_journal(codex_home.path(), thread_id);
+        journal.observe(&turn_started()).await;
+        journal.observe(&exec_begin("call-1")).await;
+        journal
+            .observe(&EventMsg::AgentMessageDelta(AgentMessageDeltaEvent {
+                delta: "Running the tests".to_string(),
+            }))
+            .await;
+
+        assert_eq!(find_orphaned_turns(codex_home.path()), Vec::new());
+
+        drop(journal);
+        let orphans = find_orphaned_turns(codex_home.path());
+        assert_eq!(orphans.len(), 1);
+        let orphan = &orphans[0];
+        assert_eq!(orphan.thread_id, thread_id);
+        assert_eq!(orphan.turn_id, "turn-1");
+        assert_eq!(
+            orphan.pending_tool_calls,
+            vec![PendingToolCall {
+                call_id: "call-1".to_string(),
+                description: "cargo test".to_string(),
+            }]
+        );
+        // The delta arrived within the write interval of the exec begin.
+        assert_eq!(orphan.partial_agent_message, "");
+
+        let resumed = open_journal(codex_home.path(), thread_id);
+        assert_eq!(resumed.take_recovered().await, Some(orphan.clone()));
+        assert_eq!(resumed.take_recovered().await, None);
+        drop(resumed);
+        assert_eq!(find_orphaned_turns(codex_home.path()), Vec::new());
+    }
+
+    #[tokio::test]
+    async fn finished_turn_leaves_no_journal() {
+        let codex_home = TempDir::new().expect("tempdir");
+        let journal = open_journal(codex_home.path(), ThreadId::new());
+        journal.observe(&turn_started()).await;
+        journal.observe(&exec_begin("call-1")).await;
+        journal
+            .observe(&EventMsg::TurnComplete(TurnCompleteEvent {
+                turn_id: "turn-1".to_string(),
+                last_agent_message: None,
+            }))
+            .await;
+        drop(journal);
+
+        assert_eq!(find_orphaned_turns(codex_home.path()), Vec::new());
+    }
+
+    #[test]
+    fn recovery_note_lists_pending_calls_and_partial_reply() {
+        let mut turn = InFlightTurn {
+            thread_id: ThreadId::new(),
+            source: SessionSource::Cli,
+            rollout_path: PathBuf::from("/sessions/rollout.jsonl"),
+            cwd: PathBuf::from("/repo"),
+            turn_id: "turn-1".to_string(),
+            started_at: "2026-01-01T00:00:00.000Z".to_string(),
+            pending_tool_calls: Vec::new(),
+            partial_agent_message: String::new(),
+        };
+        turn.observe(&exec_begin("call-1"));
+        turn.observe(&exec_begin("call-1"));
+        turn.observe(&EventMsg::AgentMessageDelta(AgentMessageDeltaEvent {
+            delta: "The failing test is".to_string(),
+        }));
+
+        assert_eq!(
+            turn.recovery_note(),
+            "<turn_interrupted>\nCodex exited unexpectedly during the previous turn (started 2026-01-01T00:00:00.000Z). The turn did not finish and its results may be incomplete.\nThese tool calls were still running; they may have partially executed, so verify current state before retrying:\n- cargo test\nYour reply was cut off after:\nThe failing test is\n</turn_interrupted>"
+        );
+
+        turn.observe(&EventMsg::AgentMessage(AgentMessageEvent {
+            message: "The failing test is fixed.".to_string(),
+        }));
+        assert_eq!(turn.partial_agent_message, "");
+    }
+}
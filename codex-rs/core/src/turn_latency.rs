@@ -0,0 +1,269 @@
+//! Per-turn latency breakdown by pipeline stage.
+//!
+//! Every turn carries a [`TurnLatency`] that the stages on its path record
+//! into: waiting for the task to start, the model's first event and the rest
+//! of its stream, tool dispatch, sandbox setup, sidecar round trips and
+//! approvals. When the turn finishes the totals are reported as a
+//! `TurnLatency` event, which tells users whether a slow turn was spent
+//! waiting on the model or on their own machine.
+//!
+//! Stages are summed across calls, so parallel tool calls (and tool calls
+//! running while the model is still streaming) can add up to more than the
+//! turn's wall time. The time tool calls overlapped a model stream is
+//! reported on its own, to show how much of the stream they hid.
+
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_protocol::protocol::TurnLatencyEvent;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LatencyStage {
+    /// From the turn being submitted to its task starting.
+    QueueWait,
+    /// From sending a model request to its first streamed event.
+    ModelTtfb,
+    /// From a response's first streamed event to its last.
+    ModelStream,
+    /// Running a tool call, approvals, sandbox setup and sidecars included.
+    ToolDispatch,
+    /// Preparing each sandboxed attempt, managed network approval included,
+    /// before the tool runs.
+    SandboxSetup,
+    /// A request to a sidecar process (`py_repl`, `js_repl`, the browser).
+    SidecarRoundTrip,
+    /// Waiting for the user to answer an approval prompt.
+    ApprovalWait,
+    /// The part of `ToolDispatch` during which a model response was still
+    /// streaming.
+    ToolStreamOverlap,
+}
+
+impl LatencyStage {
+    const COUNT: usize = 8;
+
+    fn index(self) -> usize {
+        match self {
+            Self::QueueWait => 0,
+            Self::ModelTtfb => 1,
+            Self::ModelStream => 2,
+            Self::ToolDispatch => 3,
+            Self::SandboxSetup => 4,
+            Self::SidecarRoundTrip => 5,
+            Self::ApprovalWait => 6,
+            Self::ToolStreamOverlap => 7,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct StageTotal {
+    elapsed: Duration,
+    count: u32,
+}
+
+#[derive(Debug)]
+pub(crate) struct TurnLatency {
+    submitted_at: Instant,
+    totals: StdMutex<[StageTotal; LatencyStage::COUNT]>,
+    /// Start and, once finished, end of each model stream of the turn.
+    model_streams: StdMutex<Vec<(Instant, Option<Instant>)>>,
+}
+
+impl TurnLatency {
+    pub(crate) fn new() -> Self {
+        Self {
+            submitted_at: Instant::now(),
+            totals: StdMutex::new([StageTotal::default(); LatencyStage::COUNT]),
+            model_streams: StdMutex::new(Vec::new()),
+        }
+    }
+
+    pub(crate) fn record(&self, stage: LatencyStage, elapsed: Duration) {
+        let mut totals = self
+            .totals
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let total = &mut totals[stage.index()];
+        total.elapsed = total.elapsed.saturating_add(elapsed);
+        total.count = total.count.saturating_add(1);
+    }
+
+    pub(crate) fn record_since(&self, stage: LatencyStage, started_at: Instant) {
+        self.record(stage, started_at.elapsed());
+    }
+
+    /// Records the time since the turn was submitted as its queue wait. Only
+    /// the first call counts.
+    pub(crate) fn record_task_started(&self) {
+        if self.total(LatencyStage::QueueWait).count == 0 {
+            self.record_since(LatencyStage::QueueWait, self.submitted_at);
+        }
+    }
+
+    /// Marks a model response as streaming from `started_at` until the
+    /// returned guard is dropped, so tool calls running meanwhile are counted
+    /// as overlapping it.
+    pub(crate) fn model_stream(&self, started_at: Instant) -> ModelStreamWindow<'_> {
+        self.model_streams
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push((started_at, None));
+        ModelStreamWindow { latency: self }
+    }
+
+    /// Time between `started_at` and `ended_at` during which a model response
+    /// was streaming.
+    fn model_stream_overlap(&self, started_at: Instant, ended_at: Instant) -> Duration {
+        self.model_streams
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .iter()
+            .map(|(stream_start, stream_end)| {
+                let start = started_at.max(*stream_start);
+                let end = stream_end.map_or(ended_at, |stream_end| stream_end.min(ended_at));
+                end.saturating_duration_since(start)
+            })
+            .sum()
+    }
+
+    /// Times `stage` until the returned guard is dropped, so early returns
+    /// are counted too.
+    pub(crate) fn span(&self, stage: LatencyStage) -> LatencySpan<'_> {
+        LatencySpan {
+            latency: self,
+            stage,
+            started_at: Instant::now(),
+        }
+    }
+
+    fn total(&self, stage: LatencyStage) -> StageTotal {
+        self.totals
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)[stage.index()]
+    }
+
+    /// The breakdown so far, for the turn `turn_id`.
+    pub(crate) fn to_event(&self, turn_id: String) -> TurnLatencyEvent {
+        let ms = |stage| duration_ms(self.total(stage).elapsed);
+        let count = |stage| self.total(stage).count;
+        TurnLatencyEvent {
+            turn_id,
+            total_ms: duration_ms(self.submitted_at.elapsed()),
+            queue_wait_ms: ms(LatencyStage::QueueWait),
+            model_ttfb_ms: ms(LatencyStage::ModelTtfb),
+            model_stream_ms: ms(LatencyStage::ModelStream),
+            model_requests: count(LatencyStage::ModelTtfb),
+            tool_dispatch_ms: ms(LatencyStage::ToolDispatch),
+            tool_calls: count(LatencyStage::ToolDispatch),
+            tool_stream_overlap_ms: ms(LatencyStage::ToolStreamOverlap),
+            sandbox_setup_ms: ms(LatencyStage::SandboxSetup),
+            sidecar_round_trip_ms: ms(LatencyStage::SidecarRoundTrip),
+            sidecar_calls: count(LatencyStage::SidecarRoundTrip),
+            approval_wait_ms: ms(LatencyStage::ApprovalWait),
+            approvals: count(LatencyStage::ApprovalWait),
+        }
+    }
+}
+
+/// Returned by [`TurnLatency::span`]; records its stage when dropped.
+pub(crate) struct LatencySpan<'a> {
+    latency: &'a TurnLatency,
+    stage: LatencyStage,
+    started_at: Instant,
+}
+
+impl Drop for LatencySpan<'_> {
+    fn drop(&mut self) {
+        let ended_at = Instant::now();
+        self.latency
+            .record(self.stage, ended_at.duration_since(self.started_at));
+        if self.stage == LatencyStage::ToolDispatch {
+            let overlap = self.latency.model_stream_overlap(self.started_at, ended_at);
+            if !overlap.is_zero() {
+                self.latency
+                    .record(LatencyStage::ToolStreamOverlap, overlap);
+            }
+        }
+    }
+}
+
+/// Returned by [`TurnLatency::model_stream`]; ends the stream when dropped.
+pub(crate) struct ModelStreamWindow<'a> {
+    latency: &'a TurnLatency,
+}
+
+impl Drop for ModelStreamWindow<'_> {
+    fn drop(&mut self) {
+        let mut streams = self
+            .latency
+            .model_streams
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some((_, ended_at)) = streams.last_mut() {
+            ended_at.get_or_insert_with(Instant::now);
+        }
+    }
+}
+
+fn duration_ms(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn sums_stages_and_counts_calls() {
+        let latency = TurnLatency::new();
+        latency.record(LatencyStage::ModelTtfb, Duration::from_millis(300));
+        latency.record(LatencyStage::ModelTtfb, Duration::from_millis(200));
+        latency.record(LatencyStage::ModelStream, Duration::from_millis(1_500));
+        latency.record(LatencyStage::ApprovalWait, Duration::from_millis(4_000));
+        drop(latency.span(LatencyStage::ToolDispatch));
+
+        let event = latency.to_event("turn-1".to_string());
+        assert_eq!(event.turn_id, "turn-1");
+        assert_eq!(event.model_ttfb_ms, 500);
+        assert_eq!(event.model_requests, 2);
+        assert_eq!(event.model_stream_ms, 1_500);
+        assert_eq!(event.approval_wait_ms, 4_000);
+        assert_eq!(event.approvals, 1);
+        assert_eq!(event.tool_calls, 1);
+        assert_eq!(event.sidecar_calls, 0);
+    }
+
+    #[test]
+    fn counts_tool_time_overlapping_model_streams() {
+        let latency = TurnLatency::new();
+        let now = Instant::now();
+        let stream = latency.model_stream(now - Duration::from_millis(500));
+        assert_eq!(
+            latency.model_stream_overlap(now - Duration::from_millis(200), now),
+            Duration::from_millis(200)
+        );
+        drop(stream);
+
+        let after_stream = Instant::now() + Duration::from_millis(50);
+        assert_eq!(
+            latency.model_stream_overlap(after_stream, after_stream + Duration::from_millis(100)),
+            Duration::ZERO
+        );
+        assert_eq!(
+            latency.model_stream_overlap(now - Duration::from_millis(600), now),
+            Duration::from_millis(500)
+        );
+    }
+
+    #[test]
+    fn counts_queue_wait_once() {
+        let latency = TurnLatency::new();
+        latency.record_task_started();
+        latency.record_task_started();
+
+        assert_eq!(latency.total(LatencyStage::QueueWait).count, 1);
+    }
+}
@@ -0,0 +1,505 @@
+//! Daily and weekly token and dollar quotas per profile
+//! (`[profiles.<name>.quota]` in config.toml).
+//!
+//! The token usage of every model response is appended to a *usage ledger*
+//! shared by all sessions (`~/.codex/usage.jsonl`), one JSON object per line:
+//!
+//! ````text
+//! {"ts":<unix_seconds>,"profile":"team","input_tokens":1200,"cached_input_tokens":800,"output_tokens":300}
+//! ````
+//!
+//! Before each model request a session whose profile has a quota sums the
+//! profile's records over the last 24 hours and the last 7 days, so the limits
+//! hold across sessions and processes. Dollars are priced when read, with the
+//! prices configured now. Crossing 80% of a limit sends one warning per
+//! session; reaching it stops the turn. In the TUI the user may override the
+//! limit for the rest of the session instead.
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use codex_protocol::num_format::format_with_separators;
+use codex_protocol::protocol::SessionSource;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::types::TokenPrices;
+use crate::config::types::UsageQuotaConfig;
+use crate::protocol::CodexErrorInfo;
+use crate::protocol::ErrorEvent;
+use crate::protocol::EventMsg;
+use crate::protocol::TokenUsage;
+use crate::protocol::UsageQuotaExceededEvent;
+use crate::protocol::WarningEvent;
+
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+
+/// Filename of the usage ledger inside `~/.codex`.
+const USAGE_LEDGER_FILENAME: &str = "usage.jsonl";
+
+const DAY_SECS: u64 = 24 * 60 * 60;
+const WEEK_SECS: u64 = 7 * DAY_SECS;
+
+/// Share of a limit at which the session is warned.
+const WARN_FRACTION: f64 = 0.8;
+
+/// Once the ledger grows past this size, appending also drops the records no
+/// window needs any more.
+const PRUNE_THRESHOLD_BYTES: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct UsageRecord {
+    ts: u64,
+    profile: String,
+    input_tokens: i64,
+    cached_input_tokens: i64,
+    output_tokens: i64,
+}
+
+impl UsageRecord {
+    fn tokens(&self) -> u64 {
+        u64::try_from(self.input_tokens.saturating_add(self.output_tokens)).unwrap_or(0)
+    }
+
+    fn usd(&self, prices: &TokenPrices) -> f64 {
+        prices.usd(
+            self.input_tokens,
+            self.cached_input_tokens,
+            self.output_tokens,
+        )
+    }
+}
+
+/// Usage of one profile over one window.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct Usage {
+    tokens: u64,
+    usd: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Limit {
+    DailyTokens,
+    WeeklyTokens,
+    DailyUsd,
+    WeeklyUsd,
+}
+
+impl Limit {
+    const ALL: [Limit; 4] = [
+        Limit::DailyTokens,
+        Limit::WeeklyTokens,
+        Limit::DailyUsd,
+        Limit::WeeklyUsd,
+    ];
+
+    fn cap(self, config: &UsageQuotaConfig) -> Option<f64> {
+        match self {
+            Limit::DailyTokens => config.daily_tokens.map(|tokens| tokens as f64),
+            Limit::WeeklyTokens => config.weekly_tokens.map(|tokens| tokens as f64),
+            Limit::DailyUsd => config.daily_usd,
+            Limit::WeeklyUsd => config.weekly_usd,
+        }
+    }
+
+    fn used(self, day: Usage, week: Usage) -> f64 {
+        match self {
+            Limit::DailyTokens => day.tokens as f64,
+            Limit::WeeklyTokens => week.tokens as f64,
+            Limit::DailyUsd => day.usd,
+            Limit::WeeklyUsd => week.usd,
+        }
+    }
+
+    /// E.g. "1,650,000 of 2,000,000 tokens per day" or "$81.20 of $80.00 per
+    /// week".
+    fn describe(self, used: f64, cap: f64) -> String {
+        match self {
+            Limit::DailyTokens | Limit::WeeklyTokens => format!(
+                "{} of {} tokens per {}",
+                format_with_separators(used as i64),
+                format_with_separators(cap as i64),
+                self.period()
+            ),
+            Limit::DailyUsd | Limit::WeeklyUsd => {
+                format!("${used:.2} of ${cap:.2} per {}", self.period())
+            }
+        }
+    }
+
+    fn period(self) -> &'static str {
+        match self {
+            Limit::DailyTokens | Limit::DailyUsd => "day",
+            Limit::WeeklyTokens | Limit::WeeklyUsd => "week",
+        }
+    }
+}
+
+/// Limits that a check found newly past the warning threshold or reached.
+#[derive(Debug, Default, PartialEq)]
+struct QuotaReport {
+    warnings: Vec<String>,
+    exceeded: Vec<(Limit, String)>,
+}
+
+#[derive(Debug, Default)]
+struct QuotaState {
+    warned: HashSet<Limit>,
+    overridden: HashSet<Limit>,
+}
+
+/// The active profile's quota and what this session has been told about it.
+pub(crate) struct UsageQuota {
+    path: PathBuf,
+    config: UsageQuotaConfig,
+    state: Mutex<QuotaState>,
+}
+
+impl UsageQuota {
+    pub(crate) fn new(codex_home: &Path, config: UsageQuotaConfig) -> Self {
+        Self {
+            path: codex_home.join(USAGE_LEDGER_FILENAME),
+            config,
+            state: Mutex::new(QuotaState::default()),
+        }
+    }
+
+    /// Appends the usage of one model response to the ledger. Failures are
+    /// logged; an unwritable ledger never blocks the turn.
+    pub(crate) async fn record(&self, usage: &TokenUsage) {
+        if usage.input_tokens <= 0 && usage.output_tokens <= 0 {
+            return;
+        }
+        let record = UsageRecord {
+            ts: now_secs(),
+            profile: self.config.profile.clone(),
+            input_tokens: usage.input_tokens,
+            cached_input_tokens: usage.cached_input_tokens,
+            output_tokens: usage.output_tokens,
+        };
+        let path = self.path.clone();
+        match tokio::task::spawn_blocking(move || append(&path, &record)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => warn!("failed to append to {}: {err}", self.path.display()),
+            Err(err) => warn!("usage ledger writer panicked: {err}"),
+        }
+    }
+
+    /// Keeps going past the `limits` for the rest of the session.
+    fn override_limits(&self, limits: impl IntoIterator<Item = Limit>) {
+        if let Ok(mut state) = self.state.lock() {
+            state.overridden.extend(limits);
+        }
+    }
+
+    async fn check(&self) -> QuotaReport {
+        let path = self.path.clone();
+        let profile = self.config.profile.clone();
+        let prices = self.config.prices;
+        let now = now_secs();
+        let usage =
+            tokio::task::spawn_blocking(move || read_usage(&path, &profile, prices.as_ref(), now))
+                .await;
+        let (day, week) = match usage {
+            Ok(Ok(usage)) => usage,
+            Ok(Err(err)) => {
+                warn!("failed to read {}: {err}", self.path.display());
+                return QuotaReport::default();
+            }
+            Err(err) => {
+                warn!("usage ledger reader panicked: {err}");
+                return QuotaReport::default();
+            }
+        };
+        match self.state.lock() {
+            Ok(mut state) => evaluate(&self.config, &mut state, day, week),
+            Err(_) => QuotaReport::default(),
+        }
+    }
+}
+
+fn evaluate(
+    config: &UsageQuotaConfig,
+    state: &mut QuotaState,
+    day: Usage,
+    week: Usage,
+) -> QuotaReport {
+    let mut report = QuotaReport::default();
+    for limit in Limit::ALL {
+        let Some(cap) = limit.cap(config) else {
+            continue;
+        };
+        if state.overridden.contains(&limit) {
+            continue;
+        }
+        let used = limit.used(day, week);
+        if used >= cap {
+            state.warned.insert(limit);
+            report.exceeded.push((limit, limit.describe(used, cap)));
+        } else if used >= cap * WARN_FRACTION && state.warned.insert(limit) {
+            report.warnings.push(format!(
+                "Profile `{}` has used {:.0}% of its quota: {}.",
+                config.profile,
+                used / cap * 100.0,
+                limit.describe(used, cap)
+            ));
+        }
+    }
+    report
+}
+
+/// Checks the quota before a model request. Returns false when the turn has
+/// to stop.
+pub(crate) async fn enforce(sess: &Arc<Session>, turn_context: &Arc<TurnContext>) -> bool {
+    let Some(quota) = sess.services.usage_quota.as_ref() else {
+        return true;
+    };
+    let report = quota.check().await;
+    for message in report.warnings {
+        sess.send_event(turn_context, EventMsg::Warning(WarningEvent { message }))
+            .await;
+    }
+    if report.exceeded.is_empty() {
+        return true;
+    }
+
+    let profile = quota.config.profile.clone();
+    let message = report
+        .exceeded
+        .iter()
+        .map(|(_, description)| description.as_str())
+        .collect::<Vec<_>>()
+        .join("; ");
+    let approved = turn_context.session_source == SessionSource::Cli
+        && sess
+            .request_usage_quota_override(
+                turn_context,
+                UsageQuotaExceededEvent {
+                    turn_id: turn_context.sub_id.clone(),
+                    profile: profile.clone(),
+                    message: message.clone(),
+                },
+            )
+            .await
+            .unwrap_or(false);
+    if approved {
+        quota.override_limits(report.exceeded.into_iter().map(|(limit, _)| limit));
+        sess.send_event(
+            turn_context,
+            EventMsg::Warning(WarningEvent {
+                message: format!(
+                    "Continuing past the quota of profile `{profile}` for this session."
+                ),
+            }),
+        )
+        .await;
+        return true;
+    }
+    sess.send_event(
+        turn_context,
+        EventMsg::Error(ErrorEvent {
+            message: format!("Profile `{profile}` has used up its quota: {message}."),
+            codex_error_info: Some(CodexErrorInfo::UsageLimitExceeded),
+        }),
+    )
+    .await;
+    false
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+fn open_ledger(path: &Path) -> io::Result<File> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut options = OpenOptions::new();
+    options.read(true).append(true).create(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    options.open(path)
+}
+
+fn append(path: &Path, record: &UsageRecord) -> io::Result<()> {
+    let mut file = open_ledger(path)?;
+    file.lock()?;
+    if file.metadata()?.len() > PRUNE_THRESHOLD_BYTES {
+        prune(&mut file, now_secs())?;
+    }
+    let mut line = serde_json::to_string(record).map_err(io::Error::other)?;
+    line.push('\n');
+    file.write_all(line.as_bytes())?;
+    file.flush()
+}
+
+/// Rewrites the ledger without the records older than a week, while holding
+/// the lock.
+fn prune(file: &mut File, now: u64) -> io::Result<()> {
+    file.seek(SeekFrom::Start(0))?;
+    let kept = records(BufReader::new(&*file))
+        .filter(|record| now.saturating_sub(record.ts) < WEEK_SECS)
+        .map(|record| serde_json::to_string(&record).map(|line| line + "\n"))
+        .collect::<Result<String, _>>()
+        .map_err(io::Error::other)?;
+    file.set_len(0)?;
+    file.write_all(kept.as_bytes())
+}
+
+/// Usage of `profile` over the last day and the last week.
+fn read_usage(
+    path: &Path,
+    profile: &str,
+    prices: Option<&TokenPrices>,
+    now: u64,
+) -> io::Result<(Usage, Usage)> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Ok((Usage::default(), Usage::default()));
+        }
+        Err(err) => return Err(err),
+    };
+    file.lock_shared()?;
+    let mut day = Usage::default();
+    let mut week = Usage::default();
+    for record in records(BufReader::new(&file)).filter(|record| record.profile == profile) {
+        let age = now.saturating_sub(record.ts);
+        if age >= WEEK_SECS {
+            continue;
+        }
+        let tokens = record.tokens();
+        let usd = prices.map(|prices| record.usd(prices)).unwrap_or_default();
+        week.tokens = week.tokens.saturating_add(tokens);
+        week.usd += usd;
+        if age < DAY_SECS {
+            day.tokens = day.tokens.saturating_add(tokens);
+            day.usd += usd;
+        }
+    }
+    Ok((day, week))
+}
+
+/// The records of the ledger, skipping lines that do not parse.
+fn records(reader: impl BufRead) -> impl Iterator<Item = UsageRecord> {
+    reader
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn record(ts: u64, profile: &str, input_tokens: i64, output_tokens: i64) -> UsageRecord {
+        UsageRecord {
+            ts,
+            profile: profile.to_string(),
+            input_tokens,
+            cached_input_tokens: input_tokens / 2,
+            output_tokens,
+        }
+    }
+
+    #[test]
+    fn sums_the_profile_usage_per_window() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(USAGE_LEDGER_FILENAME);
+        let now = 10 * WEEK_SECS;
+        for record in [
+            record(now - 2 * WEEK_SECS, "team", 9_000, 0),
+            record(now - 3 * DAY_SECS, "team", 400_000, 100_000),
+            record(now - 60, "team", 1_000_000, 500_000),
+            record(now - 60, "other", 7_000, 7_000),
+        ] {
+            append(&path, &record).expect("append");
+        }
+        let prices = TokenPrices {
+            input: 2.0,
+            cached_input: 1.0,
+            output: 10.0,
+        };
+
+        let (day, week) = read_usage(&path, "team", Some(&prices), now).expect("read usage");
+        assert_eq!(
+            day,
+            Usage {
+                tokens: 1_500_000,
+                usd: 6.5,
+            }
+        );
+        assert_eq!(week.tokens, 2_000_000);
+        assert!((week.usd - 8.1).abs() < 1e-9, "{week:?}");
+
+        let mut file = open_ledger(&path).expect("open ledger");
+        prune(&mut file, now).expect("prune");
+        let remaining = records(BufReader::new(File::open(&path).expect("open"))).count();
+        assert_eq!(remaining, 3);
+    }
+
+    #[test]
+    fn warns_once_and_stops_until_overridden() {
+        let config = UsageQuotaConfig {
+            profile: "team".to_string(),
+            daily_tokens: Some(1_000),
+            weekly_tokens: None,
+            daily_usd: None,
+            weekly_usd: Some(10.0),
+            prices: None,
+        };
+        let mut state = QuotaState::default();
+        let usage = |tokens, usd| Usage { tokens, usd };
+
+        let report = evaluate(&config, &mut state, usage(850, 1.0), usage(850, 1.0));
+        assert_eq!(
+            report,
+            QuotaReport {
+                warnings: vec![
+                    "Profile `team` has used 85% of its quota: 850 of 1,000 tokens per day."
+                        .to_string()
+                ],
+                exceeded: Vec::new(),
+            }
+        );
+        let report = evaluate(&config, &mut state, usage(900, 1.0), usage(900, 1.0));
+        assert_eq!(report, QuotaReport::default());
+
+        let report = evaluate(&config, &mut state, usage(1_200, 1.0), usage(1_200, 10.5));
+        assert_eq!(
+            report.exceeded,
+            vec![
+                (
+                    Limit::DailyTokens,
+                    "1,200 of 1,000 tokens per day".to_string()
+                ),
+                (Limit::WeeklyUsd, "$10.50 of $10.00 per week".to_string()),
+            ]
+        );
+
+        state
+            .overridden
+            .extend([Limit::DailyTokens, Limit::WeeklyUsd]);
+        let report = evaluate(&config, &mut state, usage(1_500, 1.0), usage(1_500, 12.0));
+        assert_eq!(report, QuotaReport::default());
+    }
+}
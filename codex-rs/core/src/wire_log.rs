@@ -0,0 +1,471 @@
+//! Wire log of model API traffic (`--wire-log <path>`, or toggled at runtime
+//! with `Op::SetWireLog`), for debugging providers that disagree with Codex
+//! about the shape of a request or response.
+//!
+//! Each line of the file is one JSON record:
+//!
+//! ````text
+//! {"ts":"…","id":1,"type":"request","method":"POST","url":"…","headers":{…},"body":{…}}
+//! {"ts":"…","id":1,"type":"response","status":200,"headers":{…}}
+//! {"ts":"…","id":1,"type":"response_chunk","data":"event: response.created\n…"}
+//! {"ts":"…","id":1,"type":"error","error":"…"}
+//! ````
+//!
+//! `id` ties the records of one HTTP exchange together. Unary responses carry
+//! their `body`; streamed responses are followed by one `response_chunk` per
+//! chunk received. Credentials are redacted: headers such as `Authorization`
+//! and URL query parameters such as `key` are replaced entirely, and every
+//! string goes through [`redact_secrets`]. Only HTTP requests are recorded;
+//! the Responses WebSocket transport is not.
+
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+use async_trait::async_trait;
+use chrono::SecondsFormat;
+use chrono::Utc;
+use codex_client::HttpTransport;
+use codex_client::Request;
+use codex_client::Response;
+use codex_client::StreamResponse;
+use codex_client::TransportError;
+use codex_utils_sanitizer::redact_secrets;
+use futures::StreamExt;
+use http::HeaderMap;
+use serde::Serialize;
+use serde_json::Value;
+use tracing::warn;
+
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+
+const REDACTED: &str = "[REDACTED_SECRET]";
+
+/// Header, query parameter, and JSON member names whose values are always
+/// redacted. Matching is case-insensitive on the whole name.
+const SECRET_NAMES: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "api-key",
+    "api_key",
+    "x-api-key",
+    "key",
+    "access_token",
+    "refresh_token",
+    "id_token",
+    "client_secret",
+    "password",
+];
+
+fn is_secret_name(name: &str) -> bool {
+    SECRET_NAMES.contains(&name.to_ascii_lowercase().as_str())
+}
+
+/// Also catches provider-specific headers such as `x-goog-api-key` and
+/// `x-amz-security-token`, but not `x-ratelimit-remaining-tokens`.
+fn is_secret_header(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    is_secret_name(&name)
+        || name.ends_with("-token")
+        || name.contains("secret")
+        || name.contains("api-key")
+        || name.contains("apikey")
+}
+
+/// Destination of the wire log for one session. Logging can be switched on
+/// and off while the session runs; the path stays the same.
+#[derive(Debug)]
+pub(crate) struct WireLog {
+    path: PathBuf,
+    /// Open while logging is on.
+    file: Mutex<Option<File>>,
+    next_id: AtomicU64,
+}
+
+impl WireLog {
+    /// A wire log writing to `path` once enabled.
+    pub(crate) fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            file: Mutex::new(None),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.file.lock().is_ok_and(|file| file.is_some())
+    }
+
+    /// Starts or stops logging. Starting appends to an existing file.
+    pub(crate) fn set_enabled(&self, enabled: bool) -> io::Result<()> {
+        let file = if enabled {
+            if let Some(parent) = self.path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let mut options = OpenOptions::new();
+            options.create(true).append(true);
+            #[cfg(unix)]
+            options.mode(0o600);
+            Some(options.open(&self.path)?)
+        } else {
+            None
+        };
+        let mut guard = self
+            .file
+            .lock()
+            .map_err(|_| io::Error::other("wire log lock poisoned"))?;
+        *guard = file;
+        Ok(())
+    }
+
+    /// Records `request` and returns the id for the records that follow, or
+    /// `None` when logging is off.
+    fn log_request(&self, request: &Request) -> Option<u64> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.write(&WireRecord {
+            id,
+            kind: WireRecordKind::Request {
+                method: request.method.to_string(),
+                url: redact_url(&request.url),
+                headers: redact_headers(&request.headers),
+                body: request.body.clone().map(redact_json),
+            },
+        });
+        Some(id)
+    }
+
+    fn log_response(
+        &self,
+        id: u64,
+        status: http::StatusCode,
+        headers: &HeaderMap,
+        body: Option<&[u8]>,
+    ) {
+        self.write(&WireRecord {
+            id,
+            kind: WireRecordKind::Response {
+                status: status.as_u16(),
+                headers: redact_headers(headers),
+                body: body.map(redact_body),
+            },
+        });
+    }
+
+    fn log_chunk(&self, id: u64, chunk: &[u8]) {
+        self.write(&WireRecord {
+            id,
+            kind: WireRecordKind::ResponseChunk {
+                data: redact_secrets(String::from_utf8_lossy(chunk).into_owned()),
+            },
+        });
+    }
+
+    fn log_error(&self, id: u64, error: &TransportError) {
+        let (error, body) = match error {
+            TransportError::Http { status, body, .. } => (
+                format!("http {status}"),
+                body.as_deref().map(|body| redact_body(body.as_bytes())),
+            ),
+            other => (redact_secrets(other.to_string()), None),
+        };
+        self.write(&WireRecord {
+            id,
+            kind: WireRecordKind::Error { error, body },
+        });
+    }
+
+    fn write(&self, record: &WireRecord) {
+        let Ok(mut line) = serde_json::to_string(&TimestampedRecord {
+            ts: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            record,
+        }) else {
+            return;
+        };
+        line.push('\n');
+        let Ok(mut guard) = self.file.lock() else {
+            return;
+        };
+        if let Some(file) = guard.as_mut()
+            && let Err(err) = file.write_all(line.as_bytes())
+        {
+            warn!("failed to write wire log {}: {err}", self.path.display());
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct TimestampedRecord<'a> {
+    ts: String,
+    #[serde(flatten)]
+    record: &'a WireRecord,
+}
+
+#[derive(Serialize)]
+struct WireRecord {
+    id: u64,
+    #[serde(flatten)]
+    kind: WireRecordKind,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum WireRecordKind {
+    Request {
+        method: String,
+        url: String,
+        headers: serde_json::Map<String, Value>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        body: Option<Value>,
+    },
+    Response {
+        status: u16,
+        headers: serde_json::Map<String, Value>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        body: Option<Value>,
+    },
+    ResponseChunk {
+        data: String,
+    },
+    Error {
+        error: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        body: Option<Value>,
+    },
+}
+
+fn redact_headers(headers: &HeaderMap) -> serde_json::Map<String, Value> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if is_secret_header(name.as_str()) {
+                REDACTED.to_string()
+            } else {
+                redact_secrets(String::from_utf8_lossy(value.as_bytes()).into_owned())
+            };
+            (name.as_str().to_string(), Value::String(value))
+        })
+        .collect()
+}
+
+fn redact_url(url: &str) -> String {
+    let Ok(mut parsed) = url::Url::parse(url) else {
+        return redact_secrets(url.to_string());
+    };
+    if parsed.query().is_some() {
+        let pairs: Vec<(String, String)> = parsed
+            .query_pairs()
+            .map(|(name, value)| {
+                let value = if is_secret_name(&name) {
+                    REDACTED.to_string()
+                } else {
+                    value.into_owned()
+                };
+                (name.into_owned(), value)
+            })
+            .collect();
+        parsed.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+    if parsed.password().is_some() {
+        let _ = parsed.set_password(Some(REDACTED));
+    }
+    redact_secrets(parsed.to_string())
+}
+
+/// A response body as JSON when it parses, otherwise as a string.
+fn redact_body(body: &[u8]) -> Value {
+    match serde_json::from_slice::<Value>(body) {
+        Ok(value) => redact_json(value),
+        Err(_) => Value::String(redact_secrets(String::from_utf8_lossy(body).into_owned())),
+    }
+}
+
+fn redact_json(value: Value) -> Value {
+    match value {
+        Value::String(text) => Value::String(redact_secrets(text)),
+        Value::Array(items) => Value::Array(items.into_iter().map(redact_json).collect()),
+        Value::Object(members) => Value::Object(
+            members
+                .into_iter()
+                .map(|(name, value)| {
+                    let value = if is_secret_name(&name) && !value.is_null() {
+                        Value::String(REDACTED.to_string())
+                    } else {
+                        redact_json(value)
+                    };
+                    (name, value)
+                })
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+/// [`HttpTransport`] that records every exchange in a [`WireLog`] while it is
+/// enabled and otherwise passes requests straight through.
+pub(crate) struct WireLogTransport<T> {
+    inner: T,
+    wire_log: Option<Arc<WireLog>>,
+}
+
+impl<T> WireLogTransport<T> {
+    pub(crate) fn new(inner: T, wire_log: Option<Arc<WireLog>>) -> Self {
+        Self { inner, wire_log }
+    }
+
+    fn start(&self, request: &Request) -> Option<(Arc<WireLog>, u64)> {
+        let wire_log = self.wire_log.as_ref()?;
+        let id = wire_log.log_request(request)?;
+        Some((Arc::clone(wire_log), id))
+    }
+}
+
+#[async_trait]
+impl<T: HttpTransport> HttpTransport for WireLogTransport<T> {
+    async fn execute(&self, req: Request) -> Result<Response, TransportError> {
+        let logging = self.start(&req);
+        let result = self.inner.execute(req).await;
+        if let Some((wire_log, id)) = logging {
+            match &result {
+                Ok(response) => {
+                    wire_log.log_response(
+                        id,
+                        response.status,
+                        &response.headers,
+                        Some(response.body.as_ref()),
+                    );
+                }
+                Err(err) => wire_log.log_error(id, err),
+            }
+        }
+        result
+    }
+
+    async fn stream(&self, req: Request) -> Result<StreamResponse, TransportError> {
+        let logging = self.start(&req);
+        let result = self.inner.stream(req).await;
+        let Some((wire_log, id)) = logging else {
+            return result;
+        };
+        match result {
+            Ok(StreamResponse {
+                status,
+                headers,
+                bytes,
+            }) => {
+                wire_log.log_response(id, status, &headers, None);
+                let bytes = bytes.inspect(move |chunk| match chunk {
+                    Ok(chunk) => wire_log.log_chunk(id, chunk),
+                    Err(err) => wire_log.log_error(id, err),
+                });
+                Ok(StreamResponse {
+                    status,
+                    headers,
+                    bytes: Box::pin(bytes),
+                })
+            }
+            Err(err) => {
+                wire_log.log_error(id, &err);
+                Err(err)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+    use http::Method;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn credentials_are_redacted() {
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", HeaderValue::from_static("Bearer abc123"));
+        headers.insert("x-goog-api-key", HeaderValue::from_static("AIzaXYZ"));
+        headers.insert("content-type", HeaderValue::from_static("application/json"));
+
+        assert_eq!(
+            Value::Object(redact_headers(&headers)),
+            json!({
+                "authorization": REDACTED,
+                "x-goog-api-key": REDACTED,
+                "content-type": "application/json",
+            })
+        );
+        assert_eq!(
+            redact_url("https://example.com/v1/models?key=AIzaXYZ&alt=sse"),
+            "https://example.com/v1/models?key=%5BREDACTED_SECRET%5D&alt=sse"
+        );
+        assert_eq!(
+            redact_json(json!({
+                "model": "gpt-5",
+                "client_secret": "s3cr3t",
+                "input": [{"text": "my key is sk-abcdefghijklmnopqrstuvwxyz0123"}],
+            })),
+            json!({
+                "model": "gpt-5",
+                "client_secret": REDACTED,
+                "input": [{"text": "my key is [REDACTED_SECRET]"}],
+            })
+        );
+    }
+
+    #[test]
+    fn records_requests_only_while_enabled() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let wire_log = WireLog::new(tmp.path().join("logs").join("wire.jsonl"));
+        let mut request =
+            Request::new(Method::POST, "https://example.com/v1/responses".to_string());
+        request.body = Some(json!({"model": "gpt-5"}));
+
+        assert_eq!(wire_log.log_request(&request), None);
+        wire_log.set_enabled(true).expect("enable wire log");
+        let id = wire_log.log_request(&request).expect("request id");
+        wire_log.log_chunk(id, b"data: {}\n\n");
+        wire_log.set_enabled(false).expect("disable wire log");
+        assert_eq!(wire_log.log_request(&request), None);
+
+        let contents = std::fs::read_to_string(wire_log.path()).expect("read wire log");
+        let records: Vec<Value> = contents
+            .lines()
+            .map(|line| {
+                let mut record: Value = serde_json::from_str(line).expect("json line");
+                record.as_object_mut().expect("object").remove("ts");
+                record
+            })
+            .collect();
+        assert_eq!(
+            records,
+            vec![
+                json!({
+                    "id": id,
+                    "type": "request",
+                    "method": "POST",
+                    "url": "https://example.com/v1/responses",
+                    "headers": {},
+                    "body": {"model": "gpt-5"},
+                }),
+                json!({"id": id, "type": "response_chunk", "data": "data: {}\n\n"}),
+            ]
+        );
+    }
+}
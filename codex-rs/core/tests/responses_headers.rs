@@ -53,8 +53,14 @@ async fn responses_stream_includes_subagent_header_on_review() {
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
+        stream_stall_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        oidc: None,
+        http_client: None,
+        organization: None,
+        project: None,
+        azure: None,
     };
 
     let codex_home = TempDir::new().expect("failed to create TempDir");
@@ -158,8 +164,14 @@ async fn responses_stream_includes_subagent_header_on_other() {
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
+        stream_stall_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        oidc: None,
+        http_client: None,
+        organization: None,
+        project: None,
+        azure: None,
     };
 
     let codex_home = TempDir::new().expect("failed to create TempDir");
@@ -258,8 +270,14 @@ async fn responses_respects_model_info_overrides_from_config() {
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
+        stream_stall_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        oidc: None,
+        http_client: None,
+        organization: None,
+        project: None,
+        azure: None,
     };
 
     let codex_home = TempDir::new().expect("failed to create TempDir");
@@ -7,15 +7,22 @@ use codex_core::protocol::Op;
 use codex_protocol::user_input::UserInput;
 use core_test_support::responses::ev_completed;
 use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_message_item_added;
+use core_test_support::responses::ev_output_text_delta;
 use core_test_support::responses::ev_response_created;
 use core_test_support::responses::mount_sse_once;
 use core_test_support::responses::mount_sse_sequence;
 use core_test_support::responses::sse;
 use core_test_support::responses::start_mock_server;
+use core_test_support::streaming_sse::StreamingSseChunk;
+use core_test_support::streaming_sse::start_streaming_sse_server;
 use core_test_support::test_codex::test_codex;
 use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
 use regex_lite::Regex;
+use serde_json::Value;
 use serde_json::json;
+use tokio::sync::oneshot;
 
 /// Integration test: spawn a long‑running shell_command tool via a mocked Responses SSE
 /// function call, then interrupt the session and expect TurnAborted.
@@ -239,3 +246,92 @@ async fn interrupt_persists_turn_aborted_marker_in_next_request() {
         "expected <turn_aborted> marker in follow-up request"
     );
 }
+
+/// Assistant text that streamed before an interrupt stays in history, marked as incomplete,
+/// so the next request still carries it.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn interrupt_keeps_partial_agent_message_in_next_request() {
+    // Never released: the first response stalls after its deltas until the turn is interrupted.
+    let (_gate_tx, gate_rx) = oneshot::channel();
+    let first_chunks = vec![
+        StreamingSseChunk {
+            gate: None,
+            body: sse(vec![
+                ev_response_created("resp-partial"),
+                ev_message_item_added("msg-partial", ""),
+                ev_output_text_delta("Renamed the first "),
+                ev_output_text_delta("two files"),
+            ]),
+        },
+        StreamingSseChunk {
+            gate: Some(gate_rx),
+            body: sse(vec![ev_completed("resp-partial")]),
+        },
+    ];
+    let follow_up_chunks = vec![StreamingSseChunk {
+        gate: None,
+        body: sse(vec![
+            ev_response_created("resp-followup"),
+            ev_completed("resp-followup"),
+        ]),
+    }];
+    let (server, _completions) =
+        start_streaming_sse_server(vec![first_chunks, follow_up_chunks]).await;
+
+    let codex = test_codex()
+        .with_model("gpt-5.1")
+        .build_with_streaming_server(&server)
+        .await
+        .unwrap()
+        .codex;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "rename the files".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+
+    wait_for_event(
+        &codex,
+        |ev| matches!(ev, EventMsg::AgentMessageContentDelta(delta) if delta.delta == "two files"),
+    )
+    .await;
+    codex.submit(Op::Interrupt).await.unwrap();
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnAborted(_))).await;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "follow up".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    let requests = server.requests().await;
+    assert_eq!(requests.len(), 2, "expected two calls to the responses API");
+    let follow_up: Value = serde_json::from_slice(&requests[1]).expect("parse follow-up request");
+    let assistant_texts: Vec<&str> = follow_up["input"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|item| item["type"] == "message" && item["role"] == "assistant")
+        .filter_map(|item| item["content"][0]["text"].as_str())
+        .collect();
+    assert_eq!(
+        assistant_texts,
+        vec![
+            "Renamed the first two files\n\n[Interrupted by the user; the response above is incomplete.]"
+        ]
+    );
+
+    server.shutdown().await;
+}
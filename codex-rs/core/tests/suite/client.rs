@@ -1221,6 +1221,51 @@ async fn configured_verbosity_is_sent() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn override_turn_settings_applies_to_next_turn() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+    let server = MockServer::start().await;
+
+    let resp_mock = mount_sse_once(
+        &server,
+        sse(vec![ev_response_created("resp1"), ev_completed("resp1")]),
+    )
+    .await;
+    let TestCodex { codex, .. } = test_codex()
+        .with_model("gpt-5.1")
+        .with_config(|config| {
+            config.model_verbosity = Some(Verbosity::High);
+        })
+        .build(&server)
+        .await?;
+
+    codex
+        .submit(Op::OverrideTurnSettings {
+            model: None,
+            effort: Some(Some(ReasoningEffort::Low)),
+            verbosity: Some(Some(Verbosity::Low)),
+        })
+        .await?;
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    let request_body = resp_mock.single_request().body_json();
+    assert_eq!(request_body["model"].as_str(), Some("gpt-5.1"));
+    assert_eq!(request_body["text"]["verbosity"].as_str(), Some("low"));
+    assert_eq!(request_body["reasoning"]["effort"].as_str(), Some("low"));
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn includes_developer_instructions_message_in_request() {
     skip_if_no_network!();
@@ -1315,8 +1360,14 @@ async fn azure_responses_request_includes_store_and_reasoning_ids() {
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
+        stream_stall_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        oidc: None,
+        http_client: None,
+        organization: None,
+        project: None,
+        azure: None,
     };
 
     let codex_home = TempDir::new().unwrap();
@@ -1908,8 +1959,14 @@ async fn azure_overrides_assign_properties_used_for_responses_url() {
         request_max_retries: None,
         stream_max_retries: None,
         stream_idle_timeout_ms: None,
+        stream_stall_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        oidc: None,
+        http_client: None,
+        organization: None,
+        project: None,
+        azure: None,
     };
 
     // Init session
@@ -1992,8 +2049,14 @@ async fn env_var_overrides_loaded_auth() {
         request_max_retries: None,
         stream_max_retries: None,
         stream_idle_timeout_ms: None,
+        stream_stall_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        oidc: None,
+        http_client: None,
+        organization: None,
+        project: None,
+        azure: None,
     };
 
     // Init session
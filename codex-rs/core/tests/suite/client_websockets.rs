@@ -972,8 +972,14 @@ fn websocket_provider(server: &WebSocketTestServer) -> ModelProviderInfo {
         request_max_retries: Some(0),
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
+        stream_stall_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: true,
+        oidc: None,
+        http_client: None,
+        organization: None,
+        project: None,
+        azure: None,
     }
 }
 
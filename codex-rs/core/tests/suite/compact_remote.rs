@@ -693,7 +693,7 @@ async fn remote_compact_trim_estimate_uses_session_base_instructions() -> Result
     let baseline_payload_tokens = estimate_compact_payload_tokens(&baseline_compact_request);
 
     let override_base_instructions =
-        format!("REMOTE_BASE_INSTRUCTIONS_OVERRIDE {}", "x".repeat(120_000));
+        format!("REMOTE_BASE_INSTRUCTIONS_OVERRIDE {}", "x ".repeat(60_000));
     let override_context_window = baseline_payload_tokens.saturating_add(1_000);
     let pretrim_override_estimate =
         baseline_input_tokens.saturating_add(approx_token_count(&override_base_instructions));
@@ -14,6 +14,8 @@ use core_test_support::wait_for_event;
 use pretty_assertions::assert_eq;
 use responses::ev_assistant_message;
 use responses::ev_completed;
+use responses::ev_response_created;
+use responses::mount_sse_sequence;
 use responses::sse;
 use responses::start_mock_server;
 
@@ -107,3 +109,71 @@ async fn codex_returns_json_result(model: String) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn codex_repairs_final_message_that_does_not_match_schema() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let valid = r#"{"explanation": "explanation", "final_answer": "final_answer"}"#;
+    let response_mock = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("r1"),
+                ev_assistant_message("m1", r#"Sure! {"final_answer": 42}"#),
+                ev_completed("r1"),
+            ]),
+            sse(vec![
+                ev_response_created("r2"),
+                ev_assistant_message("m2", valid),
+                ev_completed("r2"),
+            ]),
+        ],
+    )
+    .await;
+
+    let TestCodex {
+        codex,
+        cwd,
+        session_configured,
+        ..
+    } = test_codex().build(&server).await?;
+
+    codex
+        .submit(Op::UserTurn {
+            items: vec![UserInput::Text {
+                text: "hello world".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: Some(serde_json::from_str(SCHEMA)?),
+            cwd: cwd.path().to_path_buf(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::DangerFullAccess,
+            model: session_configured.model.clone(),
+            effort: None,
+            summary: ReasoningSummary::Auto,
+            collaboration_mode: None,
+            personality: None,
+        })
+        .await?;
+
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::Warning(_))).await;
+    let EventMsg::TurnComplete(complete) =
+        wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await
+    else {
+        anyhow::bail!("expected turn complete event");
+    };
+    assert_eq!(complete.last_agent_message.as_deref(), Some(valid));
+
+    let requests = response_mock.requests();
+    assert_eq!(requests.len(), 2);
+    let repair = requests[1]
+        .message_input_texts("developer")
+        .into_iter()
+        .find(|text| text.contains("does not match the required output JSON schema"))
+        .expect("repair request sent to the model");
+    assert!(repair.contains("- the final message is not a single JSON value"));
+
+    Ok(())
+}
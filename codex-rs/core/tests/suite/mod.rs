@@ -83,6 +83,7 @@ mod live_cli;
 mod live_reload;
 mod model_info_overrides;
 mod model_overrides;
+mod model_routing;
 mod model_switching;
 mod model_tools;
 mod models_cache_ttl;
@@ -112,6 +113,7 @@ mod skills;
 mod sqlite_state;
 mod stream_error_allows_next_turn;
 mod stream_no_completed;
+mod stream_stall;
 mod text_encoding_fix;
 mod tool_harness;
 mod tool_parallelism;
@@ -0,0 +1,109 @@
+use anyhow::Result;
+use codex_core::config::types::ModelRoutingConfig;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::ModelRoutedEvent;
+use codex_core::protocol::ModelTier;
+use codex_core::protocol::Op;
+use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse_completed;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
+use pretty_assertions::assert_eq;
+use wiremock::MockServer;
+
+const FLAGSHIP_MODEL: &str = "gpt-5.2-codex";
+const FAST_MODEL: &str = "gpt-5.1-codex-mini";
+
+async fn submit_text(test: &TestCodex, text: &str) -> Result<ModelRoutedEvent> {
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: text.to_string(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+    let routed = wait_for_event_match(&test.codex, |ev| match ev {
+        EventMsg::ModelRouted(event) => Some(event.clone()),
+        _ => None,
+    })
+    .await;
+    wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+    Ok(routed)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn routes_trivial_turns_to_the_fast_model() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = MockServer::start().await;
+    let resp_mock = mount_sse_sequence(
+        &server,
+        vec![
+            sse_completed("resp-1"),
+            sse_completed("resp-2"),
+            sse_completed("resp-3"),
+        ],
+    )
+    .await;
+    let mut builder = test_codex()
+        .with_model(FLAGSHIP_MODEL)
+        .with_config(|config| {
+            config.model_routing = Some(ModelRoutingConfig {
+                fast_model: FAST_MODEL.to_string(),
+                fast_reasoning_effort: Some(ReasoningEffort::Low),
+                max_fast_input_chars: 300,
+            });
+        });
+    let test = builder.build(&server).await?;
+
+    let routed = submit_text(&test, "What does the retry loop do?").await?;
+    assert_eq!(
+        routed,
+        ModelRoutedEvent {
+            model: FAST_MODEL.to_string(),
+            tier: ModelTier::Fast,
+            reason: "short question or read-only request".to_string(),
+            overridden: false,
+        }
+    );
+
+    let routed = submit_text(&test, "Fix the failing retry test.").await?;
+    assert_eq!(routed.tier, ModelTier::Flagship);
+    assert_eq!(routed.model, FLAGSHIP_MODEL);
+
+    test.codex
+        .submit(Op::RouteNextTurn {
+            tier: ModelTier::Fast,
+        })
+        .await?;
+    let routed = submit_text(&test, "Fix the typo in the README.").await?;
+    assert_eq!(routed.tier, ModelTier::Fast);
+    assert!(routed.overridden);
+
+    let requests = resp_mock.requests();
+    let models: Vec<_> = requests
+        .iter()
+        .map(|request| request.body_json()["model"].as_str().map(str::to_string))
+        .collect();
+    assert_eq!(
+        models,
+        vec![
+            Some(FAST_MODEL.to_string()),
+            Some(FLAGSHIP_MODEL.to_string()),
+            Some(FAST_MODEL.to_string()),
+        ]
+    );
+    assert_eq!(
+        requests[0].body_json()["reasoning"]["effort"].as_str(),
+        Some("low")
+    );
+
+    Ok(())
+}
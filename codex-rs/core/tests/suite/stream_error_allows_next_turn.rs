@@ -76,8 +76,14 @@ async fn continue_after_stream_error() {
         request_max_retries: Some(1),
         stream_max_retries: Some(1),
         stream_idle_timeout_ms: Some(2_000),
+        stream_stall_timeout_ms: None,
         requires_openai_auth: false,
         supports_websockets: false,
+        oidc: None,
+        http_client: None,
+        organization: None,
+        project: None,
+        azure: None,
     };
 
     let TestCodex { codex, .. } = test_codex()
@@ -0,0 +1,103 @@
+//! Verifies that a response stream that stops sending deltas mid-message is
+//! re-requested, continuing from the partial output.
+
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_message_item_added;
+use core_test_support::responses::ev_output_text_delta;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::sse;
+use core_test_support::streaming_sse::StreamingSseChunk;
+use core_test_support::streaming_sse::start_streaming_sse_server;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use tokio::sync::oneshot;
+
+fn chunk(event: Value) -> StreamingSseChunk {
+    StreamingSseChunk {
+        gate: None,
+        body: sse(vec![event]),
+    }
+}
+
+fn assistant_input_texts(body: &Value) -> Vec<String> {
+    body.get("input")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|item| item.get("role").and_then(Value::as_str) == Some("assistant"))
+        .filter_map(|item| item.get("content").and_then(Value::as_array))
+        .flatten()
+        .filter_map(|span| span.get("text").and_then(Value::as_str).map(str::to_owned))
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn stalled_stream_is_retried_with_partial_output() {
+    // Never fired: the first response stops after its first delta.
+    let (stalled_tx, stalled_rx) = oneshot::channel();
+    let stalled = vec![
+        chunk(ev_response_created("resp-1")),
+        chunk(ev_message_item_added("msg-1", "")),
+        chunk(ev_output_text_delta("The answer")),
+        StreamingSseChunk {
+            gate: Some(stalled_rx),
+            body: sse(vec![ev_completed("resp-1")]),
+        },
+    ];
+    let continued = vec![
+        chunk(ev_response_created("resp-2")),
+        chunk(ev_assistant_message("msg-2", "is 42.")),
+        chunk(ev_completed("resp-2")),
+    ];
+    let (server, _completions) = start_streaming_sse_server(vec![stalled, continued]).await;
+
+    let codex = test_codex()
+        .with_config(|config| {
+            config.model_provider.stream_stall_timeout_ms = Some(200);
+            config.model_provider.stream_max_retries = Some(1);
+        })
+        .build_with_streaming_server(&server)
+        .await
+        .unwrap()
+        .codex;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "what is the answer?".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+
+    let stream_error = wait_for_event_match(&codex, |event| match event {
+        EventMsg::StreamError(err) => Some(err.message.clone()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(stream_error, "Model stream stalled; continuing... 1/1");
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TurnComplete(_))).await;
+
+    let requests = server.requests().await;
+    assert_eq!(requests.len(), 2);
+    let retried: Value = serde_json::from_slice(&requests[1]).expect("parse retried request");
+    let assistant_texts = assistant_input_texts(&retried);
+    assert!(
+        assistant_texts
+            .iter()
+            .any(|text| text.starts_with("The answer\n\n[The response stream stalled here")),
+        "retried request should carry the partial output: {assistant_texts:?}"
+    );
+
+    drop(stalled_tx);
+    server.shutdown().await;
+}
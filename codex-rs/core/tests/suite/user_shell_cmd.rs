@@ -30,6 +30,38 @@ use tempfile::TempDir;
 use tokio::time::Duration;
 use tokio::time::timeout;
 
+#[tokio::test]
+async fn user_shell_cmd_is_recorded_in_audit_log() -> anyhow::Result<()> {
+    let home = TempDir::new()?;
+    let audit_path = home.path().join("audit.jsonl");
+    let server = start_mock_server().await;
+    let mut builder = test_codex().with_config({
+        let audit_path = audit_path.clone();
+        move |config| {
+            config.audit_log_path = Some(audit_path);
+        }
+    });
+    let test = builder.build(&server).await?;
+
+    test.codex
+        .submit(Op::RunUserShellCommand {
+            command: "echo audited".to_string(),
+        })
+        .await?;
+    wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::ExecCommandEnd(_))).await;
+
+    let verification = codex_core::audit_log::verify(&audit_path)?;
+    assert_eq!(verification.records, 1);
+    let record: serde_json::Value =
+        serde_json::from_str(std::fs::read_to_string(&audit_path)?.trim_end())?;
+    assert_eq!(record["type"], "command_executed");
+    assert_eq!(record["source"], "user_shell");
+    assert_eq!(record["exit_code"], 0);
+    let command = record["command"].to_string();
+    assert!(command.contains("echo audited"), "{command}");
+    Ok(())
+}
+
 #[tokio::test]
 async fn user_shell_cmd_ls_and_cat_in_temp_dir() {
     // Create a temporary working directory with a known file.
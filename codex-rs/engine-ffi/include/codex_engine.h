@@ -0,0 +1,167 @@
+/* Generated by cbindgen from codex-rs/engine-ffi. Do not edit; run `just write-engine-ffi-header`. */
+
+#ifndef CODEX_ENGINE_H
+#define CODEX_ENGINE_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Result of every fallible call. On anything but `CODEX_STATUS_OK`, the message is stored in
+ * `out_error` when it is not NULL.
+ */
+typedef enum CodexStatus {
+  CODEX_STATUS_OK = 0,
+  /**
+   * A required pointer was NULL, a string was not valid UTF-8, or JSON did not parse.
+   */
+  CODEX_STATUS_INVALID_ARGUMENT = 1,
+  /**
+   * The engine reported an error.
+   */
+  CODEX_STATUS_FAILED = 2,
+  /**
+   * A bug in Codex; the call had no effect.
+   */
+  CODEX_STATUS_PANICKED = 3,
+} CodexStatus;
+
+/**
+ * A running conversation.
+ */
+typedef struct CodexConversation CodexConversation;
+
+/**
+ * Loaded configuration and the threads conversations run on.
+ */
+typedef struct CodexEngine CodexEngine;
+
+/**
+ * Receives one event as JSON. `event_json` is only valid for the duration of the call.
+ *
+ * Runs on an engine thread, one call at a time per conversation. It may call
+ * `codex_conversation_send_message` and `codex_conversation_submit`, but must not free handles
+ * or start conversations.
+ */
+typedef void (*CodexEventCallback)(void *user_data, const char *event_json);
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Creates an engine. `options_json` may be NULL, or an object with any of `codex_home`, `cwd`,
+ * `model`, `approval_policy`, `sandbox_mode`, `linux_sandbox_exe`, and `config_overrides`.
+ *
+ * # Safety
+ *
+ * `options_json` must be NULL or a NUL-terminated string. `out_engine` must be valid for
+ * writes, and `out_error` NULL or valid for writes.
+ */
+CodexStatus codex_engine_new(const char *options_json,
+                             CodexEngine **out_engine,
+                             char **out_error);
+
+/**
+ * Frees an engine. Conversations started from it keep running until they are freed.
+ *
+ * # Safety
+ *
+ * `engine` must be NULL or a handle from `codex_engine_new` that has not been freed, and must
+ * not be freed from an event callback.
+ */
+void codex_engine_free(CodexEngine *engine);
+
+/**
+ * Starts a conversation whose events go to `callback`, called with `user_data`.
+ *
+ * # Safety
+ *
+ * `engine` must be a live handle. `callback` must be safe to call from another thread with
+ * `user_data` until `codex_conversation_free` returns for the new conversation. `out_conversation`
+ * must be valid for writes, and `out_error` NULL or valid for writes.
+ */
+CodexStatus codex_conversation_start(CodexEngine *engine,
+                                     CodexEventCallback callback,
+                                     void *user_data,
+                                     CodexConversation **out_conversation,
+                                     char **out_error);
+
+/**
+ * Resumes the conversation recorded at `rollout_path`. Its events go to `callback`, as with
+ * `codex_conversation_start`.
+ *
+ * # Safety
+ *
+ * As for `codex_conversation_start`; `rollout_path` must be a NUL-terminated string.
+ */
+CodexStatus codex_conversation_resume(CodexEngine *engine,
+                                      const char *rollout_path,
+                                      CodexEventCallback callback,
+                                      void *user_data,
+                                      CodexConversation **out_conversation,
+                                      char **out_error);
+
+/**
+ * The conversation's id, valid until the conversation is freed.
+ *
+ * # Safety
+ *
+ * `conversation` must be a live handle.
+ */
+const char *codex_conversation_id(const CodexConversation *conversation);
+
+/**
+ * Starts a turn with `text` as the user message. Failures to submit arrive as error events.
+ *
+ * # Safety
+ *
+ * `conversation` must be a live handle and `text` a NUL-terminated string. `out_error` must be
+ * NULL or valid for writes.
+ */
+CodexStatus codex_conversation_send_message(CodexConversation *conversation,
+                                            const char *text,
+                                            char **out_error);
+
+/**
+ * Submits an operation, for example
+ * `{"type":"exec_approval","id":"<call_id>","decision":"approved"}` to answer an
+ * `exec_approval_request` event, or `{"type":"interrupt"}`. Failures to submit arrive as error
+ * events.
+ *
+ * # Safety
+ *
+ * `conversation` must be a live handle and `op_json` a NUL-terminated string. `out_error` must
+ * be NULL or valid for writes.
+ */
+CodexStatus codex_conversation_submit(CodexConversation *conversation,
+                                      const char *op_json,
+                                      char **out_error);
+
+/**
+ * Shuts the conversation down, waits for its history to be written, and frees it. The callback
+ * is not called again once this returns. Called from the conversation's own callback, it cannot
+ * wait for the callback to finish, so it returns at once and the shutdown completes afterwards.
+ *
+ * # Safety
+ *
+ * `conversation` must be NULL or a live handle, and must not be freed from an event callback.
+ */
+void codex_conversation_free(CodexConversation *conversation);
+
+/**
+ * Frees a string returned through `out_error`.
+ *
+ * # Safety
+ *
+ * `string` must be NULL or a string from this library that has not been freed.
+ */
+void codex_string_free(char *string);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* CODEX_ENGINE_H */
@@ -0,0 +1,664 @@
+//! C ABI over [`codex_engine`], for embedding Codex from C, C++, Swift, and other languages with
+//! a C FFI. `include/codex_engine.h` is generated from this file with cbindgen.
+//!
+//! Handles are opaque pointers. Options, operations, and events cross the boundary as UTF-8 JSON:
+//! events are serialized [`Event`]s and operations are [`Op`]s, in the same shapes the protocol
+//! uses elsewhere. Each conversation delivers its events, in order, to a callback that runs on an
+//! engine thread.
+
+use std::cell::Cell;
+use std::ffi::CStr;
+use std::ffi::CString;
+use std::ffi::c_char;
+use std::ffi::c_void;
+use std::panic::AssertUnwindSafe;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+use codex_engine::Conversation;
+use codex_engine::Engine;
+use codex_engine::protocol::AskForApproval;
+use codex_engine::protocol::Event;
+use codex_engine::protocol::EventMsg;
+use codex_engine::protocol::Op;
+use codex_engine::protocol::SandboxMode;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::runtime::Runtime;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Result of every fallible call. On anything but `CODEX_STATUS_OK`, the message is stored in
+/// `out_error` when it is not NULL.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodexStatus {
+    Ok = 0,
+    /// A required pointer was NULL, a string was not valid UTF-8, or JSON did not parse.
+    InvalidArgument = 1,
+    /// The engine reported an error.
+    Failed = 2,
+    /// A bug in Codex; the call had no effect.
+    Panicked = 3,
+}
+
+/// Receives one event as JSON. `event_json` is only valid for the duration of the call.
+///
+/// Runs on an engine thread, one call at a time per conversation. It may call
+/// `codex_conversation_send_message` and `codex_conversation_submit`, but must not free handles
+/// or start conversations.
+pub type CodexEventCallback =
+    Option<unsafe extern "C" fn(user_data: *mut c_void, event_json: *const c_char)>;
+
+/// Loaded configuration and the threads conversations run on.
+pub struct CodexEngine {
+    runtime: Arc<Runtime>,
+    engine: Engine,
+}
+
+/// A running conversation.
+pub struct CodexConversation {
+    id: CString,
+    key: u64,
+    commands: mpsc::UnboundedSender<Command>,
+    pump: Option<JoinHandle<()>>,
+    runtime: Arc<Runtime>,
+}
+
+/// Options of `codex_engine_new`; every field may be left out.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct EngineOptions {
+    codex_home: Option<PathBuf>,
+    cwd: Option<PathBuf>,
+    model: Option<String>,
+    approval_policy: Option<AskForApproval>,
+    sandbox_mode: Option<SandboxMode>,
+    linux_sandbox_exe: Option<PathBuf>,
+    /// `key=value` strings, as with `codex -c`.
+    config_overrides: Vec<String>,
+}
+
+enum Command {
+    Message(String),
+    Op(Op),
+}
+
+struct FfiError {
+    status: CodexStatus,
+    message: String,
+}
+
+impl FfiError {
+    fn invalid(message: impl Into<String>) -> Self {
+        Self {
+            status: CodexStatus::InvalidArgument,
+            message: message.into(),
+        }
+    }
+
+    fn failed(err: impl std::fmt::Display) -> Self {
+        Self {
+            status: CodexStatus::Failed,
+            message: err.to_string(),
+        }
+    }
+}
+
+thread_local! {
+    /// The conversation whose event callback is running on this thread, if any.
+    static CALLBACK_CONVERSATION: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+static NEXT_CONVERSATION_KEY: AtomicU64 = AtomicU64::new(1);
+
+/// The callback and its context, moved to the conversation's pump task.
+#[derive(Clone, Copy)]
+struct EventSink {
+    callback: unsafe extern "C" fn(*mut c_void, *const c_char),
+    user_data: *mut c_void,
+    /// Identifies the conversation while its callback runs; see [`CALLBACK_CONVERSATION`].
+    conversation: u64,
+}
+
+// SAFETY: `codex_conversation_start` requires `user_data` to be usable from another thread, and
+// the pump task is the only caller.
+unsafe impl Send for EventSink {}
+
+impl EventSink {
+    fn deliver(&self, json: String) {
+        // serde_json escapes NUL, so this only fails on a serializer bug.
+        let Ok(json) = CString::new(json) else {
+            return;
+        };
+        let previous = CALLBACK_CONVERSATION.replace(Some(self.conversation));
+        // SAFETY: the caller of `codex_conversation_start` vouched for the callback and user_data.
+        unsafe { (self.callback)(self.user_data, json.as_ptr()) };
+        CALLBACK_CONVERSATION.set(previous);
+    }
+
+    fn deliver_event(&self, event: &Event) {
+        match serde_json::to_string(event) {
+            Ok(json) => self.deliver(json),
+            Err(err) => self.deliver_error(&format!("failed to serialize event: {err}")),
+        }
+    }
+
+    /// Reports a failure that has no event of its own, in the shape of an error event.
+    fn deliver_error(&self, message: &str) {
+        let event = json!({ "id": "", "msg": { "type": "error", "message": message } });
+        self.deliver(event.to_string());
+    }
+}
+
+/// Creates an engine. `options_json` may be NULL, or an object with any of `codex_home`, `cwd`,
+/// `model`, `approval_policy`, `sandbox_mode`, `linux_sandbox_exe`, and `config_overrides`.
+///
+/// # Safety
+///
+/// `options_json` must be NULL or a NUL-terminated string. `out_engine` must be valid for
+/// writes, and `out_error` NULL or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_engine_new(
+    options_json: *const c_char,
+    out_engine: *mut *mut CodexEngine,
+    out_error: *mut *mut c_char,
+) -> CodexStatus {
+    guard(out_error, || {
+        if out_engine.is_null() {
+            return Err(FfiError::invalid("out_engine is NULL"));
+        }
+        ensure_not_in_callback()?;
+        // SAFETY: guaranteed by the caller.
+        let options = match unsafe { optional_str(options_json, "options_json") }? {
+            Some(json) => serde_json::from_str::<EngineOptions>(json)
+                .map_err(|err| FfiError::invalid(format!("invalid options: {err}")))?,
+            None => EngineOptions::default(),
+        };
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .thread_name("codex-engine")
+            .build()
+            .map_err(FfiError::failed)?;
+        let mut builder = Engine::builder();
+        if let Some(codex_home) = options.codex_home {
+            builder = builder.codex_home(codex_home);
+        }
+        if let Some(cwd) = options.cwd {
+            builder = builder.cwd(cwd);
+        }
+        if let Some(model) = options.model {
+            builder = builder.model(model);
+        }
+        if let Some(approval_policy) = options.approval_policy {
+            builder = builder.approval_policy(approval_policy);
+        }
+        if let Some(sandbox_mode) = options.sandbox_mode {
+            builder = builder.sandbox_mode(sandbox_mode);
+        }
+        if let Some(linux_sandbox_exe) = options.linux_sandbox_exe {
+            builder = builder.linux_sandbox_exe(linux_sandbox_exe);
+        }
+        for key_value in options.config_overrides {
+            builder = builder.config_override(key_value);
+        }
+        let engine = block_on(&runtime, builder.build())?.map_err(FfiError::failed)?;
+
+        let engine = Box::new(CodexEngine {
+            runtime: Arc::new(runtime),
+            engine,
+        });
+        // SAFETY: checked for NULL above; valid for writes per the caller.
+        unsafe { *out_engine = Box::into_raw(engine) };
+        Ok(())
+    })
+}
+
+/// Frees an engine. Conversations started from it keep running until they are freed.
+///
+/// # Safety
+///
+/// `engine` must be NULL or a handle from `codex_engine_new` that has not been freed, and must
+/// not be freed from an event callback.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_engine_free(engine: *mut CodexEngine) {
+    if engine.is_null() {
+        return;
+    }
+    // SAFETY: guaranteed by the caller.
+    let CodexEngine { runtime, engine } = *unsafe { Box::from_raw(engine) };
+    let _ = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        let _enter = runtime.enter();
+        drop(engine);
+    }));
+}
+
+/// Starts a conversation whose events go to `callback`, called with `user_data`.
+///
+/// # Safety
+///
+/// `engine` must be a live handle. `callback` must be safe to call from another thread with
+/// `user_data` until `codex_conversation_free` returns for the new conversation. `out_conversation`
+/// must be valid for writes, and `out_error` NULL or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_conversation_start(
+    engine: *mut CodexEngine,
+    callback: CodexEventCallback,
+    user_data: *mut c_void,
+    out_conversation: *mut *mut CodexConversation,
+    out_error: *mut *mut c_char,
+) -> CodexStatus {
+    guard(out_error, || {
+        // SAFETY: guaranteed by the caller.
+        let engine =
+            unsafe { engine.as_ref() }.ok_or_else(|| FfiError::invalid("engine is NULL"))?;
+        let sink = event_sink(callback, user_data, out_conversation)?;
+        let conversation = block_on(&engine.runtime, engine.engine.start_conversation())?
+            .map_err(FfiError::failed)?;
+        // SAFETY: `event_sink` checked `out_conversation` for NULL.
+        unsafe { *out_conversation = spawn_conversation(&engine.runtime, conversation, sink) };
+        Ok(())
+    })
+}
+
+/// Resumes the conversation recorded at `rollout_path`. Its events go to `callback`, as with
+/// `codex_conversation_start`.
+///
+/// # Safety
+///
+/// As for `codex_conversation_start`; `rollout_path` must be a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_conversation_resume(
+    engine: *mut CodexEngine,
+    rollout_path: *const c_char,
+    callback: CodexEventCallback,
+    user_data: *mut c_void,
+    out_conversation: *mut *mut CodexConversation,
+    out_error: *mut *mut c_char,
+) -> CodexStatus {
+    guard(out_error, || {
+        // SAFETY: guaranteed by the caller.
+        let engine =
+            unsafe { engine.as_ref() }.ok_or_else(|| FfiError::invalid("engine is NULL"))?;
+        // SAFETY: guaranteed by the caller.
+        let rollout_path = unsafe { required_str(rollout_path, "rollout_path") }?;
+        let sink = event_sink(callback, user_data, out_conversation)?;
+        let conversation = block_on(
+            &engine.runtime,
+            engine.engine.resume_conversation(rollout_path),
+        )?
+        .map_err(FfiError::failed)?;
+        // SAFETY: `event_sink` checked `out_conversation` for NULL.
+        unsafe { *out_conversation = spawn_conversation(&engine.runtime, conversation, sink) };
+        Ok(())
+    })
+}
+
+/// The conversation's id, valid until the conversation is freed.
+///
+/// # Safety
+///
+/// `conversation` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_conversation_id(
+    conversation: *const CodexConversation,
+) -> *const c_char {
+    // SAFETY: guaranteed by the caller.
+    match unsafe { conversation.as_ref() } {
+        Some(conversation) => conversation.id.as_ptr(),
+        None => std::ptr::null(),
+    }
+}
+
+/// Starts a turn with `text` as the user message. Failures to submit arrive as error events.
+///
+/// # Safety
+///
+/// `conversation` must be a live handle and `text` a NUL-terminated string. `out_error` must be
+/// NULL or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_conversation_send_message(
+    conversation: *mut CodexConversation,
+    text: *const c_char,
+    out_error: *mut *mut c_char,
+) -> CodexStatus {
+    guard(out_error, || {
+        // SAFETY: guaranteed by the caller.
+        let text = unsafe { required_str(text, "text") }?;
+        // SAFETY: guaranteed by the caller.
+        unsafe { send(conversation, Command::Message(text.to_string())) }
+    })
+}
+
+/// Submits an operation, for example
+/// `{"type":"exec_approval","id":"<call_id>","decision":"approved"}` to answer an
+/// `exec_approval_request` event, or `{"type":"interrupt"}`. Failures to submit arrive as error
+/// events.
+///
+/// # Safety
+///
+/// `conversation` must be a live handle and `op_json` a NUL-terminated string. `out_error` must
+/// be NULL or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_conversation_submit(
+    conversation: *mut CodexConversation,
+    op_json: *const c_char,
+    out_error: *mut *mut c_char,
+) -> CodexStatus {
+    guard(out_error, || {
+        // SAFETY: guaranteed by the caller.
+        let op_json = unsafe { required_str(op_json, "op_json") }?;
+        let op = serde_json::from_str::<Op>(op_json)
+            .map_err(|err| FfiError::invalid(format!("invalid op: {err}")))?;
+        // SAFETY: guaranteed by the caller.
+        unsafe { send(conversation, Command::Op(op)) }
+    })
+}
+
+/// Shuts the conversation down, waits for its history to be written, and frees it. The callback
+/// is not called again once this returns. Called from the conversation's own callback, it cannot
+/// wait for the callback to finish, so it returns at once and the shutdown completes afterwards.
+///
+/// # Safety
+///
+/// `conversation` must be NULL or a live handle, and must not be freed from an event callback.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_conversation_free(conversation: *mut CodexConversation) {
+    if conversation.is_null() {
+        return;
+    }
+    // SAFETY: guaranteed by the caller.
+    let CodexConversation {
+        key,
+        commands,
+        pump,
+        runtime,
+        ..
+    } = *unsafe { Box::from_raw(conversation) };
+    // Closing the channel tells the pump to shut the conversation down.
+    drop(commands);
+    let Some(pump) = pump else {
+        return;
+    };
+    // The pump is the caller here, so waiting for it would never finish.
+    if CALLBACK_CONVERSATION.get() == Some(key) {
+        return;
+    }
+    let _ = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        if tokio::runtime::Handle::try_current().is_ok() {
+            // `block_on` panics on a runtime thread, so wait from a thread of our own.
+            std::thread::scope(|scope| {
+                scope.spawn(|| runtime.block_on(pump));
+            });
+        } else {
+            runtime.block_on(pump);
+        }
+    }));
+}
+
+/// Frees a string returned through `out_error`.
+///
+/// # Safety
+///
+/// `string` must be NULL or a string from this library that has not been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        // SAFETY: guaranteed by the caller.
+        drop(unsafe { CString::from_raw(string) });
+    }
+}
+
+/// Runs `f`, turning errors and panics into a status and an `out_error` message.
+fn guard(out_error: *mut *mut c_char, f: impl FnOnce() -> Result<(), FfiError>) -> CodexStatus {
+    let error = match std::panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => return CodexStatus::Ok,
+        Ok(Err(error)) => error,
+        Err(_) => FfiError {
+            status: CodexStatus::Panicked,
+            message: "codex-engine panicked".to_string(),
+        },
+    };
+    if !out_error.is_null() {
+        let message = CString::new(error.message.replace('\0', " ")).unwrap_or_default();
+        // SAFETY: callers of the public functions guarantee `out_error` is valid for writes.
+        unsafe { *out_error = message.into_raw() };
+    }
+    error.status
+}
+
+/// # Safety
+///
+/// `ptr` must be NULL or a NUL-terminated string that outlives `'a`.
+unsafe fn optional_str<'a>(ptr: *const c_char, name: &str) -> Result<Option<&'a str>, FfiError> {
+    if ptr.is_null() {
+        return Ok(None);
+    }
+    // SAFETY: guaranteed by the caller.
+    let value = unsafe { CStr::from_ptr(ptr) };
+    value
+        .to_str()
+        .map(Some)
+        .map_err(|_| FfiError::invalid(format!("{name} is not valid UTF-8")))
+}
+
+/// # Safety
+///
+/// As for [`optional_str`].
+unsafe fn required_str<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, FfiError> {
+    // SAFETY: guaranteed by the caller.
+    unsafe { optional_str(ptr, name) }?.ok_or_else(|| FfiError::invalid(format!("{name} is NULL")))
+}
+
+/// Event callbacks run on the engine's threads, which must not block on the engine.
+fn ensure_not_in_callback() -> Result<(), FfiError> {
+    if tokio::runtime::Handle::try_current().is_ok() {
+        return Err(FfiError::invalid(
+            "this function cannot be called from an event callback",
+        ));
+    }
+    Ok(())
+}
+
+fn block_on<F: std::future::Future>(runtime: &Runtime, future: F) -> Result<F::Output, FfiError> {
+    ensure_not_in_callback()?;
+    Ok(runtime.block_on(future))
+}
+
+fn event_sink(
+    callback: CodexEventCallback,
+    user_data: *mut c_void,
+    out_conversation: *mut *mut CodexConversation,
+) -> Result<EventSink, FfiError> {
+    if out_conversation.is_null() {
+        return Err(FfiError::invalid("out_conversation is NULL"));
+    }
+    let callback = callback.ok_or_else(|| FfiError::invalid("callback is NULL"))?;
+    Ok(EventSink {
+        callback,
+        user_data,
+        conversation: NEXT_CONVERSATION_KEY.fetch_add(1, Ordering::Relaxed),
+    })
+}
+
+/// # Safety
+///
+/// `conversation` must be NULL or a live handle.
+unsafe fn send(conversation: *mut CodexConversation, command: Command) -> Result<(), FfiError> {
+    // SAFETY: guaranteed by the caller.
+    let conversation = unsafe { conversation.as_ref() }
+        .ok_or_else(|| FfiError::invalid("conversation is NULL"))?;
+    conversation
+        .commands
+        .send(command)
+        .map_err(|_| FfiError::failed("the conversation has ended"))
+}
+
+fn spawn_conversation(
+    runtime: &Arc<Runtime>,
+    conversation: Conversation,
+    sink: EventSink,
+) -> *mut CodexConversation {
+    let id = CString::new(conversation.id().to_string()).unwrap_or_default();
+    let (commands, commands_rx) = mpsc::unbounded_channel();
+    let pump = runtime.spawn(pump(conversation, commands_rx, sink));
+    Box::into_raw(Box::new(CodexConversation {
+        id,
+        key: sink.conversation,
+        commands,
+        pump: Some(pump),
+        runtime: Arc::clone(runtime),
+    }))
+}
+
+/// Delivers events and submits commands for one conversation, so the callback runs on one task
+/// and can submit without blocking it. Shuts the conversation down once the handle is freed.
+async fn pump(
+    conversation: Conversation,
+    mut commands: mpsc::UnboundedReceiver<Command>,
+    sink: EventSink,
+) {
+    loop {
+        tokio::select! {
+            command = commands.recv() => {
+                let result = match command {
+                    Some(Command::Message(text)) => conversation.send_message(text).await,
+                    Some(Command::Op(op)) => conversation.submit(op).await,
+                    None => break,
+                };
+                if let Err(err) = result {
+                    sink.deliver_error(&err.to_string());
+                }
+            }
+            event = conversation.next_event() => match event {
+                Ok(event) => {
+                    sink.deliver_event(&event);
+                    if matches!(event.msg, EventMsg::ShutdownComplete) {
+                        return;
+                    }
+                }
+                Err(err) => {
+                    sink.deliver_error(&err.to_string());
+                    return;
+                }
+            },
+        }
+    }
+    let _ = conversation.shutdown().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::ptr;
+
+    fn take_error(error: *mut c_char) -> String {
+        assert!(!error.is_null());
+        // SAFETY: set by the call under test and freed below.
+        let message = unsafe { CStr::from_ptr(error) }
+            .to_string_lossy()
+            .into_owned();
+        // SAFETY: a string returned through `out_error`.
+        unsafe { codex_string_free(error) };
+        message
+    }
+
+    #[test]
+    fn invalid_arguments_are_reported_not_dereferenced() {
+        let mut error = ptr::null_mut();
+        // SAFETY: the pointers are NULL or point at locals.
+        let status = unsafe { codex_engine_new(ptr::null(), ptr::null_mut(), &mut error) };
+        assert_eq!(status, CodexStatus::InvalidArgument);
+        assert_eq!(take_error(error), "out_engine is NULL");
+
+        let options = CString::new(r#"{"approval_policy":"sometimes"}"#).expect("cstring");
+        let mut engine = ptr::null_mut();
+        let mut error = ptr::null_mut();
+        // SAFETY: the pointers point at locals.
+        let status = unsafe { codex_engine_new(options.as_ptr(), &mut engine, &mut error) };
+        assert_eq!(status, CodexStatus::InvalidArgument);
+        assert!(engine.is_null());
+        assert!(take_error(error).starts_with("invalid options:"));
+
+        let op = CString::new(r#"{"type":"interrupt"}"#).expect("cstring");
+        // SAFETY: a NULL conversation and no error out-pointer are both allowed.
+        let status =
+            unsafe { codex_conversation_submit(ptr::null_mut(), op.as_ptr(), ptr::null_mut()) };
+        assert_eq!(status, CodexStatus::InvalidArgument);
+
+        // SAFETY: NULL is a no-op for every free function.
+        unsafe {
+            codex_engine_free(ptr::null_mut());
+            codex_conversation_free(ptr::null_mut());
+            codex_string_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn engine_loads_an_empty_codex_home() {
+        let codex_home = tempfile::tempdir().expect("codex home");
+        let options = json!({
+            "codex_home": codex_home.path(),
+            "cwd": codex_home.path(),
+            "approval_policy": "on-request",
+            "config_overrides": ["model=\"gpt-5.1-codex\""],
+        })
+        .to_string();
+        let options = CString::new(options).expect("cstring");
+        let mut engine = ptr::null_mut();
+        let mut error = ptr::null_mut();
+        // SAFETY: the pointers point at locals.
+        let status = unsafe { codex_engine_new(options.as_ptr(), &mut engine, &mut error) };
+        assert!(error.is_null(), "{}", take_error(error));
+        assert_eq!(status, CodexStatus::Ok);
+        assert!(!engine.is_null());
+        // SAFETY: a live engine from `codex_engine_new`.
+        unsafe { codex_engine_free(engine) };
+    }
+
+    #[test]
+    fn submission_failures_arrive_as_error_events() {
+        unsafe extern "C" fn record(user_data: *mut c_void, event_json: *const c_char) {
+            // SAFETY: the test passes a `Vec<String>` and a valid event string.
+            let events = unsafe { &mut *user_data.cast::<Vec<String>>() };
+            let json = unsafe { CStr::from_ptr(event_json) };
+            events.push(json.to_string_lossy().into_owned());
+        }
+
+        let mut events = Vec::<String>::new();
+        let sink = EventSink {
+            callback: record,
+            user_data: (&mut events as *mut Vec<String>).cast(),
+            conversation: 7,
+        };
+        sink.deliver_error("the conversation has ended");
+        let event: Event = serde_json::from_str(&events[0]).expect("error event");
+        match event.msg {
+            EventMsg::Error(error) => assert_eq!(error.message, "the conversation has ended"),
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn callbacks_run_marked_with_their_conversation() {
+        unsafe extern "C" fn record(user_data: *mut c_void, _event_json: *const c_char) {
+            // SAFETY: the test passes an `Option<u64>`.
+            let seen = unsafe { &mut *user_data.cast::<Option<u64>>() };
+            *seen = CALLBACK_CONVERSATION.get();
+        }
+
+        let mut seen = None;
+        let sink = EventSink {
+            callback: record,
+            user_data: (&mut seen as *mut Option<u64>).cast(),
+            conversation: 7,
+        };
+        sink.deliver_error("boom");
+        assert_eq!(seen, Some(7));
+        assert_eq!(CALLBACK_CONVERSATION.get(), None);
+    }
+}
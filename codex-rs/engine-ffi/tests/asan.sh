@@ -0,0 +1,22 @@
+#!/usr/bin/env bash
+# Builds codex-engine-ffi with AddressSanitizer and runs the C smoke test against it. Arguments
+# are passed to the test; give it a prompt to also run a turn against the configured model.
+#
+# Needs a nightly toolchain with rust-src (`rustup component add rust-src --toolchain nightly`)
+# and clang.
+set -euo pipefail
+
+cd "$(dirname "$0")/../.."
+target="$(rustc -vV | sed -n 's/^host: //p')"
+RUSTFLAGS="-Zsanitizer=address" cargo +nightly build -Zbuild-std --target "$target" \
+    -p codex-engine-ffi --lib
+
+out="target/$target/debug"
+case "$(uname -s)" in
+    Darwin) libs=(-framework CoreFoundation -framework Security -framework SystemConfiguration) ;;
+    *) libs=(-lpthread -ldl -lm) ;;
+esac
+clang -fsanitize=address -fno-omit-frame-pointer -g -Wall -Werror \
+    -I engine-ffi/include engine-ffi/tests/c/smoke.c "$out/libcodex_engine_ffi.a" "${libs[@]}" \
+    -o "$out/codex-engine-ffi-smoke"
+ASAN_OPTIONS="${ASAN_OPTIONS:-detect_leaks=1}" "$out/codex-engine-ffi-smoke" "$@"
@@ -0,0 +1,145 @@
+/*
+ * Exercises the C API the way an embedder would. Run it under AddressSanitizer with
+ * engine-ffi/tests/asan.sh.
+ *
+ * Without arguments it covers argument checking, error strings, and the engine lifecycle, which
+ * need no credentials. With a prompt argument it also runs one turn against the configured
+ * model, declining every approval request, and prints the events it receives.
+ */
+
+#include <pthread.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <unistd.h>
+
+#include "codex_engine.h"
+
+#define CHECK(cond)                                                       \
+  do {                                                                    \
+    if (!(cond)) {                                                        \
+      fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #cond); \
+      exit(1);                                                            \
+    }                                                                     \
+  } while (0)
+
+typedef struct {
+  pthread_mutex_t mutex;
+  pthread_cond_t done;
+  int turn_ended;
+  CodexConversation *conversation;
+} TurnState;
+
+static void on_event(void *user_data, const char *event_json) {
+  TurnState *state = user_data;
+  printf("%s\n", event_json);
+
+  /* Approvals are answered from the callback, which the API allows. */
+  const char *call_id = strstr(event_json, "\"call_id\":\"");
+  if (call_id != NULL && (strstr(event_json, "\"type\":\"exec_approval_request\"") != NULL ||
+                          strstr(event_json, "\"type\":\"apply_patch_approval_request\"") != NULL)) {
+    call_id += strlen("\"call_id\":\"");
+    const char *end = strchr(call_id, '"');
+    const char *type = strstr(event_json, "\"type\":\"exec_approval_request\"") != NULL
+                           ? "exec_approval"
+                           : "patch_approval";
+    char op[512];
+    snprintf(op, sizeof op, "{\"type\":\"%s\",\"id\":\"%.*s\",\"decision\":\"denied\"}", type,
+             (int)(end - call_id), call_id);
+    char *error = NULL;
+    CHECK(codex_conversation_submit(state->conversation, op, &error) == CODEX_STATUS_OK);
+  }
+
+  if (strstr(event_json, "\"type\":\"turn_complete\"") != NULL ||
+      strstr(event_json, "\"type\":\"turn_aborted\"") != NULL) {
+    pthread_mutex_lock(&state->mutex);
+    state->turn_ended = 1;
+    pthread_cond_signal(&state->done);
+    pthread_mutex_unlock(&state->mutex);
+  }
+}
+
+static void check_invalid_arguments(void) {
+  char *error = NULL;
+  CHECK(codex_engine_new(NULL, NULL, &error) == CODEX_STATUS_INVALID_ARGUMENT);
+  CHECK(error != NULL && strcmp(error, "out_engine is NULL") == 0);
+  codex_string_free(error);
+
+  CodexEngine *engine = NULL;
+  error = NULL;
+  CHECK(codex_engine_new("{\"not_an_option\":true}", &engine, &error) ==
+        CODEX_STATUS_INVALID_ARGUMENT);
+  CHECK(engine == NULL);
+  CHECK(error != NULL && strncmp(error, "invalid options:", 16) == 0);
+  codex_string_free(error);
+
+  /* A NULL out_error is allowed; the message is dropped. */
+  CHECK(codex_engine_new("not json", &engine, NULL) == CODEX_STATUS_INVALID_ARGUMENT);
+  CHECK(codex_conversation_submit(NULL, "{\"type\":\"interrupt\"}", NULL) ==
+        CODEX_STATUS_INVALID_ARGUMENT);
+  CHECK(codex_conversation_id(NULL) == NULL);
+
+  codex_engine_free(NULL);
+  codex_conversation_free(NULL);
+  codex_string_free(NULL);
+}
+
+static CodexEngine *new_engine(const char *codex_home) {
+  char options[1024];
+  snprintf(options, sizeof options,
+           "{\"codex_home\":\"%s\",\"approval_policy\":\"on-request\",\"sandbox_mode\":\"read-only\"}",
+           codex_home);
+  CodexEngine *engine = NULL;
+  char *error = NULL;
+  CodexStatus status = codex_engine_new(options, &engine, &error);
+  if (status != CODEX_STATUS_OK) {
+    fprintf(stderr, "codex_engine_new: %s\n", error != NULL ? error : "(no message)");
+    codex_string_free(error);
+    exit(1);
+  }
+  return engine;
+}
+
+static void run_turn(CodexEngine *engine, const char *prompt) {
+  TurnState state = {PTHREAD_MUTEX_INITIALIZER, PTHREAD_COND_INITIALIZER, 0, NULL};
+  char *error = NULL;
+  /* No approval request can arrive before the first message, so the callback always sees
+   * state.conversation set. */
+  CHECK(codex_conversation_start(engine, on_event, &state, &state.conversation, &error) ==
+        CODEX_STATUS_OK);
+  printf("conversation %s\n", codex_conversation_id(state.conversation));
+
+  CHECK(codex_conversation_send_message(state.conversation, prompt, &error) == CODEX_STATUS_OK);
+  pthread_mutex_lock(&state.mutex);
+  while (!state.turn_ended) {
+    pthread_cond_wait(&state.done, &state.mutex);
+  }
+  pthread_mutex_unlock(&state.mutex);
+
+  codex_conversation_free(state.conversation);
+}
+
+int main(int argc, char **argv) {
+  check_invalid_arguments();
+
+  const char *codex_home = getenv("CODEX_HOME");
+  char temp_home[] = "/tmp/codex-engine-ffi-XXXXXX";
+  if (argc < 2 || codex_home == NULL) {
+    CHECK(mkdtemp(temp_home) != NULL);
+    codex_home = temp_home;
+  }
+
+  CodexEngine *engine = new_engine(codex_home);
+  if (argc >= 2) {
+    run_turn(engine, argv[1]);
+  }
+  codex_engine_free(engine);
+
+  if (codex_home == temp_home) {
+    char command[64];
+    snprintf(command, sizeof command, "rm -rf %s", temp_home);
+    CHECK(system(command) == 0);
+  }
+  printf("ok\n");
+  return 0;
+}
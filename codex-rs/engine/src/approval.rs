@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::FileChange;
+use codex_protocol::protocol::ReviewDecision;
+
+/// Something the agent wants to do that the approval policy sends to the embedder.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ApprovalRequest {
+    /// Run a command outside the sandbox, or one the policy does not trust.
+    Exec {
+        call_id: String,
+        turn_id: String,
+        command: Vec<String>,
+        cwd: PathBuf,
+        reason: Option<String>,
+    },
+    /// Apply file changes the sandbox does not allow.
+    Patch {
+        call_id: String,
+        turn_id: String,
+        changes: HashMap<PathBuf, FileChange>,
+        reason: Option<String>,
+        /// Set when the agent asks for write access to this directory for the rest of the session.
+        grant_root: Option<PathBuf>,
+    },
+}
+
+impl ApprovalRequest {
+    /// The request carried by `msg`, if it is one.
+    pub fn from_event(msg: &EventMsg) -> Option<Self> {
+        match msg {
+            EventMsg::ExecApprovalRequest(ev) => Some(Self::Exec {
+                call_id: ev.call_id.clone(),
+                turn_id: ev.turn_id.clone(),
+                command: ev.command.clone(),
+                cwd: ev.cwd.clone(),
+                reason: ev.reason.clone(),
+            }),
+            EventMsg::ApplyPatchApprovalRequest(ev) => Some(Self::Patch {
+                call_id: ev.call_id.clone(),
+                turn_id: ev.turn_id.clone(),
+                changes: ev.changes.clone(),
+                reason: ev.reason.clone(),
+                grant_root: ev.grant_root.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Decides approval requests during [`crate::Conversation::run_turn`].
+///
+/// Closures taking `&ApprovalRequest` and returning a [`ReviewDecision`] implement this trait;
+/// implement it directly to decide asynchronously, for example by asking a person.
+#[async_trait]
+pub trait ApprovalHandler: Send + Sync {
+    async fn decide(&self, request: &ApprovalRequest) -> ReviewDecision;
+}
+
+#[async_trait]
+impl<F> ApprovalHandler for F
+where
+    F: Fn(&ApprovalRequest) -> ReviewDecision + Send + Sync,
+{
+    async fn decide(&self, request: &ApprovalRequest) -> ReviewDecision {
+        self(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::ApplyPatchApprovalRequestEvent;
+    use codex_protocol::protocol::TurnCompleteEvent;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn patch_requests_reach_closure_handlers() {
+        let msg = EventMsg::ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent {
+            call_id: "call-1".to_string(),
+            turn_id: "turn-1".to_string(),
+            changes: HashMap::new(),
+            reason: Some("needs to edit the lockfile".to_string()),
+            grant_root: None,
+        });
+        let request = ApprovalRequest::from_event(&msg).expect("approval request");
+        assert_eq!(
+            request,
+            ApprovalRequest::Patch {
+                call_id: "call-1".to_string(),
+                turn_id: "turn-1".to_string(),
+                changes: HashMap::new(),
+                reason: Some("needs to edit the lockfile".to_string()),
+                grant_root: None,
+            }
+        );
+
+        let handler = |request: &ApprovalRequest| match request {
+            ApprovalRequest::Patch { .. } => ReviewDecision::Approved,
+            _ => ReviewDecision::Denied,
+        };
+        assert_eq!(handler.decide(&request).await, ReviewDecision::Approved);
+
+        let msg = EventMsg::TurnComplete(TurnCompleteEvent {
+            turn_id: "turn-1".to_string(),
+            last_agent_message: None,
+        });
+        assert_eq!(ApprovalRequest::from_event(&msg), None);
+    }
+}
@@ -0,0 +1,186 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_core::CodexThread;
+use codex_core::ThreadManager;
+use codex_protocol::ThreadId;
+use codex_protocol::approvals::ElicitationAction;
+use codex_protocol::protocol::Event;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::Op;
+use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::protocol::TurnAbortReason;
+use codex_protocol::user_input::UserInput;
+
+use crate::ApprovalHandler;
+use crate::ApprovalRequest;
+use crate::EngineError;
+
+/// How a turn started by [`Conversation::run_turn`] ended.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum TurnOutcome {
+    Completed {
+        turn_id: String,
+        last_agent_message: Option<String>,
+    },
+    Aborted {
+        reason: TurnAbortReason,
+    },
+}
+
+/// One session with the agent.
+///
+/// Events are delivered once, to whichever caller reads them first, so either drive turns with
+/// [`Conversation::run_turn`] or read every event with [`Conversation::next_event`].
+pub struct Conversation {
+    id: ThreadId,
+    model: String,
+    thread: Arc<CodexThread>,
+    thread_manager: Arc<ThreadManager>,
+}
+
+impl Conversation {
+    pub(crate) fn new(
+        id: ThreadId,
+        model: String,
+        thread: Arc<CodexThread>,
+        thread_manager: Arc<ThreadManager>,
+    ) -> Self {
+        Self {
+            id,
+            model,
+            thread,
+            thread_manager,
+        }
+    }
+
+    pub fn id(&self) -> ThreadId {
+        self.id
+    }
+
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Where the session is recorded, for [`crate::Engine::resume_conversation`]. `None` for
+    /// ephemeral sessions.
+    pub fn rollout_path(&self) -> Option<PathBuf> {
+        self.thread.rollout_path()
+    }
+
+    /// Starts a turn with `text` as the user message and returns the submission id.
+    pub async fn send_message(&self, text: impl Into<String>) -> Result<String, EngineError> {
+        self.submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: text.into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+    }
+
+    /// Sends any operation and returns its submission id.
+    pub async fn submit(&self, op: Op) -> Result<String, EngineError> {
+        Ok(self.thread.submit(op).await?)
+    }
+
+    pub async fn next_event(&self) -> Result<Event, EngineError> {
+        Ok(self.thread.next_event().await?)
+    }
+
+    /// Answers an approval request read from [`Conversation::next_event`].
+    pub async fn resolve_approval(
+        &self,
+        request: &ApprovalRequest,
+        decision: ReviewDecision,
+    ) -> Result<(), EngineError> {
+        let op = match request {
+            ApprovalRequest::Exec {
+                call_id, turn_id, ..
+            } => Op::ExecApproval {
+                id: call_id.clone(),
+                turn_id: Some(turn_id.clone()),
+                decision,
+            },
+            ApprovalRequest::Patch { call_id, .. } => Op::PatchApproval {
+                id: call_id.clone(),
+                decision,
+            },
+        };
+        self.submit(op).await?;
+        Ok(())
+    }
+
+    /// Stops the running turn. It ends with [`TurnOutcome::Aborted`].
+    pub async fn interrupt(&self) -> Result<(), EngineError> {
+        self.submit(Op::Interrupt).await?;
+        Ok(())
+    }
+
+    /// Sends `text` and waits for the turn to end, passing approval requests to `handler`.
+    ///
+    /// Other events are dropped. Requests from MCP servers for user input are declined.
+    pub async fn run_turn<H>(
+        &self,
+        text: impl Into<String>,
+        handler: &H,
+    ) -> Result<TurnOutcome, EngineError>
+    where
+        H: ApprovalHandler + ?Sized,
+    {
+        self.send_message(text).await?;
+        let mut error = None;
+        loop {
+            let event = self.next_event().await?;
+            if let Some(request) = ApprovalRequest::from_event(&event.msg) {
+                let decision = handler.decide(&request).await;
+                self.resolve_approval(&request, decision).await?;
+                continue;
+            }
+            match event.msg {
+                EventMsg::ElicitationRequest(ev) => {
+                    self.submit(Op::ResolveElicitation {
+                        server_name: ev.server_name,
+                        request_id: ev.id,
+                        decision: ElicitationAction::Cancel,
+                    })
+                    .await?;
+                }
+                EventMsg::Error(ev) => error = Some(ev.message),
+                EventMsg::TurnComplete(ev) => {
+                    return match error {
+                        Some(message) => Err(EngineError::TurnFailed(message)),
+                        None => Ok(TurnOutcome::Completed {
+                            turn_id: ev.turn_id,
+                            last_agent_message: ev.last_agent_message,
+                        }),
+                    };
+                }
+                EventMsg::TurnAborted(ev) => {
+                    return Ok(TurnOutcome::Aborted { reason: ev.reason });
+                }
+                EventMsg::ShutdownComplete => {
+                    return Err(EngineError::TurnFailed(
+                        error.unwrap_or_else(|| "the conversation has shut down".to_string()),
+                    ));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Ends the session and waits for its history to be written. Calls on the conversation fail
+    /// afterwards.
+    pub async fn shutdown(&self) -> Result<(), EngineError> {
+        self.submit(Op::Shutdown).await?;
+        while let Ok(event) = self.thread.next_event().await {
+            if matches!(event.msg, EventMsg::ShutdownComplete) {
+                break;
+            }
+        }
+        self.thread_manager.remove_thread(&self.id).await;
+        Ok(())
+    }
+}
@@ -0,0 +1,179 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_core::AuthManager;
+use codex_core::NewThread;
+use codex_core::ThreadManager;
+use codex_core::config::Config;
+use codex_core::config::ConfigBuilder;
+use codex_core::config::ConfigOverrides;
+use codex_protocol::config_types::SandboxMode;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::SessionSource;
+use codex_utils_cli::CliConfigOverrides;
+
+use crate::Conversation;
+use crate::EngineError;
+
+/// Loads the configuration an [`Engine`] runs with.
+///
+/// Settings left unset come from `config.toml` in the Codex home, as they do for the CLI.
+#[derive(Debug, Default, Clone)]
+pub struct EngineBuilder {
+    codex_home: Option<PathBuf>,
+    cwd: Option<PathBuf>,
+    model: Option<String>,
+    approval_policy: Option<AskForApproval>,
+    sandbox_mode: Option<SandboxMode>,
+    linux_sandbox_exe: Option<PathBuf>,
+    config_overrides: Vec<String>,
+}
+
+impl EngineBuilder {
+    /// Directory holding `config.toml`, credentials, and session rollouts. Defaults to
+    /// `$CODEX_HOME` or `~/.codex`.
+    pub fn codex_home(mut self, codex_home: impl Into<PathBuf>) -> Self {
+        self.codex_home = Some(codex_home.into());
+        self
+    }
+
+    /// Working directory of the agent. Defaults to the process's current directory.
+    pub fn cwd(mut self, cwd: impl Into<PathBuf>) -> Self {
+        self.cwd = Some(cwd.into());
+        self
+    }
+
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// When the agent stops to ask; see [`crate::Conversation::run_turn`] for answering.
+    pub fn approval_policy(mut self, approval_policy: AskForApproval) -> Self {
+        self.approval_policy = Some(approval_policy);
+        self
+    }
+
+    pub fn sandbox_mode(mut self, sandbox_mode: SandboxMode) -> Self {
+        self.sandbox_mode = Some(sandbox_mode);
+        self
+    }
+
+    /// Path of the `codex-linux-sandbox` helper. Commands cannot be sandboxed on Linux without it.
+    pub fn linux_sandbox_exe(mut self, linux_sandbox_exe: impl Into<PathBuf>) -> Self {
+        self.linux_sandbox_exe = Some(linux_sandbox_exe.into());
+        self
+    }
+
+    /// Overrides one setting, written `key=value` as with `codex -c`. The value is parsed as
+    /// TOML, and used as a string when that fails.
+    pub fn config_override(mut self, key_value: impl Into<String>) -> Self {
+        self.config_overrides.push(key_value.into());
+        self
+    }
+
+    pub async fn build(self) -> Result<Engine, EngineError> {
+        let cli_overrides = CliConfigOverrides {
+            raw_overrides: self.config_overrides,
+        }
+        .parse_overrides()
+        .map_err(|message| {
+            EngineError::Config(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                message,
+            ))
+        })?;
+        let overrides = ConfigOverrides {
+            model: self.model,
+            cwd: self.cwd,
+            approval_policy: self.approval_policy,
+            sandbox_mode: self.sandbox_mode,
+            codex_linux_sandbox_exe: self.linux_sandbox_exe,
+            ..Default::default()
+        };
+        let mut builder = ConfigBuilder::default()
+            .cli_overrides(cli_overrides)
+            .harness_overrides(overrides);
+        if let Some(codex_home) = self.codex_home {
+            builder = builder.codex_home(codex_home);
+        }
+        let config = builder.build().await.map_err(EngineError::Config)?;
+
+        let auth_manager = AuthManager::shared(
+            config.auth_home.clone(),
+            true,
+            config.cli_auth_credentials_store_mode,
+        );
+        let thread_manager = Arc::new(ThreadManager::new(
+            config.codex_home.clone(),
+            Arc::clone(&auth_manager),
+            SessionSource::Exec,
+        ));
+        Ok(Engine {
+            config,
+            auth_manager,
+            thread_manager,
+        })
+    }
+}
+
+/// Starts and resumes conversations. Conversations keep running while any of them is alive,
+/// even after the engine is dropped.
+pub struct Engine {
+    config: Config,
+    auth_manager: Arc<AuthManager>,
+    thread_manager: Arc<ThreadManager>,
+}
+
+impl Engine {
+    pub fn builder() -> EngineBuilder {
+        EngineBuilder::default()
+    }
+
+    pub fn codex_home(&self) -> &Path {
+        &self.config.codex_home
+    }
+
+    pub fn cwd(&self) -> &Path {
+        &self.config.cwd
+    }
+
+    pub async fn start_conversation(&self) -> Result<Conversation, EngineError> {
+        let new_thread = self
+            .thread_manager
+            .start_thread(self.config.clone())
+            .await?;
+        Ok(self.conversation(new_thread))
+    }
+
+    /// Continues the session recorded at `rollout_path`; see [`Conversation::rollout_path`].
+    pub async fn resume_conversation(
+        &self,
+        rollout_path: impl Into<PathBuf>,
+    ) -> Result<Conversation, EngineError> {
+        let new_thread = self
+            .thread_manager
+            .resume_thread_from_rollout(
+                self.config.clone(),
+                rollout_path.into(),
+                Arc::clone(&self.auth_manager),
+            )
+            .await?;
+        Ok(self.conversation(new_thread))
+    }
+
+    fn conversation(&self, new_thread: NewThread) -> Conversation {
+        let NewThread {
+            thread_id,
+            thread,
+            session_configured,
+        } = new_thread;
+        Conversation::new(
+            thread_id,
+            session_configured.model,
+            thread,
+            Arc::clone(&self.thread_manager),
+        )
+    }
+}
@@ -0,0 +1,24 @@
+use codex_core::error::CodexErr;
+
+/// Errors returned by the engine.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum EngineError {
+    /// `config.toml` or an override could not be loaded.
+    #[error("failed to load configuration: {0}")]
+    Config(#[source] std::io::Error),
+
+    /// The agent could not start, or the conversation has ended.
+    #[error(transparent)]
+    Session(Box<dyn std::error::Error + Send + Sync>),
+
+    /// The turn ended with an error event.
+    #[error("turn failed: {0}")]
+    TurnFailed(String),
+}
+
+impl From<CodexErr> for EngineError {
+    fn from(err: CodexErr) -> Self {
+        Self::Session(Box::new(err))
+    }
+}
@@ -0,0 +1,54 @@
+//! Embed the Codex agent in a Rust application.
+//!
+//! ```no_run
+//! use codex_engine::ApprovalRequest;
+//! use codex_engine::Engine;
+//! use codex_engine::TurnOutcome;
+//! use codex_engine::protocol::ReviewDecision;
+//!
+//! # async fn run() -> Result<(), codex_engine::EngineError> {
+//! let engine = Engine::builder().cwd("/path/to/repo").build().await?;
+//! let conversation = engine.start_conversation().await?;
+//! let decline_all = |_: &ApprovalRequest| ReviewDecision::Denied;
+//! let outcome = conversation
+//!     .run_turn("Fix the failing test", &decline_all)
+//!     .await?;
+//! if let TurnOutcome::Completed { last_agent_message, .. } = outcome {
+//!     println!("{}", last_agent_message.unwrap_or_default());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The items exported from this crate are its stable surface and follow semver: a release that
+//! removes or changes any of them bumps the major version. Types marked `#[non_exhaustive]` may
+//! gain variants and fields in minor releases. [`protocol`] re-exports the wire types that the CLI
+//! and the app server share; they grow as the agent does, so match them with a wildcard arm.
+//! Nothing else in the workspace, `codex-core` included, is covered by this promise.
+
+mod approval;
+mod conversation;
+mod engine;
+mod error;
+
+pub use approval::ApprovalHandler;
+pub use approval::ApprovalRequest;
+pub use conversation::Conversation;
+pub use conversation::TurnOutcome;
+pub use engine::Engine;
+pub use engine::EngineBuilder;
+pub use error::EngineError;
+
+/// Operations, events, and the types they carry.
+pub mod protocol {
+    pub use codex_protocol::ThreadId;
+    pub use codex_protocol::config_types::SandboxMode;
+    pub use codex_protocol::protocol::AskForApproval;
+    pub use codex_protocol::protocol::Event;
+    pub use codex_protocol::protocol::EventMsg;
+    pub use codex_protocol::protocol::FileChange;
+    pub use codex_protocol::protocol::Op;
+    pub use codex_protocol::protocol::ReviewDecision;
+    pub use codex_protocol::protocol::TurnAbortReason;
+    pub use codex_protocol::user_input::UserInput;
+}
@@ -58,33 +58,45 @@
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Context as _;
 use clap::Parser;
+use codex_core::config::Config;
 use codex_core::config::find_codex_home;
-use codex_core::is_dangerous_command::command_might_be_dangerous;
-use codex_core::sandboxing::SandboxPermissions;
+use codex_core::evaluate_intercepted_exec;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Policy;
-use codex_execpolicy::RuleMatch;
+use codex_otel::otel_provider::OtelProvider;
 use rmcp::ErrorData as McpError;
 use tokio::sync::RwLock;
 use tracing_subscriber::EnvFilter;
+use tracing_subscriber::Layer as _;
+use tracing_subscriber::layer::SubscriberExt as _;
+use tracing_subscriber::util::SubscriberInitExt as _;
 use tracing_subscriber::{self};
 
 use crate::posix::mcp_escalation_policy::ExecPolicyOutcome;
 
+mod background;
 mod escalate_client;
 mod escalate_protocol;
 mod escalate_server;
 mod escalation_policy;
 mod mcp;
 mod mcp_escalation_policy;
+mod reaper;
+mod self_test;
 mod socket;
+mod stdin_pipe;
 mod stopwatch;
+mod warm_pool;
 
 pub use mcp::ExecResult;
 
+/// Service name reported on exported spans.
+const OTEL_SERVICE_NAME: &str = "codex_exec_mcp_server";
+
 /// Default value of --execve option relative to the current executable.
 /// Note this must match the name of the binary as specified in Cargo.toml.
 const CODEX_EXECVE_WRAPPER_EXE_NAME: &str = "codex-execve-wrapper";
@@ -104,17 +116,58 @@ struct McpServerCli {
     /// Note: this does change the actual program being run.
     #[arg(long)]
     preserve_program_paths: bool,
+
+    /// Number of shells to keep started and ready for `shell` calls; 0 disables them.
+    #[arg(long = "warm-pool", default_value_t = 2)]
+    warm_pool_size: usize,
+
+    /// Replace warm shells that have been idle for longer than this many seconds.
+    #[arg(long, default_value_t = 300)]
+    warm_pool_max_idle_secs: u64,
 }
 
 #[tokio::main]
 pub async fn main_mcp_server() -> anyhow::Result<()> {
-    tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::from_default_env())
+    let fmt_layer = tracing_subscriber::fmt::layer()
         .with_writer(std::io::stderr)
         .with_ansi(false)
-        .init();
+        .with_filter(EnvFilter::from_default_env());
+    let config = Config::load_with_cli_overrides(Vec::new())
+        .await
+        .context("failed to load config");
+    // Export spans with the same `[otel]` settings as the Codex session that
+    // launched us; tool calls carry the caller's trace context in `_meta`.
+    let (otel, otel_error) = match config.as_ref().map(build_otel_provider) {
+        Ok(Ok(otel)) => (otel, None),
+        Ok(Err(err)) => (None, Some(err)),
+        Err(err) => (None, Some(anyhow::anyhow!("{err:#}"))),
+    };
+    let otel_tracing_layer = otel.as_ref().and_then(|o| o.tracing_layer());
+    let _ = tracing_subscriber::registry()
+        .with(fmt_layer)
+        .with(otel_tracing_layer)
+        .try_init();
+    if let Some(err) = otel_error {
+        tracing::warn!("could not create otel exporter: {err:#}");
+    }
 
     let cli = McpServerCli::parse();
+    if let Ok(config) = &config {
+        codex_core::text_encoding::set_output_encoding_override(config.exec_output_encoding);
+        codex_core::output_escapes::set_output_escapes(config.exec_output_escapes);
+        codex_core::seatbelt_profile::set_profile_additions(config.macos_seatbelt.clone());
+        codex_core::sandbox_devices::set_allowed_devices(config.sandbox_allowed_devices.clone());
+    }
+    if let Err(err) = reaper::become_subreaper() {
+        tracing::warn!("could not become a child subreaper: {err}");
+    }
+    let stale = tokio::task::spawn_blocking(reaper::reap_stale_execs).await?;
+    if !stale.is_empty() {
+        tracing::info!(
+            "killed {} processes left behind by an exited server",
+            stale.len()
+        );
+    }
     let execve_wrapper = match cli.execve_wrapper {
         Some(path) => path,
         None => {
@@ -138,13 +191,22 @@ pub async fn main_mcp_server() -> anyhow::Result<()> {
         execve_wrapper,
         policy,
         cli.preserve_program_paths,
+        warm_pool::WarmPoolConfig {
+            size: cli.warm_pool_size,
+            max_idle: Duration::from_secs(cli.warm_pool_max_idle_secs),
+        },
     )
     .await
     .inspect_err(|e| {
         tracing::error!("serving error: {:?}", e);
     })?;
 
-    service.waiting().await?;
+    let quit_reason = service.waiting().await;
+    let orphans = tokio::task::spawn_blocking(reaper::reap_all_execs).await?;
+    if !orphans.is_empty() {
+        tracing::info!("killed {} processes on shutdown", orphans.len());
+    }
+    quit_reason?;
     Ok(())
 }
 
@@ -189,27 +251,9 @@ pub(crate) fn evaluate_exec_policy(
         // Use the normalized program name instead of argv[0].
         .chain(argv.iter().skip(1).cloned())
         .collect();
-    let evaluation = policy.check(&command, &|cmd| {
-        if command_might_be_dangerous(cmd) {
-            Decision::Prompt
-        } else {
-            Decision::Allow
-        }
-    });
-
-    // decisions driven by policy should run outside sandbox
-    let decision_driven_by_policy = evaluation.matched_rules.iter().any(|rule_match| {
-        !matches!(rule_match, RuleMatch::HeuristicsRuleMatch { .. })
-            && rule_match.decision() == evaluation.decision
-    });
+    let (decision, sandbox_permissions) = evaluate_intercepted_exec(policy, &command);
 
-    let sandbox_permissions = if decision_driven_by_policy {
-        SandboxPermissions::RequireEscalated
-    } else {
-        SandboxPermissions::UseDefault
-    };
-
-    Ok(match evaluation.decision {
+    Ok(match decision {
         Decision::Forbidden => ExecPolicyOutcome::Forbidden,
         Decision::Prompt => ExecPolicyOutcome::Prompt {
             sandbox_permissions,
@@ -228,6 +272,19 @@ fn format_program_name(path: &Path, preserve_program_paths: bool) -> Option<Stri
     }
 }
 
+fn build_otel_provider(config: &Config) -> anyhow::Result<Option<OtelProvider>> {
+    let mut config = config.clone();
+    // The Codex process that launched us already serves the metrics endpoint.
+    config.otel.prometheus_listen_addr = None;
+    codex_core::otel_init::build_provider(
+        &config,
+        env!("CARGO_PKG_VERSION"),
+        Some(OTEL_SERVICE_NAME),
+        false,
+    )
+    .map_err(|err| anyhow::anyhow!("{err}"))
+}
+
 async fn load_exec_policy() -> anyhow::Result<Policy> {
     let codex_home = find_codex_home().context("failed to resolve codex_home for execpolicy")?;
 
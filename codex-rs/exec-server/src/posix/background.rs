@@ -0,0 +1,303 @@
+//! Commands started by the `shell` tool with `background: true`.
+//!
+//! The tool returns as soon as a background command starts. Its output is
+//! buffered here until `exec_attach` collects it, and `list_execs` shows every
+//! background command this server has started. `exec_stdin` feeds its stdin.
+//! A background command runs until it exits, its `timeout_ms` (if any)
+//! elapses, or `exec_kill` stops it.
+
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_core::output_escapes::normalize_output;
+use codex_core::text_encoding::bytes_to_string_smart;
+use rmcp::schemars;
+use tokio::sync::mpsc;
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
+
+/// Output kept for a background command between two `exec_attach` calls.
+/// Older output is dropped first.
+const MAX_PENDING_OUTPUT_BYTES: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, schemars::JsonSchema)]
+pub struct BackgroundExecStarted {
+    /// Pass this to `exec_attach` or `exec_kill`.
+    pub exec_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, schemars::JsonSchema)]
+pub struct BackgroundExecInfo {
+    pub exec_id: String,
+    pub command: String,
+    pub workdir: String,
+    pub running: bool,
+    /// Set once the command has exited.
+    pub exit_code: Option<i32>,
+    /// Time since the command started, or how long it ran.
+    pub duration: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, schemars::JsonSchema)]
+pub struct ExecAttachResult {
+    pub exec_id: String,
+    pub running: bool,
+    /// Set once the command has exited.
+    pub exit_code: Option<i32>,
+    /// Whether the command was stopped by its timeout.
+    pub timed_out: bool,
+    /// Why the command could not be run, if it failed to start.
+    pub error: Option<String>,
+    /// Output produced since the previous `exec_attach` for this command.
+    pub output: String,
+    /// Bytes of output that were dropped because nobody collected them in time.
+    pub dropped_bytes: usize,
+}
+
+/// How a background command ended.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct BackgroundExecOutcome {
+    pub(crate) exit_code: Option<i32>,
+    pub(crate) timed_out: bool,
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Default)]
+pub(crate) struct BackgroundExecs {
+    next_id: AtomicU64,
+    execs: Mutex<BTreeMap<u64, Arc<BackgroundExec>>>,
+}
+
+impl BackgroundExecs {
+    pub(crate) fn start(&self, command: &str, workdir: &str) -> Arc<BackgroundExec> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let (finished, _) = watch::channel(None);
+        let exec = Arc::new(BackgroundExec {
+            exec_id: format!("bg-{id}"),
+            command: command.to_string(),
+            workdir: workdir.to_string(),
+            started_at: Instant::now(),
+            cancellation_token: CancellationToken::new(),
+            pending: Mutex::new(PendingOutput::default()),
+            stdin: Mutex::new(None),
+            finished,
+        });
+        if let Ok(mut execs) = self.execs.lock() {
+            execs.insert(id, Arc::clone(&exec));
+        }
+        exec
+    }
+
+    pub(crate) fn get(&self, exec_id: &str) -> Option<Arc<BackgroundExec>> {
+        let id = exec_id.strip_prefix("bg-")?.parse::<u64>().ok()?;
+        self.execs.lock().ok()?.get(&id).cloned()
+    }
+
+    pub(crate) fn list(&self) -> Vec<BackgroundExecInfo> {
+        self.execs
+            .lock()
+            .map(|execs| execs.values().map(|exec| exec.info()).collect())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Default)]
+struct PendingOutput {
+    /// Ring of the most recent output, at most `MAX_PENDING_OUTPUT_BYTES`.
+    bytes: VecDeque<u8>,
+    dropped_bytes: usize,
+}
+
+pub(crate) struct BackgroundExec {
+    exec_id: String,
+    command: String,
+    workdir: String,
+    started_at: Instant,
+    cancellation_token: CancellationToken,
+    pending: Mutex<PendingOutput>,
+    /// Feeds the command's stdin until the caller sends end of file.
+    stdin: Mutex<Option<mpsc::UnboundedSender<Vec<u8>>>>,
+    finished: watch::Sender<Option<(BackgroundExecOutcome, Duration)>>,
+}
+
+impl BackgroundExec {
+    pub(crate) fn exec_id(&self) -> &str {
+        &self.exec_id
+    }
+
+    /// Cancelling this token kills the command.
+    pub(crate) fn cancellation_token(&self) -> CancellationToken {
+        self.cancellation_token.clone()
+    }
+
+    pub(crate) fn set_stdin(&self, stdin: mpsc::UnboundedSender<Vec<u8>>) {
+        if let Ok(mut current) = self.stdin.lock() {
+            *current = Some(stdin);
+        }
+    }
+
+    /// Writes `data` to the command's stdin, then closes it if `eof` is set.
+    pub(crate) fn write_stdin(&self, data: Vec<u8>, eof: bool) -> Result<(), String> {
+        let mut stdin = self
+            .stdin
+            .lock()
+            .map_err(|_| "stdin is unavailable".to_string())?;
+        let Some(sender) = stdin.as_ref() else {
+            return Err(format!("stdin of {} is already closed", self.exec_id));
+        };
+        if !data.is_empty() && sender.send(data).is_err() {
+            *stdin = None;
+            return Err(format!("{} is no longer reading stdin", self.exec_id));
+        }
+        if eof {
+            *stdin = None;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn push_output(&self, chunk: &[u8]) {
+        let Ok(mut pending) = self.pending.lock() else {
+            return;
+        };
+        // Make room first, so the ring never grows past its cap.
+        let skipped = chunk.len().saturating_sub(MAX_PENDING_OUTPUT_BYTES);
+        let chunk = &chunk[skipped..];
+        let overflow = pending
+            .bytes
+            .len()
+            .saturating_add(chunk.len())
+            .saturating_sub(MAX_PENDING_OUTPUT_BYTES);
+        pending.bytes.drain(..overflow);
+        pending.dropped_bytes += overflow + skipped;
+        pending.bytes.extend(chunk);
+    }
+
+    /// Records how the command ended. Call after all output has been pushed.
+    pub(crate) fn finish(&self, outcome: BackgroundExecOutcome) {
+        if let Ok(mut stdin) = self.stdin.lock() {
+            *stdin = None;
+        }
+        let duration = self.started_at.elapsed();
+        self.finished.send_replace(Some((outcome, duration)));
+    }
+
+    /// Waits up to `wait` for the command to exit, then returns the output
+    /// produced since the last attach. A zero `wait` polls.
+    pub(crate) async fn attach(&self, wait: Duration) -> ExecAttachResult {
+        let mut finished = self.finished.subscribe();
+        let _ = tokio::time::timeout(wait, finished.wait_for(Option::is_some)).await;
+        let outcome = self.finished.borrow().clone().map(|(outcome, _)| outcome);
+        let (output, dropped_bytes) = match self.pending.lock() {
+            Ok(mut pending) => (
+                normalize_output(bytes_to_string_smart(&Vec::from(std::mem::take(
+                    &mut pending.bytes,
+                )))),
+                std::mem::take(&mut pending.dropped_bytes),
+            ),
+            Err(_) => (String::new(), 0),
+        };
+        let outcome = outcome.as_ref();
+        ExecAttachResult {
+            exec_id: self.exec_id.clone(),
+            running: outcome.is_none(),
+            exit_code: outcome.and_then(|outcome| outcome.exit_code),
+            timed_out: outcome.is_some_and(|outcome| outcome.timed_out),
+            error: outcome.and_then(|outcome| outcome.error.clone()),
+            output,
+            dropped_bytes,
+        }
+    }
+
+    fn info(&self) -> BackgroundExecInfo {
+        let finished = self.finished.borrow();
+        let (exit_code, duration) = match finished.as_ref() {
+            Some((outcome, duration)) => (outcome.exit_code, *duration),
+            None => (None, self.started_at.elapsed()),
+        };
+        BackgroundExecInfo {
+            exec_id: self.exec_id.clone(),
+            command: self.command.clone(),
+            workdir: self.workdir.clone(),
+            running: finished.is_none(),
+            exit_code,
+            duration,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn attach_returns_output_since_previous_attach() {
+        let execs = BackgroundExecs::default();
+        let exec = execs.start("npm run dev", "/repo");
+        assert_eq!(exec.exec_id(), "bg-1");
+
+        exec.push_output(b"compiling\n");
+        let first = exec.attach(Duration::ZERO).await;
+        assert_eq!(first.output, "compiling\n");
+        assert!(first.running);
+
+        exec.push_output(b"ready on :3000\n");
+        exec.finish(BackgroundExecOutcome {
+            exit_code: Some(0),
+            ..Default::default()
+        });
+        let second = exec.attach(Duration::from_secs(5)).await;
+        assert_eq!(
+            second,
+            ExecAttachResult {
+                exec_id: "bg-1".to_string(),
+                running: false,
+                exit_code: Some(0),
+                timed_out: false,
+                error: None,
+                output: "ready on :3000\n".to_string(),
+                dropped_bytes: 0,
+            }
+        );
+
+        let listed = execs.list();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].exit_code, Some(0));
+        assert!(execs.get("bg-1").is_some());
+        assert!(execs.get("bg-2").is_none());
+    }
+
+    #[test]
+    fn stdin_rejects_writes_after_eof() {
+        let exec = BackgroundExecs::default().start("python -", "/");
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        exec.set_stdin(tx);
+
+        assert_eq!(exec.write_stdin(b"print(1)\n".to_vec(), true), Ok(()));
+        assert_eq!(rx.try_recv(), Ok(b"print(1)\n".to_vec()));
+        assert!(rx.try_recv().is_err());
+        assert_eq!(
+            exec.write_stdin(b"print(2)\n".to_vec(), false),
+            Err("stdin of bg-1 is already closed".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn pending_output_keeps_the_newest_bytes() {
+        let exec = BackgroundExecs::default().start("yes", "/");
+        exec.push_output(&vec![b'a'; MAX_PENDING_OUTPUT_BYTES]);
+        exec.push_output(b"tail");
+
+        let attached = exec.attach(Duration::ZERO).await;
+
+        assert_eq!(attached.dropped_bytes, 4);
+        assert!(attached.output.ends_with("tail"));
+        assert_eq!(attached.output.len(), MAX_PENDING_OUTPUT_BYTES);
+    }
+}
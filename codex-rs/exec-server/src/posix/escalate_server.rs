@@ -1,8 +1,12 @@
 use std::collections::HashMap;
 use std::os::fd::AsRawFd;
+use std::os::fd::OwnedFd;
+use std::os::fd::RawFd;
+use std::path::Path;
 use std::path::PathBuf;
 use std::process::Stdio;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::time::Duration;
 
 use anyhow::Context as _;
@@ -14,6 +18,10 @@ use codex_core::protocol_config_types::WindowsSandboxLevel;
 use codex_core::sandboxing::SandboxPermissions;
 use tokio::process::Command;
 use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
+use tracing::Span;
+use tracing::field;
+use tracing::info_span;
 
 use crate::posix::escalate_protocol::ESCALATE_SOCKET_ENV_VAR;
 use crate::posix::escalate_protocol::EXEC_WRAPPER_ENV_VAR;
@@ -25,9 +33,15 @@ use crate::posix::escalate_protocol::SuperExecMessage;
 use crate::posix::escalate_protocol::SuperExecResult;
 use crate::posix::escalation_policy::EscalationPolicy;
 use crate::posix::mcp::ExecParams;
+use crate::posix::reaper::EXEC_ID_ENV_VAR;
+use crate::posix::reaper::ReapedProcess;
+use crate::posix::reaper::next_exec_id;
+use crate::posix::reaper::reap_exec;
 use crate::posix::socket::AsyncDatagramSocket;
 use crate::posix::socket::AsyncSocket;
+use crate::posix::stdin_pipe::redirect_stdin;
 use codex_core::exec::ExecExpiration;
+use codex_core::exec::StdoutStream;
 
 pub(crate) struct EscalateServer {
     bash_path: PathBuf,
@@ -52,32 +66,37 @@ impl EscalateServer {
         params: ExecParams,
         cancel_rx: CancellationToken,
         sandbox_state: &SandboxState,
+        stdout_stream: Option<StdoutStream>,
+        stdin: Option<OwnedFd>,
     ) -> anyhow::Result<ExecResult> {
         let (escalate_server, escalate_client) = AsyncDatagramSocket::pair()?;
         let client_socket = escalate_client.into_inner();
         client_socket.set_cloexec(false)?;
 
-        let escalate_task = tokio::spawn(escalate_task(escalate_server, self.policy.clone()));
-        let mut env = std::env::vars().collect::<HashMap<String, String>>();
-        env.insert(
-            ESCALATE_SOCKET_ENV_VAR.to_string(),
-            client_socket.as_raw_fd().to_string(),
-        );
-        env.insert(
-            EXEC_WRAPPER_ENV_VAR.to_string(),
-            self.execve_wrapper.to_string_lossy().to_string(),
-        );
-        env.insert(
-            LEGACY_BASH_EXEC_WRAPPER_ENV_VAR.to_string(),
-            self.execve_wrapper.to_string_lossy().to_string(),
+        let exec_id = next_exec_id();
+        let escalated_process_groups = Arc::new(Mutex::new(Vec::new()));
+        let escalate_task = tokio::spawn(
+            escalate_task(
+                escalate_server,
+                self.policy.clone(),
+                Arc::clone(&escalated_process_groups),
+            )
+            .in_current_span(),
         );
+        let env = intercepted_shell_env(&exec_id, client_socket.as_raw_fd(), &self.execve_wrapper);
 
         let ExecParams {
             command,
             workdir,
             timeout_ms: _,
             login,
+            background: _,
+            stdin: _,
         } = params;
+        let command = match &stdin {
+            Some(fd) => redirect_stdin(&command, fd),
+            None => command,
+        };
         let result = process_exec_tool_call(
             codex_core::exec::ExecParams {
                 command: vec![
@@ -103,23 +122,61 @@ impl EscalateServer {
             &sandbox_state.sandbox_cwd,
             &sandbox_state.codex_linux_sandbox_exe,
             sandbox_state.use_linux_sandbox_bwrap,
-            None,
+            stdout_stream,
         )
-        .await?;
+        .await;
+        drop(stdin);
         escalate_task.abort();
+        // Kill anything the command left running, whether it exited, timed
+        // out, or failed to run.
+        let escalated_process_groups = escalated_process_groups
+            .lock()
+            .map(|groups| groups.clone())
+            .unwrap_or_default();
+        let orphans =
+            tokio::task::spawn_blocking(move || reap_exec(&exec_id, &escalated_process_groups))
+                .await
+                .unwrap_or_default();
+        let result = result?;
         let result = ExecResult {
             exit_code: result.exit_code,
             output: result.aggregated_output.text,
             duration: result.duration,
             timed_out: result.timed_out,
+            orphans,
         };
         Ok(result)
     }
 }
 
-async fn escalate_task(
+/// The server's environment, plus what the shell of exec `exec_id` needs to
+/// send its `execve(2)` calls to the server over `escalate_socket`.
+pub(crate) fn intercepted_shell_env(
+    exec_id: &str,
+    escalate_socket: RawFd,
+    execve_wrapper: &Path,
+) -> HashMap<String, String> {
+    let mut env = std::env::vars().collect::<HashMap<String, String>>();
+    env.insert(EXEC_ID_ENV_VAR.to_string(), exec_id.to_string());
+    env.insert(
+        ESCALATE_SOCKET_ENV_VAR.to_string(),
+        escalate_socket.to_string(),
+    );
+    env.insert(
+        EXEC_WRAPPER_ENV_VAR.to_string(),
+        execve_wrapper.to_string_lossy().to_string(),
+    );
+    env.insert(
+        LEGACY_BASH_EXEC_WRAPPER_ENV_VAR.to_string(),
+        execve_wrapper.to_string_lossy().to_string(),
+    );
+    env
+}
+
+pub(crate) async fn escalate_task(
     socket: AsyncDatagramSocket,
     policy: Arc<dyn EscalationPolicy>,
+    escalated_process_groups: Arc<Mutex<Vec<u32>>>,
 ) -> anyhow::Result<()> {
     loop {
         let (_, mut fds) = socket.receive_with_fds().await?;
@@ -129,11 +186,29 @@ async fn escalate_task(
         }
         let stream_socket = AsyncSocket::from_fd(fds.remove(0))?;
         let policy = policy.clone();
-        tokio::spawn(async move {
-            if let Err(err) = handle_escalate_session_with_policy(stream_socket, policy).await {
-                tracing::error!("escalate session failed: {err:?}");
+        let escalated_process_groups = Arc::clone(&escalated_process_groups);
+        // One span per intercepted exec(), including the time spent waiting on
+        // the policy decision (which may prompt the user).
+        let span = info_span!(
+            "exec_server.intercepted_exec",
+            program = field::Empty,
+            decision = field::Empty,
+            exit_code = field::Empty,
+        );
+        tokio::spawn(
+            async move {
+                if let Err(err) = handle_escalate_session_with_policy(
+                    stream_socket,
+                    policy,
+                    escalated_process_groups,
+                )
+                .await
+                {
+                    tracing::error!("escalate session failed: {err:?}");
+                }
             }
-        });
+            .instrument(span),
+        );
     }
 }
 
@@ -143,11 +218,14 @@ pub(crate) struct ExecResult {
     pub(crate) output: String,
     pub(crate) duration: Duration,
     pub(crate) timed_out: bool,
+    /// Processes still running when the command finished, which were killed.
+    pub(crate) orphans: Vec<ReapedProcess>,
 }
 
 async fn handle_escalate_session_with_policy(
     socket: AsyncSocket,
     policy: Arc<dyn EscalationPolicy>,
+    escalated_process_groups: Arc<Mutex<Vec<u32>>>,
 ) -> anyhow::Result<()> {
     let EscalateRequest {
         file,
@@ -162,6 +240,16 @@ async fn handle_escalate_session_with_policy(
         .await?;
 
     tracing::debug!("decided {action:?} for {file:?} {argv:?} {workdir:?}");
+    let span = Span::current();
+    span.record("program", file.display().to_string());
+    span.record(
+        "decision",
+        match &action {
+            EscalateAction::Run => "run",
+            EscalateAction::Escalate => "escalate",
+            EscalateAction::Deny { .. } => "deny",
+        },
+    );
 
     match action {
         EscalateAction::Run => {
@@ -205,6 +293,9 @@ async fn handle_escalate_session_with_policy(
                 .arg0(argv[0].clone())
                 .envs(&env)
                 .current_dir(&workdir)
+                // Its own group, so that it and anything it spawns can be
+                // killed when the exec finishes.
+                .process_group(0)
                 .stdin(Stdio::null())
                 .stdout(Stdio::null())
                 .stderr(Stdio::null());
@@ -217,12 +308,13 @@ async fn handle_escalate_session_with_policy(
                 });
             }
             let mut child = command.spawn()?;
+            if let (Some(pid), Ok(mut groups)) = (child.id(), escalated_process_groups.lock()) {
+                groups.push(pid);
+            }
             let exit_status = child.wait().await?;
-            socket
-                .send(SuperExecResult {
-                    exit_code: exit_status.code().unwrap_or(127),
-                })
-                .await?;
+            let exit_code = exit_status.code().unwrap_or(127);
+            span.record("exit_code", exit_code);
+            socket.send(SuperExecResult { exit_code }).await?;
         }
         EscalateAction::Deny { reason } => {
             socket
@@ -267,6 +359,7 @@ mod tests {
             Arc::new(DeterministicEscalationPolicy {
                 action: EscalateAction::Run,
             }),
+            Arc::default(),
         ));
 
         let mut env = HashMap::new();
@@ -302,6 +395,7 @@ mod tests {
             Arc::new(DeterministicEscalationPolicy {
                 action: EscalateAction::Escalate,
             }),
+            Arc::default(),
         ));
 
         client
@@ -1,14 +1,23 @@
+use std::os::fd::OwnedFd;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::Context as _;
 use anyhow::Result;
+use base64::Engine as _;
+use base64::prelude::BASE64_STANDARD;
 use codex_core::MCP_SANDBOX_STATE_CAPABILITY;
 use codex_core::MCP_SANDBOX_STATE_METHOD;
+use codex_core::MCP_SHELL_SELF_TEST_METHOD;
 use codex_core::SandboxState;
+use codex_core::exec::StdoutStream;
+use codex_core::protocol::EventMsg;
 use codex_core::protocol::SandboxPolicy;
 use codex_execpolicy::Policy;
+use codex_otel::otel_provider::TRACEPARENT_KEY;
+use codex_otel::otel_provider::TRACESTATE_KEY;
+use codex_otel::otel_provider::set_parent_from_trace_context;
 use rmcp::ErrorData as McpError;
 use rmcp::RoleServer;
 use rmcp::ServerHandler;
@@ -19,6 +28,7 @@ use rmcp::model::CustomRequest;
 use rmcp::model::CustomResult;
 use rmcp::model::*;
 use rmcp::schemars;
+use rmcp::service::Peer;
 use rmcp::service::RequestContext;
 use rmcp::service::RunningService;
 use rmcp::tool;
@@ -27,17 +37,44 @@ use rmcp::tool_router;
 use rmcp::transport::stdio;
 use serde_json::json;
 use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
+use tracing::Span;
+use tracing::field;
+use tracing::info_span;
 
+use crate::posix::background::BackgroundExec;
+use crate::posix::background::BackgroundExecOutcome;
+use crate::posix::background::BackgroundExecStarted;
+use crate::posix::background::BackgroundExecs;
 use crate::posix::escalate_server::EscalateServer;
 use crate::posix::escalate_server::{self};
 use crate::posix::mcp_escalation_policy::McpEscalationPolicy;
+use crate::posix::reaper::ReapedProcess;
+use crate::posix::self_test::run_self_test;
+use crate::posix::stdin_pipe::stdin_pipe;
 use crate::posix::stopwatch::Stopwatch;
+use crate::posix::warm_pool::WarmPool;
+use crate::posix::warm_pool::WarmPoolConfig;
 
 /// Path to our patched bash.
 const CODEX_BASH_PATH_ENV_VAR: &str = "CODEX_BASH_PATH";
 
 const SANDBOX_STATE_CAPABILITY_VERSION: &str = "1.0.0";
 
+/// Custom request that writes to the stdin of a background command. Params
+/// are `{"execId", "dataBase64", "eof"}`; the same as the `exec_stdin` tool,
+/// for clients that send raw bytes.
+pub const EXEC_STDIN_METHOD: &str = "zsh/execStdin";
+
+/// How long `exec_kill` waits for the command to exit before returning.
+const BACKGROUND_KILL_WAIT: Duration = Duration::from_secs(5);
+
+/// Sent after a `shell` call when processes it started were still running
+/// and had to be killed. Params are `{"workdir", "processes": [{"pid",
+/// "command"}]}`.
+pub const ORPHAN_REAPED_NOTIFICATION: &str = "zsh/event/orphanReaped";
+
 pub(crate) fn get_bash_path() -> Result<PathBuf> {
     std::env::var(CODEX_BASH_PATH_ENV_VAR)
         .map(PathBuf::from)
@@ -54,6 +91,10 @@ pub struct ExecParams {
     pub timeout_ms: Option<u64>,
     /// Launch Bash with -lc instead of -c: defaults to true.
     pub login: Option<bool>,
+    /// Return as soon as the command starts and keep it running (e.g. a dev server). Collect its output with `exec_attach`; without `timeout_ms` it runs until it exits or `exec_kill` stops it.
+    pub background: Option<bool>,
+    /// Text to write to the command's stdin, which is otherwise empty. Background commands can be sent more with `exec_stdin`.
+    pub stdin: Option<String>,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
@@ -75,6 +116,41 @@ impl From<escalate_server::ExecResult> for ExecResult {
     }
 }
 
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct ExecAttachParams {
+    /// The `exec_id` returned by a background `shell` call.
+    pub exec_id: String,
+    /// How long to wait for the command to exit before returning the output so far: defaults to 0, which returns immediately.
+    pub wait_ms: Option<u64>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct ExecStdinParams {
+    /// The `exec_id` returned by a background `shell` call.
+    pub exec_id: String,
+    /// Text to write to the command's stdin.
+    pub input: Option<String>,
+    /// Close stdin after writing, so the command sees end of file: defaults to false.
+    pub eof: Option<bool>,
+}
+
+/// Params of the `zsh/execStdin` request.
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExecStdinRequest {
+    exec_id: String,
+    #[serde(default)]
+    data_base64: String,
+    #[serde(default)]
+    eof: bool,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct ExecKillParams {
+    /// The `exec_id` returned by a background `shell` call.
+    pub exec_id: String,
+}
+
 #[derive(Clone)]
 pub struct ExecTool {
     tool_router: ToolRouter<ExecTool>,
@@ -83,6 +159,8 @@ pub struct ExecTool {
     policy: Arc<RwLock<Policy>>,
     preserve_program_paths: bool,
     sandbox_state: Arc<RwLock<Option<SandboxState>>>,
+    background_execs: Arc<BackgroundExecs>,
+    warm_pool: Arc<WarmPool>,
 }
 
 #[tool_router]
@@ -92,14 +170,21 @@ impl ExecTool {
         execve_wrapper: PathBuf,
         policy: Arc<RwLock<Policy>>,
         preserve_program_paths: bool,
+        warm_pool: WarmPoolConfig,
     ) -> Self {
         Self {
             tool_router: Self::tool_router(),
+            warm_pool: Arc::new(WarmPool::new(
+                bash_path.clone(),
+                execve_wrapper.clone(),
+                warm_pool,
+            )),
             bash_path,
             execve_wrapper,
             policy,
             preserve_program_paths,
             sandbox_state: Arc::new(RwLock::new(None)),
+            background_execs: Arc::default(),
         }
     }
 
@@ -110,43 +195,269 @@ impl ExecTool {
         context: RequestContext<RoleServer>,
         Parameters(params): Parameters<ExecParams>,
     ) -> Result<CallToolResult, McpError> {
+        let background = params.background == Some(true);
         let effective_timeout = Duration::from_millis(
             params
                 .timeout_ms
                 .unwrap_or(codex_core::exec::DEFAULT_EXEC_COMMAND_TIMEOUT_MS),
         );
+        let exec_span = info_span!(
+            "exec_server.shell",
+            workdir = params.workdir.as_str(),
+            login = params.login.unwrap_or(true),
+            background,
+            exit_code = field::Empty,
+            timed_out = field::Empty,
+        );
+        if let Some(traceparent) = context.meta.get(TRACEPARENT_KEY).and_then(|v| v.as_str()) {
+            let tracestate = context.meta.get(TRACESTATE_KEY).and_then(|v| v.as_str());
+            set_parent_from_trace_context(&exec_span, traceparent, tracestate);
+        }
+        let peer = context.peer.clone();
         let stopwatch = Stopwatch::new(effective_timeout);
-        let cancel_token = stopwatch.cancellation_token();
-        let sandbox_state =
-            self.sandbox_state
-                .read()
+        let client_sandbox_state = self.sandbox_state.read().await.clone();
+        // Warm shells are only kept for a sandbox state the client has set.
+        let warm_shell = client_sandbox_state
+            .as_ref()
+            .and_then(|sandbox_state| self.warm_pool.take(&params, sandbox_state));
+        let sandbox_state = client_sandbox_state.unwrap_or_else(|| SandboxState {
+            sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            codex_linux_sandbox_exe: None,
+            sandbox_cwd: PathBuf::from(&params.workdir),
+            use_linux_sandbox_bwrap: false,
+        });
+        let workdir = params.workdir.clone();
+        let escalation_policy = McpEscalationPolicy::new(
+            self.policy.clone(),
+            context,
+            stopwatch.clone(),
+            self.preserve_program_paths,
+        );
+
+        if let Some(warm_shell) = warm_shell {
+            let result = warm_shell
+                .run(
+                    params,
+                    Arc::new(escalation_policy),
+                    stopwatch.cancellation_token(),
+                )
+                .instrument(exec_span.clone())
                 .await
-                .clone()
-                .unwrap_or_else(|| SandboxState {
-                    sandbox_policy: SandboxPolicy::new_read_only_policy(),
-                    codex_linux_sandbox_exe: None,
-                    sandbox_cwd: PathBuf::from(&params.workdir),
-                    use_linux_sandbox_bwrap: false,
-                });
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+            exec_span.record("exit_code", result.exit_code);
+            exec_span.record("timed_out", result.timed_out);
+            notify_orphans_reaped(&peer, &workdir, &result.orphans).await;
+            return Ok(CallToolResult::success(vec![Content::json(
+                ExecResult::from(result),
+            )?]));
+        }
+
         let escalate_server = EscalateServer::new(
             self.bash_path.clone(),
             self.execve_wrapper.clone(),
-            McpEscalationPolicy::new(
-                self.policy.clone(),
-                context,
-                stopwatch.clone(),
-                self.preserve_program_paths,
-            ),
+            escalation_policy,
         );
 
+        if background {
+            let (stdin, stdin_tx) =
+                stdin_pipe().map_err(|err| McpError::internal_error(err.to_string(), None))?;
+            if let Some(input) = &params.stdin {
+                let _ = stdin_tx.send(input.clone().into_bytes());
+            }
+            let exec = self.background_execs.start(&params.command, &workdir);
+            exec.set_stdin(stdin_tx);
+            let cancel_token = exec.cancellation_token();
+            if params.timeout_ms.is_some() {
+                let timeout = stopwatch.cancellation_token();
+                let cancel_token = cancel_token.clone();
+                tokio::spawn(async move {
+                    timeout.cancelled().await;
+                    cancel_token.cancel();
+                });
+            }
+            let started = BackgroundExecStarted {
+                exec_id: exec.exec_id().to_string(),
+            };
+            tokio::spawn(
+                run_background_exec(
+                    escalate_server,
+                    params,
+                    cancel_token,
+                    sandbox_state,
+                    stdin,
+                    exec,
+                    peer,
+                )
+                .instrument(exec_span),
+            );
+            return Ok(CallToolResult::success(vec![Content::json(started)?]));
+        }
+
+        // The sender is dropped right away, so the command sees end of file
+        // once it has read the input.
+        let stdin = match &params.stdin {
+            Some(input) => {
+                let (stdin, stdin_tx) =
+                    stdin_pipe().map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                let _ = stdin_tx.send(input.clone().into_bytes());
+                Some(stdin)
+            }
+            None => None,
+        };
         let result = escalate_server
-            .exec(params, cancel_token, &sandbox_state)
+            .exec(
+                params,
+                stopwatch.cancellation_token(),
+                &sandbox_state,
+                None,
+                stdin,
+            )
+            .instrument(exec_span.clone())
             .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        exec_span.record("exit_code", result.exit_code);
+        exec_span.record("timed_out", result.timed_out);
+        notify_orphans_reaped(&peer, &workdir, &result.orphans).await;
         Ok(CallToolResult::success(vec![Content::json(
             ExecResult::from(result),
         )?]))
     }
+
+    /// Lists the commands started with `background: true` and whether they are still running.
+    #[tool]
+    async fn list_execs(&self) -> Result<CallToolResult, McpError> {
+        Ok(CallToolResult::success(vec![Content::json(
+            self.background_execs.list(),
+        )?]))
+    }
+
+    /// Returns the output a background command produced since the last call, optionally waiting up to wait_ms for it to exit.
+    #[tool]
+    async fn exec_attach(
+        &self,
+        Parameters(params): Parameters<ExecAttachParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let exec = self.background_exec(&params.exec_id)?;
+        let wait = Duration::from_millis(params.wait_ms.unwrap_or_default());
+        Ok(CallToolResult::success(vec![Content::json(
+            exec.attach(wait).await,
+        )?]))
+    }
+
+    /// Writes to the stdin of a background command. Set eof to close stdin when done.
+    #[tool]
+    async fn exec_stdin(
+        &self,
+        Parameters(params): Parameters<ExecStdinParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let exec = self.background_exec(&params.exec_id)?;
+        exec.write_stdin(
+            params.input.unwrap_or_default().into_bytes(),
+            params.eof.unwrap_or(false),
+        )
+        .map_err(|err| McpError::invalid_params(err, None))?;
+        Ok(CallToolResult::success(vec![Content::json(
+            exec.attach(Duration::ZERO).await,
+        )?]))
+    }
+
+    /// Stops a background command and everything it started.
+    #[tool]
+    async fn exec_kill(
+        &self,
+        Parameters(params): Parameters<ExecKillParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let exec = self.background_exec(&params.exec_id)?;
+        exec.cancellation_token().cancel();
+        Ok(CallToolResult::success(vec![Content::json(
+            exec.attach(BACKGROUND_KILL_WAIT).await,
+        )?]))
+    }
+
+    fn background_exec(&self, exec_id: &str) -> Result<Arc<BackgroundExec>, McpError> {
+        self.background_execs.get(exec_id).ok_or_else(|| {
+            McpError::invalid_params(format!("no background command with id {exec_id}"), None)
+        })
+    }
+}
+
+/// Runs a background command to completion, buffering its output as it arrives.
+async fn run_background_exec(
+    escalate_server: EscalateServer,
+    params: ExecParams,
+    cancel_token: CancellationToken,
+    sandbox_state: SandboxState,
+    stdin: OwnedFd,
+    exec: Arc<BackgroundExec>,
+    peer: Peer<RoleServer>,
+) {
+    let workdir = params.workdir.clone();
+    let (tx_event, rx_event) = async_channel::unbounded();
+    let stdout_stream = StdoutStream {
+        sub_id: exec.exec_id().to_string(),
+        call_id: exec.exec_id().to_string(),
+        tx_event,
+    };
+    let collector = tokio::spawn({
+        let exec = Arc::clone(&exec);
+        async move {
+            while let Ok(event) = rx_event.recv().await {
+                if let EventMsg::ExecCommandOutputDelta(delta) = event.msg {
+                    exec.push_output(&delta.chunk);
+                }
+            }
+        }
+    });
+    let result = escalate_server
+        .exec(
+            params,
+            cancel_token,
+            &sandbox_state,
+            Some(stdout_stream),
+            Some(stdin),
+        )
+        .await;
+    // The stream's sender is dropped once the command finishes, which ends
+    // the collector after it has pushed the last of the output.
+    let _ = collector.await;
+    let outcome = match result {
+        Ok(result) => {
+            let span = Span::current();
+            span.record("exit_code", result.exit_code);
+            span.record("timed_out", result.timed_out);
+            notify_orphans_reaped(&peer, &workdir, &result.orphans).await;
+            BackgroundExecOutcome {
+                exit_code: Some(result.exit_code),
+                timed_out: result.timed_out,
+                error: None,
+            }
+        }
+        Err(err) => BackgroundExecOutcome {
+            error: Some(err.to_string()),
+            ..Default::default()
+        },
+    };
+    exec.finish(outcome);
+}
+
+async fn notify_orphans_reaped(peer: &Peer<RoleServer>, workdir: &str, orphans: &[ReapedProcess]) {
+    if orphans.is_empty() {
+        return;
+    }
+    let params = json!({
+        "workdir": workdir,
+        "processes": orphans,
+    });
+    if let Err(err) = peer
+        .send_notification(ServerNotification::CustomNotification(CustomNotification {
+            method: ORPHAN_REAPED_NOTIFICATION.to_string(),
+            params: Some(params),
+            extensions: Extensions::new(),
+        }))
+        .await
+    {
+        tracing::warn!("failed to send {ORPHAN_REAPED_NOTIFICATION}: {err}");
+    }
 }
 
 #[derive(Default)]
@@ -169,6 +480,8 @@ impl ServerHandler for ExecTool {
             MCP_SANDBOX_STATE_CAPABILITY.to_string(),
             sandbox_state_capability,
         );
+        experimental_capabilities.insert(MCP_SHELL_SELF_TEST_METHOD.to_string(), JsonObject::new());
+        experimental_capabilities.insert(EXEC_STDIN_METHOD.to_string(), JsonObject::new());
         ServerInfo {
             protocol_version: ProtocolVersion::V_2025_06_18,
             capabilities: ServerCapabilities::builder()
@@ -197,6 +510,42 @@ impl ServerHandler for ExecTool {
         _context: rmcp::service::RequestContext<rmcp::RoleServer>,
     ) -> Result<CustomResult, McpError> {
         let CustomRequest { method, params, .. } = request;
+        if method == MCP_SHELL_SELF_TEST_METHOD {
+            let sandbox_state =
+                self.sandbox_state
+                    .read()
+                    .await
+                    .clone()
+                    .unwrap_or_else(|| SandboxState {
+                        sandbox_policy: SandboxPolicy::new_read_only_policy(),
+                        codex_linux_sandbox_exe: None,
+                        sandbox_cwd: std::env::temp_dir(),
+                        use_linux_sandbox_bwrap: false,
+                    });
+            let report = run_self_test(&self.bash_path, &self.execve_wrapper, &sandbox_state).await;
+            let report = serde_json::to_value(report)
+                .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+            return Ok(CustomResult::new(report));
+        }
+        if method == EXEC_STDIN_METHOD {
+            let request = params
+                .and_then(|params| serde_json::from_value::<ExecStdinRequest>(params).ok())
+                .ok_or_else(|| {
+                    McpError::invalid_params(
+                        format!("invalid params for {EXEC_STDIN_METHOD}"),
+                        None,
+                    )
+                })?;
+            let data = BASE64_STANDARD
+                .decode(&request.data_base64)
+                .map_err(|err| {
+                    McpError::invalid_params(format!("invalid dataBase64: {err}"), None)
+                })?;
+            self.background_exec(&request.exec_id)?
+                .write_stdin(data, request.eof)
+                .map_err(|err| McpError::invalid_params(err, None))?;
+            return Ok(CustomResult::new(json!({})));
+        }
         if method != MCP_SANDBOX_STATE_METHOD {
             return Err(McpError::method_not_found::<CodexSandboxStateUpdateMethod>());
         }
@@ -215,6 +564,7 @@ impl ServerHandler for ExecTool {
             ));
         };
 
+        self.warm_pool.fill(&sandbox_state);
         *self.sandbox_state.write().await = Some(sandbox_state);
 
         Ok(CustomResult::new(json!({})))
@@ -226,8 +576,15 @@ pub(crate) async fn serve(
     execve_wrapper: PathBuf,
     policy: Arc<RwLock<Policy>>,
     preserve_program_paths: bool,
+    warm_pool: WarmPoolConfig,
 ) -> Result<RunningService<RoleServer, ExecTool>, rmcp::service::ServerInitializeError> {
-    let tool = ExecTool::new(bash_path, execve_wrapper, policy, preserve_program_paths);
+    let tool = ExecTool::new(
+        bash_path,
+        execve_wrapper,
+        policy,
+        preserve_program_paths,
+        warm_pool,
+    );
     tool.serve(stdio()).await
 }
 
@@ -238,8 +595,8 @@ mod tests {
     use serde_json::json;
 
     /// Verify that the way we use serde does not compromise the desired JSON
-    /// schema via schemars. In particular, ensure that the `login` and
-    /// `timeout_ms` fields are optional.
+    /// schema via schemars. In particular, ensure that the `background`,
+    /// `login`, `stdin`, and `timeout_ms` fields are optional.
     #[test]
     fn exec_params_json_schema_matches_expected() {
         let schema = schemars::schema_for!(ExecParams);
@@ -252,10 +609,18 @@ mod tests {
                 "title": "ExecParams",
                 "type": "object",
                 "properties": {
+                    "background": {
+                        "description": "Return as soon as the command starts and keep it running (e.g. a dev server). Collect its output with `exec_attach`; without `timeout_ms` it runs until it exits or `exec_kill` stops it.",
+                        "type": ["boolean", "null"]
+                    },
                     "command": {
                         "description": "The bash string to execute.",
                         "type": "string"
                     },
+                    "stdin": {
+                        "description": "Text to write to the command's stdin, which is otherwise empty. Background commands can be sent more with `exec_stdin`.",
+                        "type": ["string", "null"]
+                    },
                     "login": {
                         "description": "Launch Bash with -lc instead of -c: defaults to true.",
                         "type": ["boolean", "null"]
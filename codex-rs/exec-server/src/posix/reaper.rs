@@ -0,0 +1,211 @@
+//! Cleans up processes that outlive a `shell` tool call.
+//!
+//! Every exec gets an id that is exported to the shell as `CODEX_EXEC_ID`, so
+//! all of its descendants carry it in their environment, including ones that
+//! start a new session or process group to daemonize. When the exec finishes,
+//! [`reap_exec`] kills every process still tagged with its id. The server also
+//! marks itself as a child subreaper, so those orphans are reparented to it and
+//! can be waited on instead of lingering under init.
+//!
+//! Escalated commands run outside the shell, so each one gets its own process
+//! group, which is killed when its exec finishes.
+//!
+//! Finding tagged processes needs `/proc`, so on other Unix platforms only the
+//! process groups of escalated commands are cleaned up.
+
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+use serde::Serialize;
+
+/// Environment variable that ties a process to the exec that started it.
+/// Values are `<server pid>-<sequence number>`.
+pub(crate) const EXEC_ID_ENV_VAR: &str = "CODEX_EXEC_ID";
+
+/// How many times to rescan for stragglers, in case one forked mid-sweep.
+const MAX_SWEEPS: usize = 3;
+
+static NEXT_EXEC_SEQUENCE: AtomicU64 = AtomicU64::new(1);
+
+/// A process that was still running after its exec finished, and was killed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ReapedProcess {
+    pub(crate) pid: u32,
+    pub(crate) command: String,
+}
+
+pub(crate) fn next_exec_id() -> String {
+    format!(
+        "{}-{}",
+        std::process::id(),
+        NEXT_EXEC_SEQUENCE.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+/// Makes orphaned descendants reparent to this process rather than to init.
+#[cfg(target_os = "linux")]
+pub(crate) fn become_subreaper() -> std::io::Result<()> {
+    if unsafe { libc::prctl(libc::PR_SET_CHILD_SUBREAPER, 1) } == -1 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn become_subreaper() -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Kills everything left behind by `exec_id`, including the process groups of
+/// its escalated commands. Blocks until killed children are reaped.
+pub(crate) fn reap_exec(exec_id: &str, escalated_process_groups: &[u32]) -> Vec<ReapedProcess> {
+    for &process_group in escalated_process_groups {
+        unsafe {
+            libc::killpg(process_group as libc::pid_t, libc::SIGKILL);
+        }
+    }
+    kill_tagged(|tag| tag == exec_id)
+}
+
+/// Kills everything left behind by any exec of this server. Used on shutdown.
+pub(crate) fn reap_all_execs() -> Vec<ReapedProcess> {
+    let prefix = format!("{}-", std::process::id());
+    kill_tagged(|tag| tag.starts_with(&prefix))
+}
+
+/// Kills processes left behind by servers that are no longer running, e.g.
+/// because they crashed before they could clean up.
+pub(crate) fn reap_stale_execs() -> Vec<ReapedProcess> {
+    kill_tagged(|tag| {
+        let Some(server_pid) = tag
+            .split_once('-')
+            .and_then(|(pid, _)| pid.parse::<libc::pid_t>().ok())
+        else {
+            return false;
+        };
+        server_pid != std::process::id() as libc::pid_t
+            && unsafe { libc::kill(server_pid, 0) } == -1
+            && std::io::Error::last_os_error().raw_os_error() == Some(libc::ESRCH)
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn kill_tagged(matches: impl Fn(&str) -> bool) -> Vec<ReapedProcess> {
+    let own_pid = std::process::id();
+    let mut reaped = Vec::new();
+    for _ in 0..MAX_SWEEPS {
+        let Ok(entries) = std::fs::read_dir("/proc") else {
+            break;
+        };
+        let mut killed_this_sweep = false;
+        for entry in entries.flatten() {
+            let Some(pid) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.parse::<u32>().ok())
+            else {
+                continue;
+            };
+            if pid == own_pid || reaped.iter().any(|p: &ReapedProcess| p.pid == pid) {
+                continue;
+            }
+            let Ok(environ) = std::fs::read(entry.path().join("environ")) else {
+                continue;
+            };
+            let Some(tag) = exec_id_from_environ(&environ) else {
+                continue;
+            };
+            if !matches(tag) {
+                continue;
+            }
+            let command = std::fs::read(entry.path().join("cmdline"))
+                .map(|cmdline| command_from_cmdline(&cmdline))
+                .unwrap_or_default();
+            if unsafe { libc::kill(pid as libc::pid_t, libc::SIGKILL) } == 0 {
+                killed_this_sweep = true;
+                reaped.push(ReapedProcess { pid, command });
+            }
+        }
+        if !killed_this_sweep {
+            break;
+        }
+    }
+
+    // Orphans were reparented to us; wait for them so they do not linger as
+    // zombies. Processes we are not the parent of fail fast with ECHILD.
+    for process in &reaped {
+        let mut status = 0;
+        unsafe {
+            libc::waitpid(process.pid as libc::pid_t, &mut status, 0);
+        }
+    }
+    reaped
+}
+
+#[cfg(not(target_os = "linux"))]
+fn kill_tagged(_matches: impl Fn(&str) -> bool) -> Vec<ReapedProcess> {
+    Vec::new()
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn exec_id_from_environ(environ: &[u8]) -> Option<&str> {
+    environ
+        .split(|&byte| byte == 0)
+        .filter_map(|entry| std::str::from_utf8(entry).ok())
+        .find_map(|entry| entry.strip_prefix(EXEC_ID_ENV_VAR)?.strip_prefix('='))
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn command_from_cmdline(cmdline: &[u8]) -> String {
+    cmdline
+        .split(|&byte| byte == 0)
+        .filter(|arg| !arg.is_empty())
+        .map(String::from_utf8_lossy)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn exec_id_is_read_from_environ() {
+        let environ = b"PATH=/usr/bin\0CODEX_EXEC_ID_OTHER=x\0CODEX_EXEC_ID=42-7\0HOME=/root\0";
+        assert_eq!(exec_id_from_environ(environ), Some("42-7"));
+        assert_eq!(exec_id_from_environ(b"PATH=/usr/bin\0"), None);
+    }
+
+    #[test]
+    fn command_joins_cmdline_arguments() {
+        assert_eq!(
+            command_from_cmdline(b"node\0server.js\0--port\08080\0"),
+            "node server.js --port 8080"
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn reap_exec_kills_tagged_daemons() -> anyhow::Result<()> {
+        let exec_id = next_exec_id();
+        let mut daemon = std::process::Command::new("sleep")
+            .arg("300")
+            .env(EXEC_ID_ENV_VAR, &exec_id)
+            .spawn()?;
+
+        let reaped = reap_exec(&exec_id, &[]);
+
+        assert_eq!(
+            reaped,
+            vec![ReapedProcess {
+                pid: daemon.id(),
+                command: "sleep 300".to_string(),
+            }]
+        );
+        // The reaper already waited on it, so there is no exit status left.
+        assert!(daemon.try_wait().is_err());
+        Ok(())
+    }
+}
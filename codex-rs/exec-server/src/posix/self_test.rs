@@ -0,0 +1,154 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use codex_core::SandboxState;
+use codex_core::ShellSelfTestReport;
+
+use crate::posix::escalate_protocol::EscalateAction;
+use crate::posix::escalate_server::EscalateServer;
+use crate::posix::escalation_policy::EscalationPolicy;
+use crate::posix::mcp::ExecParams;
+use crate::posix::stopwatch::Stopwatch;
+
+/// How long the probe may run before the self-test gives up on the shell.
+const SELF_TEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Prints the shell version, then runs an external program so that the shell
+/// has to go through `execve(2)` (and therefore the exec wrapper).
+const PROBE_COMMAND: &str = r#"printf '%s\n' "${ZSH_VERSION:-$BASH_VERSION}"; command env true"#;
+
+/// Lets every exec() run in the sandbox and records which programs it saw.
+#[derive(Default)]
+struct RecordingPolicy {
+    intercepted: Arc<Mutex<Vec<PathBuf>>>,
+}
+
+#[async_trait::async_trait]
+impl EscalationPolicy for RecordingPolicy {
+    async fn determine_action(
+        &self,
+        file: &Path,
+        _argv: &[String],
+        _workdir: &Path,
+    ) -> Result<EscalateAction, rmcp::ErrorData> {
+        if let Ok(mut intercepted) = self.intercepted.lock() {
+            intercepted.push(file.to_path_buf());
+        }
+        Ok(EscalateAction::Run)
+    }
+}
+
+/// Runs the probe through the same path as the `shell` tool and reports which
+/// steps worked.
+pub(crate) async fn run_self_test(
+    bash_path: &Path,
+    execve_wrapper: &Path,
+    sandbox_state: &SandboxState,
+) -> ShellSelfTestReport {
+    let mut report = ShellSelfTestReport {
+        shell_path: bash_path.to_path_buf(),
+        exec_wrapper_found: execve_wrapper.is_file(),
+        ..Default::default()
+    };
+    if !report.exec_wrapper_found {
+        report.error = Some(format!("{} does not exist", execve_wrapper.display()));
+        return report;
+    }
+
+    let policy = RecordingPolicy::default();
+    let intercepted = Arc::clone(&policy.intercepted);
+    let escalate_server = EscalateServer::new(
+        bash_path.to_path_buf(),
+        execve_wrapper.to_path_buf(),
+        policy,
+    );
+    let params = ExecParams {
+        command: PROBE_COMMAND.to_string(),
+        workdir: sandbox_state.sandbox_cwd.to_string_lossy().to_string(),
+        timeout_ms: None,
+        login: Some(false),
+        background: None,
+        stdin: None,
+    };
+    let stopwatch = Stopwatch::new(SELF_TEST_TIMEOUT);
+    match escalate_server
+        .exec(
+            params,
+            stopwatch.cancellation_token(),
+            sandbox_state,
+            None,
+            None,
+        )
+        .await
+    {
+        Ok(result) if result.timed_out => {
+            report.error = Some(format!(
+                "probe did not finish within {}s",
+                SELF_TEST_TIMEOUT.as_secs()
+            ));
+        }
+        Ok(result) if result.exit_code != 0 => {
+            report.error = Some(format!(
+                "probe exited with {}: {}",
+                result.exit_code,
+                result.output.trim()
+            ));
+        }
+        Ok(result) => {
+            report.shell_started = true;
+            report.shell_version = result
+                .output
+                .lines()
+                .next()
+                .map(str::trim)
+                .filter(|version| !version.is_empty())
+                .map(str::to_string);
+        }
+        Err(err) => {
+            report.error = Some(format!("failed to start {}: {err}", bash_path.display()));
+        }
+    }
+    report.exec_intercepted = intercepted
+        .lock()
+        .is_ok_and(|intercepted| !intercepted.is_empty());
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::SandboxPolicy;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn missing_exec_wrapper_fails_before_spawning_the_shell() {
+        let sandbox_state = SandboxState {
+            sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            codex_linux_sandbox_exe: None,
+            sandbox_cwd: std::env::temp_dir(),
+            use_linux_sandbox_bwrap: false,
+        };
+
+        let report = run_self_test(
+            Path::new("/bin/sh"),
+            Path::new("/nonexistent/codex-execve-wrapper"),
+            &sandbox_state,
+        )
+        .await;
+
+        assert_eq!(
+            ShellSelfTestReport {
+                shell_path: PathBuf::from("/bin/sh"),
+                shell_version: None,
+                exec_wrapper_found: false,
+                shell_started: false,
+                exec_intercepted: false,
+                error: Some("/nonexistent/codex-execve-wrapper does not exist".to_string()),
+            },
+            report
+        );
+    }
+}
@@ -0,0 +1,96 @@
+//! Standard input for `shell` commands.
+//!
+//! Commands normally run with stdin at `/dev/null`. When input is wanted, the
+//! server creates a pipe, lets the shell inherit the read end, and has the
+//! shell move it onto fd 0 before running the command. Input is written from
+//! a blocking task, so a command that is slow to read never stalls the server;
+//! dropping the returned sender closes the write end, which the command sees
+//! as end of file.
+
+use std::io;
+use std::io::Write as _;
+use std::os::fd::AsRawFd as _;
+use std::os::fd::FromRawFd as _;
+use std::os::fd::OwnedFd;
+
+use tokio::sync::mpsc;
+
+/// Creates the pipe and starts the writer. The read end is inheritable; the
+/// write end stays in this process.
+pub(crate) fn stdin_pipe() -> io::Result<(OwnedFd, mpsc::UnboundedSender<Vec<u8>>)> {
+    let (read_end, write_end) = cloexec_pipe()?;
+    if unsafe { libc::fcntl(read_end.as_raw_fd(), libc::F_SETFD, 0) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let (tx, mut rx) = mpsc::unbounded_channel::<Vec<u8>>();
+    tokio::task::spawn_blocking(move || {
+        let mut write_end = std::fs::File::from(write_end);
+        while let Some(data) = rx.blocking_recv() {
+            // The command exited or closed its stdin; drop further input.
+            if write_end.write_all(&data).is_err() {
+                break;
+            }
+        }
+    });
+    Ok((read_end, tx))
+}
+
+/// Prefixes `command` so the shell reads stdin from `fd`.
+pub(crate) fn redirect_stdin(command: &str, fd: &OwnedFd) -> String {
+    format!("exec 0<&{}\n{command}", fd.as_raw_fd())
+}
+
+/// A pipe whose write end is not inherited by spawned processes.
+#[cfg(target_os = "linux")]
+fn cloexec_pipe() -> io::Result<(OwnedFd, OwnedFd)> {
+    let mut fds = [0; 2];
+    if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) })
+}
+
+/// A pipe whose write end is not inherited by spawned processes.
+#[cfg(not(target_os = "linux"))]
+fn cloexec_pipe() -> io::Result<(OwnedFd, OwnedFd)> {
+    let mut fds = [0; 2];
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    let (read_end, write_end) =
+        unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+    if unsafe { libc::fcntl(write_end.as_raw_fd(), libc::F_SETFD, libc::FD_CLOEXEC) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok((read_end, write_end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::io::Read as _;
+
+    #[tokio::test]
+    async fn written_input_arrives_followed_by_eof() -> anyhow::Result<()> {
+        let (read_end, stdin) = stdin_pipe()?;
+        stdin.send(b"print('hi')\n".to_vec())?;
+        drop(stdin);
+
+        let mut input = String::new();
+        std::fs::File::from(read_end).read_to_string(&mut input)?;
+
+        assert_eq!(input, "print('hi')\n");
+        Ok(())
+    }
+
+    #[test]
+    fn redirect_moves_the_pipe_onto_stdin() -> anyhow::Result<()> {
+        let (read_end, write_end) = cloexec_pipe()?;
+        drop(write_end);
+        let expected = format!("exec 0<&{}\npython -", read_end.as_raw_fd());
+        assert_eq!(redirect_stdin("python -", &read_end), expected);
+        Ok(())
+    }
+}
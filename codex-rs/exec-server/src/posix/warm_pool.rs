@@ -0,0 +1,413 @@
+//! Shells started ahead of `shell` calls.
+//!
+//! Starting the sandboxed shell and running its login startup files is a
+//! noticeable part of a short command's time. The pool keeps a few shells
+//! started under the current sandbox state, each blocked reading its command
+//! from a pipe. A foreground `shell` call hands its command to one of them
+//! instead of starting a shell, and a replacement is started right away.
+//!
+//! A warm shell runs exactly one command, so nothing a command does to its
+//! shell carries over to the next one. Shells that have been idle longer than
+//! the configured limit are replaced so they pick up changes to startup files,
+//! and shells started under a different sandbox state are never used.
+//!
+//! Execs made by a shell's startup files are held until the shell is handed a
+//! command. They are then decided by that call's escalation policy, as they
+//! would be for a shell the call started itself.
+
+use std::os::fd::AsRawFd as _;
+use std::os::fd::RawFd;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_core::SandboxState;
+use codex_core::exec::ExecExpiration;
+use codex_core::exec::ExecToolCallOutput;
+use codex_core::exec::process_exec_tool_call;
+use codex_core::protocol_config_types::WindowsSandboxLevel;
+use codex_core::sandboxing::SandboxPermissions;
+use rmcp::ErrorData as McpError;
+use tokio::sync::mpsc;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::posix::escalate_protocol::EscalateAction;
+use crate::posix::escalate_server::ExecResult;
+use crate::posix::escalate_server::escalate_task;
+use crate::posix::escalate_server::intercepted_shell_env;
+use crate::posix::escalation_policy::EscalationPolicy;
+use crate::posix::mcp::ExecParams;
+use crate::posix::reaper::next_exec_id;
+use crate::posix::reaper::reap_exec;
+use crate::posix::socket::AsyncDatagramSocket;
+use crate::posix::stdin_pipe::stdin_pipe;
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct WarmPoolConfig {
+    /// Shells kept ready; 0 disables the pool.
+    pub(crate) size: usize,
+    /// Shells idle longer than this are replaced instead of used.
+    pub(crate) max_idle: Duration,
+}
+
+pub(crate) struct WarmPool {
+    bash_path: PathBuf,
+    execve_wrapper: PathBuf,
+    config: WarmPoolConfig,
+    shells: Mutex<Vec<WarmShell>>,
+}
+
+impl WarmPool {
+    pub(crate) fn new(bash_path: PathBuf, execve_wrapper: PathBuf, config: WarmPoolConfig) -> Self {
+        Self {
+            bash_path,
+            execve_wrapper,
+            config,
+            shells: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Takes a shell for `params`, and starts its replacement. Returns `None`
+    /// when the call needs a shell the pool does not keep (background, or
+    /// `login: false`) or no shell is ready under `sandbox_state`.
+    pub(crate) fn take(
+        &self,
+        params: &ExecParams,
+        sandbox_state: &SandboxState,
+    ) -> Option<WarmShell> {
+        if self.config.size == 0 || !accepts(params) {
+            return None;
+        }
+        let shell = {
+            let mut shells = self.lock_shells();
+            shells
+                .iter()
+                .position(|shell| shell.is_ready_for(sandbox_state, self.config.max_idle))
+                .map(|index| shells.swap_remove(index))
+        };
+        self.fill(sandbox_state);
+        shell
+    }
+
+    /// Discards shells that cannot serve `sandbox_state` and starts new ones
+    /// until the pool is full.
+    pub(crate) fn fill(&self, sandbox_state: &SandboxState) {
+        if self.config.size == 0 {
+            return;
+        }
+        let mut shells = self.lock_shells();
+        let (ready, stale): (Vec<_>, Vec<_>) = std::mem::take(&mut *shells)
+            .into_iter()
+            .partition(|shell| shell.is_ready_for(sandbox_state, self.config.max_idle));
+        *shells = ready;
+        for shell in stale {
+            shell.discard();
+        }
+        while shells.len() < self.config.size {
+            match WarmShell::start(&self.bash_path, &self.execve_wrapper, sandbox_state) {
+                Ok(shell) => shells.push(shell),
+                Err(err) => {
+                    tracing::warn!("failed to start a warm shell: {err:#}");
+                    break;
+                }
+            }
+        }
+    }
+
+    fn lock_shells(&self) -> std::sync::MutexGuard<'_, Vec<WarmShell>> {
+        self.shells
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Whether a warm shell can run `params` exactly as a shell started for it
+/// would.
+fn accepts(params: &ExecParams) -> bool {
+    params.background != Some(true)
+        && params.login != Some(false)
+        && !params.command.contains('\0')
+        && !params.workdir.contains('\0')
+        && Path::new(&params.workdir).is_dir()
+}
+
+/// A started shell waiting for its command.
+pub(crate) struct WarmShell {
+    exec_id: String,
+    sandbox_state: SandboxState,
+    started_at: Instant,
+    input: mpsc::UnboundedSender<Vec<u8>>,
+    policy: watch::Sender<Option<Arc<dyn EscalationPolicy>>>,
+    cancel: CancellationToken,
+    escalated_process_groups: Arc<Mutex<Vec<u32>>>,
+    escalate_task: JoinHandle<anyhow::Result<()>>,
+    process: JoinHandle<codex_core::error::Result<ExecToolCallOutput>>,
+}
+
+impl WarmShell {
+    fn start(
+        bash_path: &Path,
+        execve_wrapper: &Path,
+        sandbox_state: &SandboxState,
+    ) -> anyhow::Result<Self> {
+        let (escalate_server, escalate_client) = AsyncDatagramSocket::pair()?;
+        let client_socket = escalate_client.into_inner();
+        client_socket.set_cloexec(false)?;
+        let (input_fd, input) = stdin_pipe()?;
+
+        let exec_id = next_exec_id();
+        let escalated_process_groups = Arc::new(Mutex::new(Vec::new()));
+        let (policy, deferred_policy) = watch::channel(None);
+        let escalate_task = tokio::spawn(escalate_task(
+            escalate_server,
+            Arc::new(DeferredPolicy {
+                policy: deferred_policy,
+            }),
+            Arc::clone(&escalated_process_groups),
+        ));
+        let cancel = CancellationToken::new();
+        let params = codex_core::exec::ExecParams {
+            command: vec![
+                bash_path.to_string_lossy().to_string(),
+                "-lc".to_string(),
+                bootstrap_script(input_fd.as_raw_fd()),
+            ],
+            cwd: sandbox_state.sandbox_cwd.clone(),
+            expiration: ExecExpiration::Cancellation(cancel.clone()),
+            env: intercepted_shell_env(&exec_id, client_socket.as_raw_fd(), execve_wrapper),
+            network: None,
+            network_attempt_id: None,
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            windows_sandbox_level: WindowsSandboxLevel::Disabled,
+            justification: None,
+            arg0: None,
+        };
+        let state = sandbox_state.clone();
+        let process = tokio::spawn(async move {
+            let result = process_exec_tool_call(
+                params,
+                &state.sandbox_policy,
+                &state.sandbox_cwd,
+                &state.codex_linux_sandbox_exe,
+                state.use_linux_sandbox_bwrap,
+                None,
+            )
+            .await;
+            // Both are inherited by the shell, so they stay open until it exits.
+            drop((client_socket, input_fd));
+            result
+        });
+
+        Ok(Self {
+            exec_id,
+            sandbox_state: sandbox_state.clone(),
+            started_at: Instant::now(),
+            input,
+            policy,
+            cancel,
+            escalated_process_groups,
+            escalate_task,
+            process,
+        })
+    }
+
+    fn is_ready_for(&self, sandbox_state: &SandboxState, max_idle: Duration) -> bool {
+        self.sandbox_state == *sandbox_state
+            && self.started_at.elapsed() < max_idle
+            && !self.process.is_finished()
+    }
+
+    /// Runs `params` in this shell, with `policy` deciding its execs, until
+    /// it exits or `cancel` fires.
+    pub(crate) async fn run(
+        self,
+        params: ExecParams,
+        policy: Arc<dyn EscalationPolicy>,
+        cancel: CancellationToken,
+    ) -> anyhow::Result<ExecResult> {
+        let started = Instant::now();
+        let Self {
+            exec_id,
+            input,
+            policy: policy_tx,
+            cancel: shell_cancel,
+            escalated_process_groups,
+            escalate_task,
+            process,
+            ..
+        } = self;
+        policy_tx.send_replace(Some(policy));
+        let _ = input.send(shell_input(
+            &params.workdir,
+            &params.command,
+            params.stdin.as_deref(),
+        ));
+        // Closing the pipe ends the command's stdin after the input.
+        drop(input);
+        let forward_cancel = tokio::spawn({
+            let shell_cancel = shell_cancel.clone();
+            async move {
+                cancel.cancelled().await;
+                shell_cancel.cancel();
+            }
+        });
+
+        let result = process.await;
+        forward_cancel.abort();
+        escalate_task.abort();
+        let escalated_process_groups = escalated_process_groups
+            .lock()
+            .map(|groups| groups.clone())
+            .unwrap_or_default();
+        let orphans =
+            tokio::task::spawn_blocking(move || reap_exec(&exec_id, &escalated_process_groups))
+                .await
+                .unwrap_or_default();
+        let result = result??;
+        Ok(ExecResult {
+            exit_code: result.exit_code,
+            output: result.aggregated_output.text,
+            duration: started.elapsed(),
+            timed_out: result.timed_out,
+            orphans,
+        })
+    }
+
+    /// Stops the shell without running anything in it. Execs held from its
+    /// startup files are refused once the policy sender is dropped.
+    fn discard(self) {
+        self.cancel.cancel();
+        let Self {
+            exec_id,
+            escalated_process_groups,
+            escalate_task,
+            process,
+            ..
+        } = self;
+        tokio::spawn(async move {
+            let _ = process.await;
+            escalate_task.abort();
+            let escalated_process_groups = escalated_process_groups
+                .lock()
+                .map(|groups| groups.clone())
+                .unwrap_or_default();
+            let _ =
+                tokio::task::spawn_blocking(move || reap_exec(&exec_id, &escalated_process_groups))
+                    .await;
+        });
+    }
+}
+
+/// Holds a warm shell's execs until the shell is handed a call, then decides
+/// them with that call's policy.
+struct DeferredPolicy {
+    policy: watch::Receiver<Option<Arc<dyn EscalationPolicy>>>,
+}
+
+#[async_trait::async_trait]
+impl EscalationPolicy for DeferredPolicy {
+    async fn determine_action(
+        &self,
+        file: &Path,
+        argv: &[String],
+        workdir: &Path,
+    ) -> Result<EscalateAction, McpError> {
+        let mut policy = self.policy.clone();
+        let policy = policy
+            .wait_for(Option::is_some)
+            .await
+            .ok()
+            .and_then(|policy| policy.clone())
+            .ok_or_else(|| {
+                McpError::internal_error("the warm shell was discarded".to_string(), None)
+            })?;
+        policy.determine_action(file, argv, workdir).await
+    }
+}
+
+/// Script a warm shell starts with: it reads the working directory and the
+/// command from `fd`, each terminated by a NUL, and hands the rest of the pipe
+/// to the command as its stdin.
+fn bootstrap_script(fd: RawFd) -> String {
+    format!(
+        "IFS= read -r -d '' __codex_workdir <&{fd} && IFS= read -r -d '' __codex_command <&{fd} || exit 1\n\
+         cd -- \"$__codex_workdir\" || exit 1\n\
+         exec 0<&{fd} {fd}<&-\n\
+         unset __codex_workdir\n\
+         eval \"unset __codex_command; $__codex_command\"\n"
+    )
+}
+
+/// What [`bootstrap_script`] reads.
+fn shell_input(workdir: &str, command: &str, stdin: Option<&str>) -> Vec<u8> {
+    let mut input = Vec::with_capacity(workdir.len() + command.len() + 2);
+    input.extend_from_slice(workdir.as_bytes());
+    input.push(0);
+    input.extend_from_slice(command.as_bytes());
+    input.push(0);
+    if let Some(stdin) = stdin {
+        input.extend_from_slice(stdin.as_bytes());
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::process::Stdio;
+    use tokio::process::Command;
+
+    #[tokio::test]
+    async fn bootstrap_runs_the_command_in_its_workdir_with_the_rest_as_stdin() -> anyhow::Result<()>
+    {
+        let workdir = tempfile::tempdir()?;
+        let workdir = workdir.path().canonicalize()?;
+        let (input_fd, input) = stdin_pipe()?;
+        let child = Command::new("bash")
+            .arg("-c")
+            .arg(bootstrap_script(input_fd.as_raw_fd()))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        drop(input_fd);
+
+        input.send(shell_input(
+            &workdir.to_string_lossy(),
+            "pwd\ncat",
+            Some("from stdin\n"),
+        ))?;
+        drop(input);
+        let output = child.wait_with_output().await?;
+
+        assert_eq!(
+            String::from_utf8(output.stdout)?,
+            format!("{}\nfrom stdin\n", workdir.display())
+        );
+        assert!(output.status.success());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn bootstrap_exits_when_the_shell_is_discarded() -> anyhow::Result<()> {
+        let (input_fd, input) = stdin_pipe()?;
+        let child = Command::new("bash")
+            .arg("-c")
+            .arg(bootstrap_script(input_fd.as_raw_fd()))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        drop(input_fd);
+        drop(input);
+
+        let output = child.wait_with_output().await?;
+        assert_eq!(output.status.code(), Some(1));
+        assert!(output.stdout.is_empty());
+        Ok(())
+    }
+}
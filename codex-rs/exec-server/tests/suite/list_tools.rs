@@ -27,9 +27,22 @@ async fn list_tools() -> Result<()> {
     let transport = create_transport(codex_home.path(), dotslash_cache).await?;
 
     let service = ().serve(transport).await?;
-    let tools = service.list_tools(Default::default()).await?.tools;
+    let mut tools = service.list_tools(Default::default()).await?.tools;
+    tools.sort_by(|a, b| a.name.cmp(&b.name));
+    let tool_names: Vec<&str> = tools.iter().map(|tool| tool.name.as_ref()).collect();
     assert_eq!(
-        vec![Tool {
+        vec![
+            "exec_attach",
+            "exec_kill",
+            "exec_stdin",
+            "list_execs",
+            "shell"
+        ],
+        tool_names
+    );
+    let shell = tools.into_iter().find(|tool| tool.name == "shell");
+    assert_eq!(
+        Some(Tool {
             name: Cow::Borrowed("shell"),
             title: None,
             description: Some(Cow::Borrowed(
@@ -38,10 +51,20 @@ async fn list_tools() -> Result<()> {
             input_schema: Arc::new(object(json!({
                 "$schema": "https://json-schema.org/draft/2020-12/schema",
                 "properties": {
+                    "background": {
+                        "description": "Return as soon as the command starts and keep it running (e.g. a dev server). Collect its output with `exec_attach`; without `timeout_ms` it runs until it exits or `exec_kill` stops it.",
+                        "nullable": true,
+                        "type": "boolean",
+                    },
                     "command": {
                         "description": "The bash string to execute.",
                         "type": "string",
                     },
+                    "stdin": {
+                        "description": "Text to write to the command's stdin, which is otherwise empty. Background commands can be sent more with `exec_stdin`.",
+                        "nullable": true,
+                        "type": "string",
+                    },
                     "login": {
                         "description": "Launch Bash with -lc instead of -c: defaults to true.",
                         "nullable": true,
@@ -71,8 +94,8 @@ async fn list_tools() -> Result<()> {
             annotations: None,
             icons: None,
             meta: None
-        }],
-        tools
+        }),
+        shell
     );
 
     Ok(())
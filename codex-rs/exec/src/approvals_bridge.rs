@@ -0,0 +1,743 @@
+//! Approval requests of `codex exec --approvals-bridge`, answered from a chat channel.
+//!
+//! Each request is posted to the `[approvals_bridge]` webhook (Slack, or a Matrix hookshot
+//! generic webhook) with the command or a summary of the patch and one signed link per decision.
+//! The links point at a small listener started for the run. Opening one shows a confirmation
+//! page and only that page's form records the decision, so link previews in the chat client
+//! cannot approve anything. Requests nobody answers before the timeout are declined.
+
+use std::collections::HashMap;
+use std::io;
+use std::io::Cursor;
+use std::net::TcpListener;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::thread::JoinHandle;
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use codex_core::config::types::ApprovalsBridgeConfig;
+use codex_core::config::types::ApprovalsBridgeFormat;
+use codex_core::default_client::build_reqwest_client;
+use codex_core::is_dangerous_command::command_might_be_dangerous;
+use codex_core::is_safe_command::is_known_safe_command;
+use codex_core::protocol::ApplyPatchApprovalRequestEvent;
+use codex_core::protocol::ExecApprovalRequestEvent;
+use codex_core::protocol::FileChange;
+use codex_core::protocol::ReviewDecision;
+use hmac::Hmac;
+use hmac::Mac;
+use rand::RngCore;
+use serde_json::Value;
+use serde_json::json;
+use sha2::Sha256;
+use tiny_http::Header;
+use tiny_http::Method;
+use tiny_http::Response;
+use tiny_http::Server;
+use tiny_http::StatusCode;
+use tokio::sync::oneshot;
+use tracing::warn;
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const APPROVALS_PATH: &str = "/approvals/";
+
+/// A decision offered in the chat message, one link each.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Choice {
+    Approve,
+    ApproveForSession,
+    Decline,
+    Abort,
+}
+
+impl Choice {
+    const ALL: [Choice; 4] = [
+        Choice::Approve,
+        Choice::ApproveForSession,
+        Choice::Decline,
+        Choice::Abort,
+    ];
+
+    fn slug(self) -> &'static str {
+        match self {
+            Choice::Approve => "approve",
+            Choice::ApproveForSession => "approve-session",
+            Choice::Decline => "decline",
+            Choice::Abort => "abort",
+        }
+    }
+
+    fn from_slug(slug: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|choice| choice.slug() == slug)
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Choice::Approve => "Approve",
+            Choice::ApproveForSession => "Approve for this run",
+            Choice::Decline => "Decline",
+            Choice::Abort => "Decline and stop",
+        }
+    }
+
+    fn outcome(self) -> &'static str {
+        match self {
+            Choice::Approve | Choice::ApproveForSession => "approved",
+            Choice::Decline => "declined",
+            Choice::Abort => "declined and stopped the run",
+        }
+    }
+
+    fn decision(self) -> ReviewDecision {
+        match self {
+            Choice::Approve => ReviewDecision::Approved,
+            Choice::ApproveForSession => ReviewDecision::ApprovedForSession,
+            Choice::Decline => ReviewDecision::Denied,
+            Choice::Abort => ReviewDecision::Abort,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Risk {
+    Low,
+    Medium,
+    High,
+}
+
+impl Risk {
+    fn label(self) -> &'static str {
+        match self {
+            Risk::Low => "low",
+            Risk::Medium => "medium",
+            Risk::High => "high",
+        }
+    }
+}
+
+/// What the chat message shows about one request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ApprovalPrompt {
+    title: &'static str,
+    /// The command, or one line per changed file.
+    detail: String,
+    risk: Risk,
+    reason: Option<String>,
+    cwd: Option<PathBuf>,
+}
+
+impl ApprovalPrompt {
+    pub(crate) fn exec(event: &ExecApprovalRequestEvent) -> Self {
+        let destructive = event
+            .explanation
+            .as_ref()
+            .is_some_and(|explanation| !explanation.destructive_operations.is_empty());
+        let risk = if destructive || command_might_be_dangerous(&event.command) {
+            Risk::High
+        } else if is_known_safe_command(&event.command) && event.network_approval_context.is_none()
+        {
+            Risk::Low
+        } else {
+            Risk::Medium
+        };
+        let reason = event
+            .explanation
+            .as_ref()
+            .map(|explanation| explanation.summary.clone())
+            .or_else(|| event.reason.clone());
+        Self {
+            title: "Run this command?",
+            detail: shlex::try_join(event.command.iter().map(String::as_str))
+                .unwrap_or_else(|_| event.command.join(" ")),
+            risk,
+            reason,
+            cwd: Some(event.cwd.clone()),
+        }
+    }
+
+    pub(crate) fn patch(event: &ApplyPatchApprovalRequestEvent) -> Self {
+        let mut files = event.changes.iter().collect::<Vec<_>>();
+        files.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let detail = files
+            .into_iter()
+            .map(|(path, change)| change_summary(path, change))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let deletes = event
+            .changes
+            .values()
+            .any(|change| matches!(change, FileChange::Delete { .. }));
+        let risk = if deletes || event.grant_root.is_some() {
+            Risk::High
+        } else {
+            Risk::Medium
+        };
+        let reason = match (&event.reason, &event.grant_root) {
+            (Some(reason), _) => Some(reason.clone()),
+            (None, Some(root)) => Some(format!("Write access to {}", root.display())),
+            (None, None) => None,
+        };
+        Self {
+            title: "Apply these changes?",
+            detail,
+            risk,
+            reason,
+            cwd: None,
+        }
+    }
+
+    /// Short form for the follow-up message and the console.
+    fn headline(&self) -> String {
+        let first = self.detail.lines().next().unwrap_or_default();
+        let more = self.detail.lines().count().saturating_sub(1);
+        if more == 0 {
+            first.to_string()
+        } else {
+            format!("{first} and {more} more")
+        }
+    }
+}
+
+fn change_summary(path: &Path, change: &FileChange) -> String {
+    let path = path.display();
+    match change {
+        FileChange::Add { content } => format!("A {path} (+{})", content.lines().count()),
+        FileChange::Delete { content } => format!("D {path} (-{})", content.lines().count()),
+        FileChange::Update {
+            unified_diff,
+            move_path,
+        } => {
+            let (added, removed) = diff_line_counts(unified_diff);
+            match move_path {
+                Some(dest) => format!("R {path} → {} (+{added} -{removed})", dest.display()),
+                None => format!("M {path} (+{added} -{removed})"),
+            }
+        }
+    }
+}
+
+fn diff_line_counts(diff: &str) -> (usize, usize) {
+    diff.lines().fold((0, 0), |(added, removed), line| {
+        if line.starts_with('+') && !line.starts_with("+++") {
+            (added + 1, removed)
+        } else if line.starts_with('-') && !line.starts_with("---") {
+            (added, removed + 1)
+        } else {
+            (added, removed)
+        }
+    })
+}
+
+struct Pending {
+    /// Shown on the confirmation page.
+    headline: String,
+    tx: oneshot::Sender<Choice>,
+}
+
+struct Shared {
+    config: ApprovalsBridgeConfig,
+    /// Base of the decision links, without a trailing slash.
+    public_url: String,
+    key: Vec<u8>,
+    client: reqwest::Client,
+    pending: Mutex<HashMap<String, Pending>>,
+}
+
+impl Shared {
+    fn mac(&self, id: &str, choice: Choice) -> Option<HmacSha256> {
+        let mut mac = HmacSha256::new_from_slice(&self.key).ok()?;
+        mac.update(id.as_bytes());
+        mac.update(b":");
+        mac.update(choice.slug().as_bytes());
+        Some(mac)
+    }
+
+    fn signature(&self, id: &str, choice: Choice) -> String {
+        self.mac(id, choice)
+            .map(|mac| URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes()))
+            .unwrap_or_default()
+    }
+
+    fn verify(&self, link: &DecisionLink) -> bool {
+        let Ok(signature) = URL_SAFE_NO_PAD.decode(&link.signature) else {
+            return false;
+        };
+        self.mac(&link.id, link.choice)
+            .is_some_and(|mac| mac.verify_slice(&signature).is_ok())
+    }
+
+    fn decision_link(&self, id: &str, choice: Choice) -> String {
+        format!(
+            "{}{APPROVALS_PATH}{id}?decision={}&sig={}",
+            self.public_url,
+            choice.slug(),
+            self.signature(id, choice)
+        )
+    }
+
+    fn pending_headline(&self, id: &str) -> Option<String> {
+        let pending = self.pending.lock().ok()?;
+        pending.get(id).map(|pending| pending.headline.clone())
+    }
+
+    fn take(&self, id: &str) -> Option<Pending> {
+        self.pending.lock().ok()?.remove(id)
+    }
+
+    async fn post(&self, payload: &Value) -> Result<(), reqwest::Error> {
+        self.client
+            .post(&self.config.webhook_url)
+            .json(payload)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Posts approval requests and serves the decision links. Stops listening when dropped.
+pub(crate) struct ApprovalsBridge {
+    shared: Arc<Shared>,
+    server: Arc<Server>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ApprovalsBridge {
+    pub(crate) fn start(config: ApprovalsBridgeConfig) -> io::Result<Self> {
+        let listener = TcpListener::bind(config.listen_addr)?;
+        let local_addr = listener.local_addr()?;
+        let server = Server::from_listener(listener, None)
+            .map_err(|err| io::Error::other(format!("failed to start bridge listener: {err}")))?;
+        let server = Arc::new(server);
+
+        let key = match std::env::var(&config.secret_env) {
+            Ok(secret) if !secret.is_empty() => secret.into_bytes(),
+            _ => {
+                let mut key = vec![0u8; 32];
+                rand::rng().fill_bytes(&mut key);
+                key
+            }
+        };
+        let shared = Arc::new(Shared {
+            public_url: config
+                .public_url
+                .clone()
+                .unwrap_or_else(|| format!("http://{local_addr}")),
+            config,
+            key,
+            client: build_reqwest_client(),
+            pending: Mutex::new(HashMap::new()),
+        });
+
+        let thread = std::thread::Builder::new()
+            .name("codex-approvals-bridge".to_string())
+            .spawn({
+                let server = Arc::clone(&server);
+                let shared = Arc::clone(&shared);
+                move || {
+                    for request in server.incoming_requests() {
+                        let response = handle_request(&shared, request.method(), request.url());
+                        let _ = request.respond(response);
+                    }
+                }
+            })?;
+
+        Ok(Self {
+            shared,
+            server,
+            thread: Some(thread),
+        })
+    }
+
+    /// Base URL of the decision links.
+    pub(crate) fn public_url(&self) -> &str {
+        &self.shared.public_url
+    }
+
+    /// Posts `prompt` to the channel and waits for a decision. Requests that cannot be posted,
+    /// or that nobody answers in time, are declined.
+    pub(crate) async fn request_decision(&self, prompt: ApprovalPrompt) -> ReviewDecision {
+        let shared = &self.shared;
+        let headline = prompt.headline();
+        let id = Uuid::new_v4().simple().to_string();
+        let (tx, rx) = oneshot::channel();
+        if let Ok(mut pending) = shared.pending.lock() {
+            let headline = headline.clone();
+            pending.insert(id.clone(), Pending { headline, tx });
+        }
+
+        let links = Choice::ALL
+            .into_iter()
+            .map(|choice| (choice, shared.decision_link(&id, choice)))
+            .collect::<Vec<_>>();
+        let payload = request_payload(shared.config.format, &prompt, &links);
+        if let Err(err) = shared.post(&payload).await {
+            shared.take(&id);
+            eprintln!(
+                "Could not post approval request to the approvals bridge ({err}); declined: {headline}"
+            );
+            return ReviewDecision::Denied;
+        }
+        eprintln!(
+            "Waiting for a decision in chat (risk: {}): {headline}",
+            prompt.risk.label()
+        );
+
+        let choice = match tokio::time::timeout(shared.config.timeout, rx).await {
+            Ok(Ok(choice)) => Some(choice),
+            Ok(Err(_)) | Err(_) => {
+                shared.take(&id);
+                None
+            }
+        };
+        let outcome = choice.map_or("timed out and was declined", Choice::outcome);
+        eprintln!("Approval {outcome}: {headline}");
+        let text = format!("Codex approval {outcome}: {headline}");
+        if let Err(err) = shared
+            .post(&outcome_payload(shared.config.format, &text))
+            .await
+        {
+            warn!("failed to post approval outcome to the approvals bridge: {err}");
+        }
+        choice.unwrap_or(Choice::Decline).decision()
+    }
+}
+
+impl Drop for ApprovalsBridge {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct DecisionLink {
+    id: String,
+    choice: Choice,
+    signature: String,
+}
+
+/// Parses `/approvals/<id>?decision=<choice>&sig=<signature>`.
+fn parse_link(url: &str) -> Option<DecisionLink> {
+    let (path, query) = url.split_once('?')?;
+    let id = path.strip_prefix(APPROVALS_PATH)?;
+    if id.is_empty() || id.contains('/') {
+        return None;
+    }
+    let mut choice = None;
+    let mut signature = None;
+    for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+        match key {
+            "decision" => choice = Choice::from_slug(value),
+            "sig" => signature = Some(value),
+            _ => {}
+        }
+    }
+    Some(DecisionLink {
+        id: id.to_string(),
+        choice: choice?,
+        signature: signature?.to_string(),
+    })
+}
+
+/// `GET` on a link shows a confirmation form; only its `POST` records the decision.
+fn handle_request(shared: &Shared, method: &Method, url: &str) -> Response<Cursor<Vec<u8>>> {
+    let Some(link) = parse_link(url) else {
+        return page(404, "Not found", "<p>This is not an approval link.</p>");
+    };
+    if !shared.verify(&link) {
+        return page(
+            403,
+            "Invalid link",
+            "<p>This approval link is not valid for this run.</p>",
+        );
+    }
+    let answered = || {
+        page(
+            410,
+            "Already answered",
+            "<p>This request was already answered or has timed out.</p>",
+        )
+    };
+    let label = link.choice.label();
+    match method {
+        Method::Get => match shared.pending_headline(&link.id) {
+            Some(headline) => page(
+                200,
+                label,
+                &format!(
+                    "<pre>{}</pre><form method=\"post\"><button type=\"submit\">{label}</button></form>",
+                    html_escape(&headline)
+                ),
+            ),
+            None => answered(),
+        },
+        Method::Post => match shared.take(&link.id) {
+            Some(pending) => {
+                let _ = pending.tx.send(link.choice);
+                page(
+                    200,
+                    label,
+                    &format!(
+                        "<p>Codex {}: <code>{}</code></p>",
+                        link.choice.outcome(),
+                        html_escape(&pending.headline)
+                    ),
+                )
+            }
+            None => answered(),
+        },
+        _ => page(405, "Method not allowed", ""),
+    }
+}
+
+fn page(status: u16, title: &str, body: &str) -> Response<Cursor<Vec<u8>>> {
+    let html = format!(
+        "<!doctype html><html lang=\"en\"><head><meta charset=\"utf-8\">\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+         <meta name=\"referrer\" content=\"no-referrer\"><title>{title}</title></head>\
+         <body style=\"font-family: system-ui, sans-serif; max-width: 40rem; margin: 3rem auto; padding: 0 1rem\">\
+         <h1>{title}</h1>{body}</body></html>"
+    );
+    let mut response = Response::from_string(html).with_status_code(StatusCode(status));
+    if let Ok(header) = Header::from_bytes("Content-Type", "text/html; charset=utf-8") {
+        response = response.with_header(header);
+    }
+    response
+}
+
+/// Chat messages have length limits, and a long patch summary is no easier to review there.
+const MAX_DETAIL_CHARS: usize = 2_500;
+
+fn truncated_detail(detail: &str) -> String {
+    match detail.char_indices().nth(MAX_DETAIL_CHARS) {
+        Some((end, _)) => format!("{}\n…", &detail[..end]),
+        None => detail.to_string(),
+    }
+}
+
+fn request_payload(
+    format: ApprovalsBridgeFormat,
+    prompt: &ApprovalPrompt,
+    links: &[(Choice, String)],
+) -> Value {
+    let detail = truncated_detail(&prompt.detail);
+    let fallback = format!("Codex: {} {}", prompt.title, prompt.headline());
+    match format {
+        ApprovalsBridgeFormat::Slack => {
+            let mut context = format!("*Risk:* {}", prompt.risk.label());
+            if let Some(cwd) = &prompt.cwd {
+                context.push_str(&format!(
+                    "  ·  *In:* `{}`",
+                    slack_escape(&cwd.display().to_string())
+                ));
+            }
+            let mut blocks = vec![json!({
+                "type": "section",
+                "text": {
+                    "type": "mrkdwn",
+                    "text": format!("*{}*\n```{}```", prompt.title, slack_escape(&detail)),
+                },
+            })];
+            if let Some(reason) = &prompt.reason {
+                blocks.push(json!({
+                    "type": "section",
+                    "text": { "type": "mrkdwn", "text": slack_escape(reason) },
+                }));
+            }
+            blocks.push(json!({
+                "type": "context",
+                "elements": [{ "type": "mrkdwn", "text": context }],
+            }));
+            let buttons = links
+                .iter()
+                .map(|(choice, url)| {
+                    let mut button = json!({
+                        "type": "button",
+                        "action_id": choice.slug(),
+                        "text": { "type": "plain_text", "text": choice.label() },
+                        "url": url,
+                    });
+                    match choice {
+                        Choice::Approve => button["style"] = json!("primary"),
+                        Choice::Abort => button["style"] = json!("danger"),
+                        Choice::ApproveForSession | Choice::Decline => {}
+                    }
+                    button
+                })
+                .collect::<Vec<_>>();
+            blocks.push(json!({ "type": "actions", "elements": buttons }));
+            json!({ "text": slack_escape(&fallback), "blocks": blocks })
+        }
+        ApprovalsBridgeFormat::Matrix => {
+            let mut text = format!("{}\n{detail}\n", prompt.title);
+            let mut html = format!(
+                "<p><strong>{}</strong></p><pre><code>{}</code></pre>",
+                prompt.title,
+                html_escape(&detail)
+            );
+            if let Some(reason) = &prompt.reason {
+                text.push_str(&format!("{reason}\n"));
+                html.push_str(&format!("<p>{}</p>", html_escape(reason)));
+            }
+            let mut context = format!("Risk: {}", prompt.risk.label());
+            if let Some(cwd) = &prompt.cwd {
+                context.push_str(&format!(" · In: {}", cwd.display()));
+            }
+            text.push_str(&format!("{context}\n"));
+            html.push_str(&format!("<p>{}</p>", html_escape(&context)));
+            let anchors = links
+                .iter()
+                .map(|(choice, url)| {
+                    text.push_str(&format!("{}: {url}\n", choice.label()));
+                    format!("<a href=\"{}\">{}</a>", html_escape(url), choice.label())
+                })
+                .collect::<Vec<_>>();
+            html.push_str(&format!("<p>{}</p>", anchors.join(" · ")));
+            json!({ "text": text, "html": html, "username": "Codex" })
+        }
+    }
+}
+
+fn outcome_payload(format: ApprovalsBridgeFormat, text: &str) -> Value {
+    match format {
+        ApprovalsBridgeFormat::Slack => json!({ "text": slack_escape(text) }),
+        ApprovalsBridgeFormat::Matrix => {
+            json!({ "text": text, "html": html_escape(text), "username": "Codex" })
+        }
+    }
+}
+
+/// Slack treats `&`, `<` and `>` as control characters in message text.
+fn slack_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn html_escape(text: &str) -> String {
+    slack_escape(text).replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn shared() -> Shared {
+        Shared {
+            config: ApprovalsBridgeConfig {
+                webhook_url: "http://127.0.0.1:9/hook".to_string(),
+                format: ApprovalsBridgeFormat::Slack,
+                listen_addr: "127.0.0.1:0".parse().expect("socket address"),
+                public_url: None,
+                secret_env: "UNUSED".to_string(),
+                timeout: Duration::from_secs(1),
+            },
+            public_url: "https://codex.example.com".to_string(),
+            key: b"secret".to_vec(),
+            client: reqwest::Client::new(),
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn link_path(link: &str) -> &str {
+        link.strip_prefix("https://codex.example.com")
+            .expect("link under the public url")
+    }
+
+    #[test]
+    fn decision_links_are_signed_per_request_and_choice() {
+        let shared = shared();
+        let link = shared.decision_link("abc", Choice::Approve);
+        let parsed = parse_link(link_path(&link)).expect("parse link");
+        assert_eq!(parsed.id, "abc");
+        assert_eq!(parsed.choice, Choice::Approve);
+        assert!(shared.verify(&parsed));
+
+        // A valid signature does not carry over to another decision or request.
+        let forged = DecisionLink {
+            choice: Choice::ApproveForSession,
+            ..parsed
+        };
+        assert!(!shared.verify(&forged));
+        let forged = DecisionLink {
+            id: "abd".to_string(),
+            choice: Choice::Approve,
+            ..forged
+        };
+        assert!(!shared.verify(&forged));
+        assert_eq!(parse_link("/approvals/abc?decision=maybe&sig=x"), None);
+        assert_eq!(parse_link("/metrics"), None);
+    }
+
+    #[test]
+    fn only_the_confirmation_form_records_a_decision() {
+        let shared = shared();
+        let (tx, mut rx) = oneshot::channel();
+        shared.pending.lock().expect("lock").insert(
+            "abc".to_string(),
+            Pending {
+                headline: "rm -rf target".to_string(),
+                tx,
+            },
+        );
+        let link = shared.decision_link("abc", Choice::Decline);
+        let url = link_path(&link);
+
+        let response = handle_request(&shared, &Method::Get, url);
+        assert_eq!(response.status_code(), StatusCode(200));
+        assert!(rx.try_recv().is_err());
+
+        let response = handle_request(&shared, &Method::Post, url);
+        assert_eq!(response.status_code(), StatusCode(200));
+        assert_eq!(rx.try_recv().ok(), Some(Choice::Decline));
+
+        let response = handle_request(&shared, &Method::Post, url);
+        assert_eq!(response.status_code(), StatusCode(410));
+        let response = handle_request(
+            &shared,
+            &Method::Post,
+            "/approvals/abc?decision=decline&sig=AAAA",
+        );
+        assert_eq!(response.status_code(), StatusCode(403));
+    }
+
+    #[test]
+    fn patch_prompts_summarize_each_file() {
+        let event = ApplyPatchApprovalRequestEvent {
+            call_id: "call".to_string(),
+            turn_id: "turn".to_string(),
+            changes: HashMap::from([
+                (
+                    PathBuf::from("src/lib.rs"),
+                    FileChange::Update {
+                        unified_diff: "--- a\n+++ b\n@@ -1 +1,2 @@\n-old\n+new\n+more\n"
+                            .to_string(),
+                        move_path: None,
+                    },
+                ),
+                (
+                    PathBuf::from("notes.md"),
+                    FileChange::Add {
+                        content: "one\ntwo\n".to_string(),
+                    },
+                ),
+            ]),
+            reason: None,
+            grant_root: None,
+        };
+        let prompt = ApprovalPrompt::patch(&event);
+        assert_eq!(prompt.detail, "A notes.md (+2)\nM src/lib.rs (+2 -1)");
+        assert_eq!(prompt.risk, Risk::Medium);
+        assert_eq!(prompt.headline(), "A notes.md (+2) and 1 more");
+    }
+}
@@ -2,8 +2,10 @@ use clap::Args;
 use clap::FromArgMatches;
 use clap::Parser;
 use clap::ValueEnum;
+use codex_core::config::types::ResponseCacheMode;
 use codex_utils_cli::CliConfigOverrides;
 use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Parser, Debug)]
 #[command(version)]
@@ -44,6 +46,10 @@ pub struct Cli {
     #[arg(long = "profile", short = 'p')]
     pub config_profile: Option<String>,
 
+    /// Orchestration from config.toml that routes turns across agent roles.
+    #[arg(long = "orchestration", value_name = "NAME")]
+    pub orchestration: Option<String>,
+
     /// Convenience alias for low-friction sandboxed automatic execution (-a on-request, --sandbox workspace-write).
     #[arg(long = "full-auto", default_value_t = false, global = true)]
     pub full_auto: bool,
@@ -59,6 +65,32 @@ pub struct Cli {
     )]
     pub dangerously_bypass_approvals_and_sandbox: bool,
 
+    /// Look but don't touch: force the read-only sandbox, deny every approval,
+    /// and leave out the tools that edit files or run code outside the sandbox.
+    #[arg(
+        long = "read-only",
+        default_value_t = false,
+        global = true,
+        conflicts_with_all = [
+            "full_auto",
+            "dangerously_bypass_approvals_and_sandbox",
+            "sandbox_mode",
+            "commit",
+            "create_pr"
+        ]
+    )]
+    pub read_only: bool,
+
+    /// Ask before leaving the sandbox and answer each request from the chat channel
+    /// configured in `[approvals_bridge]`.
+    #[arg(
+        long = "approvals-bridge",
+        default_value_t = false,
+        global = true,
+        conflicts_with_all = ["dangerously_bypass_approvals_and_sandbox", "read_only"]
+    )]
+    pub approvals_bridge: bool,
+
     /// Tell the agent to use the specified directory as its working root.
     #[clap(long = "cd", short = 'C', value_name = "DIR")]
     pub cwd: Option<PathBuf>,
@@ -71,14 +103,70 @@ pub struct Cli {
     #[arg(long = "add-dir", value_name = "DIR", value_hint = clap::ValueHint::DirPath)]
     pub add_dir: Vec<PathBuf>,
 
+    /// Narrow the session to this sub-directory of the working directory, for
+    /// example one package of a monorepo. Repeat to scope several directories.
+    #[arg(long = "scope", value_name = "DIR", value_hint = clap::ValueHint::DirPath)]
+    pub scope: Vec<PathBuf>,
+
     /// Run without persisting session files to disk.
     #[arg(long = "ephemeral", global = true, default_value_t = false)]
     pub ephemeral: bool,
 
+    /// After the run completes, commit the files Codex changed during the session.
+    /// Passing this flag is the explicit approval for that commit.
+    #[arg(long = "commit", default_value_t = false)]
+    pub commit: bool,
+
+    /// After the run completes, push the current branch and open a GitHub pull
+    /// request or GitLab merge request for it. Runs after `--commit` when both are set.
+    #[arg(long = "create-pr", default_value_t = false)]
+    pub create_pr: bool,
+
     /// Path to a JSON Schema file describing the model's final response shape.
+    /// A final response that does not match is sent back to the model for
+    /// repair, and the run fails if it still does not match.
     #[arg(long = "output-schema", value_name = "FILE")]
     pub output_schema: Option<PathBuf>,
 
+    /// Replay model responses cached in DIR and record the ones that are
+    /// missing, so an identical rerun makes no API calls.
+    #[arg(long = "response-cache", value_name = "DIR")]
+    pub response_cache: Option<PathBuf>,
+
+    /// How the response cache is used. Enables the cache when set.
+    #[arg(long = "response-cache-mode", value_enum)]
+    pub response_cache_mode: Option<ResponseCacheModeCliArg>,
+
+    /// Only talk to services on this machine: requires a localhost model
+    /// provider and turns off everything else that uses the network.
+    #[arg(long = "offline", default_value_t = false)]
+    pub offline: bool,
+
+    /// Record model API requests and responses, with credentials redacted,
+    /// as JSON lines in FILE.
+    #[arg(long = "wire-log", value_name = "FILE")]
+    pub wire_log: Option<PathBuf>,
+
+    /// Stop working after this much time, such as `45m` or `2h`, and end the
+    /// run with a summary of its state, the remaining work, and how to resume.
+    #[arg(long = "max-runtime", value_name = "DURATION", value_parser = parse_duration)]
+    pub max_runtime: Option<Duration>,
+
+    /// Stop working after this many model requests and end the run with a
+    /// summary of its state, the remaining work, and how to resume.
+    #[arg(long = "max-turns", value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_turns: Option<u32>,
+
+    /// What happens to the patches the agent makes. `propose-only` saves
+    /// each one as a numbered `.patch` file instead of applying it.
+    #[arg(long = "patch-mode", value_enum, default_value_t = PatchModeCliArg::Apply)]
+    pub patch_mode: PatchModeCliArg,
+
+    /// Directory that `--patch-mode propose-only` saves patches in.
+    /// Defaults to `.codex/patches` in the working directory.
+    #[arg(long = "patch-dir", value_name = "DIR")]
+    pub patch_dir: Option<PathBuf>,
+
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 
@@ -249,6 +337,57 @@ pub enum Color {
     Auto,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum ResponseCacheModeCliArg {
+    /// Replay cached responses and record the ones that are missing.
+    ReadWrite,
+    /// Ignore cached responses, call the model, and overwrite the entries.
+    Refresh,
+    /// Only replay cached responses; fail on a request that is not cached.
+    ReplayOnly,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum PatchModeCliArg {
+    /// Apply patches to the workspace.
+    #[default]
+    Apply,
+    /// Save patches for review and leave the workspace unchanged.
+    ProposeOnly,
+}
+
+impl From<ResponseCacheModeCliArg> for ResponseCacheMode {
+    fn from(mode: ResponseCacheModeCliArg) -> Self {
+        match mode {
+            ResponseCacheModeCliArg::ReadWrite => ResponseCacheMode::ReadWrite,
+            ResponseCacheModeCliArg::Refresh => ResponseCacheMode::Refresh,
+            ResponseCacheModeCliArg::ReplayOnly => ResponseCacheMode::ReplayOnly,
+        }
+    }
+}
+
+/// Parses durations such as `90s`, `45m` or `2h`. A bare number is seconds.
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let (number, unit_secs) = match value.char_indices().last() {
+        Some((index, 's')) => (&value[..index], 1),
+        Some((index, 'm')) => (&value[..index], 60),
+        Some((index, 'h')) => (&value[..index], 3600),
+        _ => (value, 1),
+    };
+    let secs = number
+        .parse::<u64>()
+        .ok()
+        .and_then(|number| number.checked_mul(unit_secs))
+        .filter(|secs| *secs > 0)
+        .ok_or_else(|| {
+            format!("invalid duration `{value}`: expected a positive number of seconds, or a number followed by s, m or h")
+        })?;
+    Ok(Duration::from_secs(secs))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -283,4 +422,86 @@ mod tests {
         });
         assert_eq!(effective_prompt.as_deref(), Some(PROMPT));
     }
+
+    #[test]
+    fn response_cache_flags_parse() {
+        let cli = Cli::parse_from([
+            "codex-exec",
+            "--response-cache",
+            ".codex-cache",
+            "--response-cache-mode",
+            "replay-only",
+            "fix the tests",
+        ]);
+
+        assert_eq!(cli.response_cache, Some(PathBuf::from(".codex-cache")));
+        assert_eq!(
+            cli.response_cache_mode.map(ResponseCacheMode::from),
+            Some(ResponseCacheMode::ReplayOnly)
+        );
+    }
+
+    #[test]
+    fn run_limit_flags_parse() {
+        let cli = Cli::parse_from([
+            "codex-exec",
+            "--max-runtime",
+            "45m",
+            "--max-turns",
+            "30",
+            "fix the tests",
+        ]);
+
+        assert_eq!(cli.max_runtime, Some(Duration::from_secs(45 * 60)));
+        assert_eq!(cli.max_turns, Some(30));
+        assert_eq!(parse_duration("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("2h"), Ok(Duration::from_secs(7200)));
+        assert!(parse_duration("0m").is_err());
+        assert!(parse_duration("1.5h").is_err());
+        assert!(Cli::try_parse_from(["codex-exec", "--max-turns", "0", "x"]).is_err());
+    }
+
+    #[test]
+    fn patch_mode_flags_parse() {
+        let cli = Cli::parse_from(["codex-exec", "fix the tests"]);
+        assert_eq!(cli.patch_mode, PatchModeCliArg::Apply);
+        assert_eq!(cli.patch_dir, None);
+
+        let cli = Cli::parse_from([
+            "codex-exec",
+            "--patch-mode",
+            "propose-only",
+            "--patch-dir",
+            "out/patches",
+            "fix the tests",
+        ]);
+        assert_eq!(cli.patch_mode, PatchModeCliArg::ProposeOnly);
+        assert_eq!(cli.patch_dir, Some(PathBuf::from("out/patches")));
+    }
+
+    #[test]
+    fn read_only_excludes_write_flags() {
+        let cli = Cli::parse_from(["codex-exec", "--read-only", "explain the build"]);
+        assert!(cli.read_only);
+
+        for flag in ["--full-auto", "--commit", "--create-pr"] {
+            assert!(
+                Cli::try_parse_from(["codex-exec", "--read-only", flag, "x"]).is_err(),
+                "{flag}"
+            );
+        }
+    }
+
+    #[test]
+    fn approvals_bridge_excludes_flags_that_never_ask() {
+        let cli = Cli::parse_from(["codex-exec", "--approvals-bridge", "fix the build"]);
+        assert!(cli.approvals_bridge);
+
+        for flag in ["--yolo", "--read-only"] {
+            assert!(
+                Cli::try_parse_from(["codex-exec", "--approvals-bridge", flag, "x"]).is_err(),
+                "{flag}"
+            );
+        }
+    }
 }
@@ -11,6 +11,10 @@ use codex_core::protocol::CollabCloseBeginEvent;
 use codex_core::protocol::CollabCloseEndEvent;
 use codex_core::protocol::CollabWaitingBeginEvent;
 use codex_core::protocol::CollabWaitingEndEvent;
+use codex_core::protocol::CommitCompletedEvent;
+use codex_core::protocol::ConversationForkedEvent;
+use codex_core::protocol::DelegateBeginEvent;
+use codex_core::protocol::DelegateEndEvent;
 use codex_core::protocol::DeprecationNoticeEvent;
 use codex_core::protocol::ErrorEvent;
 use codex_core::protocol::Event;
@@ -22,8 +26,12 @@ use codex_core::protocol::ItemCompletedEvent;
 use codex_core::protocol::McpInvocation;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::ModelRoutedEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
+use codex_core::protocol::PullRequestCompletedEvent;
+use codex_core::protocol::RequestBudgetEvent;
+use codex_core::protocol::ResumeSummarizedEvent;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_core::protocol::StreamErrorEvent;
 use codex_core::protocol::TurnAbortReason;
@@ -72,6 +80,9 @@ pub(crate) struct EventProcessorWithHumanOutput {
     /// Whether to include `AgentReasoning` events in the output.
     show_agent_reasoning: bool,
     show_raw_agent_reasoning: bool,
+    /// Whether to print `UserMessage` events. A live run echoes its prompt in
+    /// the config summary instead.
+    show_user_messages: bool,
     last_message_path: Option<PathBuf>,
     last_total_token_usage: Option<codex_core::protocol::TokenUsageInfo>,
     final_message: Option<String>,
@@ -99,6 +110,7 @@ impl EventProcessorWithHumanOutput {
                 yellow: Style::new().yellow(),
                 show_agent_reasoning: !config.hide_agent_reasoning,
                 show_raw_agent_reasoning: config.show_raw_agent_reasoning,
+                show_user_messages: false,
                 last_message_path,
                 last_total_token_usage: None,
                 final_message: None,
@@ -117,6 +129,7 @@ impl EventProcessorWithHumanOutput {
                 yellow: Style::new(),
                 show_agent_reasoning: !config.hide_agent_reasoning,
                 show_raw_agent_reasoning: config.show_raw_agent_reasoning,
+                show_user_messages: false,
                 last_message_path,
                 last_total_token_usage: None,
                 final_message: None,
@@ -124,6 +137,12 @@ impl EventProcessorWithHumanOutput {
             }
         }
     }
+
+    /// Print every user message, for transcripts that have no config summary.
+    pub(crate) fn with_user_messages(mut self) -> Self {
+        self.show_user_messages = true;
+        self
+    }
 }
 
 struct PatchApplyBegin {
@@ -203,6 +222,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     codex_core::protocol::McpStartupStatus::Starting => "starting".to_string(),
                     codex_core::protocol::McpStartupStatus::Ready => "ready".to_string(),
                     codex_core::protocol::McpStartupStatus::Cancelled => "cancelled".to_string(),
+                    codex_core::protocol::McpStartupStatus::Lazy => "lazy".to_string(),
                     codex_core::protocol::McpStartupStatus::Failed { ref error } => {
                         format!("failed: {error}")
                     }
@@ -237,6 +257,54 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             EventMsg::BackgroundEvent(BackgroundEventEvent { message }) => {
                 ts_msg!(self, "{}", message.style(self.dimmed));
             }
+            EventMsg::CommitCompleted(CommitCompletedEvent {
+                success,
+                commit_sha,
+                message,
+            }) => {
+                let message = message.unwrap_or_default();
+                if success {
+                    let sha = commit_sha.as_deref().map(|sha| &sha[..sha.len().min(7)]);
+                    ts_msg!(
+                        self,
+                        "{} {} {message}",
+                        "committed".style(self.green),
+                        sha.unwrap_or_default().style(self.dimmed)
+                    );
+                } else {
+                    ts_msg!(self, "{} {message}", "commit failed:".style(self.red));
+                }
+            }
+            EventMsg::PullRequestCompleted(PullRequestCompletedEvent {
+                success,
+                url,
+                message,
+            }) => {
+                let message = message.unwrap_or_default();
+                if success {
+                    ts_msg!(
+                        self,
+                        "{} {message} {}",
+                        "opened".style(self.green),
+                        url.unwrap_or_default().style(self.cyan)
+                    );
+                } else {
+                    ts_msg!(self, "{} {message}", "pull request failed:".style(self.red));
+                }
+            }
+            EventMsg::ConversationForked(ConversationForkedEvent {
+                thread_id,
+                nth_user_message,
+                ..
+            }) => {
+                ts_msg!(
+                    self,
+                    "{} {} {}",
+                    "forked into".style(self.magenta),
+                    thread_id.to_string().style(self.cyan),
+                    format!("(before user message {nth_user_message})").style(self.dimmed)
+                );
+            }
             EventMsg::StreamError(StreamErrorEvent {
                 message,
                 additional_details,
@@ -251,6 +319,14 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             EventMsg::TurnStarted(_) => {
                 // Ignore.
             }
+            EventMsg::UserMessage(user_message) if self.show_user_messages => {
+                ts_msg!(
+                    self,
+                    "{}\n{}",
+                    "user".style(self.cyan),
+                    user_message.message
+                );
+            }
             EventMsg::ElicitationRequest(ev) => {
                 ts_msg!(
                     self,
@@ -281,6 +357,26 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             EventMsg::TokenCount(ev) => {
                 self.last_total_token_usage = ev.info;
             }
+            EventMsg::RequestBudget(RequestBudgetEvent {
+                delay_ms: Some(delay_ms),
+                ..
+            }) => {
+                let message = format!(
+                    "waiting {:.1}s for the provider rate limit",
+                    delay_ms as f64 / 1000.0
+                );
+                ts_msg!(self, "{}", message.style(self.dimmed));
+            }
+            EventMsg::RequestBudget(_) => {}
+            EventMsg::ReadPrefetchStats(_) => {}
+            EventMsg::TurnLatency(_) => {}
+            EventMsg::ModelRouted(ModelRoutedEvent { model, reason, .. }) => {
+                ts_msg!(
+                    self,
+                    "{}",
+                    format!("routed to {model} ({reason})").style(self.dimmed)
+                );
+            }
 
             EventMsg::AgentReasoningSectionBreak(_) => {
                 if !self.show_agent_reasoning {
@@ -612,6 +708,20 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             EventMsg::ContextCompacted(_) => {
                 ts_msg!(self, "context compacted");
             }
+            EventMsg::ResumeSummarized(ResumeSummarizedEvent {
+                summarized_turns,
+                kept_turns,
+                ..
+            }) => {
+                ts_msg!(
+                    self,
+                    "{}",
+                    format!(
+                        "summarized {summarized_turns} earlier turn(s); kept the last {kept_turns}"
+                    )
+                    .style(self.dimmed)
+                );
+            }
             EventMsg::CollabAgentSpawnBegin(CollabAgentSpawnBeginEvent {
                 call_id,
                 sender_thread_id: _,
@@ -644,6 +754,55 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     eprintln!("  agent: {}", new_thread_id.to_string().style(self.dimmed));
                 }
             }
+            EventMsg::DelegateBegin(DelegateBeginEvent {
+                call_id,
+                task_index,
+                prompt,
+                model,
+                role,
+            }) => {
+                ts_msg!(
+                    self,
+                    "{} {}",
+                    "delegate".style(self.magenta),
+                    format_collab_invocation(
+                        &format_delegate_task(task_index, role.as_deref()),
+                        &call_id,
+                        Some(&prompt)
+                    )
+                    .style(self.bold)
+                );
+                eprintln!("  model: {}", model.style(self.dimmed));
+            }
+            EventMsg::DelegateEnd(DelegateEndEvent {
+                call_id,
+                task_index,
+                prompt,
+                model: _,
+                role,
+                status,
+                total_tokens,
+            }) => {
+                let title_style = if is_collab_status_failure(&status) {
+                    self.red
+                } else {
+                    self.green
+                };
+                let title = format!(
+                    "{} {}:",
+                    format_collab_invocation(
+                        &format_delegate_task(task_index, role.as_deref()),
+                        &call_id,
+                        Some(&prompt)
+                    ),
+                    format_collab_status(&status)
+                );
+                ts_msg!(self, "{}", title.style(title_style));
+                eprintln!(
+                    "  tokens used: {}",
+                    format_with_separators(total_tokens).style(self.dimmed)
+                );
+            }
             EventMsg::CollabAgentInteractionBegin(CollabAgentInteractionBeginEvent {
                 call_id,
                 sender_thread_id: _,
@@ -770,6 +929,8 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             }
             EventMsg::ShutdownComplete => return CodexStatus::Shutdown,
             EventMsg::ThreadNameUpdated(_)
+            | EventMsg::QueuedUserMessagesUpdated(_)
+            | EventMsg::BookmarkAdded(_)
             | EventMsg::ExecApprovalRequest(_)
             | EventMsg::ApplyPatchApprovalRequest(_)
             | EventMsg::TerminalInteraction(_)
@@ -798,6 +959,8 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::UndoStarted(_)
             | EventMsg::ThreadRolledBack(_)
             | EventMsg::RequestUserInput(_)
+            | EventMsg::RetriesExhausted(_)
+            | EventMsg::UsageQuotaExceeded(_)
             | EventMsg::CollabResumeBegin(_)
             | EventMsg::CollabResumeEnd(_)
             | EventMsg::DynamicToolCallRequest(_) => {}
@@ -857,6 +1020,13 @@ fn format_collab_invocation(tool: &str, call_id: &str, prompt: Option<&str>) ->
     }
 }
 
+fn format_delegate_task(task_index: u32, role: Option<&str>) -> String {
+    match role {
+        Some(role) => format!("task {task_index} ({role})"),
+        None => format!("task {task_index}"),
+    }
+}
+
 fn format_collab_status(status: &AgentStatus) -> String {
     match status {
         AgentStatus::PendingInit => "pending init".to_string(),
@@ -62,7 +62,9 @@ pub struct EventProcessorWithJsonOutput {
     next_event_id: AtomicU64,
     // Tracks running commands by call_id, including the associated item id.
     running_commands: HashMap<String, RunningCommand>,
-    running_patch_applies: HashMap<String, protocol::PatchApplyBeginEvent>,
+    running_patch_applies: HashMap<String, RunningPatchApply>,
+    // Whether patches are saved for review instead of applied.
+    propose_patches: bool,
     // Tracks the todo list for the current turn (at most one per turn).
     running_todo_list: Option<RunningTodoList>,
     last_total_token_usage: Option<codex_core::protocol::TokenUsage>,
@@ -79,6 +81,12 @@ struct RunningCommand {
     aggregated_output: String,
 }
 
+#[derive(Debug, Clone)]
+struct RunningPatchApply {
+    item_id: String,
+    changes: Vec<FileUpdateChange>,
+}
+
 #[derive(Debug, Clone)]
 struct RunningTodoList {
     item_id: String,
@@ -107,6 +115,7 @@ impl EventProcessorWithJsonOutput {
             next_event_id: AtomicU64::new(0),
             running_commands: HashMap::new(),
             running_patch_applies: HashMap::new(),
+            propose_patches: false,
             running_todo_list: None,
             last_total_token_usage: None,
             running_mcp_tool_calls: HashMap::new(),
@@ -116,6 +125,13 @@ impl EventProcessorWithJsonOutput {
         }
     }
 
+    /// Reports successful patches as `proposed` rather than `completed`, for
+    /// runs with `--patch-mode propose-only`.
+    pub fn with_patch_proposals(mut self, propose_patches: bool) -> Self {
+        self.propose_patches = propose_patches;
+        self
+    }
+
     pub fn collect_thread_events(&mut self, event: &protocol::Event) -> Vec<ThreadEvent> {
         match &event.msg {
             protocol::EventMsg::SessionConfigured(ev) => self.handle_session_configured(ev),
@@ -630,10 +646,41 @@ impl EventProcessorWithJsonOutput {
         &mut self,
         ev: &protocol::PatchApplyBeginEvent,
     ) -> Vec<ThreadEvent> {
+        let mut changes: Vec<FileUpdateChange> = ev
+            .changes
+            .iter()
+            .map(|(path, change)| {
+                let hashes = ev.content_hashes.get(path).cloned().unwrap_or_default();
+                FileUpdateChange {
+                    path: path.to_str().unwrap_or("").to_string(),
+                    kind: self.map_change_kind(change),
+                    move_path: match change {
+                        protocol::FileChange::Update {
+                            move_path: Some(move_path),
+                            ..
+                        } => Some(move_path.to_str().unwrap_or("").to_string()),
+                        _ => None,
+                    },
+                    hunks: diff_hunks(change),
+                    before_sha256: hashes.before,
+                    after_sha256: hashes.after,
+                }
+            })
+            .collect();
+        changes.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let item_id = self.get_next_item_id();
+        let item = ThreadItem {
+            id: item_id.clone(),
+            details: ThreadItemDetails::FileChange(FileChangeItem {
+                changes: changes.clone(),
+                status: PatchApplyStatus::InProgress,
+            }),
+        };
         self.running_patch_applies
-            .insert(ev.call_id.clone(), ev.clone());
+            .insert(ev.call_id.clone(), RunningPatchApply { item_id, changes });
 
-        Vec::new()
+        vec![ThreadEvent::ItemStarted(ItemStartedEvent { item })]
     }
 
     fn map_change_kind(&self, kind: &protocol::FileChange) -> PatchChangeKind {
@@ -645,26 +692,17 @@ impl EventProcessorWithJsonOutput {
     }
 
     fn handle_patch_apply_end(&mut self, ev: &protocol::PatchApplyEndEvent) -> Vec<ThreadEvent> {
-        if let Some(running_patch_apply) = self.running_patch_applies.remove(&ev.call_id) {
-            let status = if ev.success {
-                PatchApplyStatus::Completed
-            } else {
-                PatchApplyStatus::Failed
+        if let Some(RunningPatchApply { item_id, changes }) =
+            self.running_patch_applies.remove(&ev.call_id)
+        {
+            let status = match (ev.success, self.propose_patches) {
+                (true, true) => PatchApplyStatus::Proposed,
+                (true, false) => PatchApplyStatus::Completed,
+                (false, _) => PatchApplyStatus::Failed,
             };
             let item = ThreadItem {
-                id: self.get_next_item_id(),
-
-                details: ThreadItemDetails::FileChange(FileChangeItem {
-                    changes: running_patch_apply
-                        .changes
-                        .iter()
-                        .map(|(path, change)| FileUpdateChange {
-                            path: path.to_str().unwrap_or("").to_string(),
-                            kind: self.map_change_kind(change),
-                        })
-                        .collect(),
-                    status,
-                }),
+                id: item_id,
+                details: ThreadItemDetails::FileChange(FileChangeItem { changes, status }),
             };
 
             return vec![ThreadEvent::ItemCompleted(ItemCompletedEvent { item })];
@@ -804,6 +842,46 @@ fn is_collab_failure(status: &CoreAgentStatus) -> bool {
     )
 }
 
+/// Splits a file change into unified diff hunks, each starting with its `@@`
+/// header. Added and deleted files become a single hunk.
+fn diff_hunks(change: &protocol::FileChange) -> Vec<String> {
+    let whole_file = |content: &str, header: String, prefix: char| {
+        let mut hunk = header;
+        for line in content.lines() {
+            hunk.push(prefix);
+            hunk.push_str(line);
+            hunk.push('\n');
+        }
+        hunk
+    };
+    match change {
+        protocol::FileChange::Add { content } if !content.is_empty() => vec![whole_file(
+            content,
+            format!("@@ -0,0 +1,{} @@\n", content.lines().count()),
+            '+',
+        )],
+        protocol::FileChange::Delete { content } if !content.is_empty() => vec![whole_file(
+            content,
+            format!("@@ -1,{} +0,0 @@\n", content.lines().count()),
+            '-',
+        )],
+        protocol::FileChange::Add { .. } | protocol::FileChange::Delete { .. } => Vec::new(),
+        protocol::FileChange::Update { unified_diff, .. } => {
+            let mut hunks: Vec<String> = Vec::new();
+            for line in unified_diff.split_inclusive('\n') {
+                if line.starts_with("@@") {
+                    hunks.push(String::new());
+                }
+                // Lines before the first hunk are the `---`/`+++` headers.
+                if let Some(hunk) = hunks.last_mut() {
+                    hunk.push_str(line);
+                }
+            }
+            hunks
+        }
+    }
+}
+
 impl From<CoreAgentStatus> for CollabAgentState {
     fn from(value: CoreAgentStatus) -> Self {
         match value {
@@ -165,6 +165,23 @@ pub struct CommandExecutionItem {
 pub struct FileUpdateChange {
     pub path: String,
     pub kind: PatchChangeKind,
+    /// Where an updated file is moved to, if anywhere.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub move_path: Option<String>,
+    /// The unified diff hunks of the change, each starting with its `@@`
+    /// header.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[ts(optional)]
+    pub hunks: Vec<String>,
+    /// SHA-256 of the file before the patch; absent when it did not exist.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub before_sha256: Option<String>,
+    /// SHA-256 of the file after the patch; absent when it is deleted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub after_sha256: Option<String>,
 }
 
 /// The status of a file change.
@@ -174,6 +191,8 @@ pub enum PatchApplyStatus {
     InProgress,
     Completed,
     Failed,
+    /// Saved for review by `--patch-mode propose-only` instead of applied.
+    Proposed,
 }
 
 /// A set of file changes by the agent.
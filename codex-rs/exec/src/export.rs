@@ -0,0 +1,757 @@
+//! `codex sessions export`: render a recorded session as a Markdown or HTML
+//! report for sharing, e.g. in a code review or an incident doc.
+//!
+//! The report is built from what every rollout persists: user and agent
+//! messages, tool calls with their outputs, `apply_patch` inputs, bookmarks,
+//! and token counts. Command output is collapsed with `<details>` in both
+//! formats. Bookmarks are listed up front, linking to their turns, and shown
+//! again at the start of each bookmarked turn.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use clap::Parser;
+use clap::ValueEnum;
+use codex_core::RolloutRecorder;
+use codex_core::config::find_codex_home;
+use codex_core::find_archived_thread_path_by_id_str;
+use codex_core::find_thread_path_by_id_str;
+use codex_core::find_thread_path_by_name_str;
+use codex_core::parse_command::extract_shell_command;
+use codex_core::protocol::BookmarkAddedEvent;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::InitialHistory;
+use codex_core::protocol::ResumedHistory;
+use codex_core::protocol::RolloutItem;
+use codex_core::protocol::TokenUsage;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use serde_json::Value;
+use shlex::try_join;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    #[default]
+    Md,
+    Html,
+}
+
+#[derive(Debug, Parser)]
+pub struct SessionExportArgs {
+    /// Session id (UUID) or thread name to export.
+    #[arg(value_name = "SESSION_ID")]
+    pub session: String,
+
+    /// Report format.
+    #[arg(long = "format", value_enum, default_value_t = ExportFormat::Md)]
+    pub format: ExportFormat,
+
+    /// Write the report to this file instead of stdout.
+    #[arg(long = "output", short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Price of input tokens in USD per million, for the cost estimate.
+    #[arg(long = "input-price", value_name = "USD", requires = "output_price")]
+    pub input_price: Option<f64>,
+
+    /// Price of cached input tokens in USD per million. Defaults to
+    /// `--input-price`.
+    #[arg(
+        long = "cached-input-price",
+        value_name = "USD",
+        requires = "input_price"
+    )]
+    pub cached_input_price: Option<f64>,
+
+    /// Price of output tokens (including reasoning) in USD per million.
+    #[arg(long = "output-price", value_name = "USD", requires = "input_price")]
+    pub output_price: Option<f64>,
+}
+
+/// USD per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TokenPrices {
+    input: f64,
+    cached_input: f64,
+    output: f64,
+}
+
+impl TokenPrices {
+    fn cost(&self, usage: &TokenUsage) -> f64 {
+        let cached = usage.cached_input_tokens.max(0) as f64;
+        let uncached = (usage.input_tokens - usage.cached_input_tokens).max(0) as f64;
+        let output = usage.output_tokens.max(0) as f64;
+        (uncached * self.input + cached * self.cached_input + output * self.output) / 1_000_000.0
+    }
+}
+
+#[allow(clippy::print_stdout)]
+pub async fn run_session_export(args: SessionExportArgs) -> anyhow::Result<()> {
+    let codex_home = find_codex_home()?;
+    let Some(rollout) = find_session_rollout(&codex_home, &args.session).await? else {
+        anyhow::bail!("no session found for {}", args.session);
+    };
+    let history = match RolloutRecorder::get_rollout_history(&rollout).await? {
+        InitialHistory::Resumed(history) => history,
+        InitialHistory::New | InitialHistory::Forked(_) => {
+            anyhow::bail!("{} does not contain a recorded session", rollout.display());
+        }
+    };
+    let prices = match (args.input_price, args.output_price) {
+        (Some(input), Some(output)) => Some(TokenPrices {
+            input,
+            cached_input: args.cached_input_price.unwrap_or(input),
+            output,
+        }),
+        _ => None,
+    };
+    let report = SessionReport::from_history(&history);
+    let rendered = match args.format {
+        ExportFormat::Md => report.to_markdown(prices),
+        ExportFormat::Html => report.to_html(prices),
+    };
+    match args.output {
+        Some(path) => tokio::fs::write(&path, rendered).await?,
+        None => print!("{rendered}"),
+    }
+    Ok(())
+}
+
+async fn find_session_rollout(codex_home: &Path, session: &str) -> anyhow::Result<Option<PathBuf>> {
+    if Uuid::parse_str(session).is_err() {
+        return Ok(find_thread_path_by_name_str(codex_home, session).await?);
+    }
+    if let Some(path) = find_thread_path_by_id_str(codex_home, session).await? {
+        return Ok(Some(path));
+    }
+    Ok(find_archived_thread_path_by_id_str(codex_home, session).await?)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ReportEntry {
+    User(String),
+    Agent(String),
+    Command {
+        command: String,
+        output: Option<String>,
+    },
+    Patch {
+        patch: String,
+        output: Option<String>,
+    },
+    Tool {
+        name: String,
+        arguments: String,
+        output: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+struct SessionReport {
+    session_id: String,
+    started_at: String,
+    cwd: Option<PathBuf>,
+    model: Option<String>,
+    entries: Vec<ReportEntry>,
+    bookmarks: Vec<BookmarkAddedEvent>,
+    token_usage: Option<TokenUsage>,
+}
+
+impl SessionReport {
+    fn from_history(history: &ResumedHistory) -> Self {
+        let mut report = SessionReport {
+            session_id: history.conversation_id.to_string(),
+            ..Default::default()
+        };
+        // Tool outputs are recorded after their calls; attach each one to the
+        // entry of the call with the same id.
+        let mut calls: HashMap<String, usize> = HashMap::new();
+        for item in &history.history {
+            match item {
+                RolloutItem::SessionMeta(meta_line) => {
+                    if report.started_at.is_empty() {
+                        report.started_at = meta_line.meta.timestamp.clone();
+                        report.cwd = Some(meta_line.meta.cwd.clone());
+                    }
+                }
+                RolloutItem::TurnContext(turn_context) => {
+                    report
+                        .model
+                        .get_or_insert_with(|| turn_context.model.clone());
+                }
+                RolloutItem::EventMsg(EventMsg::UserMessage(event)) => {
+                    report
+                        .entries
+                        .push(ReportEntry::User(event.message.clone()));
+                }
+                RolloutItem::EventMsg(EventMsg::AgentMessage(event)) => {
+                    report
+                        .entries
+                        .push(ReportEntry::Agent(event.message.clone()));
+                }
+                RolloutItem::EventMsg(EventMsg::BookmarkAdded(event)) => {
+                    report.bookmarks.push(event.clone());
+                }
+                RolloutItem::EventMsg(EventMsg::TokenCount(event)) => {
+                    if let Some(info) = &event.info {
+                        report.token_usage = Some(info.total_token_usage.clone());
+                    }
+                }
+                RolloutItem::ResponseItem(item) => {
+                    let Some((call_id, entry)) = call_entry(item) else {
+                        if let Some((call_id, output)) = call_output(item)
+                            && let Some(index) = calls.get(call_id)
+                        {
+                            report.entries[*index].set_output(output);
+                        }
+                        continue;
+                    };
+                    if let Some(call_id) = call_id {
+                        calls.insert(call_id, report.entries.len());
+                    }
+                    report.entries.push(entry);
+                }
+                RolloutItem::EventMsg(_) | RolloutItem::Compacted(_) => {}
+            }
+        }
+        report
+    }
+
+    fn to_markdown(&self, prices: Option<TokenPrices>) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# Codex session {}\n", self.session_id);
+        for (label, value) in self.details() {
+            let _ = writeln!(out, "- **{label}:** {value}");
+        }
+        if !self.bookmarks.is_empty() {
+            let _ = writeln!(out, "\n## Bookmarks\n");
+            for bookmark in &self.bookmarks {
+                let turn = bookmark.turn;
+                let _ = writeln!(
+                    out,
+                    "- [Turn {turn}](#turn-{turn}): {}",
+                    bookmark_label(bookmark)
+                );
+            }
+        }
+        let mut turn = 0;
+        for entry in &self.entries {
+            match entry {
+                ReportEntry::User(message) => {
+                    turn += 1;
+                    let _ = writeln!(out, "\n## Turn {turn}\n\n**User**\n");
+                    for line in message.lines() {
+                        let _ = writeln!(out, "> {line}");
+                    }
+                    for bookmark in self.bookmarks_in(turn) {
+                        let _ = writeln!(out, "\n**Bookmark:** {}", bookmark_label(bookmark));
+                    }
+                }
+                ReportEntry::Agent(message) => {
+                    let _ = writeln!(out, "\n**Codex**\n\n{}", message.trim_end());
+                }
+                ReportEntry::Command { command, output } => {
+                    let summary = format!("<code>{}</code>", escape_html(command));
+                    markdown_details(&mut out, &summary, "text", output.as_deref());
+                }
+                ReportEntry::Patch { patch, output } => {
+                    let _ = writeln!(out, "\n{}", markdown_fence("diff", patch));
+                    if let Some(output) = output {
+                        markdown_details(&mut out, "Patch result", "text", Some(output));
+                    }
+                }
+                ReportEntry::Tool {
+                    name,
+                    arguments,
+                    output,
+                } => {
+                    let summary = format!("Tool <code>{}</code>", escape_html(name));
+                    let _ = writeln!(out, "\n<details>\n<summary>{summary}</summary>\n");
+                    let _ = writeln!(out, "{}", markdown_fence("json", arguments));
+                    if let Some(output) = output {
+                        let _ = writeln!(out, "\n{}", markdown_fence("text", output));
+                    }
+                    let _ = writeln!(out, "\n</details>");
+                }
+            }
+        }
+        if let Some(usage) = &self.token_usage {
+            let _ = writeln!(out, "\n## Token usage\n\n| | Tokens |\n| --- | ---: |");
+            for (label, tokens) in usage_rows(usage) {
+                let _ = writeln!(out, "| {label} | {tokens} |");
+            }
+            if let Some(prices) = prices {
+                let _ = writeln!(out, "\nEstimated cost: **${:.2}**", prices.cost(usage));
+            }
+        }
+        out
+    }
+
+    fn to_html(&self, prices: Option<TokenPrices>) -> String {
+        let title = escape_html(&format!("Codex session {}", self.session_id));
+        let mut out = String::new();
+        let _ = writeln!(out, "<!DOCTYPE html>\n<html lang=\"en\">\n<head>");
+        let _ = writeln!(out, "<meta charset=\"utf-8\">\n<title>{title}</title>");
+        let _ = writeln!(out, "<style>{HTML_STYLE}</style>\n</head>\n<body>");
+        let _ = writeln!(out, "<h1>{title}</h1>\n<ul class=\"details\">");
+        for (label, value) in self.details() {
+            let _ = writeln!(
+                out,
+                "<li><strong>{label}:</strong> {}</li>",
+                escape_html(&value)
+            );
+        }
+        let _ = writeln!(out, "</ul>");
+        if !self.bookmarks.is_empty() {
+            let _ = writeln!(out, "<h2>Bookmarks</h2>\n<ul class=\"bookmarks\">");
+            for bookmark in &self.bookmarks {
+                let turn = bookmark.turn;
+                let _ = writeln!(
+                    out,
+                    "<li><a href=\"#turn-{turn}\">Turn {turn}</a>: {}</li>",
+                    escape_html(&bookmark_label(bookmark))
+                );
+            }
+            let _ = writeln!(out, "</ul>");
+        }
+        let mut turn = 0;
+        for entry in &self.entries {
+            match entry {
+                ReportEntry::User(message) => {
+                    turn += 1;
+                    let _ = writeln!(
+                        out,
+                        "<h2 id=\"turn-{turn}\">Turn {turn}</h2>\n<div class=\"user\"><div class=\"role\">User</div><pre class=\"message\">{}</pre></div>",
+                        escape_html(message)
+                    );
+                    for bookmark in self.bookmarks_in(turn) {
+                        let _ = writeln!(
+                            out,
+                            "<p class=\"bookmark\"><strong>Bookmark:</strong> {}</p>",
+                            escape_html(&bookmark_label(bookmark))
+                        );
+                    }
+                }
+                ReportEntry::Agent(message) => {
+                    let _ = writeln!(
+                        out,
+                        "<div class=\"agent\"><div class=\"role\">Codex</div><pre class=\"message\">{}</pre></div>",
+                        escape_html(message.trim_end())
+                    );
+                }
+                ReportEntry::Command { command, output } => {
+                    let summary = format!("<code>{}</code>", escape_html(command));
+                    html_details(&mut out, &summary, output.as_deref());
+                }
+                ReportEntry::Patch { patch, output } => {
+                    let _ = writeln!(out, "<pre class=\"diff\">");
+                    for line in patch.lines() {
+                        let class = match line.chars().next() {
+                            Some('+') => "add",
+                            Some('-') => "del",
+                            Some('@') | Some('*') => "meta",
+                            _ => "ctx",
+                        };
+                        let _ =
+                            writeln!(out, "<span class=\"{class}\">{}</span>", escape_html(line));
+                    }
+                    let _ = writeln!(out, "</pre>");
+                    if let Some(output) = output {
+                        html_details(&mut out, "Patch result", Some(output));
+                    }
+                }
+                ReportEntry::Tool {
+                    name,
+                    arguments,
+                    output,
+                } => {
+                    let summary = format!("Tool <code>{}</code>", escape_html(name));
+                    let body = match output {
+                        Some(output) => format!("{arguments}\n\n{output}"),
+                        None => arguments.clone(),
+                    };
+                    html_details(&mut out, &summary, Some(&body));
+                }
+            }
+        }
+        if let Some(usage) = &self.token_usage {
+            let _ = writeln!(out, "<h2>Token usage</h2>\n<table>");
+            for (label, tokens) in usage_rows(usage) {
+                let _ = writeln!(out, "<tr><th>{label}</th><td>{tokens}</td></tr>");
+            }
+            let _ = writeln!(out, "</table>");
+            if let Some(prices) = prices {
+                let _ = writeln!(
+                    out,
+                    "<p>Estimated cost: <strong>${:.2}</strong></p>",
+                    prices.cost(usage)
+                );
+            }
+        }
+        let _ = writeln!(out, "</body>\n</html>");
+        out
+    }
+
+    fn bookmarks_in(&self, turn: u32) -> impl Iterator<Item = &BookmarkAddedEvent> {
+        self.bookmarks
+            .iter()
+            .filter(move |bookmark| bookmark.turn == turn)
+    }
+
+    fn details(&self) -> Vec<(&'static str, String)> {
+        let mut details = Vec::new();
+        if !self.started_at.is_empty() {
+            details.push(("Started", self.started_at.clone()));
+        }
+        if let Some(model) = &self.model {
+            details.push(("Model", model.clone()));
+        }
+        if let Some(cwd) = &self.cwd {
+            details.push(("Directory", cwd.display().to_string()));
+        }
+        details
+    }
+}
+
+impl ReportEntry {
+    fn set_output(&mut self, text: String) {
+        match self {
+            ReportEntry::Command { output, .. }
+            | ReportEntry::Patch { output, .. }
+            | ReportEntry::Tool { output, .. } => *output = Some(text),
+            ReportEntry::User(_) | ReportEntry::Agent(_) => {}
+        }
+    }
+}
+
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:60rem;margin:2rem auto;padding:0 1rem;line-height:1.5}\
+pre{background:#f6f8fa;padding:.75rem;overflow-x:auto;white-space:pre-wrap}\
+.message{background:none;padding:0;font-family:inherit}\
+.role{font-weight:bold}.user{border-left:4px solid #0969da;padding-left:.75rem}\
+.diff .add{color:#1a7f37}.diff .del{color:#cf222e}.diff .meta{color:#8250df}\
+.bookmark{background:#fff8c5;padding:.25rem .75rem}\
+details{margin:.5rem 0}table{border-collapse:collapse}th,td{padding:.25rem .75rem;text-align:left}";
+
+/// The call id (when the output can be matched later) and the entry for a
+/// tool call; `None` for anything that is not a call.
+fn call_entry(item: &ResponseItem) -> Option<(Option<String>, ReportEntry)> {
+    match item {
+        ResponseItem::LocalShellCall {
+            call_id,
+            action: LocalShellAction::Exec(action),
+            ..
+        } => Some((
+            call_id.clone(),
+            ReportEntry::Command {
+                command: display_command(&action.command),
+                output: None,
+            },
+        )),
+        ResponseItem::FunctionCall {
+            name,
+            arguments,
+            call_id,
+            ..
+        } => {
+            let parsed = serde_json::from_str::<Value>(arguments).ok();
+            let entry = if name == "apply_patch"
+                && let Some(input) = parsed
+                    .as_ref()
+                    .and_then(|arguments| arguments.get("input"))
+                    .and_then(Value::as_str)
+            {
+                ReportEntry::Patch {
+                    patch: input.to_string(),
+                    output: None,
+                }
+            } else if let Some(command) = parsed.as_ref().and_then(command_from_arguments) {
+                ReportEntry::Command {
+                    command,
+                    output: None,
+                }
+            } else {
+                ReportEntry::Tool {
+                    name: name.clone(),
+                    arguments: parsed
+                        .and_then(|arguments| serde_json::to_string_pretty(&arguments).ok())
+                        .unwrap_or_else(|| arguments.clone()),
+                    output: None,
+                }
+            };
+            Some((Some(call_id.clone()), entry))
+        }
+        ResponseItem::CustomToolCall {
+            name,
+            input,
+            call_id,
+            ..
+        } => {
+            let entry = if name == "apply_patch" {
+                ReportEntry::Patch {
+                    patch: input.clone(),
+                    output: None,
+                }
+            } else {
+                ReportEntry::Tool {
+                    name: name.clone(),
+                    arguments: input.clone(),
+                    output: None,
+                }
+            };
+            Some((Some(call_id.clone()), entry))
+        }
+        _ => None,
+    }
+}
+
+fn call_output(item: &ResponseItem) -> Option<(&str, String)> {
+    match item {
+        ResponseItem::FunctionCallOutput { call_id, output } => {
+            Some((call_id, output.body.to_text().unwrap_or_default()))
+        }
+        ResponseItem::CustomToolCallOutput { call_id, output } => Some((call_id, output.clone())),
+        _ => None,
+    }
+}
+
+/// The command of a shell-like tool call: `command` as an argv array or a
+/// string, or the unified exec `cmd`.
+fn command_from_arguments(arguments: &Value) -> Option<String> {
+    match arguments.get("command").or_else(|| arguments.get("cmd"))? {
+        Value::String(command) => Some(command.clone()),
+        Value::Array(argv) => Some(display_command(
+            &argv
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect::<Vec<_>>(),
+        )),
+        _ => None,
+    }
+}
+
+fn display_command(command: &[String]) -> String {
+    if let Some((_, script)) = extract_shell_command(command) {
+        return script.to_string();
+    }
+    try_join(command.iter().map(String::as_str)).unwrap_or_else(|_| command.join(" "))
+}
+
+fn usage_rows(usage: &TokenUsage) -> [(&'static str, i64); 5] {
+    [
+        ("Input", usage.input_tokens),
+        ("Cached input", usage.cached_input_tokens),
+        ("Output", usage.output_tokens),
+        ("Reasoning output", usage.reasoning_output_tokens),
+        ("Total", usage.total_tokens),
+    ]
+}
+
+/// A fenced code block whose fence is longer than any backtick run in `body`.
+fn markdown_fence(lang: &str, body: &str) -> String {
+    let longest_run = body.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    format!("{fence}{lang}\n{}\n{fence}", body.trim_end_matches('\n'))
+}
+
+fn markdown_details(out: &mut String, summary: &str, lang: &str, body: Option<&str>) {
+    let _ = writeln!(out, "\n<details>\n<summary>{summary}</summary>\n");
+    let _ = writeln!(
+        out,
+        "{}",
+        markdown_fence(lang, body.unwrap_or("(no output)"))
+    );
+    let _ = writeln!(out, "\n</details>");
+}
+
+fn html_details(out: &mut String, summary: &str, body: Option<&str>) {
+    let _ = writeln!(
+        out,
+        "<details><summary>{summary}</summary><pre>{}</pre></details>",
+        escape_html(body.unwrap_or("(no output)"))
+    );
+}
+
+/// The bookmark's note, if any, followed by the first line of the bookmarked cell.
+fn bookmark_label(bookmark: &BookmarkAddedEvent) -> String {
+    match &bookmark.note {
+        Some(note) => format!("{note} (\"{}\")", bookmark.excerpt),
+        None => format!("\"{}\"", bookmark.excerpt),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::AgentMessageEvent;
+    use codex_core::protocol::SessionMeta;
+    use codex_core::protocol::SessionMetaLine;
+    use codex_core::protocol::TokenCountEvent;
+    use codex_core::protocol::TokenUsageInfo;
+    use codex_core::protocol::UserMessageEvent;
+    use codex_protocol::ThreadId;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn recorded_history() -> ResumedHistory {
+        let conversation_id = ThreadId::new();
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            cached_input_tokens: 400_000,
+            output_tokens: 100_000,
+            reasoning_output_tokens: 20_000,
+            total_tokens: 1_100_000,
+        };
+        ResumedHistory {
+            conversation_id,
+            history: vec![
+                RolloutItem::SessionMeta(SessionMetaLine {
+                    meta: SessionMeta {
+                        id: conversation_id,
+                        timestamp: "2026-01-01T00:00:00.000Z".to_string(),
+                        cwd: PathBuf::from("/repo"),
+                        ..Default::default()
+                    },
+                    git: None,
+                }),
+                RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+                    message: "fix <the> build".to_string(),
+                    images: None,
+                    local_images: Vec::new(),
+                    text_elements: Vec::new(),
+                })),
+                RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                    id: None,
+                    name: "shell".to_string(),
+                    arguments: json!({ "command": ["bash", "-lc", "cargo build"] }).to_string(),
+                    call_id: "call-1".to_string(),
+                }),
+                RolloutItem::ResponseItem(ResponseItem::CustomToolCall {
+                    id: None,
+                    status: None,
+                    call_id: "call-2".to_string(),
+                    name: "apply_patch".to_string(),
+                    input: "*** Begin Patch\n*** Update File: a.rs\n-old\n+new\n*** End Patch"
+                        .to_string(),
+                }),
+                RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput {
+                    call_id: "call-1".to_string(),
+                    output: FunctionCallOutputPayload::from_text("error[E0308]".to_string()),
+                }),
+                RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+                    message: "fixed".to_string(),
+                })),
+                RolloutItem::EventMsg(EventMsg::BookmarkAdded(BookmarkAddedEvent {
+                    turn: 1,
+                    excerpt: "fixed".to_string(),
+                    note: Some("type <mismatch> fix".to_string()),
+                })),
+                RolloutItem::EventMsg(EventMsg::TokenCount(TokenCountEvent {
+                    info: Some(TokenUsageInfo {
+                        total_token_usage: usage.clone(),
+                        last_token_usage: usage,
+                        model_context_window: None,
+                    }),
+                    rate_limits: None,
+                })),
+            ],
+            rollout_path: PathBuf::from("/sessions/rollout.jsonl"),
+        }
+    }
+
+    #[test]
+    fn report_attaches_outputs_to_their_calls() {
+        let report = SessionReport::from_history(&recorded_history());
+        assert_eq!(
+            report.entries,
+            vec![
+                ReportEntry::User("fix <the> build".to_string()),
+                ReportEntry::Command {
+                    command: "cargo build".to_string(),
+                    output: Some("error[E0308]".to_string()),
+                },
+                ReportEntry::Patch {
+                    patch: "*** Begin Patch\n*** Update File: a.rs\n-old\n+new\n*** End Patch"
+                        .to_string(),
+                    output: None,
+                },
+                ReportEntry::Agent("fixed".to_string()),
+            ]
+        );
+        assert_eq!(report.cwd, Some(PathBuf::from("/repo")));
+        assert_eq!(
+            report.token_usage.map(|usage| usage.total_tokens),
+            Some(1_100_000)
+        );
+    }
+
+    #[test]
+    fn markdown_collapses_command_output_and_prices_tokens() {
+        let markdown =
+            SessionReport::from_history(&recorded_history()).to_markdown(Some(TokenPrices {
+                input: 1.0,
+                cached_input: 0.5,
+                output: 10.0,
+            }));
+        assert!(markdown.contains(
+            "<details>\n<summary><code>cargo build</code></summary>\n\n```text\nerror[E0308]\n```\n\n</details>"
+        ));
+        assert!(markdown.contains("```diff\n*** Begin Patch"));
+        assert!(markdown.contains("| Total | 1100000 |"));
+        // 0.6M uncached input at $1 + 0.4M cached at $0.50 + 0.1M output at $10.
+        assert!(markdown.contains("Estimated cost: **$1.80**"));
+    }
+
+    #[test]
+    fn html_escapes_messages() {
+        let html = SessionReport::from_history(&recorded_history()).to_html(None);
+        assert!(html.contains("fix &lt;the&gt; build"));
+        assert!(html.contains("<span class=\"add\">+new</span>"));
+        assert!(!html.contains("Estimated cost"));
+    }
+
+    #[test]
+    fn bookmarks_link_to_their_turns() {
+        let report = SessionReport::from_history(&recorded_history());
+
+        let markdown = report.to_markdown(None);
+        assert!(
+            markdown
+                .contains("## Bookmarks\n\n- [Turn 1](#turn-1): type <mismatch> fix (\"fixed\")")
+        );
+        assert!(markdown.contains("> fix <the> build\n\n**Bookmark:** type <mismatch> fix"));
+
+        let html = report.to_html(None);
+        assert!(html.contains(
+            "<li><a href=\"#turn-1\">Turn 1</a>: type &lt;mismatch&gt; fix (&quot;fixed&quot;)</li>"
+        ));
+        assert!(html.contains("<h2 id=\"turn-1\">Turn 1</h2>"));
+    }
+
+    #[test]
+    fn markdown_fence_outgrows_backticks_in_body() {
+        assert_eq!(
+            markdown_fence("text", "a ```b```"),
+            "````text\na ```b```\n````"
+        );
+    }
+}
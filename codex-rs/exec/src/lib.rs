@@ -4,11 +4,14 @@
 // For both modes, any other output must be written to stderr.
 #![deny(clippy::print_stdout)]
 
+mod approvals_bridge;
 mod cli;
 mod event_processor;
 mod event_processor_with_human_output;
 pub mod event_processor_with_jsonl_output;
 pub mod exec_events;
+mod export;
+mod replay;
 
 pub use cli::Cli;
 pub use cli::Command;
@@ -25,8 +28,10 @@ use codex_core::config::Config;
 use codex_core::config::ConfigBuilder;
 use codex_core::config::ConfigOverrides;
 use codex_core::config::find_codex_home;
+use codex_core::config::is_loopback_url;
 use codex_core::config::load_config_as_toml_with_cli_overrides;
 use codex_core::config::resolve_oss_provider;
+use codex_core::config_loader::CloudRequirementsLoader;
 use codex_core::config_loader::ConfigLoadError;
 use codex_core::config_loader::format_config_error_with_source;
 use codex_core::format_exec_policy_error_with_source;
@@ -36,9 +41,11 @@ use codex_core::protocol::AskForApproval;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
+use codex_core::protocol::ReviewDecision;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SessionSource;
+use codex_core::run_limits::RunLimits;
 use codex_protocol::approvals::ElicitationAction;
 use codex_protocol::config_types::SandboxMode;
 use codex_protocol::user_input::UserInput;
@@ -47,8 +54,14 @@ use codex_utils_oss::ensure_oss_provider_ready;
 use codex_utils_oss::get_default_model_for_oss_provider;
 use event_processor_with_human_output::EventProcessorWithHumanOutput;
 use event_processor_with_jsonl_output::EventProcessorWithJsonOutput;
+pub use export::ExportFormat;
+pub use export::SessionExportArgs;
+pub use export::run_session_export;
+pub use replay::ReplayArgs;
+pub use replay::run_replay;
 use serde_json::Value;
 use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::io::IsTerminal;
 use std::io::Read;
 use std::path::PathBuf;
@@ -63,12 +76,16 @@ use tracing_subscriber::EnvFilter;
 use tracing_subscriber::prelude::*;
 use uuid::Uuid;
 
+use crate::approvals_bridge::ApprovalPrompt;
+use crate::approvals_bridge::ApprovalsBridge;
 use crate::cli::Command as ExecCommand;
+use crate::cli::PatchModeCliArg;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
+use codex_core::default_client::set_default_client_http_config;
+use codex_core::default_client::set_default_client_offline;
 use codex_core::default_client::set_default_client_residency_requirement;
 use codex_core::default_client::set_default_originator;
-use codex_core::find_thread_path_by_id_str;
 use codex_core::find_thread_path_by_name_str;
 
 enum InitialOperation {
@@ -100,18 +117,32 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         oss,
         oss_provider,
         config_profile,
+        orchestration,
         full_auto,
         dangerously_bypass_approvals_and_sandbox,
+        read_only,
+        approvals_bridge,
         cwd,
         skip_git_repo_check,
         add_dir,
+        scope,
         ephemeral,
+        commit,
+        create_pr,
         color,
         last_message_file,
         json: json_mode,
         sandbox_mode: sandbox_mode_cli_arg,
         prompt,
         output_schema: output_schema_path,
+        response_cache,
+        response_cache_mode,
+        offline,
+        wire_log,
+        max_runtime,
+        max_turns,
+        patch_mode,
+        patch_dir,
         config_overrides,
     } = cli;
 
@@ -197,18 +228,24 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         }
     };
 
-    let cloud_auth_manager = AuthManager::shared(
-        codex_home.clone(),
-        false,
-        config_toml.cli_auth_credentials_store.unwrap_or_default(),
-    );
-    let chatgpt_base_url = config_toml
-        .chatgpt_base_url
-        .clone()
-        .unwrap_or_else(|| "https://chatgpt.com/backend-api/".to_string());
+    let offline = offline || config_toml.offline.unwrap_or(false);
+    set_default_client_offline(offline);
+    set_default_client_http_config(config_toml.http_client.clone().unwrap_or_default());
     // TODO(gt): Make cloud requirements failures blocking once we can fail-closed.
-    let cloud_requirements =
-        cloud_requirements_loader(cloud_auth_manager, chatgpt_base_url, codex_home.clone());
+    let cloud_requirements = if offline {
+        CloudRequirementsLoader::default()
+    } else {
+        let cloud_auth_manager = AuthManager::shared(
+            config_toml.auth_home(&codex_home, config_profile.clone()),
+            false,
+            config_toml.cli_auth_credentials_store.unwrap_or_default(),
+        );
+        let chatgpt_base_url = config_toml
+            .chatgpt_base_url
+            .clone()
+            .unwrap_or_else(|| "https://chatgpt.com/backend-api/".to_string());
+        cloud_requirements_loader(cloud_auth_manager, chatgpt_base_url, codex_home.clone())
+    };
 
     let model_provider = if oss {
         let resolved = resolve_oss_provider(
@@ -240,13 +277,29 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         None // No model specified, will use the default.
     };
 
+    let response_cache_dir = response_cache.map(std::path::absolute).transpose()?;
+    let wire_log = wire_log.map(std::path::absolute).transpose()?;
+    let patch_proposal_dir = match patch_mode {
+        PatchModeCliArg::Apply => None,
+        PatchModeCliArg::ProposeOnly => Some(
+            config_cwd
+                .join(patch_dir.unwrap_or_else(|| PathBuf::from(".codex/patches")))?
+                .into_path_buf(),
+        ),
+    };
+
     // Load configuration and determine approval policy
     let overrides = ConfigOverrides {
         model,
         review_model: None,
         config_profile,
         // Default to never ask for approvals in headless mode. Feature flags can override.
-        approval_policy: Some(AskForApproval::Never),
+        // With --approvals-bridge the requests go to the configured chat channel instead.
+        approval_policy: Some(if approvals_bridge {
+            AskForApproval::OnRequest
+        } else {
+            AskForApproval::Never
+        }),
         sandbox_mode,
         cwd: resolved_cwd,
         model_provider: model_provider.clone(),
@@ -261,6 +314,18 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         tools_web_search_request: None,
         ephemeral: ephemeral.then_some(true),
         additional_writable_roots: add_dir,
+        scope,
+        orchestration,
+        response_cache_dir,
+        response_cache_mode: response_cache_mode.map(Into::into),
+        offline: offline.then_some(true),
+        wire_log,
+        run_limits: Some(RunLimits {
+            max_turns,
+            max_runtime,
+        }),
+        patch_proposal_dir,
+        read_only: read_only.then_some(true),
     };
 
     let config = ConfigBuilder::default()
@@ -314,7 +379,10 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         .try_init();
 
     let mut event_processor: Box<dyn EventProcessor> = match json_mode {
-        true => Box::new(EventProcessorWithJsonOutput::new(last_message_file.clone())),
+        true => Box::new(
+            EventProcessorWithJsonOutput::new(last_message_file.clone())
+                .with_patch_proposals(patch_mode == PatchModeCliArg::ProposeOnly),
+        ),
         _ => Box::new(EventProcessorWithHumanOutput::create_with_ansi(
             stdout_with_ansi,
             &config,
@@ -362,8 +430,33 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         std::process::exit(1);
     }
 
+    let approvals_bridge = if approvals_bridge {
+        let Some(bridge_config) = config.approvals_bridge.clone() else {
+            eprintln!("--approvals-bridge requires an [approvals_bridge] section in config.toml.");
+            std::process::exit(1);
+        };
+        if offline
+            && !reqwest::Url::parse(&bridge_config.webhook_url)
+                .is_ok_and(|url| is_loopback_url(&url))
+        {
+            eprintln!(
+                "--approvals-bridge cannot be used with --offline unless approvals_bridge.webhook_url is on this machine."
+            );
+            std::process::exit(1);
+        }
+        let bridge = ApprovalsBridge::start(bridge_config)
+            .map_err(|err| anyhow::anyhow!("failed to start the approvals bridge: {err}"))?;
+        eprintln!(
+            "Approval requests will be posted to chat; decision links point at {}",
+            bridge.public_url()
+        );
+        Some(Arc::new(bridge))
+    } else {
+        None
+    };
+
     let auth_manager = AuthManager::shared(
-        config.codex_home.clone(),
+        config.auth_home.clone(),
         true,
         config.cli_auth_credentials_store_mode,
     );
@@ -541,6 +634,18 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     // exit with a non-zero status for automation-friendly signaling.
     let mut error_seen = false;
     let mut shutdown_requested = false;
+    // Follow-up operations requested on the command line run in order after the
+    // agent's turn completes; passing the flag is the user's approval for them,
+    // so their approval requests are answered here.
+    let mut follow_up_ops: VecDeque<Op> = VecDeque::new();
+    if commit {
+        follow_up_ops.push_back(Op::CommitChanges { message: None });
+    }
+    if create_pr {
+        follow_up_ops.push_back(Op::CreatePullRequest { title: None });
+    }
+    // Submission id of the follow-up operation that is running.
+    let mut follow_up_id: Option<String> = None;
     while let Some(envelope) = rx.recv().await {
         let ThreadEventEnvelope {
             thread_id,
@@ -565,6 +670,51 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
                 })
                 .await?;
         }
+        if let EventMsg::ExecApprovalRequest(ev) = &event.msg
+            && follow_up_id.as_deref() == Some(ev.call_id.as_str())
+        {
+            thread
+                .submit(Op::ExecApproval {
+                    id: ev.call_id.clone(),
+                    turn_id: Some(ev.turn_id.clone()),
+                    decision: ReviewDecision::Approved,
+                })
+                .await?;
+        } else if let Some(bridge) = &approvals_bridge {
+            match &event.msg {
+                EventMsg::ExecApprovalRequest(ev) => {
+                    let bridge = Arc::clone(bridge);
+                    let thread = Arc::clone(&thread);
+                    let prompt = ApprovalPrompt::exec(ev);
+                    let id = ev.call_id.clone();
+                    let turn_id = Some(ev.turn_id.clone());
+                    tokio::spawn(async move {
+                        let decision = bridge.request_decision(prompt).await;
+                        let op = Op::ExecApproval {
+                            id,
+                            turn_id,
+                            decision,
+                        };
+                        if let Err(err) = thread.submit(op).await {
+                            error!("failed to submit approval decision: {err}");
+                        }
+                    });
+                }
+                EventMsg::ApplyPatchApprovalRequest(ev) => {
+                    let bridge = Arc::clone(bridge);
+                    let thread = Arc::clone(&thread);
+                    let prompt = ApprovalPrompt::patch(ev);
+                    let id = ev.call_id.clone();
+                    tokio::spawn(async move {
+                        let decision = bridge.request_decision(prompt).await;
+                        if let Err(err) = thread.submit(Op::PatchApproval { id, decision }).await {
+                            error!("failed to submit approval decision: {err}");
+                        }
+                    });
+                }
+                _ => {}
+            }
+        }
         if let EventMsg::McpStartupUpdate(update) = &event.msg
             && required_mcp_servers.contains(&update.server)
             && let codex_core::protocol::McpStartupStatus::Failed { error } = &update.status
@@ -582,12 +732,30 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         if thread_id != primary_thread_id && matches!(&event.msg, EventMsg::TurnComplete(_)) {
             continue;
         }
+        if follow_up_id.is_some() && matches!(&event.msg, EventMsg::TurnComplete(_)) {
+            // A follow-up task's completion must not replace the agent's final message.
+            if !error_seen && let Some(op) = follow_up_ops.pop_front() {
+                follow_up_id = Some(thread.submit(op).await?);
+            } else {
+                follow_up_id = None;
+                if !shutdown_requested {
+                    thread.submit(Op::Shutdown).await?;
+                    shutdown_requested = true;
+                }
+            }
+            continue;
+        }
         let shutdown = event_processor.process_event(event);
         if thread_id != primary_thread_id && matches!(shutdown, CodexStatus::InitiateShutdown) {
             continue;
         }
         match shutdown {
             CodexStatus::Running => continue,
+            CodexStatus::InitiateShutdown if !error_seen && !follow_up_ops.is_empty() => {
+                if let Some(op) = follow_up_ops.pop_front() {
+                    follow_up_id = Some(thread.submit(op).await?);
+                }
+            }
             CodexStatus::InitiateShutdown => {
                 if !shutdown_requested {
                     thread.submit(Op::Shutdown).await?;
@@ -673,7 +841,7 @@ async fn resolve_resume_path(
         }
     } else if let Some(id_str) = args.session_id.as_deref() {
         if Uuid::parse_str(id_str).is_ok() {
-            let path = find_thread_path_by_id_str(&config.codex_home, id_str).await?;
+            let path = codex_core::RolloutRecorder::find_thread_path(&config, id_str).await?;
             Ok(path)
         } else {
             let path = find_thread_path_by_name_str(&config.codex_home, id_str).await?;
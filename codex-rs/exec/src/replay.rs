@@ -0,0 +1,270 @@
+//! `codex replay`: re-render a recorded session from its rollout file.
+//!
+//! The events persisted in the rollout are fed, in order, through the same
+//! event processors `codex exec` uses, so the transcript or JSONL stream
+//! matches what a live run printed for those events. Nothing is sent to the
+//! model and no command runs. Events the rollout did not persist (for example
+//! streaming deltas) are not available to replay.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use codex_core::RolloutRecorder;
+use codex_core::config::Config;
+use codex_core::protocol::AskForApproval;
+use codex_core::protocol::Event;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::InitialHistory;
+use codex_core::protocol::ResumedHistory;
+use codex_core::protocol::RolloutItem;
+use codex_core::protocol::SandboxPolicy;
+use codex_core::protocol::SessionConfiguredEvent;
+use codex_utils_cli::CliConfigOverrides;
+use supports_color::Stream;
+
+use crate::cli::Color;
+use crate::event_processor::EventProcessor;
+use crate::event_processor_with_human_output::EventProcessorWithHumanOutput;
+use crate::event_processor_with_jsonl_output::EventProcessorWithJsonOutput;
+
+#[derive(Debug, Parser)]
+pub struct ReplayArgs {
+    /// Rollout file to replay, e.g. `~/.codex/sessions/YYYY/MM/DD/rollout-*.jsonl`.
+    #[arg(value_name = "ROLLOUT")]
+    pub rollout: PathBuf,
+
+    /// Print the events as `codex exec --json` would, instead of the
+    /// human-readable transcript.
+    #[arg(long = "json", default_value_t = false)]
+    pub json: bool,
+
+    /// Print the recorded protocol events verbatim, one JSON object per line.
+    #[arg(long = "raw", default_value_t = false, conflicts_with = "json")]
+    pub raw: bool,
+
+    /// Specifies color settings for the human-readable transcript.
+    #[arg(long = "color", value_enum, default_value_t = Color::Auto)]
+    pub color: Color,
+
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+}
+
+#[allow(clippy::print_stdout)]
+pub async fn run_replay(args: ReplayArgs) -> anyhow::Result<()> {
+    let ReplayArgs {
+        rollout,
+        json,
+        raw,
+        color,
+        config_overrides,
+    } = args;
+
+    let history = match RolloutRecorder::get_rollout_history(&rollout).await? {
+        InitialHistory::Resumed(history) => history,
+        InitialHistory::New | InitialHistory::Forked(_) => {
+            anyhow::bail!("{} does not contain a recorded session", rollout.display());
+        }
+    };
+    let events = replay_events(&history);
+
+    if raw {
+        for event in events {
+            println!("{}", serde_json::to_string(&event)?);
+        }
+        return Ok(());
+    }
+
+    let mut event_processor: Box<dyn EventProcessor> = if json {
+        Box::new(EventProcessorWithJsonOutput::new(None))
+    } else {
+        let cli_kv_overrides = config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(cli_kv_overrides).await?;
+        let with_ansi = match color {
+            Color::Always => true,
+            Color::Never => false,
+            Color::Auto => supports_color::on_cached(Stream::Stderr).is_some(),
+        };
+        Box::new(
+            EventProcessorWithHumanOutput::create_with_ansi(with_ansi, &config, None)
+                .with_user_messages(),
+        )
+    };
+    for event in events {
+        // A live run stops after its turn completes; a replay keeps going
+        // through every recorded turn.
+        let _ = event_processor.process_event(event);
+    }
+    event_processor.print_final_output();
+    Ok(())
+}
+
+/// The events to feed a processor for `history`: a `SessionConfigured` event
+/// rebuilt from the session metadata and first turn context, followed by every
+/// recorded event. Event ids are the id of the turn the event belongs to.
+pub(crate) fn replay_events(history: &ResumedHistory) -> Vec<Event> {
+    let mut events = vec![Event {
+        id: String::new(),
+        msg: EventMsg::SessionConfigured(session_configured(history)),
+    }];
+    let mut turn_id = String::new();
+    for item in &history.history {
+        let RolloutItem::EventMsg(msg) = item else {
+            continue;
+        };
+        if let EventMsg::TurnStarted(started) = msg {
+            turn_id = started.turn_id.clone();
+        }
+        events.push(Event {
+            id: turn_id.clone(),
+            msg: msg.clone(),
+        });
+    }
+    events
+}
+
+fn session_configured(history: &ResumedHistory) -> SessionConfiguredEvent {
+    let meta = history.history.iter().find_map(|item| match item {
+        RolloutItem::SessionMeta(meta_line) => Some(&meta_line.meta),
+        _ => None,
+    });
+    let turn_context = history.history.iter().find_map(|item| match item {
+        RolloutItem::TurnContext(turn_context) => Some(turn_context),
+        _ => None,
+    });
+    SessionConfiguredEvent {
+        session_id: history.conversation_id,
+        forked_from_id: meta.and_then(|meta| meta.forked_from_id),
+        thread_name: None,
+        model: turn_context
+            .map(|turn_context| turn_context.model.clone())
+            .unwrap_or_default(),
+        model_provider_id: meta
+            .and_then(|meta| meta.model_provider.clone())
+            .unwrap_or_default(),
+        approval_policy: turn_context
+            .map(|turn_context| turn_context.approval_policy)
+            .unwrap_or(AskForApproval::Never),
+        sandbox_policy: turn_context
+            .map(|turn_context| turn_context.sandbox_policy.clone())
+            .unwrap_or_else(SandboxPolicy::new_read_only_policy),
+        cwd: turn_context
+            .map(|turn_context| turn_context.cwd.clone())
+            .or_else(|| meta.map(|meta| meta.cwd.clone()))
+            .unwrap_or_default(),
+        reasoning_effort: turn_context.and_then(|turn_context| turn_context.effort),
+        history_log_id: 0,
+        history_entry_count: 0,
+        initial_messages: None,
+        network_proxy: None,
+        rollout_path: Some(history.rollout_path.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exec_events::ThreadEvent;
+    use crate::exec_events::ThreadItemDetails;
+    use codex_core::protocol::AgentMessageEvent;
+    use codex_core::protocol::SessionMeta;
+    use codex_core::protocol::SessionMetaLine;
+    use codex_core::protocol::TurnCompleteEvent;
+    use codex_core::protocol::TurnStartedEvent;
+    use codex_core::protocol::UserMessageEvent;
+    use codex_protocol::ThreadId;
+    use codex_protocol::models::ContentItem;
+    use codex_protocol::models::ResponseItem;
+    use pretty_assertions::assert_eq;
+
+    fn recorded_history() -> ResumedHistory {
+        let conversation_id = ThreadId::new();
+        ResumedHistory {
+            conversation_id,
+            history: vec![
+                RolloutItem::SessionMeta(SessionMetaLine {
+                    meta: SessionMeta {
+                        id: conversation_id,
+                        cwd: PathBuf::from("/repo"),
+                        model_provider: Some("openai".to_string()),
+                        ..Default::default()
+                    },
+                    git: None,
+                }),
+                RolloutItem::EventMsg(EventMsg::TurnStarted(TurnStartedEvent {
+                    turn_id: "turn-1".to_string(),
+                    model_context_window: None,
+                    collaboration_mode_kind: Default::default(),
+                })),
+                RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+                    message: "fix the build".to_string(),
+                    images: None,
+                    local_images: Vec::new(),
+                    text_elements: Vec::new(),
+                })),
+                RolloutItem::ResponseItem(ResponseItem::Message {
+                    id: None,
+                    role: "assistant".to_string(),
+                    content: vec![ContentItem::OutputText {
+                        text: "done".to_string(),
+                    }],
+                    end_turn: None,
+                    phase: None,
+                }),
+                RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+                    message: "done".to_string(),
+                })),
+                RolloutItem::EventMsg(EventMsg::TurnComplete(TurnCompleteEvent {
+                    turn_id: "turn-1".to_string(),
+                    last_agent_message: Some("done".to_string()),
+                })),
+            ],
+            rollout_path: PathBuf::from("/sessions/rollout.jsonl"),
+        }
+    }
+
+    #[test]
+    fn replay_events_start_with_session_configured_and_keep_turn_ids() {
+        let history = recorded_history();
+        let events = replay_events(&history);
+
+        let EventMsg::SessionConfigured(configured) = &events[0].msg else {
+            panic!("expected SessionConfigured, got {:?}", events[0].msg);
+        };
+        assert_eq!(configured.session_id, history.conversation_id);
+        assert_eq!(configured.model_provider_id, "openai");
+        assert_eq!(configured.cwd, PathBuf::from("/repo"));
+        assert_eq!(
+            configured.rollout_path,
+            Some(PathBuf::from("/sessions/rollout.jsonl"))
+        );
+
+        let ids: Vec<&str> = events[1..].iter().map(|event| event.id.as_str()).collect();
+        assert_eq!(ids, vec!["turn-1", "turn-1", "turn-1", "turn-1"]);
+    }
+
+    #[test]
+    fn replayed_events_render_as_exec_jsonl() {
+        let mut processor = EventProcessorWithJsonOutput::new(None);
+        let thread_events: Vec<ThreadEvent> = replay_events(&recorded_history())
+            .iter()
+            .flat_map(|event| processor.collect_thread_events(event))
+            .collect();
+
+        assert!(matches!(
+            thread_events.first(),
+            Some(ThreadEvent::ThreadStarted(_))
+        ));
+        assert!(thread_events.iter().any(|event| matches!(
+            event,
+            ThreadEvent::ItemCompleted(completed)
+                if matches!(&completed.item.details, ThreadItemDetails::AgentMessage(message) if message.text == "done")
+        )));
+        assert!(matches!(
+            thread_events.last(),
+            Some(ThreadEvent::TurnCompleted(_))
+        ));
+    }
+}
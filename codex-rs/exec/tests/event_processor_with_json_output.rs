@@ -13,6 +13,7 @@ use codex_core::protocol::ExecCommandEndEvent;
 use codex_core::protocol::ExecCommandSource;
 use codex_core::protocol::ExecCommandStatus as CoreExecCommandStatus;
 use codex_core::protocol::FileChange;
+use codex_core::protocol::FileContentHashes;
 use codex_core::protocol::McpInvocation;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
@@ -34,6 +35,8 @@ use codex_exec::exec_events::CollabToolCallStatus;
 use codex_exec::exec_events::CommandExecutionItem;
 use codex_exec::exec_events::CommandExecutionStatus;
 use codex_exec::exec_events::ErrorItem;
+use codex_exec::exec_events::FileChangeItem;
+use codex_exec::exec_events::FileUpdateChange;
 use codex_exec::exec_events::ItemCompletedEvent;
 use codex_exec::exec_events::ItemStartedEvent;
 use codex_exec::exec_events::ItemUpdatedEvent;
@@ -240,10 +243,12 @@ fn plan_update_emits_todo_list_started_updated_and_completed() {
                 PlanItemArg {
                     step: "step one".to_string(),
                     status: StepStatus::Pending,
+                    id: None,
                 },
                 PlanItemArg {
                     step: "step two".to_string(),
                     status: StepStatus::InProgress,
+                    id: None,
                 },
             ],
         }),
@@ -279,10 +284,12 @@ fn plan_update_emits_todo_list_started_updated_and_completed() {
                 PlanItemArg {
                     step: "step one".to_string(),
                     status: StepStatus::Completed,
+                    id: None,
                 },
                 PlanItemArg {
                     step: "step two".to_string(),
                     status: StepStatus::InProgress,
+                    id: None,
                 },
             ],
         }),
@@ -674,6 +681,7 @@ fn plan_update_after_complete_starts_new_todo_list_with_new_id() {
             plan: vec![PlanItemArg {
                 step: "only".to_string(),
                 status: StepStatus::Pending,
+                id: None,
             }],
         }),
     );
@@ -695,6 +703,7 @@ fn plan_update_after_complete_starts_new_todo_list_with_new_id() {
             plan: vec![PlanItemArg {
                 step: "again".to_string(),
                 status: StepStatus::Pending,
+                id: None,
             }],
         }),
     );
@@ -904,6 +913,7 @@ fn exec_command_end_success_produces_completed_command_item() {
             duration: Duration::from_millis(5),
             formatted_output: String::new(),
             status: CoreExecCommandStatus::Completed,
+            test_failures: Vec::new(),
         }),
     );
     let out_ok = ep.collect_thread_events(&end_ok);
@@ -992,6 +1002,7 @@ fn command_execution_output_delta_updates_item_progress() {
             duration: Duration::from_millis(3),
             formatted_output: String::new(),
             status: CoreExecCommandStatus::Completed,
+            test_failures: Vec::new(),
         }),
     );
     let out_end = ep.collect_thread_events(&end);
@@ -1066,6 +1077,7 @@ fn exec_command_end_failure_produces_failed_command_item() {
             duration: Duration::from_millis(2),
             formatted_output: String::new(),
             status: CoreExecCommandStatus::Failed,
+            test_failures: Vec::new(),
         }),
     );
     let out_fail = ep.collect_thread_events(&end_fail);
@@ -1108,6 +1120,7 @@ fn exec_command_end_without_begin_is_ignored() {
             duration: Duration::from_millis(1),
             formatted_output: String::new(),
             status: CoreExecCommandStatus::Completed,
+            test_failures: Vec::new(),
         }),
     );
     let out = ep.collect_thread_events(&end_only);
@@ -1140,7 +1153,16 @@ fn patch_apply_success_produces_item_completed_patchapply() {
         },
     );
 
-    // Begin -> no output
+    let mut content_hashes = std::collections::HashMap::new();
+    content_hashes.insert(
+        PathBuf::from("a/added.txt"),
+        FileContentHashes {
+            before: None,
+            after: Some("after-hash".to_string()),
+        },
+    );
+
+    // Begin -> item.started (item_0) previewing the patch
     let begin = event(
         "p1",
         EventMsg::PatchApplyBegin(PatchApplyBeginEvent {
@@ -1148,10 +1170,47 @@ fn patch_apply_success_produces_item_completed_patchapply() {
             turn_id: "turn-1".to_string(),
             auto_approved: true,
             changes: changes.clone(),
+            content_hashes,
         }),
     );
     let out_begin = ep.collect_thread_events(&begin);
-    assert!(out_begin.is_empty());
+    assert_eq!(
+        out_begin,
+        vec![ThreadEvent::ItemStarted(ItemStartedEvent {
+            item: ThreadItem {
+                id: "item_0".to_string(),
+                details: ThreadItemDetails::FileChange(FileChangeItem {
+                    changes: vec![
+                        FileUpdateChange {
+                            path: "a/added.txt".to_string(),
+                            kind: PatchChangeKind::Add,
+                            move_path: None,
+                            hunks: vec!["@@ -0,0 +1,1 @@\n++hello\n".to_string()],
+                            before_sha256: None,
+                            after_sha256: Some("after-hash".to_string()),
+                        },
+                        FileUpdateChange {
+                            path: "b/deleted.txt".to_string(),
+                            kind: PatchChangeKind::Delete,
+                            move_path: None,
+                            hunks: vec!["@@ -1,1 +0,0 @@\n--goodbye\n".to_string()],
+                            before_sha256: None,
+                            after_sha256: None,
+                        },
+                        FileUpdateChange {
+                            path: "c/modified.txt".to_string(),
+                            kind: PatchChangeKind::Update,
+                            move_path: Some("c/renamed.txt".to_string()),
+                            hunks: vec!["@@\n-old\n+new\n".to_string()],
+                            before_sha256: None,
+                            after_sha256: None,
+                        },
+                    ],
+                    status: PatchApplyStatus::InProgress,
+                }),
+            },
+        })]
+    );
 
     // End (success) -> item.completed (item_0)
     let end = event(
@@ -1200,6 +1259,52 @@ fn patch_apply_success_produces_item_completed_patchapply() {
     }
 }
 
+#[test]
+fn proposed_patches_complete_as_proposed() {
+    let mut ep = EventProcessorWithJsonOutput::new(None).with_patch_proposals(true);
+
+    let mut changes = std::collections::HashMap::new();
+    changes.insert(
+        PathBuf::from("file.txt"),
+        FileChange::Add {
+            content: "hello\n".to_string(),
+        },
+    );
+    let begin = event(
+        "p1",
+        EventMsg::PatchApplyBegin(PatchApplyBeginEvent {
+            call_id: "call-3".to_string(),
+            turn_id: "turn-3".to_string(),
+            auto_approved: true,
+            changes: changes.clone(),
+            content_hashes: std::collections::HashMap::new(),
+        }),
+    );
+    assert_eq!(ep.collect_thread_events(&begin).len(), 1);
+
+    let end = event(
+        "p2",
+        EventMsg::PatchApplyEnd(PatchApplyEndEvent {
+            call_id: "call-3".to_string(),
+            turn_id: "turn-3".to_string(),
+            stdout: "Saved the patch to .codex/patches/0001-call-3.patch".to_string(),
+            stderr: String::new(),
+            success: true,
+            changes,
+            status: CorePatchApplyStatus::Completed,
+        }),
+    );
+    match ep.collect_thread_events(&end).as_slice() {
+        [ThreadEvent::ItemCompleted(ItemCompletedEvent { item })] => match &item.details {
+            ThreadItemDetails::FileChange(file_change) => {
+                assert_eq!(file_change.status, PatchApplyStatus::Proposed);
+            }
+            other => panic!("unexpected details: {other:?}"),
+        },
+        other => panic!("unexpected events: {other:?}"),
+    }
+}
+
 #[test]
 fn patch_apply_failure_produces_item_completed_patchapply_failed() {
     let mut ep = EventProcessorWithJsonOutput::new(None);
@@ -1213,7 +1318,7 @@ fn patch_apply_failure_produces_item_completed_patchapply_failed() {
         },
     );
 
-    // Begin -> no output
+    // Begin -> item.started (item_0)
     let begin = event(
         "p1",
         EventMsg::PatchApplyBegin(PatchApplyBeginEvent {
@@ -1221,9 +1326,10 @@ fn patch_apply_failure_produces_item_completed_patchapply_failed() {
             turn_id: "turn-2".to_string(),
             auto_approved: false,
             changes: changes.clone(),
+            content_hashes: std::collections::HashMap::new(),
         }),
     );
-    assert!(ep.collect_thread_events(&begin).is_empty());
+    assert_eq!(ep.collect_thread_events(&begin).len(), 1);
 
     // End (failure) -> item.completed (item_0) with Failed status
     let end = event(
@@ -0,0 +1,281 @@
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::decision::Decision;
+use crate::rule::RuleMatch;
+
+/// What `command_rule` conditions are evaluated against. Codex fills this in
+/// before it decides whether a command needs approval.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct CommandFacts {
+    /// The command as it was requested.
+    pub argv: Vec<String>,
+    /// Directory the command runs in, when known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<PathBuf>,
+    /// 0 for commands known to be safe, 2 for commands that might be
+    /// dangerous, 1 for everything else.
+    pub risk: u8,
+    /// Whether the command runs with network access; `None` when unknown.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<bool>,
+    /// Programs the command starts: each command of a `bash -lc` script, or
+    /// the command itself.
+    pub subcommands: Vec<Vec<String>>,
+}
+
+/// A rule over [`CommandFacts`] rather than an argv prefix. Every condition
+/// that is set must hold for the rule to match. A fact that is unknown holds
+/// for `forbidden` and `prompt` rules, so they fail closed, and fails for
+/// `allow` rules.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommandRule {
+    pub name: Option<String>,
+    /// One of the programs the command starts must be one of these, compared
+    /// with both the program as written and its file name.
+    pub programs: Vec<String>,
+    /// The arguments of that same program must include all of these.
+    pub argv_contains: Vec<String>,
+    /// The working directory must be one of these or below one of them,
+    /// after `.` and `..` are resolved in both.
+    pub cwd_under: Vec<PathBuf>,
+    pub min_risk: Option<u8>,
+    pub network: Option<bool>,
+    pub decision: Decision,
+    pub justification: Option<String>,
+}
+
+impl CommandRule {
+    pub fn matches(&self, facts: &CommandFacts) -> Option<RuleMatch> {
+        if self.min_risk.is_some_and(|min_risk| facts.risk < min_risk) {
+            return None;
+        }
+        let unknown_matches = self.decision != Decision::Allow;
+        if let Some(network) = self.network
+            && !facts
+                .network
+                .map_or(unknown_matches, |known| known == network)
+        {
+            return None;
+        }
+        if !self.cwd_under.is_empty()
+            && !facts.cwd.as_deref().map_or(unknown_matches, |cwd| {
+                let cwd = normalize_lexically(cwd);
+                self.cwd_under
+                    .iter()
+                    .any(|root| cwd.starts_with(normalize_lexically(root)))
+            })
+        {
+            return None;
+        }
+        if !self.programs.is_empty() || !self.argv_contains.is_empty() {
+            let subcommands = if facts.subcommands.is_empty() {
+                std::slice::from_ref(&facts.argv)
+            } else {
+                facts.subcommands.as_slice()
+            };
+            if !subcommands
+                .iter()
+                .any(|command| self.matches_subcommand(command))
+            {
+                return None;
+            }
+        }
+
+        Some(RuleMatch::CommandRuleMatch {
+            rule: self.describe(),
+            decision: self.decision,
+            justification: self.justification.clone(),
+        })
+    }
+
+    fn matches_subcommand(&self, command: &[String]) -> bool {
+        let Some((program, args)) = command.split_first() else {
+            return false;
+        };
+        let file_name = Path::new(program)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(program);
+        let program_matches = self.programs.is_empty()
+            || self
+                .programs
+                .iter()
+                .any(|expected| expected == program || expected == file_name);
+        program_matches && self.argv_contains.iter().all(|token| args.contains(token))
+    }
+
+    /// The rule's `name`, or its conditions when it has none.
+    pub fn describe(&self) -> String {
+        if let Some(name) = &self.name {
+            return name.clone();
+        }
+        let mut conditions = Vec::new();
+        if !self.programs.is_empty() {
+            conditions.push(format!("program={}", self.programs.join("|")));
+        }
+        if !self.argv_contains.is_empty() {
+            conditions.push(format!("argv_contains={}", self.argv_contains.join(",")));
+        }
+        if !self.cwd_under.is_empty() {
+            let roots: Vec<String> = self
+                .cwd_under
+                .iter()
+                .map(|root| root.display().to_string())
+                .collect();
+            conditions.push(format!("cwd_under={}", roots.join("|")));
+        }
+        if let Some(min_risk) = self.min_risk {
+            conditions.push(format!("min_risk={min_risk}"));
+        }
+        if let Some(network) = self.network {
+            conditions.push(format!("network={network}"));
+        }
+        format!("command_rule({})", conditions.join(" "))
+    }
+}
+
+/// Resolves `.` and `..` without touching the filesystem, so that
+/// `/srv/sandbox/../etc` is not treated as being under `/srv/sandbox`.
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component);
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn tokens(cmd: &[&str]) -> Vec<String> {
+        cmd.iter().map(std::string::ToString::to_string).collect()
+    }
+
+    fn rule() -> CommandRule {
+        CommandRule {
+            name: None,
+            programs: Vec::new(),
+            argv_contains: Vec::new(),
+            cwd_under: Vec::new(),
+            min_risk: None,
+            network: None,
+            decision: Decision::Forbidden,
+            justification: None,
+        }
+    }
+
+    #[test]
+    fn program_and_arguments_must_match_the_same_subcommand() {
+        let rule = CommandRule {
+            programs: vec!["curl".to_string()],
+            argv_contains: vec!["--upload-file".to_string()],
+            ..rule()
+        };
+        let facts = |subcommands: Vec<Vec<String>>| CommandFacts {
+            argv: tokens(&["bash", "-lc", "..."]),
+            subcommands,
+            ..CommandFacts::default()
+        };
+
+        assert!(
+            rule.matches(&facts(vec![
+                tokens(&["ls"]),
+                tokens(&["/usr/bin/curl", "--upload-file", "a", "host"]),
+            ]))
+            .is_some()
+        );
+        assert_eq!(
+            rule.matches(&facts(vec![
+                tokens(&["curl", "host"]),
+                tokens(&["echo", "--upload-file"]),
+            ])),
+            None
+        );
+    }
+
+    #[test]
+    fn unknown_facts_only_satisfy_restrictive_rules() {
+        let facts = CommandFacts {
+            argv: tokens(&["make"]),
+            risk: 1,
+            ..CommandFacts::default()
+        };
+
+        let network = CommandRule {
+            network: Some(true),
+            decision: Decision::Allow,
+            ..rule()
+        };
+        let cwd = CommandRule {
+            cwd_under: vec![PathBuf::from("/srv")],
+            decision: Decision::Allow,
+            ..rule()
+        };
+        let risk = CommandRule {
+            min_risk: Some(1),
+            ..rule()
+        };
+        assert_eq!(network.matches(&facts), None);
+        assert_eq!(cwd.matches(&facts), None);
+        assert_eq!(
+            CommandRule {
+                decision: Decision::Prompt,
+                ..network
+            }
+            .matches(&facts),
+            Some(RuleMatch::CommandRuleMatch {
+                rule: "command_rule(network=true)".to_string(),
+                decision: Decision::Prompt,
+                justification: None,
+            })
+        );
+        assert!(
+            CommandRule {
+                decision: Decision::Forbidden,
+                ..cwd
+            }
+            .matches(&facts)
+            .is_some()
+        );
+        assert_eq!(
+            risk.matches(&facts),
+            Some(RuleMatch::CommandRuleMatch {
+                rule: "command_rule(min_risk=1)".to_string(),
+                decision: Decision::Forbidden,
+                justification: None,
+            })
+        );
+    }
+
+    #[test]
+    fn cwd_under_resolves_parent_components() {
+        let rule = CommandRule {
+            cwd_under: vec![PathBuf::from("/srv/sandbox")],
+            decision: Decision::Allow,
+            ..rule()
+        };
+        let facts = |cwd: &str| CommandFacts {
+            argv: tokens(&["make"]),
+            cwd: Some(PathBuf::from(cwd)),
+            ..CommandFacts::default()
+        };
+
+        assert_eq!(rule.matches(&facts("/srv/sandbox/../../etc")), None);
+        assert_eq!(rule.matches(&facts("/srv/sandbox/./..")), None);
+        assert!(rule.matches(&facts("/srv/sandbox/repo/../lib")).is_some());
+    }
+}
@@ -1,4 +1,5 @@
 pub mod amend;
+pub mod command_rule;
 pub mod decision;
 pub mod error;
 pub mod execpolicycheck;
@@ -8,6 +9,8 @@ pub mod rule;
 
 pub use amend::AmendError;
 pub use amend::blocking_append_allow_prefix_rule;
+pub use command_rule::CommandFacts;
+pub use command_rule::CommandRule;
 pub use decision::Decision;
 pub use error::Error;
 pub use error::ErrorLocation;
@@ -13,8 +13,10 @@ use starlark::values::list::UnpackList;
 use starlark::values::none::NoneType;
 use std::cell::RefCell;
 use std::cell::RefMut;
+use std::path::PathBuf;
 use std::sync::Arc;
 
+use crate::command_rule::CommandRule;
 use crate::decision::Decision;
 use crate::error::Error;
 use crate::error::Result;
@@ -71,12 +73,14 @@ impl PolicyParser {
 #[derive(Debug, ProvidesStaticType)]
 struct PolicyBuilder {
     rules_by_program: MultiMap<String, RuleRef>,
+    command_rules: Vec<CommandRule>,
 }
 
 impl PolicyBuilder {
     fn new() -> Self {
         Self {
             rules_by_program: MultiMap::new(),
+            command_rules: Vec::new(),
         }
     }
 
@@ -86,7 +90,7 @@ impl PolicyBuilder {
     }
 
     fn build(self) -> crate::policy::Policy {
-        crate::policy::Policy::new(self.rules_by_program)
+        crate::policy::Policy::new(self.rules_by_program).with_command_rules(self.command_rules)
     }
 }
 
@@ -266,4 +270,105 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
         rules.into_iter().for_each(|rule| builder.add_rule(rule));
         Ok(NoneType)
     }
+
+    fn command_rule<'v>(
+        decision: &'v str,
+        name: Option<&'v str>,
+        program: Option<Value<'v>>,
+        argv_contains: Option<Value<'v>>,
+        cwd_under: Option<Value<'v>>,
+        min_risk: Option<i32>,
+        network: Option<bool>,
+        justification: Option<&'v str>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> anyhow::Result<NoneType> {
+        let decision = Decision::parse(decision)?;
+        let name = match name {
+            Some(raw) if raw.trim().is_empty() => {
+                return Err(Error::InvalidRule("name cannot be empty".to_string()).into());
+            }
+            Some(raw) => Some(raw.to_string()),
+            None => None,
+        };
+        let justification = match justification {
+            Some(raw) if raw.trim().is_empty() => {
+                return Err(Error::InvalidRule("justification cannot be empty".to_string()).into());
+            }
+            Some(raw) => Some(raw.to_string()),
+            None => None,
+        };
+        let min_risk = min_risk
+            .map(|min_risk| {
+                u8::try_from(min_risk)
+                    .ok()
+                    .filter(|min_risk| *min_risk <= 2)
+                    .ok_or_else(|| {
+                        Error::InvalidRule(format!("min_risk must be 0, 1 or 2 (got {min_risk})"))
+                    })
+            })
+            .transpose()?;
+
+        let rule = CommandRule {
+            name,
+            programs: parse_strings("program", program)?,
+            argv_contains: parse_strings("argv_contains", argv_contains)?,
+            cwd_under: parse_strings("cwd_under", cwd_under)?
+                .into_iter()
+                .map(PathBuf::from)
+                .collect(),
+            min_risk,
+            network,
+            decision,
+            justification,
+        };
+        if rule.programs.is_empty()
+            && rule.argv_contains.is_empty()
+            && rule.cwd_under.is_empty()
+            && rule.min_risk.is_none()
+            && rule.network.is_none()
+        {
+            return Err(Error::InvalidRule(
+                "command_rule needs at least one of program, argv_contains, cwd_under, \
+                 min_risk or network"
+                    .to_string(),
+            )
+            .into());
+        }
+
+        policy_builder(eval).command_rules.push(rule);
+        Ok(NoneType)
+    }
+}
+
+/// Reads a `command_rule` argument that is a string or a list of strings.
+fn parse_strings<'v>(argument: &str, value: Option<Value<'v>>) -> Result<Vec<String>> {
+    let Some(value) = value else {
+        return Ok(Vec::new());
+    };
+    let strings: Vec<String> = if let Some(s) = value.unpack_str() {
+        vec![s.to_string()]
+    } else if let Some(list) = ListRef::from_value(value) {
+        list.content()
+            .iter()
+            .map(|item| {
+                item.unpack_str().map(str::to_string).ok_or_else(|| {
+                    Error::InvalidRule(format!(
+                        "{argument} must be a string or list of strings (got a list containing {})",
+                        item.get_type()
+                    ))
+                })
+            })
+            .collect::<Result<_>>()?
+    } else {
+        return Err(Error::InvalidRule(format!(
+            "{argument} must be a string or list of strings (got {})",
+            value.get_type()
+        )));
+    };
+    if strings.iter().any(|s| s.is_empty()) {
+        return Err(Error::InvalidRule(format!(
+            "{argument} cannot contain empty strings"
+        )));
+    }
+    Ok(strings)
 }
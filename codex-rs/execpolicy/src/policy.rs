@@ -1,3 +1,5 @@
+use crate::command_rule::CommandFacts;
+use crate::command_rule::CommandRule;
 use crate::decision::Decision;
 use crate::error::Error;
 use crate::error::Result;
@@ -16,11 +18,20 @@ type HeuristicsFallback<'a> = Option<&'a dyn Fn(&[String]) -> Decision>;
 #[derive(Clone, Debug)]
 pub struct Policy {
     rules_by_program: MultiMap<String, RuleRef>,
+    command_rules: Vec<CommandRule>,
 }
 
 impl Policy {
     pub fn new(rules_by_program: MultiMap<String, RuleRef>) -> Self {
-        Self { rules_by_program }
+        Self {
+            rules_by_program,
+            command_rules: Vec::new(),
+        }
+    }
+
+    pub fn with_command_rules(mut self, command_rules: Vec<CommandRule>) -> Self {
+        self.command_rules = command_rules;
+        self
     }
 
     pub fn empty() -> Self {
@@ -31,6 +42,10 @@ impl Policy {
         &self.rules_by_program
     }
 
+    pub fn command_rules(&self) -> &[CommandRule] {
+        &self.command_rules
+    }
+
     pub fn get_allowed_prefixes(&self) -> Vec<Vec<String>> {
         let mut prefixes = Vec::new();
 
@@ -106,6 +121,41 @@ impl Policy {
         Evaluation::from_matches(matched_rules)
     }
 
+    /// Like [`Policy::check_multiple`], but `command_rule`s are consulted
+    /// first. When any of them matches `facts`, the decision is the strictest
+    /// of those matches and of the prefix rules that match, and
+    /// `heuristics_fallback` is not used.
+    pub fn check_with_facts<Commands, F>(
+        &self,
+        commands: Commands,
+        facts: &CommandFacts,
+        heuristics_fallback: &F,
+    ) -> Evaluation
+    where
+        Commands: IntoIterator,
+        Commands::Item: AsRef<[String]>,
+        F: Fn(&[String]) -> Decision,
+    {
+        let command_matches: Vec<RuleMatch> = self
+            .command_rules
+            .iter()
+            .filter_map(|rule| rule.matches(facts))
+            .collect();
+        if command_matches.is_empty() {
+            return self.check_multiple(commands, heuristics_fallback);
+        }
+
+        let matched_rules = command_matches
+            .into_iter()
+            .chain(
+                commands
+                    .into_iter()
+                    .flat_map(|command| self.matches_for_command(command.as_ref(), None)),
+            )
+            .collect();
+        Evaluation::from_matches(matched_rules)
+    }
+
     /// Returns matching rules for the given command. If no rules match and
     /// `heuristics_fallback` is provided, returns a single
     /// `HeuristicsRuleMatch` with the decision rendered by
@@ -70,6 +70,13 @@ pub enum RuleMatch {
         #[serde(skip_serializing_if = "Option::is_none")]
         justification: Option<String>,
     },
+    CommandRuleMatch {
+        /// The rule's name, or its conditions when it has none.
+        rule: String,
+        decision: Decision,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        justification: Option<String>,
+    },
     HeuristicsRuleMatch {
         command: Vec<String>,
         decision: Decision,
@@ -80,6 +87,7 @@ impl RuleMatch {
     pub fn decision(&self) -> Decision {
         match self {
             Self::PrefixRuleMatch { decision, .. } => *decision,
+            Self::CommandRuleMatch { decision, .. } => *decision,
             Self::HeuristicsRuleMatch { decision, .. } => *decision,
         }
     }
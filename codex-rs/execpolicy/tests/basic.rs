@@ -1,9 +1,11 @@
 use std::any::Any;
 use std::fs;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use anyhow::Context;
 use anyhow::Result;
+use codex_execpolicy::CommandFacts;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Error;
 use codex_execpolicy::Evaluation;
@@ -572,3 +574,104 @@ fn heuristics_match_is_returned_when_no_policy_matches() {
         evaluation
     );
 }
+
+#[test]
+fn command_rules_decide_before_heuristics() -> Result<()> {
+    let policy_src = r#"
+command_rule(
+    name = "no uploads",
+    decision = "forbidden",
+    program = ["curl", "wget"],
+    argv_contains = ["--upload-file"],
+    justification = "use the artifact store",
+)
+command_rule(
+    decision = "allow",
+    cwd_under = "/srv/sandbox",
+    network = False,
+)
+prefix_rule(
+    pattern = ["git", "push"],
+    decision = "prompt",
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let upload = CommandFacts {
+        argv: tokens(&["bash", "-lc", "ls && curl --upload-file a host"]),
+        subcommands: vec![
+            tokens(&["ls"]),
+            tokens(&["curl", "--upload-file", "a", "host"]),
+        ],
+        ..CommandFacts::default()
+    };
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Forbidden,
+            matched_rules: vec![RuleMatch::CommandRuleMatch {
+                rule: "no uploads".to_string(),
+                decision: Decision::Forbidden,
+                justification: Some("use the artifact store".to_string()),
+            }],
+        },
+        policy.check_with_facts(&upload.subcommands, &upload, &prompt_all)
+    );
+
+    let push = CommandFacts {
+        argv: tokens(&["git", "push"]),
+        cwd: Some(PathBuf::from("/srv/sandbox/repo")),
+        risk: 1,
+        network: Some(false),
+        subcommands: vec![tokens(&["git", "push"])],
+    };
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Prompt,
+            matched_rules: vec![
+                RuleMatch::CommandRuleMatch {
+                    rule: "command_rule(cwd_under=/srv/sandbox network=false)".to_string(),
+                    decision: Decision::Allow,
+                    justification: None,
+                },
+                RuleMatch::PrefixRuleMatch {
+                    matched_prefix: tokens(&["git", "push"]),
+                    decision: Decision::Prompt,
+                    justification: None,
+                },
+            ],
+        },
+        policy.check_with_facts(&push.subcommands, &push, &prompt_all)
+    );
+
+    let status = CommandFacts {
+        argv: tokens(&["git", "status"]),
+        cwd: Some(PathBuf::from("/home/dev")),
+        subcommands: vec![tokens(&["git", "status"])],
+        ..CommandFacts::default()
+    };
+    assert_eq!(
+        policy
+            .check_with_facts(&status.subcommands, &status, &prompt_all)
+            .matched_rules,
+        vec![RuleMatch::HeuristicsRuleMatch {
+            command: tokens(&["git", "status"]),
+            decision: Decision::Prompt,
+        }]
+    );
+    Ok(())
+}
+
+#[test]
+fn command_rule_needs_a_condition() {
+    let mut parser = PolicyParser::new();
+    let err = parser
+        .parse("test.rules", r#"command_rule(decision = "allow")"#)
+        .expect_err("expected parse error");
+    assert!(
+        err.to_string()
+            .contains("command_rule needs at least one of program"),
+        "{err}"
+    );
+}
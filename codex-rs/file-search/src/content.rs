@@ -0,0 +1,465 @@
+//! Content search: finds files whose text contains a query and returns ranked
+//! snippets (matching lines with line numbers and surrounding context).
+//!
+//! Files are scanned in parallel with the same walk rules as filename search
+//! (see [`crate::configure_walk`]), skipping files over
+//! [`ContentSearchOptions::max_file_size`] and files that look binary. The
+//! query is a literal string matched with smart case: it is case-sensitive
+//! only when it contains an uppercase character.
+
+use std::cmp::Reverse;
+use std::num::NonZero;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use ignore::WalkBuilder;
+use serde::Serialize;
+
+use crate::FileMatch;
+use crate::cmp_by_score_desc_then_path_asc;
+use crate::configure_walk;
+
+/// Bytes inspected for a NUL byte when deciding whether a file is binary.
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+/// Snippet lines longer than this are cut, so minified files stay readable.
+const MAX_LINE_CHARS: usize = 240;
+/// Only the best matching lines of a file count towards its score.
+const SCORED_LINES_PER_FILE: usize = 5;
+
+const LINE_SCORE: u32 = 10;
+const EXACT_CASE_BONUS: u32 = 5;
+const WHOLE_WORD_BONUS: u32 = 10;
+const FILE_NAME_BONUS: u32 = 25;
+
+#[derive(Debug, Clone)]
+pub struct ContentSearchOptions {
+    /// Maximum number of files to return.
+    pub limit: NonZero<usize>,
+    /// Lines of context shown before and after each matching line.
+    pub context_lines: usize,
+    /// Maximum number of snippets returned per file.
+    pub max_snippets_per_file: usize,
+    /// Files larger than this many bytes are skipped.
+    pub max_file_size: u64,
+    pub threads: NonZero<usize>,
+    pub respect_gitignore: bool,
+}
+
+impl Default for ContentSearchOptions {
+    fn default() -> Self {
+        Self {
+            #[expect(clippy::unwrap_used)]
+            limit: NonZero::new(20).unwrap(),
+            context_lines: 2,
+            max_snippets_per_file: 3,
+            max_file_size: 1024 * 1024,
+            #[expect(clippy::unwrap_used)]
+            threads: NonZero::new(2).unwrap(),
+            respect_gitignore: true,
+        }
+    }
+}
+
+/// A file whose contents match the query.
+///
+/// * `score` – Relevance of the file; higher is better.
+/// * `path` – Path to the file, relative to `root`.
+/// * `match_count` – Number of matching lines in the file.
+/// * `snippets` – The first matching lines with their context, in file order.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ContentMatch {
+    pub score: u32,
+    pub path: PathBuf,
+    pub root: PathBuf,
+    pub match_count: usize,
+    pub snippets: Vec<Snippet>,
+}
+
+impl ContentMatch {
+    pub fn full_path(&self) -> PathBuf {
+        self.root.join(&self.path)
+    }
+}
+
+impl From<ContentMatch> for FileMatch {
+    fn from(content_match: ContentMatch) -> Self {
+        let snippet = content_match
+            .snippets
+            .into_iter()
+            .flat_map(|snippet| snippet.lines)
+            .find(|line| line.is_match);
+        FileMatch {
+            score: content_match.score,
+            path: content_match.path,
+            root: content_match.root,
+            indices: None,
+            snippet,
+        }
+    }
+}
+
+/// Consecutive lines of a file around one or more matching lines.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct Snippet {
+    pub lines: Vec<SnippetLine>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct SnippetLine {
+    /// 1-based line number.
+    pub line_number: usize,
+    pub text: String,
+    pub is_match: bool,
+}
+
+#[derive(Debug)]
+pub struct ContentSearchResults {
+    pub matches: Vec<ContentMatch>,
+    /// Number of files that matched, including those past the limit.
+    pub total_match_count: usize,
+    pub scanned_file_count: usize,
+}
+
+/// Searches the files under `search_directories` for `query`. Worker threads
+/// check `cancel_flag` between files and stop early when it is set.
+pub fn search_content(
+    query: &str,
+    search_directories: &[PathBuf],
+    options: ContentSearchOptions,
+    cancel_flag: Option<Arc<AtomicBool>>,
+) -> anyhow::Result<ContentSearchResults> {
+    let Some(first_root) = search_directories.first() else {
+        anyhow::bail!("at least one search directory is required");
+    };
+    if query.is_empty() {
+        anyhow::bail!("the content search query must not be empty");
+    }
+
+    let mut walk_builder = WalkBuilder::new(first_root);
+    for root in search_directories.iter().skip(1) {
+        walk_builder.add(root);
+    }
+    walk_builder
+        .threads(options.threads.get())
+        .hidden(false)
+        .require_git(false)
+        .max_filesize(Some(options.max_file_size));
+    configure_walk(&mut walk_builder, options.respect_gitignore);
+
+    let matcher = QueryMatcher::new(query);
+    let cancelled = cancel_flag.unwrap_or_else(|| Arc::new(AtomicBool::new(false)));
+    let scanned = AtomicUsize::new(0);
+    let found: Mutex<Vec<ContentMatch>> = Mutex::new(Vec::new());
+
+    {
+        let cancelled = &cancelled;
+        let scanned = &scanned;
+        let found = &found;
+        let matcher = &matcher;
+        let options = &options;
+        walk_builder.build_parallel().run(move || {
+            Box::new(move |entry| {
+                if cancelled.load(Ordering::Relaxed) {
+                    return ignore::WalkState::Quit;
+                }
+                let Ok(entry) = entry else {
+                    return ignore::WalkState::Continue;
+                };
+                if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+                    return ignore::WalkState::Continue;
+                }
+                let Ok(contents) = std::fs::read(entry.path()) else {
+                    return ignore::WalkState::Continue;
+                };
+                scanned.fetch_add(1, Ordering::Relaxed);
+                if is_binary(&contents) {
+                    return ignore::WalkState::Continue;
+                }
+                let Some((root, path)) = relative_to_root(entry.path(), search_directories) else {
+                    return ignore::WalkState::Continue;
+                };
+                let text = String::from_utf8_lossy(&contents);
+                if let Some(content_match) = matcher.match_file(root, path, &text, options)
+                    && let Ok(mut found) = found.lock()
+                {
+                    found.push(content_match);
+                }
+                ignore::WalkState::Continue
+            })
+        });
+    }
+
+    let mut matches = found
+        .into_inner()
+        .map_err(|_| anyhow::anyhow!("content search results lock poisoned"))?;
+    let total_match_count = matches.len();
+    matches.sort_by(cmp_by_score_desc_then_path_asc::<ContentMatch, _, _>(
+        |content_match| content_match.score,
+        |content_match| content_match.path.to_str().unwrap_or_default(),
+    ));
+    matches.truncate(options.limit.get());
+
+    Ok(ContentSearchResults {
+        matches,
+        total_match_count,
+        scanned_file_count: scanned.load(Ordering::Relaxed),
+    })
+}
+
+fn is_binary(contents: &[u8]) -> bool {
+    contents
+        .iter()
+        .take(BINARY_SNIFF_BYTES)
+        .any(|byte| *byte == 0)
+}
+
+/// Picks the deepest search directory containing `path`.
+fn relative_to_root<'a>(path: &'a Path, roots: &'a [PathBuf]) -> Option<(&'a Path, &'a Path)> {
+    roots
+        .iter()
+        .filter_map(|root| Some((root.as_path(), path.strip_prefix(root).ok()?)))
+        .max_by_key(|(root, _)| root.components().count())
+}
+
+struct QueryMatcher {
+    query: String,
+    /// Lowercased query when matching case-insensitively.
+    folded: Option<String>,
+}
+
+impl QueryMatcher {
+    fn new(query: &str) -> Self {
+        let case_sensitive = query.chars().any(char::is_uppercase);
+        Self {
+            query: query.to_string(),
+            folded: (!case_sensitive).then(|| query.to_lowercase()),
+        }
+    }
+
+    /// Scores one line, or returns `None` when it does not contain the query.
+    fn score_line(&self, line: &str) -> Option<u32> {
+        let (haystack, needle) = match &self.folded {
+            Some(folded) => (line.to_lowercase(), folded.as_str()),
+            None => (line.to_string(), self.query.as_str()),
+        };
+        let start = haystack.find(needle)?;
+        let mut score = LINE_SCORE;
+        if line.contains(&self.query) {
+            score += EXACT_CASE_BONUS;
+        }
+        let before = haystack[..start].chars().next_back();
+        let after = haystack[start + needle.len()..].chars().next();
+        if !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char) {
+            score += WHOLE_WORD_BONUS;
+        }
+        Some(score)
+    }
+
+    fn match_file(
+        &self,
+        root: &Path,
+        path: &Path,
+        text: &str,
+        options: &ContentSearchOptions,
+    ) -> Option<ContentMatch> {
+        let lines: Vec<&str> = text.lines().collect();
+        let mut hits: Vec<(usize, u32)> = lines
+            .iter()
+            .enumerate()
+            .filter_map(|(index, line)| Some((index, self.score_line(line)?)))
+            .collect();
+        if hits.is_empty() {
+            return None;
+        }
+
+        let snippets = build_snippets(&lines, &hits, options);
+        let match_count = hits.len();
+        hits.sort_by_key(|(_, score)| Reverse(*score));
+        let mut score: u32 = hits
+            .iter()
+            .take(SCORED_LINES_PER_FILE)
+            .map(|(_, score)| score)
+            .sum();
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+        if self.score_line(&file_name).is_some() {
+            score += FILE_NAME_BONUS;
+        }
+
+        Some(ContentMatch {
+            score,
+            path: path.to_path_buf(),
+            root: root.to_path_buf(),
+            match_count,
+            snippets,
+        })
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Groups the matching lines (`hits`, in file order) with their context into
+/// snippets, merging windows that touch.
+fn build_snippets(
+    lines: &[&str],
+    hits: &[(usize, u32)],
+    options: &ContentSearchOptions,
+) -> Vec<Snippet> {
+    let mut windows: Vec<(usize, usize, Vec<usize>)> = Vec::new();
+    for (index, _) in hits {
+        let start = index.saturating_sub(options.context_lines);
+        let end = (index + options.context_lines).min(lines.len().saturating_sub(1));
+        if let Some((_, last_end, matched)) = windows.last_mut()
+            && start <= *last_end + 1
+        {
+            *last_end = end;
+            matched.push(*index);
+            continue;
+        }
+        if windows.len() == options.max_snippets_per_file {
+            break;
+        }
+        windows.push((start, end, vec![*index]));
+    }
+
+    windows
+        .into_iter()
+        .map(|(start, end, matched)| Snippet {
+            lines: (start..=end)
+                .map(|index| SnippetLine {
+                    line_number: index + 1,
+                    text: truncate_line(lines[index]),
+                    is_match: matched.contains(&index),
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+fn truncate_line(line: &str) -> String {
+    match line.char_indices().nth(MAX_LINE_CHARS) {
+        Some((cut, _)) => format!("{}…", &line[..cut]),
+        None => line.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn line(line_number: usize, text: &str, is_match: bool) -> SnippetLine {
+        SnippetLine {
+            line_number,
+            text: text.to_string(),
+            is_match,
+        }
+    }
+
+    #[test]
+    fn returns_snippets_with_context() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("notes.txt"),
+            "one\ntwo\nalpha three\nfour\nfive\nsix\nseven\nalpha eight\n",
+        )
+        .unwrap();
+        let options = ContentSearchOptions {
+            context_lines: 1,
+            ..Default::default()
+        };
+
+        let results = search_content("alpha", &[dir.path().to_path_buf()], options, None).unwrap();
+        assert_eq!(results.total_match_count, 1);
+        let content_match = &results.matches[0];
+        assert_eq!(content_match.path, PathBuf::from("notes.txt"));
+        assert_eq!(content_match.match_count, 2);
+        assert_eq!(
+            content_match.snippets,
+            vec![
+                Snippet {
+                    lines: vec![
+                        line(2, "two", false),
+                        line(3, "alpha three", true),
+                        line(4, "four", false),
+                    ],
+                },
+                Snippet {
+                    lines: vec![line(7, "seven", false), line(8, "alpha eight", true)],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ranks_whole_word_and_file_name_matches_first() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.rs"), "let parsers = 1;\n").unwrap();
+        fs::write(dir.path().join("b.rs"), "fn parser() {}\n").unwrap();
+        fs::write(dir.path().join("parser.rs"), "// parser\n").unwrap();
+
+        let results = search_content(
+            "parser",
+            &[dir.path().to_path_buf()],
+            ContentSearchOptions::default(),
+            None,
+        )
+        .unwrap();
+        let paths: Vec<PathBuf> = results
+            .matches
+            .iter()
+            .map(|content_match| content_match.path.clone())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("parser.rs"),
+                PathBuf::from("b.rs"),
+                PathBuf::from("a.rs"),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_binary_large_and_ignored_files() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("text.txt"), "needle\n").unwrap();
+        fs::write(dir.path().join("binary.bin"), b"needle\0\x01\x02").unwrap();
+        fs::write(dir.path().join("large.txt"), "needle\n".repeat(100)).unwrap();
+        fs::write(dir.path().join("skipped.txt"), "needle\n").unwrap();
+        fs::write(dir.path().join(".codexignore"), "skipped.txt\n").unwrap();
+        let options = ContentSearchOptions {
+            max_file_size: 64,
+            ..Default::default()
+        };
+
+        let results = search_content("needle", &[dir.path().to_path_buf()], options, None).unwrap();
+        let paths: Vec<PathBuf> = results
+            .matches
+            .iter()
+            .map(|content_match| content_match.path.clone())
+            .collect();
+        assert_eq!(paths, vec![PathBuf::from("text.txt")]);
+    }
+
+    #[test]
+    fn query_uses_smart_case() {
+        let lower = QueryMatcher::new("config");
+        assert!(lower.score_line("let Config = 1;").is_some());
+        let upper = QueryMatcher::new("Config");
+        assert!(upper.score_line("let config = 1;").is_none());
+    }
+}
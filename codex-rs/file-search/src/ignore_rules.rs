@@ -0,0 +1,263 @@
+//! Ignore rules shared by everything in Codex that walks a workspace: file
+//! search, `grep_files`, the repository brief and the file watcher.
+//!
+//! Besides the usual `.gitignore`, `.ignore`, `.git/info/exclude` and global
+//! gitignore files, every directory may hold a `.codexignore`. It uses
+//! gitignore syntax and hides paths from Codex (searches, context, watched
+//! changes) without touching version control. `.codexignore` always applies and
+//! takes precedence over the git rules, the way ripgrep treats custom ignore
+//! files.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::path::PathBuf;
+
+use ignore::Match;
+use ignore::WalkBuilder;
+use ignore::gitignore::Gitignore;
+use serde::Serialize;
+
+/// Per-directory ignore file read by Codex only.
+pub const CODEXIGNORE_FILENAME: &str = ".codexignore";
+
+/// Ignore files read in each directory, highest precedence first. The first
+/// entry applies even when git rules are turned off.
+const IGNORE_FILENAMES: [&str; 3] = [CODEXIGNORE_FILENAME, ".ignore", ".gitignore"];
+
+/// Applies the shared rules to a directory walk: `.codexignore` files are
+/// always honoured, and the git rules only when `respect_gitignore` is set.
+pub fn configure_walk(walk_builder: &mut WalkBuilder, respect_gitignore: bool) {
+    walk_builder.add_custom_ignore_filename(CODEXIGNORE_FILENAME);
+    if !respect_gitignore {
+        walk_builder
+            .git_ignore(false)
+            .git_global(false)
+            .git_exclude(false)
+            .ignore(false);
+    }
+}
+
+/// Why a path is excluded.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct IgnoreReason {
+    /// The excluded path itself, or the ancestor directory that is excluded.
+    pub matched_path: PathBuf,
+    /// Ignore file holding the pattern.
+    pub source: Option<PathBuf>,
+    /// The pattern as written in the ignore file.
+    pub pattern: String,
+}
+
+impl fmt::Display for IgnoreReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`", self.pattern)?;
+        if let Some(source) = &self.source {
+            write!(f, " in {}", source.display())?;
+        }
+        write!(f, " matches {}", self.matched_path.display())
+    }
+}
+
+/// Answers whether paths under `root` are excluded, with the same precedence
+/// as a walk configured by [`configure_walk`]. Ignore files are parsed once
+/// per directory and cached, so reuse one value for a batch of paths.
+pub struct IgnoreRules {
+    root: PathBuf,
+    respect_gitignore: bool,
+    global: Option<Gitignore>,
+    dirs: HashMap<PathBuf, Vec<Gitignore>>,
+}
+
+impl IgnoreRules {
+    pub fn new(root: impl Into<PathBuf>, respect_gitignore: bool) -> Self {
+        let global = respect_gitignore.then(|| Gitignore::global().0);
+        Self {
+            root: root.into(),
+            respect_gitignore,
+            global,
+            dirs: HashMap::new(),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn is_ignored(&mut self, path: &Path) -> bool {
+        self.explain(path).is_some()
+    }
+
+    /// Returns the rule that excludes `path`, or `None` when it is included.
+    /// A path is excluded when it, or a directory between `root` and it, is
+    /// matched. Paths outside `root` and `root` itself are never excluded.
+    pub fn explain(&mut self, path: &Path) -> Option<IgnoreReason> {
+        let path = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            self.root.join(path)
+        };
+        let relative = path.strip_prefix(&self.root).ok()?.to_path_buf();
+
+        let mut current = self.root.clone();
+        let mut components = relative.components().peekable();
+        while let Some(component) = components.next() {
+            current.push(component);
+            let is_dir = components.peek().is_some() || current.is_dir();
+            if let Some(reason) = self.matched(&current, is_dir) {
+                return Some(reason);
+            }
+        }
+        None
+    }
+
+    /// Checks one path against the ignore files of its ancestors. Every
+    /// `.codexignore` is consulted before any git rule; within a kind of file
+    /// the nearest directory wins, and a whitelist (`!pattern`) ends the search.
+    fn matched(&mut self, path: &Path, is_dir: bool) -> Option<IgnoreReason> {
+        let dirs = self.ancestor_dirs(path.parent()?);
+        let kinds = if self.respect_gitignore {
+            IGNORE_FILENAMES.len()
+        } else {
+            1
+        };
+
+        for kind in 0..kinds {
+            for dir in &dirs {
+                let Some(matchers) = self.dirs.get(dir) else {
+                    continue;
+                };
+                match reason_for(&matchers[kind], path, is_dir) {
+                    Decision::Ignore(reason) => return Some(reason),
+                    Decision::Whitelist => return None,
+                    Decision::None => {}
+                }
+            }
+        }
+
+        if !self.respect_gitignore {
+            return None;
+        }
+        let exclude = dirs
+            .last()
+            .map(|repo| repo.join(".git/info/exclude"))
+            .filter(|exclude| exclude.is_file())
+            .map(|exclude| Gitignore::new(exclude).0);
+        for matcher in exclude.iter().chain(self.global.iter()) {
+            match reason_for(matcher, path, is_dir) {
+                Decision::Ignore(reason) => return Some(reason),
+                Decision::Whitelist => return None,
+                Decision::None => {}
+            }
+        }
+        None
+    }
+
+    /// Lists `dir` and its ancestors, nearest first, up to the enclosing git
+    /// repository (or the filesystem root outside one), loading their ignore
+    /// files into the cache.
+    fn ancestor_dirs(&mut self, dir: &Path) -> Vec<PathBuf> {
+        let mut dirs = Vec::new();
+        for ancestor in dir.ancestors() {
+            if !self.dirs.contains_key(ancestor) {
+                let matchers = IGNORE_FILENAMES
+                    .iter()
+                    .map(|name| {
+                        let file = ancestor.join(name);
+                        if file.is_file() {
+                            Gitignore::new(file).0
+                        } else {
+                            Gitignore::empty()
+                        }
+                    })
+                    .collect();
+                self.dirs.insert(ancestor.to_path_buf(), matchers);
+            }
+            dirs.push(ancestor.to_path_buf());
+            if ancestor.join(".git").exists() {
+                break;
+            }
+        }
+        dirs
+    }
+}
+
+enum Decision {
+    Ignore(IgnoreReason),
+    Whitelist,
+    None,
+}
+
+fn reason_for(matcher: &Gitignore, path: &Path, is_dir: bool) -> Decision {
+    match matcher.matched(path, is_dir) {
+        Match::None => Decision::None,
+        Match::Whitelist(_) => Decision::Whitelist,
+        Match::Ignore(glob) => Decision::Ignore(IgnoreReason {
+            matched_path: path.to_path_buf(),
+            source: glob.from().map(Path::to_path_buf),
+            pattern: glob.original().to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn workspace() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join(".git")).unwrap();
+        fs::create_dir_all(dir.path().join("src/fixtures")).unwrap();
+        fs::create_dir_all(dir.path().join("target/debug")).unwrap();
+        fs::write(dir.path().join(".gitignore"), "target/\n*.log\n").unwrap();
+        fs::write(
+            dir.path().join(".codexignore"),
+            "transcripts/\nsrc/fixtures/\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("src/.codexignore"), "!keep.log\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn explains_which_file_excludes_a_path() {
+        let dir = workspace();
+        let root = dir.path();
+        let mut rules = IgnoreRules::new(root, true);
+
+        assert_eq!(
+            rules.explain(Path::new("target/debug/app")),
+            Some(IgnoreReason {
+                matched_path: root.join("target"),
+                source: Some(root.join(".gitignore")),
+                pattern: "target/".to_string(),
+            })
+        );
+        assert_eq!(
+            rules.explain(&root.join("src/fixtures/big.json")),
+            Some(IgnoreReason {
+                matched_path: root.join("src/fixtures"),
+                source: Some(root.join(".codexignore")),
+                pattern: "src/fixtures/".to_string(),
+            })
+        );
+        assert!(rules.is_ignored(Path::new("build.log")));
+        assert!(!rules.is_ignored(Path::new("src/keep.log")));
+        assert!(!rules.is_ignored(Path::new("src/main.rs")));
+        assert!(!rules.is_ignored(Path::new("/elsewhere/target/app")));
+    }
+
+    #[test]
+    fn codexignore_applies_without_git_rules() {
+        let dir = workspace();
+        let mut rules = IgnoreRules::new(dir.path(), false);
+
+        assert!(!rules.is_ignored(Path::new("target/debug/app")));
+        assert!(rules.is_ignored(Path::new("src/fixtures/big.json")));
+    }
+}
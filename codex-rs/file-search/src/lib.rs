@@ -35,8 +35,20 @@ use nucleo::pattern::AtomKind;
 use nucleo::pattern::Pattern;
 
 mod cli;
+mod content;
+mod ignore_rules;
 
 pub use cli::Cli;
+pub use content::ContentMatch;
+pub use content::ContentSearchOptions;
+pub use content::ContentSearchResults;
+pub use content::Snippet;
+pub use content::SnippetLine;
+pub use content::search_content;
+pub use ignore_rules::CODEXIGNORE_FILENAME;
+pub use ignore_rules::IgnoreReason;
+pub use ignore_rules::IgnoreRules;
+pub use ignore_rules::configure_walk;
 
 /// A single match result returned from the search.
 ///
@@ -48,6 +60,8 @@ pub use cli::Cli;
 ///   guidance from `nucleo::pattern::Pattern::indices`: they are
 ///   unique and sorted in ascending order so that callers can use
 ///   them directly for highlighting.
+/// * `snippet` – The first matching line, for matches converted from a
+///   [`ContentMatch`].
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 pub struct FileMatch {
     pub score: u32,
@@ -55,6 +69,8 @@ pub struct FileMatch {
     pub root: PathBuf,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub indices: Option<Vec<u32>>, // Sorted & deduplicated when present
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<SnippetLine>,
 }
 
 impl FileMatch {
@@ -401,14 +417,7 @@ fn walker_worker(
         .follow_links(true)
         // Don't require git to be present to apply to apply git-related ignore rules.
         .require_git(false);
-    if !inner.respect_gitignore {
-        walk_builder
-            .git_ignore(false)
-            .git_global(false)
-            .git_exclude(false)
-            .ignore(false)
-            .parents(false);
-    }
+    configure_walk(&mut walk_builder, inner.respect_gitignore);
     if let Some(override_matcher) = override_matcher {
         walk_builder.overrides(override_matcher);
     }
@@ -537,6 +546,7 @@ fn matcher_worker(
                                 path: PathBuf::from(relative_path),
                                 root: inner.search_directories[root_idx].clone(),
                                 indices,
+                                snippet: None,
                             })
                         })
                         .collect();
@@ -67,11 +67,15 @@ impl BwrapNetworkMode {
 /// returns `command` unchanged so we avoid unnecessary sandboxing overhead.
 /// If network isolation is requested, we still wrap with bubblewrap so network
 /// namespace restrictions apply while preserving full filesystem access.
+///
+/// `allowed_devices` are device-bound on top of the read-only root; every
+/// other device node except `/dev/null` stays unusable.
 pub(crate) fn create_bwrap_command_args(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
     options: BwrapOptions,
+    allowed_devices: &[PathBuf],
 ) -> Result<Vec<String>> {
     if sandbox_policy.has_full_disk_write_access() {
         return if options.network_mode == BwrapNetworkMode::FullAccess {
@@ -81,7 +85,7 @@ pub(crate) fn create_bwrap_command_args(
         };
     }
 
-    create_bwrap_flags(command, sandbox_policy, cwd, options)
+    create_bwrap_flags(command, sandbox_policy, cwd, options, allowed_devices)
 }
 
 fn create_bwrap_flags_full_filesystem(command: Vec<String>, options: BwrapOptions) -> Vec<String> {
@@ -111,11 +115,16 @@ fn create_bwrap_flags(
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
     options: BwrapOptions,
+    allowed_devices: &[PathBuf],
 ) -> Result<Vec<String>> {
     let mut args = Vec::new();
     args.push("--new-session".to_string());
     args.push("--die-with-parent".to_string());
-    args.extend(create_filesystem_args(sandbox_policy, cwd)?);
+    args.extend(create_filesystem_args(
+        sandbox_policy,
+        cwd,
+        allowed_devices,
+    )?);
     // Isolate the PID namespace.
     args.push("--unshare-pid".to_string());
     if options.network_mode.should_unshare_network() {
@@ -139,8 +148,13 @@ fn create_bwrap_flags(
 /// 3. `--ro-bind <subpath> <subpath>` re-applies read-only protections under
 ///    those writable roots so protected subpaths win.
 /// 4. `--dev-bind /dev/null /dev/null` preserves the common sink even under a
-///    read-only root.
-fn create_filesystem_args(sandbox_policy: &SandboxPolicy, cwd: &Path) -> Result<Vec<String>> {
+///    read-only root, and `--dev-bind <device> <device>` does the same for
+///    each allowed device.
+fn create_filesystem_args(
+    sandbox_policy: &SandboxPolicy,
+    cwd: &Path,
+    allowed_devices: &[PathBuf],
+) -> Result<Vec<String>> {
     if !sandbox_policy.has_full_disk_read_access() {
         return Err(CodexErr::UnsupportedOperation(
             "Restricted read-only access is not yet supported by the Linux bubblewrap backend."
@@ -202,6 +216,12 @@ fn create_filesystem_args(sandbox_policy: &SandboxPolicy, cwd: &Path) -> Result<
     args.push("/dev/null".to_string());
     args.push("/dev/null".to_string());
 
+    for device in allowed_devices {
+        args.push("--dev-bind".to_string());
+        args.push(path_to_string(device));
+        args.push(path_to_string(device));
+    }
+
     Ok(args)
 }
 
@@ -331,6 +351,7 @@ mod tests {
                 mount_proc: true,
                 network_mode: BwrapNetworkMode::FullAccess,
             },
+            &[],
         )
         .expect("create bwrap args");
 
@@ -348,6 +369,7 @@ mod tests {
                 mount_proc: true,
                 network_mode: BwrapNetworkMode::ProxyOnly,
             },
+            &[],
         )
         .expect("create bwrap args");
 
@@ -368,4 +390,33 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn allowed_devices_are_dev_bound_after_dev_null() {
+        let args = create_bwrap_command_args(
+            vec!["/bin/true".to_string()],
+            &SandboxPolicy::new_read_only_policy(),
+            Path::new("/"),
+            BwrapOptions::default(),
+            &[PathBuf::from("/dev/nvidia0"), PathBuf::from("/dev/dri")],
+        )
+        .expect("create bwrap args");
+
+        let dev_binds: Vec<Vec<String>> = args
+            .windows(3)
+            .filter(|window| window[0] == "--dev-bind")
+            .map(<[String]>::to_vec)
+            .collect();
+        let expected: Vec<Vec<String>> = ["/dev/null", "/dev/nvidia0", "/dev/dri"]
+            .into_iter()
+            .map(|device| {
+                vec![
+                    "--dev-bind".to_string(),
+                    device.to_string(),
+                    device.to_string(),
+                ]
+            })
+            .collect();
+        assert_eq!(dev_binds, expected);
+    }
 }
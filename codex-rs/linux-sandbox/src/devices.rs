@@ -0,0 +1,100 @@
+//! Expansion of `--allow-device` patterns into the device nodes that exist
+//! when the command starts.
+//!
+//! Patterns are validated by `codex-core` when config loads: they live under
+//! `/dev/` and may use `*` in their last component only. Expanding here rather
+//! than in the parent keeps nodes that appear later (for example after a GPU
+//! driver loads) usable without restarting Codex.
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Returns the existing paths matched by `patterns`, sorted and deduplicated.
+pub(crate) fn expand_device_patterns(patterns: &[String]) -> Vec<PathBuf> {
+    let mut devices = Vec::new();
+    for pattern in patterns {
+        let path = Path::new(pattern);
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if !name.contains('*') {
+            if path.exists() {
+                devices.push(path.to_path_buf());
+            }
+            continue;
+        }
+        let Some(parent) = path.parent() else {
+            continue;
+        };
+        let Ok(entries) = std::fs::read_dir(parent) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if let Some(entry_name) = entry.file_name().to_str()
+                && wildcard_matches(name, entry_name)
+            {
+                devices.push(entry.path());
+            }
+        }
+    }
+    devices.sort();
+    devices.dedup();
+    devices
+}
+
+/// Matches `name` against `pattern`, where `*` stands for any run of
+/// characters.
+fn wildcard_matches(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        // No `*` at all: the prefix must be the whole name.
+        return rest.is_empty();
+    };
+    for part in parts {
+        let Some(index) = rest.find(part) else {
+            return false;
+        };
+        rest = &rest[index + part.len()..];
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn wildcard_matches_device_names() {
+        assert!(wildcard_matches("nvidia*", "nvidia0"));
+        assert!(wildcard_matches("nvidia*", "nvidiactl"));
+        assert!(wildcard_matches("nvidia*-uvm", "nvidia-uvm"));
+        assert!(wildcard_matches("render*", "renderD128"));
+        assert!(!wildcard_matches("nvidia*", "null"));
+        assert!(!wildcard_matches("card*1", "card0"));
+        assert!(wildcard_matches("kfd", "kfd"));
+        assert!(!wildcard_matches("kfd", "kfd0"));
+    }
+
+    #[test]
+    fn expands_patterns_to_existing_paths() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        for name in ["nvidia0", "nvidiactl", "null"] {
+            std::fs::write(tmp.path().join(name), "").expect("create fake device");
+        }
+        let dir = tmp.path().display();
+        let devices = expand_device_patterns(&[
+            format!("{dir}/nvidia*"),
+            format!("{dir}/nvidia0"),
+            format!("{dir}/missing"),
+        ]);
+        assert_eq!(
+            devices,
+            vec![tmp.path().join("nvidia0"), tmp.path().join("nvidiactl")]
+        );
+    }
+}
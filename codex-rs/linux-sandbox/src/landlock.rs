@@ -4,6 +4,7 @@
 //! Landlock helpers remain available here as legacy/backup utilities.
 use std::collections::BTreeMap;
 use std::path::Path;
+use std::path::PathBuf;
 
 use codex_core::error::CodexErr;
 use codex_core::error::Result;
@@ -37,12 +38,15 @@ use seccompiler::apply_filter;
 /// - enabling `PR_SET_NO_NEW_PRIVS` when restrictions apply, and
 /// - installing the network seccomp filter when network access is disabled.
 ///
-/// Filesystem restrictions are intentionally handled by bubblewrap.
+/// Filesystem restrictions are intentionally handled by bubblewrap. When the
+/// legacy Landlock pipeline is used instead, `allowed_devices` are opened up
+/// for reading and writing alongside the writable roots.
 pub(crate) fn apply_sandbox_policy_to_current_thread(
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
     apply_landlock_fs: bool,
     allow_network_for_proxy: bool,
+    allowed_devices: &[PathBuf],
 ) -> Result<()> {
     let install_network_seccomp =
         should_install_network_seccomp(sandbox_policy, allow_network_for_proxy);
@@ -74,7 +78,7 @@ pub(crate) fn apply_sandbox_policy_to_current_thread(
             .into_iter()
             .map(|writable_root| writable_root.root)
             .collect();
-        install_filesystem_landlock_rules_on_current_thread(writable_roots)?;
+        install_filesystem_landlock_rules_on_current_thread(writable_roots, allowed_devices)?;
     }
 
     Ok(())
@@ -100,7 +104,7 @@ fn set_no_new_privs() -> Result<()> {
 
 /// Installs Landlock file-system rules on the current thread allowing read
 /// access to the entire file-system while restricting write access to
-/// `/dev/null` and the provided list of `writable_roots`.
+/// `/dev/null`, the provided list of `writable_roots` and `allowed_devices`.
 ///
 /// # Errors
 /// Returns [`CodexErr::Sandbox`] variants when the ruleset fails to apply.
@@ -109,6 +113,7 @@ fn set_no_new_privs() -> Result<()> {
 /// via bubblewrap. It is kept for reference and potential fallback use.
 fn install_filesystem_landlock_rules_on_current_thread(
     writable_roots: Vec<AbsolutePathBuf>,
+    allowed_devices: &[PathBuf],
 ) -> Result<()> {
     let abi = ABI::V5;
     let access_rw = AccessFs::from_all(abi);
@@ -125,6 +130,9 @@ fn install_filesystem_landlock_rules_on_current_thread(
     if !writable_roots.is_empty() {
         ruleset = ruleset.add_rules(landlock::path_beneath_rules(&writable_roots, access_rw))?;
     }
+    if !allowed_devices.is_empty() {
+        ruleset = ruleset.add_rules(landlock::path_beneath_rules(allowed_devices, access_rw))?;
+    }
 
     let status = ruleset.restrict_self()?;
 
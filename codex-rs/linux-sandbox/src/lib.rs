@@ -2,14 +2,19 @@
 //!
 //! On Linux, `codex-linux-sandbox` applies:
 //! - in-process restrictions (`no_new_privs` + seccomp), and
-//! - bubblewrap for filesystem isolation.
+//! - bubblewrap for filesystem isolation, or, on the legacy pipeline, Landlock
+//!   with a user-namespace fallback for kernels where Landlock is unusable.
 #[cfg(target_os = "linux")]
 mod bwrap;
 #[cfg(target_os = "linux")]
+mod devices;
+#[cfg(target_os = "linux")]
 mod landlock;
 #[cfg(target_os = "linux")]
 mod linux_run_main;
 #[cfg(target_os = "linux")]
+mod userns;
+#[cfg(target_os = "linux")]
 mod vendored_bwrap;
 
 #[cfg(target_os = "linux")]
@@ -9,7 +9,11 @@ use std::path::PathBuf;
 use crate::bwrap::BwrapNetworkMode;
 use crate::bwrap::BwrapOptions;
 use crate::bwrap::create_bwrap_command_args;
+use crate::devices::expand_device_patterns;
 use crate::landlock::apply_sandbox_policy_to_current_thread;
+use crate::userns::FilesystemBackend;
+use crate::userns::apply_user_namespace_mounts;
+use crate::userns::select_filesystem_backend;
 use crate::vendored_bwrap::exec_vendored_bwrap;
 use crate::vendored_bwrap::run_vendored_bwrap_main;
 
@@ -55,6 +59,12 @@ pub struct LandlockCommand {
     #[arg(long = "no-proc", default_value_t = false)]
     pub no_proc: bool,
 
+    /// Device nodes the command may open for reading and writing, such as
+    /// `/dev/nvidia*`. A `*` in the last component matches any run of
+    /// characters. Every other device except `/dev/null` stays blocked.
+    #[arg(long = "allow-device", hide = true)]
+    pub allow_devices: Vec<String>,
+
     /// Full command args to run under the Linux sandbox helper.
     #[arg(trailing_var_arg = true)]
     pub command: Vec<String>,
@@ -64,7 +74,8 @@ pub struct LandlockCommand {
 ///
 /// The sequence is:
 /// 1. When needed, wrap the command with bubblewrap to construct the
-///    filesystem view.
+///    filesystem view. Without bubblewrap, use Landlock, or a user namespace
+///    with read-only bind mounts when Landlock is unavailable.
 /// 2. Apply in-process restrictions (no_new_privs + seccomp).
 /// 3. `execvp` into the final command.
 pub fn run_main() -> ! {
@@ -75,6 +86,7 @@ pub fn run_main() -> ! {
         apply_seccomp_then_exec,
         allow_network_for_proxy,
         no_proc,
+        allow_devices,
         command,
     } = LandlockCommand::parse();
 
@@ -90,6 +102,7 @@ pub fn run_main() -> ! {
             &sandbox_policy_cwd,
             false,
             allow_network_for_proxy,
+            &[],
         ) {
             panic!("error applying Linux sandbox restrictions: {e:?}");
         }
@@ -102,6 +115,7 @@ pub fn run_main() -> ! {
             &sandbox_policy_cwd,
             false,
             allow_network_for_proxy,
+            &[],
         ) {
             panic!("error applying Linux sandbox restrictions: {e:?}");
         }
@@ -125,15 +139,36 @@ pub fn run_main() -> ! {
             inner,
             !no_proc,
             allow_network_for_proxy,
+            &expand_device_patterns(&allow_devices),
         );
     }
 
-    // Legacy path: Landlock enforcement only, when bwrap sandboxing is not enabled.
+    // Legacy path, when bwrap sandboxing is not enabled: Landlock where the
+    // kernel supports it, otherwise a user namespace with read-only mounts.
+    if !sandbox_policy.has_full_disk_write_access()
+        && select_filesystem_backend() == FilesystemBackend::UserNamespace
+    {
+        if let Err(e) = apply_user_namespace_mounts(&sandbox_policy, &sandbox_policy_cwd) {
+            panic!("error applying Linux user namespace sandbox: {e:?}");
+        }
+        if let Err(e) = apply_sandbox_policy_to_current_thread(
+            &sandbox_policy,
+            &sandbox_policy_cwd,
+            false,
+            allow_network_for_proxy,
+            &[],
+        ) {
+            panic!("error applying Linux sandbox restrictions: {e:?}");
+        }
+        exec_or_panic(command);
+    }
+
     if let Err(e) = apply_sandbox_policy_to_current_thread(
         &sandbox_policy,
         &sandbox_policy_cwd,
         true,
         allow_network_for_proxy,
+        &expand_device_patterns(&allow_devices),
     ) {
         panic!("error applying legacy Linux sandbox restrictions: {e:?}");
     }
@@ -146,6 +181,7 @@ fn run_bwrap_with_proc_fallback(
     inner: Vec<String>,
     mount_proc: bool,
     allow_network_for_proxy: bool,
+    allowed_devices: &[PathBuf],
 ) -> ! {
     let mut mount_proc = mount_proc;
 
@@ -159,7 +195,13 @@ fn run_bwrap_with_proc_fallback(
         mount_proc,
         network_mode,
     };
-    let argv = build_bwrap_argv(inner, sandbox_policy, sandbox_policy_cwd, options);
+    let argv = build_bwrap_argv(
+        inner,
+        sandbox_policy,
+        sandbox_policy_cwd,
+        options,
+        allowed_devices,
+    );
     exec_vendored_bwrap(argv);
 }
 
@@ -181,9 +223,16 @@ fn build_bwrap_argv(
     sandbox_policy: &codex_core::protocol::SandboxPolicy,
     sandbox_policy_cwd: &Path,
     options: BwrapOptions,
+    allowed_devices: &[PathBuf],
 ) -> Vec<String> {
-    let mut args = create_bwrap_command_args(inner, sandbox_policy, sandbox_policy_cwd, options)
-        .unwrap_or_else(|err| panic!("error building bubblewrap command: {err:?}"));
+    let mut args = create_bwrap_command_args(
+        inner,
+        sandbox_policy,
+        sandbox_policy_cwd,
+        options,
+        allowed_devices,
+    )
+    .unwrap_or_else(|err| panic!("error building bubblewrap command: {err:?}"));
 
     let command_separator_index = args
         .iter()
@@ -212,6 +261,7 @@ fn preflight_proc_mount_support(
             mount_proc: true,
             network_mode: BwrapNetworkMode::FullAccess,
         },
+        &[],
     );
     let stderr = run_bwrap_in_child_capture_stderr(preflight_argv);
     !is_proc_mount_failure(stderr.as_str())
@@ -397,6 +447,7 @@ mod tests {
                 mount_proc: true,
                 network_mode: BwrapNetworkMode::FullAccess,
             },
+            &[],
         );
         assert_eq!(
             argv,
@@ -431,6 +482,7 @@ mod tests {
                 mount_proc: true,
                 network_mode: BwrapNetworkMode::Isolated,
             },
+            &[],
         );
         assert_eq!(argv.contains(&"--unshare-net".to_string()), true);
     }
@@ -445,6 +497,7 @@ mod tests {
                 mount_proc: true,
                 network_mode: BwrapNetworkMode::ProxyOnly,
             },
+            &[],
         );
         assert_eq!(argv.contains(&"--unshare-net".to_string()), true);
     }
@@ -0,0 +1,339 @@
+//! User-namespace + bind-mount filesystem backend for the legacy pipeline.
+//!
+//! Some kernels ship without Landlock, or with an ABI too old to be usable
+//! (ABI 1 denies every cross-directory rename, which breaks `mv` and most
+//! build tools). On those hosts we can still get a read-only view of the
+//! filesystem if unprivileged user namespaces are allowed:
+//! - unshare a user + mount namespace, mapping the caller's uid/gid to itself,
+//! - remount every visible mount read-only,
+//! - bind each writable root back on top of itself read-write, and
+//! - bind protected subpaths (`.git`, `.codex`, ...) back read-only.
+//!
+//! Writable roots are bound rather than overlaid so that writes land on the
+//! host, matching the Landlock and bubblewrap backends. Mounts created inside
+//! a user namespace are locked once the command execs, so it cannot undo the
+//! read-only remounts from a nested namespace either.
+use std::ffi::CString;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_core::error::CodexErr;
+use codex_core::error::Result;
+use codex_core::protocol::SandboxPolicy;
+
+/// Oldest Landlock ABI we rely on. ABI 2 added `LANDLOCK_ACCESS_FS_REFER`;
+/// under ABI 1 the kernel refuses all renames between directories.
+const MIN_LANDLOCK_ABI: i64 = 2;
+
+/// `LANDLOCK_CREATE_RULESET_VERSION` from `linux/landlock.h`.
+const LANDLOCK_CREATE_RULESET_VERSION: libc::c_uint = 1 << 0;
+
+/// Filesystem backend used when the bubblewrap pipeline is not enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FilesystemBackend {
+    Landlock,
+    UserNamespace,
+}
+
+/// Probe the running kernel and pick a filesystem backend.
+///
+/// Landlock stays the default whenever it is usable. When neither backend is
+/// available we still return `Landlock` so the caller reports the usual
+/// Landlock error instead of running the command unsandboxed.
+pub(crate) fn select_filesystem_backend() -> FilesystemBackend {
+    choose_filesystem_backend(landlock_abi_version(), user_namespaces_available)
+}
+
+fn choose_filesystem_backend(
+    landlock_abi: Option<i64>,
+    user_namespaces_available: impl FnOnce() -> bool,
+) -> FilesystemBackend {
+    let landlock_usable = landlock_abi.is_some_and(|abi| abi >= MIN_LANDLOCK_ABI);
+    if !landlock_usable && user_namespaces_available() {
+        FilesystemBackend::UserNamespace
+    } else {
+        FilesystemBackend::Landlock
+    }
+}
+
+/// Returns the Landlock ABI version supported by the kernel, or `None` when
+/// Landlock is compiled out or disabled at boot.
+fn landlock_abi_version() -> Option<i64> {
+    let version = unsafe {
+        libc::syscall(
+            libc::SYS_landlock_create_ruleset,
+            std::ptr::null::<libc::c_void>(),
+            0usize,
+            LANDLOCK_CREATE_RULESET_VERSION,
+        )
+    };
+    (version > 0).then_some(version)
+}
+
+/// Check whether this process may create a user + mount namespace.
+///
+/// Sysctls such as `kernel.unprivileged_userns_clone` do not tell the whole
+/// story (AppArmor and container seccomp profiles can deny it too), so we
+/// try the `unshare` in a short-lived child and look at its exit status.
+fn user_namespaces_available() -> bool {
+    let pid = unsafe { libc::fork() };
+    if pid < 0 {
+        return false;
+    }
+    if pid == 0 {
+        let res = unsafe { libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNS) };
+        unsafe { libc::_exit(if res == 0 { 0 } else { 1 }) };
+    }
+
+    let mut status: libc::c_int = 0;
+    let wait_res = unsafe { libc::waitpid(pid, &mut status as *mut libc::c_int, 0) };
+    wait_res == pid && libc::WIFEXITED(status) && libc::WEXITSTATUS(status) == 0
+}
+
+/// Enter a new user + mount namespace and build the read-only filesystem view
+/// for `sandbox_policy` in the current process.
+///
+/// Must run while the process is still single-threaded, since `unshare` of a
+/// user namespace fails otherwise.
+pub(crate) fn apply_user_namespace_mounts(
+    sandbox_policy: &SandboxPolicy,
+    cwd: &Path,
+) -> Result<()> {
+    if !sandbox_policy.has_full_disk_read_access() {
+        return Err(CodexErr::UnsupportedOperation(
+            "Restricted read-only access is not supported by the Linux user namespace backend."
+                .to_string(),
+        ));
+    }
+
+    let writable_roots = sandbox_policy.get_writable_roots_with_cwd(cwd);
+    // Record each writable root's mount flags before we force everything
+    // read-only, so roots on genuinely read-only filesystems stay that way.
+    let mut writable_mounts = Vec::new();
+    for writable_root in &writable_roots {
+        let root = writable_root.root.as_path();
+        if !root.exists() {
+            return Err(CodexErr::UnsupportedOperation(format!(
+                "Sandbox expected writable root {root}, but it does not exist.",
+                root = root.display()
+            )));
+        }
+        writable_mounts.push((root.to_path_buf(), statvfs_flags(root)?));
+    }
+
+    enter_user_namespace()?;
+
+    // Keep our mounts from propagating back to the host namespace.
+    mount(None, Path::new("/"), libc::MS_REC | libc::MS_PRIVATE)?;
+
+    let mountinfo = std::fs::read_to_string("/proc/self/mountinfo")?;
+    for mount_point in parse_mount_points(&mountinfo) {
+        remount_read_only(&mount_point)?;
+    }
+
+    for (root, flags) in &writable_mounts {
+        mount(Some(root), root, libc::MS_BIND | libc::MS_REC)?;
+        mount(
+            None,
+            root,
+            libc::MS_REMOUNT | libc::MS_BIND | locked_mount_flags(*flags),
+        )?;
+    }
+
+    for writable_root in &writable_roots {
+        for subpath in &writable_root.read_only_subpaths {
+            let subpath = subpath.as_path();
+            // Missing paths have nothing to mount on, and `mount` would follow
+            // a symlink to its target rather than pin the link itself.
+            let Ok(metadata) = subpath.symlink_metadata() else {
+                continue;
+            };
+            if metadata.file_type().is_symlink() {
+                continue;
+            }
+            mount(Some(subpath), subpath, libc::MS_BIND | libc::MS_REC)?;
+            remount_read_only(subpath)?;
+        }
+    }
+
+    // The process cwd still points into the mount it was opened on; re-enter
+    // it so relative paths resolve through the new writable binds.
+    let current_dir = std::env::current_dir()?;
+    std::env::set_current_dir(current_dir)?;
+    Ok(())
+}
+
+/// Unshare a user + mount namespace, mapping the current uid/gid to
+/// themselves so file ownership looks unchanged inside the sandbox.
+fn enter_user_namespace() -> Result<()> {
+    let uid = unsafe { libc::geteuid() };
+    let gid = unsafe { libc::getegid() };
+    if unsafe { libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNS) } != 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    // Unprivileged processes must give up setgroups before writing gid_map.
+    std::fs::write("/proc/self/setgroups", "deny")?;
+    std::fs::write("/proc/self/uid_map", format!("{uid} {uid} 1"))?;
+    std::fs::write("/proc/self/gid_map", format!("{gid} {gid} 1"))?;
+    Ok(())
+}
+
+fn remount_read_only(mount_point: &Path) -> Result<()> {
+    let flags = match statvfs_flags(mount_point) {
+        Ok(flags) => flags,
+        // Mounts we cannot reach (hidden under another mount, or behind a
+        // directory we cannot search) cannot be written through either.
+        Err(CodexErr::Io(err))
+            if matches!(
+                err.raw_os_error(),
+                Some(libc::ENOENT | libc::EACCES | libc::ENOTDIR)
+            ) =>
+        {
+            return Ok(());
+        }
+        Err(err) => return Err(err),
+    };
+    mount(
+        None,
+        mount_point,
+        libc::MS_REMOUNT | libc::MS_BIND | libc::MS_RDONLY | locked_mount_flags(flags),
+    )
+}
+
+fn statvfs_flags(path: &Path) -> Result<libc::c_ulong> {
+    let c_path = path_to_cstring(path)?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    Ok(stat.f_flag)
+}
+
+/// Translate `statvfs` flags into the `MS_*` flags a bind remount must
+/// repeat. The kernel locks these on mounts inherited from the parent
+/// namespace and rejects a remount that tries to clear them.
+fn locked_mount_flags(statvfs_flags: libc::c_ulong) -> libc::c_ulong {
+    const FLAG_MAP: [(libc::c_ulong, libc::c_ulong); 7] = [
+        (libc::ST_RDONLY, libc::MS_RDONLY),
+        (libc::ST_NOSUID, libc::MS_NOSUID),
+        (libc::ST_NODEV, libc::MS_NODEV),
+        (libc::ST_NOEXEC, libc::MS_NOEXEC),
+        (libc::ST_NOATIME, libc::MS_NOATIME),
+        (libc::ST_NODIRATIME, libc::MS_NODIRATIME),
+        (libc::ST_RELATIME, libc::MS_RELATIME),
+    ];
+    FLAG_MAP
+        .iter()
+        .filter(|(st_flag, _)| statvfs_flags & st_flag != 0)
+        .fold(0, |flags, (_, ms_flag)| flags | ms_flag)
+}
+
+fn mount(source: Option<&Path>, target: &Path, flags: libc::c_ulong) -> Result<()> {
+    let source = source.map(path_to_cstring).transpose()?;
+    let target_c = path_to_cstring(target)?;
+    let res = unsafe {
+        libc::mount(
+            source.as_ref().map_or(std::ptr::null(), |s| s.as_ptr()),
+            target_c.as_ptr(),
+            std::ptr::null(),
+            flags,
+            std::ptr::null(),
+        )
+    };
+    if res != 0 {
+        let err = std::io::Error::last_os_error();
+        return Err(CodexErr::Io(std::io::Error::new(
+            err.kind(),
+            format!("failed to mount {}: {err}", target.display()),
+        )));
+    }
+    Ok(())
+}
+
+fn path_to_cstring(path: &Path) -> Result<CString> {
+    Ok(CString::new(path.as_os_str().as_bytes()).map_err(std::io::Error::other)?)
+}
+
+/// Extract mount points from `/proc/self/mountinfo`, in mount order.
+///
+/// The mount point is the fifth field; the kernel escapes space, tab,
+/// newline and backslash in it as three-digit octal sequences.
+fn parse_mount_points(mountinfo: &str) -> Vec<PathBuf> {
+    mountinfo
+        .lines()
+        .filter_map(|line| line.split(' ').nth(4))
+        .map(|field| PathBuf::from(unescape_mountinfo_field(field)))
+        .collect()
+}
+
+fn unescape_mountinfo_field(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'\\'
+            && let Some(octal) = field.get(i + 1..i + 4)
+            && let Ok(byte) = u8::from_str_radix(octal, 8)
+        {
+            out.push(byte);
+            i += 4;
+            continue;
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn prefers_landlock_and_falls_back_to_user_namespaces() {
+        assert_eq!(
+            choose_filesystem_backend(Some(5), || panic!("should not probe")),
+            FilesystemBackend::Landlock
+        );
+        assert_eq!(
+            choose_filesystem_backend(Some(1), || true),
+            FilesystemBackend::UserNamespace
+        );
+        assert_eq!(
+            choose_filesystem_backend(None, || true),
+            FilesystemBackend::UserNamespace
+        );
+        assert_eq!(
+            choose_filesystem_backend(None, || false),
+            FilesystemBackend::Landlock
+        );
+    }
+
+    #[test]
+    fn parses_and_unescapes_mount_points() {
+        let mountinfo = "\
+22 1 8:1 / / rw,relatime shared:1 - ext4 /dev/sda1 rw
+35 22 0:31 / /proc rw,nosuid,nodev,noexec,relatime shared:12 - proc proc rw
+41 22 8:2 / /mnt/my\\040disk rw,relatime shared:20 - ext4 /dev/sda2 rw
+";
+        assert_eq!(
+            parse_mount_points(mountinfo),
+            vec![
+                PathBuf::from("/"),
+                PathBuf::from("/proc"),
+                PathBuf::from("/mnt/my disk"),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_locked_flags_on_remount() {
+        assert_eq!(
+            locked_mount_flags(libc::ST_NOSUID | libc::ST_NODEV | libc::ST_RELATIME),
+            libc::MS_NOSUID | libc::MS_NODEV | libc::MS_RELATIME
+        );
+        assert_eq!(locked_mount_flags(0), 0);
+    }
+}
@@ -1,4 +1,5 @@
 mod device_code_auth;
+mod oidc_device_auth;
 mod pkce;
 mod server;
 
@@ -6,6 +7,10 @@ pub use device_code_auth::DeviceCode;
 pub use device_code_auth::complete_device_code_login;
 pub use device_code_auth::request_device_code;
 pub use device_code_auth::run_device_code_login;
+pub use oidc_device_auth::OidcDeviceCode;
+pub use oidc_device_auth::complete_oidc_device_code_login;
+pub use oidc_device_auth::request_oidc_device_code;
+pub use oidc_device_auth::run_oidc_device_code_login;
 pub use server::LoginServer;
 pub use server::ServerOptions;
 pub use server::ShutdownHandle;
@@ -0,0 +1,221 @@
+//! OIDC device authorization grant (RFC 8628) for model providers configured
+//! with an `oidc` issuer, such as Okta or Azure AD in front of an enterprise
+//! gateway.
+
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_core::ProviderOidcConfig;
+use codex_core::auth::AuthCredentialsStoreMode;
+use codex_core::auth::DEVICE_CODE_GRANT_TYPE;
+use codex_core::auth::OidcEndpoints;
+use codex_core::auth::OidcTokenResponse;
+use codex_core::auth::discover_oidc_endpoints;
+use codex_core::auth::save_oidc_tokens;
+use codex_core::default_client::build_reqwest_client;
+use serde::Deserialize;
+
+const ANSI_BLUE: &str = "\x1b[94m";
+const ANSI_GRAY: &str = "\x1b[90m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+// RFC 8628 defaults when the issuer leaves them out.
+const DEFAULT_INTERVAL_SECS: u64 = 5;
+const DEFAULT_EXPIRES_IN_SECS: u64 = 15 * 60;
+
+/// A pending device authorization.
+#[derive(Debug, Clone)]
+pub struct OidcDeviceCode {
+    pub verification_uri: String,
+    /// Verification URI with the user code filled in, when the issuer
+    /// provides one.
+    pub verification_uri_complete: Option<String>,
+    pub user_code: String,
+    device_code: String,
+    interval: u64,
+    expires_in: u64,
+    token_endpoint: String,
+}
+
+#[derive(Deserialize)]
+struct DeviceAuthorizationResp {
+    device_code: String,
+    user_code: String,
+    // Azure AD v1 endpoints call it `verification_url`.
+    #[serde(alias = "verification_url")]
+    verification_uri: String,
+    #[serde(default)]
+    verification_uri_complete: Option<String>,
+    #[serde(default)]
+    interval: Option<u64>,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct TokenErrorResp {
+    error: String,
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+/// Starts device authorization with the issuer of `oidc`.
+pub async fn request_oidc_device_code(oidc: &ProviderOidcConfig) -> io::Result<OidcDeviceCode> {
+    let client = build_reqwest_client();
+    let OidcEndpoints {
+        device_authorization_endpoint,
+        token_endpoint,
+    } = discover_oidc_endpoints(&client, &oidc.issuer).await?;
+    let Some(device_authorization_endpoint) = device_authorization_endpoint else {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!(
+                "{} does not support device authorization (no device_authorization_endpoint)",
+                oidc.issuer
+            ),
+        ));
+    };
+
+    let scope = oidc.scope();
+    let mut form = vec![
+        ("client_id", oidc.client_id.as_str()),
+        ("scope", scope.as_str()),
+    ];
+    if let Some(audience) = oidc.audience.as_deref() {
+        form.push(("audience", audience));
+    }
+    let resp = client
+        .post(&device_authorization_endpoint)
+        .form(&form)
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    let status = resp.status();
+    if !status.is_success() {
+        let body = resp.text().await.unwrap_or_default();
+        return Err(io::Error::other(format!(
+            "device authorization request failed with status {status}: {}",
+            token_error_message(&body)
+        )));
+    }
+    let resp: DeviceAuthorizationResp = resp.json().await.map_err(io::Error::other)?;
+
+    Ok(OidcDeviceCode {
+        verification_uri: resp.verification_uri,
+        verification_uri_complete: resp.verification_uri_complete,
+        user_code: resp.user_code,
+        device_code: resp.device_code,
+        interval: resp.interval.unwrap_or(DEFAULT_INTERVAL_SECS),
+        expires_in: resp.expires_in.unwrap_or(DEFAULT_EXPIRES_IN_SECS),
+        token_endpoint,
+    })
+}
+
+/// Polls the token endpoint until the user approves the device code, then
+/// stores the tokens.
+pub async fn complete_oidc_device_code_login(
+    codex_home: &Path,
+    auth_credentials_store_mode: AuthCredentialsStoreMode,
+    oidc: &ProviderOidcConfig,
+    device_code: OidcDeviceCode,
+) -> io::Result<()> {
+    let client = build_reqwest_client();
+    let deadline = Instant::now() + Duration::from_secs(device_code.expires_in);
+    let mut interval = device_code.interval;
+
+    loop {
+        let resp = client
+            .post(&device_code.token_endpoint)
+            .form(&[
+                ("grant_type", DEVICE_CODE_GRANT_TYPE),
+                ("device_code", device_code.device_code.as_str()),
+                ("client_id", oidc.client_id.as_str()),
+            ])
+            .send()
+            .await
+            .map_err(io::Error::other)?;
+
+        let status = resp.status();
+        if status.is_success() {
+            let tokens: OidcTokenResponse = resp.json().await.map_err(io::Error::other)?;
+            return save_oidc_tokens(
+                codex_home,
+                auth_credentials_store_mode,
+                oidc,
+                tokens.into_tokens(None),
+            );
+        }
+
+        let body = resp.text().await.unwrap_or_default();
+        match serde_json::from_str::<TokenErrorResp>(&body) {
+            Ok(err) if err.error == "authorization_pending" => {}
+            Ok(err) if err.error == "slow_down" => interval += 5,
+            Ok(err) if err.error == "access_denied" => {
+                return Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    "device authorization was denied",
+                ));
+            }
+            Ok(err) if err.error == "expired_token" => {
+                return Err(io::Error::other(
+                    "device code expired before it was approved",
+                ));
+            }
+            _ => {
+                return Err(io::Error::other(format!(
+                    "device authorization failed with status {status}: {}",
+                    token_error_message(&body)
+                )));
+            }
+        }
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::Error::other(
+                "device code expired before it was approved",
+            ));
+        }
+        tokio::time::sleep(Duration::from_secs(interval).min(remaining)).await;
+    }
+}
+
+fn token_error_message(body: &str) -> String {
+    match serde_json::from_str::<TokenErrorResp>(body) {
+        Ok(TokenErrorResp {
+            error,
+            error_description: Some(description),
+        }) => format!("{error}: {description}"),
+        Ok(TokenErrorResp { error, .. }) => error,
+        Err(_) => body.to_string(),
+    }
+}
+
+fn print_oidc_device_code_prompt(provider_name: &str, device_code: &OidcDeviceCode) {
+    let link = device_code
+        .verification_uri_complete
+        .as_deref()
+        .unwrap_or(&device_code.verification_uri);
+    let code = &device_code.user_code;
+    let minutes = device_code.expires_in.div_ceil(60);
+    println!(
+        "\nFollow these steps to sign in to {provider_name}:\n\
+\n1. Open this link in your browser and sign in with your organization account\n   {ANSI_BLUE}{link}{ANSI_RESET}\n\
+\n2. Enter this one-time code if asked {ANSI_GRAY}(expires in {minutes} minutes){ANSI_RESET}\n   {ANSI_BLUE}{code}{ANSI_RESET}\n\
+\n{ANSI_GRAY}Device codes are a common phishing target. Never share this code.{ANSI_RESET}\n",
+    );
+}
+
+/// Full OIDC device authorization flow for a provider.
+pub async fn run_oidc_device_code_login(
+    codex_home: &Path,
+    auth_credentials_store_mode: AuthCredentialsStoreMode,
+    provider_name: &str,
+    oidc: &ProviderOidcConfig,
+) -> io::Result<()> {
+    let device_code = request_oidc_device_code(oidc).await?;
+    print_oidc_device_code_prompt(provider_name, &device_code);
+    complete_oidc_device_code_login(codex_home, auth_credentials_store_mode, oidc, device_code)
+        .await
+}
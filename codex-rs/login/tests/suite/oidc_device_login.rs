@@ -0,0 +1,170 @@
+#![allow(clippy::unwrap_used)]
+
+use anyhow::Context;
+use codex_core::ProviderOidcConfig;
+use codex_core::auth::AuthCredentialsStoreMode;
+use codex_core::auth::load_oidc_tokens;
+use codex_login::complete_oidc_device_code_login;
+use codex_login::request_oidc_device_code;
+use serde_json::json;
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use tempfile::tempdir;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::Request;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::body_string_contains;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+use core_test_support::skip_if_no_network;
+
+async fn mock_discovery(server: &MockServer, with_device_endpoint: bool) {
+    let issuer = server.uri();
+    let mut document = json!({
+        "issuer": issuer,
+        "token_endpoint": format!("{issuer}/oauth2/v1/token"),
+    });
+    if with_device_endpoint {
+        document["device_authorization_endpoint"] =
+            json!(format!("{issuer}/oauth2/v1/device/authorize"));
+    }
+    Mock::given(method("GET"))
+        .and(path("/.well-known/openid-configuration"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(document))
+        .mount(server)
+        .await;
+}
+
+fn oidc(issuer: String) -> ProviderOidcConfig {
+    ProviderOidcConfig {
+        issuer,
+        client_id: "codex-cli".to_string(),
+        scopes: Some(vec!["openid".to_string(), "offline_access".to_string()]),
+        audience: Some("api://gateway".to_string()),
+    }
+}
+
+#[tokio::test]
+async fn oidc_device_code_login_polls_until_approved_and_stores_tokens() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let codex_home = tempdir().unwrap();
+    let server = MockServer::start().await;
+    mock_discovery(&server, true).await;
+    Mock::given(method("POST"))
+        .and(path("/oauth2/v1/device/authorize"))
+        .and(body_string_contains("client_id=codex-cli"))
+        .and(body_string_contains("scope=openid+offline_access"))
+        .and(body_string_contains("audience=api%3A%2F%2Fgateway"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "device_code": "device-123",
+            "user_code": "WDJB-MJHT",
+            "verification_uri": format!("{}/activate", server.uri()),
+            // Keeps the test from waiting between polls.
+            "interval": 0,
+            "expires_in": 600,
+        })))
+        .mount(&server)
+        .await;
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let counter = attempts.clone();
+    Mock::given(method("POST"))
+        .and(path("/oauth2/v1/token"))
+        .and(body_string_contains("device_code=device-123"))
+        .respond_with(move |_: &Request| {
+            if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                ResponseTemplate::new(400).set_body_json(json!({
+                    "error": "authorization_pending",
+                }))
+            } else {
+                ResponseTemplate::new(200).set_body_json(json!({
+                    "access_token": "access-123",
+                    "refresh_token": "refresh-123",
+                    "expires_in": 3600,
+                }))
+            }
+        })
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let config = oidc(server.uri());
+    let device_code = request_oidc_device_code(&config).await?;
+    assert_eq!(device_code.user_code, "WDJB-MJHT");
+    complete_oidc_device_code_login(
+        codex_home.path(),
+        AuthCredentialsStoreMode::File,
+        &config,
+        device_code,
+    )
+    .await?;
+
+    let tokens = load_oidc_tokens(codex_home.path(), AuthCredentialsStoreMode::File, &config)?
+        .context("tokens stored after login")?;
+    assert_eq!(tokens.access_token, "access-123");
+    assert_eq!(tokens.refresh_token.as_deref(), Some("refresh-123"));
+    assert!(tokens.expires_at.is_some());
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn oidc_device_code_login_reports_denied_authorization() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let codex_home = tempdir().unwrap();
+    let server = MockServer::start().await;
+    mock_discovery(&server, true).await;
+    Mock::given(method("POST"))
+        .and(path("/oauth2/v1/device/authorize"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "device_code": "device-123",
+            "user_code": "WDJB-MJHT",
+            "verification_uri": format!("{}/activate", server.uri()),
+            "interval": 0,
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/oauth2/v1/token"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+            "error": "access_denied",
+        })))
+        .mount(&server)
+        .await;
+
+    let config = oidc(server.uri());
+    let device_code = request_oidc_device_code(&config).await?;
+    let err = complete_oidc_device_code_login(
+        codex_home.path(),
+        AuthCredentialsStoreMode::File,
+        &config,
+        device_code,
+    )
+    .await
+    .expect_err("denied authorization should fail");
+
+    assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+    assert!(
+        load_oidc_tokens(codex_home.path(), AuthCredentialsStoreMode::File, &config)?.is_none()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn oidc_device_code_login_requires_device_authorization_endpoint() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = MockServer::start().await;
+    mock_discovery(&server, false).await;
+
+    let err = request_oidc_device_code(&oidc(server.uri()))
+        .await
+        .expect_err("issuer without device authorization should fail");
+
+    assert_eq!(err.kind(), std::io::ErrorKind::Unsupported);
+    Ok(())
+}
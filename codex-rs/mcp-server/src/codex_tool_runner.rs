@@ -222,6 +222,7 @@ async fn run_codex_tool_session_inner(
                         proposed_execpolicy_amendment: _,
                         parsed_cmd,
                         network_approval_context: _,
+                        explanation,
                     }) => {
                         handle_exec_approval_request(
                             command,
@@ -233,6 +234,7 @@ async fn run_codex_tool_session_inner(
                             event.id.clone(),
                             call_id,
                             parsed_cmd,
+                            explanation,
                             thread_id,
                         )
                         .await;
@@ -318,6 +320,10 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::AgentReasoningRawContentDelta(_)
                     | EventMsg::TurnStarted(_)
                     | EventMsg::TokenCount(_)
+                    | EventMsg::RequestBudget(_)
+                    | EventMsg::ModelRouted(_)
+                    | EventMsg::ReadPrefetchStats(_)
+                    | EventMsg::TurnLatency(_)
                     | EventMsg::AgentReasoning(_)
                     | EventMsg::AgentReasoningSectionBreak(_)
                     | EventMsg::McpToolCallBegin(_)
@@ -333,6 +339,8 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::ExecCommandEnd(_)
                     | EventMsg::BackgroundEvent(_)
                     | EventMsg::StreamError(_)
+                    | EventMsg::RetriesExhausted(_)
+                    | EventMsg::UsageQuotaExceeded(_)
                     | EventMsg::PatchApplyBegin(_)
                     | EventMsg::PatchApplyEnd(_)
                     | EventMsg::TurnDiff(_)
@@ -354,11 +362,17 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::SkillsUpdateAvailable
                     | EventMsg::UndoStarted(_)
                     | EventMsg::UndoCompleted(_)
+                    | EventMsg::CommitCompleted(_)
+                    | EventMsg::PullRequestCompleted(_)
                     | EventMsg::ExitedReviewMode(_)
                     | EventMsg::RequestUserInput(_)
                     | EventMsg::DynamicToolCallRequest(_)
                     | EventMsg::ContextCompacted(_)
                     | EventMsg::ThreadRolledBack(_)
+                    | EventMsg::ResumeSummarized(_)
+                    | EventMsg::ConversationForked(_)
+                    | EventMsg::QueuedUserMessagesUpdated(_)
+                    | EventMsg::BookmarkAdded(_)
                     | EventMsg::CollabAgentSpawnBegin(_)
                     | EventMsg::CollabAgentSpawnEnd(_)
                     | EventMsg::CollabAgentInteractionBegin(_)
@@ -369,6 +383,8 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::CollabCloseEnd(_)
                     | EventMsg::CollabResumeBegin(_)
                     | EventMsg::CollabResumeEnd(_)
+                    | EventMsg::DelegateBegin(_)
+                    | EventMsg::DelegateEnd(_)
                     | EventMsg::DeprecationNotice(_) => {
                         // For now, we do not do anything extra for these
                         // events. Note that
@@ -5,6 +5,7 @@ use codex_core::CodexThread;
 use codex_core::protocol::Op;
 use codex_core::protocol::ReviewDecision;
 use codex_protocol::ThreadId;
+use codex_protocol::approvals::CommandExplanation;
 use codex_protocol::parse_command::ParsedCommand;
 use rmcp::model::ErrorData;
 use rmcp::model::RequestId;
@@ -36,6 +37,8 @@ pub struct ExecApprovalElicitRequestParams {
     pub codex_command: Vec<String>,
     pub codex_cwd: PathBuf,
     pub codex_parsed_cmd: Vec<ParsedCommand>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub codex_explanation: Option<CommandExplanation>,
 }
 
 // TODO(mbolin): ExecApprovalResponse does not conform to ElicitResult. See:
@@ -58,15 +61,23 @@ pub(crate) async fn handle_exec_approval_request(
     event_id: String,
     call_id: String,
     codex_parsed_cmd: Vec<ParsedCommand>,
+    codex_explanation: Option<CommandExplanation>,
     thread_id: ThreadId,
 ) {
     let approval_id = call_id.clone();
     let escaped_command =
         shlex::try_join(command.iter().map(String::as_str)).unwrap_or_else(|_| command.join(" "));
-    let message = format!(
+    let mut message = format!(
         "Allow Codex to run `{escaped_command}` in `{cwd}`?",
         cwd = cwd.to_string_lossy()
     );
+    if let Some(explanation) = &codex_explanation {
+        message.push_str("\n\n");
+        message.push_str(&explanation.summary);
+        for operation in &explanation.destructive_operations {
+            message.push_str(&format!("\nDestructive: {operation}"));
+        }
+    }
 
     let params = ExecApprovalElicitRequestParams {
         message,
@@ -79,6 +90,7 @@ pub(crate) async fn handle_exec_approval_request(
         codex_command: command,
         codex_cwd: cwd,
         codex_parsed_cmd,
+        codex_explanation,
     };
     let params_json = match serde_json::to_value(&params) {
         Ok(value) => value,
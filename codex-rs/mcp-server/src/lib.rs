@@ -6,6 +6,8 @@ use std::io::Result as IoResult;
 use std::path::PathBuf;
 
 use codex_core::config::Config;
+use codex_core::default_client::set_default_client_http_config;
+use codex_core::default_client::set_default_client_offline;
 use codex_utils_cli::CliConfigOverrides;
 
 use rmcp::model::ClientNotification;
@@ -99,6 +101,8 @@ pub async fn run_main(
         .map_err(|e| {
             std::io::Error::new(ErrorKind::InvalidData, format!("error loading config: {e}"))
         })?;
+    set_default_client_offline(config.offline);
+    set_default_client_http_config(config.http_client.clone());
 
     // Task: process incoming messages.
     let processor_handle = tokio::spawn({
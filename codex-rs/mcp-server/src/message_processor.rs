@@ -53,7 +53,7 @@ impl MessageProcessor {
     ) -> Self {
         let outgoing = Arc::new(outgoing);
         let auth_manager = AuthManager::shared(
-            config.codex_home.clone(),
+            config.auth_home.clone(),
             false,
             config.cli_auth_credentials_store_mode,
         );
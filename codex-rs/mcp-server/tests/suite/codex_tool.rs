@@ -199,6 +199,7 @@ fn create_expected_elicitation_request_params(
         codex_cwd: workdir.to_path_buf(),
         codex_call_id: "call1234".to_string(),
         codex_parsed_cmd,
+        codex_explanation: None,
     })?;
     Ok(params_json)
 }
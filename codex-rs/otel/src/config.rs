@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 
 use codex_utils_absolute_path::AbsolutePathBuf;
@@ -38,6 +39,8 @@ pub struct OtelSettings {
     pub trace_exporter: OtelExporter,
     pub metrics_exporter: OtelExporter,
     pub runtime_metrics: bool,
+    /// Serve Prometheus text metrics on `GET /metrics` at this address.
+    pub prometheus_listen_addr: Option<SocketAddr>,
 }
 
 #[derive(Clone, Debug)]
@@ -86,6 +86,7 @@ struct MetricsClientInner {
     histograms: Mutex<HashMap<String, Histogram<f64>>>,
     duration_histograms: Mutex<HashMap<String, Histogram<f64>>>,
     runtime_reader: Option<Arc<ManualReader>>,
+    prometheus_reader: Option<Arc<ManualReader>>,
     default_tags: BTreeMap<String, String>,
 }
 
@@ -193,6 +194,7 @@ impl MetricsClient {
             exporter,
             export_interval,
             runtime_reader,
+            prometheus_reader,
             default_tags,
         } = config;
 
@@ -219,15 +221,34 @@ impl MetricsClient {
             )
         });
 
-        let (meter_provider, meter) = match exporter {
+        let prometheus_reader = prometheus_reader.then(|| {
+            Arc::new(
+                ManualReader::builder()
+                    .with_temporality(Temporality::Cumulative)
+                    .build(),
+            )
+        });
+
+        let mut provider_builder = SdkMeterProvider::builder().with_resource(resource);
+        for reader in [&runtime_reader, &prometheus_reader].into_iter().flatten() {
+            provider_builder =
+                provider_builder.with_reader(SharedManualReader::new(Arc::clone(reader)));
+        }
+        provider_builder = match exporter {
             MetricsExporter::InMemory(exporter) => {
-                build_provider(resource, exporter, export_interval, runtime_reader.clone())
+                provider_builder.with_reader(periodic_reader(exporter, export_interval))
+            }
+            // Scrape-only: nothing to push.
+            MetricsExporter::Otlp(OtelExporter::None) if prometheus_reader.is_some() => {
+                provider_builder
             }
             MetricsExporter::Otlp(exporter) => {
                 let exporter = build_otlp_metric_exporter(exporter, Temporality::Delta)?;
-                build_provider(resource, exporter, export_interval, runtime_reader.clone())
+                provider_builder.with_reader(periodic_reader(exporter, export_interval))
             }
         };
+        let meter_provider = provider_builder.build();
+        let meter = meter_provider.meter(METER_NAME);
 
         Ok(Self(std::sync::Arc::new(MetricsClientInner {
             meter_provider,
@@ -236,6 +257,7 @@ impl MetricsClient {
             histograms: Mutex::new(HashMap::new()),
             duration_histograms: Mutex::new(HashMap::new()),
             runtime_reader,
+            prometheus_reader,
             default_tags,
         })))
     }
@@ -284,6 +306,18 @@ impl MetricsClient {
         Ok(snapshot)
     }
 
+    /// Render the cumulative totals recorded so far in the Prometheus text format.
+    pub fn prometheus_text(&self) -> Result<String> {
+        let Some(reader) = &self.0.prometheus_reader else {
+            return Err(MetricsError::PrometheusUnavailable);
+        };
+        let mut snapshot = ResourceMetrics::default();
+        reader
+            .collect(&mut snapshot)
+            .map_err(|source| MetricsError::PrometheusCollect { source })?;
+        Ok(crate::metrics::prometheus::render(&snapshot))
+    }
+
     /// Flush metrics and stop the underlying OTEL meter provider.
     pub fn shutdown(&self) -> Result<()> {
         self.0.shutdown()
@@ -306,12 +340,7 @@ fn os_resource_attributes() -> Vec<KeyValue> {
     attributes
 }
 
-fn build_provider<E>(
-    resource: Resource,
-    exporter: E,
-    interval: Option<Duration>,
-    runtime_reader: Option<Arc<ManualReader>>,
-) -> (SdkMeterProvider, Meter)
+fn periodic_reader<E>(exporter: E, interval: Option<Duration>) -> PeriodicReader<E>
 where
     E: opentelemetry_sdk::metrics::exporter::PushMetricExporter + 'static,
 {
@@ -319,14 +348,7 @@ where
     if let Some(interval) = interval {
         reader_builder = reader_builder.with_interval(interval);
     }
-    let reader = reader_builder.build();
-    let mut provider_builder = SdkMeterProvider::builder().with_resource(resource);
-    if let Some(reader) = runtime_reader {
-        provider_builder = provider_builder.with_reader(SharedManualReader::new(reader));
-    }
-    let provider = provider_builder.with_reader(reader).build();
-    let meter = provider.meter(METER_NAME);
-    (provider, meter)
+    reader_builder.build()
 }
 
 fn build_otlp_metric_exporter(
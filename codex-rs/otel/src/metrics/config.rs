@@ -20,6 +20,7 @@ pub struct MetricsConfig {
     pub(crate) exporter: MetricsExporter,
     pub(crate) export_interval: Option<Duration>,
     pub(crate) runtime_reader: bool,
+    pub(crate) prometheus_reader: bool,
     pub(crate) default_tags: BTreeMap<String, String>,
 }
 
@@ -37,6 +38,7 @@ impl MetricsConfig {
             exporter: MetricsExporter::Otlp(exporter),
             export_interval: None,
             runtime_reader: false,
+            prometheus_reader: false,
             default_tags: BTreeMap::new(),
         }
     }
@@ -55,6 +57,7 @@ impl MetricsConfig {
             exporter: MetricsExporter::InMemory(exporter),
             export_interval: None,
             runtime_reader: false,
+            prometheus_reader: false,
             default_tags: BTreeMap::new(),
         }
     }
@@ -71,6 +74,13 @@ impl MetricsConfig {
         self
     }
 
+    /// Enable a cumulative reader for Prometheus scrapes. With this set, an
+    /// `OtelExporter::None` exporter is allowed and nothing is pushed.
+    pub fn with_prometheus_reader(mut self) -> Self {
+        self.prometheus_reader = true;
+        self
+    }
+
     /// Add a default tag that will be sent with every metric.
     pub fn with_tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Result<Self> {
         let key = key.into();
@@ -43,4 +43,13 @@ pub enum MetricsError {
         #[source]
         source: opentelemetry_sdk::error::OTelSdkError,
     },
+
+    #[error("prometheus metrics reader is not enabled")]
+    PrometheusUnavailable,
+
+    #[error("failed to collect metrics for prometheus")]
+    PrometheusCollect {
+        #[source]
+        source: opentelemetry_sdk::error::OTelSdkError,
+    },
 }
@@ -0,0 +1,382 @@
+//! Prometheus text exposition of the metrics recorded through [`MetricsClient`],
+//! served from a small HTTP listener for operators who scrape instead of push.
+
+use crate::metrics::MetricsClient;
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::metrics::data::AggregatedMetrics;
+use opentelemetry_sdk::metrics::data::Metric;
+use opentelemetry_sdk::metrics::data::MetricData;
+use opentelemetry_sdk::metrics::data::ResourceMetrics;
+use opentelemetry_sdk::metrics::data::ScopeMetrics;
+use std::fmt::Write as _;
+use std::io;
+use std::net::SocketAddr;
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use tiny_http::Header;
+use tiny_http::Method;
+use tiny_http::Response;
+use tiny_http::Server;
+use tiny_http::StatusCode;
+use tracing::warn;
+
+const METRICS_PATH: &str = "/metrics";
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// HTTP listener answering `GET /metrics`. Stops when dropped.
+pub struct PrometheusListener {
+    local_addr: SocketAddr,
+    server: Arc<Server>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl std::fmt::Debug for PrometheusListener {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PrometheusListener")
+            .field("local_addr", &self.local_addr)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PrometheusListener {
+    /// Bind `addr` and serve the metrics of `metrics`, which must have been
+    /// built with [`crate::metrics::MetricsConfig::with_prometheus_reader`].
+    pub fn bind(addr: SocketAddr, metrics: MetricsClient) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let server = Server::from_listener(listener, None)
+            .map_err(|err| io::Error::other(format!("failed to start metrics listener: {err}")))?;
+        let server = Arc::new(server);
+
+        let thread = std::thread::Builder::new()
+            .name("codex-prometheus".to_string())
+            .spawn({
+                let server = Arc::clone(&server);
+                move || {
+                    for request in server.incoming_requests() {
+                        let response = if request.method() != &Method::Get {
+                            Response::from_string("method not allowed")
+                                .with_status_code(StatusCode(405))
+                        } else if request.url().split('?').next() != Some(METRICS_PATH) {
+                            Response::from_string("not found").with_status_code(StatusCode(404))
+                        } else {
+                            match metrics.prometheus_text() {
+                                Ok(body) => {
+                                    let mut response = Response::from_string(body);
+                                    if let Ok(header) =
+                                        Header::from_bytes("Content-Type", CONTENT_TYPE)
+                                    {
+                                        response = response.with_header(header);
+                                    }
+                                    response
+                                }
+                                Err(err) => {
+                                    warn!("failed to collect metrics for scrape: {err}");
+                                    Response::from_string(err.to_string())
+                                        .with_status_code(StatusCode(500))
+                                }
+                            }
+                        };
+                        let _ = request.respond(response);
+                    }
+                }
+            })?;
+
+        Ok(Self {
+            local_addr,
+            server,
+            thread: Some(thread),
+        })
+    }
+
+    /// Address actually bound, useful when `addr` had port 0.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+impl Drop for PrometheusListener {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Render a cumulative snapshot in the Prometheus text format (version 0.0.4).
+pub(crate) fn render(metrics: &ResourceMetrics) -> String {
+    let mut out = String::new();
+    for metric in metrics.scope_metrics().flat_map(ScopeMetrics::metrics) {
+        match metric.data() {
+            AggregatedMetrics::U64(data) => render_metric(&mut out, metric, data),
+            AggregatedMetrics::I64(data) => render_metric(&mut out, metric, data),
+            AggregatedMetrics::F64(data) => render_metric(&mut out, metric, data),
+        }
+    }
+    out
+}
+
+fn render_metric<T: Copy + ToSample>(out: &mut String, metric: &Metric, data: &MetricData<T>) {
+    let name = metric_name(metric.name());
+    match data {
+        MetricData::Sum(sum) if sum.is_monotonic() => {
+            let name = format!("{name}_total");
+            write_header(out, &name, metric.description(), "counter");
+            for point in sum.data_points() {
+                write_sample(out, &name, point.attributes(), None, point.value());
+            }
+        }
+        MetricData::Sum(sum) => {
+            write_header(out, &name, metric.description(), "gauge");
+            for point in sum.data_points() {
+                write_sample(out, &name, point.attributes(), None, point.value());
+            }
+        }
+        MetricData::Gauge(gauge) => {
+            write_header(out, &name, metric.description(), "gauge");
+            for point in gauge.data_points() {
+                write_sample(out, &name, point.attributes(), None, point.value());
+            }
+        }
+        MetricData::Histogram(histogram) => {
+            write_header(out, &name, metric.description(), "histogram");
+            let bucket_name = format!("{name}_bucket");
+            for point in histogram.data_points() {
+                let mut cumulative = 0u64;
+                let mut counts = point.bucket_counts();
+                for bound in point.bounds() {
+                    cumulative += counts.next().unwrap_or_default();
+                    let le = format_f64(bound);
+                    write_sample(
+                        out,
+                        &bucket_name,
+                        point.attributes(),
+                        Some(le.as_str()),
+                        cumulative,
+                    );
+                }
+                write_sample(
+                    out,
+                    &bucket_name,
+                    point.attributes(),
+                    Some("+Inf"),
+                    point.count(),
+                );
+                write_sample(
+                    out,
+                    &format!("{name}_sum"),
+                    point.attributes(),
+                    None,
+                    point.sum(),
+                );
+                write_sample(
+                    out,
+                    &format!("{name}_count"),
+                    point.attributes(),
+                    None,
+                    point.count(),
+                );
+            }
+        }
+        // Codex does not record exponential histograms.
+        MetricData::ExponentialHistogram(_) => {}
+    }
+}
+
+fn write_header(out: &mut String, name: &str, description: &str, kind: &str) {
+    if !description.is_empty() {
+        let _ = writeln!(out, "# HELP {name} {}", escape_help(description));
+    }
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn write_sample<'a>(
+    out: &mut String,
+    name: &str,
+    attributes: impl Iterator<Item = &'a KeyValue>,
+    le: Option<&str>,
+    value: impl ToSample,
+) {
+    let mut labels: Vec<String> = attributes
+        .map(|kv| {
+            format!(
+                "{}=\"{}\"",
+                label_name(kv.key.as_str()),
+                escape_label_value(&kv.value.to_string())
+            )
+        })
+        .collect();
+    labels.sort();
+    if let Some(le) = le {
+        labels.push(format!("le=\"{le}\""));
+    }
+    if labels.is_empty() {
+        let _ = writeln!(out, "{name} {}", value.to_sample());
+    } else {
+        let _ = writeln!(out, "{name}{{{}}} {}", labels.join(","), value.to_sample());
+    }
+}
+
+/// Prometheus metric names allow `[a-zA-Z0-9_:]`, so `codex.tool.call`
+/// becomes `codex_tool_call`.
+fn metric_name(name: &str) -> String {
+    sanitize(name, |c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+}
+
+fn label_name(name: &str) -> String {
+    sanitize(name, |c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn sanitize(name: &str, allowed: impl Fn(char) -> bool) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| if allowed(c) { c } else { '_' })
+        .collect();
+    if sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+fn escape_help(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn format_f64(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+trait ToSample {
+    fn to_sample(self) -> String;
+}
+
+impl ToSample for f64 {
+    fn to_sample(self) -> String {
+        format_f64(self)
+    }
+}
+
+impl ToSample for u64 {
+    fn to_sample(self) -> String {
+        self.to_string()
+    }
+}
+
+impl ToSample for i64 {
+    fn to_sample(self) -> String {
+        self.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::MetricsConfig;
+    use opentelemetry_sdk::metrics::InMemoryMetricExporter;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn client() -> MetricsClient {
+        let config = MetricsConfig::in_memory(
+            "test",
+            "codex-test",
+            "1.0.0",
+            InMemoryMetricExporter::default(),
+        )
+        .with_prometheus_reader();
+        MetricsClient::new(config).expect("metrics client")
+    }
+
+    #[test]
+    fn renders_counters_and_histograms() {
+        let metrics = client();
+        metrics
+            .counter(
+                "codex.tool.call",
+                2,
+                &[("tool", "shell"), ("success", "true")],
+            )
+            .expect("counter");
+        metrics
+            .counter(
+                "codex.tool.call",
+                1,
+                &[("tool", "shell"), ("success", "true")],
+            )
+            .expect("counter");
+        metrics
+            .record_duration(
+                "codex.exec.duration_ms",
+                Duration::from_millis(12),
+                &[("sandbox", "none")],
+            )
+            .expect("duration");
+
+        let text = metrics.prometheus_text().expect("render");
+        assert!(text.contains("# TYPE codex_tool_call_total counter\n"));
+        assert!(text.contains("codex_tool_call_total{success=\"true\",tool=\"shell\"} 3\n"));
+        assert!(text.contains("# HELP codex_exec_duration_ms Duration in milliseconds.\n"));
+        assert!(text.contains("# TYPE codex_exec_duration_ms histogram\n"));
+        assert!(text.contains("codex_exec_duration_ms_bucket{sandbox=\"none\",le=\"10\"} 0\n"));
+        assert!(text.contains("codex_exec_duration_ms_bucket{sandbox=\"none\",le=\"25\"} 1\n"));
+        assert!(text.contains("codex_exec_duration_ms_bucket{sandbox=\"none\",le=\"+Inf\"} 1\n"));
+        assert!(text.contains("codex_exec_duration_ms_sum{sandbox=\"none\"} 12\n"));
+        assert!(text.contains("codex_exec_duration_ms_count{sandbox=\"none\"} 1\n"));
+
+        // Scrapes are cumulative: a second render still reports the totals.
+        assert_eq!(metrics.prometheus_text().expect("render"), text);
+    }
+
+    #[test]
+    fn listener_serves_metrics_path() {
+        let metrics = client();
+        metrics
+            .counter("codex.turn", 1, &[("kind", "regular")])
+            .expect("counter");
+        let listener =
+            PrometheusListener::bind("127.0.0.1:0".parse().expect("addr"), metrics).expect("bind");
+
+        let body = http_get(listener.local_addr(), "/metrics");
+        assert!(body.starts_with("HTTP/1.1 200"), "{body}");
+        assert!(body.contains("text/plain; version=0.0.4"), "{body}");
+        assert!(
+            body.contains("codex_turn_total{kind=\"regular\"} 1\n"),
+            "{body}"
+        );
+
+        let body = http_get(listener.local_addr(), "/other");
+        assert!(body.starts_with("HTTP/1.1 404"), "{body}");
+    }
+
+    fn http_get(addr: SocketAddr, path: &str) -> String {
+        use std::io::Read as _;
+        use std::io::Write as _;
+
+        let mut stream = std::net::TcpStream::connect(addr).expect("connect");
+        write!(
+            stream,
+            "GET {path} HTTP/1.1\r\nHost: {addr}\r\nConnection: close\r\n\r\n"
+        )
+        .expect("write request");
+        let mut response = String::new();
+        stream.read_to_string(&mut response).expect("read response");
+        response
+    }
+}
@@ -3,6 +3,7 @@ use crate::config::OtelHttpProtocol;
 use crate::config::OtelSettings;
 use crate::metrics::MetricsClient;
 use crate::metrics::MetricsConfig;
+use crate::metrics::PrometheusListener;
 use opentelemetry::Context;
 use opentelemetry::KeyValue;
 use opentelemetry::context::ContextGuard;
@@ -36,12 +37,15 @@ use std::sync::OnceLock;
 use tracing::debug;
 use tracing::level_filters::LevelFilter;
 use tracing::warn;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
 use tracing_subscriber::Layer;
 use tracing_subscriber::registry::LookupSpan;
 
 const ENV_ATTRIBUTE: &str = "env";
 const TRACEPARENT_ENV_VAR: &str = "TRACEPARENT";
 const TRACESTATE_ENV_VAR: &str = "TRACESTATE";
+pub const TRACEPARENT_KEY: &str = "traceparent";
+pub const TRACESTATE_KEY: &str = "tracestate";
 static TRACEPARENT_CONTEXT: OnceLock<Option<Context>> = OnceLock::new();
 
 thread_local! {
@@ -52,6 +56,7 @@ pub struct OtelProvider {
     pub tracer_provider: Option<SdkTracerProvider>,
     pub tracer: Option<Tracer>,
     pub metrics: Option<MetricsClient>,
+    pub prometheus: Option<PrometheusListener>,
 }
 
 impl OtelProvider {
@@ -72,7 +77,9 @@ impl OtelProvider {
         let trace_enabled = !matches!(settings.trace_exporter, OtelExporter::None);
 
         let metric_exporter = crate::config::resolve_exporter(&settings.metrics_exporter);
-        let metrics = if matches!(metric_exporter, OtelExporter::None) {
+        let metrics = if matches!(metric_exporter, OtelExporter::None)
+            && settings.prometheus_listen_addr.is_none()
+        {
             None
         } else {
             let mut config = MetricsConfig::otlp(
@@ -84,9 +91,28 @@ impl OtelProvider {
             if settings.runtime_metrics {
                 config = config.with_runtime_reader();
             }
+            if settings.prometheus_listen_addr.is_some() {
+                config = config.with_prometheus_reader();
+            }
             Some(MetricsClient::new(config)?)
         };
 
+        // Another Codex process may already own the port; keep the other
+        // exporters working rather than failing startup.
+        let prometheus = match (settings.prometheus_listen_addr, metrics.as_ref()) {
+            (Some(addr), Some(metrics)) => match PrometheusListener::bind(addr, metrics.clone()) {
+                Ok(listener) => {
+                    debug!("Serving Prometheus metrics on {}", listener.local_addr());
+                    Some(listener)
+                }
+                Err(err) => {
+                    warn!("failed to serve Prometheus metrics on {addr}: {err}");
+                    None
+                }
+            },
+            _ => None,
+        };
+
         if let Some(metrics) = metrics.as_ref() {
             crate::metrics::install_global(metrics.clone());
         }
@@ -122,6 +148,7 @@ impl OtelProvider {
             tracer_provider,
             tracer,
             metrics,
+            prometheus,
         }))
     }
 
@@ -176,6 +203,33 @@ pub(crate) fn traceparent_context_from_env() -> Option<Context> {
         .clone()
 }
 
+/// W3C trace context (`traceparent` and, when present, `tracestate`) of the
+/// current span, for continuing the trace in another process. Returns `None`
+/// when the current span is not being exported.
+pub fn current_span_trace_context() -> Option<HashMap<String, String>> {
+    let context = tracing::Span::current().context();
+    if !context.span().span_context().is_valid() {
+        return None;
+    }
+    let mut carrier = HashMap::new();
+    TraceContextPropagator::new().inject_context(&context, &mut carrier);
+    Some(carrier)
+}
+
+/// Parent `span` on a W3C trace context received from another process, as
+/// produced by [`current_span_trace_context`]. Invalid contexts are ignored.
+pub fn set_parent_from_trace_context(
+    span: &tracing::Span,
+    traceparent: &str,
+    tracestate: Option<&str>,
+) {
+    if let Some(context) =
+        extract_traceparent_context(traceparent.to_string(), tracestate.map(str::to_string))
+    {
+        let _ = span.set_parent(context);
+    }
+}
+
 fn attach_traceparent_context() {
     TRACEPARENT_GUARD.with(|guard| {
         let mut guard = guard.borrow_mut();
@@ -206,9 +260,9 @@ fn load_traceparent_context() -> Option<Context> {
 
 fn extract_traceparent_context(traceparent: String, tracestate: Option<String>) -> Option<Context> {
     let mut headers = HashMap::new();
-    headers.insert("traceparent".to_string(), traceparent);
+    headers.insert(TRACEPARENT_KEY.to_string(), traceparent);
     if let Some(tracestate) = tracestate {
-        headers.insert("tracestate".to_string(), tracestate);
+        headers.insert(TRACESTATE_KEY.to_string(), tracestate);
     }
 
     let context = TraceContextPropagator::new().extract(&headers);
@@ -398,4 +452,31 @@ mod tests {
     fn invalid_traceparent_returns_none() {
         assert!(extract_traceparent_context("not-a-traceparent".to_string(), None).is_none());
     }
+
+    #[test]
+    fn trace_context_round_trips_through_carrier() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let provider = SdkTracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry().with(
+            tracing_opentelemetry::layer().with_tracer(provider.tracer("trace-context-test")),
+        );
+        tracing::subscriber::with_default(subscriber, || {
+            assert_eq!(current_span_trace_context(), None);
+
+            let parent = tracing::info_span!("parent");
+            let parent_trace_id = parent.context().span().span_context().trace_id();
+            let carrier = parent
+                .in_scope(current_span_trace_context)
+                .expect("trace context");
+            let traceparent = carrier.get(TRACEPARENT_KEY).expect("traceparent");
+
+            let child = tracing::info_span!("child");
+            set_parent_from_trace_context(&child, traceparent, None);
+            assert_eq!(
+                child.context().span().span_context().trace_id(),
+                parent_trace_id
+            );
+        });
+    }
 }
@@ -750,6 +750,7 @@ impl OtelManager {
             }
             ResponseEvent::ServerReasoningIncluded(_) => "server_reasoning_included".into(),
             ResponseEvent::RateLimits(_) => "rate_limits".into(),
+            ResponseEvent::RequestBudget(_) => "request_budget".into(),
             ResponseEvent::ModelsEtag(_) => "models_etag".into(),
         }
     }
@@ -55,6 +55,17 @@ pub struct NetworkApprovalContext {
     pub protocol: NetworkApprovalProtocol,
 }
 
+/// Model-written explanation of a command, attached to its approval request
+/// when `[approval_explanations]` is enabled.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct CommandExplanation {
+    /// One plain-English paragraph on what the command does.
+    pub summary: String,
+    /// Operations that delete, overwrite, or otherwise cannot be undone, e.g.
+    /// "deletes the build/ directory". Empty when there are none.
+    pub destructive_operations: Vec<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ExecApprovalRequestEvent {
     /// Identifier for the associated exec call, if available.
@@ -79,6 +90,10 @@ pub struct ExecApprovalRequestEvent {
     #[ts(optional)]
     pub proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
     pub parsed_cmd: Vec<ParsedCommand>,
+    /// Explanation of the command for the user, when one was generated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub explanation: Option<CommandExplanation>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
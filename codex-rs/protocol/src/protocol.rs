@@ -18,6 +18,7 @@ use crate::config_types::CollaborationMode;
 use crate::config_types::ModeKind;
 use crate::config_types::Personality;
 use crate::config_types::ReasoningSummary as ReasoningSummaryConfig;
+use crate::config_types::Verbosity;
 use crate::config_types::WindowsSandboxLevel;
 use crate::custom_prompts::CustomPrompt;
 use crate::dynamic_tools::DynamicToolCallRequest;
@@ -51,6 +52,7 @@ use tracing::error;
 use ts_rs::TS;
 
 pub use crate::approvals::ApplyPatchApprovalRequestEvent;
+pub use crate::approvals::CommandExplanation;
 pub use crate::approvals::ElicitationAction;
 pub use crate::approvals::ExecApprovalRequestEvent;
 pub use crate::approvals::ExecPolicyAmendment;
@@ -97,6 +99,13 @@ pub enum Op {
     /// Terminate all running background terminal processes for this thread.
     CleanBackgroundTerminals,
 
+    /// Stop everything this thread is running: abort the turn and its model
+    /// requests, terminate background terminals and MCP server processes
+    /// (including the exec server), then refuse new turns. The rollout is
+    /// flushed so the thread can be resumed later in a new session. Also
+    /// triggered by creating `$CODEX_HOME/STOP`.
+    EmergencyStop,
+
     /// Legacy user input.
     ///
     /// Prefer [`Op::UserTurn`] so the caller provides full turn context
@@ -197,6 +206,33 @@ pub enum Op {
         personality: Option<Personality>,
     },
 
+    /// Change the model, reasoning effort, or verbosity used by subsequent
+    /// turns without restarting the session. Omitted fields keep their
+    /// current value; the new settings are recorded in the rollout with the
+    /// next turn.
+    OverrideTurnSettings {
+        /// Updated model slug. The reasoning effort is kept if the new model
+        /// supports it.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        model: Option<String>,
+
+        /// Use `Some(Some(_))` to set a specific effort, `Some(None)` to use
+        /// the model's default, or `None` to leave it unchanged.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        effort: Option<Option<ReasoningEffortConfig>>,
+
+        /// Use `Some(Some(_))` to set a specific verbosity, `Some(None)` to
+        /// use the model's default, or `None` to leave it unchanged. Ignored
+        /// by models that do not support verbosity.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        verbosity: Option<Option<Verbosity>>,
+    },
+
+    /// Run the next turn on the given model tier instead of letting
+    /// `model_routing` pick one. Applies to one turn only and is ignored when
+    /// model routing is not configured.
+    RouteNextTurn { tier: ModelTier },
+
     /// Approve a command execution
     ExecApproval {
         /// The id of the submission we are approving
@@ -235,6 +271,22 @@ pub enum Op {
         response: RequestUserInputResponse,
     },
 
+    /// Resolve a `RetriesExhausted` event.
+    RetryExhaustedDecision {
+        /// Turn id from the event.
+        id: String,
+        /// How the turn continues.
+        decision: RetryExhaustedDecision,
+    },
+
+    /// Resolve a `UsageQuotaExceeded` event.
+    UsageQuotaOverride {
+        /// Turn id from the event.
+        id: String,
+        /// True to keep going past the quota for the rest of the session.
+        approve: bool,
+    },
+
     /// Resolve a dynamic tool call request.
     DynamicToolResponse {
         /// Call id for the in-flight request.
@@ -268,6 +320,32 @@ pub enum Op {
     /// enable/disable state) without restarting the thread.
     ReloadUserConfig,
 
+    /// Start or stop recording model API requests and responses in the
+    /// session's wire log (`--wire-log <path>`, or
+    /// `<log_dir>/wire-<thread_id>.jsonl` when no path was given). Credentials
+    /// are redacted. The outcome is reported via `EventMsg::BackgroundEvent`.
+    SetWireLog { enabled: bool },
+
+    /// Narrow the session to these sub-directories of its working directory
+    /// (`--scope`), or widen it back to the whole working directory when
+    /// `paths` is empty. Relative paths resolve against the working
+    /// directory. Takes effect from the next turn; the outcome is reported via
+    /// `EventMsg::BackgroundEvent`.
+    SetScope { paths: Vec<PathBuf> },
+
+    /// Report whether `path` is excluded from file search, `grep_files`, the
+    /// repository brief and the file watcher by the `.gitignore` and
+    /// `.codexignore` rules of the working directory, and by which pattern.
+    /// Relative paths resolve against the working directory. The answer is
+    /// reported via `EventMsg::BackgroundEvent`.
+    ExplainIgnore { path: PathBuf },
+
+    /// Gather the context pack `name` from `[context_packs]` in config.toml
+    /// and add it to the conversation, where the next turn sees it. The
+    /// outcome is reported via `EventMsg::BackgroundEvent`, or
+    /// `EventMsg::Error` when the pack is unknown or fails.
+    AddContextPack { name: String },
+
     /// Request the list of available custom prompts.
     ListCustomPrompts,
 
@@ -309,15 +387,73 @@ pub enum Op {
     /// involve the model.
     SetThreadName { name: String },
 
+    /// Record the client's queue of user messages that are waiting for the
+    /// running turn to finish, so the queue can be restored when the thread is
+    /// resumed. This is a local-only operation; nothing is sent to the model.
+    /// Echoed back as `EventMsg::QueuedUserMessagesUpdated`.
+    SetQueuedUserMessages { messages: Vec<String> },
+
+    /// Bookmark a turn, or one cell of it, with an optional note so it can be
+    /// found again when reviewing the thread. This is a local-only operation;
+    /// nothing is sent to the model. Echoed back as `EventMsg::BookmarkAdded`.
+    AddBookmark {
+        /// 1-based turn number, counting user messages.
+        turn: u32,
+        /// First line of the bookmarked cell, identifying it within the turn.
+        excerpt: String,
+        note: Option<String>,
+    },
+
     /// Request Codex to undo a turn (turn are stacked so it is the same effect as CMD + Z).
     Undo,
 
+    /// Restore files changed by `apply_patch` from the session's backups,
+    /// independent of git. With no `paths`, every file changed by the most
+    /// recent patch is restored; otherwise each listed path (absolute or
+    /// relative to the session cwd) is restored to its version before the
+    /// latest patch that changed it. Reported via `EventMsg::UndoStarted` and
+    /// `EventMsg::UndoCompleted`.
+    UndoFileChange {
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        paths: Vec<PathBuf>,
+    },
+
+    /// Stage the files changed during this session and commit them, with a
+    /// message generated from the session's changes plus any configured
+    /// `Co-authored-by` trailers. The user is always asked to approve the
+    /// commit through an `EventMsg::ExecApprovalRequest`. Completion is
+    /// reported via `EventMsg::CommitCompleted`.
+    CommitChanges {
+        /// Commit message to use instead of the generated one. Configured
+        /// trailers are still appended.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+    },
+
+    /// Push the current branch and open a GitHub pull request (or GitLab merge
+    /// request) against the default branch. The title and description are
+    /// derived from the session and link its thread id. The user is always
+    /// asked to approve the push through an `EventMsg::ExecApprovalRequest`.
+    /// Completion is reported via `EventMsg::PullRequestCompleted`.
+    CreatePullRequest {
+        /// Title to use instead of the one derived from the session.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+    },
+
     /// Request Codex to drop the last N user turns from in-memory context.
     ///
     /// This does not attempt to revert local filesystem changes. Clients are
     /// responsible for undoing any edits on disk.
     ThreadRollback { num_turns: u32 },
 
+    /// Fork this thread into a new thread with its own rollout, keeping the
+    /// history before the `nth_user_message`-th user message (0-based).
+    ///
+    /// The current thread is left untouched. The new thread is reported via
+    /// `EventMsg::ConversationForked`.
+    ForkConversation { nth_user_message: u32 },
+
     /// Request a code review from the agent.
     Review { review_request: ReviewRequest },
 
@@ -886,9 +1022,16 @@ pub enum EventMsg {
     /// Conversation history was compacted (either automatically or manually).
     ContextCompacted(ContextCompactedEvent),
 
+    /// Older turns of a resumed session were replaced by a summary
+    /// (`[resume_summary]`).
+    ResumeSummarized(ResumeSummarizedEvent),
+
     /// Conversation history was rolled back by dropping the last N user turns.
     ThreadRolledBack(ThreadRolledBackEvent),
 
+    /// Result of an `Op::ForkConversation` request.
+    ConversationForked(ConversationForkedEvent),
+
     /// Agent has started a turn.
     /// v1 wire format uses `task_started`; accept `turn_started` for v2 interop.
     #[serde(rename = "task_started", alias = "turn_started")]
@@ -903,6 +1046,21 @@ pub enum EventMsg {
     /// Optional means unknown — UIs should not display when `None`.
     TokenCount(TokenCountEvent),
 
+    /// Provider request/token budget parsed from `x-ratelimit-*` headers, and
+    /// any delay Codex added before the next request to stay within it.
+    RequestBudget(RequestBudgetEvent),
+
+    /// Model picked for the turn by `model_routing`.
+    ModelRouted(ModelRoutedEvent),
+
+    /// Activity of the `read_file` prefetch cache during the turn
+    /// (`read_prefetch` feature).
+    ReadPrefetchStats(ReadPrefetchStatsEvent),
+
+    /// Where the turn's time went, by pipeline stage. Sent just before
+    /// `TurnComplete`.
+    TurnLatency(TurnLatencyEvent),
+
     /// Agent text output message
     AgentMessage(AgentMessageEvent),
 
@@ -932,6 +1090,12 @@ pub enum EventMsg {
     /// Updated session metadata (e.g., thread name changes).
     ThreadNameUpdated(ThreadNameUpdatedEvent),
 
+    /// The client's queue of pending user messages changed.
+    QueuedUserMessagesUpdated(QueuedUserMessagesUpdatedEvent),
+
+    /// A turn or one of its cells was bookmarked.
+    BookmarkAdded(BookmarkAddedEvent),
+
     /// Incremental MCP startup progress updates.
     McpStartupUpdate(McpStartupUpdateEvent),
 
@@ -980,10 +1144,24 @@ pub enum EventMsg {
 
     UndoCompleted(UndoCompletedEvent),
 
+    /// Result of an `Op::CommitChanges` request.
+    CommitCompleted(CommitCompletedEvent),
+
+    /// Result of an `Op::CreatePullRequest` request.
+    PullRequestCompleted(PullRequestCompletedEvent),
+
     /// Notification that a model stream experienced an error or disconnect
     /// and the system is handling it (e.g., retrying with backoff).
     StreamError(StreamErrorEvent),
 
+    /// A model request failed after all of its retries; the turn waits for an
+    /// `Op::RetryExhaustedDecision`.
+    RetriesExhausted(RetriesExhaustedEvent),
+
+    /// The active profile has used up a daily or weekly quota; the turn waits
+    /// for an `Op::UsageQuotaOverride`.
+    UsageQuotaExceeded(UsageQuotaExceededEvent),
+
     /// Notification that the agent is about to apply a code patch. Mirrors
     /// `ExecCommandBegin` so front‑ends can show progress indicators.
     PatchApplyBegin(PatchApplyBeginEvent),
@@ -1057,6 +1235,10 @@ pub enum EventMsg {
     CollabResumeBegin(CollabResumeBeginEvent),
     /// Collab interaction: resume end.
     CollabResumeEnd(CollabResumeEndEvent),
+    /// A `delegate` tool call started a sub-session for one of its tasks.
+    DelegateBegin(DelegateBeginEvent),
+    /// A delegated sub-session finished, failed, or ran out of budget.
+    DelegateEnd(DelegateEndEvent),
 }
 
 impl From<CollabAgentSpawnBeginEvent> for EventMsg {
@@ -1119,6 +1301,18 @@ impl From<CollabResumeEndEvent> for EventMsg {
     }
 }
 
+impl From<DelegateBeginEvent> for EventMsg {
+    fn from(event: DelegateBeginEvent) -> Self {
+        EventMsg::DelegateBegin(event)
+    }
+}
+
+impl From<DelegateEndEvent> for EventMsg {
+    fn from(event: DelegateEndEvent) -> Self {
+        EventMsg::DelegateEnd(event)
+    }
+}
+
 /// Agent lifecycle status, derived from emitted events.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS, Default)]
 #[serde(rename_all = "snake_case")]
@@ -1345,6 +1539,16 @@ pub struct WarningEvent {
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ContextCompactedEvent;
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ResumeSummarizedEvent {
+    /// Summary the model sees in place of the older turns.
+    pub summary: String,
+    /// Number of user turns folded into the summary.
+    pub summarized_turns: u32,
+    /// Number of most recent user turns kept verbatim.
+    pub kept_turns: u32,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct TurnCompleteEvent {
     pub turn_id: String,
@@ -1476,6 +1680,100 @@ pub struct CreditsSnapshot {
     pub balance: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct RequestBudgetEvent {
+    pub budget: RequestBudgetSnapshot,
+    /// Set when the next model request is held back for this long so that it
+    /// fits the budget.
+    #[ts(type = "number | null")]
+    pub delay_ms: Option<u64>,
+}
+
+/// Short-window provider limits (requests and tokens per minute), as reported
+/// by the `x-ratelimit-{limit,remaining,reset}-{requests,tokens}` headers.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct RequestBudgetSnapshot {
+    pub requests: Option<RequestBudgetWindow>,
+    pub tokens: Option<RequestBudgetWindow>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct RequestBudgetWindow {
+    #[ts(type = "number")]
+    pub limit: u64,
+    #[ts(type = "number")]
+    pub remaining: u64,
+    /// Milliseconds until `remaining` is back to `limit`.
+    #[ts(type = "number")]
+    pub resets_in_ms: u64,
+}
+
+/// Model tier a turn runs on when `model_routing` is configured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelTier {
+    /// The cheaper model configured as `model_routing.fast_model`.
+    Fast,
+    /// The session's configured model.
+    Flagship,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ModelRoutedEvent {
+    /// Model the turn runs on.
+    pub model: String,
+    pub tier: ModelTier,
+    /// Why the router picked this tier.
+    pub reason: String,
+    /// Whether the tier came from `Op::RouteNextTurn` rather than the router.
+    pub overridden: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ReadPrefetchStatsEvent {
+    /// `read_file` calls served from the prefetch cache.
+    pub hits: u64,
+    /// `read_file` calls that had to read the file from disk.
+    pub misses: u64,
+    /// Files read into the cache in the background.
+    pub prefetched_files: u64,
+    /// Files in the cache at the end of the turn.
+    pub cached_files: u64,
+    /// Bytes held by the cache at the end of the turn.
+    pub cached_bytes: u64,
+}
+
+/// Stage durations are summed across calls, so parallel tool calls (and tools
+/// running while the model streams) can add up to more than `total_ms`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct TurnLatencyEvent {
+    pub turn_id: String,
+    /// From the turn being submitted to it finishing.
+    pub total_ms: u64,
+    /// From the turn being submitted to its task starting.
+    pub queue_wait_ms: u64,
+    /// From sending each model request to its first streamed event.
+    pub model_ttfb_ms: u64,
+    /// From each response's first streamed event to its last.
+    pub model_stream_ms: u64,
+    pub model_requests: u32,
+    /// Running tool calls, including the sandbox, sidecar and approval time
+    /// below.
+    pub tool_dispatch_ms: u64,
+    pub tool_calls: u32,
+    /// The part of `tool_dispatch_ms` spent while a model response was still
+    /// streaming, for tool calls started before it finished.
+    pub tool_stream_overlap_ms: u64,
+    /// Preparing sandboxed attempts before the tool runs.
+    pub sandbox_setup_ms: u64,
+    /// Requests to the `py_repl`, `js_repl` and browser sidecar processes.
+    pub sidecar_round_trip_ms: u64,
+    pub sidecar_calls: u32,
+    /// Waiting for the user to answer approval prompts.
+    pub approval_wait_ms: u64,
+    pub approvals: u32,
+}
+
 // Includes prompts, tools and space to call compact.
 const BASELINE_TOKENS: i64 = 12000;
 
@@ -1965,6 +2263,8 @@ pub struct TurnContextItem {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub effort: Option<ReasoningEffortConfig>,
     pub summary: ReasoningSummaryConfig,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verbosity: Option<Verbosity>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user_instructions: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -2172,6 +2472,27 @@ pub struct ExecCommandEndEvent {
     pub formatted_output: String,
     /// Completion status for this command execution.
     pub status: ExecCommandStatus,
+    /// Failing tests found in the output of a failed `cargo test`, `pytest`,
+    /// `jest` or `go test` run.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub test_failures: Vec<TestFailure>,
+}
+
+/// A failing test reported by a test runner.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct TestFailure {
+    /// Test name as the runner prints it.
+    pub name: String,
+    /// File the failure was reported in, when the runner names one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub file: Option<String>,
+    /// Line in `file` the failure was reported at.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub line: Option<u32>,
+    /// First line of the failure message; empty when the runner gave none.
+    pub message: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -2241,12 +2562,45 @@ pub struct UndoCompletedEvent {
     pub message: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct CommitCompletedEvent {
+    pub success: bool,
+    /// SHA of the new commit, when one was created.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_sha: Option<String>,
+    /// Human-readable outcome, e.g. the commit subject or why nothing was committed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct PullRequestCompletedEvent {
+    pub success: bool,
+    /// Web URL of the new pull/merge request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Human-readable outcome, e.g. the request title or why it could not be opened.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ThreadRolledBackEvent {
     /// Number of user turns that were removed from context.
     pub num_turns: u32,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ConversationForkedEvent {
+    /// Id of the newly created thread.
+    pub thread_id: ThreadId,
+    /// Rollout file of the new thread, when session persistence is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rollout_path: Option<PathBuf>,
+    /// The user message the fork was cut before (0-based).
+    pub nth_user_message: u32,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct StreamErrorEvent {
     pub message: String,
@@ -2259,6 +2613,51 @@ pub struct StreamErrorEvent {
     pub additional_details: Option<String>,
 }
 
+/// Sent when a model request still fails after all of its stream retries and
+/// `retry_budget.on_exhausted` is `ask`. The turn waits for an
+/// `Op::RetryExhaustedDecision` with this `turn_id`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct RetriesExhaustedEvent {
+    pub turn_id: String,
+    /// The error from the last attempt.
+    pub message: String,
+    /// Retries made after the first attempt failed.
+    pub retries: u64,
+    /// Model offered by `switch_model`, from `retry_budget.fallback_model`.
+    pub fallback_model: Option<String>,
+    /// Describes what `drop_largest_context_item` would remove; `None` when
+    /// there is nothing to drop.
+    pub largest_context_item: Option<String>,
+}
+
+/// How a turn continues after [`RetriesExhaustedEvent`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type")]
+pub enum RetryExhaustedDecision {
+    /// Send the same request again.
+    RetryNow,
+    /// Send the request to `model` for the rest of the turn.
+    SwitchModel { model: String },
+    /// Replace the largest tool output in the history with a short note, then
+    /// send the request again.
+    DropLargestContextItem,
+    /// End the turn with the error.
+    Abort,
+}
+
+/// Sent before a model request when the active profile has used up one of
+/// its `[profiles.<name>.quota]` limits. The turn waits for an
+/// `Op::UsageQuotaOverride` with this `turn_id`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct UsageQuotaExceededEvent {
+    pub turn_id: String,
+    pub profile: String,
+    /// The limits that were reached, e.g. "2,150,000 of 2,000,000 daily
+    /// tokens".
+    pub message: String,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct StreamInfoEvent {
     pub message: String,
@@ -2276,6 +2675,23 @@ pub struct PatchApplyBeginEvent {
     pub auto_approved: bool,
     /// The changes to be applied.
     pub changes: HashMap<PathBuf, FileChange>,
+    /// SHA-256 digests of each changed file before and after the patch,
+    /// keyed like `changes`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub content_hashes: HashMap<PathBuf, FileContentHashes>,
+}
+
+/// SHA-256 digests of a file's content around a patch, as lowercase hex.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct FileContentHashes {
+    /// Digest before the patch; absent when the file did not exist.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub before: Option<String>,
+    /// Digest after the patch; absent when the patch deletes the file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub after: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -2347,8 +2763,12 @@ pub struct McpStartupUpdateEvent {
 pub enum McpStartupStatus {
     Starting,
     Ready,
-    Failed { error: String },
+    Failed {
+        error: String,
+    },
     Cancelled,
+    /// Not started yet; starts once its tools are first needed.
+    Lazy,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, Default)]
@@ -2568,6 +2988,23 @@ pub struct ThreadNameUpdatedEvent {
     pub thread_name: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct QueuedUserMessagesUpdatedEvent {
+    /// Queued messages in the order they will be sent.
+    pub messages: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct BookmarkAddedEvent {
+    /// 1-based turn number, counting user messages.
+    pub turn: u32,
+    /// First line of the bookmarked cell, identifying it within the turn.
+    pub excerpt: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub note: Option<String>,
+}
+
 /// User's decision in response to an ExecApprovalRequest.
 #[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq, Display, JsonSchema, TS)]
 #[serde(rename_all = "snake_case")]
@@ -2768,6 +3205,40 @@ pub struct CollabResumeEndEvent {
     pub status: AgentStatus,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct DelegateBeginEvent {
+    /// Identifier for the delegate tool call.
+    pub call_id: String,
+    /// Zero-based index of the task within the tool call.
+    pub task_index: u32,
+    /// Prompt the sub-session was started with.
+    pub prompt: String,
+    /// Model the sub-session runs on.
+    pub model: String,
+    /// Configured agent role the sub-session runs as, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct DelegateEndEvent {
+    /// Identifier for the delegate tool call.
+    pub call_id: String,
+    /// Zero-based index of the task within the tool call.
+    pub task_index: u32,
+    /// Prompt the sub-session was started with.
+    pub prompt: String,
+    /// Model the sub-session ran on.
+    pub model: String,
+    /// Configured agent role the sub-session ran as, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    /// Final status of the sub-session. `Completed` carries its last message.
+    pub status: AgentStatus,
+    /// Tokens used by the sub-session.
+    pub total_tokens: i64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3023,6 +3494,7 @@ mod tests {
             collaboration_mode: None,
             effort: None,
             summary: ReasoningSummaryConfig::Auto,
+            verbosity: None,
             user_instructions: None,
             developer_instructions: None,
             final_output_json_schema: None,
@@ -0,0 +1,15 @@
+CREATE TABLE indexed_rollouts (
+    thread_id TEXT PRIMARY KEY,
+    rollout_path TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    lines INTEGER NOT NULL,
+    turns INTEGER NOT NULL
+);
+
+CREATE VIRTUAL TABLE history_entries USING fts5(
+    text,
+    thread_id UNINDEXED,
+    turn UNINDEXED,
+    kind UNINDEXED,
+    tokenize = 'unicode61'
+);
@@ -0,0 +1,362 @@
+//! Full-text index over rollout contents.
+//!
+//! The index lives in its own SQLite file (`history_index.sqlite`) so it works
+//! whether or not the state DB is enabled. Rollouts are indexed incrementally:
+//! `indexed_rollouts` records how many lines of each rollout have been read, so
+//! a refresh only parses what was appended since. Extracting entries from
+//! rollout items is left to `codex-core`.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_protocol::ThreadId;
+use log::LevelFilter;
+use sqlx::ConnectOptions;
+use sqlx::Row;
+use sqlx::SqlitePool;
+use sqlx::migrate::Migrator;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::sqlite::SqliteJournalMode;
+use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::sqlite::SqliteSynchronous;
+
+pub const HISTORY_INDEX_FILENAME: &str = "history_index.sqlite";
+
+static HISTORY_INDEX_MIGRATOR: Migrator = sqlx::migrate!("./history_index_migrations");
+
+/// What an indexed piece of text was in the session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryEntryKind {
+    UserMessage,
+    AgentMessage,
+    Command,
+    FilePath,
+}
+
+impl HistoryEntryKind {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            HistoryEntryKind::UserMessage => "user",
+            HistoryEntryKind::AgentMessage => "agent",
+            HistoryEntryKind::Command => "command",
+            HistoryEntryKind::FilePath => "file",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "user" => Some(HistoryEntryKind::UserMessage),
+            "agent" => Some(HistoryEntryKind::AgentMessage),
+            "command" => Some(HistoryEntryKind::Command),
+            "file" => Some(HistoryEntryKind::FilePath),
+            _ => None,
+        }
+    }
+}
+
+/// One searchable piece of a session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    /// 1-based user turn the entry belongs to; 0 before the first user message.
+    pub turn: u32,
+    pub kind: HistoryEntryKind,
+    pub text: String,
+}
+
+/// How far a rollout has been indexed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedRollout {
+    pub rollout_path: PathBuf,
+    /// File size when it was last indexed; an unchanged size skips the file.
+    pub size: u64,
+    /// Complete JSONL lines read so far.
+    pub lines: u64,
+    /// User turns seen so far.
+    pub turns: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistorySearchHit {
+    pub thread_id: ThreadId,
+    pub rollout_path: PathBuf,
+    pub turn: u32,
+    pub kind: HistoryEntryKind,
+    /// Matching text with the matched terms in `[` `]`.
+    pub snippet: String,
+}
+
+#[derive(Clone)]
+pub struct HistoryIndex {
+    pool: SqlitePool,
+}
+
+impl HistoryIndex {
+    /// Open (creating and migrating if needed) the index under `codex_home`.
+    pub async fn open(codex_home: &Path) -> anyhow::Result<Self> {
+        tokio::fs::create_dir_all(codex_home).await?;
+        let options = SqliteConnectOptions::new()
+            .filename(codex_home.join(HISTORY_INDEX_FILENAME))
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .synchronous(SqliteSynchronous::Normal)
+            .busy_timeout(Duration::from_secs(5))
+            .log_statements(LevelFilter::Off);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(2)
+            .connect_with(options)
+            .await?;
+        HISTORY_INDEX_MIGRATOR.run(&pool).await?;
+        Ok(Self { pool })
+    }
+
+    pub async fn indexed_rollout(
+        &self,
+        thread_id: ThreadId,
+    ) -> anyhow::Result<Option<IndexedRollout>> {
+        let row = sqlx::query(
+            r#"
+SELECT rollout_path, size, lines, turns
+FROM indexed_rollouts
+WHERE thread_id = ?
+            "#,
+        )
+        .bind(thread_id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+        row.map(|row| {
+            Ok(IndexedRollout {
+                rollout_path: PathBuf::from(row.try_get::<String, _>("rollout_path")?),
+                size: u64::try_from(row.try_get::<i64, _>("size")?)?,
+                lines: u64::try_from(row.try_get::<i64, _>("lines")?)?,
+                turns: u32::try_from(row.try_get::<i64, _>("turns")?)?,
+            })
+        })
+        .transpose()
+    }
+
+    /// Adds `entries` for a thread and records its new progress in one
+    /// transaction. With `reset`, the thread's existing entries are dropped
+    /// first (the rollout moved or was rewritten).
+    pub async fn append(
+        &self,
+        thread_id: ThreadId,
+        progress: &IndexedRollout,
+        entries: &[HistoryEntry],
+        reset: bool,
+    ) -> anyhow::Result<()> {
+        let thread_id = thread_id.to_string();
+        let mut tx = self.pool.begin().await?;
+        if reset {
+            sqlx::query("DELETE FROM history_entries WHERE thread_id = ?")
+                .bind(thread_id.as_str())
+                .execute(&mut *tx)
+                .await?;
+        }
+        for entry in entries {
+            sqlx::query(
+                r#"
+INSERT INTO history_entries (text, thread_id, turn, kind)
+VALUES (?, ?, ?, ?)
+                "#,
+            )
+            .bind(entry.text.as_str())
+            .bind(thread_id.as_str())
+            .bind(i64::from(entry.turn))
+            .bind(entry.kind.as_str())
+            .execute(&mut *tx)
+            .await?;
+        }
+        sqlx::query(
+            r#"
+INSERT INTO indexed_rollouts (thread_id, rollout_path, size, lines, turns)
+VALUES (?, ?, ?, ?, ?)
+ON CONFLICT(thread_id) DO UPDATE SET
+    rollout_path = excluded.rollout_path,
+    size = excluded.size,
+    lines = excluded.lines,
+    turns = excluded.turns
+            "#,
+        )
+        .bind(thread_id.as_str())
+        .bind(progress.rollout_path.display().to_string())
+        .bind(i64::try_from(progress.size)?)
+        .bind(i64::try_from(progress.lines)?)
+        .bind(i64::from(progress.turns))
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Drops every thread not in `live` (its rollout was deleted). Returns the
+    /// number of threads removed.
+    pub async fn retain_threads(&self, live: &HashSet<ThreadId>) -> anyhow::Result<usize> {
+        let indexed = sqlx::query("SELECT thread_id FROM indexed_rollouts")
+            .fetch_all(&self.pool)
+            .await?;
+        let mut removed = 0;
+        for row in indexed {
+            let thread_id: String = row.try_get("thread_id")?;
+            if ThreadId::from_string(&thread_id).is_ok_and(|id| live.contains(&id)) {
+                continue;
+            }
+            let mut tx = self.pool.begin().await?;
+            sqlx::query("DELETE FROM history_entries WHERE thread_id = ?")
+                .bind(thread_id.as_str())
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("DELETE FROM indexed_rollouts WHERE thread_id = ?")
+                .bind(thread_id.as_str())
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    /// Best matches for `query`, most relevant first. Every whitespace-separated
+    /// term must match, as a prefix of a word; FTS syntax in the query is
+    /// treated as plain text.
+    pub async fn search(&self, query: &str, limit: usize) -> anyhow::Result<Vec<HistorySearchHit>> {
+        let Some(match_expr) = fts_match_expression(query) else {
+            return Ok(Vec::new());
+        };
+        let rows = sqlx::query(
+            r#"
+SELECT
+    history_entries.thread_id AS thread_id,
+    history_entries.turn AS turn,
+    history_entries.kind AS kind,
+    snippet(history_entries, 0, '[', ']', '…', 16) AS snippet,
+    indexed_rollouts.rollout_path AS rollout_path
+FROM history_entries
+JOIN indexed_rollouts ON indexed_rollouts.thread_id = history_entries.thread_id
+WHERE history_entries MATCH ?
+ORDER BY rank
+LIMIT ?
+            "#,
+        )
+        .bind(match_expr)
+        .bind(i64::try_from(limit)?)
+        .fetch_all(&self.pool)
+        .await?;
+        let mut hits = Vec::with_capacity(rows.len());
+        for row in rows {
+            let Ok(thread_id) = ThreadId::from_string(&row.try_get::<String, _>("thread_id")?)
+            else {
+                continue;
+            };
+            let Some(kind) = HistoryEntryKind::parse(&row.try_get::<String, _>("kind")?) else {
+                continue;
+            };
+            hits.push(HistorySearchHit {
+                thread_id,
+                rollout_path: PathBuf::from(row.try_get::<String, _>("rollout_path")?),
+                turn: u32::try_from(row.try_get::<i64, _>("turn")?)?,
+                kind,
+                snippet: row.try_get("snippet")?,
+            });
+        }
+        Ok(hits)
+    }
+}
+
+/// Quotes each term so user input cannot use FTS5 operators, and matches
+/// each one as a prefix so results update while the user types.
+fn fts_match_expression(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::SystemTime;
+    use std::time::UNIX_EPOCH;
+    use uuid::Uuid;
+
+    fn unique_temp_dir() -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_nanos());
+        std::env::temp_dir().join(format!(
+            "codex-state-history-index-test-{nanos}-{}",
+            Uuid::new_v4()
+        ))
+    }
+
+    fn entry(turn: u32, kind: HistoryEntryKind, text: &str) -> HistoryEntry {
+        HistoryEntry {
+            turn,
+            kind,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn match_expression_quotes_terms() {
+        assert_eq!(
+            fts_match_expression(r#"cargo "test" NOT"#),
+            Some(r#""cargo"* """test"""* "NOT"*"#.to_string())
+        );
+        assert_eq!(fts_match_expression("   "), None);
+    }
+
+    #[tokio::test]
+    async fn search_finds_appended_entries_and_forgets_removed_threads() {
+        let codex_home = unique_temp_dir();
+        let index = HistoryIndex::open(&codex_home).await.expect("open index");
+        let thread_id = ThreadId::new();
+        let progress = IndexedRollout {
+            rollout_path: codex_home.join("rollout.jsonl"),
+            size: 10,
+            lines: 3,
+            turns: 2,
+        };
+        index
+            .append(
+                thread_id,
+                &progress,
+                &[
+                    entry(1, HistoryEntryKind::UserMessage, "fix the flaky login test"),
+                    entry(2, HistoryEntryKind::Command, "cargo test -p codex-login"),
+                ],
+                false,
+            )
+            .await
+            .expect("append");
+
+        assert_eq!(
+            index.indexed_rollout(thread_id).await.expect("progress"),
+            Some(progress.clone())
+        );
+        let hits = index.search("codex-log", 10).await.expect("search");
+        let found: Vec<_> = hits
+            .iter()
+            .map(|hit| (hit.thread_id, hit.turn, hit.kind, hit.rollout_path.clone()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![(
+                thread_id,
+                2,
+                HistoryEntryKind::Command,
+                progress.rollout_path.clone()
+            )]
+        );
+        assert!(hits[0].snippet.starts_with("cargo test -p ["));
+
+        let removed = index.retain_threads(&HashSet::new()).await.expect("retain");
+        assert_eq!(removed, 1);
+        assert_eq!(index.search("flaky", 10).await.expect("search"), Vec::new());
+
+        let _ = tokio::fs::remove_dir_all(codex_home).await;
+    }
+}
@@ -5,12 +5,19 @@
 //! orchestration and rollout scanning live in `codex-core`.
 
 mod extract;
+mod history_index;
 pub mod log_db;
 mod migrations;
 mod model;
 mod paths;
 mod runtime;
 
+pub use history_index::HISTORY_INDEX_FILENAME;
+pub use history_index::HistoryEntry;
+pub use history_index::HistoryEntryKind;
+pub use history_index::HistoryIndex;
+pub use history_index::HistorySearchHit;
+pub use history_index::IndexedRollout;
 pub use model::LogEntry;
 pub use model::LogQuery;
 pub use model::LogRow;
@@ -13,9 +13,11 @@ use crate::chatwidget::ChatWidget;
 use crate::chatwidget::ExternalEditorState;
 use crate::cwd_prompt::CwdPromptAction;
 use crate::diff_render::DiffSummary;
+use crate::diff_render::file_turn_diffs_renderable;
 use crate::exec_command::strip_bash_lc_and_escape;
 use crate::external_editor;
 use crate::file_search::FileSearchManager;
+use crate::frame_stats::FrameStats;
 use crate::history_cell;
 use crate::history_cell::HistoryCell;
 #[cfg(not(debug_assertions))]
@@ -61,6 +63,7 @@ use codex_otel::OtelManager;
 use codex_otel::TelemetryAuthMode;
 use codex_protocol::ThreadId;
 use codex_protocol::config_types::Personality;
+use codex_protocol::config_types::Verbosity;
 #[cfg(target_os = "windows")]
 use codex_protocol::config_types::WindowsSandboxLevel;
 use codex_protocol::items::TurnItem;
@@ -551,6 +554,8 @@ pub(crate) struct App {
     /// This is used after a confirmed thread rollback to ensure scrollback reflects the trimmed
     /// transcript cells.
     pub(crate) backtrack_render_pending: bool,
+    /// Frame times shown over the viewport while `/debug-frames` is on.
+    frame_stats: Option<FrameStats>,
     pub(crate) feedback: codex_feedback::CodexFeedback,
     feedback_audience: FeedbackAudience,
     /// Set when the user confirms an update; propagated on exit.
@@ -690,6 +695,71 @@ impl App {
         }
     }
 
+    /// Forks the active thread into a new thread that keeps history before the
+    /// `nth_user_message`-th user message (`usize::MAX` keeps everything), and
+    /// switches the UI to it. Returns whether the fork succeeded.
+    pub(crate) async fn fork_current_session(
+        &mut self,
+        tui: &mut tui::Tui,
+        nth_user_message: usize,
+    ) -> bool {
+        let summary = session_summary(
+            self.chat_widget.token_usage(),
+            self.chat_widget.thread_id(),
+            self.chat_widget.thread_name(),
+        );
+        if let Some(path) = self.chat_widget.rollout_path() {
+            // Fresh threads expose a precomputed path, but the file is
+            // materialized lazily on first user message.
+            if path.exists() {
+                match self
+                    .server
+                    .fork_thread(nth_user_message, self.config.clone(), path.clone(), false)
+                    .await
+                {
+                    Ok(forked) => {
+                        self.shutdown_current_thread().await;
+                        let init = self
+                            .chatwidget_init_for_forked_or_resumed_thread(tui, self.config.clone());
+                        self.chat_widget = ChatWidget::new_from_existing(
+                            init,
+                            forked.thread,
+                            forked.session_configured,
+                        );
+                        self.reset_thread_event_state();
+                        if let Some(summary) = summary {
+                            let mut lines: Vec<Line<'static>> =
+                                vec![summary.usage_line.clone().into()];
+                            if let Some(command) = summary.resume_command {
+                                let spans =
+                                    vec!["To continue this session, run ".into(), command.cyan()];
+                                lines.push(spans.into());
+                            }
+                            self.chat_widget.add_plain_history_lines(lines);
+                        }
+                        return true;
+                    }
+                    Err(err) => {
+                        let path_display = path.display();
+                        self.chat_widget.add_error_message(format!(
+                            "Failed to fork current session from {path_display}: {err}"
+                        ));
+                    }
+                }
+            } else {
+                self.chat_widget.add_error_message(
+                    "A thread must contain at least one turn before it can be forked.".to_string(),
+                );
+            }
+        } else {
+            self.chat_widget.add_error_message(
+                "A thread must contain at least one turn before it can be forked.".to_string(),
+            );
+        }
+
+        false
+    }
+
     fn ensure_thread_channel(&mut self, thread_id: ThreadId) -> &mut ThreadEventChannel {
         self.thread_event_channels
             .entry(thread_id)
@@ -989,6 +1059,7 @@ impl App {
         initial_prompt: Option<String>,
         initial_images: Vec<PathBuf>,
         session_selection: SessionSelection,
+        fork_at: Option<usize>,
         feedback: codex_feedback::CodexFeedback,
         is_first_run: bool,
         should_prompt_windows_sandbox_nux_at_startup: bool,
@@ -1125,7 +1196,12 @@ impl App {
             SessionSelection::Fork(path) => {
                 otel_manager.counter("codex.thread.fork", 1, &[("source", "cli_subcommand")]);
                 let forked = thread_manager
-                    .fork_thread(usize::MAX, config.clone(), path.clone(), false)
+                    .fork_thread(
+                        fork_at.unwrap_or(usize::MAX),
+                        config.clone(),
+                        path.clone(),
+                        false,
+                    )
                     .await
                     .wrap_err_with(|| {
                         let path_display = path.display();
@@ -1158,7 +1234,14 @@ impl App {
         chat_widget
             .maybe_prompt_windows_sandbox_enable(should_prompt_windows_sandbox_nux_at_startup);
 
-        let file_search = FileSearchManager::new(config.cwd.clone(), app_event_tx.clone());
+        let file_search = FileSearchManager::new(config.cwd.clone(), app_event_tx.clone())
+            .with_scope(
+                config
+                    .scope
+                    .iter()
+                    .map(AbsolutePathBuf::to_path_buf)
+                    .collect(),
+            );
         #[cfg(not(debug_assertions))]
         let upgrade_version = crate::updates::get_upgrade_version(&config);
 
@@ -1184,6 +1267,7 @@ impl App {
             status_line_invalid_items_warned: status_line_invalid_items_warned.clone(),
             backtrack: BacktrackState::default(),
             backtrack_render_pending: false,
+            frame_stats: None,
             feedback: feedback.clone(),
             feedback_audience,
             pending_update_action: None,
@@ -1230,7 +1314,7 @@ impl App {
                     tui,
                     AppEvent::InsertHistoryCell(Box::new(UpdateAvailableHistoryCell::new(
                         latest_version,
-                        crate::update_action::get_update_action(),
+                        crate::update_action::get_update_action(&app.config),
                     ))),
                 )
                 .await?;
@@ -1337,6 +1421,9 @@ impl App {
                     let pasted = pasted.replace("\r", "\n");
                     self.chat_widget.handle_paste(pasted);
                 }
+                // The mouse is only captured while an overlay is open; anything still queued
+                // after it closes is stale.
+                TuiEvent::Mouse(_) => {}
                 TuiEvent::Draw => {
                     if resized {
                         // Rebuild inline scrollback at the new width so transcript/history content
@@ -1374,15 +1461,22 @@ impl App {
                     {
                         return Ok(AppRunControl::Continue);
                     }
+                    let frame_started_at = Instant::now();
                     tui.draw(
                         self.chat_widget.desired_height(tui.terminal.size()?.width),
                         |frame| {
                             self.chat_widget.render(frame.area(), frame.buffer);
+                            if let Some(frame_stats) = &self.frame_stats {
+                                frame_stats.render(frame.area(), frame.buffer);
+                            }
                             if let Some((x, y)) = self.chat_widget.cursor_pos(frame.area()) {
                                 frame.set_cursor_position((x, y));
                             }
                         },
                     )?;
+                    if let Some(frame_stats) = &mut self.frame_stats {
+                        frame_stats.record(frame_started_at, frame_started_at.elapsed());
+                    }
                     if self.chat_widget.external_editor_state() == ExternalEditorState::Requested {
                         self.chat_widget
                             .set_external_editor_state(ExternalEditorState::Active);
@@ -1527,67 +1621,9 @@ impl App {
             AppEvent::ForkCurrentSession => {
                 self.otel_manager
                     .counter("codex.thread.fork", 1, &[("source", "slash_command")]);
-                let summary = session_summary(
-                    self.chat_widget.token_usage(),
-                    self.chat_widget.thread_id(),
-                    self.chat_widget.thread_name(),
-                );
                 self.chat_widget
                     .add_plain_history_lines(vec!["/fork".magenta().into()]);
-                if let Some(path) = self.chat_widget.rollout_path() {
-                    // Fresh threads expose a precomputed path, but the file is
-                    // materialized lazily on first user message.
-                    if path.exists() {
-                        match self
-                            .server
-                            .fork_thread(usize::MAX, self.config.clone(), path.clone(), false)
-                            .await
-                        {
-                            Ok(forked) => {
-                                self.shutdown_current_thread().await;
-                                let init = self.chatwidget_init_for_forked_or_resumed_thread(
-                                    tui,
-                                    self.config.clone(),
-                                );
-                                self.chat_widget = ChatWidget::new_from_existing(
-                                    init,
-                                    forked.thread,
-                                    forked.session_configured,
-                                );
-                                self.reset_thread_event_state();
-                                if let Some(summary) = summary {
-                                    let mut lines: Vec<Line<'static>> =
-                                        vec![summary.usage_line.clone().into()];
-                                    if let Some(command) = summary.resume_command {
-                                        let spans = vec![
-                                            "To continue this session, run ".into(),
-                                            command.cyan(),
-                                        ];
-                                        lines.push(spans.into());
-                                    }
-                                    self.chat_widget.add_plain_history_lines(lines);
-                                }
-                            }
-                            Err(err) => {
-                                let path_display = path.display();
-                                self.chat_widget.add_error_message(format!(
-                                    "Failed to fork current session from {path_display}: {err}"
-                                ));
-                            }
-                        }
-                    } else {
-                        self.chat_widget.add_error_message(
-                            "A thread must contain at least one turn before it can be forked."
-                                .to_string(),
-                        );
-                    }
-                } else {
-                    self.chat_widget.add_error_message(
-                        "A thread must contain at least one turn before it can be forked."
-                            .to_string(),
-                    );
-                }
-
+                self.fork_current_session(tui, usize::MAX).await;
                 tui.frame_requester().schedule_frame();
             }
             AppEvent::InsertHistoryCell(cell) => {
@@ -1663,7 +1699,23 @@ impl App {
                 return Ok(AppRunControl::Exit(ExitReason::Fatal(message)));
             }
             AppEvent::CodexOp(op) => {
+                let resolves_approval = matches!(
+                    op,
+                    Op::ExecApproval { .. }
+                        | Op::PatchApproval { .. }
+                        | Op::ResolveElicitation { .. }
+                );
                 self.chat_widget.submit_op(op);
+                if resolves_approval {
+                    self.refresh_status_line();
+                }
+            }
+            AppEvent::ToggleFrameStats => {
+                self.frame_stats = match self.frame_stats {
+                    Some(_) => None,
+                    None => Some(FrameStats::default()),
+                };
+                tui.frame_requester().schedule_frame();
             }
             AppEvent::DiffResult(text) => {
                 // Clear the in-progress state in the bottom pane
@@ -1734,6 +1786,9 @@ impl App {
             AppEvent::UpdatePersonality(personality) => {
                 self.on_update_personality(personality);
             }
+            AppEvent::UpdateVerbosity(verbosity) => {
+                self.on_update_verbosity(verbosity);
+            }
             AppEvent::OpenReasoningPopup { model } => {
                 self.chat_widget.open_reasoning_popup(model);
             }
@@ -2184,6 +2239,7 @@ impl App {
                 }
                 self.runtime_sandbox_policy_override =
                     Some(self.config.permissions.sandbox_policy.get().clone());
+                self.refresh_status_line();
 
                 // If sandbox policy becomes workspace-write or read-only, run the Windows world-writable scan.
                 #[cfg(target_os = "windows")]
@@ -2464,6 +2520,34 @@ impl App {
             AppEvent::ManageSkillsClosed => {
                 self.chat_widget.handle_manage_skills_closed();
             }
+            AppEvent::BookmarkLatestTurn { note } => {
+                self.bookmark_latest_turn(note);
+            }
+            AppEvent::OpenBookmark { turn, excerpt } => {
+                self.open_bookmark(tui, turn, &excerpt);
+            }
+            AppEvent::SetChangedFilesExpanded(expanded) => {
+                self.chat_widget.set_changed_files_expanded(expanded);
+            }
+            AppEvent::OpenChangedFileDiff(path) => {
+                let turn_diffs = self.chat_widget.changed_file_diffs(&path);
+                if !turn_diffs.is_empty() {
+                    let _ = tui.enter_alt_screen();
+                    self.overlay = Some(Overlay::new_static_with_renderables(
+                        vec![file_turn_diffs_renderable(
+                            &path,
+                            &self.config.cwd,
+                            turn_diffs,
+                        )],
+                        "C H A N G E S".to_string(),
+                    ));
+                    tui.frame_requester().schedule_frame();
+                }
+            }
+            AppEvent::QueuedUserMessagesEdited { order, edit } => {
+                self.chat_widget
+                    .apply_queued_user_messages_edit(order, edit);
+            }
             AppEvent::FullScreenApprovalRequest(request) => match request {
                 ApprovalRequest::ApplyPatch { cwd, changes, .. } => {
                     let _ = tui.enter_alt_screen();
@@ -2537,7 +2621,15 @@ impl App {
     fn handle_codex_event_now(&mut self, event: Event) {
         let needs_refresh = matches!(
             event.msg,
-            EventMsg::SessionConfigured(_) | EventMsg::TokenCount(_)
+            EventMsg::SessionConfigured(_)
+                | EventMsg::TokenCount(_)
+                | EventMsg::RequestBudget(_)
+                | EventMsg::ExecApprovalRequest(_)
+                | EventMsg::ApplyPatchApprovalRequest(_)
+                | EventMsg::ElicitationRequest(_)
+                | EventMsg::ExecCommandBegin(_)
+                | EventMsg::TurnComplete(_)
+                | EventMsg::TurnAborted(_)
         );
         // This guard is only for intentional thread-switch shutdowns.
         // App-exit shutdowns are tracked by `pending_shutdown_exit_thread_id`
@@ -2708,6 +2800,11 @@ impl App {
         self.chat_widget.set_personality(personality);
     }
 
+    fn on_update_verbosity(&mut self, verbosity: Option<Verbosity>) {
+        self.config.model_verbosity = verbosity;
+        self.chat_widget.set_verbosity(verbosity);
+    }
+
     fn personality_label(personality: Personality) -> &'static str {
         match personality {
             Personality::None => "None",
@@ -2749,8 +2846,22 @@ impl App {
         match editor_result {
             Ok(new_text) => {
                 // Trim trailing whitespace
-                let cleaned = new_text.trim_end().to_string();
-                self.chat_widget.apply_external_edit(cleaned);
+                let cleaned = new_text.trim_end();
+                match external_editor::split_frontmatter(cleaned) {
+                    Ok((attachments, body)) => {
+                        self.chat_widget
+                            .apply_external_edit(body.trim_start_matches(['\r', '\n']).to_string());
+                        self.chat_widget.attach_draft_attachments(attachments);
+                    }
+                    Err(err) => {
+                        // Keep the frontmatter in the draft so it can be fixed and retried.
+                        self.chat_widget.apply_external_edit(cleaned.to_string());
+                        self.chat_widget
+                            .add_to_history(history_cell::new_error_event(format!(
+                                "Ignored the draft's frontmatter: {err}",
+                            )));
+                    }
+                }
             }
             Err(err) => {
                 self.chat_widget
@@ -3115,6 +3226,7 @@ mod tests {
             status_line_invalid_items_warned: Arc::new(AtomicBool::new(false)),
             backtrack: BacktrackState::default(),
             backtrack_render_pending: false,
+            frame_stats: None,
             feedback: codex_feedback::CodexFeedback::new(),
             feedback_audience: FeedbackAudience::External,
             pending_update_action: None,
@@ -3173,6 +3285,7 @@ mod tests {
                 status_line_invalid_items_warned: Arc::new(AtomicBool::new(false)),
                 backtrack: BacktrackState::default(),
                 backtrack_render_pending: false,
+                frame_stats: None,
                 feedback: codex_feedback::CodexFeedback::new(),
                 feedback_audience: FeedbackAudience::External,
                 pending_update_action: None,
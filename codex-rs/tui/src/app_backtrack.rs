@@ -12,6 +12,8 @@
 //! - The first `Esc` in the main view "primes" the feature and captures a base thread id.
 //! - A subsequent `Esc` opens the transcript overlay (`Ctrl+T`) and highlights a user message.
 //! - `Enter` requests a rollback from core and records a `pending_rollback` guard.
+//! - `f` instead forks the thread before the highlighted message into a new thread, leaving the
+//!   original rollout untouched.
 //! - On `EventMsg::ThreadRolledBack`, we either finish an in-flight backtrack request or queue a
 //!   rollback trim so it runs in event order with transcript inserts.
 //!
@@ -44,6 +46,7 @@ use color_eyre::eyre::Result;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
+use ratatui::style::Stylize;
 
 /// Aggregates all backtrack-related state used by the App.
 #[derive(Default)]
@@ -104,13 +107,20 @@ impl App {
     ///
     /// If backtrack preview is active, Esc / Left steps selection, Right steps forward, Enter
     /// confirms. Otherwise, Esc begins preview mode and all other events are forwarded to the
-    /// overlay.
+    /// overlay. In either mode `b` bookmarks the highlighted cell or the cell at the top, and
+    /// mouse events go to the overlay, which may hand back a gesture for `App` to act on.
     pub(crate) async fn handle_backtrack_overlay_event(
         &mut self,
         tui: &mut tui::Tui,
         event: TuiEvent,
     ) -> Result<bool> {
-        if self.backtrack.overlay_preview_active {
+        if let TuiEvent::Mouse(_) = event {
+            self.overlay_forward_event(tui, event)?;
+            if let Some(action) = self.overlay.as_mut().and_then(Overlay::take_mouse_action) {
+                self.handle_overlay_mouse_action(tui, action).await;
+            }
+            Ok(true)
+        } else if self.backtrack.overlay_preview_active {
             match event {
                 TuiEvent::Key(KeyEvent {
                     code: KeyCode::Esc,
@@ -144,6 +154,22 @@ impl App {
                     self.overlay_confirm_backtrack(tui);
                     Ok(true)
                 }
+                TuiEvent::Key(KeyEvent {
+                    code: KeyCode::Char('f'),
+                    kind: KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.overlay_fork_at_backtrack(tui).await;
+                    Ok(true)
+                }
+                TuiEvent::Key(KeyEvent {
+                    code: KeyCode::Char('b'),
+                    kind: KeyEventKind::Press,
+                    ..
+                }) => {
+                    self.overlay_bookmark(tui);
+                    Ok(true)
+                }
                 // Catchall: forward any other events to the overlay widget.
                 _ => {
                     self.overlay_forward_event(tui, event)?;
@@ -159,6 +185,15 @@ impl App {
             // First Esc in transcript overlay: begin backtrack preview at latest user message.
             self.begin_overlay_backtrack_preview(tui);
             Ok(true)
+        } else if let TuiEvent::Key(KeyEvent {
+            code: KeyCode::Char('b'),
+            kind: KeyEventKind::Press,
+            ..
+        }) = event
+            && matches!(self.overlay, Some(Overlay::Transcript(_)))
+        {
+            self.overlay_bookmark(tui);
+            Ok(true)
         } else {
             // Not in backtrack mode: forward events to the overlay widget.
             self.overlay_forward_event(tui, event)?;
@@ -349,6 +384,31 @@ impl App {
         }
     }
 
+    /// Select a transcript cell clicked in the overlay.
+    ///
+    /// Clicking a user message starts the backtrack preview there (or moves it), as if it had
+    /// been reached with Esc. Any other cell ends the preview and is only highlighted, so that
+    /// `b` bookmarks it.
+    pub(crate) fn overlay_select_cell(&mut self, tui: &mut tui::Tui, cell_idx: usize) {
+        let nth_user_message =
+            user_positions_iter(&self.transcript_cells).position(|idx| idx == cell_idx);
+        if let Some(nth_user_message) = nth_user_message {
+            self.backtrack.primed = true;
+            self.backtrack.base_id = self.chat_widget.thread_id();
+            self.backtrack.overlay_preview_active = true;
+            self.apply_backtrack_selection_internal(nth_user_message);
+        } else {
+            if self.backtrack.overlay_preview_active {
+                self.backtrack.overlay_preview_active = false;
+                self.reset_backtrack_state();
+            }
+            if let Some(Overlay::Transcript(t)) = &mut self.overlay {
+                t.set_highlight_cell(Some(cell_idx));
+            }
+        }
+        tui.frame_requester().schedule_frame();
+    }
+
     /// Forwards an event to the overlay and closes it if done.
     ///
     /// The transcript overlay draw path is special because the overlay should match the main
@@ -411,6 +471,37 @@ impl App {
         }
     }
 
+    /// Fork the thread just before the highlighted user message into a new thread, leaving the
+    /// original thread intact, and prefill the composer with the selected message.
+    async fn overlay_fork_at_backtrack(&mut self, tui: &mut tui::Tui) {
+        let nth_user_message = self.backtrack.nth_user_message;
+        let selection = self.backtrack_selection(nth_user_message);
+        self.close_transcript_overlay(tui);
+        let Some(selection) = selection else {
+            return;
+        };
+        self.otel_manager
+            .counter("codex.thread.fork", 1, &[("source", "transcript")]);
+        self.chat_widget.add_plain_history_lines(vec![
+            format!("/fork (before message {})", selection.nth_user_message + 1)
+                .magenta()
+                .into(),
+        ]);
+        if self
+            .fork_current_session(tui, selection.nth_user_message)
+            .await
+        {
+            self.chat_widget
+                .set_remote_image_urls(selection.remote_image_urls);
+            self.chat_widget.set_composer_text(
+                selection.prefill,
+                selection.text_elements,
+                selection.local_image_paths,
+            );
+        }
+        tui.frame_requester().schedule_frame();
+    }
+
     /// Handle Esc in overlay backtrack preview: step selection if armed, else forward.
     fn overlay_step_backtrack(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         if self.backtrack.base_id.is_some() {
@@ -627,7 +718,7 @@ pub(crate) fn user_count(cells: &[Arc<dyn crate::history_cell::HistoryCell>]) ->
     user_positions_iter(cells).count()
 }
 
-fn nth_user_position(
+pub(crate) fn nth_user_position(
     cells: &[Arc<dyn crate::history_cell::HistoryCell>],
     nth: usize,
 ) -> Option<usize> {
@@ -636,7 +727,7 @@ fn nth_user_position(
         .find_map(|(i, idx)| (i == nth).then_some(idx))
 }
 
-fn user_positions_iter(
+pub(crate) fn user_positions_iter(
     cells: &[Arc<dyn crate::history_cell::HistoryCell>],
 ) -> impl Iterator<Item = usize> + '_ {
     let session_start_type = TypeId::of::<SessionInfoCell>();
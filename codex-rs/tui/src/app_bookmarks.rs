@@ -0,0 +1,165 @@
+//! Transcript bookmarks: `/bookmark`, `b` in the transcript overlay, and the `/bookmarks` palette.
+//!
+//! A bookmark names a turn by its 1-based user message count and a cell within that turn by the
+//! cell's first line of text (its excerpt). Core records bookmarks in the rollout, so they come
+//! back on resume and appear in `codex sessions export`. `ChatWidget` keeps the list for the
+//! palette; this file maps bookmarks to and from the transcript cells that `App` owns.
+
+use std::sync::Arc;
+
+use crate::app::App;
+use crate::app_backtrack::nth_user_position;
+use crate::app_backtrack::user_count;
+use crate::app_backtrack::user_positions_iter;
+use crate::history_cell::HistoryCell;
+use crate::pager_overlay::Overlay;
+use crate::text_formatting::truncate_text;
+use crate::tui;
+use codex_core::protocol::Op;
+
+/// Longest excerpt, in graphemes, recorded for a bookmarked cell.
+const EXCERPT_MAX_GRAPHEMES: usize = 80;
+
+impl App {
+    /// Bookmark the latest turn (`/bookmark [note]`).
+    pub(crate) fn bookmark_latest_turn(&mut self, note: Option<String>) {
+        let Some(nth) = user_count(&self.transcript_cells).checked_sub(1) else {
+            self.chat_widget.add_info_message(
+                "Nothing to bookmark yet; send a message first.".to_string(),
+                None,
+            );
+            return;
+        };
+        let Some(idx) = nth_user_position(&self.transcript_cells, nth) else {
+            return;
+        };
+        let excerpt = cell_excerpt(self.transcript_cells[idx].as_ref());
+        self.chat_widget.submit_op(Op::AddBookmark {
+            turn: nth as u32 + 1,
+            excerpt,
+            note,
+        });
+    }
+
+    /// Bookmark the highlighted overlay cell, or the cell at the top of the overlay, after
+    /// asking for an optional note.
+    pub(crate) fn overlay_bookmark(&mut self, tui: &mut tui::Tui) {
+        let Some(Overlay::Transcript(transcript)) = &self.overlay else {
+            return;
+        };
+        let Some(idx) = transcript.bookmark_target() else {
+            return;
+        };
+        let turn = turn_of_cell(&self.transcript_cells, idx);
+        let excerpt = cell_excerpt(self.transcript_cells[idx].as_ref());
+        self.close_transcript_overlay(tui);
+        if turn == 0 {
+            self.chat_widget.add_info_message(
+                "Only cells after the first message can be bookmarked.".to_string(),
+                None,
+            );
+        } else {
+            self.chat_widget.show_bookmark_note_prompt(turn, excerpt);
+        }
+        tui.frame_requester().schedule_frame();
+    }
+
+    /// Open the transcript overlay with the bookmarked cell highlighted.
+    pub(crate) fn open_bookmark(&mut self, tui: &mut tui::Tui, turn: u32, excerpt: &str) {
+        let Some(idx) = bookmarked_cell(&self.transcript_cells, turn, excerpt) else {
+            self.chat_widget
+                .add_info_message(format!("Turn {turn} is no longer in the transcript."), None);
+            return;
+        };
+        self.open_transcript_overlay(tui);
+        if let Some(Overlay::Transcript(transcript)) = &mut self.overlay {
+            transcript.set_highlight_cell(Some(idx));
+        }
+    }
+}
+
+/// 1-based turn that `idx` belongs to, or 0 for cells before the first user message.
+fn turn_of_cell(cells: &[Arc<dyn HistoryCell>], idx: usize) -> u32 {
+    user_positions_iter(cells)
+        .take_while(|&position| position <= idx)
+        .count() as u32
+}
+
+/// The cell of `turn` whose excerpt is `excerpt`, falling back to the turn's user message.
+fn bookmarked_cell(cells: &[Arc<dyn HistoryCell>], turn: u32, excerpt: &str) -> Option<usize> {
+    let nth = (turn as usize).checked_sub(1)?;
+    let start = nth_user_position(cells, nth)?;
+    let end = nth_user_position(cells, nth + 1).unwrap_or(cells.len());
+    Some(
+        (start..end)
+            .find(|&idx| cell_excerpt(cells[idx].as_ref()) == excerpt)
+            .unwrap_or(start),
+    )
+}
+
+/// First non-blank line of a cell's transcript text, without its leading bullet glyph.
+fn cell_excerpt(cell: &dyn HistoryCell) -> String {
+    let line = cell
+        .transcript_lines(u16::MAX)
+        .into_iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        })
+        .find(|text| !text.trim().is_empty())
+        .unwrap_or_default();
+    let text = line
+        .trim_start_matches(|c: char| c.is_whitespace() || matches!(c, '›' | '•' | '└' | '│'))
+        .trim_end();
+    truncate_text(text, EXCERPT_MAX_GRAPHEMES)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history_cell::AgentMessageCell;
+    use crate::history_cell::UserHistoryCell;
+    use pretty_assertions::assert_eq;
+    use ratatui::prelude::Line;
+
+    fn user(message: &str) -> Arc<dyn HistoryCell> {
+        Arc::new(UserHistoryCell {
+            message: message.to_string(),
+            text_elements: Vec::new(),
+            local_image_paths: Vec::new(),
+            remote_image_urls: Vec::new(),
+        })
+    }
+
+    fn agent(text: &'static str) -> Arc<dyn HistoryCell> {
+        Arc::new(AgentMessageCell::new(vec![Line::from(text)], true))
+    }
+
+    #[test]
+    fn bookmarks_resolve_to_cells_within_their_turn() {
+        let cells = vec![
+            agent("intro"),
+            user("first"),
+            agent("done"),
+            user("second"),
+            agent("done"),
+            agent("also done"),
+        ];
+
+        assert_eq!(cell_excerpt(cells[1].as_ref()), "first");
+        assert_eq!(cell_excerpt(cells[2].as_ref()), "done");
+        assert_eq!(turn_of_cell(&cells, 0), 0);
+        assert_eq!(turn_of_cell(&cells, 2), 1);
+        assert_eq!(turn_of_cell(&cells, 5), 2);
+
+        assert_eq!(bookmarked_cell(&cells, 1, "done"), Some(2));
+        assert_eq!(bookmarked_cell(&cells, 2, "done"), Some(4));
+        assert_eq!(bookmarked_cell(&cells, 2, "also done"), Some(5));
+        // A cell that is gone falls back to the turn's user message.
+        assert_eq!(bookmarked_cell(&cells, 2, "missing"), Some(3));
+        assert_eq!(bookmarked_cell(&cells, 3, "done"), None);
+        assert_eq!(bookmarked_cell(&cells, 0, "intro"), None);
+    }
+}
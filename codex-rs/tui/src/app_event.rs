@@ -27,6 +27,7 @@ use codex_core::protocol::AskForApproval;
 use codex_core::protocol::SandboxPolicy;
 use codex_protocol::config_types::CollaborationModeMask;
 use codex_protocol::config_types::Personality;
+use codex_protocol::config_types::Verbosity;
 use codex_protocol::openai_models::ReasoningEffort;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -100,6 +101,9 @@ pub(crate) enum AppEvent {
     /// Result of computing a `/diff` command.
     DiffResult(String),
 
+    /// Show or hide the frame-time overlay (`/debug-frames`).
+    ToggleFrameStats,
+
     /// Open the app link view in the bottom pane.
     OpenAppLink {
         app_id: String,
@@ -148,6 +152,9 @@ pub(crate) enum AppEvent {
     /// Update the current personality in the running app and widget.
     UpdatePersonality(Personality),
 
+    /// Update the current verbosity in the running app and widget.
+    UpdateVerbosity(Option<Verbosity>),
+
     /// Persist the selected model and reasoning effort to the appropriate config.
     PersistModelSelection {
         model: String,
@@ -302,6 +309,31 @@ pub(crate) enum AppEvent {
     /// Notify that the manage skills popup was closed.
     ManageSkillsClosed,
 
+    /// Apply the `/queue` editor's result to the queued user messages.
+    QueuedUserMessagesEdited {
+        /// Original positions of the kept messages, in their new order.
+        order: Vec<usize>,
+        /// Original position of the message to move back into the composer.
+        edit: Option<usize>,
+    },
+
+    /// Bookmark the latest turn (`/bookmark`).
+    BookmarkLatestTurn {
+        note: Option<String>,
+    },
+
+    /// Show a bookmark picked from the `/bookmarks` palette in the transcript overlay.
+    OpenBookmark {
+        turn: u32,
+        excerpt: String,
+    },
+
+    /// Expand or collapse the changed-files panel above the composer.
+    SetChangedFilesExpanded(bool),
+
+    /// Show the session's diff of a file picked from the `/changes` palette.
+    OpenChangedFileDiff(PathBuf),
+
     /// Re-open the permissions presets popup.
     OpenPermissionsPopup,
 
@@ -0,0 +1,97 @@
+//! Mouse gestures in overlays (`tui.mouse`): copying drag selections, opening clicked
+//! `path:line` references in `$EDITOR`, and selecting transcript cells.
+//!
+//! Pagers turn raw mouse events into an [`OverlayMouseAction`]; this file carries it out, since
+//! `App` owns the session's working directory, the terminal hand-off to the editor and the
+//! backtrack state that a click on a user message changes.
+
+use crate::app::App;
+use crate::clipboard_copy::copy_to_clipboard;
+use crate::external_editor;
+use crate::file_reference::FileReference;
+use crate::pager_overlay::OverlayMouseAction;
+use crate::tui;
+
+impl App {
+    /// Carry out a gesture made in the open overlay.
+    ///
+    /// A drag copies the selected text. A click on a reference to an existing file opens it in
+    /// `$EDITOR` at the referenced line, and a right click copies it instead. Any other click
+    /// selects the transcript cell under the pointer.
+    pub(crate) async fn handle_overlay_mouse_action(
+        &mut self,
+        tui: &mut tui::Tui,
+        action: OverlayMouseAction,
+    ) {
+        match action {
+            OverlayMouseAction::Copy(text) => {
+                let lines = text.lines().count();
+                let notice = match copy_to_clipboard(&text) {
+                    Ok(()) if lines == 1 => "Copied 1 line".to_string(),
+                    Ok(()) => format!("Copied {lines} lines"),
+                    Err(err) => format!("Copy failed: {err}"),
+                };
+                self.set_overlay_notice(tui, notice);
+            }
+            OverlayMouseAction::Click {
+                word,
+                cell,
+                secondary,
+            } => {
+                let reference = word.and_then(|word| FileReference::parse(&word, &self.config.cwd));
+                match (reference, cell) {
+                    (Some(reference), _) if secondary => {
+                        let notice = copy_file_reference(&reference);
+                        self.set_overlay_notice(tui, notice);
+                    }
+                    (Some(reference), _) => self.open_file_reference(tui, &reference).await,
+                    (None, Some(cell)) if !secondary => self.overlay_select_cell(tui, cell),
+                    (None, _) => {}
+                }
+            }
+        }
+    }
+
+    async fn open_file_reference(&mut self, tui: &mut tui::Tui, reference: &FileReference) {
+        let editor_cmd = match external_editor::resolve_editor_command() {
+            Ok(cmd) => cmd,
+            Err(external_editor::EditorError::MissingEditor) => {
+                // Without an editor the reference is still useful on the clipboard.
+                let notice =
+                    copy_file_reference(reference) + " (set $VISUAL or $EDITOR to open it)";
+                self.set_overlay_notice(tui, notice);
+                return;
+            }
+            Err(err) => {
+                self.set_overlay_notice(tui, format!("Failed to open editor: {err}"));
+                return;
+            }
+        };
+
+        let result = tui
+            .with_restored(tui::RestoreMode::KeepRaw, || async {
+                external_editor::open_file_reference(&editor_cmd, reference).await
+            })
+            .await;
+        if let Err(err) = result {
+            self.set_overlay_notice(tui, format!("Failed to open editor: {err}"));
+        }
+        tui.frame_requester().schedule_frame();
+    }
+
+    fn set_overlay_notice(&mut self, tui: &mut tui::Tui, notice: String) {
+        if let Some(overlay) = &mut self.overlay {
+            overlay.set_notice(notice);
+        }
+        tui.frame_requester().schedule_frame();
+    }
+}
+
+/// Copies `reference` as `path:line` and describes the outcome for the overlay's notice.
+fn copy_file_reference(reference: &FileReference) -> String {
+    let location = reference.location();
+    match copy_to_clipboard(&location) {
+        Ok(()) => format!("Copied {location}"),
+        Err(err) => format!("Copy failed: {err}"),
+    }
+}
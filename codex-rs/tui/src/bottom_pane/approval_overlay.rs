@@ -17,6 +17,7 @@ use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::Renderable;
 use codex_core::features::Features;
+use codex_core::protocol::CommandExplanation;
 use codex_core::protocol::ElicitationAction;
 use codex_core::protocol::ExecPolicyAmendment;
 use codex_core::protocol::FileChange;
@@ -45,6 +46,7 @@ pub(crate) enum ApprovalRequest {
         reason: Option<String>,
         network_approval_context: Option<NetworkApprovalContext>,
         proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
+        explanation: Option<CommandExplanation>,
     },
     ApplyPatch {
         id: String,
@@ -321,6 +323,10 @@ impl BottomPaneView for ApprovalOverlay {
         self.done
     }
 
+    fn pending_approvals(&self) -> usize {
+        if self.done { 0 } else { 1 + self.queue.len() }
+    }
+
     fn try_consume_approval_request(
         &mut self,
         request: ApprovalRequest,
@@ -358,6 +364,7 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                 reason,
                 network_approval_context,
                 proposed_execpolicy_amendment,
+                explanation,
             } => {
                 let mut header: Vec<Line<'static>> = Vec::new();
                 if let Some(reason) = reason {
@@ -370,6 +377,16 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                     first.spans.insert(0, Span::from("$ "));
                 }
                 header.extend(full_cmd_lines);
+                if let Some(explanation) = explanation {
+                    header.push(Line::from(""));
+                    header.push(Line::from(explanation.summary.italic()));
+                    for operation in explanation.destructive_operations {
+                        header.push(Line::from(vec![
+                            "Destructive: ".red().bold(),
+                            operation.into(),
+                        ]));
+                    }
+                }
                 Self {
                     variant: ApprovalVariant::Exec {
                         id,
@@ -585,6 +602,7 @@ mod tests {
             reason: Some("reason".to_string()),
             network_approval_context: None,
             proposed_execpolicy_amendment: None,
+            explanation: None,
         }
     }
 
@@ -630,6 +648,7 @@ mod tests {
                 proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
                     "echo".to_string(),
                 ])),
+                explanation: None,
             },
             tx,
             Features::with_defaults(),
@@ -667,6 +686,7 @@ mod tests {
             reason: None,
             network_approval_context: None,
             proposed_execpolicy_amendment: None,
+            explanation: None,
         };
 
         let view = ApprovalOverlay::new(exec_request, tx, Features::with_defaults());
@@ -688,6 +708,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn header_includes_command_explanation() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let exec_request = ApprovalRequest::Exec {
+            id: "test".into(),
+            command: vec!["rm".into(), "-rf".into(), "build".into()],
+            reason: None,
+            network_approval_context: None,
+            proposed_execpolicy_amendment: None,
+            explanation: Some(CommandExplanation {
+                summary: "Removes the build output.".to_string(),
+                destructive_operations: vec!["deletes build/".to_string()],
+            }),
+        };
+
+        let view = ApprovalOverlay::new(exec_request, tx, Features::with_defaults());
+        let mut buf = Buffer::empty(Rect::new(0, 0, 80, view.desired_height(80)));
+        view.render(Rect::new(0, 0, 80, view.desired_height(80)), &mut buf);
+
+        let rendered: Vec<String> = (0..buf.area.height)
+            .map(|row| {
+                (0..buf.area.width)
+                    .map(|col| buf[(col, row)].symbol().to_string())
+                    .collect()
+            })
+            .collect();
+        assert!(
+            rendered
+                .iter()
+                .any(|line| line.contains("Removes the build output.")),
+            "expected header to include the explanation, got {rendered:?}"
+        );
+        assert!(
+            rendered
+                .iter()
+                .any(|line| line.contains("Destructive: deletes build/")),
+            "expected header to flag destructive operations, got {rendered:?}"
+        );
+    }
+
     #[test]
     fn network_exec_options_use_expected_labels_and_hide_execpolicy_amendment() {
         let network_context = NetworkApprovalContext {
@@ -723,6 +784,7 @@ mod tests {
                 protocol: NetworkApprovalProtocol::Https,
             }),
             proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec!["curl".into()])),
+            explanation: None,
         };
 
         let view = ApprovalOverlay::new(exec_request, tx, Features::with_defaults());
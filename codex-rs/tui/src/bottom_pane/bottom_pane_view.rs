@@ -54,6 +54,11 @@ pub(crate) trait BottomPaneView: Renderable {
         false
     }
 
+    /// Approval requests this view shows or has queued.
+    fn pending_approvals(&self) -> usize {
+        0
+    }
+
     /// Try to handle approval request; return the original value if not
     /// consumed.
     fn try_consume_approval_request(
@@ -0,0 +1,187 @@
+//! Summary of the files changed this session, shown above the composer.
+//!
+//! Collapsed, the panel is a single row with the file count and line totals. Expanded, it also
+//! lists the most recently touched files. `/changes` toggles it and opens a file's diff.
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Paragraph;
+
+use crate::bottom_pane::selection_popup_common::truncate_line_with_ellipsis_if_overflow;
+use crate::render::renderable::Renderable;
+
+/// Most files listed while expanded; the rest are counted in a trailing row.
+const MAX_EXPANDED_FILES: usize = 5;
+
+/// One row of the panel: a display path and its line counts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ChangedFileRow {
+    pub(crate) path: String,
+    pub(crate) added: usize,
+    pub(crate) removed: usize,
+}
+
+pub(crate) struct ChangedFilesPanel {
+    /// Files in the order they were first touched.
+    files: Vec<ChangedFileRow>,
+    expanded: bool,
+}
+
+impl ChangedFilesPanel {
+    pub(crate) fn new() -> Self {
+        Self {
+            files: Vec::new(),
+            expanded: false,
+        }
+    }
+
+    pub(crate) fn set_files(&mut self, files: Vec<ChangedFileRow>) -> bool {
+        if self.files == files {
+            return false;
+        }
+        self.files = files;
+        true
+    }
+
+    pub(crate) fn set_expanded(&mut self, expanded: bool) -> bool {
+        if self.expanded == expanded {
+            return false;
+        }
+        self.expanded = expanded;
+        true
+    }
+
+    pub(crate) fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    fn render_lines(&self, width: u16) -> Vec<Line<'static>> {
+        if width < 4 || self.files.is_empty() {
+            return Vec::new();
+        }
+        let count = self.files.len();
+        let noun = if count == 1 { "file" } else { "files" };
+        let added = self.files.iter().map(|file| file.added).sum();
+        let removed = self.files.iter().map(|file| file.removed).sum();
+        let mut summary: Vec<Span<'static>> = vec![format!("  {count} {noun} changed ").dim()];
+        summary.extend(line_counts(added, removed));
+        summary.push(" · /changes to view".dim());
+        let mut lines = vec![Line::from(summary)];
+
+        if self.expanded {
+            // The most recently touched files are the most interesting ones.
+            for file in self.files.iter().rev().take(MAX_EXPANDED_FILES) {
+                let mut row: Vec<Span<'static>> = vec!["    └ ".dim(), file.path.clone().into()];
+                row.push(" ".into());
+                row.extend(line_counts(file.added, file.removed));
+                lines.push(Line::from(row));
+            }
+            if count > MAX_EXPANDED_FILES {
+                lines.push(
+                    format!("    … {} more", count - MAX_EXPANDED_FILES)
+                        .dim()
+                        .into(),
+                );
+            }
+        }
+
+        lines
+            .into_iter()
+            .map(|line| truncate_line_with_ellipsis_if_overflow(line, width as usize))
+            .collect()
+    }
+}
+
+fn line_counts(added: usize, removed: usize) -> [Span<'static>; 5] {
+    [
+        "(".dim(),
+        format!("+{added}").green(),
+        " ".into(),
+        format!("-{removed}").red(),
+        ")".dim(),
+    ]
+}
+
+impl Renderable for ChangedFilesPanel {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+
+        Paragraph::new(self.render_lines(area.width)).render(area, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        self.render_lines(width).len() as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn row(path: &str, added: usize, removed: usize) -> ChangedFileRow {
+        ChangedFileRow {
+            path: path.to_string(),
+            added,
+            removed,
+        }
+    }
+
+    fn text(lines: &[Line<'_>]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn empty_panel_takes_no_space() {
+        let panel = ChangedFilesPanel::new();
+        assert_eq!(panel.desired_height(80), 0);
+    }
+
+    #[test]
+    fn collapsed_panel_is_one_summary_row() {
+        let mut panel = ChangedFilesPanel::new();
+        panel.set_files(vec![row("src/lib.rs", 10, 2), row("README.md", 1, 0)]);
+
+        assert_eq!(
+            text(&panel.render_lines(80)),
+            vec!["  2 files changed (+11 -2) · /changes to view"]
+        );
+    }
+
+    #[test]
+    fn expanded_panel_lists_recent_files_first() {
+        let mut panel = ChangedFilesPanel::new();
+        panel.set_files((1..=7).map(|n| row(&format!("f{n}.rs"), n, 0)).collect());
+        panel.set_expanded(true);
+
+        assert_eq!(
+            text(&panel.render_lines(80)),
+            vec![
+                "  7 files changed (+28 -0) · /changes to view",
+                "    └ f7.rs (+7 -0)",
+                "    └ f6.rs (+6 -0)",
+                "    └ f5.rs (+5 -0)",
+                "    └ f4.rs (+4 -0)",
+                "    └ f3.rs (+3 -0)",
+                "    … 2 more",
+            ]
+        );
+    }
+}
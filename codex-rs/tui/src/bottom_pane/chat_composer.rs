@@ -109,8 +109,8 @@
 //! edits and renders a placeholder prompt instead of the editable textarea. This is part of the
 //! overall state machine, since it affects which transitions are even possible from a given UI
 //! state.
+use crate::bottom_pane::StatusLine;
 use crate::bottom_pane::footer::mode_indicator_line;
-use crate::bottom_pane::selection_popup_common::truncate_line_with_ellipsis_if_overflow;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
 use crate::key_hint::has_ctrl_or_alt;
@@ -157,6 +157,9 @@ use super::footer::render_footer_line;
 use super::footer::reset_mode_after_activity;
 use super::footer::single_line_footer_layout;
 use super::footer::toggle_shortcut_mode;
+use super::image_thumbnail::THUMBNAIL_COLS;
+use super::image_thumbnail::THUMBNAIL_ROWS;
+use super::image_thumbnail::thumbnail_lines;
 use super::paste_burst::CharDecision;
 use super::paste_burst::PasteBurst;
 use super::skill_popup::MentionItem;
@@ -201,6 +204,7 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::ops::Range;
+use std::path::Path;
 use std::path::PathBuf;
 use std::time::Duration;
 use std::time::Instant;
@@ -302,6 +306,8 @@ pub(crate) struct ChatComposer {
     /// Invariant: attached images are labeled in vec order as
     /// `[Image #M+1]..[Image #N]`, where `M` is the number of remote images.
     attached_images: Vec<AttachedImage>,
+    /// Thumbnails of attached images by path; `None` for images that could not be decoded.
+    image_thumbnails: HashMap<PathBuf, Option<Vec<Line<'static>>>>,
     placeholder_text: String,
     is_task_running: bool,
     /// When false, the composer is temporarily read-only (e.g. during sandbox setup).
@@ -334,7 +340,7 @@ pub(crate) struct ChatComposer {
     connectors_enabled: bool,
     personality_command_enabled: bool,
     windows_degraded_sandbox_active: bool,
-    status_line_value: Option<Line<'static>>,
+    status_line_value: Option<StatusLine>,
     status_line_enabled: bool,
 }
 
@@ -408,6 +414,7 @@ impl ChatComposer {
             large_paste_counters: HashMap::new(),
             has_focus: has_input_focus,
             attached_images: Vec::new(),
+            image_thumbnails: HashMap::new(),
             placeholder_text,
             is_task_running: false,
             input_enabled: true,
@@ -541,23 +548,23 @@ impl ChatComposer {
         let [composer_rect, popup_rect] =
             Layout::vertical([Constraint::Min(3), popup_constraint]).areas(area);
         let mut textarea_rect = composer_rect.inset(Insets::tlbr(1, LIVE_PREFIX_COLS, 1, 1));
-        let remote_images_height = self
-            .remote_images_lines(textarea_rect.width)
+        let image_rows_height = self
+            .image_rows(textarea_rect.width)
             .len()
             .try_into()
             .unwrap_or(u16::MAX)
             .min(textarea_rect.height.saturating_sub(1));
-        let remote_images_separator = u16::from(remote_images_height > 0);
-        let consumed = remote_images_height.saturating_add(remote_images_separator);
-        let remote_images_rect = Rect {
+        let image_rows_separator = u16::from(image_rows_height > 0);
+        let consumed = image_rows_height.saturating_add(image_rows_separator);
+        let image_rows_rect = Rect {
             x: textarea_rect.x,
             y: textarea_rect.y,
             width: textarea_rect.width,
-            height: remote_images_height,
+            height: image_rows_height,
         };
         textarea_rect.y = textarea_rect.y.saturating_add(consumed);
         textarea_rect.height = textarea_rect.height.saturating_sub(consumed);
-        [composer_rect, remote_images_rect, textarea_rect, popup_rect]
+        [composer_rect, image_rows_rect, textarea_rect, popup_rect]
     }
 
     fn footer_spacing(footer_hint_height: u16) -> u16 {
@@ -876,6 +883,7 @@ impl ChatComposer {
 
         for (idx, path) in local_image_paths.into_iter().enumerate() {
             let placeholder = local_image_label_text(self.remote_image_urls.len() + idx + 1);
+            self.load_thumbnail(&path);
             self.attached_images
                 .push(AttachedImage { placeholder, path });
         }
@@ -1008,10 +1016,25 @@ impl ChatComposer {
         // Insert as an element to match large paste placeholder behavior:
         // styled distinctly and treated atomically for cursor/mutations.
         self.textarea.insert_element(&placeholder);
+        self.load_thumbnail(&path);
         self.attached_images
             .push(AttachedImage { placeholder, path });
     }
 
+    fn load_thumbnail(&mut self, path: &Path) {
+        self.image_thumbnails
+            .entry(path.to_path_buf())
+            .or_insert_with(|| thumbnail_lines(path));
+    }
+
+    /// Insert `[File <label>]` as an element that expands to `contents` on submit, like a
+    /// large paste.
+    pub(crate) fn attach_file(&mut self, label: &str, contents: String) {
+        let placeholder = format!("[File {label}]");
+        self.textarea.insert_element(&placeholder);
+        self.pending_pastes.push((placeholder, contents));
+    }
+
     #[cfg(test)]
     pub fn take_recent_submission_images(&mut self) -> Vec<PathBuf> {
         let images = std::mem::take(&mut self.attached_images);
@@ -2433,6 +2456,46 @@ impl ChatComposer {
             .collect()
     }
 
+    /// Thumbnails of the attached local images that could be decoded, side by side, with each
+    /// image's placeholder label underneath.
+    fn attached_image_thumbnail_lines(&self) -> Vec<Line<'static>> {
+        let thumbnails: Vec<(&str, &Vec<Line<'static>>)> = self
+            .attached_images
+            .iter()
+            .filter_map(|image| {
+                let thumbnail = self.image_thumbnails.get(&image.path)?.as_ref()?;
+                Some((image.placeholder.as_str(), thumbnail))
+            })
+            .collect();
+        if thumbnails.is_empty() {
+            return Vec::new();
+        }
+        let mut lines = vec![Line::default(); THUMBNAIL_ROWS + 1];
+        for (idx, (placeholder, thumbnail)) in thumbnails.into_iter().enumerate() {
+            let width = THUMBNAIL_COLS.max(placeholder.chars().count());
+            if idx > 0 {
+                for line in &mut lines {
+                    line.push_span(" ");
+                }
+            }
+            for (line, row) in lines.iter_mut().zip(thumbnail) {
+                line.spans.extend(row.spans.iter().cloned());
+                if width > THUMBNAIL_COLS {
+                    line.push_span(" ".repeat(width - THUMBNAIL_COLS));
+                }
+            }
+            lines[THUMBNAIL_ROWS].push_span(format!("{placeholder:<width$}").cyan());
+        }
+        lines
+    }
+
+    /// Rows shown above the textarea: remote image labels, then local image thumbnails.
+    fn image_rows(&self, width: u16) -> Vec<Line<'static>> {
+        let mut lines = self.remote_images_lines(width);
+        lines.extend(self.attached_image_thumbnail_lines());
+        lines
+    }
+
     fn clear_remote_image_selection(&mut self) {
         self.selected_remote_image_index = None;
     }
@@ -3344,7 +3407,7 @@ impl ChatComposer {
         }
     }
 
-    pub(crate) fn set_status_line(&mut self, status_line: Option<Line<'static>>) -> bool {
+    pub(crate) fn set_status_line(&mut self, status_line: Option<StatusLine>) -> bool {
         if self.status_line_value == status_line {
             return false;
         }
@@ -3440,15 +3503,15 @@ impl Renderable for ChatComposer {
         let footer_total_height = footer_hint_height + footer_spacing;
         const COLS_WITH_MARGIN: u16 = LIVE_PREFIX_COLS + 1;
         let inner_width = width.saturating_sub(COLS_WITH_MARGIN);
-        let remote_images_height: u16 = self
-            .remote_images_lines(inner_width)
+        let image_rows_height: u16 = self
+            .image_rows(inner_width)
             .len()
             .try_into()
             .unwrap_or(u16::MAX);
-        let remote_images_separator = u16::from(remote_images_height > 0);
+        let image_rows_separator = u16::from(image_rows_height > 0);
         self.textarea.desired_height(inner_width)
-            + remote_images_height
-            + remote_images_separator
+            + image_rows_height
+            + image_rows_separator
             + 2
             + match &self.active_popup {
                 ActivePopup::None => footer_total_height,
@@ -3465,8 +3528,7 @@ impl Renderable for ChatComposer {
 
 impl ChatComposer {
     pub(crate) fn render_with_mask(&self, area: Rect, buf: &mut Buffer, mask_char: Option<char>) {
-        let [composer_rect, remote_images_rect, textarea_rect, popup_rect] =
-            self.layout_areas(area);
+        let [composer_rect, image_rows_rect, textarea_rect, popup_rect] = self.layout_areas(area);
         match &self.active_popup {
             ActivePopup::Command(popup) => {
                 popup.render_ref(popup_rect, buf);
@@ -3513,19 +3575,14 @@ impl ChatComposer {
                 };
                 let available_width =
                     hint_rect.width.saturating_sub(FOOTER_INDENT_COLS as u16) as usize;
-                let status_line = footer_props
-                    .status_line_value
-                    .as_ref()
-                    .map(|line| line.clone().dim());
+                let status_line = footer_props.status_line_value.as_ref();
                 let status_line_candidate = footer_props.status_line_enabled
                     && matches!(
                         footer_props.mode,
                         FooterMode::ComposerEmpty | FooterMode::ComposerHasDraft
                     );
                 let mut truncated_status_line = if status_line_candidate {
-                    status_line.as_ref().map(|line| {
-                        truncate_line_with_ellipsis_if_overflow(line.clone(), available_width)
-                    })
+                    status_line.map(|status_line| status_line.fit(available_width).dim())
                 } else {
                     None
                 };
@@ -3576,9 +3633,8 @@ impl ChatComposer {
                 if status_line_active
                     && let Some(max_left) = max_left_width_for_right(hint_rect, right_width)
                     && left_width > max_left
-                    && let Some(line) = status_line.as_ref().map(|line| {
-                        truncate_line_with_ellipsis_if_overflow(line.clone(), max_left as usize)
-                    })
+                    && let Some(line) =
+                        status_line.map(|status_line| status_line.fit(max_left as usize).dim())
                 {
                     left_width = line.width() as u16;
                     truncated_status_line = Some(line);
@@ -3687,10 +3743,10 @@ impl ChatComposer {
         }
         let style = user_message_style();
         Block::default().style(style).render_ref(composer_rect, buf);
-        if !remote_images_rect.is_empty() {
-            Paragraph::new(self.remote_images_lines(remote_images_rect.width))
+        if !image_rows_rect.is_empty() {
+            Paragraph::new(self.image_rows(image_rows_rect.width))
                 .style(style)
-                .render_ref(remote_images_rect, buf);
+                .render_ref(image_rows_rect, buf);
         }
         if !textarea_rect.is_empty() {
             let prompt = if self.input_enabled {
@@ -5257,6 +5313,40 @@ mod tests {
         assert!(composer.pending_pastes.is_empty());
     }
 
+    /// Behavior: a file attached from an external editor draft shows as `[File <label>]` and
+    /// expands to the file block on submit.
+    #[test]
+    fn attached_file_expands_on_submit() {
+        use crossterm::event::KeyCode;
+        use crossterm::event::KeyEvent;
+        use crossterm::event::KeyModifiers;
+
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        composer.set_steer_enabled(true);
+
+        composer.apply_external_edit("explain this\n".to_string());
+        composer.attach_file("src/lib.rs", "src/lib.rs:\n```\nfn a() {}\n```".to_string());
+        assert_eq!(composer.textarea.text(), "explain this\n[File src/lib.rs]");
+
+        let (result, _) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        match result {
+            InputResult::Submitted { text, .. } => {
+                assert_eq!(text, "explain this\nsrc/lib.rs:\n```\nfn a() {}\n```")
+            }
+            _ => panic!("expected Submitted"),
+        }
+        assert!(composer.pending_pastes.is_empty());
+    }
+
     /// Behavior: editing that removes a paste placeholder should also clear the associated
     /// `pending_pastes` entry so it cannot be submitted accidentally.
     #[test]
@@ -6907,6 +6997,40 @@ mod tests {
         assert_eq!(imgs, vec![tmp_path]);
     }
 
+    #[test]
+    fn attached_images_show_thumbnails_above_textarea() {
+        let tmp = tempdir().expect("create TempDir");
+        let tmp_path: PathBuf = tmp.path().join("codex_tui_test_thumbnail.png");
+        let img: ImageBuffer<Rgba<u8>, Vec<u8>> =
+            ImageBuffer::from_fn(4, 4, |x, _y| Rgba([60 * x as u8, 0, 0, 255]));
+        img.save(&tmp_path).expect("failed to write temp png");
+
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        let text = |line: &Line<'_>| {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        };
+
+        composer.attach_image(tmp_path);
+        // An image that cannot be decoded keeps its placeholder but has no thumbnail.
+        composer.attach_image(PathBuf::from("/tmp/codex_tui_test_missing.png"));
+
+        let rows = composer.image_rows(80);
+        assert_eq!(rows.len(), THUMBNAIL_ROWS + 1);
+        assert_eq!(text(&rows[THUMBNAIL_ROWS]), "[Image #1]");
+        assert_eq!(composer.textarea.text(), "[Image #1][Image #2]");
+    }
+
     #[test]
     fn selecting_custom_prompt_without_args_submits_content() {
         let prompt_text = "Hello from saved prompt";
@@ -30,6 +30,7 @@ pub(crate) struct CustomPromptView {
     placeholder: String,
     context_label: Option<String>,
     on_submit: PromptSubmitted,
+    allow_empty: bool,
 
     // UI state
     textarea: TextArea,
@@ -49,11 +50,18 @@ impl CustomPromptView {
             placeholder,
             context_label,
             on_submit,
+            allow_empty: false,
             textarea: TextArea::new(),
             textarea_state: RefCell::new(TextAreaState::default()),
             complete: false,
         }
     }
+
+    /// Let Enter submit an empty prompt instead of ignoring it.
+    pub(crate) fn allow_empty(mut self) -> Self {
+        self.allow_empty = true;
+        self
+    }
 }
 
 impl BottomPaneView for CustomPromptView {
@@ -70,7 +78,7 @@ impl BottomPaneView for CustomPromptView {
                 ..
             } => {
                 let text = self.textarea.text().trim().to_string();
-                if !text.is_empty() {
+                if !text.is_empty() || self.allow_empty {
                     (self.on_submit)(text);
                     self.complete = true;
                 }
@@ -124,7 +124,10 @@ impl WidgetRef for &FileSearchPopup {
                         .as_ref()
                         .map(|v| v.iter().map(|&i| i as usize).collect()),
                     display_shortcut: None,
-                    description: None,
+                    description: m
+                        .snippet
+                        .as_ref()
+                        .map(|line| format!("{}: {}", line.line_number, line.text.trim())),
                     category_tag: None,
                     wrap_indent: None,
                     is_disabled: false,
@@ -32,6 +32,7 @@
 //! In short: `single_line_footer_layout` chooses *what* best fits, and the two
 //! render helpers choose whether to draw the chosen line or the default
 //! `FooterProps` mapping.
+use crate::bottom_pane::StatusLine;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
 use crate::render::line_utils::prefix_lines;
@@ -68,7 +69,7 @@ pub(crate) struct FooterProps {
     pub(crate) quit_shortcut_key: KeyBinding,
     pub(crate) context_window_percent: Option<i64>,
     pub(crate) context_window_used_tokens: Option<i64>,
-    pub(crate) status_line_value: Option<Line<'static>>,
+    pub(crate) status_line_value: Option<StatusLine>,
     pub(crate) status_line_enabled: bool,
 }
 
@@ -571,7 +572,7 @@ fn footer_from_props_lines(
             FooterMode::ComposerEmpty | FooterMode::ComposerHasDraft
         )
     {
-        return vec![status_line.clone().dim()];
+        return vec![status_line.line().dim()];
     }
     match props.mode {
         FooterMode::QuitShortcutReminder => {
@@ -990,7 +991,6 @@ const SHORTCUTS: &[ShortcutDescriptor] = &[
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::bottom_pane::selection_popup_common::truncate_line_with_ellipsis_if_overflow;
     use crate::test_backend::VT100Backend;
     use insta::assert_snapshot;
     use pretty_assertions::assert_eq;
@@ -1040,8 +1040,7 @@ mod tests {
                     props
                         .status_line_value
                         .as_ref()
-                        .map(|line| line.clone().dim())
-                        .map(|line| truncate_line_with_ellipsis_if_overflow(line, available_width))
+                        .map(|status_line| status_line.fit(available_width).dim())
                 } else {
                     None
                 };
@@ -1084,10 +1083,7 @@ mod tests {
                     && let Some(line) = props
                         .status_line_value
                         .as_ref()
-                        .map(|line| line.clone().dim())
-                        .map(|line| {
-                            truncate_line_with_ellipsis_if_overflow(line, max_left as usize)
-                        })
+                        .map(|status_line| status_line.fit(max_left as usize).dim())
                 {
                     left_width = line.width() as u16;
                     truncated_status_line = Some(line);
@@ -1442,7 +1438,7 @@ mod tests {
             quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
             context_window_percent: None,
             context_window_used_tokens: None,
-            status_line_value: Some(Line::from("Status line content".to_string())),
+            status_line_value: Some(StatusLine::new([("Status line content".to_string(), 0)])),
             status_line_enabled: true,
         };
 
@@ -1526,9 +1522,10 @@ mod tests {
             quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
             context_window_percent: Some(50),
             context_window_used_tokens: None,
-            status_line_value: Some(Line::from(
+            status_line_value: Some(StatusLine::new([(
                 "Status line content that should truncate before the mode indicator".to_string(),
-            )),
+                0,
+            )])),
             status_line_enabled: true,
         };
 
@@ -1553,10 +1550,11 @@ mod tests {
             quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
             context_window_percent: Some(50),
             context_window_used_tokens: None,
-            status_line_value: Some(Line::from(
+            status_line_value: Some(StatusLine::new([(
                 "Status line content that is definitely too long to fit alongside the mode label"
                     .to_string(),
-            )),
+                0,
+            )])),
             status_line_enabled: true,
         };
 
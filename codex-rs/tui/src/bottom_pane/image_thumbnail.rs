@@ -0,0 +1,93 @@
+//! Small previews of attached images, drawn above the composer's textarea.
+//!
+//! Each cell shows two pixels with `▀`: the upper pixel as the foreground and the lower one as
+//! the background. Terminal cells are about twice as tall as they are wide, so the two pixels
+//! come out roughly square and the thumbnail keeps the image's aspect ratio.
+
+use std::path::Path;
+
+use image::DynamicImage;
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use crate::color::blend;
+use crate::terminal_palette::best_color;
+use crate::terminal_palette::default_bg;
+
+/// Thumbnail width in cells; the width of the `[Image #N]` label printed under it.
+pub(crate) const THUMBNAIL_COLS: usize = 10;
+/// Thumbnail height in cells.
+pub(crate) const THUMBNAIL_ROWS: usize = 3;
+
+/// Thumbnail of the image at `path`, or `None` when it cannot be decoded.
+pub(crate) fn thumbnail_lines(path: &Path) -> Option<Vec<Line<'static>>> {
+    match image::open(path) {
+        Ok(image) => Some(render_thumbnail(&image)),
+        Err(err) => {
+            tracing::debug!("no thumbnail for {}: {err}", path.display());
+            None
+        }
+    }
+}
+
+/// `THUMBNAIL_ROWS` lines, each exactly `THUMBNAIL_COLS` cells wide.
+fn render_thumbnail(image: &DynamicImage) -> Vec<Line<'static>> {
+    let thumb = image
+        .thumbnail(THUMBNAIL_COLS as u32, (THUMBNAIL_ROWS * 2) as u32)
+        .to_rgba8();
+    // Transparent pixels are blended into the terminal background.
+    let backdrop = default_bg().unwrap_or((0, 0, 0));
+    let pixel = |x: u32, y: u32| {
+        (y < thumb.height()).then(|| {
+            let [r, g, b, a] = thumb.get_pixel(x, y).0;
+            best_color(blend((r, g, b), backdrop, f32::from(a) / 255.0))
+        })
+    };
+    (0..THUMBNAIL_ROWS as u32)
+        .map(|row| {
+            let mut spans: Vec<Span<'static>> = (0..thumb.width())
+                .map(|x| match (pixel(x, row * 2), pixel(x, row * 2 + 1)) {
+                    (Some(upper), Some(lower)) => {
+                        Span::styled("▀", Style::default().fg(upper).bg(lower))
+                    }
+                    (Some(upper), None) => Span::styled("▀", Style::default().fg(upper)),
+                    _ => Span::raw(" "),
+                })
+                .collect();
+            let padding = THUMBNAIL_COLS.saturating_sub(thumb.width() as usize);
+            if padding > 0 {
+                spans.push(Span::raw(" ".repeat(padding)));
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+    use image::RgbaImage;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn thumbnails_fill_a_fixed_box_and_keep_the_aspect_ratio() {
+        // 4:1, so the thumbnail is 10 cells wide but only 3 pixels (2 cells) tall.
+        let wide = DynamicImage::ImageRgba8(RgbaImage::from_pixel(40, 10, Rgba([255, 0, 0, 255])));
+        let lines = render_thumbnail(&wide);
+
+        assert_eq!(lines.len(), THUMBNAIL_ROWS);
+        for line in &lines {
+            assert_eq!(line.width(), THUMBNAIL_COLS);
+        }
+        let text = |line: &Line<'_>| {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        };
+        assert_eq!(text(&lines[0]), "▀".repeat(THUMBNAIL_COLS));
+        assert_eq!(text(&lines[2]), " ".repeat(THUMBNAIL_COLS));
+    }
+}
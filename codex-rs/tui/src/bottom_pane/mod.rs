@@ -17,6 +17,7 @@ use std::path::PathBuf;
 
 use crate::app_event::ConnectorsSnapshot;
 use crate::app_event_sender::AppEventSender;
+use crate::bottom_pane::changed_files_panel::ChangedFilesPanel;
 use crate::bottom_pane::queued_user_messages::QueuedUserMessages;
 use crate::bottom_pane::unified_exec_footer::UnifiedExecFooter;
 use crate::key_hint;
@@ -35,13 +36,13 @@ use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::text::Line;
 use std::time::Duration;
 
 mod app_link_view;
 mod approval_overlay;
 mod multi_select_picker;
 mod request_user_input;
+mod status_line;
 mod status_line_setup;
 pub(crate) use app_link_view::AppLinkView;
 pub(crate) use app_link_view::AppLinkViewParams;
@@ -63,6 +64,7 @@ pub(crate) struct MentionBinding {
     /// Canonical mention target (for example `app://...` or absolute SKILL.md path).
     pub(crate) path: String,
 }
+mod changed_files_panel;
 mod chat_composer;
 mod chat_composer_history;
 mod command_popup;
@@ -70,6 +72,7 @@ pub mod custom_prompt_view;
 mod experimental_features_view;
 mod file_search_popup;
 mod footer;
+mod image_thumbnail;
 mod list_selection_view;
 mod prompt_args;
 mod skill_popup;
@@ -85,16 +88,21 @@ pub(crate) use feedback_view::feedback_selection_params;
 pub(crate) use feedback_view::feedback_upload_consent_params;
 pub(crate) use skills_toggle_view::SkillsToggleItem;
 pub(crate) use skills_toggle_view::SkillsToggleView;
+pub(crate) use status_line::StatusLine;
 pub(crate) use status_line_setup::StatusLineItem;
 pub(crate) use status_line_setup::StatusLineSetupView;
 mod paste_burst;
 pub mod popup_consts;
+mod queued_messages_view;
 mod queued_user_messages;
 mod scroll_state;
 mod selection_popup_common;
 mod textarea;
 mod unified_exec_footer;
+pub(crate) use changed_files_panel::ChangedFileRow;
 pub(crate) use feedback_view::FeedbackNoteView;
+pub(crate) use queued_messages_view::QUEUED_MESSAGES_VIEW_ID;
+pub(crate) use queued_messages_view::QueuedMessagesView;
 
 /// How long the "press again to quit" hint stays visible.
 ///
@@ -164,8 +172,12 @@ pub(crate) struct BottomPane {
     /// When a status row exists, this summary is mirrored inline in that row;
     /// when no status row exists, it renders as its own footer row.
     unified_exec_footer: UnifiedExecFooter,
+    /// Progress of the agent's `update_plan` checklist, mirrored inline in the status row.
+    plan_progress: Option<String>,
     /// Queued user messages to show above the composer while a turn is running.
     queued_user_messages: QueuedUserMessages,
+    /// Files changed this session, shown above the composer once there are any.
+    changed_files: ChangedFilesPanel,
     context_window_percent: Option<i64>,
     context_window_used_tokens: Option<i64>,
 }
@@ -213,7 +225,9 @@ impl BottomPane {
             is_task_running: false,
             status: None,
             unified_exec_footer: UnifiedExecFooter::new(),
+            plan_progress: None,
             queued_user_messages: QueuedUserMessages::new(),
+            changed_files: ChangedFilesPanel::new(),
             esc_backtrack_hint: false,
             animations_enabled,
             context_window_percent: None,
@@ -706,6 +720,24 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// Update the changed-files panel shown above the composer.
+    pub(crate) fn set_changed_files(&mut self, files: Vec<ChangedFileRow>) {
+        if self.changed_files.set_files(files) {
+            self.request_redraw();
+        }
+    }
+
+    /// Expand the changed-files panel to list files, or collapse it to its summary row.
+    pub(crate) fn set_changed_files_expanded(&mut self, expanded: bool) {
+        if self.changed_files.set_expanded(expanded) {
+            self.request_redraw();
+        }
+    }
+
+    pub(crate) fn changed_files_expanded(&self) -> bool {
+        self.changed_files.is_expanded()
+    }
+
     /// Update the unified-exec process set and refresh whichever summary surface is active.
     ///
     /// The summary may be displayed inline in the status row or as a dedicated
@@ -717,13 +749,30 @@ impl BottomPane {
         }
     }
 
-    /// Copy unified-exec summary text into the active status row, if any.
+    /// Update the plan progress (e.g. `plan 2/5`) shown in the status row.
+    pub(crate) fn set_plan_progress(&mut self, progress: Option<String>) {
+        if self.plan_progress != progress {
+            self.plan_progress = progress;
+            self.sync_status_inline_message();
+            self.request_redraw();
+        }
+    }
+
+    /// Copy plan progress and unified-exec summary text into the active status row, if any.
     ///
     /// This keeps status-line inline text synchronized without forcing the
     /// standalone unified-exec footer row to be visible.
     fn sync_status_inline_message(&mut self) {
         if let Some(status) = self.status.as_mut() {
-            status.update_inline_message(self.unified_exec_footer.summary_text());
+            let message = [
+                self.plan_progress.clone(),
+                self.unified_exec_footer.summary_text(),
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" · ");
+            status.update_inline_message(Some(message));
         }
     }
 
@@ -766,6 +815,11 @@ impl BottomPane {
         self.push_view(view);
     }
 
+    /// `view_id` of the topmost view, if it has one.
+    pub(crate) fn active_view_id(&self) -> Option<&'static str> {
+        self.view_stack.last().and_then(|view| view.view_id())
+    }
+
     /// Called when the agent requests user approval.
     pub fn push_approval_request(&mut self, request: ApprovalRequest, features: &Features) {
         let request = if let Some(view) = self.view_stack.last_mut() {
@@ -894,6 +948,13 @@ impl BottomPane {
         }
     }
 
+    pub(crate) fn attach_file(&mut self, label: &str, contents: String) {
+        if self.view_stack.is_empty() {
+            self.composer.attach_file(label, contents);
+            self.request_redraw();
+        }
+    }
+
     #[cfg(test)]
     pub(crate) fn take_recent_submission_images(&mut self) -> Vec<PathBuf> {
         self.composer.take_recent_submission_images()
@@ -918,6 +979,9 @@ impl BottomPane {
             RenderableItem::Borrowed(view)
         } else {
             let mut flex = FlexRenderable::new();
+            if !self.changed_files.is_empty() {
+                flex.push(0, RenderableItem::Borrowed(&self.changed_files));
+            }
             if let Some(status) = &self.status {
                 flex.push(0, RenderableItem::Borrowed(status));
             }
@@ -927,8 +991,9 @@ impl BottomPane {
                 flex.push(0, RenderableItem::Borrowed(&self.unified_exec_footer));
             }
             let has_queued_messages = !self.queued_user_messages.messages.is_empty();
-            let has_status_or_footer =
-                self.status.is_some() || !self.unified_exec_footer.is_empty();
+            let has_status_or_footer = self.status.is_some()
+                || !self.unified_exec_footer.is_empty()
+                || !self.changed_files.is_empty();
             if has_queued_messages && has_status_or_footer {
                 flex.push(0, RenderableItem::Owned("".into()));
             }
@@ -943,7 +1008,7 @@ impl BottomPane {
         }
     }
 
-    pub(crate) fn set_status_line(&mut self, status_line: Option<Line<'static>>) {
+    pub(crate) fn set_status_line(&mut self, status_line: Option<StatusLine>) {
         if self.composer.set_status_line(status_line) {
             self.request_redraw();
         }
@@ -954,6 +1019,14 @@ impl BottomPane {
             self.request_redraw();
         }
     }
+
+    /// Approval requests shown or queued in the bottom pane's views.
+    pub(crate) fn pending_approvals(&self) -> usize {
+        self.view_stack
+            .iter()
+            .map(|view| view.pending_approvals())
+            .sum()
+    }
 }
 
 impl Renderable for BottomPane {
@@ -1008,6 +1081,7 @@ mod tests {
             reason: None,
             network_approval_context: None,
             proposed_execpolicy_amendment: None,
+            explanation: None,
         }
     }
 
@@ -0,0 +1,385 @@
+//! `/queue` editor for user messages waiting on the running turn.
+//!
+//! The view works on a snapshot of the queue. Closing it reports the kept
+//! entries (by their original position) in their new order, plus the entry the
+//! user picked for editing, and `ChatWidget` applies that to its own queue.
+//! Queued messages are not dispatched while the view is open.
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Constraint;
+use ratatui::layout::Layout;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Block;
+use ratatui::widgets::Widget;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::key_hint;
+use crate::render::Insets;
+use crate::render::RectExt as _;
+use crate::render::renderable::ColumnRenderable;
+use crate::render::renderable::Renderable;
+use crate::style::user_message_style;
+
+use super::CancellationEvent;
+use super::bottom_pane_view::BottomPaneView;
+use super::popup_consts::MAX_POPUP_ROWS;
+use super::scroll_state::ScrollState;
+use super::selection_popup_common::GenericDisplayRow;
+use super::selection_popup_common::measure_rows_height;
+use super::selection_popup_common::render_rows;
+
+pub(crate) const QUEUED_MESSAGES_VIEW_ID: &str = "queued-messages";
+
+pub(crate) struct QueuedMessagesView {
+    /// Remaining entries as `(original index, text)`, in their current order.
+    messages: Vec<(usize, String)>,
+    edit: Option<usize>,
+    state: ScrollState,
+    complete: bool,
+    app_event_tx: AppEventSender,
+    header: Box<dyn Renderable>,
+    footer_hint: Line<'static>,
+}
+
+impl QueuedMessagesView {
+    pub(crate) fn new(messages: Vec<String>, app_event_tx: AppEventSender) -> Self {
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from("Queued messages".bold()));
+        header.push(Line::from(
+            "Sent in this order once the current turn finishes.".dim(),
+        ));
+
+        let mut state = ScrollState::new();
+        if !messages.is_empty() {
+            state.selected_idx = Some(0);
+        }
+        Self {
+            messages: messages.into_iter().enumerate().collect(),
+            edit: None,
+            state,
+            complete: false,
+            app_event_tx,
+            header: Box::new(header),
+            footer_hint: queued_messages_hint_line(),
+        }
+    }
+
+    fn build_rows(&self) -> Vec<GenericDisplayRow> {
+        self.messages
+            .iter()
+            .enumerate()
+            .map(|(idx, (_, text))| {
+                let prefix = if self.state.selected_idx == Some(idx) {
+                    '›'
+                } else {
+                    ' '
+                };
+                let first_line = text.lines().next().unwrap_or_default();
+                let ellipsis = if text.lines().nth(1).is_some() {
+                    " …"
+                } else {
+                    ""
+                };
+                GenericDisplayRow {
+                    name: format!("{prefix} {}. {first_line}{ellipsis}", idx + 1),
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+
+    fn move_selection_up(&mut self) {
+        let len = self.messages.len();
+        if len == 0 {
+            return;
+        }
+        self.state.move_up_wrap(len);
+        self.state.ensure_visible(len, MAX_POPUP_ROWS.min(len));
+    }
+
+    fn move_selection_down(&mut self) {
+        let len = self.messages.len();
+        if len == 0 {
+            return;
+        }
+        self.state.move_down_wrap(len);
+        self.state.ensure_visible(len, MAX_POPUP_ROWS.min(len));
+    }
+
+    /// Swap the selected entry with its neighbour; `earlier` moves it towards the front.
+    fn move_selected_entry(&mut self, earlier: bool) {
+        let Some(idx) = self.state.selected_idx else {
+            return;
+        };
+        let target = if earlier {
+            idx.checked_sub(1)
+        } else {
+            Some(idx + 1).filter(|target| *target < self.messages.len())
+        };
+        if let Some(target) = target {
+            self.messages.swap(idx, target);
+            self.state.selected_idx = Some(target);
+            let len = self.messages.len();
+            self.state.ensure_visible(len, MAX_POPUP_ROWS.min(len));
+        }
+    }
+
+    fn delete_selected(&mut self) {
+        let Some(idx) = self.state.selected_idx else {
+            return;
+        };
+        if idx < self.messages.len() {
+            self.messages.remove(idx);
+        }
+        let len = self.messages.len();
+        self.state.selected_idx = if len == 0 {
+            None
+        } else {
+            Some(idx.min(len - 1))
+        };
+        self.state.ensure_visible(len, MAX_POPUP_ROWS.min(len));
+    }
+
+    fn edit_selected(&mut self) {
+        let Some(idx) = self.state.selected_idx else {
+            return;
+        };
+        if idx < self.messages.len() {
+            let (original, _) = self.messages.remove(idx);
+            self.edit = Some(original);
+        }
+        self.finish();
+    }
+
+    fn finish(&mut self) {
+        if self.complete {
+            return;
+        }
+        self.complete = true;
+        self.app_event_tx.send(AppEvent::QueuedUserMessagesEdited {
+            order: self
+                .messages
+                .iter()
+                .map(|(original, _)| *original)
+                .collect(),
+            edit: self.edit,
+        });
+    }
+
+    fn rows_width(total_width: u16) -> u16 {
+        total_width.saturating_sub(2)
+    }
+}
+
+impl BottomPaneView for QueuedMessagesView {
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        match key_event {
+            KeyEvent {
+                code: KeyCode::Left,
+                ..
+            } => self.move_selected_entry(true),
+            KeyEvent {
+                code: KeyCode::Right,
+                ..
+            } => self.move_selected_entry(false),
+            KeyEvent {
+                code: KeyCode::Up, ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('p'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('k'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => self.move_selection_up(),
+            KeyEvent {
+                code: KeyCode::Down,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('n'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('j'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => self.move_selection_down(),
+            KeyEvent {
+                code: KeyCode::Char('d'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Delete | KeyCode::Backspace,
+                ..
+            } => self.delete_selected(),
+            KeyEvent {
+                code: KeyCode::Char('e'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Enter,
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => self.edit_selected(),
+            KeyEvent {
+                code: KeyCode::Esc, ..
+            } => self.finish(),
+            _ => {}
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    fn view_id(&self) -> Option<&'static str> {
+        Some(QUEUED_MESSAGES_VIEW_ID)
+    }
+
+    fn on_ctrl_c(&mut self) -> CancellationEvent {
+        self.finish();
+        CancellationEvent::Handled
+    }
+}
+
+impl Renderable for QueuedMessagesView {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 {
+            return;
+        }
+
+        let [content_area, footer_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+
+        Block::default()
+            .style(user_message_style())
+            .render(content_area, buf);
+
+        let header_height = self
+            .header
+            .desired_height(content_area.width.saturating_sub(4));
+        let rows = self.build_rows();
+        let rows_width = Self::rows_width(content_area.width);
+        let rows_height = measure_rows_height(
+            &rows,
+            &self.state,
+            MAX_POPUP_ROWS,
+            rows_width.saturating_add(1),
+        );
+        let [header_area, _, list_area] = Layout::vertical([
+            Constraint::Max(header_height),
+            Constraint::Max(1),
+            Constraint::Length(rows_height),
+        ])
+        .areas(content_area.inset(Insets::vh(1, 2)));
+
+        self.header.render(header_area, buf);
+
+        if list_area.height > 0 {
+            let render_area = Rect {
+                x: list_area.x.saturating_sub(2),
+                y: list_area.y,
+                width: rows_width.max(1),
+                height: list_area.height,
+            };
+            render_rows(
+                render_area,
+                buf,
+                &rows,
+                &self.state,
+                MAX_POPUP_ROWS,
+                "  No queued messages",
+            );
+        }
+
+        let hint_area = Rect {
+            x: footer_area.x + 2,
+            y: footer_area.y,
+            width: footer_area.width.saturating_sub(2),
+            height: footer_area.height,
+        };
+        self.footer_hint.clone().dim().render(hint_area, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        let rows = self.build_rows();
+        let rows_width = Self::rows_width(width);
+        let rows_height = measure_rows_height(
+            &rows,
+            &self.state,
+            MAX_POPUP_ROWS,
+            rows_width.saturating_add(1),
+        );
+
+        let mut height = self.header.desired_height(width.saturating_sub(4));
+        height = height.saturating_add(rows_height + 3);
+        height.saturating_add(1)
+    }
+}
+
+fn queued_messages_hint_line() -> Line<'static> {
+    Line::from(vec![
+        key_hint::plain(KeyCode::Left).into(),
+        "/".into(),
+        key_hint::plain(KeyCode::Right).into(),
+        " move · ".into(),
+        key_hint::plain(KeyCode::Char('d')).into(),
+        " delete · ".into(),
+        key_hint::plain(KeyCode::Enter).into(),
+        " edit · ".into(),
+        key_hint::plain(KeyCode::Esc).into(),
+        " done".into(),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn press(view: &mut QueuedMessagesView, code: KeyCode) {
+        view.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn closing_reports_new_order_and_edited_entry() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let mut view = QueuedMessagesView::new(
+            vec![
+                "first".to_string(),
+                "second".to_string(),
+                "third".to_string(),
+            ],
+            AppEventSender::new(tx_raw),
+        );
+
+        // Move "first" behind "second", delete "third", then edit "second".
+        press(&mut view, KeyCode::Right);
+        press(&mut view, KeyCode::Down);
+        press(&mut view, KeyCode::Char('d'));
+        press(&mut view, KeyCode::Up);
+        press(&mut view, KeyCode::Enter);
+
+        assert!(view.is_complete());
+        match rx.try_recv() {
+            Ok(AppEvent::QueuedUserMessagesEdited { order, edit }) => {
+                assert_eq!((order, edit), (vec![0], Some(1)));
+            }
+            other => panic!("expected QueuedUserMessagesEdited, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Footer status line content.
+//!
+//! `ChatWidget` renders the configured `tui.status_line` items to text and hands them over with a
+//! drop rank each. The footer only learns its width at render time, so fitting happens there:
+//! whole items are dropped, highest rank first, until the rest fit, and only a lone remaining item
+//! that is still too wide gets cut short with an ellipsis.
+
+use ratatui::text::Line;
+use unicode_width::UnicodeWidthStr;
+
+use crate::bottom_pane::selection_popup_common::truncate_line_with_ellipsis_if_overflow;
+
+const SEPARATOR: &str = " · ";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Segment {
+    text: String,
+    /// Segments with a higher rank are dropped first.
+    drop_rank: usize,
+}
+
+/// Status line segments in display order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct StatusLine {
+    segments: Vec<Segment>,
+}
+
+impl StatusLine {
+    /// Builds a status line from `(text, drop_rank)` pairs in display order.
+    pub(crate) fn new(segments: impl IntoIterator<Item = (String, usize)>) -> Self {
+        Self {
+            segments: segments
+                .into_iter()
+                .map(|(text, drop_rank)| Segment { text, drop_rank })
+                .collect(),
+        }
+    }
+
+    /// The whole line, without fitting it to a width.
+    pub(crate) fn line(&self) -> Line<'static> {
+        Line::from(join(self.segments.iter()))
+    }
+
+    /// The line for at most `max_width` columns.
+    pub(crate) fn fit(&self, max_width: usize) -> Line<'static> {
+        let mut kept = vec![true; self.segments.len()];
+        // Highest rank first; among equal ranks, the rightmost segment goes first.
+        let mut drop_order: Vec<usize> = (0..self.segments.len()).collect();
+        drop_order.sort_by_key(|&idx| std::cmp::Reverse((self.segments[idx].drop_rank, idx)));
+        for idx in drop_order {
+            let remaining = kept.iter().filter(|kept| **kept).count();
+            if remaining <= 1 || self.width(&kept) <= max_width {
+                break;
+            }
+            kept[idx] = false;
+        }
+        let segments = self
+            .segments
+            .iter()
+            .zip(&kept)
+            .filter_map(|(segment, kept)| kept.then_some(segment));
+        truncate_line_with_ellipsis_if_overflow(Line::from(join(segments)), max_width)
+    }
+
+    fn width(&self, kept: &[bool]) -> usize {
+        let (count, width) = self
+            .segments
+            .iter()
+            .zip(kept)
+            .filter(|(_, kept)| **kept)
+            .fold((0, 0), |(count, width), (segment, _)| {
+                (count + 1, width + segment.text.width())
+            });
+        width + count.saturating_sub(1) * SEPARATOR.width()
+    }
+}
+
+fn join<'a>(segments: impl Iterator<Item = &'a Segment>) -> String {
+    segments
+        .map(|segment| segment.text.as_str())
+        .collect::<Vec<_>>()
+        .join(SEPARATOR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn text(line: &Line<'_>) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn drops_highest_ranked_items_until_the_line_fits() {
+        let status_line = StatusLine::new([
+            ("gpt-5.2-codex".to_string(), 0),
+            ("main".to_string(), 2),
+            ("~/src/codex".to_string(), 4),
+            ("82% left".to_string(), 1),
+        ]);
+
+        assert_eq!(
+            text(&status_line.fit(80)),
+            "gpt-5.2-codex · main · ~/src/codex · 82% left"
+        );
+        assert_eq!(
+            text(&status_line.fit(35)),
+            "gpt-5.2-codex · main · 82% left"
+        );
+        assert_eq!(text(&status_line.fit(26)), "gpt-5.2-codex · 82% left");
+        assert_eq!(text(&status_line.fit(20)), "gpt-5.2-codex");
+        assert_eq!(text(&status_line.fit(8)), "gpt-5.2…");
+    }
+
+    #[test]
+    fn equal_ranks_drop_from_the_end_of_the_line() {
+        let status_line = StatusLine::new([
+            ("first".to_string(), 1),
+            ("second".to_string(), 1),
+            ("third".to_string(), 1),
+        ]);
+
+        assert_eq!(text(&status_line.fit(16)), "first · second");
+        assert_eq!(text(&status_line.line()), "first · second · third");
+    }
+}
@@ -15,7 +15,13 @@
 //! - Context usage (remaining %, used %, window size)
 //! - Usage limits (5-hour, weekly)
 //! - Session info (ID, tokens used)
+//! - Provider request/token budget
+//! - Session cost (when prices are configured)
+//! - Sandbox mode, pending approvals, and queued messages
 //! - Application version
+//!
+//! When the line does not fit, items are dropped by [`StatusLineItem::default_priority`],
+//! or by `tui.status_line_priority` when it is set.
 
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
@@ -91,6 +97,21 @@ pub(crate) enum StatusLineItem {
 
     /// Full session UUID.
     SessionId,
+
+    /// Provider request and token budget left in the current window.
+    RequestBudget,
+
+    /// Estimated dollars spent in the current session.
+    Cost,
+
+    /// Sandbox mode commands run under.
+    SandboxMode,
+
+    /// Approval requests waiting for a decision.
+    PendingApprovals,
+
+    /// Messages queued to send after the current turn.
+    QueuedMessages,
 }
 
 impl StatusLineItem {
@@ -124,6 +145,44 @@ impl StatusLineItem {
             StatusLineItem::SessionId => {
                 "Current session identifier (omitted until session starts)"
             }
+            StatusLineItem::RequestBudget => {
+                "Provider requests and tokens left this minute (omitted when unavailable)"
+            }
+            StatusLineItem::Cost => {
+                "Estimated cost of the session (omitted unless the profile's quota sets prices)"
+            }
+            StatusLineItem::SandboxMode => "Sandbox mode for commands",
+            StatusLineItem::PendingApprovals => {
+                "Approval requests waiting for a decision (omitted when none)"
+            }
+            StatusLineItem::QueuedMessages => {
+                "Messages queued for after the current turn (omitted when none)"
+            }
+        }
+    }
+
+    /// Rank used to drop items when the status line does not fit; items with a higher rank are
+    /// dropped first.
+    pub(crate) fn default_priority(&self) -> usize {
+        match self {
+            StatusLineItem::ModelName | StatusLineItem::ModelWithReasoning => 0,
+            StatusLineItem::PendingApprovals
+            | StatusLineItem::ContextRemaining
+            | StatusLineItem::ContextUsed => 1,
+            StatusLineItem::QueuedMessages
+            | StatusLineItem::SandboxMode
+            | StatusLineItem::GitBranch => 2,
+            StatusLineItem::Cost
+            | StatusLineItem::FiveHourLimit
+            | StatusLineItem::WeeklyLimit
+            | StatusLineItem::RequestBudget => 3,
+            StatusLineItem::CurrentDir
+            | StatusLineItem::ProjectRoot
+            | StatusLineItem::UsedTokens => 4,
+            StatusLineItem::ContextWindowSize
+            | StatusLineItem::TotalInputTokens
+            | StatusLineItem::TotalOutputTokens => 5,
+            StatusLineItem::CodexVersion | StatusLineItem::SessionId => 6,
         }
     }
 
@@ -148,6 +207,11 @@ impl StatusLineItem {
             StatusLineItem::TotalInputTokens => "17,588 in",
             StatusLineItem::TotalOutputTokens => "265 out",
             StatusLineItem::SessionId => "019c19bd-ceb6-73b0-adc8-8ec0397b85cf",
+            StatusLineItem::RequestBudget => "req 499/500 tok 24K/30K",
+            StatusLineItem::Cost => "$0.42",
+            StatusLineItem::SandboxMode => "workspace-write",
+            StatusLineItem::PendingApprovals => "2 approvals",
+            StatusLineItem::QueuedMessages => "1 queued",
         }
     }
 }
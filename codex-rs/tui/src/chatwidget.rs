@@ -37,6 +37,7 @@ use std::sync::atomic::Ordering;
 use std::time::Duration;
 use std::time::Instant;
 
+use crate::bottom_pane::StatusLine;
 use crate::bottom_pane::StatusLineItem;
 use crate::bottom_pane::StatusLineSetupView;
 use crate::status::RateLimitWindowDisplay;
@@ -50,12 +51,14 @@ use codex_backend_client::Client as BackendClient;
 use codex_chatgpt::connectors;
 use codex_core::config::Config;
 use codex_core::config::ConstraintResult;
+use codex_core::config::types::ContextPackInject;
 use codex_core::config::types::Notifications;
 use codex_core::config::types::WindowsSandboxModeToml;
 use codex_core::config_loader::ConfigLayerStackOrdering;
 use codex_core::features::FEATURES;
 use codex_core::features::Feature;
 use codex_core::find_thread_name_by_id;
+use codex_core::format_exec_policy_error_with_source;
 use codex_core::git_info::current_branch_name;
 use codex_core::git_info::get_git_repo_root;
 use codex_core::git_info::local_git_branches;
@@ -69,7 +72,10 @@ use codex_core::protocol::AgentReasoningRawContentDeltaEvent;
 use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::BookmarkAddedEvent;
 use codex_core::protocol::CodexErrorInfo;
+use codex_core::protocol::CommitCompletedEvent;
+use codex_core::protocol::ConversationForkedEvent;
 use codex_core::protocol::CreditsSnapshot;
 use codex_core::protocol::DeprecationNoticeEvent;
 use codex_core::protocol::ErrorEvent;
@@ -89,9 +95,19 @@ use codex_core::protocol::McpStartupStatus;
 use codex_core::protocol::McpStartupUpdateEvent;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::ModelRoutedEvent;
+use codex_core::protocol::ModelTier;
 use codex_core::protocol::Op;
 use codex_core::protocol::PatchApplyBeginEvent;
+use codex_core::protocol::PullRequestCompletedEvent;
+use codex_core::protocol::QueuedUserMessagesUpdatedEvent;
 use codex_core::protocol::RateLimitSnapshot;
+use codex_core::protocol::RequestBudgetEvent;
+use codex_core::protocol::RequestBudgetSnapshot;
+use codex_core::protocol::RequestBudgetWindow;
+use codex_core::protocol::ResumeSummarizedEvent;
+use codex_core::protocol::RetriesExhaustedEvent;
+use codex_core::protocol::RetryExhaustedDecision;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SkillMetadata as ProtocolSkillMetadata;
@@ -102,13 +118,17 @@ use codex_core::protocol::TokenUsageInfo;
 use codex_core::protocol::TurnAbortReason;
 use codex_core::protocol::TurnCompleteEvent;
 use codex_core::protocol::TurnDiffEvent;
+use codex_core::protocol::TurnLatencyEvent;
 use codex_core::protocol::UndoCompletedEvent;
 use codex_core::protocol::UndoStartedEvent;
+use codex_core::protocol::UsageQuotaExceededEvent;
 use codex_core::protocol::UserMessageEvent;
 use codex_core::protocol::ViewImageToolCallEvent;
 use codex_core::protocol::WarningEvent;
 use codex_core::protocol::WebSearchBeginEvent;
 use codex_core::protocol::WebSearchEndEvent;
+use codex_core::sandbox_explain::command_for_line;
+use codex_core::sandbox_explain::explain_command;
 use codex_core::skills::model::SkillMetadata;
 #[cfg(target_os = "windows")]
 use codex_core::windows_sandbox::WindowsSandboxLevelExt;
@@ -122,6 +142,7 @@ use codex_protocol::config_types::CollaborationModeMask;
 use codex_protocol::config_types::ModeKind;
 use codex_protocol::config_types::Personality;
 use codex_protocol::config_types::Settings;
+use codex_protocol::config_types::Verbosity;
 #[cfg(target_os = "windows")]
 use codex_protocol::config_types::WindowsSandboxLevel;
 use codex_protocol::items::AgentMessageItem;
@@ -157,6 +178,9 @@ const PLAN_IMPLEMENTATION_YES: &str = "Yes, implement this plan";
 const PLAN_IMPLEMENTATION_NO: &str = "No, stay in Plan mode";
 const PLAN_IMPLEMENTATION_CODING_MESSAGE: &str = "Implement the plan.";
 const CONNECTORS_SELECTION_VIEW_ID: &str = "connectors-selection";
+/// Turns quicker than this end without a latency breakdown; it is there to
+/// explain slow turns.
+const LATENCY_BREAKDOWN_MIN_TURN_MS: u64 = 10_000;
 
 use crate::app_event::AppEvent;
 use crate::app_event::ConnectorsSnapshot;
@@ -168,6 +192,7 @@ use crate::bottom_pane::ApprovalRequest;
 use crate::bottom_pane::BottomPane;
 use crate::bottom_pane::BottomPaneParams;
 use crate::bottom_pane::CancellationEvent;
+use crate::bottom_pane::ChangedFileRow;
 use crate::bottom_pane::CollaborationModeIndicator;
 use crate::bottom_pane::ColumnWidthMode;
 use crate::bottom_pane::DOUBLE_PRESS_QUIT_SHORTCUT_ENABLED;
@@ -177,12 +202,15 @@ use crate::bottom_pane::FeedbackAudience;
 use crate::bottom_pane::InputResult;
 use crate::bottom_pane::LocalImageAttachment;
 use crate::bottom_pane::MentionBinding;
+use crate::bottom_pane::QUEUED_MESSAGES_VIEW_ID;
 use crate::bottom_pane::QUIT_SHORTCUT_TIMEOUT;
+use crate::bottom_pane::QueuedMessagesView;
 use crate::bottom_pane::SelectionAction;
 use crate::bottom_pane::SelectionItem;
 use crate::bottom_pane::SelectionViewParams;
 use crate::bottom_pane::custom_prompt_view::CustomPromptView;
 use crate::bottom_pane::popup_consts::standard_popup_hint_line;
+use crate::clipboard_paste::PasteImageError;
 use crate::clipboard_paste::paste_image_to_temp_png;
 use crate::collab;
 use crate::collaboration_modes;
@@ -191,6 +219,8 @@ use crate::exec_cell::CommandOutput;
 use crate::exec_cell::ExecCell;
 use crate::exec_cell::new_active_exec_command;
 use crate::exec_command::strip_bash_lc_and_escape;
+use crate::external_editor;
+use crate::external_editor::DraftAttachments;
 use crate::get_git_diff::get_git_diff;
 use crate::history_cell;
 use crate::history_cell::AgentMessageCell;
@@ -207,6 +237,7 @@ use crate::render::renderable::FlexRenderable;
 use crate::render::renderable::Renderable;
 use crate::render::renderable::RenderableExt;
 use crate::render::renderable::RenderableItem;
+use crate::session_changes::SessionChanges;
 use crate::slash_command::SlashCommand;
 use crate::status::RateLimitSnapshotDisplay;
 use crate::text_formatting::truncate_text;
@@ -241,6 +272,7 @@ use codex_file_search::FileMatch;
 use codex_protocol::openai_models::InputModality;
 use codex_protocol::openai_models::ModelPreset;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
+use codex_protocol::plan_tool::StepStatus;
 use codex_protocol::plan_tool::UpdatePlanArgs;
 use codex_utils_approval_presets::ApprovalPreset;
 use codex_utils_approval_presets::builtin_approval_presets;
@@ -506,6 +538,8 @@ pub(crate) struct ChatWidget {
     initial_user_message: Option<UserMessage>,
     token_info: Option<TokenUsageInfo>,
     rate_limit_snapshots_by_limit_id: BTreeMap<String, RateLimitSnapshotDisplay>,
+    /// Latest short-window request/token budget reported by the provider.
+    request_budget: Option<RequestBudgetSnapshot>,
     plan_type: Option<PlanType>,
     rate_limit_warnings: RateLimitWarningState,
     rate_limit_switch_prompt: RateLimitSwitchPromptState,
@@ -535,6 +569,8 @@ pub(crate) struct ChatWidget {
     /// bottom pane is treated as "running" while this is populated, even if no agent turn is
     /// currently executing.
     mcp_startup_status: Option<HashMap<String, McpStartupStatus>>,
+    /// Latest startup state of every MCP server this session, shown by `/mcp status`.
+    mcp_server_states: BTreeMap<String, McpStartupStatus>,
     connectors_cache: ConnectorsCacheState,
     connectors_prefetch_in_flight: bool,
     // Queue of interruptive UI events deferred during an active write cycle
@@ -560,6 +596,12 @@ pub(crate) struct ChatWidget {
     suppress_session_configured_redraw: bool,
     // User messages queued while a turn is in progress
     queued_user_messages: VecDeque<UserMessage>,
+    // Queue texts last recorded in the rollout via `Op::SetQueuedUserMessages`
+    recorded_queued_user_messages: Vec<String>,
+    // Bookmarks recorded in the rollout, oldest first, for the `/bookmarks` palette
+    bookmarks: Vec<BookmarkAddedEvent>,
+    // Files changed this session, from the turn diffs, for the changed-files panel and `/changes`
+    session_changes: SessionChanges,
     // Pending notification to show when unfocused on next Draw
     pending_notification: Option<Notification>,
     /// When `Some`, the user has pressed a quit shortcut and the second press
@@ -600,6 +642,8 @@ pub(crate) struct ChatWidget {
     last_separator_elapsed_secs: Option<u64>,
     // Runtime metrics accumulated across delta snapshots for the active turn.
     turn_runtime_metrics: RuntimeMetricsSummary,
+    // Per-stage latency reported by core just before the active turn completes.
+    turn_latency: Option<TurnLatencyEvent>,
     last_rendered_width: std::cell::Cell<Option<usize>>,
     // Feedback sink for /feedback
     feedback: codex_feedback::CodexFeedback,
@@ -875,7 +919,7 @@ impl ChatWidget {
     }
 
     /// Sets the currently rendered footer status-line value.
-    pub(crate) fn set_status_line(&mut self, status_line: Option<Line<'static>>) {
+    pub(crate) fn set_status_line(&mut self, status_line: Option<StatusLine>) {
         self.bottom_pane.set_status_line(status_line);
     }
 
@@ -889,6 +933,9 @@ impl ChatWidget {
     /// The omission behavior is intentional. If selected items are unavailable (for example before
     /// a session id exists or before branch lookup completes), those items are skipped without
     /// placeholders so the line remains compact and stable.
+    ///
+    /// Each value carries its drop rank from `tui.status_line_priority` (or the item's default
+    /// priority) so the footer can drop whole items on narrow terminals.
     pub(crate) fn refresh_status_line(&mut self) {
         let (items, invalid_items) = self.status_line_items_with_invalids();
         if self.thread_id.is_some()
@@ -931,18 +978,32 @@ impl ChatWidget {
         let mut parts = Vec::new();
         for item in items {
             if let Some(value) = self.status_line_value_for_item(&item) {
-                parts.push(value);
+                parts.push((value, self.status_line_drop_rank(&item)));
             }
         }
 
         let line = if parts.is_empty() {
             None
         } else {
-            Some(Line::from(parts.join(" · ")))
+            Some(StatusLine::new(parts))
         };
         self.set_status_line(line);
     }
 
+    /// Rank at which `item` is dropped from a status line that does not fit: items listed in
+    /// `tui.status_line_priority` by their position, all others after them by default priority.
+    fn status_line_drop_rank(&self, item: &StatusLineItem) -> usize {
+        let priority = self
+            .config
+            .tui_status_line_priority
+            .as_deref()
+            .unwrap_or_default();
+        priority
+            .iter()
+            .position(|id| id.parse::<StatusLineItem>().ok().as_ref() == Some(item))
+            .unwrap_or_else(|| priority.len() + item.default_priority())
+    }
+
     /// Records that status-line setup was canceled.
     ///
     /// Cancellation is intentionally side-effect free for config state; the existing configuration
@@ -1289,6 +1350,7 @@ impl ChatWidget {
 
     fn on_task_started(&mut self) {
         self.agent_turn_running = true;
+        self.session_changes.start_turn();
         self.turn_sleep_inhibitor.set_turn_running(true);
         self.saw_plan_update_this_turn = false;
         self.saw_plan_item_this_turn = false;
@@ -1297,6 +1359,7 @@ impl ChatWidget {
         self.adaptive_chunking.reset();
         self.plan_stream_controller = None;
         self.turn_runtime_metrics = RuntimeMetricsSummary::default();
+        self.turn_latency = None;
         self.otel_manager.reset_runtime_metrics();
         self.bottom_pane.clear_quit_shortcut_hint();
         self.quit_shortcut_expires_at = None;
@@ -1324,8 +1387,12 @@ impl ChatWidget {
             self.collect_runtime_metrics_delta();
             let runtime_metrics =
                 (!self.turn_runtime_metrics.is_empty()).then_some(self.turn_runtime_metrics);
+            let latency = self
+                .turn_latency
+                .take()
+                .filter(|latency| latency.total_ms >= LATENCY_BREAKDOWN_MIN_TURN_MS);
             let show_work_separator = self.needs_final_message_separator && self.had_work_activity;
-            if show_work_separator || runtime_metrics.is_some() {
+            if show_work_separator || runtime_metrics.is_some() || latency.is_some() {
                 let elapsed_seconds = if show_work_separator {
                     self.bottom_pane
                         .status_widget()
@@ -1334,15 +1401,16 @@ impl ChatWidget {
                 } else {
                     None
                 };
-                self.add_to_history(history_cell::FinalMessageSeparator::new(
-                    elapsed_seconds,
-                    runtime_metrics,
-                ));
+                self.add_to_history(
+                    history_cell::FinalMessageSeparator::new(elapsed_seconds, runtime_metrics)
+                        .with_latency(latency),
+                );
             }
             self.turn_runtime_metrics = RuntimeMetricsSummary::default();
             self.needs_final_message_separator = false;
             self.had_work_activity = false;
             self.request_status_line_branch_refresh();
+            self.announce("Codex is done and waiting for your next message.");
         }
         // Mark task stopped and request redraw now that all content is in history.
         self.pending_status_indicator_restore = false;
@@ -1627,6 +1695,12 @@ impl ChatWidget {
     }
 
     fn on_mcp_startup_update(&mut self, ev: McpStartupUpdateEvent) {
+        self.mcp_server_states
+            .insert(ev.server.clone(), ev.status.clone());
+        // Lazy servers start later, so they do not hold up input now.
+        if matches!(ev.status, McpStartupStatus::Lazy) {
+            return;
+        }
         let mut status = self.mcp_startup_status.take().unwrap_or_default();
         if let McpStartupStatus::Failed { error } = &ev.status {
             self.on_warning(error);
@@ -1804,6 +1878,14 @@ impl ChatWidget {
 
     fn on_plan_update(&mut self, update: UpdatePlanArgs) {
         self.saw_plan_update_this_turn = true;
+        let completed = update
+            .plan
+            .iter()
+            .filter(|item| matches!(item.status, StepStatus::Completed))
+            .count();
+        let total = update.plan.len();
+        self.bottom_pane
+            .set_plan_progress((completed < total).then(|| format!("plan {completed}/{total}")));
         self.add_to_history(history_cell::new_plan_update(update));
     }
 
@@ -1839,6 +1921,26 @@ impl ChatWidget {
         );
     }
 
+    fn on_retries_exhausted(&mut self, ev: RetriesExhaustedEvent) {
+        self.flush_answer_stream_with_separator();
+        self.add_to_history(history_cell::new_error_event(format!(
+            "{} (gave up after {} retries)",
+            ev.message, ev.retries
+        )));
+        self.open_retries_exhausted_popup(ev);
+        self.request_redraw();
+    }
+
+    fn on_usage_quota_exceeded(&mut self, ev: UsageQuotaExceededEvent) {
+        self.flush_answer_stream_with_separator();
+        self.add_to_history(history_cell::new_error_event(format!(
+            "Profile `{}` has used up its quota: {}.",
+            ev.profile, ev.message
+        )));
+        self.open_usage_quota_popup(ev);
+        self.request_redraw();
+    }
+
     fn on_exec_command_begin(&mut self, ev: ExecCommandBeginEvent) {
         self.flush_answer_stream_with_separator();
         if is_unified_exec_source(ev.source) {
@@ -2122,9 +2224,26 @@ impl ChatWidget {
 
     fn on_turn_diff(&mut self, unified_diff: String) {
         debug!("TurnDiffEvent: {unified_diff}");
+        if self.session_changes.record_turn_diff(&unified_diff) {
+            self.refresh_changed_files();
+        }
         self.refresh_status_line();
     }
 
+    fn refresh_changed_files(&mut self) {
+        let files = self
+            .session_changes
+            .files()
+            .into_iter()
+            .map(|file| ChangedFileRow {
+                path: display_path_for(&file.path, &self.config.cwd),
+                added: file.added,
+                removed: file.removed,
+            })
+            .collect();
+        self.bottom_pane.set_changed_files(files);
+    }
+
     fn on_deprecation_notice(&mut self, event: DeprecationNoticeEvent) {
         let DeprecationNoticeEvent { summary, details } = event;
         self.add_to_history(history_cell::new_deprecation_notice(summary, details));
@@ -2164,6 +2283,97 @@ impl ChatWidget {
         }
     }
 
+    fn on_commit_completed(&mut self, event: CommitCompletedEvent) {
+        let CommitCompletedEvent {
+            success,
+            commit_sha,
+            message,
+        } = event;
+        if success {
+            let subject = message.unwrap_or_else(|| "Commit created.".to_string());
+            let hint = commit_sha.map(|sha| sha.chars().take(7).collect::<String>());
+            self.add_info_message(format!("Committed: {subject}"), hint);
+        } else {
+            self.add_error_message(message.unwrap_or_else(|| "Commit failed.".to_string()));
+        }
+    }
+
+    fn on_pull_request_completed(&mut self, event: PullRequestCompletedEvent) {
+        let PullRequestCompletedEvent {
+            success,
+            url,
+            message,
+        } = event;
+        if success {
+            let title = message.unwrap_or_else(|| "Pull request opened.".to_string());
+            self.add_info_message(format!("Opened: {title}"), url);
+        } else {
+            self.add_error_message(
+                message.unwrap_or_else(|| "Opening the pull request failed.".to_string()),
+            );
+        }
+    }
+
+    fn on_conversation_forked(&mut self, event: ConversationForkedEvent) {
+        self.add_info_message(
+            format!("Forked into thread {}", event.thread_id),
+            Some(format!("codex resume {}", event.thread_id)),
+        );
+    }
+
+    fn on_request_budget(&mut self, event: RequestBudgetEvent) {
+        self.request_budget = Some(event.budget);
+        if let Some(delay_ms) = event.delay_ms
+            && delay_ms >= 1000
+        {
+            self.on_stream_error(
+                format!(
+                    "Waiting {}s for the provider rate limit",
+                    delay_ms.div_ceil(1000)
+                ),
+                None,
+            );
+        }
+        self.refresh_status_line();
+    }
+
+    fn on_model_routed(&mut self, event: ModelRoutedEvent) {
+        // Flagship turns are the norm; only call out turns that left it.
+        if event.tier == ModelTier::Flagship && !event.overridden {
+            return;
+        }
+        self.add_info_message(
+            format!("This turn runs on {}", event.model),
+            Some(event.reason),
+        );
+    }
+
+    /// Shows the summary that replaced older turns on resume, so the user can
+    /// see what the model still knows about them.
+    fn on_resume_summarized(&mut self, event: ResumeSummarizedEvent) {
+        let ResumeSummarizedEvent {
+            summary,
+            summarized_turns,
+            kept_turns,
+        } = event;
+        let mut lines: Vec<Line<'static>> = vec![
+            vec![
+                "• ".dim(),
+                format!(
+                    "Summarized {summarized_turns} earlier turn(s); the model sees this summary and the last {kept_turns} turn(s) in full:"
+                )
+                .into(),
+            ]
+            .into(),
+        ];
+        lines.extend(
+            summary
+                .lines()
+                .map(|line| Line::from(format!("  {line}").dark_gray())),
+        );
+        self.add_plain_history_lines(lines);
+    }
+
     fn on_stream_error(&mut self, message: String, additional_details: Option<String>) {
         if self.retry_status_header.is_none() {
             self.retry_status_header = Some(self.current_status_header.clone());
@@ -2351,12 +2561,14 @@ impl ChatWidget {
                     exit_code: ev.exit_code,
                     formatted_output: String::new(),
                     aggregated_output: String::new(),
+                    test_failures: Vec::new(),
                 }
             } else {
                 CommandOutput {
                     exit_code: ev.exit_code,
                     formatted_output: ev.formatted_output.clone(),
                     aggregated_output: ev.aggregated_output.clone(),
+                    test_failures: ev.test_failures.clone(),
                 }
             };
             cell.complete_call(&ev.call_id, output, ev.duration);
@@ -2388,6 +2600,7 @@ impl ChatWidget {
         self.flush_answer_stream_with_separator();
         let command = shlex::try_join(ev.command.iter().map(String::as_str))
             .unwrap_or_else(|_| ev.command.join(" "));
+        self.announce(format!("Approval needed to run: {command}"));
         self.notify(Notification::ExecApprovalRequested { command });
 
         let request = ApprovalRequest::Exec {
@@ -2396,6 +2609,7 @@ impl ChatWidget {
             reason: ev.reason,
             network_approval_context: ev.network_approval_context,
             proposed_execpolicy_amendment: ev.proposed_execpolicy_amendment,
+            explanation: ev.explanation,
         };
         self.bottom_pane
             .push_approval_request(request, &self.config.features);
@@ -2404,6 +2618,9 @@ impl ChatWidget {
 
     pub(crate) fn handle_apply_patch_approval_now(&mut self, ev: ApplyPatchApprovalRequestEvent) {
         self.flush_answer_stream_with_separator();
+        let count = ev.changes.len();
+        let noun = if count == 1 { "file" } else { "files" };
+        self.announce(format!("Approval needed to edit {count} {noun}."));
 
         let request = ApprovalRequest::ApplyPatch {
             id: ev.call_id,
@@ -2422,6 +2639,7 @@ impl ChatWidget {
 
     pub(crate) fn handle_elicitation_request_now(&mut self, ev: ElicitationRequestEvent) {
         self.flush_answer_stream_with_separator();
+        self.announce(format!("{} needs your input.", ev.server_name));
 
         self.notify(Notification::ElicitationRequested {
             server_name: ev.server_name.clone(),
@@ -2439,6 +2657,7 @@ impl ChatWidget {
 
     pub(crate) fn handle_request_user_input_now(&mut self, ev: RequestUserInputEvent) {
         self.flush_answer_stream_with_separator();
+        self.announce("Codex has questions for you.");
         self.bottom_pane.push_user_input_request(ev);
         self.request_redraw();
     }
@@ -2631,6 +2850,7 @@ impl ChatWidget {
             initial_user_message,
             token_info: None,
             rate_limit_snapshots_by_limit_id: BTreeMap::new(),
+            request_budget: None,
             plan_type: None,
             rate_limit_warnings: RateLimitWarningState::default(),
             rate_limit_switch_prompt: RateLimitSwitchPromptState::default(),
@@ -2647,6 +2867,7 @@ impl ChatWidget {
             unified_exec_processes: Vec::new(),
             agent_turn_running: false,
             mcp_startup_status: None,
+            mcp_server_states: BTreeMap::new(),
             connectors_cache: ConnectorsCacheState::default(),
             connectors_prefetch_in_flight: false,
             interrupts: InterruptManager::new(),
@@ -2659,6 +2880,9 @@ impl ChatWidget {
             thread_name: None,
             forked_from: None,
             queued_user_messages: VecDeque::new(),
+            recorded_queued_user_messages: Vec::new(),
+            bookmarks: Vec::new(),
+            session_changes: SessionChanges::default(),
             show_welcome_banner: is_first_run,
             suppress_session_configured_redraw: false,
             pending_notification: None,
@@ -2674,6 +2898,7 @@ impl ChatWidget {
             plan_item_active: false,
             last_separator_elapsed_secs: None,
             turn_runtime_metrics: RuntimeMetricsSummary::default(),
+            turn_latency: None,
             last_rendered_width: std::cell::Cell::new(None),
             feedback,
             feedback_audience,
@@ -2798,6 +3023,7 @@ impl ChatWidget {
             initial_user_message,
             token_info: None,
             rate_limit_snapshots_by_limit_id: BTreeMap::new(),
+            request_budget: None,
             plan_type: None,
             rate_limit_warnings: RateLimitWarningState::default(),
             rate_limit_switch_prompt: RateLimitSwitchPromptState::default(),
@@ -2814,6 +3040,7 @@ impl ChatWidget {
             unified_exec_processes: Vec::new(),
             agent_turn_running: false,
             mcp_startup_status: None,
+            mcp_server_states: BTreeMap::new(),
             connectors_cache: ConnectorsCacheState::default(),
             connectors_prefetch_in_flight: false,
             interrupts: InterruptManager::new(),
@@ -2830,6 +3057,9 @@ impl ChatWidget {
             plan_delta_buffer: String::new(),
             plan_item_active: false,
             queued_user_messages: VecDeque::new(),
+            recorded_queued_user_messages: Vec::new(),
+            bookmarks: Vec::new(),
+            session_changes: SessionChanges::default(),
             show_welcome_banner: is_first_run,
             suppress_session_configured_redraw: false,
             pending_notification: None,
@@ -2841,6 +3071,7 @@ impl ChatWidget {
             had_work_activity: false,
             last_separator_elapsed_secs: None,
             turn_runtime_metrics: RuntimeMetricsSummary::default(),
+            turn_latency: None,
             last_rendered_width: std::cell::Cell::new(None),
             feedback,
             feedback_audience,
@@ -2954,6 +3185,7 @@ impl ChatWidget {
             initial_user_message,
             token_info: None,
             rate_limit_snapshots_by_limit_id: BTreeMap::new(),
+            request_budget: None,
             plan_type: None,
             rate_limit_warnings: RateLimitWarningState::default(),
             rate_limit_switch_prompt: RateLimitSwitchPromptState::default(),
@@ -2970,6 +3202,7 @@ impl ChatWidget {
             unified_exec_processes: Vec::new(),
             agent_turn_running: false,
             mcp_startup_status: None,
+            mcp_server_states: BTreeMap::new(),
             connectors_cache: ConnectorsCacheState::default(),
             connectors_prefetch_in_flight: false,
             interrupts: InterruptManager::new(),
@@ -2982,6 +3215,9 @@ impl ChatWidget {
             thread_name: None,
             forked_from: None,
             queued_user_messages: VecDeque::new(),
+            recorded_queued_user_messages: Vec::new(),
+            bookmarks: Vec::new(),
+            session_changes: SessionChanges::default(),
             show_welcome_banner: false,
             suppress_session_configured_redraw: true,
             pending_notification: None,
@@ -2997,6 +3233,7 @@ impl ChatWidget {
             plan_item_active: false,
             last_separator_elapsed_secs: None,
             turn_runtime_metrics: RuntimeMetricsSummary::default(),
+            turn_latency: None,
             last_rendered_width: std::cell::Cell::new(None),
             feedback,
             feedback_audience,
@@ -3071,23 +3308,7 @@ impl ChatWidget {
             } if modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
                 && c.eq_ignore_ascii_case(&'v') =>
             {
-                match paste_image_to_temp_png() {
-                    Ok((path, info)) => {
-                        tracing::debug!(
-                            "pasted image size={}x{} format={}",
-                            info.width,
-                            info.height,
-                            info.encoded_format.label()
-                        );
-                        self.attach_image(path);
-                    }
-                    Err(err) => {
-                        tracing::warn!("failed to paste image: {err}");
-                        self.add_to_history(history_cell::new_error_event(format!(
-                            "Failed to paste image: {err}",
-                        )));
-                    }
-                }
+                self.paste_clipboard_image(true);
                 return;
             }
             other if other.kind == KeyEventKind::Press => {
@@ -3140,7 +3361,13 @@ impl ChatWidget {
                             .bottom_pane
                             .take_recent_submission_mention_bindings(),
                     };
-                    if self.is_session_configured() && !self.is_plan_streaming_in_tui() {
+                    // Messages still queued while idle (restored on resume) go first.
+                    let idle_queue_pending = !self.bottom_pane.is_task_running()
+                        && !self.queued_user_messages.is_empty();
+                    if self.is_session_configured()
+                        && !self.is_plan_streaming_in_tui()
+                        && !idle_queue_pending
+                    {
                         // Submitted is only emitted when steer is enabled.
                         // Reset any reasoning header only when we are actually submitting a turn.
                         self.reasoning_buffer.clear();
@@ -3207,6 +3434,48 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    /// Attach the images and files listed in an edited draft's frontmatter after its text.
+    pub(crate) fn attach_draft_attachments(&mut self, attachments: DraftAttachments) {
+        let DraftAttachments { images, files } = attachments;
+        if images.is_empty() && files.is_empty() {
+            return;
+        }
+        // Attachments go on their own line after the body, separated by spaces.
+        let mut separator = if self.bottom_pane.composer_is_empty() {
+            ""
+        } else {
+            "\n"
+        };
+        let cwd = self.config.cwd.clone();
+        for listed in images {
+            let path = external_editor::resolve_attachment_path(&listed, &cwd);
+            match image::image_dimensions(&path) {
+                Ok(_) => {
+                    self.bottom_pane.insert_str(separator);
+                    self.attach_image(path);
+                    separator = " ";
+                }
+                Err(err) => self.add_error_message(format!(
+                    "Could not attach image {}: {err}",
+                    listed.display()
+                )),
+            }
+        }
+        for listed in files {
+            let label = listed.display().to_string();
+            let path = external_editor::resolve_attachment_path(&listed, &cwd);
+            match external_editor::read_attached_file(&path, &label) {
+                Ok(contents) => {
+                    self.bottom_pane.insert_str(separator);
+                    self.bottom_pane.attach_file(&label, contents);
+                    separator = " ";
+                }
+                Err(err) => self.add_error_message(format!("Could not attach file {label}: {err}")),
+            }
+        }
+        self.request_redraw();
+    }
+
     pub(crate) fn external_editor_state(&self) -> ExternalEditorState {
         self.external_editor_state
     }
@@ -3291,6 +3560,9 @@ impl ChatWidget {
             SlashCommand::Personality => {
                 self.open_personality_popup();
             }
+            SlashCommand::Verbosity => {
+                self.open_verbosity_popup();
+            }
             SlashCommand::Plan => {
                 if !self.collaboration_modes_enabled() {
                     self.add_info_message(
@@ -3379,6 +3651,12 @@ impl ChatWidget {
                     "Usage: /sandbox-add-read-dir <absolute-directory-path>".to_string(),
                 );
             }
+            SlashCommand::SandboxExplain => {
+                self.add_error_message("Usage: /sandbox-explain <command>".to_string());
+            }
+            SlashCommand::Context => {
+                self.show_context_packs();
+            }
             SlashCommand::Experimental => {
                 self.open_experimental_popup();
             }
@@ -3387,16 +3665,22 @@ impl ChatWidget {
             }
             SlashCommand::Logout => {
                 if let Err(e) = codex_core::auth::logout(
-                    &self.config.codex_home,
+                    &self.config.auth_home,
                     self.config.cli_auth_credentials_store_mode,
                 ) {
                     tracing::error!("failed to logout: {e}");
                 }
                 self.request_quit_without_confirmation();
             }
-            // SlashCommand::Undo => {
-            //     self.app_event_tx.send(AppEvent::CodexOp(Op::Undo));
-            // }
+            SlashCommand::Undo => {
+                self.submit_op(Op::UndoFileChange { paths: Vec::new() });
+            }
+            SlashCommand::Commit => {
+                self.submit_op(Op::CommitChanges { message: None });
+            }
+            SlashCommand::Pr => {
+                self.submit_op(Op::CreatePullRequest { title: None });
+            }
             SlashCommand::Diff => {
                 self.add_diff_in_progress();
                 let tx = self.app_event_tx.clone();
@@ -3426,6 +3710,9 @@ impl ChatWidget {
             SlashCommand::DebugConfig => {
                 self.add_debug_config_output();
             }
+            SlashCommand::DebugFrames => {
+                self.app_event_tx.send(AppEvent::ToggleFrameStats);
+            }
             SlashCommand::Statusline => {
                 self.open_status_line_setup();
             }
@@ -3435,6 +3722,19 @@ impl ChatWidget {
             SlashCommand::Clean => {
                 self.clean_background_terminals();
             }
+            SlashCommand::Queue => {
+                self.open_queued_messages_view();
+            }
+            SlashCommand::Bookmark => {
+                self.app_event_tx
+                    .send(AppEvent::BookmarkLatestTurn { note: None });
+            }
+            SlashCommand::Bookmarks => {
+                self.open_bookmarks_popup();
+            }
+            SlashCommand::Changes => {
+                self.open_changes_popup();
+            }
             SlashCommand::MemoryDrop => {
                 self.submit_op(Op::DropMemories);
             }
@@ -3538,6 +3838,16 @@ impl ChatWidget {
                     .send(AppEvent::CodexOp(Op::SetThreadName { name }));
                 self.bottom_pane.drain_pending_submission_state();
             }
+            SlashCommand::Bookmark if !trimmed.is_empty() => {
+                let Some((note, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                self.app_event_tx
+                    .send(AppEvent::BookmarkLatestTurn { note: Some(note) });
+                self.bottom_pane.drain_pending_submission_state();
+            }
             SlashCommand::Plan if !trimmed.is_empty() => {
                 self.dispatch_command(cmd);
                 if self.active_mode_kind() != ModeKind::Plan {
@@ -3584,6 +3894,41 @@ impl ChatWidget {
                 });
                 self.bottom_pane.drain_pending_submission_state();
             }
+            SlashCommand::Commit if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                self.submit_op(Op::CommitChanges {
+                    message: Some(prepared_args),
+                });
+                self.bottom_pane.drain_pending_submission_state();
+            }
+            SlashCommand::Undo if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                let paths = prepared_args
+                    .split_whitespace()
+                    .map(PathBuf::from)
+                    .collect();
+                self.submit_op(Op::UndoFileChange { paths });
+                self.bottom_pane.drain_pending_submission_state();
+            }
+            SlashCommand::Pr if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                self.submit_op(Op::CreatePullRequest {
+                    title: Some(prepared_args),
+                });
+                self.bottom_pane.drain_pending_submission_state();
+            }
             SlashCommand::SandboxReadRoot if !trimmed.is_empty() => {
                 let Some((prepared_args, _prepared_elements)) =
                     self.bottom_pane.prepare_inline_args_submission(false)
@@ -3596,10 +3941,66 @@ impl ChatWidget {
                     });
                 self.bottom_pane.drain_pending_submission_state();
             }
+            SlashCommand::SandboxExplain if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                self.explain_sandbox_for(prepared_args);
+                self.bottom_pane.drain_pending_submission_state();
+            }
+            SlashCommand::Mcp if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                if prepared_args.trim() == "status" {
+                    self.add_mcp_status_output();
+                } else {
+                    self.add_error_message("Usage: /mcp [status]".to_string());
+                }
+                self.bottom_pane.drain_pending_submission_state();
+            }
+            SlashCommand::Context if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                self.submit_op(Op::AddContextPack {
+                    name: prepared_args.trim().to_string(),
+                });
+                self.bottom_pane.drain_pending_submission_state();
+            }
             _ => self.dispatch_command(cmd),
         }
     }
 
+    /// Reports what the sandbox would do with `line` without running it.
+    fn explain_sandbox_for(&mut self, line: String) {
+        let config = self.config.clone();
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let command = command_for_line(&line);
+            let cell = match explain_command(&config, &command).await {
+                Ok(explanation) => PlainHistoryCell::new(
+                    explanation
+                        .to_string()
+                        .lines()
+                        .map(|line| Line::from(line.to_string()))
+                        .collect(),
+                ),
+                Err(err) => history_cell::new_error_event(format!(
+                    "Failed to load rules: {}",
+                    format_exec_policy_error_with_source(&err)
+                )),
+            };
+            tx.send(AppEvent::InsertHistoryCell(Box::new(cell)));
+        });
+    }
+
     fn show_rename_prompt(&mut self) {
         let tx = self.app_event_tx.clone();
         let has_name = self
@@ -3633,9 +4034,42 @@ impl ChatWidget {
     }
 
     pub(crate) fn handle_paste(&mut self, text: String) {
+        // Terminals paste nothing when the clipboard holds only an image (a screenshot, say), so
+        // an empty paste into the composer looks for image data on the clipboard instead.
+        if text.is_empty() && self.bottom_pane.no_modal_or_popup_active() {
+            self.paste_clipboard_image(false);
+            return;
+        }
         self.bottom_pane.handle_paste(text);
     }
 
+    /// Attach the clipboard's image to the draft. With `report_missing` unset (an empty terminal
+    /// paste), a clipboard that has no image or cannot be read is not an error.
+    fn paste_clipboard_image(&mut self, report_missing: bool) {
+        match paste_image_to_temp_png() {
+            Ok((path, info)) => {
+                tracing::debug!(
+                    "pasted image size={}x{} format={}",
+                    info.width,
+                    info.height,
+                    info.encoded_format.label()
+                );
+                self.attach_image(path);
+            }
+            Err(err @ (PasteImageError::NoImage(_) | PasteImageError::ClipboardUnavailable(_)))
+                if !report_missing =>
+            {
+                tracing::debug!("empty paste without a clipboard image: {err}");
+            }
+            Err(err) => {
+                tracing::warn!("failed to paste image: {err}");
+                self.add_to_history(history_cell::new_error_event(format!(
+                    "Failed to paste image: {err}",
+                )));
+            }
+        }
+    }
+
     // Returns true if caller should skip rendering this frame (a future frame is scheduled).
     pub(crate) fn handle_paste_burst_tick(&mut self, frame_requester: FrameRequester) -> bool {
         if self.bottom_pane.flush_paste_burst_if_due() {
@@ -3661,6 +4095,16 @@ impl ChatWidget {
         }
     }
 
+    /// In screen-reader mode, reports a state change as a plain line in the history, where a
+    /// screen reader reads it, instead of only through the status indicator.
+    fn announce(&mut self, message: impl Into<String>) {
+        if self.config.tui_screen_reader {
+            self.add_to_history(history_cell::PlainHistoryCell::new(vec![Line::from(
+                message.into(),
+            )]));
+        }
+    }
+
     pub(crate) fn add_to_history(&mut self, cell: impl HistoryCell + 'static) {
         self.add_boxed_history(Box::new(cell));
     }
@@ -3689,6 +4133,10 @@ impl ChatWidget {
         {
             self.queued_user_messages.push_back(user_message);
             self.refresh_queued_user_messages();
+        } else if !self.queued_user_messages.is_empty() {
+            // A queue restored on resume is sent ahead of the new message.
+            self.queued_user_messages.push_back(user_message);
+            self.maybe_send_next_queued_input();
         } else {
             self.submit_user_message(user_message);
         }
@@ -3978,7 +4426,14 @@ impl ChatWidget {
     /// `replay_initial_messages()`. Callers should treat `None` as a "fake" id
     /// that must not be used to correlate follow-up actions.
     fn dispatch_event_msg(&mut self, id: Option<String>, msg: EventMsg, from_replay: bool) {
-        let is_stream_error = matches!(&msg, EventMsg::StreamError(_));
+        let is_stream_error = matches!(
+            &msg,
+            EventMsg::StreamError(_)
+                | EventMsg::RequestBudget(RequestBudgetEvent {
+                    delay_ms: Some(_),
+                    ..
+                })
+        );
         if !is_stream_error {
             self.restore_retry_status_header_if_present();
         }
@@ -3997,6 +4452,10 @@ impl ChatWidget {
         match msg {
             EventMsg::SessionConfigured(e) => self.on_session_configured(e),
             EventMsg::ThreadNameUpdated(e) => self.on_thread_name_updated(e),
+            EventMsg::QueuedUserMessagesUpdated(e) => {
+                self.on_queued_user_messages_updated(e, from_replay)
+            }
+            EventMsg::BookmarkAdded(e) => self.on_bookmark_added(e, from_replay),
             EventMsg::AgentMessage(AgentMessageEvent { message }) => self.on_agent_message(message),
             EventMsg::AgentMessageDelta(AgentMessageDeltaEvent { delta }) => {
                 self.on_agent_message_delta(delta)
@@ -4012,7 +4471,12 @@ impl ChatWidget {
                 self.on_agent_reasoning_final();
             }
             EventMsg::AgentReasoningSectionBreak(_) => self.on_reasoning_section_break(),
-            EventMsg::TurnStarted(_) => self.on_task_started(),
+            EventMsg::TurnStarted(_) => {
+                self.on_task_started();
+                if !from_replay {
+                    self.announce("Codex is working.");
+                }
+            }
             EventMsg::TurnComplete(TurnCompleteEvent {
                 last_agent_message, ..
             }) => self.on_task_complete(last_agent_message, from_replay),
@@ -4020,6 +4484,9 @@ impl ChatWidget {
                 self.set_token_info(ev.info);
                 self.on_rate_limit_snapshot(ev.rate_limits);
             }
+            EventMsg::RequestBudget(ev) => self.on_request_budget(ev),
+            EventMsg::ModelRouted(ev) => self.on_model_routed(ev),
+            EventMsg::TurnLatency(ev) => self.turn_latency = Some(ev),
             EventMsg::Warning(WarningEvent { message }) => self.on_warning(message),
             EventMsg::Error(ErrorEvent {
                 message,
@@ -4067,6 +4534,8 @@ impl ChatWidget {
             EventMsg::RequestUserInput(ev) => {
                 self.on_request_user_input(ev);
             }
+            EventMsg::RetriesExhausted(ev) => self.on_retries_exhausted(ev),
+            EventMsg::UsageQuotaExceeded(ev) => self.on_usage_quota_exceeded(ev),
             EventMsg::ExecCommandBegin(ev) => self.on_exec_command_begin(ev),
             EventMsg::TerminalInteraction(delta) => self.on_terminal_interaction(delta),
             EventMsg::ExecCommandOutputDelta(delta) => self.on_exec_command_output_delta(delta),
@@ -4097,6 +4566,9 @@ impl ChatWidget {
             }
             EventMsg::UndoStarted(ev) => self.on_undo_started(ev),
             EventMsg::UndoCompleted(ev) => self.on_undo_completed(ev),
+            EventMsg::CommitCompleted(ev) => self.on_commit_completed(ev),
+            EventMsg::PullRequestCompleted(ev) => self.on_pull_request_completed(ev),
+            EventMsg::ConversationForked(ev) => self.on_conversation_forked(ev),
             EventMsg::StreamError(StreamErrorEvent {
                 message,
                 additional_details,
@@ -4112,6 +4584,7 @@ impl ChatWidget {
             }
             EventMsg::ExitedReviewMode(review) => self.on_exited_review_mode(review),
             EventMsg::ContextCompacted(_) => self.on_agent_message("Context compacted".to_owned()),
+            EventMsg::ResumeSummarized(ev) => self.on_resume_summarized(ev),
             EventMsg::CollabAgentSpawnBegin(_) => {}
             EventMsg::CollabAgentSpawnEnd(ev) => self.on_collab_event(collab::spawn_end(ev)),
             EventMsg::CollabAgentInteractionBegin(_) => {}
@@ -4124,6 +4597,8 @@ impl ChatWidget {
             EventMsg::CollabCloseEnd(ev) => self.on_collab_event(collab::close_end(ev)),
             EventMsg::CollabResumeBegin(ev) => self.on_collab_event(collab::resume_begin(ev)),
             EventMsg::CollabResumeEnd(ev) => self.on_collab_event(collab::resume_end(ev)),
+            EventMsg::DelegateBegin(_) => {}
+            EventMsg::DelegateEnd(ev) => self.on_collab_event(collab::delegate_end(ev)),
             EventMsg::ThreadRolledBack(rollback) => {
                 if from_replay {
                     self.app_event_tx.send(AppEvent::ApplyThreadRollback {
@@ -4132,6 +4607,7 @@ impl ChatWidget {
                 }
             }
             EventMsg::RawResponseItem(_)
+            | EventMsg::ReadPrefetchStats(_)
             | EventMsg::ItemStarted(_)
             | EventMsg::AgentMessageContentDelta(_)
             | EventMsg::ReasoningContentDelta(_)
@@ -4280,7 +4756,9 @@ impl ChatWidget {
 
     // If idle and there are queued inputs, submit exactly one to start the next turn.
     fn maybe_send_next_queued_input(&mut self) {
-        if self.bottom_pane.is_task_running() {
+        if self.bottom_pane.is_task_running()
+            || self.bottom_pane.active_view_id() == Some(QUEUED_MESSAGES_VIEW_ID)
+        {
             return;
         }
         if let Some(user_message) = self.queued_user_messages.pop_front() {
@@ -4291,13 +4769,210 @@ impl ChatWidget {
     }
 
     /// Rebuild and update the queued user messages from the current queue.
+    ///
+    /// Changes are also recorded in the rollout so a resumed session gets the queue back.
     fn refresh_queued_user_messages(&mut self) {
         let messages: Vec<String> = self
             .queued_user_messages
             .iter()
             .map(|m| m.text.clone())
             .collect();
+        if self.is_session_configured() && messages != self.recorded_queued_user_messages {
+            self.recorded_queued_user_messages = messages.clone();
+            self.app_event_tx
+                .send(AppEvent::CodexOp(Op::SetQueuedUserMessages {
+                    messages: messages.clone(),
+                }));
+        }
         self.bottom_pane.set_queued_user_messages(messages);
+        self.refresh_status_line();
+    }
+
+    /// Restore the queue recorded in a resumed session's rollout.
+    fn on_queued_user_messages_updated(
+        &mut self,
+        event: QueuedUserMessagesUpdatedEvent,
+        from_replay: bool,
+    ) {
+        // Live echoes only confirm what this widget already holds.
+        if !from_replay {
+            return;
+        }
+        self.recorded_queued_user_messages = event.messages.clone();
+        self.queued_user_messages = event.messages.into_iter().map(UserMessage::from).collect();
+        self.refresh_queued_user_messages();
+    }
+
+    fn on_bookmark_added(&mut self, event: BookmarkAddedEvent, from_replay: bool) {
+        if !from_replay {
+            let message = match &event.note {
+                Some(note) => format!("Bookmarked turn {}: {note}", event.turn),
+                None => format!("Bookmarked turn {}.", event.turn),
+            };
+            self.add_info_message(message, Some("Use /bookmarks to return to it.".to_string()));
+        }
+        self.bookmarks.push(event);
+    }
+
+    /// Ask for an optional note, then bookmark the cell of `turn` whose first line is `excerpt`.
+    pub(crate) fn show_bookmark_note_prompt(&mut self, turn: u32, excerpt: String) {
+        let tx = self.app_event_tx.clone();
+        let view = CustomPromptView::new(
+            format!("Bookmark turn {turn}"),
+            "Type an optional note and press Enter".to_string(),
+            Some(excerpt.clone()),
+            Box::new(move |note: String| {
+                tx.send(AppEvent::CodexOp(Op::AddBookmark {
+                    turn,
+                    excerpt: excerpt.clone(),
+                    note: (!note.is_empty()).then_some(note),
+                }));
+            }),
+        )
+        .allow_empty();
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
+    fn open_bookmarks_popup(&mut self) {
+        if self.bookmarks.is_empty() {
+            self.add_info_message(
+                "No bookmarks yet.".to_string(),
+                Some(
+                    "Use /bookmark, or press b in the transcript (Ctrl+T) to bookmark a cell."
+                        .to_string(),
+                ),
+            );
+            return;
+        }
+        let items = self
+            .bookmarks
+            .iter()
+            .rev()
+            .map(|bookmark| {
+                let turn = bookmark.turn;
+                let excerpt = bookmark.excerpt.clone();
+                let name = match &bookmark.note {
+                    Some(note) => format!("Turn {turn} · {note}"),
+                    None => format!("Turn {turn}"),
+                };
+                let search_value = format!("{name} {excerpt}");
+                SelectionItem {
+                    name,
+                    description: Some(bookmark.excerpt.clone()),
+                    actions: vec![Box::new(move |tx: &AppEventSender| {
+                        tx.send(AppEvent::OpenBookmark {
+                            turn,
+                            excerpt: excerpt.clone(),
+                        });
+                    })],
+                    dismiss_on_select: true,
+                    search_value: Some(search_value),
+                    ..Default::default()
+                }
+            })
+            .collect();
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Bookmarks".to_string()),
+            subtitle: Some("Open a bookmark in the transcript.".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            is_searchable: true,
+            search_placeholder: Some("Type to search bookmarks".to_string()),
+            ..Default::default()
+        });
+    }
+
+    fn open_changes_popup(&mut self) {
+        if self.session_changes.is_empty() {
+            self.add_info_message("No files changed in this session yet.".to_string(), None);
+            return;
+        }
+        let expanded = self.bottom_pane.changed_files_expanded();
+        let mut items = vec![SelectionItem {
+            name: if expanded {
+                "Collapse the changed-files panel".to_string()
+            } else {
+                "Expand the changed-files panel".to_string()
+            },
+            description: Some(if expanded {
+                "Show only the summary row above the composer.".to_string()
+            } else {
+                "List recently changed files above the composer.".to_string()
+            }),
+            actions: vec![Box::new(move |tx: &AppEventSender| {
+                tx.send(AppEvent::SetChangedFilesExpanded(!expanded));
+            })],
+            dismiss_on_select: true,
+            ..Default::default()
+        }];
+        items.extend(self.session_changes.files().into_iter().rev().map(|file| {
+            let name = display_path_for(&file.path, &self.config.cwd);
+            let path = file.path;
+            SelectionItem {
+                search_value: Some(name.clone()),
+                name,
+                description: Some(format!("+{} -{}", file.added, file.removed)),
+                actions: vec![Box::new(move |tx: &AppEventSender| {
+                    tx.send(AppEvent::OpenChangedFileDiff(path.clone()));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            }
+        }));
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Changed files".to_string()),
+            subtitle: Some("Open the diff of a file changed in this session.".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            is_searchable: true,
+            search_placeholder: Some("Type to search files".to_string()),
+            ..Default::default()
+        });
+    }
+
+    /// Expand or collapse the changed-files panel (from the `/changes` palette).
+    pub(crate) fn set_changed_files_expanded(&mut self, expanded: bool) {
+        self.bottom_pane.set_changed_files_expanded(expanded);
+    }
+
+    /// Each turn's diff of a file changed this session, as `(turn, unified_diff)`.
+    pub(crate) fn changed_file_diffs(&self, path: &Path) -> Vec<(u32, String)> {
+        self.session_changes.file_diffs(path)
+    }
+
+    fn open_queued_messages_view(&mut self) {
+        if self.queued_user_messages.is_empty() {
+            self.add_info_message("No queued messages.".to_string(), None);
+            return;
+        }
+        let messages = self
+            .queued_user_messages
+            .iter()
+            .map(|message| message.text.clone())
+            .collect();
+        let view = QueuedMessagesView::new(messages, self.app_event_tx.clone());
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
+    /// Apply a `/queue` edit: keep the messages at `order` (original positions) in that
+    /// order and move the message at `edit` back into the composer.
+    pub(crate) fn apply_queued_user_messages_edit(
+        &mut self,
+        order: Vec<usize>,
+        edit: Option<usize>,
+    ) {
+        let mut queued: Vec<Option<UserMessage>> =
+            self.queued_user_messages.drain(..).map(Some).collect();
+        self.queued_user_messages = order
+            .into_iter()
+            .filter_map(|index| queued.get_mut(index).and_then(Option::take))
+            .collect();
+        if let Some(message) = edit.and_then(|index| queued.get_mut(index).and_then(Option::take)) {
+            self.restore_user_message_to_composer(message);
+        }
+        self.refresh_queued_user_messages();
+        self.maybe_send_next_queued_input();
+        self.request_redraw();
     }
 
     pub(crate) fn add_diff_in_progress(&mut self) {
@@ -4508,6 +5183,62 @@ impl ChatWidget {
                 format_tokens_compact(self.status_line_total_usage().output_tokens)
             )),
             StatusLineItem::SessionId => self.thread_id.map(|id| id.to_string()),
+            StatusLineItem::RequestBudget => {
+                let budget = self.request_budget.as_ref()?;
+                let format_window = |label: &str, window: &RequestBudgetWindow| {
+                    format!(
+                        "{label} {}/{}",
+                        format_tokens_compact(window.remaining as i64),
+                        format_tokens_compact(window.limit as i64)
+                    )
+                };
+                let parts: Vec<String> = [
+                    budget
+                        .requests
+                        .as_ref()
+                        .map(|window| format_window("req", window)),
+                    budget
+                        .tokens
+                        .as_ref()
+                        .map(|window| format_window("tok", window)),
+                ]
+                .into_iter()
+                .flatten()
+                .collect();
+                (!parts.is_empty()).then(|| parts.join(" "))
+            }
+            StatusLineItem::Cost => {
+                let prices = self.config.usage_quota.as_ref()?.prices?;
+                let usage = self.status_line_total_usage();
+                let usd = prices.usd(
+                    usage.input_tokens,
+                    usage.cached_input_tokens,
+                    usage.output_tokens,
+                );
+                Some(format!("${usd:.2}"))
+            }
+            StatusLineItem::SandboxMode => Some(
+                match self.config.permissions.sandbox_policy.get() {
+                    SandboxPolicy::DangerFullAccess => "full-access",
+                    SandboxPolicy::ReadOnly { .. } => "read-only",
+                    SandboxPolicy::WorkspaceWrite { .. } => "workspace-write",
+                    SandboxPolicy::ExternalSandbox { .. } => "external-sandbox",
+                }
+                .to_string(),
+            ),
+            StatusLineItem::PendingApprovals => {
+                let pending =
+                    self.interrupts.pending_approvals() + self.bottom_pane.pending_approvals();
+                match pending {
+                    0 => None,
+                    1 => Some("1 approval".to_string()),
+                    pending => Some(format!("{pending} approvals")),
+                }
+            }
+            StatusLineItem::QueuedMessages => {
+                let queued = self.queued_user_messages.len();
+                (queued > 0).then(|| format!("{queued} queued"))
+            }
         }
     }
 
@@ -4893,6 +5624,162 @@ impl ChatWidget {
         });
     }
 
+    /// Choices for a turn whose model request ran out of retries. The turn
+    /// waits until one is picked or the turn is interrupted.
+    pub(crate) fn open_retries_exhausted_popup(&mut self, ev: RetriesExhaustedEvent) {
+        let mut choices = vec![(
+            "Retry now".to_string(),
+            "Send the same request again.".to_string(),
+            RetryExhaustedDecision::RetryNow,
+        )];
+        if let Some(model) = ev.fallback_model {
+            choices.push((
+                format!("Switch to {model}"),
+                format!("Continue this turn with {model}."),
+                RetryExhaustedDecision::SwitchModel { model },
+            ));
+        }
+        if let Some(item) = ev.largest_context_item {
+            choices.push((
+                "Drop the largest context item".to_string(),
+                format!("Remove the {item} and retry."),
+                RetryExhaustedDecision::DropLargestContextItem,
+            ));
+        }
+        choices.push((
+            "Abort".to_string(),
+            "End the turn with this error.".to_string(),
+            RetryExhaustedDecision::Abort,
+        ));
+
+        let items: Vec<SelectionItem> = choices
+            .into_iter()
+            .map(|(name, description, decision)| {
+                let id = ev.turn_id.clone();
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::CodexOp(Op::RetryExhaustedDecision {
+                        id: id.clone(),
+                        decision: decision.clone(),
+                    }));
+                })];
+                SelectionItem {
+                    name,
+                    description: Some(description),
+                    actions,
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from("The model request keeps failing".bold()));
+        header.push(Line::from(
+            "Choose how to continue. Press Esc twice to interrupt the turn instead.".dim(),
+        ));
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            header: Box::new(header),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    /// Asks whether to keep going past the profile's quota. The turn waits
+    /// until one is picked or the turn is interrupted.
+    pub(crate) fn open_usage_quota_popup(&mut self, ev: UsageQuotaExceededEvent) {
+        let items: Vec<SelectionItem> = [
+            ("Stop", "End the turn here.".to_string(), false),
+            (
+                "Override for this session",
+                format!("Keep using profile `{}` past its quota.", ev.profile),
+                true,
+            ),
+        ]
+        .into_iter()
+        .map(|(name, description, approve)| {
+            let id = ev.turn_id.clone();
+            let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                tx.send(AppEvent::CodexOp(Op::UsageQuotaOverride {
+                    id: id.clone(),
+                    approve,
+                }));
+            })];
+            SelectionItem {
+                name: name.to_string(),
+                description: Some(description),
+                actions,
+                dismiss_on_select: true,
+                ..Default::default()
+            }
+        })
+        .collect();
+
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from("Usage quota reached".bold()));
+        header.push(Line::from(ev.message.dim()));
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            header: Box::new(header),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn open_verbosity_popup(&mut self) {
+        if !self.is_session_configured() {
+            self.add_info_message(
+                "Verbosity selection is disabled until startup completes.".to_string(),
+                None,
+            );
+            return;
+        }
+
+        let current_verbosity = self.config.model_verbosity;
+        let choices = [
+            None,
+            Some(Verbosity::Low),
+            Some(Verbosity::Medium),
+            Some(Verbosity::High),
+        ];
+        let items: Vec<SelectionItem> = choices
+            .into_iter()
+            .map(|verbosity| {
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::CodexOp(Op::OverrideTurnSettings {
+                        model: None,
+                        effort: None,
+                        verbosity: Some(verbosity),
+                    }));
+                    tx.send(AppEvent::UpdateVerbosity(verbosity));
+                })];
+                SelectionItem {
+                    name: Self::verbosity_label(verbosity).to_string(),
+                    description: Some(Self::verbosity_description(verbosity).to_string()),
+                    is_current: current_verbosity == verbosity,
+                    actions,
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from("Select Verbosity".bold()));
+        header.push(Line::from(
+            "Applies from the next turn; ignored by models without verbosity support.".dim(),
+        ));
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            header: Box::new(header),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
     fn model_menu_header(&self, title: &str, subtitle: &str) -> Box<dyn Renderable> {
         let title = title.to_string();
         let subtitle = subtitle.to_string();
@@ -5124,16 +6011,10 @@ impl ChatWidget {
             let effort_label = effort_for_action
                 .map(|effort| effort.to_string())
                 .unwrap_or_else(|| "default".to_string());
-            tx.send(AppEvent::CodexOp(Op::OverrideTurnContext {
-                cwd: None,
-                approval_policy: None,
-                sandbox_policy: None,
-                windows_sandbox_level: None,
+            tx.send(AppEvent::CodexOp(Op::OverrideTurnSettings {
                 model: Some(model_for_action.clone()),
                 effort: Some(effort_for_action),
-                summary: None,
-                collaboration_mode: None,
-                personality: None,
+                verbosity: None,
             }));
             tx.send(AppEvent::UpdateModel(model_for_action.clone()));
             tx.send(AppEvent::UpdateReasoningEffort(effort_for_action));
@@ -5298,16 +6179,10 @@ impl ChatWidget {
 
     fn apply_model_and_effort(&self, model: String, effort: Option<ReasoningEffortConfig>) {
         self.app_event_tx
-            .send(AppEvent::CodexOp(Op::OverrideTurnContext {
-                cwd: None,
-                approval_policy: None,
-                sandbox_policy: None,
-                windows_sandbox_level: None,
+            .send(AppEvent::CodexOp(Op::OverrideTurnSettings {
                 model: Some(model.clone()),
                 effort: Some(effort),
-                summary: None,
-                collaboration_mode: None,
-                personality: None,
+                verbosity: None,
             }));
         self.app_event_tx.send(AppEvent::UpdateModel(model.clone()));
         self.app_event_tx
@@ -6104,6 +6979,10 @@ impl ChatWidget {
         self.config.personality = Some(personality);
     }
 
+    pub(crate) fn set_verbosity(&mut self, verbosity: Option<Verbosity>) {
+        self.config.model_verbosity = verbosity;
+    }
+
     /// Set the model in the widget's config copy and stored collaboration mode.
     pub(crate) fn set_model(&mut self, model: &str) {
         self.current_collaboration_mode =
@@ -6303,6 +7182,24 @@ impl ChatWidget {
         }
     }
 
+    fn verbosity_label(verbosity: Option<Verbosity>) -> &'static str {
+        match verbosity {
+            None => "Default",
+            Some(Verbosity::Low) => "Low",
+            Some(Verbosity::Medium) => "Medium",
+            Some(Verbosity::High) => "High",
+        }
+    }
+
+    fn verbosity_description(verbosity: Option<Verbosity>) -> &'static str {
+        match verbosity {
+            None => "Use the model's default verbosity.",
+            Some(Verbosity::Low) => "Short answers with minimal commentary.",
+            Some(Verbosity::Medium) => "Balanced detail.",
+            Some(Verbosity::High) => "Thorough answers with more explanation.",
+        }
+    }
+
     /// Cycle to the next collaboration mode variant (Plan -> Default -> Plan).
     fn cycle_collaboration_mode(&mut self) {
         if !self.collaboration_modes_enabled() {
@@ -6386,6 +7283,30 @@ impl ChatWidget {
         }
     }
 
+    /// Lists the configured context packs for a bare `/context`.
+    fn show_context_packs(&mut self) {
+        if self.config.context_packs.is_empty() {
+            self.add_info_message(
+                "No context packs configured".to_string(),
+                Some("Add them under [context_packs] in config.toml".to_string()),
+            );
+            return;
+        }
+        let names: Vec<String> = self
+            .config
+            .context_packs
+            .iter()
+            .map(|pack| match pack.inject {
+                ContextPackInject::SessionStart => format!("{} (session start)", pack.name),
+                ContextPackInject::OnDemand => pack.name.clone(),
+            })
+            .collect();
+        self.add_info_message(
+            format!("Context packs: {}", names.join(", ")),
+            Some("Add one with /context <name>".to_string()),
+        );
+    }
+
     pub(crate) fn add_info_message(&mut self, message: String, hint: Option<String>) {
         self.add_to_history(history_cell::new_info_event(message, hint));
         self.request_redraw();
@@ -6415,6 +7336,10 @@ impl ChatWidget {
         PlainHistoryCell::new(vec![line.into()])
     }
 
+    pub(crate) fn add_mcp_status_output(&mut self) {
+        self.add_to_history(history_cell::new_mcp_status_output(&self.mcp_server_states));
+    }
+
     pub(crate) fn add_mcp_output(&mut self) {
         if self.config.mcp_servers.is_empty() {
             self.add_to_history(history_cell::empty_mcp_output());
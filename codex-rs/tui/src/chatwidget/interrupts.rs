@@ -42,6 +42,21 @@ impl InterruptManager {
         self.queue.is_empty()
     }
 
+    /// Approval requests deferred until the current output settles.
+    pub(crate) fn pending_approvals(&self) -> usize {
+        self.queue
+            .iter()
+            .filter(|interrupt| {
+                matches!(
+                    interrupt,
+                    QueuedInterrupt::ExecApproval(_)
+                        | QueuedInterrupt::ApplyPatchApproval(_)
+                        | QueuedInterrupt::Elicitation(_)
+                )
+            })
+            .count()
+    }
+
     pub(crate) fn push_exec_approval(&mut self, ev: ExecApprovalRequestEvent) {
         self.queue.push_back(QueuedInterrupt::ExecApproval(ev));
     }
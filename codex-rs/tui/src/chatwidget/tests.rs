@@ -31,6 +31,8 @@ use codex_core::protocol::AgentReasoningDeltaEvent;
 use codex_core::protocol::AgentReasoningEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::BookmarkAddedEvent;
+use codex_core::protocol::CommitCompletedEvent;
 use codex_core::protocol::CreditsSnapshot;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
@@ -50,7 +52,9 @@ use codex_core::protocol::Op;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::PatchApplyStatus as CorePatchApplyStatus;
+use codex_core::protocol::QueuedUserMessagesUpdatedEvent;
 use codex_core::protocol::RateLimitWindow;
+use codex_core::protocol::ResumeSummarizedEvent;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SessionSource;
@@ -1606,6 +1610,7 @@ async fn make_chatwidget_manual(
         initial_user_message: None,
         token_info: None,
         rate_limit_snapshots_by_limit_id: BTreeMap::new(),
+        request_budget: None,
         plan_type: None,
         rate_limit_warnings: RateLimitWarningState::default(),
         rate_limit_switch_prompt: RateLimitSwitchPromptState::default(),
@@ -1624,6 +1629,7 @@ async fn make_chatwidget_manual(
         unified_exec_processes: Vec::new(),
         agent_turn_running: false,
         mcp_startup_status: None,
+        mcp_server_states: BTreeMap::new(),
         connectors_cache: ConnectorsCacheState::default(),
         connectors_prefetch_in_flight: false,
         interrupts: InterruptManager::new(),
@@ -1638,6 +1644,9 @@ async fn make_chatwidget_manual(
         frame_requester: FrameRequester::test_dummy(),
         show_welcome_banner: true,
         queued_user_messages: VecDeque::new(),
+        recorded_queued_user_messages: Vec::new(),
+        bookmarks: Vec::new(),
+        session_changes: SessionChanges::default(),
         suppress_session_configured_redraw: false,
         pending_notification: None,
         quit_shortcut_expires_at: None,
@@ -1652,6 +1661,7 @@ async fn make_chatwidget_manual(
         plan_item_active: false,
         last_separator_elapsed_secs: None,
         turn_runtime_metrics: RuntimeMetricsSummary::default(),
+        turn_latency: None,
         last_rendered_width: std::cell::Cell::new(None),
         feedback: codex_feedback::CodexFeedback::new(),
         feedback_audience: FeedbackAudience::External,
@@ -2342,6 +2352,45 @@ async fn plan_implementation_popup_shows_once_when_replay_precedes_live_turn_com
     );
 }
 
+#[tokio::test]
+async fn slow_turn_ends_with_latency_breakdown() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("gpt-5")).await;
+
+    for (turn_id, total_ms) in [("quick", 2_000), ("slow", 42_000)] {
+        chat.on_task_started();
+        chat.handle_codex_event(Event {
+            id: turn_id.to_string(),
+            msg: EventMsg::TurnLatency(TurnLatencyEvent {
+                turn_id: turn_id.to_string(),
+                total_ms,
+                model_ttfb_ms: 1_200,
+                model_stream_ms: 3_000,
+                model_requests: 1,
+                approval_wait_ms: 30_000,
+                approvals: 1,
+                ..Default::default()
+            }),
+        });
+        chat.handle_codex_event(Event {
+            id: turn_id.to_string(),
+            msg: EventMsg::TurnComplete(TurnCompleteEvent {
+                turn_id: turn_id.to_string(),
+                last_agent_message: None,
+            }),
+        });
+    }
+
+    let history = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<Vec<_>>();
+    assert_eq!(history.len(), 1, "expected one separator, got {history:?}");
+    assert!(
+        history[0].contains("Model: 4.2s (first token 1.2s) • Approvals: 30.0s"),
+        "unexpected separator: {history:?}"
+    );
+}
+
 #[tokio::test]
 async fn replayed_thread_rollback_emits_ordered_app_event() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("gpt-5")).await;
@@ -2397,6 +2446,7 @@ async fn plan_implementation_popup_skips_without_proposed_plan() {
         plan: vec![PlanItemArg {
             step: "First".to_string(),
             status: StepStatus::Pending,
+            id: None,
         }],
     });
     chat.on_task_complete(None, false);
@@ -2447,6 +2497,7 @@ async fn plan_implementation_popup_skips_when_rate_limit_prompt_pending() {
         plan: vec![PlanItemArg {
             step: "First".to_string(),
             status: StepStatus::Pending,
+            id: None,
         }],
     });
     chat.on_rate_limit_snapshot(Some(snapshot(92.0)));
@@ -2481,6 +2532,7 @@ async fn exec_approval_emits_proposed_command_and_decision_history() {
         network_approval_context: None,
         proposed_execpolicy_amendment: None,
         parsed_cmd: vec![],
+        explanation: None,
     };
     chat.handle_codex_event(Event {
         id: "sub-short".into(),
@@ -2526,6 +2578,7 @@ async fn exec_approval_decision_truncates_multiline_and_long_commands() {
         network_approval_context: None,
         proposed_execpolicy_amendment: None,
         parsed_cmd: vec![],
+        explanation: None,
     };
     chat.handle_codex_event(Event {
         id: "sub-multi".into(),
@@ -2577,6 +2630,7 @@ async fn exec_approval_decision_truncates_multiline_and_long_commands() {
         network_approval_context: None,
         proposed_execpolicy_amendment: None,
         parsed_cmd: vec![],
+        explanation: None,
     };
     chat.handle_codex_event(Event {
         id: "sub-long".into(),
@@ -2733,6 +2787,7 @@ fn end_exec(
             } else {
                 CoreExecCommandStatus::Failed
             },
+            test_failures: Vec::new(),
         }),
     });
 }
@@ -2802,6 +2857,176 @@ async fn alt_up_edits_most_recent_queued_message() {
     );
 }
 
+#[tokio::test]
+async fn queue_edit_reorders_and_moves_entry_into_composer() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.thread_id = Some(ThreadId::new());
+    chat.bottom_pane.set_task_running(true);
+
+    for text in ["first queued", "second queued", "third queued"] {
+        chat.queued_user_messages.push_back(UserMessage::from(text));
+    }
+    chat.refresh_queued_user_messages();
+
+    chat.apply_queued_user_messages_edit(vec![2, 0], Some(1));
+
+    let queued: Vec<&str> = chat
+        .queued_user_messages
+        .iter()
+        .map(|message| message.text.as_str())
+        .collect();
+    assert_eq!(queued, vec!["third queued", "first queued"]);
+    assert_eq!(chat.bottom_pane.composer_text(), "second queued");
+
+    let mut recorded = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        if let AppEvent::CodexOp(Op::SetQueuedUserMessages { messages }) = event {
+            recorded.push(messages);
+        }
+    }
+    assert_eq!(
+        recorded.last().cloned(),
+        Some(vec!["third queued".to_string(), "first queued".to_string()])
+    );
+}
+
+#[tokio::test]
+async fn replayed_queue_is_restored_and_sent_before_new_input() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual(None).await;
+    chat.thread_id = Some(ThreadId::new());
+
+    chat.replay_initial_messages(vec![EventMsg::QueuedUserMessagesUpdated(
+        QueuedUserMessagesUpdatedEvent {
+            messages: vec!["left over".to_string()],
+        },
+    )]);
+    assert_eq!(chat.queued_user_messages.len(), 1);
+
+    chat.bottom_pane
+        .set_composer_text("new input".to_string(), Vec::new(), Vec::new());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    match next_submit_op(&mut op_rx) {
+        Op::UserTurn { items, .. } => assert_eq!(
+            items,
+            vec![UserInput::Text {
+                text: "left over".to_string(),
+                text_elements: Vec::new(),
+            }]
+        ),
+        other => panic!("expected Op::UserTurn, got {other:?}"),
+    }
+    assert_eq!(chat.queued_user_messages.len(), 1);
+    assert_eq!(chat.queued_user_messages.front().unwrap().text, "new input");
+}
+
+#[tokio::test]
+async fn bookmarks_palette_lists_replayed_bookmarks_newest_first() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.thread_id = Some(ThreadId::new());
+
+    chat.replay_initial_messages(vec![
+        EventMsg::BookmarkAdded(BookmarkAddedEvent {
+            turn: 1,
+            excerpt: "add a cache".to_string(),
+            note: None,
+        }),
+        EventMsg::BookmarkAdded(BookmarkAddedEvent {
+            turn: 3,
+            excerpt: "Switched to an LRU".to_string(),
+            note: Some("why LRU".to_string()),
+        }),
+    ]);
+    assert!(drain_insert_history(&mut rx).is_empty());
+
+    chat.dispatch_command(SlashCommand::Bookmarks);
+    let popup = render_bottom_popup(&chat, 80);
+    let newest = popup
+        .find("Turn 3 · why LRU")
+        .expect("newest bookmark listed");
+    let oldest = popup.find("Turn 1").expect("oldest bookmark listed");
+    assert!(newest < oldest, "{popup}");
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let opened = std::iter::from_fn(|| rx.try_recv().ok()).find_map(|event| match event {
+        AppEvent::OpenBookmark { turn, excerpt } => Some((turn, excerpt)),
+        _ => None,
+    });
+    assert_eq!(opened, Some((3, "Switched to an LRU".to_string())));
+}
+
+#[tokio::test]
+async fn screen_reader_mode_announces_turn_state_changes() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.config.tui_screen_reader = true;
+
+    chat.handle_codex_event(Event {
+        id: "turn-1".into(),
+        msg: EventMsg::TurnStarted(TurnStartedEvent {
+            turn_id: "turn-1".to_string(),
+            model_context_window: None,
+            collaboration_mode_kind: ModeKind::Default,
+        }),
+    });
+    chat.handle_codex_event(Event {
+        id: "turn-1".into(),
+        msg: EventMsg::TurnComplete(TurnCompleteEvent {
+            turn_id: "turn-1".to_string(),
+            last_agent_message: None,
+        }),
+    });
+
+    let history: String = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect();
+    let working = history
+        .find("Codex is working.")
+        .expect("turn start announced");
+    let done = history
+        .find("Codex is done and waiting for your next message.")
+        .expect("turn end announced");
+    assert!(working < done, "{history}");
+}
+
+#[tokio::test]
+async fn turn_diffs_feed_the_changed_files_panel_and_palette() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.handle_codex_event(Event {
+        id: "turn-1".into(),
+        msg: EventMsg::TurnStarted(TurnStartedEvent {
+            turn_id: "turn-1".to_string(),
+            model_context_window: None,
+            collaboration_mode_kind: ModeKind::Default,
+        }),
+    });
+    chat.handle_codex_event(Event {
+        id: "turn-1".into(),
+        msg: EventMsg::TurnDiff(TurnDiffEvent {
+            unified_diff: "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1 +1 @@\n-old\n+new\n".to_string(),
+        }),
+    });
+
+    let bottom = render_bottom_popup(&chat, 80);
+    assert!(
+        bottom.contains("1 file changed (+1 -1) · /changes to view"),
+        "{bottom}"
+    );
+
+    chat.dispatch_command(SlashCommand::Changes);
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(popup.contains("Expand the changed-files panel"), "{popup}");
+    assert!(popup.contains("src/lib.rs"), "{popup}");
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let opened = std::iter::from_fn(|| rx.try_recv().ok()).find_map(|event| match event {
+        AppEvent::OpenChangedFileDiff(path) => Some(path),
+        _ => None,
+    });
+    assert_eq!(opened, Some(PathBuf::from("src/lib.rs")));
+}
+
 /// Pressing Up to recall the most recent history entry and immediately queuing
 /// it while a task is running should always enqueue the same text, even when it
 /// is queued repeatedly.
@@ -3195,6 +3420,7 @@ async fn exec_end_without_begin_uses_event_command() {
             duration: std::time::Duration::from_millis(5),
             formatted_output: "done".to_string(),
             status: CoreExecCommandStatus::Completed,
+            test_failures: Vec::new(),
         }),
     });
 
@@ -3952,6 +4178,31 @@ async fn slash_memory_update_submits_update_memories_op() {
     assert_matches!(op_rx.try_recv(), Ok(Op::UpdateMemories));
 }
 
+#[tokio::test]
+async fn slash_undo_restores_last_patch_or_named_files() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual(None).await;
+
+    chat.dispatch_command(SlashCommand::Undo);
+    assert_matches!(
+        op_rx.try_recv(),
+        Ok(Op::UndoFileChange { paths }) if paths.is_empty()
+    );
+
+    chat.bottom_pane.set_composer_text(
+        "/undo src/a.rs src/b.rs".to_string(),
+        Vec::new(),
+        Vec::new(),
+    );
+    chat.handle_key_event(KeyEvent::from(KeyCode::Enter));
+    match op_rx.try_recv() {
+        Ok(Op::UndoFileChange { paths }) => assert_eq!(
+            paths,
+            vec![PathBuf::from("src/a.rs"), PathBuf::from("src/b.rs")]
+        ),
+        other => panic!("expected Op::UndoFileChange, got {other:?}"),
+    }
+}
+
 #[tokio::test]
 async fn slash_resume_opens_picker() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -4043,6 +4294,52 @@ async fn undo_success_events_render_info_messages() {
     );
 }
 
+#[tokio::test]
+async fn commit_completed_event_renders_subject_and_short_sha() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.handle_codex_event(Event {
+        id: "turn-1".to_string(),
+        msg: EventMsg::CommitCompleted(CommitCompletedEvent {
+            success: true,
+            commit_sha: Some("0123456789abcdef".to_string()),
+            message: Some("Update 2 files".to_string()),
+        }),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected a single commit summary");
+    let rendered = lines_to_single_string(&cells[0]);
+    assert!(
+        rendered.contains("Committed: Update 2 files") && rendered.contains("0123456"),
+        "expected commit subject and short sha, got {rendered:?}"
+    );
+}
+
+#[tokio::test]
+async fn resume_summarized_event_renders_summary() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.handle_codex_event(Event {
+        id: "turn-1".to_string(),
+        msg: EventMsg::ResumeSummarized(ResumeSummarizedEvent {
+            summary: "Migrated the parser.\nTests still fail on Windows.".to_string(),
+            summarized_turns: 12,
+            kept_turns: 3,
+        }),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected a single summary cell");
+    let rendered = lines_to_single_string(&cells[0]);
+    assert!(
+        rendered.contains("Summarized 12 earlier turn(s)")
+            && rendered.contains("  Migrated the parser.")
+            && rendered.contains("  Tests still fail on Windows."),
+        "expected summary header and text, got {rendered:?}"
+    );
+}
+
 #[tokio::test]
 async fn undo_failure_events_render_error_message() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -4902,6 +5199,105 @@ async fn personality_selection_popup_snapshot() {
     assert_snapshot!("personality_selection_popup", popup);
 }
 
+#[tokio::test]
+async fn retries_exhausted_popup_sends_the_chosen_decision() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.handle_codex_event(Event {
+        id: "turn-1".into(),
+        msg: EventMsg::RetriesExhausted(RetriesExhaustedEvent {
+            turn_id: "turn-1".to_string(),
+            message: "stream disconnected before completion".to_string(),
+            retries: 5,
+            fallback_model: Some("gpt-5.1-codex-mini".to_string()),
+            largest_context_item: None,
+        }),
+    });
+
+    // Retry now -> Switch to gpt-5.1-codex-mini.
+    chat.handle_key_event(KeyEvent::from(KeyCode::Down));
+    chat.handle_key_event(KeyEvent::from(KeyCode::Enter));
+
+    let mut app_events = Vec::new();
+    while let Ok(ev) = rx.try_recv() {
+        app_events.push(ev);
+    }
+    assert!(
+        app_events.iter().any(|ev| matches!(
+            ev,
+            AppEvent::CodexOp(Op::RetryExhaustedDecision {
+                id,
+                decision: RetryExhaustedDecision::SwitchModel { model },
+            }) if id == "turn-1" && model == "gpt-5.1-codex-mini"
+        )),
+        "expected a switch_model decision, got {app_events:?}"
+    );
+}
+
+#[tokio::test]
+async fn usage_quota_popup_sends_the_override() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.handle_codex_event(Event {
+        id: "turn-1".into(),
+        msg: EventMsg::UsageQuotaExceeded(UsageQuotaExceededEvent {
+            turn_id: "turn-1".to_string(),
+            profile: "team".to_string(),
+            message: "2,100,000 of 2,000,000 tokens per day".to_string(),
+        }),
+    });
+
+    // Stop -> Override for this session.
+    chat.handle_key_event(KeyEvent::from(KeyCode::Down));
+    chat.handle_key_event(KeyEvent::from(KeyCode::Enter));
+
+    let mut app_events = Vec::new();
+    while let Ok(ev) = rx.try_recv() {
+        app_events.push(ev);
+    }
+    assert!(
+        app_events.iter().any(|ev| matches!(
+            ev,
+            AppEvent::CodexOp(Op::UsageQuotaOverride { id, approve: true }) if id == "turn-1"
+        )),
+        "expected a quota override, got {app_events:?}"
+    );
+}
+
+#[tokio::test]
+async fn verbosity_selection_overrides_turn_settings() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("bengalfox")).await;
+    chat.thread_id = Some(ThreadId::new());
+    chat.config.model_verbosity = None;
+    chat.open_verbosity_popup();
+
+    // Default -> Low -> Medium -> High.
+    for _ in 0..3 {
+        chat.handle_key_event(KeyEvent::from(KeyCode::Down));
+    }
+    chat.handle_key_event(KeyEvent::from(KeyCode::Enter));
+
+    let mut app_events = Vec::new();
+    while let Ok(ev) = rx.try_recv() {
+        app_events.push(ev);
+    }
+    assert!(
+        app_events.iter().any(|ev| matches!(
+            ev,
+            AppEvent::CodexOp(Op::OverrideTurnSettings {
+                model: None,
+                effort: None,
+                verbosity: Some(Some(Verbosity::High)),
+            })
+        )),
+        "expected OverrideTurnSettings with high verbosity, got {app_events:?}"
+    );
+    assert!(
+        app_events
+            .iter()
+            .any(|ev| matches!(ev, AppEvent::UpdateVerbosity(Some(Verbosity::High)))),
+        "expected UpdateVerbosity event"
+    );
+}
+
 #[tokio::test]
 async fn model_picker_hides_show_in_picker_false_models_from_cache() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("test-visible-model")).await;
@@ -4965,10 +5361,12 @@ async fn server_overloaded_error_does_not_switch_models() {
     }
 
     while let Ok(event) = op_rx.try_recv() {
-        if let Op::OverrideTurnContext { model, .. } = event {
+        if let Op::OverrideTurnContext { model, .. } | Op::OverrideTurnSettings { model, .. } =
+            event
+        {
             assert!(
                 model.is_none(),
-                "did not expect OverrideTurnContext model update on server-overloaded error"
+                "did not expect a model override on server-overloaded error"
             );
         }
     }
@@ -5652,6 +6050,7 @@ async fn approval_modal_exec_snapshot() -> anyhow::Result<()> {
             "world".into(),
         ])),
         parsed_cmd: vec![],
+        explanation: None,
     };
     chat.handle_codex_event(Event {
         id: "sub-approve".into(),
@@ -5709,6 +6108,7 @@ async fn approval_modal_exec_without_reason_snapshot() -> anyhow::Result<()> {
             "world".into(),
         ])),
         parsed_cmd: vec![],
+        explanation: None,
     };
     chat.handle_codex_event(Event {
         id: "sub-approve-noreason".into(),
@@ -5753,6 +6153,7 @@ async fn approval_modal_exec_multiline_prefix_hides_execpolicy_option_snapshot()
         network_approval_context: None,
         proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(command)),
         parsed_cmd: vec![],
+        explanation: None,
     };
     chat.handle_codex_event(Event {
         id: "sub-approve-multiline-trunc".into(),
@@ -6116,6 +6517,7 @@ async fn status_widget_and_approval_modal_snapshot() {
             "hello world".into(),
         ])),
         parsed_cmd: vec![],
+        explanation: None,
     };
     chat.handle_codex_event(Event {
         id: "sub-approve-exec".into(),
@@ -6289,6 +6691,7 @@ async fn apply_patch_events_emit_history_cells() {
         turn_id: "turn-c1".into(),
         auto_approved: true,
         changes: changes2,
+        content_hashes: HashMap::new(),
     };
     chat.handle_codex_event(Event {
         id: "s1".into(),
@@ -6367,6 +6770,7 @@ async fn apply_patch_manual_approval_adjusts_header() {
             turn_id: "turn-c1".into(),
             auto_approved: false,
             changes: apply_changes,
+            content_hashes: HashMap::new(),
         }),
     });
 
@@ -6420,6 +6824,7 @@ async fn apply_patch_manual_flow_snapshot() {
             turn_id: "turn-c1".into(),
             auto_approved: false,
             changes: apply_changes,
+            content_hashes: HashMap::new(),
         }),
     });
     let approved_lines = drain_insert_history(&mut rx)
@@ -6529,6 +6934,7 @@ async fn apply_patch_full_flow_integration_like() {
             turn_id: "turn-call-1".into(),
             auto_approved: false,
             changes: changes2,
+            content_hashes: HashMap::new(),
         }),
     });
     let mut end_changes = HashMap::new();
@@ -6680,14 +7086,17 @@ async fn plan_update_renders_history_cell() {
             PlanItemArg {
                 step: "Explore codebase".into(),
                 status: StepStatus::Completed,
+                id: None,
             },
             PlanItemArg {
                 step: "Implement feature".into(),
                 status: StepStatus::InProgress,
+                id: None,
             },
             PlanItemArg {
                 step: "Write tests".into(),
                 status: StepStatus::Pending,
+                id: None,
             },
         ],
     };
@@ -6810,6 +7219,41 @@ async fn status_line_invalid_items_warn_once() {
     );
 }
 
+#[tokio::test]
+async fn status_line_priority_overrides_default_drop_order() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
+    assert_eq!(
+        chat.status_line_drop_rank(&StatusLineItem::SessionId),
+        StatusLineItem::SessionId.default_priority()
+    );
+
+    chat.config.tui_status_line_priority =
+        Some(vec!["session-id".to_string(), "git-branch".to_string()]);
+
+    assert_eq!(chat.status_line_drop_rank(&StatusLineItem::SessionId), 0);
+    assert_eq!(chat.status_line_drop_rank(&StatusLineItem::GitBranch), 1);
+    assert_eq!(chat.status_line_drop_rank(&StatusLineItem::ModelName), 2);
+}
+
+#[tokio::test]
+async fn status_line_omits_empty_counts_and_unpriced_cost() {
+    let (chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    assert!(
+        chat.status_line_value_for_item(&StatusLineItem::SandboxMode)
+            .is_some()
+    );
+    assert_eq!(
+        chat.status_line_value_for_item(&StatusLineItem::PendingApprovals),
+        None
+    );
+    assert_eq!(
+        chat.status_line_value_for_item(&StatusLineItem::QueuedMessages),
+        None
+    );
+    assert_eq!(chat.status_line_value_for_item(&StatusLineItem::Cost), None);
+}
+
 #[tokio::test]
 async fn status_line_branch_state_resets_when_git_branch_disabled() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -7143,6 +7587,7 @@ async fn chatwidget_exec_and_status_layout_vt100_snapshot() {
             duration: std::time::Duration::from_millis(16000),
             formatted_output: String::new(),
             status: CoreExecCommandStatus::Completed,
+            test_failures: Vec::new(),
         }),
     });
     chat.handle_codex_event(Event {
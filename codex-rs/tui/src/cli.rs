@@ -49,6 +49,11 @@ pub struct Cli {
     #[clap(skip)]
     pub fork_show_all: bool,
 
+    /// Internal: keep only the first N user turns of the forked session. Set by
+    /// `codex resume --fork-at <N>`; `None` keeps the full history.
+    #[clap(skip)]
+    pub fork_at: Option<usize>,
+
     /// Model the agent should use.
     #[arg(long, short = 'm')]
     pub model: Option<String>,
@@ -67,6 +72,20 @@ pub struct Cli {
     #[arg(long = "profile", short = 'p')]
     pub config_profile: Option<String>,
 
+    /// Orchestration from config.toml that routes turns across agent roles.
+    #[arg(long = "orchestration", value_name = "NAME")]
+    pub orchestration: Option<String>,
+
+    /// Only talk to services on this machine: requires a localhost model
+    /// provider and turns off everything else that uses the network.
+    #[arg(long = "offline", default_value_t = false)]
+    pub offline: bool,
+
+    /// Record model API requests and responses, with credentials redacted,
+    /// as JSON lines in FILE.
+    #[arg(long = "wire-log", value_name = "FILE")]
+    pub wire_log: Option<PathBuf>,
+
     /// Select the sandbox policy to use when executing model-generated shell
     /// commands.
     #[arg(long = "sandbox", short = 's')]
@@ -90,6 +109,20 @@ pub struct Cli {
     )]
     pub dangerously_bypass_approvals_and_sandbox: bool,
 
+    /// Look but don't touch: force the read-only sandbox, deny every approval,
+    /// and leave out the tools that edit files or run code outside the sandbox.
+    #[arg(
+        long = "read-only",
+        default_value_t = false,
+        conflicts_with_all = [
+            "approval_policy",
+            "full_auto",
+            "dangerously_bypass_approvals_and_sandbox",
+            "sandbox_mode"
+        ]
+    )]
+    pub read_only: bool,
+
     /// Tell the agent to use the specified directory as its working root.
     #[clap(long = "cd", short = 'C', value_name = "DIR")]
     pub cwd: Option<PathBuf>,
@@ -102,6 +135,11 @@ pub struct Cli {
     #[arg(long = "add-dir", value_name = "DIR", value_hint = ValueHint::DirPath)]
     pub add_dir: Vec<PathBuf>,
 
+    /// Narrow the session to this sub-directory of the working directory, for
+    /// example one package of a monorepo. Repeat to scope several directories.
+    #[arg(long = "scope", value_name = "DIR", value_hint = ValueHint::DirPath)]
+    pub scope: Vec<PathBuf>,
+
     /// Disable alternate screen mode
     ///
     /// Runs the TUI in inline mode, preserving terminal scrollback history. This is useful
@@ -110,6 +148,11 @@ pub struct Cli {
     #[arg(long = "no-alt-screen", default_value_t = false)]
     pub no_alt_screen: bool,
 
+    /// Render for screen readers: no spinners, animations, or box drawing, and
+    /// state changes announced as plain lines. Same as `-c tui.screen_reader=true`.
+    #[arg(long = "screen-reader", default_value_t = false)]
+    pub screen_reader: bool,
+
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 }
@@ -0,0 +1,69 @@
+//! Copying text to the clipboard.
+//!
+//! Locally the text goes to the system clipboard through `arboard`. Over SSH, on Android/Termux,
+//! or when `arboard` cannot reach a clipboard, it is sent to the terminal as an OSC 52 sequence
+//! instead, which most terminals forward to the clipboard of the machine they run on.
+
+use std::io::Write;
+use std::io::stdout;
+
+use base64::Engine;
+
+/// Copies `text` to the clipboard.
+pub(crate) fn copy_to_clipboard(text: &str) -> std::io::Result<()> {
+    #[cfg(not(target_os = "android"))]
+    if !is_remote_session() {
+        match copy_with_arboard(text) {
+            Ok(()) => return Ok(()),
+            Err(err) => tracing::debug!("arboard copy failed, falling back to OSC 52: {err}"),
+        }
+    }
+    copy_with_osc52(text)
+}
+
+/// The system clipboard of an SSH session belongs to the remote host, not the user's machine.
+#[cfg(not(target_os = "android"))]
+fn is_remote_session() -> bool {
+    std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some()
+}
+
+#[cfg(not(target_os = "android"))]
+fn copy_with_arboard(text: &str) -> Result<(), arboard::Error> {
+    // On X11 and Wayland the copied text is served by the `Clipboard` that set it, so keep one
+    // alive instead of dropping it right away.
+    thread_local! {
+        static CLIPBOARD: std::cell::RefCell<Option<arboard::Clipboard>> =
+            const { std::cell::RefCell::new(None) };
+    }
+    CLIPBOARD.with_borrow_mut(|clipboard| {
+        if clipboard.is_none() {
+            *clipboard = Some(arboard::Clipboard::new()?);
+        }
+        match clipboard.as_mut() {
+            Some(clipboard) => clipboard.set_text(text),
+            None => Err(arboard::Error::ClipboardNotSupported),
+        }
+    })
+}
+
+fn copy_with_osc52(text: &str) -> std::io::Result<()> {
+    let mut out = stdout();
+    out.write_all(osc52_sequence(text).as_bytes())?;
+    out.flush()
+}
+
+fn osc52_sequence(text: &str) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    format!("\x1b]52;c;{encoded}\x07")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn osc52_sequence_encodes_text_as_base64() {
+        assert_eq!(osc52_sequence("hi\n"), "\x1b]52;c;aGkK\x07");
+    }
+}
@@ -9,7 +9,9 @@ use codex_core::protocol::CollabResumeBeginEvent;
 use codex_core::protocol::CollabResumeEndEvent;
 use codex_core::protocol::CollabWaitingBeginEvent;
 use codex_core::protocol::CollabWaitingEndEvent;
+use codex_core::protocol::DelegateEndEvent;
 use codex_protocol::ThreadId;
+use codex_protocol::num_format::format_with_separators;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::text::Span;
@@ -127,6 +129,37 @@ pub(crate) fn resume_end(ev: CollabResumeEndEvent) -> PlainHistoryCell {
     collab_event("Agent resumed", details)
 }
 
+pub(crate) fn delegate_end(ev: DelegateEndEvent) -> PlainHistoryCell {
+    let DelegateEndEvent {
+        call_id,
+        task_index,
+        prompt,
+        model,
+        role,
+        status,
+        total_tokens,
+    } = ev;
+    let mut details = vec![detail_line("call", call_id)];
+    if let Some(role) = role {
+        details.push(detail_line("role", role));
+    }
+    details.extend([
+        detail_line("model", model),
+        status_line(&status),
+        detail_line("tokens", format_with_separators(total_tokens)),
+    ]);
+    if let AgentStatus::Errored(message) = &status {
+        details.push(detail_line(
+            "error",
+            Span::from(truncate_text(message, COLLAB_AGENT_ERROR_PREVIEW_GRAPHEMES)).red(),
+        ));
+    }
+    if let Some(line) = prompt_line(&prompt) {
+        details.push(line);
+    }
+    collab_event(format!("Delegated task {task_index} finished"), details)
+}
+
 fn collab_event(title: impl Into<String>, details: Vec<Line<'static>>) -> PlainHistoryCell {
     let title = title.into();
     let mut lines: Vec<Line<'static>> =
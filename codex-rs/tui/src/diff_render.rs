@@ -76,6 +76,42 @@ impl From<DiffSummary> for Box<dyn Renderable> {
     }
 }
 
+/// One file's diff in each turn that changed it, for the `/changes` overlay.
+pub(crate) fn file_turn_diffs_renderable(
+    path: &Path,
+    cwd: &Path,
+    turn_diffs: Vec<(u32, String)>,
+) -> Box<dyn Renderable> {
+    let counts: Vec<(usize, usize)> = turn_diffs
+        .iter()
+        .map(|(_, diff)| calculate_add_remove_from_diff(diff))
+        .collect();
+    let added = counts.iter().map(|(added, _)| added).sum();
+    let removed = counts.iter().map(|(_, removed)| removed).sum();
+    let mut header = RtLine::from(display_path_for(path, cwd).bold());
+    header.push_span(" ");
+    header.extend(render_line_count_summary(added, removed));
+    let mut rows: Vec<Box<dyn Renderable>> = vec![Box::new(header)];
+
+    for ((turn, unified_diff), (added, removed)) in turn_diffs.into_iter().zip(counts) {
+        rows.push(Box::new(RtLine::from("")));
+        let mut turn_header = RtLine::from(format!("Turn {turn} "));
+        turn_header.extend(render_line_count_summary(added, removed));
+        rows.push(Box::new(turn_header));
+        rows.push(Box::new(RtLine::from("")));
+        let change = FileChange::Update {
+            unified_diff,
+            move_path: None,
+        };
+        rows.push(Box::new(InsetRenderable::new(
+            Box::new(change) as Box<dyn Renderable>,
+            Insets::tlbr(0, 2, 0, 0),
+        )));
+    }
+
+    Box::new(ColumnRenderable::with(rows))
+}
+
 pub(crate) fn create_diff_summary(
     changes: &HashMap<PathBuf, FileChange>,
     cwd: &Path,
@@ -2,6 +2,7 @@ use std::time::Duration;
 use std::time::Instant;
 
 use codex_core::protocol::ExecCommandSource;
+use codex_core::protocol::TestFailure;
 use codex_protocol::parse_command::ParsedCommand;
 
 #[derive(Clone, Debug, Default)]
@@ -11,6 +12,8 @@ pub(crate) struct CommandOutput {
     pub(crate) aggregated_output: String,
     /// The formatted output of the command, as seen by the model.
     pub(crate) formatted_output: String,
+    /// Failing tests parsed from the output.
+    pub(crate) test_failures: Vec<TestFailure>,
 }
 
 #[derive(Debug, Clone)]
@@ -97,6 +100,7 @@ impl ExecCell {
                     exit_code: 1,
                     formatted_output: String::new(),
                     aggregated_output: String::new(),
+                    test_failures: Vec::new(),
                 });
             }
         }
@@ -15,6 +15,7 @@ use crate::wrapping::word_wrap_lines;
 use codex_ansi_escape::ansi_escape_line;
 use codex_core::bash::extract_bash_command;
 use codex_core::protocol::ExecCommandSource;
+use codex_core::protocol::TestFailure;
 use codex_protocol::parse_command::ParsedCommand;
 use codex_utils_elapsed::format_duration;
 use itertools::Itertools;
@@ -27,6 +28,7 @@ use unicode_width::UnicodeWidthStr;
 pub(crate) const TOOL_CALL_MAX_LINES: usize = 5;
 const USER_SHELL_TOOL_CALL_MAX_LINES: usize = 50;
 const MAX_INTERACTION_PREVIEW_CHARS: usize = 80;
+const MAX_TEST_FAILURE_LINES: usize = 5;
 
 pub(crate) struct OutputLinesParams {
     pub(crate) line_limit: usize,
@@ -177,6 +179,35 @@ pub(crate) fn output_lines(
     }
 }
 
+/// Summary of the failing tests parsed from a command's output.
+fn test_failure_lines(failures: &[TestFailure]) -> Vec<Line<'static>> {
+    let count = failures.len();
+    let noun = if count == 1 { "test" } else { "tests" };
+    let mut lines = vec![Line::from(format!("{count} failed {noun}").red().bold())];
+    for failure in failures.iter().take(MAX_TEST_FAILURE_LINES) {
+        let mut spans = vec!["✗ ".red(), failure.name.clone().into()];
+        if let Some(file) = &failure.file {
+            let location = match failure.line {
+                Some(line) => format!(" {file}:{line}"),
+                None => format!(" {file}"),
+            };
+            spans.push(location.dim());
+        }
+        if !failure.message.is_empty() {
+            spans.push(format!(" {}", failure.message).dim());
+        }
+        lines.push(Line::from(spans));
+    }
+    if count > MAX_TEST_FAILURE_LINES {
+        lines.push(
+            format!("… +{} more", count - MAX_TEST_FAILURE_LINES)
+                .dim()
+                .into(),
+        );
+    }
+    lines
+}
+
 pub(crate) fn spinner(start_time: Option<Instant>, animations_enabled: bool) -> Span<'static> {
     if !animations_enabled {
         return "•".dim();
@@ -486,6 +517,23 @@ impl ExecCell {
                     ));
                 }
             }
+
+            if !output.test_failures.is_empty() {
+                let failure_opts = RtOptions::new(layout.output_block.wrap_width(width))
+                    .word_splitter(WordSplitter::NoHyphenation);
+                let mut failure_lines: Vec<Line<'static>> = Vec::new();
+                for line in test_failure_lines(&output.test_failures) {
+                    push_owned_lines(
+                        &word_wrap_line(&line, failure_opts.clone()),
+                        &mut failure_lines,
+                    );
+                }
+                lines.extend(prefix_lines(
+                    failure_lines,
+                    Span::from(layout.output_block.subsequent_prefix),
+                    Span::from(layout.output_block.subsequent_prefix),
+                ));
+            }
         }
 
         lines
@@ -633,6 +681,7 @@ mod tests {
             exit_code: 0,
             aggregated_output,
             formatted_output: String::new(),
+            test_failures: Vec::new(),
         };
         let width = 20;
         let layout = EXEC_DISPLAY_LAYOUT;
@@ -1,13 +1,21 @@
 use std::env;
 use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
 use std::process::Stdio;
 
 use color_eyre::eyre::Report;
 use color_eyre::eyre::Result;
+use serde::Deserialize;
 use tempfile::Builder;
 use thiserror::Error;
 use tokio::process::Command;
 
+use crate::file_reference::FileReference;
+
+/// Largest file that a draft's frontmatter can attach.
+const MAX_ATTACHED_FILE_BYTES: u64 = 256 * 1024;
+
 #[derive(Debug, Error)]
 pub(crate) enum EditorError {
     #[error("neither VISUAL nor EDITOR is set")]
@@ -60,6 +68,47 @@ pub(crate) async fn run_editor(seed: &str, editor_cmd: &[String]) -> Result<Stri
     let temp_path = Builder::new().suffix(".md").tempfile()?.into_temp_path();
     fs::write(&temp_path, seed)?;
 
+    let status = editor_command(editor_cmd)
+        .arg(&temp_path)
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .await?;
+
+    if !status.success() {
+        return Err(Report::msg(format!("editor exited with status {status}")));
+    }
+
+    let contents = fs::read_to_string(&temp_path)?;
+    Ok(contents)
+}
+
+/// Open a file reference clicked in an overlay and wait for the editor to exit.
+pub(crate) async fn open_file_reference(
+    editor_cmd: &[String],
+    reference: &FileReference,
+) -> Result<()> {
+    if editor_cmd.is_empty() {
+        return Err(Report::msg("editor command is empty"));
+    }
+
+    let status = editor_command(editor_cmd)
+        .args(reference.editor_args(&editor_cmd[0]))
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .await?;
+
+    if !status.success() {
+        return Err(Report::msg(format!("editor exited with status {status}")));
+    }
+    Ok(())
+}
+
+/// The editor program with its own arguments; `editor_cmd` must not be empty.
+fn editor_command(editor_cmd: &[String]) -> Command {
     let mut cmd = {
         #[cfg(windows)]
         {
@@ -74,20 +123,86 @@ pub(crate) async fn run_editor(seed: &str, editor_cmd: &[String]) -> Result<Stri
     if editor_cmd.len() > 1 {
         cmd.args(&editor_cmd[1..]);
     }
-    let status = cmd
-        .arg(&temp_path)
-        .stdin(Stdio::inherit())
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .status()
-        .await?;
+    cmd
+}
 
-    if !status.success() {
-        return Err(Report::msg(format!("editor exited with status {status}")));
+/// Attachments listed in the YAML frontmatter of a draft saved from the editor:
+///
+/// ```text
+/// ---
+/// images: [screenshots/bug.png]
+/// files:
+///   - src/render.rs
+/// ---
+/// Why does the footer render twice?
+/// ```
+///
+/// Relative paths are resolved against the session's working directory.
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct DraftAttachments {
+    /// Images attached as if they had been pasted.
+    pub(crate) images: Vec<PathBuf>,
+    /// Text files whose contents are sent with the message.
+    pub(crate) files: Vec<PathBuf>,
+}
+
+/// Split a saved draft into its frontmatter attachments and the message body.
+///
+/// A draft without a leading `---` line, or without a closing one, is all body.
+pub(crate) fn split_frontmatter(
+    draft: &str,
+) -> std::result::Result<(DraftAttachments, &str), serde_yaml::Error> {
+    let mut segments = draft.split_inclusive('\n');
+    if segments.next().map(str::trim_end) != Some("---") {
+        return Ok((DraftAttachments::default(), draft));
     }
+    let start = draft.find('\n').map_or(draft.len(), |idx| idx + 1);
+    let mut offset = start;
+    for segment in segments {
+        if segment.trim_end() == "---" {
+            let yaml = &draft[start..offset];
+            let attachments = if yaml.trim().is_empty() {
+                DraftAttachments::default()
+            } else {
+                serde_yaml::from_str(yaml)?
+            };
+            return Ok((attachments, &draft[offset + segment.len()..]));
+        }
+        offset += segment.len();
+    }
+    Ok((DraftAttachments::default(), draft))
+}
 
-    let contents = fs::read_to_string(&temp_path)?;
-    Ok(contents)
+/// Resolve a frontmatter path, expanding a leading `~/` and joining relative paths to `cwd`.
+pub(crate) fn resolve_attachment_path(path: &Path, cwd: &Path) -> PathBuf {
+    if let Ok(rest) = path.strip_prefix("~")
+        && let Some(home) = dirs::home_dir()
+    {
+        return home.join(rest);
+    }
+    cwd.join(path)
+}
+
+/// Read a file attached by frontmatter and wrap it in a fenced block labelled with `label`.
+pub(crate) fn read_attached_file(path: &Path, label: &str) -> Result<String> {
+    let len = fs::metadata(path)?.len();
+    if len > MAX_ATTACHED_FILE_BYTES {
+        return Err(Report::msg(format!(
+            "file is {len} bytes; the limit is {MAX_ATTACHED_FILE_BYTES}"
+        )));
+    }
+    let contents = fs::read_to_string(path)?;
+    let longest_run = contents
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    Ok(format!(
+        "{label}:\n{fence}\n{}\n{fence}",
+        contents.trim_end_matches('\n')
+    ))
 }
 
 #[cfg(test)]
@@ -152,6 +267,41 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn split_frontmatter_reads_attachments_and_body() {
+        let draft = "---\nimages: [shot.png]\nfiles:\n  - src/lib.rs\n---\nexplain this\n";
+        let (attachments, body) = split_frontmatter(draft).unwrap();
+        assert_eq!(
+            attachments,
+            DraftAttachments {
+                images: vec![PathBuf::from("shot.png")],
+                files: vec![PathBuf::from("src/lib.rs")],
+            }
+        );
+        assert_eq!(body, "explain this\n");
+
+        let unterminated = "---\nnot frontmatter";
+        let (attachments, body) = split_frontmatter(unterminated).unwrap();
+        assert_eq!(attachments, DraftAttachments::default());
+        assert_eq!(body, unterminated);
+
+        assert!(split_frontmatter("---\npictures: [a.png]\n---\nhi").is_err());
+    }
+
+    #[test]
+    fn attached_files_are_fenced_past_their_own_backticks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.md");
+        fs::write(&path, "```rust\nfn main() {}\n```\n").unwrap();
+
+        let block = read_attached_file(&path, "notes.md").unwrap();
+        assert_eq!(block, "notes.md:\n````\n```rust\nfn main() {}\n```\n````");
+        assert_eq!(
+            resolve_attachment_path(Path::new("notes.md"), dir.path()),
+            path
+        );
+    }
+
     #[tokio::test]
     #[cfg(unix)]
     async fn run_editor_returns_updated_content() {
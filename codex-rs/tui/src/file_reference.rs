@@ -0,0 +1,239 @@
+//! `path:line[:column]` references in rendered text, so that clicking one in an overlay can open
+//! the file in `$EDITOR` or copy the reference, and so that history can link to it.
+//!
+//! Rendered rows are plain text by the time a click reaches us, so a reference is simply the
+//! whitespace-delimited word under the pointer. It only counts when it names a file that exists,
+//! which keeps URLs, timestamps and prose from being treated as paths.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use unicode_width::UnicodeWidthChar;
+
+use crate::external_editor::resolve_attachment_path;
+
+/// Punctuation that surrounds paths in prose and markdown but is not part of them.
+const LEADING_PUNCTUATION: &[char] = &['(', '[', '{', '<', '"', '\'', '`'];
+const TRAILING_PUNCTUATION: &[char] = &[')', ']', '}', '>', '"', '\'', '`', ',', ';', '.', ':'];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct FileReference {
+    pub(crate) path: PathBuf,
+    /// 1-based line number, if the reference had one.
+    pub(crate) line: Option<u32>,
+}
+
+impl FileReference {
+    /// Parses `word` as a reference to an existing file, resolving relative paths against `cwd`.
+    ///
+    /// Paths from diff headers (`a/src/lib.rs`, `b/src/lib.rs`) are also tried without their
+    /// `a/` or `b/` prefix.
+    pub(crate) fn parse(word: &str, cwd: &Path) -> Option<Self> {
+        let word = trim_punctuation(word);
+        let word = word.strip_prefix("file://").unwrap_or(word);
+        let (path, line) = split_line_suffix(word);
+        if path.is_empty() {
+            return None;
+        }
+        let candidates = [Some(path), path.strip_prefix("a/"), path.strip_prefix("b/")];
+        candidates.into_iter().flatten().find_map(|candidate| {
+            let path = resolve_attachment_path(Path::new(candidate), cwd);
+            path.is_file().then_some(Self { path, line })
+        })
+    }
+
+    /// The reference as `path:line`, for copying.
+    pub(crate) fn location(&self) -> String {
+        match self.line {
+            Some(line) => format!("{}:{line}", self.path.display()),
+            None => self.path.display().to_string(),
+        }
+    }
+
+    /// Arguments that make `program` open the file at the referenced line.
+    ///
+    /// Terminal editors take `+line path`; the GUI editors below take `path:line` instead.
+    pub(crate) fn editor_args(&self, program: &str) -> Vec<String> {
+        let path = self.path.display().to_string();
+        let Some(line) = self.line else {
+            return vec![path];
+        };
+        let name = Path::new(program)
+            .file_stem()
+            .and_then(|name| name.to_str())
+            .unwrap_or(program);
+        match name {
+            "code" | "code-insiders" | "codium" | "cursor" | "windsurf" => {
+                vec!["--goto".to_string(), format!("{path}:{line}")]
+            }
+            "subl" | "zed" => vec![format!("{path}:{line}")],
+            _ => vec![format!("+{line}"), path],
+        }
+    }
+}
+
+/// Splits a trailing `:line` or `:line:column` off `word`.
+fn split_line_suffix(word: &str) -> (&str, Option<u32>) {
+    let Some((rest, last)) = word.rsplit_once(':') else {
+        return (word, None);
+    };
+    let Some(last) = parse_number(last) else {
+        return (word, None);
+    };
+    if let Some((path, line)) = rest.rsplit_once(':')
+        && let Some(line) = parse_number(line)
+    {
+        return (path, Some(line).filter(|&line| line > 0));
+    }
+    (rest, Some(last).filter(|&line| line > 0))
+}
+
+fn parse_number(text: &str) -> Option<u32> {
+    if text.bytes().all(|b| b.is_ascii_digit()) {
+        text.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// `word` without the punctuation that surrounds paths and URLs in prose.
+fn trim_punctuation(word: &str) -> &str {
+    word.trim_start_matches(LEADING_PUNCTUATION)
+        .trim_end_matches(TRAILING_PUNCTUATION)
+}
+
+/// The whitespace-delimited words of `text` with surrounding punctuation trimmed, each with its
+/// byte offset in `text`. Words that are only punctuation are skipped.
+pub(crate) fn trimmed_words(text: &str) -> Vec<(usize, &str)> {
+    let mut words = Vec::new();
+    let mut push = |start: usize, end: usize| {
+        let word = &text[start..end];
+        let leading = word.len() - word.trim_start_matches(LEADING_PUNCTUATION).len();
+        let trimmed = trim_punctuation(word);
+        if !trimmed.is_empty() {
+            words.push((start + leading, trimmed));
+        }
+    };
+    let mut start = None;
+    for (idx, ch) in text.char_indices() {
+        match (ch.is_whitespace(), start) {
+            (true, Some(word_start)) => {
+                push(word_start, idx);
+                start = None;
+            }
+            (false, None) => start = Some(idx),
+            _ => {}
+        }
+    }
+    if let Some(word_start) = start {
+        push(word_start, text.len());
+    }
+    words
+}
+
+/// The whitespace-delimited word covering display column `column` of `text`.
+pub(crate) fn word_at_column(text: &str, column: usize) -> Option<&str> {
+    let mut col = 0;
+    let mut start = None;
+    for (idx, ch) in text.char_indices() {
+        if ch.is_whitespace() {
+            if col >= column {
+                // Either the word just ended past `column`, or `column` is this whitespace.
+                return start
+                    .filter(|_| col > column)
+                    .map(|start| &text[start..idx]);
+            }
+            start = None;
+        } else if start.is_none() {
+            start = Some(idx);
+        }
+        col += ch.width().unwrap_or(0);
+    }
+    start.filter(|_| col > column).map(|start| &text[start..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn word_at_column_finds_the_word_under_the_pointer() {
+        let text = "  └ src/lib.rs:12 (+3 -1)";
+        assert_eq!(word_at_column(text, 0), None);
+        assert_eq!(word_at_column(text, 2), Some("└"));
+        assert_eq!(word_at_column(text, 4), Some("src/lib.rs:12"));
+        assert_eq!(word_at_column(text, 16), Some("src/lib.rs:12"));
+        assert_eq!(word_at_column(text, 17), None);
+        assert_eq!(word_at_column(text, 22), Some("-1)"));
+        assert_eq!(word_at_column(text, 40), None);
+    }
+
+    #[test]
+    fn trimmed_words_keep_their_offsets() {
+        assert_eq!(
+            trimmed_words("see (`src/lib.rs:12`), and https://example.com."),
+            vec![
+                (0, "see"),
+                (6, "src/lib.rs:12"),
+                (23, "and"),
+                (27, "https://example.com"),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_references_to_existing_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir(dir.path().join("src")).expect("create src");
+        std::fs::write(dir.path().join("src/lib.rs"), "").expect("write file");
+        let lib = dir.path().join("src/lib.rs");
+        let reference = |line| {
+            Some(FileReference {
+                path: lib.clone(),
+                line,
+            })
+        };
+
+        assert_eq!(
+            FileReference::parse("src/lib.rs", dir.path()),
+            reference(None)
+        );
+        assert_eq!(
+            FileReference::parse("src/lib.rs:12", dir.path()),
+            reference(Some(12))
+        );
+        assert_eq!(
+            FileReference::parse("(`src/lib.rs:12:5`),", dir.path()),
+            reference(Some(12))
+        );
+        assert_eq!(
+            FileReference::parse("b/src/lib.rs", dir.path()),
+            reference(None)
+        );
+        assert_eq!(FileReference::parse("src/main.rs:3", dir.path()), None);
+        assert_eq!(
+            FileReference::parse("https://example.com", dir.path()),
+            None
+        );
+        assert_eq!(FileReference::parse("src", dir.path()), None);
+    }
+
+    #[test]
+    fn editor_args_put_the_line_where_each_editor_expects_it() {
+        let reference = FileReference {
+            path: PathBuf::from("/repo/src/lib.rs"),
+            line: Some(12),
+        };
+        assert_eq!(
+            reference.editor_args("nvim"),
+            vec!["+12", "/repo/src/lib.rs"]
+        );
+        assert_eq!(
+            reference.editor_args("/usr/local/bin/code"),
+            vec!["--goto", "/repo/src/lib.rs:12"]
+        );
+        assert_eq!(reference.editor_args("zed"), vec!["/repo/src/lib.rs:12"]);
+        assert_eq!(reference.location(), "/repo/src/lib.rs:12");
+    }
+}
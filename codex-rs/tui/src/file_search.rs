@@ -4,18 +4,33 @@
 //! `AppEvent::StartFileSearch(query)`. This manager owns a single
 //! `codex-file-search` session for the current search root, updates the query
 //! on every keystroke, and drops the session when the query becomes empty.
+//! A scoped session (`--scope`) searches its scope directories instead, with
+//! matches still reported relative to the search root.
+//!
+//! Queries starting with [`CONTENT_QUERY_PREFIX`] (`@#needle`) search file
+//! contents instead: each one runs a content search on a background thread,
+//! cancelling the previous one, and reports the files with their first
+//! matching line.
 
 use codex_file_search as file_search;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::thread;
 
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
 
+/// `@` queries starting with this character search file contents.
+pub(crate) const CONTENT_QUERY_PREFIX: char = '#';
+
 pub(crate) struct FileSearchManager {
     state: Arc<Mutex<SearchState>>,
     search_dir: PathBuf,
+    scope: Vec<PathBuf>,
     app_tx: AppEventSender,
 }
 
@@ -23,6 +38,8 @@ struct SearchState {
     latest_query: String,
     session: Option<file_search::FileSearchSession>,
     session_token: usize,
+    /// Cancels the content search in flight, if any.
+    content_cancel: Option<Arc<AtomicBool>>,
 }
 
 impl FileSearchManager {
@@ -32,12 +49,21 @@ impl FileSearchManager {
                 latest_query: String::new(),
                 session: None,
                 session_token: 0,
+                content_cancel: None,
             })),
             search_dir,
+            scope: Vec::new(),
             app_tx: tx,
         }
     }
 
+    /// Searches these sub-directories of the search directory instead of the
+    /// whole of it.
+    pub fn with_scope(mut self, scope: Vec<PathBuf>) -> Self {
+        self.scope = scope;
+        self
+    }
+
     /// Updates the directory used for file searches.
     /// This should be called when the session's CWD changes on resume.
     /// Drops the current session so it will be recreated with the new directory on next query.
@@ -46,6 +72,7 @@ impl FileSearchManager {
         #[expect(clippy::unwrap_used)]
         let mut st = self.state.lock().unwrap();
         st.session.take();
+        cancel_content_search(&mut st);
         st.latest_query.clear();
     }
 
@@ -58,11 +85,18 @@ impl FileSearchManager {
         }
         st.latest_query.clear();
         st.latest_query.push_str(&query);
+        cancel_content_search(&mut st);
 
         if query.is_empty() {
             st.session.take();
             return;
         }
+        if let Some(content_query) = query.strip_prefix(CONTENT_QUERY_PREFIX) {
+            st.session.take();
+            let content_query = content_query.to_string();
+            self.start_content_search_locked(&mut st, query, content_query);
+            return;
+        }
 
         if st.session.is_none() {
             self.start_session_locked(&mut st);
@@ -79,9 +113,10 @@ impl FileSearchManager {
             state: self.state.clone(),
             app_tx: self.app_tx.clone(),
             session_token,
+            search_dir: self.search_dir.clone(),
         });
         let session = file_search::create_session(
-            vec![self.search_dir.clone()],
+            self.search_directories(),
             file_search::FileSearchOptions {
                 compute_indices: true,
                 ..Default::default()
@@ -99,10 +134,79 @@ impl FileSearchManager {
     }
 }
 
+impl FileSearchManager {
+    fn search_directories(&self) -> Vec<PathBuf> {
+        if self.scope.is_empty() {
+            vec![self.search_dir.clone()]
+        } else {
+            self.scope.clone()
+        }
+    }
+
+    fn start_content_search_locked(
+        &self,
+        st: &mut SearchState,
+        query: String,
+        content_query: String,
+    ) {
+        if content_query.is_empty() {
+            self.app_tx.send(AppEvent::FileSearchResult {
+                query,
+                matches: Vec::new(),
+            });
+            return;
+        }
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        st.content_cancel = Some(cancel.clone());
+        let state = self.state.clone();
+        let app_tx = self.app_tx.clone();
+        let search_dir = self.search_dir.clone();
+        let search_directories = self.search_directories();
+        thread::spawn(move || {
+            let results = file_search::search_content(
+                &content_query,
+                &search_directories,
+                file_search::ContentSearchOptions {
+                    context_lines: 0,
+                    max_snippets_per_file: 1,
+                    ..Default::default()
+                },
+                Some(cancel.clone()),
+            );
+            #[expect(clippy::unwrap_used)]
+            let st = state.lock().unwrap();
+            if cancel.load(Ordering::Relaxed) || st.latest_query != query {
+                return;
+            }
+            drop(st);
+            let matches = match results {
+                Ok(results) => results
+                    .matches
+                    .into_iter()
+                    .map(|content_match| relative_to_search_dir(content_match.into(), &search_dir))
+                    .collect(),
+                Err(err) => {
+                    tracing::warn!("content search failed: {err}");
+                    Vec::new()
+                }
+            };
+            app_tx.send(AppEvent::FileSearchResult { query, matches });
+        });
+    }
+}
+
+fn cancel_content_search(st: &mut SearchState) {
+    if let Some(cancel) = st.content_cancel.take() {
+        cancel.store(true, Ordering::Relaxed);
+    }
+}
+
 struct TuiSessionReporter {
     state: Arc<Mutex<SearchState>>,
     app_tx: AppEventSender,
     session_token: usize,
+    search_dir: PathBuf,
 }
 
 impl TuiSessionReporter {
@@ -117,10 +221,49 @@ impl TuiSessionReporter {
         }
         let query = snapshot.query.clone();
         drop(st);
-        self.app_tx.send(AppEvent::FileSearchResult {
-            query,
-            matches: snapshot.matches.clone(),
-        });
+        let matches = snapshot
+            .matches
+            .iter()
+            .cloned()
+            .map(|file_match| relative_to_search_dir(file_match, &self.search_dir))
+            .collect();
+        self.app_tx
+            .send(AppEvent::FileSearchResult { query, matches });
+    }
+}
+
+/// Rebases a match found under a scope directory onto the search directory,
+/// so the path the composer inserts resolves from the session's cwd.
+fn relative_to_search_dir(
+    file_match: file_search::FileMatch,
+    search_dir: &Path,
+) -> file_search::FileMatch {
+    if file_match.root == search_dir {
+        return file_match;
+    }
+    let full_path = file_match.full_path();
+    let Ok(path) = full_path.strip_prefix(search_dir) else {
+        return file_match;
+    };
+    // Highlight indices count characters of the path, which is now longer by
+    // the scope directory's prefix.
+    let shift = path
+        .to_string_lossy()
+        .chars()
+        .count()
+        .saturating_sub(file_match.path.to_string_lossy().chars().count());
+    let shift = u32::try_from(shift).unwrap_or(u32::MAX);
+    file_search::FileMatch {
+        score: file_match.score,
+        path: path.to_path_buf(),
+        root: search_dir.to_path_buf(),
+        indices: file_match.indices.map(|indices| {
+            indices
+                .into_iter()
+                .map(|index| index.saturating_add(shift))
+                .collect()
+        }),
+        snippet: file_match.snippet,
     }
 }
 
@@ -0,0 +1,110 @@
+//! Frame-time overlay toggled with `/debug-frames`.
+//!
+//! While enabled, `App` times every draw of the main viewport and this module
+//! shows the last frame's time, the average and worst over recent frames, and
+//! how many frames were drawn in the last second, in the top-right corner of
+//! the viewport. It is meant for checking rendering changes by eye, for
+//! example that streaming a long answer does not slow frames down.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+use std::time::Instant;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::WidgetRef;
+
+/// Frames the average and worst frame time are taken over.
+const FRAME_WINDOW: usize = 120;
+
+#[derive(Debug, Default)]
+pub(crate) struct FrameStats {
+    /// Start and duration of the most recent frames, oldest first.
+    frames: VecDeque<(Instant, Duration)>,
+}
+
+impl FrameStats {
+    pub(crate) fn record(&mut self, started_at: Instant, duration: Duration) {
+        if self.frames.len() == FRAME_WINDOW {
+            self.frames.pop_front();
+        }
+        self.frames.push_back((started_at, duration));
+    }
+
+    fn label(&self, now: Instant) -> Option<String> {
+        let (_, last) = self.frames.back()?;
+        let total: Duration = self.frames.iter().map(|(_, duration)| *duration).sum();
+        let average = total / u32::try_from(self.frames.len()).unwrap_or(u32::MAX);
+        let worst = self
+            .frames
+            .iter()
+            .map(|(_, duration)| *duration)
+            .max()
+            .unwrap_or_default();
+        let last_second = self
+            .frames
+            .iter()
+            .filter(|(started_at, _)| now.duration_since(*started_at) <= Duration::from_secs(1))
+            .count();
+        Some(format!(
+            " frame {} · avg {} · max {} · {last_second} fps ",
+            format_frame_time(*last),
+            format_frame_time(average),
+            format_frame_time(worst),
+        ))
+    }
+
+    /// Draws the overlay over the top-right corner of `area`.
+    pub(crate) fn render(&self, area: Rect, buf: &mut Buffer) {
+        let Some(label) = self.label(Instant::now()) else {
+            return;
+        };
+        let width = u16::try_from(label.chars().count())
+            .unwrap_or(u16::MAX)
+            .min(area.width);
+        if width == 0 || area.height == 0 {
+            return;
+        }
+        let overlay = Rect::new(area.right() - width, area.y, width, 1);
+        Line::from(label.reversed()).render_ref(overlay, buf);
+    }
+}
+
+fn format_frame_time(duration: Duration) -> String {
+    format!("{:.1}ms", duration.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn label_reports_last_average_worst_and_rate() {
+        let now = Instant::now();
+        let mut stats = FrameStats::default();
+        assert_eq!(stats.label(now), None);
+
+        stats.record(now - Duration::from_secs(2), Duration::from_millis(9));
+        stats.record(now - Duration::from_millis(500), Duration::from_millis(1));
+        stats.record(now, Duration::from_millis(2));
+
+        assert_eq!(
+            stats.label(now).as_deref(),
+            Some(" frame 2.0ms · avg 4.0ms · max 9.0ms · 2 fps ")
+        );
+    }
+
+    #[test]
+    fn keeps_only_recent_frames() {
+        let now = Instant::now();
+        let mut stats = FrameStats::default();
+        for _ in 0..FRAME_WINDOW + 5 {
+            stats.record(now, Duration::from_millis(1));
+        }
+
+        assert_eq!(stats.frames.len(), FRAME_WINDOW);
+    }
+}
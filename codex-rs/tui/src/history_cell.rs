@@ -42,7 +42,9 @@ use codex_core::config::types::McpServerTransportConfig;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::McpAuthStatus;
 use codex_core::protocol::McpInvocation;
+use codex_core::protocol::McpStartupStatus;
 use codex_core::protocol::SessionConfiguredEvent;
+use codex_core::protocol::TurnLatencyEvent;
 use codex_core::web_search::web_search_detail;
 use codex_otel::RuntimeMetricsSummary;
 use codex_protocol::account::PlanType;
@@ -69,6 +71,7 @@ use ratatui::style::Stylize;
 use ratatui::widgets::Paragraph;
 use ratatui::widgets::Wrap;
 use std::any::Any;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::io::Cursor;
 use std::path::Path;
@@ -1032,7 +1035,8 @@ pub(crate) fn new_session_info(
         reasoning_effort,
         config.cwd.clone(),
         CODEX_CLI_VERSION,
-    );
+    )
+    .with_read_only(config.read_only);
     let mut parts: Vec<Box<dyn HistoryCell>> = vec![Box::new(header)];
 
     if is_first_event {
@@ -1110,6 +1114,7 @@ pub(crate) struct SessionHeaderHistoryCell {
     model_style: Style,
     reasoning_effort: Option<ReasoningEffortConfig>,
     directory: PathBuf,
+    read_only: bool,
 }
 
 impl SessionHeaderHistoryCell {
@@ -1141,9 +1146,16 @@ impl SessionHeaderHistoryCell {
             model_style,
             reasoning_effort,
             directory,
+            read_only: false,
         }
     }
 
+    /// Marks the session as running in read-only mode (`--read-only`).
+    pub(crate) fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
     fn format_directory(&self, max_width: Option<usize>) -> String {
         Self::format_directory_inner(&self.directory, max_width)
     }
@@ -1232,12 +1244,20 @@ impl HistoryCell for SessionHeaderHistoryCell {
         let dir = self.format_directory(Some(dir_max_width));
         let dir_spans = vec![Span::from(dir_prefix).dim(), Span::from(dir)];
 
-        let lines = vec![
+        let mut lines = vec![
             make_row(title_spans),
             make_row(Vec::new()),
             make_row(model_spans),
             make_row(dir_spans),
         ];
+        if self.read_only {
+            let mode_label = format!("{:<label_width$}", "mode:");
+            lines.push(make_row(vec![
+                Span::from(format!("{mode_label} ")).dim(),
+                "read-only".magenta().bold(),
+                Span::from(" (no edits or approvals)").dim(),
+            ]));
+        }
 
         with_border(lines)
     }
@@ -1670,6 +1690,44 @@ pub(crate) fn empty_mcp_output() -> PlainHistoryCell {
     PlainHistoryCell { lines }
 }
 
+/// Render the startup state of each MCP server reported this session.
+pub(crate) fn new_mcp_status_output(
+    states: &BTreeMap<String, McpStartupStatus>,
+) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = vec![
+        "/mcp status".magenta().into(),
+        "".into(),
+        vec!["🔌  ".into(), "MCP Servers".bold()].into(),
+        "".into(),
+    ];
+    if states.is_empty() {
+        lines.push(
+            "  • No MCP servers were started this session."
+                .italic()
+                .into(),
+        );
+    }
+    for (server, state) in states {
+        let mut line: Vec<Span<'static>> = vec!["  • ".into(), server.clone().into(), ": ".into()];
+        match state {
+            McpStartupStatus::Lazy => {
+                line.push("lazy".cyan());
+                line.push(" (starts when its tools are first needed)".dim());
+            }
+            McpStartupStatus::Starting => line.push("starting".cyan()),
+            McpStartupStatus::Ready => line.push("started".green()),
+            McpStartupStatus::Failed { error } => {
+                line.push("failed".red());
+                line.push(format!(" ({error})").dim());
+            }
+            McpStartupStatus::Cancelled => line.push("cancelled".dim()),
+        }
+        lines.push(line.into());
+    }
+
+    PlainHistoryCell { lines }
+}
+
 /// Render MCP tools grouped by connection using the fully-qualified tool names.
 pub(crate) fn new_mcp_tools_output(
     config: &Config,
@@ -2111,7 +2169,7 @@ impl HistoryCell for PlanUpdateCell {
         if self.plan.is_empty() {
             indented_lines.push(Line::from("(no steps provided)".dim().italic()));
         } else {
-            for PlanItemArg { step, status } in self.plan.iter() {
+            for PlanItemArg { step, status, .. } in self.plan.iter() {
                 indented_lines.extend(render_step(status, step));
             }
         }
@@ -2146,6 +2204,7 @@ pub(crate) fn new_patch_apply_failure(stderr: String) -> PlainHistoryCell {
                 exit_code: 1,
                 formatted_output: String::new(),
                 aggregated_output: stderr,
+                test_failures: Vec::new(),
             }),
             OutputLinesParams {
                 line_limit: TOOL_CALL_MAX_LINES,
@@ -2206,6 +2265,7 @@ pub(crate) fn new_reasoning_summary_block(full_reasoning_buffer: String) -> Box<
 pub struct FinalMessageSeparator {
     elapsed_seconds: Option<u64>,
     runtime_metrics: Option<RuntimeMetricsSummary>,
+    latency: Option<TurnLatencyEvent>,
 }
 impl FinalMessageSeparator {
     /// Creates a separator; `elapsed_seconds` typically comes from the status indicator timer.
@@ -2216,8 +2276,15 @@ impl FinalMessageSeparator {
         Self {
             elapsed_seconds,
             runtime_metrics,
+            latency: None,
         }
     }
+
+    /// Adds the turn's per-stage latency breakdown to the label.
+    pub(crate) fn with_latency(mut self, latency: Option<TurnLatencyEvent>) -> Self {
+        self.latency = latency;
+        self
+    }
 }
 impl HistoryCell for FinalMessageSeparator {
     fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
@@ -2229,6 +2296,9 @@ impl HistoryCell for FinalMessageSeparator {
         {
             label_parts.push(format!("Worked for {elapsed_seconds}"));
         }
+        if let Some(latency_label) = self.latency.as_ref().and_then(turn_latency_label) {
+            label_parts.push(latency_label);
+        }
         if let Some(metrics_label) = self.runtime_metrics.and_then(runtime_metrics_label) {
             label_parts.push(metrics_label);
         }
@@ -2249,6 +2319,49 @@ impl HistoryCell for FinalMessageSeparator {
     }
 }
 
+/// Stages shorter than this are left out of the latency breakdown.
+const LATENCY_STAGE_MIN_MS: u64 = 100;
+
+pub(crate) fn turn_latency_label(latency: &TurnLatencyEvent) -> Option<String> {
+    let mut parts = Vec::new();
+    let model_ms = latency
+        .model_ttfb_ms
+        .saturating_add(latency.model_stream_ms);
+    if model_ms >= LATENCY_STAGE_MIN_MS {
+        parts.push(format!(
+            "Model: {} (first token {})",
+            format_duration_ms(model_ms),
+            format_duration_ms(latency.model_ttfb_ms)
+        ));
+    }
+    if latency.tool_dispatch_ms >= LATENCY_STAGE_MIN_MS {
+        let mut tools = format!("Tools: {}", format_duration_ms(latency.tool_dispatch_ms));
+        if latency.tool_stream_overlap_ms >= LATENCY_STAGE_MIN_MS {
+            tools.push_str(&format!(
+                " ({} while streaming)",
+                format_duration_ms(latency.tool_stream_overlap_ms)
+            ));
+        }
+        parts.push(tools);
+    }
+    let stages = [
+        ("Approvals", latency.approval_wait_ms),
+        ("Sandbox setup", latency.sandbox_setup_ms),
+        ("Sidecars", latency.sidecar_round_trip_ms),
+        ("Queued", latency.queue_wait_ms),
+    ];
+    for (label, duration_ms) in stages {
+        if duration_ms >= LATENCY_STAGE_MIN_MS {
+            parts.push(format!("{label}: {}", format_duration_ms(duration_ms)));
+        }
+    }
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(" • "))
+    }
+}
+
 pub(crate) fn runtime_metrics_label(summary: RuntimeMetricsSummary) -> Option<String> {
     let mut parts = Vec::new();
     if summary.tool_calls.count > 0 {
@@ -2388,6 +2501,7 @@ mod tests {
     use std::collections::HashMap;
 
     use codex_core::protocol::ExecCommandSource;
+    use codex_core::protocol::TestFailure;
     use codex_protocol::mcp::CallToolResult;
     use codex_protocol::mcp::Tool;
     use rmcp::model::Content;
@@ -2517,6 +2631,36 @@ mod tests {
         assert!(rendered[0].contains("TBT: 1.2s (iapi) 1.2s (service)"));
     }
 
+    #[test]
+    fn final_message_separator_includes_latency_breakdown() {
+        let latency = TurnLatencyEvent {
+            turn_id: "turn-1".to_string(),
+            total_ms: 9_800,
+            queue_wait_ms: 12,
+            model_ttfb_ms: 900,
+            model_stream_ms: 2_300,
+            model_requests: 2,
+            tool_dispatch_ms: 5_600,
+            tool_calls: 3,
+            tool_stream_overlap_ms: 1_300,
+            sandbox_setup_ms: 40,
+            sidecar_round_trip_ms: 0,
+            sidecar_calls: 0,
+            approval_wait_ms: 4_100,
+            approvals: 1,
+        };
+        let cell = FinalMessageSeparator::new(None, None).with_latency(Some(latency));
+        let rendered = render_lines(&cell.display_lines(200));
+
+        assert_eq!(rendered.len(), 1);
+        assert!(
+            rendered[0]
+                .contains("Model: 3.2s (first token 900ms) • Tools: 5.6s (1.3s while streaming) • Approvals: 4.1s ─")
+        );
+        assert!(!rendered[0].contains("Sandbox setup"));
+        assert!(!rendered[0].contains("Queued"));
+    }
+
     #[test]
     fn final_message_separator_includes_worked_label_after_one_minute() {
         let cell = FinalMessageSeparator::new(Some(61), None);
@@ -2680,6 +2824,35 @@ mod tests {
         insta::assert_snapshot!(rendered);
     }
 
+    #[test]
+    fn mcp_status_output_lists_server_states() {
+        let states = BTreeMap::from([
+            ("docs".to_string(), McpStartupStatus::Lazy),
+            ("git".to_string(), McpStartupStatus::Ready),
+            (
+                "search".to_string(),
+                McpStartupStatus::Failed {
+                    error: "timed out".to_string(),
+                },
+            ),
+        ]);
+
+        let cell = new_mcp_status_output(&states);
+
+        assert_eq!(
+            render_lines(&cell.display_lines(80)),
+            vec![
+                "/mcp status",
+                "",
+                "🔌  MCP Servers",
+                "",
+                "  • docs: lazy (starts when its tools are first needed)",
+                "  • git: started",
+                "  • search: failed (timed out)",
+            ]
+        );
+    }
+
     #[test]
     fn empty_agent_message_cell_transcript() {
         let cell = AgentMessageCell::new(vec![Line::default()], false);
@@ -3054,6 +3227,20 @@ mod tests {
         assert!(model_line.contains("/model to change"));
     }
 
+    #[test]
+    fn session_header_marks_read_only_sessions() {
+        let header =
+            SessionHeaderHistoryCell::new("gpt-4o".to_string(), None, std::env::temp_dir(), "test");
+        let has_mode_line = |cell: &SessionHeaderHistoryCell| {
+            render_lines(&cell.display_lines(80))
+                .iter()
+                .any(|line| line.contains("mode:") && line.contains("read-only"))
+        };
+
+        assert!(!has_mode_line(&header));
+        assert!(has_mode_line(&header.with_read_only(true)));
+    }
+
     #[test]
     fn session_header_directory_center_truncates() {
         let mut dir = home_dir().expect("home directory");
@@ -3362,6 +3549,62 @@ mod tests {
                 exit_code: 1,
                 formatted_output: String::new(),
                 aggregated_output: stderr,
+                test_failures: Vec::new(),
+            },
+            Duration::from_millis(1),
+        );
+
+        let rendered = cell
+            .display_lines(80)
+            .iter()
+            .map(|l| {
+                l.spans
+                    .iter()
+                    .map(|s| s.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        insta::assert_snapshot!(rendered);
+    }
+
+    #[test]
+    fn test_failures_summary_snapshot() {
+        let call_id = "c_tests".to_string();
+        let mut cell = ExecCell::new(
+            ExecCall {
+                call_id: call_id.clone(),
+                command: vec!["bash".into(), "-lc".into(), "cargo test".into()],
+                parsed: Vec::new(),
+                output: None,
+                source: ExecCommandSource::Agent,
+                start_time: Some(Instant::now()),
+                duration: None,
+                interaction_input: None,
+            },
+            true,
+        );
+        cell.complete_call(
+            &call_id,
+            CommandOutput {
+                exit_code: 101,
+                formatted_output: String::new(),
+                aggregated_output: "test parser::nested ... FAILED\ntest parser::flat ... FAILED"
+                    .to_string(),
+                test_failures: vec![
+                    TestFailure {
+                        name: "parser::nested".to_string(),
+                        file: Some("src/parser.rs".to_string()),
+                        line: Some(120),
+                        message: "assertion `left == right` failed".to_string(),
+                    },
+                    TestFailure {
+                        name: "parser::flat".to_string(),
+                        file: None,
+                        line: None,
+                        message: String::new(),
+                    },
+                ],
             },
             Duration::from_millis(1),
         );
@@ -3410,6 +3653,7 @@ mod tests {
                 exit_code: 1,
                 formatted_output: String::new(),
                 aggregated_output: stderr,
+                test_failures: Vec::new(),
             },
             Duration::from_millis(5),
         );
@@ -3573,14 +3817,17 @@ mod tests {
                 PlanItemArg {
                     step: "Investigate existing error paths and logging around HTTP timeouts".into(),
                     status: StepStatus::Completed,
+                    id: None,
                 },
                 PlanItemArg {
                     step: "Harden Grafana client error handling with retry/backoff and user‑friendly messages".into(),
                     status: StepStatus::InProgress,
+                    id: None,
                 },
                 PlanItemArg {
                     step: "Add tests for transient failure scenarios and surfacing to the UI".into(),
                     status: StepStatus::Pending,
+                    id: None,
                 },
             ],
         };
@@ -3600,10 +3847,12 @@ mod tests {
                 PlanItemArg {
                     step: "Define error taxonomy".into(),
                     status: StepStatus::InProgress,
+                    id: None,
                 },
                 PlanItemArg {
                     step: "Implement mapping to user messages".into(),
                     status: StepStatus::Pending,
+                    id: None,
                 },
             ],
         };
@@ -0,0 +1,199 @@
+//! OSC 8 hyperlinks for URLs and file references in history (`tui.hyperlinks`).
+//!
+//! Links are added as history is written to the scrollback, after the lines have been wrapped to
+//! the terminal width. Targets are looked up on the lines as cells produced them, before wrapping,
+//! so a URL that had to be broken across rows is left alone instead of being linked to a
+//! truncated address. File references are linked only when the file exists, and open through
+//! the `file_opener` scheme (`vscode://`, `idea://`, …).
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_core::config::types::UriBasedFileOpener;
+use ratatui::text::Line;
+
+use crate::file_reference::FileReference;
+use crate::file_reference::trimmed_words;
+
+/// Link targets keyed by the word they were found as.
+pub(crate) type LinkTargets = HashMap<String, String>;
+
+#[derive(Clone, Debug)]
+pub(crate) struct Hyperlinks {
+    file_opener: UriBasedFileOpener,
+    /// Directory that relative file references are resolved against.
+    cwd: PathBuf,
+}
+
+impl Hyperlinks {
+    pub(crate) fn new(file_opener: UriBasedFileOpener, cwd: PathBuf) -> Self {
+        Self { file_opener, cwd }
+    }
+
+    /// Targets of the URLs and file references in `lines`.
+    pub(crate) fn targets(&self, lines: &[Line<'_>]) -> LinkTargets {
+        let mut targets = LinkTargets::new();
+        for line in lines {
+            let text = line_text(line);
+            for (_, word) in trimmed_words(&text) {
+                if targets.contains_key(word) {
+                    continue;
+                }
+                if let Some(target) = self.target(word) {
+                    targets.insert(word.to_string(), target);
+                }
+            }
+        }
+        targets
+    }
+
+    fn target(&self, word: &str) -> Option<String> {
+        if is_url(word) {
+            return Some(word.to_string());
+        }
+        if !looks_like_path(word) {
+            return None;
+        }
+        let reference = FileReference::parse(word, &self.cwd)?;
+        file_uri(self.file_opener, &reference)
+    }
+}
+
+/// Byte ranges in `text` of the words that have a target, in order, with their targets.
+pub(crate) fn link_ranges<'a>(
+    text: &str,
+    targets: &'a LinkTargets,
+) -> Vec<(Range<usize>, &'a str)> {
+    if targets.is_empty() {
+        return Vec::new();
+    }
+    trimmed_words(text)
+        .into_iter()
+        .filter_map(|(start, word)| {
+            let target = targets.get(word)?;
+            Some((start..start + word.len(), target.as_str()))
+        })
+        .collect()
+}
+
+pub(crate) fn line_text(line: &Line<'_>) -> String {
+    line.spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect()
+}
+
+fn is_url(word: &str) -> bool {
+    ["https://", "http://"]
+        .iter()
+        .any(|scheme| word.len() > scheme.len() && word.starts_with(scheme))
+}
+
+/// Cheap check before touching the filesystem: paths have a separator or an extension.
+fn looks_like_path(word: &str) -> bool {
+    word.starts_with("file://")
+        || (!word.contains("://") && (word.contains('/') || word.contains('.')))
+}
+
+fn file_uri(file_opener: UriBasedFileOpener, reference: &FileReference) -> Option<String> {
+    let path = encode_path(&reference.path);
+    if file_opener == UriBasedFileOpener::Idea {
+        return Some(match reference.line {
+            Some(line) => format!("idea://open?file={path}&line={line}"),
+            None => format!("idea://open?file={path}"),
+        });
+    }
+    let scheme = file_opener.get_scheme()?;
+    // `vscode://file/C:/repo/...` on Windows, `vscode://file/home/...` elsewhere.
+    let separator = if path.starts_with('/') { "" } else { "/" };
+    Some(match reference.line {
+        Some(line) => format!("{scheme}://file{separator}{path}:{line}"),
+        None => format!("{scheme}://file{separator}{path}"),
+    })
+}
+
+/// `path` with forward slashes and the characters that would end or split a URI escaped.
+fn encode_path(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut encoded = String::with_capacity(path.len());
+    for ch in path.chars() {
+        match ch {
+            '%' => encoded.push_str("%25"),
+            ' ' => encoded.push_str("%20"),
+            '#' => encoded.push_str("%23"),
+            '&' => encoded.push_str("%26"),
+            '?' => encoded.push_str("%3F"),
+            _ => encoded.push(ch),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use ratatui::style::Stylize;
+
+    #[test]
+    fn finds_urls_and_existing_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir(dir.path().join("src")).expect("create src");
+        std::fs::write(dir.path().join("src/lib.rs"), "").expect("write file");
+        let hyperlinks = Hyperlinks::new(UriBasedFileOpener::VsCode, dir.path().to_path_buf());
+
+        let lines = vec![
+            Line::from(vec![
+                "Edited ".into(),
+                "src/lib.rs:12".cyan(),
+                " and src/missing.rs.".into(),
+            ]),
+            Line::from("See https://example.com/docs)."),
+        ];
+        let targets = hyperlinks.targets(&lines);
+
+        let lib = encode_path(&dir.path().join("src/lib.rs"));
+        let separator = if lib.starts_with('/') { "" } else { "/" };
+        assert_eq!(
+            targets,
+            LinkTargets::from([
+                (
+                    "src/lib.rs:12".to_string(),
+                    format!("vscode://file{separator}{lib}:12"),
+                ),
+                (
+                    "https://example.com/docs".to_string(),
+                    "https://example.com/docs".to_string(),
+                ),
+            ])
+        );
+
+        // A word cut short by wrapping is not linked.
+        let text = "See https://example.com/do";
+        assert_eq!(link_ranges(text, &targets), Vec::new());
+        let text = "(https://example.com/docs)";
+        assert_eq!(
+            link_ranges(text, &targets),
+            vec![(1..25, "https://example.com/docs")]
+        );
+    }
+
+    #[test]
+    fn file_uris_follow_the_opener_scheme() {
+        let reference = FileReference {
+            path: PathBuf::from("/repo/my notes.md"),
+            line: Some(3),
+        };
+        assert_eq!(
+            file_uri(UriBasedFileOpener::Cursor, &reference),
+            Some("cursor://file/repo/my%20notes.md:3".to_string())
+        );
+        assert_eq!(
+            file_uri(UriBasedFileOpener::Idea, &reference),
+            Some("idea://open?file=/repo/my%20notes.md&line=3".to_string())
+        );
+        assert_eq!(file_uri(UriBasedFileOpener::None, &reference), None);
+    }
+}
@@ -1,7 +1,11 @@
 use std::fmt;
 use std::io;
 use std::io::Write;
+use std::ops::Range;
 
+use crate::hyperlink::Hyperlinks;
+use crate::hyperlink::line_text;
+use crate::hyperlink::link_ranges;
 use crate::wrapping::word_wrap_lines_borrowed;
 use crossterm::Command;
 use crossterm::cursor::MoveTo;
@@ -31,6 +35,22 @@ pub fn insert_history_lines<B>(
 where
     B: Backend + Write,
 {
+    insert_history_lines_with_hyperlinks(terminal, lines, None)
+}
+
+/// Like [`insert_history_lines`], but also wraps URLs and file references in OSC 8 hyperlinks.
+pub(crate) fn insert_history_lines_with_hyperlinks<B>(
+    terminal: &mut crate::custom_terminal::Terminal<B>,
+    lines: Vec<Line>,
+    hyperlinks: Option<&Hyperlinks>,
+) -> io::Result<()>
+where
+    B: Backend + Write,
+{
+    // Find link targets before wrapping, so words broken across rows are not linked.
+    let link_targets = hyperlinks
+        .map(|hyperlinks| hyperlinks.targets(&lines))
+        .unwrap_or_default();
     let screen_size = terminal.backend().size().unwrap_or(Size::new(0, 0));
 
     let mut area = terminal.viewport_area;
@@ -118,7 +138,8 @@ where
                 content: s.content.clone(),
             })
             .collect();
-        write_spans(writer, merged_spans.iter())?;
+        let links = link_ranges(&line_text(&line), &link_targets);
+        write_linked_spans(writer, &merged_spans, &links)?;
     }
 
     queue!(writer, ResetScrollRegion)?;
@@ -174,6 +195,9 @@ impl Command for ResetScrollRegion {
     }
 }
 
+/// Ends an OSC 8 hyperlink.
+const OSC8_CLOSE: &str = "\x1b]8;;\x07";
+
 struct ModifierDiff {
     pub from: Modifier,
     pub to: Modifier,
@@ -241,14 +265,68 @@ impl ModifierDiff {
     }
 }
 
-fn write_spans<'a, I>(mut writer: &mut impl Write, content: I) -> io::Result<()>
+fn write_spans<'a, I>(writer: &mut impl Write, content: I) -> io::Result<()>
 where
     I: IntoIterator<Item = &'a Span<'a>>,
+{
+    write_spans_with_links(writer, content.into_iter().map(|span| (span, None)))
+}
+
+/// Writes `spans` with the byte ranges in `links` (over the spans' joined text) hyperlinked.
+fn write_linked_spans(
+    writer: &mut impl Write,
+    spans: &[Span<'_>],
+    links: &[(Range<usize>, &str)],
+) -> io::Result<()> {
+    if links.is_empty() {
+        return write_spans(writer, spans.iter());
+    }
+    let pieces = split_at_links(spans, links);
+    write_spans_with_links(writer, pieces.iter().map(|(span, link)| (span, *link)))
+}
+
+/// Splits `spans` at the edges of `links`, pairing each piece with the link target it is part of.
+fn split_at_links<'a>(
+    spans: &'a [Span<'a>],
+    links: &[(Range<usize>, &'a str)],
+) -> Vec<(Span<'a>, Option<&'a str>)> {
+    let mut pieces = Vec::new();
+    let mut offset = 0;
+    for span in spans {
+        let content: &'a str = span.content.as_ref();
+        let end = offset + content.len();
+        let mut cuts: Vec<usize> = links
+            .iter()
+            .flat_map(|(range, _)| [range.start, range.end])
+            .filter(|&cut| cut > offset && cut < end)
+            .collect();
+        cuts.push(end);
+        let mut start = offset;
+        for cut in cuts {
+            let link = links
+                .iter()
+                .find(|(range, _)| range.contains(&start))
+                .map(|(_, target)| *target);
+            pieces.push((
+                Span::styled(&content[start - offset..cut - offset], span.style),
+                link,
+            ));
+            start = cut;
+        }
+        offset = end;
+    }
+    pieces
+}
+
+fn write_spans_with_links<'a, I>(mut writer: &mut impl Write, content: I) -> io::Result<()>
+where
+    I: IntoIterator<Item = (&'a Span<'a>, Option<&'a str>)>,
 {
     let mut fg = Color::Reset;
     let mut bg = Color::Reset;
     let mut last_modifier = Modifier::empty();
-    for span in content {
+    let mut open_link: Option<&str> = None;
+    for (span, link) in content {
         let mut modifier = Modifier::empty();
         modifier.insert(span.style.add_modifier);
         modifier.remove(span.style.sub_modifier);
@@ -270,9 +348,21 @@ where
             fg = next_fg;
             bg = next_bg;
         }
+        if link != open_link {
+            if open_link.is_some() {
+                queue!(writer, Print(OSC8_CLOSE))?;
+            }
+            if let Some(target) = link {
+                queue!(writer, Print(format!("\x1b]8;;{target}\x07")))?;
+            }
+            open_link = link;
+        }
 
         queue!(writer, Print(span.content.clone()))?;
     }
+    if open_link.is_some() {
+        queue!(writer, Print(OSC8_CLOSE))?;
+    }
 
     queue!(
         writer,
@@ -318,6 +408,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn links_wrap_only_the_linked_text() {
+        use ratatui::style::Stylize;
+
+        let spans = ["see ".into(), "src/lib".cyan(), ".rs now".into()];
+        let links = [(4..14, "vscode://file/repo/src/lib.rs")];
+
+        let mut actual: Vec<u8> = Vec::new();
+        write_linked_spans(&mut actual, &spans, &links).unwrap();
+
+        let mut expected: Vec<u8> = Vec::new();
+        queue!(
+            expected,
+            Print("see "),
+            SetColors(Colors::new(CColor::Cyan, CColor::Reset)),
+            Print("\x1b]8;;vscode://file/repo/src/lib.rs\x07"),
+            Print("src/lib"),
+            SetColors(Colors::new(CColor::Reset, CColor::Reset)),
+            Print(".rs"),
+            Print(OSC8_CLOSE),
+            Print(" now"),
+            SetForegroundColor(CColor::Reset),
+            SetBackgroundColor(CColor::Reset),
+            SetAttribute(crossterm::style::Attribute::Reset),
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(actual).unwrap(),
+            String::from_utf8(expected).unwrap()
+        );
+    }
+
     #[test]
     fn vt100_blockquote_line_emits_green_fg() {
         // Set up a small off-screen terminal
@@ -25,14 +25,18 @@ use codex_core::config::resolve_oss_provider;
 use codex_core::config_loader::CloudRequirementsLoader;
 use codex_core::config_loader::ConfigLoadError;
 use codex_core::config_loader::format_config_error_with_source;
+use codex_core::default_client::set_default_client_http_config;
+use codex_core::default_client::set_default_client_offline;
 use codex_core::default_client::set_default_client_residency_requirement;
-use codex_core::find_thread_path_by_id_str;
 use codex_core::find_thread_path_by_name_str;
 use codex_core::format_exec_policy_error_with_source;
 use codex_core::path_utils;
 use codex_core::protocol::AskForApproval;
 use codex_core::read_session_meta_line;
 use codex_core::terminal::Multiplexer;
+use codex_core::turn_journal::InFlightTurn;
+use codex_core::turn_journal::discard_orphaned_turn;
+use codex_core::turn_journal::find_orphaned_turns;
 use codex_core::windows_sandbox::WindowsSandboxLevelExt;
 use codex_protocol::config_types::AltScreenMode;
 use codex_protocol::config_types::SandboxMode;
@@ -57,12 +61,15 @@ use uuid::Uuid;
 mod additional_dirs;
 mod app;
 mod app_backtrack;
+mod app_bookmarks;
 mod app_event;
 mod app_event_sender;
+mod app_mouse;
 mod ascii_animation;
 mod bottom_pane;
 mod chatwidget;
 mod cli;
+mod clipboard_copy;
 mod clipboard_paste;
 mod collab;
 mod collaboration_modes;
@@ -74,10 +81,13 @@ mod diff_render;
 mod exec_cell;
 mod exec_command;
 mod external_editor;
+mod file_reference;
 mod file_search;
+mod frame_stats;
 mod frames;
 mod get_git_diff;
 mod history_cell;
+mod hyperlink;
 pub mod insert_history;
 mod key_hint;
 pub mod live_wrap;
@@ -91,9 +101,12 @@ pub mod onboarding;
 mod oss_selection;
 mod pager_overlay;
 pub mod public_widgets;
+mod recovery_prompt;
 mod render;
 mod resume_picker;
+mod screen_reader;
 mod selection_list;
+mod session_changes;
 mod session_log;
 mod shimmer;
 mod skills_helpers;
@@ -117,6 +130,7 @@ mod wrapping;
 #[cfg(test)]
 pub mod test_backend;
 
+use crate::hyperlink::Hyperlinks;
 use crate::onboarding::onboarding_screen::OnboardingScreenArgs;
 use crate::onboarding::onboarding_screen::run_onboarding_app;
 use crate::tui::Tui;
@@ -154,6 +168,12 @@ pub async fn run_main(
             .push("web_search=\"live\"".to_string());
     }
 
+    if cli.screen_reader {
+        cli.config_overrides
+            .raw_overrides
+            .push("tui.screen_reader=true".to_string());
+    }
+
     // When using `--oss`, let the bootstrapper pick the model (defaulting to
     // gpt-oss:20b) and ensure it is present locally. Also, force the built‑in
     let raw_overrides = cli.config_overrides.raw_overrides.clone();
@@ -218,20 +238,27 @@ pub async fn run_main(
         tracing::warn!(error = %err, "failed to run personality migration");
     }
 
-    let cloud_auth_manager = AuthManager::shared(
-        codex_home.to_path_buf(),
-        false,
-        config_toml.cli_auth_credentials_store.unwrap_or_default(),
-    );
-    let chatgpt_base_url = config_toml
-        .chatgpt_base_url
-        .clone()
-        .unwrap_or_else(|| "https://chatgpt.com/backend-api/".to_string());
-    let cloud_requirements = cloud_requirements_loader(
-        cloud_auth_manager,
-        chatgpt_base_url,
-        codex_home.to_path_buf(),
-    );
+    let offline = cli.offline || config_toml.offline.unwrap_or(false);
+    set_default_client_offline(offline);
+    set_default_client_http_config(config_toml.http_client.clone().unwrap_or_default());
+    let cloud_requirements = if offline {
+        CloudRequirementsLoader::default()
+    } else {
+        let cloud_auth_manager = AuthManager::shared(
+            config_toml.auth_home(&codex_home, cli.config_profile.clone()),
+            false,
+            config_toml.cli_auth_credentials_store.unwrap_or_default(),
+        );
+        let chatgpt_base_url = config_toml
+            .chatgpt_base_url
+            .clone()
+            .unwrap_or_else(|| "https://chatgpt.com/backend-api/".to_string());
+        cloud_requirements_loader(
+            cloud_auth_manager,
+            chatgpt_base_url,
+            codex_home.to_path_buf(),
+        )
+    };
 
     let model_provider_override = if cli.oss {
         let resolved = resolve_oss_provider(
@@ -278,9 +305,14 @@ pub async fn run_main(
         cwd,
         model_provider: model_provider_override.clone(),
         config_profile: cli.config_profile.clone(),
+        orchestration: cli.orchestration.clone(),
+        offline: cli.offline.then_some(true),
+        read_only: cli.read_only.then_some(true),
+        wire_log: cli.wire_log.clone().map(std::path::absolute).transpose()?,
         codex_linux_sandbox_exe,
         show_raw_agent_reasoning: cli.oss.then_some(true),
         additional_writable_roots: additional_dirs,
+        scope: cli.scope.clone(),
         ..Default::default()
     };
 
@@ -440,7 +472,9 @@ async fn run_ratatui_app(
 ) -> color_eyre::Result<AppExitInfo> {
     color_eyre::install()?;
 
-    tooltips::announcement::prewarm();
+    if !initial_config.offline {
+        tooltips::announcement::prewarm();
+    }
 
     // Forward panic reports through tracing so they appear in the UI status
     // line, but do not swallow the default/color-eyre panic handler.
@@ -455,6 +489,13 @@ async fn run_ratatui_app(
     terminal.clear()?;
 
     let mut tui = Tui::new(terminal);
+    tui.set_screen_reader(initial_config.tui_screen_reader);
+    tui.set_mouse_capture(initial_config.tui_mouse);
+    tui.set_hyperlinks(
+        initial_config
+            .tui_hyperlinks
+            .then(|| Hyperlinks::new(initial_config.file_opener, initial_config.cwd.clone())),
+    );
 
     #[cfg(not(debug_assertions))]
     {
@@ -482,7 +523,7 @@ async fn run_ratatui_app(
     session_log::maybe_init(&initial_config);
 
     let auth_manager = AuthManager::shared(
-        initial_config.codex_home.clone(),
+        initial_config.auth_home.clone(),
         false,
         initial_config.cli_auth_credentials_store_mode,
     );
@@ -565,7 +606,7 @@ async fn run_ratatui_app(
         if let Some(id_str) = cli.fork_session_id.as_deref() {
             let is_uuid = Uuid::parse_str(id_str).is_ok();
             let path = if is_uuid {
-                find_thread_path_by_id_str(&config.codex_home, id_str).await?
+                RolloutRecorder::find_thread_path(&config, id_str).await?
             } else {
                 find_thread_path_by_name_str(&config.codex_home, id_str).await?
             };
@@ -614,7 +655,7 @@ async fn run_ratatui_app(
     } else if let Some(id_str) = cli.resume_session_id.as_deref() {
         let is_uuid = Uuid::parse_str(id_str).is_ok();
         let path = if is_uuid {
-            find_thread_path_by_id_str(&config.codex_home, id_str).await?
+            RolloutRecorder::find_thread_path(&config, id_str).await?
         } else {
             find_thread_path_by_name_str(&config.codex_home, id_str).await?
         };
@@ -659,6 +700,18 @@ async fn run_ratatui_app(
             }
             other => other,
         }
+    } else if let Some(interrupted) = find_interrupted_session(&config) {
+        match recovery_prompt::run_recovery_prompt(&mut tui, &interrupted).await? {
+            recovery_prompt::RecoverySelection::Resume => {
+                resume_picker::SessionSelection::Resume(interrupted.rollout_path)
+            }
+            recovery_prompt::RecoverySelection::StartFresh => {
+                if let Err(err) = discard_orphaned_turn(&config.codex_home, interrupted.thread_id) {
+                    tracing::warn!("failed to discard interrupted turn journal: {err}");
+                }
+                resume_picker::SessionSelection::StartFresh
+            }
+        }
     } else {
         resume_picker::SessionSelection::StartFresh
     };
@@ -701,10 +754,13 @@ async fn run_ratatui_app(
         prompt,
         images,
         no_alt_screen,
+        fork_at,
         ..
     } = cli;
 
-    let use_alt_screen = determine_alt_screen_mode(no_alt_screen, config.tui_alternate_screen);
+    // Screen readers follow the scrollback, which the alternate screen hides.
+    let use_alt_screen = !config.tui_screen_reader
+        && determine_alt_screen_mode(no_alt_screen, config.tui_alternate_screen);
     tui.set_alt_screen_enabled(use_alt_screen);
 
     let app_result = App::run(
@@ -717,6 +773,7 @@ async fn run_ratatui_app(
         prompt,
         images,
         session_selection,
+        fork_at,
         feedback,
         should_show_trust_screen, // Proxy to: is it a first run in this directory?
         should_prompt_windows_sandbox_nux_at_startup,
@@ -730,6 +787,18 @@ async fn run_ratatui_app(
     app_result
 }
 
+/// The most recent interactive session in this directory whose process exited
+/// mid-turn, if its rollout still exists.
+fn find_interrupted_session(config: &Config) -> Option<InFlightTurn> {
+    find_orphaned_turns(&config.codex_home)
+        .into_iter()
+        .find(|turn| {
+            INTERACTIVE_SESSION_SOURCES.contains(&turn.source)
+                && turn.cwd == config.cwd
+                && turn.rollout_path.exists()
+        })
+}
+
 pub(crate) async fn read_session_cwd(path: &Path) -> Option<PathBuf> {
     // Prefer the latest TurnContext cwd so resume/fork reflects the most recent
     // session directory (for the changed-cwd prompt). The alternative would be
@@ -754,7 +823,7 @@ pub(crate) async fn read_session_cwd(path: &Path) -> Option<PathBuf> {
 }
 
 async fn parse_latest_turn_context_cwd(path: &Path) -> Option<PathBuf> {
-    let text = tokio::fs::read_to_string(path).await.ok()?;
+    let text = codex_core::read_rollout_text(path).await.ok()?;
     for line in text.lines().rev() {
         let trimmed = line.trim();
         if trimmed.is_empty() {
@@ -854,8 +923,10 @@ fn get_login_status(config: &Config) -> LoginStatus {
     if config.model_provider.requires_openai_auth {
         // Reading the OpenAI API key is an async operation because it may need
         // to refresh the token. Block on it.
-        let codex_home = config.codex_home.clone();
-        match CodexAuth::from_auth_storage(&codex_home, config.cli_auth_credentials_store_mode) {
+        match CodexAuth::from_auth_storage(
+            &config.auth_home,
+            config.cli_auth_credentials_store_mode,
+        ) {
             Ok(Some(auth)) => LoginStatus::AuthMode(auth.auth_mode()),
             Ok(None) => LoginStatus::NotAuthenticated,
             Err(err) => {
@@ -1029,6 +1100,7 @@ mod tests {
             collaboration_mode: None,
             effort: config.model_reasoning_effort,
             summary: config.model_reasoning_summary,
+            verbosity: None,
             user_instructions: None,
             developer_instructions: None,
             final_output_json_schema: None,
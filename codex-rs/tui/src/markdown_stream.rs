@@ -4,10 +4,20 @@ use crate::markdown;
 
 /// Newline-gated accumulator that renders markdown and commits only fully
 /// completed logical lines.
+///
+/// Re-rendering the whole message on every completed line would make long
+/// answers quadratic to stream, so the collector settles a prefix of the
+/// buffer once it can no longer change how it renders: its lines are kept and
+/// only the source after it is rendered again.
 pub(crate) struct MarkdownStreamCollector {
     buffer: String,
     committed_line_count: usize,
     width: Option<usize>,
+    /// Bytes of `buffer` whose rendering is kept in `settled_lines`.
+    settled_len: usize,
+    /// Rendered lines of the settled prefix, including the blank lines that
+    /// separate it from what follows.
+    settled_lines: Vec<Line<'static>>,
 }
 
 impl MarkdownStreamCollector {
@@ -16,12 +26,16 @@ impl MarkdownStreamCollector {
             buffer: String::new(),
             committed_line_count: 0,
             width,
+            settled_len: 0,
+            settled_lines: Vec::new(),
         }
     }
 
     pub fn clear(&mut self) {
         self.buffer.clear();
         self.committed_line_count = 0;
+        self.settled_len = 0;
+        self.settled_lines.clear();
     }
 
     pub fn push_delta(&mut self, delta: &str) {
@@ -29,24 +43,21 @@ impl MarkdownStreamCollector {
         self.buffer.push_str(delta);
     }
 
-    /// Render the full buffer and return only the newly completed logical lines
+    /// Render the buffer and return only the newly completed logical lines
     /// since the last commit. When the buffer does not end with a newline, the
     /// final rendered line is considered incomplete and is not emitted.
     pub fn commit_complete_lines(&mut self) -> Vec<Line<'static>> {
-        let source = self.buffer.clone();
-        let last_newline_idx = source.rfind('\n');
-        let source = if let Some(last_newline_idx) = last_newline_idx {
-            source[..=last_newline_idx].to_string()
-        } else {
+        let Some(last_newline_idx) = self.buffer.rfind('\n') else {
             return Vec::new();
         };
-        let mut rendered: Vec<Line<'static>> = Vec::new();
-        markdown::append_markdown(&source, self.width, &mut rendered);
-        let mut complete_line_count = rendered.len();
-        if complete_line_count > 0
-            && crate::render::line_utils::is_blank_line_spaces_only(
-                &rendered[complete_line_count - 1],
-            )
+        let complete_len = last_newline_idx + 1;
+        let mut rendered = self.render_unsettled(&self.buffer[self.settled_len..complete_len]);
+        self.try_settle(complete_len, &mut rendered);
+
+        let settled_count = self.settled_lines.len();
+        let mut complete_line_count = settled_count + rendered.len();
+        if let Some(last) = rendered.last()
+            && crate::render::line_utils::is_blank_line_spaces_only(last)
         {
             complete_line_count -= 1;
         }
@@ -55,9 +66,7 @@ impl MarkdownStreamCollector {
             return Vec::new();
         }
 
-        let out_slice = &rendered[self.committed_line_count..complete_line_count];
-
-        let out = out_slice.to_vec();
+        let out = self.lines_between(&rendered, self.committed_line_count, complete_line_count);
         self.committed_line_count = complete_line_count;
         out
     }
@@ -67,13 +76,14 @@ impl MarkdownStreamCollector {
     /// for rendering. Optionally unwraps ```markdown language fences in
     /// non-test builds.
     pub fn finalize_and_drain(&mut self) -> Vec<Line<'static>> {
-        let raw_buffer = self.buffer.clone();
-        let mut source: String = raw_buffer.clone();
+        let raw_buffer = &self.buffer;
+        let mut source = raw_buffer[self.settled_len..].to_string();
         if !source.ends_with('\n') {
             source.push('\n');
         }
         tracing::debug!(
             raw_len = raw_buffer.len(),
+            settled_len = self.settled_len,
             source_len = source.len(),
             "markdown finalize (raw length: {}, rendered length: {})",
             raw_buffer.len(),
@@ -81,19 +91,136 @@ impl MarkdownStreamCollector {
         );
         tracing::trace!("markdown finalize (raw source):\n---\n{source}\n---");
 
-        let mut rendered: Vec<Line<'static>> = Vec::new();
-        markdown::append_markdown(&source, self.width, &mut rendered);
-
-        let out = if self.committed_line_count >= rendered.len() {
+        let rendered = self.render_unsettled(&source);
+        let total = self.settled_lines.len() + rendered.len();
+        let out = if self.committed_line_count >= total {
             Vec::new()
         } else {
-            rendered[self.committed_line_count..].to_vec()
+            self.lines_between(&rendered, self.committed_line_count, total)
         };
 
         // Reset collector state for next stream.
         self.clear();
         out
     }
+
+    fn render_unsettled(&self, source: &str) -> Vec<Line<'static>> {
+        let mut rendered = Vec::new();
+        markdown::append_markdown(source, self.width, &mut rendered);
+        rendered
+    }
+
+    /// Lines `start..end` of the settled lines followed by `unsettled`.
+    fn lines_between(
+        &self,
+        unsettled: &[Line<'static>],
+        start: usize,
+        end: usize,
+    ) -> Vec<Line<'static>> {
+        let settled_count = self.settled_lines.len();
+        let mut out = Vec::with_capacity(end.saturating_sub(start));
+        if start < settled_count {
+            out.extend_from_slice(&self.settled_lines[start..end.min(settled_count)]);
+        }
+        if end > settled_count {
+            out.extend_from_slice(
+                &unsettled[start.saturating_sub(settled_count)..end - settled_count],
+            );
+        }
+        out
+    }
+
+    /// Moves the settled boundary forward when the unsettled source ends in a
+    /// block that has been closed by a blank line, so later text can no longer
+    /// change how anything before that block renders. `rendered` is the
+    /// rendering of the unsettled source up to `complete_len`, and is left
+    /// holding the rendering of what remains unsettled.
+    fn try_settle(&mut self, complete_len: usize, rendered: &mut Vec<Line<'static>>) {
+        // Link reference definitions apply to the whole document.
+        if self.buffer.contains("]:") {
+            return;
+        }
+        let unsettled = &self.buffer[self.settled_len..complete_len];
+        let Some(boundary) = settle_boundary(unsettled) else {
+            return;
+        };
+        let (head, rest) = unsettled.split_at(boundary);
+        let head_lines = self.render_unsettled(head);
+        let rest_lines = self.render_unsettled(rest);
+        // Only settle when rendering the two parts separately gives the same
+        // lines as rendering them together, give or take separating blanks.
+        let Some(separator) = rendered
+            .len()
+            .checked_sub(head_lines.len() + rest_lines.len())
+        else {
+            return;
+        };
+        let (rendered_head, tail) = rendered.split_at(head_lines.len());
+        let (blanks, rendered_rest) = tail.split_at(separator);
+        if rendered_head != head_lines.as_slice()
+            || rendered_rest != rest_lines.as_slice()
+            || !blanks
+                .iter()
+                .all(crate::render::line_utils::is_blank_line_spaces_only)
+        {
+            return;
+        }
+        let settled = rendered.drain(..head_lines.len() + separator);
+        self.settled_lines.extend(settled);
+        self.settled_len += boundary;
+    }
+}
+
+/// Where the last safely settleable prefix of `source` ends: just after a
+/// blank line that is outside code fences and is followed by a paragraph
+/// starting in column 0 that another blank line has already closed. A
+/// paragraph like that ends any list or quote before it, and can no longer
+/// turn into a heading or join them.
+fn settle_boundary(source: &str) -> Option<usize> {
+    let mut in_fence = false;
+    let mut previous_blank = false;
+    // Start of a paragraph that followed a blank line, until a blank line
+    // closes it.
+    let mut candidate: Option<usize> = None;
+    let mut best = None;
+    let mut offset = 0;
+    for line in source.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            candidate = None;
+            previous_blank = false;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        // HTML blocks such as comments can span blank lines.
+        if trimmed.starts_with('<') {
+            return None;
+        }
+        let blank = trimmed.is_empty();
+        if blank {
+            if let Some(paragraph_start) = candidate.take() {
+                best = Some(paragraph_start);
+            }
+        } else if previous_blank && starts_plain_paragraph(line) {
+            candidate = Some(start);
+        } else if candidate.is_some() && !starts_plain_paragraph(line) {
+            candidate = None;
+        }
+        previous_blank = blank;
+    }
+    best.filter(|&boundary| boundary > 0)
+}
+
+/// Whether `line` can only start (or continue) an ordinary paragraph: list
+/// markers, quotes, headings, setext underlines, tables and indented code all
+/// start with something else.
+fn starts_plain_paragraph(line: &str) -> bool {
+    line.chars().next().is_some_and(char::is_alphabetic)
 }
 
 #[cfg(test)]
@@ -658,6 +785,30 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn settled_prefix_streams_like_full_render() {
+        assert_streamed_equals_full(&[
+            "Intro paragraph.\n\n",
+            "- one\n- two\n\n",
+            "Middle paragraph\nthat continues.\n\n",
+            "```rust\nfn main() {}\n\nlet x = 1;\n```\n\n",
+            "1. first\n\n2. second\n\n",
+            "Closing words.\n\n",
+            "Tail without newline",
+        ])
+        .await;
+    }
+
+    #[tokio::test]
+    async fn closed_paragraphs_are_settled() {
+        let mut c = super::MarkdownStreamCollector::new(None);
+        for delta in ["# Title\n\n", "First.\n\n", "Second.\n\n", "Third\n"] {
+            c.push_delta(delta);
+            c.commit_complete_lines();
+        }
+        assert_eq!(c.settled_len, "# Title\n\nFirst.\n\n".len());
+    }
+
     #[tokio::test]
     async fn streaming_html_block_then_text_matches_full() {
         assert_streamed_equals_full(&[
@@ -14,11 +14,24 @@
 //! `TranscriptOverlay::sync_live_tail` uses the key to decide when the cached tail must be
 //! recomputed. `ChatWidget` is responsible for producing a key that changes when the active cell
 //! mutates in place or when its transcript output is time-dependent.
+//!
+//! Transcripts can run to tens of thousands of lines, so `PagerView` does not walk or render every
+//! cell per frame. `VirtualizedRenderables` keeps per-cell heights as prefix offsets to find the
+//! viewport by binary search, and keeps rendered rows only for cells near the viewport; cells that
+//! scroll further away are rendered again from their `HistoryCell` when they come back.
+//!
+//! With `tui.mouse` set, overlays also take mouse input: the wheel scrolls, dragging selects text
+//! and a click reports the word and transcript cell under the pointer. Pagers only turn gestures
+//! into an [`OverlayMouseAction`]; `App` copies the text, opens file references or selects the
+//! cell, since it owns the clipboard fallbacks, the editor and backtrack state.
 
+use std::collections::HashMap;
 use std::io::Result;
+use std::ops::Range;
 use std::sync::Arc;
 
 use crate::chatwidget::ActiveCellTranscriptKey;
+use crate::file_reference::word_at_column;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::UserHistoryCell;
 use crate::key_hint;
@@ -31,8 +44,12 @@ use crate::tui;
 use crate::tui::TuiEvent;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
+use crossterm::event::MouseButton;
+use crossterm::event::MouseEvent;
+use crossterm::event::MouseEventKind;
 use ratatui::buffer::Buffer;
 use ratatui::buffer::Cell;
+use ratatui::layout::Position;
 use ratatui::layout::Rect;
 use ratatui::style::Style;
 use ratatui::style::Stylize;
@@ -44,6 +61,7 @@ use ratatui::widgets::Paragraph;
 use ratatui::widgets::Widget;
 use ratatui::widgets::WidgetRef;
 use ratatui::widgets::Wrap;
+use unicode_width::UnicodeWidthStr;
 
 pub(crate) enum Overlay {
     Transcript(TranscriptOverlay),
@@ -79,6 +97,36 @@ impl Overlay {
             Overlay::Static(o) => o.is_done(),
         }
     }
+
+    /// Takes the action of the last mouse gesture, if it asked for one.
+    pub(crate) fn take_mouse_action(&mut self) -> Option<OverlayMouseAction> {
+        match self {
+            Overlay::Transcript(o) => o.mouse_action.take(),
+            Overlay::Static(o) => o.mouse_action.take(),
+        }
+    }
+
+    /// Shows a short message in the bottom bar until the next key press or click.
+    pub(crate) fn set_notice(&mut self, notice: String) {
+        match self {
+            Overlay::Transcript(o) => o.view.notice = Some(notice),
+            Overlay::Static(o) => o.view.notice = Some(notice),
+        }
+    }
+}
+
+/// A mouse gesture in an overlay that `App` acts on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum OverlayMouseAction {
+    /// Text selected by dragging, to be copied.
+    Copy(String),
+    /// A click that did not drag. `word` is the whitespace-delimited text under the pointer and
+    /// `cell` the committed transcript cell clicked, if any; `secondary` marks right clicks.
+    Click {
+        word: Option<String>,
+        cell: Option<usize>,
+        secondary: bool,
+    },
 }
 
 const KEY_UP: KeyBinding = key_hint::plain(KeyCode::Up);
@@ -101,6 +149,8 @@ const KEY_Q: KeyBinding = key_hint::plain(KeyCode::Char('q'));
 const KEY_ESC: KeyBinding = key_hint::plain(KeyCode::Esc);
 const KEY_ENTER: KeyBinding = key_hint::plain(KeyCode::Enter);
 const KEY_CTRL_T: KeyBinding = key_hint::ctrl(KeyCode::Char('t'));
+const KEY_F: KeyBinding = key_hint::plain(KeyCode::Char('f'));
+const KEY_B: KeyBinding = key_hint::plain(KeyCode::Char('b'));
 const KEY_CTRL_C: KeyBinding = key_hint::ctrl(KeyCode::Char('c'));
 
 // Common pager navigation hints rendered on the first line
@@ -131,34 +181,70 @@ fn render_key_hints(area: Rect, buf: &mut Buffer, pairs: &[(&[KeyBinding], &str)
     Paragraph::new(vec![Line::from(spans).dim()]).render_ref(area, buf);
 }
 
+/// Pages kept materialized above and below the viewport, so short scrolls reuse rendered rows.
+const MATERIALIZE_MARGIN_PAGES: usize = 1;
+
+/// Rows scrolled per mouse wheel notch.
+const MOUSE_SCROLL_ROWS: usize = 3;
+
+/// A row of a pager's content, counted from its top, and a column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct ContentPosition {
+    row: usize,
+    column: u16,
+}
+
+/// Text being selected by dragging the mouse, from where the drag started to where it is now.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct MouseSelection {
+    anchor: ContentPosition,
+    head: ContentPosition,
+}
+
+impl MouseSelection {
+    fn ordered(self) -> (ContentPosition, ContentPosition) {
+        (self.anchor.min(self.head), self.anchor.max(self.head))
+    }
+
+    fn is_empty(self) -> bool {
+        self.anchor == self.head
+    }
+}
+
 /// Generic widget for rendering a pager view.
 struct PagerView {
-    renderables: Vec<Box<dyn Renderable>>,
+    renderables: VirtualizedRenderables,
     scroll_offset: usize,
     title: String,
     last_content_height: Option<usize>,
     last_rendered_height: Option<usize>,
     /// If set, on next render ensure this chunk is visible.
     pending_scroll_chunk: Option<usize>,
+    /// Content area of the last render, for mapping mouse positions to content.
+    last_content_area: Option<Rect>,
+    /// Mouse selection, highlighted until the next click or key press.
+    selection: Option<MouseSelection>,
+    /// Short message shown in the bottom bar until the next click or key press.
+    notice: Option<String>,
 }
 
 impl PagerView {
     fn new(renderables: Vec<Box<dyn Renderable>>, title: String, scroll_offset: usize) -> Self {
         Self {
-            renderables,
+            renderables: VirtualizedRenderables::new(renderables),
             scroll_offset,
             title,
             last_content_height: None,
             last_rendered_height: None,
             pending_scroll_chunk: None,
+            last_content_area: None,
+            selection: None,
+            notice: None,
         }
     }
 
-    fn content_height(&self, width: u16) -> usize {
-        self.renderables
-            .iter()
-            .map(|c| c.desired_height(width) as usize)
-            .sum()
+    fn content_height(&mut self, width: u16) -> usize {
+        self.renderables.total_height(width)
     }
 
     fn render(&mut self, area: Rect, buf: &mut Buffer) {
@@ -178,6 +264,8 @@ impl PagerView {
             .min(content_height.saturating_sub(content_area.height as usize));
 
         self.render_content(content_area, buf);
+        self.render_selection(content_area, buf);
+        self.last_content_area = Some(content_area);
 
         self.render_bottom_bar(area, content_area, buf, content_height);
     }
@@ -190,30 +278,22 @@ impl PagerView {
         header.dim().render_ref(area, buf);
     }
 
-    fn render_content(&self, area: Rect, buf: &mut Buffer) {
-        let mut y = -(self.scroll_offset as isize);
+    fn render_content(&mut self, area: Rect, buf: &mut Buffer) {
+        let view_top = self.scroll_offset;
+        let view_bottom = view_top.saturating_add(area.height as usize);
         let mut drawn_bottom = area.y;
-        for renderable in &self.renderables {
-            let top = y;
-            let height = renderable.desired_height(area.width) as isize;
-            y += height;
-            let bottom = y;
-            if bottom < area.y as isize {
-                continue;
-            }
-            if top > area.y as isize + area.height as isize {
-                break;
-            }
-            if top < 0 {
-                let drawn = render_offset_content(area, buf, &**renderable, (-top) as u16);
-                drawn_bottom = drawn_bottom.max(area.y + drawn);
-            } else {
-                let draw_height = (height as u16).min(area.height.saturating_sub(top as u16));
-                let draw_area = Rect::new(area.x, area.y + top as u16, area.width, draw_height);
-                renderable.render(draw_area, buf);
-                drawn_bottom = drawn_bottom.max(draw_area.y.saturating_add(draw_area.height));
-            }
+        for idx in self.renderables.visible(area.width, view_top..view_bottom) {
+            let rows = self.renderables.rows(idx);
+            let skip = view_top.saturating_sub(rows.start);
+            let y = area.y + rows.start.saturating_sub(view_top) as u16;
+            let height = (rows.len().saturating_sub(skip) as u16).min(area.bottom() - y);
+            let draw_area = Rect::new(area.x, y, area.width, height);
+            self.renderables.render_rows(idx, skip, draw_area, buf);
+            drawn_bottom = drawn_bottom.max(draw_area.bottom());
         }
+        let margin = (area.height as usize).saturating_mul(MATERIALIZE_MARGIN_PAGES);
+        self.renderables
+            .evict_outside(view_top.saturating_sub(margin)..view_bottom.saturating_add(margin));
 
         for y in drawn_bottom..area.bottom() {
             if area.width == 0 {
@@ -226,6 +306,32 @@ impl PagerView {
         }
     }
 
+    fn render_selection(&self, area: Rect, buf: &mut Buffer) {
+        let Some(selection) = self.selection.filter(|selection| !selection.is_empty()) else {
+            return;
+        };
+        let (start, end) = selection.ordered();
+        for y in area.top()..area.bottom() {
+            let row = self.scroll_offset + (y - area.y) as usize;
+            if row < start.row || row > end.row {
+                continue;
+            }
+            let from = if row == start.row { start.column } else { 0 };
+            let to = if row == end.row {
+                end.column.saturating_add(1)
+            } else {
+                area.width
+            };
+            let (from, to) = (from.min(area.width), to.min(area.width));
+            if to > from {
+                buf.set_style(
+                    Rect::new(area.x + from, y, to - from, 1),
+                    Style::default().reversed(),
+                );
+            }
+        }
+    }
+
     fn render_bottom_bar(
         &self,
         full_area: Rect,
@@ -256,9 +362,16 @@ impl PagerView {
         Span::from(pct_text)
             .dim()
             .render_ref(Rect::new(pct_x, sep_rect.y, pct_w, 1), buf);
+        if let Some(notice) = &self.notice {
+            let notice = format!(" {notice} ");
+            let width = (notice.width() as u16).min(pct_x.saturating_sub(sep_rect.x + 1));
+            Span::from(notice).render_ref(Rect::new(sep_rect.x + 1, sep_rect.y, width, 1), buf);
+        }
     }
 
     fn handle_key_event(&mut self, tui: &mut tui::Tui, key_event: KeyEvent) -> Result<()> {
+        self.selection = None;
+        self.notice = None;
         match key_event {
             e if KEY_UP.is_press(e) || KEY_K.is_press(e) => {
                 self.scroll_offset = self.scroll_offset.saturating_sub(1);
@@ -302,6 +415,137 @@ impl PagerView {
         Ok(())
     }
 
+    /// Scrolls on the wheel and tracks drag selections; returns what a finished gesture asks for.
+    fn handle_mouse_event(
+        &mut self,
+        tui: &mut tui::Tui,
+        event: MouseEvent,
+    ) -> Option<OverlayMouseAction> {
+        let area = self.last_content_area.filter(|area| !area.is_empty())?;
+        let action = match event.kind {
+            MouseEventKind::ScrollUp => {
+                self.scroll_offset = self.view_top(area).saturating_sub(MOUSE_SCROLL_ROWS);
+                None
+            }
+            MouseEventKind::ScrollDown => {
+                self.scroll_offset = self.view_top(area).saturating_add(MOUSE_SCROLL_ROWS);
+                None
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.notice = None;
+                self.selection =
+                    self.content_position(area, event.column, event.row)
+                        .map(|position| MouseSelection {
+                            anchor: position,
+                            head: position,
+                        });
+                None
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                // Dragging past the top or bottom edge scrolls the selection along.
+                if event.row < area.top() {
+                    self.scroll_offset = self.view_top(area).saturating_sub(1);
+                } else if event.row >= area.bottom() {
+                    self.scroll_offset = self.view_top(area).saturating_add(1);
+                }
+                let column = event.column.clamp(area.left(), area.right() - 1);
+                let row = event.row.clamp(area.top(), area.bottom() - 1);
+                let head = self.content_position(area, column, row);
+                if let (Some(selection), Some(head)) = (self.selection.as_mut(), head) {
+                    selection.head = head;
+                }
+                None
+            }
+            MouseEventKind::Up(MouseButton::Left) => match self.selection {
+                Some(selection) if selection.is_empty() => {
+                    self.selection = None;
+                    Some(self.click_action(area.width, selection.anchor, false))
+                }
+                Some(selection) => Some(OverlayMouseAction::Copy(
+                    self.selection_text(area.width, selection),
+                )),
+                None => None,
+            },
+            MouseEventKind::Down(MouseButton::Right) => {
+                self.notice = None;
+                self.content_position(area, event.column, event.row)
+                    .map(|position| self.click_action(area.width, position, true))
+            }
+            _ => return None,
+        };
+        tui.frame_requester()
+            .schedule_frame_in(crate::tui::TARGET_FRAME_INTERVAL);
+        action
+    }
+
+    /// First content row in view as of the last render, even while pinned to the bottom.
+    fn view_top(&self, area: Rect) -> usize {
+        let max_scroll = self
+            .last_rendered_height
+            .unwrap_or(0)
+            .saturating_sub(area.height as usize);
+        self.scroll_offset.min(max_scroll)
+    }
+
+    /// The content position under screen cell (`column`, `row`), if it is in `area`.
+    fn content_position(&self, area: Rect, column: u16, row: u16) -> Option<ContentPosition> {
+        area.contains(Position::new(column, row))
+            .then(|| ContentPosition {
+                row: self.view_top(area) + (row - area.y) as usize,
+                column: column - area.x,
+            })
+    }
+
+    fn click_action(
+        &mut self,
+        width: u16,
+        position: ContentPosition,
+        secondary: bool,
+    ) -> OverlayMouseAction {
+        let rows = self.render_plain_rows(position.row..position.row + 1, width);
+        let text = plain_row_text(&rows, 0, 0..width);
+        OverlayMouseAction::Click {
+            word: word_at_column(&text, position.column as usize).map(str::to_string),
+            cell: self.chunk_at_row(position.row),
+            secondary,
+        }
+    }
+
+    /// The selected text as plain characters, without styles, trailing spaces or filler rows.
+    fn selection_text(&mut self, width: u16, selection: MouseSelection) -> String {
+        let (start, end) = selection.ordered();
+        let rows = self.render_plain_rows(start.row..end.row + 1, width);
+        let lines: Vec<String> = (0..rows.area.height)
+            .map(|y| {
+                let row = start.row + y as usize;
+                let from = if row == start.row { start.column } else { 0 };
+                let to = if row == end.row {
+                    end.column.saturating_add(1)
+                } else {
+                    width
+                };
+                plain_row_text(&rows, y, from..to)
+            })
+            .collect();
+        lines.join("\n").trim_end_matches('\n').to_string()
+    }
+
+    /// Renders content `rows` at `width` into a scratch buffer whose first row is `rows.start`.
+    fn render_plain_rows(&mut self, rows: Range<usize>, width: u16) -> Buffer {
+        let height = rows.len().min(u16::MAX as usize) as u16;
+        let rows = rows.start..rows.start + height as usize;
+        let mut buf = Buffer::empty(Rect::new(0, 0, width, height));
+        for idx in self.renderables.visible(width, rows.clone()) {
+            let item_rows = self.renderables.rows(idx);
+            let skip = rows.start.saturating_sub(item_rows.start);
+            let y = item_rows.start.saturating_sub(rows.start) as u16;
+            let item_height = (item_rows.len().saturating_sub(skip) as u16).min(height - y);
+            self.renderables
+                .render_rows(idx, skip, Rect::new(0, y, width, item_height), &mut buf);
+        }
+        buf
+    }
+
     /// Returns the height of one page in content rows.
     ///
     /// Prefers the last rendered content height (excluding header/footer chrome);
@@ -350,17 +594,33 @@ impl PagerView {
         self.pending_scroll_chunk = Some(chunk_index);
     }
 
+    /// Index of the chunk at the top of the last rendered view, if it has been measured.
+    fn top_chunk(&self) -> Option<usize> {
+        self.renderables.width?;
+        let measured = self.renderables.offsets.len().checked_sub(1)?;
+        self.renderables.offsets[..measured]
+            .partition_point(|&top| top <= self.scroll_offset)
+            .checked_sub(1)
+    }
+
+    /// Index of the chunk covering content `row`, if the row has content.
+    fn chunk_at_row(&self, row: usize) -> Option<usize> {
+        let measured = self.renderables.offsets.len().checked_sub(1)?;
+        if row >= self.renderables.offsets[measured] {
+            return None;
+        }
+        self.renderables.offsets[..measured]
+            .partition_point(|&top| top <= row)
+            .checked_sub(1)
+    }
+
     fn ensure_chunk_visible(&mut self, idx: usize, area: Rect) {
         if area.height == 0 || idx >= self.renderables.len() {
             return;
         }
-        let first = self
-            .renderables
-            .iter()
-            .take(idx)
-            .map(|r| r.desired_height(area.width) as usize)
-            .sum();
-        let last = first + self.renderables[idx].desired_height(area.width) as usize;
+        self.renderables.measure(area.width);
+        let rows = self.renderables.rows(idx);
+        let (first, last) = (rows.start, rows.end);
         let current_top = self.scroll_offset;
         let current_bottom = current_top.saturating_add(area.height.saturating_sub(1) as usize);
         if first < current_top {
@@ -371,6 +631,159 @@ impl PagerView {
     }
 }
 
+/// Text of row `y` of `buf` within `columns`, with trailing spaces trimmed. A wide character
+/// takes one `char` for all the columns it covers, so columns in the text line up with the screen.
+fn plain_row_text(buf: &Buffer, y: u16, columns: Range<u16>) -> String {
+    let mut text = String::new();
+    let mut covered = 0;
+    for x in 0..buf.area.width {
+        let symbol = buf[(x, y)].symbol();
+        if covered > 0 {
+            covered -= 1;
+            continue;
+        }
+        if columns.contains(&x) {
+            text.push_str(symbol);
+        }
+        covered = symbol.width().saturating_sub(1);
+    }
+    text.trim_end().to_string()
+}
+
+/// The renderables of a [`PagerView`], measured and rendered lazily.
+///
+/// Heights are folded into prefix offsets for the current width, so the renderables in view are
+/// found by binary search, and appending only measures the new entries. Rendered rows are kept in
+/// `materialized` for entries near the viewport and dropped once they scroll further away.
+struct VirtualizedRenderables {
+    items: Vec<Box<dyn Renderable>>,
+    /// Width that `offsets` and `materialized` were computed for.
+    width: Option<u16>,
+    /// `offsets[i]` is the first row of `items[i]`; the entry after the last measured item is the
+    /// row just past it.
+    offsets: Vec<usize>,
+    /// Fully rendered rows of items near the viewport, keyed by index into `items`.
+    materialized: HashMap<usize, Buffer>,
+}
+
+impl VirtualizedRenderables {
+    fn new(items: Vec<Box<dyn Renderable>>) -> Self {
+        Self {
+            items,
+            width: None,
+            offsets: Vec::new(),
+            materialized: HashMap::new(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    fn set(&mut self, items: Vec<Box<dyn Renderable>>) {
+        self.items = items;
+        self.offsets.clear();
+        self.materialized.clear();
+    }
+
+    fn push(&mut self, item: Box<dyn Renderable>) {
+        self.items.push(item);
+    }
+
+    fn pop(&mut self) -> Option<Box<dyn Renderable>> {
+        let item = self.items.pop()?;
+        self.invalidate(self.items.len());
+        Some(item)
+    }
+
+    fn replace(&mut self, idx: usize, item: Box<dyn Renderable>) {
+        if let Some(slot) = self.items.get_mut(idx) {
+            *slot = item;
+            self.invalidate(idx);
+        }
+    }
+
+    /// Forgets the rendering of `items[idx]` and the offsets of everything after it.
+    fn invalidate(&mut self, idx: usize) {
+        self.offsets.truncate(idx + 1);
+        self.materialized.remove(&idx);
+    }
+
+    /// Brings `offsets` up to date for `width`, measuring only items not measured yet.
+    fn measure(&mut self, width: u16) {
+        if self.width != Some(width) {
+            self.width = Some(width);
+            self.offsets.clear();
+            self.materialized.clear();
+        }
+        if self.offsets.is_empty() {
+            self.offsets.push(0);
+        }
+        for item in &self.items[self.offsets.len() - 1..] {
+            let top = self.offsets[self.offsets.len() - 1];
+            self.offsets.push(top + item.desired_height(width) as usize);
+        }
+    }
+
+    fn total_height(&mut self, width: u16) -> usize {
+        self.measure(width);
+        self.offsets[self.items.len()]
+    }
+
+    /// Rows covered by `items[idx]`; requires a prior [`Self::measure`].
+    fn rows(&self, idx: usize) -> Range<usize> {
+        self.offsets[idx]..self.offsets[idx + 1]
+    }
+
+    /// Indices of the non-empty items that overlap `view` at `width`.
+    fn visible(&mut self, width: u16, view: Range<usize>) -> Vec<usize> {
+        self.measure(width);
+        let first = self.offsets[..self.items.len()]
+            .partition_point(|&top| top <= view.start)
+            .saturating_sub(1);
+        (first..self.items.len())
+            .take_while(|&idx| self.offsets[idx] < view.end)
+            .filter(|&idx| self.offsets[idx + 1] > view.start)
+            .collect()
+    }
+
+    /// Copies rows of `items[idx]`, starting `skip` rows into it, into `area`, rendering the item
+    /// first if it is not materialized.
+    fn render_rows(&mut self, idx: usize, skip: usize, area: Rect, buf: &mut Buffer) {
+        let rows = self.rows(idx);
+        let item = &self.items[idx];
+        let rendered = self.materialized.entry(idx).or_insert_with(|| {
+            let mut rendered = Buffer::empty(Rect::new(0, 0, area.width, rows.len() as u16));
+            item.render(rendered.area, &mut rendered);
+            rendered
+        });
+        let copy_height = area
+            .height
+            .min(rendered.area.height.saturating_sub(skip as u16));
+        for y in 0..copy_height {
+            let src_y = y + skip as u16;
+            for x in 0..area.width.min(rendered.area.width) {
+                buf[(area.x + x, area.y + y)] = rendered[(x, src_y)].clone();
+            }
+        }
+    }
+
+    /// Drops the rendered rows of items entirely outside `keep`.
+    fn evict_outside(&mut self, keep: Range<usize>) {
+        let offsets = &self.offsets;
+        self.materialized.retain(|&idx, _| {
+            matches!(
+                (offsets.get(idx), offsets.get(idx + 1)),
+                (Some(&top), Some(&bottom)) if top < keep.end && bottom > keep.start
+            )
+        });
+    }
+}
+
 /// A renderable that caches its desired height.
 struct CachedRenderable {
     renderable: Box<dyn Renderable>,
@@ -430,6 +843,8 @@ pub(crate) struct TranscriptOverlay {
     highlight_cell: Option<usize>,
     /// Cache key for the render-only live tail appended after committed cells.
     live_tail_key: Option<LiveTailKey>,
+    /// Set by a mouse gesture for `App` to act on.
+    mouse_action: Option<OverlayMouseAction>,
     is_done: bool,
 }
 
@@ -463,6 +878,7 @@ impl TranscriptOverlay {
             cells: transcript_cells,
             highlight_cell: None,
             live_tail_key: None,
+            mouse_action: None,
             is_done: false,
         }
     }
@@ -474,39 +890,47 @@ impl TranscriptOverlay {
         cells
             .iter()
             .enumerate()
-            .flat_map(|(i, c)| {
-                let mut v: Vec<Box<dyn Renderable>> = Vec::new();
-                let mut cell_renderable = if c.as_any().is::<UserHistoryCell>() {
-                    Box::new(CachedRenderable::new(CellRenderable {
-                        cell: c.clone(),
-                        style: if highlight_cell == Some(i) {
-                            user_message_style().reversed()
-                        } else {
-                            user_message_style()
-                        },
-                    })) as Box<dyn Renderable>
-                } else {
-                    Box::new(CachedRenderable::new(CellRenderable {
-                        cell: c.clone(),
-                        style: Style::default(),
-                    })) as Box<dyn Renderable>
-                };
-                if !c.is_stream_continuation() && i > 0 {
-                    cell_renderable = Box::new(InsetRenderable::new(
-                        cell_renderable,
-                        Insets::tlbr(1, 0, 0, 0),
-                    ));
-                }
-                v.push(cell_renderable);
-                v
-            })
+            .map(|(i, c)| Self::render_cell(i, c, highlight_cell))
             .collect()
     }
 
+    fn render_cell(
+        i: usize,
+        c: &Arc<dyn HistoryCell>,
+        highlight_cell: Option<usize>,
+    ) -> Box<dyn Renderable> {
+        let mut cell_renderable = if c.as_any().is::<UserHistoryCell>() {
+            Box::new(CachedRenderable::new(CellRenderable {
+                cell: c.clone(),
+                style: if highlight_cell == Some(i) {
+                    user_message_style().reversed()
+                } else {
+                    user_message_style()
+                },
+            })) as Box<dyn Renderable>
+        } else {
+            Box::new(CachedRenderable::new(CellRenderable {
+                cell: c.clone(),
+                style: if highlight_cell == Some(i) {
+                    Style::default().reversed()
+                } else {
+                    Style::default()
+                },
+            })) as Box<dyn Renderable>
+        };
+        if !c.is_stream_continuation() && i > 0 {
+            cell_renderable = Box::new(InsetRenderable::new(
+                cell_renderable,
+                Insets::tlbr(1, 0, 0, 0),
+            ));
+        }
+        cell_renderable
+    }
+
     /// Insert a committed history cell while keeping any cached live tail.
     ///
-    /// The live tail is temporarily removed, the committed cells are rebuilt,
-    /// then the tail is reattached. If the tail previously had no leading
+    /// The live tail is temporarily removed, a renderable for the new cell is
+    /// appended after the existing ones, then the tail is reattached. If the tail previously had no leading
     /// spacing because it was the only renderable, we add the missing inset
     /// when the first committed cell arrives.
     ///
@@ -517,8 +941,11 @@ impl TranscriptOverlay {
         let follow_bottom = self.view.is_scrolled_to_bottom();
         let had_prior_cells = !self.cells.is_empty();
         let tail_renderable = self.take_live_tail_renderable();
+        let idx = self.cells.len();
+        self.view
+            .renderables
+            .push(Self::render_cell(idx, &cell, self.highlight_cell));
         self.cells.push(cell);
-        self.view.renderables = Self::render_cells(&self.cells, self.highlight_cell);
         if let Some(tail) = tail_renderable {
             let tail = if !had_prior_cells
                 && self
@@ -607,9 +1034,23 @@ impl TranscriptOverlay {
         }
     }
 
+    /// Cell that `b` bookmarks: the highlighted cell, else the cell at the top of the view.
+    pub(crate) fn bookmark_target(&self) -> Option<usize> {
+        self.highlight_cell
+            .or_else(|| self.view.top_chunk())
+            .filter(|&idx| idx < self.cells.len())
+    }
+
     pub(crate) fn set_highlight_cell(&mut self, cell: Option<usize>) {
-        self.highlight_cell = cell;
-        self.rebuild_renderables();
+        let previous = std::mem::replace(&mut self.highlight_cell, cell);
+        // Only the previously and newly highlighted cells change style.
+        for idx in [previous, cell].into_iter().flatten() {
+            if let Some(c) = self.cells.get(idx) {
+                self.view
+                    .renderables
+                    .replace(idx, Self::render_cell(idx, c, cell));
+            }
+        }
         if let Some(idx) = self.highlight_cell {
             self.view.scroll_chunk_into_view(idx);
         }
@@ -625,7 +1066,9 @@ impl TranscriptOverlay {
 
     fn rebuild_renderables(&mut self) {
         let tail_renderable = self.take_live_tail_renderable();
-        self.view.renderables = Self::render_cells(&self.cells, self.highlight_cell);
+        self.view
+            .renderables
+            .set(Self::render_cells(&self.cells, self.highlight_cell));
         if let Some(tail) = tail_renderable {
             self.view.renderables.push(tail);
         }
@@ -663,9 +1106,11 @@ impl TranscriptOverlay {
             pairs.push((&[KEY_ESC, KEY_LEFT], "to edit prev"));
             pairs.push((&[KEY_RIGHT], "to edit next"));
             pairs.push((&[KEY_ENTER], "to edit message"));
+            pairs.push((&[KEY_F], "to fork here"));
         } else {
             pairs.push((&[KEY_ESC], "to edit prev"));
         }
+        pairs.push((&[KEY_B], "to bookmark"));
         render_key_hints(line2, buf, &pairs);
     }
 
@@ -688,6 +1133,26 @@ impl TranscriptOverlay {
                 }
                 other => self.view.handle_key_event(tui, other),
             },
+            TuiEvent::Mouse(mouse_event) => {
+                let committed = self.cells.len();
+                self.mouse_action = self
+                    .view
+                    .handle_mouse_event(tui, mouse_event)
+                    .map(|action| match action {
+                        // The live tail is not a committed cell and cannot be selected.
+                        OverlayMouseAction::Click {
+                            word,
+                            cell,
+                            secondary,
+                        } => OverlayMouseAction::Click {
+                            word,
+                            cell: cell.filter(|&idx| idx < committed),
+                            secondary,
+                        },
+                        copy => copy,
+                    });
+                Ok(())
+            }
             TuiEvent::Draw => {
                 tui.draw(u16::MAX, |frame| {
                     self.render(frame.area(), frame.buffer);
@@ -709,6 +1174,8 @@ impl TranscriptOverlay {
 
 pub(crate) struct StaticOverlay {
     view: PagerView,
+    /// Set by a mouse gesture for `App` to act on.
+    mouse_action: Option<OverlayMouseAction>,
     is_done: bool,
 }
 
@@ -721,6 +1188,7 @@ impl StaticOverlay {
     pub(crate) fn with_renderables(renderables: Vec<Box<dyn Renderable>>, title: String) -> Self {
         Self {
             view: PagerView::new(renderables, title, 0),
+            mouse_action: None,
             is_done: false,
         }
     }
@@ -752,6 +1220,23 @@ impl StaticOverlay {
                 }
                 other => self.view.handle_key_event(tui, other),
             },
+            TuiEvent::Mouse(mouse_event) => {
+                // Static overlays have no cells to select; clicks only matter for their words.
+                self.mouse_action = self
+                    .view
+                    .handle_mouse_event(tui, mouse_event)
+                    .map(|action| match action {
+                        OverlayMouseAction::Click {
+                            word, secondary, ..
+                        } => OverlayMouseAction::Click {
+                            word,
+                            cell: None,
+                            secondary,
+                        },
+                        copy => copy,
+                    });
+                Ok(())
+            }
             TuiEvent::Draw => {
                 tui.draw(u16::MAX, |frame| {
                     self.render(frame.area(), frame.buffer);
@@ -766,33 +1251,6 @@ impl StaticOverlay {
     }
 }
 
-fn render_offset_content(
-    area: Rect,
-    buf: &mut Buffer,
-    renderable: &dyn Renderable,
-    scroll_offset: u16,
-) -> u16 {
-    let height = renderable.desired_height(area.width);
-    let mut tall_buf = Buffer::empty(Rect::new(
-        0,
-        0,
-        area.width,
-        height.min(area.height + scroll_offset),
-    ));
-    renderable.render(*tall_buf.area(), &mut tall_buf);
-    let copy_height = area
-        .height
-        .min(tall_buf.area().height.saturating_sub(scroll_offset));
-    for y in 0..copy_height {
-        let src_y = y + scroll_offset;
-        for x in 0..area.width {
-            buf[(area.x + x, area.y + y)] = tall_buf[(x, src_y)].clone();
-        }
-    }
-
-    copy_height
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -800,8 +1258,10 @@ mod tests {
     use codex_core::protocol::ReviewDecision;
     use insta::assert_snapshot;
     use pretty_assertions::assert_eq;
+    use std::cell::Cell;
     use std::collections::HashMap;
     use std::path::PathBuf;
+    use std::rc::Rc;
     use std::sync::Arc;
     use std::time::Duration;
 
@@ -857,6 +1317,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bookmark_target_prefers_highlight_then_top_of_view() {
+        let mut overlay = TranscriptOverlay::new(
+            (0..10)
+                .map(|i| {
+                    Arc::new(TestCell {
+                        lines: vec![Line::from(format!("line{i}"))],
+                    }) as Arc<dyn HistoryCell>
+                })
+                .collect(),
+        );
+        assert_eq!(overlay.bookmark_target(), None);
+
+        let area = Rect::new(0, 0, 40, 8);
+        let mut buf = Buffer::empty(area);
+        overlay.view.scroll_offset = 0;
+        overlay.render(area, &mut buf);
+        assert_eq!(overlay.bookmark_target(), Some(0));
+
+        // Every cell after the first has a blank line above it, so cell 3 starts at row 5.
+        overlay.view.scroll_offset = 5;
+        assert_eq!(overlay.bookmark_target(), Some(3));
+
+        overlay.set_highlight_cell(Some(7));
+        assert_eq!(overlay.bookmark_target(), Some(7));
+    }
+
     #[test]
     fn edit_next_hint_is_visible_when_highlighted() {
         let mut overlay = TranscriptOverlay::new(vec![Arc::new(TestCell {
@@ -1006,6 +1493,7 @@ mod tests {
                 exit_code: 0,
                 aggregated_output: "src\nREADME.md\n".into(),
                 formatted_output: "src\nREADME.md\n".into(),
+                test_failures: Vec::new(),
             },
             Duration::from_millis(420),
         );
@@ -1196,7 +1684,7 @@ mod tests {
 
     #[test]
     fn pager_view_content_height_counts_renderables() {
-        let pv = PagerView::new(
+        let mut pv = PagerView::new(
             vec![paragraph_block("a", 2), paragraph_block("b", 3)],
             "T".to_string(),
             0,
@@ -1205,6 +1693,62 @@ mod tests {
         assert_eq!(pv.content_height(80), 5);
     }
 
+    #[test]
+    fn pager_view_selection_copies_plain_text_across_rows() {
+        let mut pv = PagerView::new(
+            vec![
+                paragraph_block("a", 3),
+                Box::new(Paragraph::new(Line::from("styled text".cyan()))) as Box<dyn Renderable>,
+            ],
+            "T".to_string(),
+            0,
+        );
+        let area = Rect::new(0, 0, 20, 8);
+        let mut buf = Buffer::empty(area);
+        pv.render(area, &mut buf);
+
+        // Dragged upwards, from the middle of the last row to the second column of row 1.
+        let selection = MouseSelection {
+            anchor: ContentPosition { row: 3, column: 5 },
+            head: ContentPosition { row: 1, column: 1 },
+        };
+        assert_eq!(pv.selection_text(20, selection), "1\na2\nstyled");
+    }
+
+    #[test]
+    fn pager_view_click_reports_word_and_chunk_under_pointer() {
+        let mut pv = PagerView::new(
+            vec![
+                paragraph_block("a", 2),
+                Box::new(Paragraph::new(Line::from("see src/lib.rs:12 now")))
+                    as Box<dyn Renderable>,
+            ],
+            "T".to_string(),
+            0,
+        );
+        let area = Rect::new(0, 0, 30, 8);
+        let mut buf = Buffer::empty(area);
+        pv.render(area, &mut buf);
+
+        assert_eq!(
+            pv.click_action(30, ContentPosition { row: 2, column: 6 }, false),
+            OverlayMouseAction::Click {
+                word: Some("src/lib.rs:12".to_string()),
+                cell: Some(1),
+                secondary: false,
+            }
+        );
+        // Filler rows below the content have neither.
+        assert_eq!(
+            pv.click_action(30, ContentPosition { row: 4, column: 0 }, true),
+            OverlayMouseAction::Click {
+                word: None,
+                cell: None,
+                secondary: true,
+            }
+        );
+    }
+
     #[test]
     fn pager_view_ensure_chunk_visible_scrolls_down_when_needed() {
         let mut pv = PagerView::new(
@@ -1259,6 +1803,91 @@ mod tests {
         assert_eq!(pv.scroll_offset, 0);
     }
 
+    /// Paragraph block that counts how often it is measured and rendered.
+    struct CountingBlock {
+        block: Box<dyn Renderable>,
+        measured: Rc<Cell<usize>>,
+        rendered: Rc<Cell<usize>>,
+    }
+
+    impl Renderable for CountingBlock {
+        fn render(&self, area: Rect, buf: &mut Buffer) {
+            self.rendered.set(self.rendered.get() + 1);
+            self.block.render(area, buf);
+        }
+
+        fn desired_height(&self, width: u16) -> u16 {
+            self.measured.set(self.measured.get() + 1);
+            self.block.desired_height(width)
+        }
+    }
+
+    fn counting_blocks(
+        count: usize,
+        lines: usize,
+    ) -> (Vec<Box<dyn Renderable>>, Rc<Cell<usize>>, Rc<Cell<usize>>) {
+        let measured = Rc::new(Cell::new(0));
+        let rendered = Rc::new(Cell::new(0));
+        let blocks = (0..count)
+            .map(|i| {
+                Box::new(CountingBlock {
+                    block: paragraph_block(&format!("b{i}."), lines),
+                    measured: measured.clone(),
+                    rendered: rendered.clone(),
+                }) as Box<dyn Renderable>
+            })
+            .collect();
+        (blocks, measured, rendered)
+    }
+
+    #[test]
+    fn pager_view_materializes_only_renderables_near_viewport() {
+        let (blocks, measured, rendered) = counting_blocks(10_000, 3);
+        let mut pv = PagerView::new(blocks, "T".to_string(), 15_000);
+        let area = Rect::new(0, 0, 20, 8);
+        let mut buf = Buffer::empty(area);
+
+        pv.render(area, &mut buf);
+
+        assert_eq!(measured.get(), 10_000);
+        // Rows 15000..15006 are blocks 5000 and 5001.
+        assert_eq!(rendered.get(), 2);
+        let rendered_text = buffer_to_text(&buf, area);
+        assert!(
+            rendered_text.contains("b5000.0") && rendered_text.contains("b5001.2"),
+            "expected the blocks at the scroll offset: {rendered_text:?}"
+        );
+
+        // Scrolling within the margin reuses rendered rows and measures nothing again.
+        pv.scroll_offset = 15_001;
+        pv.render(area, &mut buf);
+        assert_eq!(measured.get(), 10_000);
+        assert_eq!(rendered.get(), 3);
+
+        pv.scroll_offset = 0;
+        pv.render(area, &mut buf);
+        let mut materialized: Vec<usize> = pv.renderables.materialized.keys().copied().collect();
+        materialized.sort_unstable();
+        assert_eq!(materialized, vec![0, 1]);
+    }
+
+    #[test]
+    fn pager_view_measures_only_appended_renderables() {
+        let (blocks, measured, _) = counting_blocks(100, 2);
+        let mut pv = PagerView::new(blocks, "T".to_string(), usize::MAX);
+        assert_eq!(pv.content_height(20), 200);
+        assert_eq!(measured.get(), 100);
+
+        pv.renderables.push(paragraph_block("tail", 3));
+        assert_eq!(pv.content_height(20), 203);
+        assert_eq!(measured.get(), 100);
+
+        pv.renderables.pop();
+        assert_eq!(pv.content_height(20), 200);
+        assert_eq!(pv.content_height(10), 200);
+        assert_eq!(measured.get(), 200);
+    }
+
     #[test]
     fn pager_view_is_scrolled_to_bottom_accounts_for_wrapped_height() {
         let mut pv = PagerView::new(vec![paragraph_block("a", 10)], "T".to_string(), 0);
@@ -52,6 +52,12 @@ impl ComposerInput {
             .set_text_content(String::new(), Vec::new(), Vec::new());
     }
 
+    /// Replace the input text (e.g. to prefill an edited draft) and place the cursor at the end.
+    pub fn set_text(&mut self, text: String) {
+        self.inner.set_text_content(text, Vec::new(), Vec::new());
+        self.inner.move_cursor_to_end();
+    }
+
     /// Feed a key event into the composer and return a high-level action.
     pub fn input(&mut self, key: KeyEvent) -> ComposerAction {
         let action = match self.inner.handle_key_event(key).0 {
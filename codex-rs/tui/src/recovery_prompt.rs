@@ -0,0 +1,227 @@
+use crate::key_hint;
+use crate::render::Insets;
+use crate::render::renderable::ColumnRenderable;
+use crate::render::renderable::Renderable;
+use crate::render::renderable::RenderableExt as _;
+use crate::selection_list::selection_option_row;
+use crate::tui::FrameRequester;
+use crate::tui::Tui;
+use crate::tui::TuiEvent;
+use codex_core::turn_journal::InFlightTurn;
+use color_eyre::Result;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::prelude::Widget;
+use ratatui::style::Stylize as _;
+use ratatui::text::Line;
+use ratatui::widgets::Clear;
+use ratatui::widgets::WidgetRef;
+use tokio_stream::StreamExt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RecoverySelection {
+    Resume,
+    StartFresh,
+}
+
+impl RecoverySelection {
+    fn toggle(self) -> Self {
+        match self {
+            RecoverySelection::Resume => RecoverySelection::StartFresh,
+            RecoverySelection::StartFresh => RecoverySelection::Resume,
+        }
+    }
+}
+
+/// Asks whether to resume a session whose previous process exited mid-turn.
+pub(crate) async fn run_recovery_prompt(
+    tui: &mut Tui,
+    interrupted: &InFlightTurn,
+) -> Result<RecoverySelection> {
+    let mut screen = RecoveryPromptScreen::new(tui.frame_requester(), interrupted);
+    tui.draw(u16::MAX, |frame| {
+        frame.render_widget_ref(&screen, frame.area());
+    })?;
+
+    let events = tui.event_stream();
+    tokio::pin!(events);
+
+    while !screen.is_done() {
+        if let Some(event) = events.next().await {
+            match event {
+                TuiEvent::Key(key_event) => screen.handle_key(key_event),
+                TuiEvent::Paste(_) | TuiEvent::Mouse(_) => {}
+                TuiEvent::Draw => {
+                    tui.draw(u16::MAX, |frame| {
+                        frame.render_widget_ref(&screen, frame.area());
+                    })?;
+                }
+            }
+        } else {
+            break;
+        }
+    }
+
+    Ok(screen.selection().unwrap_or(RecoverySelection::StartFresh))
+}
+
+struct RecoveryPromptScreen {
+    request_frame: FrameRequester,
+    started_at: String,
+    pending_tool_calls: Vec<String>,
+    highlighted: RecoverySelection,
+    selection: Option<RecoverySelection>,
+}
+
+impl RecoveryPromptScreen {
+    fn new(request_frame: FrameRequester, interrupted: &InFlightTurn) -> Self {
+        Self {
+            request_frame,
+            started_at: interrupted.started_at.clone(),
+            pending_tool_calls: interrupted
+                .pending_tool_calls
+                .iter()
+                .map(|call| call.description.clone())
+                .collect(),
+            highlighted: RecoverySelection::Resume,
+            selection: None,
+        }
+    }
+
+    fn handle_key(&mut self, key_event: KeyEvent) {
+        if key_event.kind == KeyEventKind::Release {
+            return;
+        }
+        if key_event.modifiers.contains(KeyModifiers::CONTROL)
+            && matches!(key_event.code, KeyCode::Char('c') | KeyCode::Char('d'))
+        {
+            self.select(RecoverySelection::StartFresh);
+            return;
+        }
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') | KeyCode::Down | KeyCode::Char('j') => {
+                self.highlighted = self.highlighted.toggle();
+                self.request_frame.schedule_frame();
+            }
+            KeyCode::Char('1') => self.select(RecoverySelection::Resume),
+            KeyCode::Char('2') => self.select(RecoverySelection::StartFresh),
+            KeyCode::Enter => self.select(self.highlighted),
+            KeyCode::Esc => self.select(RecoverySelection::StartFresh),
+            _ => {}
+        }
+    }
+
+    fn select(&mut self, selection: RecoverySelection) {
+        self.highlighted = selection;
+        self.selection = Some(selection);
+        self.request_frame.schedule_frame();
+    }
+
+    fn is_done(&self) -> bool {
+        self.selection.is_some()
+    }
+
+    fn selection(&self) -> Option<RecoverySelection> {
+        self.selection
+    }
+}
+
+impl WidgetRef for &RecoveryPromptScreen {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        let mut column = ColumnRenderable::new();
+
+        column.push("");
+        column.push(Line::from(vec![
+            "Codex exited unexpectedly ".into(),
+            "during a turn".bold(),
+            " in this directory".into(),
+        ]));
+        column.push("");
+        column.push(
+            Line::from(format!("The turn started at {}.", self.started_at))
+                .dim()
+                .inset(Insets::tlbr(0, 2, 0, 0)),
+        );
+        if !self.pending_tool_calls.is_empty() {
+            column.push(
+                Line::from("These tool calls were still running:")
+                    .dim()
+                    .inset(Insets::tlbr(0, 2, 0, 0)),
+            );
+            for call in &self.pending_tool_calls {
+                column.push(
+                    Line::from(format!("• {call}"))
+                        .dim()
+                        .inset(Insets::tlbr(0, 4, 0, 0)),
+                );
+            }
+        }
+        column.push("");
+        column.push(selection_option_row(
+            0,
+            "Resume the interrupted session".to_string(),
+            self.highlighted == RecoverySelection::Resume,
+        ));
+        column.push(selection_option_row(
+            1,
+            "Start a new session".to_string(),
+            self.highlighted == RecoverySelection::StartFresh,
+        ));
+        column.push("");
+        column.push(
+            Line::from(vec![
+                "Press ".dim(),
+                key_hint::plain(KeyCode::Enter).into(),
+                " to continue".dim(),
+            ])
+            .inset(Insets::tlbr(0, 2, 0, 0)),
+        );
+        column.render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::SessionSource;
+    use codex_core::turn_journal::PendingToolCall;
+    use codex_protocol::ThreadId;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn new_prompt() -> RecoveryPromptScreen {
+        let interrupted = InFlightTurn {
+            thread_id: ThreadId::new(),
+            source: SessionSource::Cli,
+            rollout_path: PathBuf::from("/sessions/rollout.jsonl"),
+            cwd: PathBuf::from("/repo"),
+            turn_id: "turn-1".to_string(),
+            started_at: "2026-01-01T00:00:00.000Z".to_string(),
+            pending_tool_calls: vec![PendingToolCall {
+                call_id: "call-1".to_string(),
+                description: "cargo test".to_string(),
+            }],
+            partial_agent_message: String::new(),
+        };
+        RecoveryPromptScreen::new(FrameRequester::test_dummy(), &interrupted)
+    }
+
+    #[test]
+    fn recovery_prompt_resumes_by_default() {
+        let mut screen = new_prompt();
+        screen.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(screen.selection(), Some(RecoverySelection::Resume));
+    }
+
+    #[test]
+    fn recovery_prompt_escape_starts_fresh() {
+        let mut screen = new_prompt();
+        screen.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(screen.selection(), Some(RecoverySelection::StartFresh));
+    }
+}
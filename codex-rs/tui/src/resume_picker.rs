@@ -87,12 +87,22 @@ struct PageLoadRequest {
 
 type PageLoader = Arc<dyn Fn(PageLoadRequest) + Send + Sync>;
 
+/// Starts a full-text search of session contents for a query.
+type ContentSearcher = Arc<dyn Fn(String) + Send + Sync>;
+
+/// Upper bound on content matches considered for one query.
+const CONTENT_SEARCH_LIMIT: usize = 200;
+
 enum BackgroundEvent {
     PageLoaded {
         request_token: usize,
         search_token: Option<usize>,
         page: std::io::Result<ThreadsPage>,
     },
+    ContentSearchResults {
+        query: String,
+        thread_ids: HashSet<ThreadId>,
+    },
 }
 
 /// Interactive session picker that lists recorded rollout files with simple
@@ -111,6 +121,9 @@ enum BackgroundEvent {
 /// 1. Provider and source filtering at the backend (only interactive CLI sessions
 ///    for the current model provider).
 /// 2. Working-directory filtering at the picker (unless `--all` is passed).
+///
+/// The search box matches session names and previews, and also the full
+/// contents of each session through the history search index.
 pub async fn run_resume_picker(
     tui: &mut Tui,
     config: &Config,
@@ -144,6 +157,33 @@ async fn run_session_picker(
         std::env::current_dir().ok()
     };
 
+    let search_codex_home = config.codex_home.clone();
+    let search_tx = bg_tx.clone();
+    let index_refreshed = Arc::new(tokio::sync::OnceCell::new());
+    let content_searcher: ContentSearcher = Arc::new(move |query: String| {
+        let tx = search_tx.clone();
+        let codex_home = search_codex_home.clone();
+        let index_refreshed = Arc::clone(&index_refreshed);
+        tokio::spawn(async move {
+            index_refreshed
+                .get_or_init(|| async {
+                    if let Err(err) = codex_core::refresh_history_index(&codex_home).await {
+                        tracing::warn!("failed to refresh history index: {err}");
+                    }
+                })
+                .await;
+            let thread_ids =
+                match codex_core::search_history(&codex_home, &query, CONTENT_SEARCH_LIMIT).await {
+                    Ok(hits) => hits.into_iter().map(|hit| hit.thread_id).collect(),
+                    Err(err) => {
+                        tracing::warn!("history search failed: {err}");
+                        HashSet::new()
+                    }
+                };
+            let _ = tx.send(BackgroundEvent::ContentSearchResults { query, thread_ids });
+        });
+    });
+
     let config = config.clone();
     let loader_tx = bg_tx.clone();
     let page_loader: PageLoader = Arc::new(move |request: PageLoadRequest| {
@@ -178,6 +218,7 @@ async fn run_session_picker(
         filter_cwd,
         action,
     );
+    state.content_searcher = Some(content_searcher);
     state.start_initial_load();
     state.request_frame();
 
@@ -265,6 +306,9 @@ struct PickerState {
     action: SessionPickerAction,
     sort_key: ThreadSortKey,
     thread_name_cache: HashMap<ThreadId, Option<String>>,
+    content_searcher: Option<ContentSearcher>,
+    /// Sessions whose contents match the current query.
+    content_matches: HashSet<ThreadId>,
 }
 
 struct PaginationState {
@@ -382,6 +426,8 @@ impl PickerState {
             action,
             sort_key: ThreadSortKey::CreatedAt,
             thread_name_cache: HashMap::new(),
+            content_searcher: None,
+            content_matches: HashSet::new(),
         }
     }
 
@@ -516,6 +562,16 @@ impl PickerState {
                 let completed_token = pending.search_token.or(search_token);
                 self.continue_search_if_token_matches(completed_token);
             }
+            BackgroundEvent::ContentSearchResults { query, thread_ids } => {
+                if query != self.query {
+                    return Ok(());
+                }
+                self.content_matches = thread_ids;
+                self.apply_filter();
+                if self.filtered_rows.is_empty() && !self.search_state.is_active() {
+                    self.start_search();
+                }
+            }
         }
         Ok(())
     }
@@ -602,7 +658,14 @@ impl PickerState {
             self.filtered_rows = base_iter.cloned().collect();
         } else {
             let q = self.query.to_lowercase();
-            self.filtered_rows = base_iter.filter(|r| r.matches_query(&q)).cloned().collect();
+            self.filtered_rows = base_iter
+                .filter(|r| {
+                    r.matches_query(&q)
+                        || r.thread_id
+                            .is_some_and(|id| self.content_matches.contains(&id))
+                })
+                .cloned()
+                .collect();
         }
         if self.selected >= self.filtered_rows.len() {
             self.selected = self.filtered_rows.len().saturating_sub(1);
@@ -633,11 +696,20 @@ impl PickerState {
         }
         self.query = new_query;
         self.selected = 0;
+        self.content_matches.clear();
         self.apply_filter();
         if self.query.is_empty() {
             self.search_state = SearchState::Idle;
             return;
         }
+        if let Some(content_searcher) = self.content_searcher.as_ref() {
+            content_searcher(self.query.clone());
+        }
+        self.start_search();
+    }
+
+    /// Pages through more sessions until one matches the query.
+    fn start_search(&mut self) {
         if !self.filtered_rows.is_empty() {
             self.search_state = SearchState::Idle;
             return;
@@ -1338,6 +1410,7 @@ mod tests {
             cli_version: None,
             created_at: Some(ts.to_string()),
             updated_at: Some(ts.to_string()),
+            tokens_used: None,
         }
     }
 
@@ -1473,6 +1546,7 @@ mod tests {
             cli_version: None,
             created_at: Some("2025-01-01T00:00:00Z".into()),
             updated_at: Some("2025-01-01T00:00:00Z".into()),
+            tokens_used: None,
         };
         let row = head_to_row(&item);
         assert_eq!(row.preview, "real question");
@@ -1494,6 +1568,7 @@ mod tests {
             cli_version: None,
             created_at: Some("2025-01-01T00:00:00Z".into()),
             updated_at: Some("2025-01-01T00:00:00Z".into()),
+            tokens_used: None,
         };
         let b = ThreadItem {
             path: PathBuf::from("/tmp/b.jsonl"),
@@ -1508,6 +1583,7 @@ mod tests {
             cli_version: None,
             created_at: Some("2025-01-02T00:00:00Z".into()),
             updated_at: Some("2025-01-02T00:00:00Z".into()),
+            tokens_used: None,
         };
         let rows = rows_from_items(vec![a, b]);
         assert_eq!(rows.len(), 2);
@@ -1531,6 +1607,7 @@ mod tests {
             cli_version: None,
             created_at: Some("2025-01-01T00:00:00Z".into()),
             updated_at: Some("2025-01-01T01:00:00Z".into()),
+            tokens_used: None,
         };
 
         let row = head_to_row(&item);
@@ -2176,6 +2253,58 @@ mod tests {
         assert_eq!(state.selected, state.filtered_rows.len().saturating_sub(2));
     }
 
+    #[tokio::test]
+    async fn content_search_results_match_rows_by_thread_id() {
+        let searched: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+        let search_sink = searched.clone();
+        let loader: PageLoader = Arc::new(|_req: PageLoadRequest| {});
+        let mut state = PickerState::new(
+            PathBuf::from("/tmp"),
+            FrameRequester::test_dummy(),
+            loader,
+            String::from("openai"),
+            true,
+            None,
+            SessionPickerAction::Resume,
+        );
+        state.content_searcher = Some(Arc::new(move |query: String| {
+            search_sink.lock().unwrap().push(query);
+        }));
+        let thread_id = ThreadId::new();
+        let mut item = make_item("/tmp/a.jsonl", "2025-01-01T00:00:00Z", "alpha");
+        item.thread_id = Some(thread_id);
+        state.reset_pagination();
+        state.ingest_page(page(vec![item], None, 1, false));
+
+        state.set_query("lexer".to_string());
+        assert_eq!(*searched.lock().unwrap(), vec!["lexer".to_string()]);
+        assert!(state.filtered_rows.is_empty());
+
+        // Results for a query the user has since changed are dropped.
+        state
+            .handle_background_event(BackgroundEvent::ContentSearchResults {
+                query: "lex".to_string(),
+                thread_ids: HashSet::from([thread_id]),
+            })
+            .await
+            .unwrap();
+        assert!(state.filtered_rows.is_empty());
+
+        state
+            .handle_background_event(BackgroundEvent::ContentSearchResults {
+                query: "lexer".to_string(),
+                thread_ids: HashSet::from([thread_id]),
+            })
+            .await
+            .unwrap();
+        let matched: Vec<_> = state
+            .filtered_rows
+            .iter()
+            .map(|row| row.thread_id)
+            .collect();
+        assert_eq!(matched, vec![Some(thread_id)]);
+    }
+
     #[tokio::test]
     async fn set_query_loads_until_match_and_respects_scan_cap() {
         let recorded_requests: Arc<Mutex<Vec<PageLoadRequest>>> = Arc::new(Mutex::new(Vec::new()));
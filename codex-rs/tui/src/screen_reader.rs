@@ -0,0 +1,145 @@
+//! Output rewriting for screen-reader mode (`tui.screen_reader` or `--screen-reader`).
+//!
+//! Screen readers read the terminal's text, so glyphs that only make sense visually get in the
+//! way: box-drawing borders and tree connectors are read out one character at a time, and a
+//! green or red bullet sounds the same either way. `Tui` passes history lines through
+//! [`plain_lines`] before they reach the scrollback and each frame through [`plain_buffer`].
+//! Spinners and animations are turned off through the config instead, and `ChatWidget`
+//! announces state changes as plain history lines.
+
+use ratatui::buffer::Buffer;
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+/// Rewrites history lines so that every indicator has a textual equivalent and no box drawing
+/// is left.
+pub(crate) fn plain_lines(lines: Vec<Line<'static>>) -> Vec<Line<'static>> {
+    lines.into_iter().map(plain_line).collect()
+}
+
+/// Blanks box-drawing and block characters in a rendered frame.
+pub(crate) fn plain_buffer(buf: &mut Buffer) {
+    for cell in buf.content.iter_mut() {
+        let mut chars = cell.symbol().chars();
+        if let (Some(ch), None) = (chars.next(), chars.next())
+            && is_drawing_char(ch)
+        {
+            cell.set_symbol(" ");
+        }
+    }
+}
+
+fn plain_line(line: Line<'static>) -> Line<'static> {
+    let Line {
+        style,
+        alignment,
+        spans,
+    } = line;
+    let spans = spans
+        .iter()
+        .enumerate()
+        .map(|(idx, span)| {
+            let content = match indicator_text(span, spans.get(idx + 1)) {
+                Some(text) => text,
+                None => span.content.chars().map(plain_char).collect(),
+            };
+            Span::styled(content, span.style)
+        })
+        .collect();
+    Line {
+        style,
+        alignment,
+        spans,
+    }
+}
+
+/// Words for status glyphs whose meaning is carried by their color or shape. An unstyled glyph
+/// takes its meaning from the style of the text after it, as plan checklist boxes do.
+fn indicator_text(span: &Span<'_>, next: Option<&Span<'_>>) -> Option<String> {
+    let glyph = span.content.trim();
+    let trailing = span.content.len() > span.content.trim_end().len();
+    let style = match next {
+        Some(next) if span.style == Style::default() => next.style,
+        _ => span.style,
+    };
+    let text = match (glyph, style.fg) {
+        ("•", Some(Color::Green)) => "[ok]",
+        ("•", Some(Color::Red)) => "[failed]",
+        ("✓" | "✔", _) if style.add_modifier.contains(Modifier::CROSSED_OUT) => "[done]",
+        ("✓" | "✔", _) => "[ok]",
+        ("✗" | "✘", _) => "[failed]",
+        ("□", Some(Color::Cyan)) => "[in progress]",
+        ("□", _) => "[pending]",
+        ("⚠", _) => "[warning]",
+        _ => return None,
+    };
+    // Keep the space that separated the glyph from the text after it.
+    Some(if trailing {
+        format!("{text} ")
+    } else {
+        text.to_string()
+    })
+}
+
+fn plain_char(ch: char) -> char {
+    if is_drawing_char(ch) { ' ' } else { ch }
+}
+
+/// Box drawing (`─│└╭…`) and block elements (`▌▀█…`).
+fn is_drawing_char(ch: char) -> bool {
+    matches!(ch, '\u{2500}'..='\u{259F}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use ratatui::layout::Rect;
+    use ratatui::style::Stylize;
+
+    fn text(line: &Line<'_>) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn colored_indicators_get_words() {
+        let lines = plain_lines(vec![
+            Line::from(vec!["•".green().bold(), " Ran cargo test".into()]),
+            Line::from(vec!["•".red().bold(), " Ran cargo build".into()]),
+            Line::from(vec!["•".dim(), " Explored".into()]),
+            Line::from(vec!["□ ".into(), "Write the parser".cyan().bold()]),
+            Line::from(vec!["□ ".into(), "Add tests".dim()]),
+            Line::from(vec!["✔ ".into(), "Read the spec".crossed_out().dim()]),
+        ]);
+
+        assert_eq!(
+            lines.iter().map(text).collect::<Vec<_>>(),
+            vec![
+                "[ok] Ran cargo test",
+                "[failed] Ran cargo build",
+                "• Explored",
+                "[in progress] Write the parser",
+                "[pending] Add tests",
+                "[done] Read the spec",
+            ]
+        );
+    }
+
+    #[test]
+    fn box_drawing_is_blanked() {
+        let lines = plain_lines(vec![Line::from(vec!["  └ ".dim(), "src/lib.rs".into()])]);
+        assert_eq!(text(&lines[0]), "    src/lib.rs");
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 4, 1));
+        buf.set_string(0, 0, "╭─a│", Default::default());
+        plain_buffer(&mut buf);
+        let row: String = buf.content.iter().map(|cell| cell.symbol()).collect();
+        assert_eq!(row, "  a ");
+    }
+}
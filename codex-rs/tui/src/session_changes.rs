@@ -0,0 +1,202 @@
+//! Files changed during the session, for the changed-files panel and `/changes`.
+//!
+//! Core sends a `TurnDiff` after every patch it applies, holding the cumulative diff of the running
+//! turn since it started. A later diff of the same turn therefore replaces the earlier one, while
+//! diffs of different turns are kept side by side and added up per file.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::diff_render::calculate_add_remove_from_diff;
+
+/// A file touched this session with its line counts summed over all turns.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ChangedFile {
+    pub(crate) path: PathBuf,
+    pub(crate) added: usize,
+    pub(crate) removed: usize,
+}
+
+#[derive(Debug)]
+struct TurnChanges {
+    /// 1-based number of the turn within this session.
+    turn: u32,
+    /// Per-file sections of the turn's latest diff, in diff order.
+    files: Vec<(PathBuf, String)>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct SessionChanges {
+    turns: Vec<TurnChanges>,
+    current_turn: u32,
+}
+
+impl SessionChanges {
+    /// Called when a turn starts; later diffs belong to the new turn.
+    pub(crate) fn start_turn(&mut self) {
+        self.current_turn += 1;
+    }
+
+    /// Records the running turn's cumulative diff. Returns whether anything changed.
+    pub(crate) fn record_turn_diff(&mut self, unified_diff: &str) -> bool {
+        let files = split_file_diffs(unified_diff);
+        let turn = self.current_turn;
+        match self.turns.last_mut() {
+            Some(last) if last.turn == turn => {
+                if last.files == files {
+                    return false;
+                }
+                last.files = files;
+            }
+            _ if files.is_empty() => return false,
+            _ => self.turns.push(TurnChanges { turn, files }),
+        }
+        true
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.turns.iter().all(|turn| turn.files.is_empty())
+    }
+
+    /// Changed files in the order they were first touched.
+    pub(crate) fn files(&self) -> Vec<ChangedFile> {
+        let mut files: Vec<ChangedFile> = Vec::new();
+        for (path, diff) in self.turns.iter().flat_map(|turn| &turn.files) {
+            let (added, removed) = calculate_add_remove_from_diff(diff);
+            match files.iter_mut().find(|file| &file.path == path) {
+                Some(file) => {
+                    file.added += added;
+                    file.removed += removed;
+                }
+                None => files.push(ChangedFile {
+                    path: path.clone(),
+                    added,
+                    removed,
+                }),
+            }
+        }
+        files
+    }
+
+    /// The diff of `path` in each turn that touched it, as `(turn, unified_diff)`, oldest first.
+    pub(crate) fn file_diffs(&self, path: &Path) -> Vec<(u32, String)> {
+        self.turns
+            .iter()
+            .flat_map(|turn| {
+                turn.files
+                    .iter()
+                    .filter(|(file, _)| file.as_path() == path)
+                    .map(|(_, diff)| (turn.turn, diff.clone()))
+            })
+            .collect()
+    }
+}
+
+/// Splits a `git diff`-style patch into one section per file, keyed by the file's new path (or
+/// its old path when it was deleted).
+fn split_file_diffs(unified_diff: &str) -> Vec<(PathBuf, String)> {
+    let mut sections: Vec<String> = Vec::new();
+    for line in unified_diff.split_inclusive('\n') {
+        match sections.last_mut() {
+            Some(section) if !line.starts_with("diff --git ") => section.push_str(line),
+            _ if line.starts_with("diff --git ") => sections.push(line.to_string()),
+            _ => {}
+        }
+    }
+    sections
+        .into_iter()
+        .filter_map(|section| Some((section_path(&section)?, section)))
+        .collect()
+}
+
+fn section_path(section: &str) -> Option<PathBuf> {
+    let header_path = |prefix: &str| {
+        section
+            .lines()
+            .find_map(|line| line.strip_prefix(prefix))
+            .map(str::trim_end)
+            .filter(|path| *path != "/dev/null")
+    };
+    let path = header_path("+++ b/")
+        .or_else(|| header_path("--- a/"))
+        .or_else(|| {
+            // Diffs without hunks (mode changes, binary files) only have the `diff --git` line.
+            let header = section.lines().next()?;
+            let (_, right) = header.rsplit_once(" b/")?;
+            Some(right)
+        })?;
+    Some(PathBuf::from(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn file_diff(path: &str, old: &str, new: &str) -> String {
+        format!(
+            "diff --git a/{path} b/{path}\nindex 1111111..2222222 100644\n--- a/{path}\n+++ b/{path}\n@@ -1 +1 @@\n-{old}\n+{new}\n"
+        )
+    }
+
+    fn changed(path: &str, added: usize, removed: usize) -> ChangedFile {
+        ChangedFile {
+            path: PathBuf::from(path),
+            added,
+            removed,
+        }
+    }
+
+    #[test]
+    fn later_diffs_of_a_turn_replace_earlier_ones() {
+        let mut changes = SessionChanges::default();
+        changes.start_turn();
+        assert!(changes.record_turn_diff(&file_diff("src/lib.rs", "a", "b")));
+        let both = file_diff("src/lib.rs", "a", "c")
+            + "diff --git a/new.txt b/new.txt\nnew file mode 100644\nindex 0000000..3333333\n--- /dev/null\n+++ b/new.txt\n@@ -0,0 +1,2 @@\n+one\n+two\n";
+        assert!(changes.record_turn_diff(&both));
+        assert!(!changes.record_turn_diff(&both));
+
+        assert_eq!(
+            changes.files(),
+            vec![changed("src/lib.rs", 1, 1), changed("new.txt", 2, 0)]
+        );
+    }
+
+    #[test]
+    fn turns_add_up_per_file() {
+        let mut changes = SessionChanges::default();
+        changes.start_turn();
+        changes.record_turn_diff(&file_diff("src/lib.rs", "a", "b"));
+        changes.start_turn();
+        assert!(!changes.record_turn_diff(""));
+        changes.start_turn();
+        changes.record_turn_diff(&format!(
+            "{}{}",
+            file_diff("src/main.rs", "x", "y"),
+            file_diff("src/lib.rs", "b", "c")
+        ));
+
+        assert_eq!(
+            changes.files(),
+            vec![changed("src/lib.rs", 2, 2), changed("src/main.rs", 1, 1)]
+        );
+        let turns: Vec<u32> = changes
+            .file_diffs(Path::new("src/lib.rs"))
+            .into_iter()
+            .map(|(turn, _)| turn)
+            .collect();
+        assert_eq!(turns, vec![1, 3]);
+    }
+
+    #[test]
+    fn deleted_files_use_their_old_path() {
+        let mut changes = SessionChanges::default();
+        changes.start_turn();
+        changes.record_turn_diff(
+            "diff --git a/gone.txt b/gone.txt\ndeleted file mode 100644\nindex 4444444..0000000\n--- a/gone.txt\n+++ /dev/null\n@@ -1 +0,0 @@\n-x\n",
+        );
+
+        assert_eq!(changes.files(), vec![changed("gone.txt", 0, 1)]);
+    }
+}
@@ -19,6 +19,7 @@ pub enum SlashCommand {
     ElevateSandbox,
     #[strum(serialize = "sandbox-add-read-dir")]
     SandboxReadRoot,
+    SandboxExplain,
     Experimental,
     Skills,
     Review,
@@ -31,11 +32,16 @@ pub enum SlashCommand {
     Plan,
     Collab,
     Agent,
-    // Undo,
+    Undo,
     Diff,
+    Changes,
+    Commit,
+    Pr,
     Mention,
+    Context,
     Status,
     DebugConfig,
+    DebugFrames,
     Statusline,
     Mcp,
     Apps,
@@ -46,7 +52,11 @@ pub enum SlashCommand {
     Rollout,
     Ps,
     Clean,
+    Queue,
+    Bookmark,
+    Bookmarks,
     Personality,
+    Verbosity,
     TestApproval,
     // Debugging commands.
     #[strum(serialize = "debug-m-drop")]
@@ -67,20 +77,29 @@ impl SlashCommand {
             SlashCommand::Rename => "rename the current thread",
             SlashCommand::Resume => "resume a saved chat",
             SlashCommand::Fork => "fork the current chat",
-            // SlashCommand::Undo => "ask Codex to undo a turn",
+            SlashCommand::Undo => "restore files changed by the last patch, or the files you name",
             SlashCommand::Quit | SlashCommand::Exit => "exit Codex",
             SlashCommand::Diff => "show git diff (including untracked files)",
+            SlashCommand::Changes => "list files changed this session and open their diffs",
+            SlashCommand::Commit => "commit the files Codex changed in this session",
+            SlashCommand::Pr => "push this branch and open a pull request",
             SlashCommand::Mention => "mention a file",
+            SlashCommand::Context => "add a context pack from config.toml: /context <name>",
             SlashCommand::Skills => "use skills to improve how Codex performs specific tasks",
             SlashCommand::Status => "show current session configuration and token usage",
             SlashCommand::DebugConfig => "show config layers and requirement sources for debugging",
+            SlashCommand::DebugFrames => "toggle a frame-time overlay for debugging rendering",
             SlashCommand::Statusline => "configure which items appear in the status line",
             SlashCommand::Ps => "list background terminals",
             SlashCommand::Clean => "stop all background terminals",
+            SlashCommand::Queue => "view, reorder, edit, or delete queued messages",
+            SlashCommand::Bookmark => "bookmark the latest turn, with an optional note",
+            SlashCommand::Bookmarks => "jump to a bookmarked turn or cell in the transcript",
             SlashCommand::MemoryDrop => "DO NOT USE",
             SlashCommand::MemoryUpdate => "DO NOT USE",
             SlashCommand::Model => "choose what model and reasoning effort to use",
             SlashCommand::Personality => "choose a communication style for Codex",
+            SlashCommand::Verbosity => "choose how detailed the model's answers are",
             SlashCommand::Plan => "switch to Plan mode",
             SlashCommand::Collab => "change collaboration mode (experimental)",
             SlashCommand::Agent => "switch the active agent thread",
@@ -90,8 +109,11 @@ impl SlashCommand {
             SlashCommand::SandboxReadRoot => {
                 "let sandbox read a directory: /sandbox-add-read-dir <absolute_path>"
             }
+            SlashCommand::SandboxExplain => {
+                "show what the sandbox allows for a command: /sandbox-explain <command>"
+            }
             SlashCommand::Experimental => "toggle experimental features",
-            SlashCommand::Mcp => "list configured MCP tools",
+            SlashCommand::Mcp => "list configured MCP tools; /mcp status shows server startup",
             SlashCommand::Apps => "manage apps",
             SlashCommand::Logout => "log out of Codex",
             SlashCommand::Rollout => "print the rollout file path",
@@ -111,8 +133,14 @@ impl SlashCommand {
             self,
             SlashCommand::Review
                 | SlashCommand::Rename
+                | SlashCommand::Bookmark
                 | SlashCommand::Plan
+                | SlashCommand::Undo
+                | SlashCommand::Commit
+                | SlashCommand::Context
+                | SlashCommand::Mcp
                 | SlashCommand::SandboxReadRoot
+                | SlashCommand::SandboxExplain
         )
     }
 
@@ -124,9 +152,11 @@ impl SlashCommand {
             | SlashCommand::Fork
             | SlashCommand::Init
             | SlashCommand::Compact
-            // | SlashCommand::Undo
+            | SlashCommand::Commit
+            | SlashCommand::Pr
             | SlashCommand::Model
             | SlashCommand::Personality
+            | SlashCommand::Verbosity
             | SlashCommand::Approvals
             | SlashCommand::Permissions
             | SlashCommand::ElevateSandbox
@@ -138,13 +168,20 @@ impl SlashCommand {
             | SlashCommand::MemoryDrop
             | SlashCommand::MemoryUpdate => false,
             SlashCommand::Diff
+            | SlashCommand::Changes
             | SlashCommand::Rename
             | SlashCommand::Mention
+            | SlashCommand::Context
             | SlashCommand::Skills
             | SlashCommand::Status
             | SlashCommand::DebugConfig
+            | SlashCommand::DebugFrames
+            | SlashCommand::SandboxExplain
             | SlashCommand::Ps
             | SlashCommand::Clean
+            | SlashCommand::Queue
+            | SlashCommand::Bookmark
+            | SlashCommand::Bookmarks
             | SlashCommand::Mcp
             | SlashCommand::Apps
             | SlashCommand::Feedback
@@ -154,6 +154,10 @@ pub(crate) mod announcement {
     }
 
     fn blocking_init_announcement_tip() -> Option<String> {
+        // This client skips the default client's offline backstop.
+        if codex_core::default_client::is_default_client_offline() {
+            return None;
+        }
         // Avoid system proxy detection to prevent macOS system-configuration panics (#8912).
         let client = reqwest::blocking::Client::builder()
             .no_proxy()
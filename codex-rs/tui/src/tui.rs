@@ -16,10 +16,13 @@ use crossterm::Command;
 use crossterm::SynchronizedUpdate;
 use crossterm::event::DisableBracketedPaste;
 use crossterm::event::DisableFocusChange;
+use crossterm::event::DisableMouseCapture;
 use crossterm::event::EnableBracketedPaste;
 use crossterm::event::EnableFocusChange;
+use crossterm::event::EnableMouseCapture;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyboardEnhancementFlags;
+use crossterm::event::MouseEvent;
 use crossterm::event::PopKeyboardEnhancementFlags;
 use crossterm::event::PushKeyboardEnhancementFlags;
 use crossterm::terminal::EnterAlternateScreen;
@@ -37,6 +40,7 @@ use tokio_stream::Stream;
 pub use self::frame_requester::FrameRequester;
 use crate::custom_terminal;
 use crate::custom_terminal::Terminal as CustomTerminal;
+use crate::hyperlink::Hyperlinks;
 use crate::notifications::DesktopNotificationBackend;
 use crate::notifications::detect_backend;
 use crate::tui::event_stream::EventBroker;
@@ -127,6 +131,8 @@ fn restore_common(should_disable_raw_mode: bool) -> Result<()> {
     let _ = execute!(stdout(), PopKeyboardEnhancementFlags);
     execute!(stdout(), DisableBracketedPaste)?;
     let _ = execute!(stdout(), DisableFocusChange);
+    // Mouse capture is only on in the alternate screen; make sure it never outlives the TUI.
+    let _ = execute!(stdout(), DisableMouseCapture);
     if should_disable_raw_mode {
         disable_raw_mode()?;
     }
@@ -233,6 +239,8 @@ fn set_panic_hook() {
 pub enum TuiEvent {
     Key(KeyEvent),
     Paste(String),
+    /// Only delivered while mouse capture is on, i.e. in an overlay with `tui.mouse` set.
+    Mouse(MouseEvent),
     Draw,
 }
 
@@ -253,6 +261,12 @@ pub struct Tui {
     notification_backend: Option<DesktopNotificationBackend>,
     // When false, enter_alt_screen() becomes a no-op (for Zellij scrollback support)
     alt_screen_enabled: bool,
+    // When true, output is rewritten for screen readers (see `screen_reader`)
+    screen_reader: bool,
+    // When true, the mouse is captured while the alternate screen is active (`tui.mouse`)
+    mouse_capture: bool,
+    // When set, URLs and file references in history get OSC 8 hyperlinks (`tui.hyperlinks`)
+    hyperlinks: Option<Hyperlinks>,
 }
 
 impl Tui {
@@ -281,6 +295,9 @@ impl Tui {
             enhanced_keys_supported,
             notification_backend: Some(detect_backend(NotificationMethod::default())),
             alt_screen_enabled: true,
+            screen_reader: false,
+            mouse_capture: false,
+            hyperlinks: None,
         }
     }
 
@@ -289,6 +306,21 @@ impl Tui {
         self.alt_screen_enabled = enabled;
     }
 
+    /// Set whether history lines and frames are rewritten for screen readers.
+    pub fn set_screen_reader(&mut self, enabled: bool) {
+        self.screen_reader = enabled;
+    }
+
+    /// Set whether the mouse is captured while the alternate screen is active.
+    pub fn set_mouse_capture(&mut self, enabled: bool) {
+        self.mouse_capture = enabled;
+    }
+
+    /// Set how history lines are hyperlinked; `None` writes them without links.
+    pub(crate) fn set_hyperlinks(&mut self, hyperlinks: Option<Hyperlinks>) {
+        self.hyperlinks = hyperlinks;
+    }
+
     pub fn set_notification_method(&mut self, method: NotificationMethod) {
         self.notification_backend = Some(detect_backend(method));
     }
@@ -409,6 +441,9 @@ impl Tui {
         let _ = execute!(self.terminal.backend_mut(), EnterAlternateScreen);
         // Enable "alternate scroll" so terminals may translate wheel to arrows
         let _ = execute!(self.terminal.backend_mut(), EnableAlternateScroll);
+        if self.mouse_capture {
+            let _ = execute!(self.terminal.backend_mut(), EnableMouseCapture);
+        }
         if let Ok(size) = self.terminal.size() {
             self.alt_saved_viewport = Some(self.terminal.viewport_area);
             self.terminal.set_viewport_area(ratatui::layout::Rect::new(
@@ -428,6 +463,9 @@ impl Tui {
         if !self.alt_screen_enabled {
             return Ok(());
         }
+        if self.mouse_capture {
+            let _ = execute!(self.terminal.backend_mut(), DisableMouseCapture);
+        }
         // Disable alternate scroll when leaving alt-screen
         let _ = execute!(self.terminal.backend_mut(), DisableAlternateScroll);
         let _ = execute!(self.terminal.backend_mut(), LeaveAlternateScreen);
@@ -439,7 +477,12 @@ impl Tui {
     }
 
     pub fn insert_history_lines(&mut self, lines: Vec<Line<'static>>) {
-        self.pending_history_lines.extend(lines);
+        if self.screen_reader {
+            self.pending_history_lines
+                .extend(crate::screen_reader::plain_lines(lines));
+        } else {
+            self.pending_history_lines.extend(lines);
+        }
         self.frame_requester().schedule_frame();
     }
 
@@ -482,9 +525,10 @@ impl Tui {
             }
 
             if !self.pending_history_lines.is_empty() {
-                crate::insert_history::insert_history_lines(
+                crate::insert_history::insert_history_lines_with_hyperlinks(
                     terminal,
                     self.pending_history_lines.clone(),
+                    self.hyperlinks.as_ref(),
                 )?;
                 self.pending_history_lines.clear();
             }
@@ -502,8 +546,12 @@ impl Tui {
                 self.suspend_context.set_cursor_y(inline_area_bottom);
             }
 
+            let screen_reader = self.screen_reader;
             terminal.draw(|frame| {
                 draw_fn(frame);
+                if screen_reader {
+                    crate::screen_reader::plain_buffer(frame.buffer_mut());
+                }
             })
         })?
     }
@@ -1,3 +1,5 @@
+use codex_core::config::Config;
+
 /// Update action the CLI should perform after the TUI exits.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UpdateAction {
@@ -7,6 +9,8 @@ pub enum UpdateAction {
     BunGlobalLatest,
     /// Update via `brew upgrade codex`.
     BrewUpgrade,
+    /// Update via `codex upgrade` from the configured self-hosted release server.
+    SelfUpgrade,
 }
 
 impl UpdateAction {
@@ -16,6 +20,7 @@ impl UpdateAction {
             UpdateAction::NpmGlobalLatest => ("npm", &["install", "-g", "@openai/codex"]),
             UpdateAction::BunGlobalLatest => ("bun", &["install", "-g", "@openai/codex"]),
             UpdateAction::BrewUpgrade => ("brew", &["upgrade", "--cask", "codex"]),
+            UpdateAction::SelfUpgrade => ("codex", &["upgrade"]),
         }
     }
 
@@ -27,8 +32,17 @@ impl UpdateAction {
     }
 }
 
-#[cfg(not(debug_assertions))]
-pub(crate) fn get_update_action() -> Option<UpdateAction> {
+/// Returns how this installation should be updated, if Codex knows how.
+pub fn get_update_action(config: &Config) -> Option<UpdateAction> {
+    if config.updates.release_server.is_some() {
+        Some(UpdateAction::SelfUpgrade)
+    } else {
+        get_package_manager_update_action()
+    }
+}
+
+/// Returns the package manager update for this binary, if it was installed by one.
+pub fn get_package_manager_update_action() -> Option<UpdateAction> {
     let exe = std::env::current_exe().unwrap_or_default();
     let managed_by_npm = std::env::var_os("CODEX_MANAGED_BY_NPM").is_some();
     let managed_by_bun = std::env::var_os("CODEX_MANAGED_BY_BUN").is_some();
@@ -41,7 +55,6 @@ pub(crate) fn get_update_action() -> Option<UpdateAction> {
     )
 }
 
-#[cfg(any(not(debug_assertions), test))]
 fn detect_update_action(
     is_macos: bool,
     current_exe: &std::path::Path,
@@ -6,7 +6,9 @@ use chrono::DateTime;
 use chrono::Duration;
 use chrono::Utc;
 use codex_core::config::Config;
+use codex_core::config::types::UpdatesConfig;
 use codex_core::default_client::create_client;
+use codex_core::self_update;
 use serde::Deserialize;
 use serde::Serialize;
 use std::path::Path;
@@ -20,7 +22,12 @@ pub fn get_upgrade_version(config: &Config) -> Option<String> {
     }
 
     let version_file = version_filepath(config);
-    let info = read_version_info(&version_file).ok();
+    let source = release_source(&config.updates);
+    // A cached version from another release source or channel says nothing
+    // about this one.
+    let info = read_version_info(&version_file)
+        .ok()
+        .filter(|info| info.source == source);
 
     if match &info {
         None => true,
@@ -29,15 +36,17 @@ pub fn get_upgrade_version(config: &Config) -> Option<String> {
         // Refresh the cached latest version in the background so TUI startup
         // isn’t blocked by a network call. The UI reads the previously cached
         // value (if any) for this run; the next run shows the banner if needed.
+        let updates = config.updates.clone();
         tokio::spawn(async move {
-            check_for_update(&version_file)
+            check_for_update(&version_file, &updates)
                 .await
                 .inspect_err(|e| tracing::error!("Failed to update version: {e}"))
         });
     }
 
+    let self_hosted = config.updates.release_server.is_some();
     info.and_then(|info| {
-        if is_newer(&info.latest_version, CODEX_CLI_VERSION).unwrap_or(false) {
+        if update_available(&info.latest_version, CODEX_CLI_VERSION, self_hosted) {
             Some(info.latest_version)
         } else {
             None
@@ -52,6 +61,10 @@ struct VersionInfo {
     last_checked_at: DateTime<Utc>,
     #[serde(default)]
     dismissed_version: Option<String>,
+    /// Release server and channel the version came from, or `None` for the
+    /// public releases.
+    #[serde(default)]
+    source: Option<String>,
 }
 
 const VERSION_FILENAME: &str = "version.json";
@@ -78,29 +91,42 @@ fn read_version_info(version_file: &Path) -> anyhow::Result<VersionInfo> {
     Ok(serde_json::from_str(&contents)?)
 }
 
-async fn check_for_update(version_file: &Path) -> anyhow::Result<()> {
-    let latest_version = match update_action::get_update_action() {
-        Some(UpdateAction::BrewUpgrade) => {
-            let HomebrewCaskInfo { version } = create_client()
-                .get(HOMEBREW_CASK_API_URL)
-                .send()
-                .await?
-                .error_for_status()?
-                .json::<HomebrewCaskInfo>()
-                .await?;
-            version
-        }
-        _ => {
-            let ReleaseInfo {
-                tag_name: latest_tag_name,
-            } = create_client()
-                .get(LATEST_RELEASE_URL)
-                .send()
-                .await?
-                .error_for_status()?
-                .json::<ReleaseInfo>()
-                .await?;
-            extract_version_from_latest_tag(&latest_tag_name)?
+fn release_source(updates: &UpdatesConfig) -> Option<String> {
+    updates
+        .release_server
+        .as_ref()
+        .map(|server| format!("{}#{}", server.endpoint, updates.channel))
+}
+
+async fn check_for_update(version_file: &Path, updates: &UpdatesConfig) -> anyhow::Result<()> {
+    let latest_version = if let Some(server) = &updates.release_server {
+        self_update::fetch_manifest(server, updates.channel)
+            .await?
+            .version
+    } else {
+        match update_action::get_package_manager_update_action() {
+            Some(UpdateAction::BrewUpgrade) => {
+                let HomebrewCaskInfo { version } = create_client()
+                    .get(HOMEBREW_CASK_API_URL)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json::<HomebrewCaskInfo>()
+                    .await?;
+                version
+            }
+            _ => {
+                let ReleaseInfo {
+                    tag_name: latest_tag_name,
+                } = create_client()
+                    .get(LATEST_RELEASE_URL)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json::<ReleaseInfo>()
+                    .await?;
+                extract_version_from_latest_tag(&latest_tag_name)?
+            }
         }
     };
 
@@ -110,6 +136,7 @@ async fn check_for_update(version_file: &Path) -> anyhow::Result<()> {
         latest_version,
         last_checked_at: Utc::now(),
         dismissed_version: prev_info.and_then(|p| p.dismissed_version),
+        source: release_source(updates),
     };
 
     let json_line = format!("{}\n", serde_json::to_string(&info)?);
@@ -127,6 +154,14 @@ fn is_newer(latest: &str, current: &str) -> Option<bool> {
     }
 }
 
+/// Self-hosted channels may publish pre-release versions, such as nightly
+/// builds, that `parse_version` does not handle; they count as updates when
+/// `codex upgrade` would install them without `--allow-downgrade`.
+fn update_available(latest: &str, current: &str, self_hosted: bool) -> bool {
+    is_newer(latest, current)
+        .unwrap_or(self_hosted && self_update::is_newer_release(latest, current))
+}
+
 fn extract_version_from_latest_tag(latest_tag_name: &str) -> anyhow::Result<String> {
     latest_tag_name
         .strip_prefix("rust-v")
@@ -223,6 +258,18 @@ mod tests {
         assert_eq!(is_newer("0.9.9", "1.0.0"), Some(false));
     }
 
+    #[test]
+    fn unparsed_self_hosted_versions_count_as_updates() {
+        assert!(update_available("0.12.0-nightly.20261015", "0.11.0", true));
+        assert!(!update_available(
+            "0.12.0-nightly.20261015",
+            "0.11.0",
+            false
+        ));
+        assert!(!update_available("0.11.0", "0.11.0", true));
+        assert!(!update_available("0.10.0", "0.11.0", true));
+    }
+
     #[test]
     fn whitespace_is_ignored() {
         assert_eq!(parse_version(" 1.2.3 \n"), Some((1, 2, 3)));
@@ -18,6 +18,9 @@ pub fn create_config_summary_entries(config: &Config, model: &str) -> Vec<(&'sta
             summarize_sandbox_policy(config.permissions.sandbox_policy.get()),
         ),
     ];
+    if config.read_only {
+        entries.push(("mode", "read-only".to_string()));
+    }
     if config.model_provider.wire_api == WireApi::Responses {
         let reasoning_effort = config
             .model_reasoning_effort
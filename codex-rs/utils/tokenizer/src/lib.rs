@@ -0,0 +1,152 @@
+//! Token counting with the BPE vocabularies of OpenAI models.
+//!
+//! Vocabularies are embedded in the binary and loaded once per process, the
+//! first time a tokenizer for them is requested.
+
+use std::fmt;
+use std::sync::Arc;
+use std::sync::OnceLock;
+
+use tiktoken_rs::CoreBPE;
+
+/// A BPE vocabulary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EncodingKind {
+    /// GPT-4o, GPT-4.1, GPT-5 and the o-series reasoning models.
+    O200kBase,
+    /// GPT-4 and GPT-3.5.
+    Cl100kBase,
+}
+
+impl EncodingKind {
+    /// The vocabulary `model` uses. Models outside the OpenAI families, such
+    /// as local OSS models, get `o200k_base` as the closest approximation.
+    pub fn for_model(model: &str) -> Self {
+        // Provider-prefixed slugs such as `openai/gpt-4o`.
+        let model = model.rsplit('/').next().unwrap_or(model);
+        let legacy_gpt4 = model.starts_with("gpt-4")
+            && !model.starts_with("gpt-4o")
+            && !model.starts_with("gpt-4.");
+        if legacy_gpt4 || model.starts_with("gpt-3.5") {
+            Self::Cl100kBase
+        } else {
+            Self::O200kBase
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::O200kBase => "o200k_base",
+            Self::Cl100kBase => "cl100k_base",
+        }
+    }
+}
+
+impl fmt::Display for EncodingKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TokenizerError {
+    #[error("failed to load the {encoding} vocabulary: {message}")]
+    Load {
+        encoding: EncodingKind,
+        message: String,
+    },
+}
+
+/// Counts tokens the way the model will. Cloning is cheap: clones share the
+/// loaded vocabulary.
+#[derive(Clone)]
+pub struct Tokenizer {
+    encoding: EncodingKind,
+    bpe: Arc<CoreBPE>,
+}
+
+impl fmt::Debug for Tokenizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tokenizer")
+            .field("encoding", &self.encoding)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Tokenizer {
+    pub fn new(encoding: EncodingKind) -> Result<Self, TokenizerError> {
+        static O200K_BASE: OnceLock<Result<Arc<CoreBPE>, String>> = OnceLock::new();
+        static CL100K_BASE: OnceLock<Result<Arc<CoreBPE>, String>> = OnceLock::new();
+
+        let cell = match encoding {
+            EncodingKind::O200kBase => &O200K_BASE,
+            EncodingKind::Cl100kBase => &CL100K_BASE,
+        };
+        let bpe = cell
+            .get_or_init(|| {
+                let loaded = match encoding {
+                    EncodingKind::O200kBase => tiktoken_rs::o200k_base(),
+                    EncodingKind::Cl100kBase => tiktoken_rs::cl100k_base(),
+                };
+                loaded.map(Arc::new).map_err(|err| err.to_string())
+            })
+            .clone()
+            .map_err(|message| TokenizerError::Load { encoding, message })?;
+        Ok(Self { encoding, bpe })
+    }
+
+    /// The tokenizer for `model`'s vocabulary.
+    pub fn for_model(model: &str) -> Result<Self, TokenizerError> {
+        Self::new(EncodingKind::for_model(model))
+    }
+
+    pub fn encoding(&self) -> EncodingKind {
+        self.encoding
+    }
+
+    /// Token ids for `text`. Special tokens such as `<|endoftext|>` are
+    /// encoded as plain text.
+    pub fn encode(&self, text: &str) -> Vec<u32> {
+        self.bpe.encode_ordinary(text)
+    }
+
+    pub fn count(&self, text: &str) -> usize {
+        if text.is_empty() {
+            return 0;
+        }
+        self.encode(text).len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn picks_vocabulary_by_model() {
+        for model in ["gpt-5.1-codex", "gpt-4o-mini", "gpt-4.1", "o3", "llama3.2"] {
+            assert_eq!(
+                EncodingKind::for_model(model),
+                EncodingKind::O200kBase,
+                "{model}"
+            );
+        }
+        for model in ["gpt-4", "gpt-4-turbo", "gpt-3.5-turbo", "openai/gpt-4"] {
+            assert_eq!(
+                EncodingKind::for_model(model),
+                EncodingKind::Cl100kBase,
+                "{model}"
+            );
+        }
+    }
+
+    #[test]
+    fn counts_tokens() {
+        for encoding in [EncodingKind::O200kBase, EncodingKind::Cl100kBase] {
+            let tokenizer = Tokenizer::new(encoding).expect("vocabulary loads");
+            assert_eq!(tokenizer.count(""), 0);
+            assert_eq!(tokenizer.count("hello world"), 2);
+        }
+    }
+}
@@ -0,0 +1,34 @@
+"""Embed the Codex agent in Python programs.
+
+>>> import asyncio, codex
+>>> async def main():
+...     client = await codex.Codex.create(cwd="/path/to/repo")
+...     async with await client.start_thread() as thread:
+...         turn = await thread.run("Diagnose the test failure")
+...         print(turn.final_response)
+>>> asyncio.run(main())  # doctest: +SKIP
+"""
+
+from ._client import (
+    ApprovalHandler,
+    ApprovalRequest,
+    Codex,
+    CodexError,
+    Decision,
+    Event,
+    Thread,
+    Turn,
+)
+from ._config import flatten_config
+
+__all__ = [
+    "ApprovalHandler",
+    "ApprovalRequest",
+    "Codex",
+    "CodexError",
+    "Decision",
+    "Event",
+    "Thread",
+    "Turn",
+    "flatten_config",
+]
@@ -0,0 +1,243 @@
+"""Codex client, threads, and turns."""
+
+from __future__ import annotations
+
+import inspect
+import json
+from collections.abc import AsyncIterator, Awaitable, Callable, Mapping
+from dataclasses import dataclass, field
+from os import PathLike
+from typing import Any, Literal, Union
+
+from . import _native
+from ._config import flatten_config
+
+CodexError = _native.CodexError
+
+Event = dict[str, Any]
+Decision = Literal["approved", "approved_for_session", "denied", "abort"]
+ApprovalHandler = Callable[
+    ["ApprovalRequest"], Union[Decision, bool, Awaitable[Union[Decision, bool]]]
+]
+
+
+@dataclass(frozen=True)
+class ApprovalRequest:
+    """Something the agent wants to do that the approval policy sends to you.
+
+    ``kind`` is ``"exec"`` for a command, with ``command`` and ``cwd`` set, or ``"patch"`` for
+    file changes, with ``changes`` mapping each path to its change.
+    """
+
+    kind: Literal["exec", "patch"]
+    call_id: str
+    turn_id: str
+    reason: str | None = None
+    command: list[str] | None = None
+    cwd: str | None = None
+    changes: dict[str, Any] | None = None
+    event: Event = field(default_factory=dict, repr=False)
+
+    @classmethod
+    def from_event(cls, event: Event) -> ApprovalRequest | None:
+        """The request carried by ``event``, if it is one."""
+        msg = event.get("msg", {})
+        if msg.get("type") == "exec_approval_request":
+            return cls(
+                kind="exec",
+                call_id=msg["call_id"],
+                turn_id=msg.get("turn_id", ""),
+                reason=msg.get("reason"),
+                command=msg.get("command"),
+                cwd=msg.get("cwd"),
+                event=event,
+            )
+        if msg.get("type") == "apply_patch_approval_request":
+            return cls(
+                kind="patch",
+                call_id=msg["call_id"],
+                turn_id=msg.get("turn_id", ""),
+                reason=msg.get("reason"),
+                changes=msg.get("changes"),
+                event=event,
+            )
+        return None
+
+    def response(self, decision: Decision | bool) -> dict[str, Any]:
+        """The operation that answers this request; ``True`` approves and ``False`` declines."""
+        if isinstance(decision, bool):
+            decision = "approved" if decision else "denied"
+        if self.kind == "exec":
+            return {
+                "type": "exec_approval",
+                "id": self.call_id,
+                "turn_id": self.turn_id,
+                "decision": decision,
+            }
+        return {"type": "patch_approval", "id": self.call_id, "decision": decision}
+
+
+@dataclass(frozen=True)
+class Turn:
+    """A finished turn: the agent's last message and every event of the turn."""
+
+    turn_id: str
+    final_response: str | None
+    events: list[Event]
+
+
+class Codex:
+    """Loaded configuration that starts and resumes threads. Create one with ``Codex.create``."""
+
+    def __init__(self, engine: _native.Engine, on_approval: ApprovalHandler | None) -> None:
+        self._engine = engine
+        self._on_approval = on_approval
+
+    @classmethod
+    async def create(
+        cls,
+        *,
+        cwd: str | PathLike[str] | None = None,
+        codex_home: str | PathLike[str] | None = None,
+        model: str | None = None,
+        approval_policy: str | None = None,
+        sandbox_mode: str | None = None,
+        config: Mapping[str, Any] | None = None,
+        linux_sandbox_exe: str | PathLike[str] | None = None,
+        on_approval: ApprovalHandler | None = None,
+    ) -> Codex:
+        """Loads ``config.toml`` from ``codex_home`` (``~/.codex`` by default).
+
+        ``approval_policy`` and ``sandbox_mode`` take the values ``config.toml`` does, such as
+        ``"on-request"`` and ``"workspace-write"``. ``config`` overrides any other setting;
+        nested mappings become dotted keys, as with ``codex -c``. ``on_approval`` decides the
+        approval requests of every thread unless a turn passes its own; without one, requests
+        are declined. On Linux, pass the path of the ``codex-linux-sandbox`` helper as
+        ``linux_sandbox_exe`` so that commands can be sandboxed.
+        """
+        engine = await _native.Engine.create(
+            codex_home=codex_home,
+            cwd=cwd,
+            model=model,
+            approval_policy=approval_policy,
+            sandbox_mode=sandbox_mode,
+            linux_sandbox_exe=linux_sandbox_exe,
+            config_overrides=flatten_config(config or {}),
+        )
+        return cls(engine, on_approval)
+
+    async def start_thread(self) -> Thread:
+        return Thread(await self._engine.start_conversation(), self._on_approval)
+
+    async def resume_thread(self, rollout_path: str | PathLike[str]) -> Thread:
+        """Continues the session recorded at ``rollout_path``; see ``Thread.rollout_path``."""
+        return Thread(await self._engine.resume_conversation(rollout_path), self._on_approval)
+
+
+class Thread:
+    """One conversation with the agent.
+
+    Each event is delivered once, so drive turns with ``run`` or ``run_streamed``, or read every
+    event with ``events`` and answer requests with ``submit``, but not both at the same time.
+    """
+
+    def __init__(
+        self, conversation: _native.Conversation, on_approval: ApprovalHandler | None
+    ) -> None:
+        self._conversation = conversation
+        self._on_approval = on_approval
+
+    @property
+    def id(self) -> str:
+        return self._conversation.id
+
+    @property
+    def model(self) -> str:
+        return self._conversation.model
+
+    @property
+    def rollout_path(self) -> str | None:
+        """Where the thread is recorded, for ``Codex.resume_thread``."""
+        path = self._conversation.rollout_path
+        return None if path is None else str(path)
+
+    async def run(self, prompt: str, *, on_approval: ApprovalHandler | None = None) -> Turn:
+        """Sends ``prompt`` and waits for the turn to end.
+
+        Raises ``CodexError`` if the turn reports an error or is aborted.
+        """
+        events: list[Event] = []
+        error: str | None = None
+        async for event in self.run_streamed(prompt, on_approval=on_approval):
+            events.append(event)
+            msg = event["msg"]
+            if msg["type"] == "error":
+                error = msg.get("message")
+            elif msg["type"] == "turn_aborted":
+                raise CodexError(f"turn aborted: {msg.get('reason')}")
+            elif msg["type"] == "turn_complete":
+                if error is not None:
+                    raise CodexError(f"turn failed: {error}")
+                return Turn(
+                    turn_id=msg.get("turn_id", ""),
+                    final_response=msg.get("last_agent_message"),
+                    events=events,
+                )
+        raise CodexError("the thread shut down before the turn ended")
+
+    async def run_streamed(
+        self, prompt: str, *, on_approval: ApprovalHandler | None = None
+    ) -> AsyncIterator[Event]:
+        """Sends ``prompt`` and yields the turn's events, ending with ``turn_complete`` or
+        ``turn_aborted``. Approval requests are answered before they are yielded.
+        """
+        handler = on_approval or self._on_approval
+        await self._conversation.send_message(prompt)
+        async for event in self.events():
+            request = ApprovalRequest.from_event(event)
+            if request is not None:
+                await self.submit(request.response(await _decide(handler, request)))
+            yield event
+            if event["msg"]["type"] in ("turn_complete", "turn_aborted"):
+                return
+
+    async def events(self) -> AsyncIterator[Event]:
+        """Yields every event until the thread shuts down."""
+        while True:
+            event = await self.next_event()
+            yield event
+            if event["msg"]["type"] == "shutdown_complete":
+                return
+
+    async def next_event(self) -> Event:
+        return json.loads(await self._conversation.next_event())
+
+    async def submit(self, op: Mapping[str, Any]) -> str:
+        """Sends an operation, such as ``{"type": "interrupt"}``, and returns its submission id."""
+        return await self._conversation.submit(json.dumps(op))
+
+    async def send_message(self, prompt: str) -> str:
+        """Starts a turn without waiting for it; read its events with ``events``."""
+        return await self._conversation.send_message(prompt)
+
+    async def interrupt(self) -> None:
+        await self.submit({"type": "interrupt"})
+
+    async def close(self) -> None:
+        """Shuts the thread down and waits for its history to be written."""
+        await self._conversation.shutdown()
+
+    async def __aenter__(self) -> Thread:
+        return self
+
+    async def __aexit__(self, *exc_info: object) -> None:
+        await self.close()
+
+
+async def _decide(handler: ApprovalHandler | None, request: ApprovalRequest) -> Decision | bool:
+    if handler is None:
+        return "denied"
+    decision = handler(request)
+    if inspect.isawaitable(decision):
+        decision = await decision
+    return decision
@@ -0,0 +1,62 @@
+"""Conversion of a ``config`` mapping into ``-c key=value`` overrides."""
+
+from __future__ import annotations
+
+import json
+import math
+from collections.abc import Mapping
+from typing import Any
+
+
+def flatten_config(config: Mapping[str, Any]) -> list[str]:
+    """Flattens nested tables into dotted keys with TOML values, as ``codex -c`` takes them.
+
+    >>> flatten_config({"model": "o3", "sandbox_workspace_write": {"network_access": True}})
+    ['model="o3"', 'sandbox_workspace_write.network_access=true']
+    """
+    overrides: list[str] = []
+    _flatten(config, "", overrides)
+    return overrides
+
+
+def _flatten(table: Mapping[str, Any], prefix: str, overrides: list[str]) -> None:
+    for key, value in table.items():
+        if not isinstance(key, str) or not key:
+            raise ValueError(f"config keys must be non-empty strings, got {key!r}")
+        path = f"{prefix}{_toml_key(key)}"
+        if isinstance(value, Mapping) and value:
+            _flatten(value, f"{path}.", overrides)
+        else:
+            overrides.append(f"{path}={toml_value(value, path)}")
+
+
+def _toml_key(key: str) -> str:
+    if all(c.isascii() and (c.isalnum() or c in "-_") for c in key):
+        return key
+    return json.dumps(key, ensure_ascii=False)
+
+
+def toml_value(value: Any, path: str = "value") -> str:
+    """Formats ``value`` as a TOML literal."""
+    # bool is a subclass of int, so it has to come first.
+    if isinstance(value, bool):
+        return "true" if value else "false"
+    if isinstance(value, int):
+        return str(value)
+    if isinstance(value, float):
+        if math.isnan(value):
+            return "nan"
+        if math.isinf(value):
+            return "inf" if value > 0 else "-inf"
+        return repr(value)
+    if isinstance(value, str):
+        # JSON string escapes are valid TOML basic string escapes.
+        return json.dumps(value, ensure_ascii=False)
+    if isinstance(value, Mapping):
+        items = ", ".join(
+            f"{_toml_key(k)} = {toml_value(v, f'{path}.{k}')}" for k, v in value.items()
+        )
+        return f"{{{items}}}" if items else "{}"
+    if isinstance(value, (list, tuple)):
+        return "[" + ", ".join(toml_value(v, path) for v in value) + "]"
+    raise TypeError(f"config value at {path} has unsupported type {type(value).__name__}")
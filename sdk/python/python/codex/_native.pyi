@@ -0,0 +1,31 @@
+from os import PathLike
+from pathlib import Path
+
+class CodexError(Exception): ...
+
+class Engine:
+    @staticmethod
+    async def create(
+        *,
+        codex_home: str | PathLike[str] | None = None,
+        cwd: str | PathLike[str] | None = None,
+        model: str | None = None,
+        approval_policy: str | None = None,
+        sandbox_mode: str | None = None,
+        linux_sandbox_exe: str | PathLike[str] | None = None,
+        config_overrides: list[str] = ...,
+    ) -> Engine: ...
+    async def start_conversation(self) -> Conversation: ...
+    async def resume_conversation(self, rollout_path: str | PathLike[str]) -> Conversation: ...
+
+class Conversation:
+    @property
+    def id(self) -> str: ...
+    @property
+    def model(self) -> str: ...
+    @property
+    def rollout_path(self) -> Path | None: ...
+    async def send_message(self, text: str) -> str: ...
+    async def submit(self, op_json: str) -> str: ...
+    async def next_event(self) -> str: ...
+    async def shutdown(self) -> None: ...
@@ -0,0 +1,198 @@
+//! `codex._native`: the engine API as Python awaitables. The `codex` package wraps it; see
+//! `python/codex/__init__.py`.
+//!
+//! Events and operations cross the boundary as JSON strings, in the shapes of
+//! `codex_engine::protocol`, and are decoded on the Python side.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_engine::EngineError;
+use codex_engine::protocol::AskForApproval;
+use codex_engine::protocol::Op;
+use codex_engine::protocol::SandboxMode;
+use pyo3::exceptions::PyException;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3_async_runtimes::tokio::future_into_py;
+use serde_json::Value;
+
+pyo3::create_exception!(
+    codex,
+    CodexError,
+    PyException,
+    "Raised when the engine fails."
+);
+
+fn codex_error(err: EngineError) -> PyErr {
+    CodexError::new_err(err.to_string())
+}
+
+/// Parses a string in the spelling `config.toml` uses, such as `"on-request"`.
+fn parse_setting<T: serde::de::DeserializeOwned>(name: &str, value: String) -> PyResult<T> {
+    serde_json::from_value(Value::String(value))
+        .map_err(|err| PyValueError::new_err(format!("invalid {name}: {err}")))
+}
+
+#[pyclass(module = "codex._native", frozen)]
+struct Engine {
+    inner: Arc<codex_engine::Engine>,
+}
+
+#[pymethods]
+impl Engine {
+    /// Loads the configuration; see `codex.Codex.create` for the arguments.
+    #[staticmethod]
+    #[pyo3(signature = (
+        *,
+        codex_home=None,
+        cwd=None,
+        model=None,
+        approval_policy=None,
+        sandbox_mode=None,
+        linux_sandbox_exe=None,
+        config_overrides=Vec::new(),
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn create(
+        py: Python<'_>,
+        codex_home: Option<PathBuf>,
+        cwd: Option<PathBuf>,
+        model: Option<String>,
+        approval_policy: Option<String>,
+        sandbox_mode: Option<String>,
+        linux_sandbox_exe: Option<PathBuf>,
+        config_overrides: Vec<String>,
+    ) -> PyResult<Bound<'_, PyAny>> {
+        let mut builder = codex_engine::Engine::builder();
+        if let Some(codex_home) = codex_home {
+            builder = builder.codex_home(codex_home);
+        }
+        if let Some(cwd) = cwd {
+            builder = builder.cwd(cwd);
+        }
+        if let Some(model) = model {
+            builder = builder.model(model);
+        }
+        if let Some(approval_policy) = approval_policy {
+            builder = builder.approval_policy(parse_setting::<AskForApproval>(
+                "approval_policy",
+                approval_policy,
+            )?);
+        }
+        if let Some(sandbox_mode) = sandbox_mode {
+            builder =
+                builder.sandbox_mode(parse_setting::<SandboxMode>("sandbox_mode", sandbox_mode)?);
+        }
+        if let Some(linux_sandbox_exe) = linux_sandbox_exe {
+            builder = builder.linux_sandbox_exe(linux_sandbox_exe);
+        }
+        for key_value in config_overrides {
+            builder = builder.config_override(key_value);
+        }
+        future_into_py(py, async move {
+            let engine = builder.build().await.map_err(codex_error)?;
+            Ok(Engine {
+                inner: Arc::new(engine),
+            })
+        })
+    }
+
+    fn start_conversation<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let engine = Arc::clone(&self.inner);
+        future_into_py(py, async move {
+            let conversation = engine.start_conversation().await.map_err(codex_error)?;
+            Ok(Conversation::new(conversation))
+        })
+    }
+
+    fn resume_conversation<'py>(
+        &self,
+        py: Python<'py>,
+        rollout_path: PathBuf,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let engine = Arc::clone(&self.inner);
+        future_into_py(py, async move {
+            let conversation = engine
+                .resume_conversation(rollout_path)
+                .await
+                .map_err(codex_error)?;
+            Ok(Conversation::new(conversation))
+        })
+    }
+}
+
+#[pyclass(module = "codex._native", frozen)]
+struct Conversation {
+    inner: Arc<codex_engine::Conversation>,
+}
+
+impl Conversation {
+    fn new(conversation: codex_engine::Conversation) -> Self {
+        Self {
+            inner: Arc::new(conversation),
+        }
+    }
+}
+
+#[pymethods]
+impl Conversation {
+    #[getter]
+    fn id(&self) -> String {
+        self.inner.id().to_string()
+    }
+
+    #[getter]
+    fn model(&self) -> String {
+        self.inner.model().to_string()
+    }
+
+    #[getter]
+    fn rollout_path(&self) -> Option<PathBuf> {
+        self.inner.rollout_path()
+    }
+
+    /// Starts a turn and resolves to the submission id.
+    fn send_message<'py>(&self, py: Python<'py>, text: String) -> PyResult<Bound<'py, PyAny>> {
+        let conversation = Arc::clone(&self.inner);
+        future_into_py(py, async move {
+            conversation.send_message(text).await.map_err(codex_error)
+        })
+    }
+
+    /// Submits an operation given as JSON and resolves to the submission id.
+    fn submit<'py>(&self, py: Python<'py>, op_json: &str) -> PyResult<Bound<'py, PyAny>> {
+        let op = serde_json::from_str::<Op>(op_json)
+            .map_err(|err| PyValueError::new_err(format!("invalid op: {err}")))?;
+        let conversation = Arc::clone(&self.inner);
+        future_into_py(py, async move {
+            conversation.submit(op).await.map_err(codex_error)
+        })
+    }
+
+    /// Resolves to the next event as JSON.
+    fn next_event<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let conversation = Arc::clone(&self.inner);
+        future_into_py(py, async move {
+            let event = conversation.next_event().await.map_err(codex_error)?;
+            serde_json::to_string(&event)
+                .map_err(|err| CodexError::new_err(format!("failed to serialize event: {err}")))
+        })
+    }
+
+    fn shutdown<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let conversation = Arc::clone(&self.inner);
+        future_into_py(py, async move {
+            conversation.shutdown().await.map_err(codex_error)
+        })
+    }
+}
+
+#[pymodule]
+#[pyo3(name = "_native")]
+fn codex_python(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Engine>()?;
+    m.add_class::<Conversation>()?;
+    m.add("CodexError", m.py().get_type::<CodexError>())?;
+    Ok(())
+}
@@ -0,0 +1,46 @@
+import pytest
+
+from codex import flatten_config
+
+
+def test_nested_tables_become_dotted_keys() -> None:
+    config = {
+        "model": "gpt-5.1-codex",
+        "show_raw_agent_reasoning": True,
+        "sandbox_workspace_write": {
+            "network_access": True,
+            "writable_roots": ["/tmp", "/var/cache"],
+        },
+        "model_providers": {
+            "local.llm": {"base_url": "http://localhost:8080/v1", "stream_max_retries": 2},
+        },
+    }
+    assert flatten_config(config) == [
+        'model="gpt-5.1-codex"',
+        "show_raw_agent_reasoning=true",
+        "sandbox_workspace_write.network_access=true",
+        'sandbox_workspace_write.writable_roots=["/tmp", "/var/cache"]',
+        'model_providers."local.llm".base_url="http://localhost:8080/v1"',
+        'model_providers."local.llm".stream_max_retries=2',
+    ]
+
+
+def test_values_are_toml_literals() -> None:
+    assert flatten_config(
+        {
+            "text": 'say "hi"\n',
+            "ratio": 0.5,
+            "empty": {},
+            "servers": [{"name": "a", "port": 1}],
+        }
+    ) == [
+        'text="say \\"hi\\"\\n"',
+        "ratio=0.5",
+        "empty={}",
+        'servers=[{name = "a", port = 1}]',
+    ]
+
+
+def test_unsupported_values_are_rejected() -> None:
+    with pytest.raises(TypeError, match="notify"):
+        flatten_config({"notify": None})
@@ -0,0 +1,102 @@
+import asyncio
+import json
+from typing import Any
+
+import pytest
+
+from codex import ApprovalRequest, CodexError, Thread
+
+
+class FakeConversation:
+    """Replays scripted events and records what the thread submits."""
+
+    id = "thread-1"
+    model = "gpt-5.1-codex"
+    rollout_path = None
+
+    def __init__(self, events: list[dict[str, Any]]) -> None:
+        self._events = [json.dumps({"id": "1", "msg": msg}) for msg in events]
+        self.messages: list[str] = []
+        self.ops: list[dict[str, Any]] = []
+
+    async def send_message(self, text: str) -> str:
+        self.messages.append(text)
+        return "1"
+
+    async def submit(self, op_json: str) -> str:
+        self.ops.append(json.loads(op_json))
+        return "2"
+
+    async def next_event(self) -> str:
+        return self._events.pop(0)
+
+    async def shutdown(self) -> None:
+        pass
+
+
+def exec_request(call_id: str) -> dict[str, Any]:
+    return {
+        "type": "exec_approval_request",
+        "call_id": call_id,
+        "turn_id": "turn-1",
+        "command": ["cargo", "test"],
+        "cwd": "/repo",
+        "parsed_cmd": [],
+    }
+
+
+def test_run_answers_approvals_and_returns_the_final_message() -> None:
+    conversation = FakeConversation(
+        [
+            {"type": "task_started"},
+            exec_request("call-1"),
+            {"type": "turn_complete", "turn_id": "turn-1", "last_agent_message": "All green."},
+        ]
+    )
+    seen: list[ApprovalRequest] = []
+
+    async def approve_tests(request: ApprovalRequest) -> bool:
+        seen.append(request)
+        return request.command == ["cargo", "test"]
+
+    thread = Thread(conversation, on_approval=None)
+    turn = asyncio.run(thread.run("Run the tests", on_approval=approve_tests))
+
+    assert conversation.messages == ["Run the tests"]
+    assert [request.call_id for request in seen] == ["call-1"]
+    assert conversation.ops == [
+        {"type": "exec_approval", "id": "call-1", "turn_id": "turn-1", "decision": "approved"}
+    ]
+    assert turn.final_response == "All green."
+    assert len(turn.events) == 3
+
+
+def test_requests_without_a_handler_are_declined() -> None:
+    conversation = FakeConversation(
+        [
+            {
+                "type": "apply_patch_approval_request",
+                "call_id": "call-2",
+                "turn_id": "turn-1",
+                "changes": {},
+            },
+            {"type": "turn_complete", "turn_id": "turn-1", "last_agent_message": None},
+        ]
+    )
+    asyncio.run(Thread(conversation, on_approval=None).run("Edit the lockfile"))
+    assert conversation.ops == [{"type": "patch_approval", "id": "call-2", "decision": "denied"}]
+
+
+def test_errors_and_aborts_raise() -> None:
+    failed = FakeConversation(
+        [
+            {"type": "error", "message": "quota exceeded"},
+            {"type": "turn_complete", "turn_id": "turn-1", "last_agent_message": None},
+        ]
+    )
+    with pytest.raises(CodexError, match="quota exceeded"):
+        asyncio.run(Thread(failed, on_approval=None).run("x"))
+
+    aborted = FakeConversation([{"type": "turn_aborted", "reason": "interrupted"}])
+    with pytest.raises(CodexError, match="interrupted"):
+        asyncio.run(Thread(aborted, on_approval=None).run("x"))
@@ -26,18 +26,26 @@ export type PatchChangeKind = "add" | "delete" | "update";
 export type FileUpdateChange = {
   path: string;
   kind: PatchChangeKind;
+  /** Where an updated file is moved to, if anywhere. */
+  move_path?: string;
+  /** The unified diff hunks of the change, each starting with its `@@` header. */
+  hunks?: string[];
+  /** SHA-256 of the file before the patch; absent when it did not exist. */
+  before_sha256?: string;
+  /** SHA-256 of the file after the patch; absent when it is deleted. */
+  after_sha256?: string;
 };
 
-/** The status of a file change. */
-export type PatchApplyStatus = "completed" | "failed";
+/** The status of a file change. `proposed` means the patch was saved for review instead of applied. */
+export type PatchApplyStatus = "in_progress" | "completed" | "failed" | "proposed";
 
-/** A set of file changes by the agent. Emitted once the patch succeeds or fails. */
+/** A set of file changes by the agent. Emitted before the patch is applied and again once it succeeds or fails. */
 export type FileChangeItem = {
   id: string;
   type: "file_change";
   /** Individual file changes that comprise the patch. */
   changes: FileUpdateChange[];
-  /** Whether the patch ultimately succeeded or failed. */
+  /** Whether the patch is still being applied, succeeded, failed, or was only proposed. */
   status: PatchApplyStatus;
 };
 